@@ -1,5 +1,6 @@
 """Service-account API key management endpoints."""
 
+from datetime import UTC, datetime
 from typing import Any
 
 import ugoite_core
@@ -19,6 +20,16 @@ from app.models.payloads import (
 
 router = APIRouter()
 
+DEFAULT_EXPIRY_WINDOW_SECONDS = 7 * 24 * 60 * 60
+
+
+def _expires_at_iso(expires_at: datetime | None) -> str | None:
+    if expires_at is None:
+        return None
+    if expires_at.tzinfo is None:
+        expires_at = expires_at.replace(tzinfo=UTC)
+    return expires_at.isoformat()
+
 
 @router.get("/spaces/{space_id}/service-accounts")
 async def list_service_accounts_endpoint(
@@ -54,6 +65,38 @@ async def list_service_accounts_endpoint(
         ) from exc
 
 
+@router.get("/spaces/{space_id}/service-accounts/expiring-keys")
+async def list_expiring_service_account_keys_endpoint(
+    space_id: str,
+    request: Request,
+    window_seconds: int = DEFAULT_EXPIRY_WINDOW_SECONDS,
+) -> list[dict[str, Any]]:
+    """List active API keys that expire within the window, soonest first."""
+    identity = request_identity(request)
+    _validate_path_id(space_id, "space_id")
+    if window_seconds < 0:
+        raise HTTPException(
+            status_code=status.HTTP_400_BAD_REQUEST,
+            detail="window_seconds must not be negative",
+        )
+    storage_config = _storage_config()
+    await _ensure_space_exists(storage_config, space_id)
+    try:
+        await ugoite_core.require_space_action(
+            storage_config,
+            space_id,
+            identity,
+            "space_admin",
+        )
+        return await ugoite_core.list_expiring_service_account_keys(
+            storage_config,
+            space_id,
+            window_seconds,
+        )
+    except ugoite_core.AuthorizationError as exc:
+        raise_authorization_http_error(exc, space_id=space_id)
+
+
 @router.post("/spaces/{space_id}/service-accounts", status_code=status.HTTP_201_CREATED)
 async def create_service_account_endpoint(
     space_id: str,
@@ -127,6 +170,7 @@ async def create_service_account_key_endpoint(
                 service_account_id=service_account_id,
                 key_name=payload.key_name,
                 created_by_user_id=identity.user_id,
+                expires_at=_expires_at_iso(payload.expires_at),
             ),
         )
     except ugoite_core.AuthorizationError as exc:
@@ -178,6 +222,7 @@ async def rotate_service_account_key_endpoint(
                 key_id=key_id,
                 rotated_by_user_id=identity.user_id,
                 key_name=payload.key_name,
+                expires_at=_expires_at_iso(payload.expires_at),
             ),
         )
     except ugoite_core.AuthorizationError as exc:
@@ -1,5 +1,6 @@
 """Pydantic models for the application."""
 
+from datetime import datetime
 from typing import Annotated, Any, Literal
 
 from pydantic import AliasChoices, BaseModel, ConfigDict, Field, StringConstraints
@@ -182,12 +183,14 @@ class ServiceAccountKeyCreate(BaseModel):
     """Service account key creation payload."""
 
     key_name: ShortText
+    expires_at: datetime | None = None
 
 
 class ServiceAccountKeyRotate(BaseModel):
     """Service account key rotation payload."""
 
     key_name: ShortText | None = None
+    expires_at: datetime | None = None
 
 
 class FormCreate(BaseModel):
@@ -31,6 +31,11 @@ EXPECTED_FORM_COLUMN_TYPES = [
     "timestamp_tz",
     "timestamp_ns",
     "timestamp_tz_ns",
+    "datetime",
+    "duration",
+    "decimal",
+    "geopoint",
+    "sequence",
     "uuid",
     "row_reference",
     "binary",
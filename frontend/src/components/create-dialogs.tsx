@@ -381,6 +381,7 @@ export function CreateEntryDialog(props: CreateEntryDialogProps) {
 		if (name.toLowerCase() === "sql") return "SELECT * FROM entries LIMIT 50";
 		switch (field.type) {
 			case "integer":
+			case "decimal":
 			case "long":
 			case "number":
 			case "double":
@@ -396,6 +397,7 @@ export function CreateEntryDialog(props: CreateEntryDialogProps) {
 			case "timestamp_tz":
 			case "timestamp_ns":
 			case "timestamp_tz_ns":
+			case "datetime":
 				return formatDatetimeLocal(new Date());
 			case "object_list":
 				return "[]";
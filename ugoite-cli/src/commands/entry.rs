@@ -117,6 +117,26 @@ pub enum EntrySubCmd {
         #[arg(long)]
         hard_delete: bool,
     },
+    /// Render an entry body to sanitized HTML
+    #[command(
+        long_about = "Render an entry body (without frontmatter) to sanitized HTML using the same renderer as the backend.\n\nExamples:\n  # Core mode\n  ugoite entry render /root/spaces/my-space my-note\n\n  # Backend mode, with footnotes and math passthrough\n  ugoite entry render my-space my-note --footnotes --math"
+    )]
+    Render {
+        #[arg(
+            value_name = "SPACE_ID_OR_PATH",
+            help = "Space ID in backend/api mode, or /root/spaces/<id> in core mode."
+        )]
+        space_path: String,
+        entry_id: String,
+        #[arg(long, help = "Enable footnotes")]
+        footnotes: bool,
+        #[arg(long, help = "Pass $..$ and $$..$$ through as math spans")]
+        math: bool,
+        #[arg(long, help = "URL template for entry links; {id} is replaced")]
+        entry_url: Option<String>,
+        #[arg(long, help = "URL template for asset links; {id} is replaced")]
+        asset_url: Option<String>,
+    },
     /// Get entry history
     History {
         #[arg(
@@ -302,6 +322,46 @@ pub async fn run(cmd: EntryCmd) -> Result<()> {
             ugoite_core::entry::delete_entry(&op, &ws, &entry_id, hard_delete).await?;
             print_json(&serde_json::json!({"deleted": true}));
         }
+        EntrySubCmd::Render {
+            space_path,
+            entry_id,
+            footnotes,
+            math,
+            entry_url,
+            asset_url,
+        } => {
+            let (root, space_id) = resolve_space_reference(&config, &space_path, "entry render")?;
+            let defaults = ugoite_core::render::RenderOptions::default();
+            let options = ugoite_core::render::RenderOptions {
+                footnotes,
+                math,
+                entry_url: entry_url.unwrap_or(defaults.entry_url.clone()),
+                asset_url: asset_url.unwrap_or(defaults.asset_url.clone()),
+                ..defaults
+            };
+            if let Some(base) = validated_base_url(&config)? {
+                // Render locally so backend and core mode produce identical HTML.
+                let entry =
+                    http::http_get(&format!("{base}/spaces/{space_id}/entries/{entry_id}")).await?;
+                let markdown = entry
+                    .get("content")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default();
+                println!(
+                    "{}",
+                    ugoite_core::render::render_html(
+                        ugoite_core::render::strip_frontmatter(markdown),
+                        &options
+                    )
+                );
+                return Ok(());
+            }
+            let op = operator_for_path(&root)?;
+            let ws = space_ws_path(&root, &space_id);
+            let html =
+                ugoite_core::render::render_entry_html(&op, &ws, &entry_id, &options).await?;
+            println!("{html}");
+        }
         EntrySubCmd::History {
             space_path,
             entry_id,
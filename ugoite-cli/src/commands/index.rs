@@ -3,7 +3,7 @@ use crate::config::{
     validated_base_url,
 };
 use crate::http;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::{Args, Subcommand};
 
 #[derive(Args)]
@@ -36,6 +36,14 @@ pub enum IndexSubCmd {
         )]
         space_path: String,
     },
+    /// Rewrite revision history into snapshot-plus-delta storage
+    #[command(
+        long_about = "Rewrite revision history into snapshot-plus-delta storage.\n\nForms whose entries are under legal hold are left untouched.\nOnly available in core mode.\n\nExamples:\n  ugoite index migrate-revisions /root/spaces/my-space"
+    )]
+    MigrateRevisions {
+        #[arg(value_name = "SPACE_PATH", help = "/root/spaces/<id> in core mode.")]
+        space_path: String,
+    },
 }
 
 pub async fn run(cmd: IndexCmd) -> Result<()> {
@@ -69,6 +77,17 @@ pub async fn run(cmd: IndexCmd) -> Result<()> {
             let stats = ugoite_core::index::get_space_stats(&op, &ws).await?;
             print_json(&stats);
         }
+        IndexSubCmd::MigrateRevisions { space_path } => {
+            if validated_base_url(&config)?.is_some() {
+                bail!("index migrate-revisions is only available in core mode");
+            }
+            let (root, space_id) =
+                resolve_space_reference(&config, &space_path, "index migrate-revisions")?;
+            let op = operator_for_path(&root)?;
+            let ws = space_ws_path(&root, &space_id);
+            let report = ugoite_core::revision_delta::migrate_revision_storage(&op, &ws).await?;
+            print_json(&report);
+        }
     }
     Ok(())
 }
@@ -110,6 +110,12 @@ pub enum SpaceSubCmd {
         entry_count: usize,
         #[arg(long, help = "Deterministic random seed for reproducible sample data")]
         seed: Option<u64>,
+        #[arg(
+            long,
+            requires = "seed",
+            help = "Freeze timestamps and derive ids from the seed so output is byte-stable"
+        )]
+        fixture: bool,
         /// Bootstrap this user ID as the active admin owner of the seeded space.
         /// Defaults to the UGOITE_DEV_USER_ID environment variable when unset.
         #[arg(long)]
@@ -142,6 +148,12 @@ pub enum SpaceSubCmd {
         entry_count: usize,
         #[arg(long, help = "Deterministic random seed for reproducible sample data")]
         seed: Option<u64>,
+        #[arg(
+            long,
+            requires = "seed",
+            help = "Freeze timestamps and derive ids from the seed so output is byte-stable"
+        )]
+        fixture: bool,
         /// Bootstrap this user ID as the active admin owner of the seeded space.
         /// Defaults to the UGOITE_DEV_USER_ID environment variable when unset.
         #[arg(long)]
@@ -360,6 +372,7 @@ pub async fn run(cmd: SpaceCmd) -> Result<()> {
             scenario,
             entry_count,
             seed,
+            fixture,
             owner,
         } => {
             let op = operator_for_path(&root_path)?;
@@ -370,6 +383,7 @@ pub async fn run(cmd: SpaceCmd) -> Result<()> {
                 entry_count,
                 seed,
                 owner_user_id: resolve_sample_owner_user_id(owner),
+                fixture,
             };
             ugoite_core::sample_data::create_sample_space_with_terminal_progress(
                 &op, &root_uri, &opts,
@@ -387,6 +401,7 @@ pub async fn run(cmd: SpaceCmd) -> Result<()> {
             scenario,
             entry_count,
             seed,
+            fixture,
             owner,
         } => {
             let op = operator_for_path(&root_path)?;
@@ -397,6 +412,7 @@ pub async fn run(cmd: SpaceCmd) -> Result<()> {
                 entry_count,
                 seed,
                 owner_user_id: resolve_sample_owner_user_id(owner),
+                fixture,
             };
             let job =
                 ugoite_core::sample_data::create_sample_space_job(&op, &root_uri, &opts).await?;
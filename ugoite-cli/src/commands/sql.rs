@@ -116,6 +116,8 @@ pub async fn run(cmd: SqlCmd) -> Result<()> {
                 name,
                 sql,
                 variables: vars,
+                folder: None,
+                tags: None,
             };
             let result = ugoite_core::saved_sql::create_sql(
                 &op, &ws, &sql_id, &payload, &author, &integrity,
@@ -153,6 +155,8 @@ pub async fn run(cmd: SqlCmd) -> Result<()> {
                 name,
                 sql,
                 variables: vars,
+                folder: None,
+                tags: None,
             };
             let result = ugoite_core::saved_sql::update_sql(
                 &op,
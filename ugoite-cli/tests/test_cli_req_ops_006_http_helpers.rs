@@ -6,13 +6,15 @@ mod support;
 use serde_json::json;
 use std::net::TcpListener;
 use std::path::Path;
-use std::sync::{Mutex, OnceLock};
+use std::sync::OnceLock;
 use std::time::Duration;
 use support::spawn_recording_server;
+use tokio::sync::Mutex;
 use ugoite_cli::http::{
     http_delete, http_get, http_patch, http_post, http_post_with_dev_auth_proxy, http_put,
 };
 
+/// Held across requests so tests mutating process env never overlap.
 fn env_lock() -> &'static Mutex<()> {
     static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
     LOCK.get_or_init(|| Mutex::new(()))
@@ -202,7 +204,7 @@ async fn test_cli_req_ops_006_http_get_rejects_invalid_json_success_body() {
 /// REQ-OPS-006: HTTP helpers must surface transport errors when loopback endpoints are unreachable.
 #[tokio::test]
 async fn test_cli_req_ops_006_http_helpers_surface_unreachable_loopback_transport_errors() {
-    let _guard = env_lock().lock().expect("env lock");
+    let _guard = env_lock().lock().await;
     let _env = EnvState::capture();
     let listener = TcpListener::bind("127.0.0.1:0").expect("bind closed-port probe");
     let addr = listener.local_addr().expect("probe local addr");
@@ -308,7 +310,7 @@ async fn test_cli_req_ops_006_http_post_with_dev_auth_proxy_rejects_invalid_url(
 /// REQ-OPS-006: auth headers must prefer bearer tokens and fall back to API keys.
 #[tokio::test]
 async fn test_cli_req_ops_006_http_helpers_apply_auth_headers() {
-    let _guard = env_lock().lock().expect("env lock");
+    let _guard = env_lock().lock().await;
     let _env = EnvState::capture();
 
     std::env::set_var("UGOITE_AUTH_BEARER_TOKEN", "bearer-secret");
@@ -343,7 +345,7 @@ async fn test_cli_req_ops_006_http_helpers_apply_auth_headers() {
 /// REQ-OPS-006: explicit auth POST helpers must only send dev local-auth headers when configured.
 #[tokio::test]
 async fn test_cli_req_ops_006_http_helpers_apply_dev_auth_proxy_header() {
-    let _guard = env_lock().lock().expect("env lock");
+    let _guard = env_lock().lock().await;
     let _env = EnvState::capture();
     let dir = tempfile::tempdir().expect("tempdir");
     let missing_auth_file = dir.path().join("missing-dev-auth.json");
@@ -452,7 +454,7 @@ async fn test_cli_req_ops_006_http_helpers_apply_dev_auth_proxy_header() {
 /// REQ-OPS-015: loopback auth helpers must ignore malformed cached dev auth files.
 #[tokio::test]
 async fn test_cli_req_ops_015_http_helpers_ignore_malformed_cached_dev_auth_file() {
-    let _guard = env_lock().lock().expect("env lock");
+    let _guard = env_lock().lock().await;
     let _env = EnvState::capture();
     let dir = tempfile::tempdir().expect("tempdir");
     let malformed_auth_file = dir.path().join("malformed-dev-auth.json");
@@ -483,7 +485,7 @@ async fn test_cli_req_ops_015_http_helpers_ignore_malformed_cached_dev_auth_file
 /// REQ-OPS-006: explicit auth POST helpers must surface proxy-auth errors with response text.
 #[tokio::test]
 async fn test_cli_req_ops_006_http_helpers_surface_dev_auth_proxy_error_bodies() {
-    let _guard = env_lock().lock().expect("env lock");
+    let _guard = env_lock().lock().await;
     let _env = EnvState::capture();
 
     std::env::set_var("UGOITE_DEV_AUTH_PROXY_TOKEN", "proxy-secret");
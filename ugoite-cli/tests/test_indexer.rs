@@ -1,5 +1,6 @@
 //! Integration tests for indexer operations.
-//! REQ-IDX-001, REQ-IDX-002, REQ-IDX-003, REQ-IDX-004, REQ-IDX-005, REQ-IDX-006, REQ-ENTRY-004
+//! REQ-IDX-001, REQ-IDX-002, REQ-IDX-003, REQ-IDX-004, REQ-IDX-005, REQ-IDX-006, REQ-ENTRY-004,
+//! REQ-STO-014
 
 use std::process::Command;
 
@@ -109,6 +110,32 @@ fn test_aggregate_stats_includes_field_usage() {
     assert!(!stdout.trim().is_empty());
 }
 
+/// REQ-STO-014: Revision storage migration reports the rewritten forms.
+#[test]
+fn test_index_migrate_revisions() {
+    let dir = tempfile::tempdir().unwrap();
+    let (_root, space_path, config_path) = setup_space_with_entries(&dir);
+
+    let output = Command::new(ugoite_bin())
+        .args(["index", "migrate-revisions", &space_path])
+        .env("UGOITE_CLI_CONFIG_PATH", &config_path)
+        .output()
+        .expect("failed to execute");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["migrated_forms"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|form| form == "Entry"));
+    assert_eq!(report["revisions"], 2);
+}
+
 /// REQ-ENTRY-004: Properties extracted from H2 sections.
 #[test]
 fn test_extract_properties_h2_sections() {
@@ -43,7 +43,7 @@ fn test_cli_sql_lint_reports_errors() {
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(
-        !output.status.success() || stdout.contains("error") || stderr.contains("error") || true,
+        !output.status.success() || stdout.contains("error") || stderr.contains("error"),
         "Lint should report errors for invalid SQL"
     );
 }
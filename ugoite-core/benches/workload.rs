@@ -0,0 +1,38 @@
+//! Mixed read/write/search/SQL workload against a synthetic space.
+//!
+//! `cargo bench -p ugoite-core --bench workload` populates a sample-data space
+//! in a temporary directory and prints the latency report as JSON. Set
+//! `UGOITE_BENCH_URI` to benchmark another backend, and
+//! `UGOITE_BENCH_ENTRIES`, `UGOITE_BENCH_OPERATIONS`,
+//! `UGOITE_BENCH_CONCURRENCY` or `UGOITE_BENCH_SEED` to resize the run.
+
+use _ugoite_core::benchmark::{self, BenchmarkConfig};
+use _ugoite_core::storage;
+
+fn env_usize(name: &str) -> Option<usize> {
+    std::env::var(name).ok()?.trim().parse().ok()
+}
+
+fn main() -> anyhow::Result<()> {
+    let scratch = tempfile::tempdir()?;
+    let uri = std::env::var("UGOITE_BENCH_URI")
+        .unwrap_or_else(|_| format!("fs://{}", scratch.path().display()));
+    let defaults = BenchmarkConfig::new(&format!("bench-{}", uuid::Uuid::new_v4().simple()));
+    let config = BenchmarkConfig {
+        entry_count: env_usize("UGOITE_BENCH_ENTRIES").unwrap_or(defaults.entry_count),
+        operations: env_usize("UGOITE_BENCH_OPERATIONS").unwrap_or(defaults.operations),
+        concurrency: env_usize("UGOITE_BENCH_CONCURRENCY").unwrap_or(defaults.concurrency),
+        seed: env_usize("UGOITE_BENCH_SEED")
+            .map(|seed| seed as u64)
+            .or(Some(42)),
+        ..defaults
+    };
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let report = runtime.block_on(async {
+        let op = storage::operator_from_uri(&uri)?;
+        benchmark::run_benchmark(&op, &uri, &config).await
+    })?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
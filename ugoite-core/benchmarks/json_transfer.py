@@ -0,0 +1,76 @@
+"""Compare dict-building bindings with the JSON-bytes fast paths.
+
+Run after ``maturin develop``:
+
+    uv run python benchmarks/json_transfer.py --entries 2000
+"""
+
+from __future__ import annotations
+
+import argparse
+import asyncio
+import sys
+import tempfile
+import time
+from collections.abc import Awaitable, Callable
+from typing import Any
+
+import ugoite_core
+
+SPACE_ID = "json-transfer-bench"
+
+
+async def _seed(storage: dict[str, Any], entries: int) -> None:
+    await ugoite_core.create_space(storage, SPACE_ID)
+    body = "Lorem ipsum dolor sit amet. " * 40
+    for idx in range(entries):
+        markdown = f"---\nform: Entry\n---\n# Entry {idx}\n\n## Body\n{body}\n"
+        await ugoite_core.create_entry(storage, SPACE_ID, f"entry-{idx}", markdown)
+
+
+async def _time(
+    label: str,
+    call: Callable[[], Awaitable[object]],
+    rounds: int,
+) -> float:
+    best = float("inf")
+    for _ in range(rounds):
+        started = time.perf_counter()
+        await call()
+        best = min(best, time.perf_counter() - started)
+    sys.stdout.write(f"{label:<32} {best * 1000:9.1f} ms\n")
+    return best
+
+
+async def _main(storage: dict[str, Any], entries: int, rounds: int) -> None:
+    await _seed(storage, entries)
+
+    async def as_dicts() -> object:
+        return await ugoite_core.list_entries(storage, SPACE_ID)
+
+    async def as_bytes() -> object:
+        return ugoite_core.JsonPayload(
+            await ugoite_core.list_entries_json(storage, SPACE_ID),
+        )
+
+    async def as_bytes_decoded() -> object:
+        return ugoite_core.JsonPayload(
+            await ugoite_core.list_entries_json(storage, SPACE_ID),
+        ).decode()
+
+    sys.stdout.write(f"list_entries over {entries} entries, best of {rounds}\n")
+    baseline = await _time("list_entries (dicts)", as_dicts, rounds)
+    raw = await _time("list_entries_json (bytes)", as_bytes, rounds)
+    decoded = await _time("list_entries_json + decode", as_bytes_decoded, rounds)
+    sys.stdout.write(
+        f"speedup: {baseline / raw:.2f}x raw, {baseline / decoded:.2f}x decoded\n",
+    )
+
+
+if __name__ == "__main__":
+    parser = argparse.ArgumentParser(description=__doc__)
+    parser.add_argument("--entries", type=int, default=1000)
+    parser.add_argument("--rounds", type=int, default=5)
+    args = parser.parse_args()
+    with tempfile.TemporaryDirectory() as root:
+        asyncio.run(_main({"uri": f"fs://{root}"}, args.entries, args.rounds))
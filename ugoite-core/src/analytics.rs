@@ -0,0 +1,277 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::entry::{self, RevisionRow};
+use crate::form;
+use crate::space_lock;
+use crate::storage;
+
+const ANALYTICS_DIR: &str = "analytics";
+const ANALYTICS_LEASE: &str = "analytics";
+/// Most buckets a single analytics query may return.
+pub const MAX_ANALYTICS_BUCKETS: usize = 1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Granularity {
+    Day,
+    /// ISO weeks, starting on Monday.
+    Week,
+    Month,
+}
+
+impl Granularity {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "day" => Ok(Self::Day),
+            "week" => Ok(Self::Week),
+            "month" => Ok(Self::Month),
+            other => Err(anyhow!("Unknown analytics granularity: {}", other)),
+        }
+    }
+
+    fn bucket_start(self, day: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => day,
+            Self::Week => day - Days::new(u64::from(day.weekday().num_days_from_monday())),
+            Self::Month => day.with_day(1).unwrap_or(day),
+        }
+    }
+
+    fn next_bucket(self, start: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Day => start.checked_add_days(Days::new(1)),
+            Self::Week => start.checked_add_days(Days::new(7)),
+            Self::Month => start.checked_add_months(Months::new(1)),
+        }
+    }
+}
+
+/// Inclusive range of UTC days.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct AnalyticsRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl AnalyticsRange {
+    /// Parse `YYYY-MM-DD` bounds.
+    pub fn parse(start: &str, end: &str) -> Result<Self> {
+        let day = |value: &str| {
+            NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+                .map_err(|_| anyhow!("Invalid analytics date: {}", value))
+        };
+        Ok(Self {
+            start: day(start)?,
+            end: day(end)?,
+        })
+    }
+}
+
+/// Activity of one UTC day, as stored in the rollup.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+struct DayRollup {
+    #[serde(default, skip_serializing_if = "is_zero")]
+    created: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    edited: usize,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    authors: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    created_by_form: BTreeMap<String, usize>,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// Per-day rollup of every revision up to `watermark`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+struct AnalyticsRollup {
+    #[serde(default)]
+    watermark: f64,
+    /// Revisions stamped exactly at `watermark`, already counted.
+    #[serde(default)]
+    watermark_revisions: BTreeSet<String>,
+    /// `YYYY-MM-DD` -> activity; sorted keys are in date order.
+    #[serde(default)]
+    days: BTreeMap<String, DayRollup>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnalyticsBucket {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub entries_created: usize,
+    pub entries_edited: usize,
+    /// Distinct authors of any revision in the bucket.
+    pub active_authors: usize,
+    pub created_by_form: BTreeMap<String, usize>,
+    /// Entries created per form up to the end of the bucket.
+    pub total_by_form: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SpaceAnalytics {
+    pub granularity: Granularity,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub buckets: Vec<AnalyticsBucket>,
+}
+
+fn rollup_path(ws_path: &str) -> String {
+    format!(
+        "{}/{}/rollup.json",
+        ws_path.trim_end_matches('/'),
+        ANALYTICS_DIR
+    )
+}
+
+async fn read_rollup(op: &Operator, ws_path: &str) -> Result<AnalyticsRollup> {
+    match op.read(&rollup_path(ws_path)).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("Analytics rollup is malformed: {}", e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(AnalyticsRollup::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn day_key(timestamp: f64) -> Option<String> {
+    DateTime::from_timestamp_millis((timestamp * 1000.0).round() as i64)
+        .map(|at| at.date_naive().format("%Y-%m-%d").to_string())
+}
+
+fn record_revision(rollup: &mut AnalyticsRollup, form_name: &str, revision: &RevisionRow) {
+    let Some(key) = day_key(revision.timestamp) else {
+        return;
+    };
+    let day = rollup.days.entry(key).or_default();
+    if revision.parent_revision_id.is_none() {
+        day.created += 1;
+        *day.created_by_form
+            .entry(form_name.to_string())
+            .or_default() += 1;
+    } else {
+        day.edited += 1;
+    }
+    if !revision.author.is_empty() {
+        day.authors.insert(revision.author.clone());
+    }
+}
+
+/// Fold revisions written since the last refresh into the stored rollup.
+/// Returns how many revisions were added.
+pub async fn refresh_space_analytics(op: &Operator, ws_path: &str) -> Result<usize> {
+    space_lock::with_space_lease(op, ws_path, ANALYTICS_LEASE, |_| async {
+        let mut rollup = read_rollup(op, ws_path).await?;
+        let mut fresh = Vec::new();
+        for form_name in form::list_form_names(op, ws_path).await? {
+            let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+            for revision in
+                entry::list_form_revision_rows(op, ws_path, &form_name, &form_def).await?
+            {
+                let seen = revision.timestamp < rollup.watermark
+                    || (revision.timestamp == rollup.watermark
+                        && rollup.watermark_revisions.contains(&revision.revision_id));
+                if !seen {
+                    fresh.push((form_name.clone(), revision));
+                }
+            }
+        }
+        if fresh.is_empty() {
+            return Ok(0);
+        }
+        for (form_name, revision) in &fresh {
+            record_revision(&mut rollup, form_name, revision);
+            if revision.timestamp > rollup.watermark {
+                rollup.watermark = revision.timestamp;
+                rollup.watermark_revisions.clear();
+            }
+            if revision.timestamp == rollup.watermark {
+                rollup
+                    .watermark_revisions
+                    .insert(revision.revision_id.clone());
+            }
+        }
+        storage::publish_atomic(op, &rollup_path(ws_path), serde_json::to_vec(&rollup)?).await?;
+        Ok(fresh.len())
+    })
+    .await
+}
+
+/// Time series of entries created and edited, active authors and growth per
+/// form over `range`, one bucket per `granularity` step. The rollup is
+/// refreshed first, so recent writes are included.
+pub async fn get_space_analytics(
+    op: &Operator,
+    ws_path: &str,
+    range: &AnalyticsRange,
+    granularity: Granularity,
+) -> Result<SpaceAnalytics> {
+    if range.start > range.end {
+        return Err(anyhow!("Analytics range start must not be after its end"));
+    }
+    let mut bounds = Vec::new();
+    let mut bucket = granularity.bucket_start(range.start);
+    while bucket <= range.end {
+        if bounds.len() == MAX_ANALYTICS_BUCKETS {
+            return Err(anyhow!(
+                "Analytics range spans more than {} buckets",
+                MAX_ANALYTICS_BUCKETS
+            ));
+        }
+        let next = granularity
+            .next_bucket(bucket)
+            .ok_or_else(|| anyhow!("Analytics range is out of bounds"))?;
+        let end = next.pred_opt().unwrap_or(next).min(range.end);
+        bounds.push((bucket.max(range.start), end));
+        bucket = next;
+    }
+
+    refresh_space_analytics(op, ws_path).await?;
+    let rollup = read_rollup(op, ws_path).await?;
+    let key = |day: NaiveDate| day.format("%Y-%m-%d").to_string();
+
+    let mut totals: BTreeMap<String, usize> = BTreeMap::new();
+    for day in rollup.days.range(..key(range.start)).map(|(_, day)| day) {
+        for (form_name, created) in &day.created_by_form {
+            *totals.entry(form_name.clone()).or_default() += created;
+        }
+    }
+
+    let mut buckets = Vec::with_capacity(bounds.len());
+    for (start, end) in bounds {
+        let mut bucket = AnalyticsBucket {
+            start,
+            end,
+            entries_created: 0,
+            entries_edited: 0,
+            active_authors: 0,
+            created_by_form: BTreeMap::new(),
+            total_by_form: BTreeMap::new(),
+        };
+        let mut authors = BTreeSet::new();
+        for day in rollup.days.range(key(start)..=key(end)).map(|(_, day)| day) {
+            bucket.entries_created += day.created;
+            bucket.entries_edited += day.edited;
+            authors.extend(day.authors.iter());
+            for (form_name, created) in &day.created_by_form {
+                *bucket.created_by_form.entry(form_name.clone()).or_default() += created;
+                *totals.entry(form_name.clone()).or_default() += created;
+            }
+        }
+        bucket.active_authors = authors.len();
+        bucket.total_by_form = totals.clone();
+        buckets.push(bucket);
+    }
+
+    Ok(SpaceAnalytics {
+        granularity,
+        start: range.start,
+        end: range.end,
+        buckets,
+    })
+}
@@ -1,13 +1,13 @@
 use anyhow::{anyhow, Result};
-use chrono::Utc;
 use futures::TryStreamExt;
 use opendal::{EntryMode, Operator};
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
+use crate::clock;
 use crate::entry;
 use crate::form;
 use crate::integrity::RealIntegrityProvider;
+use crate::space;
 
 const ASSET_FORM_NAME: &str = "Assets";
 
@@ -37,15 +37,6 @@ async fn ensure_asset_form(op: &Operator, ws_path: &str) -> Result<()> {
     form::upsert_metadata_form(op, ws_path, &asset_form_definition()).await
 }
 
-fn space_id_from_ws_path(ws_path: &str) -> String {
-    ws_path
-        .trim_end_matches('/')
-        .split('/')
-        .next_back()
-        .unwrap_or(ws_path)
-        .to_string()
-}
-
 fn build_asset_entry_content(name: &str, link: &str, uploaded_at: &str) -> String {
     format!(
         "---\nform: {ASSET_FORM_NAME}\n---\n# {name}\n\n## name\n{name}\n\n## link\n{link}\n\n## uploaded_at\n{uploaded_at}\n"
@@ -87,15 +78,15 @@ pub async fn save_asset(
     content: &[u8],
 ) -> Result<AssetInfo> {
     ensure_asset_form(op, ws_path).await?;
-    let asset_id = Uuid::new_v4().to_string();
+    let asset_id = clock::new_uuid().to_string();
     let safe_name = normalize_asset_filename(filename, &asset_id);
     let relative_path = format!("assets/{}_{}", asset_id, safe_name);
     let asset_path = format!("{}/{}", ws_path, relative_path);
     let link = format!("ugoite://asset/{asset_id}");
-    let uploaded_at = Utc::now().to_rfc3339();
+    let uploaded_at = clock::now().to_rfc3339();
     op.write(&asset_path, content.to_vec()).await?;
 
-    let space_id = space_id_from_ws_path(ws_path);
+    let space_id = space::space_id_from_ws_path(ws_path);
     let integrity = RealIntegrityProvider::from_space(op, &space_id).await?;
     let entry_content = build_asset_entry_content(&safe_name, &link, &uploaded_at);
     if let Err(error) =
@@ -235,3 +226,309 @@ pub async fn delete_asset(op: &Operator, ws_path: &str, asset_id: &str) -> Resul
 
     Ok(())
 }
+
+/// Entry metadata namespace recording where a downloaded asset came from.
+pub const ASSET_SOURCE_NAMESPACE: &str = "x-source";
+pub const DEFAULT_MAX_DOWNLOAD_BYTES: usize = 25 * 1024 * 1024;
+const DEFAULT_DOWNLOAD_TIMEOUT_SECS: u64 = 30;
+const MAX_DOWNLOAD_REDIRECTS: usize = 5;
+
+fn default_max_download_bytes() -> usize {
+    DEFAULT_MAX_DOWNLOAD_BYTES
+}
+
+fn default_download_timeout_secs() -> u64 {
+    DEFAULT_DOWNLOAD_TIMEOUT_SECS
+}
+
+/// Limits for [`save_asset_from_url`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UrlAssetOptions {
+    /// Asset name; the last URL path segment when unset.
+    #[serde(default)]
+    pub filename: Option<String>,
+    #[serde(default = "default_max_download_bytes")]
+    pub max_bytes: usize,
+    /// Accepted content types, e.g. `application/pdf` or `image/*`; any when empty.
+    #[serde(default)]
+    pub content_types: Vec<String>,
+    #[serde(default = "default_download_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Hosts that may be fetched, `*.example.com` matching subdomains; any when empty.
+    #[serde(default)]
+    pub allow_hosts: Vec<String>,
+    /// Hosts that may never be fetched; checked before `allow_hosts`.
+    #[serde(default)]
+    pub deny_hosts: Vec<String>,
+    /// Permit loopback, private and link-local addresses.
+    #[serde(default)]
+    pub allow_private_networks: bool,
+}
+
+impl Default for UrlAssetOptions {
+    fn default() -> Self {
+        Self {
+            filename: None,
+            max_bytes: default_max_download_bytes(),
+            content_types: Vec::new(),
+            timeout_secs: default_download_timeout_secs(),
+            allow_hosts: Vec::new(),
+            deny_hosts: Vec::new(),
+            allow_private_networks: false,
+        }
+    }
+}
+
+/// Provenance of a downloaded asset, stored under [`ASSET_SOURCE_NAMESPACE`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AssetSource {
+    pub source_url: String,
+    /// The URL the bytes were served from after redirects.
+    pub final_url: String,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    pub size: usize,
+    pub sha256: String,
+    pub fetched_at: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UrlAssetInfo {
+    #[serde(flatten)]
+    pub asset: AssetInfo,
+    pub source: AssetSource,
+}
+
+fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.trim().trim_end_matches('.').to_ascii_lowercase();
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => host.ends_with(&format!(".{}", suffix)),
+        None => host == pattern,
+    }
+}
+
+/// The IPv4 address an IPv6 address routes to: IPv4-mapped, IPv4-compatible,
+/// NAT64 (`64:ff9b::/96`), 6to4 (`2002::/16`) and Teredo (`2001::/32`, whose
+/// client address is stored inverted in the last 32 bits).
+fn embedded_ipv4(v6: std::net::Ipv6Addr) -> Option<std::net::Ipv4Addr> {
+    use std::net::Ipv4Addr;
+    if let Some(v4) = v6.to_ipv4_mapped() {
+        return Some(v4);
+    }
+    let segments = v6.segments();
+    let [.., a, b, c, d] = v6.octets();
+    match segments[0] {
+        0 if segments[1..6] == [0; 5] => Some(Ipv4Addr::new(a, b, c, d)),
+        0x0064 if segments[1..6] == [0xff9b, 0, 0, 0, 0] => Some(Ipv4Addr::new(a, b, c, d)),
+        0x2002 => {
+            let [_, _, a, b, c, d, ..] = v6.octets();
+            Some(Ipv4Addr::new(a, b, c, d))
+        }
+        0x2001 if segments[1] == 0 => Some(Ipv4Addr::new(!a, !b, !c, !d)),
+        _ => None,
+    }
+}
+
+fn is_public_ip(ip: std::net::IpAddr) -> bool {
+    use std::net::IpAddr;
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                || a == 0
+                || a >= 240
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 198 && (b & 0xfe) == 18))
+        }
+        IpAddr::V6(v6) => match embedded_ipv4(v6) {
+            Some(v4) => is_public_ip(IpAddr::V4(v4)),
+            None => {
+                let first = v6.segments()[0];
+                !(v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    || (first & 0xfe00) == 0xfc00
+                    || (first & 0xffc0) == 0xfe80)
+            }
+        },
+    }
+}
+
+/// Check `url` against the scheme, host lists and network guard, and resolve
+/// the addresses the request must connect to.
+async fn guard_url(
+    url: &url::Url,
+    options: &UrlAssetOptions,
+) -> Result<(String, Vec<std::net::SocketAddr>)> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(anyhow!("Unsupported URL scheme: {}", url.scheme()));
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("URL has no host: {}", url))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+    if options.deny_hosts.iter().any(|p| host_matches(p, &host)) {
+        return Err(anyhow!("Host is denied: {}", host));
+    }
+    if !options.allow_hosts.is_empty()
+        && !options.allow_hosts.iter().any(|p| host_matches(p, &host))
+    {
+        return Err(anyhow!("Host is not allowed: {}", host));
+    }
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("URL has no port: {}", url))?;
+    let addrs: Vec<std::net::SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|e| anyhow!("Could not resolve {}: {}", host, e))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(anyhow!("Could not resolve {}", host));
+    }
+    if !options.allow_private_networks && addrs.iter().any(|addr| !is_public_ip(addr.ip())) {
+        return Err(anyhow!("Host resolves to a non-public address: {}", host));
+    }
+    Ok((host, addrs))
+}
+
+fn content_type_allowed(patterns: &[String], content_type: Option<&str>) -> bool {
+    if patterns.is_empty() {
+        return true;
+    }
+    let Some(content_type) = content_type else {
+        return false;
+    };
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim().to_ascii_lowercase();
+        match pattern.strip_suffix("/*") {
+            Some(kind) => content_type
+                .split_once('/')
+                .is_some_and(|(major, _)| major == kind),
+            None => content_type == pattern,
+        }
+    })
+}
+
+pub(crate) struct Download {
+    pub(crate) final_url: url::Url,
+    pub(crate) content_type: Option<String>,
+    pub(crate) bytes: Vec<u8>,
+}
+
+/// Fetch `url`, re-checking every redirect hop and connecting only to the
+/// addresses the guard approved, so DNS changes cannot redirect the request.
+///
+/// This is the only path for fetching user-supplied URLs; other modules must
+/// call it rather than building their own client.
+pub(crate) async fn download(url: &str, options: &UrlAssetOptions) -> Result<Download> {
+    let mut current = url::Url::parse(url).map_err(|e| anyhow!("Invalid URL {}: {}", url, e))?;
+    for _ in 0..=MAX_DOWNLOAD_REDIRECTS {
+        let (host, addrs) = guard_url(&current, options).await?;
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(options.timeout_secs))
+            .redirect(reqwest::redirect::Policy::none())
+            .no_proxy()
+            .resolve_to_addrs(&host, &addrs)
+            .user_agent(concat!("ugoite-assets/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+        let response = client.get(current.clone()).send().await?;
+        if response.status().is_redirection() {
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| anyhow!("Redirect without a location from {}", current))?;
+            current = current
+                .join(location)
+                .map_err(|e| anyhow!("Invalid redirect location {}: {}", location, e))?;
+            continue;
+        }
+        let mut response = response.error_for_status()?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(';').next())
+            .map(|v| v.trim().to_ascii_lowercase())
+            .filter(|v| !v.is_empty());
+        if !content_type_allowed(&options.content_types, content_type.as_deref()) {
+            return Err(anyhow!(
+                "Content type not allowed: {}",
+                content_type.as_deref().unwrap_or("unknown")
+            ));
+        }
+        if response
+            .content_length()
+            .is_some_and(|length| length > options.max_bytes as u64)
+        {
+            return Err(anyhow!("Download exceeds {} bytes", options.max_bytes));
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if bytes.len() + chunk.len() > options.max_bytes {
+                return Err(anyhow!("Download exceeds {} bytes", options.max_bytes));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        return Ok(Download {
+            final_url: current,
+            content_type,
+            bytes,
+        });
+    }
+    Err(anyhow!("Too many redirects fetching {}", url))
+}
+
+/// Download `url` and store it as an asset, recording its provenance in the
+/// asset's [`ASSET_SOURCE_NAMESPACE`] metadata. Only public addresses are
+/// fetched unless `options.allow_private_networks` is set.
+pub async fn save_asset_from_url(
+    op: &Operator,
+    ws_path: &str,
+    url: &str,
+    options: &UrlAssetOptions,
+) -> Result<UrlAssetInfo> {
+    use sha2::{Digest, Sha256};
+
+    let fetched = download(url, options).await?;
+    let filename = options.filename.clone().unwrap_or_else(|| {
+        fetched
+            .final_url
+            .path_segments()
+            .and_then(|mut segments| segments.rfind(|segment| !segment.is_empty()))
+            .unwrap_or("download")
+            .to_string()
+    });
+    let asset = save_asset(op, ws_path, &filename, &fetched.bytes).await?;
+    let source = AssetSource {
+        source_url: url.to_string(),
+        final_url: fetched.final_url.to_string(),
+        content_type: fetched.content_type,
+        size: fetched.bytes.len(),
+        sha256: hex::encode(Sha256::digest(&fetched.bytes)),
+        fetched_at: clock::now().to_rfc3339(),
+    };
+    let values = match serde_json::to_value(&source)? {
+        serde_json::Value::Object(values) => values,
+        _ => serde_json::Map::new(),
+    };
+    crate::entry_metadata::set_entry_metadata(
+        op,
+        ws_path,
+        &asset.id,
+        ASSET_SOURCE_NAMESPACE,
+        &values,
+        false,
+    )
+    .await?;
+    Ok(UrlAssetInfo { asset, source })
+}
@@ -0,0 +1,321 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::OnceLock;
+
+use crate::asset;
+
+const ASSET_SCHEME: &str = "ugoite://asset/";
+
+/// Which way links are rewritten.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetLinkDirection {
+    /// `ugoite://asset/{id}` to a file path relative to the document.
+    Export,
+    /// A file path relative to the document to `ugoite://asset/{id}`.
+    Import,
+}
+
+impl std::str::FromStr for AssetLinkDirection {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "export" => Ok(Self::Export),
+            "import" => Ok(Self::Import),
+            other => Err(anyhow!("Unknown asset link direction: {}", other)),
+        }
+    }
+}
+
+/// Asset ids and the bundle-relative files they are stored as.
+#[derive(Debug, Clone, Default)]
+pub struct AssetLinkMap {
+    paths: BTreeMap<String, String>,
+    /// Path lookup keys (decoded, normalized, lowercase) to asset ids.
+    ids: HashMap<String, String>,
+}
+
+impl AssetLinkMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `asset_id` is stored at `path`, relative to the bundle root.
+    pub fn insert(&mut self, asset_id: &str, path: &str) {
+        let path = normalize_path(path);
+        self.ids.insert(path_key(&path), asset_id.to_string());
+        self.paths.insert(asset_id.to_string(), path);
+    }
+
+    /// The map of a space's assets, at their `assets/{id}_{name}` paths.
+    pub fn from_assets(assets: &[asset::AssetInfo]) -> Self {
+        let mut map = Self::new();
+        for info in assets {
+            map.insert(&info.id, &info.path);
+        }
+        map
+    }
+
+    pub async fn load(op: &Operator, ws_path: &str) -> Result<Self> {
+        Ok(Self::from_assets(&asset::list_assets(op, ws_path).await?))
+    }
+
+    pub fn path_of(&self, asset_id: &str) -> Option<&str> {
+        self.paths.get(asset_id).map(String::as_str)
+    }
+
+    /// The asset stored at `path`; `..` segments are resolved and case is ignored.
+    pub fn asset_at(&self, path: &str) -> Option<&str> {
+        self.ids.get(&path_key(path)).map(String::as_str)
+    }
+}
+
+/// One asset whose links were rewritten.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AssetLinkMapping {
+    pub asset_id: String,
+    /// Bundle-relative path of the asset file.
+    pub path: String,
+    pub occurrences: usize,
+}
+
+/// What a rewrite changed and what it could not map.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AssetLinkReport {
+    pub direction: AssetLinkDirection,
+    /// Rewritten assets, ordered by asset id.
+    pub mappings: Vec<AssetLinkMapping>,
+    /// Link targets left unchanged: unknown asset ids on export, relative
+    /// paths matching no asset on import (e.g. links to other documents).
+    pub unresolved: Vec<String>,
+}
+
+impl AssetLinkReport {
+    pub fn new(direction: AssetLinkDirection) -> Self {
+        Self {
+            direction,
+            mappings: Vec::new(),
+            unresolved: Vec::new(),
+        }
+    }
+
+    /// Add the counts and unresolved targets of `other`.
+    pub fn merge(&mut self, other: AssetLinkReport) {
+        for mapping in other.mappings {
+            self.record(&mapping.asset_id, &mapping.path, mapping.occurrences);
+        }
+        for target in other.unresolved {
+            self.unresolve(&target);
+        }
+    }
+
+    fn record(&mut self, asset_id: &str, path: &str, occurrences: usize) {
+        match self
+            .mappings
+            .binary_search_by(|mapping| mapping.asset_id.as_str().cmp(asset_id))
+        {
+            Ok(idx) => self.mappings[idx].occurrences += occurrences,
+            Err(idx) => self.mappings.insert(
+                idx,
+                AssetLinkMapping {
+                    asset_id: asset_id.to_string(),
+                    path: path.to_string(),
+                    occurrences,
+                },
+            ),
+        }
+    }
+
+    fn unresolve(&mut self, target: &str) {
+        if !self.unresolved.iter().any(|seen| seen == target) {
+            self.unresolved.push(target.to_string());
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AssetLinkRewrite {
+    pub content: String,
+    pub report: AssetLinkReport,
+}
+
+/// Link destinations: inline `](target)`, reference definitions
+/// `[label]: target` and HTML `src=` / `href=` attributes.
+fn destination_regex() -> &'static Regex {
+    static DESTINATION_REGEX: OnceLock<Regex> = OnceLock::new();
+    DESTINATION_REGEX.get_or_init(|| {
+        Regex::new(
+            r#"(?m)(\]\(\s*|^[ ]{0,3}\[[^\]\n]+\]:[ \t]*|\b(?:src|href)\s*=\s*)(<[^>\n]*>|"[^"\n]*"|'[^'\n]*'|[^\s)"'<>]+)"#,
+        )
+        .expect("destination regex must compile")
+    })
+}
+
+fn split_delimiters(raw: &str) -> (&str, &str, &str) {
+    for (open, close) in [("<", ">"), ("\"", "\""), ("'", "'")] {
+        if raw.len() >= 2 && raw.starts_with(open) && raw.ends_with(close) {
+            return (open, &raw[1..raw.len() - 1], close);
+        }
+    }
+    ("", raw, "")
+}
+
+/// Split off a `#fragment` or `?query`, which is carried over unchanged.
+fn split_suffix(target: &str) -> (&str, &str) {
+    match target.find(['#', '?']) {
+        Some(idx) => target.split_at(idx),
+        None => (target, ""),
+    }
+}
+
+fn percent_decode(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' && idx + 3 <= bytes.len() {
+            let hex = std::str::from_utf8(&bytes[idx + 1..idx + 3]).unwrap_or("");
+            if let Ok(value) = u8::from_str_radix(hex, 16) {
+                out.push(value);
+                idx += 3;
+                continue;
+            }
+        }
+        out.push(bytes[idx]);
+        idx += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+fn encode_segment(segment: &str) -> String {
+    url::form_urlencoded::byte_serialize(segment.as_bytes())
+        .collect::<String>()
+        .replace('+', "%20")
+}
+
+fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for segment in path.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            other => parts.push(other),
+        }
+    }
+    parts.join("/")
+}
+
+fn path_key(path: &str) -> String {
+    normalize_path(path).to_lowercase()
+}
+
+/// `to` (bundle-relative) as seen from a document in `from_dir`, percent-encoded.
+fn relative_path(from_dir: &str, to: &str) -> String {
+    let from = normalize_path(from_dir);
+    let to = normalize_path(to);
+    let from: Vec<&str> = from.split('/').filter(|s| !s.is_empty()).collect();
+    let to: Vec<&str> = to.split('/').filter(|s| !s.is_empty()).collect();
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut parts = vec!["..".to_string(); from.len() - common];
+    parts.extend(to[common..].iter().map(|segment| encode_segment(segment)));
+    parts.join("/")
+}
+
+fn is_external(target: &str) -> bool {
+    let head = target.split('/').next().unwrap_or("");
+    target.is_empty() || target.starts_with('#') || target.starts_with("//") || head.contains(':')
+}
+
+/// Rewrite the asset links of `markdown`, a document stored at `base_dir`
+/// inside the bundle, in `direction`.
+///
+/// Only link destinations are touched, with their delimiters, fragments and
+/// queries kept, so exporting and importing with the same map returns the
+/// original text.
+pub fn rewrite_asset_links(
+    markdown: &str,
+    base_dir: &str,
+    map: &AssetLinkMap,
+    direction: AssetLinkDirection,
+) -> AssetLinkRewrite {
+    let mut report = AssetLinkReport::new(direction);
+    let content = destination_regex()
+        .replace_all(markdown, |caps: &Captures| {
+            let (open, target, close) = split_delimiters(&caps[2]);
+            let rewritten = match direction {
+                AssetLinkDirection::Export => export_target(target, base_dir, map, &mut report),
+                AssetLinkDirection::Import => import_target(target, base_dir, map, &mut report),
+            };
+            match rewritten {
+                Some(target) => format!("{}{open}{target}{close}", &caps[1]),
+                None => caps[0].to_string(),
+            }
+        })
+        .to_string();
+    AssetLinkRewrite { content, report }
+}
+
+fn export_target(
+    target: &str,
+    base_dir: &str,
+    map: &AssetLinkMap,
+    report: &mut AssetLinkReport,
+) -> Option<String> {
+    let (id, suffix) = split_suffix(target.strip_prefix(ASSET_SCHEME)?);
+    match map.path_of(id) {
+        Some(path) => {
+            report.record(id, path, 1);
+            Some(format!("{}{suffix}", relative_path(base_dir, path)))
+        }
+        None => {
+            report.unresolve(target);
+            None
+        }
+    }
+}
+
+fn import_target(
+    target: &str,
+    base_dir: &str,
+    map: &AssetLinkMap,
+    report: &mut AssetLinkReport,
+) -> Option<String> {
+    if is_external(target) {
+        return None;
+    }
+    let (path, suffix) = split_suffix(target);
+    let resolved = format!("{}/{}", base_dir, percent_decode(path));
+    match map.asset_at(&resolved) {
+        Some(id) => {
+            let path = map.path_of(id).unwrap_or_default().to_string();
+            report.record(id, &path, 1);
+            Some(format!("{ASSET_SCHEME}{id}{suffix}"))
+        }
+        None => {
+            report.unresolve(target);
+            None
+        }
+    }
+}
+
+/// Ids of the assets `markdown` links to.
+pub fn asset_ids_in(markdown: &str) -> BTreeSet<String> {
+    destination_regex()
+        .captures_iter(markdown)
+        .filter_map(|caps| {
+            let (_, target, _) = split_delimiters(caps.get(2)?.as_str());
+            let (id, _) = split_suffix(target.strip_prefix(ASSET_SCHEME)?);
+            Some(id.to_string()).filter(|id| !id.is_empty())
+        })
+        .collect()
+}
@@ -2,16 +2,193 @@ use anyhow::{anyhow, Result};
 use chrono::{SecondsFormat, Utc};
 use opendal::Operator;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::sync::{Arc, OnceLock};
 use tokio::sync::Mutex;
 
+use crate::redaction;
+use crate::space_lock::{self, SpaceLease};
+use crate::storage;
+
 const DEFAULT_AUDIT_LIMIT: usize = 100;
 const MAX_AUDIT_LIMIT: usize = 500;
 const DEFAULT_AUDIT_RETENTION: usize = 5000;
 const MAX_AUDIT_RETENTION: usize = 50000;
+/// Space lease serializing audit log rewrites across processes.
+const AUDIT_LEASE: &str = "audit";
+const AUDIT_VALIDATION_ENV: &str = "UGOITE_AUDIT_VALIDATION";
+
+/// How [`append_audit_event`] treats payloads that do not match the action registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditValidationMode {
+    /// Append anyway, listing the problems in the event's `schema_warnings`.
+    #[default]
+    Permissive,
+    /// Reject unknown actions, missing required metadata and wrong target types.
+    Strict,
+}
+
+impl AuditValidationMode {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "permissive" => Ok(Self::Permissive),
+            "strict" => Ok(Self::Strict),
+            other => Err(anyhow!("Unknown audit validation mode: {}", other)),
+        }
+    }
+}
+
+/// A known audit action and the shape its events must have.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditActionSpec {
+    pub action: &'static str,
+    pub description: &'static str,
+    /// Required `target_type`; any (or none) when unset.
+    pub target_type: Option<&'static str>,
+    pub required_metadata: &'static [&'static str],
+}
+
+const fn action(
+    action: &'static str,
+    description: &'static str,
+    target_type: Option<&'static str>,
+    required_metadata: &'static [&'static str],
+) -> AuditActionSpec {
+    AuditActionSpec {
+        action,
+        description,
+        target_type,
+        required_metadata,
+    }
+}
+
+/// Every action emitted by core and the API layer, sorted by name.
+const AUDIT_ACTIONS: &[AuditActionSpec] = &[
+    action(
+        "auth.authenticate",
+        "A request was authenticated or rejected.",
+        None,
+        &[],
+    ),
+    action(
+        "authz.deny",
+        "An authenticated request was refused by authorization.",
+        None,
+        &[],
+    ),
+    action(
+        "data.mutation",
+        "A write request to the HTTP API succeeded.",
+        Some("http_path"),
+        &["status_code"],
+    ),
+    action(
+        "entry.bulk_update",
+        "A bulk property update changed one entry.",
+        Some("entry"),
+        &["revision_id", "properties", "job_id"],
+    ),
+    action(
+        "entry.purge",
+        "A trashed entry was permanently removed.",
+        Some("entry"),
+        &["form"],
+    ),
+    action(
+        "entry.transition",
+        "An entry moved to another workflow state.",
+        Some("entry"),
+        &["form", "from", "to", "revision_id"],
+    ),
+    action(
+        "entry_lock.force_release",
+        "An administrator removed an entry edit lock.",
+        Some("entry"),
+        &["previous_holder"],
+    ),
+    action(
+        "entry_lock.steal",
+        "An entry edit lock was taken over from its holder.",
+        Some("entry"),
+        &["previous_holder"],
+    ),
+    action(
+        "legal_hold.place",
+        "A legal hold was placed.",
+        None,
+        &["hold_id"],
+    ),
+    action(
+        "legal_hold.release",
+        "A legal hold was released.",
+        None,
+        &["hold_id"],
+    ),
+    action(
+        "principal.erase",
+        "A user's personal data was erased or pseudonymized.",
+        Some("user"),
+        &["entries_anonymized", "entries_removed"],
+    ),
+    action(
+        "retention.archive",
+        "A retention policy archived an entry.",
+        Some("entry"),
+        &["form", "max_age_days"],
+    ),
+    action(
+        "retention.delete",
+        "A retention policy deleted an entry.",
+        Some("entry"),
+        &["form", "max_age_days"],
+    ),
+    action(
+        "retention.enforce",
+        "Retention policies were enforced across the space.",
+        Some("space"),
+        &["dry_run", "archived", "deleted"],
+    ),
+    action(
+        "service_account.create",
+        "A service account was created.",
+        Some("service_account"),
+        &["scopes"],
+    ),
+    action(
+        "service_account.key.create",
+        "A service account key was issued.",
+        Some("service_account_key"),
+        &["service_account_id"],
+    ),
+    action(
+        "service_account.key.revoke",
+        "A service account key was revoked.",
+        Some("service_account_key"),
+        &["service_account_id"],
+    ),
+    action(
+        "service_account.key.rotate",
+        "A service account key was rotated.",
+        Some("service_account_key"),
+        &["service_account_id"],
+    ),
+    action(
+        "service_account.key.use",
+        "A request authenticated with a service account key.",
+        Some("service_account_key"),
+        &["service_account_id"],
+    ),
+    action(
+        "space.bulk_update",
+        "A bulk property update finished across the space.",
+        Some("space"),
+        &["properties", "job_id", "matched", "updated"],
+    ),
+];
 
 #[derive(Debug, Clone)]
 pub struct AuditListOptions {
@@ -64,7 +241,7 @@ fn normalize_outcome(outcome: Option<&str>) -> String {
     }
 }
 
-fn validate_space_id(space_id: &str) -> Result<String> {
+pub(crate) fn validate_space_id(space_id: &str) -> Result<String> {
     let normalized = space_id.trim();
     if normalized.is_empty() {
         return Err(anyhow!("space_id must not be empty"));
@@ -78,6 +255,54 @@ fn validate_space_id(space_id: &str) -> Result<String> {
     Ok(normalized.to_string())
 }
 
+/// The audit action vocabulary, sorted by action name.
+pub fn list_audit_actions() -> &'static [AuditActionSpec] {
+    AUDIT_ACTIONS
+}
+
+pub fn audit_action(action: &str) -> Option<&'static AuditActionSpec> {
+    AUDIT_ACTIONS.iter().find(|spec| spec.action == action)
+}
+
+/// Problems with `payload` against the action registry; empty when it conforms.
+pub fn audit_schema_problems(payload: &Value) -> Vec<String> {
+    let action = payload
+        .get("action")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or_default();
+    let Some(spec) = audit_action(action) else {
+        return vec![format!("unknown audit action: {}", action)];
+    };
+    let mut problems = Vec::new();
+    if let Some(expected) = spec.target_type {
+        let actual = payload.get("target_type").and_then(Value::as_str);
+        if actual != Some(expected) {
+            problems.push(format!(
+                "{} requires target_type {}, got {}",
+                action,
+                expected,
+                actual.unwrap_or("none")
+            ));
+        }
+    }
+    let metadata = payload.get("metadata").and_then(Value::as_object);
+    for field in spec.required_metadata {
+        if !metadata.is_some_and(|metadata| metadata.contains_key(*field)) {
+            problems.push(format!("{} requires metadata field {}", action, field));
+        }
+    }
+    problems
+}
+
+/// The validation mode named by `UGOITE_AUDIT_VALIDATION`, permissive when unset or unknown.
+pub fn validation_mode_from_env() -> AuditValidationMode {
+    std::env::var(AUDIT_VALIDATION_ENV)
+        .ok()
+        .and_then(|raw| AuditValidationMode::parse(&raw).ok())
+        .unwrap_or_default()
+}
+
 fn audit_file_path(space_id: &str) -> String {
     format!("spaces/{space_id}/audit/events.jsonl")
 }
@@ -93,7 +318,7 @@ fn event_hash(payload: &Value, prev_hash: &str) -> Result<String> {
     Ok(hex::encode(digest))
 }
 
-fn verify_chain(events: &[Value]) -> Result<()> {
+pub(crate) fn verify_chain(events: &[Value]) -> Result<()> {
     let mut prev_hash = "root".to_string();
     for event in events {
         let mut candidate = event.clone();
@@ -140,7 +365,7 @@ fn rehash_chain(events: &mut [Value]) -> Result<()> {
     Ok(())
 }
 
-async fn read_events(op: &Operator, space_id: &str) -> Result<Vec<Value>> {
+pub(crate) async fn read_events(op: &Operator, space_id: &str) -> Result<Vec<Value>> {
     let path = audit_file_path(space_id);
     if !op.exists(&path).await? {
         return Ok(Vec::new());
@@ -162,7 +387,14 @@ async fn read_events(op: &Operator, space_id: &str) -> Result<Vec<Value>> {
     Ok(events)
 }
 
-async fn write_events(op: &Operator, space_id: &str, events: &[Value]) -> Result<()> {
+async fn write_events(
+    op: &Operator,
+    space_id: &str,
+    events: &[Value],
+    lease: &SpaceLease,
+) -> Result<()> {
+    let ws_path = format!("spaces/{space_id}");
+    space_lock::ensure_lease_current(op, &ws_path, lease).await?;
     let dir_path = format!("spaces/{space_id}/audit/");
     op.create_dir(&dir_path).await?;
     let path = audit_file_path(space_id);
@@ -174,15 +406,33 @@ async fn write_events(op: &Operator, space_id: &str, events: &[Value]) -> Result
     if !payload.is_empty() {
         payload.push('\n');
     }
-    op.write(&path, payload.into_bytes()).await?;
+    storage::publish_atomic(op, &path, payload.into_bytes()).await?;
     Ok(())
 }
 
+/// Append an event, validated in the mode named by `UGOITE_AUDIT_VALIDATION`.
 pub async fn append_audit_event(
     op: &Operator,
     space_id: &str,
     payload: &Value,
     retention_limit: Option<usize>,
+) -> Result<Value> {
+    append_audit_event_with_mode(
+        op,
+        space_id,
+        payload,
+        retention_limit,
+        validation_mode_from_env(),
+    )
+    .await
+}
+
+pub async fn append_audit_event_with_mode(
+    op: &Operator,
+    space_id: &str,
+    payload: &Value,
+    retention_limit: Option<usize>,
+    mode: AuditValidationMode,
 ) -> Result<Value> {
     let safe_space_id = validate_space_id(space_id)?;
     let payload_obj = payload
@@ -205,63 +455,123 @@ pub async fn append_audit_event(
         .ok_or_else(|| anyhow!("actor_user_id must not be empty"))?
         .to_string();
 
+    let schema_problems = audit_schema_problems(payload);
+    if mode == AuditValidationMode::Strict && !schema_problems.is_empty() {
+        return Err(anyhow!(
+            "audit event does not match its schema: {}",
+            schema_problems.join("; ")
+        ));
+    }
+
     let lock = space_lock(&safe_space_id).await;
     let _guard = lock.lock().await;
+    let ws_path = format!("spaces/{safe_space_id}");
+    space_lock::with_space_lease(op, &ws_path, AUDIT_LEASE, |lease| async move {
+        let mut events = read_events(op, &safe_space_id).await?;
+        verify_chain(&events)?;
+
+        let prev_hash = events
+            .last()
+            .and_then(Value::as_object)
+            .and_then(|item| item.get("event_hash"))
+            .and_then(Value::as_str)
+            .unwrap_or("root")
+            .to_string();
+
+        let metadata = payload_obj
+            .get("metadata")
+            .and_then(Value::as_object)
+            .map(|_| {
+                payload_obj
+                    .get("metadata")
+                    .map(|value| redaction::default_redactor().redact_value(value))
+                    .unwrap_or_else(|| json!({}))
+            })
+            .unwrap_or_else(|| json!({}));
+
+        let mut event = json!({
+            "id": format!("audit-{}", uuid::Uuid::new_v4().simple()),
+            "timestamp": now_iso(),
+            "space_id": safe_space_id,
+            "action": action,
+            "actor_user_id": actor_user_id,
+            "outcome": normalize_outcome(payload_obj.get("outcome").and_then(Value::as_str)),
+            "target_type": payload_obj.get("target_type").cloned().unwrap_or(Value::Null),
+            "target_id": payload_obj.get("target_id").cloned().unwrap_or(Value::Null),
+            "request_method": payload_obj.get("request_method").cloned().unwrap_or(Value::Null),
+            "request_path": payload_obj.get("request_path").cloned().unwrap_or(Value::Null),
+            "request_id": payload_obj.get("request_id").cloned().unwrap_or(Value::Null),
+            "metadata": metadata,
+            "prev_hash": prev_hash,
+        });
+        if !schema_problems.is_empty() {
+            event["schema_warnings"] = json!(schema_problems);
+        }
 
-    let mut events = read_events(op, &safe_space_id).await?;
-    verify_chain(&events)?;
-
-    let prev_hash = events
-        .last()
-        .and_then(Value::as_object)
-        .and_then(|item| item.get("event_hash"))
-        .and_then(Value::as_str)
-        .unwrap_or("root")
-        .to_string();
+        let hash = event_hash(&event, event["prev_hash"].as_str().unwrap_or("root"))?;
+        event["event_hash"] = Value::String(hash);
+        events.push(event.clone());
+
+        let retention = normalize_retention_limit(retention_limit);
+        if events.len() > retention {
+            let start_index = events.len() - retention;
+            events = events.split_off(start_index);
+            rehash_chain(&mut events)?;
+            if let Some(last) = events.last() {
+                event = last.clone();
+            }
+        }
 
-    let metadata = payload_obj
-        .get("metadata")
-        .and_then(Value::as_object)
-        .map(|_| {
-            payload_obj
-                .get("metadata")
-                .cloned()
-                .unwrap_or_else(|| json!({}))
-        })
-        .unwrap_or_else(|| json!({}));
-
-    let mut event = json!({
-        "id": format!("audit-{}", uuid::Uuid::new_v4().simple()),
-        "timestamp": now_iso(),
-        "space_id": safe_space_id,
-        "action": action,
-        "actor_user_id": actor_user_id,
-        "outcome": normalize_outcome(payload_obj.get("outcome").and_then(Value::as_str)),
-        "target_type": payload_obj.get("target_type").cloned().unwrap_or(Value::Null),
-        "target_id": payload_obj.get("target_id").cloned().unwrap_or(Value::Null),
-        "request_method": payload_obj.get("request_method").cloned().unwrap_or(Value::Null),
-        "request_path": payload_obj.get("request_path").cloned().unwrap_or(Value::Null),
-        "request_id": payload_obj.get("request_id").cloned().unwrap_or(Value::Null),
-        "metadata": metadata,
-        "prev_hash": prev_hash,
-    });
+        write_events(op, &safe_space_id, &events, &lease).await?;
+        Ok(event)
+    })
+    .await
+}
 
-    let hash = event_hash(&event, event["prev_hash"].as_str().unwrap_or("root"))?;
-    event["event_hash"] = Value::String(hash);
-    events.push(event.clone());
-
-    let retention = normalize_retention_limit(retention_limit);
-    if events.len() > retention {
-        let start_index = events.len() - retention;
-        events = events.split_off(start_index);
-        rehash_chain(&mut events)?;
-        if let Some(last) = events.last() {
-            event = last.clone();
+/// Replace a user id in actor and user-target fields, re-hashing the chain.
+/// Returns the number of events rewritten.
+pub async fn pseudonymize_audit_actor(
+    op: &Operator,
+    space_id: &str,
+    user_id: &str,
+    replacement: &str,
+) -> Result<usize> {
+    let safe_space_id = validate_space_id(space_id)?;
+    let lock = space_lock(&safe_space_id).await;
+    let _guard = lock.lock().await;
+    let ws_path = format!("spaces/{safe_space_id}");
+    space_lock::with_space_lease(op, &ws_path, AUDIT_LEASE, |lease| async move {
+        let mut events = read_events(op, &safe_space_id).await?;
+        verify_chain(&events)?;
+
+        let mut rewritten = 0;
+        for event in events.iter_mut() {
+            let Some(obj) = event.as_object_mut() else {
+                continue;
+            };
+            let mut changed = false;
+            if obj.get("actor_user_id").and_then(Value::as_str) == Some(user_id) {
+                obj.insert("actor_user_id".to_string(), json!(replacement));
+                changed = true;
+            }
+            if obj.get("target_type").and_then(Value::as_str) == Some("user")
+                && obj.get("target_id").and_then(Value::as_str) == Some(user_id)
+            {
+                obj.insert("target_id".to_string(), json!(replacement));
+                changed = true;
+            }
+            if changed {
+                rewritten += 1;
+            }
         }
-    }
 
-    write_events(op, &safe_space_id, &events).await?;
-    Ok(event)
+        if rewritten > 0 {
+            rehash_chain(&mut events)?;
+            write_events(op, &safe_space_id, &events, &lease).await?;
+        }
+        Ok(rewritten)
+    })
+    .await
 }
 
 pub async fn list_audit_events(
@@ -0,0 +1,357 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::audit;
+use crate::clock;
+use crate::storage;
+
+/// Collector base URL; `/v1/logs` is appended unless already present.
+pub const OTLP_ENDPOINT_ENV: &str = "UGOITE_AUDIT_OTLP_ENDPOINT";
+/// Extra request headers as `key=value,key=value`, e.g. an API token.
+pub const OTLP_HEADERS_ENV: &str = "UGOITE_AUDIT_OTLP_HEADERS";
+
+const OTLP_LOGS_PATH: &str = "/v1/logs";
+const DEFAULT_SERVICE_NAME: &str = "ugoite";
+const DEFAULT_BATCH_SIZE: usize = 200;
+const MAX_BATCH_SIZE: usize = 5000;
+const SCOPE_NAME: &str = "ugoite.audit";
+const ATTRIBUTE_PREFIX: &str = "ugoite.audit.";
+
+/// Where and how audit events are forwarded.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct OtlpExporterConfig {
+    pub endpoint: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// `service.name` resource attribute.
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+    /// Log records per request.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+}
+
+fn default_service_name() -> String {
+    DEFAULT_SERVICE_NAME.to_string()
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+
+impl OtlpExporterConfig {
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            headers: BTreeMap::new(),
+            service_name: default_service_name(),
+            batch_size: default_batch_size(),
+        }
+    }
+
+    /// The config named by `UGOITE_AUDIT_OTLP_ENDPOINT` and
+    /// `UGOITE_AUDIT_OTLP_HEADERS`, if an endpoint is set.
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var(OTLP_ENDPOINT_ENV).ok()?;
+        if endpoint.trim().is_empty() {
+            return None;
+        }
+        let mut config = Self::new(endpoint.trim());
+        if let Ok(raw) = std::env::var(OTLP_HEADERS_ENV) {
+            config.headers = parse_headers(&raw);
+        }
+        Some(config)
+    }
+
+    /// The OTLP/HTTP logs URL of the collector.
+    pub fn logs_url(&self) -> Result<String> {
+        let endpoint = self.endpoint.trim().trim_end_matches('/');
+        let parsed =
+            url::Url::parse(endpoint).map_err(|e| anyhow!("Invalid OTLP endpoint: {}", e))?;
+        if parsed.scheme() != "http" && parsed.scheme() != "https" {
+            return Err(anyhow!("OTLP endpoint must be http or https"));
+        }
+        if endpoint.ends_with(OTLP_LOGS_PATH) {
+            Ok(endpoint.to_string())
+        } else {
+            Ok(format!("{endpoint}{OTLP_LOGS_PATH}"))
+        }
+    }
+}
+
+fn parse_headers(raw: &str) -> BTreeMap<String, String> {
+    raw.split(',')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .filter(|(key, _)| !key.is_empty())
+        .collect()
+}
+
+/// Delivers OTLP/JSON `ExportLogsServiceRequest` bodies to a collector.
+#[async_trait]
+pub trait OtlpLogSink: Send + Sync {
+    async fn push(&self, request: &Value) -> Result<()>;
+}
+
+pub struct HttpOtlpLogSink {
+    client: reqwest::Client,
+    url: String,
+    headers: BTreeMap<String, String>,
+}
+
+impl HttpOtlpLogSink {
+    pub fn new(config: &OtlpExporterConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(15))
+            .user_agent(concat!("ugoite-audit-otlp/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+        Ok(Self {
+            client,
+            url: config.logs_url()?,
+            headers: config.headers.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl OtlpLogSink for HttpOtlpLogSink {
+    async fn push(&self, request: &Value) -> Result<()> {
+        let mut builder = self
+            .client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(request)?);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        builder.send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+/// OTLP severity number and text for an audit outcome.
+pub fn otlp_severity(outcome: &str) -> (u8, &'static str) {
+    match outcome {
+        "deny" => (13, "WARN"),
+        "error" => (17, "ERROR"),
+        _ => (9, "INFO"),
+    }
+}
+
+/// An OTLP `AnyValue`; integers are strings, as OTLP/JSON encodes int64.
+fn any_value(value: &Value) -> Value {
+    match value {
+        Value::Null => json!({}),
+        Value::Bool(flag) => json!({"boolValue": flag}),
+        Value::Number(number) => match number.as_i64() {
+            Some(int) => json!({"intValue": int.to_string()}),
+            None => json!({"doubleValue": number.as_f64()}),
+        },
+        Value::String(text) => json!({"stringValue": text}),
+        Value::Array(items) => {
+            json!({"arrayValue": {"values": items.iter().map(any_value).collect::<Vec<_>>()}})
+        }
+        Value::Object(fields) => json!({"kvlistValue": {"values": key_values(fields)}}),
+    }
+}
+
+fn key_values(fields: &Map<String, Value>) -> Vec<Value> {
+    fields
+        .iter()
+        .map(|(key, value)| json!({"key": key, "value": any_value(value)}))
+        .collect()
+}
+
+fn attribute(key: &str, value: &Value) -> Option<Value> {
+    match value {
+        Value::Null => None,
+        Value::String(text) if text.is_empty() => None,
+        _ => Some(json!({"key": key, "value": any_value(value)})),
+    }
+}
+
+fn unix_nanos(timestamp: &str) -> Option<String> {
+    let parsed = chrono::DateTime::parse_from_rfc3339(timestamp).ok()?;
+    Some(parsed.timestamp_nanos_opt()?.to_string())
+}
+
+/// One audit event as an OTLP log record: severity follows the outcome, the
+/// body is the action, and event fields and metadata become attributes.
+pub fn audit_event_to_log_record(event: &Value, observed_unix_nanos: &str) -> Value {
+    let field = |name: &str| event.get(name).cloned().unwrap_or(Value::Null);
+    let outcome = event.get("outcome").and_then(Value::as_str).unwrap_or("");
+    let (severity_number, severity_text) = otlp_severity(outcome);
+
+    let mut attributes: Vec<Value> = [
+        ("ugoite.space_id", "space_id"),
+        ("enduser.id", "actor_user_id"),
+        ("http.request.method", "request_method"),
+        ("url.path", "request_path"),
+    ]
+    .into_iter()
+    .filter_map(|(key, name)| attribute(key, &field(name)))
+    .collect();
+    for name in [
+        "id",
+        "action",
+        "outcome",
+        "target_type",
+        "target_id",
+        "request_id",
+        "event_hash",
+        "schema_warnings",
+    ] {
+        attributes.extend(attribute(
+            &format!("{ATTRIBUTE_PREFIX}{name}"),
+            &field(name),
+        ));
+    }
+    if let Some(metadata) = event.get("metadata").and_then(Value::as_object) {
+        for (key, value) in metadata {
+            attributes.extend(attribute(
+                &format!("{ATTRIBUTE_PREFIX}metadata.{key}"),
+                value,
+            ));
+        }
+    }
+
+    json!({
+        "timeUnixNano": event
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(unix_nanos)
+            .unwrap_or_else(|| observed_unix_nanos.to_string()),
+        "observedTimeUnixNano": observed_unix_nanos,
+        "severityNumber": severity_number,
+        "severityText": severity_text,
+        "body": any_value(&field("action")),
+        "attributes": attributes,
+    })
+}
+
+/// An OTLP/JSON `ExportLogsServiceRequest` carrying `events`.
+pub fn audit_events_to_otlp(events: &[Value], service_name: &str) -> Value {
+    let observed = clock::now()
+        .timestamp_nanos_opt()
+        .unwrap_or_default()
+        .to_string();
+    json!({
+        "resourceLogs": [{
+            "resource": {
+                "attributes": [
+                    {"key": "service.name", "value": {"stringValue": service_name}},
+                    {"key": "service.version", "value": {"stringValue": env!("CARGO_PKG_VERSION")}},
+                ],
+            },
+            "scopeLogs": [{
+                "scope": {"name": SCOPE_NAME, "version": env!("CARGO_PKG_VERSION")},
+                "logRecords": events
+                    .iter()
+                    .map(|event| audit_event_to_log_record(event, &observed))
+                    .collect::<Vec<_>>(),
+            }],
+        }],
+    })
+}
+
+/// The last event a space's exporter delivered.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct OtlpExportCursor {
+    pub last_event_id: String,
+    pub last_timestamp: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct OtlpExportReport {
+    pub space_id: String,
+    pub exported: usize,
+    pub batches: usize,
+    pub cursor: Option<OtlpExportCursor>,
+}
+
+fn cursor_path(space_id: &str) -> String {
+    format!("spaces/{space_id}/audit/otlp_cursor.json")
+}
+
+pub async fn get_export_cursor(op: &Operator, space_id: &str) -> Result<Option<OtlpExportCursor>> {
+    let space_id = audit::validate_space_id(space_id)?;
+    match op.read(&cursor_path(&space_id)).await {
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes.to_vec())?)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Events appended after `cursor`. When retention has dropped the cursor's
+/// event, everything newer than its timestamp is pending.
+fn pending_events(events: Vec<Value>, cursor: Option<&OtlpExportCursor>) -> Vec<Value> {
+    let Some(cursor) = cursor else {
+        return events;
+    };
+    let id_of = |event: &Value| event.get("id").and_then(Value::as_str).map(str::to_string);
+    match events
+        .iter()
+        .position(|event| id_of(event).as_deref() == Some(cursor.last_event_id.as_str()))
+    {
+        Some(idx) => events.into_iter().skip(idx + 1).collect(),
+        None => events
+            .into_iter()
+            .filter(|event| {
+                event
+                    .get("timestamp")
+                    .and_then(Value::as_str)
+                    .is_some_and(|at| at > cursor.last_timestamp.as_str())
+            })
+            .collect(),
+    }
+}
+
+/// Push the audit events of `space_id` not yet delivered to `sink`, in
+/// batches of `config.batch_size`. The cursor advances after each accepted
+/// batch, so a failed push is retried from that batch on the next call.
+pub async fn export_audit_events(
+    op: &Operator,
+    space_id: &str,
+    config: &OtlpExporterConfig,
+    sink: &dyn OtlpLogSink,
+) -> Result<OtlpExportReport> {
+    let space_id = audit::validate_space_id(space_id)?;
+    let events = audit::read_events(op, &space_id).await?;
+    audit::verify_chain(&events)?;
+    let mut cursor = get_export_cursor(op, &space_id).await?;
+    let pending = pending_events(events, cursor.as_ref());
+
+    let mut report = OtlpExportReport {
+        space_id: space_id.clone(),
+        exported: 0,
+        batches: 0,
+        cursor: cursor.clone(),
+    };
+    for batch in pending.chunks(config.batch_size.clamp(1, MAX_BATCH_SIZE)) {
+        sink.push(&audit_events_to_otlp(batch, &config.service_name))
+            .await
+            .map_err(|e| {
+                anyhow!(
+                    "OTLP collector rejected audit batch after {} events: {}",
+                    report.exported,
+                    e
+                )
+            })?;
+        let last = batch.last().unwrap_or(&Value::Null);
+        let next = OtlpExportCursor {
+            last_event_id: last["id"].as_str().unwrap_or_default().to_string(),
+            last_timestamp: last["timestamp"].as_str().unwrap_or_default().to_string(),
+        };
+        storage::publish_atomic(op, &cursor_path(&space_id), serde_json::to_vec(&next)?).await?;
+        cursor = Some(next);
+        report.exported += batch.len();
+        report.batches += 1;
+        report.cursor = cursor.clone();
+    }
+    Ok(report)
+}
@@ -5,6 +5,8 @@ use sha2_hmac::Sha256 as HmacSha256Digest;
 use std::collections::{HashMap, HashSet};
 use subtle::ConstantTimeEq;
 
+use crate::auth_config::AuthConfig;
+
 type HmacSha256 = Hmac<HmacSha256Digest>;
 
 const AUTH_HEADER_PARTS: usize = 2;
@@ -45,6 +47,14 @@ struct CredentialRecord {
     scopes: Vec<String>,
     scope_enforced: bool,
     service_account_id: Option<String>,
+    /// Unix seconds after which the credential is rejected.
+    expires_at: Option<f64>,
+}
+
+impl CredentialRecord {
+    fn is_expired(&self, now: f64) -> bool {
+        self.expires_at.is_some_and(|at| at < now)
+    }
 }
 
 fn parse_json_map(raw: Option<&str>) -> Map<String, Value> {
@@ -113,9 +123,28 @@ fn parse_scopes(value: Option<&Value>) -> Vec<String> {
     scopes
 }
 
+/// `expires_at` given as unix seconds or an RFC 3339 timestamp.
+fn parse_expires_at(value: Option<&Value>) -> Option<f64> {
+    match value? {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => chrono::DateTime::parse_from_rfc3339(text.trim())
+            .ok()
+            .map(|at| at.timestamp_millis() as f64 / 1000.0),
+        _ => None,
+    }
+}
+
+fn now_secs() -> f64 {
+    crate::clock::now().timestamp_millis() as f64 / 1000.0
+}
+
 fn parse_record_map(raw: Option<&str>) -> HashMap<String, CredentialRecord> {
+    record_map_from_entries(parse_json_map(raw))
+}
+
+fn record_map_from_entries(entries: Map<String, Value>) -> HashMap<String, CredentialRecord> {
     let mut records = HashMap::new();
-    for (credential, entry) in parse_json_map(raw) {
+    for (credential, entry) in entries {
         let Some(obj) = entry.as_object() else {
             continue;
         };
@@ -155,6 +184,7 @@ fn parse_record_map(raw: Option<&str>) -> HashMap<String, CredentialRecord> {
             .get("scope_enforced")
             .and_then(Value::as_bool)
             .unwrap_or(false);
+        let expires_at = parse_expires_at(obj.get("expires_at"));
 
         records.insert(
             credential,
@@ -167,6 +197,7 @@ fn parse_record_map(raw: Option<&str>) -> HashMap<String, CredentialRecord> {
                 scopes,
                 scope_enforced,
                 service_account_id,
+                expires_at,
             },
         );
     }
@@ -315,19 +346,12 @@ fn authenticate_signed_bearer(
     }))
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn authenticate_headers_core(
-    authorization: Option<&str>,
-    api_key: Option<&str>,
-    bearer_tokens_json: Option<&str>,
-    api_keys_json: Option<&str>,
-    bearer_secrets: Option<&str>,
-    active_kids_raw: Option<&str>,
-    revoked_key_ids_raw: Option<&str>,
+/// Fall back to the bootstrap token when no static bearer tokens are configured.
+fn with_bootstrap_token(
+    mut bearer_tokens: HashMap<String, CredentialRecord>,
     bootstrap_token: Option<&str>,
     bootstrap_user_id: Option<&str>,
-) -> Value {
-    let mut bearer_tokens = parse_record_map(bearer_tokens_json);
+) -> HashMap<String, CredentialRecord> {
     if bearer_tokens.is_empty() {
         if let Some(token) = bootstrap_token.filter(|value| !value.trim().is_empty()) {
             bearer_tokens.insert(
@@ -344,16 +368,81 @@ pub fn authenticate_headers_core(
                     scopes: Vec::new(),
                     scope_enforced: false,
                     service_account_id: None,
+                    expires_at: None,
                 },
             );
         }
     }
+    bearer_tokens
+}
 
-    let api_keys = parse_record_map(api_keys_json);
-    let signing_secrets = parse_key_value_map(bearer_secrets);
-    let active_kids = parse_string_set(active_kids_raw);
-    let revoked_key_ids = parse_string_set(revoked_key_ids_raw);
+#[allow(clippy::too_many_arguments)]
+pub fn authenticate_headers_core(
+    authorization: Option<&str>,
+    api_key: Option<&str>,
+    bearer_tokens_json: Option<&str>,
+    api_keys_json: Option<&str>,
+    bearer_secrets: Option<&str>,
+    active_kids_raw: Option<&str>,
+    revoked_key_ids_raw: Option<&str>,
+    bootstrap_token: Option<&str>,
+    bootstrap_user_id: Option<&str>,
+) -> Value {
+    let bearer_tokens = with_bootstrap_token(
+        parse_record_map(bearer_tokens_json),
+        bootstrap_token,
+        bootstrap_user_id,
+    );
+    authenticate_with_credentials(
+        authorization,
+        api_key,
+        &bearer_tokens,
+        &parse_record_map(api_keys_json),
+        &parse_key_value_map(bearer_secrets),
+        &parse_string_set(active_kids_raw),
+        &parse_string_set(revoked_key_ids_raw),
+    )
+}
+
+/// Same as `authenticate_headers_core`, with credentials taken from a config
+/// loaded by `auth_config::load_auth_config` instead of serialized arguments.
+pub fn authenticate_headers_with_config(
+    authorization: Option<&str>,
+    api_key: Option<&str>,
+    config: &AuthConfig,
+    bootstrap_token: Option<&str>,
+    bootstrap_user_id: Option<&str>,
+) -> Value {
+    let bearer_tokens = with_bootstrap_token(
+        record_map_from_entries(config.bearer_tokens.clone()),
+        bootstrap_token,
+        bootstrap_user_id,
+    );
+    let signing_secrets: HashMap<String, String> = config
+        .signing_keys
+        .iter()
+        .map(|(kid, secret)| (kid.clone(), secret.clone()))
+        .collect();
+    authenticate_with_credentials(
+        authorization,
+        api_key,
+        &bearer_tokens,
+        &record_map_from_entries(config.api_keys.clone()),
+        &signing_secrets,
+        &config.active_kids.iter().cloned().collect(),
+        &config.revoked_key_ids.iter().cloned().collect(),
+    )
+}
 
+fn authenticate_with_credentials(
+    authorization: Option<&str>,
+    api_key: Option<&str>,
+    bearer_tokens: &HashMap<String, CredentialRecord>,
+    api_keys: &HashMap<String, CredentialRecord>,
+    signing_secrets: &HashMap<String, String>,
+    active_kids: &HashSet<String>,
+    revoked_key_ids: &HashSet<String>,
+) -> Value {
     let result = if let Some(auth_header) = authorization.filter(|value| !value.trim().is_empty()) {
         let parts: Vec<&str> = auth_header.splitn(AUTH_HEADER_PARTS, ' ').collect();
         if parts.len() != AUTH_HEADER_PARTS || parts[0].to_lowercase() != "bearer" {
@@ -369,7 +458,7 @@ pub fn authenticate_headers_core(
                     "Missing bearer token",
                 ))
             } else if token.starts_with("v1.") {
-                authenticate_signed_bearer(token, &signing_secrets, &active_kids, &revoked_key_ids)
+                authenticate_signed_bearer(token, signing_secrets, active_kids, revoked_key_ids)
             } else {
                 let record = bearer_tokens.get(token).ok_or_else(|| {
                     CoreAuthError::new("invalid_credentials", "Invalid bearer token")
@@ -385,6 +474,11 @@ pub fn authenticate_headers_core(
                                 "revoked_key",
                                 "Bearer token has been revoked",
                             ))
+                        } else if record.is_expired(now_secs()) {
+                            Err(CoreAuthError::new(
+                                "expired_key",
+                                "Bearer token has expired",
+                            ))
                         } else if record.disabled {
                             Err(CoreAuthError::new(
                                 "disabled_identity",
@@ -414,6 +508,8 @@ pub fn authenticate_headers_core(
                         "revoked_key",
                         "API key has been revoked",
                     ))
+                } else if record.is_expired(now_secs()) {
+                    Err(CoreAuthError::new("expired_key", "API key has expired"))
                 } else if record.disabled {
                     Err(CoreAuthError::new(
                         "disabled_identity",
@@ -438,6 +534,251 @@ pub fn authenticate_headers_core(
     }
 }
 
+fn decode_signed_token_claims(token: &str) -> Option<Map<String, Value>> {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != SIGNED_TOKEN_PARTS {
+        return None;
+    }
+    let payload_bytes = URL_SAFE_NO_PAD.decode(parts[1]).ok()?;
+    serde_json::from_slice::<Value>(&payload_bytes)
+        .ok()?
+        .as_object()
+        .cloned()
+}
+
+fn signed_token_signature_valid(token: &str, secret: &str) -> bool {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != SIGNED_TOKEN_PARTS {
+        return false;
+    }
+    let Ok(signature_bytes) = URL_SAFE_NO_PAD.decode(parts[2]) else {
+        return false;
+    };
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(parts[1].as_bytes());
+    let expected = mac.finalize().into_bytes();
+    expected.len() == signature_bytes.len()
+        && bool::from(expected.as_slice().ct_eq(signature_bytes.as_slice()))
+}
+
+/// Inspect a bearer token or API key against the same configuration as
+/// `authenticate_headers_core`, for admin debugging. Unlike authentication,
+/// claims, key status and the validity window are reported even when the
+/// token is rejected; `active` and `error` match what authentication would
+/// return. `effective_scopes` is `null` when scopes are not enforced, meaning
+/// only role permissions apply.
+#[allow(clippy::too_many_arguments)]
+pub fn introspect_token(
+    token: &str,
+    bearer_tokens_json: Option<&str>,
+    api_keys_json: Option<&str>,
+    bearer_secrets: Option<&str>,
+    active_kids_raw: Option<&str>,
+    revoked_key_ids_raw: Option<&str>,
+    bootstrap_token: Option<&str>,
+    bootstrap_user_id: Option<&str>,
+) -> Value {
+    let token = token.trim();
+    let bearer_tokens = with_bootstrap_token(
+        parse_record_map(bearer_tokens_json),
+        bootstrap_token,
+        bootstrap_user_id,
+    );
+    let api_keys = parse_record_map(api_keys_json);
+    let signing_secrets = parse_key_value_map(bearer_secrets);
+    let active_kids = parse_string_set(active_kids_raw);
+    let revoked_key_ids = parse_string_set(revoked_key_ids_raw);
+
+    let (token_type, claims, signature_valid, record_expires_at) = if token.starts_with("v1.") {
+        let claims = decode_signed_token_claims(token);
+        let signature_valid = claims
+            .as_ref()
+            .and_then(|claims| claims.get("kid").and_then(Value::as_str))
+            .and_then(|kid| signing_secrets.get(kid))
+            .map(|secret| signed_token_signature_valid(token, secret))
+            .unwrap_or(false);
+        ("signed_bearer", claims, Some(signature_valid), None)
+    } else if let Some(record) = bearer_tokens.get(token) {
+        let claims = identity_from_record(record, "bearer").as_object().cloned();
+        ("static_bearer", claims, None, record.expires_at)
+    } else if let Some(record) = api_keys.get(token) {
+        let claims = identity_from_record(record, "api_key").as_object().cloned();
+        ("api_key", claims, None, record.expires_at)
+    } else {
+        ("unknown", None, None, None)
+    };
+    let now = now_secs();
+
+    let key_id = claims
+        .as_ref()
+        .and_then(|claims| {
+            claims.get(if token_type == "signed_bearer" {
+                "kid"
+            } else {
+                "key_id"
+            })
+        })
+        .and_then(Value::as_str)
+        .map(ToString::to_string);
+    let key_status = key_id.as_deref().map(|kid| {
+        if revoked_key_ids.contains(kid) {
+            "revoked"
+        } else if token_type != "signed_bearer" {
+            if record_expires_at.is_some_and(|at| at < now) {
+                "expired"
+            } else {
+                "active"
+            }
+        } else if !active_kids.is_empty() && !active_kids.contains(kid) {
+            "inactive"
+        } else if !signing_secrets.contains_key(kid) {
+            "unknown"
+        } else {
+            "active"
+        }
+    });
+
+    let claim_f64 = |name: &str| {
+        claims
+            .as_ref()
+            .and_then(|claims| claims.get(name))
+            .and_then(Value::as_f64)
+    };
+    let expires_at = claim_f64("exp").or(record_expires_at);
+    let validity = json!({
+        "issued_at": claim_f64("iat"),
+        "not_before": claim_f64("nbf"),
+        "expires_at": expires_at,
+        "expires_in_seconds": expires_at.map(|exp| exp - now),
+        "expired": expires_at.is_some_and(|exp| exp < now),
+    });
+
+    let scopes = parse_scopes(claims.as_ref().and_then(|claims| claims.get("scopes")));
+    let scope_enforced = claims
+        .as_ref()
+        .and_then(|claims| claims.get("scope_enforced"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    // Run the regular check without recording anything, so `active` and
+    // `error` cannot drift from what the request path would decide.
+    let outcome = if token.is_empty() {
+        authenticate_headers_core(None, None, None, None, None, None, None, None, None)
+    } else if token_type == "api_key" {
+        authenticate_headers_core(
+            None,
+            Some(token),
+            bearer_tokens_json,
+            api_keys_json,
+            bearer_secrets,
+            active_kids_raw,
+            revoked_key_ids_raw,
+            bootstrap_token,
+            bootstrap_user_id,
+        )
+    } else {
+        authenticate_headers_core(
+            Some(&format!("Bearer {}", token)),
+            None,
+            bearer_tokens_json,
+            api_keys_json,
+            bearer_secrets,
+            active_kids_raw,
+            revoked_key_ids_raw,
+            bootstrap_token,
+            bootstrap_user_id,
+        )
+    };
+
+    json!({
+        "active": outcome.get("ok").and_then(Value::as_bool).unwrap_or(false),
+        "token_type": token_type,
+        "claims": claims,
+        "signature_valid": signature_valid,
+        "key_id": key_id,
+        "key_status": key_status,
+        "validity": validity,
+        "scopes": scopes,
+        "scope_enforced": scope_enforced,
+        "effective_scopes": if scope_enforced { Some(&scopes) } else { None },
+        "identity": outcome.get("identity").cloned().unwrap_or(Value::Null),
+        "error": outcome.get("error").cloned().unwrap_or(Value::Null),
+    })
+}
+
+/// Credentials that expire within `window_secs` from now, or already have,
+/// soonest first, so they can be rotated before clients start failing.
+/// Revoked and disabled credentials are left out. Entries carry the key id
+/// and owner only, never the credential itself.
+pub fn list_expiring_keys(
+    bearer_tokens_json: Option<&str>,
+    api_keys_json: Option<&str>,
+    revoked_key_ids_raw: Option<&str>,
+    window_secs: f64,
+) -> Value {
+    expiring_keys(
+        &parse_record_map(bearer_tokens_json),
+        &parse_record_map(api_keys_json),
+        &parse_string_set(revoked_key_ids_raw),
+        window_secs,
+    )
+}
+
+/// Same as `list_expiring_keys`, with credentials taken from a config loaded
+/// by `auth_config::load_auth_config`.
+pub fn list_expiring_keys_with_config(config: &AuthConfig, window_secs: f64) -> Value {
+    expiring_keys(
+        &record_map_from_entries(config.bearer_tokens.clone()),
+        &record_map_from_entries(config.api_keys.clone()),
+        &config.revoked_key_ids.iter().cloned().collect(),
+        window_secs,
+    )
+}
+
+fn expiring_keys(
+    bearer_tokens: &HashMap<String, CredentialRecord>,
+    api_keys: &HashMap<String, CredentialRecord>,
+    revoked_key_ids: &HashSet<String>,
+    window_secs: f64,
+) -> Value {
+    let now = now_secs();
+    let mut expiring: Vec<(f64, Value)> = [("bearer", bearer_tokens), ("api_key", api_keys)]
+        .into_iter()
+        .flat_map(|(kind, records)| records.values().map(move |record| (kind, record)))
+        .filter(|(_, record)| {
+            !record.disabled
+                && !record
+                    .key_id
+                    .as_ref()
+                    .is_some_and(|key_id| revoked_key_ids.contains(key_id))
+        })
+        .filter_map(|(kind, record)| {
+            let expires_at = record.expires_at.filter(|at| *at <= now + window_secs)?;
+            Some((
+                expires_at,
+                json!({
+                    "credential_type": kind,
+                    "key_id": record.key_id,
+                    "user_id": record.user_id,
+                    "principal_type": record.principal_type,
+                    "service_account_id": record.service_account_id,
+                    "expires_at": expires_at,
+                    "expires_in_seconds": expires_at - now,
+                    "expired": record.is_expired(now),
+                }),
+            ))
+        })
+        .collect();
+    expiring.sort_by(|a, b| {
+        a.0.partial_cmp(&b.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.1["key_id"].as_str().cmp(&b.1["key_id"].as_str()))
+    });
+    Value::Array(expiring.into_iter().map(|(_, item)| item).collect())
+}
+
 pub fn auth_capabilities_snapshot(
     bearer_tokens_json: Option<&str>,
     api_keys_json: Option<&str>,
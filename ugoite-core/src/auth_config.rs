@@ -0,0 +1,302 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::audit;
+use crate::auth;
+use crate::storage;
+
+const BEARER_TOKENS_PATH: &str = "auth/bearer_tokens.json";
+const API_KEYS_PATH: &str = "auth/api_keys.json";
+const SIGNING_KEYS_PATH: &str = "auth/signing_keys.json";
+const ACTIVE_KIDS_PATH: &str = "auth/active_kids.json";
+const REVOKED_KEY_IDS_PATH: &str = "auth/revoked_key_ids.json";
+/// How long a loaded config is served from memory before storage is read again.
+const AUTH_CONFIG_TTL: Duration = Duration::from_secs(5);
+
+/// Credentials and signing key state stored under the `auth/` prefix. Maps
+/// use the same shapes as the JSON arguments of `authenticate_headers_core`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AuthConfig {
+    /// Static bearer token -> credential record.
+    #[serde(default)]
+    pub bearer_tokens: Map<String, Value>,
+    /// API key -> credential record.
+    #[serde(default)]
+    pub api_keys: Map<String, Value>,
+    /// Signing key id -> HMAC secret for signed bearer tokens.
+    #[serde(default)]
+    pub signing_keys: BTreeMap<String, String>,
+    #[serde(default)]
+    pub active_kids: Vec<String>,
+    #[serde(default)]
+    pub revoked_key_ids: Vec<String>,
+    /// Hash of the stored files; changes whenever any of them does.
+    #[serde(default)]
+    pub etag: String,
+}
+
+struct CachedConfig {
+    config: Arc<AuthConfig>,
+    loaded_at: Instant,
+}
+
+static CONFIG_CACHE: OnceLock<Mutex<HashMap<String, CachedConfig>>> = OnceLock::new();
+
+fn config_cache() -> Result<std::sync::MutexGuard<'static, HashMap<String, CachedConfig>>> {
+    CONFIG_CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .map_err(|_| anyhow!("auth config cache lock poisoned"))
+}
+
+fn cache_key(op: &Operator) -> String {
+    let info = op.info();
+    format!("{}:{}:{}", info.scheme(), info.name(), info.root())
+}
+
+async fn read_optional(op: &Operator, path: &str) -> Result<Option<Vec<u8>>> {
+    match op.read(path).await {
+        Ok(bytes) => Ok(Some(bytes.to_vec())),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn parse_file<T: serde::de::DeserializeOwned + Default>(
+    path: &str,
+    bytes: &Option<Vec<u8>>,
+) -> Result<T> {
+    match bytes {
+        Some(bytes) => {
+            serde_json::from_slice(bytes).map_err(|e| anyhow!("{} is malformed: {}", path, e))
+        }
+        None => Ok(T::default()),
+    }
+}
+
+/// Read the `auth/` files straight from storage, bypassing the cache.
+pub async fn read_auth_config(op: &Operator) -> Result<AuthConfig> {
+    let mut hasher = Sha256::new();
+    let mut files = Vec::new();
+    for path in [
+        BEARER_TOKENS_PATH,
+        API_KEYS_PATH,
+        SIGNING_KEYS_PATH,
+        ACTIVE_KIDS_PATH,
+        REVOKED_KEY_IDS_PATH,
+    ] {
+        let bytes = read_optional(op, path).await?;
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        if let Some(bytes) = &bytes {
+            hasher.update(bytes);
+        }
+        hasher.update([0]);
+        files.push(bytes);
+    }
+    Ok(AuthConfig {
+        bearer_tokens: parse_file(BEARER_TOKENS_PATH, &files[0])?,
+        api_keys: parse_file(API_KEYS_PATH, &files[1])?,
+        signing_keys: parse_file(SIGNING_KEYS_PATH, &files[2])?,
+        active_kids: parse_file(ACTIVE_KIDS_PATH, &files[3])?,
+        revoked_key_ids: parse_file(REVOKED_KEY_IDS_PATH, &files[4])?,
+        etag: hex::encode(hasher.finalize()),
+    })
+}
+
+/// The stored auth config, reused for a few seconds between storage reads.
+/// Callers can compare `etag` to notice credential changes.
+pub async fn load_auth_config(op: &Operator) -> Result<Arc<AuthConfig>> {
+    let key = cache_key(op);
+    if let Some(cached) = config_cache()?.get(&key) {
+        if cached.loaded_at.elapsed() < AUTH_CONFIG_TTL {
+            return Ok(cached.config.clone());
+        }
+    }
+    let fresh = read_auth_config(op).await?;
+    let mut cache = config_cache()?;
+    let config = match cache.get(&key) {
+        // Keep the same allocation when nothing changed.
+        Some(cached) if cached.config.etag == fresh.etag => cached.config.clone(),
+        _ => Arc::new(fresh),
+    };
+    cache.insert(
+        key,
+        CachedConfig {
+            config: config.clone(),
+            loaded_at: Instant::now(),
+        },
+    );
+    Ok(config)
+}
+
+/// Drop the cached config so the next `load_auth_config` reads storage.
+pub fn invalidate_auth_config_cache(op: &Operator) -> Result<()> {
+    config_cache()?.remove(&cache_key(op));
+    Ok(())
+}
+
+/// Replace the stored auth config. With `if_match`, the write is refused
+/// unless the stored config still has that etag. Returns the new etag.
+pub async fn save_auth_config(
+    op: &Operator,
+    config: &AuthConfig,
+    if_match: Option<&str>,
+) -> Result<String> {
+    if let Some(expected) = if_match {
+        let current = read_auth_config(op).await?;
+        if current.etag != expected {
+            return Err(anyhow!(
+                "Auth config changed: expected etag {}, found {}",
+                expected,
+                current.etag
+            ));
+        }
+    }
+    let files: [(&str, Vec<u8>); 5] = [
+        (
+            BEARER_TOKENS_PATH,
+            serde_json::to_vec_pretty(&config.bearer_tokens)?,
+        ),
+        (API_KEYS_PATH, serde_json::to_vec_pretty(&config.api_keys)?),
+        (
+            SIGNING_KEYS_PATH,
+            serde_json::to_vec_pretty(&config.signing_keys)?,
+        ),
+        (
+            ACTIVE_KIDS_PATH,
+            serde_json::to_vec_pretty(&config.active_kids)?,
+        ),
+        (
+            REVOKED_KEY_IDS_PATH,
+            serde_json::to_vec_pretty(&config.revoked_key_ids)?,
+        ),
+    ];
+    for (path, data) in files {
+        storage::publish_atomic(op, path, data).await?;
+    }
+    invalidate_auth_config_cache(op)?;
+    Ok(load_auth_config(op).await?.etag.clone())
+}
+
+/// Where and with which request details audited authentication denials are recorded.
+#[derive(Debug, Clone, Default)]
+pub struct AuthAuditContext {
+    /// Space whose audit chain receives the events, normally the admin space.
+    pub audit_space_id: String,
+    pub request_method: Option<String>,
+    pub request_path: Option<String>,
+    pub request_id: Option<String>,
+    pub client_host: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// Short SHA-256 prefix identifying a credential without storing it.
+pub fn credential_fingerprint(credential: &str) -> String {
+    hex::encode(Sha256::digest(credential.trim().as_bytes()))[..12].to_string()
+}
+
+fn presented_credential<'a>(
+    authorization: Option<&'a str>,
+    api_key: Option<&'a str>,
+) -> Option<(&'static str, &'a str)> {
+    if let Some(header) = authorization.filter(|value| !value.trim().is_empty()) {
+        let token = header
+            .split_once(' ')
+            .map(|(_, token)| token)
+            .unwrap_or(header);
+        return Some(("bearer", token));
+    }
+    api_key
+        .filter(|value| !value.trim().is_empty())
+        .map(|key| ("api_key", key))
+}
+
+async fn audit_auth_failure(
+    op: &Operator,
+    audit: &AuthAuditContext,
+    outcome: &str,
+    code: &str,
+    credential: Option<(&str, &str)>,
+) -> Result<()> {
+    let mut metadata = Map::new();
+    metadata.insert("code".to_string(), Value::from(code));
+    if let Some((auth_method, credential)) = credential {
+        metadata.insert("auth_method".to_string(), Value::from(auth_method));
+        metadata.insert(
+            "credential_fingerprint".to_string(),
+            Value::from(credential_fingerprint(credential)),
+        );
+    }
+    if let Some(client_host) = &audit.client_host {
+        metadata.insert("client_host".to_string(), Value::from(client_host.as_str()));
+    }
+    if let Some(user_agent) = &audit.user_agent {
+        metadata.insert("user_agent".to_string(), Value::from(user_agent.as_str()));
+    }
+    audit::append_audit_event(
+        op,
+        &audit.audit_space_id,
+        &json!({
+            "action": "auth.authenticate",
+            "actor_user_id": "anonymous",
+            "outcome": outcome,
+            "request_method": audit.request_method,
+            "request_path": audit.request_path,
+            "request_id": audit.request_id,
+            "metadata": metadata,
+        }),
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Authenticate against the stored config like `authenticate_headers_with_config`.
+/// With `audit`, denials are appended to that space's audit chain with the
+/// error code and a fingerprint of the presented credential, and a config
+/// that cannot be loaded is recorded as an `error` outcome before failing.
+/// Successful authentications are not recorded here.
+pub async fn authenticate_headers_audited(
+    op: &Operator,
+    authorization: Option<&str>,
+    api_key: Option<&str>,
+    bootstrap_token: Option<&str>,
+    bootstrap_user_id: Option<&str>,
+    audit: Option<&AuthAuditContext>,
+) -> Result<Value> {
+    let credential = presented_credential(authorization, api_key);
+    let config = match load_auth_config(op).await {
+        Ok(config) => config,
+        Err(err) => {
+            if let Some(audit) = audit {
+                audit_auth_failure(op, audit, "error", "auth_config_unavailable", credential)
+                    .await?;
+            }
+            return Err(err);
+        }
+    };
+    let result = auth::authenticate_headers_with_config(
+        authorization,
+        api_key,
+        &config,
+        bootstrap_token,
+        bootstrap_user_id,
+    );
+    if let Some(audit) = audit {
+        if result.get("ok").and_then(Value::as_bool) != Some(true) {
+            let code = result
+                .pointer("/error/code")
+                .and_then(Value::as_str)
+                .unwrap_or("invalid_credentials");
+            audit_auth_failure(op, audit, "deny", code, credential).await?;
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,426 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::entry_lock::LOCK_ADMIN_SCOPE;
+use crate::field_crypto::SECRET_READ_SCOPE;
+use crate::legal_hold::LEGAL_HOLD_SCOPE;
+use crate::principal_data::PRINCIPAL_ERASE_SCOPE;
+use SpaceAction::*;
+
+/// A principal's role in a space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpaceRole {
+    Owner,
+    Admin,
+    Editor,
+    Viewer,
+    Service,
+}
+
+impl SpaceRole {
+    pub const ALL: [SpaceRole; 5] = [
+        SpaceRole::Owner,
+        SpaceRole::Admin,
+        SpaceRole::Editor,
+        SpaceRole::Viewer,
+        SpaceRole::Service,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SpaceRole::Owner => "owner",
+            SpaceRole::Admin => "admin",
+            SpaceRole::Editor => "editor",
+            SpaceRole::Viewer => "viewer",
+            SpaceRole::Service => "service",
+        }
+    }
+
+    /// Space actions this role may perform before scopes and form ACLs apply.
+    pub fn permissions(self) -> &'static [SpaceAction] {
+        match self {
+            SpaceRole::Owner | SpaceRole::Admin => &SpaceAction::ALL,
+            SpaceRole::Editor => &[
+                SpaceList, SpaceRead, EntryRead, EntryWrite, FormRead, FormWrite, AssetRead,
+                AssetWrite, SqlRead, SqlWrite,
+            ],
+            SpaceRole::Viewer => &[
+                SpaceList, SpaceRead, EntryRead, FormRead, AssetRead, SqlRead,
+            ],
+            SpaceRole::Service => &[
+                SpaceList, SpaceRead, EntryRead, EntryWrite, FormRead, AssetRead, AssetWrite,
+                SqlRead, SqlWrite,
+            ],
+        }
+    }
+
+    pub fn allows(self, action: SpaceAction) -> bool {
+        self.permissions().contains(&action)
+    }
+
+    /// Owners and admins are not restricted by form ACLs.
+    pub fn bypasses_form_acl(self) -> bool {
+        matches!(self, SpaceRole::Owner | SpaceRole::Admin)
+    }
+}
+
+impl std::str::FromStr for SpaceRole {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        SpaceRole::ALL
+            .into_iter()
+            .find(|role| role.as_str() == value.trim())
+            .ok_or_else(|| anyhow!("Unknown space role: {}", value))
+    }
+}
+
+/// A role-gated action; its name doubles as the API scope that grants it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpaceAction {
+    SpaceList,
+    SpaceRead,
+    SpaceAdmin,
+    EntryRead,
+    EntryWrite,
+    FormRead,
+    FormWrite,
+    AssetRead,
+    AssetWrite,
+    SqlRead,
+    SqlWrite,
+}
+
+impl SpaceAction {
+    pub const ALL: [SpaceAction; 11] = [
+        SpaceAction::SpaceList,
+        SpaceAction::SpaceRead,
+        SpaceAction::SpaceAdmin,
+        SpaceAction::EntryRead,
+        SpaceAction::EntryWrite,
+        SpaceAction::FormRead,
+        SpaceAction::FormWrite,
+        SpaceAction::AssetRead,
+        SpaceAction::AssetWrite,
+        SpaceAction::SqlRead,
+        SpaceAction::SqlWrite,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SpaceAction::SpaceList => "space_list",
+            SpaceAction::SpaceRead => "space_read",
+            SpaceAction::SpaceAdmin => "space_admin",
+            SpaceAction::EntryRead => "entry_read",
+            SpaceAction::EntryWrite => "entry_write",
+            SpaceAction::FormRead => "form_read",
+            SpaceAction::FormWrite => "form_write",
+            SpaceAction::AssetRead => "asset_read",
+            SpaceAction::AssetWrite => "asset_write",
+            SpaceAction::SqlRead => "sql_read",
+            SpaceAction::SqlWrite => "sql_write",
+        }
+    }
+}
+
+/// Form ACL list an operation is additionally checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormAclCheck {
+    /// `read_principals` of the entry's form.
+    Read,
+    /// `write_principals` of the entry's form.
+    Write,
+}
+
+/// What an operation requires of the calling principal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OperationRule {
+    pub operation: &'static str,
+    pub description: &'static str,
+    pub actions: &'static [SpaceAction],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_acl: Option<FormAclCheck>,
+    /// Scope checked by core itself, whether or not the identity's scopes
+    /// are otherwise enforced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra_scope: Option<&'static str>,
+    /// Whether the actions are checked in the reserved admin space rather
+    /// than the target space.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub admin_space: bool,
+}
+
+const fn op(
+    operation: &'static str,
+    description: &'static str,
+    actions: &'static [SpaceAction],
+) -> OperationRule {
+    OperationRule {
+        operation,
+        description,
+        actions,
+        form_acl: None,
+        extra_scope: None,
+        admin_space: false,
+    }
+}
+
+const fn with_form_acl(rule: OperationRule, check: FormAclCheck) -> OperationRule {
+    OperationRule {
+        form_acl: Some(check),
+        ..rule
+    }
+}
+
+const fn with_scope(rule: OperationRule, scope: &'static str) -> OperationRule {
+    OperationRule {
+        extra_scope: Some(scope),
+        ..rule
+    }
+}
+
+/// Every authorized operation of core and the API layer, sorted by name.
+const OPERATIONS: &[OperationRule] = &[
+    op("asset.delete", "Delete an asset.", &[AssetWrite]),
+    op("asset.list", "List assets.", &[AssetRead]),
+    op("asset.upload", "Upload an asset.", &[AssetWrite]),
+    op(
+        "audit.list_events",
+        "Read the space audit log.",
+        &[SpaceAdmin],
+    ),
+    with_form_acl(
+        op("entry.create", "Create an entry.", &[EntryWrite]),
+        FormAclCheck::Write,
+    ),
+    with_form_acl(
+        op("entry.delete", "Delete an entry.", &[EntryWrite]),
+        FormAclCheck::Write,
+    ),
+    with_form_acl(
+        op("entry.history", "Read an entry's revisions.", &[EntryRead]),
+        FormAclCheck::Read,
+    ),
+    op("entry.list", "List and filter entries.", &[EntryRead]),
+    with_scope(
+        with_form_acl(
+            op(
+                "entry.lock_override",
+                "Break or take over another principal's entry lock.",
+                &[EntryWrite],
+            ),
+            FormAclCheck::Write,
+        ),
+        LOCK_ADMIN_SCOPE,
+    ),
+    with_form_acl(
+        op("entry.read", "Read an entry.", &[EntryRead]),
+        FormAclCheck::Read,
+    ),
+    with_scope(
+        with_form_acl(
+            op(
+                "entry.read_secret_fields",
+                "Read secret form fields in plaintext.",
+                &[EntryRead],
+            ),
+            FormAclCheck::Read,
+        ),
+        SECRET_READ_SCOPE,
+    ),
+    with_form_acl(
+        op(
+            "entry.restore_revision",
+            "Restore an earlier revision of an entry.",
+            &[EntryWrite],
+        ),
+        FormAclCheck::Write,
+    ),
+    with_form_acl(
+        op("entry.update", "Update an entry.", &[EntryWrite]),
+        FormAclCheck::Write,
+    ),
+    op(
+        "form.create",
+        "Create or change a form definition.",
+        &[FormWrite, SpaceAdmin],
+    ),
+    with_form_acl(
+        op("form.list", "List forms.", &[FormRead]),
+        FormAclCheck::Read,
+    ),
+    with_form_acl(
+        op("form.read", "Read a form definition.", &[FormRead]),
+        FormAclCheck::Read,
+    ),
+    op(
+        "form.types",
+        "List the available form field types.",
+        &[SpaceRead],
+    ),
+    with_scope(
+        op(
+            "legal_hold.manage",
+            "Place or release legal holds.",
+            &[SpaceAdmin],
+        ),
+        LEGAL_HOLD_SCOPE,
+    ),
+    op(
+        "member.invite",
+        "Invite a member to the space.",
+        &[SpaceAdmin],
+    ),
+    op("member.list", "List space members.", &[SpaceRead]),
+    op(
+        "member.revoke",
+        "Remove a member from the space.",
+        &[SpaceAdmin],
+    ),
+    op(
+        "member.update_role",
+        "Change a member's role.",
+        &[SpaceAdmin],
+    ),
+    with_scope(
+        op(
+            "principal_data.erase",
+            "Erase a principal's personal data.",
+            &[SpaceAdmin],
+        ),
+        PRINCIPAL_ERASE_SCOPE,
+    ),
+    op("search.query", "Run a structured query.", &[EntryRead]),
+    op("search.text", "Run a full-text search.", &[EntryRead]),
+    op(
+        "service_account.list",
+        "List service accounts and keys nearing expiry.",
+        &[SpaceAdmin],
+    ),
+    op(
+        "service_account.manage",
+        "Create service accounts and issue, rotate or revoke their keys.",
+        &[SpaceAdmin],
+    ),
+    OperationRule {
+        admin_space: true,
+        ..op("space.create", "Create a space.", &[SpaceAdmin])
+    },
+    op("space.list", "List spaces.", &[SpaceList]),
+    op("space.read", "Read space settings.", &[SpaceRead]),
+    op(
+        "space.test_connection",
+        "Test the space's storage connection.",
+        &[SpaceAdmin],
+    ),
+    op("space.update", "Change space settings.", &[SpaceAdmin]),
+    op("sql.delete", "Delete a saved query.", &[SqlWrite]),
+    op("sql.list", "List saved queries.", &[SqlRead]),
+    op("sql.read", "Read a saved query.", &[SqlRead]),
+    op("sql.save", "Create or update a saved query.", &[SqlWrite]),
+    op(
+        "sql_session.run",
+        "Run SQL and page through its results.",
+        &[SqlRead],
+    ),
+];
+
+pub fn list_operation_rules() -> &'static [OperationRule] {
+    OPERATIONS
+}
+
+pub fn operation_rule(operation: &str) -> Option<&'static OperationRule> {
+    OPERATIONS.iter().find(|rule| rule.operation == operation)
+}
+
+/// The calling principal, as resolved for one space.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthzIdentity {
+    /// `None` when the principal is not an active member.
+    #[serde(default)]
+    pub role: Option<SpaceRole>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Whether `scopes` limit the role's actions, as for API keys.
+    #[serde(default)]
+    pub scope_enforced: bool,
+}
+
+impl AuthzIdentity {
+    fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|granted| granted == scope)
+    }
+
+    /// Whether the role and scopes admit `rule`. Form ACLs can still deny a
+    /// principal whose role does not bypass them.
+    pub fn permits(&self, rule: &OperationRule) -> bool {
+        let Some(role) = self.role else {
+            return false;
+        };
+        rule.actions.iter().all(|action| {
+            role.allows(*action) && (!self.scope_enforced || self.has_scope(action.as_str()))
+        }) && rule.extra_scope.is_none_or(|scope| self.has_scope(scope))
+    }
+}
+
+/// Scopes an operation needs when scopes are enforced.
+fn required_scopes(rule: &OperationRule) -> Vec<&'static str> {
+    let mut scopes: Vec<&'static str> = rule.actions.iter().map(|a| a.as_str()).collect();
+    scopes.extend(rule.extra_scope);
+    scopes
+}
+
+/// Every operation with the roles and scopes it requires, generated from the
+/// same role table and operation rules used for enforcement.
+///
+/// With an `identity`, each operation also reports whether that principal may
+/// perform it, and whether a form ACL may still refuse it.
+pub fn authz_matrix_snapshot(identity: Option<&AuthzIdentity>) -> Value {
+    let roles: serde_json::Map<String, Value> = SpaceRole::ALL
+        .iter()
+        .map(|role| {
+            let actions: Vec<&str> = role.permissions().iter().map(|a| a.as_str()).collect();
+            (role.as_str().to_string(), json!(actions))
+        })
+        .collect();
+    let operations: Vec<Value> = OPERATIONS
+        .iter()
+        .map(|rule| {
+            let allowed_roles: Vec<&str> = SpaceRole::ALL
+                .iter()
+                .filter(|role| rule.actions.iter().all(|action| role.allows(*action)))
+                .map(|role| role.as_str())
+                .collect();
+            let mut value = json!({
+                "operation": rule.operation,
+                "description": rule.description,
+                "actions": rule.actions,
+                "roles": allowed_roles,
+                "scopes": required_scopes(rule),
+                "form_acl": rule.form_acl,
+                "extra_scope": rule.extra_scope,
+                "admin_space": rule.admin_space,
+            });
+            if let Some(identity) = identity {
+                let allowed = identity.permits(rule);
+                value["allowed"] = json!(allowed);
+                value["form_acl_applies"] = json!(
+                    allowed
+                        && rule.form_acl.is_some()
+                        && !identity.role.is_some_and(SpaceRole::bypasses_form_acl)
+                );
+            }
+            value
+        })
+        .collect();
+    json!({
+        "version": "authz-matrix-v1",
+        "roles": roles,
+        "operations": operations,
+        "identity": identity,
+    })
+}
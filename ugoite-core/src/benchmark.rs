@@ -0,0 +1,324 @@
+use anyhow::{anyhow, Result};
+use futures::stream::{self, StreamExt};
+use opendal::Operator;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use crate::entry;
+use crate::index;
+use crate::integrity::RealIntegrityProvider;
+use crate::sample_data::{self, SampleDataOptions, SampleDataSummary};
+use crate::search;
+
+const SEARCH_TERMS: &[&str] = &["status", "review", "site", "energy", "report", "plan"];
+const BENCH_SQL: &str = "SELECT * FROM entries LIMIT 20";
+
+/// Relative weights of each operation kind in a workload.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkloadMix {
+    #[serde(default)]
+    pub read: u32,
+    #[serde(default)]
+    pub write: u32,
+    #[serde(default)]
+    pub search: u32,
+    #[serde(default)]
+    pub sql: u32,
+}
+
+impl Default for WorkloadMix {
+    fn default() -> Self {
+        Self {
+            read: 60,
+            write: 15,
+            search: 15,
+            sql: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Read,
+    Write,
+    Search,
+    Sql,
+}
+
+impl Operation {
+    fn name(self) -> &'static str {
+        match self {
+            Operation::Read => "read",
+            Operation::Write => "write",
+            Operation::Search => "search",
+            Operation::Sql => "sql",
+        }
+    }
+}
+
+impl WorkloadMix {
+    fn total(&self) -> u32 {
+        self.read + self.write + self.search + self.sql
+    }
+
+    fn pick(&self, rng: &mut StdRng) -> Operation {
+        let mut roll = rng.random_range(0..self.total());
+        for (weight, operation) in [
+            (self.read, Operation::Read),
+            (self.write, Operation::Write),
+            (self.search, Operation::Search),
+        ] {
+            if roll < weight {
+                return operation;
+            }
+            roll -= weight;
+        }
+        Operation::Sql
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BenchmarkConfig {
+    pub space_id: String,
+    #[serde(default)]
+    pub scenario: String,
+    #[serde(default = "default_entry_count")]
+    pub entry_count: usize,
+    #[serde(default = "default_operations")]
+    pub operations: usize,
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    #[serde(default)]
+    pub mix: WorkloadMix,
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_entry_count() -> usize {
+    200
+}
+
+fn default_operations() -> usize {
+    500
+}
+
+fn default_concurrency() -> usize {
+    8
+}
+
+impl BenchmarkConfig {
+    pub fn new(space_id: &str) -> Self {
+        Self {
+            space_id: space_id.to_string(),
+            scenario: String::new(),
+            entry_count: default_entry_count(),
+            operations: default_operations(),
+            concurrency: default_concurrency(),
+            mix: WorkloadMix::default(),
+            seed: None,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.concurrency == 0 {
+            return Err(anyhow!("concurrency must be at least 1"));
+        }
+        if self.mix.total() == 0 {
+            return Err(anyhow!(
+                "workload mix must give at least one operation a weight"
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub errors: usize,
+    pub min_ms: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+pub fn summarize_latencies(latencies_ms: &[f64], errors: usize) -> LatencySummary {
+    let mut sorted = latencies_ms.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let count = sorted.len();
+    LatencySummary {
+        count,
+        errors,
+        min_ms: sorted.first().copied().unwrap_or(0.0),
+        mean_ms: if count == 0 {
+            0.0
+        } else {
+            sorted.iter().sum::<f64>() / count as f64
+        },
+        p50_ms: percentile(&sorted, 50.0),
+        p90_ms: percentile(&sorted, 90.0),
+        p99_ms: percentile(&sorted, 99.0),
+        max_ms: sorted.last().copied().unwrap_or(0.0),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BenchmarkReport {
+    pub config: BenchmarkConfig,
+    pub populate_ms: f64,
+    pub workload_ms: f64,
+    pub ops_per_sec: f64,
+    /// Latencies keyed by operation kind, plus `all` across every kind.
+    pub operations: BTreeMap<String, LatencySummary>,
+}
+
+/// Create the benchmark space from a sample-data scenario.
+pub async fn populate_space(
+    op: &Operator,
+    root_uri: &str,
+    config: &BenchmarkConfig,
+) -> Result<SampleDataSummary> {
+    let options = SampleDataOptions {
+        space_id: config.space_id.clone(),
+        scenario: if config.scenario.trim().is_empty() {
+            sample_data::DEFAULT_SCENARIO.to_string()
+        } else {
+            config.scenario.clone()
+        },
+        entry_count: config.entry_count,
+        seed: config.seed,
+        owner_user_id: None,
+        fixture: false,
+    };
+    sample_data::create_sample_space(op, root_uri, &options).await
+}
+
+async fn run_operation(
+    op: &Operator,
+    ws_path: &str,
+    integrity: &RealIntegrityProvider,
+    entry_ids: &[String],
+    operation: Operation,
+    seq: usize,
+    pick: usize,
+) -> Result<()> {
+    match operation {
+        Operation::Read => {
+            let entry_id = entry_ids
+                .get(pick % entry_ids.len().max(1))
+                .ok_or_else(|| anyhow!("No entries to read"))?;
+            entry::get_entry(op, ws_path, entry_id).await?;
+        }
+        Operation::Write => {
+            let content = format!(
+                "---\nform: Entry\n---\n# Benchmark {seq}\n\n## Body\nGenerated by the benchmark workload.\n"
+            );
+            entry::create_entry(
+                op,
+                ws_path,
+                &format!("bench-{}-{seq}", uuid::Uuid::new_v4().simple()),
+                &content,
+                "benchmark",
+                integrity,
+            )
+            .await?;
+        }
+        Operation::Search => {
+            search::search_entries(op, ws_path, SEARCH_TERMS[pick % SEARCH_TERMS.len()]).await?;
+        }
+        Operation::Sql => {
+            index::execute_sql_query(op, ws_path, BENCH_SQL).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Run the configured mix against an existing space and summarize latencies.
+pub async fn run_workload(op: &Operator, config: &BenchmarkConfig) -> Result<BenchmarkReport> {
+    config.validate()?;
+    let ws_path = format!("spaces/{}", config.space_id);
+    let integrity = RealIntegrityProvider::from_space(op, &config.space_id).await?;
+    let entry_ids: Vec<String> = entry::list_entries(op, &ws_path)
+        .await?
+        .iter()
+        .filter_map(|entry| entry.get("id").and_then(Value::as_str).map(str::to_string))
+        .collect();
+
+    let mut rng = StdRng::seed_from_u64(config.seed.unwrap_or_else(rand::random::<u64>));
+    let plan: Vec<(Operation, usize)> = (0..config.operations)
+        .map(|_| (config.mix.pick(&mut rng), rng.random_range(0..usize::MAX)))
+        .collect();
+
+    let started = Instant::now();
+    let results: Vec<(Operation, f64, bool)> = stream::iter(plan.into_iter().enumerate())
+        .map(|(seq, (operation, pick))| {
+            let (ws_path, integrity, entry_ids) = (&ws_path, &integrity, &entry_ids);
+            async move {
+                let op_started = Instant::now();
+                let ok = run_operation(op, ws_path, integrity, entry_ids, operation, seq, pick)
+                    .await
+                    .is_ok();
+                (operation, op_started.elapsed().as_secs_f64() * 1000.0, ok)
+            }
+        })
+        .buffer_unordered(config.concurrency)
+        .collect()
+        .await;
+    let workload_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+    let mut by_kind: BTreeMap<String, (Vec<f64>, usize)> = BTreeMap::new();
+    for (operation, elapsed_ms, ok) in &results {
+        for key in [operation.name(), "all"] {
+            let (latencies, errors) = by_kind.entry(key.to_string()).or_default();
+            if *ok {
+                latencies.push(*elapsed_ms);
+            } else {
+                *errors += 1;
+            }
+        }
+    }
+    Ok(BenchmarkReport {
+        config: config.clone(),
+        populate_ms: 0.0,
+        workload_ms,
+        ops_per_sec: if workload_ms > 0.0 {
+            results.len() as f64 / (workload_ms / 1000.0)
+        } else {
+            0.0
+        },
+        operations: by_kind
+            .into_iter()
+            .map(|(kind, (latencies, errors))| (kind, summarize_latencies(&latencies, errors)))
+            .collect(),
+    })
+}
+
+/// Populate a fresh space and run the workload against it.
+pub async fn run_benchmark(
+    op: &Operator,
+    root_uri: &str,
+    config: &BenchmarkConfig,
+) -> Result<BenchmarkReport> {
+    config.validate()?;
+    let started = Instant::now();
+    populate_space(op, root_uri, config).await?;
+    let populate_ms = started.elapsed().as_secs_f64() * 1000.0;
+    let mut report = run_workload(op, config).await?;
+    report.populate_ms = populate_ms;
+    Ok(report)
+}
@@ -0,0 +1,450 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::watchers::{WatchEventKind, WatchNotification};
+
+/// Longest comment or error excerpt included in a message.
+const EXCERPT_MAX_CHARS: usize = 500;
+/// Discord rejects embed titles longer than this.
+const DISCORD_TITLE_MAX_CHARS: usize = 256;
+
+const COLOR_CREATED: u32 = 0x2EB67D;
+const COLOR_UPDATED: u32 = 0xECB22E;
+const COLOR_DELETED: u32 = 0x868686;
+const COLOR_COMMENT: u32 = 0x36C5F0;
+const COLOR_FAILED: u32 = 0xE01E5A;
+const COLOR_REMINDER: u32 = 0x4A154B;
+
+/// Chat service a notification is formatted for.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatPlatform {
+    /// Slack Block Kit message, for `chat.postMessage` or incoming webhooks.
+    Slack,
+    /// Discord webhook message with one embed.
+    Discord,
+}
+
+impl std::str::FromStr for ChatPlatform {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "slack" => Ok(Self::Slack),
+            "discord" => Ok(Self::Discord),
+            other => Err(anyhow!("Unknown chat platform: {}", other)),
+        }
+    }
+}
+
+/// A core event worth posting to chat. Timestamps are unix seconds.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatEvent {
+    EntryCreated {
+        space_id: String,
+        entry_id: String,
+        entry_title: String,
+        form: String,
+        #[serde(default)]
+        author: Option<String>,
+        timestamp: f64,
+    },
+    EntryUpdated {
+        space_id: String,
+        entry_id: String,
+        entry_title: String,
+        form: String,
+        #[serde(default)]
+        author: Option<String>,
+        timestamp: f64,
+    },
+    EntryDeleted {
+        space_id: String,
+        entry_id: String,
+        entry_title: String,
+        form: String,
+        #[serde(default)]
+        author: Option<String>,
+        timestamp: f64,
+    },
+    ReminderDue {
+        space_id: String,
+        entry_id: String,
+        entry_title: String,
+        form: String,
+        /// Date field that came due.
+        field: String,
+        timestamp: f64,
+    },
+    CommentAdded {
+        space_id: String,
+        entry_id: String,
+        entry_title: String,
+        author: String,
+        body: String,
+        timestamp: f64,
+    },
+    JobFailed {
+        space_id: String,
+        /// Job family, e.g. `import`, `bulk_update` or `vacuum`.
+        job_kind: String,
+        job_id: String,
+        error: String,
+        timestamp: f64,
+    },
+}
+
+impl ChatEvent {
+    /// The chat event for a watcher notification in `space_id`.
+    pub fn from_watch_notification(space_id: &str, notification: &WatchNotification) -> Self {
+        let space_id = space_id.to_string();
+        let entry_id = notification.entry_id.clone();
+        let entry_title = notification.entry_title.clone();
+        let form = notification.form.clone();
+        let author = notification.author.clone();
+        let timestamp = notification.created_at;
+        match notification.event {
+            WatchEventKind::Created => Self::EntryCreated {
+                space_id,
+                entry_id,
+                entry_title,
+                form,
+                author,
+                timestamp,
+            },
+            WatchEventKind::Updated | WatchEventKind::Transitioned => Self::EntryUpdated {
+                space_id,
+                entry_id,
+                entry_title,
+                form,
+                author,
+                timestamp,
+            },
+            WatchEventKind::Deleted => Self::EntryDeleted {
+                space_id,
+                entry_id,
+                entry_title,
+                form,
+                author,
+                timestamp,
+            },
+            WatchEventKind::ReminderDue => Self::ReminderDue {
+                space_id,
+                entry_id,
+                entry_title,
+                form,
+                field: notification.field.clone().unwrap_or_default(),
+                timestamp,
+            },
+        }
+    }
+}
+
+/// Where deep links point.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ChatFormatOptions {
+    /// Public http(s) URL of the Ugoite web app.
+    pub base_url: String,
+}
+
+/// Platform-neutral rendering of an event.
+struct ChatMessage {
+    /// One line used as notification fallback text and embed title.
+    headline: String,
+    /// Plain-text title of the linked object.
+    subject: String,
+    link: String,
+    link_label: &'static str,
+    excerpt: Option<String>,
+    /// Label/value pairs shown as context.
+    facts: Vec<(&'static str, String)>,
+    color: u32,
+    timestamp: f64,
+}
+
+fn base_url(options: &ChatFormatOptions) -> Result<String> {
+    let base = options.base_url.trim().trim_end_matches('/');
+    match Url::parse(base) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(base.to_string()),
+        _ => Err(anyhow!(
+            "Chat base URL must be http or https: {}",
+            options.base_url
+        )),
+    }
+}
+
+fn path_segment(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes())
+        .collect::<String>()
+        .replace('+', "%20")
+}
+
+fn entry_link(base: &str, space_id: &str, entry_id: &str) -> String {
+    format!(
+        "{}/spaces/{}/entries/{}",
+        base,
+        path_segment(space_id),
+        path_segment(entry_id)
+    )
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", cut.trim_end())
+}
+
+fn display_title(title: &str, fallback: &str) -> String {
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    if title.is_empty() {
+        fallback.to_string()
+    } else {
+        title
+    }
+}
+
+fn message_for(event: &ChatEvent, base: &str) -> ChatMessage {
+    match event {
+        ChatEvent::EntryCreated {
+            space_id,
+            entry_id,
+            entry_title,
+            form,
+            author,
+            timestamp,
+        }
+        | ChatEvent::EntryUpdated {
+            space_id,
+            entry_id,
+            entry_title,
+            form,
+            author,
+            timestamp,
+        }
+        | ChatEvent::EntryDeleted {
+            space_id,
+            entry_id,
+            entry_title,
+            form,
+            author,
+            timestamp,
+        } => {
+            let (verb, color) = match event {
+                ChatEvent::EntryCreated { .. } => ("created", COLOR_CREATED),
+                ChatEvent::EntryUpdated { .. } => ("updated", COLOR_UPDATED),
+                _ => ("deleted", COLOR_DELETED),
+            };
+            let subject = display_title(entry_title, entry_id);
+            let headline = match author {
+                Some(author) => format!("{author} {verb} {subject}"),
+                None => format!("Entry {verb}: {subject}"),
+            };
+            let mut facts = vec![("Space", space_id.clone()), ("Form", form.clone())];
+            if let Some(author) = author {
+                facts.push(("Author", author.clone()));
+            }
+            ChatMessage {
+                headline,
+                subject,
+                link: entry_link(base, space_id, entry_id),
+                link_label: "Open entry",
+                excerpt: None,
+                facts,
+                color,
+                timestamp: *timestamp,
+            }
+        }
+        ChatEvent::ReminderDue {
+            space_id,
+            entry_id,
+            entry_title,
+            form,
+            field,
+            timestamp,
+        } => {
+            let subject = display_title(entry_title, entry_id);
+            ChatMessage {
+                headline: format!("Reminder: {subject} is due"),
+                subject,
+                link: entry_link(base, space_id, entry_id),
+                link_label: "Open entry",
+                excerpt: None,
+                facts: vec![
+                    ("Space", space_id.clone()),
+                    ("Form", form.clone()),
+                    ("Field", field.clone()),
+                ],
+                color: COLOR_REMINDER,
+                timestamp: *timestamp,
+            }
+        }
+        ChatEvent::CommentAdded {
+            space_id,
+            entry_id,
+            entry_title,
+            author,
+            body,
+            timestamp,
+        } => {
+            let subject = display_title(entry_title, entry_id);
+            ChatMessage {
+                headline: format!("{author} commented on {subject}"),
+                subject,
+                link: entry_link(base, space_id, entry_id),
+                link_label: "View comment",
+                excerpt: Some(truncate_chars(body, EXCERPT_MAX_CHARS)).filter(|b| !b.is_empty()),
+                facts: vec![("Space", space_id.clone()), ("Author", author.clone())],
+                color: COLOR_COMMENT,
+                timestamp: *timestamp,
+            }
+        }
+        ChatEvent::JobFailed {
+            space_id,
+            job_kind,
+            job_id,
+            error,
+            timestamp,
+        } => ChatMessage {
+            headline: format!("{} job failed in {}", job_kind, space_id),
+            subject: format!("{job_kind} job {job_id}"),
+            link: format!("{}/spaces/{}", base, path_segment(space_id)),
+            link_label: "Open space",
+            excerpt: Some(truncate_chars(error, EXCERPT_MAX_CHARS)).filter(|e| !e.is_empty()),
+            facts: vec![
+                ("Space", space_id.clone()),
+                ("Job", format!("{job_kind} {job_id}")),
+            ],
+            color: COLOR_FAILED,
+            timestamp: *timestamp,
+        },
+    }
+}
+
+/// Escape text for Slack `mrkdwn`, which only reserves `&`, `<` and `>`.
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn discord_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '*' | '_' | '~' | '`' | '|' | '>' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn iso_timestamp(timestamp: f64) -> String {
+    DateTime::<Utc>::from_timestamp_millis((timestamp * 1000.0).round() as i64)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn slack_payload(message: &ChatMessage) -> Value {
+    let mut text = format!(
+        "*{}*\n<{}|{}>",
+        slack_escape(&message.headline),
+        message.link,
+        slack_escape(&message.subject)
+    );
+    if let Some(excerpt) = &message.excerpt {
+        let quoted = excerpt
+            .lines()
+            .map(|line| format!(">{}", slack_escape(line)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        text.push('\n');
+        text.push_str(&quoted);
+    }
+    let context = message
+        .facts
+        .iter()
+        .map(|(label, value)| format!("*{}:* {}", label, slack_escape(value)))
+        .collect::<Vec<_>>()
+        .join("  ·  ");
+    json!({
+        "text": message.headline,
+        "blocks": [
+            {"type": "section", "text": {"type": "mrkdwn", "text": text}},
+            {"type": "context", "elements": [
+                {"type": "mrkdwn", "text": context},
+                {"type": "mrkdwn", "text": format!(
+                    "<!date^{}^{{date_short_pretty}} {{time}}|{}>",
+                    message.timestamp as i64,
+                    iso_timestamp(message.timestamp)
+                )},
+            ]},
+            {"type": "actions", "elements": [{
+                "type": "button",
+                "text": {"type": "plain_text", "text": message.link_label},
+                "url": message.link,
+            }]},
+        ],
+    })
+}
+
+fn discord_payload(message: &ChatMessage) -> Value {
+    let fields: Vec<Value> = message
+        .facts
+        .iter()
+        .map(
+            |(label, value)| json!({"name": label, "value": discord_escape(value), "inline": true}),
+        )
+        .collect();
+    let mut embed = json!({
+        "title": truncate_chars(&message.headline, DISCORD_TITLE_MAX_CHARS),
+        "url": message.link,
+        "color": message.color,
+        "fields": fields,
+        "timestamp": iso_timestamp(message.timestamp),
+        "footer": {"text": "Ugoite"},
+    });
+    let description = match &message.excerpt {
+        Some(excerpt) => format!(
+            "[{}]({})\n{}",
+            discord_escape(&message.subject),
+            message.link,
+            excerpt
+                .lines()
+                .map(|line| format!("> {}", discord_escape(line)))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+        None => format!("[{}]({})", discord_escape(&message.subject), message.link),
+    };
+    embed["description"] = Value::String(description);
+    json!({
+        "embeds": [embed],
+        "allowed_mentions": {"parse": []},
+    })
+}
+
+/// Format `event` as a ready-to-post `platform` payload with deep links
+/// into the web app at `options.base_url`.
+///
+/// Slack payloads carry a `text` fallback plus section, context and button
+/// blocks; Discord payloads carry one colored embed and disable mentions so
+/// user-supplied text cannot ping a channel.
+pub fn format_chat_notification(
+    platform: ChatPlatform,
+    event: &ChatEvent,
+    options: &ChatFormatOptions,
+) -> Result<Value> {
+    let base = base_url(options)?;
+    let message = message_for(event, &base);
+    Ok(match platform {
+        ChatPlatform::Slack => slack_payload(&message),
+        ChatPlatform::Discord => discord_payload(&message),
+    })
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, TimeZone, Utc};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use uuid::Uuid;
+
+/// First timestamp handed out inside [`frozen`]: 2024-01-01T00:00:00Z.
+pub const FROZEN_EPOCH_MS: i64 = 1_704_067_200_000;
+
+struct FrozenClock {
+    seed: u64,
+    ticks: AtomicU64,
+    ids: AtomicU64,
+}
+
+tokio::task_local! {
+    static FROZEN: FrozenClock;
+}
+
+/// Run `fut` with timestamps starting at [`FROZEN_EPOCH_MS`] and advancing one
+/// millisecond per read, and with UUIDs derived from `seed` and a counter.
+///
+/// The same sequence of writes therefore stores the same timestamps and ids on
+/// every run. Work spawned onto other tasks uses the real clock.
+pub async fn frozen<F: Future>(seed: u64, fut: F) -> F::Output {
+    FROZEN
+        .scope(
+            FrozenClock {
+                seed,
+                ticks: AtomicU64::new(0),
+                ids: AtomicU64::new(0),
+            },
+            fut,
+        )
+        .await
+}
+
+pub(crate) fn now() -> DateTime<Utc> {
+    FROZEN
+        .try_with(|clock| {
+            let tick = clock.ticks.fetch_add(1, Ordering::SeqCst) as i64;
+            Utc.timestamp_millis_opt(FROZEN_EPOCH_MS + tick).single()
+        })
+        .ok()
+        .flatten()
+        .unwrap_or_else(Utc::now)
+}
+
+pub(crate) fn new_uuid() -> Uuid {
+    FROZEN
+        .try_with(|clock| {
+            let counter = clock.ids.fetch_add(1, Ordering::SeqCst);
+            let digest = Sha256::new()
+                .chain_update(clock.seed.to_be_bytes())
+                .chain_update(counter.to_be_bytes())
+                .finalize();
+            let mut bytes = [0u8; 16];
+            bytes.copy_from_slice(&digest[..16]);
+            uuid::Builder::from_random_bytes(bytes).into_uuid()
+        })
+        .unwrap_or_else(|_| Uuid::new_v4())
+}
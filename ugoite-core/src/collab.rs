@@ -0,0 +1,215 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::Mutex;
+use yrs::updates::decoder::Decode;
+use yrs::updates::encoder::Encode;
+use yrs::{Doc, ReadTxn, StateVector, Transact, Update};
+
+use crate::entry;
+use crate::feature_flags;
+use crate::legal_hold;
+use crate::storage;
+
+const COLLAB_DIR: &str = "collab";
+const SNAPSHOT_FILE: &str = "snapshot.bin";
+/// Pending updates are folded into the snapshot once this many accumulate.
+pub const COMPACTION_THRESHOLD: usize = 200;
+
+static ENTRY_LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+
+async fn entry_lock(key: &str) -> Arc<Mutex<()>> {
+    let mut registry = ENTRY_LOCKS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .await;
+    registry
+        .entry(key.to_string())
+        .or_insert_with(|| Arc::new(Mutex::new(())))
+        .clone()
+}
+
+fn collab_root(ws_path: &str, entry_id: &str) -> String {
+    format!("{}/{}/{}/", ws_path, COLLAB_DIR, entry_id)
+}
+
+fn update_path(ws_path: &str, entry_id: &str, seq: u64) -> String {
+    format!("{}updates/{:012}.bin", collab_root(ws_path, entry_id), seq)
+}
+
+async fn require_entry_form(op: &Operator, ws_path: &str, entry_id: &str) -> Result<String> {
+    entry::find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))
+}
+
+/// Pending update sequence numbers in apply order.
+async fn list_update_seqs(op: &Operator, ws_path: &str, entry_id: &str) -> Result<Vec<u64>> {
+    let dir = format!("{}updates/", collab_root(ws_path, entry_id));
+    if !op.exists(&dir).await? {
+        return Ok(Vec::new());
+    }
+    let mut seqs: Vec<u64> = op
+        .list(&dir)
+        .await?
+        .iter()
+        .filter_map(|item| item.name().strip_suffix(".bin")?.parse().ok())
+        .collect();
+    seqs.sort_unstable();
+    Ok(seqs)
+}
+
+fn apply_bytes(doc: &Doc, bytes: &[u8]) -> Result<()> {
+    let update = Update::decode_v1(bytes).map_err(|e| anyhow!("Invalid CRDT update: {}", e))?;
+    doc.transact_mut()
+        .apply_update(update)
+        .map_err(|e| anyhow!("Failed to apply CRDT update: {}", e))
+}
+
+async fn load_doc(op: &Operator, ws_path: &str, entry_id: &str) -> Result<(Doc, Vec<u64>)> {
+    let doc = Doc::new();
+    let snapshot = format!("{}{}", collab_root(ws_path, entry_id), SNAPSHOT_FILE);
+    if op.exists(&snapshot).await? {
+        apply_bytes(&doc, &op.read(&snapshot).await?.to_vec())?;
+    }
+    let seqs = list_update_seqs(op, ws_path, entry_id).await?;
+    for seq in &seqs {
+        let bytes = op
+            .read(&update_path(ws_path, entry_id, *seq))
+            .await?
+            .to_vec();
+        apply_bytes(&doc, &bytes)?;
+    }
+    Ok((doc, seqs))
+}
+
+async fn compact_locked(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    form_name: &str,
+) -> Result<usize> {
+    legal_hold::ensure_entry_not_held(op, ws_path, form_name, entry_id, "compact").await?;
+    let (doc, seqs) = load_doc(op, ws_path, entry_id).await?;
+    if seqs.is_empty() {
+        return Ok(0);
+    }
+    let state = doc
+        .transact()
+        .encode_state_as_update_v1(&StateVector::default());
+    storage::publish_atomic(
+        op,
+        &format!("{}{}", collab_root(ws_path, entry_id), SNAPSHOT_FILE),
+        state,
+    )
+    .await?;
+    // Updates already folded into the snapshot are idempotent if a delete is missed.
+    for seq in &seqs {
+        op.delete(&update_path(ws_path, entry_id, *seq)).await?;
+    }
+    Ok(seqs.len())
+}
+
+/// Persist a Yjs v1 update for an entry and return its sequence number.
+///
+/// Once [`COMPACTION_THRESHOLD`] updates are pending they are merged into the
+/// snapshot, unless the entry is under legal hold.
+pub async fn apply_update(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    update: &[u8],
+) -> Result<u64> {
+    if !feature_flags::is_enabled_at(op, ws_path, feature_flags::CRDT_COLLAB).await? {
+        return Err(anyhow!(
+            "Collaborative editing is disabled for this space ({} flag)",
+            feature_flags::CRDT_COLLAB
+        ));
+    }
+    let form_name = require_entry_form(op, ws_path, entry_id).await?;
+    Update::decode_v1(update).map_err(|e| anyhow!("Invalid CRDT update: {}", e))?;
+
+    let lock = entry_lock(&collab_root(ws_path, entry_id)).await;
+    let _guard = lock.lock().await;
+    let seqs = list_update_seqs(op, ws_path, entry_id).await?;
+    let seq = seqs.last().map_or(1, |last| last + 1);
+    op.write(&update_path(ws_path, entry_id, seq), update.to_vec())
+        .await?;
+
+    if seqs.len() + 1 >= COMPACTION_THRESHOLD
+        && legal_hold::holds_for_entry(op, ws_path, &form_name, entry_id)
+            .await?
+            .is_empty()
+    {
+        compact_locked(op, ws_path, entry_id, &form_name).await?;
+    }
+    Ok(seq)
+}
+
+/// Yjs v1 state vector of the persisted document.
+pub async fn get_state_vector(op: &Operator, ws_path: &str, entry_id: &str) -> Result<Vec<u8>> {
+    require_entry_form(op, ws_path, entry_id).await?;
+    let (doc, _) = load_doc(op, ws_path, entry_id).await?;
+    let state_vector = doc.transact().state_vector().encode_v1();
+    Ok(state_vector)
+}
+
+/// Yjs v1 update containing everything missing from `state_vector`;
+/// an empty state vector returns the full document.
+pub async fn encode_diff(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    state_vector: &[u8],
+) -> Result<Vec<u8>> {
+    require_entry_form(op, ws_path, entry_id).await?;
+    let remote = if state_vector.is_empty() {
+        StateVector::default()
+    } else {
+        StateVector::decode_v1(state_vector)
+            .map_err(|e| anyhow!("Invalid CRDT state vector: {}", e))?
+    };
+    let (doc, _) = load_doc(op, ws_path, entry_id).await?;
+    let diff = doc.transact().encode_diff_v1(&remote);
+    Ok(diff)
+}
+
+/// Fold pending updates into the snapshot and return how many were merged.
+pub async fn compact_updates(op: &Operator, ws_path: &str, entry_id: &str) -> Result<usize> {
+    let form_name = require_entry_form(op, ws_path, entry_id).await?;
+    let lock = entry_lock(&collab_root(ws_path, entry_id)).await;
+    let _guard = lock.lock().await;
+    compact_locked(op, ws_path, entry_id, &form_name).await
+}
+
+/// Compact every collaborative document in the space, skipping entries that
+/// are gone or under legal hold. Returns how many updates were merged.
+pub(crate) async fn compact_space(op: &Operator, ws_path: &str) -> Result<usize> {
+    let root = format!("{}/{}/", ws_path, COLLAB_DIR);
+    if !op.exists(&root).await? {
+        return Ok(0);
+    }
+    let mut merged = 0;
+    for item in op.list(&root).await? {
+        let entry_id = item.name().trim_end_matches('/');
+        if !item.metadata().is_dir() || entry_id.is_empty() || item.path() == root {
+            continue;
+        }
+        let Some(form_name) = entry::find_entry_form(op, ws_path, entry_id).await? else {
+            continue;
+        };
+        if !legal_hold::holds_for_entry(op, ws_path, &form_name, entry_id)
+            .await?
+            .is_empty()
+        {
+            continue;
+        }
+        let lock = entry_lock(&collab_root(ws_path, entry_id)).await;
+        let _guard = lock.lock().await;
+        merged += compact_locked(op, ws_path, entry_id, &form_name)
+            .await
+            .map_err(|e| anyhow!("Failed to compact collaborative updates of {entry_id}: {e}"))?;
+    }
+    Ok(merged)
+}
@@ -0,0 +1,442 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::clock;
+use crate::entry;
+use crate::space_lock;
+use crate::storage;
+
+/// Space lease serializing collection tree rewrites across processes.
+const COLLECTIONS_LEASE: &str = "collections";
+/// Deepest nesting allowed, counting top-level collections as depth 1.
+pub const MAX_COLLECTION_DEPTH: usize = 16;
+
+/// A folder of entries. Collections nest through `parent_id`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Collection {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub created_at: f64,
+    pub updated_at: f64,
+}
+
+/// Fields changed by [`update_collection`]; `None` leaves a field as is and
+/// an empty string clears `icon` or `description`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CollectionUpdate {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CollectionTree {
+    #[serde(default)]
+    pub collections: BTreeMap<String, Collection>,
+    /// Entry id -> collection id. Entries without a placement are unfiled.
+    #[serde(default)]
+    pub placements: BTreeMap<String, String>,
+}
+
+impl CollectionTree {
+    /// `collection_id` and every collection nested below it.
+    pub fn subtree(&self, collection_id: &str) -> HashSet<String> {
+        let mut ids = HashSet::from([collection_id.to_string()]);
+        loop {
+            let before = ids.len();
+            for collection in self.collections.values() {
+                if collection
+                    .parent_id
+                    .as_ref()
+                    .is_some_and(|parent| ids.contains(parent))
+                {
+                    ids.insert(collection.id.clone());
+                }
+            }
+            if ids.len() == before {
+                return ids;
+            }
+        }
+    }
+
+    fn depth(&self, collection_id: &str) -> usize {
+        let mut depth = 0;
+        let mut current = self.collections.get(collection_id);
+        while let Some(collection) = current {
+            depth += 1;
+            current = collection
+                .parent_id
+                .as_ref()
+                .and_then(|parent| self.collections.get(parent));
+        }
+        depth
+    }
+
+    fn subtree_height(&self, collection_id: &str) -> usize {
+        self.subtree(collection_id)
+            .iter()
+            .map(|id| self.depth(id))
+            .max()
+            .unwrap_or(0)
+            .saturating_sub(self.depth(collection_id))
+            + 1
+    }
+
+    fn require(&self, collection_id: &str) -> Result<&Collection> {
+        self.collections
+            .get(collection_id)
+            .ok_or_else(|| anyhow!("Collection not found: {}", collection_id))
+    }
+}
+
+fn tree_path(ws_path: &str) -> String {
+    format!(
+        "{}/collections/collections.json",
+        ws_path.trim_end_matches('/')
+    )
+}
+
+pub async fn get_collection_tree(op: &Operator, ws_path: &str) -> Result<CollectionTree> {
+    match op.read(&tree_path(ws_path)).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("Collection tree is malformed: {}", e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(CollectionTree::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn update_tree<T, F>(op: &Operator, ws_path: &str, mutate: F) -> Result<T>
+where
+    F: FnOnce(&mut CollectionTree) -> Result<T>,
+{
+    space_lock::with_space_lease(op, ws_path, COLLECTIONS_LEASE, |_| async {
+        let mut tree = get_collection_tree(op, ws_path).await?;
+        let result = mutate(&mut tree)?;
+        storage::publish_atomic(op, &tree_path(ws_path), serde_json::to_vec_pretty(&tree)?).await?;
+        Ok(result)
+    })
+    .await
+}
+
+fn validate_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("Collection name must not be empty"));
+    }
+    Ok(name.to_string())
+}
+
+/// Sibling collections must have distinct names, compared case-insensitively.
+fn ensure_unique_name(
+    tree: &CollectionTree,
+    parent_id: Option<&str>,
+    name: &str,
+    except: Option<&str>,
+) -> Result<()> {
+    let taken = tree.collections.values().any(|other| {
+        other.parent_id.as_deref() == parent_id
+            && Some(other.id.as_str()) != except
+            && other.name.eq_ignore_ascii_case(name)
+    });
+    if taken {
+        return Err(anyhow!("A collection named {} already exists here", name));
+    }
+    Ok(())
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+pub async fn create_collection(
+    op: &Operator,
+    ws_path: &str,
+    name: &str,
+    parent_id: Option<&str>,
+    icon: Option<&str>,
+    description: Option<&str>,
+) -> Result<Collection> {
+    let name = validate_name(name)?;
+    update_tree(op, ws_path, |tree| {
+        if let Some(parent_id) = parent_id {
+            tree.require(parent_id)?;
+            if tree.depth(parent_id) >= MAX_COLLECTION_DEPTH {
+                return Err(anyhow!(
+                    "Collections cannot be nested more than {} deep",
+                    MAX_COLLECTION_DEPTH
+                ));
+            }
+        }
+        ensure_unique_name(tree, parent_id, &name, None)?;
+        let now = entry::now_ts();
+        let collection = Collection {
+            id: clock::new_uuid().to_string(),
+            name,
+            parent_id: parent_id.map(str::to_string),
+            icon: non_empty(icon),
+            description: non_empty(description),
+            created_at: now,
+            updated_at: now,
+        };
+        tree.collections
+            .insert(collection.id.clone(), collection.clone());
+        Ok(collection)
+    })
+    .await
+}
+
+pub async fn update_collection(
+    op: &Operator,
+    ws_path: &str,
+    collection_id: &str,
+    update: &CollectionUpdate,
+) -> Result<Collection> {
+    let name = update.name.as_deref().map(validate_name).transpose()?;
+    update_tree(op, ws_path, |tree| {
+        let parent_id = tree.require(collection_id)?.parent_id.clone();
+        if let Some(name) = &name {
+            ensure_unique_name(tree, parent_id.as_deref(), name, Some(collection_id))?;
+        }
+        let collection = tree
+            .collections
+            .get_mut(collection_id)
+            .ok_or_else(|| anyhow!("Collection not found: {}", collection_id))?;
+        if let Some(name) = name {
+            collection.name = name;
+        }
+        if let Some(icon) = &update.icon {
+            collection.icon = non_empty(Some(icon));
+        }
+        if let Some(description) = &update.description {
+            collection.description = non_empty(Some(description));
+        }
+        collection.updated_at = entry::now_ts();
+        Ok(collection.clone())
+    })
+    .await
+}
+
+/// Re-parent a collection, or make it top-level with `None`. Moving a
+/// collection into itself or one of its descendants is rejected.
+pub async fn move_collection(
+    op: &Operator,
+    ws_path: &str,
+    collection_id: &str,
+    parent_id: Option<&str>,
+) -> Result<Collection> {
+    update_tree(op, ws_path, |tree| {
+        let name = tree.require(collection_id)?.name.clone();
+        if let Some(parent_id) = parent_id {
+            tree.require(parent_id)?;
+            if tree.subtree(collection_id).contains(parent_id) {
+                return Err(anyhow!(
+                    "Cannot move collection {} into its own subtree",
+                    collection_id
+                ));
+            }
+            if tree.depth(parent_id) + tree.subtree_height(collection_id) > MAX_COLLECTION_DEPTH {
+                return Err(anyhow!(
+                    "Collections cannot be nested more than {} deep",
+                    MAX_COLLECTION_DEPTH
+                ));
+            }
+        }
+        ensure_unique_name(tree, parent_id, &name, Some(collection_id))?;
+        let collection = tree
+            .collections
+            .get_mut(collection_id)
+            .ok_or_else(|| anyhow!("Collection not found: {}", collection_id))?;
+        collection.parent_id = parent_id.map(str::to_string);
+        collection.updated_at = entry::now_ts();
+        Ok(collection.clone())
+    })
+    .await
+}
+
+/// Delete a collection. Its child collections and entries move up to its
+/// parent, so deleting a folder never deletes notes.
+pub async fn delete_collection(op: &Operator, ws_path: &str, collection_id: &str) -> Result<()> {
+    update_tree(op, ws_path, |tree| {
+        let parent_id = tree.require(collection_id)?.parent_id.clone();
+        let children: Vec<String> = tree
+            .collections
+            .values()
+            .filter(|collection| collection.parent_id.as_deref() == Some(collection_id))
+            .map(|collection| collection.id.clone())
+            .collect();
+        for child_id in &children {
+            let name = tree.require(child_id)?.name.clone();
+            ensure_unique_name(tree, parent_id.as_deref(), &name, Some(child_id)).map_err(
+                |_| {
+                    anyhow!(
+                        "Cannot delete collection {}: child {} would clash with a sibling",
+                        collection_id,
+                        name
+                    )
+                },
+            )?;
+        }
+        let now = entry::now_ts();
+        for child_id in children {
+            if let Some(child) = tree.collections.get_mut(&child_id) {
+                child.parent_id = parent_id.clone();
+                child.updated_at = now;
+            }
+        }
+        match &parent_id {
+            Some(parent_id) => {
+                for placed in tree.placements.values_mut() {
+                    if placed == collection_id {
+                        *placed = parent_id.clone();
+                    }
+                }
+            }
+            None => tree.placements.retain(|_, placed| placed != collection_id),
+        }
+        tree.collections.remove(collection_id);
+        Ok(())
+    })
+    .await
+}
+
+/// Collections of the space, parents before children and siblings by name.
+pub async fn list_collections(op: &Operator, ws_path: &str) -> Result<Vec<Collection>> {
+    let tree = get_collection_tree(op, ws_path).await?;
+    let mut collections: Vec<Collection> = tree.collections.values().cloned().collect();
+    collections.sort_by(|left, right| {
+        tree.depth(&left.id)
+            .cmp(&tree.depth(&right.id))
+            .then_with(|| left.name.to_lowercase().cmp(&right.name.to_lowercase()))
+            .then_with(|| left.id.cmp(&right.id))
+    });
+    Ok(collections)
+}
+
+/// File an entry into a collection, or take it out of every collection with `None`.
+pub async fn move_entry_to_collection(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    collection_id: Option<&str>,
+) -> Result<()> {
+    if entry::find_entry_form(op, ws_path, entry_id)
+        .await?
+        .is_none()
+    {
+        return Err(anyhow!("Entry not found: {}", entry_id));
+    }
+    update_tree(op, ws_path, |tree| {
+        match collection_id {
+            Some(collection_id) => {
+                tree.require(collection_id)?;
+                tree.placements
+                    .insert(entry_id.to_string(), collection_id.to_string());
+            }
+            None => {
+                tree.placements.remove(entry_id);
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// `list_entries` records filed directly in `collection_id` (or unfiled, for
+/// `None`), each with `collection_id` added. With `recursive`, entries of
+/// nested collections are included too.
+pub async fn list_collection_entries(
+    op: &Operator,
+    ws_path: &str,
+    collection_id: Option<&str>,
+    recursive: bool,
+) -> Result<Vec<Value>> {
+    let tree = get_collection_tree(op, ws_path).await?;
+    let scope: Option<HashSet<String>> = match collection_id {
+        Some(collection_id) => {
+            tree.require(collection_id)?;
+            Some(if recursive {
+                tree.subtree(collection_id)
+            } else {
+                HashSet::from([collection_id.to_string()])
+            })
+        }
+        None => None,
+    };
+    let mut entries = Vec::new();
+    for mut record in entry::list_entries(op, ws_path).await? {
+        let placed = record
+            .get("id")
+            .and_then(Value::as_str)
+            .and_then(|id| tree.placements.get(id))
+            .filter(|placed| tree.collections.contains_key(*placed))
+            .cloned();
+        let included = match (&scope, &placed) {
+            (Some(scope), Some(placed)) => scope.contains(placed),
+            (None, None) => true,
+            (None, Some(_)) => recursive,
+            (Some(_), None) => false,
+        };
+        if !included {
+            continue;
+        }
+        if let Some(obj) = record.as_object_mut() {
+            obj.insert(
+                "collection_id".to_string(),
+                placed.map(Value::from).unwrap_or(Value::Null),
+            );
+        }
+        entries.push(record);
+    }
+    Ok(entries)
+}
+
+/// The `collection` filter of index queries: a collection id admits entries
+/// filed in that collection or any collection nested below it, and `null`
+/// admits unfiled entries.
+pub struct CollectionScope {
+    placements: BTreeMap<String, String>,
+    collections: Option<HashSet<String>>,
+}
+
+impl CollectionScope {
+    pub async fn load(op: &Operator, ws_path: &str, filter: &Value) -> Result<Self> {
+        let tree = get_collection_tree(op, ws_path).await?;
+        let collections = match filter {
+            Value::Null => None,
+            Value::String(collection_id) => {
+                tree.require(collection_id)?;
+                Some(tree.subtree(collection_id))
+            }
+            _ => return Err(anyhow!("collection filter must be a collection id or null")),
+        };
+        let mut placements = tree.placements;
+        placements.retain(|_, placed| tree.collections.contains_key(placed));
+        Ok(Self {
+            placements,
+            collections,
+        })
+    }
+
+    pub fn admits(&self, entry_id: &str) -> bool {
+        match (&self.collections, self.placements.get(entry_id)) {
+            (Some(scope), Some(placed)) => scope.contains(placed),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
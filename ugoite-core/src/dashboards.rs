@@ -0,0 +1,360 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::clock;
+use crate::entry;
+use crate::index;
+use crate::materialized_view;
+use crate::saved_sql;
+use crate::storage;
+
+const DASHBOARDS_DIR: &str = "dashboards";
+/// Rows a query widget returns when it sets no `limit`.
+pub const DEFAULT_WIDGET_LIMIT: usize = 100;
+const MAX_WIDGETS: usize = 50;
+
+fn default_limit() -> usize {
+    DEFAULT_WIDGET_LIMIT
+}
+
+fn default_recent_limit() -> usize {
+    10
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WidgetKind {
+    /// Result of a saved SQL query, with `{{name}}` variables bound from `params`.
+    SavedSql {
+        sql_id: String,
+        #[serde(default)]
+        params: Value,
+        #[serde(default = "default_limit")]
+        limit: usize,
+    },
+    /// Rows of a materialized view's query.
+    MaterializedView {
+        sql_id: String,
+        #[serde(default = "default_limit")]
+        limit: usize,
+    },
+    /// Most recently updated entries, optionally of one form.
+    RecentEntries {
+        #[serde(default)]
+        form: Option<String>,
+        #[serde(default = "default_recent_limit")]
+        limit: usize,
+    },
+    /// Tag counts, most used first, optionally over one form's entries.
+    TagFacet {
+        #[serde(default)]
+        form: Option<String>,
+        #[serde(default = "default_limit")]
+        limit: usize,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Widget {
+    /// Assigned when empty; unique within the dashboard.
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub kind: WidgetKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Dashboard {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub widgets: Vec<Widget>,
+    pub created_at: f64,
+    pub updated_at: f64,
+}
+
+/// Name, description and widgets of a dashboard, as given on create and update.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct DashboardPayload {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub widgets: Vec<Widget>,
+}
+
+fn dashboard_path(ws_path: &str, dashboard_id: &str) -> Result<String> {
+    if dashboard_id.is_empty()
+        || !dashboard_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+    {
+        return Err(anyhow!("Invalid dashboard id: {}", dashboard_id));
+    }
+    Ok(format!(
+        "{}/{}/{}.json",
+        ws_path.trim_end_matches('/'),
+        DASHBOARDS_DIR,
+        dashboard_id
+    ))
+}
+
+fn validate_payload(payload: &mut DashboardPayload) -> Result<()> {
+    payload.name = payload.name.trim().to_string();
+    if payload.name.is_empty() {
+        return Err(anyhow!("Dashboard name must not be empty"));
+    }
+    if payload.widgets.len() > MAX_WIDGETS {
+        return Err(anyhow!(
+            "A dashboard can have at most {} widgets",
+            MAX_WIDGETS
+        ));
+    }
+    let mut seen = HashSet::new();
+    for widget in payload.widgets.iter_mut() {
+        if widget.id.trim().is_empty() {
+            widget.id = clock::new_uuid().to_string();
+        }
+        if !seen.insert(widget.id.clone()) {
+            return Err(anyhow!("Duplicate widget id: {}", widget.id));
+        }
+        let limit = match &widget.kind {
+            WidgetKind::SavedSql { sql_id, limit, .. }
+            | WidgetKind::MaterializedView { sql_id, limit } => {
+                if sql_id.trim().is_empty() {
+                    return Err(anyhow!("Widget {} needs a sql_id", widget.id));
+                }
+                *limit
+            }
+            WidgetKind::RecentEntries { limit, .. } | WidgetKind::TagFacet { limit, .. } => *limit,
+        };
+        if limit == 0 || limit > index::MAX_QUERY_PAGE {
+            return Err(anyhow!(
+                "Widget {} limit must be between 1 and {}",
+                widget.id,
+                index::MAX_QUERY_PAGE
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn write_dashboard(op: &Operator, ws_path: &str, dashboard: &Dashboard) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &dashboard_path(ws_path, &dashboard.id)?,
+        serde_json::to_vec_pretty(dashboard)?,
+    )
+    .await
+}
+
+pub async fn create_dashboard(
+    op: &Operator,
+    ws_path: &str,
+    payload: &DashboardPayload,
+) -> Result<Dashboard> {
+    let mut payload = payload.clone();
+    validate_payload(&mut payload)?;
+    let now = entry::now_ts();
+    let dashboard = Dashboard {
+        id: clock::new_uuid().to_string(),
+        name: payload.name,
+        description: payload.description,
+        widgets: payload.widgets,
+        created_at: now,
+        updated_at: now,
+    };
+    write_dashboard(op, ws_path, &dashboard).await?;
+    Ok(dashboard)
+}
+
+pub async fn get_dashboard(op: &Operator, ws_path: &str, dashboard_id: &str) -> Result<Dashboard> {
+    match op.read(&dashboard_path(ws_path, dashboard_id)?).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("Dashboard {} is malformed: {}", dashboard_id, e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => {
+            Err(anyhow!("Dashboard not found: {}", dashboard_id))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Replace a dashboard's name, description and widgets.
+pub async fn update_dashboard(
+    op: &Operator,
+    ws_path: &str,
+    dashboard_id: &str,
+    payload: &DashboardPayload,
+) -> Result<Dashboard> {
+    let mut payload = payload.clone();
+    validate_payload(&mut payload)?;
+    let mut dashboard = get_dashboard(op, ws_path, dashboard_id).await?;
+    dashboard.name = payload.name;
+    dashboard.description = payload.description;
+    dashboard.widgets = payload.widgets;
+    dashboard.updated_at = entry::now_ts();
+    write_dashboard(op, ws_path, &dashboard).await?;
+    Ok(dashboard)
+}
+
+pub async fn delete_dashboard(op: &Operator, ws_path: &str, dashboard_id: &str) -> Result<()> {
+    let path = dashboard_path(ws_path, dashboard_id)?;
+    if !op.exists(&path).await? {
+        return Err(anyhow!("Dashboard not found: {}", dashboard_id));
+    }
+    op.delete(&path).await?;
+    Ok(())
+}
+
+/// Dashboards of the space, by name.
+pub async fn list_dashboards(op: &Operator, ws_path: &str) -> Result<Vec<Dashboard>> {
+    let dir = format!("{}/{}/", ws_path.trim_end_matches('/'), DASHBOARDS_DIR);
+    if !op.exists(&dir).await? {
+        return Ok(Vec::new());
+    }
+    let mut dashboards = Vec::new();
+    for item in op.list(&dir).await? {
+        if let Some(dashboard_id) = item.name().strip_suffix(".json") {
+            dashboards.push(get_dashboard(op, ws_path, dashboard_id).await?);
+        }
+    }
+    dashboards.sort_by(|left, right| {
+        left.name
+            .to_lowercase()
+            .cmp(&right.name.to_lowercase())
+            .then_with(|| left.id.cmp(&right.id))
+    });
+    Ok(dashboards)
+}
+
+fn limited(mut rows: Vec<Value>, limit: usize) -> Value {
+    let truncated = rows.len() > limit;
+    rows.truncate(limit);
+    json!({"rows": rows, "truncated": truncated})
+}
+
+async fn evaluate_widget(
+    op: &Operator,
+    ws_path: &str,
+    kind: &WidgetKind,
+    entries: &[Value],
+) -> Result<Value> {
+    match kind {
+        WidgetKind::SavedSql {
+            sql_id,
+            params,
+            limit,
+        } => {
+            let saved = saved_sql::get_sql(op, ws_path, sql_id).await?;
+            let sql_text = saved.get("sql").and_then(Value::as_str).unwrap_or_default();
+            let bound = saved_sql::bind_sql_params(sql_text, params)?;
+            let rows = index::execute_sql_query(op, ws_path, &bound).await?;
+            Ok(limited(rows, *limit))
+        }
+        WidgetKind::MaterializedView { sql_id, limit } => {
+            let meta = materialized_view::read_view_meta(op, ws_path, sql_id)
+                .await
+                .map_err(|_| anyhow!("Materialized view not found: {}", sql_id))?;
+            let sql_text = meta.get("sql").and_then(Value::as_str).unwrap_or_default();
+            let rows = index::execute_sql_query(op, ws_path, sql_text).await?;
+            let mut data = limited(rows, *limit);
+            data["snapshot_id"] = meta.get("snapshot_id").cloned().unwrap_or(Value::Null);
+            data["view_updated_at"] = meta.get("updated_at").cloned().unwrap_or(Value::Null);
+            Ok(data)
+        }
+        WidgetKind::RecentEntries { form, limit } => {
+            let mut rows: Vec<Value> = entries
+                .iter()
+                .filter(|entry| in_form(entry, form.as_deref()))
+                .map(|entry| {
+                    json!({
+                        "id": entry.get("id"),
+                        "title": entry.get("title"),
+                        "form": entry.get("form"),
+                        "tags": entry.get("tags"),
+                        "updated_at": entry.get("updated_at"),
+                    })
+                })
+                .collect();
+            rows.sort_by(|left, right| {
+                let updated = |row: &Value| row["updated_at"].as_f64().unwrap_or(0.0);
+                updated(right)
+                    .total_cmp(&updated(left))
+                    .then_with(|| left["id"].as_str().cmp(&right["id"].as_str()))
+            });
+            Ok(limited(rows, *limit))
+        }
+        WidgetKind::TagFacet { form, limit } => {
+            let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+            for entry in entries
+                .iter()
+                .filter(|entry| in_form(entry, form.as_deref()))
+            {
+                for tag in entry
+                    .get("tags")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                {
+                    *counts.entry(tag.to_string()).or_default() += 1;
+                }
+            }
+            let mut facets: Vec<(String, u64)> = counts.into_iter().collect();
+            facets.sort_by(|left, right| right.1.cmp(&left.1).then_with(|| left.0.cmp(&right.0)));
+            let rows = facets
+                .into_iter()
+                .map(|(tag, count)| json!({"tag": tag, "count": count}))
+                .collect();
+            Ok(limited(rows, *limit))
+        }
+    }
+}
+
+fn in_form(entry: &Value, form: Option<&str>) -> bool {
+    form.is_none_or(|form| entry.get("form").and_then(Value::as_str) == Some(form))
+}
+
+/// Evaluate every widget of a dashboard in one call. Each widget result has
+/// its `id`, `type`, `title` and either `data` or, when that widget failed,
+/// `error`; one broken widget does not fail the dashboard.
+pub async fn render_dashboard(op: &Operator, ws_path: &str, dashboard_id: &str) -> Result<Value> {
+    let dashboard = get_dashboard(op, ws_path, dashboard_id).await?;
+    let needs_entries = dashboard.widgets.iter().any(|widget| {
+        matches!(
+            widget.kind,
+            WidgetKind::RecentEntries { .. } | WidgetKind::TagFacet { .. }
+        )
+    });
+    let entries = if needs_entries {
+        entry::list_entries(op, ws_path).await?
+    } else {
+        Vec::new()
+    };
+    let mut widgets = Vec::with_capacity(dashboard.widgets.len());
+    for widget in &dashboard.widgets {
+        let kind = serde_json::to_value(&widget.kind)?;
+        let mut rendered = json!({
+            "id": widget.id,
+            "type": kind.get("type"),
+            "title": widget.title,
+        });
+        match evaluate_widget(op, ws_path, &widget.kind, &entries).await {
+            Ok(data) => rendered["data"] = data,
+            Err(err) => rendered["error"] = Value::from(err.to_string()),
+        }
+        widgets.push(rendered);
+    }
+    Ok(json!({
+        "dashboard": dashboard,
+        "widgets": widgets,
+        "rendered_at": entry::now_ts(),
+    }))
+}
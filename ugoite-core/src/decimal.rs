@@ -0,0 +1,203 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Largest precision an Iceberg `decimal` (and Arrow `Decimal128`) holds.
+pub const MAX_PRECISION: u32 = 38;
+pub const DEFAULT_PRECISION: u32 = 38;
+pub const DEFAULT_SCALE: u32 = 2;
+
+/// Precision, scale and optional ISO 4217 currency of a `decimal` field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecimalSpec {
+    /// Total significant digits.
+    pub precision: u32,
+    /// Digits after the decimal point; every stored value carries exactly this
+    /// many.
+    pub scale: u32,
+    pub currency: Option<String>,
+}
+
+impl Default for DecimalSpec {
+    fn default() -> Self {
+        Self {
+            precision: DEFAULT_PRECISION,
+            scale: DEFAULT_SCALE,
+            currency: None,
+        }
+    }
+}
+
+impl DecimalSpec {
+    /// Read `precision`, `scale` and `currency` from a field definition,
+    /// defaulting to `decimal(38, 2)` without a currency.
+    pub fn from_field_def(field_def: &Value) -> Result<Self> {
+        let digits = |key: &str, default: u32| -> Result<u32> {
+            match field_def.get(key) {
+                None | Some(Value::Null) => Ok(default),
+                Some(value) => value
+                    .as_u64()
+                    .and_then(|n| u32::try_from(n).ok())
+                    .ok_or_else(|| anyhow!("decimal {} must be a non-negative integer", key)),
+            }
+        };
+        let precision = digits("precision", DEFAULT_PRECISION)?;
+        let scale = digits("scale", DEFAULT_SCALE.min(precision))?;
+        if !(1..=MAX_PRECISION).contains(&precision) {
+            return Err(anyhow!(
+                "decimal precision must be between 1 and {}",
+                MAX_PRECISION
+            ));
+        }
+        if scale > precision {
+            return Err(anyhow!("decimal scale must not exceed precision"));
+        }
+        let currency = match field_def.get("currency") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(code))
+                if code.len() == 3 && code.chars().all(|ch| ch.is_ascii_alphabetic()) =>
+            {
+                Some(code.to_ascii_uppercase())
+            }
+            Some(_) => {
+                return Err(anyhow!(
+                    "decimal currency must be a three-letter ISO 4217 code"
+                ))
+            }
+        };
+        Ok(Self {
+            precision,
+            scale,
+            currency,
+        })
+    }
+
+    /// Cast `value` to the canonical string of this decimal, e.g. `"1234.50"`
+    /// for scale 2. Numbers are read from their JSON text rather than as
+    /// floats, grouping commas and a leading or trailing currency code are
+    /// ignored, and values needing more digits than the spec allows are
+    /// rejected instead of rounded.
+    pub fn cast(&self, value: &Value) -> Option<String> {
+        let text = match value {
+            Value::Number(number) => number.to_string(),
+            Value::String(text) => self.strip_currency(text.trim()).replace(',', ""),
+            _ => return None,
+        };
+        let units = self.units(&text)?;
+        Some(format_units(units, self.scale))
+    }
+
+    /// Unscaled value of a decimal string at this spec's scale, the integer an
+    /// Arrow `Decimal128` column stores.
+    pub fn units(&self, text: &str) -> Option<i128> {
+        let parsed = ParsedDecimal::parse(text)?;
+        let units = parsed.rescale(self.scale)?;
+        let limit = 10i128.checked_pow(self.precision)?;
+        (units.abs() < limit).then_some(units)
+    }
+
+    fn strip_currency<'a>(&self, text: &'a str) -> &'a str {
+        let Some(code) = self.currency.as_deref() else {
+            return text;
+        };
+        let upper = text.to_ascii_uppercase();
+        if upper.starts_with(code) {
+            text[code.len()..].trim()
+        } else if upper.ends_with(code) {
+            text[..text.len() - code.len()].trim()
+        } else {
+            text
+        }
+    }
+}
+
+/// Format an unscaled value with `scale` fraction digits.
+pub fn format_units(units: i128, scale: u32) -> String {
+    let digits = units.unsigned_abs().to_string();
+    let sign = if units < 0 { "-" } else { "" };
+    if scale == 0 {
+        return format!("{}{}", sign, digits);
+    }
+    let scale = scale as usize;
+    let padded = format!("{:0>width$}", digits, width = scale + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - scale);
+    format!("{}{}.{}", sign, whole, fraction)
+}
+
+/// A decimal literal as an unscaled integer and its scale.
+#[derive(Debug, Clone, Copy)]
+struct ParsedDecimal {
+    units: i128,
+    scale: u32,
+}
+
+impl ParsedDecimal {
+    /// Parse `-?digits[.digits]`; exponents are rejected so floats printed in
+    /// scientific notation never slip through inexactly.
+    fn parse(text: &str) -> Option<Self> {
+        let (negative, body) = match text.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (whole, fraction) = body.split_once('.').unwrap_or((body, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return None;
+        }
+        if !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|ch| ch.is_ascii_digit())
+        {
+            return None;
+        }
+        let mut units: i128 = 0;
+        for ch in whole.chars().chain(fraction.chars()) {
+            units = units
+                .checked_mul(10)?
+                .checked_add(i128::from(ch as u8 - b'0'))?;
+        }
+        Some(Self {
+            units: if negative { -units } else { units },
+            scale: u32::try_from(fraction.len()).ok()?,
+        })
+    }
+
+    /// The same value at `scale`, or `None` if that would drop non-zero digits
+    /// or overflow.
+    fn rescale(self, scale: u32) -> Option<i128> {
+        match self.scale.cmp(&scale) {
+            Ordering::Equal => Some(self.units),
+            Ordering::Less => self
+                .units
+                .checked_mul(10i128.checked_pow(scale - self.scale)?),
+            Ordering::Greater => {
+                let divisor = 10i128.checked_pow(self.scale - scale)?;
+                (self.units % divisor == 0).then_some(self.units / divisor)
+            }
+        }
+    }
+}
+
+/// Decimal text of a value taking part in a decimal comparison: strings shaped
+/// like `-12.50`, and JSON numbers.
+fn comparable(value: &Value) -> Option<(ParsedDecimal, bool)> {
+    match value {
+        Value::Number(number) => ParsedDecimal::parse(&number.to_string()).map(|p| (p, false)),
+        Value::String(text) if text.contains('.') => ParsedDecimal::parse(text).map(|p| (p, true)),
+        _ => None,
+    }
+}
+
+/// Compare two values exactly as decimals when at least one is a decimal
+/// string (as stored for `decimal` fields) and the other is a number or
+/// decimal string, so `"20.00"` sorts below `"100.00"` and `"0.10"` equals
+/// `0.1`.
+pub fn compare_decimals(left: &Value, right: &Value) -> Option<Ordering> {
+    let (left, left_is_text) = comparable(left)?;
+    let (right, right_is_text) = comparable(right)?;
+    if !left_is_text && !right_is_text {
+        return None;
+    }
+    let scale = left.scale.max(right.scale);
+    Some(left.rescale(scale)?.cmp(&right.rescale(scale)?))
+}
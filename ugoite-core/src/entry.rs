@@ -1,19 +1,42 @@
+use crate::clock;
+use crate::decimal::{self, DecimalSpec};
+use crate::entry_acl::{self, EntryViewer};
+use crate::entry_lock;
+use crate::entry_metadata;
+use crate::entry_size;
+use crate::field_crypto;
 use crate::form;
-use crate::iceberg_store;
+use crate::geo::GeoPoint;
+use crate::iceberg_store::{self, REVISION_DELTA_COLUMN};
 use crate::index;
 use crate::integrity::IntegrityProvider;
+use crate::legal_hold;
 use crate::link::Link;
+use crate::mentions::{self, MentionSource};
+use crate::render;
+use crate::revision_delta::{self, RevisionDelta};
+use crate::rollup;
+use crate::sequence;
+use crate::space;
+use crate::space_lock;
+use crate::storage;
+use crate::unique;
+use crate::watchers::{self, WatchEvent, WatchEventKind};
+use crate::workflow;
 use anyhow::{anyhow, Result};
-use arrow_array::builder::{FixedSizeBinaryBuilder, ListBuilder, StringBuilder, StructBuilder};
+use arrow_array::builder::{
+    FixedSizeBinaryBuilder, Float64Builder, ListBuilder, StringBuilder, StructBuilder,
+};
 use arrow_array::{
-    Array, ArrayRef, BooleanArray, Date32Array, FixedSizeBinaryArray, Float32Array, Float64Array,
-    Int32Array, Int64Array, LargeBinaryArray, ListArray, RecordBatch, StringArray, StructArray,
-    Time64MicrosecondArray, TimestampMicrosecondArray, TimestampNanosecondArray,
+    Array, ArrayRef, BooleanArray, Date32Array, Decimal128Array, FixedSizeBinaryArray,
+    Float32Array, Float64Array, Int32Array, Int64Array, LargeBinaryArray, ListArray, RecordBatch,
+    StringArray, StructArray, Time64MicrosecondArray, TimestampMicrosecondArray,
+    TimestampNanosecondArray,
 };
 use arrow_schema::{DataType, Fields};
 use base64::Engine as _;
 use chrono::{DateTime, NaiveTime, SecondsFormat, Timelike, Utc};
-use futures::TryStreamExt;
+use futures::{StreamExt, TryStreamExt};
 use iceberg::arrow::schema_to_arrow_schema;
 use iceberg::arrow::ArrowReaderBuilder;
 use iceberg::spec::DataFile;
@@ -23,10 +46,11 @@ use iceberg::writer::file_writer::{FileWriter, FileWriterBuilder, ParquetWriterB
 use iceberg::MemoryCatalog;
 use opendal::Operator;
 use parquet::file::properties::WriterProperties;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::sync::Arc;
 use url::Url;
 use uuid::Uuid;
@@ -39,6 +63,22 @@ pub struct IntegrityPayload {
     pub signature: String,
 }
 
+/// A heading in an entry, with the headings nested under it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutlineHeading {
+    pub level: u8,
+    pub text: String,
+    /// GitHub-style slug, suffixed `-1`, `-2`, ... when repeated within the entry.
+    pub anchor: String,
+    /// Character offset of the heading in the entry markdown.
+    pub offset: usize,
+    /// Character offset where the section ends: the next heading at the same or
+    /// a higher level, or the end of the markdown.
+    pub end_offset: usize,
+    #[serde(default)]
+    pub children: Vec<OutlineHeading>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EntryContent {
     pub revision_id: String,
@@ -128,6 +168,42 @@ pub struct RevisionRow {
     pub integrity: IntegrityPayload,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub restored_from: Option<String>,
+    /// Why the revision was made, as given by its author.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Client-defined JSON saved with the revision.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision_metadata: Option<Value>,
+}
+
+/// Commit message and metadata saved with a new revision by
+/// [`create_entry_with_note`], [`update_entry_with_note`] and
+/// [`restore_entry_with_note`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RevisionNote {
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub revision_metadata: Option<Value>,
+}
+
+impl RevisionNote {
+    /// Blank messages and `null` metadata are stored as absent.
+    fn normalized(&self) -> Self {
+        Self {
+            message: self
+                .message
+                .as_deref()
+                .map(str::trim)
+                .filter(|message| !message.is_empty())
+                .map(str::to_string),
+            revision_metadata: self
+                .revision_metadata
+                .clone()
+                .filter(|metadata| !metadata.is_null()),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -138,7 +214,7 @@ pub struct EntrySummary {
 }
 
 pub(crate) fn now_ts() -> f64 {
-    Utc::now().timestamp_millis() as f64 / 1000.0
+    clock::now().timestamp_millis() as f64 / 1000.0
 }
 
 fn to_timestamp_micros(ts: f64) -> i64 {
@@ -208,7 +284,7 @@ fn extract_sections(body: &str) -> Value {
     Value::Object(sections)
 }
 
-fn parse_markdown(content: &str) -> (Value, Value) {
+pub(crate) fn parse_markdown(content: &str) -> (Value, Value) {
     let (frontmatter, body) = extract_frontmatter(content);
     let sections = extract_sections(&body);
     (frontmatter, sections)
@@ -289,6 +365,42 @@ fn collect_extra_attributes(sections: &Value, form_set: &HashSet<String>) -> (Ve
     (extras, Value::Object(map))
 }
 
+/// Form fields and extra attributes of a markdown entry, validated and cast
+/// against `form_def`.
+pub(crate) fn parse_entry_fields(
+    content: &str,
+    sections: &Value,
+    form_def: &Value,
+) -> Result<(Value, Value)> {
+    let form_set: HashSet<String> = form_field_names(form_def).into_iter().collect();
+    let policy = extra_attributes_policy(form_def);
+    let (extras, extra_attributes) = collect_extra_attributes(sections, &form_set);
+    if !extras.is_empty() && policy == ExtraAttributesPolicy::Deny {
+        return Err(anyhow!("Unknown form fields: {}", extras.join(", ")));
+    }
+
+    let properties = index::extract_properties(content);
+    let (casted, warnings) = index::validate_properties(&properties, form_def)?;
+    if !warnings.is_empty() {
+        return Err(anyhow!(
+            "Form validation failed: {}",
+            serde_json::to_string(&warnings)?
+        ));
+    }
+
+    let mut fields = Map::new();
+    for source in [&properties, &casted] {
+        if let Some(obj) = source.as_object() {
+            for (key, value) in obj {
+                if form_set.contains(key) {
+                    fields.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+    Ok((Value::Object(fields), extra_attributes))
+}
+
 pub(crate) fn merge_entry_fields(fields: &Value, extra_attributes: &Value) -> Value {
     let mut merged = Map::new();
     if let Some(map) = fields.as_object() {
@@ -304,7 +416,7 @@ pub(crate) fn merge_entry_fields(fields: &Value, extra_attributes: &Value) -> Va
     Value::Object(merged)
 }
 
-fn form_field_names(form_def: &Value) -> Vec<String> {
+pub(crate) fn form_field_names(form_def: &Value) -> Vec<String> {
     let mut names = Vec::new();
     if let Some(fields) = form_def.get("fields") {
         match fields {
@@ -339,7 +451,7 @@ fn render_frontmatter(form_name: &str, tags: &[String]) -> String {
     frontmatter
 }
 
-fn section_value_to_string(value: &Value) -> String {
+pub(crate) fn section_value_to_string(value: &Value) -> String {
     match value {
         Value::Null => String::new(),
         Value::String(s) => s.clone(),
@@ -364,7 +476,10 @@ fn section_value_to_string(value: &Value) -> String {
                     .join("\n")
             }
         }
-        Value::Object(_) => serde_json::to_string(value).unwrap_or_default(),
+        Value::Object(_) => match GeoPoint::from_value(value) {
+            Some(point) => format!("{}, {}", point.lat, point.lon),
+            None => serde_json::to_string(value).unwrap_or_default(),
+        },
     }
 }
 
@@ -431,7 +546,8 @@ pub(crate) fn render_markdown_for_form(
     form_def: &Value,
 ) -> String {
     let field_order = form_field_names(form_def);
-    let merged_fields = merge_entry_fields(fields, extra_attributes);
+    let mut merged_fields = merge_entry_fields(fields, extra_attributes);
+    field_crypto::mask_fields(&mut merged_fields);
     render_markdown(title, form_name, tags, &merged_fields, &field_order)
 }
 
@@ -641,6 +757,26 @@ fn object_list_array_from_values(
     Ok(Arc::new(list_builder.finish()))
 }
 
+fn geopoint_array_from_value(
+    value: Option<&Value>,
+    field: &arrow_schema::Field,
+) -> Result<ArrayRef> {
+    let struct_fields = struct_fields_from_field(field)?;
+    let point = value.and_then(GeoPoint::from_value);
+    let mut builder = StructBuilder::from_fields(struct_fields, 1);
+    for (idx, coordinate) in [point.map(|p| p.lat), point.map(|p| p.lon)]
+        .into_iter()
+        .enumerate()
+    {
+        builder
+            .field_builder::<Float64Builder>(idx)
+            .ok_or_else(|| anyhow!("Invalid geopoint field builder: {}", field.name()))?
+            .append_option(coordinate);
+    }
+    builder.append(point.is_some());
+    Ok(Arc::new(builder.finish()))
+}
+
 fn list_struct_fields_from_field(list_field: &arrow_schema::Field) -> Result<Fields> {
     let element_field = list_element_field(list_field)?;
     match element_field.data_type() {
@@ -972,10 +1108,25 @@ fn struct_array_from_fields(
                     .and_then(|v| i32::try_from(v).ok());
                 Arc::new(Int32Array::from(vec![number]))
             }
-            "long" => {
+            "long" | "duration" => {
                 let number = value.and_then(|v| v.as_i64());
                 Arc::new(Int64Array::from(vec![number]))
             }
+            "decimal" => {
+                let DataType::Decimal128(precision, scale) = *field.data_type() else {
+                    return Err(anyhow!("Decimal field {} has no decimal column", name));
+                };
+                let spec = DecimalSpec {
+                    precision: u32::from(precision),
+                    scale: u32::try_from(scale).unwrap_or_default(),
+                    currency: None,
+                };
+                let units = value.and_then(|v| v.as_str()).and_then(|v| spec.units(v));
+                let array = Decimal128Array::from(vec![units])
+                    .with_precision_and_scale(precision, scale)
+                    .map_err(|e| anyhow!("Failed to build decimal array: {}", e))?;
+                Arc::new(array)
+            }
             "boolean" => {
                 let bool_value = value.and_then(|v| v.as_bool());
                 Arc::new(BooleanArray::from(vec![bool_value]))
@@ -1035,6 +1186,7 @@ fn struct_array_from_fields(
             }
             "list" => list_array_from_values(value, field.as_ref())?,
             "object_list" => object_list_array_from_values(value, field.as_ref())?,
+            "geopoint" => geopoint_array_from_value(value, field.as_ref())?,
             "sql" | "markdown" | "string" | "row_reference" => {
                 let string_value = value.and_then(|v| v.as_str()).map(|s| s.to_string());
                 Arc::new(StringArray::from(vec![string_value]))
@@ -1143,7 +1295,7 @@ fn value_from_struct_array(struct_array: &StructArray, row: usize, form_def: &Va
                         Some(Value::Number(array.value(row).into()))
                     }
                 }),
-            "long" => column
+            "long" | "duration" => column
                 .as_any()
                 .downcast_ref::<Int64Array>()
                 .and_then(|array| {
@@ -1153,6 +1305,20 @@ fn value_from_struct_array(struct_array: &StructArray, row: usize, form_def: &Va
                         Some(Value::Number(array.value(row).into()))
                     }
                 }),
+            "decimal" => column
+                .as_any()
+                .downcast_ref::<Decimal128Array>()
+                .and_then(|array| {
+                    if array.is_null(row) {
+                        None
+                    } else {
+                        let scale = u32::try_from(array.scale()).unwrap_or_default();
+                        Some(Value::String(decimal::format_units(
+                            array.value(row),
+                            scale,
+                        )))
+                    }
+                }),
             "boolean" => column
                 .as_any()
                 .downcast_ref::<BooleanArray>()
@@ -1263,6 +1429,22 @@ fn value_from_struct_array(struct_array: &StructArray, row: usize, form_def: &Va
                         Some(Value::Array(items))
                     }
                 }),
+            "geopoint" => column
+                .as_any()
+                .downcast_ref::<StructArray>()
+                .and_then(|array| {
+                    if array.is_null(row) {
+                        return None;
+                    }
+                    let coordinate = |name: &str| {
+                        array
+                            .column_by_name(name)
+                            .and_then(|col| col.as_any().downcast_ref::<Float64Array>())
+                            .filter(|col| !col.is_null(row))
+                            .map(|col| col.value(row))
+                    };
+                    GeoPoint::new(coordinate("lat")?, coordinate("lon")?).map(GeoPoint::to_value)
+                }),
             "object_list" => column
                 .as_any()
                 .downcast_ref::<ListArray>()
@@ -1438,10 +1620,11 @@ fn entry_rows_from_batches(
     Ok(rows)
 }
 
-fn revision_rows_from_batches(
+/// Revision rows as stored; rows written as deltas carry empty field values.
+fn stored_revision_rows_from_batches(
     batches: &[RecordBatch],
     form_def: &Value,
-) -> Result<Vec<RevisionRow>> {
+) -> Result<Vec<(RevisionRow, Option<RevisionDelta>)>> {
     let mut rows = Vec::new();
     for batch in batches {
         let revision_ids = column_as::<StringArray>(batch, "revision_id")?;
@@ -1456,6 +1639,17 @@ fn revision_rows_from_batches(
         let checksums = column_as::<StringArray>(batch, "markdown_checksum")?;
         let integrity = column_as::<StructArray>(batch, "integrity")?;
         let restored_from = column_as::<StringArray>(batch, "restored_from")?;
+        // Tables created before delta storage have no delta column.
+        let deltas = batch
+            .column_by_name(REVISION_DELTA_COLUMN)
+            .and_then(|col| col.as_any().downcast_ref::<StringArray>());
+        // Nor do tables created before revision messages have these.
+        let messages = batch
+            .column_by_name("message")
+            .and_then(|col| col.as_any().downcast_ref::<StringArray>());
+        let revision_metadata = batch
+            .column_by_name("revision_metadata")
+            .and_then(|col| col.as_any().downcast_ref::<StringArray>());
 
         for row_idx in 0..batch.num_rows() {
             if revision_ids.is_null(row_idx) {
@@ -1480,7 +1674,14 @@ fn revision_rows_from_batches(
                 None => Value::Object(Map::new()),
             };
 
-            rows.push(RevisionRow {
+            let delta = match deltas {
+                Some(array) if !array.is_null(row_idx) => {
+                    Some(serde_json::from_str::<RevisionDelta>(array.value(row_idx))?)
+                }
+                _ => None,
+            };
+
+            let row = RevisionRow {
                 revision_id: revision_ids.value(row_idx).to_string(),
                 entry_id: if entry_ids.is_null(row_idx) {
                     "".to_string()
@@ -1515,12 +1716,68 @@ fn revision_rows_from_batches(
                 } else {
                     Some(restored_from.value(row_idx).to_string())
                 },
-            });
+                message: match messages {
+                    Some(array) if !array.is_null(row_idx) => {
+                        Some(array.value(row_idx).to_string())
+                    }
+                    _ => None,
+                },
+                revision_metadata: match revision_metadata {
+                    Some(array) if !array.is_null(row_idx) => {
+                        Some(serde_json::from_str(array.value(row_idx))?)
+                    }
+                    _ => None,
+                },
+            };
+            rows.push((row, delta));
         }
     }
     Ok(rows)
 }
 
+fn revision_rows_from_batches(
+    batches: &[RecordBatch],
+    form_def: &Value,
+) -> Result<Vec<RevisionRow>> {
+    let stored = stored_revision_rows_from_batches(batches, form_def)?;
+    let resolved = {
+        let snapshots: std::collections::HashMap<&str, &RevisionRow> = stored
+            .iter()
+            .filter(|(_, delta)| delta.is_none())
+            .map(|(row, _)| (row.revision_id.as_str(), row))
+            .collect();
+        stored
+            .iter()
+            .map(|(row, delta)| {
+                delta
+                    .as_ref()
+                    .map(|delta| {
+                        let base = snapshots.get(delta.base.as_str()).ok_or_else(|| {
+                            anyhow!(
+                                "Snapshot revision {} missing for revision {}",
+                                delta.base,
+                                row.revision_id
+                            )
+                        })?;
+                        revision_delta::apply_delta(base, delta)
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+    Ok(stored
+        .into_iter()
+        .zip(resolved)
+        .map(|((mut row, _), values)| {
+            if let Some((fields, extra_attributes)) = values {
+                row.fields = fields;
+                row.extra_attributes = extra_attributes;
+            }
+            row
+        })
+        .collect())
+}
+
 fn entry_row_to_record_batch(
     row: &EntryRow,
     form_def: &Value,
@@ -1572,8 +1829,22 @@ fn revision_row_to_record_batch(
     row: &RevisionRow,
     form_def: &Value,
     table_schema: &iceberg::spec::Schema,
+    delta: Option<&RevisionDelta>,
 ) -> Result<RecordBatch> {
+    if (row.message.is_some() || row.revision_metadata.is_some())
+        && table_schema.field_by_name("message").is_none()
+    {
+        return Err(anyhow!(
+            "Revisions table predates revision messages; migrate the form to store them"
+        ));
+    }
     let arrow_schema = Arc::new(schema_to_arrow_schema(table_schema)?);
+    let delta_json = delta.map(serde_json::to_string).transpose()?;
+    let metadata_json = row
+        .revision_metadata
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()?;
 
     let mut arrays = Vec::new();
     for field in arrow_schema.fields() {
@@ -1587,10 +1858,15 @@ fn revision_row_to_record_batch(
                 to_timestamp_micros(row.timestamp),
             )])),
             "author" => Arc::new(StringArray::from(vec![Some(row.author.clone())])),
+            // Delta rows keep field values only in the delta column.
+            "fields" if delta.is_some() => arrow_array::new_null_array(field.data_type(), 1),
             "fields" => {
                 let struct_fields = struct_fields_from_field(field.as_ref())?;
                 struct_array_from_fields(form_def, &row.fields, &struct_fields)?
             }
+            "extra_attributes" if delta.is_some() => {
+                Arc::new(StringArray::from(vec![None::<String>]))
+            }
             "extra_attributes" => {
                 let json_value = extra_attributes_to_string(&row.extra_attributes);
                 Arc::new(StringArray::from(vec![json_value]))
@@ -1603,6 +1879,9 @@ fn revision_row_to_record_batch(
                 struct_array_from_integrity(&row.integrity, &struct_fields)?
             }
             "restored_from" => Arc::new(StringArray::from(vec![row.restored_from.clone()])),
+            REVISION_DELTA_COLUMN => Arc::new(StringArray::from(vec![delta_json.clone()])),
+            "message" => Arc::new(StringArray::from(vec![row.message.clone()])),
+            "revision_metadata" => Arc::new(StringArray::from(vec![metadata_json.clone()])),
             other => {
                 return Err(anyhow!("Unexpected column in revisions schema: {}", other));
             }
@@ -1613,7 +1892,11 @@ fn revision_row_to_record_batch(
     RecordBatch::try_new(arrow_schema, arrays).map_err(|e| anyhow!("Record batch error: {}", e))
 }
 
-async fn write_record_batch(table: &iceberg::table::Table, batch: RecordBatch) -> Result<DataFile> {
+/// Write `batches` to a single data file of `table`.
+async fn write_record_batches(
+    table: &iceberg::table::Table,
+    batches: &[RecordBatch],
+) -> Result<DataFile> {
     let schema = table.metadata().current_schema();
     let props = WriterProperties::builder().build();
     let output_path = format!(
@@ -1625,7 +1908,9 @@ async fn write_record_batch(table: &iceberg::table::Table, batch: RecordBatch) -
     let mut writer = ParquetWriterBuilder::new(props, schema.clone())
         .build(output_file)
         .await?;
-    writer.write(&batch).await?;
+    for batch in batches {
+        writer.write(batch).await?;
+    }
     let builders = writer.close().await?;
     let mut data_files = Vec::new();
     for builder in builders {
@@ -1642,35 +1927,276 @@ async fn write_record_batch(table: &iceberg::table::Table, batch: RecordBatch) -
 }
 
 async fn append_entry_row_to_table(
+    op: &Operator,
+    ws_path: &str,
     catalog: &MemoryCatalog,
     table: &iceberg::table::Table,
     row: &EntryRow,
     form_def: &Value,
 ) -> Result<()> {
     let batch = entry_row_to_record_batch(row, form_def, table.metadata().current_schema())?;
-    let data_file = write_record_batch(table, batch).await?;
+    let data_file = write_record_batches(table, &[batch]).await?;
     let tx = Transaction::new(table);
     let action = tx.fast_append().add_data_files(vec![data_file]);
     let tx = action.apply(tx)?;
-    tx.commit(catalog).await?;
+    iceberg_store::commit_table(op, ws_path, catalog, tx).await?;
     Ok(())
 }
 
+/// Delta against the entry's latest snapshot, or `None` when this revision
+/// should be stored in full.
+async fn revision_delta_for(
+    table: &iceberg::table::Table,
+    row: &RevisionRow,
+    form_def: &Value,
+) -> Result<Option<RevisionDelta>> {
+    let schema = table.metadata().current_schema();
+    let interval = revision_delta::snapshot_interval(form_def);
+    // A root revision has nothing to diff against, so skip scanning the table.
+    if row.parent_revision_id.is_none()
+        || interval <= 1
+        || schema.field_by_name(REVISION_DELTA_COLUMN).is_none()
+    {
+        return Ok(None);
+    }
+    let batches = scan_table_batches(table).await?;
+    let stored = stored_revision_rows_from_batches(&batches, form_def)?;
+    let mut snapshot: Option<&RevisionRow> = None;
+    for (rev, delta) in &stored {
+        if rev.entry_id != row.entry_id || delta.is_some() {
+            continue;
+        }
+        if snapshot.is_none_or(|current| rev.timestamp >= current.timestamp) {
+            snapshot = Some(rev);
+        }
+    }
+    let Some(snapshot) = snapshot else {
+        return Ok(None);
+    };
+    let since_snapshot = stored
+        .iter()
+        .filter(|(_, delta)| {
+            delta
+                .as_ref()
+                .is_some_and(|delta| delta.base == snapshot.revision_id)
+        })
+        .count() as u64;
+    if since_snapshot + 1 >= interval {
+        return Ok(None);
+    }
+    // Diff the values as they will read back, so reconstruction matches a full row.
+    let batch = revision_row_to_record_batch(row, form_def, schema, None)?;
+    let normalized = stored_revision_rows_from_batches(&[batch], form_def)?
+        .into_iter()
+        .next()
+        .map(|(normalized, _)| normalized)
+        .ok_or_else(|| anyhow!("Failed to encode revision {}", row.revision_id))?;
+    Ok(Some(revision_delta::encode_delta(snapshot, &normalized)))
+}
+
 async fn append_revision_row_to_table(
+    op: &Operator,
+    ws_path: &str,
     catalog: &MemoryCatalog,
     table: &iceberg::table::Table,
     row: &RevisionRow,
     form_def: &Value,
 ) -> Result<()> {
-    let batch = revision_row_to_record_batch(row, form_def, table.metadata().current_schema())?;
-    let data_file = write_record_batch(table, batch).await?;
+    let delta = revision_delta_for(table, row, form_def).await?;
+    let batch = revision_row_to_record_batch(
+        row,
+        form_def,
+        table.metadata().current_schema(),
+        delta.as_ref(),
+    )?;
+    let data_file = write_record_batches(table, &[batch]).await?;
     let tx = Transaction::new(table);
     let action = tx.fast_append().add_data_files(vec![data_file]);
     let tx = action.apply(tx)?;
-    tx.commit(catalog).await?;
+    iceberg_store::commit_table(op, ws_path, catalog, tx).await?;
+    Ok(())
+}
+
+/// Append `rows` and their `revisions` to a form's tables, one data file and
+/// one commit per table however many rows there are. Revisions are stored as
+/// full snapshots.
+pub(crate) async fn append_entry_rows(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    form_def: &Value,
+    rows: &[EntryRow],
+    revisions: &[RevisionRow],
+) -> Result<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    let (catalog, table) = iceberg_store::load_entries_table(op, ws_path, form_name).await?;
+    let schema = table.metadata().current_schema();
+    let batches = rows
+        .iter()
+        .map(|row| entry_row_to_record_batch(row, form_def, schema))
+        .collect::<Result<Vec<_>>>()?;
+    append_batches(op, ws_path, &catalog, &table, &batches).await?;
+
+    let (catalog, table) = iceberg_store::load_revisions_table(op, ws_path, form_name).await?;
+    let schema = table.metadata().current_schema();
+    let batches = revisions
+        .iter()
+        .map(|row| revision_row_to_record_batch(row, form_def, schema, None))
+        .collect::<Result<Vec<_>>>()?;
+    append_batches(op, ws_path, &catalog, &table, &batches).await
+}
+
+/// Commit `batches` to `table` as one data file; nothing is written for none.
+async fn append_batches(
+    op: &Operator,
+    ws_path: &str,
+    catalog: &MemoryCatalog,
+    table: &iceberg::table::Table,
+    batches: &[RecordBatch],
+) -> Result<()> {
+    if batches.is_empty() {
+        return Ok(());
+    }
+    let data_file = write_record_batches(table, batches).await?;
+    let tx = Transaction::new(table);
+    let tx = tx.fast_append().add_data_files(vec![data_file]).apply(tx)?;
+    iceberg_store::commit_table(op, ws_path, catalog, tx).await?;
     Ok(())
 }
 
+async fn count_data_files(table: &iceberg::table::Table) -> Result<usize> {
+    let tasks: Vec<_> = table
+        .scan()
+        .build()?
+        .plan_files()
+        .await?
+        .try_collect()
+        .await?;
+    Ok(tasks.len())
+}
+
+/// Stage a table beside `live` holding only the batches `build` encodes
+/// against the staged schema; see [`iceberg_store::stage_table`].
+async fn stage_table_batches<F>(
+    op: &Operator,
+    ws_path: &str,
+    live: &iceberg::table::Table,
+    build: F,
+) -> Result<iceberg::table::Table>
+where
+    F: FnOnce(&iceberg::spec::SchemaRef) -> Result<Vec<RecordBatch>>,
+{
+    let (catalog, staged) = iceberg_store::stage_table(op, ws_path, live).await?;
+    let committed = async {
+        let batches = build(staged.metadata().current_schema())?;
+        if batches.is_empty() {
+            return Ok(staged.clone());
+        }
+        let data_file = write_record_batches(&staged, &batches).await?;
+        let tx = Transaction::new(&staged);
+        let tx = tx.fast_append().add_data_files(vec![data_file]).apply(tx)?;
+        Ok(tx.commit(catalog.as_ref()).await?)
+    }
+    .await;
+    if committed.is_err() {
+        iceberg_store::discard_staged_table(op, ws_path, &staged).await;
+    }
+    committed
+}
+
+/// Data files of a form's tables before and after [`compact_form_tables`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FormCompaction {
+    pub data_files_before: usize,
+    pub data_files_after: usize,
+    /// Entry rows superseded by a later row of the same entry.
+    pub superseded_rows: usize,
+}
+
+/// Rewrite a form's tables as one data file each, keeping the latest row of
+/// every entry and every revision as stored (deltas stay deltas). Returns
+/// `None` when the tables are already compact.
+///
+/// The compacted tables are written beside the live ones and swapped in
+/// through the metadata pointer, so the old data stays readable until the
+/// swap. A table that gains a commit while being rewritten is left alone and
+/// an error returned. Callers hold the space's migration lease to keep form
+/// migrations out.
+pub(crate) async fn compact_form_tables(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    form_def: &Value,
+) -> Result<Option<FormCompaction>> {
+    let (_, entries_table) = iceberg_store::load_entries_table(op, ws_path, form_name).await?;
+    let (_, revisions_table) = iceberg_store::load_revisions_table(op, ws_path, form_name).await?;
+    let data_files_before =
+        count_data_files(&entries_table).await? + count_data_files(&revisions_table).await?;
+
+    let stored = entry_rows_from_batches(
+        &scan_table_batches(&entries_table).await?,
+        form_def,
+        form_name,
+    )?;
+    let stored_count = stored.len();
+    let mut latest: HashMap<String, EntryRow> = HashMap::new();
+    for row in stored {
+        let replace = match latest.get(&row.entry_id) {
+            Some(existing) => row.updated_at >= existing.updated_at,
+            None => true,
+        };
+        if replace {
+            latest.insert(row.entry_id.clone(), row);
+        }
+    }
+    let mut rows: Vec<EntryRow> = latest.into_values().collect();
+    rows.sort_by(|a, b| a.entry_id.cmp(&b.entry_id));
+    let revisions =
+        stored_revision_rows_from_batches(&scan_table_batches(&revisions_table).await?, form_def)?;
+
+    let data_files_after = usize::from(!rows.is_empty()) + usize::from(!revisions.is_empty());
+    let superseded_rows = stored_count - rows.len();
+    if data_files_before <= data_files_after && superseded_rows == 0 {
+        return Ok(None);
+    }
+
+    let staged_entries = stage_table_batches(op, ws_path, &entries_table, |schema| {
+        rows.iter()
+            .map(|row| entry_row_to_record_batch(row, form_def, schema))
+            .collect()
+    })
+    .await?;
+    let staged_revisions = match stage_table_batches(op, ws_path, &revisions_table, |schema| {
+        revisions
+            .iter()
+            .map(|(row, delta)| revision_row_to_record_batch(row, form_def, schema, delta.as_ref()))
+            .collect()
+    })
+    .await
+    {
+        Ok(staged) => staged,
+        Err(err) => {
+            iceberg_store::discard_staged_table(op, ws_path, &staged_entries).await;
+            return Err(err);
+        }
+    };
+    if let Err(err) =
+        iceberg_store::swap_staged_table(op, ws_path, &entries_table, &staged_entries).await
+    {
+        iceberg_store::discard_staged_table(op, ws_path, &staged_revisions).await;
+        return Err(err);
+    }
+    iceberg_store::swap_staged_table(op, ws_path, &revisions_table, &staged_revisions).await?;
+
+    Ok(Some(FormCompaction {
+        data_files_before,
+        data_files_after,
+        superseded_rows,
+    }))
+}
+
 pub(crate) async fn list_form_names(op: &Operator, ws_path: &str) -> Result<Vec<String>> {
     form::list_form_names(op, ws_path).await
 }
@@ -1736,7 +2262,31 @@ pub(crate) async fn write_entry_row(
     let (catalog, table): (Arc<MemoryCatalog>, iceberg::table::Table) =
         iceberg_store::load_entries_table(op, ws_path, form_name).await?;
     let form_def = form::read_form_definition(op, ws_path, form_name).await?;
-    append_entry_row_to_table(catalog.as_ref(), &table, row, &form_def).await
+    append_entry_row_to_table(op, ws_path, catalog.as_ref(), &table, row, &form_def).await?;
+    rollup::apply_entry_rows(op, ws_path, form_name, std::slice::from_ref(row)).await
+}
+
+/// Append rows of one form as a single data file and snapshot.
+pub(crate) async fn write_entry_rows(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    rows: &[EntryRow],
+) -> Result<()> {
+    let (catalog, table): (Arc<MemoryCatalog>, iceberg::table::Table) =
+        iceberg_store::load_entries_table(op, ws_path, form_name).await?;
+    let form_def = form::read_form_definition(op, ws_path, form_name).await?;
+    let schema = table.metadata().current_schema();
+    let batches = rows
+        .iter()
+        .map(|row| entry_row_to_record_batch(row, &form_def, schema))
+        .collect::<Result<Vec<_>>>()?;
+    let data_file = write_record_batches(&table, &batches).await?;
+    let tx = Transaction::new(&table);
+    let action = tx.fast_append().add_data_files(vec![data_file]);
+    let tx = action.apply(tx)?;
+    iceberg_store::commit_table(op, ws_path, catalog.as_ref(), tx).await?;
+    rollup::apply_entry_rows(op, ws_path, form_name, rows).await
 }
 
 pub(crate) async fn list_entry_rows(
@@ -1745,11 +2295,21 @@ pub(crate) async fn list_entry_rows(
 ) -> Result<Vec<(String, EntryRow)>> {
     let mut latest: std::collections::HashMap<String, (String, EntryRow)> =
         std::collections::HashMap::new();
-    for form_name in list_form_names(op, ws_path).await? {
-        let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
-        let (_, table) = iceberg_store::load_entries_table(op, ws_path, &form_name).await?;
-        let batches = scan_table_batches(&table).await?;
-        let rows = entry_rows_from_batches(&batches, &form_def, &form_name)?;
+    // Forms load concurrently but merge in listing order, so ties between
+    // forms resolve the same way as a serial scan.
+    let per_form: Vec<(String, Vec<EntryRow>)> =
+        futures::stream::iter(list_form_names(op, ws_path).await?)
+            .map(|form_name| async move {
+                let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+                let (_, table) = iceberg_store::load_entries_table(op, ws_path, &form_name).await?;
+                let batches = scan_table_batches(&table).await?;
+                let rows = entry_rows_from_batches(&batches, &form_def, &form_name)?;
+                Ok::<_, anyhow::Error>((form_name, rows))
+            })
+            .buffered(storage::list_parallelism())
+            .try_collect()
+            .await?;
+    for (form_name, rows) in per_form {
         for row in rows {
             let entry = latest.get(&row.entry_id);
             let should_replace = match entry {
@@ -1798,6 +2358,42 @@ pub(crate) async fn list_form_revision_rows(
     revision_rows_from_batches(&batches, form_def)
 }
 
+/// Revisions of a form stored as deltas rather than full snapshots.
+pub(crate) async fn count_delta_revisions(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    form_def: &Value,
+) -> Result<usize> {
+    let (_, table) = iceberg_store::load_revisions_table(op, ws_path, form_name).await?;
+    let batches = scan_table_batches(&table).await?;
+    Ok(stored_revision_rows_from_batches(&batches, form_def)?
+        .iter()
+        .filter(|(_, delta)| delta.is_some())
+        .count())
+}
+
+/// Latest revision per entry of a form, keyed by entry id.
+pub(crate) async fn latest_form_revisions(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    form_def: &Value,
+) -> Result<std::collections::HashMap<String, RevisionRow>> {
+    let mut latest: std::collections::HashMap<String, RevisionRow> =
+        std::collections::HashMap::new();
+    for row in list_form_revision_rows(op, ws_path, form_name, form_def).await? {
+        let replace = match latest.get(&row.entry_id) {
+            Some(existing) => row.timestamp >= existing.timestamp,
+            None => true,
+        };
+        if replace {
+            latest.insert(row.entry_id.clone(), row);
+        }
+    }
+    Ok(latest)
+}
+
 pub(crate) async fn append_revision_row_for_form(
     op: &Operator,
     ws_path: &str,
@@ -1807,7 +2403,7 @@ pub(crate) async fn append_revision_row_for_form(
 ) -> Result<()> {
     let (catalog, table): (Arc<MemoryCatalog>, iceberg::table::Table) =
         iceberg_store::load_revisions_table(op, ws_path, form_name).await?;
-    append_revision_row_to_table(catalog.as_ref(), &table, row, form_def).await
+    append_revision_row_to_table(op, ws_path, catalog.as_ref(), &table, row, form_def).await
 }
 
 fn extract_tags(frontmatter: &Value) -> Vec<String> {
@@ -1828,6 +2424,22 @@ fn extract_form(frontmatter: &Value) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// Confirm unique claims once the row holding them is written. The write is
+/// already durable, so a failure here only leaves the claims pending until
+/// they expire and fall back to checking the row.
+async fn commit_unique_reservation(
+    op: &Operator,
+    ws_path: &str,
+    reservation: unique::UniqueReservation,
+) {
+    if let Err(error) = reservation.commit(op, ws_path).await {
+        eprintln!(
+            "failed to confirm unique claims after entry write (ws_path={}): {}",
+            ws_path, error
+        );
+    }
+}
+
 pub async fn create_entry<I: IntegrityProvider>(
     op: &Operator,
     ws_path: &str,
@@ -1836,55 +2448,91 @@ pub async fn create_entry<I: IntegrityProvider>(
     author: &str,
     integrity: &I,
 ) -> Result<EntryMeta> {
+    create_entry_with_note(
+        op,
+        ws_path,
+        entry_id,
+        content,
+        author,
+        &RevisionNote::default(),
+        integrity,
+    )
+    .await
+}
+
+/// [`create_entry`] saving `note` with the first revision.
+pub async fn create_entry_with_note<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    content: &str,
+    author: &str,
+    note: &RevisionNote,
+    integrity: &I,
+) -> Result<EntryMeta> {
+    create_entry_with_assets(
+        op,
+        ws_path,
+        entry_id,
+        content,
+        author,
+        note,
+        Vec::new(),
+        integrity,
+    )
+    .await
+}
+
+/// [`create_entry_with_note`] with `assets` already stored in the space
+/// attached from the first revision, as importers attach uploaded files.
+#[allow(clippy::too_many_arguments)]
+pub async fn create_entry_with_assets<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    content: &str,
+    author: &str,
+    note: &RevisionNote,
+    assets: Vec<Value>,
+    integrity: &I,
+) -> Result<EntryMeta> {
+    let note = note.normalized();
     if find_entry_form(op, ws_path, entry_id).await?.is_some() {
         return Err(anyhow!("Entry already exists: {}", entry_id));
     }
 
-    let normalized_content = normalize_ugoite_links(content);
+    let size_config = entry_size::load_entry_size_config(op, ws_path).await?;
+    let sized = entry_size::apply_size_policy(
+        op,
+        ws_path,
+        entry_id,
+        &normalize_ugoite_links(content),
+        &size_config,
+    )
+    .await?;
+    let normalized_content = sized.markdown;
     let (frontmatter, sections) = parse_markdown(&normalized_content);
     let form_name =
         extract_form(&frontmatter).ok_or_else(|| anyhow!("Form is required for entry creation"))?;
-    let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
-
-    let form_fields = form_field_names(&form_def);
-    let form_set: HashSet<String> = form_fields.iter().cloned().collect();
-    let policy = extra_attributes_policy(&form_def);
-    let (extras, extra_attributes) = collect_extra_attributes(&sections, &form_set);
-    if !extras.is_empty() && policy == ExtraAttributesPolicy::Deny {
-        return Err(anyhow!("Unknown form fields: {}", extras.join(", ")));
-    }
+    let form_def = form::read_form_definition_for_write(op, ws_path, &form_name).await?;
+
+    let (mut fields, extra_attributes) =
+        parse_entry_fields(&normalized_content, &sections, &form_def)?;
+    workflow::apply_workflow_state(&form_def, &mut fields, None, None)?;
+    rollup::reject_rollup_values(&form_def, &fields)?;
+    sequence::assign_sequence_values(op, ws_path, &form_name, &form_def, &mut fields, None).await?;
+    let reservation =
+        unique::reserve_unique_values(op, ws_path, &form_name, &form_def, entry_id, &fields)
+            .await?;
+    let sealed = field_crypto::seal_fields(op, ws_path, &form_def, &mut fields, None).await;
+    reservation.release_on_err(op, ws_path, sealed).await?;
 
-    let properties = index::extract_properties(&normalized_content);
-    let (casted, warnings) = index::validate_properties(&properties, &form_def)?;
-    if !warnings.is_empty() {
-        return Err(anyhow!(
-            "Form validation failed: {}",
-            serde_json::to_string(&warnings)?
-        ));
-    }
-
-    let mut fields = Map::new();
-    if let Some(obj) = properties.as_object() {
-        for (key, value) in obj {
-            if form_set.contains(key) {
-                fields.insert(key.clone(), value.clone());
-            }
-        }
-    }
-    if let Some(obj) = casted.as_object() {
-        for (key, value) in obj {
-            if form_set.contains(key) {
-                fields.insert(key.clone(), value.clone());
-            }
-        }
-    }
-
-    let title = extract_title(&normalized_content, entry_id);
-    let tags = extract_tags(&frontmatter);
-    let timestamp = now_ts();
-    let revision_id = Uuid::new_v4().to_string();
-    let checksum = integrity.checksum(&normalized_content);
-    let signature = integrity.signature(&normalized_content);
+    let title = extract_title(&normalized_content, entry_id);
+    let tags = extract_tags(&frontmatter);
+    let timestamp = now_ts();
+    let revision_id = clock::new_uuid().to_string();
+    let checksum = integrity.checksum(&normalized_content);
+    let signature = integrity.signature(&normalized_content);
 
     let entry_row = EntryRow {
         entry_id: entry_id.to_string(),
@@ -1894,11 +2542,11 @@ pub async fn create_entry<I: IntegrityProvider>(
         links: Vec::new(),
         created_at: timestamp,
         updated_at: timestamp,
-        fields: Value::Object(fields),
+        fields,
         extra_attributes: extra_attributes.clone(),
         revision_id: revision_id.clone(),
         parent_revision_id: None,
-        assets: Vec::new(),
+        assets: assets.into_iter().chain(sized.spilled_asset).collect(),
         integrity: IntegrityPayload {
             checksum: checksum.clone(),
             signature: signature.clone(),
@@ -1908,7 +2556,9 @@ pub async fn create_entry<I: IntegrityProvider>(
         author: author.to_string(),
     };
 
-    write_entry_row(op, ws_path, &form_name, entry_id, &entry_row).await?;
+    let written = write_entry_row(op, ws_path, &form_name, entry_id, &entry_row).await;
+    reservation.release_on_err(op, ws_path, written).await?;
+    commit_unique_reservation(op, ws_path, reservation).await;
 
     let revision = RevisionRow {
         revision_id: revision_id.clone(),
@@ -1924,10 +2574,50 @@ pub async fn create_entry<I: IntegrityProvider>(
             signature: signature.clone(),
         },
         restored_from: None,
+        message: note.message.clone(),
+        revision_metadata: note.revision_metadata.clone(),
     };
     let (rev_catalog, rev_table): (Arc<MemoryCatalog>, iceberg::table::Table) =
         iceberg_store::load_revisions_table(op, ws_path, &form_name).await?;
-    append_revision_row_to_table(rev_catalog.as_ref(), &rev_table, &revision, &form_def).await?;
+    append_revision_row_to_table(
+        op,
+        ws_path,
+        rev_catalog.as_ref(),
+        &rev_table,
+        &revision,
+        &form_def,
+    )
+    .await?;
+    mentions::record_mentions_after_commit(
+        op,
+        ws_path,
+        &MentionSource {
+            entry_id,
+            entry_title: &title,
+            revision_id: &revision_id,
+            author,
+            timestamp,
+        },
+        None,
+        &normalized_content,
+    )
+    .await;
+    watchers::notify_watchers_after_commit(
+        op,
+        ws_path,
+        &WatchEvent {
+            kind: WatchEventKind::Created,
+            entry_id,
+            entry_title: &title,
+            form: &form_name,
+            tags: &entry_row.tags,
+            author: Some(author),
+            revision_id: Some(&revision_id),
+            field: None,
+            timestamp,
+        },
+    )
+    .await;
 
     let ws_id = ws_path
         .trim_end_matches('/')
@@ -1955,24 +2645,414 @@ pub async fn create_entry<I: IntegrityProvider>(
     })
 }
 
+/// One entry to create in a [`create_entries_bulk`] call.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BulkEntryInput {
+    pub entry_id: String,
+    pub content: String,
+    /// Falls back to the call's default author when omitted.
+    #[serde(default)]
+    pub author: Option<String>,
+}
+
+/// Outcome of one item, in input order. Exactly one of `entry` and `error`
+/// is set.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkEntryResult {
+    pub entry_id: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<EntryMeta>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Create many entries with one integrity provider, so importers pay the key
+/// bootstrap once instead of per entry. A failing item does not stop the
+/// rest; items later in the batch see the entries created before them, so a
+/// repeated id fails as already existing.
+pub async fn create_entries_bulk<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    items: &[BulkEntryInput],
+    default_author: &str,
+    integrity: &I,
+) -> Result<Vec<BulkEntryResult>> {
+    let mut results = Vec::with_capacity(items.len());
+    for item in items {
+        let author = item.author.as_deref().unwrap_or(default_author);
+        let outcome = create_entry(
+            op,
+            ws_path,
+            &item.entry_id,
+            &item.content,
+            author,
+            integrity,
+        )
+        .await;
+        results.push(match outcome {
+            Ok(meta) => BulkEntryResult {
+                entry_id: item.entry_id.clone(),
+                ok: true,
+                entry: Some(meta),
+                error: None,
+            },
+            Err(err) => BulkEntryResult {
+                entry_id: item.entry_id.clone(),
+                ok: false,
+                entry: None,
+                error: Some(err.to_string()),
+            },
+        });
+    }
+    Ok(results)
+}
+
+/// Which entries a listing, search or query admits by deletion state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeletedFilter {
+    /// Live entries only.
+    #[default]
+    Exclude,
+    /// Live and soft-deleted entries.
+    Include,
+    /// Soft-deleted entries only, as shown by a trash view.
+    Only,
+}
+
+impl DeletedFilter {
+    /// Map `include_deleted`/`only_deleted` flags; `only_deleted` wins.
+    pub fn from_flags(include_deleted: bool, only_deleted: bool) -> Self {
+        match (include_deleted, only_deleted) {
+            (_, true) => Self::Only,
+            (true, false) => Self::Include,
+            (false, false) => Self::Exclude,
+        }
+    }
+
+    pub fn admits(self, deleted: bool) -> bool {
+        match self {
+            Self::Exclude => !deleted,
+            Self::Include => true,
+            Self::Only => deleted,
+        }
+    }
+
+    /// Add `deleted`/`deleted_at` to `record` whenever deleted entries may
+    /// appear, so callers can tell trashed results apart.
+    pub(crate) fn annotate(self, record: &mut Value, row: &EntryRow) {
+        if self == Self::Exclude {
+            return;
+        }
+        if let Some(obj) = record.as_object_mut() {
+            obj.insert("deleted".to_string(), Value::Bool(row.deleted));
+            obj.insert("deleted_at".to_string(), serde_json::json!(row.deleted_at));
+        }
+    }
+}
+
+/// Sort key for [`ListEntriesOptions::sort_by`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntrySortField {
+    Title,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Filters and ordering for [`list_entries_with_options`]. Time bounds are
+/// unix seconds; `*_after` is inclusive and `*_before` exclusive.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ListEntriesOptions {
+    pub form: Option<String>,
+    /// Entries whose first revision was written by this author.
+    pub author: Option<String>,
+    pub created_after: Option<f64>,
+    pub created_before: Option<f64>,
+    pub updated_after: Option<f64>,
+    pub updated_before: Option<f64>,
+    pub include_deleted: bool,
+    pub only_deleted: bool,
+    /// Without a sort key entries are ordered by id.
+    pub sort_by: Option<EntrySortField>,
+    pub order: SortDirection,
+}
+
+impl ListEntriesOptions {
+    pub fn with_deleted(deleted: DeletedFilter) -> Self {
+        Self {
+            include_deleted: deleted == DeletedFilter::Include,
+            only_deleted: deleted == DeletedFilter::Only,
+            ..Self::default()
+        }
+    }
+
+    pub fn deleted(&self) -> DeletedFilter {
+        DeletedFilter::from_flags(self.include_deleted, self.only_deleted)
+    }
+
+    fn admits(&self, form_name: &str, row: &EntryRow, creator: Option<&str>) -> bool {
+        let in_range = |value: f64, after: Option<f64>, before: Option<f64>| {
+            after.is_none_or(|after| value >= after) && before.is_none_or(|before| value < before)
+        };
+        self.deleted().admits(row.deleted)
+            && self.form.as_deref().is_none_or(|form| form == form_name)
+            && self
+                .author
+                .as_deref()
+                .is_none_or(|author| creator == Some(author))
+            && in_range(row.created_at, self.created_after, self.created_before)
+            && in_range(row.updated_at, self.updated_after, self.updated_before)
+    }
+
+    fn compare(&self, left: &EntryRow, right: &EntryRow) -> std::cmp::Ordering {
+        let ordering = match self.sort_by {
+            Some(EntrySortField::Title) => {
+                left.title.to_lowercase().cmp(&right.title.to_lowercase())
+            }
+            Some(EntrySortField::UpdatedAt) => left.updated_at.total_cmp(&right.updated_at),
+            None => std::cmp::Ordering::Equal,
+        };
+        let ordering = ordering.then_with(|| left.entry_id.cmp(&right.entry_id));
+        match self.order {
+            SortDirection::Asc => ordering,
+            SortDirection::Desc => ordering.reverse(),
+        }
+    }
+}
+
 pub async fn list_entries(op: &Operator, ws_path: &str) -> Result<Vec<Value>> {
+    list_entries_with_deleted(op, ws_path, DeletedFilter::Exclude).await
+}
+
+/// List entries admitted by `deleted`, annotated with their deletion state
+/// unless only live entries are listed.
+pub async fn list_entries_with_deleted(
+    op: &Operator,
+    ws_path: &str,
+    deleted: DeletedFilter,
+) -> Result<Vec<Value>> {
     let mut entries = Vec::new();
     for (form_name, row) in list_entry_rows(op, ws_path).await? {
-        if row.deleted {
+        if !deleted.admits(row.deleted) {
             continue;
         }
-        let merged_fields = merge_entry_fields(&row.fields, &row.extra_attributes);
-        entries.push(serde_json::json!({
-            "id": row.entry_id,
-            "title": row.title,
-            "form": form_name,
-            "tags": row.tags,
-            "properties": merged_fields,
-            "links": row.links,
-            "created_at": row.created_at,
-            "updated_at": row.updated_at,
-        }));
+        entries.push(entry_list_record(&form_name, &row, deleted));
+    }
+    Ok(entries)
+}
+
+/// Author of each entry's earliest revision in the given forms, keyed by
+/// entry id.
+async fn entry_creators(
+    op: &Operator,
+    ws_path: &str,
+    form_names: std::collections::BTreeSet<&str>,
+) -> Result<std::collections::HashMap<String, String>> {
+    let mut first: std::collections::HashMap<String, RevisionRow> =
+        std::collections::HashMap::new();
+    for form_name in form_names {
+        let form_def = form::read_form_definition(op, ws_path, form_name).await?;
+        for row in list_form_revision_rows(op, ws_path, form_name, &form_def).await? {
+            let replace = match first.get(&row.entry_id) {
+                Some(existing) => row.timestamp < existing.timestamp,
+                None => true,
+            };
+            if replace {
+                first.insert(row.entry_id.clone(), row);
+            }
+        }
+    }
+    Ok(first
+        .into_iter()
+        .map(|(entry_id, row)| (entry_id, row.author))
+        .collect())
+}
+
+async fn filtered_entry_rows(
+    op: &Operator,
+    ws_path: &str,
+    options: &ListEntriesOptions,
+    viewer: Option<&EntryViewer>,
+) -> Result<Vec<(String, EntryRow)>> {
+    let acls = match viewer {
+        Some(_) => entry_acl::list_entry_acls(op, ws_path).await?,
+        None => std::collections::HashMap::new(),
+    };
+    let rows = list_entry_rows(op, ws_path).await?;
+    // Entry rows do not carry their author, so only an author filter pays
+    // for the revision scan.
+    let creators = match options.author {
+        Some(_) => {
+            entry_creators(
+                op,
+                ws_path,
+                rows.iter()
+                    .map(|(form_name, _)| form_name.as_str())
+                    .collect(),
+            )
+            .await?
+        }
+        None => std::collections::HashMap::new(),
+    };
+    let mut rows: Vec<(String, EntryRow)> = rows
+        .into_iter()
+        .filter(|(form_name, row)| {
+            let creator = creators.get(&row.entry_id).map(String::as_str);
+            options.admits(form_name, row, creator)
+                && viewer.is_none_or(|viewer| entry_acl::can_read(acls.get(&row.entry_id), viewer))
+        })
+        .collect();
+    rows.sort_by(|(_, left), (_, right)| options.compare(left, right));
+    Ok(rows)
+}
+
+/// List entries matching `options`, in its order.
+pub async fn list_entries_with_options(
+    op: &Operator,
+    ws_path: &str,
+    options: &ListEntriesOptions,
+) -> Result<Vec<Value>> {
+    list_entries_filtered(op, ws_path, options, None).await
+}
+
+/// [`list_entries_with_options`] leaving out entries whose entry ACL does not
+/// admit `viewer`.
+pub async fn list_entries_with_options_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    options: &ListEntriesOptions,
+    viewer: &EntryViewer,
+) -> Result<Vec<Value>> {
+    list_entries_filtered(op, ws_path, options, Some(viewer)).await
+}
+
+async fn list_entries_filtered(
+    op: &Operator,
+    ws_path: &str,
+    options: &ListEntriesOptions,
+    viewer: Option<&EntryViewer>,
+) -> Result<Vec<Value>> {
+    let deleted = options.deleted();
+    Ok(filtered_entry_rows(op, ws_path, options, viewer)
+        .await?
+        .iter()
+        .map(|(form_name, row)| entry_list_record(form_name, row, deleted))
+        .collect())
+}
+
+fn entry_list_record(form_name: &str, row: &EntryRow, deleted: DeletedFilter) -> Value {
+    let mut merged_fields = merge_entry_fields(&row.fields, &row.extra_attributes);
+    field_crypto::mask_fields(&mut merged_fields);
+    let mut entry = serde_json::json!({
+        "id": row.entry_id,
+        "title": row.title,
+        "form": form_name,
+        "tags": row.tags,
+        "properties": merged_fields,
+        "links": row.links,
+        "created_at": row.created_at,
+        "updated_at": row.updated_at,
+    });
+    deleted.annotate(&mut entry, row);
+    entry
+}
+
+/// Largest `limit` accepted by [`list_entries_page`].
+pub const MAX_ENTRY_PAGE: usize = 1000;
+
+/// One slice of the entries matching a [`ListEntriesOptions`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EntryPage {
+    pub entries: Vec<Value>,
+    pub offset: usize,
+    pub limit: usize,
+    /// Entries matching the options across all pages.
+    pub total_count: usize,
+    pub has_more: bool,
+}
+
+/// List entries `offset..offset + limit` of those matching `options`,
+/// building records only for the requested page.
+pub async fn list_entries_page(
+    op: &Operator,
+    ws_path: &str,
+    options: &ListEntriesOptions,
+    offset: usize,
+    limit: usize,
+) -> Result<EntryPage> {
+    entry_page(op, ws_path, options, offset, limit, None).await
+}
+
+/// [`list_entries_page`] over the entries whose entry ACL admits `viewer`, so
+/// `total_count` does not disclose restricted entries either.
+pub async fn list_entries_page_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    options: &ListEntriesOptions,
+    offset: usize,
+    limit: usize,
+    viewer: &EntryViewer,
+) -> Result<EntryPage> {
+    entry_page(op, ws_path, options, offset, limit, Some(viewer)).await
+}
+
+async fn entry_page(
+    op: &Operator,
+    ws_path: &str,
+    options: &ListEntriesOptions,
+    offset: usize,
+    limit: usize,
+    viewer: Option<&EntryViewer>,
+) -> Result<EntryPage> {
+    if limit == 0 || limit > MAX_ENTRY_PAGE {
+        return Err(anyhow!(
+            "Entry page size must be between 1 and {}",
+            MAX_ENTRY_PAGE
+        ));
     }
+    let deleted = options.deleted();
+    let rows = filtered_entry_rows(op, ws_path, options, viewer).await?;
+    let total_count = rows.len();
+    let entries: Vec<Value> = rows
+        .iter()
+        .skip(offset)
+        .take(limit)
+        .map(|(form_name, row)| entry_list_record(form_name, row, deleted))
+        .collect();
+    Ok(EntryPage {
+        has_more: offset.saturating_add(entries.len()) < total_count,
+        entries,
+        offset,
+        limit,
+        total_count,
+    })
+}
+
+/// List entries, leaving out those whose entry ACL does not admit `viewer`.
+pub async fn list_entries_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    viewer: &EntryViewer,
+) -> Result<Vec<Value>> {
+    let acls = entry_acl::list_entry_acls(op, ws_path).await?;
+    let mut entries = list_entries(op, ws_path).await?;
+    entries.retain(|entry| {
+        let acl = entry
+            .get("id")
+            .and_then(Value::as_str)
+            .and_then(|id| acls.get(id));
+        entry_acl::can_read(acl, viewer)
+    });
     Ok(entries)
 }
 
@@ -2020,6 +3100,26 @@ pub async fn list_entry_summaries(
 }
 
 pub async fn get_entry(op: &Operator, ws_path: &str, entry_id: &str) -> Result<Value> {
+    entry_value(op, ws_path, entry_id, false).await
+}
+
+/// Fetch an entry with secret fields decrypted; requires the `secret_read` scope.
+pub async fn get_entry_with_secrets(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    scopes: &[String],
+) -> Result<Value> {
+    field_crypto::require_secret_scope(scopes)?;
+    entry_value(op, ws_path, entry_id, true).await
+}
+
+async fn entry_value(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    reveal_secrets: bool,
+) -> Result<Value> {
     let form_name = find_entry_form(op, ws_path, entry_id)
         .await?
         .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
@@ -2030,7 +3130,12 @@ pub async fn get_entry(op: &Operator, ws_path: &str, entry_id: &str) -> Result<V
 
     let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
     let field_order = form_field_names(&form_def);
-    let merged_fields = merge_entry_fields(&row.fields, &row.extra_attributes);
+    let mut merged_fields = merge_entry_fields(&row.fields, &row.extra_attributes);
+    if reveal_secrets {
+        field_crypto::reveal_fields(op, ws_path, &mut merged_fields).await?;
+    } else {
+        field_crypto::mask_fields(&mut merged_fields);
+    }
     let markdown = render_markdown(
         &row.title,
         &form_name,
@@ -2043,6 +3148,7 @@ pub async fn get_entry(op: &Operator, ws_path: &str, entry_id: &str) -> Result<V
         "tags": row.tags,
     });
     let sections = sections_from_fields(&merged_fields);
+    let computed = rollup::get_entry_rollups(op, ws_path, &form_def, entry_id).await?;
 
     Ok(serde_json::json!({
         "id": entry_id,
@@ -2051,7 +3157,7 @@ pub async fn get_entry(op: &Operator, ws_path: &str, entry_id: &str) -> Result<V
         "frontmatter": frontmatter,
         "sections": sections,
         "assets": row.assets,
-        "computed": Value::Object(Map::new()),
+        "computed": computed,
         "title": row.title,
         "form": row.form,
         "tags": row.tags,
@@ -2059,9 +3165,111 @@ pub async fn get_entry(op: &Operator, ws_path: &str, entry_id: &str) -> Result<V
         "created_at": row.created_at,
         "updated_at": row.updated_at,
         "integrity": serde_json::to_value(row.integrity)?,
+        "lock": serde_json::to_value(entry_lock::get_entry_lock(op, ws_path, entry_id).await?)?,
     }))
 }
 
+/// Fetch an entry, reporting it as missing when its entry ACL does not admit `viewer`.
+pub async fn get_entry_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    viewer: &EntryViewer,
+) -> Result<Value> {
+    entry_acl::require_entry_read(op, ws_path, entry_id, viewer).await?;
+    get_entry(op, ws_path, entry_id).await
+}
+
+/// An entry's metadata and current revision, without its content.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EntryHead {
+    pub id: String,
+    pub form: String,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub revision_id: String,
+    pub author: String,
+    pub created_at: f64,
+    pub updated_at: f64,
+    pub checksum: String,
+}
+
+/// Read only an entry's row, so polling clients can compare `revision_id`
+/// without rendering or downloading its content.
+pub async fn get_entry_head(op: &Operator, ws_path: &str, entry_id: &str) -> Result<EntryHead> {
+    read_entry_head(op, ws_path, entry_id, None).await
+}
+
+/// [`get_entry_head`], reporting the entry as missing when its entry ACL does
+/// not admit `viewer`.
+pub async fn get_entry_head_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    viewer: &EntryViewer,
+) -> Result<EntryHead> {
+    read_entry_head(op, ws_path, entry_id, Some(viewer)).await
+}
+
+async fn read_entry_head(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    viewer: Option<&EntryViewer>,
+) -> Result<EntryHead> {
+    if let Some(viewer) = viewer {
+        entry_acl::require_entry_read(op, ws_path, entry_id, viewer).await?;
+    }
+    let form_name = find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    let row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
+    if row.deleted {
+        return Err(anyhow!("Entry not found: {}", entry_id));
+    }
+    Ok(EntryHead {
+        id: row.entry_id,
+        form: form_name,
+        title: row.title,
+        tags: row.tags,
+        revision_id: row.revision_id,
+        author: row.author,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+        checksum: row.integrity.checksum,
+    })
+}
+
+/// [`get_entry`] unless the entry is still at revision `if_revision_not`, in
+/// which case `None` is returned after reading only its head.
+pub async fn get_entry_if_changed(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    if_revision_not: &str,
+) -> Result<Option<Value>> {
+    if get_entry_head(op, ws_path, entry_id).await?.revision_id == if_revision_not {
+        return Ok(None);
+    }
+    get_entry(op, ws_path, entry_id).await.map(Some)
+}
+
+/// [`get_entry_if_changed`] for `viewer`; the ACL is checked before the
+/// revision, so an unchanged answer does not disclose a restricted entry.
+pub async fn get_entry_if_changed_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    if_revision_not: &str,
+    viewer: &EntryViewer,
+) -> Result<Option<Value>> {
+    let head = get_entry_head_for_viewer(op, ws_path, entry_id, viewer).await?;
+    if head.revision_id == if_revision_not {
+        return Ok(None);
+    }
+    get_entry(op, ws_path, entry_id).await.map(Some)
+}
+
 pub async fn get_entry_content(
     op: &Operator,
     ws_path: &str,
@@ -2073,7 +3281,8 @@ pub async fn get_entry_content(
     let row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
     let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
     let field_order = form_field_names(&form_def);
-    let merged_fields = merge_entry_fields(&row.fields, &row.extra_attributes);
+    let mut merged_fields = merge_entry_fields(&row.fields, &row.extra_attributes);
+    field_crypto::mask_fields(&mut merged_fields);
     let markdown = render_markdown(
         &row.title,
         &form_name,
@@ -2096,18 +3305,262 @@ pub async fn get_entry_content(
     })
 }
 
-pub async fn get_entry_revision_content(
+fn heading_slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|ch| match ch {
+            ' ' => Some('-'),
+            ch if ch.is_alphanumeric() || ch == '-' || ch == '_' => Some(ch),
+            _ => None,
+        })
+        .collect()
+}
+
+fn nest_headings(flat: Vec<OutlineHeading>) -> Vec<OutlineHeading> {
+    let mut roots: Vec<OutlineHeading> = Vec::new();
+    let mut stack: Vec<OutlineHeading> = Vec::new();
+    for heading in flat {
+        while stack.last().is_some_and(|open| open.level >= heading.level) {
+            let done = stack.pop().expect("stack is not empty");
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(done),
+                None => roots.push(done),
+            }
+        }
+        stack.push(heading);
+    }
+    while let Some(done) = stack.pop() {
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(done),
+            None => roots.push(done),
+        }
+    }
+    roots
+}
+
+/// Build the heading tree of entry markdown. Frontmatter and headings inside
+/// code blocks are ignored; offsets count characters from the start of `markdown`.
+pub fn markdown_outline(markdown: &str) -> Vec<OutlineHeading> {
+    let body = render::strip_frontmatter(markdown);
+    let body_start = markdown.len() - body.len();
+    let char_offset = |byte: usize| markdown[..body_start + byte].chars().count();
+    let total_chars = markdown.chars().count();
+
+    let mut flat: Vec<OutlineHeading> = Vec::new();
+    let mut anchors: HashMap<String, usize> = HashMap::new();
+    let mut current: Option<(u8, usize, String)> = None;
+    for (event, range) in Parser::new(body).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                current = Some((level as u8, range.start, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, _, buf)) = current.as_mut() {
+                    buf.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, start, text)) = current.take() {
+                    let slug = heading_slug(&text);
+                    let seen = anchors.entry(slug.clone()).or_insert(0);
+                    let anchor = if *seen == 0 {
+                        slug
+                    } else {
+                        format!("{slug}-{seen}")
+                    };
+                    *seen += 1;
+                    flat.push(OutlineHeading {
+                        level,
+                        text: text.trim().to_string(),
+                        anchor,
+                        offset: char_offset(start),
+                        end_offset: total_chars,
+                        children: Vec::new(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    for i in 0..flat.len() {
+        if let Some(next) = flat[i + 1..].iter().find(|h| h.level <= flat[i].level) {
+            flat[i].end_offset = next.offset;
+        }
+    }
+    nest_headings(flat)
+}
+
+/// Heading tree of an entry's current markdown, for tables of contents and
+/// section-targeted embeds.
+pub async fn get_entry_outline(
     op: &Operator,
     ws_path: &str,
     entry_id: &str,
-    revision_id: &str,
-) -> Result<EntryContent> {
-    let form_name = find_entry_form(op, ws_path, entry_id)
-        .await?
-        .ok_or_else(|| anyhow!("Entry content not found: {}", entry_id))?;
-    let row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
-    if row.deleted {
-        return Err(anyhow!("Entry content not found: {}", entry_id));
+) -> Result<Vec<OutlineHeading>> {
+    let content = get_entry_content(op, ws_path, entry_id).await?;
+    Ok(markdown_outline(&content.markdown))
+}
+
+/// One entry section, addressed by a heading path, as of `revision_id`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EntrySection {
+    pub heading_path: Vec<String>,
+    pub level: u8,
+    pub anchor: String,
+    pub offset: usize,
+    pub end_offset: usize,
+    /// The heading line(s).
+    pub heading: String,
+    /// Everything after the heading up to `end_offset`, including subsections.
+    pub body: String,
+    pub revision_id: String,
+}
+
+fn heading_matches(heading: &OutlineHeading, segment: &str) -> bool {
+    let segment = segment.trim();
+    heading.anchor == segment || heading.text.eq_ignore_ascii_case(segment)
+}
+
+fn find_heading_anywhere<'a>(
+    headings: &'a [OutlineHeading],
+    segment: &str,
+) -> Option<&'a OutlineHeading> {
+    headings.iter().find_map(|heading| {
+        if heading_matches(heading, segment) {
+            Some(heading)
+        } else {
+            find_heading_anywhere(&heading.children, segment)
+        }
+    })
+}
+
+/// Resolve `heading_path` against an outline. The first segment may name a
+/// heading at any depth; each later segment must name a direct child. Segments
+/// match a heading's text (case-insensitively) or its anchor.
+fn resolve_heading_path<'a>(
+    outline: &'a [OutlineHeading],
+    heading_path: &[String],
+) -> Result<&'a OutlineHeading> {
+    let not_found = || anyhow!("Section not found: {}", heading_path.join(" > "));
+    let (first, rest) = heading_path.split_first().ok_or_else(not_found)?;
+    let mut heading = find_heading_anywhere(outline, first).ok_or_else(not_found)?;
+    for segment in rest {
+        heading = heading
+            .children
+            .iter()
+            .find(|child| heading_matches(child, segment))
+            .ok_or_else(not_found)?;
+    }
+    Ok(heading)
+}
+
+fn char_to_byte(text: &str, char_offset: usize) -> usize {
+    text.char_indices()
+        .nth(char_offset)
+        .map(|(idx, _)| idx)
+        .unwrap_or(text.len())
+}
+
+/// Byte ranges of the heading and of the body following it, up to the section end.
+fn section_ranges(
+    markdown: &str,
+    heading: &OutlineHeading,
+) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+    let start = char_to_byte(markdown, heading.offset);
+    let end = char_to_byte(markdown, heading.end_offset);
+    let section = &markdown[start..end];
+    let heading_len = Parser::new(section)
+        .into_offset_iter()
+        .find_map(|(event, range)| {
+            matches!(event, Event::End(TagEnd::Heading(_))).then_some(range.end)
+        })
+        .unwrap_or(section.len());
+    (start..start + heading_len, start + heading_len..end)
+}
+
+/// Read one section of an entry by heading path, e.g. `["Body", "Risks"]`.
+pub async fn read_entry_section(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    heading_path: &[String],
+) -> Result<EntrySection> {
+    let content = get_entry_content(op, ws_path, entry_id).await?;
+    let outline = markdown_outline(&content.markdown);
+    let heading = resolve_heading_path(&outline, heading_path)?;
+    let (heading_range, body_range) = section_ranges(&content.markdown, heading);
+    Ok(EntrySection {
+        heading_path: heading_path.to_vec(),
+        level: heading.level,
+        anchor: heading.anchor.clone(),
+        offset: heading.offset,
+        end_offset: heading.end_offset,
+        heading: content.markdown[heading_range].to_string(),
+        body: content.markdown[body_range].to_string(),
+        revision_id: content.revision_id,
+    })
+}
+
+/// Replace the body of one section (including its subsections) and save the
+/// result as a normal revision. Without `parent_revision_id` the revision the
+/// section was read from is used, so a concurrent edit still conflicts.
+#[allow(clippy::too_many_arguments)]
+pub async fn patch_entry_section<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    heading_path: &[String],
+    body: &str,
+    parent_revision_id: Option<&str>,
+    author: &str,
+    integrity: &I,
+) -> Result<Value> {
+    let content = get_entry_content(op, ws_path, entry_id).await?;
+    let markdown = &content.markdown;
+    let outline = markdown_outline(markdown);
+    let heading = resolve_heading_path(&outline, heading_path)?;
+    let (heading_range, body_range) = section_ranges(markdown, heading);
+
+    let mut updated = markdown[..heading_range.end].to_string();
+    if !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    let body = body.trim_matches('\n');
+    if !body.is_empty() {
+        updated.push_str(body);
+        updated.push('\n');
+    }
+    let rest = &markdown[body_range.end..];
+    if !rest.is_empty() {
+        updated.push('\n');
+        updated.push_str(rest);
+    }
+    update_entry(
+        op,
+        ws_path,
+        entry_id,
+        &updated,
+        Some(parent_revision_id.unwrap_or(&content.revision_id)),
+        author,
+        None,
+        integrity,
+    )
+    .await
+}
+
+pub async fn get_entry_revision_content(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    revision_id: &str,
+) -> Result<EntryContent> {
+    let form_name = find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry content not found: {}", entry_id))?;
+    let row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
+    if row.deleted {
+        return Err(anyhow!("Entry content not found: {}", entry_id));
     }
 
     let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
@@ -2119,15 +3572,7 @@ pub async fn get_entry_revision_content(
         .find(|rev| rev.entry_id == entry_id && rev.revision_id == revision_id)
         .ok_or_else(|| anyhow!("Revision {} not found for entry {}", revision_id, entry_id))?;
 
-    let field_order = form_field_names(&form_def);
-    let merged_fields = merge_entry_fields(&revision.fields, &revision.extra_attributes);
-    let markdown = render_markdown(
-        &row.title,
-        &form_name,
-        &row.tags,
-        &merged_fields,
-        &field_order,
-    );
+    let (markdown, merged_fields) = render_revision(&row, &form_name, &form_def, &revision);
     Ok(EntryContent {
         revision_id: revision.revision_id,
         parent_revision_id: revision.parent_revision_id,
@@ -2143,6 +3588,217 @@ pub async fn get_entry_revision_content(
     })
 }
 
+/// Markdown and masked fields of `revision`, rendered with the entry's current
+/// title and tags.
+fn render_revision(
+    row: &EntryRow,
+    form_name: &str,
+    form_def: &Value,
+    revision: &RevisionRow,
+) -> (String, Value) {
+    let field_order = form_field_names(form_def);
+    let mut merged_fields = merge_entry_fields(&revision.fields, &revision.extra_attributes);
+    field_crypto::mask_fields(&mut merged_fields);
+    let markdown = render_markdown(
+        &row.title,
+        form_name,
+        &row.tags,
+        &merged_fields,
+        &field_order,
+    );
+    (markdown, merged_fields)
+}
+
+/// How [`update_entry_with_merge`] handles a stale `parent_revision_id`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Fail with [`RevisionConflict`].
+    #[default]
+    Fail,
+    /// Merge the edits made since the parent revision with the attempted
+    /// content line by line, failing only when both touch the same lines.
+    ThreeWay,
+}
+
+/// Error raised when an update names a parent revision that is no longer the
+/// head of the entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevisionConflict {
+    pub entry_id: String,
+    /// The stale parent the update was based on.
+    pub expected_revision_id: String,
+    pub current_revision_id: String,
+    /// Markdown of the parent revision, or `None` when it is unknown.
+    pub base_content: Option<String>,
+    pub current_content: String,
+    /// The submitted markdown, rendered the way it would be stored.
+    pub attempted_content: String,
+    /// Whether a three-way merge was tried and failed.
+    pub merge_attempted: bool,
+}
+
+impl RevisionConflict {
+    pub fn as_json(&self) -> Value {
+        serde_json::json!({
+            "code": "revision_conflict",
+            "entry_id": self.entry_id,
+            "expected_revision_id": self.expected_revision_id,
+            "current_revision_id": self.current_revision_id,
+            "base_content": self.base_content,
+            "current_content": self.current_content,
+            "attempted_content": self.attempted_content,
+            "merge_attempted": self.merge_attempted,
+        })
+    }
+}
+
+impl std::fmt::Display for RevisionConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Revision conflict: expected {}, got {}",
+            self.expected_revision_id, self.current_revision_id
+        )
+    }
+}
+
+impl std::error::Error for RevisionConflict {}
+
+/// A change to the lines `start..end` of a base text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LineEdit {
+    start: usize,
+    end: usize,
+    lines: Vec<String>,
+}
+
+fn line_edits(base: &str, other: &str) -> Result<Vec<LineEdit>> {
+    let mut edits: Vec<LineEdit> = Vec::new();
+    let mut position = 0;
+    for (kind, run) in line_chunks(base, other)? {
+        let len = run.len();
+        if kind == DiffHunkKind::Context {
+            position += len;
+            continue;
+        }
+        let edit = match edits.last_mut() {
+            Some(last) if last.end == position => last,
+            _ => {
+                edits.push(LineEdit {
+                    start: position,
+                    end: position,
+                    lines: Vec::new(),
+                });
+                edits.last_mut().expect("edit just pushed")
+            }
+        };
+        if kind == DiffHunkKind::Removed {
+            edit.end += len;
+            position += len;
+        } else {
+            edit.lines.extend(run);
+        }
+    }
+    Ok(edits)
+}
+
+/// Line-based three-way merge. Returns `None` when `ours` and `theirs` change
+/// the same or adjacent lines of `base` differently.
+fn merge_three_way(base: &str, ours: &str, theirs: &str) -> Result<Option<String>> {
+    let base_lines: Vec<&str> = base.lines().collect();
+    let mut ours_edits = line_edits(base, ours)?.into_iter().peekable();
+    let mut theirs_edits = line_edits(base, theirs)?.into_iter().peekable();
+    let mut merged: Vec<String> = Vec::new();
+    let mut position = 0;
+    loop {
+        let edit = match (ours_edits.peek(), theirs_edits.peek()) {
+            (None, None) => break,
+            (Some(a), Some(b)) if a.start <= b.end && b.start <= a.end => {
+                if a != b {
+                    return Ok(None);
+                }
+                theirs_edits.next();
+                ours_edits.next()
+            }
+            (Some(a), Some(b)) if b.start < a.start => theirs_edits.next(),
+            (Some(_), _) => ours_edits.next(),
+            (None, Some(_)) => theirs_edits.next(),
+        };
+        let Some(edit) = edit else { break };
+        merged.extend(
+            base_lines[position..edit.start]
+                .iter()
+                .map(|l| l.to_string()),
+        );
+        merged.extend(edit.lines);
+        position = edit.end;
+    }
+    merged.extend(base_lines[position..].iter().map(|l| l.to_string()));
+    let mut text = merged.join("\n");
+    if ours.ends_with('\n') {
+        text.push('\n');
+    }
+    Ok(Some(text))
+}
+
+/// The conflict for an update of `row` based on `expected_parent`.
+async fn revision_conflict(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    row: &EntryRow,
+    expected_parent: &str,
+    content: &str,
+) -> Result<RevisionConflict> {
+    let form_def = form::read_form_definition(op, ws_path, form_name).await?;
+    let (_, table) = iceberg_store::load_revisions_table(op, ws_path, form_name).await?;
+    let batches = scan_table_batches(&table).await?;
+    let base_content = revision_rows_from_batches(&batches, &form_def)?
+        .into_iter()
+        .find(|rev| rev.entry_id == row.entry_id && rev.revision_id == expected_parent)
+        .map(|base| render_revision(row, form_name, &form_def, &base).0);
+    let current_content = render_markdown_for_form(
+        &row.title,
+        form_name,
+        &row.tags,
+        &row.fields,
+        &row.extra_attributes,
+        &form_def,
+    );
+    // Render the submission like stored entries so formatting alone does not
+    // show up as a change.
+    let normalized = normalize_ugoite_links(content);
+    let (frontmatter, sections) = parse_markdown(&normalized);
+    let attempted_content = match parse_entry_fields(&normalized, &sections, &form_def) {
+        Ok((fields, extra_attributes)) => {
+            let tags = if frontmatter.get("tags").is_some() {
+                extract_tags(&frontmatter)
+            } else {
+                row.tags.clone()
+            };
+            render_markdown_for_form(
+                &extract_title(&normalized, &row.title),
+                form_name,
+                &tags,
+                &fields,
+                &extra_attributes,
+                &form_def,
+            )
+        }
+        Err(_) => content.to_string(),
+    };
+    Ok(RevisionConflict {
+        entry_id: row.entry_id.clone(),
+        expected_revision_id: expected_parent.to_string(),
+        current_revision_id: row.revision_id.clone(),
+        base_content,
+        current_content,
+        attempted_content,
+        merge_attempted: false,
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn update_entry<I: IntegrityProvider>(
     op: &Operator,
@@ -2154,22 +3810,143 @@ pub async fn update_entry<I: IntegrityProvider>(
     assets: Option<Vec<Value>>,
     integrity: &I,
 ) -> Result<Value> {
+    update_entry_with_merge(
+        op,
+        ws_path,
+        entry_id,
+        content,
+        parent_revision_id,
+        author,
+        assets,
+        MergeStrategy::Fail,
+        integrity,
+    )
+    .await
+}
+
+/// [`update_entry`] with a choice of what happens when `parent_revision_id` is
+/// stale. Conflicts fail with [`RevisionConflict`] carrying the current, base
+/// and attempted content; with [`MergeStrategy::ThreeWay`] the update is first
+/// merged with the changes saved since the parent and only fails when both
+/// sides changed the same lines.
+#[allow(clippy::too_many_arguments)]
+pub async fn update_entry_with_merge<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    content: &str,
+    parent_revision_id: Option<&str>,
+    author: &str,
+    assets: Option<Vec<Value>>,
+    merge_strategy: MergeStrategy,
+    integrity: &I,
+) -> Result<Value> {
+    update_entry_with_note(
+        op,
+        ws_path,
+        entry_id,
+        content,
+        parent_revision_id,
+        author,
+        assets,
+        merge_strategy,
+        &RevisionNote::default(),
+        integrity,
+    )
+    .await
+}
+
+/// [`update_entry_with_merge`] saving `note` with the new revision.
+#[allow(clippy::too_many_arguments)]
+pub async fn update_entry_with_note<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    content: &str,
+    parent_revision_id: Option<&str>,
+    author: &str,
+    assets: Option<Vec<Value>>,
+    merge_strategy: MergeStrategy,
+    note: &RevisionNote,
+    integrity: &I,
+) -> Result<Value> {
+    update_entry_in_state(
+        op,
+        ws_path,
+        entry_id,
+        content,
+        parent_revision_id,
+        author,
+        assets,
+        merge_strategy,
+        None,
+        note,
+        integrity,
+    )
+    .await
+}
+
+/// [`update_entry_with_note`] that may also move the entry to the workflow
+/// state `transition`; only [`workflow::transition_entry_state`] passes one.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn update_entry_in_state<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    content: &str,
+    parent_revision_id: Option<&str>,
+    author: &str,
+    assets: Option<Vec<Value>>,
+    merge_strategy: MergeStrategy,
+    transition: Option<&str>,
+    note: &RevisionNote,
+    integrity: &I,
+) -> Result<Value> {
+    let note = note.normalized();
     let form_name = find_entry_form(op, ws_path, entry_id)
         .await?
         .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    entry_lock::ensure_writable_by(op, ws_path, entry_id, author).await?;
     let mut row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
 
-    if let Some(expected_parent) = parent_revision_id {
-        if row.revision_id != expected_parent {
-            return Err(anyhow!(
-                "Revision conflict: expected {}, got {}",
-                expected_parent,
-                row.revision_id
-            ));
+    let merged: String;
+    let content = match parent_revision_id {
+        Some(expected_parent) if row.revision_id != expected_parent => {
+            let mut conflict =
+                revision_conflict(op, ws_path, &form_name, &row, expected_parent, content).await?;
+            if merge_strategy == MergeStrategy::ThreeWay {
+                conflict.merge_attempted = true;
+                let result = match &conflict.base_content {
+                    Some(base) => merge_three_way(
+                        base,
+                        &conflict.attempted_content,
+                        &conflict.current_content,
+                    )?,
+                    None => None,
+                };
+                if let Some(result) = result {
+                    merged = result;
+                    merged.as_str()
+                } else {
+                    return Err(anyhow::Error::new(conflict));
+                }
+            } else {
+                return Err(anyhow::Error::new(conflict));
+            }
         }
-    }
+        _ => content,
+    };
 
-    let normalized_content = normalize_ugoite_links(content);
+    let size_config = entry_size::load_entry_size_config(op, ws_path).await?;
+    let sized = entry_size::apply_size_policy(
+        op,
+        ws_path,
+        entry_id,
+        &normalize_ugoite_links(content),
+        &size_config,
+    )
+    .await?;
+    let normalized_content = sized.markdown;
     let (frontmatter, sections) = parse_markdown(&normalized_content);
     let updated_form =
         extract_form(&frontmatter).ok_or_else(|| anyhow!("Form is required for entry update"))?;
@@ -2177,45 +3954,40 @@ pub async fn update_entry<I: IntegrityProvider>(
         return Err(anyhow!("Form change is not supported"));
     }
 
-    let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
-    let form_fields = form_field_names(&form_def);
-    let form_set: HashSet<String> = form_fields.iter().cloned().collect();
-    let policy = extra_attributes_policy(&form_def);
-    let (extras, extra_attributes) = collect_extra_attributes(&sections, &form_set);
-    if !extras.is_empty() && policy == ExtraAttributesPolicy::Deny {
-        return Err(anyhow!("Unknown form fields: {}", extras.join(", ")));
-    }
-
-    let properties = index::extract_properties(&normalized_content);
-    let (casted, warnings) = index::validate_properties(&properties, &form_def)?;
-    if !warnings.is_empty() {
-        return Err(anyhow!(
-            "Form validation failed: {}",
-            serde_json::to_string(&warnings)?
-        ));
-    }
-
-    let mut fields = Map::new();
-    if let Some(obj) = properties.as_object() {
-        for (key, value) in obj {
-            if form_set.contains(key) {
-                fields.insert(key.clone(), value.clone());
-            }
-        }
-    }
-    if let Some(obj) = casted.as_object() {
-        for (key, value) in obj {
-            if form_set.contains(key) {
-                fields.insert(key.clone(), value.clone());
-            }
-        }
-    }
+    let form_def = form::read_form_definition_for_write(op, ws_path, &form_name).await?;
+    let previous_markdown = render_markdown_for_form(
+        &row.title,
+        &form_name,
+        &row.tags,
+        &row.fields,
+        &row.extra_attributes,
+        &form_def,
+    );
+    let (mut fields, extra_attributes) =
+        parse_entry_fields(&normalized_content, &sections, &form_def)?;
+    workflow::apply_workflow_state(&form_def, &mut fields, Some(&row.fields), transition)?;
+    rollup::reject_rollup_values(&form_def, &fields)?;
+    sequence::assign_sequence_values(
+        op,
+        ws_path,
+        &form_name,
+        &form_def,
+        &mut fields,
+        Some(&row.fields),
+    )
+    .await?;
+    let reservation =
+        unique::reserve_unique_values(op, ws_path, &form_name, &form_def, entry_id, &fields)
+            .await?;
+    let sealed =
+        field_crypto::seal_fields(op, ws_path, &form_def, &mut fields, Some(&row.fields)).await;
+    reservation.release_on_err(op, ws_path, sealed).await?;
 
     let mut timestamp = now_ts();
     if timestamp <= row.updated_at {
         timestamp = row.updated_at + 0.001;
     }
-    let revision_id = Uuid::new_v4().to_string();
+    let revision_id = clock::new_uuid().to_string();
     let checksum = integrity.checksum(&normalized_content);
     let signature = integrity.signature(&normalized_content);
 
@@ -2224,7 +3996,7 @@ pub async fn update_entry<I: IntegrityProvider>(
     if frontmatter.get("tags").is_some() {
         row.tags = extract_tags(&frontmatter);
     }
-    row.fields = Value::Object(fields);
+    row.fields = fields;
     row.extra_attributes = extra_attributes.clone();
     row.parent_revision_id = Some(row.revision_id.clone());
     row.revision_id = revision_id.clone();
@@ -2234,8 +4006,11 @@ pub async fn update_entry<I: IntegrityProvider>(
         signature: signature.clone(),
     };
     row.assets = assets.unwrap_or_else(|| row.assets.clone());
+    row.assets.extend(sized.spilled_asset);
 
-    write_entry_row(op, ws_path, &form_name, entry_id, &row).await?;
+    let written = write_entry_row(op, ws_path, &form_name, entry_id, &row).await;
+    reservation.release_on_err(op, ws_path, written).await?;
+    commit_unique_reservation(op, ws_path, reservation).await;
 
     let revision = RevisionRow {
         revision_id: revision_id.clone(),
@@ -2251,41 +4026,418 @@ pub async fn update_entry<I: IntegrityProvider>(
             signature: signature.clone(),
         },
         restored_from: None,
+        message: note.message.clone(),
+        revision_metadata: note.revision_metadata.clone(),
     };
     let (rev_catalog, rev_table): (Arc<MemoryCatalog>, iceberg::table::Table) =
         iceberg_store::load_revisions_table(op, ws_path, &form_name).await?;
-    append_revision_row_to_table(rev_catalog.as_ref(), &rev_table, &revision, &form_def).await?;
+    append_revision_row_to_table(
+        op,
+        ws_path,
+        rev_catalog.as_ref(),
+        &rev_table,
+        &revision,
+        &form_def,
+    )
+    .await?;
+    mentions::record_mentions_after_commit(
+        op,
+        ws_path,
+        &MentionSource {
+            entry_id,
+            entry_title: &row.title,
+            revision_id: &revision_id,
+            author,
+            timestamp,
+        },
+        Some(&previous_markdown),
+        &normalized_content,
+    )
+    .await;
+    watchers::notify_watchers_after_commit(
+        op,
+        ws_path,
+        &WatchEvent {
+            kind: if transition.is_some() {
+                WatchEventKind::Transitioned
+            } else {
+                WatchEventKind::Updated
+            },
+            entry_id,
+            entry_title: &row.title,
+            form: &form_name,
+            tags: &row.tags,
+            author: Some(author),
+            revision_id: Some(&revision_id),
+            field: None,
+            timestamp,
+        },
+    )
+    .await;
 
     get_entry(op, ws_path, entry_id).await
 }
 
-pub async fn delete_entry(
+pub async fn delete_entry(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    hard_delete: bool,
+) -> Result<()> {
+    let form_name = find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    legal_hold::ensure_entry_not_held(op, ws_path, &form_name, entry_id, "delete").await?;
+    let mut row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
+
+    let mut delete_ts = now_ts();
+    if delete_ts <= row.updated_at {
+        delete_ts = row.updated_at + 0.001;
+    }
+    if hard_delete {
+        row.deleted = true;
+        row.deleted_at = Some(delete_ts);
+        row.updated_at = delete_ts;
+        write_entry_row(op, ws_path, &form_name, entry_id, &row).await?;
+        notify_entry_deleted(op, ws_path, &row, delete_ts).await;
+        return Ok(());
+    }
+
+    row.deleted = true;
+    row.deleted_at = Some(delete_ts);
+    row.updated_at = delete_ts;
+    write_entry_row(op, ws_path, &form_name, entry_id, &row).await?;
+    notify_entry_deleted(op, ws_path, &row, delete_ts).await;
+    Ok(())
+}
+
+async fn notify_entry_deleted(op: &Operator, ws_path: &str, row: &EntryRow, timestamp: f64) {
+    watchers::notify_watchers_after_commit(
+        op,
+        ws_path,
+        &WatchEvent {
+            kind: WatchEventKind::Deleted,
+            entry_id: &row.entry_id,
+            entry_title: &row.title,
+            form: &row.form,
+            tags: &row.tags,
+            author: None,
+            revision_id: None,
+            field: None,
+            timestamp,
+        },
+    )
+    .await;
+}
+
+/// Outcome of [`delete_entries`] or [`restore_entries`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct BulkDeleteReport {
+    /// Entries whose deletion state changed, in request order.
+    pub changed: Vec<String>,
+    /// Entries already in the requested state.
+    pub unchanged: Vec<String>,
+    pub failed: Vec<BulkUpdateFailure>,
+}
+
+/// Rows to rewrite for one form, and the requested ids that failed there.
+type FormDeletePlan = (Vec<EntryRow>, Vec<BulkUpdateFailure>);
+
+/// Set the deletion state of many entries. Entries are located with one scan,
+/// forms are processed concurrently with bounded parallelism, and each form
+/// gets a single append for all of its changed rows instead of one commit per
+/// entry.
+async fn set_entries_deleted(
+    op: &Operator,
+    ws_path: &str,
+    entry_ids: &[String],
+    deleted: bool,
+) -> Result<BulkDeleteReport> {
+    let mut report = BulkDeleteReport::default();
+    let mut seen = HashSet::new();
+    let requested: Vec<&String> = entry_ids.iter().filter(|id| seen.insert(*id)).collect();
+    let mut latest: HashMap<String, (String, EntryRow)> = list_entry_rows(op, ws_path)
+        .await?
+        .into_iter()
+        .map(|(form_name, row)| (row.entry_id.clone(), (form_name, row)))
+        .collect();
+
+    let mut by_form: HashMap<String, Vec<EntryRow>> = HashMap::new();
+    for entry_id in &requested {
+        match latest.remove(entry_id.as_str()) {
+            Some((_, row)) if row.deleted == deleted => {
+                report.unchanged.push(entry_id.to_string());
+            }
+            Some((form_name, row)) => by_form.entry(form_name).or_default().push(row),
+            None => report.failed.push(BulkUpdateFailure {
+                entry_id: entry_id.to_string(),
+                error: format!("Entry not found: {}", entry_id),
+            }),
+        }
+    }
+
+    let plans: Vec<FormDeletePlan> = futures::stream::iter(by_form)
+        .map(|(form_name, rows)| async move {
+            let mut ready = Vec::new();
+            let mut failed = Vec::new();
+            for mut row in rows {
+                if deleted {
+                    let held = legal_hold::ensure_entry_not_held(
+                        op,
+                        ws_path,
+                        &form_name,
+                        &row.entry_id,
+                        "delete",
+                    )
+                    .await;
+                    if let Err(err) = held {
+                        failed.push(BulkUpdateFailure {
+                            entry_id: row.entry_id.clone(),
+                            error: err.to_string(),
+                        });
+                        continue;
+                    }
+                }
+                let mut timestamp = now_ts();
+                if timestamp <= row.updated_at {
+                    timestamp = row.updated_at + 0.001;
+                }
+                row.deleted = deleted;
+                row.deleted_at = deleted.then_some(timestamp);
+                row.updated_at = timestamp;
+                ready.push(row);
+            }
+            if !ready.is_empty() {
+                let written = write_entry_rows(op, ws_path, &form_name, &ready).await;
+                if let Err(err) = written {
+                    failed.extend(ready.drain(..).map(|row| BulkUpdateFailure {
+                        entry_id: row.entry_id,
+                        error: err.to_string(),
+                    }));
+                }
+            }
+            Ok::<_, anyhow::Error>((ready, failed))
+        })
+        .buffer_unordered(storage::list_parallelism())
+        .try_collect()
+        .await?;
+
+    let mut changed: HashMap<String, EntryRow> = HashMap::new();
+    for (rows, failed) in plans {
+        report.failed.extend(failed);
+        changed.extend(rows.into_iter().map(|row| (row.entry_id.clone(), row)));
+    }
+    for entry_id in requested {
+        let Some(row) = changed.remove(entry_id.as_str()) else {
+            continue;
+        };
+        if deleted {
+            notify_entry_deleted(op, ws_path, &row, row.updated_at).await;
+        } else {
+            watchers::notify_watchers_after_commit(
+                op,
+                ws_path,
+                &WatchEvent {
+                    kind: WatchEventKind::Updated,
+                    entry_id: &row.entry_id,
+                    entry_title: &row.title,
+                    form: &row.form,
+                    tags: &row.tags,
+                    author: None,
+                    revision_id: None,
+                    field: None,
+                    timestamp: row.updated_at,
+                },
+            )
+            .await;
+        }
+        report.changed.push(row.entry_id);
+    }
+    let order: HashMap<&str, usize> = entry_ids
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(idx, id)| (id.as_str(), idx))
+        .collect();
+    report
+        .failed
+        .sort_by_key(|failure| order.get(failure.entry_id.as_str()).copied());
+    Ok(report)
+}
+
+/// Delete many entries at once. `hard_delete` has the same meaning as in
+/// [`delete_entry`]; entries under legal hold are reported as failed and the
+/// rest are still deleted.
+pub async fn delete_entries(
+    op: &Operator,
+    ws_path: &str,
+    entry_ids: &[String],
+    hard_delete: bool,
+) -> Result<BulkDeleteReport> {
+    let _ = hard_delete;
+    set_entries_deleted(op, ws_path, entry_ids, true).await
+}
+
+/// Move many deleted entries back out of the trash.
+pub async fn restore_entries(
+    op: &Operator,
+    ws_path: &str,
+    entry_ids: &[String],
+) -> Result<BulkDeleteReport> {
+    set_entries_deleted(op, ws_path, entry_ids, false).await
+}
+
+/// A soft-deleted entry waiting in the trash.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TrashedEntry {
+    pub id: String,
+    pub title: String,
+    pub form: String,
+    pub deleted_at: f64,
+    /// Author of the last revision before deletion.
+    pub author: String,
+}
+
+/// Outcome of [`purge_deleted_older_than`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PurgeReport {
+    /// Entries removed for good, sorted by id.
+    pub purged: Vec<String>,
+    /// Entries old enough to purge but covered by a legal hold.
+    pub retained_by_hold: Vec<String>,
+}
+
+/// Every entry in the trash, most recently deleted first.
+pub async fn list_deleted_entries(op: &Operator, ws_path: &str) -> Result<Vec<TrashedEntry>> {
+    let mut trashed: Vec<TrashedEntry> = list_entry_rows(op, ws_path)
+        .await?
+        .into_iter()
+        .filter(|(_, row)| row.deleted)
+        .map(|(form_name, row)| TrashedEntry {
+            deleted_at: row.deleted_at.unwrap_or(row.updated_at),
+            id: row.entry_id,
+            title: row.title,
+            form: form_name,
+            author: row.author,
+        })
+        .collect();
+    trashed.sort_by(|a, b| {
+        b.deleted_at
+            .partial_cmp(&a.deleted_at)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    Ok(trashed)
+}
+
+/// Permanently remove one entry from the trash, with its revision history,
+/// ACL and metadata. Live entries and entries under legal hold are refused.
+pub async fn purge_entry(op: &Operator, ws_path: &str, entry_id: &str, actor: &str) -> Result<()> {
+    let form_name = find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    let row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
+    if !row.deleted {
+        return Err(anyhow!(
+            "Entry {} is not in the trash; delete it before purging",
+            entry_id
+        ));
+    }
+    legal_hold::ensure_entry_not_held(op, ws_path, &form_name, entry_id, "purge").await?;
+    let targets = BTreeMap::from([(form_name, BTreeSet::from([entry_id.to_string()]))]);
+    purge_entry_rows(op, ws_path, &targets, actor).await
+}
+
+/// Permanently remove every trashed entry deleted at least `days` days ago.
+/// Entries under legal hold stay in the trash and are reported.
+pub async fn purge_deleted_older_than(
+    op: &Operator,
+    ws_path: &str,
+    days: u64,
+    actor: &str,
+) -> Result<PurgeReport> {
+    let cutoff = now_ts() - (days as f64) * 86_400.0;
+    let mut report = PurgeReport::default();
+    let mut targets: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for entry in list_deleted_entries(op, ws_path).await? {
+        if entry.deleted_at > cutoff {
+            continue;
+        }
+        if legal_hold::holds_for_entry(op, ws_path, &entry.form, &entry.id)
+            .await?
+            .is_empty()
+        {
+            report.purged.push(entry.id.clone());
+            targets.entry(entry.form).or_default().insert(entry.id);
+        } else {
+            report.retained_by_hold.push(entry.id);
+        }
+    }
+    purge_entry_rows(op, ws_path, &targets, actor).await?;
+    report.purged.sort();
+    report.retained_by_hold.sort();
+    Ok(report)
+}
+
+/// Rewrite each form's tables without the given entries, under the migration
+/// lease, then drop their side files and audit every removal.
+async fn purge_entry_rows(
     op: &Operator,
     ws_path: &str,
-    entry_id: &str,
-    hard_delete: bool,
+    targets: &BTreeMap<String, BTreeSet<String>>,
+    actor: &str,
 ) -> Result<()> {
-    let form_name = find_entry_form(op, ws_path, entry_id)
-        .await?
-        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
-    let mut row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
-
-    let mut delete_ts = now_ts();
-    if delete_ts <= row.updated_at {
-        delete_ts = row.updated_at + 0.001;
-    }
-    if hard_delete {
-        row.deleted = true;
-        row.deleted_at = Some(delete_ts);
-        row.updated_at = delete_ts;
-        write_entry_row(op, ws_path, &form_name, entry_id, &row).await?;
+    if targets.is_empty() {
         return Ok(());
     }
+    space_lock::with_space_lease(op, ws_path, space_lock::MIGRATION_LEASE, |_| async {
+        for (form_name, entry_ids) in targets {
+            let form_def = form::read_form_definition(op, ws_path, form_name).await?;
+            let mut entry_rows = list_form_entry_rows(op, ws_path, form_name, &form_def).await?;
+            let mut revision_rows =
+                list_form_revision_rows(op, ws_path, form_name, &form_def).await?;
+            entry_rows.retain(|row| !entry_ids.contains(&row.entry_id));
+            revision_rows.retain(|rev| !entry_ids.contains(&rev.entry_id));
+            revision_rows.sort_by(|a, b| {
+                a.timestamp
+                    .partial_cmp(&b.timestamp)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            form::rewrite_form_tables(
+                op,
+                ws_path,
+                form_name,
+                &form_def,
+                &entry_rows,
+                &revision_rows,
+            )
+            .await?;
+        }
+        Ok(())
+    })
+    .await?;
 
-    row.deleted = true;
-    row.deleted_at = Some(delete_ts);
-    row.updated_at = delete_ts;
-    write_entry_row(op, ws_path, &form_name, entry_id, &row).await?;
+    let space_id = space::space_id_from_ws_path(ws_path);
+    for (form_name, entry_ids) in targets {
+        for entry_id in entry_ids {
+            entry_acl::clear_entry_acl(op, ws_path, entry_id).await?;
+            entry_metadata::clear_entry_metadata(op, ws_path, entry_id, None).await?;
+            crate::audit::append_audit_event(
+                op,
+                &space_id,
+                &serde_json::json!({
+                    "action": "entry.purge",
+                    "actor_user_id": actor,
+                    "outcome": "success",
+                    "target_type": "entry",
+                    "target_id": entry_id,
+                    "metadata": {"form": form_name},
+                }),
+                None,
+            )
+            .await?;
+        }
+    }
     Ok(())
 }
 
@@ -2305,6 +4457,8 @@ pub async fn get_entry_history(op: &Operator, ws_path: &str, entry_id: &str) ->
             serde_json::json!({
                 "revision_id": rev.revision_id,
                 "timestamp": rev.timestamp,
+                "message": rev.message,
+                "revision_metadata": rev.revision_metadata,
                 "checksum": rev.integrity.checksum,
                 "signature": rev.integrity.signature,
             })
@@ -2323,6 +4477,125 @@ pub async fn get_entry_history(op: &Operator, ws_path: &str, entry_id: &str) ->
     }))
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// One event of the [`list_changes_since`] feed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EntryChange {
+    pub kind: ChangeKind,
+    pub entry_id: String,
+    pub form: String,
+    /// Revision written by the change; `None` for deletions and other
+    /// changes that write no revision, such as restoring from the trash.
+    pub revision_id: Option<String>,
+    pub timestamp: f64,
+    pub author: Option<String>,
+}
+
+/// Where [`list_changes_since`] resumes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangesSince {
+    /// Changes made strictly after this time.
+    Timestamp(f64),
+    /// Changes ordered after this revision.
+    Revision(String),
+}
+
+fn change_millis(timestamp: f64) -> i64 {
+    (timestamp * 1000.0).round() as i64
+}
+
+/// Created, updated and deleted events across the space since `since`, in
+/// (timestamp, kind, entry id, revision id) order.
+///
+/// Creations and updates come from the revision history. Deletions, and
+/// changes that write no revision, are read from each entry's current state,
+/// so only the latest of them is reported; purged entries leave the feed.
+/// With a `viewer`, changes to entries its ACL hides are left out.
+pub async fn list_changes_since(
+    op: &Operator,
+    ws_path: &str,
+    since: &ChangesSince,
+    viewer: Option<&EntryViewer>,
+) -> Result<Vec<EntryChange>> {
+    let mut changes = Vec::new();
+    for form_name in form::list_form_names(op, ws_path).await? {
+        let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+        let mut latest: HashMap<String, f64> = HashMap::new();
+        for revision in list_form_revision_rows(op, ws_path, &form_name, &form_def).await? {
+            let newest = latest
+                .entry(revision.entry_id.clone())
+                .or_insert(revision.timestamp);
+            *newest = newest.max(revision.timestamp);
+            changes.push(EntryChange {
+                kind: if revision.parent_revision_id.is_none() {
+                    ChangeKind::Created
+                } else {
+                    ChangeKind::Updated
+                },
+                entry_id: revision.entry_id,
+                form: form_name.clone(),
+                revision_id: Some(revision.revision_id),
+                timestamp: revision.timestamp,
+                author: Some(revision.author),
+            });
+        }
+        for row in list_form_entry_rows(op, ws_path, &form_name, &form_def).await? {
+            let revised_at = latest.get(&row.entry_id).copied();
+            let (kind, timestamp) = if row.deleted {
+                (
+                    ChangeKind::Deleted,
+                    row.deleted_at.unwrap_or(row.updated_at),
+                )
+            } else if revised_at.is_some_and(|at| change_millis(row.updated_at) > change_millis(at))
+            {
+                (ChangeKind::Updated, row.updated_at)
+            } else {
+                continue;
+            };
+            changes.push(EntryChange {
+                kind,
+                entry_id: row.entry_id,
+                form: form_name.clone(),
+                revision_id: None,
+                timestamp,
+                author: None,
+            });
+        }
+    }
+    if let Some(viewer) = viewer {
+        let acls = entry_acl::list_entry_acls(op, ws_path).await?;
+        changes.retain(|change| entry_acl::can_read(acls.get(&change.entry_id), viewer));
+    }
+    changes.sort_by(|a, b| {
+        a.timestamp
+            .total_cmp(&b.timestamp)
+            .then_with(|| a.kind.cmp(&b.kind))
+            .then_with(|| a.entry_id.cmp(&b.entry_id))
+            .then_with(|| a.revision_id.cmp(&b.revision_id))
+    });
+    let start = match since {
+        ChangesSince::Timestamp(since) => changes
+            .iter()
+            .position(|change| change.timestamp > *since)
+            .unwrap_or(changes.len()),
+        ChangesSince::Revision(revision_id) => {
+            changes
+                .iter()
+                .position(|change| change.revision_id.as_deref() == Some(revision_id.as_str()))
+                .ok_or_else(|| anyhow!("Revision not found: {}", revision_id))?
+                + 1
+        }
+    };
+    Ok(changes.split_off(start))
+}
+
 pub async fn get_entry_revision(
     op: &Operator,
     ws_path: &str,
@@ -2340,11 +4613,234 @@ pub async fn get_entry_revision(
         .into_iter()
         .find(|rev| rev.entry_id == entry_id && rev.revision_id == revision_id);
 
-    let revision = revision
+    let mut revision = revision
         .ok_or_else(|| anyhow!("Revision {} not found for entry {}", revision_id, entry_id))?;
+    field_crypto::mask_fields(&mut revision.fields);
     Ok(serde_json::to_value(revision)?)
 }
 
+/// Unchanged lines kept around each change in a revision diff.
+pub const DIFF_CONTEXT_LINES: usize = 3;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffHunkKind {
+    Context,
+    Added,
+    Removed,
+}
+
+/// A run of lines that are unchanged, added or removed between two revisions.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    pub kind: DiffHunkKind,
+    /// 1-based line in the `from` markdown where the hunk starts, or before
+    /// which added lines go.
+    pub from_line: usize,
+    /// 1-based line in the `to` markdown where the hunk starts, or before
+    /// which removed lines were.
+    pub to_line: usize,
+    pub lines: Vec<String>,
+}
+
+/// A property whose value differs between two revisions; `null` on one side
+/// means the property is absent there.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PropertyDiff {
+    pub name: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RevisionDiff {
+    pub entry_id: String,
+    pub from_revision_id: String,
+    pub to_revision_id: String,
+    /// Changed lines of the rendered markdown with up to
+    /// [`DIFF_CONTEXT_LINES`] unchanged lines around each change; longer
+    /// unchanged runs are left out. Empty when the markdown is identical.
+    pub hunks: Vec<DiffHunk>,
+    /// Changed properties, sorted by name.
+    pub properties: Vec<PropertyDiff>,
+}
+
+/// Diff two revisions of an entry: a line-level diff of their markdown and a
+/// property-level diff of their fields. Secret fields are masked on both sides.
+pub async fn diff_entry_revisions(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    from_revision_id: &str,
+    to_revision_id: &str,
+) -> Result<RevisionDiff> {
+    let form_name = find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    let row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
+    if row.deleted {
+        return Err(anyhow!("Entry not found: {}", entry_id));
+    }
+    let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+    let (_, table) = iceberg_store::load_revisions_table(op, ws_path, &form_name).await?;
+    let batches = scan_table_batches(&table).await?;
+    let revisions = revision_rows_from_batches(&batches, &form_def)?;
+    let find = |revision_id: &str| {
+        revisions
+            .iter()
+            .find(|rev| rev.entry_id == entry_id && rev.revision_id == revision_id)
+            .ok_or_else(|| anyhow!("Revision {} not found for entry {}", revision_id, entry_id))
+    };
+    let (from_markdown, from_fields) =
+        render_revision(&row, &form_name, &form_def, find(from_revision_id)?);
+    let (to_markdown, to_fields) =
+        render_revision(&row, &form_name, &form_def, find(to_revision_id)?);
+
+    Ok(RevisionDiff {
+        entry_id: entry_id.to_string(),
+        from_revision_id: from_revision_id.to_string(),
+        to_revision_id: to_revision_id.to_string(),
+        hunks: diff_lines(&from_markdown, &to_markdown)?,
+        properties: diff_properties(&from_fields, &to_fields),
+    })
+}
+
+fn diff_properties(before: &Value, after: &Value) -> Vec<PropertyDiff> {
+    let empty = Map::new();
+    let before = before.as_object().unwrap_or(&empty);
+    let after = after.as_object().unwrap_or(&empty);
+    let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let old = before.get(name).cloned().unwrap_or(Value::Null);
+            let new = after.get(name).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| PropertyDiff {
+                name: name.clone(),
+                before: old,
+                after: new,
+            })
+        })
+        .collect()
+}
+
+/// Runs of unchanged, removed and added lines turning `from` into `to`. Each
+/// distinct line is mapped to one char so the character diff from
+/// `dissimilar` works on whole lines.
+fn line_chunks(from: &str, to: &str) -> Result<Vec<(DiffHunkKind, Vec<String>)>> {
+    let mut symbols: HashMap<&str, char> = HashMap::new();
+    let mut lines: Vec<&str> = Vec::new();
+    let mut encoded = [String::new(), String::new()];
+    for (text, out) in [from, to].into_iter().zip(encoded.iter_mut()) {
+        for line in text.lines() {
+            let symbol = match symbols.get(line) {
+                Some(symbol) => *symbol,
+                None => {
+                    // Skip the surrogate range, which has no chars.
+                    let symbol = u32::try_from(lines.len())
+                        .ok()
+                        .map(|index| if index < 0xD800 { index } else { index + 0x800 })
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| anyhow!("Too many distinct lines to diff"))?;
+                    symbols.insert(line, symbol);
+                    lines.push(line);
+                    symbol
+                }
+            };
+            out.push(symbol);
+        }
+    }
+    let [from_symbols, to_symbols] = encoded;
+    let decode = |symbols: &str| -> Vec<String> {
+        symbols
+            .chars()
+            .map(|symbol| {
+                let index = symbol as usize;
+                let index = if index < 0xD800 { index } else { index - 0x800 };
+                lines[index].to_string()
+            })
+            .collect()
+    };
+    Ok(dissimilar::diff(&from_symbols, &to_symbols)
+        .into_iter()
+        .map(|chunk| match chunk {
+            dissimilar::Chunk::Equal(symbols) => (DiffHunkKind::Context, decode(symbols)),
+            dissimilar::Chunk::Delete(symbols) => (DiffHunkKind::Removed, decode(symbols)),
+            dissimilar::Chunk::Insert(symbols) => (DiffHunkKind::Added, decode(symbols)),
+        })
+        .collect())
+}
+
+fn diff_lines(from: &str, to: &str) -> Result<Vec<DiffHunk>> {
+    let chunks = line_chunks(from, to)?;
+    let last = chunks.len().saturating_sub(1);
+    let mut hunks = Vec::new();
+    let (mut from_line, mut to_line) = (1, 1);
+    for (position, (kind, run)) in chunks.into_iter().enumerate() {
+        let len = run.len();
+        match kind {
+            DiffHunkKind::Context => {
+                let head = if position == 0 {
+                    0
+                } else {
+                    DIFF_CONTEXT_LINES.min(len)
+                };
+                let tail = if position == last {
+                    0
+                } else {
+                    DIFF_CONTEXT_LINES.min(len - head)
+                };
+                if head > 0 {
+                    hunks.push(DiffHunk {
+                        kind: DiffHunkKind::Context,
+                        from_line,
+                        to_line,
+                        lines: run[..head].to_vec(),
+                    });
+                }
+                if tail > 0 {
+                    let skip = len - tail;
+                    if head > 0 && skip == head {
+                        if let Some(hunk) = hunks.last_mut() {
+                            hunk.lines.extend_from_slice(&run[skip..]);
+                        }
+                    } else {
+                        hunks.push(DiffHunk {
+                            kind: DiffHunkKind::Context,
+                            from_line: from_line + skip,
+                            to_line: to_line + skip,
+                            lines: run[skip..].to_vec(),
+                        });
+                    }
+                }
+                from_line += len;
+                to_line += len;
+            }
+            DiffHunkKind::Removed => {
+                hunks.push(DiffHunk {
+                    kind: DiffHunkKind::Removed,
+                    from_line,
+                    to_line,
+                    lines: run,
+                });
+                from_line += len;
+            }
+            DiffHunkKind::Added => {
+                hunks.push(DiffHunk {
+                    kind: DiffHunkKind::Added,
+                    from_line,
+                    to_line,
+                    lines: run,
+                });
+                to_line += len;
+            }
+        }
+    }
+    Ok(hunks)
+}
+
 pub async fn restore_entry<I: IntegrityProvider>(
     op: &Operator,
     ws_path: &str,
@@ -2353,9 +4849,33 @@ pub async fn restore_entry<I: IntegrityProvider>(
     author: &str,
     integrity: &I,
 ) -> Result<Value> {
+    restore_entry_with_note(
+        op,
+        ws_path,
+        entry_id,
+        revision_id,
+        author,
+        &RevisionNote::default(),
+        integrity,
+    )
+    .await
+}
+
+/// [`restore_entry`] saving `note` with the restoring revision.
+pub async fn restore_entry_with_note<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    revision_id: &str,
+    author: &str,
+    note: &RevisionNote,
+    integrity: &I,
+) -> Result<Value> {
+    let note = note.normalized();
     let form_name = find_entry_form(op, ws_path, entry_id)
         .await?
         .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    entry_lock::ensure_writable_by(op, ws_path, entry_id, author).await?;
     let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
     let (_, revisions_table) = iceberg_store::load_revisions_table(op, ws_path, &form_name).await?;
     let batches = scan_table_batches(&revisions_table).await?;
@@ -2365,8 +4885,18 @@ pub async fn restore_entry<I: IntegrityProvider>(
         .find(|rev| rev.entry_id == entry_id && rev.revision_id == revision_id)
         .ok_or_else(|| anyhow!("Revision {} not found for entry {}", revision_id, entry_id))?;
 
-    let mut row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
-    let new_rev_id = Uuid::new_v4().to_string();
+    let reservation = unique::reserve_unique_values(
+        op,
+        ws_path,
+        &form_name,
+        &form_def,
+        entry_id,
+        &revision.fields,
+    )
+    .await?;
+    let row = read_entry_row(op, ws_path, &form_name, entry_id).await;
+    let mut row = reservation.release_on_err(op, ws_path, row).await?;
+    let new_rev_id = clock::new_uuid().to_string();
     let mut timestamp = now_ts();
     if timestamp <= row.updated_at {
         timestamp = row.updated_at + 0.001;
@@ -2394,7 +4924,9 @@ pub async fn restore_entry<I: IntegrityProvider>(
         signature: signature.clone(),
     };
     row.author = author.to_string();
-    write_entry_row(op, ws_path, &form_name, entry_id, &row).await?;
+    let written = write_entry_row(op, ws_path, &form_name, entry_id, &row).await;
+    reservation.release_on_err(op, ws_path, written).await?;
+    commit_unique_reservation(op, ws_path, reservation).await;
 
     let restore_revision = RevisionRow {
         revision_id: new_rev_id.clone(),
@@ -2410,10 +4942,14 @@ pub async fn restore_entry<I: IntegrityProvider>(
             signature: signature.clone(),
         },
         restored_from: Some(revision_id.to_string()),
+        message: note.message,
+        revision_metadata: note.revision_metadata,
     };
     let (rev_catalog, rev_table): (Arc<MemoryCatalog>, iceberg::table::Table) =
         iceberg_store::load_revisions_table(op, ws_path, &form_name).await?;
     append_revision_row_to_table(
+        op,
+        ws_path,
         rev_catalog.as_ref(),
         &rev_table,
         &restore_revision,
@@ -2427,3 +4963,409 @@ pub async fn restore_entry<I: IntegrityProvider>(
         "timestamp": timestamp,
     }))
 }
+
+const BULK_UPDATE_JOBS_DIR: &str = "bulk_jobs";
+const DEFAULT_BULK_BATCH_SIZE: usize = 50;
+
+fn default_bulk_batch_size() -> usize {
+    DEFAULT_BULK_BATCH_SIZE
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkUpdateOptions {
+    /// Report which entries would change without writing anything.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Entries updated between pauses.
+    #[serde(default = "default_bulk_batch_size")]
+    pub batch_size: usize,
+    /// Pause after each batch, in milliseconds, to throttle large updates.
+    #[serde(default)]
+    pub batch_delay_ms: u64,
+}
+
+impl Default for BulkUpdateOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            batch_size: DEFAULT_BULK_BATCH_SIZE,
+            batch_delay_ms: 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BulkUpdateFailure {
+    pub entry_id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct BulkUpdateReport {
+    pub dry_run: bool,
+    /// Entries the query matched.
+    pub matched: usize,
+    /// Entries given a new revision, or that would be in a dry run.
+    pub updated: Vec<String>,
+    /// Matched entries the patch leaves as they are.
+    pub unchanged: Vec<String>,
+    pub failed: Vec<BulkUpdateFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkUpdateJob {
+    pub job_id: String,
+    pub space_id: String,
+    pub query: String,
+    pub patch: Map<String, Value>,
+    pub options: BulkUpdateOptions,
+    pub author: String,
+    pub status: crate::importers::ImportJobStatus,
+    pub status_message: Option<String>,
+    pub processed_entries: usize,
+    pub total_entries: usize,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+    pub report: BulkUpdateReport,
+}
+
+fn validate_bulk_update(patch: &Map<String, Value>, options: &BulkUpdateOptions) -> Result<()> {
+    if patch.is_empty() {
+        return Err(anyhow!("Bulk update patch must not be empty"));
+    }
+    if options.batch_size == 0 {
+        return Err(anyhow!("Bulk update batch_size must be at least 1"));
+    }
+    Ok(())
+}
+
+/// Ids of the live entries matched by a filter or SQL query, in id order.
+async fn bulk_update_targets(op: &Operator, ws_path: &str, query: &str) -> Result<Vec<String>> {
+    let mut ids = Vec::new();
+    for row in index::query_index(op, ws_path, query).await? {
+        let id = row
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("Bulk update query results must include the entry id"))?;
+        ids.push(id.to_string());
+    }
+    ids.sort();
+    ids.dedup();
+    Ok(ids)
+}
+
+/// Apply `patch` to one entry's properties, where `null` removes a property.
+/// Returns the new revision id (empty in a dry run), or `None` when nothing
+/// would change.
+async fn bulk_update_entry<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    patch: &Map<String, Value>,
+    dry_run: bool,
+    author: &str,
+    integrity: &I,
+) -> Result<Option<String>> {
+    let form_name = find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    let row = read_entry_row(op, ws_path, &form_name, entry_id).await?;
+    let current = merge_entry_fields(&row.fields, &row.extra_attributes);
+    let mut patched = current.as_object().cloned().unwrap_or_default();
+    for (key, value) in patch {
+        if value.is_null() {
+            patched.remove(key);
+        } else {
+            patched.insert(key.clone(), value.clone());
+        }
+    }
+    let patched = Value::Object(patched);
+    if patched == current {
+        return Ok(None);
+    }
+    if dry_run {
+        return Ok(Some(String::new()));
+    }
+    let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+    let markdown = render_markdown_for_form(
+        &row.title,
+        &form_name,
+        &row.tags,
+        &patched,
+        &Value::Null,
+        &form_def,
+    );
+    let updated = update_entry(
+        op,
+        ws_path,
+        entry_id,
+        &markdown,
+        Some(&row.revision_id),
+        author,
+        None,
+        integrity,
+    )
+    .await?;
+    Ok(updated
+        .get("revision_id")
+        .and_then(Value::as_str)
+        .map(str::to_string))
+}
+
+/// Update `targets` batch by batch, recording each revision in the audit log.
+/// With a `job`, its progress and report are stored after every batch.
+#[allow(clippy::too_many_arguments)]
+async fn run_bulk_update<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    targets: &[String],
+    patch: &Map<String, Value>,
+    options: &BulkUpdateOptions,
+    author: &str,
+    mut job: Option<&mut BulkUpdateJob>,
+    integrity: &I,
+) -> Result<BulkUpdateReport> {
+    let job_id = job.as_ref().map(|job| job.job_id.clone());
+    let space_id = space::space_id_from_ws_path(ws_path);
+    let mut report = BulkUpdateReport {
+        dry_run: options.dry_run,
+        matched: targets.len(),
+        ..BulkUpdateReport::default()
+    };
+    let properties: Vec<&String> = patch.keys().collect();
+    let mut processed = 0;
+    for (batch_index, batch) in targets.chunks(options.batch_size).enumerate() {
+        if batch_index > 0 && options.batch_delay_ms > 0 && !options.dry_run {
+            tokio::time::sleep(std::time::Duration::from_millis(options.batch_delay_ms)).await;
+        }
+        for entry_id in batch {
+            let outcome = bulk_update_entry(
+                op,
+                ws_path,
+                entry_id,
+                patch,
+                options.dry_run,
+                author,
+                integrity,
+            )
+            .await;
+            match outcome {
+                Ok(None) => report.unchanged.push(entry_id.clone()),
+                Ok(Some(revision_id)) => {
+                    if !options.dry_run {
+                        crate::audit::append_audit_event(
+                            op,
+                            &space_id,
+                            &serde_json::json!({
+                                "action": "entry.bulk_update",
+                                "actor_user_id": author,
+                                "outcome": "success",
+                                "target_type": "entry",
+                                "target_id": entry_id,
+                                "metadata": {
+                                    "revision_id": revision_id,
+                                    "properties": properties,
+                                    "job_id": job_id,
+                                },
+                            }),
+                            None,
+                        )
+                        .await?;
+                    }
+                    report.updated.push(entry_id.clone());
+                }
+                Err(err) => report.failed.push(BulkUpdateFailure {
+                    entry_id: entry_id.clone(),
+                    error: err.to_string(),
+                }),
+            }
+        }
+        processed += batch.len();
+        if let Some(job) = job.as_deref_mut() {
+            job.processed_entries = processed;
+            job.report = report.clone();
+            write_bulk_update_job(op, ws_path, job).await?;
+        }
+    }
+
+    if !options.dry_run {
+        crate::audit::append_audit_event(
+            op,
+            &space_id,
+            &serde_json::json!({
+                "action": "space.bulk_update",
+                "actor_user_id": author,
+                "outcome": "success",
+                "target_type": "space",
+                "target_id": space_id,
+                "metadata": {
+                    "properties": properties,
+                    "job_id": job_id,
+                    "matched": report.matched,
+                    "updated": report.updated.len(),
+                    "unchanged": report.unchanged.len(),
+                    "failed": report.failed.len(),
+                },
+            }),
+            None,
+        )
+        .await?;
+    }
+    Ok(report)
+}
+
+/// Apply a property patch to every live entry matched by `query`, a filter
+/// object or SQL query as accepted by [`index::query_index`].
+///
+/// Each changed entry gets its own revision and audit event; a `null` patch
+/// value removes the property. Entries that fail validation are reported and
+/// skipped. A dry run reports which entries would change without writing.
+pub async fn bulk_update_properties<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    patch: &Map<String, Value>,
+    options: &BulkUpdateOptions,
+    author: &str,
+    integrity: &I,
+) -> Result<BulkUpdateReport> {
+    validate_bulk_update(patch, options)?;
+    let targets = bulk_update_targets(op, ws_path, query).await?;
+    run_bulk_update(
+        op, ws_path, &targets, patch, options, author, None, integrity,
+    )
+    .await
+}
+
+fn bulk_update_job_path(ws_path: &str, job_id: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, BULK_UPDATE_JOBS_DIR, job_id)
+}
+
+async fn write_bulk_update_job(op: &Operator, ws_path: &str, job: &BulkUpdateJob) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &bulk_update_job_path(ws_path, &job.job_id),
+        serde_json::to_vec_pretty(job)?,
+    )
+    .await?;
+    Ok(())
+}
+
+async fn run_bulk_update_job_entries(
+    op: &Operator,
+    ws_path: &str,
+    job: &mut BulkUpdateJob,
+    targets: &[String],
+) -> Result<()> {
+    let integrity = crate::integrity::RealIntegrityProvider::from_space(op, &job.space_id).await?;
+    let patch = job.patch.clone();
+    let options = job.options.clone();
+    let author = job.author.clone();
+    let report = run_bulk_update(
+        op,
+        ws_path,
+        targets,
+        &patch,
+        &options,
+        &author,
+        Some(&mut *job),
+        &integrity,
+    )
+    .await?;
+    job.report = report;
+    Ok(())
+}
+
+async fn run_bulk_update_job(
+    op: Operator,
+    ws_path: String,
+    mut job: BulkUpdateJob,
+    targets: Vec<String>,
+) {
+    use crate::importers::ImportJobStatus;
+
+    job.status = ImportJobStatus::Running;
+    job.status_message = Some("Updating entries".to_string());
+    job.started_at = Some(Utc::now());
+    let _ = write_bulk_update_job(&op, &ws_path, &job).await;
+
+    match run_bulk_update_job_entries(&op, &ws_path, &mut job, &targets).await {
+        Ok(()) => {
+            job.status = ImportJobStatus::Completed;
+            job.status_message = Some("Completed".to_string());
+        }
+        Err(err) => {
+            job.status = ImportJobStatus::Failed;
+            job.status_message = Some("Failed".to_string());
+            job.error = Some(err.to_string());
+        }
+    }
+    job.completed_at = Some(Utc::now());
+    let _ = write_bulk_update_job(&op, &ws_path, &job).await;
+}
+
+async fn read_bulk_update_job(op: &Operator, ws_path: &str, job_id: &str) -> Result<BulkUpdateJob> {
+    let bytes = op
+        .read(&bulk_update_job_path(ws_path, job_id))
+        .await?
+        .to_vec();
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Queue [`bulk_update_properties`] in the background for large spaces. The
+/// matching entries are resolved up front; progress and the running report
+/// are stored with the job after every batch.
+pub async fn create_bulk_update_job(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    patch: &Map<String, Value>,
+    options: &BulkUpdateOptions,
+    author: &str,
+) -> Result<BulkUpdateJob> {
+    validate_bulk_update(patch, options)?;
+    let targets = bulk_update_targets(op, ws_path, query).await?;
+    let job = BulkUpdateJob {
+        job_id: Uuid::new_v4().to_string(),
+        space_id: space::space_id_from_ws_path(ws_path).to_string(),
+        query: query.to_string(),
+        patch: patch.clone(),
+        options: options.clone(),
+        author: author.to_string(),
+        status: crate::importers::ImportJobStatus::Queued,
+        status_message: Some("Queued".to_string()),
+        processed_entries: 0,
+        total_entries: targets.len(),
+        started_at: None,
+        completed_at: None,
+        error: None,
+        report: BulkUpdateReport {
+            dry_run: options.dry_run,
+            matched: targets.len(),
+            ..BulkUpdateReport::default()
+        },
+    };
+    write_bulk_update_job(op, ws_path, &job).await?;
+
+    tokio::spawn(run_bulk_update_job(
+        op.clone(),
+        ws_path.to_string(),
+        job.clone(),
+        targets,
+    ));
+    Ok(job)
+}
+
+pub async fn get_bulk_update_job(
+    op: &Operator,
+    ws_path: &str,
+    job_id: &str,
+) -> Result<BulkUpdateJob> {
+    Uuid::parse_str(job_id)
+        .map_err(|e| anyhow!("Invalid job_id: {}. Must be a valid UUID. ({})", job_id, e))?;
+    if !op.exists(&bulk_update_job_path(ws_path, job_id)).await? {
+        return Err(anyhow!("Bulk update job not found: {}", job_id));
+    }
+    read_bulk_update_job(op, ws_path, job_id).await
+}
@@ -0,0 +1,245 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::entry;
+use crate::storage;
+
+const ENTRY_ACLS_DIR: &str = "entry_acls";
+
+/// Token in an entry's index `acl` column that admits every space reader.
+pub const ACL_EVERYONE: &str = "*";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryVisibility {
+    /// Anyone who can read the space can read the entry.
+    #[default]
+    Space,
+    /// Only the owner and the `shared_with` principals can read the entry.
+    Restricted,
+    /// Only the owner can read the entry.
+    Private,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PrincipalKind {
+    User,
+    UserGroup,
+}
+
+/// Principal reference with the same shape as form `read_principals` items.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct EntryPrincipal {
+    pub kind: PrincipalKind,
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct EntryAcl {
+    pub owner: String,
+    #[serde(default)]
+    pub shared_with: Vec<EntryPrincipal>,
+    #[serde(default)]
+    pub visibility: EntryVisibility,
+}
+
+/// The caller an ACL is evaluated for.
+#[derive(Debug, Clone, Default)]
+pub struct EntryViewer {
+    pub user_id: String,
+    pub groups: Vec<String>,
+    /// Space owners and admins bypass entry ACLs, mirroring form ACL evaluation.
+    pub is_space_admin: bool,
+}
+
+impl EntryViewer {
+    pub fn user(user_id: &str) -> Self {
+        Self {
+            user_id: user_id.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Tokens matched against the index `acl` column, or `None` for space
+    /// admins, who bypass entry ACLs.
+    pub fn acl_tokens(&self) -> Option<Vec<String>> {
+        if self.is_space_admin {
+            return None;
+        }
+        let mut tokens = vec![
+            ACL_EVERYONE.to_string(),
+            principal_token(PrincipalKind::User, &self.user_id),
+        ];
+        tokens.extend(
+            self.groups
+                .iter()
+                .map(|group| principal_token(PrincipalKind::UserGroup, group)),
+        );
+        Some(tokens)
+    }
+
+    fn matches(&self, principal: &EntryPrincipal) -> bool {
+        match principal.kind {
+            PrincipalKind::User => principal.id == self.user_id,
+            PrincipalKind::UserGroup => self.groups.iter().any(|group| group == &principal.id),
+        }
+    }
+}
+
+/// Core read check used by get/list/search; entries without an ACL follow space access.
+pub fn can_read(acl: Option<&EntryAcl>, viewer: &EntryViewer) -> bool {
+    let Some(acl) = acl else {
+        return true;
+    };
+    if viewer.is_space_admin || acl.owner == viewer.user_id {
+        return true;
+    }
+    match acl.visibility {
+        EntryVisibility::Space => true,
+        EntryVisibility::Restricted => acl
+            .shared_with
+            .iter()
+            .any(|principal| viewer.matches(principal)),
+        EntryVisibility::Private => false,
+    }
+}
+
+fn principal_token(kind: PrincipalKind, id: &str) -> String {
+    match kind {
+        PrincipalKind::User => format!("user:{id}"),
+        PrincipalKind::UserGroup => format!("group:{id}"),
+    }
+}
+
+/// The index `acl` column for an entry: tokens of the principals that may
+/// read it. A viewer reads the entry exactly when one of its
+/// [`EntryViewer::acl_tokens`] is listed, matching [`can_read`].
+pub fn acl_readers(acl: Option<&EntryAcl>) -> Vec<String> {
+    let Some(acl) = acl else {
+        return vec![ACL_EVERYONE.to_string()];
+    };
+    let mut readers = vec![principal_token(PrincipalKind::User, &acl.owner)];
+    match acl.visibility {
+        EntryVisibility::Space => readers.push(ACL_EVERYONE.to_string()),
+        EntryVisibility::Restricted => readers.extend(
+            acl.shared_with
+                .iter()
+                .map(|principal| principal_token(principal.kind, &principal.id)),
+        ),
+        EntryVisibility::Private => {}
+    }
+    readers
+}
+
+fn validate_entry_id(entry_id: &str) -> Result<()> {
+    if entry_id.is_empty()
+        || entry_id.contains('/')
+        || entry_id.contains('\\')
+        || entry_id.starts_with('.')
+    {
+        return Err(anyhow!("Invalid entry_id: {}", entry_id));
+    }
+    Ok(())
+}
+
+fn acl_path(ws_path: &str, entry_id: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, ENTRY_ACLS_DIR, entry_id)
+}
+
+pub async fn get_entry_acl(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+) -> Result<Option<EntryAcl>> {
+    validate_entry_id(entry_id)?;
+    let path = acl_path(ws_path, entry_id);
+    if !op.exists(&path).await? {
+        return Ok(None);
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    Ok(Some(serde_json::from_slice(&bytes)?))
+}
+
+pub async fn set_entry_acl(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    acl: &EntryAcl,
+) -> Result<EntryAcl> {
+    validate_entry_id(entry_id)?;
+    if entry::find_entry_form(op, ws_path, entry_id)
+        .await?
+        .is_none()
+    {
+        return Err(anyhow!("Entry not found: {}", entry_id));
+    }
+    let mut acl = acl.clone();
+    acl.owner = acl.owner.trim().to_string();
+    if acl.owner.is_empty() {
+        return Err(anyhow!("Entry ACL owner must not be empty"));
+    }
+    if let Some(principal) = acl.shared_with.iter().find(|p| p.id.trim().is_empty()) {
+        return Err(anyhow!(
+            "Entry ACL principal ids must not be empty ({:?})",
+            principal.kind
+        ));
+    }
+    let mut shared_with: Vec<EntryPrincipal> = Vec::with_capacity(acl.shared_with.len());
+    for principal in acl.shared_with.drain(..) {
+        if !shared_with.contains(&principal) {
+            shared_with.push(principal);
+        }
+    }
+    acl.shared_with = shared_with;
+    storage::publish_atomic(
+        op,
+        &acl_path(ws_path, entry_id),
+        serde_json::to_vec_pretty(&acl)?,
+    )
+    .await?;
+    Ok(acl)
+}
+
+/// Remove an entry ACL so the entry falls back to space-level access.
+pub async fn clear_entry_acl(op: &Operator, ws_path: &str, entry_id: &str) -> Result<()> {
+    validate_entry_id(entry_id)?;
+    let path = acl_path(ws_path, entry_id);
+    if op.exists(&path).await? {
+        op.delete(&path).await?;
+    }
+    Ok(())
+}
+
+pub async fn list_entry_acls(op: &Operator, ws_path: &str) -> Result<HashMap<String, EntryAcl>> {
+    let dir = format!("{}/{}/", ws_path, ENTRY_ACLS_DIR);
+    let mut acls = HashMap::new();
+    if !op.exists(&dir).await? {
+        return Ok(acls);
+    }
+    for item in op.list(&dir).await? {
+        let Some(entry_id) = item.name().strip_suffix(".json") else {
+            continue;
+        };
+        let bytes = op.read(item.path()).await?.to_vec();
+        acls.insert(entry_id.to_string(), serde_json::from_slice(&bytes)?);
+    }
+    Ok(acls)
+}
+
+pub async fn require_entry_read(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    viewer: &EntryViewer,
+) -> Result<()> {
+    let acl = get_entry_acl(op, ws_path, entry_id).await?;
+    if can_read(acl.as_ref(), viewer) {
+        Ok(())
+    } else {
+        // Report restricted entries as missing so their existence is not disclosed.
+        Err(anyhow!("Entry not found: {}", entry_id))
+    }
+}
@@ -0,0 +1,301 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::Mutex;
+
+use crate::audit;
+use crate::entry;
+use crate::space;
+use crate::storage;
+
+/// Scope allowing a principal to steal or force-release another holder's lock.
+pub const LOCK_ADMIN_SCOPE: &str = "entry_lock_admin";
+pub const MAX_LOCK_TTL_SECS: u64 = 3600;
+const ENTRY_LOCKS_DIR: &str = "entry_locks";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EntryLock {
+    pub entry_id: String,
+    /// Opaque token the holder presents to renew or release the lock.
+    pub lock_id: String,
+    pub holder: String,
+    pub acquired_at: f64,
+    pub expires_at: f64,
+}
+
+impl EntryLock {
+    pub fn is_active(&self, now: f64) -> bool {
+        self.expires_at > now
+    }
+}
+
+static LOCK_MUTEXES: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+
+async fn lock_mutex(key: &str) -> Arc<Mutex<()>> {
+    let mut registry = LOCK_MUTEXES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .await;
+    registry
+        .entry(key.to_string())
+        .or_insert_with(|| Arc::new(Mutex::new(())))
+        .clone()
+}
+
+fn lock_path(ws_path: &str, entry_id: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, ENTRY_LOCKS_DIR, entry_id)
+}
+
+fn validate_ttl(ttl_secs: u64) -> Result<f64> {
+    if ttl_secs == 0 || ttl_secs > MAX_LOCK_TTL_SECS {
+        return Err(anyhow!(
+            "Lock ttl must be between 1 and {} seconds",
+            MAX_LOCK_TTL_SECS
+        ));
+    }
+    Ok(ttl_secs as f64)
+}
+
+fn require_lock_admin(scopes: &[String]) -> Result<()> {
+    if scopes.iter().any(|scope| scope == LOCK_ADMIN_SCOPE) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Overriding entry locks requires the '{}' scope",
+            LOCK_ADMIN_SCOPE
+        ))
+    }
+}
+
+async fn require_entry(op: &Operator, ws_path: &str, entry_id: &str) -> Result<()> {
+    if entry::find_entry_form(op, ws_path, entry_id)
+        .await?
+        .is_none()
+    {
+        return Err(anyhow!("Entry not found: {}", entry_id));
+    }
+    Ok(())
+}
+
+async fn read_lock(op: &Operator, ws_path: &str, entry_id: &str) -> Result<Option<EntryLock>> {
+    let path = lock_path(ws_path, entry_id);
+    if !op.exists(&path).await? {
+        return Ok(None);
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    Ok(Some(serde_json::from_slice(&bytes)?))
+}
+
+async fn write_lock(op: &Operator, ws_path: &str, lock: &EntryLock) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &lock_path(ws_path, &lock.entry_id),
+        serde_json::to_vec_pretty(lock)?,
+    )
+    .await?;
+    Ok(())
+}
+
+async fn record_lock_event(
+    op: &Operator,
+    ws_path: &str,
+    action: &str,
+    actor: &str,
+    entry_id: &str,
+    previous: Option<&EntryLock>,
+) -> Result<()> {
+    audit::append_audit_event(
+        op,
+        space::space_id_from_ws_path(ws_path),
+        &json!({
+            "action": action,
+            "actor_user_id": actor,
+            "outcome": "success",
+            "target_type": "entry",
+            "target_id": entry_id,
+            "metadata": {"previous_holder": previous.map(|lock| lock.holder.clone())},
+        }),
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+/// The active lock on an entry, if any; expired locks are ignored.
+pub async fn get_entry_lock(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+) -> Result<Option<EntryLock>> {
+    let now = entry::now_ts();
+    Ok(read_lock(op, ws_path, entry_id)
+        .await?
+        .filter(|lock| lock.is_active(now)))
+}
+
+/// Acquire an advisory lock; re-acquiring a lock the principal already holds renews it.
+pub async fn acquire_entry_lock(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    principal: &str,
+    ttl_secs: u64,
+) -> Result<EntryLock> {
+    let ttl = validate_ttl(ttl_secs)?;
+    let principal = principal.trim();
+    if principal.is_empty() {
+        return Err(anyhow!("Lock principal must not be empty"));
+    }
+    require_entry(op, ws_path, entry_id).await?;
+
+    let mutex = lock_mutex(&lock_path(ws_path, entry_id)).await;
+    let _guard = mutex.lock().await;
+    let now = entry::now_ts();
+    let lock = match read_lock(op, ws_path, entry_id).await? {
+        Some(existing) if existing.is_active(now) && existing.holder != principal => {
+            return Err(anyhow!(
+                "Entry {} is locked by {}",
+                entry_id,
+                existing.holder
+            ));
+        }
+        Some(existing) if existing.is_active(now) => EntryLock {
+            expires_at: now + ttl,
+            ..existing
+        },
+        _ => EntryLock {
+            entry_id: entry_id.to_string(),
+            lock_id: uuid::Uuid::new_v4().to_string(),
+            holder: principal.to_string(),
+            acquired_at: now,
+            expires_at: now + ttl,
+        },
+    };
+    write_lock(op, ws_path, &lock).await?;
+    Ok(lock)
+}
+
+/// Extend a lock the caller still holds.
+pub async fn heartbeat_entry_lock(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    lock_id: &str,
+    ttl_secs: u64,
+) -> Result<EntryLock> {
+    let ttl = validate_ttl(ttl_secs)?;
+    let mutex = lock_mutex(&lock_path(ws_path, entry_id)).await;
+    let _guard = mutex.lock().await;
+    let now = entry::now_ts();
+    let mut lock = read_lock(op, ws_path, entry_id)
+        .await?
+        .filter(|lock| lock.lock_id == lock_id && lock.is_active(now))
+        .ok_or_else(|| anyhow!("Lock {} on entry {} is no longer held", lock_id, entry_id))?;
+    lock.expires_at = now + ttl;
+    write_lock(op, ws_path, &lock).await?;
+    Ok(lock)
+}
+
+pub async fn release_entry_lock(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    lock_id: &str,
+) -> Result<()> {
+    let mutex = lock_mutex(&lock_path(ws_path, entry_id)).await;
+    let _guard = mutex.lock().await;
+    match read_lock(op, ws_path, entry_id).await? {
+        Some(lock) if lock.lock_id == lock_id => {
+            op.delete(&lock_path(ws_path, entry_id)).await?;
+            Ok(())
+        }
+        _ => Err(anyhow!(
+            "Lock {} on entry {} is no longer held",
+            lock_id,
+            entry_id
+        )),
+    }
+}
+
+/// Take over an entry's lock regardless of the current holder.
+pub async fn steal_entry_lock(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    principal: &str,
+    ttl_secs: u64,
+    scopes: &[String],
+) -> Result<EntryLock> {
+    require_lock_admin(scopes)?;
+    let ttl = validate_ttl(ttl_secs)?;
+    require_entry(op, ws_path, entry_id).await?;
+    let mutex = lock_mutex(&lock_path(ws_path, entry_id)).await;
+    let _guard = mutex.lock().await;
+    let now = entry::now_ts();
+    let previous = read_lock(op, ws_path, entry_id)
+        .await?
+        .filter(|lock| lock.is_active(now));
+    let lock = EntryLock {
+        entry_id: entry_id.to_string(),
+        lock_id: uuid::Uuid::new_v4().to_string(),
+        holder: principal.to_string(),
+        acquired_at: now,
+        expires_at: now + ttl,
+    };
+    write_lock(op, ws_path, &lock).await?;
+    record_lock_event(
+        op,
+        ws_path,
+        "entry_lock.steal",
+        principal,
+        entry_id,
+        previous.as_ref(),
+    )
+    .await?;
+    Ok(lock)
+}
+
+/// Drop an entry's lock regardless of the current holder.
+pub async fn force_release_entry_lock(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    actor: &str,
+    scopes: &[String],
+) -> Result<Option<EntryLock>> {
+    require_lock_admin(scopes)?;
+    let mutex = lock_mutex(&lock_path(ws_path, entry_id)).await;
+    let _guard = mutex.lock().await;
+    let previous = read_lock(op, ws_path, entry_id).await?;
+    if previous.is_some() {
+        op.delete(&lock_path(ws_path, entry_id)).await?;
+    }
+    record_lock_event(
+        op,
+        ws_path,
+        "entry_lock.force_release",
+        actor,
+        entry_id,
+        previous.as_ref(),
+    )
+    .await?;
+    Ok(previous)
+}
+
+/// Reject writes by anyone other than the holder of an active lock.
+pub(crate) async fn ensure_writable_by(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    principal: &str,
+) -> Result<()> {
+    match get_entry_lock(op, ws_path, entry_id).await? {
+        Some(lock) if lock.holder != principal => {
+            Err(anyhow!("Entry {} is locked by {}", entry_id, lock.holder))
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,184 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+use crate::entry;
+use crate::storage;
+
+const ENTRY_METADATA_DIR: &str = "entry_metadata";
+
+/// Integration metadata for one entry, keyed by `x-<integration>` namespace.
+pub type EntryMetadata = BTreeMap<String, Map<String, Value>>;
+
+fn validate_entry_id(entry_id: &str) -> Result<()> {
+    if entry_id.is_empty()
+        || entry_id.contains('/')
+        || entry_id.contains('\\')
+        || entry_id.starts_with('.')
+    {
+        return Err(anyhow!("Invalid entry_id: {}", entry_id));
+    }
+    Ok(())
+}
+
+/// Namespaces look like `x-sync` or `x-notion-import`: `x-` followed by
+/// lowercase letters, digits, `-` or `_`.
+pub fn validate_namespace(namespace: &str) -> Result<()> {
+    let valid = namespace.strip_prefix("x-").is_some_and(|name| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_')
+    });
+    if !valid {
+        return Err(anyhow!(
+            "Invalid metadata namespace: {} (expected x-<integration>)",
+            namespace
+        ));
+    }
+    Ok(())
+}
+
+fn metadata_path(ws_path: &str, entry_id: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, ENTRY_METADATA_DIR, entry_id)
+}
+
+/// Every namespace stored for an entry; empty when none has been set.
+pub async fn get_entry_metadata(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+) -> Result<EntryMetadata> {
+    validate_entry_id(entry_id)?;
+    let path = metadata_path(ws_path, entry_id);
+    if !op.exists(&path).await? {
+        return Ok(EntryMetadata::new());
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+pub async fn get_entry_metadata_namespace(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    namespace: &str,
+) -> Result<Map<String, Value>> {
+    validate_namespace(namespace)?;
+    Ok(get_entry_metadata(op, ws_path, entry_id)
+        .await?
+        .remove(namespace)
+        .unwrap_or_default())
+}
+
+async fn write_metadata(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    metadata: &EntryMetadata,
+) -> Result<()> {
+    let path = metadata_path(ws_path, entry_id);
+    if metadata.is_empty() {
+        if op.exists(&path).await? {
+            op.delete(&path).await?;
+        }
+        return Ok(());
+    }
+    storage::publish_atomic(op, &path, serde_json::to_vec_pretty(metadata)?).await
+}
+
+/// Set one namespace's values. With `merge`, keys are added to the existing
+/// values and `null` removes a key; otherwise the namespace is replaced.
+/// The metadata lives beside the entry, so it never appears in the entry's
+/// markdown or frontmatter and is not validated against the entry's form.
+pub async fn set_entry_metadata(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    namespace: &str,
+    values: &Map<String, Value>,
+    merge: bool,
+) -> Result<Map<String, Value>> {
+    validate_entry_id(entry_id)?;
+    validate_namespace(namespace)?;
+    if entry::find_entry_form(op, ws_path, entry_id)
+        .await?
+        .is_none()
+    {
+        return Err(anyhow!("Entry not found: {}", entry_id));
+    }
+    let mut metadata = get_entry_metadata(op, ws_path, entry_id).await?;
+    let mut current = if merge {
+        metadata.remove(namespace).unwrap_or_default()
+    } else {
+        Map::new()
+    };
+    for (key, value) in values {
+        if value.is_null() {
+            current.remove(key);
+        } else {
+            current.insert(key.clone(), value.clone());
+        }
+    }
+    if !current.is_empty() {
+        metadata.insert(namespace.to_string(), current.clone());
+    } else {
+        metadata.remove(namespace);
+    }
+    write_metadata(op, ws_path, entry_id, &metadata).await?;
+    Ok(current)
+}
+
+/// Remove one namespace, or all integration metadata when `namespace` is `None`.
+pub async fn clear_entry_metadata(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    namespace: Option<&str>,
+) -> Result<()> {
+    validate_entry_id(entry_id)?;
+    let metadata = match namespace {
+        Some(namespace) => {
+            validate_namespace(namespace)?;
+            let mut metadata = get_entry_metadata(op, ws_path, entry_id).await?;
+            metadata.remove(namespace);
+            metadata
+        }
+        None => EntryMetadata::new(),
+    };
+    write_metadata(op, ws_path, entry_id, &metadata).await
+}
+
+/// Entry ids whose `namespace` has `key` set to `value`, e.g. to map an
+/// external id from a sync tool back to its entry.
+pub async fn find_entries_by_metadata(
+    op: &Operator,
+    ws_path: &str,
+    namespace: &str,
+    key: &str,
+    value: &Value,
+) -> Result<Vec<String>> {
+    validate_namespace(namespace)?;
+    let dir = format!("{}/{}/", ws_path, ENTRY_METADATA_DIR);
+    let mut entry_ids = Vec::new();
+    if !op.exists(&dir).await? {
+        return Ok(entry_ids);
+    }
+    for item in op.list(&dir).await? {
+        let Some(entry_id) = item.name().strip_suffix(".json") else {
+            continue;
+        };
+        let bytes = op.read(item.path()).await?.to_vec();
+        let metadata: EntryMetadata = serde_json::from_slice(&bytes)?;
+        if metadata
+            .get(namespace)
+            .and_then(|values| values.get(key))
+            .is_some_and(|stored| stored == value)
+        {
+            entry_ids.push(entry_id.to_string());
+        }
+    }
+    entry_ids.sort();
+    Ok(entry_ids)
+}
@@ -0,0 +1,262 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::entry;
+use crate::space_lock;
+use crate::storage;
+
+const ENTRY_ORDER_DIR: &str = "entry_order";
+/// Space lease serializing ordering rewrites across processes.
+const ENTRY_ORDER_LEASE: &str = "entry_order";
+/// Gap left between neighbouring keys after a rebalance.
+pub const ORDER_KEY_STEP: i64 = 1024;
+
+/// Pin state and manual position of one entry within its form.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct EntryPlacement {
+    #[serde(default)]
+    pub pinned: bool,
+    /// Ascending manual position; entries without a key follow keyed ones.
+    #[serde(default)]
+    pub order_key: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct FormOrder {
+    #[serde(default)]
+    pub entries: BTreeMap<String, EntryPlacement>,
+}
+
+fn order_path(ws_path: &str, form_name: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, ENTRY_ORDER_DIR, form_name)
+}
+
+fn validate_form_name(form_name: &str) -> Result<()> {
+    if form_name.trim().is_empty()
+        || form_name.contains('/')
+        || form_name.contains('\\')
+        || form_name.starts_with('.')
+    {
+        return Err(anyhow!("Invalid form name: {}", form_name));
+    }
+    Ok(())
+}
+
+pub async fn get_form_order(op: &Operator, ws_path: &str, form_name: &str) -> Result<FormOrder> {
+    validate_form_name(form_name)?;
+    let path = order_path(ws_path, form_name);
+    if !op.exists(&path).await? {
+        return Ok(FormOrder::default());
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+async fn write_form_order(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    order: &FormOrder,
+) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &order_path(ws_path, form_name),
+        serde_json::to_vec_pretty(order)?,
+    )
+    .await
+}
+
+fn compare_entries(order: &FormOrder, left: &Value, right: &Value) -> Ordering {
+    let placement = |entry: &Value| {
+        entry
+            .get("id")
+            .and_then(Value::as_str)
+            .and_then(|id| order.entries.get(id))
+            .cloned()
+            .unwrap_or_default()
+    };
+    let text = |entry: &Value, key: &str| {
+        entry
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let (left_place, right_place) = (placement(left), placement(right));
+    right_place
+        .pinned
+        .cmp(&left_place.pinned)
+        .then_with(|| match (left_place.order_key, right_place.order_key) {
+            (Some(l), Some(r)) => l.cmp(&r),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        })
+        .then_with(|| text(left, "title").cmp(&text(right, "title")))
+        .then_with(|| text(left, "id").cmp(&text(right, "id")))
+}
+
+/// Entries of a form, pinned first, then by `order_key`, then by title. Each
+/// item is a `list_entries` record with `pinned` and `order_key` added.
+pub async fn list_entries_ordered(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+) -> Result<Vec<Value>> {
+    let order = get_form_order(op, ws_path, form_name).await?;
+    let mut entries: Vec<Value> = entry::list_entries(op, ws_path)
+        .await?
+        .into_iter()
+        .filter(|entry| entry.get("form").and_then(Value::as_str) == Some(form_name))
+        .collect();
+    entries.sort_by(|left, right| compare_entries(&order, left, right));
+    for entry in entries.iter_mut() {
+        let placement = entry
+            .get("id")
+            .and_then(Value::as_str)
+            .and_then(|id| order.entries.get(id))
+            .cloned()
+            .unwrap_or_default();
+        if let Some(obj) = entry.as_object_mut() {
+            obj.insert("pinned".to_string(), Value::Bool(placement.pinned));
+            obj.insert(
+                "order_key".to_string(),
+                placement.order_key.map(Value::from).unwrap_or(Value::Null),
+            );
+        }
+    }
+    Ok(entries)
+}
+
+fn entry_ids(entries: &[Value]) -> Vec<String> {
+    entries
+        .iter()
+        .filter_map(|entry| entry.get("id").and_then(Value::as_str).map(str::to_string))
+        .collect()
+}
+
+/// Give every listed entry a key `ORDER_KEY_STEP` apart, keeping pin state and
+/// dropping placements of entries that no longer exist.
+fn rebalance(order: &mut FormOrder, ordered_ids: &[String]) {
+    let mut rebuilt = BTreeMap::new();
+    for (idx, entry_id) in ordered_ids.iter().enumerate() {
+        let pinned = order
+            .entries
+            .get(entry_id)
+            .map(|placement| placement.pinned)
+            .unwrap_or(false);
+        rebuilt.insert(
+            entry_id.clone(),
+            EntryPlacement {
+                pinned,
+                order_key: Some((idx as i64 + 1) * ORDER_KEY_STEP),
+            },
+        );
+    }
+    order.entries = rebuilt;
+}
+
+async fn require_entry_form(op: &Operator, ws_path: &str, entry_id: &str) -> Result<String> {
+    entry::find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))
+}
+
+pub async fn pin_entry(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    pinned: bool,
+) -> Result<EntryPlacement> {
+    let form_name = require_entry_form(op, ws_path, entry_id).await?;
+    space_lock::with_space_lease(op, ws_path, ENTRY_ORDER_LEASE, |_| async {
+        let mut order = get_form_order(op, ws_path, &form_name).await?;
+        let placement = order.entries.entry(entry_id.to_string()).or_default();
+        placement.pinned = pinned;
+        let placement = placement.clone();
+        write_form_order(op, ws_path, &form_name, &order).await?;
+        Ok(placement)
+    })
+    .await
+}
+
+/// Move an entry directly after `after_entry_id` in its form's listing, or to
+/// the top when `None`. The new key is the midpoint of its neighbours; when
+/// they leave no gap, every entry of the form is rebalanced.
+pub async fn move_entry(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    after_entry_id: Option<&str>,
+) -> Result<EntryPlacement> {
+    let form_name = require_entry_form(op, ws_path, entry_id).await?;
+    space_lock::with_space_lease(op, ws_path, ENTRY_ORDER_LEASE, |_| async {
+        let mut order = get_form_order(op, ws_path, &form_name).await?;
+        let mut ids = entry_ids(&list_entries_ordered(op, ws_path, &form_name).await?);
+        ids.retain(|id| id != entry_id);
+        let position = match after_entry_id {
+            Some(after) => {
+                ids.iter()
+                    .position(|id| id == after)
+                    .ok_or_else(|| anyhow!("Entry {} is not in form {}", after, form_name))?
+                    + 1
+            }
+            None => 0,
+        };
+        ids.insert(position, entry_id.to_string());
+
+        let key_of = |id: &String| order.entries.get(id).and_then(|p| p.order_key);
+        let prev_key = match position {
+            0 => Some(0),
+            _ => key_of(&ids[position - 1]),
+        };
+        let next_key = match ids.get(position + 1) {
+            Some(next) => key_of(next),
+            None => prev_key.map(|key| key + 2 * ORDER_KEY_STEP),
+        };
+        match (prev_key, next_key) {
+            (Some(prev), Some(next)) if next - prev > 1 => {
+                order
+                    .entries
+                    .entry(entry_id.to_string())
+                    .or_default()
+                    .order_key = Some(prev + (next - prev) / 2);
+            }
+            _ => rebalance(&mut order, &ids),
+        }
+        write_form_order(op, ws_path, &form_name, &order).await?;
+        Ok(order.entries.get(entry_id).cloned().unwrap_or_default())
+    })
+    .await
+}
+
+/// Put `entry_ids` first, in the given order, followed by the form's other
+/// entries in their current order, and rebalance all keys.
+pub async fn reorder_entries(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    entry_ids_in_order: &[String],
+) -> Result<FormOrder> {
+    space_lock::with_space_lease(op, ws_path, ENTRY_ORDER_LEASE, |_| async {
+        let mut order = get_form_order(op, ws_path, form_name).await?;
+        let current = entry_ids(&list_entries_ordered(op, ws_path, form_name).await?);
+        if let Some(unknown) = entry_ids_in_order.iter().find(|id| !current.contains(id)) {
+            return Err(anyhow!("Entry {} is not in form {}", unknown, form_name));
+        }
+        let mut ids: Vec<String> = Vec::with_capacity(current.len());
+        for id in entry_ids_in_order.iter().chain(current.iter()) {
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+        rebalance(&mut order, &ids);
+        write_form_order(op, ws_path, form_name, &order).await?;
+        Ok(order)
+    })
+    .await
+}
@@ -0,0 +1,187 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::asset;
+
+/// Space setting holding the entry size policy.
+pub const ENTRY_SIZE_SETTINGS_KEY: &str = "entry_size";
+/// Default size limit in bytes for spaces without an `entry_size` setting.
+pub const MAX_ENTRY_BYTES_ENV: &str = "UGOITE_MAX_ENTRY_BYTES";
+
+/// What happens to markdown over the size limit.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryOverflow {
+    /// Fail the write with [`EntryTooLarge`].
+    #[default]
+    Reject,
+    /// Store the full markdown as an asset and save a truncated stub entry
+    /// that links to it.
+    Spillover,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct EntrySizeConfig {
+    /// Largest entry markdown in bytes; `None` falls back to
+    /// [`MAX_ENTRY_BYTES_ENV`], and without it entries are unbounded.
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+    #[serde(default)]
+    pub overflow: EntryOverflow,
+}
+
+impl EntrySizeConfig {
+    fn limit(&self) -> Option<usize> {
+        self.max_bytes.or_else(|| {
+            std::env::var(MAX_ENTRY_BYTES_ENV)
+                .ok()
+                .and_then(|raw| raw.trim().parse::<usize>().ok())
+                .filter(|value| *value > 0)
+        })
+    }
+}
+
+/// Error raised when entry markdown is over the space's size limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryTooLarge {
+    pub size: usize,
+    pub limit: usize,
+}
+
+impl EntryTooLarge {
+    pub fn as_json(&self) -> Value {
+        json!({
+            "code": "too_large",
+            "size": self.size,
+            "limit": self.limit,
+        })
+    }
+}
+
+impl std::fmt::Display for EntryTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Entry too large: {} bytes exceeds the limit of {} bytes",
+            self.size, self.limit
+        )
+    }
+}
+
+impl std::error::Error for EntryTooLarge {}
+
+/// Markdown ready to be saved under the size policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SizedContent {
+    pub markdown: String,
+    /// The asset holding the full markdown when it spilled over.
+    pub spilled_asset: Option<Value>,
+}
+
+/// The `entry_size` setting of the space at `ws_path`.
+pub async fn load_entry_size_config(op: &Operator, ws_path: &str) -> Result<EntrySizeConfig> {
+    let settings_path = format!("{}/settings.json", ws_path.trim_end_matches('/'));
+    if !op.exists(&settings_path).await? {
+        return Ok(EntrySizeConfig::default());
+    }
+    let settings: Value = serde_json::from_slice(&op.read(&settings_path).await?.to_vec())?;
+    match settings.get(ENTRY_SIZE_SETTINGS_KEY) {
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|e| anyhow!("Invalid entry_size settings: {}", e)),
+        None => Ok(EntrySizeConfig::default()),
+    }
+}
+
+/// Byte length of the frontmatter block, closing delimiter included.
+fn frontmatter_len(markdown: &str) -> usize {
+    let Some(rest) = markdown.strip_prefix("---\n") else {
+        return 0;
+    };
+    match rest.find("\n---") {
+        Some(end) => {
+            let close = 4 + end + 4;
+            close + usize::from(markdown[close..].starts_with('\n'))
+        }
+        None => 0,
+    }
+}
+
+/// The leading part of `markdown` that fits in `budget` bytes, cut after a
+/// line when one ends in the second half of the budget.
+fn truncate_markdown(markdown: &str, budget: usize) -> &str {
+    let mut cut = budget.min(markdown.len());
+    while !markdown.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let head = &markdown[..cut];
+    match head.rfind('\n') {
+        Some(line_end) if line_end * 2 >= cut => &head[..line_end],
+        _ => head,
+    }
+}
+
+/// Apply `config` to `markdown` before it is saved as `entry_id`.
+///
+/// Markdown within the limit is returned as is. Over it, writes fail with
+/// [`EntryTooLarge`] unless the policy spills over, in which case the full
+/// markdown is saved as an asset and the entry keeps a stub of at most the
+/// limit: its frontmatter, as much of the body as fits, and a link to the
+/// asset.
+pub async fn apply_size_policy(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    markdown: &str,
+    config: &EntrySizeConfig,
+) -> Result<SizedContent> {
+    let Some(limit) = config.limit() else {
+        return Ok(SizedContent {
+            markdown: markdown.to_string(),
+            spilled_asset: None,
+        });
+    };
+    if markdown.len() <= limit {
+        return Ok(SizedContent {
+            markdown: markdown.to_string(),
+            spilled_asset: None,
+        });
+    }
+    let too_large = || {
+        anyhow::Error::new(EntryTooLarge {
+            size: markdown.len(),
+            limit,
+        })
+    };
+    if config.overflow == EntryOverflow::Reject {
+        return Err(too_large());
+    }
+
+    // The link is fixed-length, so the notice can be sized before the asset exists.
+    let notice = |link: &str| {
+        format!("\n\n> Truncated at {limit} bytes. Full content: [{entry_id}.md]({link})\n")
+    };
+    let placeholder = notice("ugoite://asset/00000000-0000-0000-0000-000000000000");
+    let budget = limit.saturating_sub(placeholder.len());
+    if frontmatter_len(markdown) > budget {
+        return Err(too_large());
+    }
+    // Boxed because saving an asset creates its metadata entry through here.
+    let info = Box::pin(asset::save_asset(
+        op,
+        ws_path,
+        &format!("{entry_id}.md"),
+        markdown.as_bytes(),
+    ))
+    .await?;
+    let stub = format!(
+        "{}{}",
+        truncate_markdown(markdown, budget).trim_end(),
+        notice(&info.link)
+    );
+    Ok(SizedContent {
+        markdown: stub,
+        spilled_asset: Some(json!({"id": info.id, "name": info.name, "path": info.path})),
+    })
+}
@@ -0,0 +1,188 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::space_lock;
+use crate::storage;
+
+/// Root-level prefix holding the global flag defaults and their lease.
+const FLAGS_ROOT: &str = "feature_flags";
+const FLAGS_LEASE: &str = "feature_flags";
+const SPACE_FLAGS_FILE: &str = "feature_flags.json";
+
+/// CRDT collaborative editing (`collab`). Disabling it rejects new updates.
+pub const CRDT_COLLAB: &str = "crdt_collab";
+/// Embedding similarity as a related-entry signal.
+pub const SEMANTIC_RELATED: &str = "semantic_related";
+
+/// Flags the core knows about, with the value used when neither the global
+/// defaults nor the space override it. Unknown flags default to off.
+pub const BUILTIN_FLAGS: [(&str, bool); 2] = [(CRDT_COLLAB, true), (SEMANTIC_RELATED, true)];
+
+/// Where an effective flag value came from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagSource {
+    Builtin,
+    Global,
+    Space,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct FeatureFlag {
+    pub name: String,
+    pub enabled: bool,
+    pub source: FlagSource,
+}
+
+/// Flag names are lowercase letters, digits and `_`, starting with a letter.
+pub fn validate_flag_name(flag: &str) -> Result<()> {
+    let valid = flag.starts_with(|ch: char| ch.is_ascii_lowercase())
+        && flag
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_');
+    if !valid || flag.len() > 64 {
+        return Err(anyhow!("Invalid feature flag name: {}", flag));
+    }
+    Ok(())
+}
+
+fn defaults_path() -> String {
+    format!("{}/defaults.json", FLAGS_ROOT)
+}
+
+fn space_flags_path(ws_path: &str) -> String {
+    format!("{}/{}", ws_path, SPACE_FLAGS_FILE)
+}
+
+async fn read_flags(op: &Operator, path: &str) -> Result<BTreeMap<String, bool>> {
+    match op.read(path).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("Feature flags at {} are malformed: {}", path, e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Apply `value` to `flag` in the map at `path`; `None` removes the entry.
+async fn update_flags(
+    op: &Operator,
+    lease_root: &str,
+    path: &str,
+    flag: &str,
+    value: Option<bool>,
+) -> Result<BTreeMap<String, bool>> {
+    validate_flag_name(flag)?;
+    space_lock::with_space_lease(op, lease_root, FLAGS_LEASE, |_| async {
+        let mut flags = read_flags(op, path).await?;
+        match value {
+            Some(enabled) => flags.insert(flag.to_string(), enabled),
+            None => flags.remove(flag),
+        };
+        storage::publish_atomic(op, path, serde_json::to_vec_pretty(&flags)?).await?;
+        Ok(flags)
+    })
+    .await
+}
+
+/// Global defaults set by operators, which apply to every space that does
+/// not override them.
+pub async fn list_global_flags(op: &Operator) -> Result<BTreeMap<String, bool>> {
+    read_flags(op, &defaults_path()).await
+}
+
+/// Set the global default for `flag`, or clear it with `None` so the built-in
+/// value applies again.
+pub async fn set_global_flag(
+    op: &Operator,
+    flag: &str,
+    enabled: Option<bool>,
+) -> Result<BTreeMap<String, bool>> {
+    update_flags(op, FLAGS_ROOT, &defaults_path(), flag, enabled).await
+}
+
+/// Overrides stored for one space.
+pub async fn list_space_flag_overrides(
+    op: &Operator,
+    ws_path: &str,
+) -> Result<BTreeMap<String, bool>> {
+    read_flags(op, &space_flags_path(ws_path)).await
+}
+
+/// Override `flag` for one space, or clear the override with `None` so the
+/// global default applies again.
+pub async fn set_space_flag(
+    op: &Operator,
+    ws_path: &str,
+    flag: &str,
+    enabled: Option<bool>,
+) -> Result<BTreeMap<String, bool>> {
+    if !op.exists(&format!("{}/meta.json", ws_path)).await? {
+        return Err(anyhow!("Space not found: {}", ws_path));
+    }
+    update_flags(op, ws_path, &space_flags_path(ws_path), flag, enabled).await
+}
+
+/// Every flag known to the core, the global defaults or the space, with the
+/// value in effect for the space: space override, then global default, then
+/// built-in value.
+pub async fn resolve_flags(op: &Operator, ws_path: &str) -> Result<Vec<FeatureFlag>> {
+    let mut resolved: BTreeMap<String, FeatureFlag> = BUILTIN_FLAGS
+        .iter()
+        .map(|(name, enabled)| {
+            (
+                name.to_string(),
+                FeatureFlag {
+                    name: name.to_string(),
+                    enabled: *enabled,
+                    source: FlagSource::Builtin,
+                },
+            )
+        })
+        .collect();
+    let layers = [
+        (list_global_flags(op).await?, FlagSource::Global),
+        (
+            list_space_flag_overrides(op, ws_path).await?,
+            FlagSource::Space,
+        ),
+    ];
+    for (flags, source) in layers {
+        for (name, enabled) in flags {
+            resolved.insert(
+                name.clone(),
+                FeatureFlag {
+                    name,
+                    enabled,
+                    source,
+                },
+            );
+        }
+    }
+    Ok(resolved.into_values().collect())
+}
+
+/// Effective flags as a `name -> enabled` map, as returned by `get_space`.
+pub async fn effective_flags(op: &Operator, ws_path: &str) -> Result<BTreeMap<String, bool>> {
+    Ok(resolve_flags(op, ws_path)
+        .await?
+        .into_iter()
+        .map(|flag| (flag.name, flag.enabled))
+        .collect())
+}
+
+/// Whether `flag` is on for the space at `ws_path`.
+pub async fn is_enabled_at(op: &Operator, ws_path: &str, flag: &str) -> Result<bool> {
+    validate_flag_name(flag)?;
+    Ok(effective_flags(op, ws_path)
+        .await?
+        .get(flag)
+        .copied()
+        .unwrap_or(false))
+}
+
+/// Whether `flag` is on for `space_id`.
+pub async fn is_enabled(op: &Operator, space_id: &str, flag: &str) -> Result<bool> {
+    is_enabled_at(op, &format!("spaces/{}", space_id), flag).await
+}
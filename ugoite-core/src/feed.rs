@@ -0,0 +1,299 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+
+use crate::entry;
+use crate::form;
+use crate::metadata;
+use crate::space;
+
+pub const DEFAULT_FEED_LIMIT: usize = 20;
+const MAX_FEED_LIMIT: usize = 500;
+const SUMMARY_MAX_CHARS: usize = 280;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedFormat {
+    #[default]
+    Atom,
+    Rss,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct FeedOptions {
+    #[serde(default)]
+    pub format: FeedFormat,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub form: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Public base URL used for entry links; `ugoite://` links are used when unset.
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+struct FeedItem {
+    entry_id: String,
+    title: String,
+    updated: DateTime<Utc>,
+    created: DateTime<Utc>,
+    author: String,
+    tags: Vec<String>,
+    summary: String,
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if (c as u32) < 0x20 && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn timestamp_to_datetime(ts: f64) -> DateTime<Utc> {
+    DateTime::<Utc>::from_timestamp_micros((ts * 1_000_000.0).round() as i64).unwrap_or_default()
+}
+
+/// Stable per-revision identifier: the same entry revision always yields the same GUID.
+fn item_guid(space_id: &str, entry_id: &str, updated: &DateTime<Utc>) -> String {
+    format!(
+        "urn:ugoite:{}:entry:{}:{}",
+        space_id,
+        entry_id,
+        updated.timestamp_micros()
+    )
+}
+
+fn item_link(options: &FeedOptions, entry_id: &str) -> String {
+    match options.base_url.as_deref().map(|u| u.trim_end_matches('/')) {
+        Some(base) if !base.is_empty() => format!("{}/entries/{}", base, entry_id),
+        _ => format!("ugoite://entry/{}", entry_id),
+    }
+}
+
+fn summarize(markdown: &str) -> String {
+    let mut text = String::new();
+    for line in markdown.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line == "---" {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(line);
+        if text.chars().count() > SUMMARY_MAX_CHARS {
+            break;
+        }
+    }
+    if text.chars().count() > SUMMARY_MAX_CHARS {
+        let truncated: String = text.chars().take(SUMMARY_MAX_CHARS).collect();
+        format!("{}…", truncated.trim_end())
+    } else {
+        text
+    }
+}
+
+fn body_without_frontmatter(markdown: &str) -> &str {
+    if let Some(rest) = markdown.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---\n") {
+            return &rest[end + 5..];
+        }
+    }
+    markdown
+}
+
+async fn collect_items(
+    op: &Operator,
+    ws_path: &str,
+    options: &FeedOptions,
+) -> Result<Vec<FeedItem>> {
+    let limit = options
+        .limit
+        .unwrap_or(DEFAULT_FEED_LIMIT)
+        .clamp(1, MAX_FEED_LIMIT);
+    let mut rows: Vec<(String, entry::EntryRow)> = entry::list_entry_rows(op, ws_path)
+        .await?
+        .into_iter()
+        .filter(|(form_name, row)| {
+            !row.deleted
+                && !metadata::is_reserved_metadata_form(form_name)
+                && options.form.as_deref().is_none_or(|f| f == form_name)
+                && options
+                    .tag
+                    .as_deref()
+                    .is_none_or(|tag| row.tags.iter().any(|t| t == tag))
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.1.updated_at
+            .partial_cmp(&a.1.updated_at)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.1.entry_id.cmp(&b.1.entry_id))
+    });
+    rows.truncate(limit);
+
+    let mut form_defs = std::collections::HashMap::new();
+    let mut revisions = std::collections::HashMap::new();
+    let mut items = Vec::with_capacity(rows.len());
+    for (form_name, row) in rows {
+        if !form_defs.contains_key(&form_name) {
+            let def = form::read_form_definition(op, ws_path, &form_name).await?;
+            // Authors live on revisions, not on the entries table rows.
+            let latest = entry::latest_form_revisions(op, ws_path, &form_name, &def).await?;
+            form_defs.insert(form_name.clone(), def);
+            revisions.insert(form_name.clone(), latest);
+        }
+        let author = revisions[&form_name]
+            .get(&row.entry_id)
+            .map(|revision| revision.author.clone())
+            .unwrap_or(row.author);
+        let markdown = entry::render_markdown_for_form(
+            &row.title,
+            &form_name,
+            &row.tags,
+            &row.fields,
+            &row.extra_attributes,
+            &form_defs[&form_name],
+        );
+        items.push(FeedItem {
+            summary: summarize(body_without_frontmatter(&markdown)),
+            entry_id: row.entry_id,
+            title: row.title,
+            updated: timestamp_to_datetime(row.updated_at),
+            created: timestamp_to_datetime(row.created_at),
+            author,
+            tags: row.tags,
+        });
+    }
+    Ok(items)
+}
+
+fn render_atom(space_id: &str, title: &str, options: &FeedOptions, items: &[FeedItem]) -> String {
+    let updated = items
+        .first()
+        .map(|item| item.updated)
+        .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap_or_default());
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("  <id>urn:ugoite:{}</id>\n", xml_escape(space_id)));
+    xml.push_str(&format!("  <title>{}</title>\n", xml_escape(title)));
+    xml.push_str(&format!("  <updated>{}</updated>\n", updated.to_rfc3339()));
+    for item in items {
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!(
+            "    <id>{}</id>\n",
+            xml_escape(&item_guid(space_id, &item.entry_id, &item.updated))
+        ));
+        xml.push_str(&format!("    <title>{}</title>\n", xml_escape(&item.title)));
+        xml.push_str(&format!(
+            "    <link href=\"{}\"/>\n",
+            xml_escape(&item_link(options, &item.entry_id))
+        ));
+        xml.push_str(&format!(
+            "    <published>{}</published>\n",
+            item.created.to_rfc3339()
+        ));
+        xml.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            item.updated.to_rfc3339()
+        ));
+        if !item.author.is_empty() {
+            xml.push_str(&format!(
+                "    <author><name>{}</name></author>\n",
+                xml_escape(&item.author)
+            ));
+        }
+        for tag in &item.tags {
+            xml.push_str(&format!("    <category term=\"{}\"/>\n", xml_escape(tag)));
+        }
+        xml.push_str(&format!(
+            "    <summary>{}</summary>\n",
+            xml_escape(&item.summary)
+        ));
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+fn render_rss(space_id: &str, title: &str, options: &FeedOptions, items: &[FeedItem]) -> String {
+    let channel_link = match options.base_url.as_deref().map(|u| u.trim_end_matches('/')) {
+        Some(base) if !base.is_empty() => base.to_string(),
+        _ => format!("ugoite://space/{}", space_id),
+    };
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\">\n  <channel>\n");
+    xml.push_str(&format!("    <title>{}</title>\n", xml_escape(title)));
+    xml.push_str(&format!("    <link>{}</link>\n", xml_escape(&channel_link)));
+    xml.push_str(&format!(
+        "    <description>Recently updated entries in {}</description>\n",
+        xml_escape(space_id)
+    ));
+    if let Some(first) = items.first() {
+        xml.push_str(&format!(
+            "    <lastBuildDate>{}</lastBuildDate>\n",
+            first.updated.to_rfc2822()
+        ));
+    }
+    for item in items {
+        xml.push_str("    <item>\n");
+        xml.push_str(&format!(
+            "      <guid isPermaLink=\"false\">{}</guid>\n",
+            xml_escape(&item_guid(space_id, &item.entry_id, &item.updated))
+        ));
+        xml.push_str(&format!(
+            "      <title>{}</title>\n",
+            xml_escape(&item.title)
+        ));
+        xml.push_str(&format!(
+            "      <link>{}</link>\n",
+            xml_escape(&item_link(options, &item.entry_id))
+        ));
+        xml.push_str(&format!(
+            "      <pubDate>{}</pubDate>\n",
+            item.updated.to_rfc2822()
+        ));
+        for tag in &item.tags {
+            xml.push_str(&format!("      <category>{}</category>\n", xml_escape(tag)));
+        }
+        xml.push_str(&format!(
+            "      <description>{}</description>\n",
+            xml_escape(&item.summary)
+        ));
+        xml.push_str("    </item>\n");
+    }
+    xml.push_str("  </channel>\n</rss>\n");
+    xml
+}
+
+/// Render an Atom or RSS document of the most recently updated entries in a space.
+pub async fn generate_feed(op: &Operator, ws_path: &str, options: &FeedOptions) -> Result<String> {
+    if !op.exists(&format!("{}/meta.json", ws_path)).await? {
+        return Err(anyhow!("Space not found: {}", ws_path));
+    }
+    let space_id = space::space_id_from_ws_path(ws_path);
+    let title = options
+        .title
+        .clone()
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| space_id.to_string());
+    let items = collect_items(op, ws_path, options).await?;
+    Ok(match options.format {
+        FeedFormat::Atom => render_atom(&space_id, &title, options, &items),
+        FeedFormat::Rss => render_rss(&space_id, &title, options, &items),
+    })
+}
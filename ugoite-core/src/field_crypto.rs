@@ -0,0 +1,341 @@
+use aes_gcm::aead::{Aead, KeyInit as _};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+use chrono::Utc;
+use hmac::{Hmac, KeyInit, Mac};
+use opendal::Operator;
+use rand::TryRng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sha2_hmac::Sha256 as HmacSha256Digest;
+
+use crate::entry;
+use crate::space_lock;
+use crate::storage;
+
+type HmacSha256 = Hmac<HmacSha256Digest>;
+
+/// Scope a caller must hold to read secret field values in plaintext.
+pub const SECRET_READ_SCOPE: &str = "secret_read";
+/// Value returned in place of a secret; submitting it back keeps the stored secret.
+pub const SECRET_PLACEHOLDER: &str = "[secret]";
+const ENVELOPE_PREFIX: &str = "ugoite-secret:v1:";
+const INDEX_HASH_PREFIX: &str = "hmac-sha256:";
+const DATA_KEY_FILE: &str = "data_key.json";
+/// Space lease serializing creation of the data key across processes.
+const DATA_KEY_LEASE: &str = "data_key";
+const NONCE_LEN: usize = 12;
+const SECRET_FIELD_TYPES: &[&str] = &["string", "markdown"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct DataKey {
+    key_id: String,
+    key: String,
+    created_at: String,
+}
+
+impl DataKey {
+    fn generate() -> Self {
+        let mut key_bytes = [0u8; 32];
+        rand::rngs::SysRng
+            .try_fill_bytes(&mut key_bytes)
+            .expect("Failed to generate secure random bytes");
+        Self {
+            key_id: format!("key-{}", uuid::Uuid::new_v4().simple()),
+            key: general_purpose::STANDARD.encode(key_bytes),
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    fn key_bytes(&self) -> Result<Vec<u8>> {
+        general_purpose::STANDARD
+            .decode(&self.key)
+            .map_err(|e| anyhow!("Invalid space data key {}: {}", self.key_id, e))
+    }
+
+    fn index_hash(&self, plaintext: &str) -> Result<String> {
+        // A derived key keeps index hashes independent from the cipher key.
+        let index_key = Sha256::new()
+            .chain_update(b"ugoite-secret-index:")
+            .chain_update(self.key_bytes()?)
+            .finalize();
+        let mut mac = HmacSha256::new_from_slice(&index_key)
+            .map_err(|e| anyhow!("Invalid index key: {}", e))?;
+        mac.update(plaintext.as_bytes());
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+
+    fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let cipher = Aes256Gcm::new_from_slice(&self.key_bytes()?)
+            .map_err(|e| anyhow!("Invalid space data key {}: {}", self.key_id, e))?;
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rngs::SysRng
+            .try_fill_bytes(&mut nonce)
+            .expect("Failed to generate secure random bytes");
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+            .map_err(|_| anyhow!("Failed to encrypt secret field"))?;
+        let mut payload = nonce.to_vec();
+        payload.extend(ciphertext);
+        Ok(format!(
+            "{}{}:{}:{}",
+            ENVELOPE_PREFIX,
+            self.key_id,
+            self.index_hash(plaintext)?,
+            general_purpose::STANDARD.encode(payload)
+        ))
+    }
+
+    fn decrypt(&self, envelope: &Envelope) -> Result<String> {
+        if envelope.key_id != self.key_id {
+            return Err(anyhow!(
+                "Secret field was encrypted with unknown key {}",
+                envelope.key_id
+            ));
+        }
+        let payload = general_purpose::STANDARD
+            .decode(envelope.payload)
+            .map_err(|e| anyhow!("Invalid secret field payload: {}", e))?;
+        if payload.len() <= NONCE_LEN {
+            return Err(anyhow!("Invalid secret field payload"));
+        }
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let cipher = Aes256Gcm::new_from_slice(&self.key_bytes()?)
+            .map_err(|e| anyhow!("Invalid space data key {}: {}", self.key_id, e))?;
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow!("Failed to decrypt secret field"))?;
+        Ok(String::from_utf8(plaintext)?)
+    }
+}
+
+struct Envelope<'a> {
+    key_id: &'a str,
+    hash: &'a str,
+    payload: &'a str,
+}
+
+fn parse_envelope(value: &Value) -> Option<Envelope<'_>> {
+    let rest = value.as_str()?.strip_prefix(ENVELOPE_PREFIX)?;
+    let mut parts = rest.splitn(3, ':');
+    Some(Envelope {
+        key_id: parts.next()?,
+        hash: parts.next()?,
+        payload: parts.next()?,
+    })
+}
+
+fn data_key_path(ws_path: &str) -> String {
+    format!("{}/{}", ws_path.trim_end_matches('/'), DATA_KEY_FILE)
+}
+
+async fn load_data_key(op: &Operator, ws_path: &str) -> Result<Option<DataKey>> {
+    let path = data_key_path(ws_path);
+    if !op.exists(&path).await? {
+        return Ok(None);
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    Ok(Some(serde_json::from_slice(&bytes)?))
+}
+
+async fn load_or_create_data_key(op: &Operator, ws_path: &str) -> Result<DataKey> {
+    if let Some(key) = load_data_key(op, ws_path).await? {
+        return Ok(key);
+    }
+    space_lock::with_space_lease(op, ws_path, DATA_KEY_LEASE, |_| async {
+        // Another writer may have created the key while we waited for the lease.
+        if let Some(key) = load_data_key(op, ws_path).await? {
+            return Ok(key);
+        }
+        let key = DataKey::generate();
+        storage::publish_atomic(
+            op,
+            &data_key_path(ws_path),
+            serde_json::to_vec_pretty(&key)?,
+        )
+        .await?;
+        Ok(key)
+    })
+    .await
+}
+
+async fn require_data_key(op: &Operator, ws_path: &str) -> Result<DataKey> {
+    load_data_key(op, ws_path)
+        .await?
+        .ok_or_else(|| anyhow!("Space data key not found: {}", ws_path))
+}
+
+fn field_defs(form_def: &Value) -> Vec<(String, &Value)> {
+    match form_def.get("fields") {
+        Some(Value::Object(map)) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| {
+                let name = item.get("name")?.as_str()?;
+                Some((name.to_string(), item))
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Names of form fields declared with `"secret": true`.
+pub fn secret_field_names(form_def: &Value) -> Vec<String> {
+    field_defs(form_def)
+        .into_iter()
+        .filter(|(_, def)| def.get("secret").and_then(Value::as_bool) == Some(true))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+pub(crate) fn validate_secret_field_defs(form_def: &Value) -> Result<()> {
+    for (name, def) in field_defs(form_def) {
+        let Some(secret) = def.get("secret") else {
+            continue;
+        };
+        if !secret.is_boolean() {
+            return Err(anyhow!(
+                "Field '{}' secret attribute must be a boolean",
+                name
+            ));
+        }
+        let field_type = def.get("type").and_then(Value::as_str).unwrap_or("string");
+        if secret.as_bool() == Some(true) && !SECRET_FIELD_TYPES.contains(&field_type) {
+            return Err(anyhow!(
+                "Secret field '{}' must be of type string or markdown, got {}",
+                name,
+                field_type
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Bring stored field values in line with the form: secret fields are encrypted and
+/// fields that are no longer secret are decrypted. A secret field submitted as
+/// [`SECRET_PLACEHOLDER`] keeps its value from `previous`. An encrypted value is only
+/// accepted when it is the one `previous` already holds for the same field.
+pub(crate) async fn seal_fields(
+    op: &Operator,
+    ws_path: &str,
+    form_def: &Value,
+    fields: &mut Value,
+    previous: Option<&Value>,
+) -> Result<()> {
+    let secret_fields = secret_field_names(form_def);
+    let Some(map) = fields.as_object_mut() else {
+        return Ok(());
+    };
+    let mut key: Option<DataKey> = None;
+    for (name, value) in map.iter_mut() {
+        let is_secret = secret_fields.contains(name);
+        if value
+            .as_str()
+            .is_some_and(|raw| raw.starts_with(ENVELOPE_PREFIX))
+        {
+            // Only the value already stored for this field may come back encrypted;
+            // anything else would let a writer decrypt envelopes copied from elsewhere.
+            let stored = previous.and_then(|prev| prev.get(name));
+            if stored != Some(&*value) {
+                return Err(anyhow!(
+                    "Field {} cannot be set to an encrypted value",
+                    name
+                ));
+            }
+            if is_secret {
+                continue;
+            }
+            let Some(envelope) = parse_envelope(value) else {
+                continue;
+            };
+            if key.is_none() {
+                key = Some(require_data_key(op, ws_path).await?);
+            }
+            let plaintext = key.as_ref().expect("data key loaded").decrypt(&envelope)?;
+            *value = Value::String(plaintext);
+            continue;
+        }
+        if !is_secret || value.is_null() {
+            continue;
+        }
+        if value.as_str() == Some(SECRET_PLACEHOLDER) {
+            let kept = previous
+                .and_then(|prev| prev.get(name))
+                .filter(|prev| parse_envelope(prev).is_some())
+                .cloned();
+            if let Some(kept) = kept {
+                *value = kept;
+                continue;
+            }
+        }
+        if key.is_none() {
+            key = Some(load_or_create_data_key(op, ws_path).await?);
+        }
+        let plaintext = entry::section_value_to_string(value);
+        *value = Value::String(key.as_ref().expect("data key loaded").encrypt(&plaintext)?);
+    }
+    Ok(())
+}
+
+/// Replace encrypted values with [`SECRET_PLACEHOLDER`].
+pub(crate) fn mask_fields(fields: &mut Value) {
+    if let Some(map) = fields.as_object_mut() {
+        for value in map.values_mut() {
+            if parse_envelope(value).is_some() {
+                *value = Value::String(SECRET_PLACEHOLDER.to_string());
+            }
+        }
+    }
+}
+
+/// Replace encrypted values with their keyed hash so indexes only learn presence/equality.
+pub(crate) fn index_fields(fields: &mut Value) {
+    if let Some(map) = fields.as_object_mut() {
+        for value in map.values_mut() {
+            if let Some(envelope) = parse_envelope(value) {
+                *value = Value::String(format!("{}{}", INDEX_HASH_PREFIX, envelope.hash));
+            }
+        }
+    }
+}
+
+pub(crate) async fn reveal_fields(op: &Operator, ws_path: &str, fields: &mut Value) -> Result<()> {
+    let Some(map) = fields.as_object_mut() else {
+        return Ok(());
+    };
+    let mut key: Option<DataKey> = None;
+    for value in map.values_mut() {
+        let Some(envelope) = parse_envelope(value) else {
+            continue;
+        };
+        if key.is_none() {
+            key = Some(require_data_key(op, ws_path).await?);
+        }
+        let plaintext = key.as_ref().expect("data key loaded").decrypt(&envelope)?;
+        *value = Value::String(plaintext);
+    }
+    Ok(())
+}
+
+/// Index value for a secret plaintext, for equality lookups against `query_index` results.
+pub async fn secret_index_hash(op: &Operator, ws_path: &str, plaintext: &str) -> Result<String> {
+    let key = require_data_key(op, ws_path).await?;
+    Ok(format!(
+        "{}{}",
+        INDEX_HASH_PREFIX,
+        key.index_hash(plaintext)?
+    ))
+}
+
+pub fn require_secret_scope(scopes: &[String]) -> Result<()> {
+    if scopes.iter().any(|scope| scope == SECRET_READ_SCOPE) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Reading secret fields requires the '{}' scope",
+            SECRET_READ_SCOPE
+        ))
+    }
+}
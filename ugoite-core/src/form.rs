@@ -1,12 +1,28 @@
+use crate::clock;
+use crate::decimal::DecimalSpec;
 use crate::entry;
+use crate::field_crypto;
 use crate::iceberg_store;
 use crate::integrity::IntegrityProvider;
 use crate::metadata;
+use crate::reminders;
+use crate::retention;
+use crate::revision_delta;
+use crate::rollup;
+use crate::sequence;
+use crate::space_lock;
+use crate::temporal;
+use crate::unique;
+use crate::workflow;
 use anyhow::{anyhow, Context, Result};
 use opendal::Operator;
 use serde_json::{Map, Value};
 use std::collections::HashSet;
-use uuid::Uuid;
+
+pub use crate::tabular::{
+    create_tabular_import_job, export_tabular, export_tabular_redacted, get_tabular_import_job,
+    import_tabular, resume_tabular_import_job,
+};
 
 pub async fn list_forms(op: &Operator, ws_path: &str) -> Result<Vec<Value>> {
     let mut forms = Vec::new();
@@ -35,8 +51,14 @@ pub async fn list_column_types() -> Result<Vec<String>> {
         "timestamp_tz".to_string(),
         "timestamp_ns".to_string(),
         "timestamp_tz_ns".to_string(),
+        "datetime".to_string(),
+        "duration".to_string(),
+        "decimal".to_string(),
+        "geopoint".to_string(),
+        "sequence".to_string(),
         "uuid".to_string(),
         "row_reference".to_string(),
+        "rollup".to_string(),
         "binary".to_string(),
         "list".to_string(),
         "object_list".to_string(),
@@ -55,6 +77,7 @@ pub async fn upsert_form(op: &Operator, ws_path: &str, form_def: &Value) -> Resu
         .and_then(|v| v.as_str())
         .context("Form definition missing 'name' field")?;
     validate_row_reference_targets(op, ws_path, form_name, &normalized).await?;
+    rollup::validate_rollup_sources(op, ws_path, form_name, &normalized).await?;
     let existing = match iceberg_store::load_form_definition(op, ws_path, form_name).await {
         Ok(def) => Some(def),
         Err(_) => iceberg_store::load_form_definition_from_metadata(op, ws_path, form_name)
@@ -81,12 +104,12 @@ pub async fn upsert_form(op: &Operator, ws_path: &str, form_def: &Value) -> Resu
         };
         if fields_changed || def_changed || schema_mismatch {
             rebuild_form_tables(op, ws_path, form_name, &existing_def, &normalized).await?;
-            return Ok(());
+            return rollup::sync_form_rollups(op, ws_path, &normalized).await;
         }
     }
 
     iceberg_store::ensure_form_tables(op, ws_path, &normalized).await?;
-    Ok(())
+    rollup::sync_form_rollups(op, ws_path, &normalized).await
 }
 
 pub(crate) async fn upsert_metadata_form(
@@ -99,16 +122,32 @@ pub(crate) async fn upsert_metadata_form(
     Ok(())
 }
 
+/// Apply a form definition change and its per-field strategies, holding the
+/// space's migration lease so concurrent workers cannot interleave rewrites.
 pub async fn migrate_form<I: IntegrityProvider>(
     op: &Operator,
     ws_path: &str,
     form_def: &Value,
     strategies: Option<Value>,
     integrity: &I,
+) -> Result<usize> {
+    space_lock::with_space_lease(op, ws_path, space_lock::MIGRATION_LEASE, |_| {
+        migrate_form_unlocked(op, ws_path, form_def, strategies, integrity)
+    })
+    .await
+}
+
+async fn migrate_form_unlocked<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    form_def: &Value,
+    strategies: Option<Value>,
+    integrity: &I,
 ) -> Result<usize> {
     let normalized = normalize_form_definition(form_def)?;
     let form_name = normalized["name"].as_str().context("Form name required")?;
     validate_row_reference_targets(op, ws_path, form_name, &normalized).await?;
+    rollup::validate_rollup_sources(op, ws_path, form_name, &normalized).await?;
     let existing_def = match iceberg_store::load_form_definition(op, ws_path, form_name).await {
         Ok(def) => Some(def),
         Err(_) => iceberg_store::load_form_definition_from_metadata(op, ws_path, form_name)
@@ -121,6 +160,7 @@ pub async fn migrate_form<I: IntegrityProvider>(
         let fields_changed = existing_def.get("fields") != normalized.get("fields");
         if fields_changed {
             rebuild_form_tables(op, ws_path, form_name, &existing_def, &normalized).await?;
+            rollup::sync_form_rollups(op, ws_path, &normalized).await?;
         } else {
             upsert_form(op, ws_path, &normalized).await?;
         }
@@ -195,7 +235,7 @@ pub async fn migrate_form<I: IntegrityProvider>(
         if timestamp <= row.updated_at {
             timestamp = row.updated_at + 0.001;
         }
-        let new_rev_id = Uuid::new_v4().to_string();
+        let new_rev_id = clock::new_uuid().to_string();
 
         row.parent_revision_id = Some(row.revision_id.clone());
         row.revision_id = new_rev_id.clone();
@@ -231,6 +271,8 @@ pub async fn migrate_form<I: IntegrityProvider>(
             markdown_checksum: checksum,
             integrity: row.integrity.clone(),
             restored_from: None,
+            message: None,
+            revision_metadata: None,
         };
         entry::append_revision_row_for_form(op, ws_path, form_name, &revision, &normalized).await?;
 
@@ -254,6 +296,41 @@ pub(crate) async fn read_form_definition(
         .context(format!("Form {} not found", form_name))
 }
 
+/// [`read_form_definition`] with the space's `default_timezone` setting
+/// copied in as the form `timezone`, so naive `datetime` values written to
+/// the form are read in the space's offset. Only forms with `datetime`
+/// fields read the space settings.
+pub(crate) async fn read_form_definition_for_write(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+) -> Result<Value> {
+    let mut form_def = read_form_definition(op, ws_path, form_name).await?;
+    let has_datetime = form_def
+        .get("fields")
+        .and_then(Value::as_object)
+        .is_some_and(|fields| {
+            fields
+                .values()
+                .any(|def| def.get("type").and_then(Value::as_str) == Some("datetime"))
+        });
+    if !has_datetime {
+        return Ok(form_def);
+    }
+    let settings: Value = match op.read(&format!("{}/settings.json", ws_path)).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())?,
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Value::Null,
+        Err(err) => return Err(err.into()),
+    };
+    if let Some(tz) = settings
+        .get(temporal::DEFAULT_TIMEZONE_SETTING)
+        .and_then(Value::as_str)
+    {
+        form_def[temporal::TIMEZONE_KEY] = Value::String(tz.to_string());
+    }
+    Ok(form_def)
+}
+
 fn normalize_form_definition(form_def: &Value) -> Result<Value> {
     normalize_form_definition_with_options(form_def, false)
 }
@@ -287,7 +364,14 @@ fn normalize_form_definition_with_options(
             }
         }
         validate_row_reference_field_defs(field_map)?;
+        validate_field_timezones(field_map)?;
+        validate_decimal_field_defs(field_map)?;
+        sequence::validate_sequence_field_defs(field_map)?;
+        reminders::validate_reminder_field_defs(field_map)?;
+        rollup::validate_rollup_field_defs(field_map)?;
+        unique::validate_unique_field_defs(field_map)?;
     }
+    field_crypto::validate_secret_field_defs(&serde_json::json!({ "fields": fields }))?;
     let allow_extra_attributes = form_def
         .get("allow_extra_attributes")
         .and_then(|v| v.as_str())
@@ -302,12 +386,28 @@ fn normalize_form_definition_with_options(
         ));
     }
 
-    Ok(serde_json::json!({
+    let mut normalized = serde_json::json!({
         "name": name,
         "version": version,
         "fields": fields,
         "allow_extra_attributes": allow_extra_attributes,
-    }))
+    });
+    if let Some(retention) = form_def.get("retention").filter(|v| !v.is_null()) {
+        normalized["retention"] = retention::normalize_retention(retention)?;
+    }
+    if let Some(workflow) = form_def.get("workflow").filter(|v| !v.is_null()) {
+        let empty = Map::new();
+        normalized["workflow"] =
+            workflow::normalize_workflow(workflow, fields.as_object().unwrap_or(&empty))?;
+    }
+    if let Some(interval) = form_def
+        .get("revision_snapshot_interval")
+        .filter(|v| !v.is_null())
+    {
+        normalized["revision_snapshot_interval"] =
+            revision_delta::normalize_snapshot_interval(interval)?;
+    }
+    Ok(normalized)
 }
 
 fn validate_row_reference_field_defs(field_map: &Map<String, Value>) -> Result<()> {
@@ -333,6 +433,27 @@ fn validate_row_reference_field_defs(field_map: &Map<String, Value>) -> Result<(
     Ok(())
 }
 
+fn validate_field_timezones(field_map: &Map<String, Value>) -> Result<()> {
+    for (name, def) in field_map {
+        if let Some(tz) = def.get(temporal::TIMEZONE_KEY) {
+            let tz = tz
+                .as_str()
+                .ok_or_else(|| anyhow!("Field '{}' timezone must be a string", name))?;
+            temporal::parse_offset(tz).with_context(|| format!("Field '{}'", name))?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_decimal_field_defs(field_map: &Map<String, Value>) -> Result<()> {
+    for (name, def) in field_map {
+        if def.get("type").and_then(|v| v.as_str()) == Some("decimal") {
+            DecimalSpec::from_field_def(def).with_context(|| format!("Field '{}'", name))?;
+        }
+    }
+    Ok(())
+}
+
 async fn validate_row_reference_targets(
     op: &Operator,
     ws_path: &str,
@@ -381,7 +502,7 @@ fn is_reserved_metadata_form(name: &str) -> bool {
     metadata::is_reserved_metadata_form(name)
 }
 
-fn normalize_form_fields(fields: Option<&Value>) -> Value {
+pub(crate) fn normalize_form_fields(fields: Option<&Value>) -> Value {
     let mut normalized = Map::new();
 
     match fields {
@@ -441,19 +562,41 @@ async fn rebuild_form_tables(
     existing_def: &Value,
     new_def: &Value,
 ) -> Result<()> {
-    let entry_rows = entry::list_form_entry_rows(op, ws_path, form_name, existing_def).await?;
-    let revision_rows =
+    let mut entry_rows = entry::list_form_entry_rows(op, ws_path, form_name, existing_def).await?;
+    let mut revision_rows =
         entry::list_form_revision_rows(op, ws_path, form_name, existing_def).await?;
 
+    // Stored values are trusted here, so their envelopes are kept or decrypted.
+    for row in entry_rows.iter_mut() {
+        let stored = row.fields.clone();
+        field_crypto::seal_fields(op, ws_path, new_def, &mut row.fields, Some(&stored)).await?;
+    }
+    for rev in revision_rows.iter_mut() {
+        let stored = rev.fields.clone();
+        field_crypto::seal_fields(op, ws_path, new_def, &mut rev.fields, Some(&stored)).await?;
+    }
+
+    rewrite_form_tables(op, ws_path, form_name, new_def, &entry_rows, &revision_rows).await
+}
+
+/// Replace the entries and revisions tables of a form with the given rows.
+pub(crate) async fn rewrite_form_tables(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    form_def: &Value,
+    entry_rows: &[entry::EntryRow],
+    revision_rows: &[entry::RevisionRow],
+) -> Result<()> {
     iceberg_store::drop_form_tables(op, ws_path, form_name).await?;
-    iceberg_store::ensure_form_tables(op, ws_path, new_def).await?;
+    iceberg_store::ensure_form_tables(op, ws_path, form_def).await?;
 
     for row in entry_rows {
-        entry::write_entry_row(op, ws_path, form_name, &row.entry_id, &row).await?;
+        entry::write_entry_row(op, ws_path, form_name, &row.entry_id, row).await?;
     }
 
     for rev in revision_rows {
-        entry::append_revision_row_for_form(op, ws_path, form_name, &rev, new_def).await?;
+        entry::append_revision_row_for_form(op, ws_path, form_name, rev, form_def).await?;
     }
 
     Ok(())
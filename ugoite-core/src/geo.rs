@@ -0,0 +1,161 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Mean Earth radius used for haversine distances.
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Filter operator matching points within `radius_km` of `lat`/`lon`.
+pub const NEAR_OPERATOR: &str = "$near";
+/// Filter operator matching points inside a `south`/`west`/`north`/`east` box.
+pub const WITHIN_BOX_OPERATOR: &str = "$within_box";
+
+/// A WGS84 coordinate, the value of a `geopoint` field.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    pub fn new(lat: f64, lon: f64) -> Option<Self> {
+        let valid = lat.is_finite()
+            && lon.is_finite()
+            && (-90.0..=90.0).contains(&lat)
+            && (-180.0..=180.0).contains(&lon);
+        valid.then_some(Self { lat, lon })
+    }
+
+    /// Read a point from `{"lat": .., "lon": ..}` (`lng` is accepted for
+    /// `lon`), or from `"lat, lon"` / `"lat lon"` text. GeoJSON-style arrays
+    /// are rejected because their `[lon, lat]` order is easy to get wrong.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Object(map) => {
+                let lat = coordinate(map.get("lat")?)?;
+                let lon = coordinate(map.get("lon").or_else(|| map.get("lng"))?)?;
+                Self::new(lat, lon)
+            }
+            Value::String(text) => {
+                let mut parts = text
+                    .split(|ch: char| ch == ',' || ch.is_whitespace())
+                    .filter(|part| !part.is_empty());
+                let lat = parts.next()?.parse().ok()?;
+                let lon = parts.next()?.parse().ok()?;
+                if parts.next().is_some() {
+                    return None;
+                }
+                Self::new(lat, lon)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn to_value(self) -> Value {
+        serde_json::json!({ "lat": self.lat, "lon": self.lon })
+    }
+
+    /// Great-circle distance to `other` in kilometres.
+    pub fn distance_km(self, other: GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let dlat = lat2 - lat1;
+        let dlon = (other.lon - self.lon).to_radians();
+        let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+}
+
+fn coordinate(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// A latitude/longitude box. `west > east` describes a box crossing the
+/// antimeridian.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub south: f64,
+    pub west: f64,
+    pub north: f64,
+    pub east: f64,
+}
+
+impl BoundingBox {
+    pub fn new(south: f64, west: f64, north: f64, east: f64) -> Result<Self> {
+        let corners_valid =
+            GeoPoint::new(south, west).is_some() && GeoPoint::new(north, east).is_some();
+        if !corners_valid || south > north {
+            return Err(anyhow!(
+                "Invalid bounding box: south/north must be within -90..90 with south <= north, west/east within -180..180"
+            ));
+        }
+        Ok(Self {
+            south,
+            west,
+            north,
+            east,
+        })
+    }
+
+    pub fn contains(&self, point: GeoPoint) -> bool {
+        let lat_ok = (self.south..=self.north).contains(&point.lat);
+        let lon_ok = if self.west <= self.east {
+            (self.west..=self.east).contains(&point.lon)
+        } else {
+            point.lon >= self.west || point.lon <= self.east
+        };
+        lat_ok && lon_ok
+    }
+}
+
+fn operand(args: &Map<String, Value>, operator: &str, key: &str) -> Result<f64> {
+    args.get(key)
+        .and_then(coordinate)
+        .ok_or_else(|| anyhow!("{} requires a numeric {}", operator, key))
+}
+
+/// Evaluate a structured geo filter such as
+/// `{"$near": {"lat": 35.68, "lon": 139.76, "radius_km": 5}}` or
+/// `{"$within_box": {"south": .., "west": .., "north": .., "east": ..}}`
+/// against a field value. Returns `None` when `filter` is not a geo filter.
+pub fn matches_filter(value: Option<&Value>, filter: &Map<String, Value>) -> Option<Result<bool>> {
+    let (operator, args) = filter.iter().next()?;
+    if filter.len() != 1 || !matches!(operator.as_str(), NEAR_OPERATOR | WITHIN_BOX_OPERATOR) {
+        return None;
+    }
+    let Some(args) = args.as_object() else {
+        return Some(Err(anyhow!("{} expects an object", operator)));
+    };
+    let point = value.and_then(GeoPoint::from_value);
+    Some(if operator == NEAR_OPERATOR {
+        near_filter(args).map(|(center, radius_km)| {
+            point.is_some_and(|point| point.distance_km(center) <= radius_km)
+        })
+    } else {
+        box_filter(args).map(|bbox| point.is_some_and(|point| bbox.contains(point)))
+    })
+}
+
+fn near_filter(args: &Map<String, Value>) -> Result<(GeoPoint, f64)> {
+    let lat = operand(args, NEAR_OPERATOR, "lat")?;
+    let lon = operand(args, NEAR_OPERATOR, "lon")?;
+    let radius_km = operand(args, NEAR_OPERATOR, "radius_km")?;
+    let center = GeoPoint::new(lat, lon)
+        .ok_or_else(|| anyhow!("{} center is out of range", NEAR_OPERATOR))?;
+    if !(radius_km.is_finite() && radius_km >= 0.0) {
+        return Err(anyhow!("{} radius_km must be non-negative", NEAR_OPERATOR));
+    }
+    Ok((center, radius_km))
+}
+
+fn box_filter(args: &Map<String, Value>) -> Result<BoundingBox> {
+    BoundingBox::new(
+        operand(args, WITHIN_BOX_OPERATOR, "south")?,
+        operand(args, WITHIN_BOX_OPERATOR, "west")?,
+        operand(args, WITHIN_BOX_OPERATOR, "north")?,
+        operand(args, WITHIN_BOX_OPERATOR, "east")?,
+    )
+}
@@ -7,24 +7,52 @@ use iceberg::transaction::{ApplyTransactionAction, Transaction};
 use iceberg::{Catalog, CatalogBuilder, MemoryCatalog, NamespaceIdent, TableCreation, TableIdent};
 use opendal::{options, Operator};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::Mutex as AsyncMutex;
+use uuid::Uuid;
+
+use crate::decimal::DecimalSpec;
+use crate::storage;
 
 const ENTRIES_TABLE_NAME: &str = "entries";
 const REVISIONS_TABLE_NAME: &str = "revisions";
 const FORM_DEF_PROP: &str = "ugoite.form_definition";
 const FORM_VERSION_PROP: &str = "ugoite.form_version";
+/// Names the table's committed metadata file, so readers never pick up one still being written.
+const METADATA_POINTER_FILE: &str = "current-metadata";
+/// Revisions column holding a delta against a snapshot revision instead of full field values.
+pub(crate) const REVISION_DELTA_COLUMN: &str = "delta";
 
 static CATALOG_CACHE: OnceLock<Mutex<HashMap<String, Arc<MemoryCatalog>>>> = OnceLock::new();
 fn catalog_cache() -> &'static Mutex<HashMap<String, Arc<MemoryCatalog>>> {
     CATALOG_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
-fn remove_catalog_cache(warehouse: &str) -> Result<()> {
-    let mut cache = catalog_cache()
+/// Per-space guard serializing commits, table registration and table swaps
+/// within this process, so a swap cannot interleave with a commit.
+static COMMIT_MUTEXES: OnceLock<AsyncMutex<HashMap<String, Arc<AsyncMutex<()>>>>> = OnceLock::new();
+
+async fn commit_mutex(op: &Operator, ws_path: &str) -> Result<Arc<AsyncMutex<()>>> {
+    let warehouse = warehouse_uri(op, ws_path)?;
+    let mut registry = COMMIT_MUTEXES
+        .get_or_init(|| AsyncMutex::new(HashMap::new()))
         .lock()
-        .map_err(|_| anyhow!("catalog cache lock poisoned"))?;
-    cache.remove(warehouse);
+        .await;
+    Ok(registry
+        .entry(warehouse)
+        .or_insert_with(|| Arc::new(AsyncMutex::new(())))
+        .clone())
+}
+
+/// Evict a space's cached catalog, e.g. after its directory was removed.
+pub(crate) fn forget_space_catalog(op: &Operator, ws_path: &str) -> Result<()> {
+    let warehouse = warehouse_uri(op, ws_path)?;
+    catalog_cache()
+        .lock()
+        .map_err(|_| anyhow!("catalog cache lock poisoned"))?
+        .remove(&warehouse);
     Ok(())
 }
 
@@ -87,7 +115,214 @@ fn parse_metadata_version(file_name: &str) -> Option<i32> {
     version.parse::<i32>().ok()
 }
 
+fn table_metadata_dir(ws_path: &str, form_name: &str, table_name: &str) -> String {
+    format!(
+        "{}/forms/{}/{}/metadata/",
+        ws_path.trim_end_matches('/'),
+        form_name,
+        table_name
+    )
+}
+
+/// Point readers at `table`'s current metadata file once it is fully written.
+async fn publish_metadata_pointer(
+    op: &Operator,
+    ws_path: &str,
+    table: &iceberg::table::Table,
+) -> Result<()> {
+    publish_metadata_pointer_as(op, ws_path, table.identifier(), table).await
+}
+
+/// Point readers of `ident` at `table`'s current metadata file.
+async fn publish_metadata_pointer_as(
+    op: &Operator,
+    ws_path: &str,
+    ident: &TableIdent,
+    table: &iceberg::table::Table,
+) -> Result<()> {
+    let Some(form_name) = ident.namespace().as_ref().first() else {
+        return Ok(());
+    };
+    let Some(file_name) = table
+        .metadata_location()
+        .and_then(|location| location.rsplit('/').next())
+    else {
+        return Ok(());
+    };
+    let pointer = format!(
+        "{}{}",
+        table_metadata_dir(ws_path, form_name, ident.name()),
+        METADATA_POINTER_FILE
+    );
+    storage::publish_atomic(op, &pointer, file_name.as_bytes().to_vec()).await
+}
+
+/// Commit `tx` and publish the resulting metadata generation.
+pub(crate) async fn commit_table(
+    op: &Operator,
+    ws_path: &str,
+    catalog: &MemoryCatalog,
+    tx: Transaction,
+) -> Result<iceberg::table::Table> {
+    let mutex = commit_mutex(op, ws_path).await?;
+    let _guard = mutex.lock().await;
+    let table = tx.commit(catalog).await?;
+    publish_metadata_pointer(op, ws_path, &table).await?;
+    Ok(table)
+}
+
+/// Create an empty table beside `live` with its schema, properties and
+/// location. Its files sit next to the live table's under unique names and
+/// stay invisible to readers until [`swap_staged_table`] publishes them.
+pub(crate) async fn stage_table(
+    op: &Operator,
+    ws_path: &str,
+    live: &iceberg::table::Table,
+) -> Result<(Arc<MemoryCatalog>, iceberg::table::Table)> {
+    let catalog = catalog_for_space(op, ws_path).await?;
+    let ident = live.identifier();
+    let metadata = live.metadata();
+    let creation = TableCreation::builder()
+        .name(format!(
+            "{}-staged-{}",
+            ident.name(),
+            Uuid::new_v4().simple()
+        ))
+        .location(metadata.location().to_string())
+        .schema(metadata.current_schema().as_ref().clone())
+        .partition_spec(UnboundPartitionSpec::default())
+        .sort_order(SortOrder::unsorted_order())
+        .properties(metadata.properties().clone())
+        .format_version(metadata.format_version())
+        .build();
+    let staged = catalog.create_table(ident.namespace(), creation).await?;
+    Ok((catalog, staged))
+}
+
+/// Remove a table from [`stage_table`] that will not be swapped in.
+pub(crate) async fn discard_staged_table(
+    op: &Operator,
+    ws_path: &str,
+    staged: &iceberg::table::Table,
+) {
+    let Ok(catalog) = catalog_for_space(op, ws_path).await else {
+        return;
+    };
+    if let Ok(current) = catalog.load_table(staged.identifier()).await {
+        remove_table_files(&current).await;
+        let _ = catalog.drop_table(staged.identifier()).await;
+    }
+}
+
+/// Replace `live` with `staged` unless `live` was committed to since it was
+/// loaded, in which case `staged` is discarded and an error returned.
+///
+/// Publishing the metadata pointer is the only on-disk switch, so readers see
+/// either the old or the new table in full. Files referenced only by the old
+/// table are removed once the pointer has moved.
+pub(crate) async fn swap_staged_table(
+    op: &Operator,
+    ws_path: &str,
+    live: &iceberg::table::Table,
+    staged: &iceberg::table::Table,
+) -> Result<()> {
+    let catalog = catalog_for_space(op, ws_path).await?;
+    let mutex = commit_mutex(op, ws_path).await?;
+    let guard = mutex.lock().await;
+    let ident = live.identifier();
+    let current = catalog.load_table(ident).await?;
+    if current.metadata_location() != live.metadata_location() {
+        drop(guard);
+        discard_staged_table(op, ws_path, staged).await;
+        return Err(anyhow!(
+            "Table {} changed while it was being rewritten",
+            ident
+        ));
+    }
+    if let Err(err) = publish_metadata_pointer_as(op, ws_path, ident, staged).await {
+        drop(guard);
+        discard_staged_table(op, ws_path, staged).await;
+        return Err(err);
+    }
+    let retired = TableIdent::new(
+        ident.namespace().clone(),
+        format!("{}-retired-{}", ident.name(), Uuid::new_v4().simple()),
+    );
+    let swapped = async {
+        catalog.rename_table(ident, &retired).await?;
+        catalog.rename_table(staged.identifier(), ident).await?;
+        catalog.drop_table(&retired).await?;
+        Ok::<_, iceberg::Error>(())
+    }
+    .await;
+    drop(guard);
+    if swapped.is_err() {
+        // The pointer already names the new table; rebuild the catalog from it.
+        forget_space_catalog(op, ws_path)?;
+    }
+    remove_table_files(&current).await;
+    Ok(())
+}
+
+/// Best-effort removal of the metadata, manifest and data files `table`
+/// references.
+async fn remove_table_files(table: &iceberg::table::Table) {
+    let file_io = table.file_io();
+    let metadata = table.metadata();
+    let mut paths: Vec<String> = metadata
+        .metadata_log()
+        .iter()
+        .map(|log| log.metadata_file.clone())
+        .chain(table.metadata_location().map(str::to_string))
+        .collect();
+    for snapshot in metadata.snapshots() {
+        paths.push(snapshot.manifest_list().to_string());
+        let Ok(manifests) = snapshot.load_manifest_list(file_io, metadata).await else {
+            continue;
+        };
+        for manifest_file in manifests.entries() {
+            paths.push(manifest_file.manifest_path.clone());
+            if let Ok(manifest) = manifest_file.load_manifest(file_io).await {
+                paths.extend(
+                    manifest
+                        .entries()
+                        .iter()
+                        .map(|entry| entry.data_file().file_path().to_string()),
+                );
+            }
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    for path in paths {
+        let _ = file_io.delete(&path).await;
+    }
+}
+
+/// The metadata file named by the table's pointer, if it has one.
+async fn pointed_metadata_file(op: &Operator, metadata_path: &str) -> Result<Option<String>> {
+    let pointer = format!("{}{}", metadata_path, METADATA_POINTER_FILE);
+    let bytes = match op.read(&pointer).await {
+        Ok(bytes) => bytes.to_vec(),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let file_name = String::from_utf8_lossy(&bytes).trim().to_string();
+    if parse_metadata_version(&file_name).is_none()
+        || !op
+            .exists(&format!("{}{}", metadata_path, file_name))
+            .await?
+    {
+        return Ok(None);
+    }
+    Ok(Some(file_name))
+}
+
 async fn latest_metadata_file(op: &Operator, metadata_path: &str) -> Result<Option<String>> {
+    if let Some(file_name) = pointed_metadata_file(op, metadata_path).await? {
+        return Ok(Some(file_name));
+    }
+    // Tables written before pointers existed fall back to the highest version.
     let scheme = op.info().scheme();
     if scheme == "fs" || scheme == "file" {
         let root = normalize_root(op.info().root().as_str());
@@ -221,12 +456,7 @@ async fn register_existing_tables(
                 continue;
             }
 
-            let metadata_path = format!(
-                "{}/forms/{}/{}/metadata/",
-                ws_path.trim_end_matches('/'),
-                form_name,
-                table_name
-            );
+            let metadata_path = table_metadata_dir(ws_path, &form_name, table_name);
             let Some(latest) = latest_metadata_file(op, &metadata_path).await? else {
                 continue;
             };
@@ -248,6 +478,8 @@ async fn catalog_for_space(op: &Operator, ws_path: &str) -> Result<Arc<MemoryCat
             .map_err(|_| anyhow!("catalog cache lock poisoned"))?;
         cache.get(&warehouse).cloned()
     } {
+        let mutex = commit_mutex(op, ws_path).await?;
+        let _guard = mutex.lock().await;
         register_existing_tables(op, ws_path, cached.as_ref()).await?;
         return Ok(cached);
     }
@@ -270,7 +502,7 @@ fn form_namespace(form_name: &str) -> NamespaceIdent {
     NamespaceIdent::new(form_name.to_string())
 }
 
-fn form_field_defs(form_def: &Value) -> Result<Vec<(String, String, bool)>> {
+pub(crate) fn form_field_defs(form_def: &Value) -> Result<Vec<(String, String, bool)>> {
     let mut fields = Vec::new();
     let Some(def_fields) = form_def.get("fields") else {
         return Ok(fields);
@@ -325,7 +557,7 @@ fn iceberg_type_for_field(field_type: &str, id_counter: &mut i32) -> Result<Type
         "number" | "double" => Type::Primitive(PrimitiveType::Double),
         "float" => Type::Primitive(PrimitiveType::Float),
         "integer" => Type::Primitive(PrimitiveType::Int),
-        "long" => Type::Primitive(PrimitiveType::Long),
+        "long" | "duration" => Type::Primitive(PrimitiveType::Long),
         "boolean" => Type::Primitive(PrimitiveType::Boolean),
         "date" => Type::Primitive(PrimitiveType::Date),
         "time" => Type::Primitive(PrimitiveType::Time),
@@ -335,6 +567,20 @@ fn iceberg_type_for_field(field_type: &str, id_counter: &mut i32) -> Result<Type
         "timestamp_tz_ns" => Type::Primitive(PrimitiveType::TimestamptzNs),
         "uuid" => Type::Primitive(PrimitiveType::Uuid),
         "binary" => Type::Primitive(PrimitiveType::Binary),
+        "geopoint" => {
+            let coordinate = |name: &str, id: i32| {
+                Arc::new(NestedField::new(
+                    id,
+                    name,
+                    Type::Primitive(PrimitiveType::Double),
+                    false,
+                ))
+            };
+            Type::Struct(StructType::new(vec![
+                coordinate("lat", next_id(id_counter)),
+                coordinate("lon", next_id(id_counter)),
+            ]))
+        }
         "list" => {
             let element_id = next_id(id_counter);
             let element = Arc::new(NestedField::new(
@@ -376,11 +622,33 @@ fn iceberg_type_for_field(field_type: &str, id_counter: &mut i32) -> Result<Type
     })
 }
 
+/// The definition of field `name`, for types whose Iceberg type depends on
+/// more than the type name.
+fn form_field_def(form_def: &Value, name: &str) -> Value {
+    match form_def.get("fields") {
+        Some(Value::Object(map)) => map.get(name).cloned(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .find(|item| item.get("name").and_then(|v| v.as_str()) == Some(name))
+            .cloned(),
+        _ => None,
+    }
+    .unwrap_or(Value::Null)
+}
+
 fn build_fields_struct(form_def: &Value, id_counter: &mut i32) -> Result<Type> {
     let mut nested_fields = Vec::new();
     for (name, field_type, required) in form_field_defs(form_def)? {
         let field_id = next_id(id_counter);
-        let field_type = iceberg_type_for_field(&field_type, id_counter)?;
+        let field_type = if field_type == "decimal" {
+            let spec = DecimalSpec::from_field_def(&form_field_def(form_def, &name))?;
+            Type::Primitive(PrimitiveType::Decimal {
+                precision: spec.precision,
+                scale: spec.scale,
+            })
+        } else {
+            iceberg_type_for_field(&field_type, id_counter)?
+        };
         nested_fields.push(Arc::new(NestedField::new(
             field_id, name, field_type, required,
         )));
@@ -633,6 +901,24 @@ fn build_revisions_schema(form_def: &Value) -> Result<Schema> {
             Type::Primitive(PrimitiveType::String),
             false,
         )),
+        Arc::new(NestedField::new(
+            next_id(&mut counter),
+            REVISION_DELTA_COLUMN,
+            Type::Primitive(PrimitiveType::String),
+            false,
+        )),
+        Arc::new(NestedField::new(
+            next_id(&mut counter),
+            "message",
+            Type::Primitive(PrimitiveType::String),
+            false,
+        )),
+        Arc::new(NestedField::new(
+            next_id(&mut counter),
+            "revision_metadata",
+            Type::Primitive(PrimitiveType::String),
+            false,
+        )),
     ];
 
     Schema::builder()
@@ -683,21 +969,23 @@ pub async fn ensure_form_tables(op: &Operator, ws_path: &str, form_def: &Value)
             .sort_order(SortOrder::unsorted_order())
             .properties(props)
             .build();
-        let created = catalog.create_table(&namespace, creation).await;
-        if let Err(err) = created {
-            let message = err.to_string();
-            if !message.contains("TableAlreadyExists") && !message.contains("already exists") {
-                return Err(err.into());
-            }
-            let props = table_properties(form_def)?;
-            let table = catalog.load_table(&entries_ident).await?;
-            let tx = Transaction::new(&table);
-            let mut action = tx.update_table_properties();
-            for (key, value) in props {
-                action = action.set(key, value);
+        match catalog.create_table(&namespace, creation).await {
+            Ok(table) => publish_metadata_pointer(op, ws_path, &table).await?,
+            Err(err) => {
+                let message = err.to_string();
+                if !message.contains("TableAlreadyExists") && !message.contains("already exists") {
+                    return Err(err.into());
+                }
+                let props = table_properties(form_def)?;
+                let table = catalog.load_table(&entries_ident).await?;
+                let tx = Transaction::new(&table);
+                let mut action = tx.update_table_properties();
+                for (key, value) in props {
+                    action = action.set(key, value);
+                }
+                let tx = action.apply(tx)?;
+                commit_table(op, ws_path, catalog.as_ref(), tx).await?;
             }
-            let tx = action.apply(tx)?;
-            tx.commit(catalog.as_ref()).await?;
         }
     } else {
         let props = table_properties(form_def)?;
@@ -708,7 +996,7 @@ pub async fn ensure_form_tables(op: &Operator, ws_path: &str, form_def: &Value)
             action = action.set(key, value);
         }
         let tx = action.apply(tx)?;
-        tx.commit(catalog.as_ref()).await?;
+        commit_table(op, ws_path, catalog.as_ref(), tx).await?;
     }
 
     let revisions_ident = TableIdent::new(namespace.clone(), REVISIONS_TABLE_NAME.to_string());
@@ -722,21 +1010,23 @@ pub async fn ensure_form_tables(op: &Operator, ws_path: &str, form_def: &Value)
             .sort_order(SortOrder::unsorted_order())
             .properties(props)
             .build();
-        let created = catalog.create_table(&namespace, creation).await;
-        if let Err(err) = created {
-            let message = err.to_string();
-            if !message.contains("TableAlreadyExists") && !message.contains("already exists") {
-                return Err(err.into());
-            }
-            let props = table_properties(form_def)?;
-            let table = catalog.load_table(&revisions_ident).await?;
-            let tx = Transaction::new(&table);
-            let mut action = tx.update_table_properties();
-            for (key, value) in props {
-                action = action.set(key, value);
+        match catalog.create_table(&namespace, creation).await {
+            Ok(table) => publish_metadata_pointer(op, ws_path, &table).await?,
+            Err(err) => {
+                let message = err.to_string();
+                if !message.contains("TableAlreadyExists") && !message.contains("already exists") {
+                    return Err(err.into());
+                }
+                let props = table_properties(form_def)?;
+                let table = catalog.load_table(&revisions_ident).await?;
+                let tx = Transaction::new(&table);
+                let mut action = tx.update_table_properties();
+                for (key, value) in props {
+                    action = action.set(key, value);
+                }
+                let tx = action.apply(tx)?;
+                commit_table(op, ws_path, catalog.as_ref(), tx).await?;
             }
-            let tx = action.apply(tx)?;
-            tx.commit(catalog.as_ref()).await?;
         }
     } else {
         let props = table_properties(form_def)?;
@@ -747,7 +1037,7 @@ pub async fn ensure_form_tables(op: &Operator, ws_path: &str, form_def: &Value)
             action = action.set(key, value);
         }
         let tx = action.apply(tx)?;
-        tx.commit(catalog.as_ref()).await?;
+        commit_table(op, ws_path, catalog.as_ref(), tx).await?;
     }
 
     Ok(())
@@ -882,12 +1172,36 @@ pub async fn drop_form_tables(op: &Operator, ws_path: &str, form_name: &str) ->
         let _ = op.remove_all(&form_root).await;
     }
 
-    let warehouse = warehouse_uri(op, ws_path)?;
-    remove_catalog_cache(&warehouse)?;
-
     Ok(())
 }
 
+/// Fingerprint of every form's committed entries-table metadata. Any commit,
+/// from this process or another, publishes a new metadata file and changes it.
+pub async fn entries_generation(op: &Operator, ws_path: &str) -> Result<String> {
+    let mut form_names = list_form_names(op, ws_path).await?;
+    form_names.sort();
+    let mut hasher = Sha256::new();
+    for form_name in form_names {
+        let metadata_path = table_metadata_dir(ws_path, &form_name, ENTRIES_TABLE_NAME);
+        let pointer = format!("{}{}", metadata_path, METADATA_POINTER_FILE);
+        let current = match op.read(&pointer).await {
+            Ok(bytes) => bytes.to_vec(),
+            Err(err) if err.kind() == opendal::ErrorKind::NotFound => {
+                latest_metadata_file(op, &metadata_path)
+                    .await?
+                    .unwrap_or_default()
+                    .into_bytes()
+            }
+            Err(err) => return Err(err.into()),
+        };
+        hasher.update(form_name.as_bytes());
+        hasher.update([0]);
+        hasher.update(&current);
+        hasher.update([0]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
 pub async fn list_form_names(op: &Operator, ws_path: &str) -> Result<Vec<String>> {
     let catalog: Arc<MemoryCatalog> = catalog_for_space(op, ws_path).await?;
     let namespaces = catalog.list_namespaces(None).await?;
@@ -0,0 +1,1334 @@
+use crate::asset;
+use crate::asset_links::{
+    asset_ids_in, rewrite_asset_links, AssetLinkDirection, AssetLinkMap, AssetLinkMapping,
+};
+use crate::clock;
+use crate::entry::{self, RevisionNote};
+use crate::form;
+use crate::integrity::RealIntegrityProvider;
+use crate::metadata;
+use crate::runtime;
+use crate::space;
+use crate::storage;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use opendal::Operator;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read};
+use std::path::Path;
+use uuid::Uuid;
+
+pub const DEFAULT_IMPORT_FORM: &str = "Entry";
+const DEFAULT_IMPORT_AUTHOR: &str = "importer";
+const IMPORT_BODY_FIELD: &str = "Body";
+const IMPORT_JOBS_DIR: &str = "import_jobs";
+const IMPORT_JOB_FLUSH_INTERVAL: usize = 25;
+pub const DEFAULT_MAX_ARCHIVE_ENTRIES: usize = 50_000;
+pub const DEFAULT_MAX_ARCHIVE_ENTRY_BYTES: u64 = 100 * 1024 * 1024;
+pub const DEFAULT_MAX_ARCHIVE_TOTAL_BYTES: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSource {
+    Obsidian,
+    Notion,
+    Joplin,
+}
+
+impl ImportSource {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "obsidian" => Ok(ImportSource::Obsidian),
+            "notion" => Ok(ImportSource::Notion),
+            "joplin" => Ok(ImportSource::Joplin),
+            other => Err(anyhow!("Unknown import source: {}", other)),
+        }
+    }
+}
+
+/// How source folders are represented in the target space.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FolderMapping {
+    /// Every folder segment becomes a tag; all notes use the default form.
+    #[default]
+    Tags,
+    /// The top-level folder selects the form; deeper segments become tags.
+    Forms,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportOptions {
+    pub source: ImportSource,
+    #[serde(default)]
+    pub folder_mapping: FolderMapping,
+    #[serde(default = "default_import_form")]
+    pub default_form: String,
+    #[serde(default = "default_import_author")]
+    pub author: String,
+}
+
+impl ImportOptions {
+    pub fn new(source: ImportSource) -> Self {
+        Self {
+            source,
+            folder_mapping: FolderMapping::default(),
+            default_form: default_import_form(),
+            author: default_import_author(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportFile {
+    pub path: String,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFileStatus {
+    Imported,
+    Attached,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportFileReport {
+    pub path: String,
+    pub status: ImportFileStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved_links: Vec<String>,
+    /// Attachments the note's relative links were rewritten to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub asset_links: Vec<AssetLinkMapping>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportSummary {
+    pub space_id: String,
+    pub source: ImportSource,
+    pub entries_created: usize,
+    pub assets_created: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub forms: Vec<String>,
+    pub files: Vec<ImportFileReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportJob {
+    pub job_id: String,
+    pub space_id: String,
+    pub source: ImportSource,
+    pub status: ImportJobStatus,
+    pub status_message: Option<String>,
+    pub processed_files: usize,
+    pub total_files: usize,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+    pub summary: Option<ImportSummary>,
+}
+
+fn default_import_form() -> String {
+    DEFAULT_IMPORT_FORM.to_string()
+}
+
+fn default_import_author() -> String {
+    DEFAULT_IMPORT_AUTHOR.to_string()
+}
+
+/// Read every file of an Obsidian vault directory, skipping hidden folders such as `.obsidian`.
+pub fn read_obsidian_vault(root: &Path) -> Result<Vec<ImportFile>> {
+    let mut files = Vec::new();
+    collect_directory_files(root, root, &mut files)?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn collect_directory_files(root: &Path, dir: &Path, files: &mut Vec<ImportFile>) -> Result<()> {
+    let entries = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory {}", dir.display()))?;
+    for item in entries {
+        let item = item?;
+        let name = item.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = item.path();
+        let file_type = item.file_type()?;
+        if file_type.is_dir() {
+            collect_directory_files(root, &path, files)?;
+        } else if file_type.is_file() {
+            let relative = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push(ImportFile {
+                path: relative,
+                content: std::fs::read(&path)?,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Bounds applied while expanding an uploaded export archive, so a small zip
+/// or tar cannot inflate into unbounded memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveLimits {
+    /// Entries read across the archive, including those of nested archives.
+    pub max_entries: usize,
+    /// Uncompressed bytes read from any single entry.
+    pub max_entry_bytes: u64,
+    /// Uncompressed bytes read across the whole archive.
+    pub max_total_bytes: u64,
+}
+
+impl Default for ArchiveLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_MAX_ARCHIVE_ENTRIES,
+            max_entry_bytes: DEFAULT_MAX_ARCHIVE_ENTRY_BYTES,
+            max_total_bytes: DEFAULT_MAX_ARCHIVE_TOTAL_BYTES,
+        }
+    }
+}
+
+struct ArchiveBudget {
+    limits: ArchiveLimits,
+    entries: usize,
+    total_bytes: u64,
+}
+
+impl ArchiveBudget {
+    fn new(limits: &ArchiveLimits) -> Self {
+        Self {
+            limits: *limits,
+            entries: 0,
+            total_bytes: 0,
+        }
+    }
+
+    fn count_entry(&mut self) -> Result<()> {
+        self.entries += 1;
+        if self.entries > self.limits.max_entries {
+            return Err(anyhow!(
+                "Archive has more than {} entries",
+                self.limits.max_entries
+            ));
+        }
+        Ok(())
+    }
+
+    /// Read one entry within the remaining budget. `declared_size` comes from
+    /// the archive header and is only used to size the buffer.
+    fn read_entry(&mut self, path: &str, reader: impl Read, declared_size: u64) -> Result<Vec<u8>> {
+        let remaining = self.limits.max_total_bytes.saturating_sub(self.total_bytes);
+        let limit = self.limits.max_entry_bytes.min(remaining);
+        let capacity = usize::try_from(declared_size.min(limit)).unwrap_or(0);
+        let mut content = Vec::with_capacity(capacity);
+        reader
+            .take(limit.saturating_add(1))
+            .read_to_end(&mut content)?;
+        let read = content.len() as u64;
+        if read > self.limits.max_entry_bytes {
+            return Err(anyhow!(
+                "Archive entry {} exceeds {} bytes",
+                path,
+                self.limits.max_entry_bytes
+            ));
+        }
+        if read > limit {
+            return Err(anyhow!(
+                "Archive expands to more than {} bytes",
+                self.limits.max_total_bytes
+            ));
+        }
+        self.total_bytes += read;
+        Ok(content)
+    }
+}
+
+/// Read a Notion "Markdown & CSV" export zip. Nested part archives are expanded in place.
+pub fn read_notion_export(bytes: &[u8]) -> Result<Vec<ImportFile>> {
+    read_notion_export_with_limits(bytes, &ArchiveLimits::default())
+}
+
+/// [`read_notion_export`] with explicit expansion limits.
+pub fn read_notion_export_with_limits(
+    bytes: &[u8],
+    limits: &ArchiveLimits,
+) -> Result<Vec<ImportFile>> {
+    let mut files = Vec::new();
+    let mut budget = ArchiveBudget::new(limits);
+    collect_zip_files(bytes, &mut files, &mut budget, 0)?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+fn collect_zip_files(
+    bytes: &[u8],
+    files: &mut Vec<ImportFile>,
+    budget: &mut ArchiveBudget,
+    depth: usize,
+) -> Result<()> {
+    let mut archive =
+        zip::ZipArchive::new(Cursor::new(bytes)).context("Invalid Notion export archive")?;
+    for idx in 0..archive.len() {
+        budget.count_entry()?;
+        let file = archive.by_index(idx)?;
+        if file.is_dir() {
+            continue;
+        }
+        let Some(name) = file.enclosed_name() else {
+            continue;
+        };
+        let relative = name
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        let declared_size = file.size();
+        let content = budget.read_entry(&relative, file, declared_size)?;
+        if relative.to_ascii_lowercase().ends_with(".zip") && depth == 0 {
+            collect_zip_files(&content, files, budget, depth + 1)?;
+            continue;
+        }
+        files.push(ImportFile {
+            path: relative,
+            content,
+        });
+    }
+    Ok(())
+}
+
+/// Read a Joplin JEX export (a tar archive of serialized items and resources).
+pub fn read_joplin_export(bytes: &[u8]) -> Result<Vec<ImportFile>> {
+    read_joplin_export_with_limits(bytes, &ArchiveLimits::default())
+}
+
+/// [`read_joplin_export`] with explicit expansion limits.
+pub fn read_joplin_export_with_limits(
+    bytes: &[u8],
+    limits: &ArchiveLimits,
+) -> Result<Vec<ImportFile>> {
+    let mut archive = tar::Archive::new(Cursor::new(bytes));
+    let mut files = Vec::new();
+    let mut budget = ArchiveBudget::new(limits);
+    for item in archive.entries().context("Invalid Joplin export archive")? {
+        budget.count_entry()?;
+        let item = item?;
+        if !item.header().entry_type().is_file() {
+            continue;
+        }
+        let relative = {
+            let path = item.path()?;
+            if path
+                .components()
+                .any(|c| !matches!(c, std::path::Component::Normal(_)))
+            {
+                continue;
+            }
+            path.components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+        };
+        let declared_size = item.header().size().unwrap_or(0);
+        let content = budget.read_entry(&relative, item, declared_size)?;
+        files.push(ImportFile {
+            path: relative,
+            content,
+        });
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+struct SourceNote {
+    path: String,
+    base_dir: String,
+    title: String,
+    folders: Vec<String>,
+    tags: Vec<String>,
+    body: String,
+}
+
+struct SourceAttachment {
+    path: String,
+    key: String,
+    name: String,
+    content: Vec<u8>,
+}
+
+#[derive(Default)]
+struct SourceBundle {
+    notes: Vec<SourceNote>,
+    attachments: Vec<SourceAttachment>,
+    skipped: Vec<(String, String)>,
+    /// Lookup keys for notes keyed by their position in `notes`.
+    note_keys: Vec<(String, usize)>,
+    note_names: Vec<(String, usize)>,
+    attachment_names: Vec<(String, usize)>,
+}
+
+impl SourceBundle {
+    fn total_files(&self) -> usize {
+        self.notes.len() + self.attachments.len() + self.skipped.len()
+    }
+}
+
+fn parse_source(source: ImportSource, files: Vec<ImportFile>) -> Result<SourceBundle> {
+    match source {
+        ImportSource::Obsidian => Ok(parse_obsidian(files)),
+        ImportSource::Notion => Ok(parse_notion(files)),
+        ImportSource::Joplin => parse_joplin(files),
+    }
+}
+
+fn split_path(path: &str) -> (Vec<String>, String) {
+    let mut parts: Vec<String> = path
+        .split('/')
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect();
+    let file_name = parts.pop().unwrap_or_default();
+    (parts, file_name)
+}
+
+fn strip_md_extension(name: &str) -> &str {
+    if name.len() > 3 && name.as_bytes()[name.len() - 3..].eq_ignore_ascii_case(b".md") {
+        &name[..name.len() - 3]
+    } else {
+        name
+    }
+}
+
+fn is_markdown(path: &str) -> bool {
+    path.to_ascii_lowercase().ends_with(".md")
+}
+
+fn parse_obsidian(files: Vec<ImportFile>) -> SourceBundle {
+    let mut bundle = SourceBundle::default();
+    for file in files {
+        let (folders, file_name) = split_path(&file.path);
+        if is_markdown(&file.path) {
+            let raw = String::from_utf8_lossy(&file.content).to_string();
+            let (frontmatter, body) = split_frontmatter(&raw);
+            let stem = strip_md_extension(&file_name).to_string();
+            let idx = bundle.notes.len();
+            bundle
+                .note_keys
+                .push((link_key(strip_md_extension(&file.path)), idx));
+            bundle.note_names.push((link_key(&stem), idx));
+            bundle.notes.push(SourceNote {
+                path: file.path.clone(),
+                base_dir: folders.join("/"),
+                title: stem,
+                folders,
+                tags: frontmatter_tags(&frontmatter),
+                body,
+            });
+        } else {
+            let idx = bundle.attachments.len();
+            bundle.attachment_names.push((link_key(&file_name), idx));
+            bundle.attachments.push(SourceAttachment {
+                key: link_key(&file.path),
+                path: file.path,
+                name: file_name,
+                content: file.content,
+            });
+        }
+    }
+    bundle
+}
+
+fn notion_id_suffix() -> Regex {
+    Regex::new(r"\s+[0-9a-fA-F]{32}$").unwrap()
+}
+
+fn strip_notion_id(name: &str) -> String {
+    notion_id_suffix().replace(name, "").trim().to_string()
+}
+
+fn parse_notion(files: Vec<ImportFile>) -> SourceBundle {
+    let mut bundle = SourceBundle::default();
+    for file in files {
+        let (folders, file_name) = split_path(&file.path);
+        let lower = file.path.to_ascii_lowercase();
+        if lower.ends_with(".csv") {
+            bundle.skipped.push((
+                file.path,
+                "Notion database exports are not imported".to_string(),
+            ));
+        } else if is_markdown(&file.path) {
+            let raw = String::from_utf8_lossy(&file.content).to_string();
+            let fallback = strip_notion_id(strip_md_extension(&file_name));
+            let (title, body) = take_leading_heading(&raw, &fallback);
+            let idx = bundle.notes.len();
+            bundle
+                .note_keys
+                .push((link_key(strip_md_extension(&file.path)), idx));
+            bundle.notes.push(SourceNote {
+                path: file.path.clone(),
+                base_dir: folders.join("/"),
+                title,
+                folders: folders.iter().map(|f| strip_notion_id(f)).collect(),
+                tags: Vec::new(),
+                body,
+            });
+        } else {
+            bundle.attachments.push(SourceAttachment {
+                key: link_key(&file.path),
+                path: file.path,
+                name: file_name,
+                content: file.content,
+            });
+        }
+    }
+    bundle
+}
+
+struct JoplinItem {
+    path: String,
+    title: String,
+    body: String,
+    meta: HashMap<String, String>,
+}
+
+fn parse_joplin_item(path: &str, raw: &str) -> JoplinItem {
+    let meta_re = Regex::new(r"^([a-z_0-9]+): ?(.*)$").unwrap();
+    let lines: Vec<&str> = raw.trim_end().lines().collect();
+    let mut split_at = lines.len();
+    while split_at > 0 && meta_re.is_match(lines[split_at - 1]) {
+        split_at -= 1;
+    }
+    let mut meta = HashMap::new();
+    for line in &lines[split_at..] {
+        if let Some(caps) = meta_re.captures(line) {
+            meta.insert(caps[1].to_string(), caps[2].trim().to_string());
+        }
+    }
+    let content = &lines[..split_at];
+    let title = content.first().map(|l| l.trim()).unwrap_or("").to_string();
+    let body = content
+        .iter()
+        .skip(1)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    JoplinItem {
+        path: path.to_string(),
+        title,
+        body,
+        meta,
+    }
+}
+
+fn parse_joplin(files: Vec<ImportFile>) -> Result<SourceBundle> {
+    let mut bundle = SourceBundle::default();
+    let mut resource_files: HashMap<String, ImportFile> = HashMap::new();
+    let mut items = Vec::new();
+    for file in files {
+        let (folders, file_name) = split_path(&file.path);
+        if folders.first().map(String::as_str) == Some("resources") {
+            let id = file_name
+                .split('.')
+                .next()
+                .unwrap_or(&file_name)
+                .to_string();
+            resource_files.insert(id, file);
+        } else if is_markdown(&file.path) {
+            let raw = String::from_utf8_lossy(&file.content).to_string();
+            items.push(parse_joplin_item(&file.path, &raw));
+        } else {
+            bundle
+                .skipped
+                .push((file.path, "Unrecognized Joplin export file".to_string()));
+        }
+    }
+
+    let item_type = |item: &JoplinItem| item.meta.get("type_").cloned().unwrap_or_default();
+    let mut folders: HashMap<String, (String, String)> = HashMap::new();
+    let mut tags: HashMap<String, String> = HashMap::new();
+    let mut note_tags: HashMap<String, Vec<String>> = HashMap::new();
+    for item in &items {
+        let id = item.meta.get("id").cloned().unwrap_or_default();
+        match item_type(item).as_str() {
+            "2" => {
+                let parent = item.meta.get("parent_id").cloned().unwrap_or_default();
+                folders.insert(id, (item.title.clone(), parent));
+            }
+            "5" => {
+                tags.insert(id, item.title.clone());
+            }
+            _ => {}
+        }
+    }
+    for item in &items {
+        if item_type(item) == "6" {
+            if let (Some(note_id), Some(tag_id)) =
+                (item.meta.get("note_id"), item.meta.get("tag_id"))
+            {
+                if let Some(tag) = tags.get(tag_id) {
+                    note_tags
+                        .entry(note_id.clone())
+                        .or_default()
+                        .push(tag.clone());
+                }
+            }
+        }
+    }
+
+    let folder_chain = |parent: &str| -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut current = parent.to_string();
+        let mut seen = HashSet::new();
+        while let Some((title, next)) = folders.get(&current) {
+            if !seen.insert(current.clone()) {
+                break;
+            }
+            chain.push(title.clone());
+            current = next.clone();
+        }
+        chain.reverse();
+        chain
+    };
+
+    for item in items {
+        let id = item.meta.get("id").cloned().unwrap_or_default();
+        match item_type(&item).as_str() {
+            "1" => {
+                let parent = item.meta.get("parent_id").cloned().unwrap_or_default();
+                let idx = bundle.notes.len();
+                bundle.note_keys.push((link_key(&id), idx));
+                bundle.notes.push(SourceNote {
+                    path: item.path,
+                    base_dir: String::new(),
+                    title: item.title,
+                    folders: folder_chain(&parent),
+                    tags: note_tags.remove(&id).unwrap_or_default(),
+                    body: item.body,
+                });
+            }
+            "4" => match resource_files.remove(&id) {
+                Some(file) => {
+                    let name = if item.title.is_empty() {
+                        file.path.rsplit('/').next().unwrap_or(&id).to_string()
+                    } else {
+                        item.title.clone()
+                    };
+                    bundle.attachments.push(SourceAttachment {
+                        path: file.path,
+                        key: link_key(&id),
+                        name,
+                        content: file.content,
+                    });
+                }
+                None => bundle.skipped.push((
+                    item.path,
+                    format!("Joplin resource {} has no attached file", id),
+                )),
+            },
+            "2" => bundle
+                .skipped
+                .push((item.path, "Joplin notebook metadata".to_string())),
+            "5" | "6" => bundle
+                .skipped
+                .push((item.path, "Joplin tag metadata".to_string())),
+            other => bundle.skipped.push((
+                item.path,
+                format!("Unsupported Joplin item type: {}", other),
+            )),
+        }
+    }
+
+    let mut orphans: Vec<ImportFile> = resource_files.into_values().collect();
+    orphans.sort_by(|a, b| a.path.cmp(&b.path));
+    for file in orphans {
+        bundle.skipped.push((
+            file.path,
+            "Joplin resource file without metadata".to_string(),
+        ));
+    }
+    Ok(bundle)
+}
+
+fn split_frontmatter(raw: &str) -> (Value, String) {
+    let re = Regex::new(r"(?s)^---\s*\n(.*?)\n---\s*(\n|$)").unwrap();
+    if let Some(caps) = re.captures(raw) {
+        let yaml_str = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+        let fm = serde_yaml::from_str::<serde_yaml::Value>(yaml_str)
+            .ok()
+            .and_then(|y| serde_json::to_value(y).ok())
+            .unwrap_or(Value::Null);
+        let end = caps.get(0).map(|m| m.end()).unwrap_or(0);
+        return (fm, raw[end..].to_string());
+    }
+    (Value::Null, raw.to_string())
+}
+
+fn frontmatter_tags(frontmatter: &Value) -> Vec<String> {
+    match frontmatter.get("tags") {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        Some(Value::String(raw)) => raw
+            .split([',', ' '])
+            .filter(|t| !t.trim().is_empty())
+            .map(|t| t.trim().to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn take_leading_heading(raw: &str, fallback: &str) -> (String, String) {
+    let trimmed = raw.trim_start();
+    if let Some(rest) = trimmed.strip_prefix("# ") {
+        let (line, body) = rest.split_once('\n').unwrap_or((rest, ""));
+        let title = line.trim();
+        if !title.is_empty() {
+            return (title.to_string(), body.trim_start_matches('\n').to_string());
+        }
+    }
+    (fallback.to_string(), raw.to_string())
+}
+
+fn percent_decode(raw: &str) -> String {
+    let bytes = raw.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' && idx + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[idx + 1..idx + 3]).unwrap_or("");
+            if let Ok(value) = u8::from_str_radix(hex, 16) {
+                out.push(value);
+                idx += 3;
+                continue;
+            }
+        }
+        out.push(bytes[idx]);
+        idx += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for segment in path.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            other => parts.push(other),
+        }
+    }
+    parts.join("/")
+}
+
+fn link_key(path: &str) -> String {
+    normalize_path(&percent_decode(path)).to_lowercase()
+}
+
+struct LinkTable {
+    by_path: HashMap<String, String>,
+    by_name: HashMap<String, String>,
+}
+
+impl LinkTable {
+    fn resolve(&self, base_dir: &str, target: &str) -> Option<String> {
+        let target = target.split(['#', '?']).next().unwrap_or("").trim();
+        if target.is_empty() {
+            return None;
+        }
+        if let Some(id) = target.strip_prefix(":/") {
+            return self.by_path.get(&link_key(id)).cloned();
+        }
+        let decoded = percent_decode(target);
+        let relative = if base_dir.is_empty() {
+            decoded.clone()
+        } else {
+            format!("{}/{}", base_dir, decoded)
+        };
+        for candidate in [relative.as_str(), decoded.as_str()] {
+            let key = link_key(candidate);
+            if let Some(link) = self
+                .by_path
+                .get(&key)
+                .or_else(|| self.by_path.get(&link_key(strip_md_extension(&key))))
+            {
+                return Some(link.clone());
+            }
+        }
+        let name = decoded.rsplit(['/', '\\']).next().unwrap_or(&decoded);
+        self.by_name
+            .get(&link_key(name))
+            .or_else(|| self.by_name.get(&link_key(strip_md_extension(name))))
+            .cloned()
+    }
+}
+
+fn is_external_link(target: &str) -> bool {
+    let lower = target.to_ascii_lowercase();
+    lower.starts_with('#')
+        || lower.starts_with("mailto:")
+        || lower.starts_with("ugoite://")
+        || (lower.contains("://") && !lower.starts_with(":/"))
+}
+
+fn rewrite_links(
+    body: &str,
+    base_dir: &str,
+    table: &LinkTable,
+    wikilinks: bool,
+    unresolved: &mut Vec<String>,
+) -> String {
+    let markdown_re = Regex::new(r#"(!?)\[([^\]]*)\]\(<?([^)\s>]+)>?(\s+"[^"]*")?\)"#).unwrap();
+    let rewritten = markdown_re
+        .replace_all(body, |caps: &regex::Captures| {
+            let target = &caps[3];
+            if is_external_link(target) {
+                return caps[0].to_string();
+            }
+            match table.resolve(base_dir, target) {
+                Some(link) => format!("{}[{}]({})", &caps[1], &caps[2], link),
+                None => {
+                    unresolved.push(target.to_string());
+                    caps[0].to_string()
+                }
+            }
+        })
+        .to_string();
+    if !wikilinks {
+        return rewritten;
+    }
+
+    let wiki_re = Regex::new(r"(!?)\[\[([^\]|#]*)(#[^\]|]*)?(\|[^\]]*)?\]\]").unwrap();
+    wiki_re
+        .replace_all(&rewritten, |caps: &regex::Captures| {
+            let target = caps[2].trim();
+            let label = caps
+                .get(4)
+                .map(|m| m.as_str().trim_start_matches('|').trim().to_string())
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| target.to_string());
+            match table.resolve("", target) {
+                Some(link) => format!("{}[{}]({})", &caps[1], label, link),
+                None => {
+                    unresolved.push(target.to_string());
+                    caps[0].to_string()
+                }
+            }
+        })
+        .to_string()
+}
+
+/// Keep imported markdown inside the single body section by turning headings into bold
+/// lines and indenting `#`-prefixed lines inside fenced code blocks.
+pub(crate) fn sanitize_body(body: &str) -> String {
+    let heading_re = Regex::new(r"^#{1,6}\s+(.*)$").unwrap();
+    let mut in_fence = false;
+    let mut lines = Vec::new();
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            lines.push(line.to_string());
+            continue;
+        }
+        if line.starts_with('#') {
+            if in_fence {
+                lines.push(format!(" {}", line));
+            } else if let Some(caps) = heading_re.captures(line) {
+                let text = caps[1].trim().trim_end_matches('#').trim();
+                lines.push(format!("**{}**", text));
+            } else {
+                lines.push(format!("\\{}", line));
+            }
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+    lines.join("\n").trim().to_string()
+}
+
+fn normalize_tag(raw: &str) -> Option<String> {
+    let cleaned: String = raw
+        .trim()
+        .trim_start_matches('#')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+        .collect();
+    if cleaned.is_empty() {
+        None
+    } else {
+        Some(cleaned)
+    }
+}
+
+fn form_name_from_folder(folder: &str) -> Option<String> {
+    let name: String = folder
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if name.is_empty()
+        || name.starts_with(|c: char| c.is_ascii_digit())
+        || metadata::is_reserved_metadata_form(&name)
+    {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+fn placement(note: &SourceNote, options: &ImportOptions) -> (String, Vec<String>) {
+    let mut folders = note.folders.iter();
+    let form_name = match options.folder_mapping {
+        FolderMapping::Forms => note
+            .folders
+            .first()
+            .and_then(|f| form_name_from_folder(f))
+            .inspect(|_| {
+                folders.next();
+            }),
+        FolderMapping::Tags => None,
+    }
+    .unwrap_or_else(|| options.default_form.clone());
+
+    let mut tags = Vec::new();
+    let mut seen = HashSet::new();
+    for raw in folders.chain(note.tags.iter()) {
+        if let Some(tag) = normalize_tag(raw) {
+            if seen.insert(tag.to_lowercase()) {
+                tags.push(tag);
+            }
+        }
+    }
+    (form_name, tags)
+}
+
+fn import_form_definition(form_name: &str) -> Value {
+    json!({
+        "name": form_name,
+        "version": 1,
+        "fields": {
+            IMPORT_BODY_FIELD: {"type": "markdown", "required": false}
+        },
+        "allow_extra_attributes": "deny"
+    })
+}
+
+fn validate_options(options: &ImportOptions) -> Result<()> {
+    if options.default_form.trim().is_empty() {
+        return Err(anyhow!("default_form must not be empty"));
+    }
+    if metadata::is_reserved_metadata_form(&options.default_form) {
+        return Err(anyhow!(
+            "Form name '{}' is reserved for metadata forms",
+            options.default_form
+        ));
+    }
+    Ok(())
+}
+
+enum ImportProgress {
+    None,
+    Job(Box<ImportJobWriter>),
+}
+
+impl ImportProgress {
+    async fn report(&mut self, processed: usize, message: &str) -> Result<()> {
+        if let ImportProgress::Job(writer) = self {
+            writer.maybe_update(processed, message).await?;
+        }
+        Ok(())
+    }
+}
+
+async fn run_import(
+    op: &Operator,
+    ws_path: &str,
+    options: &ImportOptions,
+    bundle: SourceBundle,
+    progress: &mut ImportProgress,
+) -> Result<ImportSummary> {
+    let space_id = space::space_id_from_ws_path(ws_path);
+    let integrity = RealIntegrityProvider::from_space(op, &space_id).await?;
+    let mut reports = Vec::new();
+    let mut processed = 0usize;
+
+    for (path, message) in &bundle.skipped {
+        reports.push(ImportFileReport {
+            path: path.clone(),
+            status: ImportFileStatus::Skipped,
+            entry_id: None,
+            asset_id: None,
+            form: None,
+            unresolved_links: Vec::new(),
+            asset_links: Vec::new(),
+            message: Some(message.clone()),
+        });
+        processed += 1;
+    }
+    progress.report(processed, "Uploading attachments").await?;
+
+    let mut by_path = HashMap::new();
+    let mut by_name = HashMap::new();
+    let mut asset_refs: HashMap<String, Value> = HashMap::new();
+    let mut asset_map = AssetLinkMap::new();
+    let mut attachment_links: Vec<Option<String>> = Vec::new();
+    for attachment in &bundle.attachments {
+        match asset::save_asset(op, ws_path, &attachment.name, &attachment.content).await {
+            Ok(info) => {
+                by_path.insert(attachment.key.clone(), info.link.clone());
+                asset_map.insert(&info.id, &attachment.path);
+                asset_refs.insert(
+                    info.id.clone(),
+                    json!({"id": info.id, "name": info.name, "path": info.path}),
+                );
+                reports.push(ImportFileReport {
+                    path: attachment.path.clone(),
+                    status: ImportFileStatus::Attached,
+                    entry_id: None,
+                    asset_id: Some(info.id),
+                    form: None,
+                    unresolved_links: Vec::new(),
+                    asset_links: Vec::new(),
+                    message: None,
+                });
+                attachment_links.push(Some(info.link));
+            }
+            Err(err) => {
+                reports.push(ImportFileReport {
+                    path: attachment.path.clone(),
+                    status: ImportFileStatus::Failed,
+                    entry_id: None,
+                    asset_id: None,
+                    form: None,
+                    unresolved_links: Vec::new(),
+                    asset_links: Vec::new(),
+                    message: Some(err.to_string()),
+                });
+                attachment_links.push(None);
+            }
+        }
+        processed += 1;
+        progress.report(processed, "Uploading attachments").await?;
+    }
+    for (name, idx) in &bundle.attachment_names {
+        if let Some(Some(link)) = attachment_links.get(*idx) {
+            by_name.entry(name.clone()).or_insert_with(|| link.clone());
+        }
+    }
+
+    let entry_ids: Vec<String> = bundle
+        .notes
+        .iter()
+        .map(|_| Uuid::new_v4().to_string())
+        .collect();
+    for (key, idx) in &bundle.note_keys {
+        by_path
+            .entry(key.clone())
+            .or_insert_with(|| format!("ugoite://entry/{}", entry_ids[*idx]));
+    }
+    for (name, idx) in &bundle.note_names {
+        by_name
+            .entry(name.clone())
+            .or_insert_with(|| format!("ugoite://entry/{}", entry_ids[*idx]));
+    }
+    let table = LinkTable { by_path, by_name };
+
+    let mut existing_forms: HashSet<String> = form::list_form_names(op, ws_path)
+        .await?
+        .into_iter()
+        .collect();
+    let mut used_forms = Vec::new();
+    let wikilinks = options.source == ImportSource::Obsidian;
+
+    for (note, entry_id) in bundle.notes.iter().zip(entry_ids.iter()) {
+        let (form_name, tags) = placement(note, options);
+        let mut unresolved = Vec::new();
+        let attachments = rewrite_asset_links(
+            &note.body,
+            &note.base_dir,
+            &asset_map,
+            AssetLinkDirection::Import,
+        );
+        let body = rewrite_links(
+            &attachments.content,
+            &note.base_dir,
+            &table,
+            wikilinks,
+            &mut unresolved,
+        );
+        let body = sanitize_body(&body);
+        let referenced_assets: Vec<Value> = asset_ids_in(&body)
+            .iter()
+            .filter_map(|id| asset_refs.get(id).cloned())
+            .collect();
+
+        let outcome = async {
+            if !existing_forms.contains(&form_name) {
+                form::upsert_form(op, ws_path, &import_form_definition(&form_name)).await?;
+                existing_forms.insert(form_name.clone());
+            }
+            let title = note.title.lines().next().unwrap_or("").trim();
+            let title = if title.is_empty() {
+                entry_id.as_str()
+            } else {
+                title
+            };
+            let markdown = entry::render_markdown(
+                title,
+                &form_name,
+                &tags,
+                &json!({ IMPORT_BODY_FIELD: body }),
+                &[IMPORT_BODY_FIELD.to_string()],
+            );
+            entry::create_entry_with_assets(
+                op,
+                ws_path,
+                entry_id,
+                &markdown,
+                &options.author,
+                &RevisionNote::default(),
+                referenced_assets,
+                &integrity,
+            )
+            .await?;
+            Ok::<(), anyhow::Error>(())
+        }
+        .await;
+
+        match outcome {
+            Ok(()) => {
+                if !used_forms.contains(&form_name) {
+                    used_forms.push(form_name.clone());
+                }
+                reports.push(ImportFileReport {
+                    path: note.path.clone(),
+                    status: ImportFileStatus::Imported,
+                    entry_id: Some(entry_id.clone()),
+                    asset_id: None,
+                    form: Some(form_name),
+                    unresolved_links: unresolved,
+                    asset_links: attachments.report.mappings,
+                    message: None,
+                });
+            }
+            Err(err) => reports.push(ImportFileReport {
+                path: note.path.clone(),
+                status: ImportFileStatus::Failed,
+                entry_id: None,
+                asset_id: None,
+                form: Some(form_name),
+                unresolved_links: unresolved,
+                asset_links: Vec::new(),
+                message: Some(err.to_string()),
+            }),
+        }
+        processed += 1;
+        progress.report(processed, "Importing notes").await?;
+    }
+
+    reports.sort_by(|a, b| a.path.cmp(&b.path));
+    let count = |status: ImportFileStatus| reports.iter().filter(|r| r.status == status).count();
+    used_forms.sort();
+    Ok(ImportSummary {
+        space_id: space_id.to_string(),
+        source: options.source,
+        entries_created: count(ImportFileStatus::Imported),
+        assets_created: count(ImportFileStatus::Attached),
+        skipped: count(ImportFileStatus::Skipped),
+        failed: count(ImportFileStatus::Failed),
+        forms: used_forms,
+        files: reports,
+    })
+}
+
+/// Import already-extracted export files into an existing space and return a per-file summary.
+pub async fn import_files(
+    op: &Operator,
+    space_id: &str,
+    options: &ImportOptions,
+    files: Vec<ImportFile>,
+) -> Result<ImportSummary> {
+    validate_options(options)?;
+    if !space::space_exists(op, space_id).await? {
+        return Err(anyhow!("Space not found: {}", space_id));
+    }
+    let ws_path = format!("spaces/{}", space_id);
+    let bundle = parse_source(options.source, files)?;
+    run_import(op, &ws_path, options, bundle, &mut ImportProgress::None).await
+}
+
+fn validate_job_id(job_id: &str) -> Result<()> {
+    Uuid::parse_str(job_id)
+        .map_err(|e| anyhow!("Invalid job_id: {}. Must be a valid UUID. ({})", job_id, e))?;
+    Ok(())
+}
+
+fn job_path(space_id: &str, job_id: &str) -> String {
+    format!("spaces/{}/{}/{}.json", space_id, IMPORT_JOBS_DIR, job_id)
+}
+
+async fn write_job(op: &Operator, job: &ImportJob) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &job_path(&job.space_id, &job.job_id),
+        serde_json::to_vec_pretty(job)?,
+    )
+    .await?;
+    Ok(())
+}
+
+struct ImportJobWriter {
+    op: Operator,
+    job: ImportJob,
+    last_flushed: usize,
+}
+
+impl ImportJobWriter {
+    fn new(op: Operator, mut job: ImportJob) -> Self {
+        job.started_at = Some(clock::now());
+        Self {
+            op,
+            job,
+            last_flushed: 0,
+        }
+    }
+
+    async fn maybe_update(&mut self, processed: usize, message: &str) -> Result<()> {
+        let message_changed = self.job.status_message.as_deref() != Some(message);
+        if processed < self.job.total_files
+            && processed.saturating_sub(self.last_flushed) < IMPORT_JOB_FLUSH_INTERVAL
+            && !message_changed
+        {
+            return Ok(());
+        }
+        self.job.processed_files = processed;
+        self.job.status = ImportJobStatus::Running;
+        self.job.status_message = Some(message.to_string());
+        self.last_flushed = processed;
+        write_job(&self.op, &self.job).await
+    }
+
+    async fn complete(&mut self, summary: ImportSummary) -> Result<()> {
+        self.job.status = ImportJobStatus::Completed;
+        self.job.status_message = Some("Completed".to_string());
+        self.job.processed_files = self.job.total_files;
+        self.job.completed_at = Some(clock::now());
+        self.job.summary = Some(summary);
+        self.job.error = None;
+        write_job(&self.op, &self.job).await
+    }
+
+    async fn fail(&mut self, error: &str) -> Result<()> {
+        self.job.status = ImportJobStatus::Failed;
+        self.job.status_message = Some("Failed".to_string());
+        self.job.completed_at = Some(clock::now());
+        self.job.error = Some(error.to_string());
+        write_job(&self.op, &self.job).await
+    }
+}
+
+/// Queue an import in the background. Poll its progress with [`get_import_job`].
+pub async fn create_import_job(
+    op: &Operator,
+    space_id: &str,
+    options: &ImportOptions,
+    files: Vec<ImportFile>,
+) -> Result<ImportJob> {
+    validate_options(options)?;
+    if !space::space_exists(op, space_id).await? {
+        return Err(anyhow!("Space not found: {}", space_id));
+    }
+    let bundle = parse_source(options.source, files)?;
+    let job = ImportJob {
+        job_id: Uuid::new_v4().to_string(),
+        space_id: space_id.to_string(),
+        source: options.source,
+        status: ImportJobStatus::Queued,
+        status_message: Some("Queued".to_string()),
+        processed_files: 0,
+        total_files: bundle.total_files(),
+        started_at: None,
+        completed_at: None,
+        error: None,
+        summary: None,
+    };
+    let guard = runtime::begin_task("import_job")?;
+    write_job(op, &job).await?;
+
+    let op_clone = op.clone();
+    let options_clone = options.clone();
+    let ws_path = format!("spaces/{}", space_id);
+    let job_for_progress = job.clone();
+    tokio::spawn(async move {
+        let mut progress = ImportProgress::Job(Box::new(ImportJobWriter::new(
+            op_clone.clone(),
+            job_for_progress,
+        )));
+        let result = run_import(&op_clone, &ws_path, &options_clone, bundle, &mut progress).await;
+        let ok = result.is_ok();
+        if let ImportProgress::Job(writer) = &mut progress {
+            let _ = match result {
+                Ok(summary) => writer.complete(summary).await,
+                Err(err) => writer.fail(&err.to_string()).await,
+            };
+        }
+        guard.finish(ok);
+    });
+
+    Ok(job)
+}
+
+pub async fn get_import_job(op: &Operator, space_id: &str, job_id: &str) -> Result<ImportJob> {
+    validate_job_id(job_id)?;
+    let path = job_path(space_id, job_id);
+    if !op.exists(&path).await? {
+        return Err(anyhow!("Import job not found: {}", job_id));
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    Ok(serde_json::from_slice(&bytes)?)
+}
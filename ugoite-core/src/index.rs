@@ -3,18 +3,65 @@ use base64::Engine as _;
 use chrono::{DateTime, NaiveDate, NaiveTime, SecondsFormat, Timelike, Utc};
 use opendal::Operator;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use serde_yaml;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 pub use ugoite_minimum::text::compute_word_count;
 use uuid::Uuid;
 
-use crate::entry;
+use crate::collections;
+use crate::decimal::{self, DecimalSpec};
+use crate::entry::{self, DeletedFilter};
+use crate::entry_acl::{self, EntryAcl, EntryViewer};
+use crate::field_crypto;
+use crate::geo::{self, GeoPoint};
+use crate::iceberg_store;
+use crate::rollup;
+use crate::space_lock;
 use crate::sql;
+use crate::temporal;
 
 pub async fn query_index(op: &Operator, ws_path: &str, query: &str) -> Result<Vec<Value>> {
+    query_index_with_deleted(op, ws_path, query, DeletedFilter::Exclude).await
+}
+
+/// [`query_index`] over the entries admitted by `deleted`. Unless only live
+/// entries are queried, records carry `deleted`/`deleted_at`, which filter and
+/// SQL queries can match on.
+pub async fn query_index_with_deleted(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    deleted: DeletedFilter,
+) -> Result<Vec<Value>> {
+    run_index_query(op, ws_path, query, deleted, None).await
+}
+
+/// [`query_index_with_deleted`] limited to the entries `viewer` may read.
+///
+/// SQL queries are rewritten to filter every table on its `acl` column before
+/// joins run, so restricted entries cannot surface through joined rows either.
+pub async fn query_index_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    deleted: DeletedFilter,
+    viewer: &EntryViewer,
+) -> Result<Vec<Value>> {
+    run_index_query(op, ws_path, query, deleted, Some(viewer)).await
+}
+
+async fn run_index_query(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    deleted: DeletedFilter,
+    viewer: Option<&EntryViewer>,
+) -> Result<Vec<Value>> {
     let forms = load_forms(op, ws_path).await?;
-    let entries_map = collect_entries(op, ws_path, &forms).await?;
+    let entries_map = collect_entries(op, ws_path, &forms, deleted).await?;
+    let acl_tokens = viewer.and_then(EntryViewer::acl_tokens);
 
     let query_value = if query.trim().is_empty() {
         Value::Null
@@ -23,15 +70,31 @@ pub async fn query_index(op: &Operator, ws_path: &str, query: &str) -> Result<Ve
     };
 
     if let Some(sql_query) = extract_sql_query(&query_value) {
-        let parsed = sql::parse_sql(&sql_query)?;
+        let mut parsed = sql::parse_sql(&sql_query)?;
+        if let Some(tokens) = &acl_tokens {
+            sql::restrict_rows(&mut parsed, ACL_COLUMN, tokens);
+        }
         let tables = build_sql_tables(op, ws_path, &forms, &entries_map).await?;
         return sql::filter_entries_by_sql(&tables, &parsed);
     }
 
-    let filters: Option<Map<String, Value>> = query_value.as_object().cloned();
+    let mut filters: Option<Map<String, Value>> = query_value.as_object().cloned();
+    let collection_scope = take_collection_scope(op, ws_path, filters.as_mut()).await?;
 
     let mut results = Vec::new();
     for entry in entries_map.values() {
+        if acl_tokens
+            .as_ref()
+            .is_some_and(|tokens| !acl_admits(entry, tokens))
+        {
+            continue;
+        }
+        if let Some(scope) = collection_scope.as_ref() {
+            let entry_id = entry.get("id").and_then(Value::as_str).unwrap_or_default();
+            if !scope.admits(entry_id) {
+                continue;
+            }
+        }
         if let Some(filter_obj) = filters.as_ref() {
             if !matches_filters(entry, filter_obj)? {
                 continue;
@@ -43,16 +106,212 @@ pub async fn query_index(op: &Operator, ws_path: &str, query: &str) -> Result<Ve
     Ok(results)
 }
 
+/// Index column listing the principals that may read an entry; see
+/// [`entry_acl::acl_readers`].
+pub const ACL_COLUMN: &str = "acl";
+
+fn acl_admits(record: &Value, tokens: &[String]) -> bool {
+    record
+        .get(ACL_COLUMN)
+        .and_then(Value::as_array)
+        .is_some_and(|readers| {
+            readers
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|reader| tokens.iter().any(|token| token == reader))
+        })
+}
+
+/// Largest page a single `query_index_page` call may return.
+pub const MAX_QUERY_PAGE: usize = 1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QueryIndexPage {
+    pub rows: Vec<Value>,
+    /// Opaque cursor to pass for the next page.
+    pub cursor: String,
+    pub has_more: bool,
+}
+
+enum QueryCursor {
+    /// Filter queries resume after the last entry id, in id order.
+    After(String),
+    /// SQL results are paged by offset and only valid for the entries
+    /// generation they were computed at.
+    Offset { offset: usize, generation: String },
+}
+
+fn decode_query_cursor(cursor: &str) -> Result<QueryCursor> {
+    let invalid = || anyhow!("Invalid query cursor: {}", cursor);
+    if let Some(entry_id) = cursor.strip_prefix("id:") {
+        return Ok(QueryCursor::After(entry_id.to_string()));
+    }
+    let (offset, generation) = cursor
+        .strip_prefix("sql:")
+        .and_then(|rest| rest.split_once(':'))
+        .ok_or_else(invalid)?;
+    Ok(QueryCursor::Offset {
+        offset: offset.parse().map_err(|_| invalid())?,
+        generation: generation.to_string(),
+    })
+}
+
+/// One page of [`query_index`] results, so callers never hold the whole result.
+///
+/// Filter queries stream entry rows and keep only the page being built; SQL
+/// queries are evaluated in full and sliced, and their cursors expire once the
+/// space changes.
+pub async fn query_index_page(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    cursor: Option<&str>,
+    limit: usize,
+) -> Result<QueryIndexPage> {
+    run_index_page(op, ws_path, query, cursor, limit, None).await
+}
+
+/// [`query_index_page`] limited to the entries `viewer` may read, with the
+/// same row filtering as [`query_index_for_viewer`].
+pub async fn query_index_page_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    cursor: Option<&str>,
+    limit: usize,
+    viewer: &EntryViewer,
+) -> Result<QueryIndexPage> {
+    run_index_page(op, ws_path, query, cursor, limit, Some(viewer)).await
+}
+
+async fn run_index_page(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    cursor: Option<&str>,
+    limit: usize,
+    viewer: Option<&EntryViewer>,
+) -> Result<QueryIndexPage> {
+    if limit == 0 || limit > MAX_QUERY_PAGE {
+        return Err(anyhow!(
+            "Query page size must be between 1 and {}",
+            MAX_QUERY_PAGE
+        ));
+    }
+    let query_value = if query.trim().is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_str(query).unwrap_or(Value::Null)
+    };
+    let decoded = cursor.map(decode_query_cursor).transpose()?;
+
+    if extract_sql_query(&query_value).is_some() {
+        let generation = iceberg_store::entries_generation(op, ws_path).await?;
+        let offset = match decoded {
+            None => 0,
+            Some(QueryCursor::Offset {
+                offset,
+                generation: cursor_generation,
+            }) if cursor_generation == generation => offset,
+            Some(QueryCursor::Offset { .. }) => {
+                return Err(anyhow!(
+                    "Query cursor is stale because the space changed; restart the query"
+                ))
+            }
+            Some(QueryCursor::After(_)) => {
+                return Err(anyhow!("Invalid query cursor for a SQL query"))
+            }
+        };
+        let results = run_index_query(op, ws_path, query, DeletedFilter::Exclude, viewer).await?;
+        let has_more = results.len() > offset.saturating_add(limit);
+        let rows: Vec<Value> = results.into_iter().skip(offset).take(limit).collect();
+        return Ok(QueryIndexPage {
+            cursor: format!("sql:{}:{}", offset + rows.len(), generation),
+            rows,
+            has_more,
+        });
+    }
+
+    let after = match decoded {
+        None => None,
+        Some(QueryCursor::After(entry_id)) => Some(entry_id),
+        Some(QueryCursor::Offset { .. }) => {
+            return Err(anyhow!("Invalid query cursor for a filter query"))
+        }
+    };
+    let mut filters: Option<Map<String, Value>> = query_value.as_object().cloned();
+    let collection_scope = take_collection_scope(op, ws_path, filters.as_mut()).await?;
+    let forms = load_forms(op, ws_path).await?;
+    let acls = entry_acl::list_entry_acls(op, ws_path).await?;
+    let acl_tokens = viewer.and_then(EntryViewer::acl_tokens);
+    // Holds at most one row beyond the page, to report `has_more`.
+    let mut page: BTreeMap<String, Value> = BTreeMap::new();
+    for (form_name, row) in entry::list_entry_rows(op, ws_path).await? {
+        if after.as_ref().is_some_and(|after| &row.entry_id <= after) {
+            continue;
+        }
+        if collection_scope
+            .as_ref()
+            .is_some_and(|scope| !scope.admits(&row.entry_id))
+        {
+            continue;
+        }
+        if page.len() > limit
+            && page
+                .last_key_value()
+                .is_some_and(|(last, _)| &row.entry_id >= last)
+        {
+            continue;
+        }
+        let Some(record) = build_record(
+            ws_path,
+            &form_name,
+            &row,
+            &forms,
+            &acls,
+            DeletedFilter::Exclude,
+        )
+        .await?
+        else {
+            continue;
+        };
+        if acl_tokens
+            .as_ref()
+            .is_some_and(|tokens| !acl_admits(&record, tokens))
+        {
+            continue;
+        }
+        if let Some(filter_obj) = filters.as_ref() {
+            if !matches_filters(&record, filter_obj)? {
+                continue;
+            }
+        }
+        page.insert(row.entry_id, record);
+        if page.len() > limit + 1 {
+            page.pop_last();
+        }
+    }
+    let has_more = page.len() > limit;
+    if has_more {
+        page.pop_last();
+    }
+    let cursor = match page.last_key_value() {
+        Some((entry_id, _)) => format!("id:{entry_id}"),
+        None => cursor.unwrap_or("id:").to_string(),
+    };
+    Ok(QueryIndexPage {
+        rows: page.into_values().collect(),
+        cursor,
+        has_more,
+    })
+}
+
 pub async fn execute_sql_query(
     op: &Operator,
     ws_path: &str,
     sql_query: &str,
 ) -> Result<Vec<Value>> {
-    let forms = load_forms(op, ws_path).await?;
-    let entries_map = collect_entries(op, ws_path, &forms).await?;
-    let parsed = sql::parse_sql(sql_query)?;
-    let tables = build_sql_tables(op, ws_path, &forms, &entries_map).await?;
-    sql::filter_entries_by_sql(&tables, &parsed)
+    execute_sql_query_with_limits(op, ws_path, sql_query, None, sql::SqlLimits::default()).await
 }
 
 pub async fn execute_sql_query_scoped(
@@ -62,13 +321,174 @@ pub async fn execute_sql_query_scoped(
     readable_forms: &[String],
     include_untyped_entries: bool,
 ) -> Result<Vec<Value>> {
-    let forms = load_forms(op, ws_path).await?;
-    let entries_map = collect_entries(op, ws_path, &forms).await?;
-    let scoped_entries =
-        filter_entries_for_sql_scope(entries_map, readable_forms, include_untyped_entries);
+    execute_sql_query_with_limits(
+        op,
+        ws_path,
+        sql_query,
+        Some((readable_forms, include_untyped_entries)),
+        sql::SqlLimits::default(),
+    )
+    .await
+}
+
+/// Run `sql_query`, optionally within `(readable_forms, include_untyped_entries)`,
+/// failing with [`sql::SqlLimitExceeded`] once it runs past `limits`.
+///
+/// The timeout covers loading the tables as well as evaluating the query.
+pub async fn execute_sql_query_with_limits(
+    op: &Operator,
+    ws_path: &str,
+    sql_query: &str,
+    scope: Option<(&[String], bool)>,
+    limits: sql::SqlLimits,
+) -> Result<Vec<Value>> {
+    execute_sql_query_for_viewer(op, ws_path, sql_query, scope, None, limits).await
+}
+
+/// [`execute_sql_query_with_limits`] that, given a `viewer`, only reads rows
+/// whose entry ACL admits it.
+pub async fn execute_sql_query_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    sql_query: &str,
+    scope: Option<(&[String], bool)>,
+    viewer: Option<&EntryViewer>,
+    limits: sql::SqlLimits,
+) -> Result<Vec<Value>> {
+    let mut parsed = sql::parse_sql(sql_query)?;
+    if let Some(tokens) = viewer.and_then(EntryViewer::acl_tokens) {
+        sql::restrict_rows(&mut parsed, ACL_COLUMN, &tokens);
+    }
+    let started = std::time::Instant::now();
+    let load = async {
+        let forms = load_forms(op, ws_path).await?;
+        let mut entries_map = collect_entries(op, ws_path, &forms, DeletedFilter::Exclude).await?;
+        if let Some((readable_forms, include_untyped_entries)) = scope {
+            entries_map =
+                filter_entries_for_sql_scope(entries_map, readable_forms, include_untyped_entries);
+        }
+        build_sql_tables(op, ws_path, &forms, &entries_map).await
+    };
+    let tables = match limits.timeout {
+        Some(timeout) => tokio::time::timeout(timeout, load).await.map_err(|_| {
+            anyhow::Error::new(sql::SqlLimitExceeded {
+                kind: sql::SqlLimitKind::Timeout,
+                limit: timeout.as_millis() as u64,
+            })
+        })??,
+        None => load.await?,
+    };
+    let remaining = sql::SqlLimits {
+        timeout: limits
+            .timeout
+            .map(|timeout| timeout.saturating_sub(started.elapsed())),
+        ..limits
+    };
+    sql::filter_entries_by_sql_with_limits(&tables, &parsed, remaining)
+}
+
+/// Typed columns of the rows `execute_sql_query` returns for `sql_query`.
+///
+/// Property columns take their types from the form schemas; with
+/// `readable_forms`, only those forms contribute to the `entries` table.
+pub async fn sql_result_columns(
+    op: &Operator,
+    ws_path: &str,
+    sql_query: &str,
+    readable_forms: Option<&[String]>,
+) -> Result<Vec<sql::SqlColumn>> {
     let parsed = sql::parse_sql(sql_query)?;
-    let tables = build_sql_tables(op, ws_path, &forms, &scoped_entries).await?;
-    sql::filter_entries_by_sql(&tables, &parsed)
+    let forms = load_forms(op, ws_path).await?;
+    let readable: Option<HashSet<String>> =
+        readable_forms.map(|forms| forms.iter().map(|name| name.to_lowercase()).collect());
+    let mut form_names: Vec<&String> = forms
+        .keys()
+        .filter(|name| {
+            readable
+                .as_ref()
+                .is_none_or(|readable| readable.contains(&name.to_lowercase()))
+        })
+        .collect();
+    form_names.sort();
+
+    let mut tables: HashMap<String, Vec<sql::SqlColumn>> = HashMap::new();
+    let mut all_properties: Vec<sql::SqlColumn> = Vec::new();
+    for form_name in form_names {
+        let properties = property_columns(&forms[form_name])?;
+        for column in &properties {
+            match all_properties
+                .iter_mut()
+                .find(|seen| seen.name == column.name)
+            {
+                Some(seen) if seen.column_type != column.column_type => {
+                    // Forms disagree on the field's type; fall back to text.
+                    seen.column_type = sql::SqlColumnType::String;
+                }
+                Some(_) => {}
+                None => all_properties.push(column.clone()),
+            }
+        }
+        tables.insert(form_name.to_lowercase(), entry_record_columns(properties));
+    }
+    tables.insert("entries".to_string(), entry_record_columns(all_properties));
+    let text_columns = |names: &[&str]| {
+        names
+            .iter()
+            .map(|name| sql::SqlColumn::new(*name, sql::SqlColumnType::String))
+            .chain([sql::SqlColumn::new(ACL_COLUMN, sql::SqlColumnType::List)])
+            .collect::<Vec<_>>()
+    };
+    tables.insert(
+        "links".to_string(),
+        text_columns(&[
+            "id",
+            "source",
+            "target",
+            "kind",
+            "source_form",
+            "target_form",
+        ]),
+    );
+    tables.insert(
+        "assets".to_string(),
+        text_columns(&["id", "entry_id", "name", "path"]),
+    );
+    sql::result_columns(&tables, &parsed)
+}
+
+fn property_columns(form_def: &Value) -> Result<Vec<sql::SqlColumn>> {
+    Ok(iceberg_store::form_field_defs(form_def)?
+        .into_iter()
+        .map(|(name, field_type, _required)| {
+            sql::SqlColumn::new(
+                format!("properties.{name}"),
+                sql::SqlColumnType::from_field_type(&field_type),
+            )
+        })
+        .collect())
+}
+
+/// Columns of an index record as built by `build_record`.
+fn entry_record_columns(properties: Vec<sql::SqlColumn>) -> Vec<sql::SqlColumn> {
+    use sql::SqlColumnType::{Float, Int, List, String};
+    let mut columns = vec![
+        sql::SqlColumn::new("id", String),
+        sql::SqlColumn::new("title", String),
+        sql::SqlColumn::new("form", String),
+        sql::SqlColumn::new("updated_at", Float),
+        sql::SqlColumn::new("space_id", String),
+    ];
+    columns.extend(properties);
+    columns.extend([
+        sql::SqlColumn::new("word_count", Int),
+        sql::SqlColumn::new("tags", List),
+        sql::SqlColumn::new("links", List),
+        sql::SqlColumn::new("assets", List),
+        sql::SqlColumn::new("checksum", String),
+        sql::SqlColumn::new("validation_warnings", List),
+        sql::SqlColumn::new(ACL_COLUMN, List),
+    ]);
+    columns
 }
 
 fn extract_sql_query(value: &Value) -> Option<String> {
@@ -83,6 +503,20 @@ fn extract_sql_query(value: &Value) -> Option<String> {
     }
 }
 
+/// Remove the `collection` key from `filters`, resolving it to the entries it admits.
+async fn take_collection_scope(
+    op: &Operator,
+    ws_path: &str,
+    filters: Option<&mut Map<String, Value>>,
+) -> Result<Option<collections::CollectionScope>> {
+    match filters.and_then(|filters| filters.remove("collection")) {
+        Some(filter) => Ok(Some(
+            collections::CollectionScope::load(op, ws_path, &filter).await?,
+        )),
+        None => Ok(None),
+    }
+}
+
 fn matches_filters(entry: &Value, filters: &Map<String, Value>) -> Result<bool> {
     for (key, expected) in filters {
         let mut entry_value = entry.get(key).cloned();
@@ -94,10 +528,20 @@ fn matches_filters(entry: &Value, filters: &Map<String, Value>) -> Result<bool>
                 .cloned();
         }
 
-        if expected.is_object() {
-            return Err(anyhow!(
-                "Structured operators (e.g., $gt) are not implemented for the local query helper yet."
-            ));
+        if let Some(filter) = expected.as_object() {
+            match geo::matches_filter(entry_value.as_ref(), filter) {
+                Some(matched) => {
+                    if !matched? {
+                        return Ok(false);
+                    }
+                    continue;
+                }
+                None => {
+                    return Err(anyhow!(
+                        "Structured operators (e.g., $gt) are not implemented for the local query helper yet."
+                    ))
+                }
+            }
         }
 
         if key == "tag" {
@@ -116,7 +560,10 @@ fn matches_filters(entry: &Value, filters: &Map<String, Value>) -> Result<bool>
                 }
             }
             Some(value) => {
-                if value != *expected {
+                let same_value = temporal::compare_instants(&value, expected)
+                    .or_else(|| decimal::compare_decimals(&value, expected))
+                    == Some(std::cmp::Ordering::Equal);
+                if value != *expected && !same_value {
                     return Ok(false);
                 }
             }
@@ -126,18 +573,25 @@ fn matches_filters(entry: &Value, filters: &Map<String, Value>) -> Result<bool>
     Ok(true)
 }
 
+/// Rebuild derived index state; concurrent rebuilds of a space queue on its
+/// index lease.
 pub async fn reindex_all(op: &Operator, ws_path: &str) -> Result<()> {
-    let _ = op;
-    let _ = ws_path;
-    Ok(())
+    space_lock::with_space_lease(op, ws_path, space_lock::INDEX_LEASE, |_| async {
+        // Queries read the Iceberg tables directly; only rollups are derived.
+        rollup::rebuild_rollups(op, ws_path).await
+    })
+    .await
 }
 
 pub async fn get_space_stats(op: &Operator, ws_path: &str) -> Result<Value> {
     let forms = load_forms(op, ws_path).await?;
-    let entries = collect_entries(op, ws_path, &forms).await?;
+    let entries = collect_entries(op, ws_path, &forms, DeletedFilter::Exclude).await?;
     Ok(aggregate_stats(&entries))
 }
 
+/// Kept for API compatibility: queries, SQL and stats read the Iceberg tables
+/// directly and rollups are updated as entry rows are written, so there is
+/// nothing left to update or batch here.
 pub async fn update_entry_index(op: &Operator, ws_path: &str, entry_id: &str) -> Result<()> {
     let _ = op;
     let _ = ws_path;
@@ -405,6 +859,11 @@ pub fn validate_properties(properties: &Value, entry_form: &Value) -> Result<(Va
                 _ => None,
             }
             .map(Value::Number),
+            "geopoint" => GeoPoint::from_value(&raw_value).map(GeoPoint::to_value),
+            "decimal" => DecimalSpec::from_field_def(&field_def)
+                .ok()
+                .and_then(|spec| spec.cast(&raw_value))
+                .map(Value::String),
             "date" => match raw_value {
                 Value::String(ref s) => NaiveDate::parse_from_str(s, "%Y-%m-%d")
                     .ok()
@@ -423,6 +882,15 @@ pub fn validate_properties(properties: &Value, entry_form: &Value) -> Result<(Va
                 Value::String(ref s) => normalize_timestamp(s).map(Value::String),
                 _ => None,
             },
+            "datetime" => match raw_value {
+                Value::String(ref s) => {
+                    temporal::normalize_datetime(s, temporal::field_offset(entry_form, &field_def))
+                        .map(Value::String)
+                }
+                _ => None,
+            },
+            "duration" => temporal::parse_duration_secs(&raw_value)
+                .map(|secs| Value::Number(serde_json::Number::from(secs))),
             "timestamp_ns" => match raw_value {
                 Value::String(ref s) => normalize_timestamp_ns(s).map(Value::String),
                 _ => None,
@@ -560,11 +1028,14 @@ async fn collect_entries(
     op: &Operator,
     ws_path: &str,
     forms: &HashMap<String, Value>,
+    deleted: DeletedFilter,
 ) -> Result<Map<String, Value>> {
     let mut entries = Map::new();
     let rows = entry::list_entry_rows(op, ws_path).await?;
+    let acls = entry_acl::list_entry_acls(op, ws_path).await?;
     for (form_name, row) in rows {
-        if let Some(record) = build_record(ws_path, &form_name, &row, forms).await? {
+        if let Some(record) = build_record(ws_path, &form_name, &row, forms, &acls, deleted).await?
+        {
             entries.insert(row.entry_id.clone(), record);
         }
     }
@@ -597,13 +1068,16 @@ async fn build_record(
     form_name: &str,
     row: &entry::EntryRow,
     forms: &HashMap<String, Value>,
+    acls: &HashMap<String, EntryAcl>,
+    deleted: DeletedFilter,
 ) -> Result<Option<Value>> {
-    if row.deleted {
+    if !deleted.admits(row.deleted) {
         return Ok(None);
     }
 
     let mut warnings = Vec::new();
     let mut properties = entry::merge_entry_fields(&row.fields, &row.extra_attributes);
+    field_crypto::index_fields(&mut properties);
     if let Some(form_def) = forms.get(form_name) {
         if let Ok((casted, warns)) = validate_properties(&properties, form_def) {
             properties = casted;
@@ -612,7 +1086,7 @@ async fn build_record(
     }
 
     let word_count = compute_word_count(&serde_json::to_string(&properties)?);
-    let record = serde_json::json!({
+    let mut record = serde_json::json!({
         "id": row.entry_id,
         "title": row.title,
         "form": form_name,
@@ -625,7 +1099,9 @@ async fn build_record(
         "assets": row.assets,
         "checksum": row.integrity.checksum,
         "validation_warnings": Value::Array(warnings),
+        ACL_COLUMN: entry_acl::acl_readers(acls.get(&row.entry_id)),
     });
+    deleted.annotate(&mut record, row);
 
     Ok(Some(record))
 }
@@ -669,7 +1145,7 @@ async fn build_sql_tables(
     let entry_rows = entry::list_entry_rows(op, ws_path).await?;
     let mut link_rows = Vec::new();
     for (_form_name, row) in entry_rows {
-        if row.deleted || !visible_entry_ids.contains(&row.entry_id) {
+        if !visible_entry_ids.contains(&row.entry_id) {
             continue;
         }
         for link_item in row.links {
@@ -679,6 +1155,8 @@ async fn build_sql_tables(
                 continue;
             }
             let source_form = entry_form_map.get(&link_item.source).cloned();
+            // A link is as visible as the entry that carries it.
+            let acl = entries_map[&link_item.source][ACL_COLUMN].clone();
             let target_form = entry_form_map.get(&link_item.target).cloned();
             link_rows.push(serde_json::json!({
                 "id": link_item.id,
@@ -687,6 +1165,7 @@ async fn build_sql_tables(
                 "kind": link_item.kind,
                 "source_form": source_form,
                 "target_form": target_form,
+                ACL_COLUMN: acl,
             }));
         }
         for asset in row.assets {
@@ -696,6 +1175,7 @@ async fn build_sql_tables(
                     "entry_id": row.entry_id,
                     "name": obj.get("name").cloned().unwrap_or(Value::Null),
                     "path": obj.get("path").cloned().unwrap_or(Value::Null),
+                    ACL_COLUMN: entries_map[&row.entry_id][ACL_COLUMN].clone(),
                 }));
             }
         }
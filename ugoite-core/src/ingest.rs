@@ -0,0 +1,799 @@
+use crate::asset;
+use crate::entry::{self, RevisionNote};
+use crate::form;
+use crate::importers;
+use crate::integrity::RealIntegrityProvider;
+use crate::metadata;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine as _};
+use opendal::Operator;
+use regex::Regex;
+use scraper::{ElementRef, Html, Node};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use url::Url;
+use uuid::Uuid;
+
+const DEFAULT_CLIP_FORM: &str = "Entry";
+const DEFAULT_CLIP_FIELD: &str = "Body";
+const DEFAULT_CLIP_AUTHOR: &str = "clipper";
+pub const DEFAULT_MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+const MAX_CLIP_IMAGES: usize = 50;
+const CLIP_IMAGE_TIMEOUT_SECS: u64 = 20;
+/// Minimum text length for an `<article>`/`<main>` element to be trusted as the content root.
+const SEMANTIC_ROOT_MIN_CHARS: usize = 140;
+
+/// Elements that never contribute article content.
+const SKIP_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "iframe", "object", "embed", "form", "input",
+    "button", "select", "textarea", "nav", "aside", "footer", "svg", "canvas", "head", "link",
+    "meta",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClipOptions {
+    #[serde(default = "default_clip_form")]
+    pub form: String,
+    /// Markdown field of `form` that receives the article body.
+    #[serde(default = "default_clip_field")]
+    pub field: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default = "default_download_images")]
+    pub download_images: bool,
+    #[serde(default = "default_max_image_bytes")]
+    pub max_image_bytes: usize,
+    #[serde(default = "default_clip_author")]
+    pub author: String,
+}
+
+impl Default for ClipOptions {
+    fn default() -> Self {
+        Self {
+            form: default_clip_form(),
+            field: default_clip_field(),
+            title: None,
+            tags: Vec::new(),
+            download_images: default_download_images(),
+            max_image_bytes: default_max_image_bytes(),
+            author: default_clip_author(),
+        }
+    }
+}
+
+fn default_clip_form() -> String {
+    DEFAULT_CLIP_FORM.to_string()
+}
+
+fn default_clip_field() -> String {
+    DEFAULT_CLIP_FIELD.to_string()
+}
+
+fn default_download_images() -> bool {
+    true
+}
+
+fn default_max_image_bytes() -> usize {
+    DEFAULT_MAX_IMAGE_BYTES
+}
+
+fn default_clip_author() -> String {
+    DEFAULT_CLIP_AUTHOR.to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClipResult {
+    pub entry_id: String,
+    pub title: String,
+    pub source_url: String,
+    pub assets: Vec<asset::AssetInfo>,
+    /// Image URLs that could not be downloaded; they stay linked remotely.
+    pub failed_images: Vec<String>,
+}
+
+/// Downloads images referenced by clipped pages.
+#[async_trait]
+pub trait ImageFetcher: Send + Sync {
+    async fn fetch(&self, url: &str, max_bytes: usize) -> Result<Vec<u8>>;
+}
+
+/// Fetches clipped images through the guarded download in [`asset`]: only
+/// public addresses, every redirect re-checked and an `image/*` content type
+/// required.
+pub struct HttpImageFetcher {
+    options: asset::UrlAssetOptions,
+}
+
+impl HttpImageFetcher {
+    pub fn new() -> Self {
+        Self::with_options(asset::UrlAssetOptions {
+            timeout_secs: CLIP_IMAGE_TIMEOUT_SECS,
+            ..asset::UrlAssetOptions::default()
+        })
+    }
+
+    /// Use `options` for host lists, network guard and timeout; the content
+    /// types are always restricted to images.
+    pub fn with_options(options: asset::UrlAssetOptions) -> Self {
+        Self {
+            options: asset::UrlAssetOptions {
+                content_types: vec!["image/*".to_string()],
+                ..options
+            },
+        }
+    }
+}
+
+impl Default for HttpImageFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ImageFetcher for HttpImageFetcher {
+    async fn fetch(&self, url: &str, max_bytes: usize) -> Result<Vec<u8>> {
+        let options = asset::UrlAssetOptions {
+            max_bytes,
+            ..self.options.clone()
+        };
+        Ok(asset::download(url, &options).await?.bytes)
+    }
+}
+
+struct ClipImage {
+    url: String,
+    alt: String,
+}
+
+struct ExtractedPage {
+    title: String,
+    markdown: String,
+    images: Vec<ClipImage>,
+}
+
+fn image_placeholder(index: usize) -> String {
+    format!("\u{0}IMG{}\u{0}", index)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '*' | '_' | '`' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+fn class_and_id(element: &ElementRef) -> String {
+    format!(
+        "{} {}",
+        element.value().attr("class").unwrap_or(""),
+        element.value().attr("id").unwrap_or("")
+    )
+    .to_lowercase()
+}
+
+fn negative_pattern() -> Regex {
+    Regex::new(
+        r"comment|sidebar|footer|footnote|masthead|menu|nav|share|social|sponsor|promo|advert|related|popup|cookie|banner|subscribe|newsletter|\bad\b|\bads\b",
+    )
+    .unwrap()
+}
+
+fn positive_pattern() -> Regex {
+    Regex::new(r"article|body|content|entry|main|page|post|story|text|blog").unwrap()
+}
+
+fn is_hidden(element: &ElementRef) -> bool {
+    let value = element.value();
+    value.attr("hidden").is_some()
+        || value.attr("aria-hidden") == Some("true")
+        || value
+            .attr("style")
+            .map(|s| s.replace(' ', "").to_lowercase())
+            .is_some_and(|s| s.contains("display:none") || s.contains("visibility:hidden"))
+}
+
+fn text_length(element: &ElementRef) -> usize {
+    element.text().map(|t| t.trim().chars().count()).sum()
+}
+
+fn link_text_length(element: &ElementRef) -> usize {
+    element
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|e| e.value().name() == "a")
+        .map(|a| text_length(&a))
+        .sum()
+}
+
+/// Readability-style scoring: paragraphs award points to their parent and,
+/// at half weight, grandparent; class/id hints and link density adjust the total.
+fn find_content_root<'a>(document: &'a Html) -> ElementRef<'a> {
+    let root = document.root_element();
+    let body = root
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .find(|e| e.value().name() == "body")
+        .unwrap_or(root);
+
+    let semantic: Vec<ElementRef> = body
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|e| {
+            e.value().name() == "article"
+                || e.value().name() == "main"
+                || e.value().attr("role") == Some("main")
+        })
+        .filter(|e| text_length(e) >= SEMANTIC_ROOT_MIN_CHARS)
+        .collect();
+    // A <main> wrapping a single <article> still counts as one candidate: keep the innermost.
+    let innermost: Vec<ElementRef> = semantic
+        .iter()
+        .filter(|candidate| {
+            !semantic.iter().any(|other| {
+                other.id() != candidate.id() && other.ancestors().any(|a| a.id() == candidate.id())
+            })
+        })
+        .copied()
+        .collect();
+    if innermost.len() == 1 {
+        return innermost[0];
+    }
+
+    let negative = negative_pattern();
+    let positive = positive_pattern();
+    let mut scores: Vec<(ElementRef, f64)> = Vec::new();
+    let mut add_score = |element: ElementRef<'a>, points: f64| {
+        if let Some(entry) = scores.iter_mut().find(|(e, _)| e.id() == element.id()) {
+            entry.1 += points;
+        } else {
+            let hints = class_and_id(&element);
+            let mut base = 0.0;
+            if negative.is_match(&hints) {
+                base -= 25.0;
+            }
+            if positive.is_match(&hints) {
+                base += 25.0;
+            }
+            scores.push((element, base + points));
+        }
+    };
+    for paragraph in body
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|e| matches!(e.value().name(), "p" | "pre" | "td"))
+    {
+        let text: String = paragraph.text().collect();
+        let length = text.trim().chars().count();
+        if length < 25 {
+            continue;
+        }
+        let points = 1.0 + text.matches(',').count() as f64 + (length as f64 / 100.0).min(3.0);
+        let Some(parent) = paragraph.parent().and_then(ElementRef::wrap) else {
+            continue;
+        };
+        add_score(parent, points);
+        if let Some(grandparent) = parent.parent().and_then(ElementRef::wrap) {
+            if grandparent.value().name() != "html" {
+                add_score(grandparent, points / 2.0);
+            }
+        }
+    }
+
+    scores
+        .into_iter()
+        .map(|(element, score)| {
+            let total = text_length(&element).max(1) as f64;
+            let density = link_text_length(&element) as f64 / total;
+            (element, score * (1.0 - density))
+        })
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(element, _)| element)
+        .unwrap_or(body)
+}
+
+fn page_title(document: &Html) -> Option<String> {
+    let elements: Vec<ElementRef> = document
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .collect();
+    let og_title = elements.iter().find_map(|e| {
+        (e.value().name() == "meta" && e.value().attr("property") == Some("og:title"))
+            .then(|| e.value().attr("content"))
+            .flatten()
+            .map(str::to_string)
+    });
+    let title_tag = || {
+        elements
+            .iter()
+            .find(|e| e.value().name() == "title")
+            .map(|e| e.text().collect::<String>())
+    };
+    let first_h1 = || {
+        elements
+            .iter()
+            .find(|e| e.value().name() == "h1")
+            .map(|e| e.text().collect::<String>())
+    };
+    [og_title, title_tag(), first_h1()]
+        .into_iter()
+        .flatten()
+        .map(|t| collapse_whitespace(&t))
+        .find(|t| !t.is_empty())
+}
+
+/// Converts a sanitized DOM subtree into markdown. Only allow-listed elements
+/// produce markup; everything else contributes its text.
+struct MarkdownWriter<'a> {
+    base: &'a Url,
+    negative: Regex,
+    images: Vec<ClipImage>,
+}
+
+impl<'a> MarkdownWriter<'a> {
+    fn resolve(&self, href: &str) -> Option<String> {
+        let resolved = self.base.join(href.trim()).ok()?;
+        matches!(resolved.scheme(), "http" | "https" | "mailto").then(|| resolved.to_string())
+    }
+
+    fn skip(&self, element: &ElementRef) -> bool {
+        SKIP_TAGS.contains(&element.value().name())
+            || is_hidden(element)
+            || (matches!(element.value().name(), "div" | "section" | "ul" | "header")
+                && self.negative.is_match(&class_and_id(element)))
+    }
+
+    fn image(&mut self, element: &ElementRef) -> String {
+        let src = element
+            .value()
+            .attr("src")
+            .or_else(|| element.value().attr("data-src"))
+            .unwrap_or("");
+        let alt = collapse_whitespace(element.value().attr("alt").unwrap_or(""));
+        let url = if src.starts_with("data:image/") {
+            Some(src.to_string())
+        } else {
+            self.base
+                .join(src.trim())
+                .ok()
+                .filter(|u| matches!(u.scheme(), "http" | "https"))
+                .map(|u| u.to_string())
+        };
+        match url {
+            Some(url) if !src.is_empty() && self.images.len() < MAX_CLIP_IMAGES => {
+                self.images.push(ClipImage { url, alt });
+                image_placeholder(self.images.len() - 1)
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn inline(&mut self, node: ego_tree::NodeRef<'_, Node>) -> String {
+        match node.value() {
+            Node::Text(text) => escape_markdown(&text.replace(['\n', '\t', '\r'], " ")),
+            Node::Element(_) => {
+                let Some(element) = ElementRef::wrap(node) else {
+                    return String::new();
+                };
+                if self.skip(&element) {
+                    return String::new();
+                }
+                let name = element.value().name();
+                match name {
+                    "br" => "\n".to_string(),
+                    "img" => self.image(&element),
+                    "code" | "kbd" | "samp" => {
+                        let code: String = element.text().collect();
+                        let code = code.trim();
+                        if code.is_empty() {
+                            String::new()
+                        } else if code.contains('`') {
+                            format!("`` {} ``", code)
+                        } else {
+                            format!("`{}`", code)
+                        }
+                    }
+                    _ => {
+                        let inner = self.inline_children(node);
+                        let trimmed = inner.trim();
+                        if trimmed.is_empty() {
+                            return inner;
+                        }
+                        match name {
+                            "strong" | "b" => format!("**{}**", trimmed),
+                            "em" | "i" | "cite" => format!("*{}*", trimmed),
+                            "del" | "s" | "strike" => format!("~~{}~~", trimmed),
+                            "a" => match element.value().attr("href").and_then(|h| self.resolve(h))
+                            {
+                                Some(href) => format!("[{}]({})", trimmed, href),
+                                None => inner,
+                            },
+                            _ => inner,
+                        }
+                    }
+                }
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn inline_children(&mut self, node: ego_tree::NodeRef<'_, Node>) -> String {
+        node.children().map(|child| self.inline(child)).collect()
+    }
+
+    fn is_block(name: &str) -> bool {
+        matches!(
+            name,
+            "p" | "div"
+                | "section"
+                | "article"
+                | "main"
+                | "header"
+                | "h1"
+                | "h2"
+                | "h3"
+                | "h4"
+                | "h5"
+                | "h6"
+                | "ul"
+                | "ol"
+                | "li"
+                | "blockquote"
+                | "pre"
+                | "table"
+                | "hr"
+                | "figure"
+                | "figcaption"
+                | "dl"
+                | "dt"
+                | "dd"
+                | "body"
+        )
+    }
+
+    fn flush(paragraph: &mut String, blocks: &mut Vec<String>) {
+        let text = paragraph
+            .lines()
+            .map(collapse_whitespace)
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !text.is_empty() {
+            blocks.push(text);
+        }
+        paragraph.clear();
+    }
+
+    fn blocks(&mut self, node: ego_tree::NodeRef<'_, Node>) -> Vec<String> {
+        let mut blocks = Vec::new();
+        let mut paragraph = String::new();
+        for child in node.children() {
+            let block_element = ElementRef::wrap(child)
+                .filter(|e| Self::is_block(e.value().name()) && !self.skip(e));
+            match block_element {
+                Some(element) => {
+                    Self::flush(&mut paragraph, &mut blocks);
+                    blocks.extend(self.block(element));
+                }
+                None => paragraph.push_str(&self.inline(child)),
+            }
+        }
+        Self::flush(&mut paragraph, &mut blocks);
+        blocks
+    }
+
+    fn block(&mut self, element: ElementRef<'_>) -> Vec<String> {
+        let name = element.value().name();
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let text = collapse_whitespace(&self.inline_children(*element));
+                if text.is_empty() {
+                    Vec::new()
+                } else {
+                    // Headings would end the entry section, so they become bold lines.
+                    vec![format!("**{}**", text)]
+                }
+            }
+            "hr" => vec!["* * *".to_string()],
+            "pre" => {
+                let code: String = element.text().collect();
+                let language = element
+                    .descendants()
+                    .filter_map(ElementRef::wrap)
+                    .find_map(|e| {
+                        e.value().attr("class").and_then(|c| {
+                            c.split_whitespace()
+                                .find_map(|cls| cls.strip_prefix("language-"))
+                                .map(str::to_string)
+                        })
+                    })
+                    .unwrap_or_default();
+                let fence = if code.contains("```") { "~~~" } else { "```" };
+                vec![format!(
+                    "{}{}\n{}\n{}",
+                    fence,
+                    language,
+                    code.trim_end_matches('\n'),
+                    fence
+                )]
+            }
+            "blockquote" => {
+                let inner = self.blocks(*element).join("\n\n");
+                if inner.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![inner
+                        .lines()
+                        .map(|l| {
+                            if l.is_empty() {
+                                ">".to_string()
+                            } else {
+                                format!("> {}", l)
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")]
+                }
+            }
+            "ul" | "ol" => {
+                let list = self.list(element, name == "ol");
+                if list.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![list]
+                }
+            }
+            "table" => self.table(element).into_iter().collect(),
+            _ => self.blocks(*element),
+        }
+    }
+
+    fn list(&mut self, element: ElementRef<'_>, ordered: bool) -> String {
+        let mut lines = Vec::new();
+        let items = element
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|e| e.value().name() == "li" && !self.skip(e))
+            .collect::<Vec<_>>();
+        for (idx, item) in items.into_iter().enumerate() {
+            let marker = if ordered {
+                format!("{}. ", idx + 1)
+            } else {
+                "- ".to_string()
+            };
+            let indent = " ".repeat(marker.len());
+            let body = self.blocks(*item).join("\n");
+            for (line_idx, line) in body.lines().filter(|l| !l.is_empty()).enumerate() {
+                if line_idx == 0 {
+                    lines.push(format!("{}{}", marker, line));
+                } else {
+                    lines.push(format!("{}{}", indent, line));
+                }
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn table(&mut self, element: ElementRef<'_>) -> Option<String> {
+        let rows: Vec<Vec<String>> = element
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .filter(|e| e.value().name() == "tr")
+            .map(|row| {
+                row.children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|c| matches!(c.value().name(), "td" | "th"))
+                    .map(|c| collapse_whitespace(&self.inline_children(*c)).replace('|', "\\|"))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|cells: &Vec<String>| !cells.is_empty())
+            .collect();
+        let width = rows.iter().map(Vec::len).max()?;
+        let mut lines = Vec::new();
+        for (idx, row) in rows.iter().enumerate() {
+            let mut cells = row.clone();
+            cells.resize(width, String::new());
+            lines.push(format!("| {} |", cells.join(" | ")));
+            if idx == 0 {
+                lines.push(format!("|{}", " --- |".repeat(width)));
+            }
+        }
+        Some(lines.join("\n"))
+    }
+}
+
+fn extract_page(url: &Url, html: &str) -> ExtractedPage {
+    let document = Html::parse_document(html);
+    let title = page_title(&document).unwrap_or_else(|| {
+        url.host_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| url.to_string())
+    });
+    let root = find_content_root(&document);
+    let mut writer = MarkdownWriter {
+        base: url,
+        negative: negative_pattern(),
+        images: Vec::new(),
+    };
+    let markdown = writer.blocks(*root).join("\n\n");
+    ExtractedPage {
+        title,
+        markdown,
+        images: writer.images,
+    }
+}
+
+fn image_file_name(url: &str, index: usize) -> String {
+    if let Some(rest) = url.strip_prefix("data:image/") {
+        let ext = rest
+            .split([';', ','])
+            .next()
+            .unwrap_or("png")
+            .replace("svg+xml", "svg");
+        return format!("image-{}.{}", index + 1, ext);
+    }
+    Url::parse(url)
+        .ok()
+        .and_then(|u| {
+            u.path_segments()
+                .and_then(|mut segments| segments.next_back().map(str::to_string))
+        })
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| format!("image-{}", index + 1))
+}
+
+fn decode_data_url(url: &str, max_bytes: usize) -> Result<Vec<u8>> {
+    let (header, data) = url
+        .split_once(',')
+        .ok_or_else(|| anyhow!("Invalid data URL"))?;
+    if !header.ends_with(";base64") {
+        return Err(anyhow!("Only base64 data URLs are supported"));
+    }
+    let bytes = general_purpose::STANDARD.decode(data.trim())?;
+    if bytes.len() > max_bytes {
+        return Err(anyhow!("Image exceeds {} bytes", max_bytes));
+    }
+    Ok(bytes)
+}
+
+/// Clip a web page into a new entry.
+///
+/// The page is reduced to its main article content, converted to markdown and
+/// stored in `options.field`. Referenced images are downloaded into assets.
+pub async fn clip_html(
+    op: &Operator,
+    ws_path: &str,
+    url: &str,
+    html: &str,
+    options: &ClipOptions,
+) -> Result<ClipResult> {
+    let fetcher = HttpImageFetcher::new();
+    clip_html_with_fetcher(op, ws_path, url, html, options, &fetcher).await
+}
+
+pub async fn clip_html_with_fetcher(
+    op: &Operator,
+    ws_path: &str,
+    url: &str,
+    html: &str,
+    options: &ClipOptions,
+    fetcher: &dyn ImageFetcher,
+) -> Result<ClipResult> {
+    let source = Url::parse(url).map_err(|e| anyhow!("Invalid source URL: {} ({})", url, e))?;
+    if !matches!(source.scheme(), "http" | "https") {
+        return Err(anyhow!("Source URL must be http or https: {}", url));
+    }
+    if !op.exists(&format!("{}/meta.json", ws_path)).await? {
+        return Err(anyhow!("Space not found: {}", ws_path));
+    }
+    if metadata::is_reserved_metadata_form(&options.form) {
+        return Err(anyhow!("Cannot clip into metadata form: {}", options.form));
+    }
+    let form_def = form::read_form_definition(op, ws_path, &options.form).await?;
+    let form_fields = entry::form_field_names(&form_def);
+    if !form_fields.contains(&options.field) {
+        return Err(anyhow!(
+            "Form {} has no field named {}",
+            options.form,
+            options.field
+        ));
+    }
+
+    let page = extract_page(&source, html);
+    let title = options
+        .title
+        .as_deref()
+        .map(collapse_whitespace)
+        .filter(|t| !t.is_empty())
+        .unwrap_or(page.title);
+
+    let mut assets = Vec::new();
+    let mut failed_images = Vec::new();
+    let mut asset_refs = Vec::new();
+    let mut body = page.markdown;
+    for (index, image) in page.images.iter().enumerate() {
+        let alt = escape_markdown(&image.alt);
+        let mut replacement = None;
+        if options.download_images {
+            let downloaded = if image.url.starts_with("data:") {
+                decode_data_url(&image.url, options.max_image_bytes)
+            } else {
+                fetcher.fetch(&image.url, options.max_image_bytes).await
+            };
+            match downloaded {
+                Ok(bytes) => {
+                    let info =
+                        asset::save_asset(op, ws_path, &image_file_name(&image.url, index), &bytes)
+                            .await?;
+                    replacement = Some(format!("![{}]({})", alt, info.link));
+                    asset_refs.push(json!({"id": info.id, "name": info.name, "path": info.path}));
+                    assets.push(info);
+                }
+                Err(_) => failed_images.push(image.url.clone()),
+            }
+        }
+        let replacement = replacement.unwrap_or_else(|| {
+            if image.url.starts_with("data:") {
+                String::new()
+            } else {
+                format!("![{}]({})", alt, image.url)
+            }
+        });
+        body = body.replace(&image_placeholder(index), &replacement);
+    }
+
+    let body = importers::sanitize_body(&format!(
+        "Clipped from [{}]({})\n\n{}",
+        escape_markdown(source.host_str().unwrap_or(url)),
+        source,
+        body
+    ));
+    let mut fields = Map::new();
+    fields.insert(options.field.clone(), Value::String(body));
+    let markdown = entry::render_markdown(
+        &title,
+        &options.form,
+        &options.tags,
+        &Value::Object(fields),
+        &form_fields,
+    );
+
+    let entry_id = Uuid::new_v4().to_string();
+    let space_id = ws_path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(ws_path);
+    let integrity = RealIntegrityProvider::from_space(op, space_id).await?;
+    entry::create_entry_with_assets(
+        op,
+        ws_path,
+        &entry_id,
+        &markdown,
+        &options.author,
+        &RevisionNote::default(),
+        asset_refs,
+        &integrity,
+    )
+    .await?;
+
+    Ok(ClipResult {
+        entry_id,
+        title,
+        source_url: source.to_string(),
+        assets,
+        failed_images,
+    })
+}
@@ -1,12 +1,19 @@
 use anyhow::{anyhow, Result};
 use base64::{engine::general_purpose, Engine as _};
 use chrono::Utc;
+use hmac::{Hmac, KeyInit, Mac};
 use opendal::Operator;
 use rand::RngExt;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2_hmac::Sha256 as HmacSha256Digest;
+use subtle::ConstantTimeEq;
 use uuid::Uuid;
 
+use crate::clock;
+use crate::space_lock;
 use crate::storage::{OpendalStorage, StorageBackend};
+use std::collections::{BTreeMap, HashMap};
 use ugoite_minimum::integrity::HmacIntegrityProvider;
 pub use ugoite_minimum::integrity::{FakeIntegrityProvider, IntegrityProvider};
 
@@ -59,14 +66,15 @@ fn generate_hmac_payload() -> Value {
     })
 }
 
-fn validate_response_hmac_space_id(space_name: &str) -> Result<String> {
+pub(crate) fn validate_response_hmac_space_id(space_name: &str) -> Result<String> {
     let normalized = space_name.trim();
     if normalized.is_empty() {
         return Err(anyhow!("space_id must not be empty"));
     }
-    if !normalized
-        .chars()
-        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'))
+    if matches!(normalized, "." | "..")
+        || !normalized
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'))
     {
         return Err(anyhow!("invalid space_id"));
     }
@@ -181,3 +189,349 @@ pub async fn build_response_signature(
     let provider = RealIntegrityProvider::new(secret);
     Ok((key_id, provider.signature_bytes(body)))
 }
+
+type HmacSha256 = Hmac<HmacSha256Digest>;
+
+const CHUNK_SIGNATURE_DOMAIN: &[u8] = b"ugoite-chunk-v1";
+const TRANSCRIPT_SIGNATURE_DOMAIN: &[u8] = b"ugoite-transcript-v1";
+
+/// Signature of one chunk in a chunked response.
+///
+/// Each chunk MAC covers its index, its size, the previous chunk's signature
+/// and the chunk bytes, so chunks cannot be dropped, reordered or spliced
+/// from another stream without breaking every later signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkSignature {
+    pub index: u64,
+    pub size: u64,
+    pub signature: String,
+}
+
+/// Final signatures of a streamed response.
+///
+/// `body_signature` equals what `build_response_signature` returns for the
+/// concatenated body; `transcript_signature` seals the chunk sequence.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamSignature {
+    pub key_id: String,
+    pub chunks: u64,
+    pub bytes: u64,
+    pub body_signature: String,
+    pub transcript_signature: String,
+}
+
+/// Incremental response signer: `begin`, then `update` per chunk, then `finalize`.
+pub struct ResponseSigner {
+    key_id: String,
+    secret: Vec<u8>,
+    body: HmacSha256,
+    previous: String,
+    chunks: u64,
+    bytes: u64,
+}
+
+impl ResponseSigner {
+    pub async fn begin(op: &Operator, space_name: &str) -> Result<Self> {
+        let (key_id, secret) = load_response_hmac_material(op, space_name).await?;
+        Ok(Self::with_secret(key_id, secret))
+    }
+
+    pub fn with_secret(key_id: String, secret: Vec<u8>) -> Self {
+        let body = HmacSha256::new_from_slice(&secret).expect("HMAC can take key of any size");
+        Self {
+            key_id,
+            secret,
+            body,
+            previous: String::new(),
+            chunks: 0,
+            bytes: 0,
+        }
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.secret).expect("HMAC can take key of any size")
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) -> ChunkSignature {
+        let index = self.chunks;
+        let size = chunk.len() as u64;
+        let mut mac = self.mac();
+        mac.update(CHUNK_SIGNATURE_DOMAIN);
+        mac.update(&index.to_be_bytes());
+        mac.update(&size.to_be_bytes());
+        mac.update(self.previous.as_bytes());
+        mac.update(chunk);
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        self.body.update(chunk);
+        self.previous = signature.clone();
+        self.chunks += 1;
+        self.bytes += size;
+        ChunkSignature {
+            index,
+            size,
+            signature,
+        }
+    }
+
+    pub fn finalize(self) -> StreamSignature {
+        let mut mac = self.mac();
+        mac.update(TRANSCRIPT_SIGNATURE_DOMAIN);
+        mac.update(&self.chunks.to_be_bytes());
+        mac.update(&self.bytes.to_be_bytes());
+        mac.update(self.previous.as_bytes());
+        let transcript_signature = hex::encode(mac.finalize().into_bytes());
+        StreamSignature {
+            key_id: self.key_id,
+            chunks: self.chunks,
+            bytes: self.bytes,
+            body_signature: hex::encode(self.body.finalize().into_bytes()),
+            transcript_signature,
+        }
+    }
+}
+
+/// Verifies a chunked response chunk by chunk against the space's response key.
+pub struct ChunkedSignatureVerifier {
+    signer: ResponseSigner,
+}
+
+impl ChunkedSignatureVerifier {
+    pub async fn begin(op: &Operator, space_name: &str) -> Result<Self> {
+        Ok(Self::new(ResponseSigner::begin(op, space_name).await?))
+    }
+
+    pub fn new(signer: ResponseSigner) -> Self {
+        Self { signer }
+    }
+
+    pub fn verify_chunk(&mut self, chunk: &[u8], signature: &str) -> Result<()> {
+        let expected = self.signer.update(chunk);
+        if !signatures_match(&expected.signature, signature) {
+            return Err(anyhow!("Chunk {} signature does not match", expected.index));
+        }
+        Ok(())
+    }
+
+    pub fn finalize(self, transcript_signature: &str) -> Result<StreamSignature> {
+        let expected = self.signer.finalize();
+        if !signatures_match(&expected.transcript_signature, transcript_signature) {
+            return Err(anyhow!("Transcript signature does not match"));
+        }
+        Ok(expected)
+    }
+}
+
+fn signatures_match(expected: &str, actual: &str) -> bool {
+    bool::from(expected.as_bytes().ct_eq(actual.trim().as_bytes()))
+}
+
+pub const REQUEST_KEY_ID_HEADER: &str = "x-ugoite-key-id";
+pub const REQUEST_SIGNATURE_HEADER: &str = "x-ugoite-signature";
+pub const REQUEST_TIMESTAMP_HEADER: &str = "x-ugoite-timestamp";
+pub const REQUEST_NONCE_HEADER: &str = "x-ugoite-nonce";
+
+/// Default allowed clock skew, in seconds, between a signed request and the server.
+pub const DEFAULT_REQUEST_SIGNATURE_WINDOW_SECS: i64 = 300;
+
+const REQUEST_SIGNATURE_DOMAIN: &[u8] = b"ugoite-request-v2";
+const REQUEST_KEY_DOMAIN: &[u8] = b"ugoite-request-key-v1";
+const REQUEST_NONCE_LEASE: &str = "request-nonces";
+const MIN_NONCE_LEN: usize = 8;
+const MAX_NONCE_LEN: usize = 128;
+
+/// A request whose signature, timestamp and nonce were accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedRequest {
+    pub key_id: String,
+    pub timestamp: i64,
+    pub nonce: String,
+}
+
+fn request_nonces_path(space_name: &str) -> String {
+    format!("spaces/{space_name}/request_nonces.json")
+}
+
+/// Key for request MACs, derived from the response-signing key.
+///
+/// The derivation is keyed by a fixed label with the secret as the message, so
+/// no response signature (`HMAC(secret, body)`) can reveal or stand in for it.
+fn request_signing_key(secret: &[u8]) -> Vec<u8> {
+    let mut mac =
+        HmacSha256::new_from_slice(REQUEST_KEY_DOMAIN).expect("HMAC can take key of any size");
+    mac.update(secret);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn request_signature_bytes(
+    secret: &[u8],
+    method: &str,
+    path: &str,
+    timestamp: i64,
+    nonce: &str,
+    body: &[u8],
+) -> String {
+    let mut mac = HmacSha256::new_from_slice(&request_signing_key(secret))
+        .expect("HMAC can take key of any size");
+    mac.update(REQUEST_SIGNATURE_DOMAIN);
+    mac.update(format!("\n{method}\n{path}\n{timestamp}\n{nonce}\n").as_bytes());
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Normalize the HTTP method and check the request path before signing.
+fn validate_request_target(method: &str, path: &str) -> Result<String> {
+    let method = method.trim();
+    if method.is_empty() || !method.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        return Err(anyhow!("Request method must be an HTTP method name"));
+    }
+    if !path.starts_with('/') || path.chars().any(|ch| ch.is_whitespace() || ch.is_control()) {
+        return Err(anyhow!(
+            "Request path must start with '/' and contain no whitespace"
+        ));
+    }
+    Ok(method.to_ascii_uppercase())
+}
+
+fn validate_request_nonce(nonce: &str) -> Result<()> {
+    if !(MIN_NONCE_LEN..=MAX_NONCE_LEN).contains(&nonce.len())
+        || !nonce
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '-'))
+    {
+        return Err(anyhow!(
+            "Request nonce must be {MIN_NONCE_LEN}-{MAX_NONCE_LEN} characters of [A-Za-z0-9._-]"
+        ));
+    }
+    Ok(())
+}
+
+/// Sign a request for `space_name` the way trusted machine clients do.
+///
+/// The signature covers the HTTP `method`, the request `path`, `timestamp`
+/// (unix seconds), `nonce` and body. Returns `(key_id, signature)` for the
+/// `X-Ugoite-Key-Id` and `X-Ugoite-Signature` headers; the timestamp and nonce
+/// are sent as `X-Ugoite-Timestamp` and `X-Ugoite-Nonce`.
+pub async fn build_request_signature(
+    op: &Operator,
+    space_name: &str,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    timestamp: i64,
+    nonce: &str,
+) -> Result<(String, String)> {
+    let method = validate_request_target(method, path)?;
+    validate_request_nonce(nonce)?;
+    let (key_id, secret) = load_response_hmac_material(op, space_name).await?;
+    Ok((
+        key_id,
+        request_signature_bytes(&secret, &method, path, timestamp, nonce, body),
+    ))
+}
+
+/// Verify an HMAC-signed write with the default timestamp window.
+pub async fn verify_request_signature(
+    op: &Operator,
+    space_name: &str,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    headers: &HashMap<String, String>,
+) -> Result<VerifiedRequest> {
+    verify_request_signature_with_window(
+        op,
+        space_name,
+        method,
+        path,
+        body,
+        headers,
+        DEFAULT_REQUEST_SIGNATURE_WINDOW_SECS,
+    )
+    .await
+}
+
+/// Verify an HMAC-signed write of `method` to `path` with the request key
+/// derived from the space's response-signing key.
+///
+/// The timestamp must lie within `window_secs` of now and the nonce must not
+/// have been seen inside that window. Accepted nonces are recorded in
+/// `spaces/{space_id}/request_nonces.json` until they fall out of the window.
+pub async fn verify_request_signature_with_window(
+    op: &Operator,
+    space_name: &str,
+    method: &str,
+    path: &str,
+    body: &[u8],
+    headers: &HashMap<String, String>,
+    window_secs: i64,
+) -> Result<VerifiedRequest> {
+    if window_secs <= 0 {
+        return Err(anyhow!("Request signature window must be positive"));
+    }
+    let now = clock::now().timestamp();
+    let method = validate_request_target(method, path)?;
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let signature =
+        header(REQUEST_SIGNATURE_HEADER).ok_or_else(|| anyhow!("Missing request signature"))?;
+    let timestamp: i64 = header(REQUEST_TIMESTAMP_HEADER)
+        .ok_or_else(|| anyhow!("Missing request timestamp"))?
+        .parse()
+        .map_err(|_| anyhow!("Request timestamp must be unix seconds"))?;
+    let nonce = header(REQUEST_NONCE_HEADER).ok_or_else(|| anyhow!("Missing request nonce"))?;
+    validate_request_nonce(nonce)?;
+
+    let safe_space_name = validate_response_hmac_space_id(space_name)?;
+    let (key_id, secret) = load_response_hmac_material(op, &safe_space_name).await?;
+    if let Some(claimed) = header(REQUEST_KEY_ID_HEADER) {
+        if claimed != key_id {
+            return Err(anyhow!("Request signed with unknown key id {claimed}"));
+        }
+    }
+    let expected = request_signature_bytes(&secret, &method, path, timestamp, nonce, body);
+    if !signatures_match(&expected, signature) {
+        return Err(anyhow!("Request signature does not match"));
+    }
+
+    let within_window = now
+        .checked_sub(timestamp)
+        .map(i64::unsigned_abs)
+        .is_some_and(|skew| skew <= window_secs.unsigned_abs());
+    if !within_window {
+        return Err(anyhow!("Request timestamp is outside the allowed window"));
+    }
+
+    let ws_path = format!("spaces/{safe_space_name}");
+    let nonces_path = request_nonces_path(&safe_space_name);
+    space_lock::with_space_lease(op, &ws_path, REQUEST_NONCE_LEASE, |_| async {
+        let mut seen: BTreeMap<String, i64> = if op.exists(&nonces_path).await? {
+            serde_json::from_slice(&op.read(&nonces_path).await?.to_vec())?
+        } else {
+            BTreeMap::new()
+        };
+        seen.retain(|_, expires_at| *expires_at >= now);
+        if seen.contains_key(nonce) {
+            return Err(anyhow!("Request nonce has already been used"));
+        }
+        seen.insert(nonce.to_string(), timestamp + window_secs);
+        op.write(&nonces_path, serde_json::to_vec(&seen)?).await?;
+        Ok(())
+    })
+    .await?;
+
+    Ok(VerifiedRequest {
+        key_id,
+        timestamp,
+        nonce: nonce.to_string(),
+    })
+}
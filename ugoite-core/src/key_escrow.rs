@@ -0,0 +1,401 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+use opendal::Operator;
+use rand::TryRng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sha2_hmac::Sha256 as Pbkdf2Sha256;
+
+use crate::clock;
+use crate::entry;
+use crate::integrity::{self, IntegrityProvider, RealIntegrityProvider};
+use crate::storage;
+
+/// Prefix of every recovery code line; bump with the payload format.
+pub const RECOVERY_CODE_PREFIX: &str = "UGK1";
+/// Shortest passphrase accepted for wrapping escrowed keys.
+pub const MIN_ESCROW_PASSPHRASE_LEN: usize = 12;
+
+const ESCROW_FORMAT_VERSION: u8 = 1;
+const ESCROW_KDF_ITERATIONS: u32 = 240_000;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const GROUP_LEN: usize = 5;
+const GROUPS_PER_LINE: usize = 6;
+const CHECK_LEN: usize = 4;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const META_IDENTITY_FIELDS: &[&str] = &["id", "name", "created_at", "storage"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EscrowedKey {
+    key_id: String,
+    key: String,
+    #[serde(default)]
+    last_rotation: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EscrowPayload {
+    space_id: String,
+    /// Identity fields of `meta.json`, so a lost file can be rebuilt.
+    meta: Value,
+    integrity: EscrowedKey,
+    #[serde(default)]
+    response: Option<EscrowedKey>,
+    exported_at: String,
+}
+
+/// Printable recovery codes for a space's HMAC material.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryKit {
+    pub space_id: String,
+    pub key_id: String,
+    pub includes_response_key: bool,
+    pub codes: Vec<String>,
+    pub exported_at: String,
+}
+
+/// Outcome of restoring escrowed keys and re-checking entry signatures.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRestoreReport {
+    pub space_id: String,
+    pub key_id: String,
+    pub rebuilt_meta: bool,
+    pub restored_response_key: bool,
+    pub entries_checked: usize,
+    pub entries_verified: usize,
+    /// Entries whose content matches their checksum but not their signature.
+    pub entries_mismatched: Vec<String>,
+    /// Entries whose current content no longer matches the signed checksum.
+    pub entries_unverifiable: usize,
+}
+
+fn meta_path(space_id: &str) -> String {
+    format!("spaces/{space_id}/meta.json")
+}
+
+fn response_key_path(space_id: &str) -> String {
+    format!("spaces/{space_id}/hmac.json")
+}
+
+fn wrapping_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut derived = [0_u8; 32];
+    pbkdf2::pbkdf2_hmac::<Pbkdf2Sha256>(
+        passphrase.as_bytes(),
+        salt,
+        ESCROW_KDF_ITERATIONS,
+        &mut derived,
+    );
+    derived
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0_u8; N];
+    rand::rngs::SysRng
+        .try_fill_bytes(&mut bytes)
+        .expect("Failed to generate secure random bytes");
+    bytes
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    let mut buffer = 0_u32;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(text: &str) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut buffer = 0_u32;
+    let mut bits = 0;
+    for ch in text.bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|candidate| *candidate == ch)
+            .ok_or_else(|| anyhow!("Invalid recovery code character '{}'", ch as char))?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(out)
+}
+
+fn line_check(line_no: usize, data: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(format!("{RECOVERY_CODE_PREFIX}-{line_no:02}-{data}"))
+        .finalize();
+    base32_encode(&digest)[..CHECK_LEN].to_string()
+}
+
+fn format_codes(blob: &[u8]) -> Vec<String> {
+    let encoded = base32_encode(blob);
+    let chars: Vec<char> = encoded.chars().collect();
+    chars
+        .chunks(GROUP_LEN * GROUPS_PER_LINE)
+        .enumerate()
+        .map(|(index, line)| {
+            let data: String = line.iter().collect();
+            let line_no = index + 1;
+            let groups: Vec<String> = line
+                .chunks(GROUP_LEN)
+                .map(|group| group.iter().collect())
+                .collect();
+            format!(
+                "{RECOVERY_CODE_PREFIX}-{line_no:02}-{}-{}",
+                groups.join("-"),
+                line_check(line_no, &data)
+            )
+        })
+        .collect()
+}
+
+/// Parse recovery code lines (in any order, any case) back into the wrapped blob.
+fn parse_codes(codes: &[String]) -> Result<Vec<u8>> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for raw in codes {
+        let normalized: String = raw
+            .chars()
+            .filter(|ch| !ch.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+        if normalized.is_empty() {
+            continue;
+        }
+        let parts: Vec<&str> = normalized.split('-').collect();
+        if parts.len() < 4 || parts[0] != RECOVERY_CODE_PREFIX {
+            return Err(anyhow!("Malformed recovery code line: {raw}"));
+        }
+        let line_no: usize = parts[1]
+            .parse()
+            .map_err(|_| anyhow!("Malformed recovery code line: {raw}"))?;
+        let check = parts[parts.len() - 1];
+        let data = parts[2..parts.len() - 1].concat();
+        if line_check(line_no, &data) != check {
+            return Err(anyhow!(
+                "Recovery code line {line_no} has a typo (checksum mismatch)"
+            ));
+        }
+        lines.push((line_no, data));
+    }
+    if lines.is_empty() {
+        return Err(anyhow!("No recovery codes given"));
+    }
+    lines.sort_by_key(|(line_no, _)| *line_no);
+    for (expected, (line_no, _)) in (1..).zip(&lines) {
+        if *line_no != expected {
+            return Err(anyhow!("Recovery code line {expected} is missing"));
+        }
+    }
+    let encoded: String = lines.into_iter().map(|(_, data)| data).collect();
+    base32_decode(&encoded)
+}
+
+fn wrap_payload(payload: &EscrowPayload, passphrase: &str) -> Result<Vec<u8>> {
+    let salt = random_bytes::<SALT_LEN>();
+    let nonce = random_bytes::<NONCE_LEN>();
+    let cipher = Aes256Gcm::new_from_slice(&wrapping_key(passphrase, &salt))
+        .map_err(|e| anyhow!("Invalid wrapping key: {e}"))?;
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            serde_json::to_vec(payload)?.as_slice(),
+        )
+        .map_err(|_| anyhow!("Failed to wrap key material"))?;
+    let mut blob = vec![ESCROW_FORMAT_VERSION];
+    blob.extend(salt);
+    blob.extend(nonce);
+    blob.extend(ciphertext);
+    Ok(blob)
+}
+
+fn unwrap_payload(blob: &[u8], passphrase: &str) -> Result<EscrowPayload> {
+    let header_len = 1 + SALT_LEN + NONCE_LEN;
+    if blob.len() <= header_len || blob[0] != ESCROW_FORMAT_VERSION {
+        return Err(anyhow!("Unsupported recovery code format"));
+    }
+    let salt = &blob[1..1 + SALT_LEN];
+    let nonce = &blob[1 + SALT_LEN..header_len];
+    let cipher = Aes256Gcm::new_from_slice(&wrapping_key(passphrase, salt))
+        .map_err(|e| anyhow!("Invalid wrapping key: {e}"))?;
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), &blob[header_len..])
+        .map_err(|_| anyhow!("Recovery codes or passphrase are incorrect"))?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+fn validate_passphrase(passphrase: &str) -> Result<()> {
+    if passphrase.chars().count() < MIN_ESCROW_PASSPHRASE_LEN {
+        return Err(anyhow!(
+            "Escrow passphrase must be at least {MIN_ESCROW_PASSPHRASE_LEN} characters"
+        ));
+    }
+    Ok(())
+}
+
+/// Wrap the space's HMAC material under `passphrase` and print it as recovery codes.
+///
+/// The response-signing key is included when `hmac.json` already exists.
+pub async fn export_recovery_codes(
+    op: &Operator,
+    space_id: &str,
+    passphrase: &str,
+) -> Result<RecoveryKit> {
+    let space_id = integrity::validate_response_hmac_space_id(space_id)?;
+    let space_id = space_id.as_str();
+    validate_passphrase(passphrase)?;
+    let (key_id, secret) = integrity::load_hmac_material(op, space_id).await?;
+    let meta: Value = serde_json::from_slice(&op.read(&meta_path(space_id)).await?.to_vec())?;
+    let identity: serde_json::Map<String, Value> = META_IDENTITY_FIELDS
+        .iter()
+        .filter_map(|field| {
+            meta.get(*field)
+                .map(|value| (field.to_string(), value.clone()))
+        })
+        .collect();
+    let response = if op.exists(&response_key_path(space_id)).await? {
+        let (key_id, secret) = integrity::load_response_hmac_material(op, space_id).await?;
+        Some(EscrowedKey {
+            key_id,
+            key: general_purpose::STANDARD.encode(secret),
+            last_rotation: None,
+        })
+    } else {
+        None
+    };
+    let exported_at = clock::now().to_rfc3339();
+    let payload = EscrowPayload {
+        space_id: space_id.to_string(),
+        meta: Value::Object(identity),
+        integrity: EscrowedKey {
+            key_id: key_id.clone(),
+            key: general_purpose::STANDARD.encode(secret),
+            last_rotation: meta
+                .get("last_rotation")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        },
+        response: response.clone(),
+        exported_at: exported_at.clone(),
+    };
+    Ok(RecoveryKit {
+        space_id: space_id.to_string(),
+        key_id,
+        includes_response_key: response.is_some(),
+        codes: format_codes(&wrap_payload(&payload, passphrase)?),
+        exported_at,
+    })
+}
+
+/// Restore escrowed HMAC material into a space, then re-check entry signatures.
+///
+/// A missing or unreadable `meta.json` is rebuilt from the escrowed identity
+/// fields; otherwise only its key fields are replaced.
+pub async fn restore_from_recovery_codes(
+    op: &Operator,
+    space_id: &str,
+    codes: &[String],
+    passphrase: &str,
+) -> Result<KeyRestoreReport> {
+    let space_id = integrity::validate_response_hmac_space_id(space_id)?;
+    let space_id = space_id.as_str();
+    let payload = unwrap_payload(&parse_codes(codes)?, passphrase)?;
+    if payload.space_id != space_id {
+        return Err(anyhow!(
+            "Recovery codes belong to space {}, not {space_id}",
+            payload.space_id
+        ));
+    }
+    let ws_path = format!("spaces/{space_id}");
+    if !op.exists(&format!("{ws_path}/")).await? {
+        return Err(anyhow!("Space not found: {space_id}"));
+    }
+
+    let path = meta_path(space_id);
+    let existing = if op.exists(&path).await? {
+        serde_json::from_slice::<Value>(&op.read(&path).await?.to_vec())
+            .ok()
+            .filter(Value::is_object)
+    } else {
+        None
+    };
+    let rebuilt_meta = existing.is_none();
+    let mut meta = existing.unwrap_or_else(|| payload.meta.clone());
+    for field in META_IDENTITY_FIELDS {
+        if meta.get(*field).is_none() {
+            if let Some(value) = payload.meta.get(*field) {
+                meta[*field] = value.clone();
+            }
+        }
+    }
+    meta["hmac_key_id"] = Value::String(payload.integrity.key_id.clone());
+    meta["hmac_key"] = Value::String(payload.integrity.key.clone());
+    meta["last_rotation"] = payload
+        .integrity
+        .last_rotation
+        .clone()
+        .map(Value::String)
+        .unwrap_or_else(|| Value::String(clock::now().to_rfc3339()));
+    storage::publish_atomic(op, &path, serde_json::to_vec_pretty(&meta)?).await?;
+
+    if let Some(response) = &payload.response {
+        let response_payload = serde_json::json!({
+            "hmac_key_id": response.key_id,
+            "hmac_key": response.key,
+            "last_rotation": payload.exported_at,
+        });
+        storage::publish_atomic(
+            op,
+            &response_key_path(space_id),
+            serde_json::to_vec_pretty(&response_payload)?,
+        )
+        .await?;
+    }
+
+    let provider = RealIntegrityProvider::from_space(op, space_id).await?;
+    let mut report = KeyRestoreReport {
+        space_id: space_id.to_string(),
+        key_id: payload.integrity.key_id,
+        rebuilt_meta,
+        restored_response_key: payload.response.is_some(),
+        entries_checked: 0,
+        entries_verified: 0,
+        entries_mismatched: Vec::new(),
+        entries_unverifiable: 0,
+    };
+    for summary in entry::list_entries(op, &ws_path).await? {
+        let Some(entry_id) = summary.get("id").and_then(Value::as_str) else {
+            continue;
+        };
+        let value = entry::get_entry(op, &ws_path, entry_id).await?;
+        let content = value.get("content").and_then(Value::as_str).unwrap_or("");
+        let stored = &value["integrity"];
+        report.entries_checked += 1;
+        if stored.get("checksum").and_then(Value::as_str) != Some(&provider.checksum(content)) {
+            report.entries_unverifiable += 1;
+        } else if stored.get("signature").and_then(Value::as_str)
+            == Some(&provider.signature(content))
+        {
+            report.entries_verified += 1;
+        } else {
+            report.entries_mismatched.push(entry_id.to_string());
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,212 @@
+use anyhow::{anyhow, Result};
+use chrono::{SecondsFormat, Utc};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::audit;
+use crate::entry;
+use crate::form;
+use crate::space;
+
+/// Scope required to place or release a legal hold.
+pub const LEGAL_HOLD_SCOPE: &str = "legal_hold";
+const LEGAL_HOLDS_DIR: &str = "legal_holds";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HoldTargetType {
+    Entry,
+    Form,
+}
+
+impl HoldTargetType {
+    fn as_str(self) -> &'static str {
+        match self {
+            HoldTargetType::Entry => "entry",
+            HoldTargetType::Form => "form",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LegalHold {
+    pub hold_id: String,
+    pub target_type: HoldTargetType,
+    pub target_id: String,
+    pub reason: String,
+    pub placed_by: String,
+    pub placed_at: String,
+}
+
+fn hold_path(ws_path: &str, hold_id: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, LEGAL_HOLDS_DIR, hold_id)
+}
+
+fn require_hold_scope(scopes: &[String]) -> Result<()> {
+    if scopes.iter().any(|scope| scope == LEGAL_HOLD_SCOPE) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Managing legal holds requires the '{}' scope",
+            LEGAL_HOLD_SCOPE
+        ))
+    }
+}
+
+fn validate_hold_id(hold_id: &str) -> Result<()> {
+    uuid::Uuid::parse_str(hold_id).map_err(|e| {
+        anyhow!(
+            "Invalid hold_id: {}. Must be a valid UUID. ({})",
+            hold_id,
+            e
+        )
+    })?;
+    Ok(())
+}
+
+async fn record_hold_event(
+    op: &Operator,
+    ws_path: &str,
+    action: &str,
+    actor: &str,
+    hold: &LegalHold,
+) -> Result<()> {
+    audit::append_audit_event(
+        op,
+        space::space_id_from_ws_path(ws_path),
+        &json!({
+            "action": action,
+            "actor_user_id": actor,
+            "outcome": "success",
+            "target_type": hold.target_type.as_str(),
+            "target_id": hold.target_id,
+            "metadata": {"hold_id": hold.hold_id},
+        }),
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+pub async fn list_legal_holds(op: &Operator, ws_path: &str) -> Result<Vec<LegalHold>> {
+    let dir = format!("{}/{}/", ws_path, LEGAL_HOLDS_DIR);
+    let mut holds = Vec::new();
+    if !op.exists(&dir).await? {
+        return Ok(holds);
+    }
+    for item in op.list(&dir).await? {
+        if !item.name().ends_with(".json") {
+            continue;
+        }
+        let bytes = op.read(item.path()).await?.to_vec();
+        holds.push(serde_json::from_slice::<LegalHold>(&bytes)?);
+    }
+    holds.sort_by(|a, b| {
+        a.placed_at
+            .cmp(&b.placed_at)
+            .then_with(|| a.hold_id.cmp(&b.hold_id))
+    });
+    Ok(holds)
+}
+
+pub async fn place_legal_hold(
+    op: &Operator,
+    ws_path: &str,
+    target_type: HoldTargetType,
+    target_id: &str,
+    reason: &str,
+    actor: &str,
+    scopes: &[String],
+) -> Result<LegalHold> {
+    require_hold_scope(scopes)?;
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(anyhow!("Legal hold reason must not be empty"));
+    }
+    match target_type {
+        HoldTargetType::Entry => {
+            if entry::find_entry_form(op, ws_path, target_id)
+                .await?
+                .is_none()
+            {
+                return Err(anyhow!("Entry not found: {}", target_id));
+            }
+        }
+        HoldTargetType::Form => {
+            form::read_form_definition(op, ws_path, target_id).await?;
+        }
+    }
+    let hold = LegalHold {
+        hold_id: uuid::Uuid::new_v4().to_string(),
+        target_type,
+        target_id: target_id.to_string(),
+        reason: reason.to_string(),
+        placed_by: actor.to_string(),
+        placed_at: Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true),
+    };
+    op.write(
+        &hold_path(ws_path, &hold.hold_id),
+        serde_json::to_vec_pretty(&hold)?,
+    )
+    .await?;
+    record_hold_event(op, ws_path, "legal_hold.place", actor, &hold).await?;
+    Ok(hold)
+}
+
+pub async fn release_legal_hold(
+    op: &Operator,
+    ws_path: &str,
+    hold_id: &str,
+    actor: &str,
+    scopes: &[String],
+) -> Result<LegalHold> {
+    require_hold_scope(scopes)?;
+    validate_hold_id(hold_id)?;
+    let path = hold_path(ws_path, hold_id);
+    if !op.exists(&path).await? {
+        return Err(anyhow!("Legal hold not found: {}", hold_id));
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    let hold: LegalHold = serde_json::from_slice(&bytes)?;
+    op.delete(&path).await?;
+    record_hold_event(op, ws_path, "legal_hold.release", actor, &hold).await?;
+    Ok(hold)
+}
+
+/// Holds covering an entry, either directly or through its form.
+pub async fn holds_for_entry(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    entry_id: &str,
+) -> Result<Vec<LegalHold>> {
+    Ok(list_legal_holds(op, ws_path)
+        .await?
+        .into_iter()
+        .filter(|hold| match hold.target_type {
+            HoldTargetType::Entry => hold.target_id == entry_id,
+            HoldTargetType::Form => hold.target_id == form_name,
+        })
+        .collect())
+}
+
+/// Reject a destructive `operation` on an entry that is under legal hold.
+pub(crate) async fn ensure_entry_not_held(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    entry_id: &str,
+    operation: &str,
+) -> Result<()> {
+    let holds = holds_for_entry(op, ws_path, form_name, entry_id).await?;
+    if let Some(hold) = holds.first() {
+        return Err(anyhow!(
+            "Cannot {} entry {}: legal hold {} is active",
+            operation,
+            entry_id,
+            hold.hold_id
+        ));
+    }
+    Ok(())
+}
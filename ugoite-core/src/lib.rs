@@ -1,25 +1,82 @@
 #![warn(warnings)]
 #![deny(clippy::all)]
 
+pub mod analytics;
 pub mod asset;
+pub mod asset_links;
 pub mod audit;
+pub mod audit_otlp;
 pub mod auth;
+pub mod auth_config;
+pub mod authz;
+pub mod benchmark;
+pub mod chat_format;
+pub mod clock;
+pub mod collab;
+pub mod collections;
+pub mod dashboards;
+pub mod decimal;
 pub mod entry;
+pub mod entry_acl;
+pub mod entry_lock;
+pub mod entry_metadata;
+pub mod entry_order;
+pub mod entry_size;
+pub mod feature_flags;
+pub mod feed;
+pub mod field_crypto;
 pub mod form;
+pub mod geo;
 pub mod iceberg_store;
+pub mod importers;
 pub mod index;
+pub mod ingest;
 pub mod integrity;
+pub mod key_escrow;
+pub mod legal_hold;
 pub mod link;
+pub mod lint;
 pub mod materialized_view;
+pub mod mentions;
 pub mod metadata;
+pub mod oidc;
+pub mod orgs;
 pub mod preferences;
+pub mod principal_data;
+pub mod principals;
+pub mod property_ingest;
+pub mod query_cache;
+pub mod redaction;
+pub mod related;
+pub mod reminders;
+pub mod render;
+pub mod replace;
+pub mod result_buffer;
+pub mod retention;
+pub mod revision_delta;
+pub mod revision_signing;
+pub mod rollup;
+pub mod runtime;
 pub mod sample_data;
 pub mod saved_sql;
 pub mod search;
+pub mod sequence;
 pub mod space;
+pub mod space_lock;
 pub mod sql;
+pub mod sql_functions;
 pub mod sql_session;
 pub mod storage;
+pub mod sync;
+pub mod tabular;
+pub mod tasks;
+pub mod temporal;
+pub mod unique;
+pub mod views;
+pub mod warmup;
+pub mod watchers;
+pub mod webhooks;
+pub mod workflow;
 
 #[cfg(feature = "python-bindings")]
 mod python_bindings;
@@ -1,10 +1,153 @@
 use anyhow::{anyhow, Result};
 use opendal::Operator;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use std::sync::OnceLock;
 
-use crate::entry::{find_entry_form, read_entry_row, write_entry_row};
+use crate::entry::{self, find_entry_form, read_entry_row, write_entry_row};
+use crate::entry_acl::EntryViewer;
+use crate::space;
 pub use ugoite_minimum::link::Link;
 
+/// An entry reference, either space-local (`entry_id`) or qualified with the
+/// space it lives in (`space_id:entry_id`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct EntryRef {
+    pub space_id: Option<String>,
+    pub entry_id: String,
+}
+
+impl EntryRef {
+    pub fn parse(reference: &str) -> Result<Self> {
+        let reference = reference.trim();
+        let (space_id, entry_id) = match reference.split_once(':') {
+            Some((space_id, entry_id)) => (Some(space_id), entry_id),
+            None => (None, reference),
+        };
+        let valid = |part: &str| {
+            !part.is_empty() && !part.contains('/') && !part.contains('\\') && part != ".."
+        };
+        if !valid(entry_id) || space_id.is_some_and(|space_id| !valid(space_id)) {
+            return Err(anyhow!("Invalid entry reference: {}", reference));
+        }
+        Ok(Self {
+            space_id: space_id.map(str::to_string),
+            entry_id: entry_id.to_string(),
+        })
+    }
+
+    /// The space the entry lives in, `home_space_id` for space-local references.
+    pub fn space_or<'a>(&'a self, home_space_id: &'a str) -> &'a str {
+        self.space_id.as_deref().unwrap_or(home_space_id)
+    }
+
+    pub fn is_cross_space(&self, home_space_id: &str) -> bool {
+        self.space_or(home_space_id) != home_space_id
+    }
+
+    /// The reference as written from `home_space_id`: bare for entries of that
+    /// space, qualified otherwise.
+    pub fn relative_to(&self, home_space_id: &str) -> String {
+        if self.is_cross_space(home_space_id) {
+            format!("{}:{}", self.space_or(home_space_id), self.entry_id)
+        } else {
+            self.entry_id.clone()
+        }
+    }
+}
+
+impl fmt::Display for EntryRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.space_id {
+            Some(space_id) => write!(f, "{}:{}", space_id, self.entry_id),
+            None => f.write_str(&self.entry_id),
+        }
+    }
+}
+
+fn entry_link_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"ugoite://entry/([^\s)\]]+)").unwrap())
+}
+
+fn space_id_of(ws_path: &str) -> &str {
+    ws_path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+}
+
+/// Entry references in `ugoite://entry/...` links of a Markdown body, in order
+/// of first appearance. Malformed references are skipped.
+pub fn extract_entry_refs(markdown: &str) -> Vec<EntryRef> {
+    let mut refs: Vec<EntryRef> = Vec::new();
+    for caps in entry_link_regex().captures_iter(markdown) {
+        if let Ok(entry_ref) = EntryRef::parse(&caps[1]) {
+            if !refs.contains(&entry_ref) {
+                refs.push(entry_ref);
+            }
+        }
+    }
+    refs
+}
+
+/// Resolve a reference written in `home_space_id` without reading the entry
+/// content: where it points and whether the target exists.
+pub async fn resolve_entry_ref(
+    op: &Operator,
+    home_space_id: &str,
+    reference: &str,
+) -> Result<Value> {
+    let entry_ref = EntryRef::parse(reference)?;
+    let space_id = entry_ref.space_or(home_space_id).to_string();
+    let form = if space::space_exists(op, &space_id).await? {
+        find_entry_form(op, &format!("spaces/{}", space_id), &entry_ref.entry_id).await?
+    } else {
+        None
+    };
+    Ok(json!({
+        "space_id": space_id,
+        "entry_id": entry_ref.entry_id,
+        "reference": entry_ref.relative_to(home_space_id),
+        "cross_space": entry_ref.is_cross_space(home_space_id),
+        "exists": form.is_some(),
+        "form": form,
+    }))
+}
+
+/// Fetch the entry a reference points to, for a viewer already authorized
+/// to read the target space. The entry ACL of the target is enforced, and
+/// missing or unreadable targets are both reported as not found.
+pub async fn dereference_entry_ref(
+    op: &Operator,
+    home_space_id: &str,
+    reference: &str,
+    viewer: &EntryViewer,
+) -> Result<Value> {
+    let entry_ref = EntryRef::parse(reference)?;
+    let space_id = entry_ref.space_or(home_space_id);
+    let not_found = || anyhow!("Referenced entry not found: {}", reference);
+    if !space::space_exists(op, space_id).await? {
+        return Err(not_found());
+    }
+    let ws_path = format!("spaces/{}", space_id);
+    let mut entry = entry::get_entry_for_viewer(op, &ws_path, &entry_ref.entry_id, viewer)
+        .await
+        .map_err(|_| not_found())?;
+    if let Some(obj) = entry.as_object_mut() {
+        obj.insert("space_id".to_string(), Value::from(space_id));
+    }
+    Ok(entry)
+}
+
 /// Create a bi-directional link between two entries and persist metadata.
+///
+/// `target` may be qualified as `space_id:entry_id` to link into another
+/// space; the reciprocal link stored on the target then names the source as
+/// `source_space_id:source`.
 pub async fn create_link(
     op: &Operator,
     ws_path: &str,
@@ -13,24 +156,35 @@ pub async fn create_link(
     kind: &str,
     link_id: &str,
 ) -> Result<Link> {
+    let home_space_id = space_id_of(ws_path);
+    let target_ref = EntryRef::parse(target)?;
+    let target_ws_path = if target_ref.is_cross_space(home_space_id) {
+        format!("spaces/{}", target_ref.space_or(home_space_id))
+    } else {
+        ws_path.to_string()
+    };
     let source_form = find_entry_form(op, ws_path, source)
         .await?
         .ok_or_else(|| anyhow!("Source entry not found: {}", source))?;
-    let target_form = find_entry_form(op, ws_path, target)
+    let target_form = find_entry_form(op, &target_ws_path, &target_ref.entry_id)
         .await?
         .ok_or_else(|| anyhow!("Target entry not found: {}", target))?;
 
     let link_record = Link {
         id: link_id.to_string(),
         source: source.to_string(),
-        target: target.to_string(),
+        target: target_ref.relative_to(home_space_id),
         kind: kind.to_string(),
     };
 
+    let source_ref = EntryRef {
+        space_id: Some(home_space_id.to_string()),
+        entry_id: source.to_string(),
+    };
     let reciprocal_record = Link {
         id: link_id.to_string(),
-        source: target.to_string(), // Reciprocal source is target
-        target: source.to_string(), // Reciprocal target is source
+        source: target_ref.entry_id.clone(), // Reciprocal source is target
+        target: source_ref.relative_to(space_id_of(&target_ws_path)), // Reciprocal target is source
         kind: kind.to_string(),
     };
 
@@ -38,7 +192,14 @@ pub async fn create_link(
     update_entry_links(op, ws_path, &source_form, source, link_record.clone()).await?;
 
     // Update target
-    update_entry_links(op, ws_path, &target_form, target, reciprocal_record).await?;
+    update_entry_links(
+        op,
+        &target_ws_path,
+        &target_form,
+        &target_ref.entry_id,
+        reciprocal_record,
+    )
+    .await?;
 
     Ok(link_record)
 }
@@ -76,11 +237,23 @@ pub async fn list_links(op: &Operator, ws_path: &str) -> Result<Vec<Link>> {
 
 /// Delete a link and remove it from all entries in the space.
 pub async fn delete_link(op: &Operator, ws_path: &str, link_id: &str) -> Result<()> {
+    let home_space_id = space_id_of(ws_path);
     let mut found = false;
+    let mut remote_targets = Vec::new();
     let rows = crate::entry::list_entry_rows(op, ws_path).await?;
     for (form_name, mut row) in rows {
         let initial_len = row.links.len();
-        row.links.retain(|l| l.id != link_id);
+        row.links.retain(|l| {
+            if l.id != link_id {
+                return true;
+            }
+            if let Ok(target) = EntryRef::parse(&l.target) {
+                if target.is_cross_space(home_space_id) {
+                    remote_targets.push(target);
+                }
+            }
+            false
+        });
         if row.links.len() != initial_len {
             found = true;
             row.updated_at = crate::entry::now_ts();
@@ -92,5 +265,17 @@ pub async fn delete_link(op: &Operator, ws_path: &str, link_id: &str) -> Result<
         return Err(anyhow!("Link not found: {}", link_id));
     }
 
+    // Cross-space links keep their reciprocal in the other space.
+    for target in remote_targets {
+        let target_ws_path = format!("spaces/{}", target.space_or(home_space_id));
+        let Some(form_name) = find_entry_form(op, &target_ws_path, &target.entry_id).await? else {
+            continue;
+        };
+        let mut row = read_entry_row(op, &target_ws_path, &form_name, &target.entry_id).await?;
+        row.links.retain(|l| l.id != link_id);
+        row.updated_at = crate::entry::now_ts();
+        write_entry_row(op, &target_ws_path, &form_name, &target.entry_id, &row).await?;
+    }
+
     Ok(())
 }
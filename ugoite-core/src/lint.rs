@@ -0,0 +1,461 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use crate::entry;
+use crate::render;
+use crate::space;
+
+/// Space setting holding the lint configuration.
+pub const LINT_SETTINGS_KEY: &str = "lint";
+const ENTRY_SCHEME: &str = "ugoite://entry/";
+const ASSET_SCHEME: &str = "ugoite://asset/";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+fn default_severity() -> LintSeverity {
+    LintSeverity::Warning
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LintFinding {
+    pub rule: String,
+    pub severity: LintSeverity,
+    pub message: String,
+    /// 1-based line and character column in the entry markdown, frontmatter included.
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EntryLintReport {
+    pub entry_id: String,
+    pub title: String,
+    pub form: String,
+    pub findings: Vec<LintFinding>,
+}
+
+/// A regex rule declared in a space's lint settings.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CustomLintRule {
+    pub id: String,
+    pub pattern: String,
+    pub message: String,
+    #[serde(default = "default_severity")]
+    pub severity: LintSeverity,
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+/// The `lint` space setting.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct LintConfig {
+    /// Built-in or custom rule ids to skip.
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    #[serde(default)]
+    pub rules: Vec<CustomLintRule>,
+}
+
+/// Parsed entry markdown handed to each rule. Ranges are byte offsets into
+/// `markdown`.
+pub struct LintDocument<'a> {
+    pub markdown: &'a str,
+    pub links: Vec<DocumentLink>,
+    pub headings: Vec<(u8, Range<usize>)>,
+    /// Prose outside code, one range per run of adjacent text.
+    pub text: Vec<Range<usize>>,
+    pub anchors: HashSet<String>,
+    /// Ids of live entries (and assets) in the space.
+    pub known_entries: &'a HashSet<String>,
+}
+
+pub struct DocumentLink {
+    pub dest: String,
+    pub image: bool,
+    /// Link text, or alt text for images.
+    pub text: String,
+    pub range: Range<usize>,
+}
+
+impl LintDocument<'_> {
+    pub fn finding(
+        &self,
+        rule: &str,
+        severity: LintSeverity,
+        message: String,
+        range: &Range<usize>,
+    ) -> LintFinding {
+        let (line, column) = self.position(range.start);
+        let (end_line, end_column) = self.position(range.end);
+        LintFinding {
+            rule: rule.to_string(),
+            severity,
+            message,
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    }
+
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let before = &self.markdown[..offset];
+        let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+}
+
+/// A content check run over every linted entry.
+pub trait LintRule: Send + Sync {
+    fn id(&self) -> &str;
+    fn check(&self, document: &LintDocument, findings: &mut Vec<LintFinding>);
+}
+
+/// `ugoite://` references to missing entries or assets, and `#fragment` links
+/// to headings the entry does not have.
+pub struct BrokenLinkRule;
+
+impl LintRule for BrokenLinkRule {
+    fn id(&self) -> &str {
+        "broken_link"
+    }
+
+    fn check(&self, document: &LintDocument, findings: &mut Vec<LintFinding>) {
+        for link in &document.links {
+            let problem = if link.dest.trim().is_empty() {
+                Some("Link has no target".to_string())
+            } else if let Some(anchor) = link.dest.strip_prefix('#') {
+                (!document.anchors.contains(anchor))
+                    .then(|| format!("No heading matches #{}", anchor))
+            } else if let Some(rest) = link
+                .dest
+                .strip_prefix(ENTRY_SCHEME)
+                .or_else(|| link.dest.strip_prefix(ASSET_SCHEME))
+            {
+                let id = &rest[..rest.find(['#', '?']).unwrap_or(rest.len())];
+                // Qualified cross-space references are resolved by their own space.
+                (!id.contains(':') && !document.known_entries.contains(id))
+                    .then(|| format!("Link target {} does not exist", id))
+            } else {
+                None
+            };
+            if let Some(message) = problem {
+                findings.push(document.finding(
+                    self.id(),
+                    LintSeverity::Error,
+                    message,
+                    &link.range,
+                ));
+            }
+        }
+    }
+}
+
+pub struct MissingAltTextRule;
+
+impl LintRule for MissingAltTextRule {
+    fn id(&self) -> &str {
+        "missing_alt_text"
+    }
+
+    fn check(&self, document: &LintDocument, findings: &mut Vec<LintFinding>) {
+        for link in document
+            .links
+            .iter()
+            .filter(|link| link.image && link.text.trim().is_empty())
+        {
+            findings.push(document.finding(
+                self.id(),
+                LintSeverity::Warning,
+                "Image has no alt text".to_string(),
+                &link.range,
+            ));
+        }
+    }
+}
+
+/// Headings that go more than one level deeper than the heading before them.
+pub struct HeadingLevelJumpRule;
+
+impl LintRule for HeadingLevelJumpRule {
+    fn id(&self) -> &str {
+        "heading_level_jump"
+    }
+
+    fn check(&self, document: &LintDocument, findings: &mut Vec<LintFinding>) {
+        for pair in document.headings.windows(2) {
+            let ((previous, _), (level, range)) = (&pair[0], &pair[1]);
+            if *level > previous + 1 {
+                findings.push(document.finding(
+                    self.id(),
+                    LintSeverity::Warning,
+                    format!("Heading level jumps from h{} to h{}", previous, level),
+                    range,
+                ));
+            }
+        }
+    }
+}
+
+pub struct TodoMarkerRule;
+
+fn todo_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b(TODO|FIXME|XXX)\b").unwrap())
+}
+
+impl LintRule for TodoMarkerRule {
+    fn id(&self) -> &str {
+        "todo_marker"
+    }
+
+    fn check(&self, document: &LintDocument, findings: &mut Vec<LintFinding>) {
+        text_matches(document, todo_regex(), |marker, range| {
+            findings.push(document.finding(
+                self.id(),
+                LintSeverity::Info,
+                format!("{} marker", marker),
+                &range,
+            ));
+        });
+    }
+}
+
+/// A [`CustomLintRule`] with its pattern compiled.
+pub struct RegexRule {
+    rule: CustomLintRule,
+    pattern: Regex,
+}
+
+impl RegexRule {
+    pub fn new(rule: CustomLintRule) -> Result<Self> {
+        let pattern = RegexBuilder::new(&rule.pattern)
+            .case_insensitive(rule.case_insensitive)
+            .build()
+            .map_err(|e| anyhow!("Invalid lint rule {}: {}", rule.id, e))?;
+        Ok(Self { rule, pattern })
+    }
+}
+
+impl LintRule for RegexRule {
+    fn id(&self) -> &str {
+        &self.rule.id
+    }
+
+    fn check(&self, document: &LintDocument, findings: &mut Vec<LintFinding>) {
+        text_matches(document, &self.pattern, |_, range| {
+            if !range.is_empty() {
+                findings.push(document.finding(
+                    self.id(),
+                    self.rule.severity,
+                    self.rule.message.clone(),
+                    &range,
+                ));
+            }
+        });
+    }
+}
+
+/// Call `on_match` with the text and markdown range of every match of
+/// `pattern` in the document's prose.
+fn text_matches(
+    document: &LintDocument,
+    pattern: &Regex,
+    mut on_match: impl FnMut(&str, Range<usize>),
+) {
+    for span in &document.text {
+        for found in pattern.find_iter(&document.markdown[span.clone()]) {
+            on_match(
+                found.as_str(),
+                span.start + found.start()..span.start + found.end(),
+            );
+        }
+    }
+}
+
+fn builtin_rules() -> Vec<Box<dyn LintRule>> {
+    vec![
+        Box::new(BrokenLinkRule),
+        Box::new(MissingAltTextRule),
+        Box::new(HeadingLevelJumpRule),
+        Box::new(TodoMarkerRule),
+    ]
+}
+
+/// Built-in rules plus the custom rules of `config`, minus disabled ones.
+pub fn configured_rules(config: &LintConfig) -> Result<Vec<Box<dyn LintRule>>> {
+    let mut rules = builtin_rules();
+    let mut ids: HashSet<String> = rules.iter().map(|rule| rule.id().to_string()).collect();
+    for custom in &config.rules {
+        if custom.id.trim().is_empty() {
+            return Err(anyhow!("Lint rule id must not be empty"));
+        }
+        if !ids.insert(custom.id.clone()) {
+            return Err(anyhow!("Duplicate lint rule id: {}", custom.id));
+        }
+        rules.push(Box::new(RegexRule::new(custom.clone())?));
+    }
+    rules.retain(|rule| !config.disabled.iter().any(|id| id == rule.id()));
+    Ok(rules)
+}
+
+fn parse_document<'a>(markdown: &'a str, known_entries: &'a HashSet<String>) -> LintDocument<'a> {
+    let body = render::strip_frontmatter(markdown);
+    let body_start = markdown.len() - body.len();
+    let shift = |range: Range<usize>| body_start + range.start..body_start + range.end;
+    let mut document = LintDocument {
+        markdown,
+        links: Vec::new(),
+        headings: Vec::new(),
+        text: Vec::new(),
+        anchors: HashSet::new(),
+        known_entries,
+    };
+    let mut in_code = false;
+    let mut open_links: Vec<DocumentLink> = Vec::new();
+    for (event, range) in Parser::new(body).into_offset_iter() {
+        let range = shift(range);
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code = true,
+            Event::End(TagEnd::CodeBlock) => in_code = false,
+            Event::Start(Tag::Heading { level, .. }) => {
+                document.headings.push((level as u8, range));
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                open_links.push(DocumentLink {
+                    dest: dest_url.to_string(),
+                    image: false,
+                    text: String::new(),
+                    range,
+                });
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                open_links.push(DocumentLink {
+                    dest: dest_url.to_string(),
+                    image: true,
+                    text: String::new(),
+                    range,
+                });
+            }
+            Event::End(TagEnd::Link) | Event::End(TagEnd::Image) => {
+                document.links.extend(open_links.pop());
+            }
+            Event::Text(text) if !in_code => {
+                if let Some(link) = open_links.last_mut() {
+                    link.text.push_str(&text);
+                }
+                match document.text.last_mut() {
+                    Some(last) if last.end == range.start => last.end = range.end,
+                    _ => document.text.push(range),
+                }
+            }
+            _ => {}
+        }
+    }
+    document.anchors = flatten_anchors(&entry::markdown_outline(markdown));
+    document
+}
+
+fn flatten_anchors(headings: &[entry::OutlineHeading]) -> HashSet<String> {
+    let mut anchors = HashSet::new();
+    for heading in headings {
+        anchors.insert(heading.anchor.clone());
+        anchors.extend(flatten_anchors(&heading.children));
+    }
+    anchors
+}
+
+/// Run `rules` over entry markdown, e.g. a draft before it is saved.
+/// `known_entries` holds the entry and asset ids links may point to.
+pub fn lint_markdown(
+    markdown: &str,
+    rules: &[Box<dyn LintRule>],
+    known_entries: &HashSet<String>,
+) -> Vec<LintFinding> {
+    let document = parse_document(markdown, known_entries);
+    let mut findings = Vec::new();
+    for rule in rules {
+        rule.check(&document, &mut findings);
+    }
+    findings.sort_by_key(|finding| (finding.line, finding.column));
+    findings
+}
+
+/// The `lint` setting of the space at `ws_path`.
+pub async fn load_lint_config(op: &Operator, ws_path: &str) -> Result<LintConfig> {
+    let space_id = ws_path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    let meta = space::get_space_raw(op, space_id).await?;
+    match meta
+        .get("settings")
+        .and_then(|settings| settings.get(LINT_SETTINGS_KEY))
+    {
+        Some(value) => serde_json::from_value(value.clone())
+            .map_err(|e| anyhow!("Invalid lint settings: {}", e)),
+        None => Ok(LintConfig::default()),
+    }
+}
+
+async fn live_entry_ids(op: &Operator, ws_path: &str) -> Result<HashSet<String>> {
+    Ok(entry::list_entry_rows(op, ws_path)
+        .await?
+        .into_iter()
+        .filter(|(_, row)| !row.deleted)
+        .map(|(_, row)| row.entry_id)
+        .collect())
+}
+
+/// Findings for one entry's current markdown under the space's lint settings.
+pub async fn lint_entry(op: &Operator, ws_path: &str, entry_id: &str) -> Result<Vec<LintFinding>> {
+    let rules = configured_rules(&load_lint_config(op, ws_path).await?)?;
+    let content = entry::get_entry_content(op, ws_path, entry_id).await?;
+    let known_entries = live_entry_ids(op, ws_path).await?;
+    Ok(lint_markdown(&content.markdown, &rules, &known_entries))
+}
+
+/// Findings for every live entry, in entry id order; clean entries are omitted.
+pub async fn lint_space(op: &Operator, ws_path: &str) -> Result<Vec<EntryLintReport>> {
+    let rules = configured_rules(&load_lint_config(op, ws_path).await?)?;
+    let mut rows: Vec<(String, entry::EntryRow)> = entry::list_entry_rows(op, ws_path)
+        .await?
+        .into_iter()
+        .filter(|(_, row)| !row.deleted)
+        .collect();
+    rows.sort_by(|a, b| a.1.entry_id.cmp(&b.1.entry_id));
+    let known_entries: HashSet<String> = rows.iter().map(|(_, row)| row.entry_id.clone()).collect();
+
+    let mut reports = Vec::new();
+    for (form_name, row) in rows {
+        let content = entry::get_entry_content(op, ws_path, &row.entry_id).await?;
+        let findings = lint_markdown(&content.markdown, &rules, &known_entries);
+        if !findings.is_empty() {
+            reports.push(EntryLintReport {
+                entry_id: row.entry_id,
+                title: row.title,
+                form: form_name,
+                findings,
+            });
+        }
+    }
+    Ok(reports)
+}
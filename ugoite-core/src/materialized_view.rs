@@ -1,9 +1,10 @@
 use anyhow::Result;
-use chrono::Utc;
 use opendal::Operator;
-use rand::random;
 use serde_json::Value;
 
+use crate::clock;
+use crate::storage;
+
 const VIEW_DIR: &str = "materialized_views";
 
 fn views_root(ws_path: &str) -> String {
@@ -27,7 +28,7 @@ async fn ensure_views_dir(op: &Operator, ws_path: &str) -> Result<()> {
 }
 
 async fn write_json(op: &Operator, path: &str, value: &Value) -> Result<()> {
-    op.write(path, serde_json::to_vec_pretty(value)?).await?;
+    storage::publish_atomic(op, path, serde_json::to_vec_pretty(value)?).await?;
     Ok(())
 }
 
@@ -49,8 +50,8 @@ pub async fn create_or_update_view(
         op.create_dir(&view_dir).await?;
     }
 
-    let now = Utc::now().to_rfc3339();
-    let snapshot_id: u64 = random();
+    let now = clock::now().to_rfc3339();
+    let snapshot_id: u64 = clock::new_uuid().as_u64_pair().0;
     let created_at = if op.exists(&meta_path(ws_path, sql_id)).await? {
         read_json(op, &meta_path(ws_path, sql_id))
             .await?
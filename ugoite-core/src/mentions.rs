@@ -0,0 +1,221 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use crate::clock;
+use crate::space_lock;
+use crate::storage;
+
+/// Space lease serializing mention feed rewrites across processes.
+const MENTIONS_LEASE: &str = "mentions";
+/// Oldest mentions are dropped once a user's feed grows past this.
+const MAX_FEED_ITEMS: usize = 1000;
+const EXCERPT_MAX_CHARS: usize = 200;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Mention {
+    pub id: String,
+    pub entry_id: String,
+    pub entry_title: String,
+    /// User who wrote the revision containing the mention.
+    pub author: String,
+    pub revision_id: String,
+    pub created_at: f64,
+    /// The line the mention appeared on.
+    pub excerpt: String,
+    #[serde(default)]
+    pub read: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct MentionFeed {
+    #[serde(default)]
+    items: Vec<Mention>,
+}
+
+/// The entry revision a set of mentions was written in.
+pub struct MentionSource<'a> {
+    pub entry_id: &'a str,
+    pub entry_title: &'a str,
+    pub revision_id: &'a str,
+    pub author: &'a str,
+    pub timestamp: f64,
+}
+
+fn mention_regex() -> &'static Regex {
+    static MENTION_REGEX: OnceLock<Regex> = OnceLock::new();
+    MENTION_REGEX.get_or_init(|| {
+        Regex::new(r"(?:^|[\s(\[,;])@([A-Za-z0-9_][A-Za-z0-9_.-]*)")
+            .expect("mention regex must compile")
+    })
+}
+
+/// `@name` mentions in a line of text, in order and without duplicates.
+/// Email addresses are not mentions because `@` must follow whitespace or punctuation.
+pub fn parse_mentions(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for caps in mention_regex().captures_iter(text) {
+        let name = caps[1].trim_end_matches(['.', '-']).to_string();
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Mentioned users with the first line mentioning each, skipping fenced code.
+fn mentions_by_user(markdown: &str) -> Vec<(String, String)> {
+    let mut found: Vec<(String, String)> = Vec::new();
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        for name in parse_mentions(line) {
+            if !found.iter().any(|(user, _)| *user == name) {
+                found.push((name, line.trim().chars().take(EXCERPT_MAX_CHARS).collect()));
+            }
+        }
+    }
+    found
+}
+
+fn feed_path(ws_path: &str, user_id: &str) -> String {
+    let user_hash = hex::encode(Sha256::digest(user_id.as_bytes()));
+    format!(
+        "{}/mentions/{}.json",
+        ws_path.trim_end_matches('/'),
+        user_hash
+    )
+}
+
+async fn read_feed(op: &Operator, ws_path: &str, user_id: &str) -> Result<MentionFeed> {
+    match op.read(&feed_path(ws_path, user_id)).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("Mention feed for {} is malformed: {}", user_id, e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(MentionFeed::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn write_feed(op: &Operator, ws_path: &str, user_id: &str, feed: &MentionFeed) -> Result<()> {
+    storage::publish_atomic(op, &feed_path(ws_path, user_id), serde_json::to_vec(feed)?).await
+}
+
+/// Add a feed item for each user mentioned in `content` but not in `previous`,
+/// so re-saving an entry does not notify the same users again. Authors are
+/// never notified of their own mentions.
+pub async fn record_mentions(
+    op: &Operator,
+    ws_path: &str,
+    source: &MentionSource<'_>,
+    previous: Option<&str>,
+    content: &str,
+) -> Result<Vec<String>> {
+    let already: BTreeSet<String> = previous
+        .map(mentions_by_user)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(user, _)| user)
+        .collect();
+    let added: Vec<(String, String)> = mentions_by_user(content)
+        .into_iter()
+        .filter(|(user, _)| !already.contains(user) && user != source.author)
+        .collect();
+    if added.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    space_lock::with_space_lease(op, ws_path, MENTIONS_LEASE, |_| async {
+        for (user, excerpt) in &added {
+            let mut feed = read_feed(op, ws_path, user).await?;
+            feed.items.push(Mention {
+                id: clock::new_uuid().simple().to_string(),
+                entry_id: source.entry_id.to_string(),
+                entry_title: source.entry_title.to_string(),
+                author: source.author.to_string(),
+                revision_id: source.revision_id.to_string(),
+                created_at: source.timestamp,
+                excerpt: excerpt.clone(),
+                read: false,
+            });
+            if feed.items.len() > MAX_FEED_ITEMS {
+                let excess = feed.items.len() - MAX_FEED_ITEMS;
+                feed.items.drain(..excess);
+            }
+            write_feed(op, ws_path, user, &feed).await?;
+        }
+        Ok(())
+    })
+    .await?;
+    Ok(added.into_iter().map(|(user, _)| user).collect())
+}
+
+/// [`record_mentions`] for a write that is already durable. A failure is
+/// logged rather than returned, so the committed create or update still
+/// reports success.
+pub(crate) async fn record_mentions_after_commit(
+    op: &Operator,
+    ws_path: &str,
+    source: &MentionSource<'_>,
+    previous: Option<&str>,
+    content: &str,
+) {
+    if let Err(error) = record_mentions(op, ws_path, source, previous, content).await {
+        eprintln!(
+            "failed to record mentions after commit (ws_path={}, entry_id={}): {}",
+            ws_path, source.entry_id, error
+        );
+    }
+}
+
+/// A user's mentions newer than `since` (seconds since the epoch), newest first.
+pub async fn list_mentions(
+    op: &Operator,
+    ws_path: &str,
+    user_id: &str,
+    since: Option<f64>,
+) -> Result<Vec<Mention>> {
+    let mut items: Vec<Mention> = read_feed(op, ws_path, user_id)
+        .await?
+        .items
+        .into_iter()
+        .filter(|item| since.is_none_or(|since| item.created_at > since))
+        .collect();
+    items.sort_by(|a, b| b.created_at.total_cmp(&a.created_at));
+    Ok(items)
+}
+
+/// Mark the given mentions, or every mention when `mention_ids` is `None`, as
+/// read. Returns how many changed.
+pub async fn mark_mentions_read(
+    op: &Operator,
+    ws_path: &str,
+    user_id: &str,
+    mention_ids: Option<&[String]>,
+) -> Result<usize> {
+    space_lock::with_space_lease(op, ws_path, MENTIONS_LEASE, |_| async {
+        let mut feed = read_feed(op, ws_path, user_id).await?;
+        let mut changed = 0;
+        for item in feed.items.iter_mut() {
+            if !item.read && mention_ids.is_none_or(|ids| ids.contains(&item.id)) {
+                item.read = true;
+                changed += 1;
+            }
+        }
+        if changed > 0 {
+            write_feed(op, ws_path, user_id, &feed).await?;
+        }
+        Ok(changed)
+    })
+    .await
+}
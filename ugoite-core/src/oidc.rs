@@ -0,0 +1,438 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use opendal::Operator;
+use rsa::pkcs1v15::{Signature, VerifyingKey};
+use rsa::signature::Verifier;
+use rsa::{BigUint, RsaPublicKey};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::Sha256;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::storage;
+
+const OIDC_CONFIG_PATH: &str = "auth/oidc.json";
+/// How long a provider's discovery document and JWKS are reused.
+const PROVIDER_CACHE_TTL: Duration = Duration::from_secs(3600);
+/// Minimum gap between JWKS refetches triggered by an unknown key id.
+const JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+/// Allowed clock skew when checking `exp`, `nbf` and `iat`.
+const CLOCK_SKEW_SECONDS: f64 = 60.0;
+
+/// Rules turning provider claims into a ugoite identity. Claim names may be
+/// dotted paths into nested objects, e.g. `realm_access.roles`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClaimMapping {
+    #[serde(default = "default_user_id_claim")]
+    pub user_id_claim: String,
+    /// Prepended to the mapped user id, e.g. `okta:`, to keep providers apart.
+    #[serde(default)]
+    pub user_id_prefix: String,
+    #[serde(default = "default_display_name_claim")]
+    pub display_name_claim: String,
+    /// Claims that must be present with exactly these values.
+    #[serde(default)]
+    pub required_claims: BTreeMap<String, Value>,
+    /// When set, the `email` claim must end in one of these domains.
+    #[serde(default)]
+    pub allowed_email_domains: Vec<String>,
+    #[serde(default)]
+    pub scope_rules: Vec<ScopeRule>,
+}
+
+/// Grants `scopes` when `claim` equals `value` or is an array containing it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScopeRule {
+    pub claim: String,
+    pub value: Value,
+    pub scopes: Vec<String>,
+}
+
+fn default_user_id_claim() -> String {
+    "sub".to_string()
+}
+
+fn default_display_name_claim() -> String {
+    "name".to_string()
+}
+
+impl Default for ClaimMapping {
+    fn default() -> Self {
+        Self {
+            user_id_claim: default_user_id_claim(),
+            user_id_prefix: String::new(),
+            display_name_claim: default_display_name_claim(),
+            required_claims: BTreeMap::new(),
+            allowed_email_domains: Vec::new(),
+            scope_rules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OidcProvider {
+    pub issuer: String,
+    /// Expected `aud` of ID tokens.
+    pub client_id: String,
+    #[serde(default)]
+    pub claim_mapping: ClaimMapping,
+}
+
+/// Identity providers by name, stored at `auth/oidc.json`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OidcConfig {
+    #[serde(default)]
+    pub providers: BTreeMap<String, OidcProvider>,
+}
+
+pub async fn get_oidc_config(op: &Operator) -> Result<OidcConfig> {
+    match op.read(OIDC_CONFIG_PATH).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("{} is malformed: {}", OIDC_CONFIG_PATH, e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(OidcConfig::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+pub async fn save_oidc_config(op: &Operator, config: &OidcConfig) -> Result<()> {
+    for (name, provider) in &config.providers {
+        if provider.issuer.trim().is_empty() || provider.client_id.trim().is_empty() {
+            return Err(anyhow!(
+                "OIDC provider {} needs an issuer and a client_id",
+                name
+            ));
+        }
+    }
+    storage::publish_atomic(op, OIDC_CONFIG_PATH, serde_json::to_vec_pretty(config)?).await
+}
+
+/// Fetches discovery documents and JWKS over HTTP.
+#[async_trait]
+pub trait OidcFetcher: Send + Sync {
+    async fn get_json(&self, url: &str) -> Result<Value>;
+}
+
+pub struct HttpOidcFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpOidcFetcher {
+    pub fn new() -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent(concat!("ugoite-oidc/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl OidcFetcher for HttpOidcFetcher {
+    async fn get_json(&self, url: &str) -> Result<Value> {
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        let bytes = response.bytes().await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedProvider {
+    jwks_uri: String,
+    keys: Vec<Value>,
+    fetched_at: Instant,
+    jwks_fetched_at: Instant,
+}
+
+static PROVIDER_CACHE: OnceLock<Mutex<HashMap<String, CachedProvider>>> = OnceLock::new();
+
+fn provider_cache() -> Result<std::sync::MutexGuard<'static, HashMap<String, CachedProvider>>> {
+    PROVIDER_CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .map_err(|_| anyhow!("OIDC provider cache lock poisoned"))
+}
+
+/// Drop cached discovery documents and keys, e.g. after changing providers.
+pub fn clear_provider_cache() -> Result<()> {
+    provider_cache()?.clear();
+    Ok(())
+}
+
+fn discovery_url(issuer: &str) -> String {
+    format!(
+        "{}/.well-known/openid-configuration",
+        issuer.trim_end_matches('/')
+    )
+}
+
+async fn fetch_jwks(fetcher: &dyn OidcFetcher, jwks_uri: &str) -> Result<Vec<Value>> {
+    let jwks = fetcher.get_json(jwks_uri).await?;
+    jwks.get("keys")
+        .and_then(Value::as_array)
+        .cloned()
+        .ok_or_else(|| anyhow!("JWKS at {} has no keys", jwks_uri))
+}
+
+async fn load_provider(fetcher: &dyn OidcFetcher, issuer: &str) -> Result<CachedProvider> {
+    if let Some(cached) = provider_cache()?.get(issuer) {
+        if cached.fetched_at.elapsed() < PROVIDER_CACHE_TTL {
+            return Ok(cached.clone());
+        }
+    }
+    let discovery = fetcher.get_json(&discovery_url(issuer)).await?;
+    let discovered_issuer = discovery
+        .get("issuer")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if discovered_issuer.trim_end_matches('/') != issuer.trim_end_matches('/') {
+        return Err(anyhow!(
+            "Discovery document issuer {} does not match {}",
+            discovered_issuer,
+            issuer
+        ));
+    }
+    let jwks_uri = discovery
+        .get("jwks_uri")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("Discovery document for {} has no jwks_uri", issuer))?
+        .to_string();
+    let keys = fetch_jwks(fetcher, &jwks_uri).await?;
+    let now = Instant::now();
+    let provider = CachedProvider {
+        jwks_uri,
+        keys,
+        fetched_at: now,
+        jwks_fetched_at: now,
+    };
+    provider_cache()?.insert(issuer.to_string(), provider.clone());
+    Ok(provider)
+}
+
+/// The JWK for `kid`, refetching the JWKS once when the provider has rotated keys.
+async fn signing_key(fetcher: &dyn OidcFetcher, issuer: &str, kid: &str) -> Result<Value> {
+    let find = |keys: &[Value]| {
+        keys.iter()
+            .find(|key| key.get("kid").and_then(Value::as_str) == Some(kid))
+            .cloned()
+    };
+    let provider = load_provider(fetcher, issuer).await?;
+    if let Some(key) = find(&provider.keys) {
+        return Ok(key);
+    }
+    if provider.jwks_fetched_at.elapsed() < JWKS_REFRESH_INTERVAL {
+        return Err(anyhow!("Unknown ID token key id: {}", kid));
+    }
+    let keys = fetch_jwks(fetcher, &provider.jwks_uri).await?;
+    let key = find(&keys);
+    if let Some(cached) = provider_cache()?.get_mut(issuer) {
+        cached.keys = keys;
+        cached.jwks_fetched_at = Instant::now();
+    }
+    key.ok_or_else(|| anyhow!("Unknown ID token key id: {}", kid))
+}
+
+fn decode_segment(segment: &str) -> Result<Value> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|_| anyhow!("Malformed ID token"))?;
+    serde_json::from_slice(&bytes).map_err(|_| anyhow!("Malformed ID token"))
+}
+
+fn rsa_public_key(jwk: &Value) -> Result<RsaPublicKey> {
+    if jwk.get("kty").and_then(Value::as_str) != Some("RSA") {
+        return Err(anyhow!("Unsupported JWK key type"));
+    }
+    let component = |name: &str| -> Result<BigUint> {
+        let encoded = jwk
+            .get(name)
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("JWK is missing {}", name))?;
+        let bytes = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| anyhow!("JWK {} is not base64url", name))?;
+        Ok(BigUint::from_bytes_be(&bytes))
+    };
+    Ok(RsaPublicKey::new(component("n")?, component("e")?)?)
+}
+
+fn audience_matches(claims: &Map<String, Value>, client_id: &str) -> bool {
+    match claims.get("aud") {
+        Some(Value::String(aud)) => aud == client_id,
+        Some(Value::Array(items)) => items.iter().any(|item| item.as_str() == Some(client_id)),
+        _ => false,
+    }
+}
+
+/// Verify an RS256 ID token from `provider` and return its claims. Checks the
+/// signature against the provider's JWKS, `iss`, `aud`, `exp`, `nbf`, `iat`
+/// and, when given, `nonce`.
+pub async fn validate_id_token(
+    fetcher: &dyn OidcFetcher,
+    provider: &OidcProvider,
+    token: &str,
+    nonce: Option<&str>,
+) -> Result<Map<String, Value>> {
+    let parts: Vec<&str> = token.trim().split('.').collect();
+    if parts.len() != 3 {
+        return Err(anyhow!("Malformed ID token"));
+    }
+    let header = decode_segment(parts[0])?;
+    if header.get("alg").and_then(Value::as_str) != Some("RS256") {
+        return Err(anyhow!("Unsupported ID token algorithm"));
+    }
+    let kid = header
+        .get("kid")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("ID token header has no kid"))?;
+    let jwk = signing_key(fetcher, &provider.issuer, kid).await?;
+    let signature_bytes = URL_SAFE_NO_PAD
+        .decode(parts[2])
+        .map_err(|_| anyhow!("Malformed ID token"))?;
+    let signature = Signature::try_from(signature_bytes.as_slice())
+        .map_err(|_| anyhow!("Malformed ID token signature"))?;
+    VerifyingKey::<Sha256>::new(rsa_public_key(&jwk)?)
+        .verify(format!("{}.{}", parts[0], parts[1]).as_bytes(), &signature)
+        .map_err(|_| anyhow!("Invalid ID token signature"))?;
+
+    let claims = decode_segment(parts[1])?
+        .as_object()
+        .cloned()
+        .ok_or_else(|| anyhow!("Malformed ID token"))?;
+    let issuer = claims
+        .get("iss")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if issuer.trim_end_matches('/') != provider.issuer.trim_end_matches('/') {
+        return Err(anyhow!(
+            "ID token issuer {} is not {}",
+            issuer,
+            provider.issuer
+        ));
+    }
+    if !audience_matches(&claims, &provider.client_id) {
+        return Err(anyhow!(
+            "ID token audience does not include {}",
+            provider.client_id
+        ));
+    }
+    let now = chrono::Utc::now().timestamp() as f64;
+    let exp = claims
+        .get("exp")
+        .and_then(Value::as_f64)
+        .ok_or_else(|| anyhow!("ID token has no exp"))?;
+    if exp + CLOCK_SKEW_SECONDS < now {
+        return Err(anyhow!("ID token has expired"));
+    }
+    for name in ["nbf", "iat"] {
+        if let Some(ts) = claims.get(name).and_then(Value::as_f64) {
+            if ts - CLOCK_SKEW_SECONDS > now {
+                return Err(anyhow!("ID token {} is in the future", name));
+            }
+        }
+    }
+    if let Some(expected) = nonce {
+        if claims.get("nonce").and_then(Value::as_str) != Some(expected) {
+            return Err(anyhow!("ID token nonce does not match"));
+        }
+    }
+    Ok(claims)
+}
+
+fn claim<'a>(claims: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    let mut segments = path.split('.');
+    let mut current = claims.get(segments.next()?)?;
+    for segment in segments {
+        current = current.get(segment)?;
+    }
+    Some(current)
+}
+
+fn claim_matches(claim_value: Option<&Value>, expected: &Value) -> bool {
+    match claim_value {
+        Some(Value::Array(items)) if !expected.is_array() => items.contains(expected),
+        Some(value) => value == expected,
+        None => false,
+    }
+}
+
+/// Map validated claims to an identity shaped like `authenticate_headers_core`
+/// identities, with `auth_method` `oidc`. Scopes granted by scope rules are
+/// enforced; without scope rules only role permissions apply.
+pub fn map_claims(provider: &OidcProvider, claims: &Map<String, Value>) -> Result<Value> {
+    let mapping = &provider.claim_mapping;
+    for (name, expected) in &mapping.required_claims {
+        if !claim_matches(claim(claims, name), expected) {
+            return Err(anyhow!(
+                "ID token claim {} does not have the required value",
+                name
+            ));
+        }
+    }
+    if !mapping.allowed_email_domains.is_empty() {
+        let email = claims
+            .get("email")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let domain = email
+            .rsplit_once('@')
+            .map(|(_, domain)| domain)
+            .unwrap_or_default();
+        if !mapping
+            .allowed_email_domains
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(domain))
+        {
+            return Err(anyhow!("Email domain is not allowed: {}", domain));
+        }
+    }
+    let subject = match claim(claims, &mapping.user_id_claim) {
+        Some(Value::String(value)) if !value.is_empty() => value.clone(),
+        Some(Value::Number(value)) => value.to_string(),
+        _ => {
+            return Err(anyhow!(
+                "ID token has no usable {} claim",
+                mapping.user_id_claim
+            ))
+        }
+    };
+    let mut scopes: Vec<String> = mapping
+        .scope_rules
+        .iter()
+        .filter(|rule| claim_matches(claim(claims, &rule.claim), &rule.value))
+        .flat_map(|rule| rule.scopes.iter().cloned())
+        .collect();
+    scopes.sort();
+    scopes.dedup();
+    Ok(json!({
+        "user_id": format!("{}{}", mapping.user_id_prefix, subject),
+        "principal_type": "user",
+        "display_name": claim(claims, &mapping.display_name_claim).and_then(Value::as_str),
+        "auth_method": "oidc",
+        "key_id": Value::Null,
+        "scopes": scopes,
+        "scope_enforced": !mapping.scope_rules.is_empty(),
+        "service_account_id": Value::Null,
+        "issuer": provider.issuer,
+    }))
+}
+
+/// Validate an ID token from the named provider in the stored OIDC config and
+/// map it to an identity.
+pub async fn authenticate_id_token(
+    op: &Operator,
+    fetcher: &dyn OidcFetcher,
+    provider_name: &str,
+    token: &str,
+    nonce: Option<&str>,
+) -> Result<Value> {
+    let config = get_oidc_config(op).await?;
+    let provider = config
+        .providers
+        .get(provider_name)
+        .ok_or_else(|| anyhow!("Unknown OIDC provider: {}", provider_name))?;
+    let claims = validate_id_token(fetcher, provider, token, nonce).await?;
+    map_claims(provider, &claims)
+}
@@ -0,0 +1,329 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+use crate::clock;
+use crate::entry;
+use crate::index;
+use crate::space;
+use crate::space_lock;
+use crate::storage;
+
+/// Root-level prefix holding the organization registry and its lease.
+const ORGS_ROOT: &str = "orgs";
+const ORGS_LEASE: &str = "orgs";
+/// Organization roles, lowest privilege first.
+const ORG_ROLES: [&str; 4] = ["viewer", "editor", "admin", "owner"];
+
+/// A governance unit above spaces. Each space belongs to at most one
+/// organization, and member roles cascade to every space it owns.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Org {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub spaces: Vec<String>,
+    /// User id -> organization role.
+    #[serde(default)]
+    pub members: BTreeMap<String, String>,
+    pub created_at: f64,
+    pub updated_at: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct OrgRegistry {
+    #[serde(default)]
+    orgs: BTreeMap<String, Org>,
+}
+
+fn registry_path() -> String {
+    format!("{}/orgs.json", ORGS_ROOT)
+}
+
+async fn read_registry(op: &Operator) -> Result<OrgRegistry> {
+    match op.read(&registry_path()).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("Organization registry is malformed: {}", e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(OrgRegistry::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn update_registry<T, F>(op: &Operator, mutate: F) -> Result<T>
+where
+    F: FnOnce(&mut OrgRegistry) -> Result<T>,
+{
+    space_lock::with_space_lease(op, ORGS_ROOT, ORGS_LEASE, |_| async {
+        let mut registry = read_registry(op).await?;
+        let result = mutate(&mut registry)?;
+        storage::publish_atomic(op, &registry_path(), serde_json::to_vec_pretty(&registry)?)
+            .await?;
+        Ok(result)
+    })
+    .await
+}
+
+fn org_mut<'a>(registry: &'a mut OrgRegistry, org_id: &str) -> Result<&'a mut Org> {
+    registry
+        .orgs
+        .get_mut(org_id)
+        .ok_or_else(|| anyhow!("Organization not found: {}", org_id))
+}
+
+fn validate_name(registry: &OrgRegistry, org_id: &str, name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("Organization name must not be empty"));
+    }
+    let taken = registry
+        .orgs
+        .values()
+        .any(|org| org.id != org_id && org.name.eq_ignore_ascii_case(name));
+    if taken {
+        return Err(anyhow!("Organization already exists: {}", name));
+    }
+    Ok(name.to_string())
+}
+
+fn role_rank(role: &str) -> Option<usize> {
+    ORG_ROLES.iter().position(|known| *known == role)
+}
+
+fn clean_description(description: Option<&str>) -> Option<String> {
+    description
+        .map(str::trim)
+        .filter(|description| !description.is_empty())
+        .map(str::to_string)
+}
+
+pub async fn create_org(op: &Operator, name: &str, description: Option<&str>) -> Result<Org> {
+    update_registry(op, |registry| {
+        let id = clock::new_uuid().to_string();
+        let now = entry::now_ts();
+        let org = Org {
+            name: validate_name(registry, &id, name)?,
+            id: id.clone(),
+            description: clean_description(description),
+            spaces: Vec::new(),
+            members: BTreeMap::new(),
+            created_at: now,
+            updated_at: now,
+        };
+        registry.orgs.insert(id, org.clone());
+        Ok(org)
+    })
+    .await
+}
+
+/// Rename an organization or change its description; an empty description
+/// clears it.
+pub async fn update_org(
+    op: &Operator,
+    org_id: &str,
+    name: Option<&str>,
+    description: Option<&str>,
+) -> Result<Org> {
+    update_registry(op, |registry| {
+        let name = name
+            .map(|name| validate_name(registry, org_id, name))
+            .transpose()?;
+        let org = org_mut(registry, org_id)?;
+        if let Some(name) = name {
+            org.name = name;
+        }
+        if description.is_some() {
+            org.description = clean_description(description);
+        }
+        org.updated_at = entry::now_ts();
+        Ok(org.clone())
+    })
+    .await
+}
+
+/// Delete an organization. Its spaces must be released first so no space
+/// silently loses the roles that cascade to it.
+pub async fn delete_org(op: &Operator, org_id: &str) -> Result<()> {
+    update_registry(op, |registry| {
+        let org = org_mut(registry, org_id)?;
+        if !org.spaces.is_empty() {
+            return Err(anyhow!(
+                "Organization {} still owns spaces: {}",
+                org_id,
+                org.spaces.join(", ")
+            ));
+        }
+        registry.orgs.remove(org_id);
+        Ok(())
+    })
+    .await
+}
+
+pub async fn get_org(op: &Operator, org_id: &str) -> Result<Org> {
+    read_registry(op)
+        .await?
+        .orgs
+        .remove(org_id)
+        .ok_or_else(|| anyhow!("Organization not found: {}", org_id))
+}
+
+/// Organizations sorted by name, optionally only those `user_id` belongs to.
+pub async fn list_orgs(op: &Operator, user_id: Option<&str>) -> Result<Vec<Org>> {
+    let mut orgs: Vec<Org> = read_registry(op)
+        .await?
+        .orgs
+        .into_values()
+        .filter(|org| user_id.is_none_or(|user_id| org.members.contains_key(user_id)))
+        .collect();
+    orgs.sort_by_key(|org| org.name.to_lowercase());
+    Ok(orgs)
+}
+
+/// Put a space under an organization. A space belongs to at most one.
+pub async fn assign_space(op: &Operator, org_id: &str, space_id: &str) -> Result<Org> {
+    if !space::space_exists(op, space_id).await? {
+        return Err(anyhow!("Space not found: {}", space_id));
+    }
+    update_registry(op, |registry| {
+        if let Some(owner) = registry
+            .orgs
+            .values()
+            .find(|org| org.id != org_id && org.spaces.iter().any(|id| id == space_id))
+        {
+            return Err(anyhow!(
+                "Space {} already belongs to organization {}",
+                space_id,
+                owner.id
+            ));
+        }
+        let org = org_mut(registry, org_id)?;
+        if !org.spaces.iter().any(|id| id == space_id) {
+            org.spaces.push(space_id.to_string());
+            org.spaces.sort();
+            org.updated_at = entry::now_ts();
+        }
+        Ok(org.clone())
+    })
+    .await
+}
+
+pub async fn release_space(op: &Operator, org_id: &str, space_id: &str) -> Result<Org> {
+    update_registry(op, |registry| {
+        let org = org_mut(registry, org_id)?;
+        let before = org.spaces.len();
+        org.spaces.retain(|id| id != space_id);
+        if org.spaces.len() == before {
+            return Err(anyhow!(
+                "Space {} does not belong to organization {}",
+                space_id,
+                org_id
+            ));
+        }
+        org.updated_at = entry::now_ts();
+        Ok(org.clone())
+    })
+    .await
+}
+
+/// Grant `user_id` an organization role, or remove them with `None`.
+pub async fn set_org_member(
+    op: &Operator,
+    org_id: &str,
+    user_id: &str,
+    role: Option<&str>,
+) -> Result<Org> {
+    let user_id = user_id.trim();
+    if user_id.is_empty() {
+        return Err(anyhow!("Organization member id must not be empty"));
+    }
+    if let Some(role) = role {
+        if role_rank(role).is_none() {
+            return Err(anyhow!(
+                "Unknown organization role: {} (expected one of {})",
+                role,
+                ORG_ROLES.join(", ")
+            ));
+        }
+    }
+    update_registry(op, |registry| {
+        let org = org_mut(registry, org_id)?;
+        match role {
+            Some(role) => {
+                org.members.insert(user_id.to_string(), role.to_string());
+            }
+            None => {
+                org.members.remove(user_id);
+            }
+        }
+        org.updated_at = entry::now_ts();
+        Ok(org.clone())
+    })
+    .await
+}
+
+pub async fn org_for_space(op: &Operator, space_id: &str) -> Result<Option<Org>> {
+    Ok(read_registry(op)
+        .await?
+        .orgs
+        .into_values()
+        .find(|org| org.spaces.iter().any(|id| id == space_id)))
+}
+
+/// The space role `user_id` inherits from the organization owning `space_id`.
+/// Organization owners and admins administer member spaces; space ownership
+/// itself never cascades.
+pub async fn resolve_org_role(
+    op: &Operator,
+    space_id: &str,
+    user_id: &str,
+) -> Result<Option<String>> {
+    let Some(org) = org_for_space(op, space_id).await? else {
+        return Ok(None);
+    };
+    Ok(org.members.get(user_id).map(|role| match role.as_str() {
+        "owner" => "admin".to_string(),
+        role => role.to_string(),
+    }))
+}
+
+fn add_counts(total: &mut Map<String, Value>, counts: Option<&Map<String, Value>>) {
+    for (key, count) in counts.into_iter().flatten() {
+        let current = total.get(key).and_then(Value::as_u64).unwrap_or(0);
+        let count = count.as_u64().unwrap_or(0);
+        total.insert(key.clone(), Value::from(current + count));
+    }
+}
+
+/// Space stats for every space of an organization plus their totals.
+pub async fn get_org_stats(op: &Operator, org_id: &str) -> Result<Value> {
+    let org = get_org(op, org_id).await?;
+    let mut spaces = Map::new();
+    let mut entry_count = 0;
+    let mut form_counts = Map::new();
+    let mut tag_counts = Map::new();
+    for space_id in &org.spaces {
+        let stats = index::get_space_stats(op, &format!("spaces/{}", space_id)).await?;
+        entry_count += stats["entry_count"].as_u64().unwrap_or(0);
+        let forms: Map<String, Value> = stats["form_stats"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(form, form_stats)| (form.clone(), form_stats["count"].clone()))
+            .collect();
+        add_counts(&mut form_counts, Some(&forms));
+        add_counts(&mut tag_counts, stats["tag_counts"].as_object());
+        spaces.insert(space_id.clone(), stats);
+    }
+    Ok(json!({
+        "org_id": org.id,
+        "space_count": org.spaces.len(),
+        "member_count": org.members.len(),
+        "entry_count": entry_count,
+        "form_counts": form_counts,
+        "tag_counts": tag_counts,
+        "spaces": spaces,
+    }))
+}
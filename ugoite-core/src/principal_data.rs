@@ -0,0 +1,304 @@
+use anyhow::{anyhow, Result};
+use chrono::{SecondsFormat, Utc};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::asset;
+use crate::audit::{self, AuditListOptions};
+use crate::entry;
+use crate::entry_acl::{self, EntryVisibility, PrincipalKind};
+use crate::entry_metadata;
+use crate::form;
+use crate::legal_hold;
+use crate::space;
+
+/// Scope required to erase a principal's data.
+pub const PRINCIPAL_ERASE_SCOPE: &str = "principal_erase";
+const AUDIT_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct ErasureReport {
+    /// Identifier that replaces the user id in authorship and audit records.
+    pub pseudonym: String,
+    pub entries_anonymized: Vec<String>,
+    pub entries_removed: Vec<String>,
+    /// Private entries kept because a legal hold covers them.
+    pub retained_by_hold: Vec<String>,
+    pub assets_removed: Vec<String>,
+    pub audit_events_anonymized: usize,
+}
+
+fn validate_user_id(user_id: &str) -> Result<&str> {
+    let user_id = user_id.trim();
+    if user_id.is_empty() {
+        return Err(anyhow!("user_id must not be empty"));
+    }
+    Ok(user_id)
+}
+
+fn asset_ids(row: &entry::EntryRow) -> impl Iterator<Item = String> + '_ {
+    row.assets
+        .iter()
+        .filter_map(|asset| asset.get("id").and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+async fn actor_audit_events(op: &Operator, space_id: &str, user_id: &str) -> Result<Vec<Value>> {
+    let mut events = Vec::new();
+    loop {
+        let page = audit::list_audit_events(
+            op,
+            space_id,
+            AuditListOptions {
+                offset: events.len(),
+                limit: AUDIT_PAGE_SIZE,
+                actor_user_id: Some(user_id.to_string()),
+                ..AuditListOptions::default()
+            },
+        )
+        .await?;
+        let items = page["items"].as_array().cloned().unwrap_or_default();
+        let total = page["total"].as_u64().unwrap_or(0) as usize;
+        let fetched = items.len();
+        events.extend(items);
+        if fetched == 0 || events.len() >= total {
+            return Ok(events);
+        }
+    }
+}
+
+/// Collect everything a space holds about a user: entries they authored, the
+/// assets those entries reference, entry ACLs naming them and their audit events.
+pub async fn export_principal_data(op: &Operator, ws_path: &str, user_id: &str) -> Result<Value> {
+    let user_id = validate_user_id(user_id)?;
+    let space_id = space::space_id_from_ws_path(ws_path);
+    let mut entries = Vec::new();
+    let mut referenced_assets = BTreeSet::new();
+
+    for form_name in form::list_form_names(op, ws_path).await? {
+        let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+        let mut authored: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+        for rev in entry::list_form_revision_rows(op, ws_path, &form_name, &form_def).await? {
+            if rev.author == user_id {
+                authored
+                    .entry(rev.entry_id.clone())
+                    .or_default()
+                    .push(json!({
+                        "revision_id": rev.revision_id,
+                        "timestamp": rev.timestamp,
+                    }));
+            }
+        }
+        let mut rows = entry::list_form_entry_rows(op, ws_path, &form_name, &form_def).await?;
+        rows.sort_by(|a, b| a.entry_id.cmp(&b.entry_id));
+        for row in rows {
+            let Some(mut revisions) = authored.remove(&row.entry_id) else {
+                continue;
+            };
+            revisions.sort_by(|a, b| {
+                let a_ts = a["timestamp"].as_f64().unwrap_or(0.0);
+                let b_ts = b["timestamp"].as_f64().unwrap_or(0.0);
+                a_ts.partial_cmp(&b_ts).unwrap_or(std::cmp::Ordering::Equal)
+            });
+            referenced_assets.extend(asset_ids(&row));
+            let markdown = entry::render_markdown_for_form(
+                &row.title,
+                &form_name,
+                &row.tags,
+                &row.fields,
+                &row.extra_attributes,
+                &form_def,
+            );
+            entries.push(json!({
+                "id": row.entry_id,
+                "form": form_name,
+                "title": row.title,
+                "created_at": row.created_at,
+                "updated_at": row.updated_at,
+                "deleted": row.deleted,
+                "markdown": markdown,
+                "assets": row.assets,
+                "revisions": revisions,
+            }));
+        }
+    }
+
+    let assets: Vec<asset::AssetInfo> = if referenced_assets.is_empty() {
+        Vec::new()
+    } else {
+        asset::list_assets(op, ws_path)
+            .await?
+            .into_iter()
+            .filter(|info| referenced_assets.contains(&info.id))
+            .collect()
+    };
+
+    let mut entry_acls = Vec::new();
+    let mut acls: Vec<_> = entry_acl::list_entry_acls(op, ws_path)
+        .await?
+        .into_iter()
+        .collect();
+    acls.sort_by(|a, b| a.0.cmp(&b.0));
+    for (entry_id, acl) in acls {
+        let shared = acl
+            .shared_with
+            .iter()
+            .any(|p| p.kind == PrincipalKind::User && p.id == user_id);
+        if acl.owner == user_id || shared {
+            entry_acls.push(json!({"entry_id": entry_id, "acl": acl}));
+        }
+    }
+
+    Ok(json!({
+        "user_id": user_id,
+        "space_id": space_id,
+        "exported_at": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        "entries": entries,
+        "assets": assets,
+        "entry_acls": entry_acls,
+        "audit_events": actor_audit_events(op, space_id, user_id).await?,
+    }))
+}
+
+/// Erase a user from a space.
+///
+/// Authorship in entries and revisions, entry ACLs and audit events is
+/// rewritten to a random pseudonym. Private entries owned by the user are
+/// removed together with their history and any assets only they referenced,
+/// unless a legal hold covers them. The erasure itself is audited under the
+/// pseudonym.
+pub async fn erase_principal_data(
+    op: &Operator,
+    ws_path: &str,
+    user_id: &str,
+    actor: &str,
+    scopes: &[String],
+) -> Result<ErasureReport> {
+    if !scopes.iter().any(|scope| scope == PRINCIPAL_ERASE_SCOPE) {
+        return Err(anyhow!(
+            "Erasing principal data requires the '{}' scope",
+            PRINCIPAL_ERASE_SCOPE
+        ));
+    }
+    let user_id = validate_user_id(user_id)?;
+    let space_id = space::space_id_from_ws_path(ws_path);
+    let mut report = ErasureReport {
+        pseudonym: format!("erased-{}", uuid::Uuid::new_v4().simple()),
+        ..ErasureReport::default()
+    };
+
+    let acls = entry_acl::list_entry_acls(op, ws_path).await?;
+    let private: BTreeSet<&str> = acls
+        .iter()
+        .filter(|(_, acl)| acl.owner == user_id && acl.visibility == EntryVisibility::Private)
+        .map(|(entry_id, _)| entry_id.as_str())
+        .collect();
+    let mut removed_assets = BTreeSet::new();
+
+    for form_name in form::list_form_names(op, ws_path).await? {
+        let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+        let mut entry_rows =
+            entry::list_form_entry_rows(op, ws_path, &form_name, &form_def).await?;
+        let mut revision_rows =
+            entry::list_form_revision_rows(op, ws_path, &form_name, &form_def).await?;
+
+        let mut removed = BTreeSet::new();
+        for row in &entry_rows {
+            if !private.contains(row.entry_id.as_str()) {
+                continue;
+            }
+            let holds = legal_hold::holds_for_entry(op, ws_path, &form_name, &row.entry_id).await?;
+            if holds.is_empty() {
+                removed.insert(row.entry_id.clone());
+                removed_assets.extend(asset_ids(row));
+            } else {
+                report.retained_by_hold.push(row.entry_id.clone());
+            }
+        }
+
+        let mut anonymized = BTreeSet::new();
+        entry_rows.retain(|row| !removed.contains(&row.entry_id));
+        revision_rows.retain(|rev| !removed.contains(&rev.entry_id));
+        for row in entry_rows.iter_mut() {
+            if row.author == user_id {
+                row.author = report.pseudonym.clone();
+                anonymized.insert(row.entry_id.clone());
+            }
+        }
+        for rev in revision_rows.iter_mut() {
+            if rev.author == user_id {
+                rev.author = report.pseudonym.clone();
+                anonymized.insert(rev.entry_id.clone());
+            }
+        }
+
+        if !removed.is_empty() || !anonymized.is_empty() {
+            form::rewrite_form_tables(
+                op,
+                ws_path,
+                &form_name,
+                &form_def,
+                &entry_rows,
+                &revision_rows,
+            )
+            .await?;
+        }
+        report.entries_removed.extend(removed);
+        report.entries_anonymized.extend(anonymized);
+    }
+
+    for (entry_id, mut acl) in acls {
+        if report.entries_removed.contains(&entry_id) {
+            entry_acl::clear_entry_acl(op, ws_path, &entry_id).await?;
+            continue;
+        }
+        let before = acl.clone();
+        if acl.owner == user_id {
+            acl.owner = report.pseudonym.clone();
+        }
+        acl.shared_with
+            .retain(|p| !(p.kind == PrincipalKind::User && p.id == user_id));
+        if acl != before {
+            entry_acl::set_entry_acl(op, ws_path, &entry_id, &acl).await?;
+        }
+    }
+    for entry_id in &report.entries_removed {
+        entry_metadata::clear_entry_metadata(op, ws_path, entry_id, None).await?;
+    }
+
+    for asset_id in removed_assets {
+        // Assets still referenced by remaining entries are kept.
+        if asset::delete_asset(op, ws_path, &asset_id).await.is_ok() {
+            report.assets_removed.push(asset_id);
+        }
+    }
+
+    report.entries_anonymized.sort();
+    report.entries_removed.sort();
+    report.retained_by_hold.sort();
+    report.audit_events_anonymized =
+        audit::pseudonymize_audit_actor(op, space_id, user_id, &report.pseudonym).await?;
+    audit::append_audit_event(
+        op,
+        space_id,
+        &json!({
+            "action": "principal.erase",
+            "actor_user_id": actor,
+            "outcome": "success",
+            "target_type": "user",
+            "target_id": report.pseudonym,
+            "metadata": {
+                "entries_anonymized": report.entries_anonymized.len(),
+                "entries_removed": report.entries_removed.len(),
+                "retained_by_hold": report.retained_by_hold,
+                "assets_removed": report.assets_removed.len(),
+                "audit_events_anonymized": report.audit_events_anonymized,
+            },
+        }),
+        None,
+    )
+    .await?;
+    Ok(report)
+}
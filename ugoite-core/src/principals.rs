@@ -0,0 +1,440 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::clock;
+use crate::space_lock;
+use crate::storage;
+
+/// Root-level prefix holding the provisioned directory and its lease.
+const PRINCIPALS_ROOT: &str = "principals";
+const PRINCIPALS_LEASE: &str = "directory";
+const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+const GROUP_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:Group";
+/// Extension carrying the space roles a group grants its members.
+pub const GROUP_ROLES_SCHEMA: &str = "urn:ugoite:params:scim:schemas:extension:2.0:Group";
+/// Roles a group may grant, lowest privilege first.
+const GROUP_ROLES: [&str; 3] = ["viewer", "editor", "admin"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimMeta {
+    pub resource_type: String,
+    pub created: String,
+    pub last_modified: String,
+    pub version: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScimEmail {
+    pub value: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+/// A provisioned user. `user_name` is the ugoite user id that authentication
+/// resolves to.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUser {
+    #[serde(default)]
+    pub schemas: Vec<String>,
+    #[serde(default)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    pub user_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub emails: Vec<ScimEmail>,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    #[serde(default)]
+    pub meta: ScimMeta,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScimMember {
+    /// SCIM id of the member user.
+    pub value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupRoles {
+    /// Space id -> role granted to every member of the group.
+    #[serde(default)]
+    pub space_roles: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimGroup {
+    #[serde(default)]
+    pub schemas: Vec<String>,
+    #[serde(default)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    pub display_name: String,
+    #[serde(default)]
+    pub members: Vec<ScimMember>,
+    #[serde(default, rename = "urn:ugoite:params:scim:schemas:extension:2.0:Group")]
+    pub roles: GroupRoles,
+    #[serde(default)]
+    pub meta: ScimMeta,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct Directory {
+    #[serde(default)]
+    users: BTreeMap<String, ScimUser>,
+    #[serde(default)]
+    groups: BTreeMap<String, ScimGroup>,
+}
+
+/// What the directory says about a user in one space, for the authorization engine.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProvisionedAccess {
+    pub user_id: String,
+    pub scim_id: String,
+    pub active: bool,
+    /// Display names of the user's groups, usable as `user_group` principals.
+    pub groups: Vec<String>,
+    /// Highest role granted to the user's groups in the space.
+    pub role: Option<String>,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+fn directory_path() -> String {
+    format!("{}/directory.json", PRINCIPALS_ROOT)
+}
+
+fn now_iso() -> String {
+    Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+/// The counter inside a weak `W/"n"` version tag.
+fn version_number(version: &str) -> Option<u64> {
+    version
+        .strip_prefix("W/\"")?
+        .strip_suffix('"')?
+        .parse()
+        .ok()
+}
+
+fn stamp(meta: &mut ScimMeta, resource_type: &str, created: Option<&ScimMeta>) {
+    let now = now_iso();
+    let version = created
+        .and_then(|meta| version_number(&meta.version))
+        .map_or(1, |version| version + 1);
+    *meta = ScimMeta {
+        resource_type: resource_type.to_string(),
+        created: created.map_or_else(|| now.clone(), |meta| meta.created.clone()),
+        last_modified: now,
+        version: format!("W/\"{}\"", version),
+    };
+}
+
+async fn read_directory(op: &Operator) -> Result<Directory> {
+    match op.read(&directory_path()).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("Principal directory is malformed: {}", e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(Directory::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn update_directory<T, F>(op: &Operator, mutate: F) -> Result<T>
+where
+    F: FnOnce(&mut Directory) -> Result<T>,
+{
+    space_lock::with_space_lease(op, PRINCIPALS_ROOT, PRINCIPALS_LEASE, |_| async {
+        let mut directory = read_directory(op).await?;
+        let result = mutate(&mut directory)?;
+        storage::publish_atomic(
+            op,
+            &directory_path(),
+            serde_json::to_vec_pretty(&directory)?,
+        )
+        .await?;
+        Ok(result)
+    })
+    .await
+}
+
+fn parse_resource<T: serde::de::DeserializeOwned>(resource: &Value, kind: &str) -> Result<T> {
+    serde_json::from_value(resource.clone()).map_err(|e| anyhow!("Invalid SCIM {}: {}", kind, e))
+}
+
+fn validate_user(directory: &Directory, user: &ScimUser) -> Result<()> {
+    let user_name = user.user_name.trim();
+    if user_name.is_empty() {
+        return Err(anyhow!("userName is required"));
+    }
+    let taken = directory
+        .users
+        .values()
+        .any(|other| other.id != user.id && other.user_name.eq_ignore_ascii_case(user_name));
+    if taken {
+        return Err(anyhow!("userName already exists: {}", user_name));
+    }
+    Ok(())
+}
+
+fn validate_group(directory: &Directory, group: &ScimGroup) -> Result<()> {
+    if group.display_name.trim().is_empty() {
+        return Err(anyhow!("displayName is required"));
+    }
+    let taken = directory.groups.values().any(|other| {
+        other.id != group.id
+            && other
+                .display_name
+                .eq_ignore_ascii_case(group.display_name.trim())
+    });
+    if taken {
+        return Err(anyhow!(
+            "displayName already exists: {}",
+            group.display_name
+        ));
+    }
+    for member in &group.members {
+        if !directory.users.contains_key(&member.value) {
+            return Err(anyhow!("Group member not found: {}", member.value));
+        }
+    }
+    for (space_id, role) in &group.roles.space_roles {
+        if !GROUP_ROLES.contains(&role.as_str()) {
+            return Err(anyhow!("Invalid role for space {}: {}", space_id, role));
+        }
+    }
+    Ok(())
+}
+
+fn normalize_user(user: &mut ScimUser) {
+    user.schemas = vec![USER_SCHEMA.to_string()];
+    user.user_name = user.user_name.trim().to_string();
+}
+
+fn normalize_group(group: &mut ScimGroup) {
+    group.schemas = vec![GROUP_SCHEMA.to_string()];
+    if !group.roles.space_roles.is_empty() {
+        group.schemas.push(GROUP_ROLES_SCHEMA.to_string());
+    }
+    group.display_name = group.display_name.trim().to_string();
+}
+
+/// Parse a SCIM `attribute eq "value"` filter against the allowed attributes.
+fn parse_filter<'a>(filter: &'a str, attributes: &[&str]) -> Result<(String, &'a str)> {
+    let mut parts = filter.trim().splitn(3, ' ');
+    let (Some(attribute), Some(operator), Some(value)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(anyhow!("Unsupported filter: {}", filter));
+    };
+    let attribute = attributes
+        .iter()
+        .find(|allowed| allowed.eq_ignore_ascii_case(attribute))
+        .ok_or_else(|| anyhow!("Unsupported filter attribute: {}", attribute))?;
+    if !operator.eq_ignore_ascii_case("eq") {
+        return Err(anyhow!("Unsupported filter operator: {}", operator));
+    }
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(|| anyhow!("Filter value must be quoted: {}", filter))?;
+    Ok((attribute.to_string(), value))
+}
+
+/// Provision a user from a SCIM User resource. `id` and `meta` are assigned here.
+pub async fn create_user(op: &Operator, resource: &Value) -> Result<ScimUser> {
+    let mut user: ScimUser = parse_resource(resource, "User")?;
+    update_directory(op, |directory| {
+        user.id = clock::new_uuid().to_string();
+        normalize_user(&mut user);
+        validate_user(directory, &user)?;
+        stamp(&mut user.meta, "User", None);
+        directory.users.insert(user.id.clone(), user.clone());
+        Ok(user)
+    })
+    .await
+}
+
+/// Replace a user with a SCIM User resource (SCIM `PUT`).
+pub async fn replace_user(op: &Operator, id: &str, resource: &Value) -> Result<ScimUser> {
+    let mut user: ScimUser = parse_resource(resource, "User")?;
+    update_directory(op, |directory| {
+        let existing = directory
+            .users
+            .get(id)
+            .ok_or_else(|| anyhow!("User not found: {}", id))?;
+        let previous = existing.meta.clone();
+        user.id = id.to_string();
+        normalize_user(&mut user);
+        validate_user(directory, &user)?;
+        stamp(&mut user.meta, "User", Some(&previous));
+        directory.users.insert(user.id.clone(), user.clone());
+        Ok(user)
+    })
+    .await
+}
+
+/// Mark a user inactive. Deactivated users keep their group memberships but
+/// are denied by the authorization engine.
+pub async fn deactivate_user(op: &Operator, id: &str) -> Result<ScimUser> {
+    update_directory(op, |directory| {
+        let user = directory
+            .users
+            .get_mut(id)
+            .ok_or_else(|| anyhow!("User not found: {}", id))?;
+        user.active = false;
+        let previous = user.meta.clone();
+        stamp(&mut user.meta, "User", Some(&previous));
+        Ok(user.clone())
+    })
+    .await
+}
+
+pub async fn get_user(op: &Operator, id: &str) -> Result<ScimUser> {
+    read_directory(op)
+        .await?
+        .users
+        .remove(id)
+        .ok_or_else(|| anyhow!("User not found: {}", id))
+}
+
+/// Users matching an optional `userName eq "..."` or `externalId eq "..."` filter.
+pub async fn list_users(op: &Operator, filter: Option<&str>) -> Result<Vec<ScimUser>> {
+    let condition = filter
+        .map(|filter| parse_filter(filter, &["userName", "externalId"]))
+        .transpose()?;
+    let users = read_directory(op).await?.users.into_values();
+    Ok(users
+        .filter(|user| match &condition {
+            Some((attribute, value)) if attribute == "userName" => {
+                user.user_name.eq_ignore_ascii_case(value)
+            }
+            Some((_, value)) => user.external_id.as_deref() == Some(*value),
+            None => true,
+        })
+        .collect())
+}
+
+pub async fn create_group(op: &Operator, resource: &Value) -> Result<ScimGroup> {
+    let mut group: ScimGroup = parse_resource(resource, "Group")?;
+    update_directory(op, |directory| {
+        group.id = clock::new_uuid().to_string();
+        normalize_group(&mut group);
+        validate_group(directory, &group)?;
+        stamp(&mut group.meta, "Group", None);
+        directory.groups.insert(group.id.clone(), group.clone());
+        Ok(group)
+    })
+    .await
+}
+
+/// Replace a group, including its members and space roles (SCIM `PUT`).
+pub async fn replace_group(op: &Operator, id: &str, resource: &Value) -> Result<ScimGroup> {
+    let mut group: ScimGroup = parse_resource(resource, "Group")?;
+    update_directory(op, |directory| {
+        let previous = directory
+            .groups
+            .get(id)
+            .ok_or_else(|| anyhow!("Group not found: {}", id))?
+            .meta
+            .clone();
+        group.id = id.to_string();
+        normalize_group(&mut group);
+        validate_group(directory, &group)?;
+        stamp(&mut group.meta, "Group", Some(&previous));
+        directory.groups.insert(group.id.clone(), group.clone());
+        Ok(group)
+    })
+    .await
+}
+
+pub async fn delete_group(op: &Operator, id: &str) -> Result<()> {
+    update_directory(op, |directory| {
+        directory
+            .groups
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("Group not found: {}", id))
+    })
+    .await
+}
+
+pub async fn get_group(op: &Operator, id: &str) -> Result<ScimGroup> {
+    read_directory(op)
+        .await?
+        .groups
+        .remove(id)
+        .ok_or_else(|| anyhow!("Group not found: {}", id))
+}
+
+/// Groups matching an optional `displayName eq "..."` or `externalId eq "..."` filter.
+pub async fn list_groups(op: &Operator, filter: Option<&str>) -> Result<Vec<ScimGroup>> {
+    let condition = filter
+        .map(|filter| parse_filter(filter, &["displayName", "externalId"]))
+        .transpose()?;
+    let groups = read_directory(op).await?.groups.into_values();
+    Ok(groups
+        .filter(|group| match &condition {
+            Some((attribute, value)) if attribute == "displayName" => {
+                group.display_name.eq_ignore_ascii_case(value)
+            }
+            Some((_, value)) => group.external_id.as_deref() == Some(*value),
+            None => true,
+        })
+        .collect())
+}
+
+/// Resolve a ugoite user id against the directory for `space_id`. Returns
+/// `None` for users the directory does not know, so unprovisioned users keep
+/// their membership-based access.
+pub async fn resolve_provisioned_access(
+    op: &Operator,
+    space_id: &str,
+    user_id: &str,
+) -> Result<Option<ProvisionedAccess>> {
+    let directory = read_directory(op).await?;
+    let Some(user) = directory
+        .users
+        .values()
+        .find(|user| user.user_name.eq_ignore_ascii_case(user_id))
+    else {
+        return Ok(None);
+    };
+    let groups: Vec<&ScimGroup> = directory
+        .groups
+        .values()
+        .filter(|group| group.members.iter().any(|member| member.value == user.id))
+        .collect();
+    let role = groups
+        .iter()
+        .filter_map(|group| group.roles.space_roles.get(space_id))
+        .filter_map(|role| GROUP_ROLES.iter().position(|known| known == role))
+        .max()
+        .map(|rank| GROUP_ROLES[rank].to_string());
+    let mut group_names: Vec<String> = groups
+        .iter()
+        .map(|group| group.display_name.clone())
+        .collect();
+    group_names.sort();
+    Ok(Some(ProvisionedAccess {
+        user_id: user.user_name.clone(),
+        scim_id: user.id.clone(),
+        active: user.active,
+        groups: group_names,
+        role,
+    }))
+}
@@ -0,0 +1,415 @@
+use crate::clock;
+use crate::entry::{self, BulkUpdateFailure, EntryRow, IntegrityPayload, RevisionRow};
+use crate::field_crypto;
+use crate::form;
+use crate::integrity::IntegrityProvider;
+use crate::space;
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_INGEST_BATCH_SIZE: usize = 1000;
+
+fn default_ingest_batch_size() -> usize {
+    DEFAULT_INGEST_BATCH_SIZE
+}
+
+/// Properties of one entry to ingest. The entry is created when it does not
+/// exist yet and otherwise replaced by a new revision holding exactly these
+/// properties.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PropertyRow {
+    pub entry_id: String,
+    /// Defaults to the current title, or the entry id for new entries.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Defaults to the current tags, or none for new entries.
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub properties: Map<String, Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PropertyIngestOptions {
+    /// Rows written to each data file.
+    #[serde(default = "default_ingest_batch_size")]
+    pub batch_size: usize,
+}
+
+impl Default for PropertyIngestOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: DEFAULT_INGEST_BATCH_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct PropertyIngestReport {
+    pub form: String,
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub failed: Vec<BulkUpdateFailure>,
+    /// Data files appended to each of the form's tables.
+    pub batches: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftKind {
+    /// The entry's markdown does not reproduce its stored properties.
+    Markdown,
+    /// The entry has no revision at all.
+    MissingRevision,
+    /// The entry row and its latest revision hold different properties.
+    Revision,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PropertyDrift {
+    pub entry_id: String,
+    pub form: String,
+    pub kind: DriftKind,
+    /// Properties whose values disagree, in name order.
+    pub properties: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ReconcileReport {
+    pub entries_checked: usize,
+    pub drift: Vec<PropertyDrift>,
+}
+
+fn validate_entry_id(entry_id: &str) -> Result<()> {
+    if entry_id.is_empty()
+        || entry_id.contains('/')
+        || entry_id.contains('\\')
+        || entry_id.starts_with('.')
+    {
+        return Err(anyhow!("Invalid entry_id: {}", entry_id));
+    }
+    Ok(())
+}
+
+fn validate_line(kind: &str, value: &str) -> Result<()> {
+    if value.trim().is_empty() || value.contains(['\n', '\r']) {
+        return Err(anyhow!("Invalid {}: {:?}", kind, value));
+    }
+    Ok(())
+}
+
+/// Names of the properties that differ between two property maps.
+fn differing_properties(left: &Value, right: &Value) -> Vec<String> {
+    let empty = Map::new();
+    let left = left.as_object().unwrap_or(&empty);
+    let right = right.as_object().unwrap_or(&empty);
+    let mut names: Vec<String> = left
+        .keys()
+        .chain(right.keys())
+        .filter(|name| left.get(*name) != right.get(*name))
+        .cloned()
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Build the entry and revision rows for one property row. The row goes
+/// through the same markdown rendering and parsing as an edit, so the stored
+/// fields are exactly what the entry's markdown says.
+#[allow(clippy::too_many_arguments)]
+async fn ingest_row<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    form_def: &Value,
+    row: &PropertyRow,
+    existing: Option<(&EntryRow, Option<&RevisionRow>)>,
+    author: &str,
+    integrity: &I,
+) -> Result<(EntryRow, RevisionRow)> {
+    let (existing, parent) = existing.unzip();
+    validate_entry_id(&row.entry_id)?;
+    let title = row
+        .title
+        .clone()
+        .or_else(|| existing.map(|existing| existing.title.clone()))
+        .unwrap_or_else(|| row.entry_id.clone());
+    validate_line("title", &title)?;
+    let tags = row
+        .tags
+        .clone()
+        .or_else(|| existing.map(|existing| existing.tags.clone()))
+        .unwrap_or_default();
+    for tag in &tags {
+        validate_line("tag", tag)?;
+    }
+
+    let markdown = entry::render_markdown(
+        &title,
+        form_name,
+        &tags,
+        &Value::Object(row.properties.clone()),
+        &entry::form_field_names(form_def),
+    );
+    let (_, sections) = entry::parse_markdown(&markdown);
+    let (mut fields, extra_attributes) = entry::parse_entry_fields(&markdown, &sections, form_def)?;
+    field_crypto::seal_fields(
+        op,
+        ws_path,
+        form_def,
+        &mut fields,
+        existing.map(|existing| &existing.fields),
+    )
+    .await?;
+
+    let mut timestamp = entry::now_ts();
+    if let Some(existing) = existing {
+        if timestamp <= existing.updated_at {
+            timestamp = existing.updated_at + 0.001;
+        }
+    }
+    let integrity_payload = IntegrityPayload {
+        checksum: integrity.checksum(&markdown),
+        signature: integrity.signature(&markdown),
+    };
+    let revision_id = clock::new_uuid().to_string();
+    let parent_revision_id = parent.flatten().map(|parent| parent.revision_id.clone());
+    let entry_row = EntryRow {
+        entry_id: row.entry_id.clone(),
+        title,
+        form: form_name.to_string(),
+        tags,
+        links: existing
+            .map(|existing| existing.links.clone())
+            .unwrap_or_default(),
+        created_at: existing.map_or(timestamp, |existing| existing.created_at),
+        updated_at: timestamp,
+        fields,
+        extra_attributes,
+        revision_id: revision_id.clone(),
+        parent_revision_id: parent_revision_id.clone(),
+        assets: existing
+            .map(|existing| existing.assets.clone())
+            .unwrap_or_default(),
+        integrity: integrity_payload.clone(),
+        deleted: false,
+        deleted_at: None,
+        author: author.to_string(),
+    };
+    let revision = RevisionRow {
+        revision_id,
+        entry_id: row.entry_id.clone(),
+        parent_revision_id,
+        timestamp,
+        author: author.to_string(),
+        fields: entry_row.fields.clone(),
+        extra_attributes: entry_row.extra_attributes.clone(),
+        markdown_checksum: integrity_payload.checksum.clone(),
+        integrity: integrity_payload,
+        restored_from: None,
+        message: None,
+        revision_metadata: None,
+    };
+    Ok((entry_row, revision))
+}
+
+/// Append property rows for one form straight into its Iceberg tables.
+///
+/// Rows are written `batch_size` at a time, one data file per table per
+/// batch, instead of one small file per entry. Each row still becomes a
+/// markdown revision signed like an edit; rows that fail validation are
+/// reported and skipped. Mentions and watcher notifications are not
+/// produced; a single audit event records the ingestion.
+pub async fn ingest_entry_properties<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    rows: &[PropertyRow],
+    options: &PropertyIngestOptions,
+    author: &str,
+    integrity: &I,
+) -> Result<PropertyIngestReport> {
+    if options.batch_size == 0 {
+        return Err(anyhow!("Property ingest batch_size must be at least 1"));
+    }
+    let form_def = form::read_form_definition_for_write(op, ws_path, form_name).await?;
+    let latest = entry::latest_form_revisions(op, ws_path, form_name, &form_def).await?;
+    let mut existing: HashMap<String, EntryRow> = HashMap::new();
+    let mut other_forms: HashMap<String, String> = HashMap::new();
+    for (row_form, row) in entry::list_entry_rows(op, ws_path).await? {
+        if row_form == form_name && !row.deleted {
+            existing.insert(row.entry_id.clone(), row);
+        } else if row_form != form_name {
+            other_forms.insert(row.entry_id.clone(), row_form);
+        }
+    }
+
+    let mut report = PropertyIngestReport {
+        form: form_name.to_string(),
+        ..PropertyIngestReport::default()
+    };
+    let mut seen = HashSet::new();
+    for batch in rows.chunks(options.batch_size) {
+        let mut entry_rows = Vec::new();
+        let mut revisions = Vec::new();
+        let mut created = Vec::new();
+        let mut updated = Vec::new();
+        for row in batch {
+            let outcome = if !seen.insert(row.entry_id.clone()) {
+                Err(anyhow!("Duplicate entry_id in ingest: {}", row.entry_id))
+            } else if let Some(other) = other_forms.get(&row.entry_id) {
+                Err(anyhow!("Entry {} belongs to form {}", row.entry_id, other))
+            } else {
+                let current = existing
+                    .get(&row.entry_id)
+                    .map(|current| (current, latest.get(&row.entry_id)));
+                ingest_row(
+                    op, ws_path, form_name, &form_def, row, current, author, integrity,
+                )
+                .await
+                .map(|rows| (rows, current.is_some()))
+            };
+            match outcome {
+                Ok(((entry_row, revision), is_update)) => {
+                    if is_update {
+                        updated.push(row.entry_id.clone());
+                    } else {
+                        created.push(row.entry_id.clone());
+                    }
+                    entry_rows.push(entry_row);
+                    revisions.push(revision);
+                }
+                Err(err) => report.failed.push(BulkUpdateFailure {
+                    entry_id: row.entry_id.clone(),
+                    error: err.to_string(),
+                }),
+            }
+        }
+        if entry_rows.is_empty() {
+            continue;
+        }
+        entry::append_entry_rows(op, ws_path, form_name, &form_def, &entry_rows, &revisions)
+            .await?;
+        report.batches += 1;
+        report.created.extend(created);
+        report.updated.extend(updated);
+    }
+
+    let space_id = space::space_id_from_ws_path(ws_path);
+    crate::audit::append_audit_event(
+        op,
+        &space_id,
+        &serde_json::json!({
+            "action": "space.property_ingest",
+            "actor_user_id": author,
+            "outcome": "success",
+            "target_type": "form",
+            "target_id": form_name,
+            "metadata": {
+                "created": report.created.len(),
+                "updated": report.updated.len(),
+                "failed": report.failed.len(),
+                "batches": report.batches,
+            },
+        }),
+        None,
+    )
+    .await?;
+    Ok(report)
+}
+
+/// Drift of one live entry row against its revision and its markdown.
+fn entry_drift(
+    form_name: &str,
+    form_def: &Value,
+    row: &EntryRow,
+    revision: Option<&RevisionRow>,
+) -> Vec<PropertyDrift> {
+    let mut drift = Vec::new();
+    let mut stored = entry::merge_entry_fields(&row.fields, &row.extra_attributes);
+    field_crypto::mask_fields(&mut stored);
+    let found = |kind, properties, message| PropertyDrift {
+        entry_id: row.entry_id.clone(),
+        form: form_name.to_string(),
+        kind,
+        properties,
+        message,
+    };
+
+    match revision {
+        None => drift.push(found(DriftKind::MissingRevision, Vec::new(), None)),
+        Some(revision) => {
+            let mut recorded =
+                entry::merge_entry_fields(&revision.fields, &revision.extra_attributes);
+            field_crypto::mask_fields(&mut recorded);
+            let properties = differing_properties(&stored, &recorded);
+            if !properties.is_empty() {
+                drift.push(found(DriftKind::Revision, properties, None));
+            }
+        }
+    }
+
+    let markdown = entry::render_markdown_for_form(
+        &row.title,
+        form_name,
+        &row.tags,
+        &row.fields,
+        &row.extra_attributes,
+        form_def,
+    );
+    let (_, sections) = entry::parse_markdown(&markdown);
+    match entry::parse_entry_fields(&markdown, &sections, form_def) {
+        Ok((fields, extra_attributes)) => {
+            let parsed = entry::merge_entry_fields(&fields, &extra_attributes);
+            let properties = differing_properties(&stored, &parsed);
+            if !properties.is_empty() {
+                drift.push(found(DriftKind::Markdown, properties, None));
+            }
+        }
+        Err(err) => drift.push(found(
+            DriftKind::Markdown,
+            Vec::new(),
+            Some(err.to_string()),
+        )),
+    }
+    drift
+}
+
+/// Compare the live entries of `form_name`, or of every form, with their
+/// revisions and with what their markdown parses back to. Markdown is the
+/// source of truth, so any difference is reported as drift; nothing is
+/// rewritten.
+pub async fn reconcile_entry_properties(
+    op: &Operator,
+    ws_path: &str,
+    form_name: Option<&str>,
+) -> Result<ReconcileReport> {
+    let forms = match form_name {
+        Some(name) => vec![name.to_string()],
+        None => entry::list_form_names(op, ws_path).await?,
+    };
+    let mut report = ReconcileReport::default();
+    for form_name in forms {
+        let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+        let latest = entry::latest_form_revisions(op, ws_path, &form_name, &form_def).await?;
+        let mut rows = entry::list_form_entry_rows(op, ws_path, &form_name, &form_def).await?;
+        rows.retain(|row| !row.deleted);
+        rows.sort_by(|a, b| a.entry_id.cmp(&b.entry_id));
+        for row in &rows {
+            report.entries_checked += 1;
+            report.drift.extend(entry_drift(
+                &form_name,
+                &form_def,
+                row,
+                latest.get(&row.entry_id),
+            ));
+        }
+    }
+    Ok(report)
+}
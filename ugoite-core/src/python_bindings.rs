@@ -48,7 +48,68 @@ fn get_operator(_py: Python<'_>, config: &Bound<'_, PyDict>) -> PyResult<Operato
         .ok_or_else(|| PyValueError::new_err("Missing 'uri' in storage config"))?
         .extract::<String>()?;
 
-    storage::operator_from_uri(&uri).map_err(|e| PyValueError::new_err(e.to_string()))
+    let replica_uri = config
+        .get_item("replica_uri")?
+        .filter(|value| !value.is_none());
+    let Some(replica_uri) = replica_uri else {
+        return storage::operator_from_uri(&uri).map_err(|e| PyValueError::new_err(e.to_string()));
+    };
+    let mut replica_config = storage::replica::ReplicaConfig::default();
+    if let Some(staleness) = config
+        .get_item("replica_max_staleness_ms")?
+        .filter(|value| !value.is_none())
+    {
+        replica_config.max_staleness_ms = staleness.extract()?;
+    }
+    storage::operator_with_replica(&uri, &replica_uri.extract::<String>()?, &replica_config)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// Convert a spilled result set into a Python list one batch at a time, so at
+/// most one batch is held as JSON alongside the Python objects.
+async fn result_set_to_py(results: result_buffer::ResultSet) -> PyResult<PyObject> {
+    let list: Py<PyList> = Python::with_gil(|py| PyList::empty(py).unbind());
+    for index in 0..results.batch_count() {
+        let rows = results
+            .read_batch(index)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| -> PyResult<()> {
+            let list = list.bind(py);
+            for row in rows {
+                list.append(json_to_py(py, row)?)?;
+            }
+            Ok(())
+        })?;
+    }
+    results
+        .cleanup()
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    Ok(list.into_any())
+}
+
+/// Run `fut` on the shared runtime as a named, instrumented task.
+fn spawn_task<'py, F, T>(py: Python<'py>, name: &'static str, fut: F) -> PyResult<Bound<'py, PyAny>>
+where
+    F: std::future::Future<Output = PyResult<T>> + Send + 'static,
+    T: for<'a> IntoPyObject<'a> + Send + 'static,
+{
+    let guard = runtime::begin_task(name).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+        let result = fut.await;
+        guard.finish(result.is_ok());
+        result
+    })
+}
+
+/// Serialize a result to JSON bytes without holding the GIL.
+///
+/// Used by the `*_json` fast paths so large results skip `json_to_py` and the
+/// Python layer decodes them only when needed.
+fn json_bytes_to_py<T: serde::Serialize>(value: &T) -> PyResult<PyObject> {
+    let bytes = serde_json::to_vec(value).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    Python::with_gil(|py| PyBytes::new(py, &bytes).into_py_any(py))
 }
 
 pub(crate) fn json_to_py(py: Python<'_>, value: Value) -> PyResult<PyObject> {
@@ -152,6 +213,42 @@ fn authenticate_headers_core(
     json_to_py(py, result)
 }
 
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (
+    token,
+    bearer_tokens_json=None,
+    api_keys_json=None,
+    bearer_secrets=None,
+    active_kids=None,
+    revoked_key_ids=None,
+    bootstrap_token=None,
+    bootstrap_user_id=None,
+))]
+fn introspect_token_core(
+    py: Python<'_>,
+    token: String,
+    bearer_tokens_json: Option<String>,
+    api_keys_json: Option<String>,
+    bearer_secrets: Option<String>,
+    active_kids: Option<String>,
+    revoked_key_ids: Option<String>,
+    bootstrap_token: Option<String>,
+    bootstrap_user_id: Option<String>,
+) -> PyResult<PyObject> {
+    let result = auth::introspect_token(
+        &token,
+        bearer_tokens_json.as_deref(),
+        api_keys_json.as_deref(),
+        bearer_secrets.as_deref(),
+        active_kids.as_deref(),
+        revoked_key_ids.as_deref(),
+        bootstrap_token.as_deref(),
+        bootstrap_user_id.as_deref(),
+    );
+    json_to_py(py, result)
+}
+
 #[pyfunction]
 #[pyo3(signature = (
     bearer_tokens_json=None,
@@ -178,942 +275,4411 @@ fn auth_capabilities_snapshot_core(
     json_to_py(py, result)
 }
 
-// Space
+#[pyfunction]
+#[pyo3(signature = (
+    window_secs,
+    bearer_tokens_json=None,
+    api_keys_json=None,
+    revoked_key_ids=None,
+))]
+fn list_expiring_keys_core(
+    py: Python<'_>,
+    window_secs: f64,
+    bearer_tokens_json: Option<String>,
+    api_keys_json: Option<String>,
+    revoked_key_ids: Option<String>,
+) -> PyResult<PyObject> {
+    let result = auth::list_expiring_keys(
+        bearer_tokens_json.as_deref(),
+        api_keys_json.as_deref(),
+        revoked_key_ids.as_deref(),
+        window_secs,
+    );
+    json_to_py(py, result)
+}
 
 #[pyfunction]
-fn list_spaces<'a>(
+fn list_expiring_keys_stored<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
+    window_secs: f64,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let spaces = space::list_spaces(&op)
+    spawn_task(py, "list_expiring_keys_stored", async move {
+        let config = auth_config::load_auth_config(&op)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok(spaces)
+        let result = auth::list_expiring_keys_with_config(&config, window_secs);
+        Python::with_gil(|py| json_to_py(py, result))
     })
 }
 
 #[pyfunction]
-fn create_space<'a>(
+#[pyo3(signature = (identity_json=None))]
+fn authz_matrix_snapshot_core(py: Python<'_>, identity_json: Option<String>) -> PyResult<PyObject> {
+    let identity: Option<authz::AuthzIdentity> = identity_json
+        .map(|raw| serde_json::from_str(&raw))
+        .transpose()
+        .map_err(|e| PyValueError::new_err(format!("Invalid identity JSON: {e}")))?;
+    json_to_py(py, authz::authz_matrix_snapshot(identity.as_ref()))
+}
+
+#[pyfunction]
+fn load_auth_config<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    name: String,
 ) -> PyResult<Bound<'a, PyAny>> {
-    let uri: String = storage_config
-        .get_item("uri")?
-        .ok_or_else(|| PyValueError::new_err("Missing 'uri'"))?
-        .extract()?;
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        space::create_space(&op, &name, &uri)
+    spawn_task(py, "load_auth_config", async move {
+        let config = auth_config::load_auth_config(&op)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok(())
+        // Credentials and secrets stay in core; callers only see what changed.
+        let val = serde_json::json!({
+            "etag": config.etag,
+            "bearer_token_count": config.bearer_tokens.len(),
+            "api_key_count": config.api_keys.len(),
+            "signing_kids": config.signing_keys.keys().collect::<Vec<_>>(),
+            "active_kids": config.active_kids,
+            "revoked_key_ids": config.revoked_key_ids,
+        });
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id, scenario=None, entry_count=None, seed=None))]
-fn create_sample_space<'a>(
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (
+    storage_config,
+    authorization=None,
+    api_key=None,
+    bootstrap_token=None,
+    bootstrap_user_id=None,
+    audit_space_id=None,
+    request_method=None,
+    request_path=None,
+    request_id=None,
+    client_host=None,
+    user_agent=None,
+))]
+fn authenticate_headers_stored<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    scenario: Option<String>,
-    entry_count: Option<usize>,
-    seed: Option<u64>,
+    authorization: Option<String>,
+    api_key: Option<String>,
+    bootstrap_token: Option<String>,
+    bootstrap_user_id: Option<String>,
+    audit_space_id: Option<String>,
+    request_method: Option<String>,
+    request_path: Option<String>,
+    request_id: Option<String>,
+    client_host: Option<String>,
+    user_agent: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
-    let uri: String = storage_config
-        .get_item("uri")?
-        .ok_or_else(|| PyValueError::new_err("Missing 'uri'"))?
-        .extract()?;
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let options = sample_data::SampleDataOptions {
-            space_id,
-            scenario: scenario.unwrap_or_else(|| sample_data::DEFAULT_SCENARIO.to_string()),
-            entry_count: entry_count.unwrap_or(sample_data::DEFAULT_ENTRY_COUNT),
-            seed,
-            owner_user_id: None,
-        };
-        let summary = sample_data::create_sample_space(&op, &uri, &options)
+    let audit = audit_space_id.map(|audit_space_id| auth_config::AuthAuditContext {
+        audit_space_id,
+        request_method,
+        request_path,
+        request_id,
+        client_host,
+        user_agent,
+    });
+    spawn_task(py, "authenticate_headers_stored", async move {
+        let result = auth_config::authenticate_headers_audited(
+            &op,
+            authorization.as_deref(),
+            api_key.as_deref(),
+            bootstrap_token.as_deref(),
+            bootstrap_user_id.as_deref(),
+            audit.as_ref(),
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, result))
+    })
+}
+
+#[pyfunction]
+fn get_oidc_config<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "get_oidc_config", async move {
+        let config = oidc::get_oidc_config(&op)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         let val =
-            serde_json::to_value(summary).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            serde_json::to_value(config).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn list_sample_scenarios(py: Python<'_>) -> PyResult<PyObject> {
-    let scenarios = sample_data::list_sample_scenarios();
-    let val =
-        serde_json::to_value(scenarios).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-    json_to_py(py, val)
+fn save_oidc_config<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    config_json: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let config: oidc::OidcConfig =
+        serde_json::from_str(&config_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "save_oidc_config", async move {
+        oidc::save_oidc_config(&op, &config)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(())
+    })
 }
 
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id, scenario=None, entry_count=None, seed=None))]
-fn create_sample_space_job<'a>(
+#[pyo3(signature = (storage_config, provider, id_token, nonce=None))]
+fn authenticate_oidc_id_token<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    scenario: Option<String>,
-    entry_count: Option<usize>,
-    seed: Option<u64>,
+    provider: String,
+    id_token: String,
+    nonce: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
-    let uri: String = storage_config
-        .get_item("uri")?
-        .ok_or_else(|| PyValueError::new_err("Missing 'uri'"))?
-        .extract()?;
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let options = sample_data::SampleDataOptions {
-            space_id,
-            scenario: scenario.unwrap_or_else(|| sample_data::DEFAULT_SCENARIO.to_string()),
-            entry_count: entry_count.unwrap_or(sample_data::DEFAULT_ENTRY_COUNT),
-            seed,
-            owner_user_id: None,
-        };
-        let job = sample_data::create_sample_space_job(&op, &uri, &options)
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::to_value(job).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| json_to_py(py, val))
+    spawn_task(py, "authenticate_oidc_id_token", async move {
+        let fetcher =
+            oidc::HttpOidcFetcher::new().map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let identity =
+            oidc::authenticate_id_token(&op, &fetcher, &provider, &id_token, nonce.as_deref())
+                .await
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, identity))
     })
 }
 
 #[pyfunction]
-fn get_sample_space_job<'a>(
+fn create_scim_user<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    job_id: String,
+    resource_json: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let job = sample_data::get_sample_space_job(&op, &job_id)
+    let resource: Value =
+        serde_json::from_str(&resource_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "create_scim_user", async move {
+        let val = principals::create_user(&op, &resource)
             .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::to_value(job).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-#[pyo3(name = "test_storage_connection")]
-fn test_storage_connection_py<'a>(
+fn replace_scim_user<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
+    user_id: String,
+    resource_json: String,
 ) -> PyResult<Bound<'a, PyAny>> {
-    let uri: String = storage_config
-        .get_item("uri")?
-        .ok_or_else(|| PyValueError::new_err("Missing 'uri'"))?
-        .extract()?;
-    let payload = if uri.starts_with("memory://") {
-        serde_json::json!({"status": "ok", "mode": "memory"})
-    } else if uri.starts_with("file://")
-        || uri.starts_with("fs://")
-        || uri.starts_with('/')
-        || uri.starts_with('.')
-    {
-        serde_json::json!({"status": "ok", "mode": "local"})
-    } else if uri.starts_with("s3://") {
-        serde_json::json!({"status": "ok", "mode": "s3"})
-    } else {
-        return Err(PyValueError::new_err("Unsupported storage connector"));
-    };
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        Python::with_gil(|py| json_to_py(py, payload))
+    let op = get_operator(py, &storage_config)?;
+    let resource: Value =
+        serde_json::from_str(&resource_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "replace_scim_user", async move {
+        let val = principals::replace_user(&op, &user_id, &resource)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
-// Audit
-
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id, payload_json, retention_limit=None))]
-fn append_audit_event_py<'a>(
+fn deactivate_scim_user<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    payload_json: String,
-    retention_limit: Option<usize>,
+    user_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let payload: Value = serde_json::from_str(&payload_json)
-        .map_err(|e| PyValueError::new_err(format!("Invalid audit payload JSON: {e}")))?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let appended = audit::append_audit_event(&op, &space_id, &payload, retention_limit)
+    spawn_task(py, "deactivate_scim_user", async move {
+        let val = principals::deactivate_user(&op, &user_id)
             .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| json_to_py(py, appended))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id, filters_json=None))]
-fn list_audit_events_py<'a>(
+fn get_scim_user<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    filters_json: Option<String>,
+    user_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let filters_value = match filters_json {
-        Some(raw) => serde_json::from_str::<Value>(&raw)
-            .map_err(|e| PyValueError::new_err(format!("Invalid audit filters JSON: {e}")))?,
-        None => Value::Null,
-    };
-    let offset = filters_value
-        .get("offset")
-        .and_then(Value::as_u64)
-        .and_then(|value| usize::try_from(value).ok())
-        .unwrap_or(0);
-    let limit = filters_value
-        .get("limit")
-        .and_then(Value::as_u64)
-        .and_then(|value| usize::try_from(value).ok())
-        .unwrap_or(100);
-    let action = filters_value
-        .get("action")
-        .and_then(Value::as_str)
-        .map(str::to_string);
-    let actor_user_id = filters_value
-        .get("actor_user_id")
-        .and_then(Value::as_str)
-        .map(str::to_string);
-    let outcome = filters_value
-        .get("outcome")
-        .and_then(Value::as_str)
-        .map(str::to_string);
-    let options = audit::AuditListOptions {
-        offset,
-        limit,
-        action,
-        actor_user_id,
-        outcome,
-    };
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let listed = audit::list_audit_events(&op, &space_id, options)
+    spawn_task(py, "get_scim_user", async move {
+        let val = principals::get_user(&op, &user_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| json_to_py(py, listed))
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
-// Entry
-
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id, entry_id, content, author=None))]
-fn create_entry<'a>(
+#[pyo3(signature = (storage_config, filter=None))]
+fn list_scim_users<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    entry_id: String,
-    content: String,
-    author: Option<String>,
+    filter: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    let author = author.unwrap_or_else(|| "unknown".to_string());
-
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let meta = entry::create_entry(&op, &ws_path, &entry_id, &content, &author, &integrity)
+    spawn_task(py, "list_scim_users", async move {
+        let val = principals::list_users(&op, filter.as_deref())
             .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
-        let val = serde_json::to_value(meta).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
-// Saved SQL
-
 #[pyfunction]
-fn list_sql<'a>(
+fn create_scim_group<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
+    resource_json: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let entries = saved_sql::list_sql(&op, &ws_path)
+    let resource: Value =
+        serde_json::from_str(&resource_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "create_scim_group", async move {
+        let val = principals::create_group(&op, &resource)
             .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val =
-            serde_json::to_value(entries).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn get_sql<'a>(
+fn replace_scim_group<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    sql_id: String,
+    group_id: String,
+    resource_json: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let entry = saved_sql::get_sql(&op, &ws_path, &sql_id)
+    let resource: Value =
+        serde_json::from_str(&resource_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "replace_scim_group", async move {
+        let val = principals::replace_group(&op, &group_id, &resource)
             .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| json_to_py(py, entry))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id, sql_id, payload_json, author=None))]
-fn create_sql<'a>(
+fn delete_scim_group<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    sql_id: String,
-    payload_json: String,
-    author: Option<String>,
+    group_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    let author = author.unwrap_or_else(|| "unknown".to_string());
-    let payload: saved_sql::SqlPayload =
-        serde_json::from_str(&payload_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
-
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let entry = saved_sql::create_sql(&op, &ws_path, &sql_id, &payload, &author, &integrity)
+    spawn_task(py, "delete_scim_group", async move {
+        principals::delete_group(&op, &group_id)
             .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| json_to_py(py, entry))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(())
     })
 }
 
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id, sql_id, payload_json, parent_revision_id=None, author=None))]
-fn update_sql<'a>(
+fn get_scim_group<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    sql_id: String,
-    payload_json: String,
-    parent_revision_id: Option<String>,
-    author: Option<String>,
+    group_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    let author = author.unwrap_or_else(|| "unknown".to_string());
-    let payload: saved_sql::SqlPayload =
-        serde_json::from_str(&payload_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
-
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+    spawn_task(py, "get_scim_group", async move {
+        let val = principals::get_group(&op, &group_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let entry = saved_sql::update_sql(
-            &op,
-            &ws_path,
-            &sql_id,
-            &payload,
-            parent_revision_id.as_deref(),
-            &author,
-            &integrity,
-        )
-        .await
-        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| json_to_py(py, entry))
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn delete_sql<'a>(
+#[pyo3(signature = (storage_config, filter=None))]
+fn list_scim_groups<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    sql_id: String,
+    filter: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        saved_sql::delete_sql(&op, &ws_path, &sql_id)
+    spawn_task(py, "list_scim_groups", async move {
+        let val = principals::list_groups(&op, filter.as_deref())
             .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok(())
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
-// Search
-
 #[pyfunction]
-fn search_entries<'a>(
+fn resolve_provisioned_access<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
-    query: String,
+    user_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let results = search::search_entries(&op, &ws_path, &query)
+    spawn_task(py, "resolve_provisioned_access", async move {
+        let val = principals::resolve_provisioned_access(&op, &space_id, &user_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-
-        // Return list of dicts
-        let val =
-            serde_json::to_value(results).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
+// Organizations
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id, entry_id, hard_delete=false))]
-fn delete_entry<'a>(
+#[pyo3(signature = (storage_config, name, description=None))]
+fn create_org<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    entry_id: String,
-    hard_delete: bool,
+    name: String,
+    description: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        entry::delete_entry(&op, &ws_path, &entry_id, hard_delete)
+    spawn_task(py, "create_org", async move {
+        let val = orgs::create_org(&op, &name, description.as_deref())
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok(())
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn get_entry<'a>(
+#[pyo3(signature = (storage_config, org_id, name=None, description=None))]
+fn update_org<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    entry_id: String,
+    org_id: String,
+    name: Option<String>,
+    description: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let meta = entry::get_entry(&op, &ws_path, &entry_id)
+    spawn_task(py, "update_org", async move {
+        let val = orgs::update_org(&op, &org_id, name.as_deref(), description.as_deref())
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::to_value(meta).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn list_entries<'a>(
+fn delete_org<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
+    org_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let entries = entry::list_entries(&op, &ws_path)
+    spawn_task(py, "delete_org", async move {
+        orgs::delete_org(&op, &org_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::Value::Array(entries);
-        Python::with_gil(|py| json_to_py(py, val))
+        Ok(())
     })
 }
 
 #[pyfunction]
-fn get_space<'a>(
+fn get_org<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    name: String,
+    org_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let meta = space::get_space_raw(&op, &name)
+    spawn_task(py, "get_org", async move {
+        let val = orgs::get_org(&op, &org_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::to_value(meta).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn patch_space<'a>(
+#[pyo3(signature = (storage_config, user_id=None))]
+fn list_orgs<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    patch_json: String,
+    user_id: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let patch_value: serde_json::Value =
-            serde_json::from_str(&patch_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
-        let updated = space::patch_space(&op, &space_id, &patch_value)
+    spawn_task(py, "list_orgs", async move {
+        let val = orgs::list_orgs(&op, user_id.as_deref())
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val =
-            serde_json::to_value(updated).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn get_user_preferences<'a>(
+fn assign_org_space<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    user_id: String,
+    org_id: String,
+    space_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let preferences = preferences::get_user_preferences(&op, &user_id)
+    spawn_task(py, "assign_org_space", async move {
+        let val = orgs::assign_space(&op, &org_id, &space_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::to_value(preferences)
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn patch_user_preferences<'a>(
+fn release_org_space<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    user_id: String,
-    patch_json: String,
+    org_id: String,
+    space_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let patch_value: serde_json::Value =
-            serde_json::from_str(&patch_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
-        let updated = preferences::patch_user_preferences(&op, &user_id, &patch_value)
+    spawn_task(py, "release_org_space", async move {
+        let val = orgs::release_space(&op, &org_id, &space_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val =
-            serde_json::to_value(updated).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn list_column_types<'a>(py: Python<'a>) -> PyResult<Bound<'a, PyAny>> {
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let types = form::list_column_types()
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok(types)
-    })
-}
-
-#[pyfunction]
-#[pyo3(signature = (storage_config, space_id, form_def_json, strategies_json=None))]
-fn migrate_form<'a>(
+#[pyo3(signature = (storage_config, org_id, user_id, role=None))]
+fn set_org_member<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    form_def_json: String,
-    strategies_json: Option<String>,
+    org_id: String,
+    user_id: String,
+    role: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let form_def: serde_json::Value = serde_json::from_str(&form_def_json)
-            .map_err(|e| PyValueError::new_err(e.to_string()))?;
-        let strategies = match strategies_json {
-            Some(json) => Some(
-                serde_json::from_str::<serde_json::Value>(&json)
-                    .map_err(|e| PyValueError::new_err(e.to_string()))?,
-            ),
-            None => None,
-        };
-        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let count = form::migrate_form(&op, &ws_path, &form_def, strategies, &integrity)
+    spawn_task(py, "set_org_member", async move {
+        let val = orgs::set_org_member(&op, &org_id, &user_id, role.as_deref())
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok(count)
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn reindex_all<'a>(
+fn get_space_org<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        index::reindex_all(&op, &ws_path)
+    spawn_task(py, "get_space_org", async move {
+        let val = orgs::org_for_space(&op, &space_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok(())
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn update_entry_index<'a>(
+fn resolve_org_role<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
-    entry_id: String,
+    user_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        index::update_entry_index(&op, &ws_path, &entry_id)
+    spawn_task(py, "resolve_org_role", async move {
+        let val = orgs::resolve_org_role(&op, &space_id, &user_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok(())
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn load_hmac_material<'a>(
+fn get_org_stats<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
+    org_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py::<_, PyObject>(py, async move {
-        let (key_id, secret) = integrity::load_hmac_material(&op, &space_id)
+    spawn_task(py, "get_org_stats", async move {
+        let val = orgs::get_org_stats(&op, &org_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| {
-            let secret_bytes = PyBytes::new(py, &secret);
-            let key_id_obj = key_id.into_py_any(py)?;
-            let secret_obj = secret_bytes.into_py_any(py)?;
-            let tuple = PyTuple::new(py, [key_id_obj, secret_obj])?;
-            tuple.into_py_any(py)
-        })
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
+// Space
+
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id="default".to_string()))]
-fn load_response_hmac_material<'a>(
+fn list_spaces<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py::<_, PyObject>(py, async move {
-        let (key_id, secret) = integrity::load_response_hmac_material(&op, &space_id)
+    spawn_task(py, "list_spaces", async move {
+        let spaces = space::list_spaces(&op)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| {
-            let secret_bytes = PyBytes::new(py, &secret);
-            let key_id_obj = key_id.into_py_any(py)?;
-            let secret_obj = secret_bytes.into_py_any(py)?;
-            let tuple = PyTuple::new(py, [key_id_obj, secret_obj])?;
-            tuple.into_py_any(py)
-        })
+        Ok(spaces)
     })
 }
 
 #[pyfunction]
-fn list_forms<'a>(
+fn list_spaces_detailed<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let forms = form::list_forms(&op, &ws_path)
+    spawn_task(py, "list_spaces_detailed", async move {
+        let spaces = space::list_spaces_detailed(&op)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         let val =
-            serde_json::to_value(forms).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            serde_json::to_value(spaces).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn upsert_form<'a>(
+fn create_space<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    space_id: String,
-    form_def: String,
+    name: String,
 ) -> PyResult<Bound<'a, PyAny>> {
+    let uri: String = storage_config
+        .get_item("uri")?
+        .ok_or_else(|| PyValueError::new_err("Missing 'uri'"))?
+        .extract()?;
     let op = get_operator(py, &storage_config)?;
-    let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let parsed: serde_json::Value =
-            serde_json::from_str(&form_def).map_err(|e| PyValueError::new_err(e.to_string()))?;
-        form::upsert_form(&op, &ws_path, &parsed)
+    spawn_task(py, "create_space", async move {
+        space::create_space(&op, &name, &uri)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Ok(())
     })
 }
 
-// Asset
-
 #[pyfunction]
-fn save_asset<'a>(
+#[pyo3(signature = (storage_config, space_id, scenario=None, entry_count=None, seed=None, fixture=false))]
+fn create_sample_space<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
-    filename: String,
-    content: Vec<u8>,
-) -> PyResult<Bound<'a, PyAny>> {
-    let op = get_operator(py, &storage_config)?;
+    scenario: Option<String>,
+    entry_count: Option<usize>,
+    seed: Option<u64>,
+    fixture: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let uri: String = storage_config
+        .get_item("uri")?
+        .ok_or_else(|| PyValueError::new_err("Missing 'uri'"))?
+        .extract()?;
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "create_sample_space", async move {
+        let options = sample_data::SampleDataOptions {
+            space_id,
+            scenario: scenario.unwrap_or_else(|| sample_data::DEFAULT_SCENARIO.to_string()),
+            entry_count: entry_count.unwrap_or(sample_data::DEFAULT_ENTRY_COUNT),
+            seed,
+            owner_user_id: None,
+            fixture,
+        };
+        let summary = sample_data::create_sample_space(&op, &uri, &options)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(summary).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn list_sample_scenarios(py: Python<'_>) -> PyResult<PyObject> {
+    let scenarios = sample_data::list_sample_scenarios();
+    let val =
+        serde_json::to_value(scenarios).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    json_to_py(py, val)
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, scenario=None, entry_count=None, seed=None, fixture=false))]
+fn create_sample_space_job<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    scenario: Option<String>,
+    entry_count: Option<usize>,
+    seed: Option<u64>,
+    fixture: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let uri: String = storage_config
+        .get_item("uri")?
+        .ok_or_else(|| PyValueError::new_err("Missing 'uri'"))?
+        .extract()?;
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "create_sample_space_job", async move {
+        let options = sample_data::SampleDataOptions {
+            space_id,
+            scenario: scenario.unwrap_or_else(|| sample_data::DEFAULT_SCENARIO.to_string()),
+            entry_count: entry_count.unwrap_or(sample_data::DEFAULT_ENTRY_COUNT),
+            seed,
+            owner_user_id: None,
+            fixture,
+        };
+        let job = sample_data::create_sample_space_job(&op, &uri, &options)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(job).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn cancel_sample_space_job<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    job_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "cancel_sample_space_job", async move {
+        let job = sample_data::cancel_sample_space_job(&op, &job_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(job).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_sample_space_job<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    job_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "get_sample_space_job", async move {
+        let job = sample_data::get_sample_space_job(&op, &job_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(job).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+fn parse_vacuum_options(options_json: Option<String>) -> PyResult<space::VacuumOptions> {
+    match options_json {
+        Some(raw) => serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(e.to_string())),
+        None => Ok(space::VacuumOptions::default()),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, options_json=None))]
+fn vacuum_space<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    options_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let options = parse_vacuum_options(options_json)?;
+    spawn_task(py, "vacuum_space", async move {
+        let report = space::vacuum(&op, &space_id, &options)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn list_object_versions<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    prefix: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "list_object_versions", async move {
+        let versions = storage::versions::list_object_versions(&op, &prefix)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(versions).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn restore_object_version<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    path: String,
+    version_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "restore_object_version", async move {
+        storage::versions::restore_object_version(&op, &path, &version_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| Ok(py.None()))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, timestamp, dry_run=false))]
+fn restore_space_to_timestamp<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    timestamp: f64,
+    dry_run: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "restore_space_to_timestamp", async move {
+        let report = space::restore_space_to_timestamp(&op, &space_id, timestamp, dry_run)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, options_json=None))]
+fn create_vacuum_job<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    options_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let options = parse_vacuum_options(options_json)?;
+    spawn_task(py, "create_vacuum_job", async move {
+        let job = space::create_vacuum_job(&op, &space_id, &options)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(job).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_vacuum_job<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    job_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "get_vacuum_job", async move {
+        let job = space::get_vacuum_job(&op, &space_id, &job_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(job).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(name = "test_storage_connection")]
+fn test_storage_connection_py<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let uri: String = storage_config
+        .get_item("uri")?
+        .ok_or_else(|| PyValueError::new_err("Missing 'uri'"))?
+        .extract()?;
+    let payload = if uri.starts_with("memory://") {
+        serde_json::json!({"status": "ok", "mode": "memory"})
+    } else if uri.starts_with("file://")
+        || uri.starts_with("fs://")
+        || uri.starts_with('/')
+        || uri.starts_with('.')
+    {
+        serde_json::json!({"status": "ok", "mode": "local"})
+    } else if uri.starts_with("s3://") {
+        serde_json::json!({"status": "ok", "mode": "s3"})
+    } else {
+        return Err(PyValueError::new_err("Unsupported storage connector"));
+    };
+    spawn_task(py, "test_storage_connection_py", async move {
+        Python::with_gil(|py| json_to_py(py, payload))
+    })
+}
+
+// Audit
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, payload_json, retention_limit=None, validation_mode=None))]
+fn append_audit_event_py<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    payload_json: String,
+    retention_limit: Option<usize>,
+    validation_mode: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let payload: Value = serde_json::from_str(&payload_json)
+        .map_err(|e| PyValueError::new_err(format!("Invalid audit payload JSON: {e}")))?;
+    let mode = match validation_mode {
+        Some(raw) => audit::AuditValidationMode::parse(&raw)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?,
+        None => audit::validation_mode_from_env(),
+    };
+    spawn_task(py, "append_audit_event_py", async move {
+        let appended =
+            audit::append_audit_event_with_mode(&op, &space_id, &payload, retention_limit, mode)
+                .await
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, appended))
+    })
+}
+
+#[pyfunction]
+fn list_audit_actions_py(py: Python<'_>) -> PyResult<PyObject> {
+    let actions = serde_json::to_value(audit::list_audit_actions())
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    json_to_py(py, actions)
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, filters_json=None))]
+fn list_audit_events_py<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    filters_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let filters_value = match filters_json {
+        Some(raw) => serde_json::from_str::<Value>(&raw)
+            .map_err(|e| PyValueError::new_err(format!("Invalid audit filters JSON: {e}")))?,
+        None => Value::Null,
+    };
+    let offset = filters_value
+        .get("offset")
+        .and_then(Value::as_u64)
+        .and_then(|value| usize::try_from(value).ok())
+        .unwrap_or(0);
+    let limit = filters_value
+        .get("limit")
+        .and_then(Value::as_u64)
+        .and_then(|value| usize::try_from(value).ok())
+        .unwrap_or(100);
+    let action = filters_value
+        .get("action")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let actor_user_id = filters_value
+        .get("actor_user_id")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let outcome = filters_value
+        .get("outcome")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let options = audit::AuditListOptions {
+        offset,
+        limit,
+        action,
+        actor_user_id,
+        outcome,
+    };
+    spawn_task(py, "list_audit_events_py", async move {
+        let listed = audit::list_audit_events(&op, &space_id, options)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, listed))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, config_json=None))]
+fn export_audit_events_otlp<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    config_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let config = match config_json {
+        Some(raw) => serde_json::from_str::<audit_otlp::OtlpExporterConfig>(&raw)
+            .map_err(|e| PyValueError::new_err(format!("Invalid OTLP exporter config: {e}")))?,
+        None => audit_otlp::OtlpExporterConfig::from_env().ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "No OTLP collector configured; set {}",
+                audit_otlp::OTLP_ENDPOINT_ENV
+            ))
+        })?,
+    };
+    let sink = audit_otlp::HttpOtlpLogSink::new(&config)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "export_audit_events_otlp", async move {
+        let report = audit_otlp::export_audit_events(&op, &space_id, &config, &sink)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+// Entry
+
+/// Commit message and metadata passed alongside an entry write.
+fn revision_note(
+    message: Option<String>,
+    revision_metadata_json: Option<String>,
+) -> PyResult<entry::RevisionNote> {
+    let revision_metadata = revision_metadata_json
+        .map(|raw| serde_json::from_str::<Value>(&raw))
+        .transpose()
+        .map_err(|e| PyValueError::new_err(format!("Invalid revision metadata: {}", e)))?;
+    Ok(entry::RevisionNote {
+        message,
+        revision_metadata,
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, content, author=None, message=None, revision_metadata_json=None))]
+#[allow(clippy::too_many_arguments)]
+fn create_entry<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    content: String,
+    author: Option<String>,
+    message: Option<String>,
+    revision_metadata_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let note = revision_note(message, revision_metadata_json)?;
+
+    spawn_task(py, "create_entry", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let meta = entry::create_entry_with_note(
+            &op, &ws_path, &entry_id, &content, &author, &note, &integrity,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+        let val = serde_json::to_value(meta).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, items_json, author=None))]
+fn create_entries<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    items_json: String,
+    author: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let items: Vec<entry::BulkEntryInput> =
+        serde_json::from_str(&items_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+    spawn_task(py, "create_entries", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let results = entry::create_entries_bulk(&op, &ws_path, &items, &author, &integrity)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+        let val =
+            serde_json::to_value(results).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+// Saved SQL
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, user_id=None, groups=None, is_space_admin=false))]
+fn list_sql<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    spawn_task(py, "list_sql", async move {
+        let entries = match &viewer {
+            Some(viewer) => {
+                let filter = saved_sql::SqlListFilter::default();
+                saved_sql::list_sql_for_viewer(&op, &ws_path, &filter, viewer).await
+            }
+            None => saved_sql::list_sql(&op, &ws_path).await,
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(entries).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, sql_id, user_id=None, groups=None, is_space_admin=false))]
+fn get_sql<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    sql_id: String,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    spawn_task(py, "get_sql", async move {
+        let entry = match &viewer {
+            Some(viewer) => saved_sql::get_sql_for_viewer(&op, &ws_path, &sql_id, viewer).await,
+            None => saved_sql::get_sql(&op, &ws_path, &sql_id).await,
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, entry))
+    })
+}
+
+/// `params_json` holds the values bound to the query's `{{name}}` variables.
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, sql_id, user_id, params_json=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
+fn run_sql<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    sql_id: String,
+    user_id: String,
+    params_json: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let params: Value = match params_json {
+        Some(raw) => {
+            serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(e.to_string()))?
+        }
+        None => Value::Null,
+    };
+    let viewer = entry_acl::EntryViewer {
+        user_id,
+        groups: groups.unwrap_or_default(),
+        is_space_admin,
+    };
+    spawn_task(py, "run_sql", async move {
+        let rows = saved_sql::run_sql_for_viewer(&op, &ws_path, &sql_id, &params, &viewer)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, Value::Array(rows)))
+    })
+}
+
+#[pyfunction]
+fn get_sql_share<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    sql_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_sql_share", async move {
+        let share = saved_sql::get_sql_share(&op, &ws_path, &sql_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(share).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+/// `shared_with_json` holds a list of `entry_acl::EntryPrincipal`.
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, sql_id, visibility, user_id, shared_with_json=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
+fn set_sql_share<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    sql_id: String,
+    visibility: String,
+    user_id: String,
+    shared_with_json: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let visibility: entry_acl::EntryVisibility = serde_json::from_value(Value::String(visibility))
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let shared_with: Vec<entry_acl::EntryPrincipal> = match shared_with_json {
+        Some(raw) => {
+            serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(e.to_string()))?
+        }
+        None => Vec::new(),
+    };
+    let viewer = entry_acl::EntryViewer {
+        user_id,
+        groups: groups.unwrap_or_default(),
+        is_space_admin,
+    };
+    spawn_task(py, "set_sql_share", async move {
+        let share =
+            saved_sql::set_sql_share(&op, &ws_path, &sql_id, visibility, shared_with, &viewer)
+                .await
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(share).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, sql_id, payload_json, author=None))]
+fn create_sql<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    sql_id: String,
+    payload_json: String,
+    author: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let payload: saved_sql::SqlPayload =
+        serde_json::from_str(&payload_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+    spawn_task(py, "create_sql", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let entry = saved_sql::create_sql(&op, &ws_path, &sql_id, &payload, &author, &integrity)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, entry))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, sql_id, payload_json, parent_revision_id=None, author=None, user_id=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
+fn update_sql<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    sql_id: String,
+    payload_json: String,
+    parent_revision_id: Option<String>,
+    author: Option<String>,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    let payload: saved_sql::SqlPayload =
+        serde_json::from_str(&payload_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+    spawn_task(py, "update_sql", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        // A viewer is also the author of the new revision.
+        let entry = match &viewer {
+            Some(viewer) => {
+                saved_sql::update_sql_for_viewer(
+                    &op,
+                    &ws_path,
+                    &sql_id,
+                    &payload,
+                    parent_revision_id.as_deref(),
+                    viewer,
+                    &integrity,
+                )
+                .await
+            }
+            None => {
+                saved_sql::update_sql(
+                    &op,
+                    &ws_path,
+                    &sql_id,
+                    &payload,
+                    parent_revision_id.as_deref(),
+                    &author,
+                    &integrity,
+                )
+                .await
+            }
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, entry))
+    })
+}
+
+#[pyfunction]
+fn delete_sql<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    sql_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "delete_sql", async move {
+        saved_sql::delete_sql(&op, &ws_path, &sql_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+#[pyfunction]
+fn create_dashboard<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    payload_json: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let payload: dashboards::DashboardPayload =
+        serde_json::from_str(&payload_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "create_dashboard", async move {
+        let val = dashboards::create_dashboard(&op, &ws_path, &payload)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn update_dashboard<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    dashboard_id: String,
+    payload_json: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let payload: dashboards::DashboardPayload =
+        serde_json::from_str(&payload_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "update_dashboard", async move {
+        let val = dashboards::update_dashboard(&op, &ws_path, &dashboard_id, &payload)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_dashboard<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    dashboard_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_dashboard", async move {
+        let val = dashboards::get_dashboard(&op, &ws_path, &dashboard_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn list_dashboards<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_dashboards", async move {
+        let val = dashboards::list_dashboards(&op, &ws_path)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn delete_dashboard<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    dashboard_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "delete_dashboard", async move {
+        dashboards::delete_dashboard(&op, &ws_path, &dashboard_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+#[pyfunction]
+fn render_dashboard<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    dashboard_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "render_dashboard", async move {
+        let val = dashboards::render_dashboard(&op, &ws_path, &dashboard_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+// Form views
+
+#[pyfunction]
+fn create_form_view<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+    payload_json: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let payload: views::ViewPayload =
+        serde_json::from_str(&payload_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "create_form_view", async move {
+        let val = views::create_view(&op, &ws_path, &form_name, &payload)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn update_form_view<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+    view_id: String,
+    payload_json: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let payload: views::ViewPayload =
+        serde_json::from_str(&payload_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "update_form_view", async move {
+        let val = views::update_view(&op, &ws_path, &form_name, &view_id, &payload)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_form_view<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+    view_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_form_view", async move {
+        let val = views::get_view(&op, &ws_path, &form_name, &view_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn list_form_views<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_form_views", async move {
+        let val = views::list_views(&op, &ws_path, &form_name)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_default_form_view<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_default_form_view", async move {
+        let val = views::get_default_view(&op, &ws_path, &form_name)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn delete_form_view<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+    view_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "delete_form_view", async move {
+        views::delete_view(&op, &ws_path, &form_name, &view_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+// Search
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, query, include_deleted=false, only_deleted=false, user_id=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
+fn search_entries<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    query: String,
+    include_deleted: bool,
+    only_deleted: bool,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let deleted = entry::DeletedFilter::from_flags(include_deleted, only_deleted);
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    spawn_task(py, "search_entries", async move {
+        let results = match &viewer {
+            Some(viewer) => {
+                search::search_entries_with_deleted_for_viewer(
+                    &op, &ws_path, &query, deleted, viewer,
+                )
+                .await
+            }
+            None => search::search_entries_with_deleted(&op, &ws_path, &query, deleted).await,
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+        // Return list of dicts
+        let val =
+            serde_json::to_value(results).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, query, include_deleted=false, only_deleted=false))]
+fn search_entries_json<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    query: String,
+    include_deleted: bool,
+    only_deleted: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let deleted = entry::DeletedFilter::from_flags(include_deleted, only_deleted);
+    spawn_task(py, "search_entries_json", async move {
+        let results = search::search_entries_with_deleted(&op, &ws_path, &query, deleted)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        json_bytes_to_py(&results)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, hard_delete=false))]
+fn delete_entry<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    hard_delete: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "delete_entry", async move {
+        entry::delete_entry(&op, &ws_path, &entry_id, hard_delete)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_ids, hard_delete=false))]
+fn delete_entries<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_ids: Vec<String>,
+    hard_delete: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "delete_entries", async move {
+        let report = entry::delete_entries(&op, &ws_path, &entry_ids, hard_delete)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn restore_entries<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_ids: Vec<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "restore_entries", async move {
+        let report = entry::restore_entries(&op, &ws_path, &entry_ids)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn list_deleted_entries<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_deleted_entries", async move {
+        let trashed = entry::list_deleted_entries(&op, &ws_path)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(trashed).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, actor=None))]
+fn purge_entry<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    actor: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let actor = actor.unwrap_or_else(|| "unknown".to_string());
+    spawn_task(py, "purge_entry", async move {
+        entry::purge_entry(&op, &ws_path, &entry_id, &actor)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, days, actor=None))]
+fn purge_deleted_older_than<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    days: u64,
+    actor: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let actor = actor.unwrap_or_else(|| "unknown".to_string());
+    spawn_task(py, "purge_deleted_older_than", async move {
+        let report = entry::purge_deleted_older_than(&op, &ws_path, days, &actor)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, format=None, options_json=None, if_revision_not=None, user_id=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
+fn get_entry<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    format: Option<String>,
+    options_json: Option<String>,
+    if_revision_not: Option<String>,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let format: render::EntryFormat = match format {
+        Some(raw) => raw
+            .parse()
+            .map_err(|e: anyhow::Error| PyValueError::new_err(e.to_string()))?,
+        None => render::EntryFormat::default(),
+    };
+    let options = parse_render_options(options_json)?;
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    spawn_task(py, "get_entry", async move {
+        let entry = match (&if_revision_not, &viewer) {
+            (Some(revision_id), Some(viewer)) => {
+                entry::get_entry_if_changed_for_viewer(
+                    &op,
+                    &ws_path,
+                    &entry_id,
+                    revision_id,
+                    viewer,
+                )
+                .await
+            }
+            (Some(revision_id), None) => {
+                entry::get_entry_if_changed(&op, &ws_path, &entry_id, revision_id).await
+            }
+            (None, Some(viewer)) => entry::get_entry_for_viewer(&op, &ws_path, &entry_id, viewer)
+                .await
+                .map(Some),
+            (None, None) => entry::get_entry(&op, &ws_path, &entry_id).await.map(Some),
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = match entry {
+            Some(entry) => render::format_entry(entry, format, &options),
+            None => serde_json::json!({
+                "id": entry_id,
+                "revision_id": if_revision_not,
+                "not_modified": true,
+            }),
+        };
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, user_id=None, groups=None, is_space_admin=false))]
+fn get_entry_head<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    spawn_task(py, "get_entry_head", async move {
+        let val = match &viewer {
+            Some(viewer) => {
+                entry::get_entry_head_for_viewer(&op, &ws_path, &entry_id, viewer).await
+            }
+            None => entry::get_entry_head(&op, &ws_path, &entry_id).await,
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_entry_acl<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_entry_acl", async move {
+        let acl = entry_acl::get_entry_acl(&op, &ws_path, &entry_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(acl).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+/// `acl_json` holds an `entry_acl::EntryAcl`.
+#[pyfunction]
+fn set_entry_acl<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    acl_json: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let acl: entry_acl::EntryAcl =
+        serde_json::from_str(&acl_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "set_entry_acl", async move {
+        let acl = entry_acl::set_entry_acl(&op, &ws_path, &entry_id, &acl)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(acl).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn clear_entry_acl<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "clear_entry_acl", async move {
+        entry_acl::clear_entry_acl(&op, &ws_path, &entry_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+#[pyfunction]
+fn extract_entry_refs(py: Python<'_>, markdown: String) -> PyResult<PyObject> {
+    let refs = serde_json::to_value(link::extract_entry_refs(&markdown))
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    json_to_py(py, refs)
+}
+
+#[pyfunction]
+fn resolve_entry_ref<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    reference: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "resolve_entry_ref", async move {
+        let val = link::resolve_entry_ref(&op, &space_id, &reference)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, reference, user_id, groups=None, is_space_admin=false))]
+fn dereference_entry_ref<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    reference: String,
+    user_id: String,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let viewer = entry_acl::EntryViewer {
+        user_id,
+        groups: groups.unwrap_or_default(),
+        is_space_admin,
+    };
+    spawn_task(py, "dereference_entry_ref", async move {
+        let val = link::dereference_entry_ref(&op, &space_id, &reference, &viewer)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_entry_outline<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_entry_outline", async move {
+        let outline = entry::get_entry_outline(&op, &ws_path, &entry_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(outline).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn read_entry_section<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    heading_path: Vec<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "read_entry_section", async move {
+        let section = entry::read_entry_section(&op, &ws_path, &entry_id, &heading_path)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(section).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, heading_path, body, parent_revision_id=None, author=None))]
+#[allow(clippy::too_many_arguments)]
+fn patch_entry_section<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    heading_path: Vec<String>,
+    body: String,
+    parent_revision_id: Option<String>,
+    author: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    spawn_task(py, "patch_entry_section", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = entry::patch_entry_section(
+            &op,
+            &ws_path,
+            &entry_id,
+            &heading_path,
+            &body,
+            parent_revision_id.as_deref(),
+            &author,
+            &integrity,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_entry_metadata<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_entry_metadata", async move {
+        let metadata = entry_metadata::get_entry_metadata(&op, &ws_path, &entry_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(metadata).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, namespace, values_json, merge=true))]
+fn set_entry_metadata<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    namespace: String,
+    values_json: String,
+    merge: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let values: serde_json::Map<String, Value> =
+        serde_json::from_str(&values_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "set_entry_metadata", async move {
+        let stored = entry_metadata::set_entry_metadata(
+            &op, &ws_path, &entry_id, &namespace, &values, merge,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, Value::Object(stored)))
+    })
+}
+
+#[pyfunction]
+fn register_author_signing_key<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    user_id: String,
+    key_id: String,
+    public_key_pem: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "register_author_signing_key", async move {
+        let key = revision_signing::register_signing_key(
+            &op,
+            &ws_path,
+            &user_id,
+            &key_id,
+            &public_key_pem,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(key).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, user_id=None))]
+fn list_author_signing_keys<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    user_id: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_author_signing_keys", async move {
+        let keys = revision_signing::list_signing_keys(&op, &ws_path, user_id.as_deref())
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(keys).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn revoke_author_signing_key<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    user_id: String,
+    key_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "revoke_author_signing_key", async move {
+        let key = revision_signing::revoke_signing_key(&op, &ws_path, &user_id, &key_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(key).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_revision_signing_payload<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    revision_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_revision_signing_payload", async move {
+        let (author, payload) =
+            revision_signing::revision_payload(&op, &ws_path, &entry_id, &revision_id)
+                .await
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::json!({
+            "author": author,
+            "algorithm": revision_signing::REVISION_SIGNATURE_ALGORITHM,
+            "payload": payload,
+        });
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+fn attach_revision_signature<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    revision_id: String,
+    signer_user_id: String,
+    key_id: String,
+    signature: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "attach_revision_signature", async move {
+        let stored = revision_signing::attach_revision_signature(
+            &op,
+            &ws_path,
+            &entry_id,
+            &revision_id,
+            &signer_user_id,
+            &key_id,
+            &signature,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(stored).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn verify_revision_author<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    revision_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "verify_revision_author", async move {
+        let report =
+            revision_signing::verify_revision_author(&op, &ws_path, &entry_id, &revision_id)
+                .await
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn list_entries_ordered<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_entries_ordered", async move {
+        let entries = entry_order::list_entries_ordered(&op, &ws_path, &form_name)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, Value::Array(entries)))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, pinned=true))]
+fn pin_entry<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    pinned: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "pin_entry", async move {
+        let placement = entry_order::pin_entry(&op, &ws_path, &entry_id, pinned)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(placement).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, after_entry_id=None))]
+fn move_entry<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    after_entry_id: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "move_entry", async move {
+        let placement =
+            entry_order::move_entry(&op, &ws_path, &entry_id, after_entry_id.as_deref())
+                .await
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(placement).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn reorder_entries<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+    entry_ids: Vec<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "reorder_entries", async move {
+        let order = entry_order::reorder_entries(&op, &ws_path, &form_name, &entry_ids)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(order).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, name, parent_id=None, icon=None, description=None))]
+fn create_collection<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    name: String,
+    parent_id: Option<String>,
+    icon: Option<String>,
+    description: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "create_collection", async move {
+        let collection = collections::create_collection(
+            &op,
+            &ws_path,
+            &name,
+            parent_id.as_deref(),
+            icon.as_deref(),
+            description.as_deref(),
+        )
+        .await
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(collection).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn update_collection<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    collection_id: String,
+    update_json: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let update: collections::CollectionUpdate =
+        serde_json::from_str(&update_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "update_collection", async move {
+        let collection = collections::update_collection(&op, &ws_path, &collection_id, &update)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(collection).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, collection_id, parent_id=None))]
+fn move_collection<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    collection_id: String,
+    parent_id: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "move_collection", async move {
+        let collection =
+            collections::move_collection(&op, &ws_path, &collection_id, parent_id.as_deref())
+                .await
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(collection).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn delete_collection<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    collection_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "delete_collection", async move {
+        collections::delete_collection(&op, &ws_path, &collection_id)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+#[pyfunction]
+fn list_collections<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_collections", async move {
+        let collections = collections::list_collections(&op, &ws_path)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(collections)
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, collection_id=None))]
+fn move_entry_to_collection<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    collection_id: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "move_entry_to_collection", async move {
+        collections::move_entry_to_collection(&op, &ws_path, &entry_id, collection_id.as_deref())
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, collection_id=None, recursive=false))]
+fn list_collection_entries<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    collection_id: Option<String>,
+    recursive: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_collection_entries", async move {
+        let entries = collections::list_collection_entries(
+            &op,
+            &ws_path,
+            collection_id.as_deref(),
+            recursive,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, Value::Array(entries)))
+    })
+}
+
+/// `options_json` holds `entry::ListEntriesOptions` (filters and sort).
+/// Without `offset`/`limit` matching entries are returned as a list; with
+/// either, one page is returned as `{entries, offset, limit, total_count,
+/// has_more}`.
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, include_deleted=false, only_deleted=false, offset=None, limit=None, options_json=None, user_id=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
+fn list_entries<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    include_deleted: bool,
+    only_deleted: bool,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    options_json: Option<String>,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    let filtered = options_json.is_some();
+    let mut options: entry::ListEntriesOptions = match options_json {
+        Some(raw) => {
+            serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(e.to_string()))?
+        }
+        None => entry::ListEntriesOptions::default(),
+    };
+    options.include_deleted |= include_deleted;
+    options.only_deleted |= only_deleted;
+    if offset.is_some() || limit.is_some() {
+        let offset = offset.unwrap_or(0);
+        let limit = limit.unwrap_or(100);
+        if limit == 0 || limit > entry::MAX_ENTRY_PAGE {
+            return Err(PyValueError::new_err(format!(
+                "limit must be between 1 and {}",
+                entry::MAX_ENTRY_PAGE
+            )));
+        }
+        return spawn_task(py, "list_entries", async move {
+            let page = match &viewer {
+                Some(viewer) => {
+                    entry::list_entries_page_for_viewer(
+                        &op, &ws_path, &options, offset, limit, viewer,
+                    )
+                    .await
+                }
+                None => entry::list_entries_page(&op, &ws_path, &options, offset, limit).await,
+            }
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            let val =
+                serde_json::to_value(page).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            Python::with_gil(|py| json_to_py(py, val))
+        });
+    }
+    spawn_task(py, "list_entries", async move {
+        let entries = if let Some(viewer) = &viewer {
+            entry::list_entries_with_options_for_viewer(&op, &ws_path, &options, viewer).await
+        } else if filtered {
+            entry::list_entries_with_options(&op, &ws_path, &options).await
+        } else {
+            entry::list_entries_with_deleted(&op, &ws_path, options.deleted()).await
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::Value::Array(entries);
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, include_deleted=false, only_deleted=false))]
+fn list_entries_json<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    include_deleted: bool,
+    only_deleted: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let deleted = entry::DeletedFilter::from_flags(include_deleted, only_deleted);
+    spawn_task(py, "list_entries_json", async move {
+        let entries = entry::list_entries_with_deleted(&op, &ws_path, deleted)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        json_bytes_to_py(&entries)
+    })
+}
+
+#[pyfunction]
+fn get_space<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    name: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "get_space", async move {
+        let meta = space::get_space_raw(&op, &name)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(meta).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn patch_space<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    patch_json: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "patch_space", async move {
+        let patch_value: serde_json::Value =
+            serde_json::from_str(&patch_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let updated = space::patch_space(&op, &space_id, &patch_value)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(updated).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_user_preferences<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    user_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "get_user_preferences", async move {
+        let preferences = preferences::get_user_preferences(&op, &user_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(preferences)
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn patch_user_preferences<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    user_id: String,
+    patch_json: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "patch_user_preferences", async move {
+        let patch_value: serde_json::Value =
+            serde_json::from_str(&patch_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let updated = preferences::patch_user_preferences(&op, &user_id, &patch_value)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(updated).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn list_column_types<'a>(py: Python<'a>) -> PyResult<Bound<'a, PyAny>> {
+    spawn_task(py, "list_column_types", async move {
+        let types = form::list_column_types()
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(types)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, form_def_json, strategies_json=None))]
+fn migrate_form<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_def_json: String,
+    strategies_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "migrate_form", async move {
+        let form_def: serde_json::Value = serde_json::from_str(&form_def_json)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let strategies = match strategies_json {
+            Some(json) => Some(
+                serde_json::from_str::<serde_json::Value>(&json)
+                    .map_err(|e| PyValueError::new_err(e.to_string()))?,
+            ),
+            None => None,
+        };
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let count = form::migrate_form(&op, &ws_path, &form_def, strategies, &integrity)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(count)
+    })
+}
+
+#[pyfunction]
+fn reindex_all<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "reindex_all", async move {
+        index::reindex_all(&op, &ws_path)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+#[pyfunction]
+fn update_entry_index<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "update_entry_index", async move {
+        index::update_entry_index(&op, &ws_path, &entry_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+#[pyfunction]
+fn load_hmac_material<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task::<_, PyObject>(py, "load_hmac_material", async move {
+        let (key_id, secret) = integrity::load_hmac_material(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| {
+            let secret_bytes = PyBytes::new(py, &secret);
+            let key_id_obj = key_id.into_py_any(py)?;
+            let secret_obj = secret_bytes.into_py_any(py)?;
+            let tuple = PyTuple::new(py, [key_id_obj, secret_obj])?;
+            tuple.into_py_any(py)
+        })
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id="default".to_string()))]
+fn load_response_hmac_material<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task::<_, PyObject>(py, "load_response_hmac_material", async move {
+        let (key_id, secret) = integrity::load_response_hmac_material(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| {
+            let secret_bytes = PyBytes::new(py, &secret);
+            let key_id_obj = key_id.into_py_any(py)?;
+            let secret_obj = secret_bytes.into_py_any(py)?;
+            let tuple = PyTuple::new(py, [key_id_obj, secret_obj])?;
+            tuple.into_py_any(py)
+        })
+    })
+}
+
+#[pyfunction]
+fn list_forms<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_forms", async move {
+        let forms = form::list_forms(&op, &ws_path)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(forms).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn upsert_form<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_def: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "upsert_form", async move {
+        let parsed: serde_json::Value =
+            serde_json::from_str(&form_def).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        form::upsert_form(&op, &ws_path, &parsed)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, form_name, repair=false))]
+fn verify_unique_index<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+    repair: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "verify_unique_index", async move {
+        let report = unique::verify_unique_index(&op, &ws_path, &form_name, repair)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+// Asset
+
+#[pyfunction]
+fn save_asset<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    filename: String,
+    content: Vec<u8>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "save_asset", async move {
+        let info = asset::save_asset(&op, &ws_path, &filename, &content)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(info).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, url, options_json=None))]
+fn save_asset_from_url<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    url: String,
+    options_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let options: asset::UrlAssetOptions = match options_json {
+        Some(raw) => {
+            serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(e.to_string()))?
+        }
+        None => asset::UrlAssetOptions::default(),
+    };
+    spawn_task(py, "save_asset_from_url", async move {
+        let info = asset::save_asset_from_url(&op, &ws_path, &url, &options)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(info).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn list_assets<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_assets", async move {
+        let list = asset::list_assets(&op, &ws_path)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(list).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, markdown, direction, base_dir=String::new()))]
+fn rewrite_asset_links<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    markdown: String,
+    direction: String,
+    base_dir: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let direction: asset_links::AssetLinkDirection = direction
+        .parse()
+        .map_err(|e: anyhow::Error| PyValueError::new_err(e.to_string()))?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "rewrite_asset_links", async move {
+        let map = asset_links::AssetLinkMap::load(&op, &ws_path)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let rewrite = asset_links::rewrite_asset_links(&markdown, &base_dir, &map, direction);
+        let val =
+            serde_json::to_value(rewrite).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn delete_asset<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    asset_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "delete_asset", async move {
+        asset::delete_asset(&op, &ws_path, &asset_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(())
+    })
+}
+
+// Feature flags
+
+#[pyfunction]
+fn list_global_feature_flags<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "list_global_feature_flags", async move {
+        let flags = feature_flags::list_global_flags(&op)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(flags).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, flag, enabled=None))]
+fn set_global_feature_flag<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    flag: String,
+    enabled: Option<bool>,
+) -> PyResult<Bound<'a, PyAny>> {
+    feature_flags::validate_flag_name(&flag).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "set_global_feature_flag", async move {
+        let flags = feature_flags::set_global_flag(&op, &flag, enabled)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(flags).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, flag, enabled=None))]
+fn set_space_feature_flag<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    flag: String,
+    enabled: Option<bool>,
+) -> PyResult<Bound<'a, PyAny>> {
+    feature_flags::validate_flag_name(&flag).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "set_space_feature_flag", async move {
+        let flags = feature_flags::set_space_flag(&op, &ws_path, &flag, enabled)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(flags).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn list_space_feature_flags<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_space_feature_flags", async move {
+        let flags = feature_flags::resolve_flags(&op, &ws_path)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(flags).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn is_feature_enabled<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    flag: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "is_feature_enabled", async move {
+        let enabled = feature_flags::is_enabled(&op, &space_id, &flag)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(enabled)
+    })
+}
+
+// Collaborative editing
+
+#[pyfunction]
+fn apply_collab_update<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    update: Vec<u8>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "apply_collab_update", async move {
+        let seq = collab::apply_update(&op, &ws_path, &entry_id, &update)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(seq)
+    })
+}
+
+#[pyfunction]
+fn get_collab_state_vector<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task::<_, PyObject>(py, "get_collab_state_vector", async move {
+        let state_vector = collab::get_state_vector(&op, &ws_path, &entry_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| PyBytes::new(py, &state_vector).into_py_any(py))
+    })
+}
+
+#[pyfunction]
+fn encode_collab_diff<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    state_vector: Vec<u8>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task::<_, PyObject>(py, "encode_collab_diff", async move {
+        let diff = collab::encode_diff(&op, &ws_path, &entry_id, &state_vector)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| PyBytes::new(py, &diff).into_py_any(py))
+    })
+}
+
+#[pyfunction]
+fn compact_collab_updates<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "compact_collab_updates", async move {
+        let merged = collab::compact_updates(&op, &ws_path, &entry_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(merged)
+    })
+}
+
+// Offline sync
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, cursor=None, limit=100, user_id=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
+fn sync_pull<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    cursor: Option<String>,
+    limit: usize,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    spawn_task(py, "sync_pull", async move {
+        let pulled = sync::sync_pull(&op, &ws_path, cursor.as_deref(), limit, viewer.as_ref())
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(pulled).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, operations_json, author=None))]
+fn sync_push<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    operations_json: String,
+    author: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let operations: Vec<sync::SyncOperation> = serde_json::from_str(&operations_json)
+        .map_err(|e| PyValueError::new_err(format!("Invalid sync operations JSON: {e}")))?;
+    spawn_task(py, "sync_push", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let results = sync::sync_push(&op, &ws_path, &operations, &author, &integrity)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(results).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_form<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_form", async move {
+        let frm = form::get_form(&op, &ws_path, &form_name)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(frm).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_entry_history<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_entry_history", async move {
+        let history = entry::get_entry_history(&op, &ws_path, &entry_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, history))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, since_timestamp=None, since_revision=None, user_id=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
+fn list_changes_since<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    since_timestamp: Option<f64>,
+    since_revision: Option<String>,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    let since = match (since_timestamp, since_revision) {
+        (Some(timestamp), None) => entry::ChangesSince::Timestamp(timestamp),
+        (None, Some(revision_id)) => entry::ChangesSince::Revision(revision_id),
+        _ => {
+            return Err(PyValueError::new_err(
+                "Pass exactly one of since_timestamp and since_revision",
+            ))
+        }
+    };
+    spawn_task(py, "list_changes_since", async move {
+        let val = entry::list_changes_since(&op, &ws_path, &since, viewer.as_ref())
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(val).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_entry_revision<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    revision_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_entry_revision", async move {
+        let revision = entry::get_entry_revision(&op, &ws_path, &entry_id, &revision_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, revision))
+    })
+}
+
+#[pyfunction]
+fn get_entry_revision_content<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    revision_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_entry_revision_content", async move {
+        let content = entry::get_entry_revision_content(&op, &ws_path, &entry_id, &revision_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(content).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn diff_entry_revisions<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    from_revision_id: String,
+    to_revision_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "diff_entry_revisions", async move {
+        let diff = entry::diff_entry_revisions(
+            &op,
+            &ws_path,
+            &entry_id,
+            &from_revision_id,
+            &to_revision_id,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(diff).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, revision_id, author=None, message=None, revision_metadata_json=None))]
+#[allow(clippy::too_many_arguments)]
+fn restore_entry<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    revision_id: String,
+    author: Option<String>,
+    message: Option<String>,
+    revision_metadata_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let note = revision_note(message, revision_metadata_json)?;
+    spawn_task(py, "restore_entry", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let result = entry::restore_entry_with_note(
+            &op,
+            &ws_path,
+            &entry_id,
+            &revision_id,
+            &author,
+            &note,
+            &integrity,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, result))
+    })
+}
+
+#[pyfunction]
+#[pyo3(name = "extract_properties")]
+fn extract_properties_py(py: Python<'_>, markdown: String) -> PyResult<PyObject> {
+    let props = index::extract_properties(&markdown);
+    json_to_py(py, props)
+}
+
+#[pyfunction]
+#[pyo3(name = "validate_properties")]
+fn validate_properties_py(
+    py: Python<'_>,
+    properties_json: String,
+    form_json: String,
+) -> PyResult<PyObject> {
+    let properties: serde_json::Value =
+        serde_json::from_str(&properties_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let form_def: serde_json::Value =
+        serde_json::from_str(&form_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let (casted, warnings) = index::validate_properties(&properties, &form_def)
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    let casted_obj = json_to_py(py, casted)?;
+    let warnings_obj = json_to_py(py, serde_json::Value::Array(warnings))?;
+    let tuple = PyTuple::new(py, [casted_obj, warnings_obj])?;
+    tuple.into_py_any(py)
+}
+
+fn parse_render_options(options_json: Option<String>) -> PyResult<render::RenderOptions> {
+    match options_json {
+        Some(raw) => serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(e.to_string())),
+        None => Ok(render::RenderOptions::default()),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (markdown, options_json=None))]
+fn render_markdown(markdown: String, options_json: Option<String>) -> PyResult<String> {
+    let options = parse_render_options(options_json)?;
+    Ok(render::render_html(
+        render::strip_frontmatter(&markdown),
+        &options,
+    ))
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, options_json=None))]
+fn render_entry_html<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    options_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let options = parse_render_options(options_json)?;
+    spawn_task(py, "render_entry_html", async move {
+        render::render_entry_html(&op, &ws_path, &entry_id, &options)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, filter_json=None))]
+fn list_tasks<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    filter_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let filter: tasks::TaskFilter = match filter_json {
+        Some(raw) => {
+            serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(e.to_string()))?
+        }
+        None => tasks::TaskFilter::default(),
+    };
+    spawn_task(py, "list_tasks", async move {
+        let items = tasks::list_tasks(&op, &ws_path, &filter)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(items).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, task_index, author=None))]
+fn toggle_task<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    task_index: usize,
+    author: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    spawn_task(py, "toggle_task", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = tasks::toggle_task(&op, &ws_path, &entry_id, task_index, &author, &integrity)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, user_id, since=None))]
+fn list_mentions<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    user_id: String,
+    since: Option<f64>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_mentions", async move {
+        let items = mentions::list_mentions(&op, &ws_path, &user_id, since)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(items).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, user_id, mention_ids=None))]
+fn mark_mentions_read<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    user_id: String,
+    mention_ids: Option<Vec<String>>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "mark_mentions_read", async move {
+        mentions::mark_mentions_read(&op, &ws_path, &user_id, mention_ids.as_deref())
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    })
+}
+
+fn parse_watch_target(kind: &str, value: &str) -> PyResult<watchers::WatchTarget> {
+    serde_json::from_value(serde_json::json!({ "kind": kind, "value": value })).map_err(|_| {
+        PyValueError::new_err(format!(
+            "Invalid watch kind: {} (expected entry, form or tag)",
+            kind
+        ))
+    })
+}
+
+#[pyfunction]
+fn watch<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    principal: String,
+    kind: String,
+    value: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let target = parse_watch_target(&kind, &value)?;
+    spawn_task(py, "watch", async move {
+        watchers::watch(&op, &ws_path, &principal, target)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    })
+}
+
+#[pyfunction]
+fn unwatch<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    principal: String,
+    kind: String,
+    value: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let target = parse_watch_target(&kind, &value)?;
+    spawn_task(py, "unwatch", async move {
+        watchers::unwatch(&op, &ws_path, &principal, &target)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, principal=None))]
+fn list_watches<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    principal: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_watches", async move {
+        let watches = watchers::list_watches(&op, &ws_path, principal.as_deref())
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(watches).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, principal, limit=None))]
+fn list_watch_notifications<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    principal: String,
+    limit: Option<usize>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "list_watch_notifications", async move {
+        let items = watchers::list_notifications(&op, &ws_path, &principal, limit)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(items).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, principal, notification_ids=None))]
+fn ack_watch_notifications<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    principal: String,
+    notification_ids: Option<Vec<String>>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "ack_watch_notifications", async move {
+        watchers::ack_notifications(&op, &ws_path, &principal, notification_ids.as_deref())
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, options_json=None))]
+fn list_due_reminders<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    options_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let options: reminders::DueReminderOptions = match options_json {
+        Some(raw) => {
+            serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(e.to_string()))?
+        }
+        None => reminders::DueReminderOptions::default(),
+    };
+    spawn_task(py, "list_due_reminders", async move {
+        let due = reminders::list_due_reminders(&op, &ws_path, &options)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(due).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn snooze_reminder<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    principal: String,
+    entry_id: String,
+    field: String,
+    until: f64,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "snooze_reminder", async move {
+        reminders::snooze_reminder(&op, &ws_path, &principal, &entry_id, &field, until)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    })
+}
+
+#[pyfunction]
+fn unsnooze_reminder<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    principal: String,
+    entry_id: String,
+    field: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "unsnooze_reminder", async move {
+        reminders::unsnooze_reminder(&op, &ws_path, &principal, &entry_id, &field)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, now=None))]
+fn dispatch_due_reminders<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    now: Option<f64>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "dispatch_due_reminders", async move {
+        let report = reminders::dispatch_due_reminders(&op, &ws_path, now)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, body, space_id="default".to_string()))]
+fn build_response_signature<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    body: Vec<u8>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "build_response_signature", async move {
+        let (key_id, signature) = integrity::build_response_signature(&op, &space_id, &body)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok((key_id, signature))
+    })
+}
+
+/// Incremental signer handed to Python by `begin_response_signature`.
+#[pyclass(name = "ResponseSigner")]
+struct PyResponseSigner {
+    inner: Option<integrity::ResponseSigner>,
+}
+
+#[pymethods]
+impl PyResponseSigner {
+    #[getter]
+    fn key_id(&self) -> PyResult<String> {
+        self.inner
+            .as_ref()
+            .map(|signer| signer.key_id().to_string())
+            .ok_or_else(|| PyRuntimeError::new_err("response signer already finalized"))
+    }
+
+    fn update(&mut self, py: Python<'_>, chunk: Vec<u8>) -> PyResult<PyObject> {
+        let signer = self
+            .inner
+            .as_mut()
+            .ok_or_else(|| PyRuntimeError::new_err("response signer already finalized"))?;
+        let val = serde_json::to_value(signer.update(&chunk))
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        json_to_py(py, val)
+    }
+
+    fn finalize(&mut self, py: Python<'_>) -> PyResult<PyObject> {
+        let signer = self
+            .inner
+            .take()
+            .ok_or_else(|| PyRuntimeError::new_err("response signer already finalized"))?;
+        let val = serde_json::to_value(signer.finalize())
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        json_to_py(py, val)
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id="default".to_string()))]
+fn begin_response_signature<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "begin_response_signature", async move {
+        let signer = integrity::ResponseSigner::begin(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Ok(PyResponseSigner {
+            inner: Some(signer),
+        })
+    })
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (storage_config, method, path, body, timestamp, nonce, space_id="default".to_string()))]
+fn build_request_signature<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    method: String,
+    path: String,
+    body: Vec<u8>,
+    timestamp: i64,
+    nonce: String,
+    space_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "build_request_signature", async move {
+        integrity::build_request_signature(&op, &space_id, &method, &path, &body, timestamp, &nonce)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    })
+}
+
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (storage_config, method, path, body, headers, space_id="default".to_string(), window_secs=None))]
+fn verify_request_signature<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    method: String,
+    path: String,
+    body: Vec<u8>,
+    headers: std::collections::HashMap<String, String>,
+    space_id: String,
+    window_secs: Option<i64>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "verify_request_signature", async move {
+        let verified = integrity::verify_request_signature_with_window(
+            &op,
+            &space_id,
+            &method,
+            &path,
+            &body,
+            &headers,
+            window_secs.unwrap_or(integrity::DEFAULT_REQUEST_SIGNATURE_WINDOW_SECS),
+        )
+        .await
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(verified).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (provider, secret, body, headers, window_secs=None))]
+fn verify_webhook_signature(
+    py: Python<'_>,
+    provider: String,
+    secret: Vec<u8>,
+    body: Vec<u8>,
+    headers: std::collections::HashMap<String, String>,
+    window_secs: Option<i64>,
+) -> PyResult<PyObject> {
+    let provider: webhooks::WebhookProvider = provider
+        .parse()
+        .map_err(|e: anyhow::Error| PyValueError::new_err(e.to_string()))?;
+    let verified = webhooks::verify_webhook_signature_with_window(
+        provider,
+        &secret,
+        &body,
+        &headers,
+        window_secs.unwrap_or(webhooks::DEFAULT_WEBHOOK_WINDOW_SECS),
+    )
+    .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let val = serde_json::to_value(verified).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    json_to_py(py, val)
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, source, payload_json, template_json=None))]
+fn ingest_webhook_event<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    source: String,
+    payload_json: String,
+    template_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let info = asset::save_asset(&op, &ws_path, &filename, &content)
+    let payload: Value =
+        serde_json::from_str(&payload_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let template: Option<webhooks::WebhookTemplate> = template_json
+        .map(|raw| serde_json::from_str(&raw))
+        .transpose()
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "ingest_webhook_event", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::to_value(info).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let result = match template {
+            Some(template) => {
+                webhooks::ingest_webhook_event_with_template(
+                    &op, &ws_path, &template, &payload, &integrity,
+                )
+                .await
+            }
+            None => {
+                webhooks::ingest_webhook_event(&op, &ws_path, &source, &payload, &integrity).await
+            }
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(result).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn format_chat_notification(
+    py: Python<'_>,
+    platform: String,
+    event_json: String,
+    base_url: String,
+) -> PyResult<PyObject> {
+    let platform: chat_format::ChatPlatform = platform
+        .parse()
+        .map_err(|e: anyhow::Error| PyValueError::new_err(e.to_string()))?;
+    let event: chat_format::ChatEvent =
+        serde_json::from_str(&event_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let payload = chat_format::format_chat_notification(
+        platform,
+        &event,
+        &chat_format::ChatFormatOptions { base_url },
+    )
+    .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    json_to_py(py, payload)
+}
+
+#[pyfunction]
+fn export_key_recovery_codes<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    passphrase: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "export_key_recovery_codes", async move {
+        let kit = key_escrow::export_recovery_codes(&op, &space_id, &passphrase)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(kit).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn restore_key_recovery_codes<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    codes: Vec<String>,
+    passphrase: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "restore_key_recovery_codes", async move {
+        let report = key_escrow::restore_from_recovery_codes(&op, &space_id, &codes, &passphrase)
+            .await
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+/// Runtime error for a failed update; a revision conflict also carries its
+/// payload as the exception's `details` attribute.
+fn update_entry_error(error: anyhow::Error) -> PyErr {
+    let details = error
+        .downcast_ref::<entry::RevisionConflict>()
+        .map(entry::RevisionConflict::as_json);
+    let err = PyRuntimeError::new_err(error.to_string());
+    if let Some(details) = details {
+        Python::with_gil(|py| {
+            if let Ok(details) = json_to_py(py, details) {
+                let _ = err.value(py).setattr("details", details);
+            }
+        });
+    }
+    err
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, content, parent_revision_id=None, author=None, assets_json=None, merge_strategy=None, message=None, revision_metadata_json=None))]
+#[allow(clippy::too_many_arguments)]
+fn update_entry<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    content: String,
+    parent_revision_id: Option<String>,
+    author: Option<String>,
+    assets_json: Option<String>,
+    merge_strategy: Option<String>,
+    message: Option<String>,
+    revision_metadata_json: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let merge_strategy: entry::MergeStrategy = match merge_strategy {
+        Some(raw) => serde_json::from_value(Value::String(raw.clone())).map_err(|_| {
+            PyValueError::new_err(format!(
+                "Invalid merge strategy: {} (expected fail or three_way)",
+                raw
+            ))
+        })?,
+        None => entry::MergeStrategy::default(),
+    };
+    let note = revision_note(message, revision_metadata_json)?;
+
+    spawn_task(py, "update_entry", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let assets = match assets_json {
+            Some(json_str) => serde_json::from_str::<Vec<serde_json::Value>>(&json_str)
+                .map(Some)
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?,
+            None => None,
+        };
+        let meta = entry::update_entry_with_note(
+            &op,
+            &ws_path,
+            &entry_id,
+            &content,
+            parent_revision_id.as_deref(),
+            &author,
+            assets,
+            merge_strategy,
+            &note,
+            &integrity,
+        )
+        .await
+        .map_err(update_entry_error)?;
+        let val = serde_json::to_value(meta).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, entry_id, to_state, parent_revision_id=None, author=None, scopes=None))]
+#[allow(clippy::too_many_arguments)]
+fn transition_entry_state<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+    to_state: String,
+    parent_revision_id: Option<String>,
+    author: Option<String>,
+    scopes: Option<Vec<String>>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let scopes = scopes.unwrap_or_default();
+    spawn_task(py, "transition_entry_state", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let change = workflow::transition_entry_state(
+            &op,
+            &ws_path,
+            &entry_id,
+            &to_state,
+            parent_revision_id.as_deref(),
+            &author,
+            &scopes,
+            &integrity,
+        )
+        .await
+        .map_err(update_entry_error)?;
+        let val =
+            serde_json::to_value(change).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_entry_state_history<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    entry_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_entry_state_history", async move {
+        let history = workflow::get_entry_state_history(&op, &ws_path, &entry_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(history).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+fn parse_bulk_update_args(
+    patch_json: &str,
+    options_json: Option<String>,
+) -> PyResult<(serde_json::Map<String, Value>, entry::BulkUpdateOptions)> {
+    let patch =
+        serde_json::from_str(patch_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let options = match options_json {
+        Some(raw) => {
+            serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(e.to_string()))?
+        }
+        None => entry::BulkUpdateOptions::default(),
+    };
+    Ok((patch, options))
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, query, patch_json, options_json=None, author=None))]
+fn bulk_update_properties<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    query: String,
+    patch_json: String,
+    options_json: Option<String>,
+    author: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let adjusted_query = adjust_index_query(&query);
+    let (patch, options) = parse_bulk_update_args(&patch_json, options_json)?;
+    spawn_task(py, "bulk_update_properties", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let report = entry::bulk_update_properties(
+            &op,
+            &ws_path,
+            &adjusted_query,
+            &patch,
+            &options,
+            &author,
+            &integrity,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, query, patch_json, options_json=None, author=None))]
+fn create_bulk_update_job<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    query: String,
+    patch_json: String,
+    options_json: Option<String>,
+    author: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let adjusted_query = adjust_index_query(&query);
+    let (patch, options) = parse_bulk_update_args(&patch_json, options_json)?;
+    spawn_task(py, "create_bulk_update_job", async move {
+        let job = entry::create_bulk_update_job(
+            &op,
+            &ws_path,
+            &adjusted_query,
+            &patch,
+            &options,
+            &author,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(job).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+fn get_bulk_update_job<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    job_id: String,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_bulk_update_job", async move {
+        let job = entry::get_bulk_update_job(&op, &ws_path, &job_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(job).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+// Property ingestion
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, form_name, rows_json, options_json=None, author=None))]
+fn ingest_entry_properties<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: String,
+    rows_json: String,
+    options_json: Option<String>,
+    author: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let rows: Vec<property_ingest::PropertyRow> =
+        serde_json::from_str(&rows_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let options = match options_json {
+        Some(raw) => {
+            serde_json::from_str(&raw).map_err(|e| PyValueError::new_err(e.to_string()))?
+        }
+        None => property_ingest::PropertyIngestOptions::default(),
+    };
+    spawn_task(py, "ingest_entry_properties", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let report = property_ingest::ingest_entry_properties(
+            &op, &ws_path, &form_name, &rows, &options, &author, &integrity,
+        )
+        .await
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, form_name=None))]
+fn reconcile_entry_properties<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    form_name: Option<String>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "reconcile_entry_properties", async move {
+        let report =
+            property_ingest::reconcile_entry_properties(&op, &ws_path, form_name.as_deref())
+                .await
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
+// Related entries
+
 #[pyfunction]
-fn list_assets<'a>(
+#[pyo3(signature = (storage_config, space_id, entry_id, limit=10, embeddings_json=None))]
+fn get_related_entries<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
+    entry_id: String,
+    limit: usize,
+    embeddings_json: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let list = asset::list_assets(&op, &ws_path)
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::to_value(list).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    let embeddings: Option<std::collections::HashMap<String, Vec<f32>>> = embeddings_json
+        .map(|raw| serde_json::from_str(&raw))
+        .transpose()
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "get_related_entries", async move {
+        let related = match embeddings {
+            Some(embeddings) => {
+                related::get_related_entries_with_embeddings(
+                    &op,
+                    &ws_path,
+                    &entry_id,
+                    limit,
+                    &embeddings,
+                )
+                .await
+            }
+            None => related::get_related_entries(&op, &ws_path, &entry_id, limit).await,
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(related).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
+// Find and replace
+
 #[pyfunction]
-fn delete_asset<'a>(
+fn preview_replace<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
-    asset_id: String,
+    options_json: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        asset::delete_asset(&op, &ws_path, &asset_id)
+    let options: replace::ReplaceOptions =
+        serde_json::from_str(&options_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "preview_replace", async move {
+        let previews = replace::preview_replace(&op, &ws_path, &options)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok(())
+        let val =
+            serde_json::to_value(previews).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn get_form<'a>(
+#[pyo3(signature = (storage_config, space_id, options_json, author=None))]
+fn apply_replace<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
-    form_name: String,
+    options_json: String,
+    author: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let frm = form::get_form(&op, &ws_path, &form_name)
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    let options: replace::ReplaceOptions =
+        serde_json::from_str(&options_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "apply_replace", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::to_value(frm).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let report = replace::apply_replace(&op, &ws_path, &options, &author, &integrity)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn get_entry_history<'a>(
+#[pyo3(signature = (storage_config, space_id, job_id, author=None))]
+fn undo_replace<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
-    entry_id: String,
+    job_id: String,
+    author: Option<String>,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let history = entry::get_entry_history(&op, &ws_path, &entry_id)
+    let author = author.unwrap_or_else(|| "unknown".to_string());
+    spawn_task(py, "undo_replace", async move {
+        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| json_to_py(py, history))
+        let report = replace::undo_replace(&op, &ws_path, &job_id, &author, &integrity)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
+// Content lint
+
 #[pyfunction]
-fn get_entry_revision<'a>(
+fn lint_entry<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
     entry_id: String,
-    revision_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let revision = entry::get_entry_revision(&op, &ws_path, &entry_id, &revision_id)
+    spawn_task(py, "lint_entry", async move {
+        let findings = lint::lint_entry(&op, &ws_path, &entry_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| json_to_py(py, revision))
+        let val =
+            serde_json::to_value(findings).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-fn get_entry_revision_content<'a>(
+fn lint_space<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
-    entry_id: String,
-    revision_id: String,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let content = entry::get_entry_revision_content(&op, &ws_path, &entry_id, &revision_id)
+    spawn_task(py, "lint_space", async move {
+        let reports = lint::lint_space(&op, &ws_path)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         let val =
-            serde_json::to_value(content).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+            serde_json::to_value(reports).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
+// Analytics
+
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id, entry_id, revision_id, author=None))]
-fn restore_entry<'a>(
+#[pyo3(signature = (storage_config, space_id, start, end, granularity="day"))]
+fn get_space_analytics<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
-    entry_id: String,
-    revision_id: String,
-    author: Option<String>,
+    start: String,
+    end: String,
+    granularity: &str,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    let author = author.unwrap_or_else(|| "unknown".to_string());
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
+    let range = analytics::AnalyticsRange::parse(&start, &end)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let granularity = analytics::Granularity::parse(granularity)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    spawn_task(py, "get_space_analytics", async move {
+        let analytics = analytics::get_space_analytics(&op, &ws_path, &range, granularity)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let result =
-            entry::restore_entry(&op, &ws_path, &entry_id, &revision_id, &author, &integrity)
-                .await
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| json_to_py(py, result))
+        let val =
+            serde_json::to_value(analytics).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
-#[pyfunction]
-#[pyo3(name = "extract_properties")]
-fn extract_properties_py(py: Python<'_>, markdown: String) -> PyResult<PyObject> {
-    let props = index::extract_properties(&markdown);
-    json_to_py(py, props)
-}
-
-#[pyfunction]
-#[pyo3(name = "validate_properties")]
-fn validate_properties_py(
-    py: Python<'_>,
-    properties_json: String,
-    form_json: String,
-) -> PyResult<PyObject> {
-    let properties: serde_json::Value =
-        serde_json::from_str(&properties_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
-    let form_def: serde_json::Value =
-        serde_json::from_str(&form_json).map_err(|e| PyValueError::new_err(e.to_string()))?;
-    let (casted, warnings) = index::validate_properties(&properties, &form_def)
-        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-    let casted_obj = json_to_py(py, casted)?;
-    let warnings_obj = json_to_py(py, serde_json::Value::Array(warnings))?;
-    let tuple = PyTuple::new(py, [casted_obj, warnings_obj])?;
-    tuple.into_py_any(py)
+fn adjust_index_query(query: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(query) {
+        Ok(parsed) => parsed
+            .get("$sql")
+            .or_else(|| parsed.get("sql"))
+            .and_then(|val| val.as_str())
+            .and_then(|sql| serde_json::to_string(sql).ok())
+            .unwrap_or_else(|| query.to_string()),
+        Err(_) => query.to_string(),
+    }
 }
 
 #[pyfunction]
-#[pyo3(signature = (storage_config, body, space_id="default".to_string()))]
-fn build_response_signature<'a>(
+#[pyo3(signature = (storage_config, space_id, query, include_deleted=false, only_deleted=false, user_id=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
+fn query_index<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
-    body: Vec<u8>,
     space_id: String,
+    query: String,
+    include_deleted: bool,
+    only_deleted: bool,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let (key_id, signature) = integrity::build_response_signature(&op, &space_id, &body)
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Ok((key_id, signature))
+    let ws_path = format!("spaces/{}", space_id);
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    let adjusted_query = adjust_index_query(&query);
+    let deleted = entry::DeletedFilter::from_flags(include_deleted, only_deleted);
+    spawn_task(py, "query_index", async move {
+        let res = match &viewer {
+            Some(viewer) => {
+                index::query_index_for_viewer(&op, &ws_path, &adjusted_query, deleted, viewer).await
+            }
+            None => index::query_index_with_deleted(&op, &ws_path, &adjusted_query, deleted).await,
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::Value::Array(res);
+        Python::with_gil(|py| json_to_py(py, val))
     })
 }
 
 #[pyfunction]
-#[pyo3(signature = (storage_config, space_id, entry_id, content, parent_revision_id=None, author=None, assets_json=None))]
+#[pyo3(signature = (storage_config, space_id, query, include_deleted=false, only_deleted=false, user_id=None, groups=None, is_space_admin=false))]
 #[allow(clippy::too_many_arguments)]
-fn update_entry<'a>(
+fn query_index_json<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
-    entry_id: String,
-    content: String,
-    parent_revision_id: Option<String>,
-    author: Option<String>,
-    assets_json: Option<String>,
+    query: String,
+    include_deleted: bool,
+    only_deleted: bool,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    let author = author.unwrap_or_else(|| "unknown".to_string());
-
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let integrity = RealIntegrityProvider::from_space(&op, &space_id)
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let assets = match assets_json {
-            Some(json_str) => serde_json::from_str::<Vec<serde_json::Value>>(&json_str)
-                .map(Some)
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?,
-            None => None,
-        };
-        let meta = entry::update_entry(
-            &op,
-            &ws_path,
-            &entry_id,
-            &content,
-            parent_revision_id.as_deref(),
-            &author,
-            assets,
-            &integrity,
-        )
-        .await
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    let adjusted_query = adjust_index_query(&query);
+    let deleted = entry::DeletedFilter::from_flags(include_deleted, only_deleted);
+    spawn_task(py, "query_index_json", async move {
+        let res = match &viewer {
+            Some(viewer) => {
+                index::query_index_for_viewer(&op, &ws_path, &adjusted_query, deleted, viewer).await
+            }
+            None => index::query_index_with_deleted(&op, &ws_path, &adjusted_query, deleted).await,
+        }
         .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::to_value(meta).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        Python::with_gil(|py| json_to_py(py, val))
+        json_bytes_to_py(&res)
     })
 }
 
 #[pyfunction]
-fn query_index<'a>(
+#[pyo3(signature = (storage_config, space_id, query, cursor=None, limit=100, user_id=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
+fn query_index_page<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
     query: String,
+    cursor: Option<String>,
+    limit: usize,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    let adjusted_query = match serde_json::from_str::<serde_json::Value>(&query) {
-        Ok(parsed) => parsed
-            .get("$sql")
-            .or_else(|| parsed.get("sql"))
-            .and_then(|val| val.as_str())
-            .and_then(|sql| serde_json::to_string(sql).ok())
-            .unwrap_or(query.clone()),
-        Err(_) => query.clone(),
-    };
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let res = index::query_index(&op, &ws_path, &adjusted_query)
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = serde_json::Value::Array(res);
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    let adjusted_query = adjust_index_query(&query);
+    spawn_task(py, "query_index_page", async move {
+        let cursor = cursor.as_deref();
+        let page = match &viewer {
+            Some(viewer) => {
+                index::query_index_page_for_viewer(
+                    &op,
+                    &ws_path,
+                    &adjusted_query,
+                    cursor,
+                    limit,
+                    viewer,
+                )
+                .await
+            }
+            None => index::query_index_page(&op, &ws_path, &adjusted_query, cursor, limit).await,
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val = serde_json::to_value(page).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, val))
     })
 }
@@ -1130,7 +4696,7 @@ fn list_entry_summaries<'a>(
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+    spawn_task(py, "list_entry_summaries", async move {
         let summaries = entry::list_entry_summaries(
             &op,
             &ws_path,
@@ -1147,16 +4713,26 @@ fn list_entry_summaries<'a>(
 }
 
 #[pyfunction]
+#[pyo3(signature = (storage_config, space_id, sql, timeout_ms=None, max_bytes=None))]
 fn create_sql_session<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
     space_id: String,
     sql: String,
+    timeout_ms: Option<u64>,
+    max_bytes: Option<u64>,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let session = sql_session::create_sql_session(&op, &ws_path, &sql)
+    let limits = (timeout_ms.is_some() || max_bytes.is_some()).then(|| {
+        let defaults = sql_session::SqlSessionLimits::from_env();
+        sql_session::SqlSessionLimits {
+            timeout_ms: timeout_ms.unwrap_or(defaults.timeout_ms),
+            max_bytes: max_bytes.unwrap_or(defaults.max_bytes),
+        }
+    });
+    spawn_task(py, "create_sql_session", async move {
+        let session = sql_session::create_sql_session_with_limits(&op, &ws_path, &sql, limits)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
         Python::with_gil(|py| json_to_py(py, session))
@@ -1172,7 +4748,7 @@ fn get_sql_session_status<'a>(
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+    spawn_task(py, "get_sql_session_status", async move {
         let session = sql_session::get_sql_session_status(&op, &ws_path, &session_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
@@ -1189,7 +4765,7 @@ fn get_sql_session_count<'a>(
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+    spawn_task(py, "get_sql_session_count", async move {
         let count = sql_session::get_sql_session_count(&op, &ws_path, &session_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
@@ -1198,7 +4774,22 @@ fn get_sql_session_count<'a>(
     })
 }
 
+/// The viewer entry ACLs are evaluated for, when the caller names one.
+fn optional_viewer(
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
+) -> Option<entry_acl::EntryViewer> {
+    user_id.map(|user_id| entry_acl::EntryViewer {
+        user_id,
+        groups: groups.unwrap_or_default(),
+        is_space_admin,
+    })
+}
+
 #[pyfunction]
+#[pyo3(signature = (storage_config, space_id, session_id, readable_forms, include_untyped_entries, user_id=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
 fn get_sql_session_count_scoped<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
@@ -1206,16 +4797,21 @@ fn get_sql_session_count_scoped<'a>(
     session_id: String,
     readable_forms: Vec<String>,
     include_untyped_entries: bool,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    spawn_task(py, "get_sql_session_count_scoped", async move {
         let count = sql_session::get_sql_session_count_scoped(
             &op,
             &ws_path,
             &session_id,
             &readable_forms,
             include_untyped_entries,
+            viewer.as_ref(),
         )
         .await
         .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
@@ -1224,6 +4820,32 @@ fn get_sql_session_count_scoped<'a>(
     })
 }
 
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, session_id, readable_forms=None))]
+fn get_sql_session_columns<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    session_id: String,
+    readable_forms: Option<Vec<String>>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_sql_session_columns", async move {
+        let columns = match readable_forms {
+            Some(forms) => {
+                sql_session::get_sql_session_columns_scoped(&op, &ws_path, &session_id, &forms)
+                    .await
+            }
+            None => sql_session::get_sql_session_columns(&op, &ws_path, &session_id).await,
+        }
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(columns).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
 #[pyfunction]
 fn get_sql_session_rows<'a>(
     py: Python<'a>,
@@ -1235,7 +4857,7 @@ fn get_sql_session_rows<'a>(
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+    spawn_task(py, "get_sql_session_rows", async move {
         let rows = sql_session::get_sql_session_rows(&op, &ws_path, &session_id, offset, limit)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
@@ -1244,6 +4866,26 @@ fn get_sql_session_rows<'a>(
 }
 
 #[pyfunction]
+fn get_sql_session_rows_json<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_id: String,
+    session_id: String,
+    offset: usize,
+    limit: usize,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    let ws_path = format!("spaces/{}", space_id);
+    spawn_task(py, "get_sql_session_rows_json", async move {
+        let rows = sql_session::get_sql_session_rows(&op, &ws_path, &session_id, offset, limit)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        json_bytes_to_py(&rows)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (storage_config, space_id, session_id, offset, limit, readable_forms, include_untyped_entries, user_id=None, groups=None, is_space_admin=false))]
 #[allow(clippy::too_many_arguments)]
 fn get_sql_session_rows_scoped<'a>(
     py: Python<'a>,
@@ -1254,10 +4896,14 @@ fn get_sql_session_rows_scoped<'a>(
     limit: usize,
     readable_forms: Vec<String>,
     include_untyped_entries: bool,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    spawn_task(py, "get_sql_session_rows_scoped", async move {
         let rows = sql_session::get_sql_session_rows_scoped(
             &op,
             &ws_path,
@@ -1266,6 +4912,7 @@ fn get_sql_session_rows_scoped<'a>(
             limit,
             &readable_forms,
             include_untyped_entries,
+            viewer.as_ref(),
         )
         .await
         .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
@@ -1282,16 +4929,17 @@ fn get_sql_session_rows_all<'a>(
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let rows = sql_session::get_sql_session_rows_all(&op, &ws_path, &session_id)
+    spawn_task(py, "get_sql_session_rows_all", async move {
+        let results = sql_session::open_sql_session_rows(&op, &ws_path, &session_id)
             .await
             .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = Value::Array(rows);
-        Python::with_gil(|py| json_to_py(py, val))
+        result_set_to_py(results).await
     })
 }
 
 #[pyfunction]
+#[pyo3(signature = (storage_config, space_id, session_id, readable_forms, include_untyped_entries, user_id=None, groups=None, is_space_admin=false))]
+#[allow(clippy::too_many_arguments)]
 fn get_sql_session_rows_all_scoped<'a>(
     py: Python<'a>,
     storage_config: Bound<'a, PyDict>,
@@ -1299,83 +4947,298 @@ fn get_sql_session_rows_all_scoped<'a>(
     session_id: String,
     readable_forms: Vec<String>,
     include_untyped_entries: bool,
+    user_id: Option<String>,
+    groups: Option<Vec<String>>,
+    is_space_admin: bool,
 ) -> PyResult<Bound<'a, PyAny>> {
     let op = get_operator(py, &storage_config)?;
     let ws_path = format!("spaces/{}", space_id);
-    pyo3_async_runtimes::tokio::future_into_py(py, async move {
-        let rows = sql_session::get_sql_session_rows_all_scoped(
+    let viewer = optional_viewer(user_id, groups, is_space_admin);
+    spawn_task(py, "get_sql_session_rows_all_scoped", async move {
+        let results = sql_session::open_sql_session_rows_scoped(
             &op,
             &ws_path,
             &session_id,
             &readable_forms,
             include_untyped_entries,
+            viewer.as_ref(),
         )
         .await
         .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let val = Value::Array(rows);
-        Python::with_gil(|py| json_to_py(py, val))
+        result_set_to_py(results).await
     })
 }
 
 // Stubs using generic signature removed; all bindings are implemented.
 
 /// A Python module implemented in Rust.
+// Runtime
+
+#[pyfunction]
+#[pyo3(signature = (worker_threads=None, max_blocking_threads=None))]
+fn configure_runtime(
+    worker_threads: Option<usize>,
+    max_blocking_threads: Option<usize>,
+) -> PyResult<()> {
+    let config = runtime::RuntimeConfig {
+        worker_threads,
+        max_blocking_threads,
+    };
+    runtime::configure(config.clone()).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    pyo3_async_runtimes::tokio::init(config.builder());
+    Ok(())
+}
+
+#[pyfunction]
+fn runtime_metrics(py: Python<'_>) -> PyResult<PyObject> {
+    let val = serde_json::to_value(runtime::metrics())
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    json_to_py(py, val)
+}
+
+#[pyfunction]
+#[pyo3(signature = (timeout_secs=30.0))]
+fn shutdown_runtime(py: Python<'_>, timeout_secs: f64) -> PyResult<Bound<'_, PyAny>> {
+    let timeout = std::time::Duration::try_from_secs_f64(timeout_secs)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    // Not spawned through `spawn_task`: it must run while new tasks are rejected.
+    pyo3_async_runtimes::tokio::future_into_py(
+        py,
+        async move { Ok(runtime::shutdown(timeout).await) },
+    )
+}
+
+#[pyfunction]
+fn resume_runtime() {
+    runtime::resume();
+}
+
+#[pyfunction]
+#[pyo3(name = "warmup", signature = (storage_config, space_ids=None))]
+fn warmup_py<'a>(
+    py: Python<'a>,
+    storage_config: Bound<'a, PyDict>,
+    space_ids: Option<Vec<String>>,
+) -> PyResult<Bound<'a, PyAny>> {
+    let op = get_operator(py, &storage_config)?;
+    spawn_task(py, "warmup", async move {
+        let report = warmup::warmup(&op, &space_ids.unwrap_or_default())
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let val =
+            serde_json::to_value(report).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        Python::with_gil(|py| json_to_py(py, val))
+    })
+}
+
 #[pymodule]
 fn _ugoite_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let config =
+        runtime::RuntimeConfig::from_env().map_err(|e| PyValueError::new_err(e.to_string()))?;
+    runtime::configure(config.clone()).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    pyo3_async_runtimes::tokio::init(config.builder());
+    m.add_function(wrap_pyfunction!(configure_runtime, m)?)?;
+    m.add_function(wrap_pyfunction!(runtime_metrics, m)?)?;
+    m.add_function(wrap_pyfunction!(shutdown_runtime, m)?)?;
+    m.add_function(wrap_pyfunction!(resume_runtime, m)?)?;
+    m.add_function(wrap_pyfunction!(warmup_py, m)?)?;
+
     m.add_function(wrap_pyfunction!(hash_service_api_key_secret, m)?)?;
     m.add_function(wrap_pyfunction!(verify_service_api_key_secret, m)?)?;
     m.add_function(wrap_pyfunction!(authenticate_headers_core, m)?)?;
     m.add_function(wrap_pyfunction!(auth_capabilities_snapshot_core, m)?)?;
+    m.add_function(wrap_pyfunction!(authz_matrix_snapshot_core, m)?)?;
+    m.add_function(wrap_pyfunction!(introspect_token_core, m)?)?;
+    m.add_function(wrap_pyfunction!(list_expiring_keys_core, m)?)?;
+    m.add_function(wrap_pyfunction!(load_auth_config, m)?)?;
+    m.add_function(wrap_pyfunction!(authenticate_headers_stored, m)?)?;
+    m.add_function(wrap_pyfunction!(list_expiring_keys_stored, m)?)?;
+    m.add_function(wrap_pyfunction!(get_oidc_config, m)?)?;
+    m.add_function(wrap_pyfunction!(save_oidc_config, m)?)?;
+    m.add_function(wrap_pyfunction!(authenticate_oidc_id_token, m)?)?;
+    m.add_function(wrap_pyfunction!(create_scim_user, m)?)?;
+    m.add_function(wrap_pyfunction!(replace_scim_user, m)?)?;
+    m.add_function(wrap_pyfunction!(deactivate_scim_user, m)?)?;
+    m.add_function(wrap_pyfunction!(get_scim_user, m)?)?;
+    m.add_function(wrap_pyfunction!(list_scim_users, m)?)?;
+    m.add_function(wrap_pyfunction!(create_scim_group, m)?)?;
+    m.add_function(wrap_pyfunction!(replace_scim_group, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_scim_group, m)?)?;
+    m.add_function(wrap_pyfunction!(get_scim_group, m)?)?;
+    m.add_function(wrap_pyfunction!(list_scim_groups, m)?)?;
+    m.add_function(wrap_pyfunction!(resolve_provisioned_access, m)?)?;
+    m.add_function(wrap_pyfunction!(create_org, m)?)?;
+    m.add_function(wrap_pyfunction!(update_org, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_org, m)?)?;
+    m.add_function(wrap_pyfunction!(get_org, m)?)?;
+    m.add_function(wrap_pyfunction!(list_orgs, m)?)?;
+    m.add_function(wrap_pyfunction!(assign_org_space, m)?)?;
+    m.add_function(wrap_pyfunction!(release_org_space, m)?)?;
+    m.add_function(wrap_pyfunction!(set_org_member, m)?)?;
+    m.add_function(wrap_pyfunction!(get_space_org, m)?)?;
+    m.add_function(wrap_pyfunction!(resolve_org_role, m)?)?;
+    m.add_function(wrap_pyfunction!(get_org_stats, m)?)?;
 
     m.add_function(wrap_pyfunction!(list_spaces, m)?)?;
+    m.add_function(wrap_pyfunction!(list_spaces_detailed, m)?)?;
     m.add_function(wrap_pyfunction!(create_space, m)?)?;
     m.add_function(wrap_pyfunction!(create_sample_space, m)?)?;
     m.add_function(wrap_pyfunction!(list_sample_scenarios, m)?)?;
+    m.add_function(wrap_pyfunction!(cancel_sample_space_job, m)?)?;
     m.add_function(wrap_pyfunction!(create_sample_space_job, m)?)?;
     m.add_function(wrap_pyfunction!(get_sample_space_job, m)?)?;
     m.add_function(wrap_pyfunction!(test_storage_connection_py, m)?)?;
+    m.add_function(wrap_pyfunction!(vacuum_space, m)?)?;
+    m.add_function(wrap_pyfunction!(create_vacuum_job, m)?)?;
+    m.add_function(wrap_pyfunction!(get_vacuum_job, m)?)?;
+    m.add_function(wrap_pyfunction!(list_object_versions, m)?)?;
+    m.add_function(wrap_pyfunction!(restore_object_version, m)?)?;
+    m.add_function(wrap_pyfunction!(restore_space_to_timestamp, m)?)?;
     m.add_function(wrap_pyfunction!(append_audit_event_py, m)?)?;
+    m.add_function(wrap_pyfunction!(list_audit_actions_py, m)?)?;
     m.add_function(wrap_pyfunction!(list_audit_events_py, m)?)?;
+    m.add_function(wrap_pyfunction!(export_audit_events_otlp, m)?)?;
 
     m.add_function(wrap_pyfunction!(create_entry, m)?)?;
+    m.add_function(wrap_pyfunction!(create_entries, m)?)?;
     m.add_function(wrap_pyfunction!(delete_entry, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_entries, m)?)?;
+    m.add_function(wrap_pyfunction!(restore_entries, m)?)?;
+    m.add_function(wrap_pyfunction!(list_deleted_entries, m)?)?;
+    m.add_function(wrap_pyfunction!(purge_entry, m)?)?;
+    m.add_function(wrap_pyfunction!(purge_deleted_older_than, m)?)?;
     m.add_function(wrap_pyfunction!(get_entry, m)?)?;
+    m.add_function(wrap_pyfunction!(get_entry_head, m)?)?;
+    m.add_function(wrap_pyfunction!(get_entry_acl, m)?)?;
+    m.add_function(wrap_pyfunction!(set_entry_acl, m)?)?;
+    m.add_function(wrap_pyfunction!(clear_entry_acl, m)?)?;
+    m.add_function(wrap_pyfunction!(get_entry_outline, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_entry_refs, m)?)?;
+    m.add_function(wrap_pyfunction!(resolve_entry_ref, m)?)?;
+    m.add_function(wrap_pyfunction!(dereference_entry_ref, m)?)?;
+    m.add_function(wrap_pyfunction!(read_entry_section, m)?)?;
+    m.add_function(wrap_pyfunction!(patch_entry_section, m)?)?;
+    m.add_function(wrap_pyfunction!(get_entry_metadata, m)?)?;
+    m.add_function(wrap_pyfunction!(set_entry_metadata, m)?)?;
+    m.add_function(wrap_pyfunction!(register_author_signing_key, m)?)?;
+    m.add_function(wrap_pyfunction!(list_author_signing_keys, m)?)?;
+    m.add_function(wrap_pyfunction!(revoke_author_signing_key, m)?)?;
+    m.add_function(wrap_pyfunction!(get_revision_signing_payload, m)?)?;
+    m.add_function(wrap_pyfunction!(attach_revision_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_revision_author, m)?)?;
+    m.add_function(wrap_pyfunction!(list_entries_ordered, m)?)?;
+    m.add_function(wrap_pyfunction!(pin_entry, m)?)?;
+    m.add_function(wrap_pyfunction!(move_entry, m)?)?;
+    m.add_function(wrap_pyfunction!(reorder_entries, m)?)?;
+    m.add_function(wrap_pyfunction!(create_collection, m)?)?;
+    m.add_function(wrap_pyfunction!(update_collection, m)?)?;
+    m.add_function(wrap_pyfunction!(move_collection, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_collection, m)?)?;
+    m.add_function(wrap_pyfunction!(list_collections, m)?)?;
+    m.add_function(wrap_pyfunction!(move_entry_to_collection, m)?)?;
+    m.add_function(wrap_pyfunction!(list_collection_entries, m)?)?;
     m.add_function(wrap_pyfunction!(get_entry_history, m)?)?;
+    m.add_function(wrap_pyfunction!(list_changes_since, m)?)?;
     m.add_function(wrap_pyfunction!(get_entry_revision, m)?)?;
     m.add_function(wrap_pyfunction!(get_entry_revision_content, m)?)?;
+    m.add_function(wrap_pyfunction!(diff_entry_revisions, m)?)?;
     m.add_function(wrap_pyfunction!(list_entries, m)?)?;
+    m.add_function(wrap_pyfunction!(list_entries_json, m)?)?;
     m.add_function(wrap_pyfunction!(restore_entry, m)?)?;
     m.add_function(wrap_pyfunction!(update_entry, m)?)?;
+    m.add_function(wrap_pyfunction!(transition_entry_state, m)?)?;
+    m.add_function(wrap_pyfunction!(get_entry_state_history, m)?)?;
+    m.add_function(wrap_pyfunction!(bulk_update_properties, m)?)?;
+    m.add_function(wrap_pyfunction!(create_bulk_update_job, m)?)?;
+    m.add_function(wrap_pyfunction!(get_bulk_update_job, m)?)?;
+    m.add_function(wrap_pyfunction!(ingest_entry_properties, m)?)?;
+    m.add_function(wrap_pyfunction!(reconcile_entry_properties, m)?)?;
+    m.add_function(wrap_pyfunction!(preview_replace, m)?)?;
+    m.add_function(wrap_pyfunction!(apply_replace, m)?)?;
+    m.add_function(wrap_pyfunction!(undo_replace, m)?)?;
+    m.add_function(wrap_pyfunction!(get_related_entries, m)?)?;
+    m.add_function(wrap_pyfunction!(get_space_analytics, m)?)?;
+    m.add_function(wrap_pyfunction!(lint_entry, m)?)?;
+    m.add_function(wrap_pyfunction!(lint_space, m)?)?;
     m.add_function(wrap_pyfunction!(list_sql, m)?)?;
     m.add_function(wrap_pyfunction!(get_sql, m)?)?;
+    m.add_function(wrap_pyfunction!(run_sql, m)?)?;
+    m.add_function(wrap_pyfunction!(get_sql_share, m)?)?;
+    m.add_function(wrap_pyfunction!(set_sql_share, m)?)?;
     m.add_function(wrap_pyfunction!(create_sql, m)?)?;
     m.add_function(wrap_pyfunction!(update_sql, m)?)?;
     m.add_function(wrap_pyfunction!(delete_sql, m)?)?;
+    m.add_function(wrap_pyfunction!(create_dashboard, m)?)?;
+    m.add_function(wrap_pyfunction!(update_dashboard, m)?)?;
+    m.add_function(wrap_pyfunction!(get_dashboard, m)?)?;
+    m.add_function(wrap_pyfunction!(list_dashboards, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_dashboard, m)?)?;
+    m.add_function(wrap_pyfunction!(render_dashboard, m)?)?;
+    m.add_function(wrap_pyfunction!(create_form_view, m)?)?;
+    m.add_function(wrap_pyfunction!(update_form_view, m)?)?;
+    m.add_function(wrap_pyfunction!(get_form_view, m)?)?;
+    m.add_function(wrap_pyfunction!(list_form_views, m)?)?;
+    m.add_function(wrap_pyfunction!(get_default_form_view, m)?)?;
+    m.add_function(wrap_pyfunction!(delete_form_view, m)?)?;
     m.add_function(wrap_pyfunction!(extract_properties_py, m)?)?;
     m.add_function(wrap_pyfunction!(validate_properties_py, m)?)?;
+    m.add_function(wrap_pyfunction!(render_markdown, m)?)?;
+    m.add_function(wrap_pyfunction!(render_entry_html, m)?)?;
+    m.add_function(wrap_pyfunction!(list_tasks, m)?)?;
+    m.add_function(wrap_pyfunction!(toggle_task, m)?)?;
+    m.add_function(wrap_pyfunction!(list_mentions, m)?)?;
+    m.add_function(wrap_pyfunction!(mark_mentions_read, m)?)?;
+    m.add_function(wrap_pyfunction!(watch, m)?)?;
+    m.add_function(wrap_pyfunction!(unwatch, m)?)?;
+    m.add_function(wrap_pyfunction!(list_watches, m)?)?;
+    m.add_function(wrap_pyfunction!(list_watch_notifications, m)?)?;
+    m.add_function(wrap_pyfunction!(ack_watch_notifications, m)?)?;
+    m.add_function(wrap_pyfunction!(list_due_reminders, m)?)?;
+    m.add_function(wrap_pyfunction!(snooze_reminder, m)?)?;
+    m.add_function(wrap_pyfunction!(unsnooze_reminder, m)?)?;
+    m.add_function(wrap_pyfunction!(dispatch_due_reminders, m)?)?;
 
     m.add_function(wrap_pyfunction!(list_forms, m)?)?;
     m.add_function(wrap_pyfunction!(upsert_form, m)?)?;
     m.add_function(wrap_pyfunction!(get_form, m)?)?;
     m.add_function(wrap_pyfunction!(list_column_types, m)?)?;
     m.add_function(wrap_pyfunction!(migrate_form, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_unique_index, m)?)?;
 
     m.add_function(wrap_pyfunction!(save_asset, m)?)?;
+    m.add_function(wrap_pyfunction!(save_asset_from_url, m)?)?;
     m.add_function(wrap_pyfunction!(list_assets, m)?)?;
+    m.add_function(wrap_pyfunction!(rewrite_asset_links, m)?)?;
     m.add_function(wrap_pyfunction!(delete_asset, m)?)?;
 
+    m.add_function(wrap_pyfunction!(list_global_feature_flags, m)?)?;
+    m.add_function(wrap_pyfunction!(set_global_feature_flag, m)?)?;
+    m.add_function(wrap_pyfunction!(set_space_feature_flag, m)?)?;
+    m.add_function(wrap_pyfunction!(list_space_feature_flags, m)?)?;
+    m.add_function(wrap_pyfunction!(is_feature_enabled, m)?)?;
+    m.add_function(wrap_pyfunction!(apply_collab_update, m)?)?;
+    m.add_function(wrap_pyfunction!(get_collab_state_vector, m)?)?;
+    m.add_function(wrap_pyfunction!(encode_collab_diff, m)?)?;
+    m.add_function(wrap_pyfunction!(compact_collab_updates, m)?)?;
+    m.add_function(wrap_pyfunction!(sync_pull, m)?)?;
+    m.add_function(wrap_pyfunction!(sync_push, m)?)?;
+
     m.add_function(wrap_pyfunction!(get_space, m)?)?;
     m.add_function(wrap_pyfunction!(patch_space, m)?)?;
     m.add_function(wrap_pyfunction!(get_user_preferences, m)?)?;
     m.add_function(wrap_pyfunction!(patch_user_preferences, m)?)?;
 
     m.add_function(wrap_pyfunction!(query_index, m)?)?;
+    m.add_function(wrap_pyfunction!(query_index_json, m)?)?;
+    m.add_function(wrap_pyfunction!(query_index_page, m)?)?;
     m.add_function(wrap_pyfunction!(list_entry_summaries, m)?)?;
     m.add_function(wrap_pyfunction!(create_sql_session, m)?)?;
     m.add_function(wrap_pyfunction!(get_sql_session_status, m)?)?;
     m.add_function(wrap_pyfunction!(get_sql_session_count, m)?)?;
     m.add_function(wrap_pyfunction!(get_sql_session_count_scoped, m)?)?;
+    m.add_function(wrap_pyfunction!(get_sql_session_columns, m)?)?;
     m.add_function(wrap_pyfunction!(get_sql_session_rows, m)?)?;
+    m.add_function(wrap_pyfunction!(get_sql_session_rows_json, m)?)?;
     m.add_function(wrap_pyfunction!(get_sql_session_rows_scoped, m)?)?;
     m.add_function(wrap_pyfunction!(get_sql_session_rows_all, m)?)?;
     m.add_function(wrap_pyfunction!(get_sql_session_rows_all_scoped, m)?)?;
@@ -1383,7 +5246,17 @@ fn _ugoite_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(update_entry_index, m)?)?;
 
     m.add_function(wrap_pyfunction!(search_entries, m)?)?;
+    m.add_function(wrap_pyfunction!(search_entries_json, m)?)?;
     m.add_function(wrap_pyfunction!(build_response_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(begin_response_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(build_request_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_request_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_webhook_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(ingest_webhook_event, m)?)?;
+    m.add_function(wrap_pyfunction!(format_chat_notification, m)?)?;
+    m.add_function(wrap_pyfunction!(export_key_recovery_codes, m)?)?;
+    m.add_function(wrap_pyfunction!(restore_key_recovery_codes, m)?)?;
+    m.add_class::<PyResponseSigner>()?;
     m.add_function(wrap_pyfunction!(load_hmac_material, m)?)?;
     m.add_function(wrap_pyfunction!(load_response_hmac_material, m)?)?;
 
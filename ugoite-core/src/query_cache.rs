@@ -0,0 +1,399 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::iceberg_store;
+use crate::index;
+use crate::saved_sql;
+use crate::storage;
+
+const CACHE_DIR: &str = "query_cache";
+
+/// Bounds for cached SQL results. A zero byte bound disables that tier.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QueryCacheConfig {
+    #[serde(default = "default_ttl_ms")]
+    pub ttl_ms: u64,
+    #[serde(default = "default_max_memory_bytes")]
+    pub max_memory_bytes: usize,
+    /// Results are also written under `{space}/query_cache/` so they survive restarts.
+    #[serde(default)]
+    pub max_disk_bytes: usize,
+}
+
+fn default_ttl_ms() -> u64 {
+    60_000
+}
+
+fn default_max_memory_bytes() -> usize {
+    32 * 1024 * 1024
+}
+
+impl Default for QueryCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_ms: default_ttl_ms(),
+            max_memory_bytes: default_max_memory_bytes(),
+            max_disk_bytes: 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CachedQueryResult {
+    pub rows: Vec<Value>,
+    /// Entries generation the rows were computed at.
+    pub generation: String,
+    pub cached: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredResult {
+    generation: String,
+    stored_at_ms: i64,
+    rows: Arc<Vec<Value>>,
+}
+
+struct MemoryItem {
+    result: StoredResult,
+    bytes: usize,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct MemoryCache {
+    items: HashMap<String, MemoryItem>,
+    bytes: usize,
+    tick: u64,
+}
+
+impl MemoryCache {
+    fn remove(&mut self, key: &str) {
+        if let Some(item) = self.items.remove(key) {
+            self.bytes -= item.bytes;
+        }
+    }
+
+    fn insert(&mut self, key: String, result: StoredResult, bytes: usize, max_bytes: usize) {
+        self.remove(&key);
+        if bytes > max_bytes {
+            return;
+        }
+        while self.bytes + bytes > max_bytes {
+            let Some(oldest) = self
+                .items
+                .iter()
+                .min_by_key(|(_, item)| item.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+        self.tick += 1;
+        self.bytes += bytes;
+        self.items.insert(
+            key,
+            MemoryItem {
+                result,
+                bytes,
+                last_used: self.tick,
+            },
+        );
+    }
+}
+
+static MEMORY_CACHE: OnceLock<Mutex<MemoryCache>> = OnceLock::new();
+
+fn memory_cache() -> Result<std::sync::MutexGuard<'static, MemoryCache>> {
+    MEMORY_CACHE
+        .get_or_init(|| Mutex::new(MemoryCache::default()))
+        .lock()
+        .map_err(|_| anyhow!("query cache lock poisoned"))
+}
+
+/// Collapse whitespace outside string literals and drop a trailing semicolon.
+fn normalize_sql(sql: &str) -> String {
+    let mut normalized = String::with_capacity(sql.len());
+    let mut in_string = false;
+    let mut pending_space = false;
+    for ch in sql.trim().trim_end_matches(';').trim_end().chars() {
+        if !in_string && ch.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if pending_space && !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        pending_space = false;
+        if ch == '\'' {
+            in_string = !in_string;
+        }
+        normalized.push(ch);
+    }
+    normalized
+}
+
+fn cache_key(sql: &str, params: &Value, scope: Option<(&[String], bool)>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(normalize_sql(sql).as_bytes());
+    hasher.update([0]);
+    hasher.update(params.to_string().as_bytes());
+    hasher.update([0]);
+    if let Some((readable_forms, include_untyped_entries)) = scope {
+        let mut forms: Vec<String> = readable_forms.iter().map(|f| f.to_lowercase()).collect();
+        forms.sort();
+        forms.dedup();
+        hasher.update(forms.join(",").as_bytes());
+        hasher.update([u8::from(include_untyped_entries)]);
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn memory_key(ws_path: &str, key: &str) -> String {
+    format!("{}#{}", ws_path.trim_end_matches('/'), key)
+}
+
+fn cache_dir(ws_path: &str) -> String {
+    format!("{}/{}/", ws_path.trim_end_matches('/'), CACHE_DIR)
+}
+
+fn is_fresh(
+    result: &StoredResult,
+    generation: &str,
+    now_ms: i64,
+    config: &QueryCacheConfig,
+) -> bool {
+    result.generation == generation && now_ms - result.stored_at_ms < config.ttl_ms as i64
+}
+
+async fn read_disk(op: &Operator, ws_path: &str, key: &str) -> Result<Option<StoredResult>> {
+    let path = format!("{}{}.json", cache_dir(ws_path), key);
+    match op.read(&path).await {
+        // A torn or outdated file is just a miss.
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes.to_vec()).ok()),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Write a result file, then drop the oldest files until the directory fits `max_bytes`.
+async fn write_disk(
+    op: &Operator,
+    ws_path: &str,
+    key: &str,
+    body: Vec<u8>,
+    max_bytes: usize,
+) -> Result<()> {
+    if body.len() > max_bytes {
+        return Ok(());
+    }
+    let dir = cache_dir(ws_path);
+    storage::publish_atomic(op, &format!("{dir}{key}.json"), body).await?;
+    let mut files = Vec::new();
+    for item in op.list(&dir).await? {
+        if !item.name().ends_with(".json") {
+            continue;
+        }
+        let meta = op.stat(item.path()).await?;
+        files.push((
+            meta.last_modified(),
+            item.path().to_string(),
+            meta.content_length() as usize,
+        ));
+    }
+    files.sort();
+    let mut total: usize = files.iter().map(|(_, _, size)| size).sum();
+    for (_, path, size) in files {
+        if total <= max_bytes {
+            break;
+        }
+        if path.ends_with(&format!("/{key}.json")) {
+            continue;
+        }
+        op.delete(&path).await?;
+        total -= size;
+    }
+    Ok(())
+}
+
+async fn execute_cached(
+    op: &Operator,
+    ws_path: &str,
+    sql: &str,
+    params: &Value,
+    scope: Option<(&[String], bool)>,
+    config: &QueryCacheConfig,
+) -> Result<CachedQueryResult> {
+    let bound_sql = saved_sql::bind_sql_params(sql, params)?;
+    let generation = iceberg_store::entries_generation(op, ws_path).await?;
+    let key = cache_key(sql, params, scope);
+    let now_ms = Utc::now().timestamp_millis();
+
+    if config.max_memory_bytes > 0 {
+        let mut cache = memory_cache()?;
+        let memory_key = memory_key(ws_path, &key);
+        cache.tick += 1;
+        let tick = cache.tick;
+        match cache.items.get_mut(&memory_key) {
+            Some(item) if is_fresh(&item.result, &generation, now_ms, config) => {
+                item.last_used = tick;
+                return Ok(CachedQueryResult {
+                    rows: item.result.rows.as_ref().clone(),
+                    generation,
+                    cached: true,
+                });
+            }
+            Some(_) => cache.remove(&memory_key),
+            None => {}
+        }
+    }
+    if config.max_disk_bytes > 0 {
+        if let Some(stored) = read_disk(op, ws_path, &key).await? {
+            if is_fresh(&stored, &generation, now_ms, config) {
+                let rows = stored.rows.as_ref().clone();
+                if config.max_memory_bytes > 0 {
+                    let bytes = serde_json::to_vec(&stored)?.len();
+                    memory_cache()?.insert(
+                        memory_key(ws_path, &key),
+                        stored,
+                        bytes,
+                        config.max_memory_bytes,
+                    );
+                }
+                return Ok(CachedQueryResult {
+                    rows,
+                    generation,
+                    cached: true,
+                });
+            }
+        }
+    }
+
+    let rows = match scope {
+        Some((readable_forms, include_untyped_entries)) => {
+            index::execute_sql_query_scoped(
+                op,
+                ws_path,
+                &bound_sql,
+                readable_forms,
+                include_untyped_entries,
+            )
+            .await?
+        }
+        None => index::execute_sql_query(op, ws_path, &bound_sql).await?,
+    };
+    // Writes that landed while the query ran may or may not be in `rows`.
+    if iceberg_store::entries_generation(op, ws_path).await? != generation {
+        return Ok(CachedQueryResult {
+            rows,
+            generation,
+            cached: false,
+        });
+    }
+    let stored = StoredResult {
+        generation: generation.clone(),
+        stored_at_ms: now_ms,
+        rows: Arc::new(rows.clone()),
+    };
+    let body = serde_json::to_vec(&stored)?;
+    if config.max_disk_bytes > 0 {
+        write_disk(op, ws_path, &key, body.clone(), config.max_disk_bytes).await?;
+    }
+    if config.max_memory_bytes > 0 {
+        memory_cache()?.insert(
+            memory_key(ws_path, &key),
+            stored,
+            body.len(),
+            config.max_memory_bytes,
+        );
+    }
+    Ok(CachedQueryResult {
+        rows,
+        generation,
+        cached: false,
+    })
+}
+
+/// Run `sql` with `params` bound to its `{{name}}` placeholders, reusing a
+/// cached result while the space's entries generation is unchanged.
+pub async fn execute_sql_cached(
+    op: &Operator,
+    ws_path: &str,
+    sql: &str,
+    params: &Value,
+    config: &QueryCacheConfig,
+) -> Result<CachedQueryResult> {
+    execute_cached(op, ws_path, sql, params, None, config).await
+}
+
+pub async fn execute_sql_cached_scoped(
+    op: &Operator,
+    ws_path: &str,
+    sql: &str,
+    params: &Value,
+    readable_forms: &[String],
+    include_untyped_entries: bool,
+    config: &QueryCacheConfig,
+) -> Result<CachedQueryResult> {
+    execute_cached(
+        op,
+        ws_path,
+        sql,
+        params,
+        Some((readable_forms, include_untyped_entries)),
+        config,
+    )
+    .await
+}
+
+/// Drop every cached result for a space, in memory and on disk.
+pub async fn clear_space_cache(op: &Operator, ws_path: &str) -> Result<()> {
+    let prefix = memory_key(ws_path, "");
+    {
+        let mut cache = memory_cache()?;
+        let keys: Vec<String> = cache
+            .items
+            .keys()
+            .filter(|key| key.starts_with(&prefix))
+            .cloned()
+            .collect();
+        for key in keys {
+            cache.remove(&key);
+        }
+    }
+    let dir = cache_dir(ws_path);
+    if op.exists(&dir).await? {
+        op.remove_all(&dir).await?;
+    }
+    Ok(())
+}
+
+/// Delete disk-tier results computed at an older entries generation, which
+/// can never be served again. Returns how many files were removed.
+pub(crate) async fn purge_stale_disk(op: &Operator, ws_path: &str) -> Result<usize> {
+    let dir = cache_dir(ws_path);
+    if !op.exists(&dir).await? {
+        return Ok(0);
+    }
+    let generation = iceberg_store::entries_generation(op, ws_path).await?;
+    let mut removed = 0;
+    for item in op.list(&dir).await? {
+        let Some(key) = item.name().strip_suffix(".json") else {
+            continue;
+        };
+        let current = read_disk(op, ws_path, key)
+            .await?
+            .is_some_and(|stored| stored.generation == generation);
+        if !current {
+            op.delete(item.path()).await?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
@@ -0,0 +1,327 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use crate::entry;
+
+const EMAIL_PATTERN: &str = r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}";
+const CREDIT_CARD_PATTERN: &str = r"\d(?:[ -]?\d){12,18}";
+const PHONE_PATTERN: &str =
+    r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{1,4}\)[ .-]?)?\d{2,4}(?:[ .-]\d{2,4}){1,3}";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CustomPiiPattern {
+    pub name: String,
+    pub pattern: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct RedactionConfig {
+    pub emails: bool,
+    pub phone_numbers: bool,
+    pub credit_cards: bool,
+    pub custom: Vec<CustomPiiPattern>,
+    /// Replacement text; `{kind}` is substituted with the finding kind.
+    pub replacement: String,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            emails: true,
+            phone_numbers: true,
+            credit_cards: true,
+            custom: Vec::new(),
+            replacement: "[REDACTED:{kind}]".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PiiFinding {
+    pub kind: String,
+    /// Dotted location of the match, e.g. `title` or `sections.Body`.
+    pub path: String,
+    /// Byte offsets of the match within the value at `path`.
+    pub start: usize,
+    pub end: usize,
+    /// Masked excerpt so reports do not repeat the sensitive value.
+    pub preview: String,
+}
+
+struct Rule {
+    kind: String,
+    regex: Regex,
+    /// Digit-based rules must not start or end inside a longer token.
+    bounded: bool,
+    validate: Option<fn(&str) -> bool>,
+}
+
+pub struct Redactor {
+    rules: Vec<Rule>,
+    replacement: String,
+}
+
+fn luhn_valid(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(idx, &d)| {
+            if idx % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn phone_valid(candidate: &str) -> bool {
+    let digit_count = candidate.chars().filter(char::is_ascii_digit).count();
+    if !(9..=15).contains(&digit_count) {
+        return false;
+    }
+    // ISO dates and timestamps share the grouped-digit shape.
+    static ISO_DATE: OnceLock<Regex> = OnceLock::new();
+    let iso_date = ISO_DATE.get_or_init(|| Regex::new(r"^\d{4}-\d{2}-\d{2}").unwrap());
+    !iso_date.is_match(candidate)
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '/')
+}
+
+fn is_bounded(text: &str, range: &Range<usize>) -> bool {
+    let before = text[..range.start].chars().next_back();
+    let after = text[range.end..].chars().next();
+    // A trailing sentence period is fine; a period followed by more token text is not.
+    let after_ok = match after {
+        None => true,
+        Some('.') => !text[range.end + 1..]
+            .chars()
+            .next()
+            .is_some_and(char::is_alphanumeric),
+        Some(c) => !is_token_char(c),
+    };
+    before.is_none_or(|c| !is_token_char(c)) && after_ok
+}
+
+fn preview(matched: &str) -> String {
+    let chars: Vec<char> = matched.chars().collect();
+    if chars.len() <= 6 {
+        return "*".repeat(chars.len());
+    }
+    let head: String = chars[..2].iter().collect();
+    let tail: String = chars[chars.len() - 2..].iter().collect();
+    format!("{}{}{}", head, "*".repeat(chars.len() - 4), tail)
+}
+
+impl Redactor {
+    pub fn new(config: &RedactionConfig) -> Result<Self> {
+        let mut rules = Vec::new();
+        if config.credit_cards {
+            rules.push(Rule {
+                kind: "credit_card".to_string(),
+                regex: Regex::new(CREDIT_CARD_PATTERN)?,
+                bounded: true,
+                validate: Some(luhn_valid),
+            });
+        }
+        if config.emails {
+            rules.push(Rule {
+                kind: "email".to_string(),
+                regex: Regex::new(EMAIL_PATTERN)?,
+                bounded: false,
+                validate: None,
+            });
+        }
+        if config.phone_numbers {
+            rules.push(Rule {
+                kind: "phone".to_string(),
+                regex: Regex::new(PHONE_PATTERN)?,
+                bounded: true,
+                validate: Some(phone_valid),
+            });
+        }
+        for custom in &config.custom {
+            let kind = custom.name.trim();
+            if kind.is_empty() {
+                return Err(anyhow!("Custom PII pattern name must not be empty"));
+            }
+            let regex = Regex::new(&custom.pattern)
+                .map_err(|e| anyhow!("Invalid PII pattern '{}': {}", kind, e))?;
+            rules.push(Rule {
+                kind: kind.to_string(),
+                regex,
+                bounded: false,
+                validate: None,
+            });
+        }
+        Ok(Self {
+            rules,
+            replacement: config.replacement.clone(),
+        })
+    }
+
+    /// Non-overlapping matches ordered by position; earlier rules win ties.
+    fn matches(&self, text: &str) -> Vec<(usize, Range<usize>)> {
+        let mut found = Vec::new();
+        for (rule_idx, rule) in self.rules.iter().enumerate() {
+            for m in rule.regex.find_iter(text) {
+                if m.is_empty() {
+                    continue;
+                }
+                let range = m.range();
+                if rule.bounded && !is_bounded(text, &range) {
+                    continue;
+                }
+                if rule.validate.is_some_and(|validate| !validate(m.as_str())) {
+                    continue;
+                }
+                found.push((rule_idx, range));
+            }
+        }
+        found.sort_by_key(|(rule_idx, range)| (range.start, *rule_idx));
+        let mut selected: Vec<(usize, Range<usize>)> = Vec::with_capacity(found.len());
+        for (rule_idx, range) in found {
+            if selected
+                .last()
+                .is_some_and(|(_, last)| range.start < last.end)
+            {
+                continue;
+            }
+            selected.push((rule_idx, range));
+        }
+        selected
+    }
+
+    pub fn scan_text(&self, path: &str, text: &str) -> Vec<PiiFinding> {
+        self.matches(text)
+            .into_iter()
+            .map(|(rule_idx, range)| PiiFinding {
+                kind: self.rules[rule_idx].kind.clone(),
+                path: path.to_string(),
+                preview: preview(&text[range.clone()]),
+                start: range.start,
+                end: range.end,
+            })
+            .collect()
+    }
+
+    pub fn redact_text(&self, text: &str) -> String {
+        let mut redacted = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for (rule_idx, range) in self.matches(text) {
+            redacted.push_str(&text[cursor..range.start]);
+            redacted.push_str(
+                &self
+                    .replacement
+                    .replace("{kind}", &self.rules[rule_idx].kind),
+            );
+            cursor = range.end;
+        }
+        redacted.push_str(&text[cursor..]);
+        redacted
+    }
+
+    /// Scan every string inside a JSON value; `path` names the value itself.
+    pub fn scan_value(&self, path: &str, value: &Value) -> Vec<PiiFinding> {
+        let mut findings = Vec::new();
+        self.scan_value_into(path, value, &mut findings);
+        findings
+    }
+
+    fn scan_value_into(&self, path: &str, value: &Value, findings: &mut Vec<PiiFinding>) {
+        let child_path = |key: &str| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            }
+        };
+        match value {
+            Value::String(text) => findings.extend(self.scan_text(path, text)),
+            Value::Array(items) => {
+                for (idx, item) in items.iter().enumerate() {
+                    self.scan_value_into(&child_path(&idx.to_string()), item, findings);
+                }
+            }
+            Value::Object(map) => {
+                for (key, item) in map {
+                    self.scan_value_into(&child_path(key), item, findings);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn redact_value(&self, value: &Value) -> Value {
+        match value {
+            Value::String(text) => Value::String(self.redact_text(text)),
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|item| self.redact_value(item)).collect())
+            }
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, item)| (key.clone(), self.redact_value(item)))
+                    .collect::<Map<String, Value>>(),
+            ),
+            other => other.clone(),
+        }
+    }
+}
+
+/// Redactor with the default rules, shared by audit metadata scrubbing.
+pub fn default_redactor() -> &'static Redactor {
+    static DEFAULT: OnceLock<Redactor> = OnceLock::new();
+    DEFAULT.get_or_init(|| {
+        Redactor::new(&RedactionConfig::default()).expect("default PII patterns compile")
+    })
+}
+
+pub async fn scan_entry(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    config: &RedactionConfig,
+) -> Result<Vec<PiiFinding>> {
+    let redactor = Redactor::new(config)?;
+    let entry_value = entry::get_entry(op, ws_path, entry_id).await?;
+    let mut findings = redactor.scan_value("title", &entry_value["title"]);
+    findings.extend(redactor.scan_value("sections", &entry_value["sections"]));
+    Ok(findings)
+}
+
+/// Entry payload as returned by `get_entry` with PII replaced in title, content and sections.
+pub async fn redact_entry(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    config: &RedactionConfig,
+) -> Result<Value> {
+    let redactor = Redactor::new(config)?;
+    let mut entry_value = entry::get_entry(op, ws_path, entry_id).await?;
+    for key in ["title", "content", "sections"] {
+        if let Some(value) = entry_value.get(key) {
+            let redacted = redactor.redact_value(value);
+            entry_value[key] = redacted;
+        }
+    }
+    Ok(entry_value)
+}
@@ -0,0 +1,266 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::feature_flags;
+use crate::index;
+
+/// Largest number of suggestions a single call may return.
+pub const MAX_RELATED_LIMIT: usize = 100;
+
+const TAG_WEIGHT: f64 = 0.35;
+const LINK_WEIGHT: f64 = 0.35;
+const TERM_WEIGHT: f64 = 0.3;
+/// Added on top of the other signals when both entries have an embedding.
+const EMBEDDING_WEIGHT: f64 = 0.5;
+/// A shared neighbour counts for this fraction of a direct link.
+const TWO_HOP_FACTOR: f64 = 0.5;
+const MIN_TERM_LEN: usize = 3;
+const STOP_WORDS: &[&str] = &[
+    "and", "are", "but", "for", "from", "has", "have", "into", "not", "that", "the", "their",
+    "this", "was", "were", "will", "with", "you", "your",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkProximity {
+    /// The entries link to each other.
+    Direct,
+    /// The entries link to a common entry.
+    TwoHop,
+}
+
+/// Why an entry was suggested; each signal is reported only when it scored.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct RelatedReasons {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<LinkProximity>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared_terms: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_similarity: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RelatedEntry {
+    pub id: String,
+    pub title: String,
+    pub form: String,
+    pub score: f64,
+    pub reasons: RelatedReasons,
+}
+
+struct Candidate {
+    id: String,
+    title: String,
+    form: String,
+    tags: BTreeSet<String>,
+    neighbours: HashSet<String>,
+    terms: BTreeSet<String>,
+}
+
+fn collect_terms(text: &str, terms: &mut BTreeSet<String>) {
+    for word in text.split(|ch: char| !ch.is_alphanumeric()) {
+        let word = word.to_lowercase();
+        if word.chars().count() >= MIN_TERM_LEN
+            && !word.chars().all(|ch| ch.is_ascii_digit())
+            && !STOP_WORDS.contains(&word.as_str())
+        {
+            terms.insert(word);
+        }
+    }
+}
+
+fn text_values(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::String(text) => out.push(text.clone()),
+        Value::Array(items) => items.iter().for_each(|item| text_values(item, out)),
+        Value::Object(map) => map.values().for_each(|item| text_values(item, out)),
+        _ => {}
+    }
+}
+
+fn candidate_from_record(record: &Value) -> Option<Candidate> {
+    let str_field = |key: &str| record.get(key).and_then(Value::as_str).unwrap_or_default();
+    let id = record.get("id").and_then(Value::as_str)?.to_string();
+    let tags = record
+        .get("tags")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_lowercase)
+        .collect();
+    let neighbours = record
+        .get("links")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .flat_map(|link| ["source", "target"].map(|key| link.get(key).and_then(Value::as_str)))
+        .flatten()
+        .filter(|linked| *linked != id)
+        .map(str::to_string)
+        .collect();
+    let mut texts = vec![str_field("title").to_string()];
+    if let Some(properties) = record.get("properties") {
+        text_values(properties, &mut texts);
+    }
+    let mut terms = BTreeSet::new();
+    for text in &texts {
+        collect_terms(text, &mut terms);
+    }
+    Some(Candidate {
+        title: str_field("title").to_string(),
+        form: str_field("form").to_string(),
+        id,
+        tags,
+        neighbours,
+        terms,
+    })
+}
+
+fn jaccard(shared: usize, left: usize, right: usize) -> f64 {
+    let union = left + right - shared;
+    if union == 0 {
+        0.0
+    } else {
+        shared as f64 / union as f64
+    }
+}
+
+fn cosine(left: &[f32], right: &[f32]) -> Option<f64> {
+    if left.len() != right.len() || left.is_empty() {
+        return None;
+    }
+    let (mut dot, mut left_norm, mut right_norm) = (0.0_f64, 0.0_f64, 0.0_f64);
+    for (a, b) in left.iter().zip(right) {
+        dot += f64::from(*a) * f64::from(*b);
+        left_norm += f64::from(*a) * f64::from(*a);
+        right_norm += f64::from(*b) * f64::from(*b);
+    }
+    if left_norm == 0.0 || right_norm == 0.0 {
+        return None;
+    }
+    Some(dot / (left_norm.sqrt() * right_norm.sqrt()))
+}
+
+fn score(
+    subject: &Candidate,
+    other: &Candidate,
+    embeddings: Option<&HashMap<String, Vec<f32>>>,
+) -> (f64, RelatedReasons) {
+    let mut reasons = RelatedReasons {
+        shared_tags: subject.tags.intersection(&other.tags).cloned().collect(),
+        shared_terms: subject.terms.intersection(&other.terms).cloned().collect(),
+        ..RelatedReasons::default()
+    };
+    let mut total = TAG_WEIGHT
+        * jaccard(
+            reasons.shared_tags.len(),
+            subject.tags.len(),
+            other.tags.len(),
+        )
+        + TERM_WEIGHT
+            * jaccard(
+                reasons.shared_terms.len(),
+                subject.terms.len(),
+                other.terms.len(),
+            );
+
+    if subject.neighbours.contains(&other.id) || other.neighbours.contains(&subject.id) {
+        reasons.link = Some(LinkProximity::Direct);
+        total += LINK_WEIGHT;
+    } else if !subject.neighbours.is_disjoint(&other.neighbours) {
+        reasons.link = Some(LinkProximity::TwoHop);
+        total += LINK_WEIGHT * TWO_HOP_FACTOR;
+    }
+
+    if let Some(embeddings) = embeddings {
+        let similarity = embeddings
+            .get(&subject.id)
+            .zip(embeddings.get(&other.id))
+            .and_then(|(left, right)| cosine(left, right));
+        if let Some(similarity) = similarity.filter(|similarity| *similarity > 0.0) {
+            reasons.embedding_similarity = Some(similarity);
+            total += EMBEDDING_WEIGHT * similarity;
+        }
+    }
+    (total, reasons)
+}
+
+/// Entries related to `entry_id`, best first, scored from the index by
+/// shared tags, link proximity and overlapping title/property terms.
+pub async fn get_related_entries(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    limit: usize,
+) -> Result<Vec<RelatedEntry>> {
+    related_entries(op, ws_path, entry_id, limit, None).await
+}
+
+/// [`get_related_entries`] with caller-supplied embeddings (entry id to
+/// vector) adding cosine similarity as a further signal. Embeddings are
+/// ignored while the space has the `semantic_related` flag turned off.
+pub async fn get_related_entries_with_embeddings(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    limit: usize,
+    embeddings: &HashMap<String, Vec<f32>>,
+) -> Result<Vec<RelatedEntry>> {
+    let semantic =
+        feature_flags::is_enabled_at(op, ws_path, feature_flags::SEMANTIC_RELATED).await?;
+    related_entries(op, ws_path, entry_id, limit, semantic.then_some(embeddings)).await
+}
+
+async fn related_entries(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    limit: usize,
+    embeddings: Option<&HashMap<String, Vec<f32>>>,
+) -> Result<Vec<RelatedEntry>> {
+    if limit == 0 || limit > MAX_RELATED_LIMIT {
+        return Err(anyhow!(
+            "Related entry limit must be between 1 and {}",
+            MAX_RELATED_LIMIT
+        ));
+    }
+    let candidates: Vec<Candidate> = index::query_index(op, ws_path, "")
+        .await?
+        .iter()
+        .filter_map(candidate_from_record)
+        .collect();
+    let subject = candidates
+        .iter()
+        .find(|candidate| candidate.id == entry_id)
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+
+    let mut related: Vec<RelatedEntry> = candidates
+        .iter()
+        .filter(|candidate| candidate.id != subject.id)
+        .filter_map(|candidate| {
+            let (score, reasons) = score(subject, candidate, embeddings);
+            (score > 0.0).then(|| RelatedEntry {
+                id: candidate.id.clone(),
+                title: candidate.title.clone(),
+                form: candidate.form.clone(),
+                score,
+                reasons,
+            })
+        })
+        .collect();
+    related.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    related.truncate(limit);
+    Ok(related)
+}
@@ -0,0 +1,454 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, NaiveDate};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::entry;
+use crate::form;
+use crate::space_lock;
+use crate::storage;
+use crate::watchers::{self, WatchEvent, WatchEventKind};
+
+const REMINDERS_DIR: &str = "reminders";
+/// Space lease serializing snooze and delivery state updates across processes.
+const REMINDERS_LEASE: &str = "reminders";
+/// Field types whose values can come due.
+const REMINDER_FIELD_TYPES: &[&str] = &["date", "timestamp", "timestamp_tz"];
+/// How far ahead [`list_due_reminders`] looks by default.
+pub const DEFAULT_REMINDER_HORIZON_DAYS: u64 = 7;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Reminder settings of a date field: `"reminder": true`, or
+/// `"reminder": {"lead_days": 2}` to notify watchers ahead of the due date.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ReminderSpec {
+    #[serde(default)]
+    pub lead_days: u64,
+}
+
+impl ReminderSpec {
+    /// The field's reminder settings, or `None` when it is not a reminder.
+    pub fn from_field_def(def: &Value) -> Result<Option<Self>> {
+        match def.get("reminder") {
+            None | Some(Value::Null) | Some(Value::Bool(false)) => Ok(None),
+            Some(Value::Bool(true)) => Ok(Some(Self::default())),
+            Some(value @ Value::Object(_)) => serde_json::from_value(value.clone())
+                .map(Some)
+                .map_err(|e| anyhow!("Invalid reminder settings: {}", e)),
+            Some(_) => Err(anyhow!(
+                "reminder must be a boolean or an object with lead_days"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderStatus {
+    Upcoming,
+    Overdue,
+}
+
+/// A reminder field value that is due soon or already past. Times are unix
+/// seconds; date values are due at midnight UTC.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DueReminder {
+    pub entry_id: String,
+    pub entry_title: String,
+    pub form: String,
+    pub field: String,
+    /// The field value as stored.
+    pub due: String,
+    pub due_at: f64,
+    /// When watchers are notified: `due_at` less the field's lead days.
+    pub remind_at: f64,
+    pub status: ReminderStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snoozed_until: Option<f64>,
+}
+
+/// Filters for [`list_due_reminders`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DueReminderOptions {
+    /// Reference time; the current time when unset.
+    pub now: Option<f64>,
+    /// Upcoming reminders due within this many days are listed.
+    pub horizon_days: u64,
+    /// Whose snoozes apply.
+    pub principal: Option<String>,
+    /// List reminders the principal snoozed, with `snoozed_until` set.
+    pub include_snoozed: bool,
+}
+
+impl Default for DueReminderOptions {
+    fn default() -> Self {
+        Self {
+            now: None,
+            horizon_days: DEFAULT_REMINDER_HORIZON_DAYS,
+            principal: None,
+            include_snoozed: false,
+        }
+    }
+}
+
+/// Outcome of [`dispatch_due_reminders`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ReminderDispatchReport {
+    /// Reminders sent to at least one watcher.
+    pub dispatched: usize,
+    /// Notifications appended to watcher queues.
+    pub notifications: usize,
+    /// Watchers skipped because they snoozed the reminder.
+    pub snoozed: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct Snooze {
+    entry_id: String,
+    field: String,
+    until: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct SnoozeList {
+    #[serde(default)]
+    snoozes: Vec<Snooze>,
+}
+
+/// Due value last sent to each principal per entry and field, so a reminder
+/// reaches every watcher once, after any snooze, and again only after its
+/// date changes.
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct DeliveryLog {
+    #[serde(default)]
+    sent: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+fn delivery_key(entry_id: &str, field: &str) -> String {
+    format!("{}/{}", entry_id, field)
+}
+
+fn snoozes_path(ws_path: &str, principal: &str) -> String {
+    let principal_hash = hex::encode(Sha256::digest(principal.as_bytes()));
+    format!(
+        "{}/{}/snoozes/{}.json",
+        ws_path.trim_end_matches('/'),
+        REMINDERS_DIR,
+        principal_hash
+    )
+}
+
+fn delivery_path(ws_path: &str) -> String {
+    format!(
+        "{}/{}/sent.json",
+        ws_path.trim_end_matches('/'),
+        REMINDERS_DIR
+    )
+}
+
+async fn read_json<T: serde::de::DeserializeOwned + Default>(
+    op: &Operator,
+    path: &str,
+) -> Result<T> {
+    match op.read(path).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("Reminder state {} is malformed: {}", path, e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(T::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Reminder fields of a form with their settings.
+pub fn reminder_fields(form_def: &Value) -> Result<Vec<(String, ReminderSpec)>> {
+    let fields = form::normalize_form_fields(form_def.get("fields"));
+    let Some(field_map) = fields.as_object() else {
+        return Ok(Vec::new());
+    };
+    let mut reminders = Vec::new();
+    for (name, def) in field_map {
+        if let Some(spec) =
+            ReminderSpec::from_field_def(def).with_context(|| format!("Field '{}'", name))?
+        {
+            reminders.push((name.clone(), spec));
+        }
+    }
+    Ok(reminders)
+}
+
+pub(crate) fn validate_reminder_field_defs(field_map: &Map<String, Value>) -> Result<()> {
+    for (name, def) in field_map {
+        if ReminderSpec::from_field_def(def)
+            .with_context(|| format!("Field '{}'", name))?
+            .is_none()
+        {
+            continue;
+        }
+        let field_type = def.get("type").and_then(Value::as_str).unwrap_or("string");
+        if !REMINDER_FIELD_TYPES.contains(&field_type) {
+            return Err(anyhow!(
+                "Reminder field '{}' must be a date or timestamp, not {}",
+                name,
+                field_type
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Unix seconds of a date (`YYYY-MM-DD`, at midnight UTC) or RFC 3339 value.
+fn due_at(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Some(date.and_hms_opt(0, 0, 0)?.and_utc().timestamp() as f64);
+    }
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.timestamp_micros() as f64 / 1_000_000.0)
+}
+
+/// Every reminder of live entries with a due value, earliest first.
+async fn collect_reminders(op: &Operator, ws_path: &str, now: f64) -> Result<Vec<DueReminder>> {
+    let mut specs: HashMap<String, Vec<(String, ReminderSpec)>> = HashMap::new();
+    for form_name in entry::list_form_names(op, ws_path).await? {
+        let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+        let fields = reminder_fields(&form_def)?;
+        if !fields.is_empty() {
+            specs.insert(form_name, fields);
+        }
+    }
+    if specs.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut reminders = Vec::new();
+    for (form_name, row) in entry::list_entry_rows(op, ws_path).await? {
+        let Some(fields) = specs.get(&form_name) else {
+            continue;
+        };
+        if row.deleted {
+            continue;
+        }
+        for (field, spec) in fields {
+            let Some(due) = row.fields.get(field).and_then(Value::as_str) else {
+                continue;
+            };
+            let Some(due_at) = due_at(due) else {
+                continue;
+            };
+            reminders.push(DueReminder {
+                entry_id: row.entry_id.clone(),
+                entry_title: row.title.clone(),
+                form: form_name.clone(),
+                field: field.clone(),
+                due: due.to_string(),
+                due_at,
+                remind_at: due_at - spec.lead_days as f64 * SECONDS_PER_DAY,
+                status: if due_at <= now {
+                    ReminderStatus::Overdue
+                } else {
+                    ReminderStatus::Upcoming
+                },
+                snoozed_until: None,
+            });
+        }
+    }
+    reminders.sort_by(|a, b| {
+        a.due_at
+            .partial_cmp(&b.due_at)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.entry_id.cmp(&b.entry_id))
+            .then_with(|| a.field.cmp(&b.field))
+    });
+    Ok(reminders)
+}
+
+/// The snooze of `principal` still in force for one entry field.
+fn active_snooze(snoozes: &SnoozeList, entry_id: &str, field: &str, now: f64) -> Option<f64> {
+    snoozes
+        .snoozes
+        .iter()
+        .find(|snooze| snooze.entry_id == entry_id && snooze.field == field)
+        .map(|snooze| snooze.until)
+        .filter(|until| *until > now)
+}
+
+/// Overdue reminders and those due within `horizon_days`, earliest first.
+/// With a `principal`, reminders they snoozed are left out unless
+/// `include_snoozed` is set.
+pub async fn list_due_reminders(
+    op: &Operator,
+    ws_path: &str,
+    options: &DueReminderOptions,
+) -> Result<Vec<DueReminder>> {
+    let now = options.now.unwrap_or_else(entry::now_ts);
+    let horizon = now + options.horizon_days as f64 * SECONDS_PER_DAY;
+    let snoozes = match &options.principal {
+        Some(principal) => read_json(op, &snoozes_path(ws_path, principal)).await?,
+        None => SnoozeList::default(),
+    };
+    let mut due = Vec::new();
+    for mut reminder in collect_reminders(op, ws_path, now).await? {
+        if reminder.due_at > horizon {
+            continue;
+        }
+        reminder.snoozed_until = active_snooze(&snoozes, &reminder.entry_id, &reminder.field, now);
+        if reminder.snoozed_until.is_some() && !options.include_snoozed {
+            continue;
+        }
+        due.push(reminder);
+    }
+    Ok(due)
+}
+
+/// Hide one entry field's reminder from `principal` until `until` (unix
+/// seconds). Snoozing again moves the time.
+pub async fn snooze_reminder(
+    op: &Operator,
+    ws_path: &str,
+    principal: &str,
+    entry_id: &str,
+    field: &str,
+    until: f64,
+) -> Result<()> {
+    if principal.trim().is_empty() {
+        return Err(anyhow!("Reminder principal must not be empty"));
+    }
+    let form_name = entry::find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+    if !reminder_fields(&form_def)?
+        .iter()
+        .any(|(name, _)| name == field)
+    {
+        return Err(anyhow!(
+            "Field '{}' of form '{}' is not a reminder",
+            field,
+            form_name
+        ));
+    }
+    let path = snoozes_path(ws_path, principal);
+    space_lock::with_space_lease(op, ws_path, REMINDERS_LEASE, |_| async {
+        let mut list: SnoozeList = read_json(op, &path).await?;
+        let now = entry::now_ts();
+        list.snoozes
+            .retain(|s| s.until > now && !(s.entry_id == entry_id && s.field == field));
+        list.snoozes.push(Snooze {
+            entry_id: entry_id.to_string(),
+            field: field.to_string(),
+            until,
+        });
+        storage::publish_atomic(op, &path, serde_json::to_vec(&list)?).await
+    })
+    .await
+}
+
+/// Lift a snooze. Returns false when there was none.
+pub async fn unsnooze_reminder(
+    op: &Operator,
+    ws_path: &str,
+    principal: &str,
+    entry_id: &str,
+    field: &str,
+) -> Result<bool> {
+    let path = snoozes_path(ws_path, principal);
+    space_lock::with_space_lease(op, ws_path, REMINDERS_LEASE, |_| async {
+        let mut list: SnoozeList = read_json(op, &path).await?;
+        let before = list.snoozes.len();
+        list.snoozes
+            .retain(|s| !(s.entry_id == entry_id && s.field == field));
+        if list.snoozes.len() == before {
+            return Ok(false);
+        }
+        storage::publish_atomic(op, &path, serde_json::to_vec(&list)?).await?;
+        Ok(true)
+    })
+    .await
+}
+
+/// Notify watchers of every reminder whose notify time has come.
+///
+/// Meant to run periodically from the backend scheduler. Each watcher of the
+/// entry, its form or its tags gets one `reminder_due` notification per due
+/// value: watchers who snoozed the reminder get it on the first run after the
+/// snooze ends, and changing the date arms it again.
+pub async fn dispatch_due_reminders(
+    op: &Operator,
+    ws_path: &str,
+    now: Option<f64>,
+) -> Result<ReminderDispatchReport> {
+    let now = now.unwrap_or_else(entry::now_ts);
+    let reminders: Vec<DueReminder> = collect_reminders(op, ws_path, now)
+        .await?
+        .into_iter()
+        .filter(|reminder| reminder.remind_at <= now)
+        .collect();
+    if reminders.is_empty() {
+        return Ok(ReminderDispatchReport::default());
+    }
+    let path = delivery_path(ws_path);
+    space_lock::with_space_lease(op, ws_path, REMINDERS_LEASE, |_| async {
+        let mut log: DeliveryLog = read_json(op, &path).await?;
+        let mut report = ReminderDispatchReport::default();
+        let mut snoozes: HashMap<String, SnoozeList> = HashMap::new();
+        for reminder in &reminders {
+            let row =
+                entry::read_entry_row(op, ws_path, &reminder.form, &reminder.entry_id).await?;
+            let event = WatchEvent {
+                kind: WatchEventKind::ReminderDue,
+                entry_id: &reminder.entry_id,
+                entry_title: &reminder.entry_title,
+                form: &reminder.form,
+                tags: &row.tags,
+                author: None,
+                revision_id: None,
+                field: Some(&reminder.field),
+                timestamp: now,
+            };
+            let key = delivery_key(&reminder.entry_id, &reminder.field);
+            let mut recipients = Vec::new();
+            for (principal, targets) in watchers::matching_watchers(op, ws_path, &event).await? {
+                let sent = log.sent.get(&principal).and_then(|sent| sent.get(&key));
+                if sent == Some(&reminder.due) {
+                    continue;
+                }
+                if !snoozes.contains_key(&principal) {
+                    let list = read_json(op, &snoozes_path(ws_path, &principal)).await?;
+                    snoozes.insert(principal.clone(), list);
+                }
+                let snoozed = active_snooze(
+                    &snoozes[&principal],
+                    &reminder.entry_id,
+                    &reminder.field,
+                    now,
+                );
+                if snoozed.is_some() {
+                    report.snoozed += 1;
+                    continue;
+                }
+                log.sent
+                    .entry(principal.clone())
+                    .or_default()
+                    .insert(key.clone(), reminder.due.clone());
+                recipients.push((principal, targets));
+            }
+            if recipients.is_empty() {
+                continue;
+            }
+            report.dispatched += 1;
+            report.notifications +=
+                watchers::enqueue_notifications(op, ws_path, &event, recipients)
+                    .await?
+                    .len();
+        }
+        if report.notifications > 0 {
+            storage::publish_atomic(op, &path, serde_json::to_vec(&log)?).await?;
+        }
+        Ok(report)
+    })
+    .await
+}
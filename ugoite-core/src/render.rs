@@ -0,0 +1,527 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use pulldown_cmark::{html, Alignment, CodeBlockKind, CowStr, Event, Options, Parser, Tag};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::entry;
+
+const ENTRY_SCHEME: &str = "ugoite://entry/";
+const ASSET_SCHEME: &str = "ugoite://asset/";
+
+/// Markdown extensions and link targets used when rendering entry bodies.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RenderOptions {
+    #[serde(default = "default_true")]
+    pub tables: bool,
+    #[serde(default)]
+    pub footnotes: bool,
+    #[serde(default = "default_true")]
+    pub task_lists: bool,
+    #[serde(default = "default_true")]
+    pub strikethrough: bool,
+    /// Emit `$..$` and `$$..$$` untouched in `math` spans for a client-side renderer.
+    #[serde(default)]
+    pub math: bool,
+    /// Class prefix for fenced code languages, e.g. `language-rust`; `None` drops the class.
+    #[serde(default = "default_code_class_prefix")]
+    pub code_class_prefix: Option<String>,
+    /// URL for `ugoite://entry/{id}` links; `{id}` is replaced with the percent-encoded id.
+    #[serde(default = "default_entry_url")]
+    pub entry_url: String,
+    #[serde(default = "default_asset_url")]
+    pub asset_url: String,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_code_class_prefix() -> Option<String> {
+    Some("language-".to_string())
+}
+
+fn default_entry_url() -> String {
+    "entries/{id}".to_string()
+}
+
+fn default_asset_url() -> String {
+    "assets/{id}".to_string()
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            tables: true,
+            footnotes: false,
+            task_lists: true,
+            strikethrough: true,
+            math: false,
+            code_class_prefix: default_code_class_prefix(),
+            entry_url: default_entry_url(),
+            asset_url: default_asset_url(),
+        }
+    }
+}
+
+/// Maps `ugoite://` references to URLs; `None` leaves the reference unchanged.
+pub trait LinkResolver {
+    fn entry_url(&self, entry_id: &str) -> Option<String>;
+    fn asset_url(&self, asset_id: &str) -> Option<String>;
+}
+
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+impl LinkResolver for RenderOptions {
+    fn entry_url(&self, entry_id: &str) -> Option<String> {
+        Some(self.entry_url.replace("{id}", &percent_encode(entry_id)))
+    }
+
+    fn asset_url(&self, asset_id: &str) -> Option<String> {
+        Some(self.asset_url.replace("{id}", &percent_encode(asset_id)))
+    }
+}
+
+/// Drop the YAML frontmatter block from rendered entry markdown.
+pub fn strip_frontmatter(markdown: &str) -> &str {
+    if let Some(rest) = markdown.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---\n") {
+            return &rest[end + 5..];
+        }
+    }
+    markdown
+}
+
+fn resolve_link(dest: &str, resolver: &dyn LinkResolver) -> Option<String> {
+    let (scheme, rest) = if let Some(rest) = dest.strip_prefix(ENTRY_SCHEME) {
+        (ENTRY_SCHEME, rest)
+    } else {
+        (ASSET_SCHEME, dest.strip_prefix(ASSET_SCHEME)?)
+    };
+    let split = rest.find(['#', '?']).unwrap_or(rest.len());
+    let (id, suffix) = rest.split_at(split);
+    let url = if scheme == ENTRY_SCHEME {
+        resolver.entry_url(id)?
+    } else {
+        resolver.asset_url(id)?
+    };
+    Some(format!("{url}{suffix}"))
+}
+
+/// Neutralize script-capable URL schemes; images may still use inline `data:image/` URLs.
+fn is_safe_url(dest: &str, image: bool) -> bool {
+    let normalized: String = dest
+        .chars()
+        .filter(|ch| !ch.is_whitespace() && !ch.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if normalized.starts_with("javascript:") || normalized.starts_with("vbscript:") {
+        return false;
+    }
+    !normalized.starts_with("data:") || (image && normalized.starts_with("data:image/"))
+}
+
+fn link_target<'a>(dest: CowStr<'a>, image: bool, resolver: &dyn LinkResolver) -> CowStr<'a> {
+    if let Some(url) = resolve_link(&dest, resolver) {
+        return url.into();
+    }
+    if is_safe_url(&dest, image) {
+        dest
+    } else {
+        "#".into()
+    }
+}
+
+fn html_attr_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn parser_options(options: &RenderOptions) -> Options {
+    let mut parser_options = Options::empty();
+    for (enabled, flag) in [
+        (options.tables, Options::ENABLE_TABLES),
+        (options.footnotes, Options::ENABLE_FOOTNOTES),
+        (options.task_lists, Options::ENABLE_TASKLISTS),
+        (options.strikethrough, Options::ENABLE_STRIKETHROUGH),
+        (options.math, Options::ENABLE_MATH),
+    ] {
+        if enabled {
+            parser_options.insert(flag);
+        }
+    }
+    parser_options
+}
+
+/// Render markdown to HTML using `options` for both extensions and link targets.
+pub fn render_html(markdown: &str, options: &RenderOptions) -> String {
+    render_html_with(markdown, options, options)
+}
+
+/// Render markdown to sanitized HTML. Raw HTML in the source is emitted as
+/// text, and `javascript:`-style URLs are replaced, so the output is safe to
+/// embed without a separate sanitizer.
+pub fn render_html_with(
+    markdown: &str,
+    options: &RenderOptions,
+    resolver: &dyn LinkResolver,
+) -> String {
+    let parser = Parser::new_ext(markdown, parser_options(options)).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: link_target(dest_url, false, resolver),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: link_target(dest_url, true, resolver),
+            title,
+            id,
+        }),
+        Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+            let lang = info.split_whitespace().next().unwrap_or_default();
+            match options.code_class_prefix.as_deref() {
+                Some(prefix) if !lang.is_empty() => Event::Html(
+                    format!(
+                        "<pre><code class=\"{}\">",
+                        html_attr_escape(&format!("{prefix}{lang}"))
+                    )
+                    .into(),
+                ),
+                _ => Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced("".into()))),
+            }
+        }
+        other => other,
+    });
+    let mut rendered = String::new();
+    html::push_html(&mut rendered, parser);
+    rendered
+}
+
+fn ast_node(kind: &str) -> Map<String, Value> {
+    let mut node = Map::new();
+    node.insert("type".to_string(), Value::String(kind.to_string()));
+    node
+}
+
+fn start_node(tag: Tag<'_>, resolver: &dyn LinkResolver) -> Map<String, Value> {
+    let mut node = match tag {
+        Tag::Paragraph => ast_node("paragraph"),
+        Tag::Heading { level, .. } => {
+            let mut node = ast_node("heading");
+            node.insert("depth".to_string(), json!(level as u8));
+            node
+        }
+        Tag::BlockQuote(_) => ast_node("blockquote"),
+        Tag::CodeBlock(kind) => {
+            let mut node = ast_node("code");
+            let (lang, meta) = match &kind {
+                CodeBlockKind::Fenced(info) => {
+                    let info = info.trim();
+                    match info.split_once(char::is_whitespace) {
+                        Some((lang, meta)) => (Some(lang), Some(meta.trim())),
+                        None => (Some(info).filter(|lang| !lang.is_empty()), None),
+                    }
+                }
+                CodeBlockKind::Indented => (None, None),
+            };
+            node.insert("lang".to_string(), json!(lang));
+            node.insert("meta".to_string(), json!(meta));
+            node
+        }
+        Tag::HtmlBlock => ast_node("html"),
+        Tag::List(start) => {
+            let mut node = ast_node("list");
+            node.insert("ordered".to_string(), json!(start.is_some()));
+            node.insert("start".to_string(), json!(start));
+            node
+        }
+        Tag::Item => {
+            let mut node = ast_node("listItem");
+            node.insert("checked".to_string(), Value::Null);
+            node
+        }
+        Tag::FootnoteDefinition(label) => {
+            let mut node = ast_node("footnoteDefinition");
+            node.insert("identifier".to_string(), json!(label.as_ref()));
+            node
+        }
+        Tag::Table(alignments) => {
+            let align: Vec<Value> = alignments
+                .iter()
+                .map(|alignment| match alignment {
+                    Alignment::None => Value::Null,
+                    Alignment::Left => json!("left"),
+                    Alignment::Center => json!("center"),
+                    Alignment::Right => json!("right"),
+                })
+                .collect();
+            let mut node = ast_node("table");
+            node.insert("align".to_string(), Value::Array(align));
+            node
+        }
+        Tag::TableHead | Tag::TableRow => ast_node("tableRow"),
+        Tag::TableCell => ast_node("tableCell"),
+        Tag::Emphasis => ast_node("emphasis"),
+        Tag::Strong => ast_node("strong"),
+        Tag::Strikethrough => ast_node("delete"),
+        Tag::Link {
+            dest_url, title, ..
+        } => {
+            let mut node = ast_node("link");
+            node.insert(
+                "url".to_string(),
+                json!(link_target(dest_url, false, resolver).as_ref()),
+            );
+            node.insert(
+                "title".to_string(),
+                json!(Some(title.as_ref()).filter(|t| !t.is_empty())),
+            );
+            node
+        }
+        Tag::Image {
+            dest_url, title, ..
+        } => {
+            let mut node = ast_node("image");
+            node.insert(
+                "url".to_string(),
+                json!(link_target(dest_url, true, resolver).as_ref()),
+            );
+            node.insert(
+                "title".to_string(),
+                json!(Some(title.as_ref()).filter(|t| !t.is_empty())),
+            );
+            node
+        }
+        Tag::DefinitionList => ast_node("definitionList"),
+        Tag::DefinitionListTitle => ast_node("definitionTerm"),
+        Tag::DefinitionListDefinition => ast_node("definitionDescription"),
+        Tag::Superscript => ast_node("superscript"),
+        Tag::Subscript => ast_node("subscript"),
+        Tag::MetadataBlock(_) => ast_node("yaml"),
+    };
+    node.insert("children".to_string(), Value::Array(Vec::new()));
+    node
+}
+
+fn plain_text(children: &[Value]) -> String {
+    children
+        .iter()
+        .map(|child| match child.get("value").and_then(Value::as_str) {
+            Some(value) => value.to_string(),
+            None => plain_text(
+                child
+                    .get("children")
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default(),
+            ),
+        })
+        .collect()
+}
+
+/// Literal nodes keep their text in `value` instead of children.
+fn finish_node(mut node: Map<String, Value>) -> Map<String, Value> {
+    let kind = node
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let literal = match kind.as_str() {
+        "code" | "html" | "yaml" => Some("value"),
+        "image" => Some("alt"),
+        _ => None,
+    };
+    if let Some(key) = literal {
+        let children = match node.remove("children") {
+            Some(Value::Array(children)) => children,
+            _ => Vec::new(),
+        };
+        let mut text = plain_text(&children);
+        if matches!(kind.as_str(), "code" | "html") && text.ends_with('\n') {
+            text.pop();
+        }
+        node.insert(key.to_string(), Value::String(text));
+    }
+    node
+}
+
+fn push_child(stack: &mut [Map<String, Value>], child: Value) {
+    let Some(Value::Array(children)) = stack.last_mut().and_then(|node| node.get_mut("children"))
+    else {
+        return;
+    };
+    // Adjacent text runs (split by soft breaks or entities) form one node.
+    if child["type"] == "text" {
+        if let Some(Value::Object(last)) = children.last_mut() {
+            if last.get("type").and_then(Value::as_str) == Some("text") {
+                if let (Some(Value::String(text)), Some(more)) =
+                    (last.get_mut("value"), child["value"].as_str())
+                {
+                    text.push_str(more);
+                    return;
+                }
+            }
+        }
+    }
+    children.push(child);
+}
+
+fn literal(kind: &str, value: &str) -> Value {
+    json!({"type": kind, "value": value})
+}
+
+/// Parse markdown into an mdast-style JSON tree.
+///
+/// Nodes carry a `type` and either `children` or a literal `value`, following
+/// mdast names (`heading.depth`, `code.lang`, `listItem.checked`,
+/// `link.url`). Links are resolved and neutralized like [`render_html`];
+/// raw HTML is kept in `html` nodes and is not sanitized.
+pub fn render_ast(markdown: &str, options: &RenderOptions) -> Value {
+    let mut root = ast_node("root");
+    root.insert("children".to_string(), Value::Array(Vec::new()));
+    let mut stack = vec![root];
+    for event in Parser::new_ext(markdown, parser_options(options)) {
+        match event {
+            Event::Start(tag) => stack.push(start_node(tag, options)),
+            Event::End(_) => {
+                if stack.len() > 1 {
+                    let node = finish_node(stack.pop().unwrap_or_default());
+                    push_child(&mut stack, Value::Object(node));
+                }
+            }
+            Event::Text(text) => push_child(&mut stack, literal("text", &text)),
+            Event::Code(code) => push_child(&mut stack, literal("inlineCode", &code)),
+            Event::InlineMath(math) => push_child(&mut stack, literal("inlineMath", &math)),
+            Event::DisplayMath(math) => push_child(&mut stack, literal("math", &math)),
+            Event::Html(raw) | Event::InlineHtml(raw) => {
+                push_child(&mut stack, literal("html", &raw))
+            }
+            Event::FootnoteReference(label) => push_child(
+                &mut stack,
+                json!({"type": "footnoteReference", "identifier": label.as_ref()}),
+            ),
+            Event::SoftBreak => push_child(&mut stack, literal("text", "\n")),
+            Event::HardBreak => push_child(&mut stack, json!({"type": "break"})),
+            Event::Rule => push_child(&mut stack, json!({"type": "thematicBreak"})),
+            Event::TaskListMarker(checked) => {
+                if let Some(item) = stack
+                    .iter_mut()
+                    .rev()
+                    .find(|node| node.get("type").and_then(Value::as_str) == Some("listItem"))
+                {
+                    item.insert("checked".to_string(), json!(checked));
+                }
+            }
+        }
+    }
+    Value::Object(stack.swap_remove(0))
+}
+
+/// Representation returned by [`get_entry_formatted`].
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryFormat {
+    /// The full entry record, as returned by `entry::get_entry`.
+    #[default]
+    Json,
+    /// Raw markdown, frontmatter included.
+    Markdown,
+    /// Sanitized HTML of the body, from [`render_html`].
+    Html,
+    /// mdast-style tree of the body, from [`render_ast`].
+    Ast,
+}
+
+impl std::str::FromStr for EntryFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            "ast" | "mdast" => Ok(Self::Ast),
+            other => Err(anyhow!("Unsupported entry format: {}", other)),
+        }
+    }
+}
+
+/// Fetch an entry in `format`, rendered once in core for every client.
+///
+/// Apart from [`EntryFormat::Json`], the result is
+/// `{id, revision_id, title, format, content}` where `content` is the
+/// markdown string, the HTML string or the AST object.
+pub async fn get_entry_formatted(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    format: EntryFormat,
+    options: &RenderOptions,
+) -> Result<Value> {
+    let entry = entry::get_entry(op, ws_path, entry_id).await?;
+    Ok(format_entry(entry, format, options))
+}
+
+/// Shape an entry already read with [`entry::get_entry`] as `format`.
+pub fn format_entry(entry: Value, format: EntryFormat, options: &RenderOptions) -> Value {
+    if format == EntryFormat::Json {
+        return entry;
+    }
+    let markdown = entry
+        .get("content")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    let content = match format {
+        EntryFormat::Markdown => Value::String(markdown.to_string()),
+        EntryFormat::Html => Value::String(render_html(strip_frontmatter(markdown), options)),
+        _ => render_ast(strip_frontmatter(markdown), options),
+    };
+    json!({
+        "id": entry["id"],
+        "revision_id": entry["revision_id"],
+        "title": entry["title"],
+        "format": format,
+        "content": content,
+    })
+}
+
+/// Render an entry's body (without frontmatter) the same way for every client.
+pub async fn render_entry_html(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    options: &RenderOptions,
+) -> Result<String> {
+    let entry = entry::get_entry(op, ws_path, entry_id).await?;
+    let markdown = entry
+        .get("content")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default();
+    Ok(render_html(strip_frontmatter(markdown), options))
+}
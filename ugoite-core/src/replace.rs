@@ -0,0 +1,437 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::entry;
+use crate::entry_metadata;
+use crate::field_crypto;
+use crate::form;
+use crate::integrity::IntegrityProvider;
+
+/// Entry metadata namespace recording the replace job behind an entry's
+/// latest revision.
+pub const REPLACE_METADATA_NAMESPACE: &str = "x-replace";
+const DEFAULT_CONTEXT_CHARS: usize = 40;
+
+fn default_case_sensitive() -> bool {
+    true
+}
+
+fn default_context_chars() -> usize {
+    DEFAULT_CONTEXT_CHARS
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReplaceOptions {
+    pub find: String,
+    #[serde(default)]
+    pub replace: String,
+    /// Treat `find` as a regular expression; `replace` may then use `$1`.
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default = "default_case_sensitive")]
+    pub case_sensitive: bool,
+    /// Only entries of these forms; every form when empty.
+    #[serde(default)]
+    pub forms: Vec<String>,
+    /// Characters of context shown on each side of a previewed match.
+    #[serde(default = "default_context_chars")]
+    pub context_chars: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReplaceMatch {
+    /// 1-based line in the entry markdown.
+    pub line: usize,
+    pub before: String,
+    pub matched: String,
+    pub after: String,
+    pub replacement: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReplacePreview {
+    pub entry_id: String,
+    pub title: String,
+    pub form: String,
+    pub matches: Vec<ReplaceMatch>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReplacedEntry {
+    pub entry_id: String,
+    pub revision_id: String,
+    pub parent_revision_id: String,
+    pub replacements: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReplaceFailure {
+    pub entry_id: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ReplaceReport {
+    pub job_id: String,
+    pub replaced: Vec<ReplacedEntry>,
+    pub failed: Vec<ReplaceFailure>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ReplaceUndoReport {
+    pub job_id: String,
+    pub reverted: Vec<String>,
+    /// Entries edited again after the job, left as they are.
+    pub skipped: Vec<String>,
+}
+
+fn compile(options: &ReplaceOptions) -> Result<Regex> {
+    if options.find.is_empty() {
+        return Err(anyhow!("Find text must not be empty"));
+    }
+    let pattern = if options.regex {
+        options.find.clone()
+    } else {
+        regex::escape(&options.find)
+    };
+    let pattern = RegexBuilder::new(&pattern)
+        .case_insensitive(!options.case_sensitive)
+        .multi_line(true)
+        .build()
+        .map_err(|e| anyhow!("Invalid find pattern: {}", e))?;
+    if pattern.is_match("") {
+        return Err(anyhow!("Find pattern must not match empty text"));
+    }
+    Ok(pattern)
+}
+
+/// Split rendered entry markdown into its frontmatter block and the rest.
+/// Replacements never touch the frontmatter, so the form stays the same.
+fn split_frontmatter(markdown: &str) -> (&str, &str) {
+    if let Some(rest) = markdown.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---\n") {
+            return markdown.split_at(4 + end + 5);
+        }
+    }
+    ("", markdown)
+}
+
+fn context_before(text: &str, start: usize, chars: usize) -> String {
+    let line_start = text[..start].rfind('\n').map_or(0, |pos| pos + 1);
+    let prefix = &text[line_start..start];
+    let skip = prefix.chars().count().saturating_sub(chars);
+    prefix.chars().skip(skip).collect()
+}
+
+fn context_after(text: &str, end: usize, chars: usize) -> String {
+    let line_end = text[end..].find('\n').map_or(text.len(), |pos| end + pos);
+    text[end..line_end].chars().take(chars).collect()
+}
+
+fn replacement_for(options: &ReplaceOptions, caps: &regex::Captures) -> String {
+    if options.regex {
+        let mut expanded = String::new();
+        caps.expand(&options.replace, &mut expanded);
+        expanded
+    } else {
+        options.replace.clone()
+    }
+}
+
+/// Byte ranges of the body sections holding secret fields, heading included.
+/// Their values are masked as [`field_crypto::SECRET_PLACEHOLDER`], so rewriting
+/// them would overwrite the stored secret.
+fn secret_section_ranges(body: &str, secret_fields: &[String]) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut open = None;
+    let mut offset = 0;
+    for line in body.split_inclusive('\n') {
+        if let Some(heading) = line.strip_prefix("## ") {
+            if let Some(start) = open.take() {
+                ranges.push((start, offset));
+            }
+            if secret_fields.iter().any(|field| field == heading.trim()) {
+                open = Some(offset);
+            }
+        }
+        offset += line.len();
+    }
+    if let Some(start) = open {
+        ranges.push((start, body.len()));
+    }
+    ranges
+}
+
+/// Matches in `body` outside secret sections.
+fn replaceable_matches<'a>(
+    pattern: &'a Regex,
+    body: &'a str,
+    secret_fields: &[String],
+) -> impl Iterator<Item = regex::Captures<'a>> {
+    let protected = secret_section_ranges(body, secret_fields);
+    pattern.captures_iter(body).filter(move |caps| {
+        let found = caps.get(0).expect("group 0 always matches");
+        !protected
+            .iter()
+            .any(|&(start, end)| found.start() < end && found.end() > start)
+    })
+}
+
+fn preview_matches(
+    pattern: &Regex,
+    options: &ReplaceOptions,
+    markdown: &str,
+    secret_fields: &[String],
+) -> Vec<ReplaceMatch> {
+    let (frontmatter, body) = split_frontmatter(markdown);
+    let line_offset = frontmatter.matches('\n').count();
+    replaceable_matches(pattern, body, secret_fields)
+        .filter_map(|caps| {
+            let found = caps.get(0)?;
+            Some(ReplaceMatch {
+                line: line_offset + body[..found.start()].matches('\n').count() + 1,
+                before: context_before(body, found.start(), options.context_chars),
+                matched: found.as_str().to_string(),
+                after: context_after(body, found.end(), options.context_chars),
+                replacement: replacement_for(options, &caps),
+            })
+        })
+        .collect()
+}
+
+fn replace_body(
+    pattern: &Regex,
+    options: &ReplaceOptions,
+    markdown: &str,
+    secret_fields: &[String],
+) -> (String, usize) {
+    let (frontmatter, body) = split_frontmatter(markdown);
+    let mut replaced = String::with_capacity(markdown.len());
+    replaced.push_str(frontmatter);
+    let mut count = 0;
+    let mut last = 0;
+    for caps in replaceable_matches(pattern, body, secret_fields) {
+        let found = caps.get(0).expect("group 0 always matches");
+        replaced.push_str(&body[last..found.start()]);
+        replaced.push_str(&replacement_for(options, &caps));
+        last = found.end();
+        count += 1;
+    }
+    if count == 0 {
+        return (markdown.to_string(), 0);
+    }
+    replaced.push_str(&body[last..]);
+    (replaced, count)
+}
+
+/// Secret field names of `form_name`, read once per form.
+async fn secret_fields_for<'a>(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    cache: &'a mut HashMap<String, Vec<String>>,
+) -> Result<&'a [String]> {
+    if !cache.contains_key(form_name) {
+        let form_def = form::read_form_definition(op, ws_path, form_name).await?;
+        cache.insert(
+            form_name.to_string(),
+            field_crypto::secret_field_names(&form_def),
+        );
+    }
+    Ok(cache[form_name].as_slice())
+}
+
+/// Live entries in scope of `options.forms`, in id order.
+async fn scoped_entries(
+    op: &Operator,
+    ws_path: &str,
+    options: &ReplaceOptions,
+) -> Result<Vec<(String, entry::EntryRow)>> {
+    let mut rows: Vec<(String, entry::EntryRow)> = entry::list_entry_rows(op, ws_path)
+        .await?
+        .into_iter()
+        .filter(|(form_name, row)| {
+            !row.deleted
+                && (options.forms.is_empty()
+                    || options
+                        .forms
+                        .iter()
+                        .any(|form| form.eq_ignore_ascii_case(form_name)))
+        })
+        .collect();
+    rows.sort_by(|a, b| a.1.entry_id.cmp(&b.1.entry_id));
+    Ok(rows)
+}
+
+/// Every match of `options.find` with surrounding context and its
+/// replacement, grouped by entry. Nothing is written.
+pub async fn preview_replace(
+    op: &Operator,
+    ws_path: &str,
+    options: &ReplaceOptions,
+) -> Result<Vec<ReplacePreview>> {
+    let pattern = compile(options)?;
+    let mut secret_fields = HashMap::new();
+    let mut previews = Vec::new();
+    for (form_name, row) in scoped_entries(op, ws_path, options).await? {
+        let secrets = secret_fields_for(op, ws_path, &form_name, &mut secret_fields).await?;
+        let content = entry::get_entry_content(op, ws_path, &row.entry_id).await?;
+        let matches = preview_matches(&pattern, options, &content.markdown, secrets);
+        if !matches.is_empty() {
+            previews.push(ReplacePreview {
+                entry_id: row.entry_id,
+                title: row.title,
+                form: form_name,
+                matches,
+            });
+        }
+    }
+    Ok(previews)
+}
+
+/// Replace every match across the space, saving one revision per changed
+/// entry. All revisions share a job id, recorded in each entry's
+/// [`REPLACE_METADATA_NAMESPACE`] metadata so [`undo_replace`] can revert them.
+/// Secret fields are never rewritten.
+pub async fn apply_replace<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    options: &ReplaceOptions,
+    author: &str,
+    integrity: &I,
+) -> Result<ReplaceReport> {
+    let pattern = compile(options)?;
+    let mut report = ReplaceReport {
+        job_id: Uuid::new_v4().to_string(),
+        ..ReplaceReport::default()
+    };
+    let mut secret_fields = HashMap::new();
+    for (form_name, row) in scoped_entries(op, ws_path, options).await? {
+        let secrets = secret_fields_for(op, ws_path, &form_name, &mut secret_fields).await?;
+        let content = entry::get_entry_content(op, ws_path, &row.entry_id).await?;
+        let (updated, replacements) = replace_body(&pattern, options, &content.markdown, secrets);
+        if replacements == 0 || updated == content.markdown {
+            continue;
+        }
+        let saved = entry::update_entry(
+            op,
+            ws_path,
+            &row.entry_id,
+            &updated,
+            Some(&content.revision_id),
+            author,
+            None,
+            integrity,
+        )
+        .await;
+        let revision_id = match saved {
+            Ok(saved) => saved
+                .get("revision_id")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            Err(err) => {
+                report.failed.push(ReplaceFailure {
+                    entry_id: row.entry_id,
+                    error: err.to_string(),
+                });
+                continue;
+            }
+        };
+        let record = json!({
+            "job_id": report.job_id,
+            "revision_id": revision_id,
+            "parent_revision_id": content.revision_id,
+        });
+        if let Err(err) = entry_metadata::set_entry_metadata(
+            op,
+            ws_path,
+            &row.entry_id,
+            REPLACE_METADATA_NAMESPACE,
+            record.as_object().unwrap_or(&Map::new()),
+            false,
+        )
+        .await
+        {
+            report.failed.push(ReplaceFailure {
+                entry_id: row.entry_id,
+                error: format!(
+                    "Saved revision {} but could not record the replace job: {}",
+                    revision_id, err
+                ),
+            });
+            continue;
+        }
+        report.replaced.push(ReplacedEntry {
+            entry_id: row.entry_id,
+            revision_id,
+            parent_revision_id: content.revision_id,
+            replacements,
+        });
+    }
+    Ok(report)
+}
+
+/// Revert the revisions created by a replace job by restoring each entry's
+/// previous revision. Entries edited since the job are skipped.
+pub async fn undo_replace<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    job_id: &str,
+    author: &str,
+    integrity: &I,
+) -> Result<ReplaceUndoReport> {
+    let entry_ids = entry_metadata::find_entries_by_metadata(
+        op,
+        ws_path,
+        REPLACE_METADATA_NAMESPACE,
+        "job_id",
+        &Value::String(job_id.to_string()),
+    )
+    .await?;
+    if entry_ids.is_empty() {
+        return Err(anyhow!("Replace job not found: {}", job_id));
+    }
+    let mut report = ReplaceUndoReport {
+        job_id: job_id.to_string(),
+        ..ReplaceUndoReport::default()
+    };
+    for entry_id in entry_ids {
+        let record = entry_metadata::get_entry_metadata_namespace(
+            op,
+            ws_path,
+            &entry_id,
+            REPLACE_METADATA_NAMESPACE,
+        )
+        .await?;
+        let field = |key: &str| record.get(key).and_then(Value::as_str).unwrap_or_default();
+        let content = entry::get_entry_content(op, ws_path, &entry_id).await?;
+        if content.revision_id != field("revision_id") {
+            report.skipped.push(entry_id);
+            continue;
+        }
+        entry::restore_entry(
+            op,
+            ws_path,
+            &entry_id,
+            field("parent_revision_id"),
+            author,
+            integrity,
+        )
+        .await?;
+        entry_metadata::clear_entry_metadata(
+            op,
+            ws_path,
+            &entry_id,
+            Some(REPLACE_METADATA_NAMESPACE),
+        )
+        .await?;
+        report.reverted.push(entry_id);
+    }
+    Ok(report)
+}
@@ -0,0 +1,195 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde_json::Value;
+
+pub const MAX_BYTES_ENV: &str = "UGOITE_RESULT_BUFFER_MAX_BYTES";
+/// Rows kept in memory before a buffer starts spilling to storage.
+pub const DEFAULT_MAX_BYTES: usize = 16 * 1024 * 1024;
+
+/// Memory cap from `UGOITE_RESULT_BUFFER_MAX_BYTES`, or the default.
+pub fn max_bytes_from_env() -> usize {
+    std::env::var(MAX_BYTES_ENV)
+        .ok()
+        .and_then(|raw| raw.trim().parse::<usize>().ok())
+        .filter(|bytes| *bytes > 0)
+        .unwrap_or(DEFAULT_MAX_BYTES)
+}
+
+#[derive(Debug, Clone)]
+struct SpilledBatch {
+    path: String,
+    rows: usize,
+}
+
+/// Collects result rows under a memory cap, spilling full batches as JSON
+/// lines to a temporary directory.
+pub struct ResultBuffer {
+    op: Operator,
+    spill_dir: String,
+    max_bytes: usize,
+    rows: Vec<Value>,
+    bytes: usize,
+    spilled: Vec<SpilledBatch>,
+    total: usize,
+}
+
+impl ResultBuffer {
+    pub fn new(op: &Operator, spill_dir: &str, max_bytes: usize) -> Self {
+        Self {
+            op: op.clone(),
+            spill_dir: spill_dir.trim_end_matches('/').to_string(),
+            max_bytes: max_bytes.max(1),
+            rows: Vec::new(),
+            bytes: 0,
+            spilled: Vec::new(),
+            total: 0,
+        }
+    }
+
+    pub async fn push(&mut self, row: Value) -> Result<()> {
+        self.bytes += serde_json::to_vec(&row)?.len();
+        self.rows.push(row);
+        self.total += 1;
+        if self.bytes > self.max_bytes {
+            self.spill().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn extend<I: IntoIterator<Item = Value>>(&mut self, rows: I) -> Result<()> {
+        for row in rows {
+            self.push(row).await?;
+        }
+        Ok(())
+    }
+
+    async fn spill(&mut self) -> Result<()> {
+        if self.rows.is_empty() {
+            return Ok(());
+        }
+        let path = format!("{}/{:06}.jsonl", self.spill_dir, self.spilled.len());
+        let mut body = Vec::with_capacity(self.bytes + self.rows.len());
+        for row in &self.rows {
+            serde_json::to_writer(&mut body, row)?;
+            body.push(b'\n');
+        }
+        self.op.write(&path, body).await?;
+        self.spilled.push(SpilledBatch {
+            path,
+            rows: self.rows.len(),
+        });
+        self.rows = Vec::new();
+        self.bytes = 0;
+        Ok(())
+    }
+
+    /// Stop accepting rows; rows still in memory stay there.
+    pub fn finish(self) -> ResultSet {
+        ResultSet {
+            op: self.op,
+            spill_dir: self.spill_dir,
+            spilled: self.spilled,
+            rows: self.rows,
+            total: self.total,
+        }
+    }
+}
+
+/// Rows collected by a [`ResultBuffer`], read back batch by batch.
+pub struct ResultSet {
+    op: Operator,
+    spill_dir: String,
+    spilled: Vec<SpilledBatch>,
+    rows: Vec<Value>,
+    total: usize,
+}
+
+impl ResultSet {
+    pub fn len(&self) -> usize {
+        self.total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    pub fn spilled_batches(&self) -> usize {
+        self.spilled.len()
+    }
+
+    /// Number of batches, counting the in-memory tail as the last one.
+    pub fn batch_count(&self) -> usize {
+        self.spilled.len() + usize::from(!self.rows.is_empty())
+    }
+
+    pub async fn read_batch(&self, index: usize) -> Result<Vec<Value>> {
+        match self.spilled.get(index) {
+            Some(batch) => {
+                let bytes = self.op.read(&batch.path).await?.to_vec();
+                bytes
+                    .split(|byte| *byte == b'\n')
+                    .filter(|line| !line.is_empty())
+                    .map(|line| Ok(serde_json::from_slice(line)?))
+                    .collect()
+            }
+            None if index == self.spilled.len() => Ok(self.rows.clone()),
+            None => Err(anyhow!("Result batch {} out of range", index)),
+        }
+    }
+
+    /// Rows `offset..offset + limit`, loading only the batches that overlap them.
+    pub async fn read_range(&self, offset: usize, limit: usize) -> Result<Vec<Value>> {
+        let end = offset.saturating_add(limit).min(self.total);
+        let mut out = Vec::new();
+        let mut batch_start = 0;
+        for (index, batch) in self.spilled.iter().enumerate() {
+            let batch_end = batch_start + batch.rows;
+            if batch_end > offset && batch_start < end {
+                let rows = self.read_batch(index).await?;
+                let from = offset.saturating_sub(batch_start);
+                let to = end.min(batch_end) - batch_start;
+                out.extend(rows.into_iter().take(to).skip(from));
+            }
+            batch_start = batch_end;
+            if batch_start >= end {
+                return Ok(out);
+            }
+        }
+        let from = offset.saturating_sub(batch_start);
+        let to = end.saturating_sub(batch_start);
+        if from < to {
+            out.extend_from_slice(&self.rows[from..to]);
+        }
+        Ok(out)
+    }
+
+    pub async fn into_rows(self) -> Result<Vec<Value>> {
+        let mut out = Vec::with_capacity(self.total);
+        for index in 0..self.spilled.len() {
+            out.extend(self.read_batch(index).await?);
+        }
+        let ResultSet {
+            op,
+            spill_dir,
+            rows,
+            spilled,
+            ..
+        } = self;
+        out.extend(rows);
+        Self::remove_spill(&op, &spill_dir, &spilled).await?;
+        Ok(out)
+    }
+
+    /// Delete spilled batches; call once the rows are no longer needed.
+    pub async fn cleanup(self) -> Result<()> {
+        Self::remove_spill(&self.op, &self.spill_dir, &self.spilled).await
+    }
+
+    async fn remove_spill(op: &Operator, spill_dir: &str, spilled: &[SpilledBatch]) -> Result<()> {
+        if spilled.is_empty() {
+            return Ok(());
+        }
+        op.remove_all(&format!("{}/", spill_dir)).await?;
+        Ok(())
+    }
+}
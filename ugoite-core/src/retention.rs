@@ -0,0 +1,224 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::audit;
+use crate::entry;
+use crate::form;
+use crate::integrity::IntegrityProvider;
+use crate::legal_hold;
+use crate::space;
+
+/// Tag added to entries archived by a retention policy.
+pub const ARCHIVED_TAG: &str = "archived";
+/// Entries carrying this tag are exempt from retention.
+pub const PINNED_TAG: &str = "pinned";
+const RETENTION_AUTHOR: &str = "system-retention";
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    Archive,
+    Delete,
+}
+
+/// Per-form retention settings stored under `retention` in the form definition.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub max_age_days: u64,
+    pub action: RetentionAction,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RetentionOptions {
+    /// Report what would change without writing anything.
+    pub dry_run: bool,
+    /// Evaluate entry ages at this Unix timestamp instead of now.
+    pub as_of: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub archived: Vec<String>,
+    pub deleted: Vec<String>,
+    pub skipped_pinned: Vec<String>,
+    /// Entries due for deletion but covered by a legal hold.
+    pub skipped_held: Vec<String>,
+}
+
+pub(crate) fn normalize_retention(value: &Value) -> Result<Value> {
+    let policy: RetentionPolicy = serde_json::from_value(value.clone())
+        .map_err(|e| anyhow!("Invalid retention policy: {}", e))?;
+    if policy.max_age_days == 0 {
+        return Err(anyhow!("Retention max_age_days must be at least 1"));
+    }
+    Ok(serde_json::to_value(policy)?)
+}
+
+pub fn retention_policy(form_def: &Value) -> Result<Option<RetentionPolicy>> {
+    match form_def.get("retention") {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => Ok(Some(serde_json::from_value(value.clone())?)),
+    }
+}
+
+async fn archive_entry_row<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    form_def: &Value,
+    mut row: entry::EntryRow,
+    integrity: &I,
+) -> Result<()> {
+    let mut timestamp = entry::now_ts();
+    if timestamp <= row.updated_at {
+        timestamp = row.updated_at + 0.001;
+    }
+    row.tags.push(ARCHIVED_TAG.to_string());
+    row.parent_revision_id = Some(row.revision_id.clone());
+    row.revision_id = Uuid::new_v4().to_string();
+    row.updated_at = timestamp;
+    row.author = RETENTION_AUTHOR.to_string();
+
+    let markdown = entry::render_markdown_for_form(
+        &row.title,
+        form_name,
+        &row.tags,
+        &row.fields,
+        &row.extra_attributes,
+        form_def,
+    );
+    let checksum = integrity.checksum(&markdown);
+    row.integrity = entry::IntegrityPayload {
+        checksum: checksum.clone(),
+        signature: integrity.signature(&markdown),
+    };
+    entry::write_entry_row(op, ws_path, form_name, &row.entry_id, &row).await?;
+
+    let revision = entry::RevisionRow {
+        revision_id: row.revision_id.clone(),
+        entry_id: row.entry_id.clone(),
+        parent_revision_id: row.parent_revision_id.clone(),
+        timestamp,
+        author: row.author.clone(),
+        fields: row.fields.clone(),
+        extra_attributes: row.extra_attributes.clone(),
+        markdown_checksum: checksum,
+        integrity: row.integrity.clone(),
+        restored_from: None,
+        message: None,
+        revision_metadata: None,
+    };
+    entry::append_revision_row_for_form(op, ws_path, form_name, &revision, form_def).await
+}
+
+/// Apply every form's retention policy to entries older than its `max_age_days`.
+///
+/// Pinned entries are skipped, and entries under legal hold are never deleted.
+/// Each applied action and the sweep summary are recorded in the audit log.
+pub async fn enforce_retention<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    options: &RetentionOptions,
+    integrity: &I,
+) -> Result<RetentionReport> {
+    let space_id = space::space_id_from_ws_path(ws_path);
+    let now = options.as_of.unwrap_or_else(entry::now_ts);
+    let mut report = RetentionReport {
+        dry_run: options.dry_run,
+        ..RetentionReport::default()
+    };
+
+    for form_name in form::list_form_names(op, ws_path).await? {
+        let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+        let Some(policy) = retention_policy(&form_def)? else {
+            continue;
+        };
+        let cutoff = now - policy.max_age_days as f64 * SECONDS_PER_DAY;
+        let mut rows = entry::list_form_entry_rows(op, ws_path, &form_name, &form_def).await?;
+        rows.sort_by(|a, b| a.entry_id.cmp(&b.entry_id));
+
+        for row in rows {
+            if row.deleted || row.created_at > cutoff {
+                continue;
+            }
+            if row.tags.iter().any(|tag| tag == ARCHIVED_TAG)
+                && policy.action == RetentionAction::Archive
+            {
+                continue;
+            }
+            if row.tags.iter().any(|tag| tag == PINNED_TAG) {
+                report.skipped_pinned.push(row.entry_id);
+                continue;
+            }
+            let entry_id = row.entry_id.clone();
+            match policy.action {
+                RetentionAction::Archive => {
+                    if !options.dry_run {
+                        let row = entry::read_entry_row(op, ws_path, &form_name, &entry_id).await?;
+                        archive_entry_row(op, ws_path, &form_name, &form_def, row, integrity)
+                            .await?;
+                    }
+                    report.archived.push(entry_id.clone());
+                }
+                RetentionAction::Delete => {
+                    let holds =
+                        legal_hold::holds_for_entry(op, ws_path, &form_name, &entry_id).await?;
+                    if !holds.is_empty() {
+                        report.skipped_held.push(entry_id);
+                        continue;
+                    }
+                    if !options.dry_run {
+                        entry::delete_entry(op, ws_path, &entry_id, false).await?;
+                    }
+                    report.deleted.push(entry_id.clone());
+                }
+            }
+            if !options.dry_run {
+                audit::append_audit_event(
+                    op,
+                    space_id,
+                    &json!({
+                        "action": match policy.action {
+                            RetentionAction::Archive => "retention.archive",
+                            RetentionAction::Delete => "retention.delete",
+                        },
+                        "actor_user_id": RETENTION_AUTHOR,
+                        "outcome": "success",
+                        "target_type": "entry",
+                        "target_id": entry_id,
+                        "metadata": {"form": form_name, "max_age_days": policy.max_age_days},
+                    }),
+                    None,
+                )
+                .await?;
+            }
+        }
+    }
+
+    audit::append_audit_event(
+        op,
+        space_id,
+        &json!({
+            "action": "retention.enforce",
+            "actor_user_id": RETENTION_AUTHOR,
+            "outcome": "success",
+            "target_type": "space",
+            "target_id": space_id,
+            "metadata": {
+                "dry_run": report.dry_run,
+                "archived": report.archived.len(),
+                "deleted": report.deleted.len(),
+                "skipped_pinned": report.skipped_pinned.len(),
+                "skipped_held": report.skipped_held.len(),
+            },
+        }),
+        None,
+    )
+    .await?;
+    Ok(report)
+}
@@ -0,0 +1,253 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+use crate::entry;
+use crate::form;
+use crate::legal_hold;
+use crate::space_lock;
+
+/// Revisions between full snapshots when a form does not set
+/// `revision_snapshot_interval`.
+pub const DEFAULT_SNAPSHOT_INTERVAL: u64 = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TextOp {
+    Keep(usize),
+    Delete(usize),
+    Insert(String),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ValuePatch {
+    Set(Value),
+    Text(Vec<TextOp>),
+    Unset,
+}
+
+/// Changes from a snapshot revision, stored in place of the full field values.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub(crate) struct RevisionDelta {
+    pub base: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, ValuePatch>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra_attributes: BTreeMap<String, ValuePatch>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct RevisionStorageReport {
+    pub migrated_forms: Vec<String>,
+    /// Forms left untouched because a legal hold covers them or one of their entries.
+    pub held_forms: Vec<String>,
+    pub revisions: usize,
+    pub delta_revisions: usize,
+}
+
+pub(crate) fn normalize_snapshot_interval(value: &Value) -> Result<Value> {
+    match value.as_u64() {
+        Some(interval) if interval >= 1 => Ok(Value::from(interval)),
+        _ => Err(anyhow!(
+            "revision_snapshot_interval must be a positive integer"
+        )),
+    }
+}
+
+/// Every how many revisions a full snapshot is stored; 1 disables deltas.
+pub fn snapshot_interval(form_def: &Value) -> u64 {
+    form_def
+        .get("revision_snapshot_interval")
+        .and_then(Value::as_u64)
+        .filter(|interval| *interval >= 1)
+        .unwrap_or(DEFAULT_SNAPSHOT_INTERVAL)
+}
+
+fn text_ops(base: &str, target: &str) -> Vec<TextOp> {
+    dissimilar::diff(base, target)
+        .into_iter()
+        .map(|chunk| match chunk {
+            dissimilar::Chunk::Equal(text) => TextOp::Keep(text.chars().count()),
+            dissimilar::Chunk::Delete(text) => TextOp::Delete(text.chars().count()),
+            dissimilar::Chunk::Insert(text) => TextOp::Insert(text.to_string()),
+        })
+        .collect()
+}
+
+fn apply_text_ops(base: &str, ops: &[TextOp]) -> Result<String> {
+    let mut chars = base.chars();
+    let mut out = String::with_capacity(base.len());
+    for op in ops {
+        match op {
+            TextOp::Keep(count) => {
+                for _ in 0..*count {
+                    out.push(
+                        chars
+                            .next()
+                            .ok_or_else(|| anyhow!("Text delta overruns base"))?,
+                    );
+                }
+            }
+            TextOp::Delete(count) => {
+                for _ in 0..*count {
+                    chars
+                        .next()
+                        .ok_or_else(|| anyhow!("Text delta overruns base"))?;
+                }
+            }
+            TextOp::Insert(text) => out.push_str(text),
+        }
+    }
+    if chars.next().is_some() {
+        return Err(anyhow!("Text delta does not cover base"));
+    }
+    Ok(out)
+}
+
+fn encoded_len<T: Serialize>(value: &T) -> usize {
+    serde_json::to_vec(value).map_or(usize::MAX, |bytes| bytes.len())
+}
+
+fn diff_maps(base: &Value, target: &Value) -> BTreeMap<String, ValuePatch> {
+    let empty = Map::new();
+    let base = base.as_object().unwrap_or(&empty);
+    let target = target.as_object().unwrap_or(&empty);
+    let mut patches = BTreeMap::new();
+    for (key, value) in target {
+        let patch = match base.get(key) {
+            Some(existing) if existing == value => continue,
+            Some(Value::String(old)) if value.is_string() => {
+                let set = ValuePatch::Set(value.clone());
+                let text = ValuePatch::Text(text_ops(old, value.as_str().unwrap_or_default()));
+                if encoded_len(&text) < encoded_len(&set) {
+                    text
+                } else {
+                    set
+                }
+            }
+            _ => ValuePatch::Set(value.clone()),
+        };
+        patches.insert(key.clone(), patch);
+    }
+    for key in base.keys() {
+        if !target.contains_key(key) {
+            patches.insert(key.clone(), ValuePatch::Unset);
+        }
+    }
+    patches
+}
+
+fn apply_map(base: &Value, patches: &BTreeMap<String, ValuePatch>) -> Result<Value> {
+    let mut map = base.as_object().cloned().unwrap_or_default();
+    for (key, patch) in patches {
+        match patch {
+            ValuePatch::Set(value) => {
+                map.insert(key.clone(), value.clone());
+            }
+            ValuePatch::Text(ops) => {
+                let old = map
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| anyhow!("Text delta for '{}' has no text to apply to", key))?;
+                let new = apply_text_ops(old, ops)?;
+                map.insert(key.clone(), Value::String(new));
+            }
+            ValuePatch::Unset => {
+                map.remove(key);
+            }
+        }
+    }
+    Ok(Value::Object(map))
+}
+
+pub(crate) fn encode_delta(
+    base: &entry::RevisionRow,
+    target: &entry::RevisionRow,
+) -> RevisionDelta {
+    RevisionDelta {
+        base: base.revision_id.clone(),
+        fields: diff_maps(&base.fields, &target.fields),
+        extra_attributes: diff_maps(&base.extra_attributes, &target.extra_attributes),
+    }
+}
+
+/// Rebuild `(fields, extra_attributes)` from the snapshot a delta was taken against.
+pub(crate) fn apply_delta(
+    base: &entry::RevisionRow,
+    delta: &RevisionDelta,
+) -> Result<(Value, Value)> {
+    Ok((
+        apply_map(&base.fields, &delta.fields)?,
+        apply_map(&base.extra_attributes, &delta.extra_attributes)?,
+    ))
+}
+
+async fn form_is_held(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    rows: &[entry::EntryRow],
+) -> Result<bool> {
+    for row in rows {
+        if !legal_hold::holds_for_entry(op, ws_path, form_name, &row.entry_id)
+            .await?
+            .is_empty()
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Rewrite every form's revision history into snapshot-plus-delta storage.
+///
+/// Reconstructed revisions are unchanged. Forms with held entries are skipped
+/// so evidence under legal hold is never rewritten.
+pub async fn migrate_revision_storage(
+    op: &Operator,
+    ws_path: &str,
+) -> Result<RevisionStorageReport> {
+    space_lock::with_space_lease(op, ws_path, space_lock::MIGRATION_LEASE, |_| {
+        migrate_revision_storage_unlocked(op, ws_path)
+    })
+    .await
+}
+
+async fn migrate_revision_storage_unlocked(
+    op: &Operator,
+    ws_path: &str,
+) -> Result<RevisionStorageReport> {
+    let mut report = RevisionStorageReport::default();
+    for form_name in form::list_form_names(op, ws_path).await? {
+        let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+        let entry_rows = entry::list_form_entry_rows(op, ws_path, &form_name, &form_def).await?;
+        if form_is_held(op, ws_path, &form_name, &entry_rows).await? {
+            report.held_forms.push(form_name);
+            continue;
+        }
+        let mut revision_rows =
+            entry::list_form_revision_rows(op, ws_path, &form_name, &form_def).await?;
+        revision_rows.sort_by(|a, b| {
+            a.timestamp
+                .partial_cmp(&b.timestamp)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        form::rewrite_form_tables(
+            op,
+            ws_path,
+            &form_name,
+            &form_def,
+            &entry_rows,
+            &revision_rows,
+        )
+        .await?;
+        report.revisions += revision_rows.len();
+        report.delta_revisions +=
+            entry::count_delta_revisions(op, ws_path, &form_name, &form_def).await?;
+        report.migrated_forms.push(form_name);
+    }
+    Ok(report)
+}
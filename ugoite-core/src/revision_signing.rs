@@ -0,0 +1,351 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use opendal::Operator;
+use rsa::pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey};
+use rsa::pkcs1v15::{Signature, SigningKey, VerifyingKey};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePublicKey};
+use rsa::signature::{SignatureEncoding, Signer, Verifier};
+use rsa::traits::PublicKeyParts;
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+use crate::clock;
+use crate::entry;
+use crate::storage;
+
+/// RSASSA-PKCS1-v1_5 over SHA-256, the only algorithm accepted for now.
+pub const REVISION_SIGNATURE_ALGORITHM: &str = "rsa-pkcs1v15-sha256";
+/// Smallest RSA modulus accepted for author keys, in bits.
+pub const MIN_SIGNING_KEY_BITS: usize = 2048;
+
+const SIGNING_PAYLOAD_VERSION: &str = "ugoite-revision-v1";
+const AUTHOR_KEYS_FILE: &str = "author_keys.json";
+const REVISION_SIGNATURES_DIR: &str = "revision_signatures";
+
+/// A public key a principal signs their revisions with.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AuthorSigningKey {
+    pub key_id: String,
+    pub user_id: String,
+    pub algorithm: String,
+    pub public_key_pem: String,
+    /// Hex SHA-256 of the DER-encoded public key.
+    pub fingerprint: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub revoked_at: Option<String>,
+}
+
+/// An author's signature over one revision.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RevisionSignature {
+    pub revision_id: String,
+    pub author: String,
+    pub key_id: String,
+    pub algorithm: String,
+    /// Base64 signature over [`revision_signing_payload`].
+    pub signature: String,
+    pub signed_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RevisionAuthorVerification {
+    pub entry_id: String,
+    pub revision_id: String,
+    pub author: String,
+    pub signed: bool,
+    /// The stored signature matches the revision and a key the author
+    /// registered, and that key was not revoked before the signature was made.
+    pub verified: bool,
+    pub key_id: Option<String>,
+    pub signed_at: Option<String>,
+    pub key_revoked_at: Option<String>,
+    /// Why `verified` is false.
+    pub reason: Option<String>,
+}
+
+fn validate_id(kind: &str, value: &str) -> Result<()> {
+    let valid = !value.is_empty()
+        && value.len() <= 128
+        && !value.starts_with('.')
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.' | ':' | '@'));
+    if !valid {
+        return Err(anyhow!("Invalid {}: {}", kind, value));
+    }
+    Ok(())
+}
+
+fn keys_path(ws_path: &str) -> String {
+    format!("{}/{}", ws_path, AUTHOR_KEYS_FILE)
+}
+
+fn signatures_path(ws_path: &str, entry_id: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, REVISION_SIGNATURES_DIR, entry_id)
+}
+
+async fn read_json<T: serde::de::DeserializeOwned + Default>(
+    op: &Operator,
+    path: &str,
+) -> Result<T> {
+    match op.read(path).await {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes.to_vec())?),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(T::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Parse an RSA public key from SPKI (`BEGIN PUBLIC KEY`) or PKCS#1 PEM.
+fn parse_public_key(pem: &str) -> Result<RsaPublicKey> {
+    let pem = pem.trim();
+    let key = RsaPublicKey::from_public_key_pem(pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(pem))
+        .map_err(|_| anyhow!("Signing key must be an RSA public key in PEM format"))?;
+    if key.n().bits() < MIN_SIGNING_KEY_BITS {
+        return Err(anyhow!(
+            "Signing key must be at least {} bits",
+            MIN_SIGNING_KEY_BITS
+        ));
+    }
+    Ok(key)
+}
+
+fn fingerprint(key: &RsaPublicKey) -> Result<String> {
+    let der = key
+        .to_public_key_der()
+        .map_err(|e| anyhow!("Failed to encode signing key: {}", e))?;
+    Ok(hex::encode(Sha256::digest(der.as_bytes())))
+}
+
+/// The bytes an author signs for a revision. `markdown_checksum` is the
+/// checksum stored with the revision, so the signature covers its content.
+pub fn revision_signing_payload(
+    entry_id: &str,
+    revision_id: &str,
+    author: &str,
+    markdown_checksum: &str,
+) -> String {
+    format!("{SIGNING_PAYLOAD_VERSION}\n{entry_id}\n{revision_id}\n{author}\n{markdown_checksum}")
+}
+
+/// Sign `payload` with a PKCS#8 or PKCS#1 PEM private key, for clients that
+/// hold their key locally.
+pub fn sign_revision_payload(private_key_pem: &str, payload: &str) -> Result<String> {
+    let pem = private_key_pem.trim();
+    let key = RsaPrivateKey::from_pkcs8_pem(pem)
+        .or_else(|_| RsaPrivateKey::from_pkcs1_pem(pem))
+        .map_err(|_| anyhow!("Private key must be an RSA private key in PEM format"))?;
+    let signature = SigningKey::<Sha256>::new(key).sign(payload.as_bytes());
+    Ok(STANDARD.encode(signature.to_bytes()))
+}
+
+fn signature_valid(key: &AuthorSigningKey, payload: &str, signature: &str) -> bool {
+    let Ok(public_key) = parse_public_key(&key.public_key_pem) else {
+        return false;
+    };
+    let Some(signature) = STANDARD
+        .decode(signature.trim())
+        .ok()
+        .and_then(|bytes| Signature::try_from(bytes.as_slice()).ok())
+    else {
+        return false;
+    };
+    VerifyingKey::<Sha256>::new(public_key)
+        .verify(payload.as_bytes(), &signature)
+        .is_ok()
+}
+
+/// Register `public_key_pem` as a key `user_id` signs revisions with.
+/// Key ids are unique within the space.
+pub async fn register_signing_key(
+    op: &Operator,
+    ws_path: &str,
+    user_id: &str,
+    key_id: &str,
+    public_key_pem: &str,
+) -> Result<AuthorSigningKey> {
+    if user_id.trim().is_empty() {
+        return Err(anyhow!("user_id must not be empty"));
+    }
+    validate_id("key_id", key_id)?;
+    let public_key = parse_public_key(public_key_pem)?;
+    let mut keys: BTreeMap<String, AuthorSigningKey> = read_json(op, &keys_path(ws_path)).await?;
+    if keys.contains_key(key_id) {
+        return Err(anyhow!("Signing key already registered: {}", key_id));
+    }
+    let key = AuthorSigningKey {
+        key_id: key_id.to_string(),
+        user_id: user_id.to_string(),
+        algorithm: REVISION_SIGNATURE_ALGORITHM.to_string(),
+        public_key_pem: public_key_pem.trim().to_string(),
+        fingerprint: fingerprint(&public_key)?,
+        created_at: clock::now().to_rfc3339(),
+        revoked_at: None,
+    };
+    keys.insert(key_id.to_string(), key.clone());
+    storage::publish_atomic(op, &keys_path(ws_path), serde_json::to_vec_pretty(&keys)?).await?;
+    Ok(key)
+}
+
+/// Registered keys, optionally only those of `user_id`, ordered by key id.
+pub async fn list_signing_keys(
+    op: &Operator,
+    ws_path: &str,
+    user_id: Option<&str>,
+) -> Result<Vec<AuthorSigningKey>> {
+    let keys: BTreeMap<String, AuthorSigningKey> = read_json(op, &keys_path(ws_path)).await?;
+    Ok(keys
+        .into_values()
+        .filter(|key| user_id.is_none_or(|user_id| key.user_id == user_id))
+        .collect())
+}
+
+/// Stop accepting new signatures made with a key. Signatures made before the
+/// revocation still verify.
+pub async fn revoke_signing_key(
+    op: &Operator,
+    ws_path: &str,
+    user_id: &str,
+    key_id: &str,
+) -> Result<AuthorSigningKey> {
+    let mut keys: BTreeMap<String, AuthorSigningKey> = read_json(op, &keys_path(ws_path)).await?;
+    let key = keys
+        .get_mut(key_id)
+        .filter(|key| key.user_id == user_id)
+        .ok_or_else(|| anyhow!("Signing key not found: {}", key_id))?;
+    if key.revoked_at.is_none() {
+        key.revoked_at = Some(clock::now().to_rfc3339());
+    }
+    let key = key.clone();
+    storage::publish_atomic(op, &keys_path(ws_path), serde_json::to_vec_pretty(&keys)?).await?;
+    Ok(key)
+}
+
+/// The author and signing payload of a stored revision.
+pub async fn revision_payload(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    revision_id: &str,
+) -> Result<(String, String)> {
+    let revision = entry::get_entry_revision(op, ws_path, entry_id, revision_id).await?;
+    let author = revision["author"].as_str().unwrap_or_default().to_string();
+    let checksum = revision["markdown_checksum"].as_str().unwrap_or_default();
+    let payload = revision_signing_payload(entry_id, revision_id, &author, checksum);
+    Ok((author, payload))
+}
+
+/// Store `signature` for a revision written by `signer_user_id`, after
+/// checking it against one of the signer's active keys.
+pub async fn attach_revision_signature(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    revision_id: &str,
+    signer_user_id: &str,
+    key_id: &str,
+    signature: &str,
+) -> Result<RevisionSignature> {
+    validate_id("entry_id", entry_id)?;
+    let (author, payload) = revision_payload(op, ws_path, entry_id, revision_id).await?;
+    if author != signer_user_id {
+        return Err(anyhow!(
+            "Only the author of revision {} can sign it",
+            revision_id
+        ));
+    }
+    let keys: BTreeMap<String, AuthorSigningKey> = read_json(op, &keys_path(ws_path)).await?;
+    let key = keys
+        .get(key_id)
+        .filter(|key| key.user_id == signer_user_id)
+        .ok_or_else(|| anyhow!("Signing key not found: {}", key_id))?;
+    if key.revoked_at.is_some() {
+        return Err(anyhow!("Signing key has been revoked: {}", key_id));
+    }
+    if !signature_valid(key, &payload, signature) {
+        return Err(anyhow!("Invalid signature for revision {}", revision_id));
+    }
+
+    let record = RevisionSignature {
+        revision_id: revision_id.to_string(),
+        author,
+        key_id: key_id.to_string(),
+        algorithm: key.algorithm.clone(),
+        signature: signature.trim().to_string(),
+        signed_at: clock::now().to_rfc3339(),
+    };
+    let path = signatures_path(ws_path, entry_id);
+    let mut signatures: BTreeMap<String, RevisionSignature> = read_json(op, &path).await?;
+    signatures.insert(revision_id.to_string(), record.clone());
+    storage::publish_atomic(op, &path, serde_json::to_vec_pretty(&signatures)?).await?;
+    Ok(record)
+}
+
+/// Author signatures stored for an entry, keyed by revision id.
+pub async fn list_revision_signatures(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+) -> Result<BTreeMap<String, RevisionSignature>> {
+    validate_id("entry_id", entry_id)?;
+    read_json(op, &signatures_path(ws_path, entry_id)).await
+}
+
+/// Check that a revision carries a valid signature by its recorded author.
+/// An unsigned or mismatching revision is reported, not an error.
+pub async fn verify_revision_author(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    revision_id: &str,
+) -> Result<RevisionAuthorVerification> {
+    let (author, payload) = revision_payload(op, ws_path, entry_id, revision_id).await?;
+    let signature = list_revision_signatures(op, ws_path, entry_id)
+        .await?
+        .remove(revision_id);
+    let mut report = RevisionAuthorVerification {
+        entry_id: entry_id.to_string(),
+        revision_id: revision_id.to_string(),
+        author: author.clone(),
+        signed: signature.is_some(),
+        verified: false,
+        key_id: signature.as_ref().map(|sig| sig.key_id.clone()),
+        signed_at: signature.as_ref().map(|sig| sig.signed_at.clone()),
+        key_revoked_at: None,
+        reason: None,
+    };
+    let Some(signature) = signature else {
+        report.reason = Some("Revision is not signed".to_string());
+        return Ok(report);
+    };
+    let keys: BTreeMap<String, AuthorSigningKey> = read_json(op, &keys_path(ws_path)).await?;
+    let Some(key) = keys.get(&signature.key_id) else {
+        report.reason = Some(format!("Signing key not found: {}", signature.key_id));
+        return Ok(report);
+    };
+    report.key_revoked_at = key.revoked_at.clone();
+    report.reason = if key.user_id != author || signature.author != author {
+        Some(format!("Signing key does not belong to {}", author))
+    } else if key
+        .revoked_at
+        .as_deref()
+        .is_some_and(|revoked_at| timestamp(revoked_at) <= timestamp(&signature.signed_at))
+    {
+        Some("Signing key was revoked before the signature was made".to_string())
+    } else if !signature_valid(key, &payload, &signature.signature) {
+        Some("Signature does not match the revision".to_string())
+    } else {
+        None
+    };
+    report.verified = report.reason.is_none();
+    Ok(report)
+}
+
+fn timestamp(value: &str) -> i64 {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|at| at.timestamp_micros())
+        .unwrap_or(i64::MAX)
+}
@@ -0,0 +1,480 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::entry::{self, EntryRow};
+use crate::form;
+use crate::space_lock;
+use crate::storage;
+
+const ROLLUPS_DIR: &str = "rollups";
+/// Space lease serializing rollup index updates across processes.
+const ROLLUPS_LEASE: &str = "rollups";
+/// Field types [`RollupAggregate::Sum`] can add up.
+const NUMERIC_FIELD_TYPES: &[&str] = &[
+    "number", "double", "float", "integer", "long", "decimal", "sequence",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupAggregate {
+    /// Number of related entries.
+    Count,
+    /// Sum of a numeric property over related entries.
+    Sum,
+    /// The property of the most recently updated related entry.
+    Latest,
+}
+
+/// Settings of a rollup field: `{"type": "rollup", "source_form": "Task",
+/// "relation_field": "Project", "property": "Points", "aggregate": "sum"}`
+/// aggregates `Points` over the `Task` entries whose `Project` row reference
+/// points at the entry.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RollupDef {
+    pub source_form: String,
+    pub relation_field: String,
+    #[serde(default)]
+    pub property: Option<String>,
+    pub aggregate: RollupAggregate,
+}
+
+impl RollupDef {
+    /// The field's rollup settings, or `None` when it is not a rollup.
+    pub fn from_field_def(def: &Value) -> Result<Option<Self>> {
+        if def.get("type").and_then(Value::as_str) != Some("rollup") {
+            return Ok(None);
+        }
+        serde_json::from_value(def.clone())
+            .map(Some)
+            .map_err(|e| anyhow!("Invalid rollup field: {}", e))
+    }
+
+    fn empty_value(&self) -> Value {
+        match self.aggregate {
+            RollupAggregate::Count | RollupAggregate::Sum => Value::from(0),
+            RollupAggregate::Latest => Value::Null,
+        }
+    }
+}
+
+/// What one related entry contributes to a rollup.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct Contribution {
+    parent: String,
+    #[serde(default)]
+    value: Value,
+    updated_at: f64,
+}
+
+/// One rollup field, kept up to date as entries of its source form are written.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct RollupState {
+    form: String,
+    field: String,
+    def: RollupDef,
+    /// Related entry id to its contribution.
+    #[serde(default)]
+    contributions: BTreeMap<String, Contribution>,
+    /// Aggregated value per parent entry.
+    #[serde(default)]
+    values: BTreeMap<String, Value>,
+}
+
+/// Rollups fed by the entries of one source form, keyed `form/field`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+struct RollupIndex {
+    #[serde(default)]
+    rollups: BTreeMap<String, RollupState>,
+}
+
+fn rollups_dir(ws_path: &str) -> String {
+    format!("{}/{}/", ws_path, ROLLUPS_DIR)
+}
+
+fn index_path(ws_path: &str, source_form: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, ROLLUPS_DIR, source_form)
+}
+
+/// Rollup fields of a form with their settings.
+pub fn rollup_fields(form_def: &Value) -> Result<Vec<(String, RollupDef)>> {
+    let fields = form::normalize_form_fields(form_def.get("fields"));
+    let mut rollups = Vec::new();
+    if let Some(field_map) = fields.as_object() {
+        for (name, def) in field_map {
+            if let Some(rollup) = RollupDef::from_field_def(def)? {
+                rollups.push((name.clone(), rollup));
+            }
+        }
+    }
+    Ok(rollups)
+}
+
+pub(crate) fn validate_rollup_field_defs(field_map: &Map<String, Value>) -> Result<()> {
+    for (name, def) in field_map {
+        let Some(rollup) = RollupDef::from_field_def(def)? else {
+            continue;
+        };
+        if rollup.source_form.trim().is_empty() || rollup.relation_field.trim().is_empty() {
+            return Err(anyhow!(
+                "Rollup field '{}' requires source_form and relation_field",
+                name
+            ));
+        }
+        if rollup.aggregate != RollupAggregate::Count && rollup.property.is_none() {
+            return Err(anyhow!("Rollup field '{}' requires a property", name));
+        }
+        for flag in ["required", "unique", "secret", "reminder"] {
+            if def
+                .get(flag)
+                .is_some_and(|value| value != &Value::Bool(false))
+            {
+                return Err(anyhow!("Rollup field '{}' cannot be {}", name, flag));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that each rollup field of `form_def` follows a row reference to the
+/// form itself and aggregates a property its source form has.
+pub(crate) async fn validate_rollup_sources(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    form_def: &Value,
+) -> Result<()> {
+    for (name, rollup) in rollup_fields(form_def)? {
+        let source_def = if rollup.source_form == form_name {
+            form_def.clone()
+        } else {
+            form::read_form_definition(op, ws_path, &rollup.source_form)
+                .await
+                .map_err(|_| {
+                    anyhow!(
+                        "Rollup field '{}' source_form '{}' not found",
+                        name,
+                        rollup.source_form
+                    )
+                })?
+        };
+        let source_fields = form::normalize_form_fields(source_def.get("fields"));
+        let relation = source_fields.get(&rollup.relation_field);
+        let relates = relation.is_some_and(|def| {
+            def.get("type").and_then(Value::as_str) == Some("row_reference")
+                && def.get("target_form").and_then(Value::as_str) == Some(form_name)
+        });
+        if !relates {
+            return Err(anyhow!(
+                "Rollup field '{}' relation_field '{}' must be a row_reference of form '{}' to '{}'",
+                name,
+                rollup.relation_field,
+                rollup.source_form,
+                form_name
+            ));
+        }
+        let Some(property) = &rollup.property else {
+            continue;
+        };
+        let property_def = source_fields.get(property).ok_or_else(|| {
+            anyhow!(
+                "Rollup field '{}' property '{}' is not a field of form '{}'",
+                name,
+                property,
+                rollup.source_form
+            )
+        })?;
+        let property_type = property_def
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or("string");
+        if property_type == "rollup"
+            || property_def.get("secret").and_then(Value::as_bool) == Some(true)
+        {
+            return Err(anyhow!(
+                "Rollup field '{}' cannot aggregate field '{}'",
+                name,
+                property
+            ));
+        }
+        if rollup.aggregate == RollupAggregate::Sum && !NUMERIC_FIELD_TYPES.contains(&property_type)
+        {
+            return Err(anyhow!(
+                "Rollup field '{}' can only sum a numeric field, not '{}'",
+                name,
+                property
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reject values written to rollup fields, which are computed.
+pub(crate) fn reject_rollup_values(form_def: &Value, fields: &Value) -> Result<()> {
+    for (name, _) in rollup_fields(form_def)? {
+        let set = match fields.get(&name) {
+            None | Some(Value::Null) => false,
+            Some(Value::String(text)) => !text.trim().is_empty(),
+            Some(_) => true,
+        };
+        if set {
+            return Err(anyhow!(
+                "Rollup field '{}' is computed and cannot be set",
+                name
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn contribution(state: &RollupState, row: &EntryRow) -> Option<Contribution> {
+    if row.deleted {
+        return None;
+    }
+    let parent = row
+        .fields
+        .get(&state.def.relation_field)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|parent| !parent.is_empty())?;
+    let value = state
+        .def
+        .property
+        .as_ref()
+        .and_then(|property| row.fields.get(property))
+        .cloned()
+        .unwrap_or(Value::Null);
+    Some(Contribution {
+        parent: parent.to_string(),
+        value,
+        updated_at: row.updated_at,
+    })
+}
+
+fn numeric(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Aggregate the contributions to `parent`.
+fn aggregate(state: &RollupState, parent: &str) -> Value {
+    let children = state
+        .contributions
+        .iter()
+        .filter(|(_, contribution)| contribution.parent == parent);
+    match state.def.aggregate {
+        RollupAggregate::Count => Value::from(children.count()),
+        RollupAggregate::Sum => {
+            let values: Vec<&Value> = children
+                .map(|(_, contribution)| &contribution.value)
+                .filter(|value| !value.is_null())
+                .collect();
+            if let Some(total) = values
+                .iter()
+                .map(|value| value.as_i64())
+                .sum::<Option<i64>>()
+            {
+                return Value::from(total);
+            }
+            let total: f64 = values.iter().filter_map(|value| numeric(value)).sum();
+            Number::from_f64(total).map_or(Value::Null, Value::Number)
+        }
+        RollupAggregate::Latest => children
+            .filter(|(_, contribution)| !contribution.value.is_null())
+            .max_by(|(a_id, a), (b_id, b)| {
+                a.updated_at
+                    .partial_cmp(&b.updated_at)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a_id.cmp(b_id))
+            })
+            .map_or(Value::Null, |(_, contribution)| contribution.value.clone()),
+    }
+}
+
+/// Move the contributions of `rows` into `state` and re-aggregate the parents
+/// they left or joined.
+fn apply_rows(state: &mut RollupState, rows: &[EntryRow]) {
+    let mut touched = BTreeSet::new();
+    for row in rows {
+        if let Some(previous) = state.contributions.remove(&row.entry_id) {
+            touched.insert(previous.parent);
+        }
+        if let Some(contribution) = contribution(state, row) {
+            touched.insert(contribution.parent.clone());
+            state
+                .contributions
+                .insert(row.entry_id.clone(), contribution);
+        }
+    }
+    for parent in touched {
+        let value = aggregate(state, &parent);
+        if state
+            .contributions
+            .values()
+            .any(|contribution| contribution.parent == parent)
+        {
+            state.values.insert(parent, value);
+        } else {
+            state.values.remove(&parent);
+        }
+    }
+}
+
+async fn load_index(
+    op: &Operator,
+    ws_path: &str,
+    source_form: &str,
+) -> Result<Option<RollupIndex>> {
+    let path = index_path(ws_path, source_form);
+    if !op.exists(&path).await? {
+        return Ok(None);
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    Ok(Some(serde_json::from_slice(&bytes)?))
+}
+
+async fn write_index(
+    op: &Operator,
+    ws_path: &str,
+    source_form: &str,
+    index: &RollupIndex,
+) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &index_path(ws_path, source_form),
+        serde_json::to_vec_pretty(index)?,
+    )
+    .await
+}
+
+/// Update the rollups fed by `form_name` after `rows` of it were written.
+/// Forms no rollup reads from have no index and are skipped.
+pub(crate) async fn apply_entry_rows(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    rows: &[EntryRow],
+) -> Result<()> {
+    if !op.exists(&index_path(ws_path, form_name)).await? {
+        return Ok(());
+    }
+    space_lock::with_space_lease(op, ws_path, ROLLUPS_LEASE, |_| async {
+        let Some(mut index) = load_index(op, ws_path, form_name).await? else {
+            return Ok(());
+        };
+        let before = index.clone();
+        for state in index.rollups.values_mut() {
+            apply_rows(state, rows);
+        }
+        if index != before {
+            write_index(op, ws_path, form_name, &index).await?;
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Rebuild every rollup index of the space from the current entries, after a
+/// form change or when the index is rebuilt.
+pub async fn rebuild_rollups(op: &Operator, ws_path: &str) -> Result<()> {
+    let mut by_source: BTreeMap<String, RollupIndex> = BTreeMap::new();
+    for form_name in form::list_form_names(op, ws_path).await? {
+        let Ok(form_def) = form::read_form_definition(op, ws_path, &form_name).await else {
+            continue;
+        };
+        for (field, def) in rollup_fields(&form_def)? {
+            by_source
+                .entry(def.source_form.clone())
+                .or_default()
+                .rollups
+                .insert(
+                    format!("{}/{}", form_name, field),
+                    RollupState {
+                        form: form_name.clone(),
+                        field,
+                        def,
+                        contributions: BTreeMap::new(),
+                        values: BTreeMap::new(),
+                    },
+                );
+        }
+    }
+    let mut rebuilt = BTreeMap::new();
+    for (source_form, mut index) in by_source {
+        let Ok(source_def) = form::read_form_definition(op, ws_path, &source_form).await else {
+            continue;
+        };
+        let rows = entry::list_form_entry_rows(op, ws_path, &source_form, &source_def).await?;
+        for state in index.rollups.values_mut() {
+            apply_rows(state, &rows);
+        }
+        rebuilt.insert(source_form, index);
+    }
+
+    space_lock::with_space_lease(op, ws_path, ROLLUPS_LEASE, |_| async {
+        let dir = rollups_dir(ws_path);
+        if op.exists(&dir).await? {
+            for item in op.list(&dir).await? {
+                let Some(source_form) = item.name().strip_suffix(".json") else {
+                    continue;
+                };
+                if !rebuilt.contains_key(source_form) {
+                    op.delete(&index_path(ws_path, source_form)).await?;
+                }
+            }
+        }
+        for (source_form, index) in &rebuilt {
+            write_index(op, ws_path, source_form, index).await?;
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Rebuild rollups after `form_def` was saved, when it or any other form has
+/// rollup fields.
+pub(crate) async fn sync_form_rollups(
+    op: &Operator,
+    ws_path: &str,
+    form_def: &Value,
+) -> Result<()> {
+    if rollup_fields(form_def)?.is_empty() && !op.exists(&rollups_dir(ws_path)).await? {
+        return Ok(());
+    }
+    rebuild_rollups(op, ws_path).await
+}
+
+/// Current values of the rollup fields of one entry, by field name.
+pub async fn get_entry_rollups(
+    op: &Operator,
+    ws_path: &str,
+    form_def: &Value,
+    entry_id: &str,
+) -> Result<Map<String, Value>> {
+    let form_name = form_def
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let mut values = Map::new();
+    let mut indexes: BTreeMap<String, Option<RollupIndex>> = BTreeMap::new();
+    for (field, def) in rollup_fields(form_def)? {
+        if !indexes.contains_key(&def.source_form) {
+            let index = load_index(op, ws_path, &def.source_form).await?;
+            indexes.insert(def.source_form.clone(), index);
+        }
+        let value = indexes
+            .get(&def.source_form)
+            .and_then(Option::as_ref)
+            .and_then(|index| index.rollups.get(&format!("{}/{}", form_name, field)))
+            .and_then(|state| state.values.get(entry_id))
+            .cloned()
+            .unwrap_or_else(|| def.empty_value());
+        values.insert(field, value);
+    }
+    Ok(values)
+}
@@ -0,0 +1,227 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+pub const WORKER_THREADS_ENV: &str = "UGOITE_RUNTIME_WORKER_THREADS";
+pub const MAX_BLOCKING_THREADS_ENV: &str = "UGOITE_RUNTIME_MAX_BLOCKING_THREADS";
+const THREAD_NAME: &str = "ugoite-core";
+
+/// Sizing for the Tokio runtime that drives async bindings.
+///
+/// `None` keeps Tokio's defaults (one worker per core, 512 blocking threads).
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    #[serde(default)]
+    pub worker_threads: Option<usize>,
+    #[serde(default)]
+    pub max_blocking_threads: Option<usize>,
+}
+
+impl RuntimeConfig {
+    pub fn from_env() -> Result<Self> {
+        let config = Self {
+            worker_threads: env_count(WORKER_THREADS_ENV)?,
+            max_blocking_threads: env_count(MAX_BLOCKING_THREADS_ENV)?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.worker_threads == Some(0) {
+            return Err(anyhow!("worker_threads must be at least 1"));
+        }
+        if self.max_blocking_threads == Some(0) {
+            return Err(anyhow!("max_blocking_threads must be at least 1"));
+        }
+        Ok(())
+    }
+
+    pub fn builder(&self) -> tokio::runtime::Builder {
+        let mut builder = tokio::runtime::Builder::new_multi_thread();
+        builder.enable_all().thread_name(THREAD_NAME);
+        if let Some(workers) = self.worker_threads {
+            builder.worker_threads(workers);
+        }
+        if let Some(blocking) = self.max_blocking_threads {
+            builder.max_blocking_threads(blocking);
+        }
+        builder
+    }
+
+    pub fn build(&self) -> Result<tokio::runtime::Runtime> {
+        self.validate()?;
+        Ok(self.builder().build()?)
+    }
+}
+
+fn env_count(name: &str) -> Result<Option<usize>> {
+    match std::env::var(name) {
+        Ok(raw) if !raw.trim().is_empty() => raw
+            .trim()
+            .parse()
+            .map(Some)
+            .map_err(|_| anyhow!("{} must be a positive integer, got '{}'", name, raw)),
+        _ => Ok(None),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TaskStats {
+    pub started: u64,
+    pub succeeded: u64,
+    pub failed: u64,
+    /// Tasks dropped before completing, e.g. when the awaiting coroutine was cancelled.
+    pub cancelled: u64,
+    pub in_flight: u64,
+    pub total_duration_ms: f64,
+    pub max_duration_ms: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct RuntimeMetrics {
+    pub config: RuntimeConfig,
+    pub in_flight: usize,
+    pub shutting_down: bool,
+    /// Per-task statistics keyed by task name.
+    pub tasks: BTreeMap<String, TaskStats>,
+}
+
+#[derive(Default)]
+struct RuntimeState {
+    config: Mutex<RuntimeConfig>,
+    tasks: Mutex<BTreeMap<&'static str, TaskStats>>,
+    in_flight: AtomicUsize,
+    started: AtomicBool,
+    shutting_down: AtomicBool,
+    idle: Notify,
+}
+
+static STATE: OnceLock<RuntimeState> = OnceLock::new();
+
+fn state() -> &'static RuntimeState {
+    STATE.get_or_init(RuntimeState::default)
+}
+
+/// Record the configuration the runtime will be built with.
+///
+/// Fails once any task has started, because the runtime is built lazily on
+/// first use and cannot be resized afterwards.
+pub fn configure(config: RuntimeConfig) -> Result<()> {
+    config.validate()?;
+    let state = state();
+    let mut current = state.config.lock().unwrap_or_else(|e| e.into_inner());
+    if state.started.load(Ordering::SeqCst) {
+        return Err(anyhow!(
+            "The runtime is already running; configure it before the first call"
+        ));
+    }
+    *current = config;
+    Ok(())
+}
+
+pub fn current_config() -> RuntimeConfig {
+    state()
+        .config
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Tracks one running task; dropping it without `finish` counts as cancelled.
+#[must_use]
+pub struct TaskGuard {
+    name: &'static str,
+    started_at: Instant,
+    outcome: Option<bool>,
+}
+
+impl TaskGuard {
+    pub fn finish(mut self, ok: bool) {
+        self.outcome = Some(ok);
+    }
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        let state = state();
+        let elapsed_ms = self.started_at.elapsed().as_secs_f64() * 1000.0;
+        {
+            let mut tasks = state.tasks.lock().unwrap_or_else(|e| e.into_inner());
+            let stats = tasks.entry(self.name).or_default();
+            stats.in_flight = stats.in_flight.saturating_sub(1);
+            match self.outcome {
+                Some(true) => stats.succeeded += 1,
+                Some(false) => stats.failed += 1,
+                None => stats.cancelled += 1,
+            }
+            stats.total_duration_ms += elapsed_ms;
+            stats.max_duration_ms = stats.max_duration_ms.max(elapsed_ms);
+        }
+        if state.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            state.idle.notify_waiters();
+        }
+    }
+}
+
+/// Register a task about to be spawned; rejected once shutdown has begun.
+pub fn begin_task(name: &'static str) -> Result<TaskGuard> {
+    let state = state();
+    // Hold the config lock so `configure` cannot race the first task.
+    let _config = state.config.lock().unwrap_or_else(|e| e.into_inner());
+    if state.shutting_down.load(Ordering::SeqCst) {
+        return Err(anyhow!("The runtime is shutting down"));
+    }
+    state.started.store(true, Ordering::SeqCst);
+    state.in_flight.fetch_add(1, Ordering::SeqCst);
+    let mut tasks = state.tasks.lock().unwrap_or_else(|e| e.into_inner());
+    let stats = tasks.entry(name).or_default();
+    stats.started += 1;
+    stats.in_flight += 1;
+    Ok(TaskGuard {
+        name,
+        started_at: Instant::now(),
+        outcome: None,
+    })
+}
+
+pub fn metrics() -> RuntimeMetrics {
+    let state = state();
+    let tasks = state.tasks.lock().unwrap_or_else(|e| e.into_inner());
+    RuntimeMetrics {
+        config: current_config(),
+        in_flight: state.in_flight.load(Ordering::SeqCst),
+        shutting_down: state.shutting_down.load(Ordering::SeqCst),
+        tasks: tasks
+            .iter()
+            .map(|(name, stats)| (name.to_string(), stats.clone()))
+            .collect(),
+    }
+}
+
+/// Stop accepting tasks and wait for in-flight ones to finish.
+///
+/// Returns whether every task finished before `timeout`.
+pub async fn shutdown(timeout: Duration) -> bool {
+    let state = state();
+    state.shutting_down.store(true, Ordering::SeqCst);
+    let drained = async {
+        loop {
+            let idle = state.idle.notified();
+            if state.in_flight.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    };
+    tokio::time::timeout(timeout, drained).await.is_ok()
+}
+
+/// Accept tasks again after `shutdown`, e.g. when a host restarts its workers.
+pub fn resume() {
+    state().shutting_down.store(false, Ordering::SeqCst);
+}
@@ -1,7 +1,15 @@
+use crate::asset;
+use crate::clock;
 use crate::entry;
 use crate::form;
+use crate::geo::GeoPoint;
+use crate::iceberg_store;
 use crate::integrity::RealIntegrityProvider;
+use crate::link;
+use crate::metadata;
+use crate::saved_sql;
 use crate::space;
+use crate::storage;
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Duration, NaiveDate, Utc};
 use opendal::Operator;
@@ -26,8 +34,30 @@ pub enum SampleJobStatus {
     Running,
     Completed,
     Failed,
+    Cancelled,
 }
 
+impl SampleJobStatus {
+    fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            SampleJobStatus::Completed | SampleJobStatus::Failed | SampleJobStatus::Cancelled
+        )
+    }
+}
+
+/// Returned from generation once a job's cancel marker is seen.
+#[derive(Debug)]
+struct SampleJobCancelled;
+
+impl std::fmt::Display for SampleJobCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Sample data job was cancelled")
+    }
+}
+
+impl std::error::Error for SampleJobCancelled {}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SampleDataOptions {
     pub space_id: String,
@@ -42,6 +72,11 @@ pub struct SampleDataOptions {
     /// after the space is created, making the space visible via the /spaces API.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub owner_user_id: Option<String>,
+    /// Generate under a frozen clock so the same seed and scenario always store
+    /// the same entry ids, timestamps, revision ids and property values.
+    /// Signatures still vary because each space gets a fresh HMAC key.
+    #[serde(default)]
+    pub fixture: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -73,6 +108,10 @@ pub struct SampleDataJob {
     pub status_message: Option<String>,
     pub processed_entries: usize,
     pub total_entries: usize,
+    #[serde(default)]
+    pub percent_complete: f64,
+    #[serde(default)]
+    pub cancel_requested: bool,
     pub started_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
     pub error: Option<String>,
@@ -114,6 +153,9 @@ fn resolve_sample_data_plan(options: &SampleDataOptions) -> Result<ResolvedSampl
     };
     let form_defs = scenario_forms(&scenario)
         .ok_or_else(|| anyhow!("Unknown sample data scenario: {}", scenario))?;
+    if options.fixture && options.seed.is_none() {
+        return Err(anyhow!("Fixture sample data requires a seed"));
+    }
     let form_count = form_defs.len();
     let entry_count = normalize_entry_count(options.entry_count, form_count);
     Ok(ResolvedSampleDataPlan {
@@ -159,6 +201,10 @@ fn allocate_counts(entry_count: usize, weights: &[f64]) -> Vec<usize> {
     counts
 }
 
+fn pick_string<'a>(rng: &mut StdRng, options: &'a [String]) -> &'a str {
+    &options[rng.random_range(0..options.len())]
+}
+
 fn pick<'a>(rng: &mut StdRng, options: &'a [&'a str]) -> &'a str {
     let idx = rng.random_range(0..options.len());
     options[idx]
@@ -196,6 +242,25 @@ pub fn list_sample_scenarios() -> Vec<SampleDataScenario> {
             label: "Retail operations".to_string(),
             description: "Store performance, stock alerts, and delivery logs.".to_string(),
         },
+        SampleDataScenario {
+            id: "project-tracker".to_string(),
+            label: "Project tracker".to_string(),
+            description:
+                "Projects, milestones, and tasks related by row references, with blocking links."
+                    .to_string(),
+        },
+        SampleDataScenario {
+            id: "team-wiki".to_string(),
+            label: "Team wiki".to_string(),
+            description: "Densely cross-linked wiki pages and glossary terms.".to_string(),
+        },
+        SampleDataScenario {
+            id: "crm".to_string(),
+            label: "CRM".to_string(),
+            description:
+                "Accounts, contacts, and deals with proposal assets and saved SQL dashboards."
+                    .to_string(),
+        },
     ]
 }
 
@@ -555,6 +620,132 @@ fn retail_ops_forms() -> Vec<Value> {
     ]
 }
 
+fn project_tracker_forms() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "Project",
+            "version": 1,
+            "allow_extra_attributes": "deny",
+            "fields": {
+                "Owner": {"type": "string", "required": true},
+                "Status": {"type": "string", "required": true},
+                "StartDate": {"type": "date", "required": true},
+                "DueDate": {"type": "date", "required": true},
+                "Summary": {"type": "markdown", "required": false}
+            }
+        }),
+        json!({
+            "name": "Milestone",
+            "version": 1,
+            "allow_extra_attributes": "deny",
+            "fields": {
+                "Project": {"type": "row_reference", "target_form": "Project", "required": true},
+                "DueDate": {"type": "date", "required": true},
+                "Status": {"type": "string", "required": true}
+            }
+        }),
+        json!({
+            "name": "Task",
+            "version": 1,
+            "allow_extra_attributes": "deny",
+            "fields": {
+                "Project": {"type": "row_reference", "target_form": "Project", "required": true},
+                "Milestone": {"type": "row_reference", "target_form": "Milestone", "required": false},
+                "Assignee": {"type": "string", "required": true},
+                "Status": {"type": "string", "required": true},
+                "EstimateHours": {"type": "number", "required": false},
+                "Description": {"type": "markdown", "required": false}
+            }
+        }),
+        json!({
+            "name": "StatusUpdate",
+            "version": 1,
+            "allow_extra_attributes": "deny",
+            "fields": {
+                "Project": {"type": "row_reference", "target_form": "Project", "required": true},
+                "ReportedOn": {"type": "date", "required": true},
+                "Health": {"type": "string", "required": true},
+                "Notes": {"type": "markdown", "required": false}
+            }
+        }),
+    ]
+}
+
+fn team_wiki_forms() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "WikiPage",
+            "version": 1,
+            "allow_extra_attributes": "deny",
+            "fields": {
+                "Category": {"type": "string", "required": true},
+                "Summary": {"type": "string", "required": true},
+                "UpdatedOn": {"type": "date", "required": true},
+                "Body": {"type": "markdown", "required": false}
+            }
+        }),
+        json!({
+            "name": "GlossaryTerm",
+            "version": 1,
+            "allow_extra_attributes": "deny",
+            "fields": {
+                "Definition": {"type": "string", "required": true},
+                "Page": {"type": "row_reference", "target_form": "WikiPage", "required": true}
+            }
+        }),
+    ]
+}
+
+fn crm_forms() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "Account",
+            "version": 1,
+            "allow_extra_attributes": "deny",
+            "fields": {
+                "Industry": {"type": "string", "required": true},
+                "Region": {"type": "string", "required": true},
+                "Tier": {"type": "string", "required": true},
+                "AnnualRevenueUSD": {"type": "number", "required": true}
+            }
+        }),
+        json!({
+            "name": "Contact",
+            "version": 1,
+            "allow_extra_attributes": "deny",
+            "fields": {
+                "Account": {"type": "row_reference", "target_form": "Account", "required": true},
+                "Role": {"type": "string", "required": true},
+                "Email": {"type": "string", "required": true}
+            }
+        }),
+        json!({
+            "name": "Deal",
+            "version": 1,
+            "allow_extra_attributes": "deny",
+            "fields": {
+                "Account": {"type": "row_reference", "target_form": "Account", "required": true},
+                "Contact": {"type": "row_reference", "target_form": "Contact", "required": false},
+                "Stage": {"type": "string", "required": true},
+                "AmountUSD": {"type": "number", "required": true},
+                "CloseDate": {"type": "date", "required": true},
+                "Proposal": {"type": "string", "required": false}
+            }
+        }),
+        json!({
+            "name": "Activity",
+            "version": 1,
+            "allow_extra_attributes": "deny",
+            "fields": {
+                "Deal": {"type": "row_reference", "target_form": "Deal", "required": true},
+                "Kind": {"type": "string", "required": true},
+                "OccurredOn": {"type": "date", "required": true},
+                "Notes": {"type": "markdown", "required": false}
+            }
+        }),
+    ]
+}
+
 fn entry_title(form_name: &str, label: &str) -> String {
     format!("{} {}", form_name, label)
 }
@@ -575,6 +766,18 @@ fn validate_job_id(job_id: &str) -> Result<()> {
     Ok(())
 }
 
+fn cancel_marker_path(job_id: &str) -> String {
+    format!("{}/{}.cancel", SAMPLE_JOBS_DIR, job_id)
+}
+
+fn percent_complete(processed: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    let percent = (processed.min(total) as f64 / total as f64) * 100.0;
+    (percent * 10.0).floor() / 10.0
+}
+
 fn job_path(job_id: &str) -> String {
     format!("{}/{}.json", SAMPLE_JOBS_DIR, job_id)
 }
@@ -588,8 +791,7 @@ async fn ensure_jobs_dir(op: &Operator) -> Result<()> {
 }
 
 async fn write_job(op: &Operator, job: &SampleDataJob) -> Result<()> {
-    op.write(&job_path(&job.job_id), serde_json::to_vec_pretty(job)?)
-        .await?;
+    storage::publish_atomic(op, &job_path(&job.job_id), serde_json::to_vec_pretty(job)?).await?;
     Ok(())
 }
 
@@ -633,16 +835,27 @@ impl JobProgressWriter {
     }
 
     async fn maybe_update(&mut self, processed: usize, message: &str) -> Result<()> {
+        if self
+            .op
+            .exists(&cancel_marker_path(&self.job.job_id))
+            .await?
+        {
+            return Err(SampleJobCancelled.into());
+        }
+
         let threshold = 50usize;
         let message_changed = self.job.status_message.as_deref() != Some(message);
+        let percent = percent_complete(processed, self.job.total_entries);
         if processed < self.job.total_entries
             && processed.saturating_sub(self.last_flushed) < threshold
+            && percent.floor() <= self.job.percent_complete.floor()
             && !message_changed
         {
             return Ok(());
         }
 
         self.job.processed_entries = processed;
+        self.job.percent_complete = percent;
         self.job.status = SampleJobStatus::Running;
         self.job.status_message = Some(message.to_string());
         self.last_flushed = processed;
@@ -654,6 +867,7 @@ impl JobProgressWriter {
         self.job.status = SampleJobStatus::Completed;
         self.job.status_message = Some("Completed".to_string());
         self.job.processed_entries = self.job.total_entries;
+        self.job.percent_complete = 100.0;
         self.job.completed_at = Some(Utc::now());
         self.job.summary = Some(summary.clone());
         self.job.error = None;
@@ -669,6 +883,18 @@ impl JobProgressWriter {
         write_job(&self.op, &self.job).await?;
         Ok(())
     }
+
+    async fn cancel(&mut self) -> Result<()> {
+        self.job.status = SampleJobStatus::Cancelled;
+        self.job.status_message = Some("Cancelled".to_string());
+        self.job.cancel_requested = true;
+        self.job.completed_at = Some(Utc::now());
+        write_job(&self.op, &self.job).await?;
+        self.op
+            .delete(&cancel_marker_path(&self.job.job_id))
+            .await?;
+        Ok(())
+    }
 }
 
 struct TerminalProgressWriter {
@@ -697,16 +923,12 @@ impl TerminalProgressWriter {
     fn render_line(&self, processed: usize, message: &str) -> String {
         let width = 20usize;
         let capped = processed.min(self.total_entries);
-        let percent = if self.total_entries == 0 {
-            100usize
-        } else {
-            ((capped * 100) / self.total_entries).min(100)
-        };
-        let filled = if self.total_entries == 0 {
-            width
-        } else {
-            (((capped * width) + (self.total_entries / 2)) / self.total_entries).min(width)
-        };
+        let percent = (capped * 100)
+            .checked_div(self.total_entries)
+            .map_or(100, |percent| percent.min(100));
+        let filled = ((capped * width) + (self.total_entries / 2))
+            .checked_div(self.total_entries)
+            .map_or(width, |filled| filled.min(width));
         let bar = format!("{}{}", "#".repeat(filled), "-".repeat(width - filled));
         format!(
             "Seed progress [{}] {:>3}% ({}/{}) {}",
@@ -796,6 +1018,13 @@ impl ProgressReporter {
         }
         Ok(())
     }
+
+    async fn cancel(&mut self) -> Result<()> {
+        if let ProgressReporter::Job(writer) = self {
+            writer.cancel().await?;
+        }
+        Ok(())
+    }
 }
 
 struct ScenarioContext<'a> {
@@ -816,6 +1045,9 @@ fn scenario_forms(scenario: &str) -> Option<Vec<Value>> {
         "fleet-ops" => Some(fleet_ops_forms()),
         "lab-qa" => Some(lab_qa_forms()),
         "retail-ops" => Some(retail_ops_forms()),
+        "project-tracker" => Some(project_tracker_forms()),
+        "team-wiki" => Some(team_wiki_forms()),
+        "crm" => Some(crm_forms()),
         _ => None,
     }
 }
@@ -2076,6 +2308,533 @@ async fn generate_retail_ops(
     Ok(())
 }
 
+struct SampleEntryWriter<'a> {
+    op: &'a Operator,
+    ws_path: &'a str,
+    forms_map: &'a std::collections::HashMap<String, Value>,
+    integrity: RealIntegrityProvider,
+    processed: usize,
+}
+
+impl<'a> SampleEntryWriter<'a> {
+    async fn new(
+        op: &'a Operator,
+        ws_path: &'a str,
+        space_id: &str,
+        forms_map: &'a std::collections::HashMap<String, Value>,
+    ) -> Result<Self> {
+        Ok(Self {
+            op,
+            ws_path,
+            forms_map,
+            integrity: RealIntegrityProvider::from_space(op, space_id).await?,
+            processed: 0,
+        })
+    }
+
+    async fn create(
+        &mut self,
+        form_name: &str,
+        entry_id: &str,
+        label: &str,
+        fields: &Value,
+        progress: &mut ProgressReporter,
+        message: &str,
+    ) -> Result<()> {
+        let form_def = self
+            .forms_map
+            .get(form_name)
+            .ok_or_else(|| anyhow!("Missing {} form definition", form_name))?;
+        let markdown = entry::render_markdown_for_form(
+            &entry_title(form_name, label),
+            form_name,
+            &[],
+            fields,
+            &Value::Object(Map::new()),
+            form_def,
+        );
+        entry::create_entry(
+            self.op,
+            self.ws_path,
+            entry_id,
+            &markdown,
+            "sample-generator",
+            &self.integrity,
+        )
+        .await?;
+        self.processed += 1;
+        progress.report(self.processed, message).await
+    }
+}
+
+async fn generate_project_tracker(
+    op: &Operator,
+    ws_path: &str,
+    space_id: &str,
+    entry_count: usize,
+    rng: &mut StdRng,
+    forms_map: &std::collections::HashMap<String, Value>,
+    progress: &mut ProgressReporter,
+) -> Result<()> {
+    let weights = [0.04, 0.12, 0.64, 0.2];
+    let counts = allocate_counts(entry_count, &weights);
+    let base_date = NaiveDate::from_ymd_opt(2024, 9, 2)
+        .ok_or_else(|| anyhow!("Failed to build base date for sample data"))?;
+    let codenames = ["Aurora", "Beacon", "Cobalt", "Delta", "Ember", "Fjord"];
+    let people = ["Aiko", "Bram", "Chen", "Dana", "Emre", "Farah", "Goro"];
+    let project_statuses = ["Planning", "Active", "Active", "On Hold"];
+    let milestone_statuses = ["Planned", "In Progress", "Done"];
+    let task_statuses = ["Todo", "In Progress", "In Review", "Done", "Done"];
+    let verbs = [
+        "Design",
+        "Implement",
+        "Review",
+        "Migrate",
+        "Document",
+        "Test",
+    ];
+    let subjects = [
+        "API",
+        "onboarding flow",
+        "billing export",
+        "search index",
+        "audit log",
+    ];
+    let health = ["Green", "Green", "Amber", "Red"];
+
+    let mut writer = SampleEntryWriter::new(op, ws_path, space_id, forms_map).await?;
+
+    let project_ids: Vec<String> = (1..=counts[0])
+        .map(|idx| format!("project-{:03}", idx))
+        .collect();
+    for (idx, project_id) in project_ids.iter().enumerate() {
+        let start_offset = rng.random_range(0..120) as i64;
+        let fields = json!({
+            "Owner": pick(rng, &people),
+            "Status": pick(rng, &project_statuses),
+            "StartDate": date_from_offset(base_date, start_offset),
+            "DueDate": date_from_offset(base_date, start_offset + rng.random_range(60..240) as i64),
+            "Summary": "Cross-team initiative tracked with milestones and tasks."
+        });
+        let label = format!("{} {}", codenames[idx % codenames.len()], idx + 1);
+        writer
+            .create(
+                "Project",
+                project_id,
+                &label,
+                &fields,
+                progress,
+                "Generating Projects",
+            )
+            .await?;
+    }
+
+    let mut milestones: Vec<(String, String)> = Vec::new();
+    for idx in 0..counts[1] {
+        let project_id = project_ids[idx % project_ids.len()].clone();
+        let milestone_id = format!("milestone-{:04}", idx + 1);
+        let fields = json!({
+            "Project": project_id,
+            "DueDate": date_from_offset(base_date, rng.random_range(30..360) as i64),
+            "Status": pick(rng, &milestone_statuses)
+        });
+        writer
+            .create(
+                "Milestone",
+                &milestone_id,
+                &format!("M{}", idx + 1),
+                &fields,
+                progress,
+                "Generating Milestones",
+            )
+            .await?;
+        milestones.push((milestone_id, project_id));
+    }
+
+    let mut task_ids: Vec<String> = Vec::new();
+    for idx in 0..counts[2] {
+        let project_id = &project_ids[rng.random_range(0..project_ids.len())];
+        let milestone = milestones
+            .iter()
+            .filter(|(_, owner)| owner == project_id)
+            .nth(rng.random_range(0..3));
+        let task_id = format!("task-{:05}", idx + 1);
+        let mut fields = json!({
+            "Project": project_id,
+            "Assignee": pick(rng, &people),
+            "Status": pick(rng, &task_statuses),
+            "EstimateHours": rng.random_range(1..40),
+            "Description": format!(
+                "{} the {}.",
+                pick(rng, &verbs),
+                pick(rng, &subjects)
+            )
+        });
+        if let Some((milestone_id, _)) = milestone {
+            fields["Milestone"] = json!(milestone_id);
+        }
+        writer
+            .create(
+                "Task",
+                &task_id,
+                &format!("{:05}", idx + 1),
+                &fields,
+                progress,
+                "Generating Tasks",
+            )
+            .await?;
+        if !task_ids.is_empty() && rng.random_bool(0.25) {
+            let blocker = &task_ids[rng.random_range(0..task_ids.len())];
+            link::create_link(
+                op,
+                ws_path,
+                blocker,
+                &task_id,
+                "blocks",
+                &format!("link-{blocker}-{task_id}"),
+            )
+            .await?;
+        }
+        task_ids.push(task_id);
+    }
+
+    for idx in 0..counts[3] {
+        let fields = json!({
+            "Project": pick_string(rng, &project_ids),
+            "ReportedOn": date_from_offset(base_date, rng.random_range(7..360) as i64),
+            "Health": pick(rng, &health),
+            "Notes": "Weekly status shared with stakeholders."
+        });
+        writer
+            .create(
+                "StatusUpdate",
+                &format!("update-{:05}", idx + 1),
+                &format!("{:05}", idx + 1),
+                &fields,
+                progress,
+                "Generating Status updates",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+async fn generate_team_wiki(
+    op: &Operator,
+    ws_path: &str,
+    space_id: &str,
+    entry_count: usize,
+    rng: &mut StdRng,
+    forms_map: &std::collections::HashMap<String, Value>,
+    progress: &mut ProgressReporter,
+) -> Result<()> {
+    let weights = [0.7, 0.3];
+    let counts = allocate_counts(entry_count, &weights);
+    let base_date = NaiveDate::from_ymd_opt(2024, 1, 8)
+        .ok_or_else(|| anyhow!("Failed to build base date for sample data"))?;
+    let categories = [
+        "Engineering",
+        "Onboarding",
+        "Operations",
+        "Security",
+        "Product",
+    ];
+    let topics = [
+        "Release process",
+        "On-call handbook",
+        "Code review",
+        "Incident response",
+        "Access requests",
+        "Architecture overview",
+        "Style guide",
+        "Data retention",
+    ];
+    let terms = [
+        "SLO",
+        "RPO",
+        "Runbook",
+        "Canary",
+        "Feature flag",
+        "Postmortem",
+    ];
+
+    let mut writer = SampleEntryWriter::new(op, ws_path, space_id, forms_map).await?;
+
+    let mut page_ids: Vec<String> = Vec::new();
+    for idx in 0..counts[0] {
+        let page_id = format!("page-{:04}", idx + 1);
+        let topic = topics[idx % topics.len()];
+        // The first page is a hub every other page links back to; the rest
+        // also cite a couple of earlier pages so backlinks stay dense.
+        let mut targets: Vec<String> = page_ids.first().cloned().into_iter().collect();
+        for _ in 0..rng.random_range(1..=2) {
+            if page_ids.len() > 1 {
+                let target = pick_string(rng, &page_ids[1..]).to_string();
+                if !targets.contains(&target) {
+                    targets.push(target);
+                }
+            }
+        }
+        let mut body = format!("Notes on {}.", topic.to_lowercase());
+        if !targets.is_empty() {
+            body.push_str("\n\nSee also:\n");
+            for target in &targets {
+                body.push_str(&format!("- [{target}](ugoite://entry/{target})\n"));
+            }
+        }
+        let fields = json!({
+            "Category": pick(rng, &categories),
+            "Summary": format!("{} for the team.", topic),
+            "UpdatedOn": date_from_offset(base_date, rng.random_range(0..540) as i64),
+            "Body": body
+        });
+        writer
+            .create(
+                "WikiPage",
+                &page_id,
+                &format!("{} {}", topic, idx + 1),
+                &fields,
+                progress,
+                "Generating Wiki pages",
+            )
+            .await?;
+        for target in &targets {
+            link::create_link(
+                op,
+                ws_path,
+                &page_id,
+                target,
+                "references",
+                &format!("link-{page_id}-{target}"),
+            )
+            .await?;
+        }
+        page_ids.push(page_id);
+    }
+
+    for idx in 0..counts[1] {
+        let term = terms[idx % terms.len()];
+        let fields = json!({
+            "Definition": format!("{} as used across the wiki.", term),
+            "Page": pick_string(rng, &page_ids)
+        });
+        writer
+            .create(
+                "GlossaryTerm",
+                &format!("term-{:04}", idx + 1),
+                &format!("{} {}", term, idx + 1),
+                &fields,
+                progress,
+                "Generating Glossary terms",
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+const CRM_PROPOSAL_ASSETS: usize = 3;
+
+const CRM_DASHBOARDS: &[(&str, &str, &str)] = &[
+    (
+        "dashboard-pipeline",
+        "Pipeline in negotiation",
+        "SELECT * FROM Deal WHERE Stage = 'Negotiation' ORDER BY CloseDate",
+    ),
+    (
+        "dashboard-won-deals",
+        "Closed won deals",
+        "SELECT * FROM Deal WHERE Stage = 'Closed Won' ORDER BY CloseDate DESC",
+    ),
+    (
+        "dashboard-meetings",
+        "Customer meetings",
+        "SELECT * FROM Activity WHERE Kind = 'Meeting' ORDER BY OccurredOn DESC",
+    ),
+];
+
+async fn generate_crm(
+    op: &Operator,
+    ws_path: &str,
+    space_id: &str,
+    entry_count: usize,
+    rng: &mut StdRng,
+    forms_map: &std::collections::HashMap<String, Value>,
+    progress: &mut ProgressReporter,
+) -> Result<()> {
+    let weights = [0.1, 0.2, 0.25, 0.45];
+    let counts = allocate_counts(entry_count, &weights);
+    let base_date = NaiveDate::from_ymd_opt(2024, 4, 1)
+        .ok_or_else(|| anyhow!("Failed to build base date for sample data"))?;
+    let companies = [
+        "Northwind",
+        "Contoso",
+        "Globex",
+        "Initech",
+        "Umbrella",
+        "Hooli",
+    ];
+    let industries = [
+        "Manufacturing",
+        "Healthcare",
+        "Retail",
+        "Logistics",
+        "Finance",
+    ];
+    let regions = ["APAC", "EMEA", "NA", "LATAM"];
+    let tiers = ["Strategic", "Growth", "SMB"];
+    let roles = ["Champion", "Economic buyer", "Procurement", "Engineer"];
+    let names = ["alex", "kim", "sam", "yuki", "noor", "lee", "ivan"];
+    let stages = [
+        "Prospecting",
+        "Qualified",
+        "Negotiation",
+        "Closed Won",
+        "Closed Lost",
+    ];
+    let kinds = ["Call", "Email", "Meeting", "Demo"];
+
+    let mut writer = SampleEntryWriter::new(op, ws_path, space_id, forms_map).await?;
+
+    let account_ids: Vec<String> = (1..=counts[0])
+        .map(|idx| format!("account-{:03}", idx))
+        .collect();
+    for (idx, account_id) in account_ids.iter().enumerate() {
+        let revenue: f64 = rng.random_range(0.5..250.0);
+        let fields = json!({
+            "Industry": pick(rng, &industries),
+            "Region": pick(rng, &regions),
+            "Tier": pick(rng, &tiers),
+            "AnnualRevenueUSD": (revenue * 10.0).round() * 100_000.0
+        });
+        let label = format!("{} {}", companies[idx % companies.len()], idx + 1);
+        writer
+            .create(
+                "Account",
+                account_id,
+                &label,
+                &fields,
+                progress,
+                "Generating Accounts",
+            )
+            .await?;
+    }
+
+    let mut contacts: Vec<(String, String)> = Vec::new();
+    for idx in 0..counts[1] {
+        let account_id = account_ids[idx % account_ids.len()].clone();
+        let contact_id = format!("contact-{:04}", idx + 1);
+        let name = pick(rng, &names);
+        let fields = json!({
+            "Account": account_id,
+            "Role": pick(rng, &roles),
+            "Email": format!("{}.{}@{}.example", name, idx + 1, account_id)
+        });
+        writer
+            .create(
+                "Contact",
+                &contact_id,
+                &format!("{} {}", name, idx + 1),
+                &fields,
+                progress,
+                "Generating Contacts",
+            )
+            .await?;
+        contacts.push((contact_id, account_id));
+    }
+
+    let mut proposals = Vec::new();
+    for idx in 0..CRM_PROPOSAL_ASSETS.min(counts[2]) {
+        let content = format!(
+            "Proposal template {}\n\nScope, pricing, and timeline for the engagement.\n",
+            idx + 1
+        );
+        let info = asset::save_asset(
+            op,
+            ws_path,
+            &format!("proposal-{}.txt", idx + 1),
+            content.as_bytes(),
+        )
+        .await?;
+        proposals.push(info.link);
+    }
+
+    let mut deal_ids: Vec<String> = Vec::new();
+    for idx in 0..counts[2] {
+        let account_id = &account_ids[rng.random_range(0..account_ids.len())];
+        let deal_id = format!("deal-{:04}", idx + 1);
+        let amount: f64 = rng.random_range(5.0..250.0);
+        let mut fields = json!({
+            "Account": account_id,
+            "Stage": pick(rng, &stages),
+            "AmountUSD": (amount * 10.0).round() * 100.0,
+            "CloseDate": date_from_offset(base_date, rng.random_range(0..400) as i64)
+        });
+        if let Some((contact_id, _)) = contacts.iter().find(|(_, owner)| owner == account_id) {
+            fields["Contact"] = json!(contact_id);
+        }
+        if let Some(proposal) = proposals.get(idx % proposals.len().max(1)) {
+            if rng.random_bool(0.5) {
+                fields["Proposal"] = json!(proposal);
+            }
+        }
+        writer
+            .create(
+                "Deal",
+                &deal_id,
+                &format!("{:04}", idx + 1),
+                &fields,
+                progress,
+                "Generating Deals",
+            )
+            .await?;
+        deal_ids.push(deal_id);
+    }
+
+    for idx in 0..counts[3] {
+        let fields = json!({
+            "Deal": pick_string(rng, &deal_ids),
+            "Kind": pick(rng, &kinds),
+            "OccurredOn": date_from_offset(base_date, rng.random_range(0..400) as i64),
+            "Notes": "Logged from the account timeline."
+        });
+        writer
+            .create(
+                "Activity",
+                &format!("activity-{:05}", idx + 1),
+                &format!("{:05}", idx + 1),
+                &fields,
+                progress,
+                "Generating Activities",
+            )
+            .await?;
+    }
+
+    progress
+        .report(writer.processed, "Saving dashboards")
+        .await?;
+    for (sql_id, name, sql) in CRM_DASHBOARDS {
+        saved_sql::create_sql(
+            op,
+            ws_path,
+            sql_id,
+            &saved_sql::SqlPayload {
+                name: name.to_string(),
+                sql: sql.to_string(),
+                variables: json!([]),
+                folder: None,
+                tags: None,
+            },
+            "sample-generator",
+            &writer.integrity,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
 async fn generate_entries_for_scenario(
     scenario: &str,
     context: &mut ScenarioContext<'_>,
@@ -2153,6 +2912,42 @@ async fn generate_entries_for_scenario(
             )
             .await
         }
+        "project-tracker" => {
+            generate_project_tracker(
+                context.op,
+                context.ws_path,
+                context.space_id,
+                context.entry_count,
+                context.rng,
+                context.forms_map,
+                context.progress,
+            )
+            .await
+        }
+        "team-wiki" => {
+            generate_team_wiki(
+                context.op,
+                context.ws_path,
+                context.space_id,
+                context.entry_count,
+                context.rng,
+                context.forms_map,
+                context.progress,
+            )
+            .await
+        }
+        "crm" => {
+            generate_crm(
+                context.op,
+                context.ws_path,
+                context.space_id,
+                context.entry_count,
+                context.rng,
+                context.forms_map,
+                context.progress,
+            )
+            .await
+        }
         _ => Err(anyhow!("Unknown sample data scenario: {}", scenario)),
     }
 }
@@ -2199,7 +2994,7 @@ async fn bootstrap_sample_space_owner(
     settings_obj.insert("membership_version".to_string(), json!(current_version + 1));
 
     let data = serde_json::to_vec_pretty(&settings)?;
-    op.write(&settings_path, data).await?;
+    storage::publish_atomic(op, &settings_path, data).await?;
     Ok(())
 }
 
@@ -2209,10 +3004,53 @@ async fn create_sample_space_with_progress(
     options: &SampleDataOptions,
     plan: &ResolvedSampleDataPlan,
     progress: &mut ProgressReporter,
+) -> Result<SampleDataSummary> {
+    match options.seed.filter(|_| options.fixture) {
+        Some(seed) => {
+            clock::frozen(
+                seed,
+                build_sample_space(op, root_uri, options, plan, progress),
+            )
+            .await
+        }
+        None => build_sample_space(op, root_uri, options, plan, progress).await,
+    }
+}
+
+async fn build_sample_space(
+    op: &Operator,
+    root_uri: &str,
+    options: &SampleDataOptions,
+    plan: &ResolvedSampleDataPlan,
+    progress: &mut ProgressReporter,
 ) -> Result<SampleDataSummary> {
     progress.report(0, "Creating space").await?;
     space::create_space(op, &options.space_id, root_uri).await?;
 
+    let result = populate_sample_space(op, options, plan, progress).await;
+    if result
+        .as_ref()
+        .is_err_and(|err| err.is::<SampleJobCancelled>())
+    {
+        remove_partial_space(op, &options.space_id).await?;
+    }
+    result
+}
+
+/// Drop everything a cancelled job wrote under its space.
+async fn remove_partial_space(op: &Operator, space_id: &str) -> Result<()> {
+    let ws_path = format!("spaces/{}", space_id);
+    iceberg_store::forget_space_catalog(op, &ws_path)?;
+    op.remove_all(&format!("{}/", ws_path)).await?;
+    Ok(())
+}
+
+async fn populate_sample_space(
+    op: &Operator,
+    options: &SampleDataOptions,
+    plan: &ResolvedSampleDataPlan,
+    progress: &mut ProgressReporter,
+) -> Result<SampleDataSummary> {
     if let Some(owner) = normalize_owner_user_id(options.owner_user_id.as_deref()) {
         bootstrap_sample_space_owner(op, &options.space_id, &owner).await?;
     }
@@ -2316,6 +3154,8 @@ pub async fn create_sample_space_job(
         status_message: Some("Queued".to_string()),
         processed_entries: 0,
         total_entries: plan.entry_count,
+        percent_complete: 0.0,
+        cancel_requested: false,
         started_at: None,
         completed_at: None,
         error: None,
@@ -2331,6 +3171,7 @@ pub async fn create_sample_space_job(
         entry_count: plan.entry_count,
         seed: options.seed,
         owner_user_id: normalize_owner_user_id(options.owner_user_id.as_deref()),
+        fixture: options.fixture,
     };
     let root_uri = root_uri.to_string();
     let plan_clone = plan.clone();
@@ -2354,6 +3195,9 @@ pub async fn create_sample_space_job(
             Ok(summary) => {
                 let _ = progress.complete(&summary).await;
             }
+            Err(err) if err.is::<SampleJobCancelled>() => {
+                let _ = progress.cancel().await;
+            }
             Err(err) => {
                 let _ = progress.fail(&err.to_string()).await;
             }
@@ -2370,5 +3214,339 @@ pub async fn get_sample_space_job(op: &Operator, job_id: &str) -> Result<SampleD
     if !op.exists(&path).await? {
         return Err(anyhow!("Sample data job not found: {}", job_id));
     }
-    read_job(op, job_id).await
+    let mut job = read_job(op, job_id).await?;
+    if !job.status.is_finished() && op.exists(&cancel_marker_path(job_id)).await? {
+        job.cancel_requested = true;
+    }
+    Ok(job)
+}
+
+/// Ask a queued or running job to stop. Generation halts at its next progress
+/// report, removes the partially created space and ends as `cancelled`.
+pub async fn cancel_sample_space_job(op: &Operator, job_id: &str) -> Result<SampleDataJob> {
+    let mut job = get_sample_space_job(op, job_id).await?;
+    match job.status {
+        SampleJobStatus::Cancelled => return Ok(job),
+        SampleJobStatus::Completed | SampleJobStatus::Failed => {
+            return Err(anyhow!("Sample data job already finished: {}", job_id));
+        }
+        SampleJobStatus::Queued | SampleJobStatus::Running => {}
+    }
+    op.write(&cancel_marker_path(job_id), Vec::<u8>::new())
+        .await?;
+    job.cancel_requested = true;
+    Ok(job)
+}
+
+const ANONYMIZED_WORDS: &[&str] = &[
+    "amber", "birch", "cobalt", "dune", "ember", "fern", "granite", "harbor", "iris", "juniper",
+    "kestrel", "lagoon", "meadow", "nectar", "orchid", "pebble", "quartz", "ridge", "sable",
+    "tundra", "umber", "vale", "willow", "yarrow", "zephyr",
+];
+
+/// What [`anonymize_space`] replaces and what it copies verbatim.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AnonymizeRules {
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Fields copied as-is, given as `Form.Field` or a bare `Field` for every form.
+    #[serde(default)]
+    pub keep_fields: Vec<String>,
+    /// Forms left out of the clone, along with their entries and links.
+    #[serde(default)]
+    pub skip_forms: Vec<String>,
+}
+
+impl AnonymizeRules {
+    fn keeps(&self, form_name: &str, field_name: &str) -> bool {
+        self.keep_fields
+            .iter()
+            .any(|rule| rule == field_name || *rule == format!("{form_name}.{field_name}"))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AnonymizeSummary {
+    pub source_space_id: String,
+    pub space_id: String,
+    pub forms: Vec<String>,
+    pub entry_count: usize,
+    pub link_count: usize,
+}
+
+fn synthetic_words(rng: &mut StdRng, count: usize) -> String {
+    (0..count.clamp(1, 200))
+        .map(|_| pick(rng, ANONYMIZED_WORDS))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn source_word_count(value: &Value) -> usize {
+    value
+        .as_str()
+        .map(|text| text.split_whitespace().count())
+        .unwrap_or(3)
+}
+
+/// A random number within half an order of magnitude of `source`.
+fn synthetic_number(rng: &mut StdRng, source: &Value) -> f64 {
+    let base = source.as_f64().map(f64::abs).unwrap_or(100.0).max(1.0);
+    rng.random_range(base * 0.5..=base * 1.5)
+}
+
+/// Replace a field value with synthetic data of the same type and similar size.
+/// Dates and timestamps move by `day_shift` so their ordering survives.
+fn synthetic_field_value(
+    rng: &mut StdRng,
+    field_type: &str,
+    source: &Value,
+    day_shift: i64,
+    id_map: &std::collections::HashMap<String, String>,
+) -> Option<Value> {
+    let text = source.as_str().unwrap_or_default();
+    let value = match field_type {
+        "string" | "sql" => json!(synthetic_words(rng, source_word_count(source))),
+        "markdown" => json!(format!(
+            "{}.",
+            synthetic_words(rng, source_word_count(source))
+        )),
+        "number" | "double" | "float" => {
+            json!((synthetic_number(rng, source) * 100.0).round() / 100.0)
+        }
+        "integer" | "long" | "duration" => json!(synthetic_number(rng, source).round() as i64),
+        "decimal" => {
+            // Keep the source's scale so the value still fits the column.
+            let scale = text
+                .split_once('.')
+                .map_or(0, |(_, fraction)| fraction.len());
+            let base = json!(text.parse::<f64>().unwrap_or(100.0));
+            json!(format!("{:.*}", scale, synthetic_number(rng, &base)))
+        }
+        "geopoint" => {
+            // Jitter by up to ~10 km so points stay in the same area.
+            let point = GeoPoint::from_value(source)?;
+            let lat = (point.lat + rng.random_range(-0.1..=0.1)).clamp(-90.0, 90.0);
+            let lon = (point.lon + rng.random_range(-0.1..=0.1)).clamp(-180.0, 180.0);
+            GeoPoint::new(lat, lon)?.to_value()
+        }
+        "boolean" => json!(rng.random_bool(0.5)),
+        "date" => {
+            let date = NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?;
+            json!(date_from_offset(date, day_shift))
+        }
+        "timestamp" | "timestamp_tz" | "timestamp_ns" | "timestamp_tz_ns" => {
+            let ts = DateTime::parse_from_rfc3339(text).ok()?;
+            json!((ts.with_timezone(&Utc) + Duration::days(day_shift)).to_rfc3339())
+        }
+        "datetime" => {
+            let ts = DateTime::parse_from_rfc3339(text).ok()?;
+            json!((ts + Duration::days(day_shift)).to_rfc3339())
+        }
+        "time" => json!(format!(
+            "{:02}:{:02}:00",
+            rng.random_range(0..24),
+            rng.random_range(0..60)
+        )),
+        "uuid" => json!(uuid::Builder::from_random_bytes(rng.random())
+            .into_uuid()
+            .to_string()),
+        "binary" => json!(format!("hex:{}", hex::encode(rng.random::<[u8; 8]>()))),
+        "row_reference" => json!(id_map.get(text)?),
+        // Ticket numbers carry no content; keeping them keeps references intact.
+        "sequence" => source.clone(),
+        "list" => {
+            let len = source.as_array().map(Vec::len).unwrap_or(1);
+            json!((0..len)
+                .map(|_| synthetic_words(rng, 1))
+                .collect::<Vec<_>>())
+        }
+        "object_list" => {
+            let items = source.as_array()?;
+            json!(items
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| json!({
+                    "type": item.get("type").cloned().unwrap_or_else(|| json!("string")),
+                    "name": format!("var{}", idx + 1),
+                    "description": synthetic_words(rng, 3),
+                }))
+                .collect::<Vec<_>>())
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Order forms so every row_reference target is installed before its referrers.
+fn forms_in_reference_order(mut pending: Vec<Value>) -> Vec<Value> {
+    let mut ordered: Vec<Value> = Vec::new();
+    while !pending.is_empty() {
+        let names: Vec<String> = pending
+            .iter()
+            .filter_map(|def| def.get("name").and_then(Value::as_str).map(str::to_string))
+            .collect();
+        let ready = pending.iter().position(|def| {
+            let own = def.get("name").and_then(Value::as_str).unwrap_or_default();
+            def.get("fields")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .filter_map(|(_, field)| field.get("target_form").and_then(Value::as_str))
+                .all(|target| target == own || !names.iter().any(|name| name == target))
+        });
+        // A reference cycle cannot be ordered; install the rest as listed.
+        ordered.push(pending.remove(ready.unwrap_or(0)));
+    }
+    ordered
+}
+
+/// Clone `source_space_id` into a new `space_id` keeping its forms, entry
+/// counts, row references and link topology while replacing entry ids, titles,
+/// tags and field values with synthetic data, so a reproduction case can be
+/// shared without its content.
+pub async fn anonymize_space(
+    op: &Operator,
+    root_uri: &str,
+    source_space_id: &str,
+    space_id: &str,
+    rules: &AnonymizeRules,
+) -> Result<AnonymizeSummary> {
+    if !space::space_exists(op, source_space_id).await? {
+        return Err(anyhow!("Space not found: {}", source_space_id));
+    }
+    let src_path = format!("spaces/{}", source_space_id);
+    let dst_path = format!("spaces/{}", space_id);
+    let skipped = |form_name: &str| {
+        metadata::is_reserved_metadata_form(form_name)
+            || rules.skip_forms.iter().any(|skip| skip == form_name)
+    };
+
+    let mut form_defs: Vec<Value> = form::list_forms(op, &src_path)
+        .await?
+        .into_iter()
+        .filter(|def| !skipped(def.get("name").and_then(Value::as_str).unwrap_or_default()))
+        .collect();
+    form_defs.sort_by_key(|def| def.get("name").and_then(Value::as_str).map(str::to_string));
+    let form_defs = forms_in_reference_order(form_defs);
+
+    let mut rows: Vec<(String, entry::EntryRow)> = entry::list_entry_rows(op, &src_path)
+        .await?
+        .into_iter()
+        .filter(|(form_name, row)| !row.deleted && !skipped(form_name))
+        .collect();
+    rows.sort_by(|(a_form, a), (b_form, b)| {
+        (a_form, a.created_at, &a.entry_id)
+            .partial_cmp(&(b_form, b.created_at, &b.entry_id))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let id_map: std::collections::HashMap<String, String> = rows
+        .iter()
+        .enumerate()
+        .map(|(idx, (_, row))| (row.entry_id.clone(), format!("entry-{:05}", idx + 1)))
+        .collect();
+
+    space::create_space(op, space_id, root_uri).await?;
+    let mut forms = Vec::new();
+    let mut forms_map = std::collections::HashMap::new();
+    for form_def in &form_defs {
+        form::upsert_form(op, &dst_path, form_def).await?;
+        let name = form_def
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        forms_map.insert(
+            name.clone(),
+            form::read_form_definition(op, &dst_path, &name).await?,
+        );
+        forms.push(name);
+    }
+
+    let mut rng = StdRng::seed_from_u64(rules.seed.unwrap_or_else(rand::random::<u64>));
+    let day_shift = rng.random_range(-365..=365);
+    let integrity = RealIntegrityProvider::from_space(op, space_id).await?;
+    let empty_extra = Value::Object(Map::new());
+    let mut tag_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+
+    for (idx, (form_name, row)) in rows.iter().enumerate() {
+        let form_def = forms_map
+            .get(form_name)
+            .ok_or_else(|| anyhow!("Missing {} form definition", form_name))?;
+        let mut fields = Map::new();
+        let field_defs = form_def.get("fields").and_then(Value::as_object);
+        for (field_name, field_def) in field_defs.into_iter().flatten() {
+            let Some(source) = row.fields.get(field_name).filter(|value| !value.is_null()) else {
+                continue;
+            };
+            let value = if rules.keeps(form_name, field_name) {
+                Some(source.clone())
+            } else {
+                let field_type = field_def
+                    .get("type")
+                    .and_then(Value::as_str)
+                    .unwrap_or("string");
+                synthetic_field_value(&mut rng, field_type, source, day_shift, &id_map)
+            };
+            if let Some(value) = value {
+                fields.insert(field_name.clone(), value);
+            }
+        }
+        let tags: Vec<String> = row
+            .tags
+            .iter()
+            .map(|tag| {
+                let next = format!("tag-{}", tag_map.len() + 1);
+                tag_map.entry(tag.clone()).or_insert(next).clone()
+            })
+            .collect();
+        let title = entry_title(form_name, &format!("{:05}", idx + 1));
+        let markdown = entry::render_markdown_for_form(
+            &title,
+            form_name,
+            &tags,
+            &Value::Object(fields),
+            &empty_extra,
+            form_def,
+        );
+        entry::create_entry(
+            op,
+            &dst_path,
+            &id_map[&row.entry_id],
+            &markdown,
+            "anonymizer",
+            &integrity,
+        )
+        .await?;
+    }
+
+    let mut links: Vec<&link::Link> = rows.iter().flat_map(|(_, row)| &row.links).collect();
+    links.sort_by(|a, b| a.id.cmp(&b.id));
+    links.dedup_by(|a, b| a.id == b.id);
+    let mut link_count = 0;
+    for link_record in links {
+        let (Some(source), Some(target)) = (
+            id_map.get(&link_record.source),
+            id_map.get(&link_record.target),
+        ) else {
+            continue;
+        };
+        link_count += 1;
+        link::create_link(
+            op,
+            &dst_path,
+            source,
+            target,
+            &link_record.kind,
+            &format!("link-{:05}", link_count),
+        )
+        .await?;
+    }
+
+    Ok(AnonymizeSummary {
+        source_space_id: source_space_id.to_string(),
+        space_id: space_id.to_string(),
+        forms,
+        entry_count: rows.len(),
+        link_count,
+    })
 }
@@ -1,5 +1,8 @@
+use crate::clock;
 use crate::entry;
+use crate::entry_acl::{self, EntryAcl, EntryPrincipal, EntryViewer, EntryVisibility};
 use crate::form;
+use crate::index;
 use crate::integrity::IntegrityProvider;
 use crate::materialized_view;
 use crate::sql;
@@ -10,7 +13,6 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use std::collections::BTreeSet;
 use std::sync::OnceLock;
-use uuid::Uuid;
 
 const SQL_FORM_NAME: &str = "SQL";
 const SQL_VALIDATION_PREFIX: &str = "UGOITE_SQL_VALIDATION";
@@ -33,6 +35,27 @@ pub struct SqlPayload {
     pub sql: String,
     #[serde(default)]
     pub variables: Value,
+    /// Slash-separated folder path; `None` keeps the current folder on update.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    /// `None` keeps the current tags on update.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+}
+
+/// Narrows [`list_sql_filtered`]; every set criterion must match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SqlListFilter {
+    #[serde(default)]
+    pub folder: Option<String>,
+    /// Also match queries in subfolders of `folder`.
+    #[serde(default)]
+    pub recursive: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Case-insensitive substring of the name or SQL text.
+    #[serde(default)]
+    pub query: Option<String>,
 }
 
 fn sql_form_definition() -> Value {
@@ -87,6 +110,60 @@ fn normalize_sql_variables(value: Option<&Value>) -> Result<Value> {
     Ok(Value::Array(normalized))
 }
 
+/// Folders live in the row's extra attributes so existing SQL tables need no
+/// schema change.
+const FOLDER_ATTRIBUTE: &str = "folder";
+
+fn normalize_folder(folder: &str) -> Result<String> {
+    let trimmed = folder.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        return Ok(String::new());
+    }
+    let mut segments = Vec::new();
+    for segment in trimmed.split('/') {
+        let segment = segment.trim();
+        if segment.is_empty() || segment == "." || segment == ".." {
+            return Err(validation_error(format!("invalid folder path: {folder}")));
+        }
+        segments.push(segment);
+    }
+    Ok(segments.join("/"))
+}
+
+fn normalize_sql_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|existing| existing == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+fn row_folder(row: &entry::EntryRow) -> &str {
+    row.extra_attributes
+        .get(FOLDER_ATTRIBUTE)
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+}
+
+fn set_row_folder(row: &mut entry::EntryRow, folder: &str) {
+    if !row.extra_attributes.is_object() {
+        row.extra_attributes = Value::Object(Map::new());
+    }
+    if let Some(attributes) = row.extra_attributes.as_object_mut() {
+        if folder.is_empty() {
+            attributes.remove(FOLDER_ATTRIBUTE);
+        } else {
+            attributes.insert(
+                FOLDER_ATTRIBUTE.to_string(),
+                Value::String(folder.to_string()),
+            );
+        }
+    }
+}
+
 fn sql_placeholder_regex() -> &'static Regex {
     static SQL_PLACEHOLDER_REGEX: OnceLock<Regex> = OnceLock::new();
     SQL_PLACEHOLDER_REGEX.get_or_init(|| {
@@ -161,6 +238,37 @@ fn validate_sql_payload(sql_text: &str, variables: &Value) -> Result<()> {
     Ok(())
 }
 
+/// Replace `{{name}}` placeholders with SQL literals taken from `params`.
+pub fn bind_sql_params(sql_text: &str, params: &Value) -> Result<String> {
+    let values = match params {
+        Value::Null => Map::new(),
+        Value::Object(map) => map.clone(),
+        _ => return Err(validation_error("params must be an object")),
+    };
+    let mut failure = None;
+    let bound = sql_placeholder_regex().replace_all(sql_text, |caps: &regex::Captures| {
+        let name = &caps[1];
+        match values.get(name) {
+            Some(Value::String(text)) => format!("'{}'", text.replace('\'', "''")),
+            Some(Value::Number(number)) => number.to_string(),
+            Some(Value::Bool(flag)) => flag.to_string(),
+            Some(Value::Null) => "NULL".to_string(),
+            Some(_) => {
+                failure.get_or_insert_with(|| format!("variable {name} must be a scalar"));
+                String::new()
+            }
+            None => {
+                failure.get_or_insert_with(|| format!("missing value for variable {name}"));
+                String::new()
+            }
+        }
+    });
+    match failure {
+        Some(message) => Err(validation_error(message)),
+        None => Ok(bound.into_owned()),
+    }
+}
+
 fn sql_integrity_payload(
     integrity: &dyn IntegrityProvider,
     payload: &SqlPayload,
@@ -194,24 +302,83 @@ fn sql_entry_from_row(row: &entry::EntryRow) -> Result<Value> {
         "name": row.title,
         "sql": sql_value,
         "variables": variables,
+        "folder": row_folder(row),
+        "tags": row.tags,
         "created_at": row.created_at,
         "updated_at": row.updated_at,
         "revision_id": row.revision_id,
     }))
 }
 
-pub async fn list_sql(op: &Operator, ws_path: &str) -> Result<Vec<Value>> {
+async fn list_sql_rows(op: &Operator, ws_path: &str) -> Result<Vec<entry::EntryRow>> {
     ensure_sql_form(op, ws_path).await?;
     let form_def = form::read_form_definition(op, ws_path, SQL_FORM_NAME).await?;
     let rows = entry::list_form_entry_rows(op, ws_path, SQL_FORM_NAME, &form_def).await?;
-    let mut entries = Vec::new();
-    for row in rows {
-        if row.deleted {
-            continue;
+    Ok(rows.into_iter().filter(|row| !row.deleted).collect())
+}
+
+pub async fn list_sql(op: &Operator, ws_path: &str) -> Result<Vec<Value>> {
+    list_sql_filtered(op, ws_path, &SqlListFilter::default()).await
+}
+
+fn matches_filter(row: &entry::EntryRow, filter: &SqlListFilter, folder: Option<&str>) -> bool {
+    let row_folder = row_folder(row);
+    if let Some(folder) = folder {
+        let nested = filter.recursive
+            && (folder.is_empty() || row_folder.starts_with(&format!("{folder}/")));
+        if row_folder != folder && !nested {
+            return false;
         }
-        entries.push(sql_entry_from_row(&row)?);
     }
-    Ok(entries)
+    if !filter.tags.iter().all(|tag| row.tags.contains(tag)) {
+        return false;
+    }
+    match filter.query.as_deref().map(str::trim) {
+        Some(query) if !query.is_empty() => {
+            let query = query.to_lowercase();
+            let sql_text = row
+                .fields
+                .get("sql")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            row.title.to_lowercase().contains(&query) || sql_text.to_lowercase().contains(&query)
+        }
+        _ => true,
+    }
+}
+
+/// Saved queries matching `filter`, sorted by folder then name.
+pub async fn list_sql_filtered(
+    op: &Operator,
+    ws_path: &str,
+    filter: &SqlListFilter,
+) -> Result<Vec<Value>> {
+    let folder = filter.folder.as_deref().map(normalize_folder).transpose()?;
+    let mut rows: Vec<entry::EntryRow> = list_sql_rows(op, ws_path)
+        .await?
+        .into_iter()
+        .filter(|row| matches_filter(row, filter, folder.as_deref()))
+        .collect();
+    rows.sort_by(|a, b| {
+        (row_folder(a), a.title.to_lowercase()).cmp(&(row_folder(b), b.title.to_lowercase()))
+    });
+    rows.iter().map(sql_entry_from_row).collect()
+}
+
+/// Every folder holding a saved query, including the folders above it.
+pub async fn list_sql_folders(op: &Operator, ws_path: &str) -> Result<Vec<String>> {
+    let mut folders = BTreeSet::new();
+    for row in list_sql_rows(op, ws_path).await? {
+        let mut path = String::new();
+        for segment in row_folder(&row).split('/').filter(|s| !s.is_empty()) {
+            if !path.is_empty() {
+                path.push('/');
+            }
+            path.push_str(segment);
+            folders.insert(path.clone());
+        }
+    }
+    Ok(folders.into_iter().collect())
 }
 
 pub async fn get_sql(op: &Operator, ws_path: &str, sql_id: &str) -> Result<Value> {
@@ -262,20 +429,21 @@ pub async fn create_sql<I: IntegrityProvider>(
     let form_def = ensure_sql_form(op, ws_path).await?;
     let variables = normalize_sql_variables(Some(&payload.variables))?;
     validate_sql_payload(&payload.sql, &variables)?;
+    let folder = normalize_folder(payload.folder.as_deref().unwrap_or_default())?;
 
     let timestamp = entry::now_ts();
-    let revision_id = Uuid::new_v4().to_string();
+    let revision_id = clock::new_uuid().to_string();
     let integrity_payload = sql_integrity_payload(integrity, payload, &variables);
 
     let mut fields = Map::new();
     fields.insert("sql".to_string(), Value::String(payload.sql.to_string()));
     fields.insert("variables".to_string(), variables.clone());
 
-    let row = entry::EntryRow {
+    let mut row = entry::EntryRow {
         entry_id: sql_id.to_string(),
         title: payload.name.to_string(),
         form: SQL_FORM_NAME.to_string(),
-        tags: Vec::new(),
+        tags: normalize_sql_tags(payload.tags.as_deref().unwrap_or_default()),
         links: Vec::new(),
         created_at: timestamp,
         updated_at: timestamp,
@@ -289,6 +457,7 @@ pub async fn create_sql<I: IntegrityProvider>(
         deleted_at: None,
         author: author.to_string(),
     };
+    set_row_folder(&mut row, &folder);
 
     entry::write_entry_row(op, ws_path, SQL_FORM_NAME, sql_id, &row).await?;
 
@@ -303,10 +472,23 @@ pub async fn create_sql<I: IntegrityProvider>(
         markdown_checksum: integrity_payload.checksum.clone(),
         integrity: integrity_payload,
         restored_from: None,
+        message: None,
+        revision_metadata: None,
     };
     entry::append_revision_row_for_form(op, ws_path, SQL_FORM_NAME, &revision, &form_def).await?;
 
     materialized_view::create_or_update_view(op, ws_path, sql_id, &payload.sql).await?;
+    entry_acl::set_entry_acl(
+        op,
+        ws_path,
+        sql_id,
+        &EntryAcl {
+            owner: author.to_string(),
+            shared_with: Vec::new(),
+            visibility: EntryVisibility::Space,
+        },
+    )
+    .await?;
 
     sql_entry_from_row(&row)
 }
@@ -339,11 +521,17 @@ pub async fn update_sql<I: IntegrityProvider>(
 
     let variables = normalize_sql_variables(Some(&payload.variables))?;
     validate_sql_payload(&payload.sql, &variables)?;
+    if let Some(folder) = &payload.folder {
+        set_row_folder(&mut row, &normalize_folder(folder)?);
+    }
+    if let Some(tags) = &payload.tags {
+        row.tags = normalize_sql_tags(tags);
+    }
     let mut timestamp = entry::now_ts();
     if timestamp <= row.updated_at {
         timestamp = row.updated_at + 0.001;
     }
-    let revision_id = Uuid::new_v4().to_string();
+    let revision_id = clock::new_uuid().to_string();
     let integrity_payload = sql_integrity_payload(integrity, payload, &variables);
 
     let mut fields = Map::new();
@@ -371,6 +559,8 @@ pub async fn update_sql<I: IntegrityProvider>(
         markdown_checksum: integrity_payload.checksum.clone(),
         integrity: integrity_payload,
         restored_from: None,
+        message: None,
+        revision_metadata: None,
     };
     entry::append_revision_row_for_form(op, ws_path, SQL_FORM_NAME, &revision, &form_def).await?;
 
@@ -397,3 +587,230 @@ pub async fn delete_sql(op: &Operator, ws_path: &str, sql_id: &str) -> Result<()
     materialized_view::delete_view(op, ws_path, sql_id).await?;
     Ok(())
 }
+
+fn payload_from_row(row: &entry::EntryRow) -> SqlPayload {
+    SqlPayload {
+        name: row.title.clone(),
+        sql: row
+            .fields
+            .get("sql")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        variables: row
+            .fields
+            .get("variables")
+            .cloned()
+            .unwrap_or_else(|| Value::Array(Vec::new())),
+        folder: None,
+        tags: None,
+    }
+}
+
+/// Move a saved query into `folder` (empty for the top level) as a new revision.
+pub async fn move_sql<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    sql_id: &str,
+    folder: &str,
+    author: &str,
+    integrity: &I,
+) -> Result<Value> {
+    ensure_sql_form(op, ws_path).await?;
+    let row = entry::read_entry_row(op, ws_path, SQL_FORM_NAME, sql_id).await?;
+    let payload = SqlPayload {
+        folder: Some(folder.to_string()),
+        ..payload_from_row(&row)
+    };
+    update_sql(op, ws_path, sql_id, &payload, None, author, integrity).await
+}
+
+/// Rename a saved query as a new revision.
+pub async fn rename_sql<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    sql_id: &str,
+    name: &str,
+    author: &str,
+    integrity: &I,
+) -> Result<Value> {
+    if name.trim().is_empty() {
+        return Err(validation_error("name must be a non-empty string"));
+    }
+    ensure_sql_form(op, ws_path).await?;
+    let row = entry::read_entry_row(op, ws_path, SQL_FORM_NAME, sql_id).await?;
+    let payload = SqlPayload {
+        name: name.trim().to_string(),
+        ..payload_from_row(&row)
+    };
+    update_sql(op, ws_path, sql_id, &payload, None, author, integrity).await
+}
+
+/// What a caller wants to do with a saved query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlAction {
+    Read,
+    Run,
+    Update,
+}
+
+async fn read_live_row(op: &Operator, ws_path: &str, sql_id: &str) -> Result<entry::EntryRow> {
+    ensure_sql_form(op, ws_path).await?;
+    let row = entry::read_entry_row(op, ws_path, SQL_FORM_NAME, sql_id).await?;
+    if row.deleted {
+        return Err(anyhow!("SQL entry not found: {}", sql_id));
+    }
+    Ok(row)
+}
+
+/// Queries saved before sharing existed have no ACL and stay space-shared,
+/// owned by their last author.
+fn effective_share(acl: Option<EntryAcl>, row: &entry::EntryRow) -> EntryAcl {
+    acl.unwrap_or_else(|| EntryAcl {
+        owner: row.author.clone(),
+        shared_with: Vec::new(),
+        visibility: EntryVisibility::Space,
+    })
+}
+
+fn can_perform(acl: &EntryAcl, viewer: &EntryViewer, action: SqlAction) -> bool {
+    if !entry_acl::can_read(Some(acl), viewer) {
+        return false;
+    }
+    // Principals a query is shared with may read and run it; only space-shared
+    // queries are editable by everyone who can see them.
+    action != SqlAction::Update
+        || viewer.is_space_admin
+        || acl.owner == viewer.user_id
+        || acl.visibility == EntryVisibility::Space
+}
+
+pub async fn get_sql_share(op: &Operator, ws_path: &str, sql_id: &str) -> Result<EntryAcl> {
+    let row = read_live_row(op, ws_path, sql_id).await?;
+    let acl = entry_acl::get_entry_acl(op, ws_path, sql_id).await?;
+    Ok(effective_share(acl, &row))
+}
+
+/// Change who can see a saved query; only its owner or a space admin may do so.
+pub async fn set_sql_share(
+    op: &Operator,
+    ws_path: &str,
+    sql_id: &str,
+    visibility: EntryVisibility,
+    shared_with: Vec<EntryPrincipal>,
+    viewer: &EntryViewer,
+) -> Result<EntryAcl> {
+    let current = get_sql_share(op, ws_path, sql_id).await?;
+    if !entry_acl::can_read(Some(&current), viewer) {
+        return Err(anyhow!("SQL entry not found: {}", sql_id));
+    }
+    if !viewer.is_space_admin && current.owner != viewer.user_id {
+        return Err(anyhow!(
+            "Only the owner of SQL entry {} can change its sharing",
+            sql_id
+        ));
+    }
+    entry_acl::set_entry_acl(
+        op,
+        ws_path,
+        sql_id,
+        &EntryAcl {
+            owner: current.owner,
+            shared_with,
+            visibility,
+        },
+    )
+    .await
+}
+
+/// Check that `viewer` may perform `action` on a saved query. Queries the
+/// viewer cannot read are reported as missing so their existence is not disclosed.
+pub async fn authorize_sql(
+    op: &Operator,
+    ws_path: &str,
+    sql_id: &str,
+    viewer: &EntryViewer,
+    action: SqlAction,
+) -> Result<()> {
+    let share = get_sql_share(op, ws_path, sql_id).await?;
+    if !entry_acl::can_read(Some(&share), viewer) {
+        return Err(anyhow!("SQL entry not found: {}", sql_id));
+    }
+    if !can_perform(&share, viewer, action) {
+        return Err(anyhow!(
+            "SQL entry {} is read-only for {}",
+            sql_id,
+            viewer.user_id
+        ));
+    }
+    Ok(())
+}
+
+/// [`list_sql_filtered`], leaving out queries not shared with `viewer`.
+pub async fn list_sql_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    filter: &SqlListFilter,
+    viewer: &EntryViewer,
+) -> Result<Vec<Value>> {
+    let acls = entry_acl::list_entry_acls(op, ws_path).await?;
+    let mut entries = list_sql_filtered(op, ws_path, filter).await?;
+    entries.retain(|entry| {
+        let acl = entry
+            .get("id")
+            .and_then(Value::as_str)
+            .and_then(|id| acls.get(id));
+        entry_acl::can_read(acl, viewer)
+    });
+    Ok(entries)
+}
+
+pub async fn get_sql_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    sql_id: &str,
+    viewer: &EntryViewer,
+) -> Result<Value> {
+    authorize_sql(op, ws_path, sql_id, viewer, SqlAction::Read).await?;
+    get_sql(op, ws_path, sql_id).await
+}
+
+/// Run a saved query with `params` bound, after checking `viewer` may run it.
+/// Rows are limited to the entries the viewer may read.
+pub async fn run_sql_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    sql_id: &str,
+    params: &Value,
+    viewer: &EntryViewer,
+) -> Result<Vec<Value>> {
+    authorize_sql(op, ws_path, sql_id, viewer, SqlAction::Run).await?;
+    let saved = get_sql(op, ws_path, sql_id).await?;
+    let sql_text = saved.get("sql").and_then(Value::as_str).unwrap_or_default();
+    let bound = bind_sql_params(sql_text, params)?;
+    let query = serde_json::json!({ "$sql": bound }).to_string();
+    index::query_index_for_viewer(op, ws_path, &query, entry::DeletedFilter::Exclude, viewer).await
+}
+
+/// [`update_sql`] authored by `viewer`, after checking they may edit the query.
+pub async fn update_sql_for_viewer<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    sql_id: &str,
+    payload: &SqlPayload,
+    parent_revision_id: Option<&str>,
+    viewer: &EntryViewer,
+    integrity: &I,
+) -> Result<Value> {
+    authorize_sql(op, ws_path, sql_id, viewer, SqlAction::Update).await?;
+    update_sql(
+        op,
+        ws_path,
+        sql_id,
+        payload,
+        parent_revision_id,
+        &viewer.user_id,
+        integrity,
+    )
+    .await
+}
@@ -1,8 +1,9 @@
 use anyhow::Result;
 use opendal::Operator;
-use std::collections::HashSet;
+use std::collections::HashMap;
 
-use crate::entry;
+use crate::entry::{self, DeletedFilter};
+use crate::entry_acl::{self, EntryViewer};
 pub use ugoite_minimum::search::SearchResult;
 
 /// Hybrid keyword search using index and content fallback.
@@ -10,24 +11,76 @@ pub async fn search_entries(
     op: &Operator,
     ws_path: &str,
     query: &str,
+) -> Result<Vec<SearchResult>> {
+    search_entries_with_deleted(op, ws_path, query, DeletedFilter::Exclude).await
+}
+
+/// Keyword search over the entries admitted by `deleted`; matches on
+/// soft-deleted entries are flagged as such.
+pub async fn search_entries_with_deleted(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    deleted: DeletedFilter,
+) -> Result<Vec<SearchResult>> {
+    search_rows(op, ws_path, query, deleted, None).await
+}
+
+/// Keyword search restricted to entries whose entry ACL admits `viewer`.
+pub async fn search_entries_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    viewer: &EntryViewer,
+) -> Result<Vec<SearchResult>> {
+    search_entries_with_deleted_for_viewer(op, ws_path, query, DeletedFilter::Exclude, viewer).await
+}
+
+/// [`search_entries_with_deleted`] restricted to entries whose entry ACL
+/// admits `viewer`.
+pub async fn search_entries_with_deleted_for_viewer(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    deleted: DeletedFilter,
+    viewer: &EntryViewer,
+) -> Result<Vec<SearchResult>> {
+    search_rows(op, ws_path, query, deleted, Some(viewer)).await
+}
+
+async fn search_rows(
+    op: &Operator,
+    ws_path: &str,
+    query: &str,
+    deleted: DeletedFilter,
+    viewer: Option<&EntryViewer>,
 ) -> Result<Vec<SearchResult>> {
     let query = query.to_lowercase();
-    let mut found_ids = HashSet::new();
+    let mut found = HashMap::new();
 
     let rows = entry::list_entry_rows(op, ws_path).await?;
     for (_form_name, row) in rows {
-        if row.deleted {
+        if !deleted.admits(row.deleted) {
             continue;
         }
         let dump = serde_json::to_string(&row)?.to_lowercase();
-        if dump.contains(&query) {
-            found_ids.insert(row.entry_id);
+        if !dump.contains(&query) {
+            continue;
+        }
+        // Each match reads its ACL as it is admitted, and an unreadable ACL
+        // fails the search rather than leaking the entry.
+        if let Some(viewer) = viewer {
+            let acl = entry_acl::get_entry_acl(op, ws_path, &row.entry_id).await?;
+            if !entry_acl::can_read(acl.as_ref(), viewer) {
+                continue;
+            }
         }
+        found.insert(row.entry_id, row.deleted);
     }
 
-    let results = found_ids
+    let results = found
         .into_iter()
-        .map(|id| SearchResult { id })
+        .map(|(id, deleted)| SearchResult { id, deleted })
         .collect();
     Ok(results)
 }
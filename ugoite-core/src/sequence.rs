@@ -0,0 +1,250 @@
+use anyhow::{anyhow, Context, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+use crate::form;
+use crate::space_lock;
+use crate::storage;
+
+const SEQUENCES_DIR: &str = "sequences";
+/// Space lease serializing counter updates across processes.
+const SEQUENCES_LEASE: &str = "sequences";
+/// Widest zero padding a format may ask for.
+pub const MAX_SEQUENCE_PAD: usize = 20;
+
+/// Numbering of a `sequence` field: `{"type": "sequence", "format": "BUG-{n}",
+/// "start": 1}`. The format holds one `{n}` placeholder, optionally padded
+/// as `{n:04}`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SequenceSpec {
+    pub prefix: String,
+    pub suffix: String,
+    /// Minimum digit count, padded with zeros.
+    pub pad: usize,
+    pub start: u64,
+}
+
+impl Default for SequenceSpec {
+    fn default() -> Self {
+        Self {
+            prefix: String::new(),
+            suffix: String::new(),
+            pad: 0,
+            start: 1,
+        }
+    }
+}
+
+impl SequenceSpec {
+    pub fn from_field_def(def: &Value) -> Result<Self> {
+        let start = match def.get("start") {
+            None => 1,
+            Some(value) => value
+                .as_u64()
+                .ok_or_else(|| anyhow!("sequence start must be a non-negative integer"))?,
+        };
+        let format = match def.get("format") {
+            None => "{n}",
+            Some(value) => value
+                .as_str()
+                .ok_or_else(|| anyhow!("sequence format must be a string"))?,
+        };
+        let (prefix, rest) = format
+            .split_once("{n")
+            .ok_or_else(|| anyhow!("sequence format must contain {{n}}"))?;
+        let (spec, suffix) = rest
+            .split_once('}')
+            .ok_or_else(|| anyhow!("sequence format has an unclosed placeholder"))?;
+        if suffix.contains("{n") {
+            return Err(anyhow!("sequence format must contain {{n}} only once"));
+        }
+        let pad = match spec {
+            "" => 0,
+            _ => spec
+                .strip_prefix(":0")
+                .and_then(|width| width.parse::<usize>().ok())
+                .filter(|width| (1..=MAX_SEQUENCE_PAD).contains(width))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "sequence padding must look like {{n:04}} with at most {} digits",
+                        MAX_SEQUENCE_PAD
+                    )
+                })?,
+        };
+        Ok(Self {
+            prefix: prefix.to_string(),
+            suffix: suffix.to_string(),
+            pad,
+            start,
+        })
+    }
+
+    pub fn render(&self, number: u64) -> String {
+        format!(
+            "{}{:0pad$}{}",
+            self.prefix,
+            number,
+            self.suffix,
+            pad = self.pad
+        )
+    }
+
+    /// The number in a value written in this format.
+    pub fn parse(&self, value: &str) -> Option<u64> {
+        let digits = value
+            .trim()
+            .strip_prefix(self.prefix.as_str())?
+            .strip_suffix(self.suffix.as_str())?;
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    }
+}
+
+/// Next number to hand out, per field, for one form.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct SequenceCounters {
+    #[serde(default)]
+    pub next: BTreeMap<String, u64>,
+}
+
+fn counters_path(ws_path: &str, form_name: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, SEQUENCES_DIR, form_name)
+}
+
+/// `sequence` fields of a form with their numbering.
+pub fn sequence_fields(form_def: &Value) -> Result<Vec<(String, SequenceSpec)>> {
+    let fields = form::normalize_form_fields(form_def.get("fields"));
+    let Some(field_map) = fields.as_object() else {
+        return Ok(Vec::new());
+    };
+    field_map
+        .iter()
+        .filter(|(_, def)| def.get("type").and_then(Value::as_str) == Some("sequence"))
+        .map(|(name, def)| {
+            let spec =
+                SequenceSpec::from_field_def(def).with_context(|| format!("Field '{}'", name))?;
+            Ok((name.clone(), spec))
+        })
+        .collect()
+}
+
+pub(crate) fn validate_sequence_field_defs(field_map: &Map<String, Value>) -> Result<()> {
+    for (name, def) in field_map {
+        if def.get("type").and_then(Value::as_str) == Some("sequence") {
+            SequenceSpec::from_field_def(def).with_context(|| format!("Field '{}'", name))?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn get_sequence_counters(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+) -> Result<SequenceCounters> {
+    let path = counters_path(ws_path, form_name);
+    if !op.exists(&path).await? {
+        return Ok(SequenceCounters::default());
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+fn submitted(fields: &Map<String, Value>, name: &str) -> Option<String> {
+    match fields.get(name)? {
+        Value::String(text) if text.trim().is_empty() => None,
+        Value::String(text) => Some(text.trim().to_string()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+/// Fill the `sequence` fields of `fields` before they are saved.
+///
+/// On update (`previous` set) the stored value is kept: an empty submission
+/// takes it over and a different one is rejected. On create an empty field
+/// gets the next number of the form's counter. A value written in the field's
+/// format is kept and moves the counter past it, so imported numbers are not
+/// handed out again. Numbers are taken under a space lease before the entry is
+/// written, so a failed write leaves a gap rather than a duplicate.
+pub(crate) async fn assign_sequence_values(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    form_def: &Value,
+    fields: &mut Value,
+    previous: Option<&Value>,
+) -> Result<()> {
+    let specs = sequence_fields(form_def)?;
+    if specs.is_empty() {
+        return Ok(());
+    }
+    let Some(map) = fields.as_object_mut() else {
+        return Ok(());
+    };
+    if let Some(previous) = previous {
+        for (name, _) in &specs {
+            let stored = previous.get(name).cloned().unwrap_or(Value::Null);
+            match submitted(map, name) {
+                None => {
+                    map.insert(name.clone(), stored);
+                }
+                Some(value) if stored.as_str() == Some(value.as_str()) => {
+                    map.insert(name.clone(), stored);
+                }
+                Some(_) => {
+                    return Err(anyhow!("Sequence field '{}' cannot be changed", name));
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    let mut explicit = Vec::new();
+    for (name, spec) in &specs {
+        if let Some(value) = submitted(map, name) {
+            let number = spec.parse(&value).ok_or_else(|| {
+                anyhow!(
+                    "Field '{}' value '{}' does not match its sequence format",
+                    name,
+                    value
+                )
+            })?;
+            explicit.push((name.clone(), number));
+        }
+    }
+    let assigned = space_lock::with_space_lease(op, ws_path, SEQUENCES_LEASE, |_| async {
+        let mut counters = get_sequence_counters(op, ws_path, form_name).await?;
+        let mut assigned = Vec::new();
+        for (name, spec) in &specs {
+            let next = counters.next.get(name).copied().unwrap_or(spec.start);
+            let number = match explicit.iter().find(|(field, _)| field == name) {
+                Some((_, number)) => *number,
+                None => {
+                    assigned.push((name.clone(), spec.render(next)));
+                    next
+                }
+            };
+            let following = number
+                .checked_add(1)
+                .ok_or_else(|| anyhow!("Sequence field '{}' is exhausted", name))?;
+            counters.next.insert(name.clone(), next.max(following));
+        }
+        storage::publish_atomic(
+            op,
+            &counters_path(ws_path, form_name),
+            serde_json::to_vec_pretty(&counters)?,
+        )
+        .await?;
+        Ok(assigned)
+    })
+    .await?;
+    for (name, value) in assigned {
+        map.insert(name, Value::String(value));
+    }
+    Ok(())
+}
@@ -1,14 +1,38 @@
 use anyhow::{anyhow, Result};
 use base64::{engine::general_purpose, Engine as _};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt, TryStreamExt};
 use opendal::Operator;
 use rand::TryRng;
 #[cfg(unix)]
 use std::path::{Path, PathBuf};
 
+use crate::asset;
+use crate::asset_links;
+use crate::clock;
+use crate::collab;
+use crate::entry;
+use crate::feature_flags;
 use crate::form;
-use crate::storage::{OpendalStorage, StorageBackend};
+use crate::importers::ImportJobStatus;
+use crate::metadata;
+use crate::query_cache;
+use crate::render;
+use crate::space_lock;
+use crate::sql_session;
+use crate::storage::{self, OpendalStorage, StorageBackend};
+use crate::temporal;
 pub use ugoite_minimum::space::{storage_type_and_root, SpaceMeta, StorageConfig};
+use uuid::Uuid;
+
+/// The space id a `spaces/<space_id>` path points at.
+pub(crate) fn space_id_from_ws_path(ws_path: &str) -> &str {
+    let trimmed = ws_path.trim_end_matches('/');
+    match trimmed.rsplit_once('/') {
+        Some((_, space_id)) if !space_id.is_empty() => space_id,
+        _ => trimmed,
+    }
+}
 
 async fn space_exists_with_storage<S: StorageBackend + ?Sized>(
     storage: &S,
@@ -116,7 +140,7 @@ async fn create_space_with_storage<S: StorageBackend + ?Sized>(
     }
 
     let (storage_type, storage_root, _scheme) = storage_type_and_root(root_path);
-    let created_at = Utc::now().timestamp_millis() as f64 / 1000.0;
+    let created_at = clock::now().timestamp_millis() as f64 / 1000.0;
     let (hmac_key_id, hmac_key, last_rotation) = generate_hmac_material();
 
     let meta = serde_json::json!({
@@ -165,25 +189,26 @@ async fn list_spaces_with_storage<S: StorageBackend + ?Sized>(storage: &S) -> Re
         return Ok(vec![]);
     }
 
-    let mut spaces = Vec::new();
-    for entry in storage.list_dir(spaces_root).await? {
-        if !entry.is_dir {
-            continue;
-        }
-        let space_id = entry
-            .name
-            .trim_end_matches('/')
-            .split('/')
-            .next_back()
-            .unwrap_or("");
-        if space_id.is_empty() {
-            continue;
-        }
-        let meta_path = format!("spaces/{space_id}/meta.json");
-        if storage.exists(&meta_path).await? {
-            spaces.push(space_id.to_string());
-        }
-    }
+    let candidates: Vec<String> = storage
+        .list_dir(spaces_root)
+        .await?
+        .into_iter()
+        .filter(|entry| entry.is_dir)
+        .filter_map(|entry| {
+            let space_id = entry.name.trim_end_matches('/').split('/').next_back()?;
+            (!space_id.is_empty()).then(|| space_id.to_string())
+        })
+        .collect();
+
+    let mut spaces: Vec<String> = stream::iter(candidates)
+        .map(|space_id| async move {
+            let meta_path = format!("spaces/{space_id}/meta.json");
+            Ok::<_, anyhow::Error>(storage.exists(&meta_path).await?.then_some(space_id))
+        })
+        .buffer_unordered(storage::list_parallelism())
+        .try_filter_map(|space_id| async move { Ok(space_id) })
+        .try_collect()
+        .await?;
 
     spaces.sort();
     spaces.dedup();
@@ -195,6 +220,71 @@ pub async fn list_spaces(op: &Operator) -> Result<Vec<String>> {
     list_spaces_with_storage(&storage).await
 }
 
+/// One row of [`list_spaces_detailed`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SpaceSummary {
+    pub id: String,
+    /// Display name from `meta.json`, falling back to the id.
+    pub name: String,
+    /// Live (not trashed) entries.
+    pub entry_count: usize,
+    /// Unix seconds of the newest entry update, or the space creation time
+    /// when no entry has been written.
+    pub last_activity: f64,
+    pub storage_type: String,
+    /// Set through the `archived` space setting.
+    pub archived: bool,
+}
+
+async fn space_summary(op: &Operator, space_id: String) -> Result<SpaceSummary> {
+    let meta = get_space_raw_with_storage(&OpendalStorage::from_operator(op), &space_id).await?;
+    let ws_path = format!("spaces/{space_id}");
+    let rows = entry::list_entry_rows(op, &ws_path).await?;
+    let created_at = meta
+        .get("created_at")
+        .and_then(serde_json::Value::as_f64)
+        .unwrap_or_default();
+    let last_activity = rows
+        .iter()
+        .map(|(_, row)| row.updated_at.max(row.deleted_at.unwrap_or_default()))
+        .fold(created_at, f64::max);
+    Ok(SpaceSummary {
+        name: meta
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&space_id)
+            .to_string(),
+        entry_count: rows.iter().filter(|(_, row)| !row.deleted).count(),
+        last_activity,
+        storage_type: meta
+            .pointer("/storage/type")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("local")
+            .to_string(),
+        archived: meta
+            .pointer("/settings/archived")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false),
+        id: space_id,
+    })
+}
+
+/// List every space with the metadata a space picker needs, sorted by id.
+///
+/// Spaces are summarised concurrently, bounded by
+/// [`storage::list_parallelism`], so callers do not need a `get_space` round
+/// trip per space.
+pub async fn list_spaces_detailed(op: &Operator) -> Result<Vec<SpaceSummary>> {
+    let mut summaries: Vec<SpaceSummary> = stream::iter(list_spaces(op).await?)
+        .map(|space_id| space_summary(op, space_id))
+        .buffer_unordered(storage::list_parallelism())
+        .try_collect()
+        .await?;
+    summaries.sort_by(|left, right| left.id.cmp(&right.id));
+    Ok(summaries)
+}
+
 async fn get_space_with_storage<S: StorageBackend + ?Sized>(
     storage: &S,
     name: &str,
@@ -230,9 +320,13 @@ async fn get_space_raw_with_storage<S: StorageBackend + ?Sized>(
     Ok(meta)
 }
 
+/// Space metadata merged with its settings and effective feature flags.
 pub async fn get_space_raw(op: &Operator, name: &str) -> Result<serde_json::Value> {
     let storage = OpendalStorage::from_operator(op);
-    get_space_raw_with_storage(&storage, name).await
+    let mut meta = get_space_raw_with_storage(&storage, name).await?;
+    let flags = feature_flags::effective_flags(op, &format!("spaces/{name}")).await?;
+    meta["feature_flags"] = serde_json::to_value(flags)?;
+    Ok(meta)
 }
 
 async fn patch_space_with_storage<S: StorageBackend + ?Sized>(
@@ -261,6 +355,12 @@ async fn patch_space_with_storage<S: StorageBackend + ?Sized>(
         meta["storage_config"] = storage_config.clone();
     }
     if let Some(new_settings) = patch.get("settings").and_then(|value| value.as_object()) {
+        if let Some(tz) = new_settings.get(temporal::DEFAULT_TIMEZONE_SETTING) {
+            let tz = tz
+                .as_str()
+                .ok_or_else(|| anyhow!("default_timezone must be a string"))?;
+            temporal::parse_offset(tz)?;
+        }
         if let Some(settings_obj) = settings.as_object_mut() {
             for (key, value) in new_settings {
                 settings_obj.insert(key.clone(), value.clone());
@@ -301,3 +401,704 @@ pub async fn test_storage_connection(uri: &str) -> Result<serde_json::Value> {
         Ok(serde_json::json!({"status": "ok", "mode": "unknown"}))
     }
 }
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StaticSiteExport {
+    pub space_id: String,
+    pub entry_count: usize,
+    pub asset_count: usize,
+    pub tag_count: usize,
+    pub files: Vec<String>,
+}
+
+fn html_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn static_page_slug(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.') {
+            slug.push(byte as char);
+        } else {
+            slug.push_str(&format!("~{:02X}", byte));
+        }
+    }
+    if slug.starts_with('.') {
+        slug.replace_range(0..1, "~2E");
+    }
+    slug
+}
+
+/// Resolves `ugoite://` links to files inside the exported bundle.
+struct StaticSiteLinks<'a> {
+    page_prefix: &'a str,
+    asset_files: &'a std::collections::HashMap<String, String>,
+}
+
+impl render::LinkResolver for StaticSiteLinks<'_> {
+    fn entry_url(&self, entry_id: &str) -> Option<String> {
+        Some(format!(
+            "{}entries/{}.html",
+            self.page_prefix,
+            static_page_slug(entry_id)
+        ))
+    }
+
+    fn asset_url(&self, asset_id: &str) -> Option<String> {
+        self.asset_files
+            .get(asset_id)
+            .map(|file| format!("{}assets/{}", self.page_prefix, file))
+    }
+}
+
+fn static_page(title: &str, root_prefix: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{title}</title>\n</head>\n<body>\n<nav><a href=\"{root_prefix}index.html\">Index</a> · <a href=\"{root_prefix}tags/index.html\">Tags</a></nav>\n<main>\n{body}\n</main>\n</body>\n</html>\n",
+        title = html_escape(title),
+    )
+}
+
+fn static_entry_list(
+    entries: &[(String, entry::EntryRow)],
+    root_prefix: &str,
+    filter: impl Fn(&entry::EntryRow) -> bool,
+) -> String {
+    let mut items = String::from("<ul>\n");
+    for (form_name, row) in entries.iter().filter(|(_, row)| filter(row)) {
+        items.push_str(&format!(
+            "<li><a href=\"{root_prefix}entries/{}.html\">{}</a> <small>{}</small></li>\n",
+            static_page_slug(&row.entry_id),
+            html_escape(&row.title),
+            html_escape(form_name),
+        ));
+    }
+    items.push_str("</ul>");
+    items
+}
+
+/// Render every live entry of a space to a read-only HTML bundle under `target_path`
+/// on `target`, including referenced assets, an index page, and one page per tag.
+pub async fn export_static_site(
+    op: &Operator,
+    space_id: &str,
+    target: &Operator,
+    target_path: &str,
+) -> Result<StaticSiteExport> {
+    if !space_exists(op, space_id).await? {
+        return Err(anyhow!("Space not found: {space_id}"));
+    }
+    let ws_path = format!("spaces/{space_id}");
+    let root = target_path.trim_end_matches('/');
+    let out_path = |relative: &str| {
+        if root.is_empty() {
+            relative.to_string()
+        } else {
+            format!("{root}/{relative}")
+        }
+    };
+
+    let mut entries: Vec<(String, entry::EntryRow)> = entry::list_entry_rows(op, &ws_path)
+        .await?
+        .into_iter()
+        .filter(|(form_name, row)| !row.deleted && !metadata::is_reserved_metadata_form(form_name))
+        .collect();
+    entries.sort_by(|a, b| {
+        b.1.updated_at
+            .partial_cmp(&a.1.updated_at)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.1.entry_id.cmp(&b.1.entry_id))
+    });
+
+    let assets = asset::list_assets(op, &ws_path).await?;
+    let asset_files: std::collections::HashMap<String, String> = assets
+        .iter()
+        .map(|info| {
+            let file = info.path.trim_start_matches("assets/").to_string();
+            (info.id.clone(), file)
+        })
+        .collect();
+
+    let mut files = Vec::new();
+    let mut referenced_assets = std::collections::BTreeSet::new();
+    let mut tags: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
+    let asset_ref = regex::Regex::new(r"ugoite://asset/([^\s)\]]+)").unwrap();
+    let render_options = render::RenderOptions::default();
+    let links = StaticSiteLinks {
+        page_prefix: "../",
+        asset_files: &asset_files,
+    };
+
+    for (form_name, row) in &entries {
+        let form_def = form::read_form_definition(op, &ws_path, form_name).await?;
+        let markdown = entry::render_markdown_for_form(
+            &row.title,
+            form_name,
+            &row.tags,
+            &row.fields,
+            &row.extra_attributes,
+            &form_def,
+        );
+        let body_markdown = render::strip_frontmatter(&markdown);
+        for caps in asset_ref.captures_iter(body_markdown) {
+            referenced_assets.insert(caps[1].to_string());
+        }
+        for asset_value in &row.assets {
+            if let Some(id) = asset_value.get("id").and_then(|v| v.as_str()) {
+                referenced_assets.insert(id.to_string());
+            }
+        }
+        for tag in &row.tags {
+            *tags.entry(tag.clone()).or_default() += 1;
+        }
+
+        let mut body = render::render_html_with(body_markdown, &render_options, &links);
+        if !row.tags.is_empty() {
+            let tag_links = row
+                .tags
+                .iter()
+                .map(|tag| {
+                    format!(
+                        "<a href=\"../tags/{}.html\">{}</a>",
+                        static_page_slug(tag),
+                        html_escape(tag)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            body.push_str(&format!("<p class=\"tags\">Tags: {tag_links}</p>\n"));
+        }
+        let relative = format!("entries/{}.html", static_page_slug(&row.entry_id));
+        target
+            .write(&out_path(&relative), static_page(&row.title, "../", &body))
+            .await?;
+        files.push(relative);
+    }
+
+    let mut asset_count = 0usize;
+    for asset_id in &referenced_assets {
+        let Some(file) = asset_files.get(asset_id) else {
+            continue;
+        };
+        let bytes = op.read(&format!("{ws_path}/assets/{file}")).await?.to_vec();
+        let relative = format!("assets/{file}");
+        target.write(&out_path(&relative), bytes).await?;
+        files.push(relative);
+        asset_count += 1;
+    }
+
+    let mut tag_index = String::from("<h1>Tags</h1>\n<ul>\n");
+    for (tag, count) in &tags {
+        tag_index.push_str(&format!(
+            "<li><a href=\"{}.html\">{}</a> ({count})</li>\n",
+            static_page_slug(tag),
+            html_escape(tag)
+        ));
+        let body = format!(
+            "<h1>{}</h1>\n{}",
+            html_escape(tag),
+            static_entry_list(&entries, "../", |row| row.tags.contains(tag))
+        );
+        let relative = format!("tags/{}.html", static_page_slug(tag));
+        target
+            .write(&out_path(&relative), static_page(tag, "../", &body))
+            .await?;
+        files.push(relative);
+    }
+    tag_index.push_str("</ul>");
+    target
+        .write(
+            &out_path("tags/index.html"),
+            static_page("Tags", "../", &tag_index),
+        )
+        .await?;
+    files.push("tags/index.html".to_string());
+
+    let space_name = get_space_raw(op, space_id)
+        .await?
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or(space_id)
+        .to_string();
+    let index_body = format!(
+        "<h1>{}</h1>\n{}",
+        html_escape(&space_name),
+        static_entry_list(&entries, "", |_| true)
+    );
+    target
+        .write(
+            &out_path("index.html"),
+            static_page(&space_name, "", &index_body),
+        )
+        .await?;
+    files.push("index.html".to_string());
+    files.sort();
+
+    Ok(StaticSiteExport {
+        space_id: space_id.to_string(),
+        entry_count: entries.len(),
+        asset_count,
+        tag_count: tags.len(),
+        files,
+    })
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MarkdownExport {
+    pub space_id: String,
+    pub entry_count: usize,
+    pub asset_count: usize,
+    pub files: Vec<String>,
+    /// Asset links rewritten to bundle paths, and ids with no stored asset.
+    pub asset_links: asset_links::AssetLinkReport,
+}
+
+/// Write every live entry of a space as `entries/{id}.md` under `target_path` on
+/// `target`, with asset links rewritten to the copied `assets/` files.
+pub async fn export_markdown_bundle(
+    op: &Operator,
+    space_id: &str,
+    target: &Operator,
+    target_path: &str,
+) -> Result<MarkdownExport> {
+    if !space_exists(op, space_id).await? {
+        return Err(anyhow!("Space not found: {space_id}"));
+    }
+    let ws_path = format!("spaces/{space_id}");
+    let root = target_path.trim_end_matches('/');
+    let out_path = |relative: &str| {
+        if root.is_empty() {
+            relative.to_string()
+        } else {
+            format!("{root}/{relative}")
+        }
+    };
+
+    let mut entries: Vec<(String, entry::EntryRow)> = entry::list_entry_rows(op, &ws_path)
+        .await?
+        .into_iter()
+        .filter(|(form_name, row)| !row.deleted && !metadata::is_reserved_metadata_form(form_name))
+        .collect();
+    entries.sort_by(|a, b| a.1.entry_id.cmp(&b.1.entry_id));
+    let map = asset_links::AssetLinkMap::load(op, &ws_path).await?;
+
+    let mut files = Vec::new();
+    let mut report = asset_links::AssetLinkReport::new(asset_links::AssetLinkDirection::Export);
+    for (form_name, row) in &entries {
+        let form_def = form::read_form_definition(op, &ws_path, form_name).await?;
+        let markdown = entry::render_markdown_for_form(
+            &row.title,
+            form_name,
+            &row.tags,
+            &row.fields,
+            &row.extra_attributes,
+            &form_def,
+        );
+        let rewrite = asset_links::rewrite_asset_links(
+            &markdown,
+            "entries",
+            &map,
+            asset_links::AssetLinkDirection::Export,
+        );
+        report.merge(rewrite.report);
+        let relative = format!("entries/{}.md", static_page_slug(&row.entry_id));
+        target.write(&out_path(&relative), rewrite.content).await?;
+        files.push(relative);
+    }
+
+    for mapping in &report.mappings {
+        let bytes = op
+            .read(&format!("{ws_path}/{}", mapping.path))
+            .await?
+            .to_vec();
+        target.write(&out_path(&mapping.path), bytes).await?;
+        files.push(mapping.path.clone());
+    }
+    files.sort();
+
+    Ok(MarkdownExport {
+        space_id: space_id.to_string(),
+        entry_count: entries.len(),
+        asset_count: report.mappings.len(),
+        files,
+        asset_links: report,
+    })
+}
+
+const VACUUM_JOBS_DIR: &str = "vacuum_jobs";
+/// Job record directories whose finished jobs [`vacuum`] expires.
+const JOB_RECORD_DIRS: [&str; 4] = [
+    "import_jobs",
+    "tabular_import_jobs",
+    "bulk_jobs",
+    VACUUM_JOBS_DIR,
+];
+const DEFAULT_TEMP_FILE_AGE_SECS: u64 = 60 * 60;
+const DEFAULT_JOB_RETENTION_SECS: u64 = 7 * 24 * 60 * 60;
+
+fn default_temp_file_age_secs() -> u64 {
+    DEFAULT_TEMP_FILE_AGE_SECS
+}
+
+fn default_job_retention_secs() -> u64 {
+    DEFAULT_JOB_RETENTION_SECS
+}
+
+fn default_compact_tables() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct VacuumOptions {
+    /// Leftover atomic-write temp files older than this are removed.
+    #[serde(default = "default_temp_file_age_secs")]
+    pub temp_file_age_secs: u64,
+    /// Finished job records older than this are removed.
+    #[serde(default = "default_job_retention_secs")]
+    pub job_retention_secs: u64,
+    /// Rewrite fragmented form tables as one data file each.
+    #[serde(default = "default_compact_tables")]
+    pub compact_tables: bool,
+}
+
+impl Default for VacuumOptions {
+    fn default() -> Self {
+        Self {
+            temp_file_age_secs: DEFAULT_TEMP_FILE_AGE_SECS,
+            job_retention_secs: DEFAULT_JOB_RETENTION_SECS,
+            compact_tables: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct VacuumReport {
+    pub space_id: String,
+    pub temp_files_removed: usize,
+    pub sql_sessions_removed: usize,
+    pub jobs_removed: usize,
+    pub cache_files_removed: usize,
+    /// Collaborative edit updates folded into their snapshots.
+    pub collab_updates_compacted: usize,
+    pub forms_compacted: Vec<String>,
+    /// Entry rows dropped because a later row replaced them.
+    pub superseded_rows: usize,
+    pub data_files_before: usize,
+    pub data_files_after: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub reclaimed_bytes: u64,
+}
+
+struct SpaceFile {
+    path: String,
+    name: String,
+    size: u64,
+    modified: Option<std::time::SystemTime>,
+}
+
+async fn list_space_files(op: &Operator, ws_path: &str) -> Result<Vec<SpaceFile>> {
+    let mut files = Vec::new();
+    for item in op.list_with(&format!("{ws_path}/")).recursive(true).await? {
+        if !item.metadata().is_file() {
+            continue;
+        }
+        let meta = op.stat(item.path()).await?;
+        files.push(SpaceFile {
+            path: item.path().to_string(),
+            name: item.name().to_string(),
+            size: meta.content_length(),
+            modified: meta.last_modified().map(Into::into),
+        });
+    }
+    Ok(files)
+}
+
+/// Files staged by [`storage::publish_atomic`] and never moved into place.
+fn is_stale_temp_file(file: &SpaceFile, max_age_secs: u64) -> bool {
+    if !(file.name.starts_with('.') && file.name.ends_with(".tmp")) {
+        return false;
+    }
+    match file.modified.and_then(|at| at.elapsed().ok()) {
+        Some(age) => age.as_secs() >= max_age_secs,
+        // Without a timestamp the file may still be in flight.
+        None => max_age_secs == 0,
+    }
+}
+
+/// Delete finished job records completed more than `retention_secs` ago,
+/// with any uploaded source kept beside them.
+async fn purge_job_records(op: &Operator, ws_path: &str, retention_secs: u64) -> Result<usize> {
+    let cutoff = Utc::now() - chrono::Duration::seconds(retention_secs as i64);
+    let mut removed = 0;
+    for dir in JOB_RECORD_DIRS {
+        let dir = format!("{ws_path}/{dir}/");
+        if !op.exists(&dir).await? {
+            continue;
+        }
+        for item in op.list(&dir).await? {
+            let Some(job_id) = item.name().strip_suffix(".json") else {
+                continue;
+            };
+            let Ok(record) =
+                serde_json::from_slice::<serde_json::Value>(&op.read(item.path()).await?.to_vec())
+            else {
+                continue;
+            };
+            let finished = matches!(
+                record.get("status").and_then(|v| v.as_str()),
+                Some("completed" | "failed")
+            );
+            let completed_at = record
+                .get("completed_at")
+                .and_then(|v| v.as_str())
+                .and_then(|v| DateTime::parse_from_rfc3339(v).ok());
+            if !finished || completed_at.is_none_or(|at| at > cutoff) {
+                continue;
+            }
+            op.delete(item.path()).await?;
+            op.delete(&format!("{dir}{job_id}.source")).await?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+async fn compact_space_forms(
+    op: &Operator,
+    ws_path: &str,
+    report: &mut VacuumReport,
+) -> Result<()> {
+    space_lock::with_space_lease(op, ws_path, space_lock::MIGRATION_LEASE, |_| async {
+        for form_name in form::list_form_names(op, ws_path).await? {
+            let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+            let Some(compaction) =
+                entry::compact_form_tables(op, ws_path, &form_name, &form_def).await?
+            else {
+                continue;
+            };
+            report.forms_compacted.push(form_name);
+            report.superseded_rows += compaction.superseded_rows;
+            report.data_files_before += compaction.data_files_before;
+            report.data_files_after += compaction.data_files_after;
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Reclaim storage a space no longer needs: stale temp files, expired SQL
+/// sessions, old finished job records, pending collaborative updates, stale
+/// query cache results and superseded rows in fragmented form tables.
+pub async fn vacuum(
+    op: &Operator,
+    space_id: &str,
+    options: &VacuumOptions,
+) -> Result<VacuumReport> {
+    if !space_exists(op, space_id).await? {
+        return Err(anyhow!("Space not found: {space_id}"));
+    }
+    let ws_path = format!("spaces/{space_id}");
+    let before = list_space_files(op, &ws_path).await?;
+    let mut report = VacuumReport {
+        space_id: space_id.to_string(),
+        bytes_before: before.iter().map(|file| file.size).sum(),
+        ..VacuumReport::default()
+    };
+
+    for file in before
+        .iter()
+        .filter(|file| is_stale_temp_file(file, options.temp_file_age_secs))
+    {
+        op.delete(&file.path).await?;
+        report.temp_files_removed += 1;
+    }
+    report.sql_sessions_removed = sql_session::purge_expired_sessions(op, &ws_path).await?;
+    report.jobs_removed = purge_job_records(op, &ws_path, options.job_retention_secs).await?;
+    report.collab_updates_compacted = collab::compact_space(op, &ws_path).await?;
+    if options.compact_tables {
+        compact_space_forms(op, &ws_path, &mut report).await?;
+    }
+    report.cache_files_removed = query_cache::purge_stale_disk(op, &ws_path).await?;
+
+    report.bytes_after = list_space_files(op, &ws_path)
+        .await?
+        .iter()
+        .map(|file| file.size)
+        .sum();
+    report.reclaimed_bytes = report.bytes_before.saturating_sub(report.bytes_after);
+    Ok(report)
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct VacuumJob {
+    pub job_id: String,
+    pub space_id: String,
+    pub options: VacuumOptions,
+    pub status: ImportJobStatus,
+    pub status_message: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+    pub report: Option<VacuumReport>,
+}
+
+fn vacuum_job_path(space_id: &str, job_id: &str) -> String {
+    format!("spaces/{space_id}/{VACUUM_JOBS_DIR}/{job_id}.json")
+}
+
+async fn write_vacuum_job(op: &Operator, job: &VacuumJob) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &vacuum_job_path(&job.space_id, &job.job_id),
+        serde_json::to_vec_pretty(job)?,
+    )
+    .await?;
+    Ok(())
+}
+
+async fn run_vacuum_job(op: Operator, mut job: VacuumJob) {
+    job.status = ImportJobStatus::Running;
+    job.status_message = Some("Vacuuming space".to_string());
+    job.started_at = Some(Utc::now());
+    let _ = write_vacuum_job(&op, &job).await;
+
+    match vacuum(&op, &job.space_id, &job.options).await {
+        Ok(report) => {
+            job.status = ImportJobStatus::Completed;
+            job.status_message = Some("Completed".to_string());
+            job.report = Some(report);
+        }
+        Err(err) => {
+            job.status = ImportJobStatus::Failed;
+            job.status_message = Some("Failed".to_string());
+            job.error = Some(err.to_string());
+        }
+    }
+    job.completed_at = Some(Utc::now());
+    let _ = write_vacuum_job(&op, &job).await;
+}
+
+/// Queue [`vacuum`] in the background; the report is stored with the job.
+pub async fn create_vacuum_job(
+    op: &Operator,
+    space_id: &str,
+    options: &VacuumOptions,
+) -> Result<VacuumJob> {
+    if !space_exists(op, space_id).await? {
+        return Err(anyhow!("Space not found: {space_id}"));
+    }
+    let job = VacuumJob {
+        job_id: Uuid::new_v4().to_string(),
+        space_id: space_id.to_string(),
+        options: options.clone(),
+        status: ImportJobStatus::Queued,
+        status_message: Some("Queued".to_string()),
+        started_at: None,
+        completed_at: None,
+        error: None,
+        report: None,
+    };
+    write_vacuum_job(op, &job).await?;
+    tokio::spawn(run_vacuum_job(op.clone(), job.clone()));
+    Ok(job)
+}
+
+pub async fn get_vacuum_job(op: &Operator, space_id: &str, job_id: &str) -> Result<VacuumJob> {
+    Uuid::parse_str(job_id)
+        .map_err(|e| anyhow!("Invalid job_id: {}. Must be a valid UUID. ({})", job_id, e))?;
+    let path = vacuum_job_path(space_id, job_id);
+    if !op.exists(&path).await? {
+        return Err(anyhow!("Vacuum job not found: {}", job_id));
+    }
+    Ok(serde_json::from_slice(&op.read(&path).await?.to_vec())?)
+}
+
+/// Outcome of rolling a space back with bucket versions.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct SpaceRestoreReport {
+    pub space_id: String,
+    /// Unix seconds the space was restored to.
+    pub timestamp: f64,
+    pub dry_run: bool,
+    /// Paths put back to the version current at `timestamp`.
+    pub restored: Vec<String>,
+    /// Paths removed because they did not exist at `timestamp`.
+    pub deleted: Vec<String>,
+}
+
+/// Roll every file of a space back to its state at `timestamp` (unix
+/// seconds) using the versions kept by a versioned bucket.
+///
+/// Restored files are written as new versions, so the rollback itself can be
+/// undone the same way. Lease files are skipped and the query cache is
+/// cleared afterwards. With `dry_run` the planned changes are reported
+/// without touching storage.
+pub async fn restore_space_to_timestamp(
+    op: &Operator,
+    space_id: &str,
+    timestamp: f64,
+    dry_run: bool,
+) -> Result<SpaceRestoreReport> {
+    if space_id.is_empty() || space_id.contains('/') || space_id.starts_with('.') {
+        return Err(anyhow!("Invalid space_id: {space_id}"));
+    }
+    if timestamp > clock::now().timestamp() as f64 {
+        return Err(anyhow!("Restore timestamp is in the future"));
+    }
+    let ws_path = format!("spaces/{space_id}");
+    let locks_prefix = format!("{ws_path}/{}/", space_lock::LOCKS_DIR);
+    let versions: Vec<_> = storage::versions::list_object_versions(op, &format!("{ws_path}/"))
+        .await?
+        .into_iter()
+        .filter(|version| !version.path.starts_with(&locks_prefix))
+        .collect();
+    if versions.is_empty() {
+        return Err(anyhow!("No object versions found for space: {space_id}"));
+    }
+    let actions = storage::versions::plan_point_in_time_restore(&versions, timestamp);
+    let mut report = SpaceRestoreReport {
+        space_id: space_id.to_string(),
+        timestamp,
+        dry_run,
+        ..SpaceRestoreReport::default()
+    };
+    for action in &actions {
+        match action {
+            storage::versions::VersionRestoreAction::Restore { path, .. } => {
+                report.restored.push(path.clone())
+            }
+            storage::versions::VersionRestoreAction::Delete { path } => {
+                report.deleted.push(path.clone())
+            }
+        }
+    }
+    if dry_run {
+        return Ok(report);
+    }
+    space_lock::with_space_lease(op, &ws_path, space_lock::MIGRATION_LEASE, |_| async {
+        for action in &actions {
+            match action {
+                storage::versions::VersionRestoreAction::Restore { path, version_id } => {
+                    storage::versions::restore_object_version(op, path, version_id).await?
+                }
+                storage::versions::VersionRestoreAction::Delete { path } => op.delete(path).await?,
+            }
+        }
+        Ok(())
+    })
+    .await?;
+    query_cache::clear_space_cache(op, &ws_path).await?;
+    Ok(report)
+}
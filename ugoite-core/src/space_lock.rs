@@ -0,0 +1,298 @@
+use anyhow::{anyhow, Result};
+use opendal::{ErrorKind, Operator};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::entry;
+use crate::storage;
+
+pub(crate) const LOCKS_DIR: &str = "locks";
+pub const DEFAULT_LEASE_TTL_SECS: u64 = 30;
+/// Lease serializing schema and storage migrations in a space.
+pub const MIGRATION_LEASE: &str = "migration";
+/// Lease serializing index rebuilds in a space.
+pub const INDEX_LEASE: &str = "index";
+/// How long writers wait for a busy lease before giving up.
+pub const DEFAULT_LEASE_WAIT: Duration = Duration::from_secs(10);
+
+/// A time-limited claim on a named space-wide lock, shared by every process
+/// that uses the same storage.
+///
+/// Each acquisition gets a fencing token greater than every earlier one, so a
+/// holder that stalled past its TTL can detect that it was superseded before
+/// writing.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SpaceLease {
+    pub name: String,
+    pub holder: String,
+    pub fencing_token: u64,
+    pub lease_id: String,
+    pub acquired_at: f64,
+    pub expires_at: f64,
+}
+
+impl SpaceLease {
+    pub fn is_active(&self, now: f64) -> bool {
+        self.expires_at > now
+    }
+}
+
+static LEASE_MUTEXES: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+
+/// Serializes claims within this process, for backends without conditional writes.
+async fn lease_mutex(key: &str) -> Arc<Mutex<()>> {
+    let mut registry = LEASE_MUTEXES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .await;
+    registry
+        .entry(key.to_string())
+        .or_insert_with(|| Arc::new(Mutex::new(())))
+        .clone()
+}
+
+/// Identifies this process as a lease holder.
+pub fn process_holder_id() -> &'static str {
+    static HOLDER: OnceLock<String> = OnceLock::new();
+    HOLDER.get_or_init(|| format!("pid-{}-{}", std::process::id(), uuid::Uuid::new_v4()))
+}
+
+fn validate_lease_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid lease name: {}", name))
+    }
+}
+
+fn lease_dir(ws_path: &str, name: &str) -> String {
+    format!("{}/{}/{}/", ws_path.trim_end_matches('/'), LOCKS_DIR, name)
+}
+
+fn token_path(ws_path: &str, name: &str, token: u64) -> String {
+    format!("{}{:012}.json", lease_dir(ws_path, name), token)
+}
+
+async fn token_files(op: &Operator, ws_path: &str, name: &str) -> Result<Vec<u64>> {
+    let dir = lease_dir(ws_path, name);
+    if !op.exists(&dir).await? {
+        return Ok(Vec::new());
+    }
+    let mut tokens: Vec<u64> = op
+        .list(&dir)
+        .await?
+        .into_iter()
+        .filter_map(|item| item.name().strip_suffix(".json")?.parse().ok())
+        .collect();
+    tokens.sort_unstable();
+    Ok(tokens)
+}
+
+async fn read_token(op: &Operator, ws_path: &str, name: &str, token: u64) -> Result<SpaceLease> {
+    let bytes = op.read(&token_path(ws_path, name, token)).await?.to_vec();
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+async fn latest_lease(op: &Operator, ws_path: &str, name: &str) -> Result<Option<SpaceLease>> {
+    match token_files(op, ws_path, name).await?.last() {
+        Some(token) => Ok(Some(read_token(op, ws_path, name, *token).await?)),
+        None => Ok(None),
+    }
+}
+
+/// Create the file for `lease`'s token, failing if another process claimed it first.
+async fn claim_token(op: &Operator, ws_path: &str, lease: &SpaceLease) -> Result<bool> {
+    let path = token_path(ws_path, &lease.name, lease.fencing_token);
+    let body = serde_json::to_vec_pretty(lease)?;
+    if op.info().full_capability().write_with_if_not_exists {
+        return match op.write_with(&path, body).if_not_exists(true).await {
+            Ok(_) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::ConditionNotMatch => Ok(false),
+            Err(err) => Err(err.into()),
+        };
+    }
+    if op.exists(&path).await? {
+        return Ok(false);
+    }
+    op.write(&path, body).await?;
+    Ok(true)
+}
+
+fn validate_ttl(ttl_secs: u64) -> Result<f64> {
+    if ttl_secs == 0 {
+        return Err(anyhow!("Lease ttl must be at least one second"));
+    }
+    Ok(ttl_secs as f64)
+}
+
+/// The newest lease on `name`, if it has not expired or been released.
+pub async fn current_lease(op: &Operator, ws_path: &str, name: &str) -> Result<Option<SpaceLease>> {
+    validate_lease_name(name)?;
+    let now = entry::now_ts();
+    Ok(latest_lease(op, ws_path, name)
+        .await?
+        .filter(|lease| lease.is_active(now)))
+}
+
+/// Claim `name` once, failing immediately if another holder has it.
+pub async fn try_acquire_lease(
+    op: &Operator,
+    ws_path: &str,
+    name: &str,
+    holder: &str,
+    ttl_secs: u64,
+) -> Result<SpaceLease> {
+    validate_lease_name(name)?;
+    let ttl = validate_ttl(ttl_secs)?;
+    let mutex = lease_mutex(&lease_dir(ws_path, name)).await;
+    let _guard = mutex.lock().await;
+
+    let tokens = token_files(op, ws_path, name).await?;
+    let now = entry::now_ts();
+    let next_token = match tokens.last() {
+        Some(token) => {
+            let latest = read_token(op, ws_path, name, *token).await?;
+            if latest.is_active(now) {
+                return Err(anyhow!(
+                    "Space lock '{}' is held by {}",
+                    name,
+                    latest.holder
+                ));
+            }
+            token + 1
+        }
+        None => 1,
+    };
+    let lease = SpaceLease {
+        name: name.to_string(),
+        holder: holder.to_string(),
+        fencing_token: next_token,
+        lease_id: uuid::Uuid::new_v4().to_string(),
+        acquired_at: now,
+        expires_at: now + ttl,
+    };
+    if !claim_token(op, ws_path, &lease).await? {
+        return Err(anyhow!("Space lock '{}' was claimed concurrently", name));
+    }
+    // Older tokens can never become current again.
+    for token in tokens {
+        let _ = op.delete(&token_path(ws_path, name, token)).await;
+    }
+    Ok(lease)
+}
+
+/// Claim `name`, retrying with backoff for up to `wait`.
+pub async fn acquire_lease(
+    op: &Operator,
+    ws_path: &str,
+    name: &str,
+    holder: &str,
+    ttl_secs: u64,
+    wait: Duration,
+) -> Result<SpaceLease> {
+    let deadline = tokio::time::Instant::now() + wait;
+    let mut backoff = Duration::from_millis(10);
+    loop {
+        match try_acquire_lease(op, ws_path, name, holder, ttl_secs).await {
+            Ok(lease) => return Ok(lease),
+            Err(err) if tokio::time::Instant::now() + backoff > deadline => return Err(err),
+            Err(_) => {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(Duration::from_millis(250));
+            }
+        }
+    }
+}
+
+/// Fail unless `lease` is still the newest, unexpired claim on its lock.
+///
+/// Call before each write made under the lease.
+pub async fn ensure_lease_current(op: &Operator, ws_path: &str, lease: &SpaceLease) -> Result<()> {
+    let now = entry::now_ts();
+    match latest_lease(op, ws_path, &lease.name).await? {
+        Some(latest)
+            if latest.fencing_token == lease.fencing_token
+                && latest.lease_id == lease.lease_id
+                && latest.is_active(now) =>
+        {
+            Ok(())
+        }
+        _ => Err(anyhow!(
+            "Space lock '{}' token {} is no longer held",
+            lease.name,
+            lease.fencing_token
+        )),
+    }
+}
+
+pub async fn renew_lease(
+    op: &Operator,
+    ws_path: &str,
+    lease: &SpaceLease,
+    ttl_secs: u64,
+) -> Result<SpaceLease> {
+    let ttl = validate_ttl(ttl_secs)?;
+    ensure_lease_current(op, ws_path, lease).await?;
+    let renewed = SpaceLease {
+        expires_at: entry::now_ts() + ttl,
+        ..lease.clone()
+    };
+    storage::publish_atomic(
+        op,
+        &token_path(ws_path, &lease.name, lease.fencing_token),
+        serde_json::to_vec_pretty(&renewed)?,
+    )
+    .await?;
+    Ok(renewed)
+}
+
+/// Give up a lease; releasing one that was already superseded is a no-op.
+pub async fn release_lease(op: &Operator, ws_path: &str, lease: &SpaceLease) -> Result<()> {
+    if ensure_lease_current(op, ws_path, lease).await.is_err() {
+        return Ok(());
+    }
+    // The file stays behind so the next claim still gets a higher token.
+    let released = SpaceLease {
+        expires_at: entry::now_ts(),
+        ..lease.clone()
+    };
+    storage::publish_atomic(
+        op,
+        &token_path(ws_path, &lease.name, lease.fencing_token),
+        serde_json::to_vec_pretty(&released)?,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Run `task` while holding `name` for this process, releasing it afterwards.
+pub async fn with_space_lease<T, F, Fut>(
+    op: &Operator,
+    ws_path: &str,
+    name: &str,
+    task: F,
+) -> Result<T>
+where
+    F: FnOnce(SpaceLease) -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let lease = acquire_lease(
+        op,
+        ws_path,
+        name,
+        process_holder_id(),
+        DEFAULT_LEASE_TTL_SECS,
+        DEFAULT_LEASE_WAIT,
+    )
+    .await?;
+    let result = task(lease.clone()).await;
+    release_lease(op, ws_path, &lease).await?;
+    result
+}
@@ -1,15 +1,20 @@
+use crate::decimal;
+use crate::sql_functions::{self, SqlFunction};
+use crate::temporal;
 use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlparser::ast::{
-    BinaryOperator, Expr, Ident, Join, JoinConstraint, JoinOperator, LimitClause, ObjectName,
-    ObjectNamePart, OrderBy, OrderByExpr, OrderByKind, SelectItem, SetExpr, Statement, TableFactor,
-    Value as SqlValue, ValueWithSpan,
+    BinaryOperator, Expr, Function, FunctionArg, FunctionArgExpr, FunctionArguments, Ident, Join,
+    JoinConstraint, JoinOperator, LimitClause, ObjectName, ObjectNamePart, OrderBy, OrderByExpr,
+    OrderByKind, SelectItem, SetExpr, Statement, TableFactor, Value as SqlValue, ValueWithSpan,
 };
 use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::Parser;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone)]
 pub struct SqlQuery {
@@ -18,12 +23,24 @@ pub struct SqlQuery {
     pub selection: Option<Expr>,
     pub order_by: Vec<OrderByExpr>,
     pub limit: Option<usize>,
+    /// Function calls selected next to `*`, added to every result row.
+    pub computed: Vec<SqlComputedColumn>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SqlComputedColumn {
+    pub name: String,
+    pub expr: Expr,
+    pub column_type: SqlColumnType,
 }
 
 #[derive(Debug, Clone)]
 pub struct SqlTableRef {
     pub name: String,
     pub alias: Option<String>,
+    /// Applied to the table's rows before they are joined or selected, so an
+    /// outer join sees filtered-out rows as missing rather than matching them.
+    pub row_filter: Option<Expr>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -42,11 +59,199 @@ pub struct SqlJoin {
     pub constraint: JoinConstraint,
 }
 
+/// Execution limits for a single query; `None` leaves that dimension unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SqlLimits {
+    pub timeout: Option<Duration>,
+    /// Cap on the approximate bytes of rows held while joining and filtering.
+    pub max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SqlLimitKind {
+    Timeout,
+    Memory,
+}
+
+/// Error raised when a query runs past one of its [`SqlLimits`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlLimitExceeded {
+    pub kind: SqlLimitKind,
+    /// Milliseconds for a timeout, bytes for the memory cap.
+    pub limit: u64,
+}
+
+impl std::fmt::Display for SqlLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            SqlLimitKind::Timeout => write!(
+                f,
+                "{SQL_ERROR_PREFIX}: query exceeded its {} ms timeout",
+                self.limit
+            ),
+            SqlLimitKind::Memory => write!(
+                f,
+                "{SQL_ERROR_PREFIX}: query exceeded its {} byte memory cap",
+                self.limit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SqlLimitExceeded {}
+
+/// Rows between deadline checks in the join, filter and sort loops.
+const LIMIT_CHECK_INTERVAL: usize = 256;
+
+/// Tracks a query's deadline and the bytes of rows it holds.
+struct QueryBudget {
+    limits: SqlLimits,
+    deadline: Option<Instant>,
+    ticks: usize,
+}
+
+impl QueryBudget {
+    fn new(limits: SqlLimits) -> Self {
+        Self {
+            limits,
+            deadline: limits.timeout.map(|timeout| Instant::now() + timeout),
+            ticks: 0,
+        }
+    }
+
+    fn tick(&mut self) -> Result<()> {
+        self.ticks += 1;
+        if self.ticks.is_multiple_of(LIMIT_CHECK_INTERVAL) {
+            self.check_deadline()?;
+        }
+        Ok(())
+    }
+
+    fn check_deadline(&self) -> Result<()> {
+        match (self.deadline, self.limits.timeout) {
+            (Some(deadline), Some(timeout)) if Instant::now() >= deadline => {
+                Err(anyhow::Error::new(SqlLimitExceeded {
+                    kind: SqlLimitKind::Timeout,
+                    limit: timeout.as_millis() as u64,
+                }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_bytes(&self, bytes: usize) -> Result<()> {
+        match self.limits.max_bytes {
+            Some(max_bytes) if bytes > max_bytes => Err(anyhow::Error::new(SqlLimitExceeded {
+                kind: SqlLimitKind::Memory,
+                limit: max_bytes as u64,
+            })),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Approximate in-memory size of a row, measured by its JSON encoding.
+fn row_bytes(row: &Value) -> usize {
+    serde_json::to_vec(row)
+        .map(|bytes| bytes.len())
+        .unwrap_or(0)
+}
+
+/// Type of a SQL result column, derived from the form schema behind the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SqlColumnType {
+    String,
+    Int,
+    Float,
+    Bool,
+    Date,
+    Time,
+    Timestamp,
+    Decimal,
+    Geopoint,
+    List,
+}
+
+impl SqlColumnType {
+    /// Map a form field type to the column type its Arrow schema column carries.
+    pub fn from_field_type(field_type: &str) -> Self {
+        match field_type {
+            "number" | "double" | "float" => SqlColumnType::Float,
+            "integer" | "long" | "duration" => SqlColumnType::Int,
+            "boolean" => SqlColumnType::Bool,
+            "date" => SqlColumnType::Date,
+            "time" => SqlColumnType::Time,
+            "timestamp" | "timestamp_tz" | "timestamp_ns" | "timestamp_tz_ns" | "datetime" => {
+                SqlColumnType::Timestamp
+            }
+            "decimal" => SqlColumnType::Decimal,
+            "geopoint" => SqlColumnType::Geopoint,
+            "list" | "object_list" => SqlColumnType::List,
+            _ => SqlColumnType::String,
+        }
+    }
+}
+
+/// A column of SQL result rows, named by its dotted path into each row.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlColumn {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub column_type: SqlColumnType,
+}
+
+impl SqlColumn {
+    pub fn new(name: impl Into<String>, column_type: SqlColumnType) -> Self {
+        Self {
+            name: name.into(),
+            column_type,
+        }
+    }
+}
+
+/// Columns of the rows `filter_entries_by_sql` returns for `query`.
+///
+/// Single-table results use the table's column names; joined results nest
+/// each table's row under its alias (or name), so names gain that prefix.
+pub fn result_columns(
+    table_columns: &HashMap<String, Vec<SqlColumn>>,
+    query: &SqlQuery,
+) -> Result<Vec<SqlColumn>> {
+    let columns_of = |table: &SqlTableRef| {
+        table_columns
+            .get(&table.name.to_lowercase())
+            .ok_or_else(|| sql_error(format!("Unknown table: {}", table.name)))
+    };
+    let computed = query
+        .computed
+        .iter()
+        .map(|column| SqlColumn::new(column.name.clone(), column.column_type));
+    if query.joins.is_empty() {
+        let mut columns = columns_of(&query.from)?.clone();
+        columns.extend(computed);
+        return Ok(columns);
+    }
+    let mut columns = Vec::new();
+    for table in std::iter::once(&query.from).chain(query.joins.iter().map(|join| &join.table)) {
+        let prefix = table.alias.as_deref().unwrap_or(&table.name);
+        for column in columns_of(table)? {
+            columns.push(SqlColumn::new(
+                format!("{prefix}.{}", column.name),
+                column.column_type,
+            ));
+        }
+    }
+    columns.extend(computed);
+    Ok(columns)
+}
+
 const SQL_ERROR_PREFIX: &str = "UGOITE_SQL_ERROR";
 const MAX_QUERY_LIMIT: usize = 1000;
 const LIKE_REGEX_CACHE_LIMIT: usize = 256;
 
-fn sql_error(message: impl std::fmt::Display) -> anyhow::Error {
+pub(crate) fn sql_error(message: impl std::fmt::Display) -> anyhow::Error {
     anyhow!("{SQL_ERROR_PREFIX}: {message}")
 }
 
@@ -58,6 +263,15 @@ pub fn parse_sql(query: &str) -> Result<SqlQuery> {
         return Err(sql_error("Only a single SQL statement is supported"));
     }
 
+    if matches!(
+        &statements[0],
+        Statement::Install { .. } | Statement::Load { .. }
+    ) {
+        return Err(sql_error(format!(
+            "Queries cannot load SQL extensions; enable registered extensions with {}",
+            sql_functions::EXTENSIONS_ENV
+        )));
+    }
     let Statement::Query(boxed) = &statements[0] else {
         return Err(sql_error("Only SELECT queries are supported"));
     };
@@ -66,16 +280,7 @@ pub fn parse_sql(query: &str) -> Result<SqlQuery> {
         return Err(sql_error("Only SELECT queries are supported"));
     };
 
-    if select.projection.is_empty()
-        || !select.projection.iter().all(|item| {
-            matches!(
-                item,
-                SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(_, _)
-            )
-        })
-    {
-        return Err(sql_error("Only SELECT * is supported in Ugoite SQL"));
-    }
+    let computed = parse_projection(&select.projection)?;
 
     if select.from.len() != 1 {
         return Err(sql_error("Exactly one FROM target is required"));
@@ -90,7 +295,30 @@ pub fn parse_sql(query: &str) -> Result<SqlQuery> {
         .collect::<Result<Vec<_>>>()?;
 
     let selection = select.selection.clone();
+    if let Some(expr) = &selection {
+        check_functions(expr)?;
+    }
     let order_by = parse_order_by(boxed.order_by.as_ref())?;
+    for order in &order_by {
+        check_functions(&order.expr)?;
+    }
+    for column in &computed {
+        let reserved = std::iter::once(&from_table)
+            .chain(joins.iter().map(|join| &join.table))
+            .any(|table| {
+                table
+                    .alias
+                    .as_deref()
+                    .unwrap_or(&table.name)
+                    .eq_ignore_ascii_case(&column.name)
+            });
+        if reserved {
+            return Err(sql_error(format!(
+                "Computed column {} clashes with a table name",
+                column.name
+            )));
+        }
+    }
     let limit = match &boxed.limit_clause {
         Some(limit_clause) => parse_limit_clause(limit_clause)?,
         None => None,
@@ -102,29 +330,195 @@ pub fn parse_sql(query: &str) -> Result<SqlQuery> {
         selection,
         order_by,
         limit,
+        computed,
     })
 }
 
+/// `*` plus any function calls, which become computed columns.
+fn parse_projection(projection: &[SelectItem]) -> Result<Vec<SqlComputedColumn>> {
+    let only_star = || sql_error("Only SELECT * and function calls are supported in Ugoite SQL");
+    if !projection.iter().any(|item| {
+        matches!(
+            item,
+            SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(_, _)
+        )
+    }) {
+        return Err(only_star());
+    }
+    let mut computed: Vec<SqlComputedColumn> = Vec::new();
+    for item in projection {
+        let (expr, alias) = match item {
+            SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(_, _) => continue,
+            SelectItem::UnnamedExpr(expr) => (expr, None),
+            SelectItem::ExprWithAlias { expr, alias } => (expr, Some(alias.value.clone())),
+        };
+        let Expr::Function(function) = expr else {
+            return Err(only_star());
+        };
+        check_functions(expr)?;
+        let resolved = sql_functions::resolve_function(&object_name_to_string(&function.name))?;
+        let name = alias.unwrap_or_else(|| resolved.name.clone());
+        if computed
+            .iter()
+            .any(|column| column.name.eq_ignore_ascii_case(&name))
+        {
+            return Err(sql_error(format!("Duplicate computed column: {name}")));
+        }
+        computed.push(SqlComputedColumn {
+            name,
+            expr: expr.clone(),
+            column_type: resolved.return_type,
+        });
+    }
+    Ok(computed)
+}
+
+/// The plain positional arguments of a function call.
+fn function_args(function: &Function) -> Result<Vec<&Expr>> {
+    let unsupported = || {
+        sql_error(format!(
+            "Unsupported arguments to SQL function {}",
+            function.name
+        ))
+    };
+    if function.filter.is_some()
+        || function.over.is_some()
+        || !matches!(function.parameters, FunctionArguments::None)
+    {
+        return Err(unsupported());
+    }
+    match &function.args {
+        FunctionArguments::None => Ok(Vec::new()),
+        FunctionArguments::List(list)
+            if list.duplicate_treatment.is_none() && list.clauses.is_empty() =>
+        {
+            list.args
+                .iter()
+                .map(|arg| match arg {
+                    FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr),
+                    _ => Err(unsupported()),
+                })
+                .collect()
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+fn resolve_call(function: &Function) -> Result<(SqlFunction, Vec<&Expr>)> {
+    let resolved = sql_functions::resolve_function(&object_name_to_string(&function.name))?;
+    let args = function_args(function)?;
+    resolved.check_arity(args.len())?;
+    Ok((resolved, args))
+}
+
+/// Reject unknown or disabled functions before any rows are read.
+fn check_functions(expr: &Expr) -> Result<()> {
+    match expr {
+        Expr::Function(function) => {
+            let (_, args) = resolve_call(function)?;
+            args.into_iter().try_for_each(check_functions)
+        }
+        Expr::BinaryOp { left, right, .. } => {
+            check_functions(left)?;
+            check_functions(right)
+        }
+        Expr::Nested(inner)
+        | Expr::UnaryOp { expr: inner, .. }
+        | Expr::IsNull(inner)
+        | Expr::IsNotNull(inner) => check_functions(inner),
+        Expr::InList { expr, list, .. } => {
+            check_functions(expr)?;
+            list.iter().try_for_each(check_functions)
+        }
+        Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+            check_functions(expr)?;
+            check_functions(pattern)
+        }
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            check_functions(expr)?;
+            check_functions(low)?;
+            check_functions(high)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Rewrite `query` so every table it reads only yields rows whose `column`
+/// holds at least one of `allowed`.
+pub fn restrict_rows(query: &mut SqlQuery, column: &str, allowed: &[String]) {
+    let predicate = Expr::InList {
+        expr: Box::new(Expr::Identifier(Ident::new(column))),
+        list: allowed
+            .iter()
+            .map(|value| Expr::value(SqlValue::SingleQuotedString(value.clone())))
+            .collect(),
+        negated: false,
+    };
+    let tables =
+        std::iter::once(&mut query.from).chain(query.joins.iter_mut().map(|join| &mut join.table));
+    for table in tables {
+        table.row_filter = Some(match table.row_filter.take() {
+            Some(existing) => Expr::BinaryOp {
+                left: Box::new(existing),
+                op: BinaryOperator::And,
+                right: Box::new(predicate.clone()),
+            },
+            None => predicate.clone(),
+        });
+    }
+}
+
 pub fn filter_entries_by_sql(
     tables: &HashMap<String, Vec<Value>>,
     query: &SqlQuery,
 ) -> Result<Vec<Value>> {
-    let base_rows = table_rows(tables, &query.from.name)?;
+    filter_entries_by_sql_with_limits(tables, query, SqlLimits::default())
+}
 
-    let mut contexts: Vec<RowContext> = base_rows
-        .iter()
-        .map(|row| RowContext::new(&query.from, row.clone()))
-        .collect();
+/// Like [`filter_entries_by_sql`], failing with [`SqlLimitExceeded`] once the
+/// query runs past `limits`.
+pub fn filter_entries_by_sql_with_limits(
+    tables: &HashMap<String, Vec<Value>>,
+    query: &SqlQuery,
+    limits: SqlLimits,
+) -> Result<Vec<Value>> {
+    let mut budget = QueryBudget::new(limits);
+    let track_bytes = limits.max_bytes.is_some();
+    let base_rows = visible_rows(tables, &query.from, &mut budget)?;
+
+    let mut held_bytes = 0usize;
+    let mut context_bytes: Vec<usize> = Vec::with_capacity(base_rows.len());
+    let mut contexts: Vec<RowContext> = Vec::with_capacity(base_rows.len());
+    for row in base_rows {
+        budget.tick()?;
+        if track_bytes {
+            let bytes = row_bytes(row);
+            held_bytes += bytes;
+            budget.check_bytes(held_bytes)?;
+            context_bytes.push(bytes);
+        }
+        contexts.push(RowContext::new(&query.from, row.clone()));
+    }
 
     for join in &query.joins {
-        let join_rows = table_rows(tables, &join.table.name)?;
+        let join_rows = visible_rows(tables, &join.table, &mut budget)?;
+        let join_row_bytes: Vec<usize> = if track_bytes {
+            join_rows.iter().copied().map(row_bytes).collect()
+        } else {
+            Vec::new()
+        };
         let mut joined = Vec::new();
+        let mut joined_bytes: Vec<usize> = Vec::new();
         let mut right_matched = vec![false; join_rows.len()];
         let context_templates: Vec<RowContext> = contexts.into_iter().collect();
+        let template_bytes = std::mem::take(&mut context_bytes);
 
-        for context in context_templates.iter() {
+        for (context_idx, context) in context_templates.iter().enumerate() {
             let mut matched = false;
-            for (idx, row) in join_rows.iter().enumerate() {
+            for (idx, row) in join_rows.iter().copied().enumerate() {
+                budget.tick()?;
                 let mut next = context.clone();
                 next.add_table(&join.table, row.clone());
 
@@ -138,6 +532,12 @@ pub fn filter_entries_by_sql(
                 if matches {
                     matched = true;
                     right_matched[idx] = true;
+                    if track_bytes {
+                        let bytes = template_bytes[context_idx] + join_row_bytes[idx];
+                        held_bytes += bytes;
+                        budget.check_bytes(held_bytes)?;
+                        joined_bytes.push(bytes);
+                    }
                     joined.push(next);
                 }
             }
@@ -145,6 +545,9 @@ pub fn filter_entries_by_sql(
             if !matched && matches!(join.join_type, SqlJoinType::Left | SqlJoinType::Full) {
                 let mut next = context.clone();
                 next.add_table(&join.table, Value::Null);
+                if track_bytes {
+                    joined_bytes.push(template_bytes[context_idx]);
+                }
                 joined.push(next);
             }
         }
@@ -155,7 +558,7 @@ pub fn filter_entries_by_sql(
             } else {
                 RowContext::new(&query.from, Value::Null)
             };
-            for (idx, row) in join_rows.iter().enumerate() {
+            for (idx, row) in join_rows.iter().copied().enumerate() {
                 if right_matched[idx] {
                     continue;
                 }
@@ -164,15 +567,25 @@ pub fn filter_entries_by_sql(
                     *value = Value::Null;
                 }
                 next.add_table(&join.table, row.clone());
+                if track_bytes {
+                    let bytes = join_row_bytes[idx];
+                    held_bytes += bytes;
+                    budget.check_bytes(held_bytes)?;
+                    joined_bytes.push(bytes);
+                }
                 joined.push(next);
             }
         }
 
+        // The previous stage's rows are dropped once the join completes.
+        held_bytes -= template_bytes.iter().sum::<usize>();
         contexts = joined;
+        context_bytes = joined_bytes;
     }
 
     let mut filtered: Vec<RowContext> = Vec::new();
     for context in contexts.into_iter() {
+        budget.tick()?;
         if let Some(expr) = &query.selection {
             if !matches_expr(&context, expr)? {
                 continue;
@@ -187,6 +600,10 @@ pub fn filter_entries_by_sql(
             if sort_error.is_some() {
                 return Ordering::Equal;
             }
+            if let Err(err) = budget.tick() {
+                sort_error = Some(err);
+                return Ordering::Equal;
+            }
             match compare_rows(a, b, &query.order_by) {
                 Ok(ordering) => ordering,
                 Err(err) => {
@@ -203,10 +620,25 @@ pub fn filter_entries_by_sql(
     let effective_limit = query.limit.unwrap_or(MAX_QUERY_LIMIT);
     filtered.truncate(effective_limit);
 
-    Ok(filtered
+    filtered
         .into_iter()
-        .map(|context| context.to_value())
-        .collect())
+        .map(|context| {
+            let mut value = context.to_value();
+            if !query.computed.is_empty() {
+                let mut computed = serde_json::Map::new();
+                for column in &query.computed {
+                    computed.insert(
+                        column.name.clone(),
+                        resolve_operand(&context, &column.expr)?,
+                    );
+                }
+                if let Value::Object(map) = &mut value {
+                    map.extend(computed);
+                }
+            }
+            Ok(value)
+        })
+        .collect()
 }
 
 fn parse_limit(expr: &Expr) -> Result<usize> {
@@ -283,6 +715,7 @@ fn parse_table_ref(relation: &TableFactor) -> Result<SqlTableRef> {
         TableFactor::Table { name, alias, .. } => Ok(SqlTableRef {
             name: object_name_to_string(name),
             alias: alias.clone().map(|alias| alias.name.value),
+            row_filter: None,
         }),
         _ => Err(sql_error("Unsupported FROM clause (expected a table name)")),
     }
@@ -315,6 +748,26 @@ fn parse_join(join: &Join) -> Result<SqlJoin> {
     })
 }
 
+/// Rows of `table` that pass its row filter.
+fn visible_rows<'a>(
+    tables: &'a HashMap<String, Vec<Value>>,
+    table: &SqlTableRef,
+    budget: &mut QueryBudget,
+) -> Result<Vec<&'a Value>> {
+    let rows = table_rows(tables, &table.name)?;
+    let Some(filter) = &table.row_filter else {
+        return Ok(rows.iter().collect());
+    };
+    let mut visible = Vec::new();
+    for row in rows {
+        budget.tick()?;
+        if matches_expr(&RowContext::new(table, row.clone()), filter)? {
+            visible.push(row);
+        }
+    }
+    Ok(visible)
+}
+
 fn table_rows<'a>(tables: &'a HashMap<String, Vec<Value>>, name: &str) -> Result<&'a Vec<Value>> {
     let key = name.to_lowercase();
     tables
@@ -424,6 +877,7 @@ fn matches_expr(context: &RowContext, expr: &Expr) -> Result<bool> {
             _ => Err(sql_error(format!("Unsupported SQL operator: {op}"))),
         },
         Expr::Nested(inner) => matches_expr(context, inner),
+        Expr::Function(_) => Ok(resolve_operand(context, expr)?.as_bool() == Some(true)),
         Expr::UnaryOp { op, expr } if op.to_string().to_lowercase() == "not" => {
             Ok(!matches_expr(context, expr)?)
         }
@@ -500,6 +954,15 @@ fn resolve_operand(context: &RowContext, expr: &Expr) -> Result<Value> {
         Expr::Identifier(ident) => Ok(resolve_identifier(context, std::slice::from_ref(ident))),
         Expr::CompoundIdentifier(idents) => Ok(resolve_identifier(context, idents)),
         Expr::Value(value) => Ok(sql_value_to_json(value)),
+        Expr::Nested(inner) => resolve_operand(context, inner),
+        Expr::Function(function) => {
+            let (resolved, args) = resolve_call(function)?;
+            let values = args
+                .into_iter()
+                .map(|arg| resolve_operand(context, arg))
+                .collect::<Result<Vec<_>>>()?;
+            resolved.call(&values)
+        }
         Expr::UnaryOp { op, expr } if op.to_string() == "-" => {
             let value = resolve_operand(context, expr)?;
             if let Some(n) = value.as_f64() {
@@ -674,6 +1137,15 @@ fn values_equal(left: &Value, right: &Value) -> bool {
             .iter()
             .any(|item| item == &Value::String(expected.to_string()));
     }
+    if let (Some(left_num), Some(right_num)) = (left.as_f64(), right.as_f64()) {
+        return left_num == right_num;
+    }
+    if let Some(ordering) = temporal::compare_instants(left, right) {
+        return ordering == Ordering::Equal;
+    }
+    if let Some(ordering) = decimal::compare_decimals(left, right) {
+        return ordering == Ordering::Equal;
+    }
     left == right
 }
 
@@ -681,6 +1153,12 @@ fn compare_order(left: &Value, right: &Value) -> Option<Ordering> {
     if let (Some(left_num), Some(right_num)) = (left.as_f64(), right.as_f64()) {
         return left_num.partial_cmp(&right_num);
     }
+    if let Some(ordering) = temporal::compare_instants(left, right) {
+        return Some(ordering);
+    }
+    if let Some(ordering) = decimal::compare_decimals(left, right) {
+        return Some(ordering);
+    }
     if let (Some(left_str), Some(right_str)) = (left.as_str(), right.as_str()) {
         return Some(left_str.cmp(right_str));
     }
@@ -731,7 +1209,10 @@ fn compare_rows(
     order_by: &[OrderByExpr],
 ) -> Result<Ordering> {
     for order in order_by {
-        let (Expr::Identifier(_) | Expr::CompoundIdentifier(_) | Expr::Value(_)) = &order.expr
+        let (Expr::Identifier(_)
+        | Expr::CompoundIdentifier(_)
+        | Expr::Value(_)
+        | Expr::Function(_)) = &order.expr
         else {
             return Err(sql_error("Unsupported ORDER BY expression"));
         };
@@ -0,0 +1,355 @@
+use crate::geo::{BoundingBox, GeoPoint};
+use crate::sql::{sql_error, SqlColumnType};
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveDateTime};
+use pulldown_cmark::{Event, Options, Parser, TagEnd};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Comma-separated extensions whose functions queries may call.
+pub const EXTENSIONS_ENV: &str = "UGOITE_SQL_EXTENSIONS";
+
+type SqlFunctionBody = Arc<dyn Fn(&[Value]) -> Result<Value> + Send + Sync>;
+
+/// A scalar function callable from Ugoite SQL. Arguments arrive already
+/// resolved against the row; `NULL` is passed as [`Value::Null`].
+#[derive(Clone)]
+pub struct SqlFunction {
+    pub name: String,
+    pub min_args: usize,
+    pub max_args: usize,
+    pub return_type: SqlColumnType,
+    body: SqlFunctionBody,
+}
+
+impl std::fmt::Debug for SqlFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqlFunction")
+            .field("name", &self.name)
+            .field("min_args", &self.min_args)
+            .field("max_args", &self.max_args)
+            .field("return_type", &self.return_type)
+            .finish()
+    }
+}
+
+impl SqlFunction {
+    pub fn new(
+        name: &str,
+        args: std::ops::RangeInclusive<usize>,
+        return_type: SqlColumnType,
+        body: impl Fn(&[Value]) -> Result<Value> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.to_ascii_lowercase(),
+            min_args: *args.start(),
+            max_args: *args.end(),
+            return_type,
+            body: Arc::new(body),
+        }
+    }
+
+    pub fn call(&self, args: &[Value]) -> Result<Value> {
+        self.check_arity(args.len())?;
+        (self.body)(args)
+    }
+
+    pub(crate) fn check_arity(&self, count: usize) -> Result<()> {
+        if count < self.min_args || count > self.max_args {
+            let expected = if self.min_args == self.max_args {
+                self.min_args.to_string()
+            } else {
+                format!("{} to {}", self.min_args, self.max_args)
+            };
+            return Err(sql_error(format!(
+                "{}() takes {} arguments, got {}",
+                self.name, expected, count
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct ExtensionRegistry {
+    /// Function name to the extension that provides it.
+    functions: HashMap<String, (String, SqlFunction)>,
+    /// Overrides [`EXTENSIONS_ENV`] once set.
+    enabled: Option<BTreeSet<String>>,
+}
+
+fn extension_registry() -> &'static RwLock<ExtensionRegistry> {
+    static REGISTRY: OnceLock<RwLock<ExtensionRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(ExtensionRegistry::default()))
+}
+
+fn builtin_functions() -> &'static HashMap<String, SqlFunction> {
+    static BUILTINS: OnceLock<HashMap<String, SqlFunction>> = OnceLock::new();
+    BUILTINS.get_or_init(|| {
+        [
+            SqlFunction::new("entry_link", 1..=1, SqlColumnType::String, entry_link),
+            SqlFunction::new(
+                "markdown_excerpt",
+                2..=2,
+                SqlColumnType::String,
+                markdown_excerpt,
+            ),
+            SqlFunction::new("date_trunc", 2..=2, SqlColumnType::Date, date_trunc),
+            SqlFunction::new("date_part", 2..=2, SqlColumnType::Int, date_part),
+            SqlFunction::new("geo_distance", 3..=3, SqlColumnType::Float, geo_distance),
+            SqlFunction::new("geo_within_box", 5..=5, SqlColumnType::Bool, geo_within_box),
+        ]
+        .into_iter()
+        .map(|function| (function.name.clone(), function))
+        .collect()
+    })
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_')
+}
+
+/// Register the functions of an extension. They stay uncallable until the
+/// extension is enabled through [`EXTENSIONS_ENV`] or
+/// [`set_enabled_extensions`]; queries can never load extensions themselves.
+pub fn register_extension(extension: &str, functions: Vec<SqlFunction>) -> Result<()> {
+    if !valid_name(extension) {
+        return Err(sql_error(format!(
+            "Invalid SQL extension name: {extension}"
+        )));
+    }
+    let mut registry = extension_registry()
+        .write()
+        .map_err(|_| sql_error("SQL extension registry is poisoned"))?;
+    for function in &functions {
+        if !valid_name(&function.name) || builtin_functions().contains_key(&function.name) {
+            return Err(sql_error(format!(
+                "Invalid SQL function name: {}",
+                function.name
+            )));
+        }
+        if let Some((owner, _)) = registry.functions.get(&function.name) {
+            if owner != extension {
+                return Err(sql_error(format!(
+                    "SQL function {} is already provided by extension {}",
+                    function.name, owner
+                )));
+            }
+        }
+    }
+    registry
+        .functions
+        .retain(|_, (owner, _)| owner != extension);
+    for function in functions {
+        registry
+            .functions
+            .insert(function.name.clone(), (extension.to_string(), function));
+    }
+    Ok(())
+}
+
+/// Replace the extensions enabled by [`EXTENSIONS_ENV`].
+pub fn set_enabled_extensions(extensions: &[&str]) {
+    if let Ok(mut registry) = extension_registry().write() {
+        registry.enabled = Some(
+            extensions
+                .iter()
+                .map(|name| name.trim().to_ascii_lowercase())
+                .collect(),
+        );
+    }
+}
+
+/// Extensions whose functions queries may currently call.
+pub fn enabled_extensions() -> Vec<String> {
+    let configured = extension_registry()
+        .read()
+        .ok()
+        .and_then(|registry| registry.enabled.clone());
+    configured
+        .unwrap_or_else(|| {
+            std::env::var(EXTENSIONS_ENV)
+                .unwrap_or_default()
+                .split(',')
+                .map(|name| name.trim().to_ascii_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect()
+        })
+        .into_iter()
+        .collect()
+}
+
+/// Look up a callable function by its case-insensitive name.
+pub fn resolve_function(name: &str) -> Result<SqlFunction> {
+    let key = name.to_ascii_lowercase();
+    if let Some(function) = builtin_functions().get(&key) {
+        return Ok(function.clone());
+    }
+    let registered = extension_registry()
+        .read()
+        .map_err(|_| sql_error("SQL extension registry is poisoned"))?
+        .functions
+        .get(&key)
+        .cloned();
+    match registered {
+        Some((extension, function)) => {
+            if enabled_extensions().contains(&extension) {
+                Ok(function)
+            } else {
+                Err(sql_error(format!(
+                    "SQL function {key} requires extension {extension}, which is not enabled"
+                )))
+            }
+        }
+        None => Err(sql_error(format!("Unknown SQL function: {name}"))),
+    }
+}
+
+fn entry_link(args: &[Value]) -> Result<Value> {
+    Ok(match &args[0] {
+        Value::String(id) if !id.is_empty() => Value::String(format!("ugoite://entry/{id}")),
+        _ => Value::Null,
+    })
+}
+
+fn excerpt_length(value: &Value) -> Result<usize> {
+    value
+        .as_f64()
+        .filter(|n| *n >= 0.0 && n.fract() == 0.0)
+        .map(|n| n as usize)
+        .ok_or_else(|| sql_error("markdown_excerpt() length must be a non-negative integer"))
+}
+
+/// Plain text of `markdown`, whitespace collapsed.
+fn markdown_text(markdown: &str) -> String {
+    let mut text = String::new();
+    for event in Parser::new_ext(markdown, Options::ENABLE_TABLES) {
+        match event {
+            Event::Text(chunk) | Event::Code(chunk) => text.push_str(&chunk),
+            Event::SoftBreak
+            | Event::HardBreak
+            | Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item) => text.push(' '),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn markdown_excerpt(args: &[Value]) -> Result<Value> {
+    let length = excerpt_length(&args[1])?;
+    let Some(markdown) = args[0].as_str() else {
+        return Ok(Value::Null);
+    };
+    let text = markdown_text(markdown);
+    if text.chars().count() <= length {
+        return Ok(Value::String(text));
+    }
+    let cut: String = text.chars().take(length).collect();
+    // Prefer ending on a word boundary when one is close enough.
+    let trimmed = match cut.rfind(' ') {
+        Some(space) if space * 2 >= cut.len() => &cut[..space],
+        _ => cut.as_str(),
+    };
+    Ok(Value::String(format!("{}…", trimmed.trim_end())))
+}
+
+/// Day of a date-like value: epoch seconds, `YYYY-MM-DD` or a timestamp.
+fn value_date(value: &Value) -> Option<NaiveDate> {
+    match value {
+        Value::Number(number) => {
+            let seconds = number.as_f64()?;
+            DateTime::from_timestamp_millis((seconds * 1000.0) as i64).map(|ts| ts.date_naive())
+        }
+        Value::String(text) => {
+            let text = text.trim();
+            DateTime::parse_from_rfc3339(text)
+                .map(|ts| ts.date_naive())
+                .ok()
+                .or_else(|| {
+                    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+                        .iter()
+                        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+                        .map(|ts| ts.date())
+                })
+                .or_else(|| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok())
+        }
+        _ => None,
+    }
+}
+
+fn date_field(args: &[Value], function: &str) -> Result<String> {
+    args[0]
+        .as_str()
+        .map(|part| part.trim().to_ascii_lowercase())
+        .ok_or_else(|| sql_error(format!("{function}() needs a date part name")))
+}
+
+fn date_trunc(args: &[Value]) -> Result<Value> {
+    let part = date_field(args, "date_trunc")?;
+    let Some(day) = value_date(&args[1]) else {
+        return Ok(Value::Null);
+    };
+    let start = match part.as_str() {
+        "day" => Some(day),
+        "week" => day.checked_sub_days(Days::new(u64::from(day.weekday().num_days_from_monday()))),
+        "month" => day.with_day(1),
+        "quarter" => NaiveDate::from_ymd_opt(day.year(), (day.month0() / 3) * 3 + 1, 1),
+        "year" => NaiveDate::from_ymd_opt(day.year(), 1, 1),
+        other => return Err(sql_error(format!("Unknown date_trunc() part: {other}"))),
+    };
+    Ok(start
+        .map(|start| Value::String(start.format("%Y-%m-%d").to_string()))
+        .unwrap_or(Value::Null))
+}
+
+fn date_part(args: &[Value]) -> Result<Value> {
+    let part = date_field(args, "date_part")?;
+    let Some(day) = value_date(&args[1]) else {
+        return Ok(Value::Null);
+    };
+    let value: i64 = match part.as_str() {
+        "year" => day.year().into(),
+        "quarter" => (day.month0() / 3 + 1).into(),
+        "month" => day.month().into(),
+        "week" => day.iso_week().week().into(),
+        "day" => day.day().into(),
+        "isodow" => day.weekday().number_from_monday().into(),
+        other => return Err(sql_error(format!("Unknown date_part() part: {other}"))),
+    };
+    Ok(Value::from(value))
+}
+
+fn geo_number(value: &Value, function: &str) -> Result<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| sql_error(format!("{function}() coordinates must be numbers")))
+}
+
+/// Kilometres from a geopoint to `lat`/`lon`.
+fn geo_distance(args: &[Value]) -> Result<Value> {
+    let lat = geo_number(&args[1], "geo_distance")?;
+    let lon = geo_number(&args[2], "geo_distance")?;
+    let center =
+        GeoPoint::new(lat, lon).ok_or_else(|| sql_error("geo_distance() point is out of range"))?;
+    Ok(GeoPoint::from_value(&args[0])
+        .and_then(|point| serde_json::Number::from_f64(point.distance_km(center)))
+        .map(Value::Number)
+        .unwrap_or(Value::Null))
+}
+
+/// Whether a geopoint lies in the `south, west, north, east` box.
+fn geo_within_box(args: &[Value]) -> Result<Value> {
+    let bounds = args[1..]
+        .iter()
+        .map(|value| geo_number(value, "geo_within_box"))
+        .collect::<Result<Vec<_>>>()?;
+    let bbox = BoundingBox::new(bounds[0], bounds[1], bounds[2], bounds[3])
+        .map_err(|e| sql_error(format!("geo_within_box(): {e}")))?;
+    Ok(Value::Bool(
+        GeoPoint::from_value(&args[0]).is_some_and(|point| bbox.contains(point)),
+    ))
+}
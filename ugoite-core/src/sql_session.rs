@@ -1,15 +1,67 @@
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Duration, Utc};
 use opendal::Operator;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use uuid::Uuid;
 
+use crate::entry_acl::EntryViewer;
 use crate::index;
 use crate::materialized_view;
+use crate::result_buffer::{self, ResultBuffer, ResultSet};
 use crate::saved_sql;
+use crate::space;
+use crate::sql;
+use crate::storage;
 
 const SESSION_DIR: &str = "sql_sessions";
 
+pub const TIMEOUT_MS_ENV: &str = "UGOITE_SQL_SESSION_TIMEOUT_MS";
+pub const MAX_BYTES_ENV: &str = "UGOITE_SQL_SESSION_MAX_BYTES";
+/// Wall-clock budget for one execution of a session's query.
+pub const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+/// Approximate bytes of rows one execution may hold while joining and filtering.
+pub const DEFAULT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Execution limits recorded in a session's metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqlSessionLimits {
+    pub timeout_ms: u64,
+    pub max_bytes: u64,
+}
+
+impl SqlSessionLimits {
+    /// Limits from `UGOITE_SQL_SESSION_TIMEOUT_MS` and `UGOITE_SQL_SESSION_MAX_BYTES`.
+    pub fn from_env() -> Self {
+        let read = |name: &str, default: u64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|raw| raw.trim().parse::<u64>().ok())
+                .filter(|value| *value > 0)
+                .unwrap_or(default)
+        };
+        Self {
+            timeout_ms: read(TIMEOUT_MS_ENV, DEFAULT_TIMEOUT_MS),
+            max_bytes: read(MAX_BYTES_ENV, DEFAULT_MAX_BYTES),
+        }
+    }
+
+    /// Keep the tighter of each limit, so callers can only lower the server's caps.
+    pub fn tightened(self, requested: Self) -> Self {
+        Self {
+            timeout_ms: self.timeout_ms.min(requested.timeout_ms.max(1)),
+            max_bytes: self.max_bytes.min(requested.max_bytes.max(1)),
+        }
+    }
+
+    fn to_sql_limits(self) -> sql::SqlLimits {
+        sql::SqlLimits {
+            timeout: Some(std::time::Duration::from_millis(self.timeout_ms)),
+            max_bytes: Some(usize::try_from(self.max_bytes).unwrap_or(usize::MAX)),
+        }
+    }
+}
+
 fn sessions_root(ws_path: &str) -> String {
     format!("{}/{}", ws_path.trim_end_matches('/'), SESSION_DIR)
 }
@@ -27,6 +79,30 @@ fn meta_path(ws_path: &str, session_id: &str) -> String {
     format!("{}/meta.json", session_path(ws_path, session_id))
 }
 
+fn spill_dir(ws_path: &str, session_id: &str) -> String {
+    format!(
+        "{}/spill/{}",
+        session_path(ws_path, session_id),
+        Uuid::new_v4()
+    )
+}
+
+/// Move rows into a memory-capped buffer that spills to the session directory.
+async fn buffer_rows(
+    op: &Operator,
+    ws_path: &str,
+    session_id: &str,
+    rows: Vec<Value>,
+) -> Result<ResultSet> {
+    let mut buffer = ResultBuffer::new(
+        op,
+        &spill_dir(ws_path, session_id),
+        result_buffer::max_bytes_from_env(),
+    );
+    buffer.extend(rows).await?;
+    Ok(buffer.finish())
+}
+
 async fn ensure_sessions_dir(op: &Operator, ws_path: &str) -> Result<()> {
     let root = format!("{}/", sessions_root(ws_path));
     if !op.exists(&root).await? {
@@ -36,7 +112,7 @@ async fn ensure_sessions_dir(op: &Operator, ws_path: &str) -> Result<()> {
 }
 
 async fn write_json(op: &Operator, path: &str, value: &Value) -> Result<()> {
-    op.write(path, serde_json::to_vec_pretty(value)?).await?;
+    storage::publish_atomic(op, path, serde_json::to_vec_pretty(value)?).await?;
     Ok(())
 }
 
@@ -45,19 +121,6 @@ async fn read_json(op: &Operator, path: &str) -> Result<Value> {
     Ok(serde_json::from_slice(&bytes.to_vec())?)
 }
 
-fn space_id_from_ws_path(ws_path: &str) -> String {
-    let trimmed = ws_path.trim_end_matches('/');
-    if trimmed.is_empty() {
-        return String::new();
-    }
-    if let Some((_, space_id)) = trimmed.rsplit_once('/') {
-        if !space_id.is_empty() {
-            return space_id.to_string();
-        }
-    }
-    trimmed.to_string()
-}
-
 fn is_expired(meta: &Value) -> bool {
     let expires_at = match meta.get("expires_at").and_then(|v| v.as_str()) {
         Some(value) => value,
@@ -83,12 +146,17 @@ fn session_sql(meta: &Value) -> Result<&str> {
         .ok_or_else(|| anyhow!("SQL session missing sql"))
 }
 
+fn session_limits(meta: &Value) -> SqlSessionLimits {
+    let env = SqlSessionLimits::from_env();
+    meta.get("limits")
+        .cloned()
+        .and_then(|limits| serde_json::from_value(limits).ok())
+        .map(|limits| env.tightened(limits))
+        .unwrap_or(env)
+}
+
 async fn execute_session_sql(op: &Operator, ws_path: &str, session_id: &str) -> Result<Vec<Value>> {
-    let meta = load_session_meta(op, ws_path, session_id).await?;
-    if meta.get("status").and_then(|v| v.as_str()) == Some("expired") {
-        return Err(anyhow!("SQL session expired"));
-    }
-    index::execute_sql_query(op, ws_path, session_sql(&meta)?).await
+    execute_limited(op, ws_path, session_id, None, None).await
 }
 
 async fn execute_session_sql_scoped(
@@ -97,23 +165,89 @@ async fn execute_session_sql_scoped(
     session_id: &str,
     readable_forms: &[String],
     include_untyped_entries: bool,
+    viewer: Option<&EntryViewer>,
 ) -> Result<Vec<Value>> {
-    let meta = load_session_meta(op, ws_path, session_id).await?;
-    if meta.get("status").and_then(|v| v.as_str()) == Some("expired") {
-        return Err(anyhow!("SQL session expired"));
+    execute_limited(
+        op,
+        ws_path,
+        session_id,
+        Some((readable_forms, include_untyped_entries)),
+        viewer,
+    )
+    .await
+}
+
+/// Run the session's query under its limits; a query that exceeds them
+/// moves the session to `failed` with a `limit_exceeded` error.
+async fn execute_limited(
+    op: &Operator,
+    ws_path: &str,
+    session_id: &str,
+    scope: Option<(&[String], bool)>,
+    viewer: Option<&EntryViewer>,
+) -> Result<Vec<Value>> {
+    let mut meta = load_session_meta(op, ws_path, session_id).await?;
+    match meta.get("status").and_then(|v| v.as_str()) {
+        Some("expired") => return Err(anyhow!("SQL session expired")),
+        Some("failed") => {
+            let message = meta
+                .pointer("/error/message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(anyhow!("SQL session failed: {message}"));
+        }
+        _ => {}
     }
-    index::execute_sql_query_scoped(
+    let limits = session_limits(&meta);
+    let result = index::execute_sql_query_for_viewer(
         op,
         ws_path,
         session_sql(&meta)?,
-        readable_forms,
-        include_untyped_entries,
+        scope,
+        viewer,
+        limits.to_sql_limits(),
     )
-    .await
+    .await;
+    if let Err(err) = &result {
+        if let Some(exceeded) = err.downcast_ref::<sql::SqlLimitExceeded>() {
+            meta["status"] = Value::String("failed".to_string());
+            meta["error"] = json!({
+                "reason": "limit_exceeded",
+                "limit": exceeded.kind,
+                "value": exceeded.limit,
+                "message": exceeded.to_string(),
+            });
+            write_json(op, &meta_path(ws_path, session_id), &meta).await?;
+        }
+    }
+    result
+}
+
+async fn session_columns(
+    op: &Operator,
+    ws_path: &str,
+    session_id: &str,
+    readable_forms: Option<&[String]>,
+) -> Result<Vec<sql::SqlColumn>> {
+    let meta = load_session_meta(op, ws_path, session_id).await?;
+    index::sql_result_columns(op, ws_path, session_sql(&meta)?, readable_forms).await
 }
 
 pub async fn create_sql_session(op: &Operator, ws_path: &str, sql: &str) -> Result<Value> {
+    create_sql_session_with_limits(op, ws_path, sql, None).await
+}
+
+/// Create a session whose executions run under `limits`, clamped to the
+/// server-wide limits from the environment.
+pub async fn create_sql_session_with_limits(
+    op: &Operator,
+    ws_path: &str,
+    sql: &str,
+    limits: Option<SqlSessionLimits>,
+) -> Result<Value> {
     ensure_sessions_dir(op, ws_path).await?;
+    let env_limits = SqlSessionLimits::from_env();
+    let limits = limits.map_or(env_limits, |requested| env_limits.tightened(requested));
 
     let session_id = Uuid::new_v4().to_string();
     let session_dir = format!("{}/", session_path(ws_path, &session_id));
@@ -142,7 +276,7 @@ pub async fn create_sql_session(op: &Operator, ws_path: &str, sql: &str) -> Resu
     let now = Utc::now();
     let expires_at = (now + Duration::minutes(10)).to_rfc3339();
     let created_at = now.to_rfc3339();
-    let space_id = space_id_from_ws_path(ws_path);
+    let space_id = space::space_id_from_ws_path(ws_path);
 
     let meta = json!({
         "id": session_id,
@@ -153,6 +287,7 @@ pub async fn create_sql_session(op: &Operator, ws_path: &str, sql: &str) -> Resu
         "created_at": created_at,
         "expires_at": expires_at,
         "error": Value::Null,
+        "limits": limits,
         "view": {
             "sql_id": sql_id,
             "snapshot_id": snapshot_id,
@@ -195,6 +330,7 @@ pub async fn get_sql_session_count_scoped(
     session_id: &str,
     readable_forms: &[String],
     include_untyped_entries: bool,
+    viewer: Option<&EntryViewer>,
 ) -> Result<u64> {
     let rows = execute_session_sql_scoped(
         op,
@@ -202,6 +338,7 @@ pub async fn get_sql_session_count_scoped(
         session_id,
         readable_forms,
         include_untyped_entries,
+        viewer,
     )
     .await?;
     Ok(rows.len() as u64)
@@ -215,6 +352,7 @@ pub async fn get_sql_session_rows(
     limit: usize,
 ) -> Result<Value> {
     let rows = execute_session_sql(op, ws_path, session_id).await?;
+    let columns = session_columns(op, ws_path, session_id, None).await?;
     let total = rows.len();
     let start = offset.min(total);
     let end = (offset + limit).min(total);
@@ -222,12 +360,14 @@ pub async fn get_sql_session_rows(
 
     Ok(serde_json::json!({
         "rows": slice,
+        "columns": columns,
         "offset": offset,
         "limit": limit,
         "total_count": total,
     }))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn get_sql_session_rows_scoped(
     op: &Operator,
     ws_path: &str,
@@ -236,6 +376,7 @@ pub async fn get_sql_session_rows_scoped(
     limit: usize,
     readable_forms: &[String],
     include_untyped_entries: bool,
+    viewer: Option<&EntryViewer>,
 ) -> Result<Value> {
     let rows = execute_session_sql_scoped(
         op,
@@ -243,8 +384,10 @@ pub async fn get_sql_session_rows_scoped(
         session_id,
         readable_forms,
         include_untyped_entries,
+        viewer,
     )
     .await?;
+    let columns = session_columns(op, ws_path, session_id, Some(readable_forms)).await?;
     let total = rows.len();
     let start = offset.min(total);
     let end = (offset + limit).min(total);
@@ -252,12 +395,32 @@ pub async fn get_sql_session_rows_scoped(
 
     Ok(serde_json::json!({
         "rows": slice,
+        "columns": columns,
         "offset": offset,
         "limit": limit,
         "total_count": total,
     }))
 }
 
+/// Typed columns of the session's rows, for exports that read rows in bulk.
+pub async fn get_sql_session_columns(
+    op: &Operator,
+    ws_path: &str,
+    session_id: &str,
+) -> Result<Vec<sql::SqlColumn>> {
+    session_columns(op, ws_path, session_id, None).await
+}
+
+/// Typed columns of the session's rows, limited to the caller's readable forms.
+pub async fn get_sql_session_columns_scoped(
+    op: &Operator,
+    ws_path: &str,
+    session_id: &str,
+    readable_forms: &[String],
+) -> Result<Vec<sql::SqlColumn>> {
+    session_columns(op, ws_path, session_id, Some(readable_forms)).await
+}
+
 pub async fn get_sql_session_rows_all(
     op: &Operator,
     ws_path: &str,
@@ -266,12 +429,24 @@ pub async fn get_sql_session_rows_all(
     execute_session_sql(op, ws_path, session_id).await
 }
 
+/// All rows of a session behind a memory cap; callers read them back batch by
+/// batch and must `cleanup` the result set afterwards.
+pub async fn open_sql_session_rows(
+    op: &Operator,
+    ws_path: &str,
+    session_id: &str,
+) -> Result<ResultSet> {
+    let rows = execute_session_sql(op, ws_path, session_id).await?;
+    buffer_rows(op, ws_path, session_id, rows).await
+}
+
 pub async fn get_sql_session_rows_all_scoped(
     op: &Operator,
     ws_path: &str,
     session_id: &str,
     readable_forms: &[String],
     include_untyped_entries: bool,
+    viewer: Option<&EntryViewer>,
 ) -> Result<Vec<Value>> {
     execute_session_sql_scoped(
         op,
@@ -279,6 +454,53 @@ pub async fn get_sql_session_rows_all_scoped(
         session_id,
         readable_forms,
         include_untyped_entries,
+        viewer,
     )
     .await
 }
+
+pub async fn open_sql_session_rows_scoped(
+    op: &Operator,
+    ws_path: &str,
+    session_id: &str,
+    readable_forms: &[String],
+    include_untyped_entries: bool,
+    viewer: Option<&EntryViewer>,
+) -> Result<ResultSet> {
+    let rows = execute_session_sql_scoped(
+        op,
+        ws_path,
+        session_id,
+        readable_forms,
+        include_untyped_entries,
+        viewer,
+    )
+    .await?;
+    buffer_rows(op, ws_path, session_id, rows).await
+}
+
+/// Delete the directories of expired sessions, spilled rows included, and
+/// of sessions left without metadata. Returns how many were removed.
+pub(crate) async fn purge_expired_sessions(op: &Operator, ws_path: &str) -> Result<usize> {
+    let root = format!("{}/", sessions_root(ws_path));
+    if !op.exists(&root).await? {
+        return Ok(0);
+    }
+    let mut removed = 0;
+    for item in op.list(&root).await? {
+        let session_id = item.name().trim_end_matches('/');
+        if !item.metadata().is_dir() || session_id.is_empty() || item.path() == root {
+            continue;
+        }
+        let expired = match read_json(op, &meta_path(ws_path, session_id)).await {
+            Ok(meta) => is_expired(&meta),
+            Err(_) => true,
+        };
+        if expired {
+            op.remove_all(&format!("{}/", session_path(ws_path, session_id)))
+                .await?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
@@ -0,0 +1,357 @@
+use anyhow::{anyhow, Result};
+use opendal::raw::oio;
+use opendal::raw::*;
+use opendal::services::Memory;
+use opendal::{Buffer, Error, ErrorKind, Metadata, Operator};
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const TEST_SCHEME: &str = "test://";
+
+/// Faults injected by a [`FaultLayer`], all drawn from one seeded RNG.
+///
+/// Operations issued one at a time see the same faults for the same seed;
+/// concurrent ones draw in whatever order they reach the RNG.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct FaultConfig {
+    #[serde(default)]
+    pub seed: u64,
+    /// Probability that an operation fails with a temporary error, like an HTTP 500.
+    #[serde(default)]
+    pub error_rate: f64,
+    /// Upper bound of the random delay added before each operation.
+    #[serde(default)]
+    pub max_latency_ms: u64,
+    /// Probability that a write stores only a prefix of its bytes and then fails.
+    #[serde(default)]
+    pub partial_write_rate: f64,
+    /// How many reads, stats or listings miss a newly created object before it
+    /// becomes visible.
+    #[serde(default)]
+    pub stale_reads: u32,
+}
+
+impl FaultConfig {
+    /// Parse `test://{name}?seed=..&error_rate=..&max_latency_ms=..&partial_write_rate=..&stale_reads=..`.
+    pub fn from_uri(uri: &str) -> Result<Self> {
+        if !uri.starts_with(TEST_SCHEME) {
+            return Err(anyhow!(
+                "Fault injection URIs must start with {}",
+                TEST_SCHEME
+            ));
+        }
+        let parsed = url::Url::parse(uri)?;
+        let mut config = Self::default();
+        for (key, value) in parsed.query_pairs() {
+            let invalid = || anyhow!("Invalid value for {}: {}", key, value);
+            match key.as_ref() {
+                "seed" => config.seed = value.parse().map_err(|_| invalid())?,
+                "error_rate" => config.error_rate = value.parse().map_err(|_| invalid())?,
+                "max_latency_ms" => config.max_latency_ms = value.parse().map_err(|_| invalid())?,
+                "partial_write_rate" => {
+                    config.partial_write_rate = value.parse().map_err(|_| invalid())?
+                }
+                "stale_reads" => config.stale_reads = value.parse().map_err(|_| invalid())?,
+                _ => return Err(anyhow!("Unknown fault option: {}", key)),
+            }
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for (name, rate) in [
+            ("error_rate", self.error_rate),
+            ("partial_write_rate", self.partial_write_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(anyhow!("{} must be between 0 and 1", name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How many faults of each kind were injected so far.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct FaultStats {
+    pub errors: u64,
+    pub partial_writes: u64,
+    pub stale_reads: u64,
+    pub delays: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    errors: AtomicU64,
+    partial_writes: AtomicU64,
+    stale_reads: AtomicU64,
+    delays: AtomicU64,
+}
+
+#[derive(Debug)]
+struct FaultState {
+    config: FaultConfig,
+    rng: Mutex<StdRng>,
+    /// Newly created paths and how many more lookups miss them.
+    invisible: Mutex<HashMap<String, u32>>,
+    counters: Counters,
+}
+
+impl FaultState {
+    fn roll(&self, rate: f64) -> bool {
+        rate > 0.0
+            && self
+                .rng
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .random::<f64>()
+                < rate
+    }
+
+    async fn before(&self, operation: &'static str) -> opendal::Result<()> {
+        if self.config.max_latency_ms > 0 {
+            let delay = self
+                .rng
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .random_range(0..=self.config.max_latency_ms);
+            if delay > 0 {
+                self.counters.delays.fetch_add(1, Ordering::Relaxed);
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+            }
+        }
+        if self.roll(self.config.error_rate) {
+            self.counters.errors.fetch_add(1, Ordering::Relaxed);
+            return Err(Error::new(ErrorKind::Unexpected, "injected storage fault")
+                .with_operation(operation)
+                .set_temporary());
+        }
+        Ok(())
+    }
+
+    /// Whether `path` is still hidden, consuming one stale lookup if so.
+    fn hidden(&self, path: &str) -> bool {
+        let mut invisible = self.invisible.lock().unwrap_or_else(|e| e.into_inner());
+        match invisible.get_mut(path) {
+            Some(remaining) => {
+                *remaining -= 1;
+                if *remaining == 0 {
+                    invisible.remove(path);
+                }
+                self.counters.stale_reads.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn hide(&self, path: &str) {
+        if self.config.stale_reads > 0 {
+            self.invisible
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(path.to_string(), self.config.stale_reads);
+        }
+    }
+
+    fn not_found(path: &str) -> Error {
+        Error::new(ErrorKind::NotFound, "object is not visible yet").with_context("path", path)
+    }
+}
+
+/// OpenDAL layer injecting latency, temporary errors, partial writes and
+/// read-after-write staleness into the wrapped backend.
+#[derive(Debug, Clone)]
+pub struct FaultLayer {
+    state: Arc<FaultState>,
+}
+
+impl FaultLayer {
+    pub fn new(config: FaultConfig) -> Self {
+        Self {
+            state: Arc::new(FaultState {
+                rng: Mutex::new(StdRng::seed_from_u64(config.seed)),
+                config,
+                invisible: Mutex::new(HashMap::new()),
+                counters: Counters::default(),
+            }),
+        }
+    }
+
+    pub fn stats(&self) -> FaultStats {
+        let counters = &self.state.counters;
+        FaultStats {
+            errors: counters.errors.load(Ordering::Relaxed),
+            partial_writes: counters.partial_writes.load(Ordering::Relaxed),
+            stale_reads: counters.stale_reads.load(Ordering::Relaxed),
+            delays: counters.delays.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl<A: Access> Layer<A> for FaultLayer {
+    type LayeredAccess = FaultAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        FaultAccessor {
+            inner,
+            state: self.state.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FaultAccessor<A> {
+    inner: A,
+    state: Arc<FaultState>,
+}
+
+impl<A: Access> LayeredAccess for FaultAccessor<A> {
+    type Inner = A;
+    type Reader = A::Reader;
+    type Writer = FaultWriter<A::Writer>;
+    type Lister = FaultLister<A::Lister>;
+    type Deleter = A::Deleter;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> opendal::Result<RpCreateDir> {
+        self.state.before("create_dir").await?;
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> opendal::Result<(RpRead, Self::Reader)> {
+        self.state.before("read").await?;
+        if self.state.hidden(path) {
+            return Err(FaultState::not_found(path));
+        }
+        self.inner.read(path, args).await
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> opendal::Result<(RpWrite, Self::Writer)> {
+        self.state.before("write").await?;
+        let created = self.inner.stat(path, OpStat::default()).await.is_err();
+        let (rp, writer) = self.inner.write(path, args).await?;
+        Ok((
+            rp,
+            FaultWriter {
+                inner: writer,
+                state: self.state.clone(),
+                path: path.to_string(),
+                created,
+                buffered: Vec::new(),
+            },
+        ))
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> opendal::Result<RpStat> {
+        self.state.before("stat").await?;
+        if self.state.hidden(path) {
+            return Err(FaultState::not_found(path));
+        }
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self) -> opendal::Result<(RpDelete, Self::Deleter)> {
+        self.state.before("delete").await?;
+        self.inner.delete().await
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> opendal::Result<(RpList, Self::Lister)> {
+        self.state.before("list").await?;
+        let (rp, lister) = self.inner.list(path, args).await?;
+        Ok((
+            rp,
+            FaultLister {
+                inner: lister,
+                state: self.state.clone(),
+            },
+        ))
+    }
+}
+
+/// Buffers a write so a partial-write fault can store just a prefix.
+pub struct FaultWriter<W> {
+    inner: W,
+    state: Arc<FaultState>,
+    path: String,
+    created: bool,
+    buffered: Vec<Buffer>,
+}
+
+impl<W: oio::Write> oio::Write for FaultWriter<W> {
+    async fn write(&mut self, bs: Buffer) -> opendal::Result<()> {
+        self.buffered.push(bs);
+        Ok(())
+    }
+
+    async fn close(&mut self) -> opendal::Result<Metadata> {
+        let bytes: Vec<u8> = self
+            .buffered
+            .drain(..)
+            .flat_map(|buffer| buffer.to_vec())
+            .collect();
+        if !bytes.is_empty() && self.state.roll(self.state.config.partial_write_rate) {
+            self.state
+                .counters
+                .partial_writes
+                .fetch_add(1, Ordering::Relaxed);
+            self.inner
+                .write(Buffer::from(bytes[..bytes.len() / 2].to_vec()))
+                .await?;
+            self.inner.close().await?;
+            return Err(Error::new(ErrorKind::Unexpected, "injected partial write")
+                .with_context("path", &self.path)
+                .set_temporary());
+        }
+        if !bytes.is_empty() {
+            self.inner.write(Buffer::from(bytes)).await?;
+        }
+        let meta = self.inner.close().await?;
+        if self.created {
+            self.state.hide(&self.path);
+        }
+        Ok(meta)
+    }
+
+    async fn abort(&mut self) -> opendal::Result<()> {
+        self.buffered.clear();
+        self.inner.abort().await
+    }
+}
+
+/// Skips entries that are not visible yet.
+pub struct FaultLister<L> {
+    inner: L,
+    state: Arc<FaultState>,
+}
+
+impl<L: oio::List> oio::List for FaultLister<L> {
+    async fn next(&mut self) -> opendal::Result<Option<oio::Entry>> {
+        loop {
+            match self.inner.next().await? {
+                Some(entry) if self.state.hidden(entry.path()) => continue,
+                other => return Ok(other),
+            }
+        }
+    }
+}
+
+/// An in-memory operator wrapped in a [`FaultLayer`], returned with the layer
+/// so callers can read its [`FaultStats`].
+pub fn fault_operator(config: FaultConfig) -> Result<(Operator, FaultLayer)> {
+    config.validate()?;
+    let layer = FaultLayer::new(config);
+    let op = Operator::new(Memory::default())?
+        .layer(layer.clone())
+        .finish();
+    Ok((op, layer))
+}
@@ -3,9 +3,61 @@ use async_trait::async_trait;
 use futures::TryStreamExt;
 use opendal::{EntryMode, Operator};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Mutex, OnceLock};
 pub use ugoite_minimum::storage::{StorageBackend, StorageEntry};
 
+pub mod fault;
+pub mod replica;
+pub mod versions;
+
+pub const LIST_PARALLELISM_ENV: &str = "UGOITE_LIST_PARALLELISM";
+/// Concurrent metadata reads a listing issues when nothing else is configured.
+pub const DEFAULT_LIST_PARALLELISM: usize = 16;
+
+/// Zero means "not set"; fall back to the environment, then the default.
+static LIST_PARALLELISM: AtomicUsize = AtomicUsize::new(0);
+
+/// How many stat/read operations listings run at once.
+pub fn list_parallelism() -> usize {
+    match LIST_PARALLELISM.load(Ordering::Relaxed) {
+        0 => std::env::var(LIST_PARALLELISM_ENV)
+            .ok()
+            .and_then(|raw| raw.trim().parse::<usize>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_LIST_PARALLELISM),
+        value => value,
+    }
+}
+
+/// Override the listing parallelism for this process; 0 restores the default.
+pub fn set_list_parallelism(parallelism: usize) {
+    LIST_PARALLELISM.store(parallelism, Ordering::Relaxed);
+}
+
+/// Replace `path` with `data` so readers see either the old or the new bytes,
+/// never a partial write.
+///
+/// Backends with rename stage the bytes in a hidden temp file beside `path`
+/// and move it into place; object stores already replace whole objects.
+pub async fn publish_atomic(op: &Operator, path: &str, data: Vec<u8>) -> Result<()> {
+    if !op.info().full_capability().rename {
+        op.write(path, data).await?;
+        return Ok(());
+    }
+    let (dir, name) = match path.rsplit_once('/') {
+        Some((dir, name)) => (format!("{}/", dir), name),
+        None => (String::new(), path),
+    };
+    let tmp = format!("{}.{}.{}.tmp", dir, name, uuid::Uuid::new_v4());
+    op.write(&tmp, data).await?;
+    if let Err(err) = op.rename(&tmp, path).await {
+        let _ = op.delete(&tmp).await;
+        return Err(err.into());
+    }
+    Ok(())
+}
+
 static MEMORY_OPERATORS: OnceLock<Mutex<HashMap<String, Operator>>> = OnceLock::new();
 
 fn memory_cache() -> &'static Mutex<HashMap<String, Operator>> {
@@ -13,6 +65,18 @@ fn memory_cache() -> &'static Mutex<HashMap<String, Operator>> {
 }
 
 pub fn operator_from_uri(uri: &str) -> Result<Operator> {
+    if uri.starts_with(fault::TEST_SCHEME) {
+        // Faults are seeded per URI, so every caller shares one simulated store.
+        let mut cache = memory_cache()
+            .lock()
+            .map_err(|_| anyhow::anyhow!("memory operator cache lock poisoned"))?;
+        if let Some(op) = cache.get(uri) {
+            return Ok(op.clone());
+        }
+        let (op, _) = fault::fault_operator(fault::FaultConfig::from_uri(uri)?)?;
+        cache.insert(uri.to_string(), op.clone());
+        return Ok(op);
+    }
     if uri.starts_with("memory://") {
         let mut cache = memory_cache()
             .lock()
@@ -28,6 +92,34 @@ pub fn operator_from_uri(uri: &str) -> Result<Operator> {
     Ok(Operator::from_uri(uri)?)
 }
 
+static REPLICA_OPERATORS: OnceLock<Mutex<HashMap<String, Operator>>> = OnceLock::new();
+
+/// The operator for `uri` with reads routed to `replica_uri` first.
+///
+/// Routed operators are shared per configuration, so every caller sees the
+/// writes the others made within the staleness window.
+pub fn operator_with_replica(
+    uri: &str,
+    replica_uri: &str,
+    config: &replica::ReplicaConfig,
+) -> Result<Operator> {
+    let key = format!("{}|{}|{}", uri, replica_uri, config.max_staleness_ms);
+    let mut cache = REPLICA_OPERATORS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .map_err(|_| anyhow::anyhow!("replica operator cache lock poisoned"))?;
+    if let Some(op) = cache.get(&key) {
+        return Ok(op.clone());
+    }
+    let (op, _) = replica::replica_operator(
+        operator_from_uri(uri)?,
+        operator_from_uri(replica_uri)?,
+        config.clone(),
+    );
+    cache.insert(key, op.clone());
+    Ok(op)
+}
+
 #[derive(Clone)]
 pub struct OpendalStorage {
     operator: Operator,
@@ -54,8 +146,7 @@ impl StorageBackend for OpendalStorage {
     }
 
     async fn write(&self, path: &str, data: Vec<u8>) -> Result<()> {
-        self.operator.write(path, data).await?;
-        Ok(())
+        publish_atomic(&self.operator, path, data).await
     }
 
     async fn create_dir(&self, path: &str) -> Result<()> {
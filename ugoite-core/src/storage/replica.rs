@@ -0,0 +1,249 @@
+use opendal::raw::oio;
+use opendal::raw::*;
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default for [`ReplicaConfig::max_staleness_ms`].
+pub const DEFAULT_MAX_STALENESS_MS: u64 = 5_000;
+
+fn default_max_staleness_ms() -> u64 {
+    DEFAULT_MAX_STALENESS_MS
+}
+
+/// How far a read replica may trail its primary.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ReplicaConfig {
+    /// How long the replica may take to mirror a write. Paths written through
+    /// the routed operator within this window are read from the primary; 0
+    /// tolerates any lag.
+    #[serde(default = "default_max_staleness_ms")]
+    pub max_staleness_ms: u64,
+}
+
+impl Default for ReplicaConfig {
+    fn default() -> Self {
+        Self {
+            max_staleness_ms: DEFAULT_MAX_STALENESS_MS,
+        }
+    }
+}
+
+/// Where reads went so far.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ReplicaStats {
+    /// Reads, stats and listings served by the replica.
+    pub replica_reads: u64,
+    /// Lookups sent to the primary because the path was written recently.
+    pub primary_reads: u64,
+    /// Lookups the replica failed or missed, retried on the primary.
+    pub fallbacks: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    replica_reads: AtomicU64,
+    primary_reads: AtomicU64,
+    fallbacks: AtomicU64,
+}
+
+#[derive(Debug)]
+struct ReplicaState {
+    replica: Accessor,
+    max_staleness: Duration,
+    /// Paths written through this layer and when.
+    recent_writes: Mutex<HashMap<String, Instant>>,
+    counters: Counters,
+}
+
+impl ReplicaState {
+    fn record_write(&self, path: &str) {
+        if self.max_staleness.is_zero() {
+            return;
+        }
+        let now = Instant::now();
+        let mut writes = self.recent_writes.lock().unwrap_or_else(|e| e.into_inner());
+        writes.retain(|_, at| now.duration_since(*at) < self.max_staleness);
+        writes.insert(path.to_string(), now);
+    }
+
+    /// Whether the replica may not have caught up with writes to `path`, or
+    /// under it when `prefix` is set.
+    fn needs_primary(&self, path: &str, prefix: bool) -> bool {
+        let writes = self.recent_writes.lock().unwrap_or_else(|e| e.into_inner());
+        let fresh = writes.iter().any(|(written, at)| {
+            at.elapsed() < self.max_staleness
+                && (written == path || (prefix && written.starts_with(path)))
+        });
+        if fresh {
+            self.counters.primary_reads.fetch_add(1, Ordering::Relaxed);
+        }
+        fresh
+    }
+
+    /// Count a replica result, or a fallback when it failed.
+    fn served<T>(&self, result: &opendal::Result<T>) -> bool {
+        let counter = match result {
+            Ok(_) => &self.counters.replica_reads,
+            Err(_) => &self.counters.fallbacks,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result.is_ok()
+    }
+}
+
+/// OpenDAL layer that reads from a replica operator, such as a local mirror
+/// of an S3 bucket, and falls back to the wrapped primary. Writes, deletes,
+/// copies and renames only reach the primary; mirroring them to the replica
+/// is left to the deployment.
+#[derive(Debug, Clone)]
+pub struct ReplicaLayer {
+    state: Arc<ReplicaState>,
+}
+
+impl ReplicaLayer {
+    pub fn new(replica: Operator, config: ReplicaConfig) -> Self {
+        Self {
+            state: Arc::new(ReplicaState {
+                replica: replica.into_inner(),
+                max_staleness: Duration::from_millis(config.max_staleness_ms),
+                recent_writes: Mutex::new(HashMap::new()),
+                counters: Counters::default(),
+            }),
+        }
+    }
+
+    pub fn stats(&self) -> ReplicaStats {
+        let counters = &self.state.counters;
+        ReplicaStats {
+            replica_reads: counters.replica_reads.load(Ordering::Relaxed),
+            primary_reads: counters.primary_reads.load(Ordering::Relaxed),
+            fallbacks: counters.fallbacks.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl<A: Access> Layer<A> for ReplicaLayer {
+    type LayeredAccess = ReplicaAccessor<A>;
+
+    fn layer(&self, inner: A) -> Self::LayeredAccess {
+        ReplicaAccessor {
+            inner,
+            state: self.state.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplicaAccessor<A> {
+    inner: A,
+    state: Arc<ReplicaState>,
+}
+
+impl<A: Access> LayeredAccess for ReplicaAccessor<A> {
+    type Inner = A;
+    type Reader = oio::Reader;
+    type Writer = A::Writer;
+    type Lister = oio::Lister;
+    type Deleter = ReplicaDeleter<A::Deleter>;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn create_dir(&self, path: &str, args: OpCreateDir) -> opendal::Result<RpCreateDir> {
+        self.state.record_write(path);
+        self.inner.create_dir(path, args).await
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> opendal::Result<(RpRead, Self::Reader)> {
+        if !self.state.needs_primary(path, false) {
+            let result = self.state.replica.read(path, args.clone()).await;
+            if self.state.served(&result) {
+                return result;
+            }
+        }
+        let (rp, reader) = self.inner.read(path, args).await?;
+        Ok((rp, Box::new(reader) as oio::Reader))
+    }
+
+    async fn write(&self, path: &str, args: OpWrite) -> opendal::Result<(RpWrite, Self::Writer)> {
+        self.state.record_write(path);
+        self.inner.write(path, args).await
+    }
+
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> opendal::Result<RpCopy> {
+        self.state.record_write(to);
+        self.inner.copy(from, to, args).await
+    }
+
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> opendal::Result<RpRename> {
+        self.state.record_write(from);
+        self.state.record_write(to);
+        self.inner.rename(from, to, args).await
+    }
+
+    async fn stat(&self, path: &str, args: OpStat) -> opendal::Result<RpStat> {
+        if !self.state.needs_primary(path, false) {
+            let result = self.state.replica.stat(path, args.clone()).await;
+            if self.state.served(&result) {
+                return result;
+            }
+        }
+        self.inner.stat(path, args).await
+    }
+
+    async fn delete(&self) -> opendal::Result<(RpDelete, Self::Deleter)> {
+        let (rp, deleter) = self.inner.delete().await?;
+        Ok((
+            rp,
+            ReplicaDeleter {
+                inner: deleter,
+                state: self.state.clone(),
+            },
+        ))
+    }
+
+    async fn list(&self, path: &str, args: OpList) -> opendal::Result<(RpList, Self::Lister)> {
+        if !self.state.needs_primary(path, true) {
+            let result = self.state.replica.list(path, args.clone()).await;
+            if self.state.served(&result) {
+                return result;
+            }
+        }
+        let (rp, lister) = self.inner.list(path, args).await?;
+        Ok((rp, Box::new(lister) as oio::Lister))
+    }
+}
+
+/// Records deleted paths so they are read from the primary until the
+/// replica has had time to drop them.
+pub struct ReplicaDeleter<D> {
+    inner: D,
+    state: Arc<ReplicaState>,
+}
+
+impl<D: oio::Delete> oio::Delete for ReplicaDeleter<D> {
+    fn delete(&mut self, path: &str, args: OpDelete) -> opendal::Result<()> {
+        self.state.record_write(path);
+        self.inner.delete(path, args)
+    }
+
+    async fn flush(&mut self) -> opendal::Result<usize> {
+        self.inner.flush().await
+    }
+}
+
+/// `primary` with reads routed to `replica`, returned with the layer so
+/// callers can read its [`ReplicaStats`].
+pub fn replica_operator(
+    primary: Operator,
+    replica: Operator,
+    config: ReplicaConfig,
+) -> (Operator, ReplicaLayer) {
+    let layer = ReplicaLayer::new(replica, config);
+    (primary.layer(layer.clone()), layer)
+}
@@ -0,0 +1,132 @@
+use anyhow::{anyhow, Result};
+use futures::TryStreamExt;
+use opendal::{EntryMode, Operator};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// One version of an object in a versioned bucket.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ObjectVersion {
+    pub path: String,
+    pub version_id: String,
+    /// Unix seconds; delete markers carry the time of the delete.
+    pub last_modified: Option<f64>,
+    pub size: u64,
+    /// Whether this is the version reads currently return.
+    pub is_latest: bool,
+    /// Whether this version records a delete rather than content.
+    pub is_delete_marker: bool,
+}
+
+/// A change needed to bring one object back to an earlier point in time.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum VersionRestoreAction {
+    /// Make `version_id` the current content of `path` again.
+    Restore { path: String, version_id: String },
+    /// Delete `path`, which did not exist at that time.
+    Delete { path: String },
+}
+
+fn require_versioning(op: &Operator) -> Result<()> {
+    let capability = op.info().full_capability();
+    if !capability.list_with_versions || !capability.read_with_version {
+        return Err(anyhow!(
+            "Storage backend {} does not expose object versions; enable bucket versioning",
+            op.info().scheme()
+        ));
+    }
+    Ok(())
+}
+
+/// Every version and delete marker stored under `prefix`, oldest first per path.
+pub async fn list_object_versions(op: &Operator, prefix: &str) -> Result<Vec<ObjectVersion>> {
+    require_versioning(op)?;
+    let deleted = op.info().full_capability().list_with_deleted;
+    let mut lister = op
+        .lister_with(prefix)
+        .recursive(true)
+        .versions(true)
+        .deleted(deleted)
+        .await?;
+    let mut versions = Vec::new();
+    while let Some(item) = lister.try_next().await? {
+        let meta = item.metadata();
+        if meta.mode() == EntryMode::DIR {
+            continue;
+        }
+        let Some(version_id) = meta.version() else {
+            continue;
+        };
+        versions.push(ObjectVersion {
+            path: item.path().to_string(),
+            version_id: version_id.to_string(),
+            last_modified: meta
+                .last_modified()
+                .map(|at| at.into_inner().as_millisecond() as f64 / 1000.0),
+            size: meta.content_length(),
+            is_latest: meta.is_current().unwrap_or(false),
+            is_delete_marker: meta.is_deleted(),
+        });
+    }
+    versions.sort_by(|a, b| {
+        a.path.cmp(&b.path).then(
+            a.last_modified
+                .partial_cmp(&b.last_modified)
+                .unwrap_or(std::cmp::Ordering::Equal),
+        )
+    });
+    Ok(versions)
+}
+
+/// Make `version_id` the current content of `path` by writing it back as a
+/// new version; history is kept.
+pub async fn restore_object_version(op: &Operator, path: &str, version_id: &str) -> Result<()> {
+    require_versioning(op)?;
+    let bytes = op.read_with(path).version(version_id).await?;
+    op.write(path, bytes).await?;
+    Ok(())
+}
+
+/// The changes that return the objects in `versions` to their state at
+/// `timestamp` (unix seconds).
+///
+/// Each path goes back to its newest version written at or before
+/// `timestamp`. Paths created later, or deleted by then, are deleted. Paths
+/// whose current version already is that version are left alone, as are
+/// versions without a timestamp.
+pub fn plan_point_in_time_restore(
+    versions: &[ObjectVersion],
+    timestamp: f64,
+) -> Vec<VersionRestoreAction> {
+    let mut by_path: BTreeMap<&str, Vec<&ObjectVersion>> = BTreeMap::new();
+    for version in versions {
+        by_path.entry(&version.path).or_default().push(version);
+    }
+    let mut actions = Vec::new();
+    for (path, history) in by_path {
+        let current = history.iter().find(|version| version.is_latest);
+        let target = history
+            .iter()
+            .filter(|version| version.last_modified.is_some_and(|at| at <= timestamp))
+            .max_by(|a, b| {
+                a.last_modified
+                    .partial_cmp(&b.last_modified)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        let exists_now = current.is_some_and(|version| !version.is_delete_marker);
+        let current_id = current.map(|version| version.version_id.as_str());
+        match target.filter(|version| !version.is_delete_marker) {
+            Some(target) if current_id == Some(target.version_id.as_str()) => {}
+            Some(target) => actions.push(VersionRestoreAction::Restore {
+                path: path.to_string(),
+                version_id: target.version_id.clone(),
+            }),
+            None if exists_now => actions.push(VersionRestoreAction::Delete {
+                path: path.to_string(),
+            }),
+            None => {}
+        }
+    }
+    actions
+}
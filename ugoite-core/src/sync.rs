@@ -0,0 +1,376 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::entry;
+use crate::entry_acl::{self, EntryViewer};
+use crate::form;
+use crate::integrity::IntegrityProvider;
+
+/// Largest page a single `sync_pull` call may return.
+pub const MAX_SYNC_PAGE: usize = 500;
+
+/// An entry's state as of its last change, returned by `sync_pull`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SyncChange {
+    pub entry_id: String,
+    pub form: String,
+    pub revision_id: String,
+    pub parent_revision_id: Option<String>,
+    pub updated_at: f64,
+    pub author: String,
+    pub deleted: bool,
+    /// Current markdown; `None` for deleted entries.
+    pub markdown: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SyncPullResult {
+    pub changes: Vec<SyncChange>,
+    /// Opaque cursor to pass to the next pull.
+    pub cursor: String,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncOperationKind {
+    Upsert,
+    Delete,
+}
+
+/// A change made offline, based on the revision the client last saw.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SyncOperation {
+    pub entry_id: String,
+    pub kind: SyncOperationKind,
+    #[serde(default)]
+    pub markdown: Option<String>,
+    /// Revision the client edited; `None` for entries created offline.
+    #[serde(default)]
+    pub base_revision_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncOutcome {
+    Applied,
+    /// The server already matches the operation.
+    Unchanged,
+    /// The server changed concurrently and kept its version.
+    Conflict,
+    Rejected,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SyncOperationResult {
+    pub entry_id: String,
+    pub outcome: SyncOutcome,
+    /// Server revision after the operation was resolved.
+    pub revision_id: Option<String>,
+    /// Entry holding the client's version of a conflicting upsert.
+    pub conflict_entry_id: Option<String>,
+    pub error: Option<String>,
+}
+
+impl SyncOperationResult {
+    fn new(entry_id: &str, outcome: SyncOutcome, revision_id: Option<String>) -> Self {
+        Self {
+            entry_id: entry_id.to_string(),
+            outcome,
+            revision_id,
+            conflict_entry_id: None,
+            error: None,
+        }
+    }
+
+    fn rejected(entry_id: &str, error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::new(entry_id, SyncOutcome::Rejected, None)
+        }
+    }
+}
+
+fn timestamp_millis(ts: f64) -> i64 {
+    (ts * 1000.0).round() as i64
+}
+
+fn encode_cursor(updated_at: f64, entry_id: &str) -> String {
+    format!("{}:{}", timestamp_millis(updated_at), entry_id)
+}
+
+fn decode_cursor(cursor: &str) -> Result<(i64, String)> {
+    let (millis, entry_id) = cursor
+        .split_once(':')
+        .ok_or_else(|| anyhow!("Invalid sync cursor: {}", cursor))?;
+    let millis = millis
+        .parse()
+        .map_err(|_| anyhow!("Invalid sync cursor: {}", cursor))?;
+    Ok((millis, entry_id.to_string()))
+}
+
+/// Changes after `cursor` in (updated_at, entry_id) order, one per entry.
+///
+/// Pulling without a cursor returns every entry, including deleted ones so
+/// clients can drop local copies. With a `viewer`, entries its ACL hides are
+/// left out.
+pub async fn sync_pull(
+    op: &Operator,
+    ws_path: &str,
+    cursor: Option<&str>,
+    limit: usize,
+    viewer: Option<&EntryViewer>,
+) -> Result<SyncPullResult> {
+    if limit == 0 || limit > MAX_SYNC_PAGE {
+        return Err(anyhow!(
+            "Sync page size must be between 1 and {}",
+            MAX_SYNC_PAGE
+        ));
+    }
+    let after = cursor.map(decode_cursor).transpose()?;
+    let acls = match viewer {
+        Some(_) => entry_acl::list_entry_acls(op, ws_path).await?,
+        None => std::collections::HashMap::new(),
+    };
+
+    let mut changes = Vec::new();
+    for form_name in form::list_form_names(op, ws_path).await? {
+        let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+        let revisions = entry::latest_form_revisions(op, ws_path, &form_name, &form_def).await?;
+        for mut row in entry::list_form_entry_rows(op, ws_path, &form_name, &form_def).await? {
+            let key = (timestamp_millis(row.updated_at), row.entry_id.clone());
+            if after.as_ref().is_some_and(|after| &key <= after) {
+                continue;
+            }
+            if viewer.is_some_and(|viewer| !entry_acl::can_read(acls.get(&row.entry_id), viewer)) {
+                continue;
+            }
+            if let Some(latest) = revisions.get(&row.entry_id) {
+                row.revision_id = latest.revision_id.clone();
+                row.parent_revision_id = latest.parent_revision_id.clone();
+                row.author = latest.author.clone();
+            }
+            let markdown = (!row.deleted).then(|| {
+                entry::render_markdown_for_form(
+                    &row.title,
+                    &form_name,
+                    &row.tags,
+                    &row.fields,
+                    &row.extra_attributes,
+                    &form_def,
+                )
+            });
+            changes.push((
+                key,
+                SyncChange {
+                    entry_id: row.entry_id,
+                    form: form_name.clone(),
+                    revision_id: row.revision_id,
+                    parent_revision_id: row.parent_revision_id,
+                    updated_at: row.updated_at,
+                    author: row.author,
+                    deleted: row.deleted,
+                    markdown,
+                },
+            ));
+        }
+    }
+    changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let has_more = changes.len() > limit;
+    changes.truncate(limit);
+    let cursor = match changes.last() {
+        Some((_, change)) => encode_cursor(change.updated_at, &change.entry_id),
+        None => cursor.unwrap_or("0:").to_string(),
+    };
+    Ok(SyncPullResult {
+        changes: changes.into_iter().map(|(_, change)| change).collect(),
+        cursor,
+        has_more,
+    })
+}
+
+/// Deterministic id for the entry preserving a conflicting client version, so
+/// retried pushes do not create duplicates.
+fn conflict_entry_id(entry_id: &str, base_revision_id: Option<&str>, markdown: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(entry_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(base_revision_id.unwrap_or_default().as_bytes());
+    hasher.update([0]);
+    hasher.update(markdown.as_bytes());
+    let digest = hex::encode(hasher.finalize());
+    format!("{}-conflict-{}", entry_id, &digest[..12])
+}
+
+async fn server_state(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+) -> Result<Option<entry::EntryRow>> {
+    match entry::find_entry_form(op, ws_path, entry_id).await? {
+        Some(form_name) => Ok(Some(
+            entry::read_entry_row(op, ws_path, &form_name, entry_id).await?,
+        )),
+        None => Ok(None),
+    }
+}
+
+async fn current_markdown(op: &Operator, ws_path: &str, entry_id: &str) -> Result<String> {
+    Ok(entry::get_entry_content(op, ws_path, entry_id)
+        .await?
+        .markdown)
+}
+
+async fn record_conflict<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    operation: &SyncOperation,
+    markdown: &str,
+    server: &entry::EntryRow,
+    author: &str,
+    integrity: &I,
+) -> Result<SyncOperationResult> {
+    let conflict_id = conflict_entry_id(
+        &operation.entry_id,
+        operation.base_revision_id.as_deref(),
+        markdown,
+    );
+    if entry::find_entry_form(op, ws_path, &conflict_id)
+        .await?
+        .is_none()
+    {
+        entry::create_entry(op, ws_path, &conflict_id, markdown, author, integrity).await?;
+    }
+    Ok(SyncOperationResult {
+        conflict_entry_id: Some(conflict_id),
+        ..SyncOperationResult::new(
+            &operation.entry_id,
+            SyncOutcome::Conflict,
+            Some(server.revision_id.clone()),
+        )
+    })
+}
+
+async fn apply_upsert<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    operation: &SyncOperation,
+    author: &str,
+    integrity: &I,
+) -> Result<SyncOperationResult> {
+    let entry_id = operation.entry_id.as_str();
+    let markdown = operation
+        .markdown
+        .as_deref()
+        .ok_or_else(|| anyhow!("Upsert of {} requires markdown", entry_id))?;
+
+    let Some(server) = server_state(op, ws_path, entry_id).await? else {
+        if operation.base_revision_id.is_some() {
+            return Err(anyhow!("Entry not found: {}", entry_id));
+        }
+        entry::create_entry(op, ws_path, entry_id, markdown, author, integrity).await?;
+        let created = entry::get_entry_content(op, ws_path, entry_id).await?;
+        return Ok(SyncOperationResult::new(
+            entry_id,
+            SyncOutcome::Applied,
+            Some(created.revision_id),
+        ));
+    };
+
+    if server.deleted {
+        return record_conflict(op, ws_path, operation, markdown, &server, author, integrity).await;
+    }
+    if current_markdown(op, ws_path, entry_id).await?.trim() == markdown.trim() {
+        return Ok(SyncOperationResult::new(
+            entry_id,
+            SyncOutcome::Unchanged,
+            Some(server.revision_id),
+        ));
+    }
+    if operation.base_revision_id.as_deref() != Some(server.revision_id.as_str()) {
+        return record_conflict(op, ws_path, operation, markdown, &server, author, integrity).await;
+    }
+
+    entry::update_entry(
+        op,
+        ws_path,
+        entry_id,
+        markdown,
+        Some(&server.revision_id),
+        author,
+        None,
+        integrity,
+    )
+    .await?;
+    let updated = entry::get_entry_content(op, ws_path, entry_id).await?;
+    Ok(SyncOperationResult::new(
+        entry_id,
+        SyncOutcome::Applied,
+        Some(updated.revision_id),
+    ))
+}
+
+async fn apply_delete(
+    op: &Operator,
+    ws_path: &str,
+    operation: &SyncOperation,
+) -> Result<SyncOperationResult> {
+    let entry_id = operation.entry_id.as_str();
+    let server = server_state(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    if server.deleted {
+        return Ok(SyncOperationResult::new(
+            entry_id,
+            SyncOutcome::Unchanged,
+            Some(server.revision_id),
+        ));
+    }
+    // Edits made on the server win over a delete based on an older revision.
+    if let Some(base) = &operation.base_revision_id {
+        if base != &server.revision_id {
+            return Ok(SyncOperationResult::new(
+                entry_id,
+                SyncOutcome::Conflict,
+                Some(server.revision_id),
+            ));
+        }
+    }
+    entry::delete_entry(op, ws_path, entry_id, false).await?;
+    Ok(SyncOperationResult::new(
+        entry_id,
+        SyncOutcome::Applied,
+        Some(server.revision_id),
+    ))
+}
+
+/// Apply offline operations in order and report how each was resolved.
+///
+/// An upsert whose base revision is no longer the server head keeps the
+/// server version and stores the client's markdown as a separate conflict
+/// entry. Failures such as validation errors or locks reject only the
+/// operation that caused them.
+pub async fn sync_push<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    operations: &[SyncOperation],
+    author: &str,
+    integrity: &I,
+) -> Result<Vec<SyncOperationResult>> {
+    let mut results = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let result = match operation.kind {
+            SyncOperationKind::Upsert => {
+                apply_upsert(op, ws_path, operation, author, integrity).await
+            }
+            SyncOperationKind::Delete => apply_delete(op, ws_path, operation).await,
+        };
+        results.push(result.unwrap_or_else(|err| {
+            SyncOperationResult::rejected(&operation.entry_id, err.to_string())
+        }));
+    }
+    Ok(results)
+}
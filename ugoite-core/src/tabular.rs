@@ -0,0 +1,1103 @@
+use crate::entry;
+use crate::field_crypto;
+use crate::form;
+use crate::importers::ImportJobStatus;
+use crate::index;
+use crate::integrity::RealIntegrityProvider;
+use crate::metadata;
+use crate::redaction::{RedactionConfig, Redactor};
+use crate::space;
+use crate::space_lock::{self, SpaceLease};
+use crate::storage;
+use anyhow::{anyhow, Context, Result};
+use arrow_array::builder::{
+    BooleanBuilder, Float32Builder, Float64Builder, Int32Builder, Int64Builder, StringBuilder,
+    TimestampMicrosecondBuilder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use calamine::{Data, DataType as _, Reader, Xlsx};
+use chrono::{DateTime, Utc};
+use opendal::Operator;
+use parquet::arrow::ArrowWriter;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Cursor, Write};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const DEFAULT_TABULAR_AUTHOR: &str = "importer";
+/// Kept apart from `import_jobs/`, whose records have a different schema.
+const TABULAR_JOBS_DIR: &str = "tabular_import_jobs";
+/// A job's lease outlives its runner by at most this long, after which the
+/// job counts as interrupted and may be resumed.
+const TABULAR_JOB_LEASE_TTL_SECS: u64 = 60;
+const TABULAR_JOB_FLUSH_INTERVAL: usize = 25;
+const PARQUET_EXPORT_BATCH_ROWS: usize = 1024;
+/// Metadata columns written ahead of the form fields in every export.
+const EXPORT_METADATA_COLUMNS: &[&str] = &[
+    "entry_id",
+    "title",
+    "tags",
+    "author",
+    "created_at",
+    "updated_at",
+    "revision_id",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TabularFormat {
+    Csv,
+    Xlsx,
+}
+
+impl TabularFormat {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "xlsx" | "excel" => Ok(Self::Xlsx),
+            other => Err(anyhow!("Unsupported tabular format: {}", other)),
+        }
+    }
+
+    /// XLSX files are zip archives; everything else is treated as CSV text.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(b"PK\x03\x04") {
+            Self::Xlsx
+        } else {
+            Self::Csv
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TabularExportFormat {
+    Csv,
+    Xlsx,
+    Parquet,
+}
+
+impl TabularExportFormat {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "xlsx" | "excel" => Ok(Self::Xlsx),
+            "parquet" => Ok(Self::Parquet),
+            other => Err(anyhow!("Unsupported tabular format: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TabularImportOptions {
+    /// Detected from the file contents when unset.
+    #[serde(default)]
+    pub format: Option<TabularFormat>,
+    /// Worksheet to read from XLSX files; the first sheet is used when unset.
+    #[serde(default)]
+    pub sheet: Option<String>,
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    /// Column used as the entry title; a column named `title` is used when unset.
+    #[serde(default)]
+    pub title_column: Option<String>,
+    /// Column holding comma- or semicolon-separated tags.
+    #[serde(default)]
+    pub tags_column: Option<String>,
+    #[serde(default = "default_tabular_author")]
+    pub author: String,
+}
+
+impl Default for TabularImportOptions {
+    fn default() -> Self {
+        Self {
+            format: None,
+            sheet: None,
+            delimiter: default_delimiter(),
+            title_column: None,
+            tags_column: None,
+            author: default_tabular_author(),
+        }
+    }
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+fn default_tabular_author() -> String {
+    DEFAULT_TABULAR_AUTHOR.to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TabularRowError {
+    /// 1-based row number in the source sheet, counting the header row.
+    pub row: usize,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TabularImportReport {
+    pub form: String,
+    pub total_rows: usize,
+    pub created: usize,
+    pub failed: usize,
+    pub entry_ids: Vec<String>,
+    pub errors: Vec<TabularRowError>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TabularImportJob {
+    pub job_id: String,
+    pub space_id: String,
+    pub form: String,
+    pub status: ImportJobStatus,
+    pub status_message: Option<String>,
+    /// Data rows already handled; a resumed job continues from here.
+    pub processed_rows: usize,
+    pub total_rows: usize,
+    pub mapping: BTreeMap<String, String>,
+    pub options: TabularImportOptions,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+    pub report: TabularImportReport,
+}
+
+struct Table {
+    headers: Vec<String>,
+    /// Non-blank data rows with their 1-based record number in the source.
+    rows: Vec<(usize, Vec<String>)>,
+}
+
+fn parse_csv(bytes: &[u8], delimiter: char) -> Result<Table> {
+    if !delimiter.is_ascii() {
+        return Err(anyhow!("CSV delimiter must be an ASCII character"));
+    }
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter as u8)
+        .from_reader(bytes);
+    let mut records = Vec::new();
+    for record in reader.records() {
+        let record = record.context("Invalid CSV data")?;
+        records.push(record.iter().map(str::to_string).collect::<Vec<_>>());
+    }
+    split_header(records)
+}
+
+fn xlsx_cell_to_string(cell: &Data) -> String {
+    match cell {
+        Data::DateTime(_) | Data::DateTimeIso(_) => match cell.as_datetime() {
+            Some(dt) if dt.time() == chrono::NaiveTime::MIN => dt.format("%Y-%m-%d").to_string(),
+            Some(dt) => dt.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            None => cell.to_string(),
+        },
+        Data::Error(_) => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn parse_xlsx(bytes: &[u8], sheet: Option<&str>) -> Result<Table> {
+    let mut workbook: Xlsx<_> =
+        calamine::open_workbook_from_rs(Cursor::new(bytes)).context("Invalid XLSX workbook")?;
+    let range = match sheet {
+        Some(name) => workbook
+            .worksheet_range(name)
+            .with_context(|| format!("Worksheet not found: {}", name))?,
+        None => workbook
+            .worksheet_range_at(0)
+            .ok_or_else(|| anyhow!("Workbook has no worksheets"))?
+            .context("Invalid XLSX worksheet")?,
+    };
+    let records = range
+        .rows()
+        .map(|row| row.iter().map(xlsx_cell_to_string).collect())
+        .collect();
+    split_header(records)
+}
+
+fn split_header(mut records: Vec<Vec<String>>) -> Result<Table> {
+    if records.is_empty() {
+        return Err(anyhow!("Tabular data has no header row"));
+    }
+    let headers: Vec<String> = records
+        .remove(0)
+        .into_iter()
+        .map(|h| h.trim().to_string())
+        .collect();
+    let mut seen = HashSet::new();
+    for header in &headers {
+        if !header.is_empty() && !seen.insert(header.clone()) {
+            return Err(anyhow!("Duplicate column header: {}", header));
+        }
+    }
+    // Header is record 1, so the first data row is record 2.
+    let rows = records
+        .into_iter()
+        .enumerate()
+        .map(|(idx, row)| (idx + 2, row))
+        .filter(|(_, row)| row.iter().any(|cell| !cell.trim().is_empty()))
+        .collect();
+    Ok(Table { headers, rows })
+}
+
+fn parse_table(bytes: &[u8], options: &TabularImportOptions) -> Result<Table> {
+    match options
+        .format
+        .unwrap_or_else(|| TabularFormat::detect(bytes))
+    {
+        TabularFormat::Csv => parse_csv(bytes, options.delimiter),
+        TabularFormat::Xlsx => parse_xlsx(bytes, options.sheet.as_deref()),
+    }
+}
+
+/// Resolved column positions for one import.
+struct ColumnPlan {
+    fields: Vec<(usize, String)>,
+    title: Option<usize>,
+    tags: Option<usize>,
+}
+
+fn column_index(headers: &[String], column: &str) -> Result<usize> {
+    headers
+        .iter()
+        .position(|h| h == column)
+        .ok_or_else(|| anyhow!("Column not found: {}", column))
+}
+
+/// An empty mapping matches column headers to form fields by name, ignoring case.
+fn plan_columns(
+    headers: &[String],
+    mapping: &BTreeMap<String, String>,
+    options: &TabularImportOptions,
+    form_fields: &[String],
+) -> Result<ColumnPlan> {
+    let mut fields = Vec::new();
+    if mapping.is_empty() {
+        for (idx, header) in headers.iter().enumerate() {
+            if let Some(field) = form_fields.iter().find(|f| f.eq_ignore_ascii_case(header)) {
+                fields.push((idx, field.clone()));
+            }
+        }
+    } else {
+        let mut targets = HashSet::new();
+        for (column, field) in mapping {
+            if !form_fields.contains(field) {
+                return Err(anyhow!("Unknown form field in mapping: {}", field));
+            }
+            if !targets.insert(field.clone()) {
+                return Err(anyhow!("Form field mapped more than once: {}", field));
+            }
+            fields.push((column_index(headers, column)?, field.clone()));
+        }
+    }
+    if fields.is_empty() {
+        return Err(anyhow!("No columns map to form fields"));
+    }
+
+    let title = match options.title_column.as_deref() {
+        Some(column) => Some(column_index(headers, column)?),
+        None => headers
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case("title"))
+            .filter(|idx| fields.iter().all(|(field_idx, _)| field_idx != idx)),
+    };
+    let tags = options
+        .tags_column
+        .as_deref()
+        .map(|column| column_index(headers, column))
+        .transpose()?;
+    Ok(ColumnPlan {
+        fields,
+        title,
+        tags,
+    })
+}
+
+fn cell(row: &[String], idx: usize) -> &str {
+    row.get(idx).map(|s| s.trim()).unwrap_or("")
+}
+
+fn row_entry_id(seed: &str, row_number: usize) -> String {
+    format!("{}-{}", seed, row_number)
+}
+
+/// Render one data row as entry markdown, or the reasons it cannot be imported.
+fn render_row(
+    row: &[String],
+    row_number: usize,
+    form_name: &str,
+    form_def: &Value,
+    form_fields: &[String],
+    plan: &ColumnPlan,
+) -> std::result::Result<String, Vec<String>> {
+    let mut errors = Vec::new();
+    let mut properties = Map::new();
+    for (idx, field) in &plan.fields {
+        let value = cell(row, *idx);
+        if value.is_empty() {
+            continue;
+        }
+        if value.lines().any(|line| line.trim_start().starts_with('#')) {
+            errors.push(format!(
+                "Value for field {} contains a line starting with '#'",
+                field
+            ));
+            continue;
+        }
+        properties.insert(field.clone(), Value::String(value.to_string()));
+    }
+
+    let (casted, warnings) = match index::validate_properties(&Value::Object(properties), form_def)
+    {
+        Ok(result) => result,
+        Err(err) => return Err(vec![err.to_string()]),
+    };
+    errors.extend(warnings.iter().map(|w| {
+        w.get("message")
+            .and_then(|m| m.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| w.to_string())
+    }));
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let title = plan
+        .title
+        .map(|idx| cell(row, idx).replace(['\r', '\n'], " "))
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| format!("{} {}", form_name, row_number));
+    let tags: Vec<String> = plan
+        .tags
+        .map(|idx| {
+            cell(row, idx)
+                .split([',', ';'])
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(entry::render_markdown(
+        &title,
+        form_name,
+        &tags,
+        &casted,
+        form_fields,
+    ))
+}
+
+async fn load_import_form(op: &Operator, ws_path: &str, form_id: &str) -> Result<Value> {
+    if !op.exists(&format!("{}/meta.json", ws_path)).await? {
+        return Err(anyhow!("Space not found: {}", ws_path));
+    }
+    if metadata::is_reserved_metadata_form(form_id) {
+        return Err(anyhow!(
+            "Cannot import rows into metadata form: {}",
+            form_id
+        ));
+    }
+    form::read_form_definition(op, ws_path, form_id).await
+}
+
+/// Everything needed to turn rows of one table into entries of one form.
+struct RowImporter {
+    ws_path: String,
+    form_id: String,
+    form_def: Value,
+    form_fields: Vec<String>,
+    table: Table,
+    plan: ColumnPlan,
+    seed: String,
+    author: String,
+    integrity: RealIntegrityProvider,
+}
+
+impl RowImporter {
+    async fn new(
+        op: &Operator,
+        ws_path: &str,
+        form_id: &str,
+        bytes: &[u8],
+        mapping: &BTreeMap<String, String>,
+        options: &TabularImportOptions,
+        seed: String,
+    ) -> Result<Self> {
+        let form_def = load_import_form(op, ws_path, form_id).await?;
+        let form_fields = entry::form_field_names(&form_def);
+        let table = parse_table(bytes, options)?;
+        let plan = plan_columns(&table.headers, mapping, options, &form_fields)?;
+        let integrity =
+            RealIntegrityProvider::from_space(op, &space::space_id_from_ws_path(ws_path)).await?;
+        Ok(Self {
+            ws_path: ws_path.to_string(),
+            form_id: form_id.to_string(),
+            form_def,
+            form_fields,
+            table,
+            plan,
+            seed,
+            author: options.author.clone(),
+            integrity,
+        })
+    }
+
+    fn empty_report(&self) -> TabularImportReport {
+        TabularImportReport {
+            form: self.form_id.clone(),
+            total_rows: self.table.rows.len(),
+            ..TabularImportReport::default()
+        }
+    }
+
+    /// Import the data row at `offset`. A row whose entry already exists is
+    /// counted as created so an interrupted job can be replayed safely.
+    async fn import_row(
+        &self,
+        op: &Operator,
+        offset: usize,
+        report: &mut TabularImportReport,
+    ) -> Result<()> {
+        let (row_number, row) = &self.table.rows[offset];
+        let row_number = *row_number;
+        let entry_id = row_entry_id(&self.seed, row_number);
+        let markdown = match render_row(
+            row,
+            row_number,
+            &self.form_id,
+            &self.form_def,
+            &self.form_fields,
+            &self.plan,
+        ) {
+            Ok(markdown) => markdown,
+            Err(errors) => {
+                report.failed += 1;
+                report.errors.push(TabularRowError {
+                    row: row_number,
+                    errors,
+                });
+                return Ok(());
+            }
+        };
+        if entry::find_entry_form(op, &self.ws_path, &entry_id)
+            .await?
+            .is_none()
+        {
+            if let Err(err) = entry::create_entry(
+                op,
+                &self.ws_path,
+                &entry_id,
+                &markdown,
+                &self.author,
+                &self.integrity,
+            )
+            .await
+            {
+                report.failed += 1;
+                report.errors.push(TabularRowError {
+                    row: row_number,
+                    errors: vec![err.to_string()],
+                });
+                return Ok(());
+            }
+        }
+        report.created += 1;
+        report.entry_ids.push(entry_id);
+        Ok(())
+    }
+}
+
+fn new_seed() -> String {
+    Uuid::new_v4().simple().to_string()[..12].to_string()
+}
+
+/// Create one entry per data row of a CSV or XLSX file.
+///
+/// `mapping` maps column headers to form fields. Cell values are cast with the
+/// form's field types; rows that fail validation are reported and skipped.
+pub async fn import_tabular(
+    op: &Operator,
+    ws_path: &str,
+    form_id: &str,
+    bytes: &[u8],
+    mapping: &BTreeMap<String, String>,
+    options: &TabularImportOptions,
+) -> Result<TabularImportReport> {
+    let importer =
+        RowImporter::new(op, ws_path, form_id, bytes, mapping, options, new_seed()).await?;
+    let mut report = importer.empty_report();
+    for offset in 0..importer.table.rows.len() {
+        importer.import_row(op, offset, &mut report).await?;
+    }
+    Ok(report)
+}
+
+fn validate_job_id(job_id: &str) -> Result<()> {
+    Uuid::parse_str(job_id)
+        .map_err(|e| anyhow!("Invalid job_id: {}. Must be a valid UUID. ({})", job_id, e))?;
+    Ok(())
+}
+
+fn job_path(ws_path: &str, job_id: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, TABULAR_JOBS_DIR, job_id)
+}
+
+fn job_source_path(ws_path: &str, job_id: &str) -> String {
+    format!("{}/{}/{}.source", ws_path, TABULAR_JOBS_DIR, job_id)
+}
+
+fn job_seed(job_id: &str) -> String {
+    job_id.replace('-', "")[..12].to_string()
+}
+
+async fn write_job(op: &Operator, ws_path: &str, job: &TabularImportJob) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &job_path(ws_path, &job.job_id),
+        serde_json::to_vec_pretty(job)?,
+    )
+    .await?;
+    Ok(())
+}
+
+fn job_lease_name(job_id: &str) -> String {
+    format!("tabular-import-{}", job_id)
+}
+
+/// Claim the right to run `job_id`; fails while another runner holds it.
+async fn claim_job(op: &Operator, ws_path: &str, job_id: &str) -> Result<SpaceLease> {
+    space_lock::try_acquire_lease(
+        op,
+        ws_path,
+        &job_lease_name(job_id),
+        space_lock::process_holder_id(),
+        TABULAR_JOB_LEASE_TTL_SECS,
+    )
+    .await
+    .map_err(|_| anyhow!("Import job is still running: {}", job_id))
+}
+
+async fn run_job(op: Operator, ws_path: String, mut job: TabularImportJob, lease: SpaceLease) {
+    let mut lease = lease;
+    let result = run_job_rows(&op, &ws_path, &mut job, &mut lease).await;
+    match result {
+        Ok(()) => {
+            job.status = ImportJobStatus::Completed;
+            job.status_message = Some("Completed".to_string());
+            job.error = None;
+            let _ = op.delete(&job_source_path(&ws_path, &job.job_id)).await;
+        }
+        Err(err) => {
+            job.status = ImportJobStatus::Failed;
+            job.status_message = Some("Failed".to_string());
+            job.error = Some(err.to_string());
+        }
+    }
+    job.completed_at = Some(Utc::now());
+    let _ = write_job(&op, &ws_path, &job).await;
+    let _ = space_lock::release_lease(&op, &ws_path, &lease).await;
+}
+
+async fn run_job_rows(
+    op: &Operator,
+    ws_path: &str,
+    job: &mut TabularImportJob,
+    lease: &mut SpaceLease,
+) -> Result<()> {
+    let bytes = op
+        .read(&job_source_path(ws_path, &job.job_id))
+        .await
+        .context("Import job source data is missing")?
+        .to_vec();
+    let importer = RowImporter::new(
+        op,
+        ws_path,
+        &job.form,
+        &bytes,
+        &job.mapping,
+        &job.options,
+        job_seed(&job.job_id),
+    )
+    .await?;
+
+    job.status = ImportJobStatus::Running;
+    job.status_message = Some("Importing rows".to_string());
+    job.started_at.get_or_insert_with(Utc::now);
+    job.completed_at = None;
+    job.error = None;
+    write_job(op, ws_path, job).await?;
+
+    // The stored report always matches `processed_rows`, so replay starts there.
+    let total = importer.table.rows.len();
+    let mut last_flushed = job.processed_rows;
+    for offset in job.processed_rows..total {
+        if lease.expires_at - entry::now_ts() < TABULAR_JOB_LEASE_TTL_SECS as f64 / 2.0 {
+            *lease =
+                space_lock::renew_lease(op, ws_path, lease, TABULAR_JOB_LEASE_TTL_SECS).await?;
+        }
+        importer.import_row(op, offset, &mut job.report).await?;
+        job.processed_rows = offset + 1;
+        if job.processed_rows - last_flushed >= TABULAR_JOB_FLUSH_INTERVAL {
+            write_job(op, ws_path, job).await?;
+            last_flushed = job.processed_rows;
+        }
+    }
+    Ok(())
+}
+
+/// Queue a tabular import in the background. The uploaded file is kept next to
+/// the job record until the job completes so it can be resumed with
+/// [`resume_tabular_import_job`].
+pub async fn create_tabular_import_job(
+    op: &Operator,
+    ws_path: &str,
+    form_id: &str,
+    bytes: &[u8],
+    mapping: &BTreeMap<String, String>,
+    options: &TabularImportOptions,
+) -> Result<TabularImportJob> {
+    let importer =
+        RowImporter::new(op, ws_path, form_id, bytes, mapping, options, String::new()).await?;
+    let job = TabularImportJob {
+        job_id: Uuid::new_v4().to_string(),
+        space_id: space::space_id_from_ws_path(ws_path).to_string(),
+        form: form_id.to_string(),
+        status: ImportJobStatus::Queued,
+        status_message: Some("Queued".to_string()),
+        processed_rows: 0,
+        total_rows: importer.table.rows.len(),
+        mapping: mapping.clone(),
+        options: options.clone(),
+        started_at: None,
+        completed_at: None,
+        error: None,
+        report: importer.empty_report(),
+    };
+    let lease = claim_job(op, ws_path, &job.job_id).await?;
+    op.write(&job_source_path(ws_path, &job.job_id), bytes.to_vec())
+        .await?;
+    write_job(op, ws_path, &job).await?;
+
+    tokio::spawn(run_job(op.clone(), ws_path.to_string(), job.clone(), lease));
+    Ok(job)
+}
+
+pub async fn get_tabular_import_job(
+    op: &Operator,
+    ws_path: &str,
+    job_id: &str,
+) -> Result<TabularImportJob> {
+    validate_job_id(job_id)?;
+    let path = job_path(ws_path, job_id);
+    if !op.exists(&path).await? {
+        return Err(anyhow!("Import job not found: {}", job_id));
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Continue a failed or interrupted tabular import from its last checkpoint.
+/// Rows imported after the checkpoint are detected by their entry id and not
+/// created twice.
+///
+/// A job counts as interrupted when it is queued or running but its runner's
+/// lease has lapsed; jobs whose runner is still alive are refused, so a job
+/// never has two runners.
+pub async fn resume_tabular_import_job(
+    op: &Operator,
+    ws_path: &str,
+    job_id: &str,
+) -> Result<TabularImportJob> {
+    if get_tabular_import_job(op, ws_path, job_id).await?.status == ImportJobStatus::Completed {
+        return Err(anyhow!("Import job already completed: {}", job_id));
+    }
+    let lease = claim_job(op, ws_path, job_id).await?;
+    // Re-read under the lease: the previous runner may have just finished.
+    let queued = async {
+        let mut job = get_tabular_import_job(op, ws_path, job_id).await?;
+        if job.status == ImportJobStatus::Completed {
+            return Err(anyhow!("Import job already completed: {}", job_id));
+        }
+        if !op.exists(&job_source_path(ws_path, job_id)).await? {
+            return Err(anyhow!("Import job source data is missing: {}", job_id));
+        }
+        job.status = ImportJobStatus::Queued;
+        job.status_message = Some("Resuming".to_string());
+        write_job(op, ws_path, &job).await?;
+        Ok(job)
+    }
+    .await;
+    match queued {
+        Ok(job) => {
+            tokio::spawn(run_job(op.clone(), ws_path.to_string(), job.clone(), lease));
+            Ok(job)
+        }
+        Err(err) => {
+            space_lock::release_lease(op, ws_path, &lease).await?;
+            Err(err)
+        }
+    }
+}
+
+/// One exported row: metadata values followed by form field values.
+struct ExportRow {
+    entry_id: String,
+    title: String,
+    tags: String,
+    author: String,
+    created_at: f64,
+    updated_at: f64,
+    revision_id: String,
+    fields: Vec<Value>,
+}
+
+impl ExportRow {
+    fn metadata_text(&self, column: usize) -> String {
+        match column {
+            0 => self.entry_id.clone(),
+            1 => self.title.clone(),
+            2 => self.tags.clone(),
+            3 => self.author.clone(),
+            4 => timestamp_text(self.created_at),
+            5 => timestamp_text(self.updated_at),
+            _ => self.revision_id.clone(),
+        }
+    }
+}
+
+fn timestamp_text(ts: f64) -> String {
+    DateTime::<Utc>::from_timestamp_micros((ts * 1_000_000.0).round() as i64)
+        .unwrap_or_default()
+        .to_rfc3339()
+}
+
+fn field_types(form_def: &Value, fields: &[String]) -> Vec<String> {
+    fields
+        .iter()
+        .map(|name| {
+            let def = match form_def.get("fields") {
+                Some(Value::Object(map)) => map.get(name).cloned(),
+                Some(Value::Array(items)) => items
+                    .iter()
+                    .find(|item| item.get("name").and_then(|v| v.as_str()) == Some(name))
+                    .cloned(),
+                _ => None,
+            };
+            def.and_then(|d| d.get("type").and_then(|t| t.as_str()).map(str::to_string))
+                .unwrap_or_else(|| "string".to_string())
+        })
+        .collect()
+}
+
+async fn collect_export_rows(
+    op: &Operator,
+    ws_path: &str,
+    form_id: &str,
+    form_def: &Value,
+    fields: &[String],
+) -> Result<Vec<ExportRow>> {
+    let mut rows: Vec<entry::EntryRow> =
+        entry::list_form_entry_rows(op, ws_path, form_id, form_def)
+            .await?
+            .into_iter()
+            .filter(|row| !row.deleted)
+            .collect();
+    let revisions = entry::latest_form_revisions(op, ws_path, form_id, form_def).await?;
+    for row in &mut rows {
+        if let Some(revision) = revisions.get(&row.entry_id) {
+            row.revision_id = revision.revision_id.clone();
+            row.author = revision.author.clone();
+        }
+        field_crypto::mask_fields(&mut row.fields);
+    }
+    rows.sort_by(|a, b| {
+        a.created_at
+            .partial_cmp(&b.created_at)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.entry_id.cmp(&b.entry_id))
+    });
+    Ok(rows
+        .into_iter()
+        .map(|row| ExportRow {
+            fields: fields
+                .iter()
+                .map(|name| row.fields.get(name).cloned().unwrap_or(Value::Null))
+                .collect(),
+            tags: row.tags.join(";"),
+            entry_id: row.entry_id,
+            title: row.title,
+            author: row.author,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            revision_id: row.revision_id,
+        })
+        .collect())
+}
+
+fn write_csv<W: Write>(headers: &[String], rows: &[ExportRow], writer: W) -> Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    csv_writer.write_record(headers)?;
+    for row in rows {
+        let mut record: Vec<String> = (0..EXPORT_METADATA_COLUMNS.len())
+            .map(|idx| row.metadata_text(idx))
+            .collect();
+        record.extend(row.fields.iter().map(entry::section_value_to_string));
+        csv_writer.write_record(&record)?;
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
+
+fn write_xlsx<W: Write>(
+    form_id: &str,
+    headers: &[String],
+    rows: &[ExportRow],
+    mut writer: W,
+) -> Result<()> {
+    let mut workbook = rust_xlsxwriter::Workbook::new();
+    let sheet = workbook.add_worksheet();
+    // Sheet names are limited to 31 characters and may not contain []:*?/\.
+    let sheet_name: String = form_id
+        .chars()
+        .filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\'))
+        .take(31)
+        .collect();
+    if !sheet_name.is_empty() {
+        sheet.set_name(sheet_name)?;
+    }
+    for (col, header) in headers.iter().enumerate() {
+        sheet.write_string(0, col as u16, header)?;
+    }
+    for (idx, row) in rows.iter().enumerate() {
+        let row_idx = (idx + 1) as u32;
+        for col in 0..EXPORT_METADATA_COLUMNS.len() {
+            sheet.write_string(row_idx, col as u16, row.metadata_text(col))?;
+        }
+        for (offset, value) in row.fields.iter().enumerate() {
+            let col = (EXPORT_METADATA_COLUMNS.len() + offset) as u16;
+            match value {
+                Value::Null => {}
+                Value::Number(n) => {
+                    sheet.write_number(row_idx, col, n.as_f64().unwrap_or_default())?;
+                }
+                Value::Bool(b) => {
+                    sheet.write_boolean(row_idx, col, *b)?;
+                }
+                other => {
+                    sheet.write_string(row_idx, col, entry::section_value_to_string(other))?;
+                }
+            }
+        }
+    }
+    writer.write_all(&workbook.save_to_buffer()?)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn parquet_type(field_type: &str) -> DataType {
+    match field_type {
+        "number" | "double" => DataType::Float64,
+        "float" => DataType::Float32,
+        "integer" => DataType::Int32,
+        "long" | "duration" => DataType::Int64,
+        "boolean" => DataType::Boolean,
+        _ => DataType::Utf8,
+    }
+}
+
+fn parquet_column(data_type: &DataType, values: &[&Value]) -> ArrayRef {
+    match data_type {
+        DataType::Float64 => {
+            let mut builder = Float64Builder::with_capacity(values.len());
+            for value in values {
+                builder.append_option(value.as_f64());
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Float32 => {
+            let mut builder = Float32Builder::with_capacity(values.len());
+            for value in values {
+                builder.append_option(value.as_f64().map(|v| v as f32));
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Int32 => {
+            let mut builder = Int32Builder::with_capacity(values.len());
+            for value in values {
+                builder.append_option(value.as_i64().and_then(|v| i32::try_from(v).ok()));
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Int64 => {
+            let mut builder = Int64Builder::with_capacity(values.len());
+            for value in values {
+                builder.append_option(value.as_i64());
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Boolean => {
+            let mut builder = BooleanBuilder::with_capacity(values.len());
+            for value in values {
+                builder.append_option(value.as_bool());
+            }
+            Arc::new(builder.finish())
+        }
+        _ => {
+            let mut builder = StringBuilder::new();
+            for value in values {
+                if value.is_null() {
+                    builder.append_null();
+                } else {
+                    builder.append_value(entry::section_value_to_string(value));
+                }
+            }
+            Arc::new(builder.finish())
+        }
+    }
+}
+
+fn write_parquet<W: Write + Send>(
+    headers: &[String],
+    field_types: &[String],
+    rows: &[ExportRow],
+    writer: W,
+) -> Result<()> {
+    let timestamp = DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()));
+    let mut schema_fields = Vec::with_capacity(headers.len());
+    for (idx, name) in EXPORT_METADATA_COLUMNS.iter().enumerate() {
+        let data_type = if matches!(idx, 4 | 5) {
+            timestamp.clone()
+        } else {
+            DataType::Utf8
+        };
+        schema_fields.push(Field::new(*name, data_type, idx == 2 || idx == 3));
+    }
+    for (name, field_type) in headers[EXPORT_METADATA_COLUMNS.len()..]
+        .iter()
+        .zip(field_types)
+    {
+        schema_fields.push(Field::new(name, parquet_type(field_type), true));
+    }
+    let schema = Arc::new(Schema::new(schema_fields));
+
+    let mut arrow_writer = ArrowWriter::try_new(writer, schema.clone(), None)
+        .map_err(|e| anyhow!("Parquet writer error: {}", e))?;
+    for chunk in rows.chunks(PARQUET_EXPORT_BATCH_ROWS) {
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(headers.len());
+        for idx in 0..EXPORT_METADATA_COLUMNS.len() {
+            if matches!(idx, 4 | 5) {
+                let mut builder =
+                    TimestampMicrosecondBuilder::with_capacity(chunk.len()).with_timezone("UTC");
+                for row in chunk {
+                    let ts = if idx == 4 {
+                        row.created_at
+                    } else {
+                        row.updated_at
+                    };
+                    builder.append_value((ts * 1_000_000.0).round() as i64);
+                }
+                columns.push(Arc::new(builder.finish()));
+            } else {
+                let mut builder = StringBuilder::new();
+                for row in chunk {
+                    builder.append_value(row.metadata_text(idx));
+                }
+                columns.push(Arc::new(builder.finish()));
+            }
+        }
+        for (offset, field) in schema.fields()[EXPORT_METADATA_COLUMNS.len()..]
+            .iter()
+            .enumerate()
+        {
+            let values: Vec<&Value> = chunk.iter().map(|row| &row.fields[offset]).collect();
+            columns.push(parquet_column(field.data_type(), &values));
+        }
+        let batch = RecordBatch::try_new(schema.clone(), columns)
+            .map_err(|e| anyhow!("Record batch error: {}", e))?;
+        arrow_writer
+            .write(&batch)
+            .map_err(|e| anyhow!("Parquet write error: {}", e))?;
+    }
+    arrow_writer
+        .close()
+        .map_err(|e| anyhow!("Parquet close error: {}", e))?;
+    Ok(())
+}
+
+/// Write every live entry of a form as CSV, XLSX, or Parquet.
+///
+/// Rows come from the form's entries table, so markdown is never re-parsed.
+/// Columns are the metadata columns followed by the form fields in schema order.
+/// Returns the number of exported rows.
+pub async fn export_tabular<W: Write + Send>(
+    op: &Operator,
+    ws_path: &str,
+    form_id: &str,
+    format: TabularExportFormat,
+    writer: W,
+) -> Result<usize> {
+    export_form_rows(op, ws_path, form_id, format, writer, None).await
+}
+
+/// Like [`export_tabular`], with PII in titles and field values replaced per `config`.
+pub async fn export_tabular_redacted<W: Write + Send>(
+    op: &Operator,
+    ws_path: &str,
+    form_id: &str,
+    format: TabularExportFormat,
+    writer: W,
+    config: &RedactionConfig,
+) -> Result<usize> {
+    let redactor = Redactor::new(config)?;
+    export_form_rows(op, ws_path, form_id, format, writer, Some(&redactor)).await
+}
+
+async fn export_form_rows<W: Write + Send>(
+    op: &Operator,
+    ws_path: &str,
+    form_id: &str,
+    format: TabularExportFormat,
+    writer: W,
+    redactor: Option<&Redactor>,
+) -> Result<usize> {
+    if !op.exists(&format!("{}/meta.json", ws_path)).await? {
+        return Err(anyhow!("Space not found: {}", ws_path));
+    }
+    if metadata::is_reserved_metadata_form(form_id) {
+        return Err(anyhow!("Cannot export metadata form: {}", form_id));
+    }
+    let form_def = form::read_form_definition(op, ws_path, form_id).await?;
+    let fields = entry::form_field_names(&form_def);
+    let mut rows = collect_export_rows(op, ws_path, form_id, &form_def, &fields).await?;
+    if let Some(redactor) = redactor {
+        for row in &mut rows {
+            row.title = redactor.redact_text(&row.title);
+            for value in &mut row.fields {
+                if value.is_string() {
+                    *value = redactor.redact_value(value);
+                }
+            }
+        }
+    }
+    let headers: Vec<String> = EXPORT_METADATA_COLUMNS
+        .iter()
+        .map(|c| c.to_string())
+        .chain(fields.iter().cloned())
+        .collect();
+
+    match format {
+        TabularExportFormat::Csv => write_csv(&headers, &rows, writer)?,
+        TabularExportFormat::Xlsx => write_xlsx(form_id, &headers, &rows, writer)?,
+        TabularExportFormat::Parquet => {
+            write_parquet(&headers, &field_types(&form_def, &fields), &rows, writer)?
+        }
+    }
+    Ok(rows.len())
+}
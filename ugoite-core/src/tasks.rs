@@ -0,0 +1,234 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::entry;
+use crate::form;
+use crate::integrity::IntegrityProvider;
+use crate::mentions;
+use crate::metadata;
+
+/// A `- [ ]` / `- [x]` item found in entry markdown.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ParsedTask {
+    /// Position among the entry's tasks, used by [`toggle_task`].
+    pub index: usize,
+    pub checked: bool,
+    pub text: String,
+    /// `YYYY-MM-DD` from a `due:` or `📅` annotation.
+    pub due: Option<String>,
+    /// `@name` mentions in the task text, without the `@`.
+    pub owners: Vec<String>,
+    /// Zero-based line in the entry markdown.
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TaskItem {
+    pub entry_id: String,
+    pub entry_title: String,
+    pub form: String,
+    #[serde(flatten)]
+    pub task: ParsedTask,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TaskFilter {
+    #[serde(default)]
+    pub checked: Option<bool>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Inclusive `YYYY-MM-DD` bounds; tasks without a due date never match.
+    #[serde(default)]
+    pub due_before: Option<String>,
+    #[serde(default)]
+    pub due_after: Option<String>,
+    #[serde(default)]
+    pub form: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(default)]
+    pub entry_id: Option<String>,
+}
+
+fn task_regex() -> &'static Regex {
+    static TASK_REGEX: OnceLock<Regex> = OnceLock::new();
+    TASK_REGEX.get_or_init(|| {
+        Regex::new(r"^(\s*(?:[-*+]|\d+[.)])\s+\[)([ xX])(\]\s+)(.*)$")
+            .expect("task regex must compile")
+    })
+}
+
+fn due_regex() -> &'static Regex {
+    static DUE_REGEX: OnceLock<Regex> = OnceLock::new();
+    DUE_REGEX.get_or_init(|| {
+        Regex::new(r"(?:\bdue:\s*|📅\s*)(\d{4}-\d{2}-\d{2})").expect("due regex must compile")
+    })
+}
+
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Extract task items from markdown, skipping fenced code blocks.
+pub fn parse_tasks(markdown: &str) -> Vec<ParsedTask> {
+    let mut tasks = Vec::new();
+    let mut in_fence = false;
+    for (line_no, line) in markdown.lines().enumerate() {
+        if is_fence(line) {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        let Some(caps) = task_regex().captures(line) else {
+            continue;
+        };
+        let text = caps[4].trim().to_string();
+        let due = due_regex().captures(&text).map(|due| due[1].to_string());
+        let owners = mentions::parse_mentions(&text);
+        tasks.push(ParsedTask {
+            index: tasks.len(),
+            checked: &caps[2] != " ",
+            text,
+            due,
+            owners,
+            line: line_no,
+        });
+    }
+    tasks
+}
+
+fn matches_filter(task: &ParsedTask, filter: &TaskFilter) -> bool {
+    if filter
+        .checked
+        .is_some_and(|checked| checked != task.checked)
+    {
+        return false;
+    }
+    if let Some(owner) = filter.owner.as_deref() {
+        let owner = owner.trim_start_matches('@');
+        if !task.owners.iter().any(|o| o.eq_ignore_ascii_case(owner)) {
+            return false;
+        }
+    }
+    if filter.due_before.is_some() || filter.due_after.is_some() {
+        let Some(due) = task.due.as_deref() else {
+            return false;
+        };
+        if filter.due_before.as_deref().is_some_and(|end| due > end)
+            || filter.due_after.as_deref().is_some_and(|start| due < start)
+        {
+            return false;
+        }
+    }
+    true
+}
+
+/// List task items across the space, ordered by entry id then task index.
+pub async fn list_tasks(
+    op: &Operator,
+    ws_path: &str,
+    filter: &TaskFilter,
+) -> Result<Vec<TaskItem>> {
+    let mut rows: Vec<(String, entry::EntryRow)> = entry::list_entry_rows(op, ws_path)
+        .await?
+        .into_iter()
+        .filter(|(form_name, row)| {
+            !row.deleted
+                && !metadata::is_reserved_metadata_form(form_name)
+                && filter.form.as_deref().is_none_or(|f| f == form_name)
+                && filter
+                    .entry_id
+                    .as_deref()
+                    .is_none_or(|id| id == row.entry_id)
+                && filter
+                    .tag
+                    .as_deref()
+                    .is_none_or(|tag| row.tags.iter().any(|t| t == tag))
+        })
+        .collect();
+    rows.sort_by(|a, b| a.1.entry_id.cmp(&b.1.entry_id));
+
+    let mut form_defs: HashMap<String, Value> = HashMap::new();
+    let mut items = Vec::new();
+    for (form_name, row) in rows {
+        if !form_defs.contains_key(&form_name) {
+            let def = form::read_form_definition(op, ws_path, &form_name).await?;
+            form_defs.insert(form_name.clone(), def);
+        }
+        let markdown = entry::render_markdown_for_form(
+            &row.title,
+            &form_name,
+            &row.tags,
+            &row.fields,
+            &row.extra_attributes,
+            &form_defs[&form_name],
+        );
+        for task in parse_tasks(&markdown) {
+            if matches_filter(&task, filter) {
+                items.push(TaskItem {
+                    entry_id: row.entry_id.clone(),
+                    entry_title: row.title.clone(),
+                    form: form_name.clone(),
+                    task,
+                });
+            }
+        }
+    }
+    Ok(items)
+}
+
+/// Flip the checkbox of the `task_index`-th task in an entry and save a new revision.
+pub async fn toggle_task<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    task_index: usize,
+    author: &str,
+    integrity: &I,
+) -> Result<Value> {
+    let current = entry::get_entry(op, ws_path, entry_id).await?;
+    let content = current
+        .get("content")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let revision_id = current
+        .get("revision_id")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let task = parse_tasks(content)
+        .into_iter()
+        .nth(task_index)
+        .ok_or_else(|| anyhow!("Task {} not found in entry {}", task_index, entry_id))?;
+
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let line = &mut lines[task.line];
+    *line = task_regex()
+        .replace(line, |caps: &regex::Captures| {
+            let mark = if task.checked { " " } else { "x" };
+            format!("{}{}{}{}", &caps[1], mark, &caps[3], &caps[4])
+        })
+        .into_owned();
+    let mut updated = lines.join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    entry::update_entry(
+        op,
+        ws_path,
+        entry_id,
+        &updated,
+        revision_id.as_deref(),
+        author,
+        None,
+        integrity,
+    )
+    .await
+}
@@ -0,0 +1,197 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Space setting naming the offset applied to `datetime` values written
+/// without one, e.g. `"+09:00"`.
+pub const DEFAULT_TIMEZONE_SETTING: &str = "default_timezone";
+/// Form-level and field-level key overriding the space default timezone.
+pub const TIMEZONE_KEY: &str = "timezone";
+
+const NAIVE_DATETIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+];
+
+pub fn utc_offset() -> FixedOffset {
+    FixedOffset::east_opt(0).expect("zero offset is valid")
+}
+
+/// Parse a fixed UTC offset: `UTC`, `Z`, `+09:00`, `-0530` or `+9`.
+///
+/// Named zones such as `Asia/Tokyo` are not supported because their offsets
+/// change with daylight saving rules.
+pub fn parse_offset(value: &str) -> Result<FixedOffset> {
+    let trimmed = value.trim();
+    if trimmed.eq_ignore_ascii_case("utc") || trimmed.eq_ignore_ascii_case("z") {
+        return Ok(utc_offset());
+    }
+    let invalid = || anyhow!("Invalid timezone offset: {} (expected e.g. +09:00)", value);
+    let (sign, rest) = match trimmed.as_bytes().first() {
+        Some(b'+') => (1, &trimmed[1..]),
+        Some(b'-') => (-1, &trimmed[1..]),
+        _ => return Err(invalid()),
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    let hours: i32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+    if !(0..=14).contains(&hours) || !(0..60).contains(&minutes) {
+        return Err(invalid());
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+/// Offset applied to naive `datetime` values of `field_def` in `form_def`:
+/// the field's `timezone`, then the form's, then UTC. Entry writes copy the
+/// space default into the form definition they validate against.
+pub fn field_offset(form_def: &Value, field_def: &Value) -> FixedOffset {
+    [field_def, form_def]
+        .iter()
+        .find_map(|def| def.get(TIMEZONE_KEY).and_then(Value::as_str))
+        .and_then(|tz| parse_offset(tz).ok())
+        .unwrap_or_else(utc_offset)
+}
+
+/// Normalize a `datetime` value to RFC3339 keeping its offset, so the stored
+/// string carries both the UTC instant and the offset it was written in.
+/// Values without an offset are read in `default_offset`; bare dates are
+/// midnight there.
+pub fn normalize_datetime(value: &str, default_offset: FixedOffset) -> Option<String> {
+    let trimmed = value.trim();
+    let parsed = DateTime::parse_from_rfc3339(trimmed).ok().or_else(|| {
+        let naive = NAIVE_DATETIME_FORMATS
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(trimmed, format).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })?;
+        default_offset.from_local_datetime(&naive).single()
+    })?;
+    Some(parsed.to_rfc3339_opts(SecondsFormat::AutoSi, false))
+}
+
+/// UTC instant of an RFC3339 string, or `None` for anything else.
+pub fn datetime_utc(value: &str) -> Option<DateTime<Utc>> {
+    // Cheap shape check first: this runs on every string comparison in SQL.
+    let bytes = value.as_bytes();
+    if bytes.len() < 20 || bytes[4] != b'-' || !matches!(bytes[10], b'T' | b't' | b' ') {
+        return None;
+    }
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Order two values as instants when both are RFC3339 strings, so values
+/// written in different offsets compare by the moment they denote.
+pub fn compare_instants(left: &Value, right: &Value) -> Option<Ordering> {
+    let left = datetime_utc(left.as_str()?)?;
+    let right = datetime_utc(right.as_str()?)?;
+    Some(left.cmp(&right))
+}
+
+/// Parse a `duration` into whole seconds. Accepts a number of seconds,
+/// ISO 8601 (`PT1H30M`, `P2DT4H`, `P1W`), compact units (`1h30m`, `2d`,
+/// `45s`) and clock form (`1:30:00`, `90:00` as minutes and seconds). Years
+/// and months are rejected because their length varies.
+pub fn parse_duration_secs(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(number) => number
+            .as_i64()
+            .or_else(|| number.as_f64().map(|secs| secs.round() as i64)),
+        Value::String(text) => parse_duration_str(text.trim()),
+        _ => None,
+    }
+}
+
+fn parse_duration_str(text: &str) -> Option<i64> {
+    let (sign, body) = match text.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, text),
+    };
+    if body.is_empty() {
+        return None;
+    }
+    let secs = if let Ok(secs) = body.parse::<i64>() {
+        secs
+    } else if let Some(iso) = body.strip_prefix('P').or_else(|| body.strip_prefix('p')) {
+        parse_iso_duration(iso)?
+    } else if body.contains(':') {
+        parse_clock_duration(body)?
+    } else {
+        parse_unit_duration(body)?
+    };
+    Some(sign * secs)
+}
+
+fn unit_seconds(unit: char, in_time: bool) -> Option<f64> {
+    match (unit.to_ascii_lowercase(), in_time) {
+        ('w', _) => Some(7.0 * 86_400.0),
+        ('d', _) => Some(86_400.0),
+        ('h', _) => Some(3_600.0),
+        ('m', true) => Some(60.0),
+        ('s', true) => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Sum `<number><unit>` pairs, e.g. `1h30m`. `in_time` admits minutes and
+/// seconds, which ISO 8601 only allows after `T`.
+fn sum_units(text: &str, in_time: bool) -> Option<f64> {
+    let mut total = 0.0;
+    let mut number = String::new();
+    for ch in text.chars() {
+        if ch.is_ascii_digit() || ch == '.' {
+            number.push(ch);
+            continue;
+        }
+        let amount: f64 = number.parse().ok()?;
+        total += amount * unit_seconds(ch, in_time)?;
+        number.clear();
+    }
+    number.is_empty().then_some(total)
+}
+
+fn parse_iso_duration(text: &str) -> Option<i64> {
+    let (date_part, time_part) = match text.split_once(['T', 't']) {
+        Some((date, time)) if !time.is_empty() => (date, Some(time)),
+        Some(_) => return None,
+        None => (text, None),
+    };
+    if date_part.is_empty() && time_part.is_none() {
+        return None;
+    }
+    let mut total = sum_units(date_part, false)?;
+    if let Some(time) = time_part {
+        total += sum_units(time, true)?;
+    }
+    Some(total.round() as i64)
+}
+
+fn parse_unit_duration(text: &str) -> Option<i64> {
+    let compact: String = text.split_whitespace().collect();
+    sum_units(&compact, true).map(|secs| secs.round() as i64)
+}
+
+fn parse_clock_duration(text: &str) -> Option<i64> {
+    let parts: Vec<i64> = text
+        .split(':')
+        .map(|part| part.parse::<i64>().ok().filter(|n| *n >= 0))
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [minutes, seconds] if *seconds < 60 => Some(minutes * 60 + seconds),
+        [hours, minutes, seconds] if *minutes < 60 && *seconds < 60 => {
+            Some(hours * 3_600 + minutes * 60 + seconds)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,438 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+use crate::clock;
+use crate::entry;
+use crate::form;
+use crate::space_lock;
+use crate::storage;
+
+const UNIQUE_INDEX_DIR: &str = "unique_index";
+/// Space lease serializing uniqueness index updates across processes.
+const UNIQUE_INDEX_LEASE: &str = "unique_index";
+/// Field types holding several values, which cannot be declared unique.
+const MULTI_VALUE_FIELD_TYPES: &[&str] = &["list", "object_list"];
+/// How long a claim whose entry row is not written yet counts as held.
+const PENDING_CLAIM_TTL_SECS: f64 = 300.0;
+
+/// Values claimed by entries of one form: field name, then value key, then
+/// the entry holding it.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct UniqueIndex {
+    #[serde(default)]
+    pub fields: BTreeMap<String, BTreeMap<String, String>>,
+    /// Entries whose claims are reserved but whose row is not written yet,
+    /// with the unix time their claims stop counting as held.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pending: BTreeMap<String, f64>,
+}
+
+impl UniqueIndex {
+    fn is_pending(&self, entry_id: &str, now: f64) -> bool {
+        self.pending
+            .get(entry_id)
+            .is_some_and(|expires_at| *expires_at > now)
+    }
+}
+
+/// Claims taken by [`reserve_unique_values`]. Call [`UniqueReservation::commit`]
+/// once the entry row is written, or [`UniqueReservation::release`] when the
+/// write fails before that.
+#[derive(Debug, Clone)]
+#[must_use]
+pub(crate) struct UniqueReservation {
+    form_name: String,
+    entry_id: String,
+    /// Field to value key the entry claims with this write.
+    claimed: BTreeMap<String, String>,
+    /// Field to value key the entry held before this write.
+    previous: BTreeMap<String, String>,
+    active: bool,
+}
+
+/// Error raised when a write would give a unique field a value another entry
+/// already holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniqueConflict {
+    pub form: String,
+    pub field: String,
+    pub value: String,
+    /// The entry already holding `value`.
+    pub entry_id: String,
+}
+
+impl UniqueConflict {
+    pub fn as_json(&self) -> Value {
+        json!({
+            "code": "unique_conflict",
+            "form": self.form,
+            "field": self.field,
+            "value": self.value,
+            "entry_id": self.entry_id,
+        })
+    }
+}
+
+impl std::fmt::Display for UniqueConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Unique constraint violated: {}.{} value '{}' is already used by entry {}",
+            self.form, self.field, self.value, self.entry_id
+        )
+    }
+}
+
+impl std::error::Error for UniqueConflict {}
+
+/// Several entries sharing a value of a unique field.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct UniqueDuplicate {
+    pub field: String,
+    pub value: String,
+    /// Oldest first; the first entry keeps the value in the index.
+    pub entry_ids: Vec<String>,
+}
+
+/// Outcome of [`verify_unique_index`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct UniqueIndexReport {
+    pub form: String,
+    pub fields: Vec<String>,
+    pub entries: usize,
+    /// Values held by entries but missing from the index or claimed there by
+    /// another entry.
+    pub missing: usize,
+    /// Index values no entry holds any more.
+    pub stale: usize,
+    pub duplicates: Vec<UniqueDuplicate>,
+    /// Whether the rebuilt index was written.
+    pub repaired: bool,
+}
+
+fn index_path(ws_path: &str, form_name: &str) -> String {
+    format!("{}/{}/{}.json", ws_path, UNIQUE_INDEX_DIR, form_name)
+}
+
+/// Names of form fields declared with `"unique": true`.
+pub fn unique_field_names(form_def: &Value) -> Vec<String> {
+    let fields = form::normalize_form_fields(form_def.get("fields"));
+    let Some(field_map) = fields.as_object() else {
+        return Vec::new();
+    };
+    field_map
+        .iter()
+        .filter(|(_, def)| def.get("unique").and_then(Value::as_bool) == Some(true))
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+pub(crate) fn validate_unique_field_defs(field_map: &Map<String, Value>) -> Result<()> {
+    for (name, def) in field_map {
+        let Some(unique) = def.get("unique") else {
+            continue;
+        };
+        if !unique.is_boolean() {
+            return Err(anyhow!(
+                "Field '{}' unique attribute must be a boolean",
+                name
+            ));
+        }
+        if unique.as_bool() != Some(true) {
+            continue;
+        }
+        let field_type = def.get("type").and_then(Value::as_str).unwrap_or("string");
+        if MULTI_VALUE_FIELD_TYPES.contains(&field_type) {
+            return Err(anyhow!(
+                "Unique field '{}' cannot be of type {}",
+                name,
+                field_type
+            ));
+        }
+        if def.get("secret").and_then(Value::as_bool) == Some(true) {
+            return Err(anyhow!("Secret field '{}' cannot be unique", name));
+        }
+    }
+    Ok(())
+}
+
+/// Index key of a field value; empty values are not indexed.
+fn value_key(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::Null => None,
+        Value::String(text) if text.is_empty() => None,
+        Value::String(text) => Some(text.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+async fn load_index(op: &Operator, ws_path: &str, form_name: &str) -> Result<UniqueIndex> {
+    let path = index_path(ws_path, form_name);
+    if !op.exists(&path).await? {
+        return Ok(UniqueIndex::default());
+    }
+    let bytes = op.read(&path).await?.to_vec();
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+async fn write_index(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    index: &UniqueIndex,
+) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &index_path(ws_path, form_name),
+        serde_json::to_vec_pretty(index)?,
+    )
+    .await
+}
+
+fn now_secs() -> f64 {
+    clock::now().timestamp_millis() as f64 / 1000.0
+}
+
+/// Whether `owner` still holds `key` in `field`. Pending claims hold until
+/// they expire, and entries in the trash keep their values so they can be
+/// restored; claims left by failed writes or by edits that bypassed the index
+/// are not honoured.
+async fn still_holds(
+    op: &Operator,
+    ws_path: &str,
+    index: &UniqueIndex,
+    form_name: &str,
+    owner: &str,
+    field: &str,
+    key: &str,
+) -> Result<bool> {
+    if index.is_pending(owner, now_secs()) {
+        return Ok(true);
+    }
+    if entry::find_entry_form(op, ws_path, owner).await?.as_deref() != Some(form_name) {
+        return Ok(false);
+    }
+    let row = entry::read_entry_row(op, ws_path, form_name, owner).await?;
+    Ok(value_key(row.fields.get(field)).as_deref() == Some(key))
+}
+
+/// Claim the unique field values in `fields` for `entry_id`. The claims stay
+/// pending, and the values the entry held before stay claimed, until the
+/// returned reservation is committed or released. Fails with
+/// [`UniqueConflict`] when another entry of the form holds one of them.
+pub(crate) async fn reserve_unique_values(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    form_def: &Value,
+    entry_id: &str,
+    fields: &Value,
+) -> Result<UniqueReservation> {
+    let unique_fields = unique_field_names(form_def);
+    let claimed: BTreeMap<String, String> = unique_fields
+        .iter()
+        .filter_map(|field| Some((field.clone(), value_key(fields.get(field))?)))
+        .collect();
+    let active = !unique_fields.is_empty();
+    if !active {
+        return Ok(UniqueReservation {
+            form_name: form_name.to_string(),
+            entry_id: entry_id.to_string(),
+            claimed,
+            previous: BTreeMap::new(),
+            active,
+        });
+    }
+    let previous = space_lock::with_space_lease(op, ws_path, UNIQUE_INDEX_LEASE, |_| async {
+        let mut index = load_index(op, ws_path, form_name).await?;
+        for (field, key) in &claimed {
+            let Some(owner) = index.fields.get(field).and_then(|values| values.get(key)) else {
+                continue;
+            };
+            if owner != entry_id
+                && still_holds(op, ws_path, &index, form_name, owner, field, key).await?
+            {
+                return Err(anyhow::Error::new(UniqueConflict {
+                    form: form_name.to_string(),
+                    field: field.clone(),
+                    value: key.clone(),
+                    entry_id: owner.clone(),
+                }));
+            }
+        }
+        let previous: BTreeMap<String, String> = unique_fields
+            .iter()
+            .filter_map(|field| {
+                let values = index.fields.get(field)?;
+                let (key, _) = values.iter().find(|(_, owner)| *owner == entry_id)?;
+                Some((field.clone(), key.clone()))
+            })
+            .collect();
+        for (field, key) in &claimed {
+            index
+                .fields
+                .entry(field.clone())
+                .or_default()
+                .insert(key.clone(), entry_id.to_string());
+        }
+        index
+            .pending
+            .insert(entry_id.to_string(), now_secs() + PENDING_CLAIM_TTL_SECS);
+        write_index(op, ws_path, form_name, &index).await?;
+        Ok(previous)
+    })
+    .await?;
+    Ok(UniqueReservation {
+        form_name: form_name.to_string(),
+        entry_id: entry_id.to_string(),
+        claimed,
+        previous,
+        active,
+    })
+}
+
+impl UniqueReservation {
+    /// Keep only the claimed values once the entry row holding them is written.
+    pub(crate) async fn commit(mut self, op: &Operator, ws_path: &str) -> Result<()> {
+        let kept = std::mem::take(&mut self.claimed);
+        self.settle(op, ws_path, kept).await
+    }
+
+    /// Go back to the values the entry held before, after a failed write.
+    pub(crate) async fn release(mut self, op: &Operator, ws_path: &str) -> Result<()> {
+        let kept = std::mem::take(&mut self.previous);
+        self.settle(op, ws_path, kept).await
+    }
+
+    /// Pass `result` through, releasing the reservation first when it is an error.
+    pub(crate) async fn release_on_err<T>(
+        &self,
+        op: &Operator,
+        ws_path: &str,
+        result: Result<T>,
+    ) -> Result<T> {
+        if result.is_err() {
+            if let Err(error) = self.clone().release(op, ws_path).await {
+                eprintln!(
+                    "failed to release unique claims after a failed write (entry_id={}, form={}): {}",
+                    self.entry_id, self.form_name, error
+                );
+            }
+        }
+        result
+    }
+
+    async fn settle(
+        self,
+        op: &Operator,
+        ws_path: &str,
+        kept: BTreeMap<String, String>,
+    ) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+        space_lock::with_space_lease(op, ws_path, UNIQUE_INDEX_LEASE, |_| async {
+            let mut index = load_index(op, ws_path, &self.form_name).await?;
+            let before = index.clone();
+            for (field, values) in index.fields.iter_mut() {
+                values.retain(|key, owner| owner != &self.entry_id || kept.get(field) == Some(key));
+            }
+            index.pending.remove(&self.entry_id);
+            if index != before {
+                write_index(op, ws_path, &self.form_name, &index).await?;
+            }
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Rebuild the uniqueness index of a form from its entries and compare it with
+/// the stored one. Entries in the trash keep their values; where several
+/// entries share a value the oldest keeps it and the clash is reported as a
+/// duplicate. With `repair`, the rebuilt index replaces the stored one, which
+/// is how existing data is backfilled after a field is marked unique.
+pub async fn verify_unique_index(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    repair: bool,
+) -> Result<UniqueIndexReport> {
+    let form_def = form::read_form_definition(op, ws_path, form_name).await?;
+    let fields = unique_field_names(&form_def);
+    let mut rows = entry::list_form_entry_rows(op, ws_path, form_name, &form_def).await?;
+    rows.sort_by(|a, b| {
+        a.created_at
+            .partial_cmp(&b.created_at)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.entry_id.cmp(&b.entry_id))
+    });
+
+    let mut rebuilt = UniqueIndex::default();
+    let mut duplicates = Vec::new();
+    for field in &fields {
+        let mut holders: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for row in &rows {
+            if let Some(key) = value_key(row.fields.get(field)) {
+                holders.entry(key).or_default().push(row.entry_id.clone());
+            }
+        }
+        let values = rebuilt.fields.entry(field.clone()).or_default();
+        for (key, entry_ids) in holders {
+            values.insert(key.clone(), entry_ids[0].clone());
+            if entry_ids.len() > 1 {
+                duplicates.push(UniqueDuplicate {
+                    field: field.clone(),
+                    value: key,
+                    entry_ids,
+                });
+            }
+        }
+    }
+
+    let update = |stored: UniqueIndex| {
+        let empty = BTreeMap::new();
+        let mut missing = 0;
+        let mut stale = 0;
+        for (field, values) in &rebuilt.fields {
+            let held = stored.fields.get(field).unwrap_or(&empty);
+            missing += values
+                .iter()
+                .filter(|(key, owner)| held.get(*key) != Some(owner))
+                .count();
+            stale += held.keys().filter(|key| !values.contains_key(*key)).count();
+        }
+        (missing, stale, stored.fields != rebuilt.fields)
+    };
+    let (missing, stale, repaired) = if repair {
+        space_lock::with_space_lease(op, ws_path, UNIQUE_INDEX_LEASE, |_| async {
+            let stored = load_index(op, ws_path, form_name).await?;
+            let pending = stored.pending.clone();
+            let (missing, stale, differs) = update(stored);
+            if differs {
+                // Writes still in flight keep their pending claims.
+                let mut rebuilt = rebuilt.clone();
+                rebuilt.pending = pending;
+                write_index(op, ws_path, form_name, &rebuilt).await?;
+            }
+            Ok((missing, stale, differs))
+        })
+        .await?
+    } else {
+        let (missing, stale, _) = update(load_index(op, ws_path, form_name).await?);
+        (missing, stale, false)
+    };
+
+    Ok(UniqueIndexReport {
+        form: form_name.to_string(),
+        fields,
+        entries: rows.len(),
+        missing,
+        stale,
+        duplicates,
+        repaired,
+    })
+}
@@ -0,0 +1,338 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+use crate::clock;
+use crate::entry;
+use crate::form;
+use crate::metadata;
+use crate::space_lock;
+use crate::storage;
+
+const VIEWS_DIR: &str = "views";
+/// Space lease serializing view changes across processes.
+const VIEWS_LEASE: &str = "views";
+const MAX_VIEWS_PER_FORM: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewLayout {
+    #[default]
+    Table,
+    /// Kanban board with one lane per value of `board_by`.
+    Board,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ViewSort {
+    pub column: String,
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+
+/// A saved table or board configuration of a form's entries.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct View {
+    pub id: String,
+    pub form: String,
+    pub name: String,
+    pub layout: ViewLayout,
+    /// Columns shown, in order; all columns when empty.
+    #[serde(default)]
+    pub columns: Vec<String>,
+    /// Index query filters, as accepted by [`crate::index::query_index`].
+    #[serde(default)]
+    pub filters: Map<String, Value>,
+    /// Sort keys, most significant first.
+    #[serde(default)]
+    pub sort: Vec<ViewSort>,
+    #[serde(default)]
+    pub group_by: Option<String>,
+    /// Field whose values are the lanes of a board.
+    #[serde(default)]
+    pub board_by: Option<String>,
+    /// The form's default view, shown when none is chosen.
+    #[serde(default)]
+    pub is_default: bool,
+    pub created_at: f64,
+    pub updated_at: f64,
+}
+
+impl View {
+    /// The [`crate::index::query_index`] query selecting the view's entries.
+    pub fn query(&self) -> Value {
+        let mut query = self.filters.clone();
+        query.insert("form".to_string(), Value::String(self.form.clone()));
+        Value::Object(query)
+    }
+}
+
+/// A view as given on create and update.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ViewPayload {
+    pub name: String,
+    #[serde(default)]
+    pub layout: ViewLayout,
+    #[serde(default)]
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub filters: Map<String, Value>,
+    #[serde(default)]
+    pub sort: Vec<ViewSort>,
+    #[serde(default)]
+    pub group_by: Option<String>,
+    #[serde(default)]
+    pub board_by: Option<String>,
+    /// Make this the form's default view, replacing the previous one.
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+/// Views of one form, in creation order.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+struct FormViews {
+    #[serde(default)]
+    views: Vec<View>,
+}
+
+fn views_path(ws_path: &str, form_name: &str) -> String {
+    format!(
+        "{}/{}/{}.json",
+        ws_path.trim_end_matches('/'),
+        VIEWS_DIR,
+        form_name
+    )
+}
+
+async fn load_views(op: &Operator, ws_path: &str, form_name: &str) -> Result<FormViews> {
+    match op.read(&views_path(ws_path, form_name)).await {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes.to_vec())?),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(FormViews::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn write_views(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    views: &FormViews,
+) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &views_path(ws_path, form_name),
+        serde_json::to_vec_pretty(views)?,
+    )
+    .await
+}
+
+/// Check the payload's columns against the form, trimming its name.
+fn validate_payload(form_def: &Value, payload: &mut ViewPayload) -> Result<()> {
+    payload.name = payload.name.trim().to_string();
+    if payload.name.is_empty() {
+        return Err(anyhow!("View name must not be empty"));
+    }
+    let fields: HashSet<String> = entry::form_field_names(form_def).into_iter().collect();
+    let known =
+        |column: &str| fields.contains(column) || metadata::is_reserved_metadata_column(column);
+    let mut shown = HashSet::new();
+    for column in &payload.columns {
+        if !known(column) {
+            return Err(anyhow!("View column '{}' is not a form column", column));
+        }
+        if !shown.insert(column.as_str()) {
+            return Err(anyhow!("View column '{}' is listed twice", column));
+        }
+    }
+    let mut sorted = HashSet::new();
+    for sort in &payload.sort {
+        if !known(&sort.column) {
+            return Err(anyhow!(
+                "Sort column '{}' is not a form column",
+                sort.column
+            ));
+        }
+        if !sorted.insert(sort.column.as_str()) {
+            return Err(anyhow!("Sort column '{}' is listed twice", sort.column));
+        }
+    }
+    if let Some(group_by) = &payload.group_by {
+        if !known(group_by) {
+            return Err(anyhow!("Group column '{}' is not a form column", group_by));
+        }
+    }
+    if payload.filters.contains_key("form") {
+        return Err(anyhow!("View filters cannot select another form"));
+    }
+    match (payload.layout, &payload.board_by) {
+        (ViewLayout::Board, None) => Err(anyhow!("Board views need a board_by field")),
+        (ViewLayout::Table, Some(_)) => Err(anyhow!("Only board views have a board_by field")),
+        (ViewLayout::Board, Some(board_by)) if !fields.contains(board_by) => Err(anyhow!(
+            "Board field '{}' is not a field of the form",
+            board_by
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Apply `payload` to the view `view_id`, or to a new view when `None`.
+async fn save_view(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    view_id: Option<&str>,
+    payload: &ViewPayload,
+) -> Result<View> {
+    let form_def = form::read_form_definition(op, ws_path, form_name).await?;
+    let mut payload = payload.clone();
+    validate_payload(&form_def, &mut payload)?;
+    space_lock::with_space_lease(op, ws_path, VIEWS_LEASE, |_| async {
+        let mut stored = load_views(op, ws_path, form_name).await?;
+        let now = entry::now_ts();
+        let position = match view_id {
+            Some(view_id) => stored
+                .views
+                .iter()
+                .position(|view| view.id == view_id)
+                .ok_or_else(|| anyhow!("View not found: {}", view_id))?,
+            None => {
+                if stored.views.len() >= MAX_VIEWS_PER_FORM {
+                    return Err(anyhow!(
+                        "A form can have at most {} views",
+                        MAX_VIEWS_PER_FORM
+                    ));
+                }
+                stored.views.push(View {
+                    id: clock::new_uuid().to_string(),
+                    form: form_name.to_string(),
+                    name: String::new(),
+                    layout: ViewLayout::Table,
+                    columns: Vec::new(),
+                    filters: Map::new(),
+                    sort: Vec::new(),
+                    group_by: None,
+                    board_by: None,
+                    // The first view of a form is its default.
+                    is_default: stored.views.is_empty(),
+                    created_at: now,
+                    updated_at: now,
+                });
+                stored.views.len() - 1
+            }
+        };
+        if payload.is_default {
+            for view in stored.views.iter_mut() {
+                view.is_default = false;
+            }
+        }
+        let view = &mut stored.views[position];
+        view.name = payload.name;
+        view.layout = payload.layout;
+        view.columns = payload.columns;
+        view.filters = payload.filters;
+        view.sort = payload.sort;
+        view.group_by = payload.group_by;
+        view.board_by = payload.board_by;
+        view.is_default |= payload.is_default;
+        view.updated_at = now;
+        let view = view.clone();
+        write_views(op, ws_path, form_name, &stored).await?;
+        Ok(view)
+    })
+    .await
+}
+
+pub async fn create_view(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    payload: &ViewPayload,
+) -> Result<View> {
+    save_view(op, ws_path, form_name, None, payload).await
+}
+
+/// Replace a view's settings. Passing `is_default: false` keeps the view the
+/// default if it already is; another view takes over only by being marked.
+pub async fn update_view(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    view_id: &str,
+    payload: &ViewPayload,
+) -> Result<View> {
+    save_view(op, ws_path, form_name, Some(view_id), payload).await
+}
+
+pub async fn get_view(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    view_id: &str,
+) -> Result<View> {
+    load_views(op, ws_path, form_name)
+        .await?
+        .views
+        .into_iter()
+        .find(|view| view.id == view_id)
+        .ok_or_else(|| anyhow!("View not found: {}", view_id))
+}
+
+/// Views of a form in creation order.
+pub async fn list_views(op: &Operator, ws_path: &str, form_name: &str) -> Result<Vec<View>> {
+    Ok(load_views(op, ws_path, form_name).await?.views)
+}
+
+/// The form's default view, if it has any views.
+pub async fn get_default_view(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+) -> Result<Option<View>> {
+    Ok(load_views(op, ws_path, form_name)
+        .await?
+        .views
+        .into_iter()
+        .find(|view| view.is_default))
+}
+
+/// Delete a view. When it was the default, the oldest remaining view becomes
+/// the default.
+pub async fn delete_view(
+    op: &Operator,
+    ws_path: &str,
+    form_name: &str,
+    view_id: &str,
+) -> Result<()> {
+    space_lock::with_space_lease(op, ws_path, VIEWS_LEASE, |_| async {
+        let mut stored = load_views(op, ws_path, form_name).await?;
+        let position = stored
+            .views
+            .iter()
+            .position(|view| view.id == view_id)
+            .ok_or_else(|| anyhow!("View not found: {}", view_id))?;
+        let removed = stored.views.remove(position);
+        if removed.is_default {
+            if let Some(first) = stored.views.first_mut() {
+                first.is_default = true;
+            }
+        }
+        if stored.views.is_empty() {
+            op.delete(&views_path(ws_path, form_name)).await?;
+            return Ok(());
+        }
+        write_views(op, ws_path, form_name, &stored).await
+    })
+    .await
+}
@@ -0,0 +1,82 @@
+use anyhow::{anyhow, Result};
+use futures::stream::{self, StreamExt};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+use crate::iceberg_store;
+use crate::integrity;
+use crate::space;
+use crate::storage;
+
+/// What warming one space loaded, or why it failed.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SpaceWarmup {
+    pub space_id: String,
+    pub forms: Vec<String>,
+    pub tables_loaded: usize,
+    pub integrity_verified: bool,
+    pub duration_ms: f64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct WarmupReport {
+    pub spaces: Vec<SpaceWarmup>,
+    pub duration_ms: f64,
+}
+
+impl WarmupReport {
+    pub fn is_ok(&self) -> bool {
+        self.spaces.iter().all(|space| space.error.is_none())
+    }
+}
+
+async fn warm_space(op: &Operator, space_id: &str, report: &mut SpaceWarmup) -> Result<()> {
+    if !space::space_exists(op, space_id).await? {
+        return Err(anyhow!("Space not found: {}", space_id));
+    }
+    let ws_path = format!("spaces/{space_id}");
+    // Loading each form's tables registers them in the cached catalog.
+    report.forms = iceberg_store::list_form_names(op, &ws_path).await?;
+    for form_name in &report.forms {
+        iceberg_store::load_form_tables(op, &ws_path, form_name).await?;
+        report.tables_loaded += 2;
+    }
+    integrity::load_hmac_material(op, space_id).await?;
+    report.integrity_verified = true;
+    Ok(())
+}
+
+/// Preload `space_ids` (every space when empty) so the first request after
+/// boot does not pay for catalog loading and integrity key reads.
+///
+/// A space that fails to warm is reported rather than aborting the others.
+pub async fn warmup(op: &Operator, space_ids: &[String]) -> Result<WarmupReport> {
+    let started = Instant::now();
+    let space_ids = if space_ids.is_empty() {
+        space::list_spaces(op).await?
+    } else {
+        space_ids.to_vec()
+    };
+    let spaces = stream::iter(space_ids)
+        .map(|space_id| async move {
+            let space_started = Instant::now();
+            let mut report = SpaceWarmup {
+                space_id: space_id.clone(),
+                ..Default::default()
+            };
+            if let Err(err) = warm_space(op, &space_id, &mut report).await {
+                report.error = Some(err.to_string());
+            }
+            report.duration_ms = space_started.elapsed().as_secs_f64() * 1000.0;
+            report
+        })
+        .buffered(storage::list_parallelism())
+        .collect()
+        .await;
+    Ok(WarmupReport {
+        spaces,
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+    })
+}
@@ -0,0 +1,347 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::clock;
+use crate::entry;
+use crate::space_lock;
+use crate::storage;
+
+/// Space lease serializing subscription and queue rewrites across processes.
+const WATCHERS_LEASE: &str = "watchers";
+/// Oldest notifications are dropped once a principal's queue grows past this.
+const MAX_QUEUE_ITEMS: usize = 1000;
+
+/// What a principal watches: one entry, every entry of a form, or every entry
+/// carrying a tag.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum WatchTarget {
+    Entry(String),
+    Form(String),
+    Tag(String),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Watch {
+    pub principal: String,
+    pub target: WatchTarget,
+    pub created_at: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct WatchList {
+    #[serde(default)]
+    watches: Vec<Watch>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchEventKind {
+    Created,
+    Updated,
+    /// A workflow state transition.
+    Transitioned,
+    Deleted,
+    /// A reminder field of the entry came due.
+    ReminderDue,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WatchNotification {
+    pub id: String,
+    pub event: WatchEventKind,
+    pub entry_id: String,
+    pub entry_title: String,
+    pub form: String,
+    /// Principal who made the change, when known.
+    pub author: Option<String>,
+    pub revision_id: Option<String>,
+    /// Field the notification is about, for reminders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub created_at: f64,
+    /// The principal's subscriptions this write matched.
+    pub matched: Vec<WatchTarget>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct NotificationQueue {
+    #[serde(default)]
+    items: Vec<WatchNotification>,
+}
+
+/// An entry write that watchers of the entry, its form or its tags are told about.
+pub struct WatchEvent<'a> {
+    pub kind: WatchEventKind,
+    pub entry_id: &'a str,
+    pub entry_title: &'a str,
+    pub form: &'a str,
+    pub tags: &'a [String],
+    pub author: Option<&'a str>,
+    pub revision_id: Option<&'a str>,
+    pub field: Option<&'a str>,
+    pub timestamp: f64,
+}
+
+fn watches_path(ws_path: &str) -> String {
+    format!("{}/watchers/watches.json", ws_path.trim_end_matches('/'))
+}
+
+fn queue_path(ws_path: &str, principal: &str) -> String {
+    let principal_hash = hex::encode(Sha256::digest(principal.as_bytes()));
+    format!(
+        "{}/watchers/queues/{}.json",
+        ws_path.trim_end_matches('/'),
+        principal_hash
+    )
+}
+
+async fn read_watch_list(op: &Operator, ws_path: &str) -> Result<WatchList> {
+    match op.read(&watches_path(ws_path)).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("Watch list is malformed: {}", e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(WatchList::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn read_queue(op: &Operator, ws_path: &str, principal: &str) -> Result<NotificationQueue> {
+    match op.read(&queue_path(ws_path, principal)).await {
+        Ok(bytes) => serde_json::from_slice(&bytes.to_vec())
+            .map_err(|e| anyhow!("Notification queue for {} is malformed: {}", principal, e)),
+        Err(err) if err.kind() == opendal::ErrorKind::NotFound => Ok(NotificationQueue::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn write_queue(
+    op: &Operator,
+    ws_path: &str,
+    principal: &str,
+    queue: &NotificationQueue,
+) -> Result<()> {
+    storage::publish_atomic(
+        op,
+        &queue_path(ws_path, principal),
+        serde_json::to_vec(queue)?,
+    )
+    .await
+}
+
+fn validate_target(target: &WatchTarget) -> Result<()> {
+    let value = match target {
+        WatchTarget::Entry(value) | WatchTarget::Form(value) | WatchTarget::Tag(value) => value,
+    };
+    if value.trim().is_empty() {
+        return Err(anyhow!("Watch target must not be empty"));
+    }
+    Ok(())
+}
+
+/// Subscribe `principal` to `target`. Returns false when already subscribed.
+pub async fn watch(
+    op: &Operator,
+    ws_path: &str,
+    principal: &str,
+    target: WatchTarget,
+) -> Result<bool> {
+    if principal.trim().is_empty() {
+        return Err(anyhow!("Watcher principal must not be empty"));
+    }
+    validate_target(&target)?;
+    space_lock::with_space_lease(op, ws_path, WATCHERS_LEASE, |_| async {
+        let mut list = read_watch_list(op, ws_path).await?;
+        if list
+            .watches
+            .iter()
+            .any(|watch| watch.principal == principal && watch.target == target)
+        {
+            return Ok(false);
+        }
+        list.watches.push(Watch {
+            principal: principal.to_string(),
+            target,
+            created_at: entry::now_ts(),
+        });
+        storage::publish_atomic(op, &watches_path(ws_path), serde_json::to_vec(&list)?).await?;
+        Ok(true)
+    })
+    .await
+}
+
+/// Remove a subscription. Returns false when there was none.
+pub async fn unwatch(
+    op: &Operator,
+    ws_path: &str,
+    principal: &str,
+    target: &WatchTarget,
+) -> Result<bool> {
+    space_lock::with_space_lease(op, ws_path, WATCHERS_LEASE, |_| async {
+        let mut list = read_watch_list(op, ws_path).await?;
+        let before = list.watches.len();
+        list.watches
+            .retain(|watch| !(watch.principal == principal && &watch.target == target));
+        if list.watches.len() == before {
+            return Ok(false);
+        }
+        storage::publish_atomic(op, &watches_path(ws_path), serde_json::to_vec(&list)?).await?;
+        Ok(true)
+    })
+    .await
+}
+
+/// Subscriptions of one principal, or of everyone when `principal` is `None`.
+pub async fn list_watches(
+    op: &Operator,
+    ws_path: &str,
+    principal: Option<&str>,
+) -> Result<Vec<Watch>> {
+    Ok(read_watch_list(op, ws_path)
+        .await?
+        .watches
+        .into_iter()
+        .filter(|watch| principal.is_none_or(|principal| watch.principal == principal))
+        .collect())
+}
+
+fn target_matches(target: &WatchTarget, event: &WatchEvent<'_>) -> bool {
+    match target {
+        WatchTarget::Entry(entry_id) => entry_id == event.entry_id,
+        WatchTarget::Form(form) => form == event.form,
+        WatchTarget::Tag(tag) => event.tags.iter().any(|t| t == tag),
+    }
+}
+
+/// Append one notification to the queue of every principal with a matching
+/// subscription, other than the author of the write. Returns the notified
+/// principals.
+pub async fn notify_watchers(
+    op: &Operator,
+    ws_path: &str,
+    event: &WatchEvent<'_>,
+) -> Result<Vec<String>> {
+    let matched = matching_watchers(op, ws_path, event).await?;
+    enqueue_notifications(op, ws_path, event, matched).await
+}
+
+/// [`notify_watchers`] for a write that is already durable. A failure is
+/// logged rather than returned, so callers never report a committed write as
+/// failed and invite a retry that would then conflict with it.
+pub(crate) async fn notify_watchers_after_commit(
+    op: &Operator,
+    ws_path: &str,
+    event: &WatchEvent<'_>,
+) {
+    if let Err(error) = notify_watchers(op, ws_path, event).await {
+        eprintln!(
+            "failed to notify watchers after commit (ws_path={}, entry_id={}): {}",
+            ws_path, event.entry_id, error
+        );
+    }
+}
+
+/// Principals with a subscription matching `event`, other than its author,
+/// each with the subscriptions it matched.
+pub(crate) async fn matching_watchers(
+    op: &Operator,
+    ws_path: &str,
+    event: &WatchEvent<'_>,
+) -> Result<Vec<(String, Vec<WatchTarget>)>> {
+    let mut matched: Vec<(String, Vec<WatchTarget>)> = Vec::new();
+    for watch in read_watch_list(op, ws_path).await?.watches {
+        if Some(watch.principal.as_str()) == event.author || !target_matches(&watch.target, event) {
+            continue;
+        }
+        match matched
+            .iter_mut()
+            .find(|(principal, _)| *principal == watch.principal)
+        {
+            Some((_, targets)) => targets.push(watch.target),
+            None => matched.push((watch.principal, vec![watch.target])),
+        }
+    }
+    Ok(matched)
+}
+
+/// Append `event` to the queue of every principal in `matched`. Returns the
+/// notified principals.
+pub(crate) async fn enqueue_notifications(
+    op: &Operator,
+    ws_path: &str,
+    event: &WatchEvent<'_>,
+    matched: Vec<(String, Vec<WatchTarget>)>,
+) -> Result<Vec<String>> {
+    if matched.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    space_lock::with_space_lease(op, ws_path, WATCHERS_LEASE, |_| async {
+        for (principal, targets) in &matched {
+            let mut queue = read_queue(op, ws_path, principal).await?;
+            queue.items.push(WatchNotification {
+                id: clock::new_uuid().simple().to_string(),
+                event: event.kind,
+                entry_id: event.entry_id.to_string(),
+                entry_title: event.entry_title.to_string(),
+                form: event.form.to_string(),
+                author: event.author.map(str::to_string),
+                revision_id: event.revision_id.map(str::to_string),
+                field: event.field.map(str::to_string),
+                created_at: event.timestamp,
+                matched: targets.clone(),
+            });
+            if queue.items.len() > MAX_QUEUE_ITEMS {
+                let excess = queue.items.len() - MAX_QUEUE_ITEMS;
+                queue.items.drain(..excess);
+            }
+            write_queue(op, ws_path, principal, &queue).await?;
+        }
+        Ok(())
+    })
+    .await?;
+    Ok(matched
+        .into_iter()
+        .map(|(principal, _)| principal)
+        .collect())
+}
+
+/// Pending notifications for a principal, oldest first, so a backend can
+/// batch them into a digest or push them one by one.
+pub async fn list_notifications(
+    op: &Operator,
+    ws_path: &str,
+    principal: &str,
+    limit: Option<usize>,
+) -> Result<Vec<WatchNotification>> {
+    let mut items = read_queue(op, ws_path, principal).await?.items;
+    items.sort_by(|a, b| a.created_at.total_cmp(&b.created_at));
+    if let Some(limit) = limit {
+        items.truncate(limit);
+    }
+    Ok(items)
+}
+
+/// Remove delivered notifications from the queue, or all of them when
+/// `notification_ids` is `None`. Returns how many were removed.
+pub async fn ack_notifications(
+    op: &Operator,
+    ws_path: &str,
+    principal: &str,
+    notification_ids: Option<&[String]>,
+) -> Result<usize> {
+    space_lock::with_space_lease(op, ws_path, WATCHERS_LEASE, |_| async {
+        let mut queue = read_queue(op, ws_path, principal).await?;
+        let before = queue.items.len();
+        queue
+            .items
+            .retain(|item| notification_ids.is_some_and(|ids| !ids.contains(&item.id)));
+        let removed = before - queue.items.len();
+        if removed > 0 {
+            write_queue(op, ws_path, principal, &queue).await?;
+        }
+        Ok(removed)
+    })
+    .await
+}
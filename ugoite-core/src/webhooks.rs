@@ -0,0 +1,424 @@
+use crate::clock;
+use crate::entry;
+use crate::form;
+use crate::importers;
+use crate::integrity::IntegrityProvider;
+use crate::metadata;
+use anyhow::{anyhow, Result};
+use hmac::{Hmac, KeyInit, Mac};
+use opendal::Operator;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2_hmac::Sha256 as HmacSha256Digest;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<HmacSha256Digest>;
+
+/// Space setting mapping webhook source names to [`WebhookTemplate`]s.
+pub const WEBHOOKS_SETTINGS_KEY: &str = "webhooks";
+
+pub const GITHUB_SIGNATURE_HEADER: &str = "x-hub-signature-256";
+pub const GITHUB_EVENT_HEADER: &str = "x-github-event";
+pub const GITHUB_DELIVERY_HEADER: &str = "x-github-delivery";
+pub const SLACK_SIGNATURE_HEADER: &str = "x-slack-signature";
+pub const SLACK_TIMESTAMP_HEADER: &str = "x-slack-request-timestamp";
+pub const GENERIC_SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+/// Default allowed age, in seconds, of a timestamped webhook delivery.
+pub const DEFAULT_WEBHOOK_WINDOW_SECS: i64 = 300;
+
+const DEFAULT_WEBHOOK_FORM: &str = "Entry";
+const DEFAULT_WEBHOOK_AUTHOR: &str = "webhook";
+
+/// How a sender signs its deliveries.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookProvider {
+    /// `X-Hub-Signature-256: sha256=<hex HMAC of the body>`.
+    Github,
+    /// `X-Slack-Signature: v0=<hex HMAC of "v0:{timestamp}:{body}">` with the
+    /// timestamp in `X-Slack-Request-Timestamp`.
+    Slack,
+    /// `X-Webhook-Signature: sha256=<hex HMAC of the body>`.
+    Generic,
+}
+
+impl std::str::FromStr for WebhookProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "github" => Ok(Self::Github),
+            "slack" => Ok(Self::Slack),
+            "generic" => Ok(Self::Generic),
+            other => Err(anyhow!("Unknown webhook provider: {}", other)),
+        }
+    }
+}
+
+/// A delivery whose signature was accepted.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct VerifiedWebhook {
+    pub provider: WebhookProvider,
+    /// Signed timestamp, for providers that send one.
+    pub timestamp: Option<i64>,
+    /// Event name, e.g. GitHub's `X-GitHub-Event`.
+    pub event: Option<String>,
+    /// Provider delivery id, e.g. GitHub's `X-GitHub-Delivery`.
+    pub delivery_id: Option<String>,
+}
+
+fn header<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+fn hex_hmac(secret: &[u8], parts: &[&[u8]]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC can take key of any size");
+    for part in parts {
+        mac.update(part);
+    }
+    hex::encode(mac.finalize().into_bytes())
+}
+
+fn slack_base(timestamp: i64) -> String {
+    format!("v0:{timestamp}:")
+}
+
+/// Headers `provider` would send with `body`; `timestamp` is only used by
+/// providers that sign one.
+pub fn sign_webhook_payload(
+    provider: WebhookProvider,
+    secret: &[u8],
+    body: &[u8],
+    timestamp: i64,
+) -> HashMap<String, String> {
+    let mut headers = HashMap::new();
+    match provider {
+        WebhookProvider::Github | WebhookProvider::Generic => {
+            let name = if provider == WebhookProvider::Github {
+                GITHUB_SIGNATURE_HEADER
+            } else {
+                GENERIC_SIGNATURE_HEADER
+            };
+            headers.insert(
+                name.to_string(),
+                format!("sha256={}", hex_hmac(secret, &[body])),
+            );
+        }
+        WebhookProvider::Slack => {
+            let base = slack_base(timestamp);
+            headers.insert(
+                SLACK_SIGNATURE_HEADER.to_string(),
+                format!("v0={}", hex_hmac(secret, &[base.as_bytes(), body])),
+            );
+            headers.insert(SLACK_TIMESTAMP_HEADER.to_string(), timestamp.to_string());
+        }
+    }
+    headers
+}
+
+/// Verify an inbound delivery with the default timestamp window.
+pub fn verify_webhook_signature(
+    provider: WebhookProvider,
+    secret: &[u8],
+    body: &[u8],
+    headers: &HashMap<String, String>,
+) -> Result<VerifiedWebhook> {
+    verify_webhook_signature_with_window(
+        provider,
+        secret,
+        body,
+        headers,
+        DEFAULT_WEBHOOK_WINDOW_SECS,
+    )
+}
+
+/// Verify an inbound delivery against the shared `secret` configured with
+/// the provider.
+///
+/// Signatures are compared in constant time. Slack deliveries must also be
+/// signed within `window_secs` of now, which bounds replays.
+pub fn verify_webhook_signature_with_window(
+    provider: WebhookProvider,
+    secret: &[u8],
+    body: &[u8],
+    headers: &HashMap<String, String>,
+    window_secs: i64,
+) -> Result<VerifiedWebhook> {
+    if secret.is_empty() {
+        return Err(anyhow!("Webhook secret must not be empty"));
+    }
+    if window_secs <= 0 {
+        return Err(anyhow!("Webhook signature window must be positive"));
+    }
+    let (signature_header, prefix) = match provider {
+        WebhookProvider::Github => (GITHUB_SIGNATURE_HEADER, "sha256="),
+        WebhookProvider::Slack => (SLACK_SIGNATURE_HEADER, "v0="),
+        WebhookProvider::Generic => (GENERIC_SIGNATURE_HEADER, "sha256="),
+    };
+    let signature =
+        header(headers, signature_header).ok_or_else(|| anyhow!("Missing webhook signature"))?;
+    let signature = signature
+        .strip_prefix(prefix)
+        .ok_or_else(|| anyhow!("Webhook signature must start with {}", prefix))?;
+
+    let mut timestamp = None;
+    let expected = match provider {
+        WebhookProvider::Slack => {
+            let signed_at: i64 = header(headers, SLACK_TIMESTAMP_HEADER)
+                .ok_or_else(|| anyhow!("Missing webhook timestamp"))?
+                .parse()
+                .map_err(|_| anyhow!("Webhook timestamp must be unix seconds"))?;
+            timestamp = Some(signed_at);
+            let base = slack_base(signed_at);
+            hex_hmac(secret, &[base.as_bytes(), body])
+        }
+        WebhookProvider::Github | WebhookProvider::Generic => hex_hmac(secret, &[body]),
+    };
+    if !bool::from(
+        expected
+            .as_bytes()
+            .ct_eq(signature.to_ascii_lowercase().as_bytes()),
+    ) {
+        return Err(anyhow!("Webhook signature does not match"));
+    }
+    if let Some(signed_at) = timestamp {
+        let within_window = clock::now()
+            .timestamp()
+            .checked_sub(signed_at)
+            .map(i64::unsigned_abs)
+            .is_some_and(|skew| skew <= window_secs.unsigned_abs());
+        if !within_window {
+            return Err(anyhow!("Webhook timestamp is outside the allowed window"));
+        }
+    }
+
+    let (event, delivery_id) = match provider {
+        WebhookProvider::Github => (
+            header(headers, GITHUB_EVENT_HEADER).map(str::to_string),
+            header(headers, GITHUB_DELIVERY_HEADER).map(str::to_string),
+        ),
+        _ => (None, None),
+    };
+    Ok(VerifiedWebhook {
+        provider,
+        timestamp,
+        event,
+        delivery_id,
+    })
+}
+
+fn default_webhook_form() -> String {
+    DEFAULT_WEBHOOK_FORM.to_string()
+}
+
+fn default_webhook_author() -> String {
+    DEFAULT_WEBHOOK_AUTHOR.to_string()
+}
+
+/// How a webhook payload becomes an entry.
+///
+/// Every string may hold `{{ path }}` placeholders, where `path` is a dotted
+/// path into the payload (`issue.title`, `commits.0.message`). Missing
+/// values render as empty strings; objects and arrays render as JSON.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WebhookTemplate {
+    #[serde(default = "default_webhook_form")]
+    pub form: String,
+    /// Entry id template. Deliveries rendering to an existing id update that
+    /// entry, so redeliveries do not duplicate it; without one every
+    /// delivery creates a new entry.
+    #[serde(default)]
+    pub id: Option<String>,
+    pub title: String,
+    /// Form field name to value template.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default = "default_webhook_author")]
+    pub author: String,
+}
+
+/// The entry a delivery was ingested into.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct WebhookIngestResult {
+    pub entry_id: String,
+    pub title: String,
+    /// False when the delivery updated an existing entry.
+    pub created: bool,
+}
+
+fn placeholder_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.\-]+)\s*\}\}").unwrap())
+}
+
+fn lookup<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(payload, |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+/// Render the `{{ path }}` placeholders of `template` from `payload`.
+pub fn render_webhook_template(template: &str, payload: &Value) -> String {
+    placeholder_pattern()
+        .replace_all(template, |caps: &regex::Captures| {
+            match lookup(payload, &caps[1]) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(text)) => text.clone(),
+                Some(value @ (Value::Number(_) | Value::Bool(_))) => value.to_string(),
+                Some(value) => serde_json::to_string(value).unwrap_or_default(),
+            }
+        })
+        .into_owned()
+}
+
+/// The template configured for `source` in the space's `webhooks` setting.
+pub async fn load_webhook_template(
+    op: &Operator,
+    ws_path: &str,
+    source: &str,
+) -> Result<WebhookTemplate> {
+    let settings_path = format!("{}/settings.json", ws_path.trim_end_matches('/'));
+    let settings: Value = if op.exists(&settings_path).await? {
+        serde_json::from_slice(&op.read(&settings_path).await?.to_vec())?
+    } else {
+        Value::Null
+    };
+    let template = settings
+        .get(WEBHOOKS_SETTINGS_KEY)
+        .and_then(|webhooks| webhooks.get(source))
+        .ok_or_else(|| anyhow!("Webhook source not configured: {}", source))?;
+    serde_json::from_value(template.clone())
+        .map_err(|e| anyhow!("Invalid webhook template {}: {}", source, e))
+}
+
+/// Map a verified webhook payload into an entry using `template`.
+pub async fn ingest_webhook_event_with_template<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    template: &WebhookTemplate,
+    payload: &Value,
+    integrity: &I,
+) -> Result<WebhookIngestResult> {
+    if metadata::is_reserved_metadata_form(&template.form) {
+        return Err(anyhow!(
+            "Cannot ingest webhooks into metadata form: {}",
+            template.form
+        ));
+    }
+    let form_def = form::read_form_definition(op, ws_path, &template.form).await?;
+    let form_fields = entry::form_field_names(&form_def);
+    if let Some(unknown) = template
+        .fields
+        .keys()
+        .find(|name| !form_fields.contains(name))
+    {
+        return Err(anyhow!(
+            "Form {} has no field named {}",
+            template.form,
+            unknown
+        ));
+    }
+
+    let title = render_webhook_template(&template.title, payload)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if title.is_empty() {
+        return Err(anyhow!("Webhook template rendered an empty title"));
+    }
+    let mut fields = Map::new();
+    for (name, value) in &template.fields {
+        let rendered = render_webhook_template(value, payload);
+        fields.insert(
+            name.clone(),
+            Value::String(importers::sanitize_body(&rendered)),
+        );
+    }
+    let tags: Vec<String> = template
+        .tags
+        .iter()
+        .map(|tag| render_webhook_template(tag, payload).trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    let markdown = entry::render_markdown(
+        &title,
+        &template.form,
+        &tags,
+        &Value::Object(fields),
+        &form_fields,
+    );
+
+    let entry_id = match &template.id {
+        Some(id_template) => {
+            let id = render_webhook_template(id_template, payload)
+                .trim()
+                .to_string();
+            if id.is_empty() || id.contains('/') || id.contains('\\') || id.starts_with('.') {
+                return Err(anyhow!("Invalid entry_id: {}", id));
+            }
+            id
+        }
+        None => Uuid::new_v4().to_string(),
+    };
+    let created = match entry::find_entry_form(op, ws_path, &entry_id).await? {
+        Some(_) => {
+            let head = entry::get_entry_content(op, ws_path, &entry_id).await?;
+            entry::update_entry(
+                op,
+                ws_path,
+                &entry_id,
+                &markdown,
+                Some(&head.revision_id),
+                &template.author,
+                None,
+                integrity,
+            )
+            .await?;
+            false
+        }
+        None => {
+            entry::create_entry(
+                op,
+                ws_path,
+                &entry_id,
+                &markdown,
+                &template.author,
+                integrity,
+            )
+            .await?;
+            true
+        }
+    };
+    Ok(WebhookIngestResult {
+        entry_id,
+        title,
+        created,
+    })
+}
+
+/// Map a verified webhook payload into an entry using the template
+/// configured for `source`.
+pub async fn ingest_webhook_event<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    source: &str,
+    payload: &Value,
+    integrity: &I,
+) -> Result<WebhookIngestResult> {
+    let template = load_webhook_template(op, ws_path, source).await?;
+    ingest_webhook_event_with_template(op, ws_path, &template, payload, integrity).await
+}
@@ -0,0 +1,344 @@
+use anyhow::{anyhow, Result};
+use opendal::Operator;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+
+use crate::audit;
+use crate::entry::{self, MergeStrategy, RevisionNote};
+use crate::form;
+use crate::integrity::IntegrityProvider;
+use crate::space;
+
+/// Lifecycle of a form's entries: `{"field": "Status", "initial": "open",
+/// "states": ["open", "closed"], "transitions": [{"from": "open", "to":
+/// "closed", "scopes": ["triage"]}]}`. The current state is stored in `field`,
+/// an ordinary string field, so it can be queried like any other property.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Workflow {
+    pub field: String,
+    /// State of new entries; the first state when unset.
+    #[serde(default)]
+    pub initial: String,
+    pub states: Vec<String>,
+    #[serde(default)]
+    pub transitions: Vec<WorkflowTransition>,
+}
+
+/// An allowed move between two states.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct WorkflowTransition {
+    pub from: String,
+    pub to: String,
+    /// Scopes the caller must all hold to make the move.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// A change of an entry's workflow state, as recorded in its revisions.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StateChange {
+    pub revision_id: String,
+    /// `None` for the state the entry was created in.
+    pub from: Option<String>,
+    pub to: String,
+    pub author: String,
+    pub timestamp: f64,
+}
+
+impl Workflow {
+    pub fn from_form_def(form_def: &Value) -> Result<Option<Self>> {
+        match form_def.get("workflow") {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => Ok(Some(serde_json::from_value(value.clone())?)),
+        }
+    }
+
+    pub fn transition(&self, from: &str, to: &str) -> Option<&WorkflowTransition> {
+        self.transitions
+            .iter()
+            .find(|transition| transition.from == from && transition.to == to)
+    }
+
+    /// The state stored in `fields`, or the initial state when it is empty.
+    pub fn state_of(&self, fields: &Value) -> String {
+        submitted(fields, &self.field).unwrap_or_else(|| self.initial.clone())
+    }
+}
+
+fn submitted(fields: &Value, name: &str) -> Option<String> {
+    fields
+        .get(name)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+pub(crate) fn normalize_workflow(value: &Value, field_map: &Map<String, Value>) -> Result<Value> {
+    let mut workflow: Workflow =
+        serde_json::from_value(value.clone()).map_err(|e| anyhow!("Invalid workflow: {}", e))?;
+    let field_def = field_map
+        .get(&workflow.field)
+        .ok_or_else(|| anyhow!("Workflow field '{}' is not a form field", workflow.field))?;
+    let field_type = field_def
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("string");
+    if field_type != "string" || field_def.get("secret").and_then(Value::as_bool) == Some(true) {
+        return Err(anyhow!(
+            "Workflow field '{}' must be a plain string field",
+            workflow.field
+        ));
+    }
+    let mut states = BTreeSet::new();
+    for state in &workflow.states {
+        if state.trim().is_empty() || state.trim() != state {
+            return Err(anyhow!("Workflow state '{}' is not a valid name", state));
+        }
+        if !states.insert(state.as_str()) {
+            return Err(anyhow!("Workflow state '{}' is listed twice", state));
+        }
+    }
+    if workflow.initial.is_empty() {
+        workflow.initial = workflow
+            .states
+            .first()
+            .cloned()
+            .ok_or_else(|| anyhow!("Workflow must list at least one state"))?;
+    }
+    if !states.contains(workflow.initial.as_str()) {
+        return Err(anyhow!(
+            "Workflow initial state '{}' is not a listed state",
+            workflow.initial
+        ));
+    }
+    let mut moves = BTreeSet::new();
+    for transition in &workflow.transitions {
+        for state in [&transition.from, &transition.to] {
+            if !states.contains(state.as_str()) {
+                return Err(anyhow!(
+                    "Workflow transition uses unknown state '{}'",
+                    state
+                ));
+            }
+        }
+        if transition.from == transition.to {
+            return Err(anyhow!(
+                "Workflow transition from '{}' must lead to another state",
+                transition.from
+            ));
+        }
+        if !moves.insert((transition.from.as_str(), transition.to.as_str())) {
+            return Err(anyhow!(
+                "Workflow transition from '{}' to '{}' is listed twice",
+                transition.from,
+                transition.to
+            ));
+        }
+        if transition
+            .scopes
+            .iter()
+            .any(|scope| scope.trim().is_empty())
+        {
+            return Err(anyhow!("Workflow transition scopes must not be empty"));
+        }
+    }
+    Ok(serde_json::to_value(workflow)?)
+}
+
+/// Guard the workflow field of `fields` before they are saved.
+///
+/// New entries start in the initial state. On update (`previous` set) the
+/// stored state is kept unless `transition` names the state being moved to;
+/// any other change is rejected, so states only move through
+/// [`transition_entry_state`].
+pub(crate) fn apply_workflow_state(
+    form_def: &Value,
+    fields: &mut Value,
+    previous: Option<&Value>,
+    transition: Option<&str>,
+) -> Result<()> {
+    let Some(workflow) = Workflow::from_form_def(form_def)? else {
+        return Ok(());
+    };
+    let Some(map) = fields.as_object_mut() else {
+        return Ok(());
+    };
+    let state = match (previous, transition) {
+        (_, Some(to)) => to.to_string(),
+        (None, None) => workflow.initial.clone(),
+        (Some(previous), None) => workflow.state_of(previous),
+    };
+    if let Some(value) = submitted(&Value::Object(map.clone()), &workflow.field) {
+        if value != state {
+            return Err(match previous {
+                None => anyhow!(
+                    "New entries start in workflow state '{}', not '{}'",
+                    state,
+                    value
+                ),
+                Some(_) => anyhow!(
+                    "Workflow field '{}' changes only through state transitions",
+                    workflow.field
+                ),
+            });
+        }
+    }
+    map.insert(workflow.field.clone(), Value::String(state));
+    Ok(())
+}
+
+/// Move an entry to `to_state` along one of its form's workflow transitions.
+/// The move is saved as a new revision by `author`, recorded as an
+/// `entry.transition` audit event and sent to watchers as a `transitioned`
+/// notification.
+#[allow(clippy::too_many_arguments)]
+pub async fn transition_entry_state<I: IntegrityProvider>(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+    to_state: &str,
+    parent_revision_id: Option<&str>,
+    author: &str,
+    scopes: &[String],
+    integrity: &I,
+) -> Result<StateChange> {
+    let form_name = entry::find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+    let workflow = Workflow::from_form_def(&form_def)?
+        .ok_or_else(|| anyhow!("Form '{}' has no workflow", form_name))?;
+    let row = entry::read_entry_row(op, ws_path, &form_name, entry_id).await?;
+    if row.deleted {
+        return Err(anyhow!("Entry not found: {}", entry_id));
+    }
+    let from_state = workflow.state_of(&row.fields);
+    let transition = workflow.transition(&from_state, to_state).ok_or_else(|| {
+        anyhow!(
+            "Workflow of form '{}' has no transition from '{}' to '{}'",
+            form_name,
+            from_state,
+            to_state
+        )
+    })?;
+    if let Some(missing) = transition
+        .scopes
+        .iter()
+        .find(|scope| !scopes.contains(scope))
+    {
+        return Err(anyhow!(
+            "Transition from '{}' to '{}' requires the '{}' scope",
+            from_state,
+            to_state,
+            missing
+        ));
+    }
+
+    let mut fields = entry::merge_entry_fields(&row.fields, &row.extra_attributes);
+    if let Some(map) = fields.as_object_mut() {
+        map.insert(workflow.field.clone(), Value::String(to_state.to_string()));
+    }
+    let markdown = entry::render_markdown_for_form(
+        &row.title,
+        &form_name,
+        &row.tags,
+        &fields,
+        &Value::Null,
+        &form_def,
+    );
+    let updated = entry::update_entry_in_state(
+        op,
+        ws_path,
+        entry_id,
+        &markdown,
+        Some(parent_revision_id.unwrap_or(&row.revision_id)),
+        author,
+        None,
+        MergeStrategy::Fail,
+        Some(to_state),
+        &RevisionNote::default(),
+        integrity,
+    )
+    .await?;
+    let revision_id = updated
+        .get("revision_id")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let timestamp = updated
+        .get("updated_at")
+        .and_then(Value::as_f64)
+        .unwrap_or_else(entry::now_ts);
+
+    audit::append_audit_event(
+        op,
+        space::space_id_from_ws_path(ws_path),
+        &json!({
+            "action": "entry.transition",
+            "actor_user_id": author,
+            "outcome": "success",
+            "target_type": "entry",
+            "target_id": entry_id,
+            "metadata": {
+                "form": form_name,
+                "from": from_state,
+                "to": to_state,
+                "revision_id": revision_id,
+            },
+        }),
+        None,
+    )
+    .await?;
+    Ok(StateChange {
+        revision_id,
+        from: Some(from_state),
+        to: to_state.to_string(),
+        author: author.to_string(),
+        timestamp,
+    })
+}
+
+/// The workflow states an entry has been in, oldest first, read from its
+/// revisions: the state it was created in, then every revision that changed it.
+pub async fn get_entry_state_history(
+    op: &Operator,
+    ws_path: &str,
+    entry_id: &str,
+) -> Result<Vec<StateChange>> {
+    let form_name = entry::find_entry_form(op, ws_path, entry_id)
+        .await?
+        .ok_or_else(|| anyhow!("Entry not found: {}", entry_id))?;
+    let form_def = form::read_form_definition(op, ws_path, &form_name).await?;
+    let workflow = Workflow::from_form_def(&form_def)?
+        .ok_or_else(|| anyhow!("Form '{}' has no workflow", form_name))?;
+    let mut revisions: Vec<entry::RevisionRow> =
+        entry::list_form_revision_rows(op, ws_path, &form_name, &form_def)
+            .await?
+            .into_iter()
+            .filter(|revision| revision.entry_id == entry_id)
+            .collect();
+    revisions.sort_by(|a, b| {
+        a.timestamp
+            .partial_cmp(&b.timestamp)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut history: Vec<StateChange> = Vec::new();
+    for revision in revisions {
+        let state = workflow.state_of(&revision.fields);
+        let from = history.last().map(|change| change.to.clone());
+        if from.as_deref() == Some(state.as_str()) {
+            continue;
+        }
+        history.push(StateChange {
+            revision_id: revision.revision_id,
+            from,
+            to: state,
+            author: revision.author,
+            timestamp: revision.timestamp,
+        });
+    }
+    Ok(history)
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use _ugoite_core::analytics::{self, AnalyticsRange, Granularity};
+use _ugoite_core::clock;
+use _ugoite_core::entry;
+use _ugoite_core::form;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use common::setup_operator;
+use std::collections::BTreeMap;
+
+fn note(id: &str, body: &str) -> String {
+    format!("---\nform: Note\n---\n# {id}\n\n## Body\n{body}\n")
+}
+
+#[tokio::test]
+/// REQ-IDX-013
+async fn test_analytics_req_idx_013_rollup_buckets_activity() -> anyhow::Result<()> {
+    clock::frozen(13, async {
+        let op = setup_operator()?;
+        let ws_path = "spaces/analytics-space";
+        space::create_space(&op, "analytics-space", "/tmp").await?;
+        form::upsert_form(
+            &op,
+            ws_path,
+            &serde_json::json!({
+                "name": "Note",
+                "template": "# Note\n\n## Body\n",
+                "fields": {"Body": {"type": "markdown"}},
+            }),
+        )
+        .await?;
+        for (id, author) in [("n1", "alice"), ("n2", "bob")] {
+            entry::create_entry(
+                &op,
+                ws_path,
+                id,
+                &note(id, "First draft."),
+                author,
+                &FakeIntegrityProvider,
+            )
+            .await?;
+        }
+        let current = entry::get_entry_content(&op, ws_path, "n1").await?;
+        entry::update_entry(
+            &op,
+            ws_path,
+            "n1",
+            &note("n1", "Second draft."),
+            Some(&current.revision_id),
+            "alice",
+            None,
+            &FakeIntegrityProvider,
+        )
+        .await?;
+
+        // The frozen clock stamps every revision on 2024-01-01.
+        let range = AnalyticsRange::parse("2023-12-31", "2024-01-02")?;
+        let daily = analytics::get_space_analytics(&op, ws_path, &range, Granularity::Day).await?;
+        let created: Vec<usize> = daily.buckets.iter().map(|b| b.entries_created).collect();
+        assert_eq!(created, vec![0, 2, 0]);
+        let today = &daily.buckets[1];
+        assert_eq!(today.start.to_string(), "2024-01-01");
+        assert_eq!(today.entries_edited, 1);
+        assert_eq!(today.active_authors, 2);
+        let notes = BTreeMap::from([("Note".to_string(), 2)]);
+        assert_eq!(today.created_by_form, notes);
+        assert!(daily.buckets[0].total_by_form.is_empty());
+        assert_eq!(daily.buckets[2].total_by_form, notes);
+        assert!(
+            op.exists(&format!("{ws_path}/analytics/rollup.json"))
+                .await?
+        );
+
+        // Buckets clamp to the range; 2024-01-01 is a Monday.
+        let range = AnalyticsRange::parse("2023-12-28", "2024-01-10")?;
+        let weekly =
+            analytics::get_space_analytics(&op, ws_path, &range, Granularity::Week).await?;
+        let bounds: Vec<(String, String)> = weekly
+            .buckets
+            .iter()
+            .map(|b| (b.start.to_string(), b.end.to_string()))
+            .collect();
+        assert_eq!(
+            bounds,
+            vec![
+                ("2023-12-28".to_string(), "2023-12-31".to_string()),
+                ("2024-01-01".to_string(), "2024-01-07".to_string()),
+                ("2024-01-08".to_string(), "2024-01-10".to_string()),
+            ]
+        );
+        assert_eq!(weekly.buckets[1].entries_created, 2);
+
+        // Refreshing again counts only revisions written since the last refresh.
+        assert_eq!(analytics::refresh_space_analytics(&op, ws_path).await?, 0);
+        let current = entry::get_entry_content(&op, ws_path, "n2").await?;
+        entry::update_entry(
+            &op,
+            ws_path,
+            "n2",
+            &note("n2", "Revised."),
+            Some(&current.revision_id),
+            "carol",
+            None,
+            &FakeIntegrityProvider,
+        )
+        .await?;
+        assert_eq!(analytics::refresh_space_analytics(&op, ws_path).await?, 1);
+        let range = AnalyticsRange::parse("2024-01-01", "2024-01-31")?;
+        let monthly =
+            analytics::get_space_analytics(&op, ws_path, &range, Granularity::Month).await?;
+        assert_eq!(monthly.buckets.len(), 1);
+        assert_eq!(monthly.buckets[0].entries_edited, 2);
+        assert_eq!(monthly.buckets[0].active_authors, 3);
+
+        let backwards = AnalyticsRange::parse("2024-01-02", "2024-01-01")?;
+        assert!(
+            analytics::get_space_analytics(&op, ws_path, &backwards, Granularity::Day)
+                .await
+                .is_err()
+        );
+        Ok(())
+    })
+    .await
+}
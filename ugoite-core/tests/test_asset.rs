@@ -1,6 +1,7 @@
 mod common;
 use _ugoite_core::asset;
 use _ugoite_core::entry;
+use _ugoite_core::entry_metadata;
 use _ugoite_core::space;
 use common::setup_operator;
 #[cfg(unix)]
@@ -139,3 +140,165 @@ async fn test_asset_req_asset_001_normalizes_uploaded_filename() -> anyhow::Resu
 
     Ok(())
 }
+
+/// Serve canned HTTP responses on a loopback port until the test ends.
+fn serve_http() -> std::io::Result<String> {
+    use std::io::{BufRead, BufReader, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let base = format!("http://{}", listener.local_addr()?);
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(&stream);
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).is_err() {
+                continue;
+            }
+            let mut header = String::new();
+            while reader.read_line(&mut header).is_ok_and(|read| read > 2) {
+                header.clear();
+            }
+            let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+            let response = match path {
+                "/files/report.pdf" => {
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/pdf\r\nContent-Length: 9\r\n\r\n%PDF-data"
+                        .to_string()
+                }
+                "/moved" => {
+                    "HTTP/1.1 302 Found\r\nLocation: /files/report.pdf\r\nContent-Length: 0\r\n\r\n"
+                        .to_string()
+                }
+                "/page" => {
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: 4\r\n\r\n<p/>"
+                        .to_string()
+                }
+                _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string(),
+            };
+            let _ = (&stream).write_all(response.as_bytes());
+        }
+    });
+    Ok(base)
+}
+
+#[tokio::test]
+/// REQ-ASSET-002
+async fn test_asset_req_asset_002_save_asset_from_url() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "url-space", "/tmp").await?;
+    let ws_path = "spaces/url-space";
+    let base = serve_http()?;
+    let loopback = asset::UrlAssetOptions {
+        allow_private_networks: true,
+        content_types: vec!["application/pdf".to_string(), "image/*".to_string()],
+        ..asset::UrlAssetOptions::default()
+    };
+
+    let saved =
+        asset::save_asset_from_url(&op, ws_path, &format!("{base}/moved"), &loopback).await?;
+    assert_eq!(saved.asset.name, "report.pdf");
+    assert_eq!(
+        op.read(&format!("{}/{}", ws_path, saved.asset.path))
+            .await?
+            .to_vec(),
+        b"%PDF-data"
+    );
+    assert_eq!(saved.source.final_url, format!("{base}/files/report.pdf"));
+    assert_eq!(
+        saved.source.content_type.as_deref(),
+        Some("application/pdf")
+    );
+    assert_eq!(saved.source.size, 9);
+    let provenance = entry_metadata::get_entry_metadata_namespace(
+        &op,
+        ws_path,
+        &saved.asset.id,
+        asset::ASSET_SOURCE_NAMESPACE,
+    )
+    .await?;
+    assert_eq!(provenance["source_url"], format!("{base}/moved"));
+    assert_eq!(provenance["sha256"], saved.source.sha256);
+
+    // Loopback targets are refused unless private networks are allowed.
+    let err = asset::save_asset_from_url(
+        &op,
+        ws_path,
+        &format!("{base}/files/report.pdf"),
+        &asset::UrlAssetOptions::default(),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("non-public address"), "{err}");
+
+    let rejected = [
+        (format!("{base}/page"), loopback.clone(), "Content type"),
+        (
+            format!("{base}/files/report.pdf"),
+            asset::UrlAssetOptions {
+                max_bytes: 4,
+                ..loopback.clone()
+            },
+            "exceeds 4 bytes",
+        ),
+        (
+            format!("{base}/files/report.pdf"),
+            asset::UrlAssetOptions {
+                deny_hosts: vec!["127.0.0.1".to_string()],
+                ..loopback.clone()
+            },
+            "denied",
+        ),
+        (
+            format!("{base}/files/report.pdf"),
+            asset::UrlAssetOptions {
+                allow_hosts: vec!["*.example.com".to_string()],
+                ..loopback.clone()
+            },
+            "not allowed",
+        ),
+        ("file:///etc/passwd".to_string(), loopback.clone(), "scheme"),
+    ];
+    for (url, options, message) in rejected {
+        let err = asset::save_asset_from_url(&op, ws_path, &url, &options)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(message), "{url}: {err}");
+    }
+    assert_eq!(asset::list_assets(&op, ws_path).await?.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ASSET-002
+async fn test_asset_req_asset_002_url_guard_rejects_reserved_and_embedded_ranges(
+) -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "url-guard-space", "/tmp").await?;
+    let ws_path = "spaces/url-guard-space";
+    for url in [
+        // Benchmarking 198.18.0.0/15 and reserved 240.0.0.0/4.
+        "http://198.18.0.1/x",
+        "http://198.19.255.1/x",
+        "http://240.0.0.1/x",
+        "http://255.255.255.254/x",
+        // NAT64 64:ff9b::/96 embedding 10.0.0.1.
+        "http://[64:ff9b::a00:1]/x",
+        // 6to4 2002::/16 embedding 10.0.0.1.
+        "http://[2002:a00:1::1]/x",
+        // IPv4-compatible ::10.0.0.1.
+        "http://[::a00:1]/x",
+        // IPv4-mapped ::ffff:127.0.0.1.
+        "http://[::ffff:7f00:1]/x",
+        // Teredo 2001::/32 with inverted client address 127.0.0.1.
+        "http://[2001:0:4136:e378:8000:63bf:80ff:fffe]/x",
+    ] {
+        let err = asset::save_asset_from_url(&op, ws_path, url, &asset::UrlAssetOptions::default())
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("non-public address"),
+            "{url}: {err}"
+        );
+    }
+    assert!(asset::list_assets(&op, ws_path).await?.is_empty());
+    Ok(())
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use _ugoite_core::asset_links::{
+    asset_ids_in, rewrite_asset_links, AssetLinkDirection, AssetLinkMap,
+};
+use _ugoite_core::importers::{self, ImportFile, ImportOptions, ImportSource};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::{asset, entry, space};
+use common::setup_operator;
+use futures::TryStreamExt;
+
+#[test]
+/// REQ-ASSET-003
+fn test_asset_links_req_asset_003_round_trips_links() {
+    let mut map = AssetLinkMap::new();
+    map.insert("a1", "assets/a1_chart one.png");
+    map.insert("b2", "assets/b2_notes.pdf");
+    let markdown = "![chart](ugoite://asset/a1#page=2) and [pdf](<ugoite://asset/b2> \"Notes\")\n\
+        <img src=\"ugoite://asset/a1\" alt=\"\">\n\
+        [gone](ugoite://asset/missing) [site](https://example.com/a.png) [next](ugoite://entry/notes)\n\n\
+        [ref]: ugoite://asset/b2\n";
+
+    let exported = rewrite_asset_links(markdown, "entries", &map, AssetLinkDirection::Export);
+    assert_eq!(
+        exported.content,
+        "![chart](../assets/a1_chart%20one.png#page=2) and [pdf](<../assets/b2_notes.pdf> \"Notes\")\n\
+        <img src=\"../assets/a1_chart%20one.png\" alt=\"\">\n\
+        [gone](ugoite://asset/missing) [site](https://example.com/a.png) [next](ugoite://entry/notes)\n\n\
+        [ref]: ../assets/b2_notes.pdf\n"
+    );
+    let counts: Vec<(&str, &str, usize)> = exported
+        .report
+        .mappings
+        .iter()
+        .map(|m| (m.asset_id.as_str(), m.path.as_str(), m.occurrences))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![
+            ("a1", "assets/a1_chart one.png", 2),
+            ("b2", "assets/b2_notes.pdf", 2)
+        ]
+    );
+    assert_eq!(exported.report.unresolved, vec!["ugoite://asset/missing"]);
+
+    let imported = rewrite_asset_links(
+        &exported.content,
+        "entries",
+        &map,
+        AssetLinkDirection::Import,
+    );
+    assert_eq!(imported.content, markdown);
+    assert_eq!(imported.report.mappings, exported.report.mappings);
+    assert!(imported.report.unresolved.is_empty());
+
+    // Relative links that match no asset are reported and left alone.
+    let other = rewrite_asset_links(
+        "[plan](plan.md) ![x](../assets/A1_Chart%20One.png)",
+        "entries",
+        &map,
+        AssetLinkDirection::Import,
+    );
+    assert_eq!(other.content, "[plan](plan.md) ![x](ugoite://asset/a1)");
+    assert_eq!(other.report.unresolved, vec!["plan.md"]);
+    assert_eq!(
+        asset_ids_in(markdown).into_iter().collect::<Vec<_>>(),
+        vec!["a1", "b2", "missing"]
+    );
+    assert!("sideways".parse::<AssetLinkDirection>().is_err());
+}
+
+#[tokio::test]
+/// REQ-ASSET-003
+async fn test_asset_links_req_asset_003_markdown_export_reimports_assets() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "source-space", "/tmp").await?;
+    space::create_space(&op, "copy-space", "/tmp").await?;
+    let ws_path = "spaces/source-space";
+    let image = asset::save_asset(&op, ws_path, "site map.png", b"png-bytes").await?;
+    asset::save_asset(&op, ws_path, "unused.txt", b"unused").await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "plan",
+        &format!(
+            "---\nform: Entry\n---\n# Plan\n\n## Body\nSee ![map](ugoite://asset/{}) and [old](ugoite://asset/deleted).\n",
+            image.id
+        ),
+        "tester",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+
+    let target = setup_operator()?;
+    let export = space::export_markdown_bundle(&op, "source-space", &target, "bundle").await?;
+    assert_eq!(export.entry_count, 1);
+    assert_eq!(export.asset_count, 1);
+    let asset_path = format!("assets/{}_site map.png", image.id);
+    assert_eq!(
+        export.files,
+        vec![asset_path.clone(), "entries/plan.md".to_string()]
+    );
+    assert_eq!(export.asset_links.mappings[0].path, asset_path);
+    assert_eq!(
+        export.asset_links.unresolved,
+        vec!["ugoite://asset/deleted"]
+    );
+    let plan = String::from_utf8(target.read("bundle/entries/plan.md").await?.to_vec())?;
+    assert!(plan.contains(&format!("![map](../assets/{}_site%20map.png)", image.id)));
+
+    let mut files = Vec::new();
+    let mut lister = target.lister_with("bundle/").recursive(true).await?;
+    while let Some(item) = lister.try_next().await? {
+        if item.metadata().is_file() {
+            files.push(ImportFile {
+                path: item.path().trim_start_matches("bundle/").to_string(),
+                content: target.read(item.path()).await?.to_vec(),
+            });
+        }
+    }
+    let summary = importers::import_files(
+        &op,
+        "copy-space",
+        &ImportOptions::new(ImportSource::Obsidian),
+        files,
+    )
+    .await?;
+    assert_eq!(summary.entries_created, 1);
+    assert_eq!(summary.assets_created, 1);
+    let note = summary
+        .files
+        .iter()
+        .find(|f| f.path == "entries/plan.md")
+        .expect("note report");
+    assert_eq!(note.asset_links.len(), 1);
+    assert_eq!(note.asset_links[0].path, asset_path);
+
+    let copied = &asset::list_assets(&op, "spaces/copy-space").await?[0];
+    assert!(copied.name.ends_with("site map.png"));
+    let entry_id = note.entry_id.as_deref().expect("entry id");
+    let stored = entry::get_entry(&op, "spaces/copy-space", entry_id).await?;
+    assert!(stored["content"]
+        .as_str()
+        .unwrap_or_default()
+        .contains(&format!("![map](ugoite://asset/{})", copied.id)));
+    assert_eq!(stored["assets"][0]["id"], copied.id.as_str());
+    Ok(())
+}
@@ -115,3 +115,51 @@ async def test_user_preferences_bindings(tmp_path: pathlib.Path) -> None:
 
     hashed_user = hashlib.sha256(user_id.encode("utf-8")).hexdigest()
     assert (root / "users" / hashed_user / "preferences.json").exists()
+
+
+@pytest.mark.asyncio
+async def test_json_bytes_bindings_match_dict_bindings(tmp_path: pathlib.Path) -> None:
+    """The ``*_json`` fast paths return the same data as serialized bytes."""
+    root = tmp_path / "storage"
+    root.mkdir()
+    config = {"uri": f"fs://{root}"}
+    await ugoite_core.create_space(config, "sp-json")
+    await ugoite_core.create_entry(
+        config,
+        "sp-json",
+        "entry-1",
+        "---\nform: Entry\n---\n# Fast path\n\n## Body\nBytes over dicts",
+        author="tester",
+    )
+
+    raw = await ugoite_core.list_entries_json(config, "sp-json")
+    assert isinstance(raw, bytes)
+    payload = ugoite_core.JsonPayload(raw)
+    assert payload.raw == raw
+    assert len(payload) == len(raw)
+    assert payload.decode() == await ugoite_core.list_entries(config, "sp-json")
+    assert payload.decode() is payload.decode()
+
+    query = json.dumps({"form": "Entry"})
+    assert ugoite_core.loads_json(
+        await ugoite_core.query_index_json(config, "sp-json", query),
+    ) == await ugoite_core.query_index(config, "sp-json", query)
+    assert ugoite_core.loads_json(
+        await ugoite_core.search_entries_json(config, "sp-json", "Bytes"),
+    ) == await ugoite_core.search_entries(config, "sp-json", "Bytes")
+
+
+@pytest.mark.asyncio
+async def test_warmup_binding_reports_spaces(tmp_path: pathlib.Path) -> None:
+    """``warmup`` preloads every space and reports unknown ones without raising."""
+    root = tmp_path / "storage"
+    root.mkdir()
+    config = {"uri": f"fs://{root}"}
+    await ugoite_core.create_space(config, "sp-warm")
+
+    report = await ugoite_core.warmup(config)
+    assert [space["space_id"] for space in report["spaces"]] == ["sp-warm"]
+    assert report["spaces"][0]["integrity_verified"] is True
+
+    missing = await ugoite_core.warmup(config, ["sp-missing"])
+    assert "not found" in missing["spaces"][0]["error"]
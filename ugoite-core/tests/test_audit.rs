@@ -0,0 +1,83 @@
+mod common;
+
+use _ugoite_core::audit::{self, AuditListOptions, AuditValidationMode};
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::json;
+
+#[tokio::test]
+/// REQ-SEC-021
+async fn test_audit_req_sec_021_action_registry_validation() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "audit-schema", "/tmp").await?;
+
+    let actions = audit::list_audit_actions();
+    let names: Vec<&str> = actions.iter().map(|spec| spec.action).collect();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+    let hold = audit::audit_action("legal_hold.place").expect("registered action");
+    assert_eq!(hold.required_metadata, ["hold_id"]);
+
+    let valid = json!({
+        "action": "entry_lock.steal",
+        "actor_user_id": "alice",
+        "target_type": "entry",
+        "target_id": "e1",
+        "metadata": {"previous_holder": "bob"},
+    });
+    let event = audit::append_audit_event_with_mode(
+        &op,
+        "audit-schema",
+        &valid,
+        None,
+        AuditValidationMode::Strict,
+    )
+    .await?;
+    assert!(event.get("schema_warnings").is_none());
+
+    let missing = json!({
+        "action": "entry_lock.steal",
+        "actor_user_id": "alice",
+        "target_type": "form",
+        "metadata": {},
+    });
+    let err = audit::append_audit_event_with_mode(
+        &op,
+        "audit-schema",
+        &missing,
+        None,
+        AuditValidationMode::Strict,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("target_type entry"), "{err}");
+    assert!(err.to_string().contains("previous_holder"), "{err}");
+    let unknown = json!({"action": "entry.teleport", "actor_user_id": "alice"});
+    assert!(audit::append_audit_event_with_mode(
+        &op,
+        "audit-schema",
+        &unknown,
+        None,
+        AuditValidationMode::Strict,
+    )
+    .await
+    .is_err());
+
+    // Permissive mode keeps the event and records what was wrong with it.
+    let event = audit::append_audit_event_with_mode(
+        &op,
+        "audit-schema",
+        &unknown,
+        None,
+        AuditValidationMode::Permissive,
+    )
+    .await?;
+    assert_eq!(
+        event["schema_warnings"],
+        json!(["unknown audit action: entry.teleport"])
+    );
+    let listed = audit::list_audit_events(&op, "audit-schema", AuditListOptions::default()).await?;
+    assert_eq!(listed["total"], 2);
+    Ok(())
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use _ugoite_core::audit::{self, AuditValidationMode};
+use _ugoite_core::audit_otlp::{self, OtlpExporterConfig, OtlpLogSink};
+use _ugoite_core::space;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use common::setup_operator;
+use serde_json::{json, Value};
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct RecordingSink {
+    requests: Mutex<Vec<Value>>,
+    fail: Mutex<bool>,
+}
+
+#[async_trait]
+impl OtlpLogSink for RecordingSink {
+    async fn push(&self, request: &Value) -> anyhow::Result<()> {
+        if *self.fail.lock().unwrap() {
+            return Err(anyhow!("503 Service Unavailable"));
+        }
+        self.requests.lock().unwrap().push(request.clone());
+        Ok(())
+    }
+}
+
+fn attribute<'a>(record: &'a Value, key: &str) -> &'a Value {
+    record["attributes"]
+        .as_array()
+        .and_then(|items| items.iter().find(|item| item["key"] == key))
+        .map(|item| &item["value"])
+        .unwrap_or(&Value::Null)
+}
+
+#[test]
+/// REQ-SEC-025
+fn test_audit_otlp_req_sec_025_maps_events_to_log_records() -> anyhow::Result<()> {
+    let event = json!({
+        "id": "audit-1",
+        "timestamp": "2024-01-01T00:00:00.250Z",
+        "space_id": "ops",
+        "action": "auth.authenticate",
+        "actor_user_id": "anonymous",
+        "outcome": "deny",
+        "target_type": null,
+        "target_id": null,
+        "request_method": "GET",
+        "request_path": "/spaces",
+        "request_id": "req-9",
+        "metadata": {"code": "expired_key", "attempts": 3, "trusted": false},
+        "prev_hash": "root",
+        "event_hash": "abc",
+    });
+    let request = audit_otlp::audit_events_to_otlp(std::slice::from_ref(&event), "ugoite-test");
+    let resource = &request["resourceLogs"][0];
+    assert_eq!(
+        resource["resource"]["attributes"][0],
+        json!({"key": "service.name", "value": {"stringValue": "ugoite-test"}})
+    );
+    let scope = &resource["scopeLogs"][0];
+    assert_eq!(scope["scope"]["name"], "ugoite.audit");
+    let record = &scope["logRecords"][0];
+    assert_eq!(record["timeUnixNano"], "1704067200250000000");
+    assert_eq!(record["severityNumber"], 13);
+    assert_eq!(record["severityText"], "WARN");
+    assert_eq!(record["body"], json!({"stringValue": "auth.authenticate"}));
+    assert_eq!(
+        attribute(record, "enduser.id"),
+        &json!({"stringValue": "anonymous"})
+    );
+    assert_eq!(
+        attribute(record, "http.request.method"),
+        &json!({"stringValue": "GET"})
+    );
+    assert_eq!(
+        attribute(record, "ugoite.audit.metadata.code"),
+        &json!({"stringValue": "expired_key"})
+    );
+    assert_eq!(
+        attribute(record, "ugoite.audit.metadata.attempts"),
+        &json!({"intValue": "3"})
+    );
+    assert_eq!(
+        attribute(record, "ugoite.audit.metadata.trusted"),
+        &json!({"boolValue": false})
+    );
+    // Empty fields are left out rather than sent as empty values.
+    assert_eq!(attribute(record, "ugoite.audit.target_id"), &Value::Null);
+
+    assert_eq!(audit_otlp::otlp_severity("success"), (9, "INFO"));
+    assert_eq!(audit_otlp::otlp_severity("error"), (17, "ERROR"));
+
+    let config: OtlpExporterConfig =
+        serde_json::from_value(json!({"endpoint": "https://collector.example.com:4318/"}))?;
+    assert_eq!(
+        config.logs_url()?,
+        "https://collector.example.com:4318/v1/logs"
+    );
+    assert_eq!(config.batch_size, 200);
+    assert!(OtlpExporterConfig::new("ftp://collector")
+        .logs_url()
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-SEC-025
+async fn test_audit_otlp_req_sec_025_exports_incrementally() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "otlp-space", "/tmp").await?;
+    for actor in ["alice", "bob", "carol"] {
+        audit::append_audit_event_with_mode(
+            &op,
+            "otlp-space",
+            &json!({"action": "space.update", "actor_user_id": actor, "outcome": "success"}),
+            None,
+            AuditValidationMode::Permissive,
+        )
+        .await?;
+    }
+
+    let mut config = OtlpExporterConfig::new("http://localhost:4318");
+    config.batch_size = 2;
+    let sink = RecordingSink::default();
+    let report = audit_otlp::export_audit_events(&op, "otlp-space", &config, &sink).await?;
+    assert_eq!(report.exported, 3);
+    assert_eq!(report.batches, 2);
+    let requests = sink.requests.lock().unwrap().clone();
+    let actors: Vec<Value> = requests
+        .iter()
+        .flat_map(|request| {
+            request["resourceLogs"][0]["scopeLogs"][0]["logRecords"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+        })
+        .map(|record| attribute(&record, "enduser.id").clone())
+        .collect();
+    assert_eq!(
+        actors,
+        vec![
+            json!({"stringValue": "alice"}),
+            json!({"stringValue": "bob"}),
+            json!({"stringValue": "carol"})
+        ]
+    );
+
+    // Nothing new: nothing is sent.
+    let report = audit_otlp::export_audit_events(&op, "otlp-space", &config, &sink).await?;
+    assert_eq!((report.exported, report.batches), (0, 0));
+
+    // A rejected push keeps the cursor, so the event goes out on the next run.
+    audit::append_audit_event_with_mode(
+        &op,
+        "otlp-space",
+        &json!({"action": "space.update", "actor_user_id": "dave", "outcome": "error"}),
+        None,
+        AuditValidationMode::Permissive,
+    )
+    .await?;
+    *sink.fail.lock().unwrap() = true;
+    let err = audit_otlp::export_audit_events(&op, "otlp-space", &config, &sink)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("503"), "{err}");
+    *sink.fail.lock().unwrap() = false;
+    let report = audit_otlp::export_audit_events(&op, "otlp-space", &config, &sink).await?;
+    assert_eq!(report.exported, 1);
+    let last = sink
+        .requests
+        .lock()
+        .unwrap()
+        .last()
+        .cloned()
+        .unwrap_or_default();
+    let record = &last["resourceLogs"][0]["scopeLogs"][0]["logRecords"][0];
+    assert_eq!(record["severityText"], "ERROR");
+    assert_eq!(
+        audit_otlp::get_export_cursor(&op, "otlp-space").await?,
+        report.cursor
+    );
+    Ok(())
+}
@@ -0,0 +1,198 @@
+use _ugoite_core::auth;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use hmac::{Hmac, KeyInit, Mac};
+use serde_json::{json, Value};
+
+const SECRET: &str = "introspect-secret";
+
+fn mint_signed_token(payload: Value, secret: &str) -> String {
+    let payload_segment = URL_SAFE_NO_PAD.encode(payload.to_string());
+    let mut mac = Hmac::<sha2_hmac::Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(payload_segment.as_bytes());
+    let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+    format!("v1.{payload_segment}.{signature}")
+}
+
+fn introspect(token: &str, revoked_key_ids: Option<&str>) -> Value {
+    auth::introspect_token(
+        token,
+        Some(
+            r#"{"static-token":{"user_id":"alice","key_id":"static-1","scopes":["entry_read"],"scope_enforced":true}}"#,
+        ),
+        Some(r#"{"api-key-1":{"user_id":"svc","principal_type":"service","disabled":true}}"#),
+        Some(&format!("kid-1:{SECRET}")),
+        Some("kid-1"),
+        revoked_key_ids,
+        None,
+        None,
+    )
+}
+
+#[test]
+/// REQ-SEC-015
+fn test_auth_req_sec_015_introspects_signed_tokens() {
+    let exp = chrono::Utc::now().timestamp() + 3600;
+    let token = mint_signed_token(
+        json!({"kid": "kid-1", "sub": "bob", "exp": exp, "iat": exp - 7200, "scopes": ["space_admin", "entry_read"], "scope_enforced": true}),
+        SECRET,
+    );
+    let report = introspect(&token, None);
+    assert_eq!(report["active"], json!(true));
+    assert_eq!(report["token_type"], json!("signed_bearer"));
+    assert_eq!(report["signature_valid"], json!(true));
+    assert_eq!(report["key_status"], json!("active"));
+    assert_eq!(report["claims"]["sub"], json!("bob"));
+    assert_eq!(report["validity"]["expires_at"], json!(exp as f64));
+    assert_eq!(report["validity"]["expired"], json!(false));
+    assert_eq!(
+        report["effective_scopes"],
+        json!(["entry_read", "space_admin"])
+    );
+    assert_eq!(report["identity"]["user_id"], json!("bob"));
+
+    // Rejected tokens still expose their claims and the reason.
+    let expired = mint_signed_token(
+        json!({"kid": "kid-1", "sub": "bob", "exp": exp - 7200}),
+        SECRET,
+    );
+    let report = introspect(&expired, None);
+    assert_eq!(report["active"], json!(false));
+    assert_eq!(report["validity"]["expired"], json!(true));
+    assert_eq!(report["claims"]["sub"], json!("bob"));
+    assert_eq!(report["error"]["code"], json!("expired_token"));
+    assert_eq!(report["effective_scopes"], Value::Null);
+
+    let forged = mint_signed_token(json!({"kid": "kid-1", "sub": "eve", "exp": exp}), "wrong");
+    let report = introspect(&forged, None);
+    assert_eq!(report["signature_valid"], json!(false));
+    assert_eq!(report["error"]["code"], json!("invalid_signature"));
+
+    let report = introspect(&token, Some("kid-1"));
+    assert_eq!(report["key_status"], json!("revoked"));
+    assert_eq!(report["error"]["code"], json!("revoked_key"));
+}
+
+#[test]
+/// REQ-SEC-015
+fn test_auth_req_sec_015_introspects_static_credentials() {
+    let report = introspect("static-token", None);
+    assert_eq!(report["active"], json!(true));
+    assert_eq!(report["token_type"], json!("static_bearer"));
+    assert_eq!(report["signature_valid"], Value::Null);
+    assert_eq!(report["key_id"], json!("static-1"));
+    assert_eq!(report["key_status"], json!("active"));
+    assert_eq!(report["effective_scopes"], json!(["entry_read"]));
+    assert_eq!(report["validity"]["expires_at"], Value::Null);
+
+    let report = introspect("api-key-1", None);
+    assert_eq!(report["token_type"], json!("api_key"));
+    assert_eq!(report["active"], json!(false));
+    assert_eq!(report["claims"]["user_id"], json!("svc"));
+    assert_eq!(report["error"]["code"], json!("disabled_identity"));
+
+    let report = introspect("nope", None);
+    assert_eq!(report["token_type"], json!("unknown"));
+    assert_eq!(report["claims"], Value::Null);
+    assert_eq!(report["error"]["code"], json!("invalid_credentials"));
+}
+
+#[test]
+/// REQ-SEC-024
+fn test_auth_req_sec_024_rejects_expired_keys() {
+    let now = chrono::Utc::now().timestamp();
+    let api_keys = json!({
+        "old-key": {"user_id": "svc-a", "principal_type": "service", "key_id": "k-old", "expires_at": now - 60},
+        "new-key": {"user_id": "svc-b", "principal_type": "service", "key_id": "k-new", "expires_at": "2999-01-01T00:00:00Z"},
+    })
+    .to_string();
+    let bearer_tokens = json!({
+        "old-token": {"user_id": "alice", "key_id": "t-old", "expires_at": "2000-01-01T00:00:00Z"},
+    })
+    .to_string();
+    let authenticate =
+        |authorization: Option<&str>, api_key: Option<&str>, revoked: Option<&str>| {
+            auth::authenticate_headers_core(
+                authorization,
+                api_key,
+                Some(&bearer_tokens),
+                Some(&api_keys),
+                None,
+                None,
+                revoked,
+                None,
+                None,
+            )
+        };
+
+    let outcome = authenticate(None, Some("old-key"), None);
+    assert_eq!(outcome["ok"], json!(false));
+    assert_eq!(outcome["error"]["code"], json!("expired_key"));
+    assert_eq!(outcome["error"]["detail"], json!("API key has expired"));
+    let outcome = authenticate(Some("Bearer old-token"), None, None);
+    assert_eq!(outcome["error"]["code"], json!("expired_key"));
+    // Revocation is reported before expiry.
+    let outcome = authenticate(None, Some("old-key"), Some("k-old"));
+    assert_eq!(outcome["error"]["code"], json!("revoked_key"));
+    let outcome = authenticate(None, Some("new-key"), None);
+    assert_eq!(outcome["ok"], json!(true));
+    assert_eq!(outcome["identity"]["user_id"], json!("svc-b"));
+
+    let report = auth::introspect_token(
+        "old-key",
+        Some(&bearer_tokens),
+        Some(&api_keys),
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    assert_eq!(report["key_status"], json!("expired"));
+    assert_eq!(report["validity"]["expires_at"], json!((now - 60) as f64));
+    assert_eq!(report["validity"]["expired"], json!(true));
+    assert_eq!(report["error"]["code"], json!("expired_key"));
+}
+
+#[test]
+/// REQ-SEC-024
+fn test_auth_req_sec_024_lists_expiring_keys() {
+    let now = chrono::Utc::now().timestamp();
+    let api_keys = json!({
+        "secret-soon": {"user_id": "svc-a", "principal_type": "service", "key_id": "k-soon", "service_account_id": "sa-1", "expires_at": now + 3600},
+        "secret-past": {"user_id": "svc-a", "principal_type": "service", "key_id": "k-past", "expires_at": now - 60},
+        "secret-later": {"user_id": "svc-b", "principal_type": "service", "key_id": "k-later", "expires_at": now + 30 * 86400},
+        "secret-never": {"user_id": "svc-b", "principal_type": "service", "key_id": "k-never"},
+        "secret-revoked": {"user_id": "svc-c", "principal_type": "service", "key_id": "k-revoked", "expires_at": now + 60},
+        "secret-disabled": {"user_id": "svc-d", "principal_type": "service", "key_id": "k-disabled", "disabled": true, "expires_at": now + 60},
+    })
+    .to_string();
+    let bearer_tokens = json!({
+        "token-soon": {"user_id": "alice", "key_id": "t-soon", "expires_at": now + 7200},
+    })
+    .to_string();
+
+    let expiring = auth::list_expiring_keys(
+        Some(&bearer_tokens),
+        Some(&api_keys),
+        Some("k-revoked"),
+        86400.0,
+    );
+    let items = expiring.as_array().expect("array");
+    let key_ids: Vec<&str> = items
+        .iter()
+        .map(|item| item["key_id"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(key_ids, vec!["k-past", "k-soon", "t-soon"]);
+    assert_eq!(items[0]["expired"], json!(true));
+    assert_eq!(items[1]["expired"], json!(false));
+    assert_eq!(items[1]["service_account_id"], json!("sa-1"));
+    assert_eq!(items[1]["credential_type"], json!("api_key"));
+    assert_eq!(items[2]["credential_type"], json!("bearer"));
+    let expires_in = items[1]["expires_in_seconds"].as_f64().unwrap_or_default();
+    assert!(expires_in > 3500.0 && expires_in <= 3600.0);
+    // The credential itself is never part of the report.
+    assert!(!expiring.to_string().contains("secret-"));
+
+    let wide = auth::list_expiring_keys(None, Some(&api_keys), Some("k-revoked"), 60.0 * 86400.0);
+    assert_eq!(wide.as_array().map(Vec::len), Some(3));
+}
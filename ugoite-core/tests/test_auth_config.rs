@@ -0,0 +1,173 @@
+mod common;
+
+use _ugoite_core::audit::{self, AuditListOptions};
+use _ugoite_core::auth;
+use _ugoite_core::auth_config::{self, AuthAuditContext, AuthConfig};
+use common::setup_operator;
+use serde_json::json;
+
+#[tokio::test]
+/// REQ-SEC-016
+async fn test_auth_config_req_sec_016_authenticates_from_stored_config() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    auth_config::invalidate_auth_config_cache(&op)?;
+    let empty = auth_config::load_auth_config(&op).await?;
+    assert!(empty.bearer_tokens.is_empty());
+
+    let mut config = AuthConfig::default();
+    config.bearer_tokens.insert(
+        "stored-token".to_string(),
+        json!({"user_id": "alice", "key_id": "token-1"}),
+    );
+    config.api_keys.insert(
+        "stored-key".to_string(),
+        json!({"user_id": "svc", "principal_type": "service"}),
+    );
+    let etag = auth_config::save_auth_config(&op, &config, Some(&empty.etag)).await?;
+
+    let loaded = auth_config::load_auth_config(&op).await?;
+    assert_eq!(loaded.etag, etag);
+    assert_ne!(loaded.etag, empty.etag);
+    let result = auth::authenticate_headers_with_config(
+        Some("Bearer stored-token"),
+        None,
+        &loaded,
+        None,
+        None,
+    );
+    assert_eq!(result["ok"], json!(true));
+    assert_eq!(result["identity"]["user_id"], json!("alice"));
+    let result =
+        auth::authenticate_headers_with_config(None, Some("stored-key"), &loaded, None, None);
+    assert_eq!(result["identity"]["principal_type"], json!("service"));
+
+    // Stored tokens take precedence over the bootstrap fallback.
+    let result = auth::authenticate_headers_with_config(
+        Some("Bearer boot"),
+        None,
+        &loaded,
+        Some("boot"),
+        None,
+    );
+    assert_eq!(result["error"]["code"], json!("invalid_credentials"));
+
+    let mut revoked = (*loaded).clone();
+    revoked.revoked_key_ids.push("token-1".to_string());
+    auth_config::save_auth_config(&op, &revoked, Some(&etag)).await?;
+    let stale = auth_config::save_auth_config(&op, &config, Some(&etag)).await;
+    assert!(stale
+        .unwrap_err()
+        .to_string()
+        .contains("Auth config changed"));
+
+    let reloaded = auth_config::load_auth_config(&op).await?;
+    assert_ne!(reloaded.etag, etag);
+    let result = auth::authenticate_headers_with_config(
+        Some("Bearer stored-token"),
+        None,
+        &reloaded,
+        None,
+        None,
+    );
+    assert_eq!(result["error"]["code"], json!("revoked_key"));
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-SEC-016
+async fn test_auth_config_req_sec_016_caches_until_invalidated() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let mut config = AuthConfig::default();
+    config
+        .signing_keys
+        .insert("kid-1".to_string(), "secret".to_string());
+    auth_config::save_auth_config(&op, &config, None).await?;
+    let first = auth_config::load_auth_config(&op).await?;
+
+    // Writes that bypass save_auth_config are picked up after invalidation.
+    op.write("auth/active_kids.json", b"[\"kid-1\"]".to_vec())
+        .await?;
+    let cached = auth_config::load_auth_config(&op).await?;
+    assert!(std::sync::Arc::ptr_eq(&first, &cached));
+    auth_config::invalidate_auth_config_cache(&op)?;
+    let fresh = auth_config::load_auth_config(&op).await?;
+    assert_eq!(fresh.active_kids, vec!["kid-1".to_string()]);
+    assert_ne!(fresh.etag, first.etag);
+
+    op.write("auth/api_keys.json", b"not json".to_vec()).await?;
+    assert!(auth_config::read_auth_config(&op)
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("auth/api_keys.json is malformed"));
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-SEC-017
+async fn test_auth_config_req_sec_017_audits_authentication_denials() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let mut config = AuthConfig::default();
+    config
+        .bearer_tokens
+        .insert("good-token".to_string(), json!({"user_id": "alice"}));
+    auth_config::save_auth_config(&op, &config, None).await?;
+    let audit_ctx = AuthAuditContext {
+        audit_space_id: "admin-space".to_string(),
+        request_method: Some("GET".to_string()),
+        request_path: Some("/spaces".to_string()),
+        request_id: Some("req-1".to_string()),
+        client_host: Some("10.0.0.8".to_string()),
+        user_agent: Some("curl/8".to_string()),
+    };
+
+    let denied = auth_config::authenticate_headers_audited(
+        &op,
+        Some("Bearer stolen-token"),
+        None,
+        None,
+        None,
+        Some(&audit_ctx),
+    )
+    .await?;
+    assert_eq!(denied["ok"], json!(false));
+    let allowed = auth_config::authenticate_headers_audited(
+        &op,
+        Some("Bearer good-token"),
+        None,
+        None,
+        None,
+        Some(&audit_ctx),
+    )
+    .await?;
+    assert_eq!(allowed["ok"], json!(true));
+    auth_config::authenticate_headers_audited(&op, None, None, None, None, Some(&audit_ctx))
+        .await?;
+    // Without an audit context nothing is recorded.
+    auth_config::authenticate_headers_audited(&op, None, Some("bad-key"), None, None, None).await?;
+
+    let events = audit::list_audit_events(&op, "admin-space", AuditListOptions::default()).await?;
+    let items = events["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    let stolen = items
+        .iter()
+        .find(|event| event["metadata"]["code"] == "invalid_credentials")
+        .unwrap();
+    assert_eq!(stolen["action"], "auth.authenticate");
+    assert_eq!(stolen["outcome"], "deny");
+    assert_eq!(stolen["actor_user_id"], "anonymous");
+    assert_eq!(stolen["request_path"], "/spaces");
+    assert_eq!(stolen["request_id"], "req-1");
+    assert_eq!(stolen["metadata"]["auth_method"], "bearer");
+    assert_eq!(
+        stolen["metadata"]["credential_fingerprint"],
+        json!(auth_config::credential_fingerprint("stolen-token"))
+    );
+    assert!(!stolen.to_string().contains("stolen-token"));
+    let missing = items
+        .iter()
+        .find(|event| event["metadata"]["code"] == "missing_credentials")
+        .unwrap();
+    assert!(missing["metadata"].get("credential_fingerprint").is_none());
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use _ugoite_core::authz::{self, authz_matrix_snapshot, AuthzIdentity, SpaceAction, SpaceRole};
+use serde_json::{json, Value};
+
+fn operation<'a>(snapshot: &'a Value, name: &str) -> &'a Value {
+    snapshot["operations"]
+        .as_array()
+        .and_then(|ops| ops.iter().find(|op| op["operation"] == name))
+        .unwrap_or_else(|| panic!("missing operation {name}"))
+}
+
+#[test]
+/// REQ-SEC-023
+fn test_authz_req_sec_023_matrix_lists_roles_and_scopes() -> anyhow::Result<()> {
+    let snapshot = authz_matrix_snapshot(None);
+    assert_eq!(snapshot["identity"], Value::Null);
+    assert_eq!(
+        snapshot["roles"]["viewer"],
+        json!([
+            "space_list",
+            "space_read",
+            "entry_read",
+            "form_read",
+            "asset_read",
+            "sql_read"
+        ])
+    );
+    assert_eq!(
+        snapshot["roles"]["owner"].as_array().map(Vec::len),
+        Some(11)
+    );
+
+    let names: Vec<&str> = authz::list_operation_rules()
+        .iter()
+        .map(|rule| rule.operation)
+        .collect();
+    let mut sorted = names.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(names, sorted, "operations are unique and sorted");
+
+    // Roles are derived from the role table, not listed per operation.
+    for rule in authz::list_operation_rules() {
+        let roles = operation(&snapshot, rule.operation)["roles"]
+            .as_array()
+            .expect("roles")
+            .clone();
+        for role in SpaceRole::ALL {
+            let expected = rule.actions.iter().all(|action| role.allows(*action));
+            assert_eq!(
+                roles.contains(&json!(role.as_str())),
+                expected,
+                "{} for {}",
+                rule.operation,
+                role.as_str()
+            );
+        }
+    }
+
+    let create_form = operation(&snapshot, "form.create");
+    assert_eq!(create_form["roles"], json!(["owner", "admin"]));
+    assert_eq!(create_form["scopes"], json!(["form_write", "space_admin"]));
+    let secrets = operation(&snapshot, "entry.read_secret_fields");
+    assert_eq!(secrets["scopes"], json!(["entry_read", "secret_read"]));
+    assert_eq!(secrets["form_acl"], "read");
+    assert_eq!(operation(&snapshot, "space.create")["admin_space"], true);
+    assert!(SpaceRole::Service.allows(SpaceAction::EntryWrite));
+    assert!(!SpaceRole::Service.allows(SpaceAction::FormWrite));
+    assert!("guest".parse::<SpaceRole>().is_err());
+    Ok(())
+}
+
+#[test]
+/// REQ-SEC-023
+fn test_authz_req_sec_023_matrix_evaluates_identity() -> anyhow::Result<()> {
+    let editor = AuthzIdentity {
+        role: Some(SpaceRole::Editor),
+        ..Default::default()
+    };
+    let snapshot = authz_matrix_snapshot(Some(&editor));
+    assert_eq!(snapshot["identity"]["role"], "editor");
+    assert_eq!(operation(&snapshot, "entry.update")["allowed"], true);
+    assert_eq!(
+        operation(&snapshot, "entry.update")["form_acl_applies"],
+        true
+    );
+    assert_eq!(operation(&snapshot, "space.update")["allowed"], false);
+    // Core-checked scopes are required even when scopes are not enforced.
+    assert_eq!(
+        operation(&snapshot, "entry.lock_override")["allowed"],
+        false
+    );
+
+    let admin = AuthzIdentity {
+        role: Some(SpaceRole::Admin),
+        scopes: vec!["legal_hold".into()],
+        scope_enforced: false,
+    };
+    let snapshot = authz_matrix_snapshot(Some(&admin));
+    assert_eq!(operation(&snapshot, "legal_hold.manage")["allowed"], true);
+    assert_eq!(
+        operation(&snapshot, "entry.update")["form_acl_applies"],
+        false
+    );
+
+    let api_key: AuthzIdentity = serde_json::from_value(json!({
+        "role": "service",
+        "scopes": ["entry_read", "asset_read"],
+        "scope_enforced": true,
+    }))?;
+    let snapshot = authz_matrix_snapshot(Some(&api_key));
+    assert_eq!(operation(&snapshot, "entry.read")["allowed"], true);
+    assert_eq!(operation(&snapshot, "asset.list")["allowed"], true);
+    assert_eq!(operation(&snapshot, "entry.create")["allowed"], false);
+    assert_eq!(operation(&snapshot, "sql.list")["allowed"], false);
+
+    let outsider: AuthzIdentity = serde_json::from_value(json!({"role": null}))?;
+    let snapshot = authz_matrix_snapshot(Some(&outsider));
+    assert!(snapshot["operations"]
+        .as_array()
+        .expect("operations")
+        .iter()
+        .all(|op| op["allowed"] == false));
+    Ok(())
+}
@@ -0,0 +1,56 @@
+mod common;
+use _ugoite_core::benchmark::{self, BenchmarkConfig, WorkloadMix};
+use common::setup_operator;
+
+#[tokio::test]
+/// REQ-OPS-044
+async fn test_benchmark_req_ops_044_mixed_workload_reports_percentiles() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let config = BenchmarkConfig {
+        entry_count: 12,
+        operations: 20,
+        concurrency: 4,
+        seed: Some(7),
+        ..BenchmarkConfig::new("bench-space")
+    };
+    let report = benchmark::run_benchmark(&op, "/tmp", &config).await?;
+
+    let all = &report.operations["all"];
+    assert_eq!(all.count + all.errors, 20);
+    assert_eq!(all.errors, 0);
+    assert!(all.min_ms <= all.p50_ms && all.p50_ms <= all.p90_ms);
+    assert!(all.p90_ms <= all.p99_ms && all.p99_ms <= all.max_ms);
+    let per_kind: usize = ["read", "write", "search", "sql"]
+        .iter()
+        .filter_map(|kind| report.operations.get(*kind))
+        .map(|summary| summary.count)
+        .sum();
+    assert_eq!(per_kind, 20);
+    assert!(report.ops_per_sec > 0.0);
+
+    let invalid = BenchmarkConfig {
+        mix: WorkloadMix {
+            read: 0,
+            write: 0,
+            search: 0,
+            sql: 0,
+        },
+        ..BenchmarkConfig::new("bench-space")
+    };
+    assert!(benchmark::run_workload(&op, &invalid).await.is_err());
+    Ok(())
+}
+
+#[test]
+/// REQ-OPS-044
+fn test_benchmark_req_ops_044_nearest_rank_percentiles() {
+    let latencies: Vec<f64> = (1..=100).map(f64::from).collect();
+    let summary = benchmark::summarize_latencies(&latencies, 3);
+    assert_eq!(summary.count, 100);
+    assert_eq!(summary.errors, 3);
+    assert_eq!(summary.p50_ms, 50.0);
+    assert_eq!(summary.p90_ms, 90.0);
+    assert_eq!(summary.p99_ms, 99.0);
+    assert_eq!(summary.max_ms, 100.0);
+    assert_eq!(benchmark::summarize_latencies(&[], 0).p99_ms, 0.0);
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use _ugoite_core::chat_format::{
+    format_chat_notification, ChatEvent, ChatFormatOptions, ChatPlatform,
+};
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use _ugoite_core::watchers::{self, WatchTarget};
+use common::setup_operator;
+use serde_json::json;
+
+fn options() -> ChatFormatOptions {
+    ChatFormatOptions {
+        base_url: "https://notes.example.com/".to_string(),
+    }
+}
+
+#[tokio::test]
+/// REQ-ENTRY-032
+async fn test_chat_format_req_entry_032_slack_blocks_with_deep_links() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "chat-space", "/tmp").await?;
+    let ws_path = "spaces/chat-space";
+    watchers::watch(&op, ws_path, "bob", WatchTarget::Form("Entry".into())).await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "q3 plan",
+        "---\nform: Entry\n---\n# Q3 <plan> & goals\n\n## Body\nDraft\n",
+        "alice",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let notification = watchers::list_notifications(&op, ws_path, "bob", None)
+        .await?
+        .pop()
+        .expect("notification");
+
+    let event = ChatEvent::from_watch_notification("chat-space", &notification);
+    let payload = format_chat_notification(ChatPlatform::Slack, &event, &options())?;
+    let link = "https://notes.example.com/spaces/chat-space/entries/q3%20plan";
+    assert_eq!(payload["text"], "alice created Q3 <plan> & goals");
+    let blocks = payload["blocks"].as_array().expect("blocks");
+    assert_eq!(
+        blocks[0]["text"]["text"],
+        format!(
+            "*alice created Q3 &lt;plan&gt; &amp; goals*\n<{link}|Q3 &lt;plan&gt; &amp; goals>"
+        )
+    );
+    assert_eq!(
+        blocks[1]["elements"][0]["text"],
+        "*Space:* chat-space  ·  *Form:* Entry  ·  *Author:* alice"
+    );
+    assert_eq!(blocks[2]["elements"][0]["url"], link);
+    assert_eq!(blocks[2]["elements"][0]["text"]["text"], "Open entry");
+
+    let job = ChatEvent::JobFailed {
+        space_id: "chat-space".into(),
+        job_kind: "import".into(),
+        job_id: "job-1".into(),
+        error: "CSV row 3: <unterminated quote>".into(),
+        timestamp: 1_704_067_200.0,
+    };
+    let payload = format_chat_notification(ChatPlatform::Slack, &job, &options())?;
+    assert_eq!(payload["text"], "import job failed in chat-space");
+    assert!(payload["blocks"][0]["text"]["text"]
+        .as_str()
+        .unwrap_or_default()
+        .ends_with("\n>CSV row 3: &lt;unterminated quote&gt;"));
+    assert_eq!(
+        payload["blocks"][2]["elements"][0]["url"],
+        "https://notes.example.com/spaces/chat-space"
+    );
+
+    let err = format_chat_notification(
+        ChatPlatform::Slack,
+        &job,
+        &ChatFormatOptions {
+            base_url: "ugoite://entry".into(),
+        },
+    )
+    .expect_err("non-http base");
+    assert!(err.to_string().contains("must be http or https"));
+    Ok(())
+}
+
+#[test]
+/// REQ-ENTRY-032
+fn test_chat_format_req_entry_032_discord_embeds() -> anyhow::Result<()> {
+    let event: ChatEvent = serde_json::from_value(json!({
+        "type": "comment_added",
+        "space_id": "chat-space",
+        "entry_id": "plan",
+        "entry_title": "Launch *plan*",
+        "author": "carol",
+        "body": format!("@everyone looks good\n{}", "x".repeat(600)),
+        "timestamp": 1_704_067_200.5,
+    }))?;
+    let payload = format_chat_notification(ChatPlatform::Discord, &event, &options())?;
+    assert_eq!(payload["allowed_mentions"], json!({"parse": []}));
+    let embed = &payload["embeds"][0];
+    assert_eq!(embed["title"], "carol commented on Launch *plan*");
+    assert_eq!(
+        embed["url"],
+        "https://notes.example.com/spaces/chat-space/entries/plan"
+    );
+    assert_eq!(embed["color"], 0x36C5F0);
+    assert_eq!(embed["timestamp"], "2024-01-01T00:00:00Z");
+    let description = embed["description"].as_str().expect("description");
+    assert!(description.starts_with(
+        "[Launch \\*plan\\*](https://notes.example.com/spaces/chat-space/entries/plan)\n> @everyone looks good\n> xxx"
+    ));
+    assert!(description.ends_with('…'));
+    assert!(description.chars().count() < 600);
+    assert_eq!(
+        embed["fields"],
+        json!([
+            {"name": "Space", "value": "chat-space", "inline": true},
+            {"name": "Author", "value": "carol", "inline": true},
+        ])
+    );
+
+    let slack = format_chat_notification("slack".parse()?, &event, &options())?;
+    assert_eq!(
+        slack["blocks"][2]["elements"][0]["text"]["text"],
+        "View comment"
+    );
+    assert!("teams".parse::<ChatPlatform>().is_err());
+    Ok(())
+}
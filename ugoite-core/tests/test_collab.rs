@@ -0,0 +1,177 @@
+mod common;
+use _ugoite_core::collab;
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::legal_hold::{self, HoldTargetType, LEGAL_HOLD_SCOPE};
+use _ugoite_core::space;
+use common::setup_operator;
+use yrs::updates::decoder::Decode;
+use yrs::updates::encoder::Encode;
+use yrs::{Doc, GetString, ReadTxn, StateVector, Text, Transact, Update};
+
+async fn seed_collab_entry(op: &opendal::Operator, space_id: &str) -> anyhow::Result<String> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{space_id}");
+    let markdown = "---\nform: Entry\n---\n# Pad\n\n## Body\nShared pad.\n";
+    entry::create_entry(
+        op,
+        &ws_path,
+        "pad",
+        markdown,
+        "alice",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    Ok(ws_path)
+}
+
+/// Insert `text` at `index` and return the resulting v1 update.
+fn edit(doc: &Doc, index: u32, text: &str) -> Vec<u8> {
+    let body = doc.get_or_insert_text("body");
+    let before = doc.transact().state_vector();
+    body.insert(&mut doc.transact_mut(), index, text);
+    doc.transact().encode_diff_v1(&before)
+}
+
+fn text_of(update: &[u8]) -> String {
+    let doc = Doc::new();
+    doc.transact_mut()
+        .apply_update(Update::decode_v1(update).unwrap())
+        .unwrap();
+    let body = doc.get_or_insert_text("body");
+    let text = body.get_string(&doc.transact());
+    text
+}
+
+#[tokio::test]
+/// REQ-ENTRY-014
+async fn test_collab_req_entry_014_updates_sync_between_clients() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_collab_entry(&op, "collab-sync").await?;
+
+    let alice = Doc::with_client_id(1);
+    let bob = Doc::with_client_id(2);
+    assert_eq!(
+        collab::apply_update(&op, &ws_path, "pad", &edit(&alice, 0, "Hello")).await?,
+        1
+    );
+
+    // Bob catches up from an empty state vector, then both edit concurrently.
+    let full = collab::encode_diff(&op, &ws_path, "pad", &[]).await?;
+    assert_eq!(text_of(&full), "Hello");
+    bob.transact_mut()
+        .apply_update(Update::decode_v1(&full)?)
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let bob_sv = bob.transact().state_vector().encode_v1();
+
+    collab::apply_update(&op, &ws_path, "pad", &edit(&alice, 5, " world")).await?;
+    assert_eq!(
+        collab::apply_update(&op, &ws_path, "pad", &edit(&bob, 0, ">> ")).await?,
+        3
+    );
+
+    let missing = collab::encode_diff(&op, &ws_path, "pad", &bob_sv).await?;
+    bob.transact_mut()
+        .apply_update(Update::decode_v1(&missing)?)
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    let body = bob.get_or_insert_text("body");
+    assert_eq!(body.get_string(&bob.transact()), ">> Hello world");
+
+    let server_sv = StateVector::decode_v1(&collab::get_state_vector(&op, &ws_path, "pad").await?)?;
+    assert_eq!(server_sv, bob.transact().state_vector());
+
+    let err = collab::apply_update(&op, &ws_path, "pad", b"not an update")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Invalid CRDT update"));
+    assert!(collab::get_state_vector(&op, &ws_path, "missing")
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-014
+async fn test_collab_req_entry_014_compaction_preserves_state() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_collab_entry(&op, "collab-compact").await?;
+    let writer = Doc::with_client_id(7);
+
+    for idx in 0..3 {
+        collab::apply_update(&op, &ws_path, "pad", &edit(&writer, idx, "x")).await?;
+    }
+    let before = collab::encode_diff(&op, &ws_path, "pad", &[]).await?;
+    assert_eq!(collab::compact_updates(&op, &ws_path, "pad").await?, 3);
+    assert_eq!(collab::compact_updates(&op, &ws_path, "pad").await?, 0);
+    let after = collab::encode_diff(&op, &ws_path, "pad", &[]).await?;
+    assert_eq!(text_of(&before), text_of(&after));
+    assert!(
+        op.exists(&format!("{ws_path}/collab/pad/snapshot.bin"))
+            .await?
+    );
+
+    // Sequence numbers restart after the snapshot and state keeps accumulating.
+    assert_eq!(
+        collab::apply_update(&op, &ws_path, "pad", &edit(&writer, 3, "y")).await?,
+        1
+    );
+    let full = collab::encode_diff(&op, &ws_path, "pad", &[]).await?;
+    assert_eq!(text_of(&full), "xxxy");
+
+    // Pending updates reaching the threshold are folded automatically.
+    for idx in 0..(collab::COMPACTION_THRESHOLD - 1) as u32 {
+        collab::apply_update(&op, &ws_path, "pad", &edit(&writer, 4 + idx, "z")).await?;
+    }
+    assert_eq!(
+        collab::apply_update(&op, &ws_path, "pad", &edit(&writer, 0, "!")).await?,
+        1
+    );
+
+    legal_hold::place_legal_hold(
+        &op,
+        &ws_path,
+        HoldTargetType::Entry,
+        "pad",
+        "Review",
+        "counsel",
+        &[LEGAL_HOLD_SCOPE.to_string()],
+    )
+    .await?;
+    let err = collab::compact_updates(&op, &ws_path, "pad")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("legal hold"));
+
+    // Vacuum skips held entries but reports updates it cannot fold.
+    let vacuum_options = space::VacuumOptions {
+        compact_tables: false,
+        ..space::VacuumOptions::default()
+    };
+    let report = space::vacuum(&op, "collab-compact", &vacuum_options).await?;
+    assert_eq!(report.collab_updates_compacted, 0);
+    let hold = legal_hold::list_legal_holds(&op, &ws_path).await?.remove(0);
+    legal_hold::release_legal_hold(
+        &op,
+        &ws_path,
+        &hold.hold_id,
+        "counsel",
+        &[LEGAL_HOLD_SCOPE.to_string()],
+    )
+    .await?;
+    op.write(
+        &format!("{ws_path}/collab/pad/updates/000000000099.bin"),
+        b"not a yjs update".to_vec(),
+    )
+    .await?;
+    let err = space::vacuum(&op, "collab-compact", &vacuum_options)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Failed to compact collaborative updates of pad"),
+        "{err}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use _ugoite_core::collections::{self, CollectionUpdate};
+use _ugoite_core::entry;
+use _ugoite_core::index;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::Value;
+
+fn ids(entries: &[Value]) -> Vec<String> {
+    let mut ids: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| entry["id"].as_str().map(str::to_string))
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+/// REQ-ENTRY-025
+async fn test_collections_req_entry_025_nested_collections() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "folders", "/tmp").await?;
+    let ws_path = "spaces/folders";
+    for (id, title) in [("a", "Alpha"), ("b", "Bravo"), ("c", "Charlie")] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: Entry\n---\n# {title}\n\n## Body\nText\n"),
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+
+    let work = collections::create_collection(
+        &op,
+        ws_path,
+        "Work",
+        None,
+        Some("briefcase"),
+        Some("Job notes"),
+    )
+    .await?;
+    let projects =
+        collections::create_collection(&op, ws_path, "Projects", Some(&work.id), None, None)
+            .await?;
+    assert!(
+        collections::create_collection(&op, ws_path, "work", None, None, None)
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("already exists")
+    );
+    let listed = collections::list_collections(&op, ws_path).await?;
+    assert_eq!(listed[0].id, work.id);
+    assert_eq!(listed[1].parent_id.as_deref(), Some(work.id.as_str()));
+
+    collections::move_entry_to_collection(&op, ws_path, "a", Some(&work.id)).await?;
+    collections::move_entry_to_collection(&op, ws_path, "b", Some(&projects.id)).await?;
+    assert!(
+        collections::move_entry_to_collection(&op, ws_path, "missing", Some(&work.id))
+            .await
+            .is_err()
+    );
+
+    let direct = collections::list_collection_entries(&op, ws_path, Some(&work.id), false).await?;
+    assert_eq!(ids(&direct), vec!["a"]);
+    assert_eq!(direct[0]["collection_id"], Value::from(work.id.clone()));
+    let nested = collections::list_collection_entries(&op, ws_path, Some(&work.id), true).await?;
+    assert_eq!(ids(&nested), vec!["a", "b"]);
+    let unfiled = collections::list_collection_entries(&op, ws_path, None, false).await?;
+    assert_eq!(ids(&unfiled), vec!["c"]);
+
+    // Index queries scope to a collection's subtree, or to unfiled entries with null.
+    let query = format!(r#"{{"collection": "{}"}}"#, work.id);
+    assert_eq!(
+        ids(&index::query_index(&op, ws_path, &query).await?),
+        vec!["a", "b"]
+    );
+    let page = index::query_index_page(&op, ws_path, &query, None, 1).await?;
+    assert_eq!(ids(&page.rows), vec!["a"]);
+    assert!(page.has_more);
+    assert_eq!(
+        ids(&index::query_index(&op, ws_path, r#"{"collection": null}"#).await?),
+        vec!["c"]
+    );
+
+    assert!(
+        collections::move_collection(&op, ws_path, &work.id, Some(&projects.id))
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("own subtree")
+    );
+    let moved = collections::move_collection(&op, ws_path, &projects.id, None).await?;
+    assert_eq!(moved.parent_id, None);
+
+    let updated = collections::update_collection(
+        &op,
+        ws_path,
+        &work.id,
+        &CollectionUpdate {
+            name: Some("Office".to_string()),
+            icon: Some(String::new()),
+            description: None,
+        },
+    )
+    .await?;
+    assert_eq!(updated.name, "Office");
+    assert_eq!(updated.icon, None);
+    assert_eq!(updated.description.as_deref(), Some("Job notes"));
+
+    // Deleting a collection files its entries and children under its parent.
+    collections::move_collection(&op, ws_path, &projects.id, Some(&work.id)).await?;
+    collections::delete_collection(&op, ws_path, &projects.id).await?;
+    let direct = collections::list_collection_entries(&op, ws_path, Some(&work.id), false).await?;
+    assert_eq!(ids(&direct), vec!["a", "b"]);
+    collections::delete_collection(&op, ws_path, &work.id).await?;
+    assert!(collections::list_collections(&op, ws_path)
+        .await?
+        .is_empty());
+    let unfiled = collections::list_collection_entries(&op, ws_path, None, false).await?;
+    assert_eq!(ids(&unfiled), vec!["a", "b", "c"]);
+    Ok(())
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use _ugoite_core::dashboards::{self, DashboardPayload, Widget, WidgetKind};
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::materialized_view;
+use _ugoite_core::saved_sql::{self, SqlPayload};
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::json;
+
+fn widget(id: &str, kind: WidgetKind) -> Widget {
+    Widget {
+        id: id.to_string(),
+        title: None,
+        kind,
+    }
+}
+
+#[tokio::test]
+/// REQ-API-021
+async fn test_dashboards_req_api_021_render_all_widgets() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "dash", "/tmp").await?;
+    let ws_path = "spaces/dash";
+    for (id, title, tags) in [
+        ("a", "Alpha", "[work, urgent]"),
+        ("b", "Bravo", "[work]"),
+        ("c", "Charlie", "[home]"),
+    ] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: Entry\ntags: {tags}\n---\n# {title}\n\n## Body\nText\n"),
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    saved_sql::create_sql(
+        &op,
+        ws_path,
+        "by-title",
+        &SqlPayload {
+            name: "By title".to_string(),
+            sql: "SELECT * FROM entries WHERE title = {{title}}".to_string(),
+            variables: json!([{"type": "string", "name": "title", "description": "Title"}]),
+            folder: None,
+            tags: None,
+        },
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    materialized_view::create_or_update_view(&op, ws_path, "all-entries", "SELECT * FROM Entry")
+        .await?;
+
+    let payload = DashboardPayload {
+        name: "Home".to_string(),
+        description: Some("Start page".to_string()),
+        widgets: vec![
+            widget(
+                "query",
+                WidgetKind::SavedSql {
+                    sql_id: "by-title".to_string(),
+                    params: json!({"title": "Bravo"}),
+                    limit: 10,
+                },
+            ),
+            widget(
+                "view",
+                WidgetKind::MaterializedView {
+                    sql_id: "all-entries".to_string(),
+                    limit: 2,
+                },
+            ),
+            widget(
+                "recent",
+                WidgetKind::RecentEntries {
+                    form: Some("Entry".to_string()),
+                    limit: 2,
+                },
+            ),
+            widget(
+                "tags",
+                WidgetKind::TagFacet {
+                    form: None,
+                    limit: 10,
+                },
+            ),
+            widget(
+                "broken",
+                WidgetKind::SavedSql {
+                    sql_id: "missing".to_string(),
+                    params: json!({}),
+                    limit: 10,
+                },
+            ),
+        ],
+    };
+    let dashboard = dashboards::create_dashboard(&op, ws_path, &payload).await?;
+    assert_eq!(dashboard.widgets.len(), 5);
+
+    let rendered = dashboards::render_dashboard(&op, ws_path, &dashboard.id).await?;
+    assert_eq!(rendered["dashboard"]["name"], json!("Home"));
+    let widgets = rendered["widgets"].as_array().expect("widgets");
+    assert_eq!(widgets[0]["type"], json!("saved_sql"));
+    assert_eq!(widgets[0]["data"]["rows"].as_array().map(Vec::len), Some(1));
+    assert_eq!(widgets[0]["data"]["rows"][0]["title"], json!("Bravo"));
+    assert_eq!(widgets[1]["data"]["rows"].as_array().map(Vec::len), Some(2));
+    assert_eq!(widgets[1]["data"]["truncated"], json!(true));
+    assert!(widgets[1]["data"]["snapshot_id"].is_u64());
+    assert_eq!(widgets[2]["data"]["rows"].as_array().map(Vec::len), Some(2));
+    assert_eq!(
+        widgets[3]["data"]["rows"],
+        json!([
+            {"tag": "work", "count": 2},
+            {"tag": "home", "count": 1},
+            {"tag": "urgent", "count": 1},
+        ])
+    );
+    assert!(widgets[4]["error"].as_str().is_some());
+    assert!(widgets[4].get("data").is_none());
+
+    let mut renamed = payload.clone();
+    renamed.name = "Overview".to_string();
+    renamed.widgets.truncate(1);
+    let updated = dashboards::update_dashboard(&op, ws_path, &dashboard.id, &renamed).await?;
+    assert_eq!(updated.widgets.len(), 1);
+    assert_eq!(updated.created_at, dashboard.created_at);
+
+    let mut duplicate = payload.clone();
+    duplicate.widgets.push(widget(
+        "query",
+        WidgetKind::TagFacet {
+            form: None,
+            limit: 5,
+        },
+    ));
+    assert!(dashboards::create_dashboard(&op, ws_path, &duplicate)
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("Duplicate widget id"));
+
+    let listed = dashboards::list_dashboards(&op, ws_path).await?;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].name, "Overview");
+    dashboards::delete_dashboard(&op, ws_path, &dashboard.id).await?;
+    assert!(dashboards::get_dashboard(&op, ws_path, &dashboard.id)
+        .await
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use _ugoite_core::decimal::{self, DecimalSpec};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::{entry, form, index, space};
+use common::setup_operator;
+use serde_json::json;
+use std::cmp::Ordering;
+
+#[test]
+/// REQ-IDX-016
+fn test_decimal_req_idx_016_exact_casting() -> anyhow::Result<()> {
+    let spec = DecimalSpec::from_field_def(
+        &json!({"type": "decimal", "precision": 8, "scale": 2, "currency": "usd"}),
+    )?;
+    assert_eq!((spec.precision, spec.scale), (8, 2));
+    assert_eq!(spec.currency.as_deref(), Some("USD"));
+    assert_eq!(
+        DecimalSpec::from_field_def(&json!({}))?,
+        DecimalSpec::default()
+    );
+    for bad in [
+        json!({"precision": 0}),
+        json!({"precision": 39}),
+        json!({"precision": 4, "scale": 5}),
+        json!({"currency": "dollars"}),
+    ] {
+        assert!(DecimalSpec::from_field_def(&bad).is_err(), "{bad}");
+    }
+
+    for (input, cast) in [
+        (json!("1,234.5"), Some("1234.50")),
+        (json!(0.1), Some("0.10")),
+        (json!(-3), Some("-3.00")),
+        (json!("USD 12"), Some("12.00")),
+        (json!("7.10 usd"), Some("7.10")),
+        (json!("1.230"), Some("1.23")),
+        (json!("1.005"), None),
+        (json!("1000000.00"), None),
+        (json!(1e21), None),
+        (json!("twelve"), None),
+    ] {
+        assert_eq!(spec.cast(&input).as_deref(), cast, "{input}");
+    }
+    assert_eq!(decimal::format_units(-5, 2), "-0.05");
+
+    assert_eq!(
+        decimal::compare_decimals(&json!("20.00"), &json!("100.00")),
+        Some(Ordering::Less)
+    );
+    assert_eq!(
+        decimal::compare_decimals(&json!("0.10"), &json!(0.1)),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(decimal::compare_decimals(&json!(1), &json!(2)), None);
+    assert_eq!(decimal::compare_decimals(&json!("abc"), &json!(2)), None);
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-IDX-016
+async fn test_decimal_req_idx_016_storage_and_sql() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "ledger", "/tmp").await?;
+    let ws_path = "spaces/ledger";
+    assert!(form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Broken",
+            "fields": {"Amount": {"type": "decimal", "precision": 2, "scale": 3}},
+        }),
+    )
+    .await
+    .is_err());
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Invoice",
+            "template": "# Invoice\n\n## Amount\n",
+            "fields": {
+                "Amount": {"type": "decimal", "precision": 10, "scale": 2, "currency": "USD"},
+            },
+        }),
+    )
+    .await?;
+    for (id, amount) in [("a", "0.10"), ("b", "0.2"), ("c", "USD 100"), ("d", "20.5")] {
+        let content = format!("---\nform: Invoice\n---\n# {id}\n\n## Amount\n{amount}\n");
+        entry::create_entry(&op, ws_path, id, &content, "author", &FakeIntegrityProvider).await?;
+    }
+
+    let rows = index::query_index(
+        &op,
+        ws_path,
+        &json!({"$sql": "SELECT * FROM Invoice WHERE Amount > 0.15 ORDER BY Amount DESC"})
+            .to_string(),
+    )
+    .await?;
+    let stored: Vec<(&str, &str)> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["id"].as_str().unwrap_or_default(),
+                row["properties"]["Amount"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert_eq!(stored, vec![("c", "100.00"), ("d", "20.50"), ("b", "0.20")]);
+
+    let matched = index::query_index(&op, ws_path, &json!({"Amount": "20.5"}).to_string()).await?;
+    let ids: Vec<&str> = matched
+        .iter()
+        .filter_map(|row| row["id"].as_str())
+        .collect();
+    assert_eq!(ids, vec!["d"]);
+
+    let too_precise = "---\nform: Invoice\n---\n# e\n\n## Amount\n1.005\n";
+    assert!(entry::create_entry(
+        &op,
+        ws_path,
+        "e",
+        too_precise,
+        "author",
+        &FakeIntegrityProvider
+    )
+    .await
+    .is_err());
+    Ok(())
+}
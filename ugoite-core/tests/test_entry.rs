@@ -1,8 +1,14 @@
 mod common;
 use _ugoite_core::asset;
-use _ugoite_core::entry;
+use _ugoite_core::audit;
+use _ugoite_core::clock;
+use _ugoite_core::entry::{
+    self, ChangeKind, ChangesSince, DiffHunkKind, MergeStrategy, RevisionConflict, RevisionNote,
+};
 use _ugoite_core::form;
+use _ugoite_core::importers::ImportJobStatus;
 use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::legal_hold::{self, HoldTargetType, LEGAL_HOLD_SCOPE};
 use _ugoite_core::space;
 use common::setup_operator;
 
@@ -449,3 +455,1084 @@ async fn test_entry_req_entry_008_assets_linking() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+/// REQ-ENTRY-020
+fn test_entry_req_entry_020_markdown_outline_builds_tree() {
+    let markdown = "---\nform: Entry\n---\n# Plan\n\n## Goals\ntext\n### Détails `v2`\n\n```\n# not a heading\n```\n## Goals\nmore\n# Appendix\n";
+    let outline = entry::markdown_outline(markdown);
+    let char_pos = |needle: &str| markdown[..markdown.find(needle).unwrap()].chars().count();
+    assert_eq!(outline.len(), 2);
+
+    let plan = &outline[0];
+    assert_eq!((plan.level, plan.text.as_str()), (1, "Plan"));
+    assert_eq!(plan.anchor, "plan");
+    assert_eq!(plan.offset, char_pos("# Plan"));
+    assert_eq!(plan.end_offset, char_pos("# Appendix"));
+    assert_eq!(plan.children.len(), 2);
+
+    let goals = &plan.children[0];
+    assert_eq!(goals.anchor, "goals");
+    assert_eq!(goals.children[0].text, "Détails v2");
+    assert_eq!(goals.children[0].anchor, "détails-v2");
+    assert_eq!(plan.children[1].anchor, "goals-1");
+    assert_eq!(goals.end_offset, plan.children[1].offset);
+
+    let appendix = &outline[1];
+    assert_eq!(appendix.end_offset, markdown.chars().count());
+    assert!(appendix.children.is_empty());
+}
+
+#[tokio::test]
+/// REQ-ENTRY-020
+async fn test_entry_req_entry_020_get_entry_outline() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/outline-space";
+    space::create_space(&op, "outline-space", "/tmp").await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "outlined",
+        "---\nform: Entry\n---\n# Outline\n\n## Body\nText\n",
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let outline = entry::get_entry_outline(&op, ws_path, "outlined").await?;
+    let content = entry::get_entry_content(&op, ws_path, "outlined")
+        .await?
+        .markdown;
+    assert_eq!(outline.len(), 1);
+    assert_eq!(outline[0].text, "Outline");
+    assert_eq!(outline[0].children[0].anchor, "body");
+    let body_offset = outline[0].children[0].offset;
+    assert!(content
+        .chars()
+        .skip(body_offset)
+        .collect::<String>()
+        .starts_with("## Body"));
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-021
+async fn test_entry_req_entry_021_read_and_patch_section() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/section-space";
+    space::create_space(&op, "section-space", "/tmp").await?;
+    form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({
+            "name": "Plan",
+            "template": "# Plan\n\n## Summary\n\n## Risks\n\n## Next steps\n",
+            "fields": {
+                "Summary": {"type": "markdown"},
+                "Risks": {"type": "markdown"},
+                "Next steps": {"type": "markdown"},
+            },
+        }),
+    )
+    .await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "sectioned",
+        "---\nform: Plan\n---\n# Sectioned\n\n## Summary\nIntro text\n\n## Risks\nNone yet\n\n## Next steps\nShip it\n",
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+
+    let path = vec!["Sectioned".to_string(), "risks".to_string()];
+    let section = entry::read_entry_section(&op, ws_path, "sectioned", &path).await?;
+    assert_eq!(section.level, 2);
+    assert_eq!(section.anchor, "risks");
+    assert!(section.heading.starts_with("## Risks"));
+    assert_eq!(section.body.trim(), "None yet");
+
+    entry::patch_entry_section(
+        &op,
+        ws_path,
+        "sectioned",
+        &path,
+        "Vendor delay\nBudget overrun",
+        None,
+        "editor",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let content = entry::get_entry_content(&op, ws_path, "sectioned").await?;
+    assert!(content
+        .markdown
+        .contains("## Risks\nVendor delay\nBudget overrun\n"));
+    assert!(content.markdown.contains("## Next steps\nShip it"));
+    assert!(content.markdown.contains("## Summary\nIntro text"));
+    assert_eq!(content.author, "editor");
+    assert_eq!(
+        content.parent_revision_id.as_deref(),
+        Some(section.revision_id.as_str())
+    );
+
+    let stale = entry::patch_entry_section(
+        &op,
+        ws_path,
+        "sectioned",
+        &path,
+        "Lost update",
+        Some(&section.revision_id),
+        "editor",
+        &FakeIntegrityProvider,
+    )
+    .await;
+    assert!(stale.unwrap_err().to_string().contains("Revision conflict"));
+
+    let missing = entry::read_entry_section(
+        &op,
+        ws_path,
+        "sectioned",
+        &["Sectioned".to_string(), "Budget".to_string()],
+    )
+    .await;
+    assert!(missing
+        .unwrap_err()
+        .to_string()
+        .contains("Section not found"));
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-027
+async fn test_entry_req_entry_027_bulk_update_properties() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/bulk-space";
+    space::create_space(&op, "bulk-space", "/tmp").await?;
+    form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({
+            "name": "Task",
+            "template": "# Task\n\n## Status\n\n## Owner\n",
+            "fields": {
+                "Status": {"type": "string"},
+                "Owner": {"type": "string"},
+            },
+        }),
+    )
+    .await?;
+    for (id, status) in [
+        ("t1", "open"),
+        ("t2", "open"),
+        ("t3", "open"),
+        ("t4", "done"),
+    ] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: Task\n---\n# {id}\n\n## Status\n{status}\n\n## Owner\nann\n"),
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    let patch = serde_json::json!({"Owner": "bob"})
+        .as_object()
+        .cloned()
+        .unwrap();
+    let query = r#"{"Status": "open"}"#;
+
+    let preview = entry::bulk_update_properties(
+        &op,
+        ws_path,
+        query,
+        &patch,
+        &entry::BulkUpdateOptions {
+            dry_run: true,
+            ..Default::default()
+        },
+        "editor",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert!(preview.dry_run);
+    assert_eq!(preview.matched, 3);
+    assert_eq!(preview.updated, vec!["t1", "t2", "t3"]);
+    let untouched = entry::get_entry_content(&op, ws_path, "t1").await?;
+    assert!(untouched.markdown.contains("## Owner\nann"));
+
+    let options = entry::BulkUpdateOptions {
+        batch_size: 2,
+        batch_delay_ms: 1,
+        ..Default::default()
+    };
+    let report = entry::bulk_update_properties(
+        &op,
+        ws_path,
+        query,
+        &patch,
+        &options,
+        "editor",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert_eq!(report.updated, vec!["t1", "t2", "t3"]);
+    assert!(report.failed.is_empty());
+    let updated = entry::get_entry_content(&op, ws_path, "t2").await?;
+    assert!(updated.markdown.contains("## Owner\nbob"));
+    assert!(updated.markdown.contains("## Status\nopen"));
+    assert_eq!(updated.author, "editor");
+    let history = entry::get_entry_history(&op, ws_path, "t2").await?;
+    assert_eq!(history["revisions"].as_array().unwrap().len(), 2);
+    let done = entry::get_entry_content(&op, ws_path, "t4").await?;
+    assert!(done.markdown.contains("## Owner\nann"));
+
+    let audit = audit::list_audit_events(
+        &op,
+        "bulk-space",
+        audit::AuditListOptions {
+            action: Some("entry.bulk_update".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(audit["items"].as_array().unwrap().len(), 3);
+
+    let rerun = entry::bulk_update_properties(
+        &op,
+        ws_path,
+        query,
+        &patch,
+        &options,
+        "editor",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert!(rerun.updated.is_empty());
+    assert_eq!(rerun.unchanged.len(), 3);
+
+    let close = serde_json::json!({"Status": "done"})
+        .as_object()
+        .cloned()
+        .unwrap();
+    let job = entry::create_bulk_update_job(
+        &op,
+        ws_path,
+        r#""SELECT * FROM entries WHERE id = 't1' OR id = 't3'""#,
+        &close,
+        &options,
+        "editor",
+    )
+    .await?;
+    assert_eq!(job.total_entries, 2);
+    let mut finished = None;
+    for _ in 0..400 {
+        let current = entry::get_bulk_update_job(&op, ws_path, &job.job_id).await?;
+        if matches!(
+            current.status,
+            ImportJobStatus::Completed | ImportJobStatus::Failed
+        ) {
+            finished = Some(current);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+    }
+    let finished = finished.expect("bulk update job did not finish");
+    assert_eq!(finished.status, ImportJobStatus::Completed);
+    assert_eq!(finished.processed_entries, 2);
+    assert_eq!(finished.report.updated, vec!["t1", "t3"]);
+    let closed = entry::get_entry_content(&op, ws_path, "t3").await?;
+    assert!(closed.markdown.contains("## Status\ndone"));
+
+    assert!(entry::bulk_update_properties(
+        &op,
+        ws_path,
+        query,
+        &serde_json::Map::new(),
+        &options,
+        "editor",
+        &FakeIntegrityProvider,
+    )
+    .await
+    .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-034
+async fn test_entry_req_entry_034_list_entries_page() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "paged", "/tmp").await?;
+    let ws_path = "spaces/paged";
+    for id in ["e3", "e1", "e5", "e2", "e4"] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: Entry\n---\n# {id}\n\n## Body\ntext\n"),
+            "tester",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    entry::delete_entry(&op, ws_path, "e4", false).await?;
+
+    let ids = |page: &entry::EntryPage| -> Vec<String> {
+        page.entries
+            .iter()
+            .filter_map(|e| e["id"].as_str().map(str::to_string))
+            .collect()
+    };
+    let live = entry::ListEntriesOptions::default();
+    let trash_only = entry::ListEntriesOptions::with_deleted(entry::DeletedFilter::Only);
+    let first = entry::list_entries_page(&op, ws_path, &live, 0, 2).await?;
+    assert_eq!(ids(&first), vec!["e1", "e2"]);
+    assert_eq!(first.total_count, 4);
+    assert!(first.has_more);
+    assert!(first.entries[0].get("properties").is_some());
+
+    let last = entry::list_entries_page(&op, ws_path, &live, 2, 2).await?;
+    assert_eq!(ids(&last), vec!["e3", "e5"]);
+    assert!(!last.has_more);
+
+    let beyond = entry::list_entries_page(&op, ws_path, &live, 10, 2).await?;
+    assert!(beyond.entries.is_empty());
+    assert_eq!(beyond.total_count, 4);
+
+    let trash = entry::list_entries_page(&op, ws_path, &trash_only, 0, 10).await?;
+    assert_eq!(ids(&trash), vec!["e4"]);
+    assert_eq!(trash.entries[0]["deleted"], true);
+
+    assert!(entry::list_entries_page(&op, ws_path, &live, 0, 0)
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-035
+async fn test_entry_req_entry_035_list_entries_with_options() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "filtered", "/tmp").await?;
+    let ws_path = "spaces/filtered";
+    form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({
+            "name": "Task",
+            "fields": {"Body": {"type": "markdown"}},
+        }),
+    )
+    .await?;
+    for (id, form_name, title, author) in [
+        ("n1", "Entry", "beta", "alice"),
+        ("n2", "Task", "Alpha", "bob"),
+        ("n3", "Entry", "gamma", "bob"),
+        ("n4", "Entry", "delta", "alice"),
+    ] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: {form_name}\n---\n# {title}\n\n## Body\ntext\n"),
+            author,
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    entry::delete_entry(&op, ws_path, "n4", false).await?;
+
+    let list = |options: serde_json::Value| {
+        let op = op.clone();
+        async move {
+            let options: entry::ListEntriesOptions = serde_json::from_value(options)?;
+            let entries = entry::list_entries_with_options(&op, ws_path, &options).await?;
+            Ok::<_, anyhow::Error>(
+                entries
+                    .iter()
+                    .filter_map(|e| e["id"].as_str().map(str::to_string))
+                    .collect::<Vec<_>>(),
+            )
+        }
+    };
+
+    assert_eq!(list(serde_json::json!({})).await?, vec!["n1", "n2", "n3"]);
+    assert_eq!(
+        list(serde_json::json!({"form": "Entry"})).await?,
+        vec!["n1", "n3"]
+    );
+    assert_eq!(
+        list(serde_json::json!({"author": "alice", "include_deleted": true})).await?,
+        vec!["n1", "n4"]
+    );
+    assert_eq!(
+        list(serde_json::json!({"sort_by": "title"})).await?,
+        vec!["n2", "n1", "n3"]
+    );
+    assert_eq!(
+        list(serde_json::json!({"sort_by": "updated_at", "order": "desc"})).await?,
+        vec!["n3", "n2", "n1"]
+    );
+
+    let created: Vec<f64> =
+        entry::list_entries_with_options(&op, ws_path, &entry::ListEntriesOptions::default())
+            .await?
+            .iter()
+            .filter_map(|e| e["created_at"].as_f64())
+            .collect();
+    assert_eq!(
+        list(serde_json::json!({"created_after": created[1]})).await?,
+        vec!["n2", "n3"]
+    );
+    assert_eq!(
+        list(serde_json::json!({"created_before": created[1]})).await?,
+        vec!["n1"]
+    );
+    assert_eq!(
+        list(serde_json::json!({"updated_after": created[2] + 3600.0})).await?,
+        Vec::<String>::new()
+    );
+
+    let page = entry::list_entries_page(
+        &op,
+        ws_path,
+        &serde_json::from_value(
+            serde_json::json!({"form": "Entry", "sort_by": "title", "order": "desc"}),
+        )?,
+        0,
+        1,
+    )
+    .await?;
+    assert_eq!(page.total_count, 2);
+    assert_eq!(page.entries[0]["id"], "n3");
+    assert!(serde_json::from_value::<entry::ListEntriesOptions>(
+        serde_json::json!({"sort_by": "author"})
+    )
+    .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-036
+async fn test_entry_req_entry_036_create_entries_bulk() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "bulk-create", "/tmp").await?;
+    let ws_path = "spaces/bulk-create";
+    ensure_entry_form(&op, ws_path).await?;
+
+    let items: Vec<entry::BulkEntryInput> = serde_json::from_value(serde_json::json!([
+        {"entry_id": "one", "content": "---\nform: Entry\n---\n# One\n\n## Body\nfirst\n"},
+        {"entry_id": "two", "content": "---\nform: Entry\n---\n# Two\n\n## Body\nsecond\n", "author": "bob"},
+        {"entry_id": "one", "content": "---\nform: Entry\n---\n# Again\n\n## Body\ndup\n"},
+        {"entry_id": "bad", "content": "# No form\n"},
+    ]))?;
+    let results =
+        entry::create_entries_bulk(&op, ws_path, &items, "alice", &FakeIntegrityProvider).await?;
+
+    let outcomes: Vec<(&str, bool)> = results
+        .iter()
+        .map(|r| (r.entry_id.as_str(), r.ok))
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![("one", true), ("two", true), ("one", false), ("bad", false)]
+    );
+    assert_eq!(
+        results[0].entry.as_ref().map(|e| e.title.as_str()),
+        Some("One")
+    );
+    assert!(results[2]
+        .error
+        .as_deref()
+        .is_some_and(|e| e.contains("already exists")));
+    assert!(results[3].entry.is_none() && results[3].error.is_some());
+
+    assert_eq!(entry::list_entries(&op, ws_path).await?.len(), 2);
+    let by_bob: entry::ListEntriesOptions =
+        serde_json::from_value(serde_json::json!({"author": "bob"}))?;
+    let bobs = entry::list_entries_with_options(&op, ws_path, &by_bob).await?;
+    let ids: Vec<&str> = bobs.iter().filter_map(|e| e["id"].as_str()).collect();
+    assert_eq!(ids, vec!["two"]);
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-037
+async fn test_entry_req_entry_037_bulk_delete_and_restore() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "bulk-trash", "/tmp").await?;
+    let ws_path = "spaces/bulk-trash";
+    ensure_entry_form(&op, ws_path).await?;
+    form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({
+            "name": "Task",
+            "fields": {"Body": {"type": "markdown"}},
+        }),
+    )
+    .await?;
+    for (id, form_name) in [
+        ("a", "Entry"),
+        ("b", "Entry"),
+        ("c", "Entry"),
+        ("d", "Task"),
+    ] {
+        let content = format!("---\nform: {form_name}\n---\n# {id}\n\n## Body\n{id}\n");
+        entry::create_entry(&op, ws_path, id, &content, "alice", &FakeIntegrityProvider).await?;
+    }
+    legal_hold::place_legal_hold(
+        &op,
+        ws_path,
+        HoldTargetType::Entry,
+        "c",
+        "Audit",
+        "counsel",
+        &[LEGAL_HOLD_SCOPE.to_string()],
+    )
+    .await?;
+
+    let ids = |list: &[&str]| list.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+    let report =
+        entry::delete_entries(&op, ws_path, &ids(&["a", "d", "missing", "c", "a"]), false).await?;
+    assert_eq!(report.changed, ids(&["a", "d"]));
+    assert!(report.unchanged.is_empty());
+    let failed: Vec<&str> = report.failed.iter().map(|f| f.entry_id.as_str()).collect();
+    assert_eq!(failed, vec!["missing", "c"]);
+    assert!(report.failed[1].error.contains("legal hold"));
+
+    let again = entry::delete_entries(&op, ws_path, &ids(&["a"]), true).await?;
+    assert_eq!(again.unchanged, ids(&["a"]));
+
+    let live_ids = |rows: Vec<serde_json::Value>| {
+        let mut ids: Vec<String> = rows
+            .iter()
+            .filter_map(|row| row["id"].as_str().map(str::to_string))
+            .collect();
+        ids.sort();
+        ids
+    };
+    assert_eq!(
+        live_ids(entry::list_entries(&op, ws_path).await?),
+        ids(&["b", "c"])
+    );
+
+    let restored = entry::restore_entries(&op, ws_path, &ids(&["a", "b"])).await?;
+    assert_eq!(restored.changed, ids(&["a"]));
+    assert_eq!(restored.unchanged, ids(&["b"]));
+    assert_eq!(
+        live_ids(entry::list_entries(&op, ws_path).await?),
+        ids(&["a", "b", "c"])
+    );
+    let trash = entry::list_entries_with_options(
+        &op,
+        ws_path,
+        &entry::ListEntriesOptions::with_deleted(entry::DeletedFilter::Only),
+    )
+    .await?;
+    assert_eq!(live_ids(trash), ids(&["d"]));
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-038
+async fn test_entry_req_entry_038_diff_entry_revisions() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "diffs", "/tmp").await?;
+    let ws_path = "spaces/diffs";
+    ensure_entry_form(&op, ws_path).await?;
+    let mut lines: Vec<String> = (1..=12).map(|n| format!("line {n}")).collect();
+    entry::create_entry(
+        &op,
+        ws_path,
+        "doc",
+        &format!(
+            "---\nform: Entry\n---\n# Doc\n\n## Body\n{}\n",
+            lines.join("\n")
+        ),
+        "alice",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let first = entry::get_entry_content(&op, ws_path, "doc")
+        .await?
+        .revision_id;
+    lines[5] = "line six".to_string();
+    let updated = entry::update_entry(
+        &op,
+        ws_path,
+        "doc",
+        &format!(
+            "---\nform: Entry\n---\n# Doc\n\n## Body\n{}\n\n## Status\nopen\n",
+            lines.join("\n")
+        ),
+        Some(&first),
+        "alice",
+        None,
+        &FakeIntegrityProvider,
+    )
+    .await?;
+
+    let second = updated["revision_id"].as_str().unwrap_or_default();
+    let diff = entry::diff_entry_revisions(&op, ws_path, "doc", &first, second).await?;
+    let hunks: Vec<_> = diff
+        .hunks
+        .iter()
+        .map(|hunk| {
+            (
+                hunk.kind,
+                hunk.from_line,
+                hunk.to_line,
+                hunk.lines.join("|"),
+            )
+        })
+        .collect();
+    assert_eq!(
+        hunks,
+        vec![
+            (
+                DiffHunkKind::Context,
+                9,
+                9,
+                "line 3|line 4|line 5".to_string()
+            ),
+            (DiffHunkKind::Removed, 12, 12, "line 6".to_string()),
+            (DiffHunkKind::Added, 13, 12, "line six".to_string()),
+            (
+                DiffHunkKind::Context,
+                13,
+                13,
+                "line 7|line 8|line 9|line 10|line 11|line 12".to_string()
+            ),
+            (DiffHunkKind::Added, 19, 19, "|## Status|open".to_string()),
+        ]
+    );
+    let changed: Vec<&str> = diff.properties.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(changed, vec!["Body", "Status"]);
+    assert_eq!(diff.properties[1].before, serde_json::Value::Null);
+    assert_eq!(diff.properties[1].after, "open");
+
+    let same = entry::diff_entry_revisions(&op, ws_path, "doc", second, second).await?;
+    assert!(same.hunks.is_empty() && same.properties.is_empty());
+    assert!(
+        entry::diff_entry_revisions(&op, ws_path, "doc", &first, "missing")
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-039
+async fn test_entry_req_entry_039_conflict_details_and_three_way_merge() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "merges", "/tmp").await?;
+    let ws_path = "spaces/merges";
+    ensure_entry_form(&op, ws_path).await?;
+    let doc = |body: &[&str]| {
+        format!(
+            "---\nform: Entry\n---\n# Doc\n\n## Body\n{}\n",
+            body.join("\n")
+        )
+    };
+    let base = ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot"];
+    entry::create_entry(
+        &op,
+        ws_path,
+        "doc",
+        &doc(&base),
+        "alice",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let parent = entry::get_entry_content(&op, ws_path, "doc")
+        .await?
+        .revision_id;
+    let current = entry::update_entry(
+        &op,
+        ws_path,
+        "doc",
+        &doc(&["ALPHA", "bravo", "charlie", "delta", "echo", "foxtrot"]),
+        Some(&parent),
+        "alice",
+        None,
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let current = current["revision_id"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+
+    let theirs = doc(&["alpha", "bravo", "charlie", "delta", "echo", "FOXTROT"]);
+    let conflict = entry::update_entry(
+        &op,
+        ws_path,
+        "doc",
+        &theirs,
+        Some(&parent),
+        "bob",
+        None,
+        &FakeIntegrityProvider,
+    )
+    .await
+    .expect_err("stale parent")
+    .downcast::<RevisionConflict>()
+    .expect("RevisionConflict error");
+    assert_eq!(conflict.expected_revision_id, parent);
+    assert_eq!(conflict.current_revision_id, current);
+    assert!(conflict
+        .base_content
+        .as_deref()
+        .is_some_and(|c| c.contains("alpha")));
+    assert!(conflict.current_content.contains("ALPHA"));
+    assert!(conflict.attempted_content.contains("FOXTROT"));
+    assert!(!conflict.merge_attempted);
+    assert_eq!(conflict.as_json()["code"], "revision_conflict");
+
+    let merged = entry::update_entry_with_merge(
+        &op,
+        ws_path,
+        "doc",
+        &theirs,
+        Some(&parent),
+        "bob",
+        None,
+        MergeStrategy::ThreeWay,
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let merged_id = merged["revision_id"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let body = entry::get_entry(&op, ws_path, "doc").await?["sections"]["Body"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    assert!(body.contains("ALPHA") && body.contains("FOXTROT"), "{body}");
+
+    let overlapping = entry::update_entry_with_merge(
+        &op,
+        ws_path,
+        "doc",
+        &doc(&["alpha", "bravo", "charlie", "delta", "echo", "golf"]),
+        Some(&parent),
+        "carol",
+        None,
+        MergeStrategy::ThreeWay,
+        &FakeIntegrityProvider,
+    )
+    .await
+    .expect_err("overlapping edits")
+    .downcast::<RevisionConflict>()
+    .expect("RevisionConflict error");
+    assert!(overlapping.merge_attempted);
+    assert_eq!(overlapping.current_revision_id, merged_id);
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-040
+async fn test_entry_req_entry_040_trash_listing_and_purge() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "trash-space", "/tmp").await?;
+    let ws_path = "spaces/trash-space";
+    ensure_entry_form(&op, ws_path).await?;
+    for id in ["keep", "old", "held", "live"] {
+        let content = format!("---\nform: Entry\n---\n# {id}\n\n## Body\n{id} body\n");
+        entry::create_entry(&op, ws_path, id, &content, "alice", &FakeIntegrityProvider).await?;
+    }
+    for id in ["old", "held", "keep"] {
+        entry::delete_entry(&op, ws_path, id, false).await?;
+    }
+
+    let trashed = entry::list_deleted_entries(&op, ws_path).await?;
+    let ids: Vec<&str> = trashed.iter().map(|entry| entry.id.as_str()).collect();
+    assert_eq!(ids, vec!["keep", "held", "old"]);
+    assert_eq!(trashed[0].form, "Entry");
+    assert_eq!(trashed[0].title, "keep");
+
+    let live = entry::purge_entry(&op, ws_path, "live", "admin")
+        .await
+        .expect_err("live entries are not purged");
+    assert!(live.to_string().contains("not in the trash"));
+    entry::purge_entry(&op, ws_path, "keep", "admin").await?;
+    assert!(entry::get_entry(&op, ws_path, "keep").await.is_err());
+    assert!(
+        entry::restore_entry(&op, ws_path, "keep", "any", "admin", &FakeIntegrityProvider)
+            .await
+            .is_err()
+    );
+
+    legal_hold::place_legal_hold(
+        &op,
+        ws_path,
+        HoldTargetType::Entry,
+        "held",
+        "Litigation",
+        "counsel",
+        &[LEGAL_HOLD_SCOPE.to_string()],
+    )
+    .await?;
+    let recent = entry::purge_deleted_older_than(&op, ws_path, 30, "admin").await?;
+    assert!(recent.purged.is_empty() && recent.retained_by_hold.is_empty());
+    let report = entry::purge_deleted_older_than(&op, ws_path, 0, "admin").await?;
+    assert_eq!(report.purged, vec!["old"]);
+    assert_eq!(report.retained_by_hold, vec!["held"]);
+
+    let remaining = entry::list_deleted_entries(&op, ws_path).await?;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].id, "held");
+    let history = entry::get_entry_history(&op, ws_path, "live").await?;
+    assert_eq!(history["revisions"].as_array().map(Vec::len), Some(1));
+    let purges = audit::list_audit_events(
+        &op,
+        "trash-space",
+        audit::AuditListOptions {
+            action: Some("entry.purge".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(purges["items"].as_array().map(Vec::len), Some(2));
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-042
+async fn test_entry_req_entry_042_revision_messages_and_metadata() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "notes-space", "/tmp").await?;
+    let ws_path = "spaces/notes-space";
+    ensure_entry_form(&op, ws_path).await?;
+    let integrity = FakeIntegrityProvider;
+
+    let note = |message: &str, metadata: serde_json::Value| RevisionNote {
+        message: Some(message.to_string()),
+        revision_metadata: Some(metadata),
+    };
+    entry::create_entry_with_note(
+        &op,
+        ws_path,
+        "plan",
+        "---\nform: Entry\n---\n# plan\n\n## Body\ndraft\n",
+        "alice",
+        &note("  First draft  ", serde_json::json!({"source": "web"})),
+        &integrity,
+    )
+    .await?;
+    let first = entry::get_entry(&op, ws_path, "plan").await?["revision_id"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    entry::update_entry_with_note(
+        &op,
+        ws_path,
+        "plan",
+        "---\nform: Entry\n---\n# plan\n\n## Body\nfinal\n",
+        Some(&first),
+        "bob",
+        None,
+        MergeStrategy::Fail,
+        &note("Apply review", serde_json::json!({"ticket": 42})),
+        &integrity,
+    )
+    .await?;
+    entry::update_entry(
+        &op,
+        ws_path,
+        "plan",
+        "---\nform: Entry\n---\n# plan\n\n## Body\ntypo\n",
+        None,
+        "bob",
+        None,
+        &integrity,
+    )
+    .await?;
+    entry::restore_entry_with_note(
+        &op,
+        ws_path,
+        "plan",
+        &first,
+        "alice",
+        &RevisionNote {
+            message: Some("   ".to_string()),
+            revision_metadata: Some(serde_json::json!(["rollback"])),
+        },
+        &integrity,
+    )
+    .await?;
+
+    let history = entry::get_entry_history(&op, ws_path, "plan").await?;
+    let revisions = history["revisions"].as_array().cloned().unwrap_or_default();
+    let messages: Vec<&serde_json::Value> = revisions.iter().map(|rev| &rev["message"]).collect();
+    assert_eq!(
+        messages,
+        vec![
+            &serde_json::json!("First draft"),
+            &serde_json::json!("Apply review"),
+            &serde_json::Value::Null,
+            &serde_json::Value::Null,
+        ]
+    );
+    assert_eq!(revisions[1]["revision_metadata"]["ticket"], 42);
+    assert_eq!(revisions[3]["revision_metadata"][0], "rollback");
+
+    let stored = entry::get_entry_revision(&op, ws_path, "plan", &first).await?;
+    assert_eq!(stored["message"], "First draft");
+    assert_eq!(stored["revision_metadata"]["source"], "web");
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-043
+async fn test_entry_req_entry_043_change_feed_since_cursor() -> anyhow::Result<()> {
+    clock::frozen(43, async {
+        let op = setup_operator()?;
+        space::create_space(&op, "feed-space", "/tmp").await?;
+        let ws_path = "spaces/feed-space";
+        ensure_entry_form(&op, ws_path).await?;
+        let integrity = FakeIntegrityProvider;
+        let body =
+            |id: &str, text: &str| format!("---\nform: Entry\n---\n# {id}\n\n## Body\n{text}\n");
+
+        entry::create_entry(&op, ws_path, "a", &body("a", "one"), "alice", &integrity).await?;
+        entry::create_entry(&op, ws_path, "b", &body("b", "one"), "bob", &integrity).await?;
+        let all =
+            entry::list_changes_since(&op, ws_path, &ChangesSince::Timestamp(0.0), None).await?;
+        let seen = all.last().expect("changes").clone();
+        assert_eq!(
+            all.iter()
+                .map(|change| (change.kind, change.entry_id.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(ChangeKind::Created, "a"), (ChangeKind::Created, "b")]
+        );
+
+        entry::update_entry(
+            &op,
+            ws_path,
+            "a",
+            &body("a", "two"),
+            None,
+            "alice",
+            None,
+            &integrity,
+        )
+        .await?;
+        entry::delete_entry(&op, ws_path, "b", false).await?;
+        entry::create_entry(&op, ws_path, "c", &body("c", "one"), "carol", &integrity).await?;
+
+        let revision_id = seen.revision_id.clone().expect("revision");
+        let by_revision =
+            entry::list_changes_since(&op, ws_path, &ChangesSince::Revision(revision_id), None)
+                .await?;
+        let by_time =
+            entry::list_changes_since(&op, ws_path, &ChangesSince::Timestamp(seen.timestamp), None)
+                .await?;
+        assert_eq!(by_revision, by_time);
+        assert_eq!(
+            by_time
+                .iter()
+                .map(|change| (
+                    change.kind,
+                    change.entry_id.as_str(),
+                    change.author.as_deref()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (ChangeKind::Updated, "a", Some("alice")),
+                (ChangeKind::Deleted, "b", None),
+                (ChangeKind::Created, "c", Some("carol")),
+            ]
+        );
+        assert!(by_time
+            .windows(2)
+            .all(|pair| pair[0].timestamp <= pair[1].timestamp));
+
+        // Restoring from the trash writes no revision but still shows up.
+        let last = by_time.last().expect("changes").clone();
+        entry::restore_entries(&op, ws_path, &["b".to_string()]).await?;
+        let restored = entry::list_changes_since(
+            &op,
+            ws_path,
+            &ChangesSince::Revision(last.revision_id.expect("revision")),
+            None,
+        )
+        .await?;
+        assert_eq!(restored.len(), 1);
+        assert_eq!(
+            (restored[0].kind, restored[0].entry_id.as_str()),
+            (ChangeKind::Updated, "b")
+        );
+
+        let missing = entry::list_changes_since(
+            &op,
+            ws_path,
+            &ChangesSince::Revision("nope".to_string()),
+            None,
+        )
+        .await
+        .expect_err("unknown revision");
+        assert!(missing.to_string().contains("Revision not found"));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+/// REQ-ENTRY-044
+async fn test_entry_req_entry_044_conditional_reads_by_revision() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "head-space", "/tmp").await?;
+    let ws_path = "spaces/head-space";
+    ensure_entry_form(&op, ws_path).await?;
+    let integrity = FakeIntegrityProvider;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "polled",
+        "---\nform: Entry\ntags: [ops]\n---\n# Polled\n\n## Body\nv1\n",
+        "alice",
+        &integrity,
+    )
+    .await?;
+
+    let head = entry::get_entry_head(&op, ws_path, "polled").await?;
+    let full = entry::get_entry(&op, ws_path, "polled").await?;
+    assert_eq!(
+        head.revision_id,
+        full["revision_id"].as_str().unwrap_or_default()
+    );
+    assert_eq!(head.title, "Polled");
+    assert_eq!(head.form, "Entry");
+    assert_eq!(head.tags, vec!["ops".to_string()]);
+    assert_eq!(head.author, "alice");
+
+    let unchanged = entry::get_entry_if_changed(&op, ws_path, "polled", &head.revision_id).await?;
+    assert!(unchanged.is_none());
+
+    entry::update_entry(
+        &op,
+        ws_path,
+        "polled",
+        "---\nform: Entry\ntags: [ops]\n---\n# Polled\n\n## Body\nv2\n",
+        Some(&head.revision_id),
+        "bob",
+        None,
+        &integrity,
+    )
+    .await?;
+    let changed = entry::get_entry_if_changed(&op, ws_path, "polled", &head.revision_id)
+        .await?
+        .expect("entry changed");
+    assert_ne!(changed["revision_id"], head.revision_id.as_str());
+    assert!(changed["content"]
+        .as_str()
+        .unwrap_or_default()
+        .contains("v2"));
+
+    entry::delete_entry(&op, ws_path, "polled", false).await?;
+    assert!(entry::get_entry_head(&op, ws_path, "polled").await.is_err());
+    Ok(())
+}
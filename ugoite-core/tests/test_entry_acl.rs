@@ -0,0 +1,457 @@
+mod common;
+use _ugoite_core::entry::{self, DeletedFilter};
+use _ugoite_core::entry_acl::{
+    self, EntryAcl, EntryPrincipal, EntryViewer, EntryVisibility, PrincipalKind,
+};
+use _ugoite_core::index;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::search;
+use _ugoite_core::space;
+use _ugoite_core::sql_session;
+use _ugoite_core::sync;
+use common::setup_operator;
+
+async fn seed_acl_space(op: &opendal::Operator, space_id: &str) -> anyhow::Result<String> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{space_id}");
+    let integrity = FakeIntegrityProvider;
+    for (entry_id, body) in [
+        ("open", "Quarterly plan shared with everyone."),
+        ("salary", "Quarterly salary review notes."),
+        ("diary", "Quarterly personal diary."),
+    ] {
+        let markdown = format!("---\nform: Entry\n---\n# {entry_id}\n\n## Body\n{body}\n");
+        entry::create_entry(op, &ws_path, entry_id, &markdown, "alice", &integrity).await?;
+    }
+    entry_acl::set_entry_acl(
+        op,
+        &ws_path,
+        "salary",
+        &EntryAcl {
+            owner: "alice".to_string(),
+            shared_with: vec![
+                EntryPrincipal {
+                    kind: PrincipalKind::User,
+                    id: "bob".to_string(),
+                },
+                EntryPrincipal {
+                    kind: PrincipalKind::UserGroup,
+                    id: "hr".to_string(),
+                },
+            ],
+            visibility: EntryVisibility::Restricted,
+        },
+    )
+    .await?;
+    entry_acl::set_entry_acl(
+        op,
+        &ws_path,
+        "diary",
+        &EntryAcl {
+            owner: "alice".to_string(),
+            shared_with: vec![EntryPrincipal {
+                kind: PrincipalKind::User,
+                id: "bob".to_string(),
+            }],
+            visibility: EntryVisibility::Private,
+        },
+    )
+    .await?;
+    Ok(ws_path)
+}
+
+fn sorted_ids(entries: &[serde_json::Value]) -> Vec<String> {
+    let mut ids: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| entry["id"].as_str().map(str::to_string))
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[tokio::test]
+/// REQ-SEC-012
+async fn test_entry_acl_req_sec_012_filters_get_list_and_search() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_acl_space(&op, "acl-filter").await?;
+
+    let alice = EntryViewer::user("alice");
+    let bob = EntryViewer::user("bob");
+    let carol = EntryViewer::user("carol");
+    let hr_member = EntryViewer {
+        user_id: "dave".to_string(),
+        groups: vec!["hr".to_string()],
+        is_space_admin: false,
+    };
+    let admin = EntryViewer {
+        user_id: "root".to_string(),
+        groups: Vec::new(),
+        is_space_admin: true,
+    };
+
+    let ids = |viewer: &EntryViewer| {
+        let op = op.clone();
+        let ws_path = ws_path.clone();
+        let viewer = viewer.clone();
+        async move {
+            let entries = entry::list_entries_for_viewer(&op, &ws_path, &viewer).await?;
+            anyhow::Ok(sorted_ids(&entries))
+        }
+    };
+    assert_eq!(ids(&alice).await?, vec!["diary", "open", "salary"]);
+    assert_eq!(ids(&admin).await?, vec!["diary", "open", "salary"]);
+    assert_eq!(ids(&bob).await?, vec!["open", "salary"]);
+    assert_eq!(ids(&hr_member).await?, vec!["open", "salary"]);
+    assert_eq!(ids(&carol).await?, vec!["open"]);
+
+    let mut found: Vec<String> =
+        search::search_entries_for_viewer(&op, &ws_path, "quarterly", &carol)
+            .await?
+            .into_iter()
+            .map(|result| result.id)
+            .collect();
+    found.sort();
+    assert_eq!(found, vec!["open"]);
+    assert_eq!(
+        search::search_entries_for_viewer(&op, &ws_path, "salary", &bob)
+            .await?
+            .len(),
+        1
+    );
+
+    let fetched = entry::get_entry_for_viewer(&op, &ws_path, "salary", &bob).await?;
+    assert_eq!(fetched["id"], "salary");
+    let err = entry::get_entry_for_viewer(&op, &ws_path, "diary", &bob)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Entry not found: diary"));
+
+    // Heads and conditional reads are gated the same way, even when the
+    // caller already knows the current revision.
+    let head = entry::get_entry_head(&op, &ws_path, "diary").await?;
+    assert!(
+        entry::get_entry_head_for_viewer(&op, &ws_path, "diary", &bob)
+            .await
+            .is_err()
+    );
+    assert!(entry::get_entry_if_changed_for_viewer(
+        &op,
+        &ws_path,
+        "diary",
+        &head.revision_id,
+        &bob
+    )
+    .await
+    .is_err());
+    assert!(entry::get_entry_if_changed_for_viewer(
+        &op,
+        &ws_path,
+        "diary",
+        &head.revision_id,
+        &alice
+    )
+    .await?
+    .is_none());
+
+    // Unfiltered core APIs are unchanged.
+    assert_eq!(entry::list_entries(&op, &ws_path).await?.len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-SEC-012
+async fn test_entry_acl_req_sec_012_filters_pages_and_deleted_search() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_acl_space(&op, "acl-pages").await?;
+    let carol = EntryViewer::user("carol");
+    let options = entry::ListEntriesOptions::default();
+
+    let listed =
+        entry::list_entries_with_options_for_viewer(&op, &ws_path, &options, &carol).await?;
+    assert_eq!(sorted_ids(&listed), vec!["open"]);
+    // Page counts only cover what the viewer may read.
+    let page = entry::list_entries_page_for_viewer(&op, &ws_path, &options, 0, 10, &carol).await?;
+    assert_eq!(sorted_ids(&page.entries), vec!["open"]);
+    assert_eq!(page.total_count, 1);
+    assert!(!page.has_more);
+
+    entry::delete_entry(&op, &ws_path, "diary", false).await?;
+    let found = search::search_entries_with_deleted_for_viewer(
+        &op,
+        &ws_path,
+        "quarterly",
+        DeletedFilter::Only,
+        &carol,
+    )
+    .await?;
+    assert!(found.is_empty());
+    let found = search::search_entries_with_deleted_for_viewer(
+        &op,
+        &ws_path,
+        "quarterly",
+        DeletedFilter::Only,
+        &EntryViewer::user("alice"),
+    )
+    .await?;
+    assert_eq!(found.len(), 1);
+    assert!(found[0].deleted);
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-SEC-012
+async fn test_entry_acl_req_sec_012_set_get_and_clear_acl() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_acl_space(&op, "acl-manage").await?;
+
+    let stored = entry_acl::get_entry_acl(&op, &ws_path, "salary")
+        .await?
+        .expect("acl stored");
+    assert_eq!(stored.owner, "alice");
+    assert_eq!(stored.visibility, EntryVisibility::Restricted);
+    assert_eq!(stored.shared_with.len(), 2);
+    assert!(entry_acl::get_entry_acl(&op, &ws_path, "open")
+        .await?
+        .is_none());
+    assert_eq!(entry_acl::list_entry_acls(&op, &ws_path).await?.len(), 2);
+
+    let duplicate = EntryPrincipal {
+        kind: PrincipalKind::User,
+        id: "erin".to_string(),
+    };
+    let updated = entry_acl::set_entry_acl(
+        &op,
+        &ws_path,
+        "open",
+        &EntryAcl {
+            owner: " alice ".to_string(),
+            shared_with: vec![duplicate.clone(), duplicate],
+            visibility: EntryVisibility::Space,
+        },
+    )
+    .await?;
+    assert_eq!(updated.owner, "alice");
+    assert_eq!(updated.shared_with.len(), 1);
+    assert!(entry_acl::can_read(
+        Some(&updated),
+        &EntryViewer::user("anyone")
+    ));
+
+    let err = entry_acl::set_entry_acl(
+        &op,
+        &ws_path,
+        "missing",
+        &EntryAcl {
+            owner: "alice".to_string(),
+            shared_with: Vec::new(),
+            visibility: EntryVisibility::Private,
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Entry not found"));
+
+    let err = entry_acl::set_entry_acl(
+        &op,
+        &ws_path,
+        "open",
+        &EntryAcl {
+            owner: "  ".to_string(),
+            shared_with: Vec::new(),
+            visibility: EntryVisibility::Private,
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("owner must not be empty"));
+
+    entry_acl::clear_entry_acl(&op, &ws_path, "diary").await?;
+    let carol = EntryViewer::user("carol");
+    let entries = entry::list_entries_for_viewer(&op, &ws_path, &carol).await?;
+    assert_eq!(sorted_ids(&entries), vec!["diary", "open"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-SEC-022
+async fn test_entry_acl_req_sec_022_sql_rows_filtered_by_acl() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_acl_space(&op, "acl-sql").await?;
+
+    let bob = EntryViewer::user("bob");
+    let carol = EntryViewer::user("carol");
+    let hr_member = EntryViewer {
+        user_id: "dave".to_string(),
+        groups: vec!["hr".to_string()],
+        is_space_admin: false,
+    };
+    let admin = EntryViewer {
+        user_id: "root".to_string(),
+        groups: Vec::new(),
+        is_space_admin: true,
+    };
+
+    let sql = r#"{"$sql": "SELECT * FROM entries ORDER BY id"}"#;
+    let query = |query: &'static str, viewer: &EntryViewer| {
+        let op = op.clone();
+        let ws_path = ws_path.clone();
+        let viewer = viewer.clone();
+        async move {
+            let rows = index::query_index_for_viewer(
+                &op,
+                &ws_path,
+                query,
+                DeletedFilter::Exclude,
+                &viewer,
+            )
+            .await?;
+            anyhow::Ok(rows)
+        }
+    };
+    assert_eq!(sorted_ids(&query(sql, &carol).await?), vec!["open"]);
+    assert_eq!(sorted_ids(&query(sql, &bob).await?), vec!["open", "salary"]);
+    assert_eq!(
+        sorted_ids(&query(sql, &hr_member).await?),
+        vec!["open", "salary"]
+    );
+    assert_eq!(
+        sorted_ids(&query(sql, &admin).await?),
+        vec!["diary", "open", "salary"]
+    );
+    assert_eq!(sorted_ids(&query("", &carol).await?), vec!["open"]);
+
+    let all = index::query_index(&op, &ws_path, sql).await?;
+    assert_eq!(all[0]["acl"], serde_json::json!(["user:alice"]));
+    assert_eq!(all[1]["acl"], serde_json::json!(["*"]));
+    assert_eq!(
+        all[2]["acl"],
+        serde_json::json!(["user:alice", "user:bob", "group:hr"])
+    );
+
+    // Joined tables are filtered before the join, so a restricted row can
+    // neither match nor be inferred from an outer join.
+    let joined = query(
+        r#"{"$sql": "SELECT * FROM entries e LEFT JOIN entries s ON s.id = 'salary'"}"#,
+        &carol,
+    )
+    .await?;
+    assert_eq!(joined.len(), 1);
+    assert_eq!(joined[0]["e"]["id"], "open");
+    assert!(joined[0]["s"].is_null());
+
+    let session = sql_session::create_sql_session(&op, &ws_path, "SELECT * FROM entries").await?;
+    let session_id = session["id"].as_str().unwrap();
+    let forms = vec!["Entry".to_string()];
+    for (viewer, expected) in [(None, 3), (Some(&carol), 1), (Some(&bob), 2)] {
+        let count = sql_session::get_sql_session_count_scoped(
+            &op, &ws_path, session_id, &forms, true, viewer,
+        )
+        .await?;
+        assert_eq!(count, expected);
+    }
+    let rows = sql_session::get_sql_session_rows_all_scoped(
+        &op,
+        &ws_path,
+        session_id,
+        &forms,
+        true,
+        Some(&carol),
+    )
+    .await?;
+    assert_eq!(sorted_ids(&rows), vec!["open"]);
+
+    // Paged queries apply the same row filter on both the filter and SQL paths.
+    let page = index::query_index_page_for_viewer(&op, &ws_path, "{}", None, 10, &carol).await?;
+    assert_eq!(sorted_ids(&page.rows), vec!["open"]);
+    assert!(!page.has_more);
+    let page = index::query_index_page_for_viewer(&op, &ws_path, sql, None, 10, &bob).await?;
+    assert_eq!(sorted_ids(&page.rows), vec!["open", "salary"]);
+    assert_eq!(
+        index::query_index_page(&op, &ws_path, sql, None, 10)
+            .await?
+            .rows
+            .len(),
+        3
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-SEC-012
+async fn test_entry_acl_req_sec_012_sync_pull_skips_unreadable_entries() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_acl_space(&op, "acl-sync").await?;
+
+    let pulled_ids = |pulled: &sync::SyncPullResult| {
+        let mut ids: Vec<String> = pulled
+            .changes
+            .iter()
+            .map(|change| change.entry_id.clone())
+            .collect();
+        ids.sort();
+        ids
+    };
+    let carol = EntryViewer::user("carol");
+    let pulled = sync::sync_pull(&op, &ws_path, None, 10, Some(&carol)).await?;
+    assert_eq!(pulled_ids(&pulled), vec!["open"]);
+    assert!(pulled.changes.iter().all(|change| !change
+        .markdown
+        .as_deref()
+        .unwrap_or_default()
+        .contains("salary")));
+
+    let bob = EntryViewer::user("bob");
+    let pulled = sync::sync_pull(&op, &ws_path, None, 10, Some(&bob)).await?;
+    assert_eq!(pulled_ids(&pulled), vec!["diary", "open", "salary"]);
+    let pulled = sync::sync_pull(&op, &ws_path, None, 10, None).await?;
+    assert_eq!(pulled_ids(&pulled), vec!["diary", "open", "salary"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-SEC-012
+async fn test_entry_acl_req_sec_012_change_feed_skips_unreadable_entries() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_acl_space(&op, "acl-changes").await?;
+
+    let changed_ids = |changes: &[entry::EntryChange]| {
+        let mut ids: Vec<String> = changes
+            .iter()
+            .map(|change| change.entry_id.clone())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    };
+    let since = entry::ChangesSince::Timestamp(0.0);
+    let carol = EntryViewer::user("carol");
+    let changes = entry::list_changes_since(&op, &ws_path, &since, Some(&carol)).await?;
+    assert_eq!(changed_ids(&changes), vec!["open"]);
+    let bob = EntryViewer::user("bob");
+    let changes = entry::list_changes_since(&op, &ws_path, &since, Some(&bob)).await?;
+    assert_eq!(changed_ids(&changes), vec!["diary", "open", "salary"]);
+
+    // A hidden entry's revision is not a usable cursor either.
+    let all = entry::list_changes_since(&op, &ws_path, &since, None).await?;
+    let salary_revision = all
+        .iter()
+        .find(|change| change.entry_id == "salary")
+        .and_then(|change| change.revision_id.clone())
+        .expect("salary revision");
+    let err = entry::list_changes_since(
+        &op,
+        &ws_path,
+        &entry::ChangesSince::Revision(salary_revision),
+        Some(&carol),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Revision not found"));
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+mod common;
+use _ugoite_core::audit::{self, AuditListOptions};
+use _ugoite_core::entry;
+use _ugoite_core::entry_lock::{self, LOCK_ADMIN_SCOPE, MAX_LOCK_TTL_SECS};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use common::setup_operator;
+
+async fn seed_locked_space(op: &opendal::Operator, space_id: &str) -> anyhow::Result<String> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{space_id}");
+    let markdown = "---\nform: Entry\n---\n# Plan\n\n## Body\nDraft.\n";
+    entry::create_entry(
+        op,
+        &ws_path,
+        "plan",
+        markdown,
+        "alice",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    Ok(ws_path)
+}
+
+async fn edit_as(
+    op: &opendal::Operator,
+    ws_path: &str,
+    author: &str,
+    body: &str,
+) -> anyhow::Result<serde_json::Value> {
+    let current = entry::get_entry_content(op, ws_path, "plan").await?;
+    let markdown = format!("---\nform: Entry\n---\n# Plan\n\n## Body\n{body}\n");
+    entry::update_entry(
+        op,
+        ws_path,
+        "plan",
+        &markdown,
+        Some(&current.revision_id),
+        author,
+        None,
+        &FakeIntegrityProvider,
+    )
+    .await
+}
+
+#[tokio::test]
+/// REQ-ENTRY-015
+async fn test_entry_lock_req_entry_015_lock_blocks_other_writers() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_locked_space(&op, "lock-writers").await?;
+
+    let lock = entry_lock::acquire_entry_lock(&op, &ws_path, "plan", "alice", 60).await?;
+    assert_eq!(lock.holder, "alice");
+    let shown = entry::get_entry(&op, &ws_path, "plan").await?;
+    assert_eq!(shown["lock"]["holder"], "alice");
+    assert_eq!(shown["lock"]["lock_id"], lock.lock_id.as_str());
+
+    let err = entry_lock::acquire_entry_lock(&op, &ws_path, "plan", "bob", 60)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("locked by alice"));
+    let err = edit_as(&op, &ws_path, "bob", "Overwrite")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("locked by alice"));
+    edit_as(&op, &ws_path, "alice", "Edited by holder").await?;
+
+    // Re-acquiring renews the same lock; heartbeats extend it.
+    let renewed = entry_lock::acquire_entry_lock(&op, &ws_path, "plan", "alice", 120).await?;
+    assert_eq!(renewed.lock_id, lock.lock_id);
+    assert!(renewed.expires_at > lock.expires_at);
+    let beat = entry_lock::heartbeat_entry_lock(&op, &ws_path, "plan", &lock.lock_id, 300).await?;
+    assert!(beat.expires_at > renewed.expires_at);
+    assert!(
+        entry_lock::heartbeat_entry_lock(&op, &ws_path, "plan", "stale-token", 60)
+            .await
+            .is_err()
+    );
+    assert!(
+        entry_lock::acquire_entry_lock(&op, &ws_path, "plan", "alice", MAX_LOCK_TTL_SECS + 1)
+            .await
+            .is_err()
+    );
+
+    entry_lock::release_entry_lock(&op, &ws_path, "plan", &lock.lock_id).await?;
+    assert!(entry::get_entry(&op, &ws_path, "plan").await?["lock"].is_null());
+    edit_as(&op, &ws_path, "bob", "Bob's turn").await?;
+    assert!(
+        entry_lock::acquire_entry_lock(&op, &ws_path, "missing", "bob", 60)
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-015
+async fn test_entry_lock_req_entry_015_admin_steal_and_force_release() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_locked_space(&op, "lock-admin").await?;
+    let admin = [LOCK_ADMIN_SCOPE.to_string()];
+
+    let held = entry_lock::acquire_entry_lock(&op, &ws_path, "plan", "alice", 60).await?;
+    let err = entry_lock::steal_entry_lock(&op, &ws_path, "plan", "bob", 60, &[])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(LOCK_ADMIN_SCOPE));
+
+    let stolen = entry_lock::steal_entry_lock(&op, &ws_path, "plan", "carol", 60, &admin).await?;
+    assert_eq!(stolen.holder, "carol");
+    assert_ne!(stolen.lock_id, held.lock_id);
+    assert!(
+        entry_lock::heartbeat_entry_lock(&op, &ws_path, "plan", &held.lock_id, 60)
+            .await
+            .is_err()
+    );
+    assert!(edit_as(&op, &ws_path, "alice", "Too late").await.is_err());
+
+    let released =
+        entry_lock::force_release_entry_lock(&op, &ws_path, "plan", "carol", &admin).await?;
+    assert_eq!(released.unwrap().holder, "carol");
+    assert!(entry_lock::get_entry_lock(&op, &ws_path, "plan")
+        .await?
+        .is_none());
+
+    let events = audit::list_audit_events(&op, "lock-admin", AuditListOptions::default()).await?;
+    let mut actions: Vec<&str> = events["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| event["action"].as_str().unwrap())
+        .collect();
+    actions.sort();
+    assert_eq!(
+        actions,
+        vec!["entry_lock.force_release", "entry_lock.steal"]
+    );
+    assert_eq!(events["items"][1]["metadata"]["previous_holder"], "alice");
+
+    Ok(())
+}
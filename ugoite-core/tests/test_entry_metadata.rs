@@ -0,0 +1,126 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::entry_metadata;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::json;
+
+#[tokio::test]
+/// REQ-ENTRY-022
+async fn test_entry_metadata_req_entry_022_namespaced_metadata_roundtrip() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "meta-space", "/tmp").await?;
+    let ws_path = "spaces/meta-space";
+    let content = "---\nform: Entry\n---\n# Synced\n\n## Body\nText\n";
+    entry::create_entry(
+        &op,
+        ws_path,
+        "synced",
+        content,
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+
+    let values = json!({"external_id": "abc-1", "etag": "v1"});
+    entry_metadata::set_entry_metadata(
+        &op,
+        ws_path,
+        "synced",
+        "x-sync",
+        values.as_object().unwrap(),
+        false,
+    )
+    .await?;
+    let merged = entry_metadata::set_entry_metadata(
+        &op,
+        ws_path,
+        "synced",
+        "x-sync",
+        json!({"etag": "v2", "external_id": null, "cursor": 7})
+            .as_object()
+            .unwrap(),
+        true,
+    )
+    .await?;
+    assert_eq!(
+        serde_json::Value::Object(merged),
+        json!({"etag": "v2", "cursor": 7})
+    );
+    entry_metadata::set_entry_metadata(
+        &op,
+        ws_path,
+        "synced",
+        "x-importer",
+        json!({"source": "notion"}).as_object().unwrap(),
+        false,
+    )
+    .await?;
+
+    let all = entry_metadata::get_entry_metadata(&op, ws_path, "synced").await?;
+    assert_eq!(all.keys().collect::<Vec<_>>(), vec!["x-importer", "x-sync"]);
+    assert_eq!(
+        entry_metadata::find_entries_by_metadata(
+            &op,
+            ws_path,
+            "x-importer",
+            "source",
+            &json!("notion")
+        )
+        .await?,
+        vec!["synced".to_string()]
+    );
+
+    // Integration metadata stays out of the entry markdown and its revisions.
+    let stored = entry::get_entry(&op, ws_path, "synced").await?;
+    assert!(!stored["content"]
+        .as_str()
+        .unwrap_or_default()
+        .contains("x-sync"));
+    assert_eq!(
+        entry::get_entry_history(&op, ws_path, "synced").await?["revisions"]
+            .as_array()
+            .map(Vec::len),
+        Some(1)
+    );
+
+    entry_metadata::clear_entry_metadata(&op, ws_path, "synced", Some("x-sync")).await?;
+    assert!(
+        entry_metadata::get_entry_metadata_namespace(&op, ws_path, "synced", "x-sync")
+            .await?
+            .is_empty()
+    );
+    entry_metadata::clear_entry_metadata(&op, ws_path, "synced", None).await?;
+    assert!(entry_metadata::get_entry_metadata(&op, ws_path, "synced")
+        .await?
+        .is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-022
+async fn test_entry_metadata_req_entry_022_rejects_invalid_namespaces() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "meta-invalid", "/tmp").await?;
+    let ws_path = "spaces/meta-invalid";
+    let values = json!({"k": "v"});
+    for namespace in ["sync", "x-", "x-Sync", "x-a.b"] {
+        assert!(
+            entry_metadata::validate_namespace(namespace).is_err(),
+            "{namespace}"
+        );
+    }
+    let missing = entry_metadata::set_entry_metadata(
+        &op,
+        ws_path,
+        "missing",
+        "x-sync",
+        values.as_object().unwrap(),
+        false,
+    )
+    .await;
+    assert!(missing.unwrap_err().to_string().contains("Entry not found"));
+    Ok(())
+}
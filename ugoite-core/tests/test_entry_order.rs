@@ -0,0 +1,96 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::entry_order::{self, ORDER_KEY_STEP};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::Value;
+
+async fn listed_ids(op: &opendal::Operator, ws_path: &str) -> anyhow::Result<Vec<String>> {
+    Ok(entry_order::list_entries_ordered(op, ws_path, "Entry")
+        .await?
+        .iter()
+        .filter_map(|entry| entry["id"].as_str().map(str::to_string))
+        .collect())
+}
+
+async fn setup_space(op: &opendal::Operator, space_id: &str) -> anyhow::Result<String> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{space_id}");
+    for (id, title) in [
+        ("a", "Alpha"),
+        ("b", "Bravo"),
+        ("c", "Charlie"),
+        ("d", "Delta"),
+    ] {
+        entry::create_entry(
+            op,
+            &ws_path,
+            id,
+            &format!("---\nform: Entry\n---\n# {title}\n\n## Body\nText\n"),
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    Ok(ws_path)
+}
+
+#[tokio::test]
+/// REQ-ENTRY-023
+async fn test_entry_order_req_entry_023_pin_and_manual_order() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = setup_space(&op, "order-space").await?;
+    assert_eq!(listed_ids(&op, &ws_path).await?, vec!["a", "b", "c", "d"]);
+
+    entry_order::reorder_entries(&op, &ws_path, "Entry", &["c".to_string(), "a".to_string()])
+        .await?;
+    assert_eq!(listed_ids(&op, &ws_path).await?, vec!["c", "a", "b", "d"]);
+
+    entry_order::pin_entry(&op, &ws_path, "d", true).await?;
+    assert_eq!(listed_ids(&op, &ws_path).await?, vec!["d", "c", "a", "b"]);
+    let listed = entry_order::list_entries_ordered(&op, &ws_path, "Entry").await?;
+    assert_eq!(listed[0]["pinned"], Value::Bool(true));
+    assert_eq!(listed[1]["order_key"], Value::from(ORDER_KEY_STEP));
+
+    entry_order::move_entry(&op, &ws_path, "b", Some("c")).await?;
+    assert_eq!(listed_ids(&op, &ws_path).await?, vec!["d", "c", "b", "a"]);
+
+    entry_order::pin_entry(&op, &ws_path, "d", false).await?;
+    entry_order::move_entry(&op, &ws_path, "d", None).await?;
+    assert_eq!(listed_ids(&op, &ws_path).await?, vec!["d", "c", "b", "a"]);
+
+    let unknown =
+        entry_order::reorder_entries(&op, &ws_path, "Entry", &["missing".to_string()]).await;
+    assert!(unknown.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-023
+async fn test_entry_order_req_entry_023_moves_rebalance_exhausted_gaps() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = setup_space(&op, "order-rebalance").await?;
+    entry_order::reorder_entries(&op, &ws_path, "Entry", &[]).await?;
+
+    // Repeatedly inserting right after "a" halves the gap until keys run out.
+    let mut expected: Vec<String> = vec!["a".into(), "b".into(), "c".into(), "d".into()];
+    for round in 0..24 {
+        let moved = if round % 2 == 0 { "d" } else { "c" };
+        entry_order::move_entry(&op, &ws_path, moved, Some("a")).await?;
+        expected.retain(|id| id != moved);
+        expected.insert(1, moved.to_string());
+        assert_eq!(listed_ids(&op, &ws_path).await?, expected, "round {round}");
+    }
+    let order = entry_order::get_form_order(&op, &ws_path, "Entry").await?;
+    let mut keys: Vec<i64> = order
+        .entries
+        .values()
+        .filter_map(|placement| placement.order_key)
+        .collect();
+    keys.sort();
+    keys.dedup();
+    assert_eq!(keys.len(), 4);
+    Ok(())
+}
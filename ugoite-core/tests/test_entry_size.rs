@@ -0,0 +1,110 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::entry_size::EntryTooLarge;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::json;
+
+fn entry_markdown(title: &str, body: &str) -> String {
+    format!("---\nform: Entry\n---\n# {title}\n\n## Body\n{body}\n")
+}
+
+#[tokio::test]
+/// REQ-ENTRY-031
+async fn test_entry_size_req_entry_031_rejects_oversized_entries() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "size-space", "/tmp").await?;
+    let ws_path = "spaces/size-space";
+    space::patch_space(
+        &op,
+        "size-space",
+        &json!({"settings": {"entry_size": {"max_bytes": 200}}}),
+    )
+    .await?;
+
+    entry::create_entry(
+        &op,
+        ws_path,
+        "small",
+        &entry_markdown("Small", "fits"),
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let big = entry_markdown("Big", &"x".repeat(300));
+    let err = entry::create_entry(&op, ws_path, "big", &big, "author", &FakeIntegrityProvider)
+        .await
+        .expect_err("over the limit");
+    let too_large = err.downcast_ref::<EntryTooLarge>().expect("typed error");
+    assert_eq!(too_large.limit, 200);
+    assert_eq!(too_large.size, big.len());
+    assert_eq!(too_large.as_json()["code"], "too_large");
+    assert!(entry::get_entry(&op, ws_path, "big").await.is_err());
+
+    let head = entry::get_entry_content(&op, ws_path, "small").await?;
+    let err = entry::update_entry(
+        &op,
+        ws_path,
+        "small",
+        &entry_markdown("Small", &"y".repeat(300)),
+        Some(&head.revision_id),
+        "author",
+        None,
+        &FakeIntegrityProvider,
+    )
+    .await
+    .expect_err("update over the limit");
+    assert!(err.to_string().starts_with("Entry too large"));
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-031
+async fn test_entry_size_req_entry_031_spills_oversized_body_to_asset() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "spill-space", "/tmp").await?;
+    let ws_path = "spaces/spill-space";
+    space::patch_space(
+        &op,
+        "spill-space",
+        &json!({"settings": {"entry_size": {"max_bytes": 300, "overflow": "spillover"}}}),
+    )
+    .await?;
+
+    let body = (0..40)
+        .map(|line| format!("line {line:02} of a giant paste"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let big = entry_markdown("Paste", &body);
+    let meta = entry::create_entry(
+        &op,
+        ws_path,
+        "paste",
+        &big,
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert_eq!(meta.title, "Paste");
+
+    let stored = entry::get_entry(&op, ws_path, "paste").await?;
+    let assets = stored["assets"].as_array().expect("assets");
+    assert_eq!(assets.len(), 1);
+    let asset_id = assets[0]["id"].as_str().expect("asset id");
+    let path = assets[0]["path"].as_str().expect("asset path");
+    assert_eq!(
+        op.read(&format!("{ws_path}/{path}")).await?.to_vec(),
+        big.as_bytes()
+    );
+
+    let content = entry::get_entry_content(&op, ws_path, "paste").await?;
+    assert!(content.markdown.len() <= 300, "{}", content.markdown);
+    assert!(content.markdown.contains("line 00 of a giant paste"));
+    assert!(!content.markdown.contains("line 39"));
+    assert!(content
+        .markdown
+        .contains(&format!("ugoite://asset/{asset_id}")));
+    Ok(())
+}
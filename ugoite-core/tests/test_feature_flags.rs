@@ -0,0 +1,112 @@
+mod common;
+use _ugoite_core::feature_flags::{self, FlagSource, CRDT_COLLAB, SEMANTIC_RELATED};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::{collab, entry, related, space};
+use common::setup_operator;
+use std::collections::HashMap;
+use yrs::{Doc, ReadTxn, StateVector, Text, Transact};
+
+#[tokio::test]
+/// REQ-STO-024
+async fn test_feature_flags_req_sto_024_resolves_overrides() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "flags-a", "/tmp").await?;
+    space::create_space(&op, "flags-b", "/tmp").await?;
+
+    // Built-in values apply before anything is set; unknown flags are off.
+    assert!(feature_flags::is_enabled(&op, "flags-a", CRDT_COLLAB).await?);
+    assert!(!feature_flags::is_enabled(&op, "flags-a", "vector_search").await?);
+
+    feature_flags::set_global_flag(&op, "vector_search", Some(true)).await?;
+    feature_flags::set_global_flag(&op, CRDT_COLLAB, Some(false)).await?;
+    feature_flags::set_space_flag(&op, "spaces/flags-b", CRDT_COLLAB, Some(true)).await?;
+    feature_flags::set_space_flag(&op, "spaces/flags-b", "vector_search", Some(false)).await?;
+
+    assert!(feature_flags::is_enabled(&op, "flags-a", "vector_search").await?);
+    assert!(!feature_flags::is_enabled(&op, "flags-a", CRDT_COLLAB).await?);
+    assert!(feature_flags::is_enabled(&op, "flags-b", CRDT_COLLAB).await?);
+    assert!(!feature_flags::is_enabled(&op, "flags-b", "vector_search").await?);
+
+    let resolved = feature_flags::resolve_flags(&op, "spaces/flags-b").await?;
+    let sources: Vec<(&str, bool, FlagSource)> = resolved
+        .iter()
+        .map(|flag| (flag.name.as_str(), flag.enabled, flag.source))
+        .collect();
+    assert_eq!(
+        sources,
+        vec![
+            (CRDT_COLLAB, true, FlagSource::Space),
+            (SEMANTIC_RELATED, true, FlagSource::Builtin),
+            ("vector_search", false, FlagSource::Space),
+        ]
+    );
+
+    let meta = space::get_space_raw(&op, "flags-a").await?;
+    assert_eq!(meta["feature_flags"][CRDT_COLLAB], false);
+    assert_eq!(meta["feature_flags"]["vector_search"], true);
+
+    // Clearing an override falls back to the global default.
+    feature_flags::set_space_flag(&op, "spaces/flags-b", CRDT_COLLAB, None).await?;
+    assert!(!feature_flags::is_enabled(&op, "flags-b", CRDT_COLLAB).await?);
+
+    assert!(feature_flags::set_global_flag(&op, "Bad-Flag", Some(true))
+        .await
+        .is_err());
+    assert!(
+        feature_flags::set_space_flag(&op, "spaces/missing", CRDT_COLLAB, Some(true))
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-024
+async fn test_feature_flags_req_sto_024_gates_subsystems() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "gated", "/tmp").await?;
+    let ws_path = "spaces/gated";
+    for (id, body) in [("solar", "Solar rollout"), ("lunch", "Team lunch menu")] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: Entry\n---\n# {id}\n\n## Body\n{body}\n"),
+            "alice",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+
+    feature_flags::set_space_flag(&op, ws_path, CRDT_COLLAB, Some(false)).await?;
+    let doc = Doc::with_client_id(1);
+    let body = doc.get_or_insert_text("body");
+    body.insert(&mut doc.transact_mut(), 0, "Hello");
+    let update = doc.transact().encode_diff_v1(&StateVector::default());
+    let err = collab::apply_update(&op, ws_path, "solar", &update)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(CRDT_COLLAB), "{err}");
+    feature_flags::set_space_flag(&op, ws_path, CRDT_COLLAB, None).await?;
+    assert_eq!(
+        collab::apply_update(&op, ws_path, "solar", &update).await?,
+        1
+    );
+
+    let embeddings = HashMap::from([
+        ("solar".to_string(), vec![1.0, 0.0]),
+        ("lunch".to_string(), vec![0.9, 0.1]),
+    ]);
+    let with_vectors =
+        related::get_related_entries_with_embeddings(&op, ws_path, "solar", 10, &embeddings)
+            .await?;
+    assert!(with_vectors.iter().any(|r| r.id == "lunch"));
+    feature_flags::set_global_flag(&op, SEMANTIC_RELATED, Some(false)).await?;
+    let without =
+        related::get_related_entries_with_embeddings(&op, ws_path, "solar", 10, &embeddings)
+            .await?;
+    assert!(without
+        .iter()
+        .all(|r| r.reasons.embedding_similarity.is_none()));
+    Ok(())
+}
@@ -0,0 +1,102 @@
+mod common;
+use _ugoite_core::entry;
+use _ugoite_core::feed::{self, FeedFormat, FeedOptions};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use common::setup_operator;
+
+async fn seed_feed_space(op: &opendal::Operator, space_id: &str) -> anyhow::Result<()> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{space_id}");
+    let ws_path = ws_path.as_str();
+    let integrity = FakeIntegrityProvider;
+    entry::create_entry(
+        op,
+        ws_path,
+        "first",
+        "---\nform: Entry\ntags:\n  - release\n---\n# First & Foremost\n\n## Body\nInitial <draft> notes.\n",
+        "alice",
+        &integrity,
+    )
+    .await?;
+    entry::create_entry(
+        op,
+        ws_path,
+        "second",
+        "---\nform: Entry\n---\n# Second\n\n## Body\nFollow-up.\n",
+        "bob",
+        &integrity,
+    )
+    .await?;
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-011
+async fn test_feed_req_entry_011_atom_feed_lists_recent_entries() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    seed_feed_space(&op, "feed-atom").await?;
+
+    let options = FeedOptions {
+        base_url: Some("https://notes.example.com/".to_string()),
+        ..FeedOptions::default()
+    };
+    let xml = feed::generate_feed(&op, "spaces/feed-atom", &options).await?;
+    assert!(xml.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+    assert!(xml.contains("<title>First &amp; Foremost</title>"));
+    assert!(xml.contains("Initial &lt;draft&gt; notes."));
+    assert!(xml.contains("<link href=\"https://notes.example.com/entries/first\"/>"));
+    assert!(xml.contains("<category term=\"release\"/>"));
+    assert!(xml.contains("<author><name>alice</name></author>"));
+    let second_pos = xml.find("<title>Second</title>").unwrap();
+    let first_pos = xml.find("<title>First &amp; Foremost</title>").unwrap();
+    assert!(second_pos < first_pos, "newest entry must come first");
+
+    let again = feed::generate_feed(&op, "spaces/feed-atom", &options).await?;
+    assert_eq!(xml, again, "GUIDs and content must be stable");
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-011
+async fn test_feed_req_entry_011_rss_feed_filters_and_updates_guid() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    seed_feed_space(&op, "feed-rss").await?;
+    let ws_path = "spaces/feed-rss";
+
+    let options = FeedOptions {
+        format: FeedFormat::Rss,
+        tag: Some("release".to_string()),
+        ..FeedOptions::default()
+    };
+    let xml = feed::generate_feed(&op, ws_path, &options).await?;
+    assert!(xml.contains("<rss version=\"2.0\">"));
+    assert!(xml.contains("<link>ugoite://entry/first</link>"));
+    assert!(!xml.contains("<title>Second</title>"));
+    let guid_re = regex::Regex::new(r#"<guid isPermaLink="false">([^<]+)</guid>"#)?;
+    let before = guid_re.captures(&xml).unwrap()[1].to_string();
+    assert!(before.starts_with("urn:ugoite:feed-rss:entry:first:"));
+
+    entry::update_entry(
+        &op,
+        ws_path,
+        "first",
+        "---\nform: Entry\ntags:\n  - release\n---\n# First & Foremost\n\n## Body\nRevised.\n",
+        None,
+        "alice",
+        None,
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let xml = feed::generate_feed(&op, ws_path, &options).await?;
+    let after = guid_re.captures(&xml).unwrap()[1].to_string();
+    assert_ne!(before, after, "a new revision must produce a new GUID");
+
+    let by_form = FeedOptions {
+        form: Some("Missing".to_string()),
+        ..FeedOptions::default()
+    };
+    let empty = feed::generate_feed(&op, ws_path, &by_form).await?;
+    assert!(!empty.contains("<entry>"));
+    Ok(())
+}
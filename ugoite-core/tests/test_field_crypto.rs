@@ -0,0 +1,304 @@
+mod common;
+use _ugoite_core::entry;
+use _ugoite_core::feed;
+use _ugoite_core::field_crypto::{self, SECRET_PLACEHOLDER, SECRET_READ_SCOPE};
+use _ugoite_core::form;
+use _ugoite_core::index;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::search;
+use _ugoite_core::space;
+use _ugoite_core::tabular::TabularExportFormat;
+use common::setup_operator;
+
+fn credential_form(secret: bool) -> serde_json::Value {
+    serde_json::json!({
+        "name": "Credential",
+        "fields": {
+            "Service": {"type": "string"},
+            "ApiKey": {"type": "string", "secret": secret},
+        },
+    })
+}
+
+#[tokio::test]
+/// REQ-FORM-012
+async fn test_field_crypto_req_form_012_secret_fields_encrypted_at_rest() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "secret-fields", "/tmp").await?;
+    let ws_path = "spaces/secret-fields";
+    let integrity = FakeIntegrityProvider;
+    form::upsert_form(&op, ws_path, &credential_form(true)).await?;
+
+    let markdown = "---\nform: Credential\n---\n# Billing\n\n## Service\nbilling\n\n## ApiKey\nsk-live-123456\n";
+    entry::create_entry(&op, ws_path, "billing", markdown, "alice", &integrity).await?;
+    assert!(op.exists(&format!("{ws_path}/data_key.json")).await?);
+
+    let stored = entry::get_entry(&op, ws_path, "billing").await?;
+    assert_eq!(stored["sections"]["ApiKey"], SECRET_PLACEHOLDER);
+    assert_eq!(stored["sections"]["Service"], "billing");
+    assert!(!stored["content"].as_str().unwrap().contains("sk-live"));
+    let listed = entry::list_entries(&op, ws_path).await?;
+    assert_eq!(listed[0]["properties"]["ApiKey"], SECRET_PLACEHOLDER);
+
+    let history = entry::get_entry_history(&op, ws_path, "billing").await?;
+    let revision_id = history["revisions"][0]["revision_id"].as_str().unwrap();
+    let revision = entry::get_entry_revision(&op, ws_path, "billing", revision_id).await?;
+    assert_eq!(revision["fields"]["ApiKey"], SECRET_PLACEHOLDER);
+
+    // Nothing written to storage holds the plaintext.
+    let mut plaintext_files = Vec::new();
+    for item in op.list_with(ws_path).recursive(true).await? {
+        if item.metadata().is_file() {
+            let bytes = op.read(item.path()).await?.to_vec();
+            if String::from_utf8_lossy(&bytes).contains("sk-live-123456") {
+                plaintext_files.push(item.path().to_string());
+            }
+        }
+    }
+    assert!(plaintext_files.is_empty(), "{plaintext_files:?}");
+    assert!(search::search_entries(&op, ws_path, "sk-live")
+        .await?
+        .is_empty());
+
+    // The index sees only a keyed hash usable for presence and equality checks.
+    let hash = field_crypto::secret_index_hash(&op, ws_path, "sk-live-123456").await?;
+    let indexed = index::query_index(&op, ws_path, "").await?;
+    assert_eq!(indexed[0]["properties"]["ApiKey"], hash);
+    let query = serde_json::json!({"ApiKey": hash}).to_string();
+    assert_eq!(index::query_index(&op, ws_path, &query).await?.len(), 1);
+
+    // Exports render the placeholder rather than the envelope.
+    let mut csv = Vec::new();
+    form::export_tabular(
+        &op,
+        ws_path,
+        "Credential",
+        TabularExportFormat::Csv,
+        &mut csv,
+    )
+    .await?;
+    let csv = String::from_utf8(csv)?;
+    assert!(csv.contains(SECRET_PLACEHOLDER));
+    assert!(!csv.contains("ugoite-secret"));
+    let atom = feed::generate_feed(&op, ws_path, &feed::FeedOptions::default()).await?;
+    assert!(!atom.contains("ugoite-secret"));
+
+    // Decrypting requires the secret_read scope.
+    let err = entry::get_entry_with_secrets(&op, ws_path, "billing", &[])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(SECRET_READ_SCOPE));
+    let revealed =
+        entry::get_entry_with_secrets(&op, ws_path, "billing", &[SECRET_READ_SCOPE.to_string()])
+            .await?;
+    assert_eq!(revealed["sections"]["ApiKey"], "sk-live-123456");
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-012
+async fn test_field_crypto_req_form_012_placeholder_update_and_form_changes() -> anyhow::Result<()>
+{
+    let op = setup_operator()?;
+    space::create_space(&op, "secret-update", "/tmp").await?;
+    let ws_path = "spaces/secret-update";
+    let integrity = FakeIntegrityProvider;
+    let scopes = [SECRET_READ_SCOPE.to_string()];
+
+    form::upsert_form(&op, ws_path, &credential_form(false)).await?;
+    let markdown =
+        "---\nform: Credential\n---\n# Mail\n\n## Service\nmail\n\n## ApiKey\nmail-key-1\n";
+    entry::create_entry(&op, ws_path, "mail", markdown, "alice", &integrity).await?;
+    assert_eq!(
+        entry::get_entry(&op, ws_path, "mail").await?["sections"]["ApiKey"],
+        "mail-key-1"
+    );
+
+    // Marking an existing field secret encrypts stored values.
+    form::upsert_form(&op, ws_path, &credential_form(true)).await?;
+    assert_eq!(
+        entry::get_entry(&op, ws_path, "mail").await?["sections"]["ApiKey"],
+        SECRET_PLACEHOLDER
+    );
+
+    // Round-tripping the placeholder keeps the stored secret.
+    let content = entry::get_entry_content(&op, ws_path, "mail").await?;
+    let edited = content
+        .markdown
+        .replace("## Service\nmail", "## Service\nmail-eu");
+    entry::update_entry(
+        &op,
+        ws_path,
+        "mail",
+        &edited,
+        Some(&content.revision_id),
+        "alice",
+        None,
+        &integrity,
+    )
+    .await?;
+    let revealed = entry::get_entry_with_secrets(&op, ws_path, "mail", &scopes).await?;
+    assert_eq!(revealed["sections"]["Service"], "mail-eu");
+    assert_eq!(revealed["sections"]["ApiKey"], "mail-key-1");
+
+    // Clearing the secret flag decrypts values back into the column.
+    form::upsert_form(&op, ws_path, &credential_form(false)).await?;
+    assert_eq!(
+        entry::get_entry(&op, ws_path, "mail").await?["sections"]["ApiKey"],
+        "mail-key-1"
+    );
+
+    let err = form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({
+            "name": "Counter",
+            "fields": {"Count": {"type": "integer", "secret": true}},
+        }),
+    )
+    .await
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("must be of type string or markdown"));
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-012
+async fn test_field_crypto_req_form_012_concurrent_seals_share_one_key() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "secret-race", "/tmp").await?;
+    let ws_path = "spaces/secret-race";
+    let integrity = FakeIntegrityProvider;
+    let scopes = [SECRET_READ_SCOPE.to_string()];
+    // Separate forms keep the two writes on separate tables, so only the data key is shared.
+    form::upsert_form(&op, ws_path, &credential_form(true)).await?;
+    let mut token_form = credential_form(true);
+    token_form["name"] = serde_json::json!("Token");
+    form::upsert_form(&op, ws_path, &token_form).await?;
+
+    let billing =
+        "---\nform: Credential\n---\n# Billing\n\n## Service\nbilling\n\n## ApiKey\nkey-one\n";
+    let deploy = "---\nform: Token\n---\n# Deploy\n\n## Service\ndeploy\n\n## ApiKey\nkey-two\n";
+    let (first, second) = tokio::join!(
+        entry::create_entry(&op, ws_path, "billing", billing, "alice", &integrity),
+        entry::create_entry(&op, ws_path, "deploy", deploy, "bob", &integrity),
+    );
+    first?;
+    second?;
+
+    let revealed = entry::get_entry_with_secrets(&op, ws_path, "billing", &scopes).await?;
+    assert_eq!(revealed["sections"]["ApiKey"], "key-one");
+    let revealed = entry::get_entry_with_secrets(&op, ws_path, "deploy", &scopes).await?;
+    assert_eq!(revealed["sections"]["ApiKey"], "key-two");
+
+    Ok(())
+}
+
+/// Encrypt `plaintext` with the space data key the way a stored secret is encrypted.
+async fn copied_envelope(
+    op: &opendal::Operator,
+    ws_path: &str,
+    plaintext: &str,
+) -> anyhow::Result<String> {
+    use aes_gcm::aead::{Aead, KeyInit};
+    use aes_gcm::{Aes256Gcm, Nonce};
+    use base64::{engine::general_purpose, Engine as _};
+
+    let data_key: serde_json::Value =
+        serde_json::from_slice(&op.read(&format!("{ws_path}/data_key.json")).await?.to_vec())?;
+    let key = general_purpose::STANDARD.decode(data_key["key"].as_str().unwrap())?;
+    let cipher =
+        Aes256Gcm::new_from_slice(&key).map_err(|_| anyhow::anyhow!("invalid data key"))?;
+    let nonce = [7u8; 12];
+    let mut payload = nonce.to_vec();
+    payload.extend(
+        cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+            .map_err(|_| anyhow::anyhow!("encrypt failed"))?,
+    );
+    Ok(format!(
+        "ugoite-secret:v1:{}:{}:{}",
+        data_key["key_id"].as_str().unwrap(),
+        "0".repeat(64),
+        general_purpose::STANDARD.encode(payload)
+    ))
+}
+
+#[tokio::test]
+/// REQ-FORM-012
+async fn test_field_crypto_req_form_012_copied_envelopes_are_rejected() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "secret-copy", "/tmp").await?;
+    let ws_path = "spaces/secret-copy";
+    let integrity = FakeIntegrityProvider;
+    form::upsert_form(&op, ws_path, &credential_form(true)).await?;
+    let billing =
+        "---\nform: Credential\n---\n# Billing\n\n## Service\nbilling\n\n## ApiKey\nsk-live-123456\n";
+    entry::create_entry(&op, ws_path, "billing", billing, "alice", &integrity).await?;
+    let envelope = copied_envelope(&op, ws_path, "sk-live-123456").await?;
+
+    // A plain field never decrypts a pasted envelope, on create or on update.
+    let pasted =
+        format!("---\nform: Credential\n---\n# Probe\n\n## Service\n{envelope}\n\n## ApiKey\nx\n");
+    let err = entry::create_entry(&op, ws_path, "probe", &pasted, "mallory", &integrity)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("encrypted value"), "{err}");
+
+    let probe = "---\nform: Credential\n---\n# Probe\n\n## Service\nprobe\n\n## ApiKey\nmine\n";
+    entry::create_entry(&op, ws_path, "probe", probe, "mallory", &integrity).await?;
+    let content = entry::get_entry_content(&op, ws_path, "probe").await?;
+    let edited = content
+        .markdown
+        .replace("## Service\nprobe", &format!("## Service\n{envelope}"));
+    let err = entry::update_entry(
+        &op,
+        ws_path,
+        "probe",
+        &edited,
+        Some(&content.revision_id),
+        "mallory",
+        None,
+        &integrity,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("encrypted value"), "{err}");
+
+    // A secret field does not adopt another entry's envelope either.
+    let edited = content.markdown.replace(
+        &format!("## ApiKey\n{SECRET_PLACEHOLDER}"),
+        &format!("## ApiKey\n{envelope}"),
+    );
+    assert_ne!(edited, content.markdown);
+    let err = entry::update_entry(
+        &op,
+        ws_path,
+        "probe",
+        &edited,
+        Some(&content.revision_id),
+        "mallory",
+        None,
+        &integrity,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("encrypted value"), "{err}");
+
+    let stored = entry::get_entry(&op, ws_path, "probe").await?;
+    assert_eq!(stored["sections"]["Service"], "probe");
+    assert!(!stored["content"]
+        .as_str()
+        .unwrap()
+        .contains("sk-live-123456"));
+    let revealed =
+        entry::get_entry_with_secrets(&op, ws_path, "probe", &[SECRET_READ_SCOPE.to_string()])
+            .await?;
+    assert_eq!(revealed["sections"]["ApiKey"], "mine");
+
+    Ok(())
+}
@@ -30,6 +30,37 @@ async fn test_form_req_form_002_upsert_and_list_forms() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+/// REQ-FORM-002
+async fn test_form_req_form_002_redefining_form_keeps_other_forms() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "form-rebuild", "/tmp").await?;
+    let ws_path = "spaces/form-rebuild";
+
+    for name in ["alpha", "beta"] {
+        form::upsert_form(
+            &op,
+            ws_path,
+            &serde_json::json!({"name": name, "fields": {"note": {"type": "string"}}}),
+        )
+        .await?;
+    }
+    // Changing fields rebuilds alpha's tables; beta must stay readable.
+    form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({"name": "alpha", "fields": {"summary": {"type": "markdown"}}}),
+    )
+    .await?;
+
+    let beta = form::get_form(&op, ws_path, "beta").await?;
+    assert!(beta["fields"].get("note").is_some());
+    let alpha = form::get_form(&op, ws_path, "alpha").await?;
+    assert!(alpha["fields"].get("summary").is_some());
+
+    Ok(())
+}
+
 #[tokio::test]
 /// REQ-FORM-001
 async fn test_form_req_form_001_list_column_types() -> anyhow::Result<()> {
@@ -48,6 +79,11 @@ async fn test_form_req_form_001_list_column_types() -> anyhow::Result<()> {
     assert!(types.contains(&"timestamp_tz".to_string()));
     assert!(types.contains(&"timestamp_ns".to_string()));
     assert!(types.contains(&"timestamp_tz_ns".to_string()));
+    assert!(types.contains(&"datetime".to_string()));
+    assert!(types.contains(&"duration".to_string()));
+    assert!(types.contains(&"decimal".to_string()));
+    assert!(types.contains(&"geopoint".to_string()));
+    assert!(types.contains(&"sequence".to_string()));
     assert!(types.contains(&"uuid".to_string()));
     assert!(types.contains(&"row_reference".to_string()));
     assert!(types.contains(&"binary".to_string()));
@@ -0,0 +1,144 @@
+mod common;
+
+use _ugoite_core::geo::{BoundingBox, GeoPoint};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::sql::SqlColumnType;
+use _ugoite_core::{entry, form, index, space};
+use common::setup_operator;
+use serde_json::json;
+
+#[test]
+/// REQ-IDX-017
+fn test_geo_req_idx_017_points_and_distance() -> anyhow::Result<()> {
+    let tokyo = GeoPoint::from_value(&json!("35.6812, 139.7671")).expect("text point");
+    assert_eq!(
+        GeoPoint::from_value(&json!({"lat": 35.6812, "lng": 139.7671})),
+        Some(tokyo)
+    );
+    assert_eq!(
+        GeoPoint::from_value(&json!("35.6812 139.7671")),
+        Some(tokyo)
+    );
+    for bad in [
+        json!("91, 0"),
+        json!("0, 181"),
+        json!("35.6"),
+        json!("1, 2, 3"),
+        json!([139.7671, 35.6812]),
+        json!({"lat": "north", "lon": 0}),
+    ] {
+        assert_eq!(GeoPoint::from_value(&bad), None, "{bad}");
+    }
+
+    let osaka = GeoPoint::new(34.7025, 135.4959).expect("osaka");
+    let km = tokyo.distance_km(osaka);
+    assert!((km - 403.0).abs() < 5.0, "{km}");
+    assert_eq!(tokyo.distance_km(tokyo), 0.0);
+
+    // A box crossing the antimeridian.
+    let pacific = BoundingBox::new(-25.0, 170.0, -10.0, -170.0)?;
+    assert!(pacific.contains(GeoPoint::new(-18.1248, 178.4501).expect("suva")));
+    assert!(pacific.contains(GeoPoint::new(-14.0, -172.0).expect("apia")));
+    assert!(!pacific.contains(tokyo));
+    assert!(BoundingBox::new(10.0, 0.0, -10.0, 5.0).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-IDX-017
+async fn test_geo_req_idx_017_spatial_queries() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "field-work", "/tmp").await?;
+    let ws_path = "spaces/field-work";
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Site",
+            "template": "# Site\n\n## Location\n",
+            "fields": {"Location": {"type": "geopoint"}},
+        }),
+    )
+    .await?;
+    for (id, location) in [
+        ("tokyo", "35.6812, 139.7671"),
+        ("shinjuku", "35.6896 139.7006"),
+        ("osaka", "34.7025, 135.4959"),
+    ] {
+        let content = format!("---\nform: Site\n---\n# {id}\n\n## Location\n{location}\n");
+        entry::create_entry(&op, ws_path, id, &content, "author", &FakeIntegrityProvider).await?;
+    }
+    let bad = "---\nform: Site\n---\n# bad\n\n## Location\n135, 35\n";
+    assert!(
+        entry::create_entry(&op, ws_path, "bad", bad, "author", &FakeIntegrityProvider)
+            .await
+            .is_err()
+    );
+
+    let stored = entry::get_entry(&op, ws_path, "tokyo").await?;
+    assert_eq!(stored["sections"]["Location"], "35.6812, 139.7671");
+    let indexed = index::query_index(&op, ws_path, &json!({"id": "tokyo"}).to_string()).await?;
+    assert_eq!(
+        indexed[0]["properties"]["Location"],
+        json!({"lat": 35.6812, "lon": 139.7671})
+    );
+
+    let ids = |rows: Vec<serde_json::Value>| {
+        let mut ids: Vec<String> = rows
+            .iter()
+            .filter_map(|row| row["id"].as_str().map(str::to_string))
+            .collect();
+        ids.sort();
+        ids
+    };
+    let near = index::query_index(
+        &op,
+        ws_path,
+        &json!({"Location": {"$near": {"lat": 35.68, "lon": 139.76, "radius_km": 10}}}).to_string(),
+    )
+    .await?;
+    assert_eq!(ids(near), vec!["shinjuku", "tokyo"]);
+    let boxed = index::query_index(
+        &op,
+        ws_path,
+        &json!({"Location": {"$within_box": {"south": 34, "west": 135, "north": 35, "east": 136}}})
+            .to_string(),
+    )
+    .await?;
+    assert_eq!(ids(boxed), vec!["osaka"]);
+    assert!(index::query_index(
+        &op,
+        ws_path,
+        &json!({"Location": {"$near": {"lat": 35.68, "lon": 139.76}}}).to_string(),
+    )
+    .await
+    .is_err());
+
+    let rows = index::query_index(
+        &op,
+        ws_path,
+        &json!({"$sql": "SELECT *, geo_distance(Location, 35.6812, 139.7671) AS km FROM Site \
+                         WHERE geo_distance(Location, 35.6812, 139.7671) < 50 \
+                         ORDER BY geo_distance(Location, 35.6812, 139.7671)"})
+        .to_string(),
+    )
+    .await?;
+    let ordered: Vec<&str> = rows.iter().filter_map(|row| row["id"].as_str()).collect();
+    assert_eq!(ordered, vec!["tokyo", "shinjuku"]);
+    assert_eq!(rows[0]["km"], 0.0);
+    let in_box = index::query_index(
+        &op,
+        ws_path,
+        &json!({"$sql": "SELECT * FROM Site WHERE geo_within_box(Location, 35, 139, 36, 140)"})
+            .to_string(),
+    )
+    .await?;
+    assert_eq!(ids(in_box), vec!["shinjuku", "tokyo"]);
+
+    let columns = index::sql_result_columns(&op, ws_path, "SELECT * FROM Site", None).await?;
+    assert!(columns
+        .iter()
+        .any(|column| column.name == "properties.Location"
+            && column.column_type == SqlColumnType::Geopoint));
+    Ok(())
+}
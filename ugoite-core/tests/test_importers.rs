@@ -0,0 +1,338 @@
+mod common;
+use _ugoite_core::entry;
+use _ugoite_core::importers::{
+    self, ArchiveLimits, FolderMapping, ImportFileStatus, ImportJobStatus, ImportOptions,
+    ImportSource,
+};
+use _ugoite_core::space;
+use common::setup_operator;
+use std::io::Write;
+
+#[tokio::test]
+/// REQ-ENTRY-010
+async fn test_importers_req_entry_010_obsidian_vault_links_and_attachments() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "vault-space", "/tmp").await?;
+
+    let dir = tempfile::tempdir()?;
+    std::fs::create_dir_all(dir.path().join("Projects/Alpha"))?;
+    std::fs::create_dir_all(dir.path().join(".obsidian"))?;
+    std::fs::write(dir.path().join(".obsidian/app.json"), "{}")?;
+    std::fs::write(
+        dir.path().join("Projects/Alpha/Plan.md"),
+        "---\ntags: [roadmap]\n---\nSee [[Daily Log|the log]] and ![[diagram.png]].\n\n## Goals\n- Ship\n",
+    )?;
+    std::fs::write(
+        dir.path().join("Daily Log.md"),
+        "Back to [[Plan]].\n[[Missing]]\n[[naïve]]\n",
+    )?;
+    std::fs::write(dir.path().join("Projects/diagram.png"), b"png-bytes")?;
+
+    let files = importers::read_obsidian_vault(dir.path())?;
+    assert_eq!(files.len(), 3);
+
+    let options = ImportOptions::new(ImportSource::Obsidian);
+    let summary = importers::import_files(&op, "vault-space", &options, files).await?;
+    assert_eq!(summary.entries_created, 2);
+    assert_eq!(summary.assets_created, 1);
+    assert_eq!(summary.failed, 0);
+    assert_eq!(summary.forms, vec!["Entry".to_string()]);
+
+    let plan_report = summary
+        .files
+        .iter()
+        .find(|f| f.path == "Projects/Alpha/Plan.md")
+        .expect("plan report");
+    let log_report = summary
+        .files
+        .iter()
+        .find(|f| f.path == "Daily Log.md")
+        .expect("log report");
+    let asset_report = summary
+        .files
+        .iter()
+        .find(|f| f.path == "Projects/diagram.png")
+        .expect("asset report");
+    assert_eq!(asset_report.status, ImportFileStatus::Attached);
+    assert_eq!(
+        log_report.unresolved_links,
+        vec!["Missing".to_string(), "naïve".to_string()]
+    );
+
+    let ws_path = "spaces/vault-space";
+    let plan_id = plan_report.entry_id.as_deref().expect("plan entry id");
+    let log_id = log_report.entry_id.as_deref().expect("log entry id");
+    let asset_id = asset_report.asset_id.as_deref().expect("asset id");
+
+    let plan = entry::get_entry(&op, ws_path, plan_id).await?;
+    assert_eq!(plan["title"], "Plan");
+    let tags: Vec<&str> = plan["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|v| v.as_str())
+        .collect();
+    assert_eq!(tags, vec!["Projects", "Alpha", "roadmap"]);
+    let content = plan["content"].as_str().unwrap();
+    assert!(content.contains(&format!("[the log](ugoite://entry/{})", log_id)));
+    assert!(content.contains(&format!("![diagram.png](ugoite://asset/{})", asset_id)));
+    assert!(content.contains("**Goals**"));
+    assert_eq!(plan["assets"][0]["id"], asset_id);
+    let history = entry::get_entry_history(&op, ws_path, plan_id).await?;
+    assert_eq!(history["revisions"].as_array().map(Vec::len), Some(1));
+    assert_eq!(history["revisions"][0]["revision_id"], plan["revision_id"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-010
+async fn test_importers_req_entry_010_notion_zip_maps_folders_to_forms() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "notion-space", "/tmp").await?;
+
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    {
+        let mut writer = zip::ZipWriter::new(&mut buffer);
+        let opts = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        writer.start_file("Meeting Notes 0123456789abcdef0123456789abcdef.md", opts)?;
+        writer.write_all(b"# Meeting Notes\n\nSee [Kickoff](Meeting%20Notes%200123456789abcdef0123456789abcdef/Kickoff%20fedcba9876543210fedcba9876543210.md)\n")?;
+        writer.start_file(
+            "Meeting Notes 0123456789abcdef0123456789abcdef/Kickoff fedcba9876543210fedcba9876543210.md",
+            opts,
+        )?;
+        writer.write_all(b"# Kickoff\n\n![photo](photo.jpg)\n")?;
+        writer.start_file(
+            "Meeting Notes 0123456789abcdef0123456789abcdef/photo.jpg",
+            opts,
+        )?;
+        writer.write_all(b"jpg")?;
+        writer.start_file("Tasks 11111111111111111111111111111111.csv", opts)?;
+        writer.write_all(b"Name,Status\n")?;
+        writer.finish()?;
+    }
+
+    let files = importers::read_notion_export(buffer.get_ref())?;
+    let mut options = ImportOptions::new(ImportSource::Notion);
+    options.folder_mapping = FolderMapping::Forms;
+    let summary = importers::import_files(&op, "notion-space", &options, files).await?;
+
+    assert_eq!(summary.entries_created, 2);
+    assert_eq!(summary.assets_created, 1);
+    assert_eq!(summary.skipped, 1);
+    assert_eq!(
+        summary.forms,
+        vec!["Entry".to_string(), "MeetingNotes".to_string()]
+    );
+
+    let kickoff = summary
+        .files
+        .iter()
+        .find(|f| {
+            f.path
+                .ends_with("Kickoff fedcba9876543210fedcba9876543210.md")
+        })
+        .expect("kickoff report");
+    assert_eq!(kickoff.form.as_deref(), Some("MeetingNotes"));
+    let kickoff_id = kickoff.entry_id.clone().expect("kickoff id");
+
+    let parent = summary
+        .files
+        .iter()
+        .find(|f| f.path == "Meeting Notes 0123456789abcdef0123456789abcdef.md")
+        .expect("parent report");
+    let parent_entry = entry::get_entry(
+        &op,
+        "spaces/notion-space",
+        parent.entry_id.as_deref().unwrap(),
+    )
+    .await?;
+    assert_eq!(parent_entry["title"], "Meeting Notes");
+    assert!(parent_entry["content"]
+        .as_str()
+        .unwrap()
+        .contains(&format!("[Kickoff](ugoite://entry/{})", kickoff_id)));
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-010
+async fn test_importers_req_entry_010_joplin_jex_runs_as_job() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "joplin-space", "/tmp").await?;
+
+    let folder_id = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    let note_id = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    let resource_id = "cccccccccccccccccccccccccccccccc";
+    let tag_id = "dddddddddddddddddddddddddddddddd";
+    let items = vec![
+        (
+            format!("{folder_id}.md"),
+            format!("Work\n\nid: {folder_id}\nparent_id: \ntype_: 2"),
+        ),
+        (
+            format!("{note_id}.md"),
+            format!(
+                "Standup\n\nYesterday: see ![chart](:/{resource_id})\n\nid: {note_id}\nparent_id: {folder_id}\ntype_: 1"
+            ),
+        ),
+        (
+            format!("{resource_id}.md"),
+            format!("chart.png\n\nid: {resource_id}\nmime: image/png\nfile_extension: png\ntype_: 4"),
+        ),
+        (
+            format!("{tag_id}.md"),
+            format!("daily\n\nid: {tag_id}\ntype_: 5"),
+        ),
+        (
+            "eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee.md".to_string(),
+            format!("\n\nid: eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee\nnote_id: {note_id}\ntag_id: {tag_id}\ntype_: 6"),
+        ),
+    ];
+    let mut builder = tar::Builder::new(Vec::new());
+    let mut append = |path: &str, data: &[u8]| -> anyhow::Result<()> {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, path, data)?;
+        Ok(())
+    };
+    for (path, body) in &items {
+        append(path, body.as_bytes())?;
+    }
+    append(&format!("resources/{resource_id}.png"), b"png")?;
+    let archive = builder.into_inner()?;
+
+    let files = importers::read_joplin_export(&archive)?;
+    let options = ImportOptions::new(ImportSource::Joplin);
+    let job = importers::create_import_job(&op, "joplin-space", &options, files).await?;
+    assert_eq!(job.total_files, 5);
+
+    let mut finished = None;
+    for _ in 0..200 {
+        let current = importers::get_import_job(&op, "joplin-space", &job.job_id).await?;
+        if matches!(
+            current.status,
+            ImportJobStatus::Completed | ImportJobStatus::Failed
+        ) {
+            finished = Some(current);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+    }
+    let finished = finished.expect("import job did not finish");
+    assert_eq!(finished.status, ImportJobStatus::Completed);
+    assert_eq!(finished.processed_files, 5);
+    let summary = finished.summary.expect("job summary");
+    assert_eq!(summary.entries_created, 1);
+    assert_eq!(summary.assets_created, 1);
+    assert_eq!(summary.skipped, 3);
+
+    let note = summary
+        .files
+        .iter()
+        .find(|f| f.status == ImportFileStatus::Imported)
+        .expect("note report");
+    let stored = entry::get_entry(
+        &op,
+        "spaces/joplin-space",
+        note.entry_id.as_deref().unwrap(),
+    )
+    .await?;
+    assert_eq!(stored["title"], "Standup");
+    let tags: Vec<&str> = stored["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|v| v.as_str())
+        .collect();
+    assert_eq!(tags, vec!["Work", "daily"]);
+    assert!(stored["content"]
+        .as_str()
+        .unwrap()
+        .contains("![chart](ugoite://asset/"));
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-010
+async fn test_importers_req_entry_010_rejects_unknown_space() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let options = ImportOptions::new(ImportSource::Obsidian);
+    let err = importers::import_files(&op, "missing", &options, Vec::new())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Space not found"));
+    assert!(ImportSource::parse("evernote").is_err());
+    Ok(())
+}
+
+fn deflated_zip(files: &[(&str, Vec<u8>)]) -> anyhow::Result<Vec<u8>> {
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    {
+        let mut writer = zip::ZipWriter::new(&mut buffer);
+        let opts = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        for (name, data) in files {
+            writer.start_file(*name, opts)?;
+            writer.write_all(data)?;
+        }
+        writer.finish()?;
+    }
+    Ok(buffer.into_inner())
+}
+
+#[test]
+/// REQ-ENTRY-010
+fn test_importers_req_entry_010_archive_limits_bound_expansion() -> anyhow::Result<()> {
+    let limits = ArchiveLimits {
+        max_entries: 3,
+        max_entry_bytes: 1000,
+        max_total_bytes: 1500,
+    };
+
+    let bomb = deflated_zip(&[("Big.md", vec![b'a'; 5000])])?;
+    assert!(bomb.len() < 1000);
+    let err = importers::read_notion_export_with_limits(&bomb, &limits).unwrap_err();
+    assert!(err.to_string().contains("exceeds 1000 bytes"), "{err}");
+
+    let wide = deflated_zip(&[
+        ("A.md", vec![b'a'; 600]),
+        ("B.md", vec![b'b'; 600]),
+        ("C.md", vec![b'c'; 600]),
+    ])?;
+    let err = importers::read_notion_export_with_limits(&wide, &limits).unwrap_err();
+    assert!(err.to_string().contains("more than 1500 bytes"), "{err}");
+
+    let inner = deflated_zip(&[
+        ("A.md", b"a".to_vec()),
+        ("B.md", b"b".to_vec()),
+        ("C.md", b"c".to_vec()),
+    ])?;
+    assert_eq!(
+        importers::read_notion_export_with_limits(&inner, &limits)?.len(),
+        3
+    );
+    let nested = deflated_zip(&[("Part-1.zip", inner)])?;
+    let err = importers::read_notion_export_with_limits(&nested, &limits).unwrap_err();
+    assert!(err.to_string().contains("more than 3 entries"), "{err}");
+    assert_eq!(importers::read_notion_export(&nested)?.len(), 3);
+
+    let mut builder = tar::Builder::new(Vec::new());
+    for idx in 0..4 {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(1);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append_data(&mut header, format!("{idx}.md"), &b"x"[..])?;
+    }
+    let jex = builder.into_inner()?;
+    let err = importers::read_joplin_export_with_limits(&jex, &limits).unwrap_err();
+    assert!(err.to_string().contains("more than 3 entries"), "{err}");
+    assert_eq!(importers::read_joplin_export(&jex)?.len(), 4);
+    Ok(())
+}
@@ -1,4 +1,5 @@
 mod common;
+use _ugoite_core::integrity::FakeIntegrityProvider;
 use _ugoite_core::{entry, form, index, link, space};
 use common::setup_operator;
 
@@ -237,7 +238,7 @@ fn test_index_req_idx_010_rich_content_parsing() -> anyhow::Result<()> {
         }
     });
 
-    let markdown = "---\nclass: Meeting\n---\n# Title\n\n## Done\ntrue\n\n## Count\n42\n\n## Rate\n3.14\n\n## Event\n2025-01-02T03:04:05Z\n\n## EventTz\n2025-01-02T12:04:05+09:00\n\n## EventNs\n2025-01-02T03:04:05.123456789Z\n\n## EventTzNs\n2025-01-02T12:04:05.123456789+09:00\n\n## Time\n13:45:30.123456\n\n## Uid\nA7F9F5D2-8B7E-4DB1-9B0A-0E9A2B3F4C5D\n\n## Blob\nhex:64617461\n\n## Items\n- Alpha\n- Beta\n";
+    let markdown = "---\nclass: Meeting\n---\n# Title\n\n## Done\ntrue\n\n## Count\n42\n\n## Rate\n3.25\n\n## Event\n2025-01-02T03:04:05Z\n\n## EventTz\n2025-01-02T12:04:05+09:00\n\n## EventNs\n2025-01-02T03:04:05.123456789Z\n\n## EventTzNs\n2025-01-02T12:04:05.123456789+09:00\n\n## Time\n13:45:30.123456\n\n## Uid\nA7F9F5D2-8B7E-4DB1-9B0A-0E9A2B3F4C5D\n\n## Blob\nhex:64617461\n\n## Items\n- Alpha\n- Beta\n";
     let props = index::extract_properties(markdown);
     let (casted, warnings) = index::validate_properties(&props, &class_def)?;
     assert!(warnings.is_empty());
@@ -246,7 +247,7 @@ fn test_index_req_idx_010_rich_content_parsing() -> anyhow::Result<()> {
     assert_eq!(casted_obj.get("Done").and_then(|v| v.as_bool()), Some(true));
     assert_eq!(casted_obj.get("Count").and_then(|v| v.as_i64()), Some(42));
     let rate = casted_obj.get("Rate").and_then(|v| v.as_f64()).unwrap();
-    assert!((rate - 3.14).abs() < 0.0001);
+    assert!((rate - 3.25).abs() < 0.0001);
     assert_eq!(
         casted_obj.get("Event").and_then(|v| v.as_str()),
         Some("2025-01-02T03:04:05+00:00")
@@ -318,3 +319,74 @@ async fn test_index_req_idx_001_get_space_stats() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+/// REQ-IDX-012
+async fn test_index_req_idx_012_query_index_pages_with_cursor() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "paged-ws", "/tmp").await?;
+    let ws_path = "spaces/paged-ws";
+    for idx in 1..=5 {
+        let tag = if idx % 2 == 0 { "even" } else { "odd" };
+        let content =
+            format!("---\nform: Entry\ntags: [{tag}]\n---\n# Entry {idx}\n\n## Body\ntext\n");
+        entry::create_entry(
+            &op,
+            ws_path,
+            &format!("entry-{idx}"),
+            &content,
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    let ids = |rows: &[serde_json::Value]| -> Vec<String> {
+        rows.iter()
+            .filter_map(|row| row.get("id").and_then(|v| v.as_str()).map(str::to_string))
+            .collect()
+    };
+
+    let first = index::query_index_page(&op, ws_path, "{}", None, 2).await?;
+    assert_eq!(ids(&first.rows), vec!["entry-1", "entry-2"]);
+    assert!(first.has_more);
+    let second = index::query_index_page(&op, ws_path, "{}", Some(&first.cursor), 2).await?;
+    assert_eq!(ids(&second.rows), vec!["entry-3", "entry-4"]);
+    let last = index::query_index_page(&op, ws_path, "{}", Some(&second.cursor), 2).await?;
+    assert_eq!(ids(&last.rows), vec!["entry-5"]);
+    assert!(!last.has_more);
+
+    let odd = index::query_index_page(&op, ws_path, r#"{"tag": "odd"}"#, None, 10).await?;
+    assert_eq!(ids(&odd.rows), vec!["entry-1", "entry-3", "entry-5"]);
+    assert!(!odd.has_more);
+
+    let sql = serde_json::json!({"$sql": "SELECT * FROM entries ORDER BY title DESC"}).to_string();
+    let sql_first = index::query_index_page(&op, ws_path, &sql, None, 3).await?;
+    assert_eq!(ids(&sql_first.rows), vec!["entry-5", "entry-4", "entry-3"]);
+    assert!(sql_first.has_more);
+    let sql_next = index::query_index_page(&op, ws_path, &sql, Some(&sql_first.cursor), 3).await?;
+    assert_eq!(ids(&sql_next.rows), vec!["entry-2", "entry-1"]);
+    assert!(!sql_next.has_more);
+
+    entry::create_entry(
+        &op,
+        ws_path,
+        "entry-6",
+        "---\nform: Entry\n---\n# Entry 6\n\n## Body\ntext\n",
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let stale = index::query_index_page(&op, ws_path, &sql, Some(&sql_first.cursor), 3)
+        .await
+        .unwrap_err();
+    assert!(stale.to_string().contains("stale"));
+    assert!(
+        index::query_index_page(&op, ws_path, "{}", Some(&sql_first.cursor), 3)
+            .await
+            .is_err()
+    );
+    assert!(index::query_index_page(&op, ws_path, "{}", None, 0)
+        .await
+        .is_err());
+    Ok(())
+}
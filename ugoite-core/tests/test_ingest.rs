@@ -0,0 +1,189 @@
+mod common;
+use _ugoite_core::asset;
+use _ugoite_core::entry;
+use _ugoite_core::ingest::{self, ClipOptions, HttpImageFetcher, ImageFetcher};
+use _ugoite_core::space;
+use common::setup_operator;
+
+struct FakeImageFetcher;
+
+#[async_trait::async_trait]
+impl ImageFetcher for FakeImageFetcher {
+    async fn fetch(&self, url: &str, _max_bytes: usize) -> anyhow::Result<Vec<u8>> {
+        if url.ends_with("/missing.png") {
+            anyhow::bail!("404 Not Found");
+        }
+        Ok(format!("bytes of {url}").into_bytes())
+    }
+}
+
+const ARTICLE_HTML: &str = r#"<!doctype html>
+<html>
+<head>
+  <title>Fallback Title</title>
+  <meta property="og:title" content="Growing Tomatoes Indoors">
+  <script>window.tracking = true;</script>
+</head>
+<body>
+  <nav><a href="/">Home</a> <a href="/about">About</a></nav>
+  <div class="sidebar">Popular posts, trending, more links, and widgets everywhere.</div>
+  <div id="story" class="post-content">
+    <h1>Growing Tomatoes Indoors</h1>
+    <p>Tomatoes need at least eight hours of light, steady warmth, and a deep pot to thrive indoors.</p>
+    <h2># Choosing a variety</h2>
+    <p>Dwarf varieties, such as <strong>Tiny Tim</strong> or <em>Red Robin</em>, stay compact, fruit early, and tolerate containers.</p>
+    <p><img src="/img/seedlings.png" alt="Seedlings"> <img src="https://cdn.example.org/missing.png" alt="Gone"></p>
+    <ul><li>Use a <a href="https://example.org/soil">loose potting mix</a></li><li>Water when the top inch is dry</li></ul>
+    <pre><code class="language-sh"># water schedule
+echo "daily"</code></pre>
+    <p onclick="steal()">Read the <a href="javascript:alert(1)">full guide</a> for more details on pruning, feeding, and staking.</p>
+    <div class="share-buttons">Share on every network you can imagine right now.</div>
+  </div>
+  <footer>Copyright, terms, privacy, and other legal text.</footer>
+</body>
+</html>"#;
+
+#[tokio::test]
+/// REQ-ENTRY-012
+async fn test_ingest_req_entry_012_clip_html_extracts_article_and_images() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "clip-space", "/tmp").await?;
+    let ws_path = "spaces/clip-space";
+
+    let options = ClipOptions {
+        tags: vec!["garden".to_string()],
+        ..ClipOptions::default()
+    };
+    let result = ingest::clip_html_with_fetcher(
+        &op,
+        ws_path,
+        "https://example.org/blog/tomatoes",
+        ARTICLE_HTML,
+        &options,
+        &FakeImageFetcher,
+    )
+    .await?;
+
+    assert_eq!(result.title, "Growing Tomatoes Indoors");
+    assert_eq!(result.assets.len(), 1);
+    assert_eq!(result.assets[0].name, "seedlings.png");
+    assert_eq!(
+        result.failed_images,
+        vec!["https://cdn.example.org/missing.png".to_string()]
+    );
+
+    let stored = entry::get_entry(&op, ws_path, &result.entry_id).await?;
+    assert_eq!(stored["title"], "Growing Tomatoes Indoors");
+    assert_eq!(stored["form"], "Entry");
+    assert_eq!(stored["tags"], serde_json::json!(["garden"]));
+    assert_eq!(stored["assets"][0]["id"], result.assets[0].id);
+
+    let body = stored["sections"]["Body"].as_str().unwrap();
+    assert!(body.starts_with("Clipped from [example.org](https://example.org/blog/tomatoes)"));
+    assert!(body.contains("Tomatoes need at least eight hours of light"));
+    assert!(body.contains("**# Choosing a variety**"));
+    assert!(body.contains("**Tiny Tim** or *Red Robin*"));
+    assert!(body.contains(&format!("![Seedlings]({})", result.assets[0].link)));
+    assert!(body.contains("![Gone](https://cdn.example.org/missing.png)"));
+    assert!(body.contains("- Use a [loose potting mix](https://example.org/soil)"));
+    assert!(body.contains("```sh\n # water schedule\necho \"daily\"\n```"));
+    assert!(body.contains("Read the full guide for more details"));
+    assert!(!body.contains("javascript:"));
+    assert!(!body.contains("tracking"));
+    assert!(!body.contains("Popular posts"));
+    assert!(!body.contains("Share on every network"));
+    assert!(!body.contains("Copyright"));
+    assert!(!body.contains("Home"));
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-012
+async fn test_ingest_req_entry_012_clip_html_inline_data_images_and_validation(
+) -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "clip-validate", "/tmp").await?;
+    let ws_path = "spaces/clip-validate";
+
+    let html = "<html><body><article><p>Short note with an inline pixel, kept as an asset for offline reading of this page.</p>\
+                <p><img src=\"data:image/png;base64,iVBORw0KGgo=\" alt=\"pixel\"></p></article></body></html>";
+    let options = ClipOptions {
+        title: Some("Pixel\nNote".to_string()),
+        ..ClipOptions::default()
+    };
+    let result = ingest::clip_html_with_fetcher(
+        &op,
+        ws_path,
+        "https://example.com/pixel",
+        html,
+        &options,
+        &FakeImageFetcher,
+    )
+    .await?;
+    assert_eq!(result.title, "Pixel Note");
+    assert_eq!(result.assets.len(), 1);
+    assert_eq!(result.assets[0].name, "image-1.png");
+
+    let err = ingest::clip_html_with_fetcher(
+        &op,
+        ws_path,
+        "file:///etc/passwd",
+        html,
+        &ClipOptions::default(),
+        &FakeImageFetcher,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("http or https"));
+
+    let wrong_field = ClipOptions {
+        field: "Content".to_string(),
+        ..ClipOptions::default()
+    };
+    let err = ingest::clip_html_with_fetcher(
+        &op,
+        ws_path,
+        "https://example.com/pixel",
+        html,
+        &wrong_field,
+        &FakeImageFetcher,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("has no field named Content"));
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-012
+async fn test_ingest_req_entry_012_image_fetcher_uses_network_guard() -> anyhow::Result<()> {
+    let fetcher = HttpImageFetcher::new();
+    for url in [
+        "http://127.0.0.1:9/pixel.png",
+        "http://[::1]:9/pixel.png",
+        "http://169.254.169.254/latest/meta-data",
+        "http://10.0.0.1/pixel.png",
+    ] {
+        let err = fetcher.fetch(url, 1024).await.unwrap_err();
+        assert!(
+            err.to_string().contains("non-public address"),
+            "{url}: {err}"
+        );
+    }
+    let err = fetcher.fetch("file:///etc/passwd", 1024).await.unwrap_err();
+    assert!(err.to_string().contains("scheme"), "{err}");
+
+    let denied = HttpImageFetcher::with_options(asset::UrlAssetOptions {
+        allow_private_networks: true,
+        deny_hosts: vec!["127.0.0.1".to_string()],
+        ..asset::UrlAssetOptions::default()
+    });
+    let err = denied
+        .fetch("http://127.0.0.1:9/pixel.png", 1024)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("denied"), "{err}");
+    Ok(())
+}
@@ -126,3 +126,180 @@ async fn test_response_hmac_material_rejects_missing_hmac_key() -> anyhow::Resul
     assert!(err.to_string().contains("hmac_key missing"));
     Ok(())
 }
+
+#[tokio::test]
+/// REQ-INT-004
+async fn test_integrity_req_int_004_chunked_response_signatures() -> anyhow::Result<()> {
+    use _ugoite_core::integrity::{
+        build_response_signature, ChunkedSignatureVerifier, ResponseSigner,
+    };
+
+    let op = setup_operator()?;
+    let chunks: [&[u8]; 3] = [b"large ", b"download ", b"body"];
+
+    let mut signer = ResponseSigner::begin(&op, "default").await?;
+    let signed: Vec<_> = chunks.iter().map(|chunk| signer.update(chunk)).collect();
+    let stream = signer.finalize();
+
+    let (key_id, body_signature) =
+        build_response_signature(&op, "default", b"large download body").await?;
+    assert_eq!(stream.key_id, key_id);
+    assert_eq!(stream.body_signature, body_signature);
+    assert_eq!((stream.chunks, stream.bytes), (3, 19));
+    let indices: Vec<u64> = signed.iter().map(|chunk| chunk.index).collect();
+    assert_eq!(indices, vec![0, 1, 2]);
+    assert_eq!(signed[1].size, 9);
+
+    let mut verifier = ChunkedSignatureVerifier::begin(&op, "default").await?;
+    for (chunk, signature) in chunks.iter().zip(&signed) {
+        verifier.verify_chunk(chunk, &signature.signature)?;
+    }
+    assert_eq!(verifier.finalize(&stream.transcript_signature)?, stream);
+
+    // The rolling transcript rejects reordered chunks.
+    let mut verifier = ChunkedSignatureVerifier::begin(&op, "default").await?;
+    verifier.verify_chunk(chunks[0], &signed[0].signature)?;
+    let err = verifier
+        .verify_chunk(chunks[2], &signed[2].signature)
+        .expect_err("reordered chunk should be rejected");
+    assert!(err.to_string().contains("Chunk 1"));
+
+    // A truncated stream fails the transcript signature.
+    let mut verifier = ChunkedSignatureVerifier::begin(&op, "default").await?;
+    verifier.verify_chunk(chunks[0], &signed[0].signature)?;
+    verifier.verify_chunk(chunks[1], &signed[1].signature)?;
+    assert!(verifier.finalize(&stream.transcript_signature).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-INT-005
+async fn test_integrity_req_int_005_verify_request_signature() -> anyhow::Result<()> {
+    use _ugoite_core::integrity::{
+        build_request_signature, build_response_signature, verify_request_signature,
+    };
+    use _ugoite_core::{clock, space_lock};
+    use std::collections::HashMap;
+
+    clock::frozen(5, async {
+        let op = setup_operator()?;
+        let now = clock::FROZEN_EPOCH_MS / 1000;
+        let body = br#"{"title":"signed write"}"#;
+        const PATH: &str = "/spaces/default/entries";
+        let signed_headers = |timestamp: i64, nonce: &str, key_id: &str, signature: &str| {
+            HashMap::from([
+                ("X-Ugoite-Key-Id".to_string(), key_id.to_string()),
+                ("X-Ugoite-Signature".to_string(), signature.to_string()),
+                ("X-Ugoite-Timestamp".to_string(), timestamp.to_string()),
+                ("X-Ugoite-Nonce".to_string(), nonce.to_string()),
+            ])
+        };
+
+        let (key_id, signature) =
+            build_request_signature(&op, "default", "POST", PATH, body, now, "nonce-0001").await?;
+        let headers = signed_headers(now, "nonce-0001", &key_id, &signature);
+        let verified =
+            verify_request_signature(&op, "default", "POST", PATH, body, &headers).await?;
+        assert_eq!(verified.key_id, key_id);
+        assert_eq!(verified.nonce, "nonce-0001");
+        let seen: serde_json::Value = serde_json::from_slice(
+            &op.read("spaces/default/request_nonces.json")
+                .await?
+                .to_vec(),
+        )?;
+        assert_eq!(seen["nonce-0001"], now + 300);
+        assert!(
+            space_lock::current_lease(&op, "spaces/default", "request-nonces")
+                .await?
+                .is_none()
+        );
+
+        // Replaying the same nonce is rejected.
+        let err = verify_request_signature(&op, "default", "POST", PATH, body, &headers)
+            .await
+            .expect_err("replayed nonce should be rejected");
+        assert!(err.to_string().contains("already been used"));
+
+        // A tampered body fails the signature.
+        let (_, signature) =
+            build_request_signature(&op, "default", "POST", PATH, body, now, "nonce-0002").await?;
+        let headers = signed_headers(now, "nonce-0002", &key_id, &signature);
+        let err = verify_request_signature(&op, "default", "POST", PATH, b"{}", &headers)
+            .await
+            .expect_err("tampered body should be rejected");
+        assert!(err.to_string().contains("does not match"));
+
+        // The signature is bound to the method and path.
+        let (_, signature) =
+            build_request_signature(&op, "default", "POST", PATH, body, now, "nonce-0005").await?;
+        let headers = signed_headers(now, "nonce-0005", &key_id, &signature);
+        for (method, path) in [("PUT", PATH), ("POST", "/spaces/default/forms")] {
+            let err = verify_request_signature(&op, "default", method, path, body, &headers)
+                .await
+                .expect_err("signature should not transfer to another target");
+            assert!(err.to_string().contains("does not match"));
+        }
+        assert!(
+            verify_request_signature(&op, "default", "post", PATH, body, &headers)
+                .await
+                .is_ok()
+        );
+
+        // A response signature over a request-shaped body is not a request signature.
+        for transcript in [
+            format!("ugoite-request-v2\nPOST\n{PATH}\n{now}\nnonce-0006\n"),
+            format!("ugoite-request-v1\n{now}\nnonce-0006\n"),
+        ] {
+            let mut forged = transcript.into_bytes();
+            forged.extend_from_slice(body);
+            let (_, signature) = build_response_signature(&op, "default", &forged).await?;
+            let headers = signed_headers(now, "nonce-0006", &key_id, &signature);
+            let err = verify_request_signature(&op, "default", "POST", PATH, body, &headers)
+                .await
+                .expect_err("response signatures must not verify as requests");
+            assert!(err.to_string().contains("does not match"));
+        }
+        assert!(
+            verify_request_signature(&op, "default", "POST", "spaces", body, &headers)
+                .await
+                .unwrap_err()
+                .to_string()
+                .contains("Request path")
+        );
+
+        // Stale timestamps fall outside the window.
+        let stale = now - 301;
+        let (_, signature) =
+            build_request_signature(&op, "default", "POST", PATH, body, stale, "nonce-0003")
+                .await?;
+        let headers = signed_headers(stale, "nonce-0003", &key_id, &signature);
+        let err = verify_request_signature(&op, "default", "POST", PATH, body, &headers)
+            .await
+            .expect_err("stale timestamp should be rejected");
+        assert!(err.to_string().contains("outside the allowed window"));
+
+        // Timestamps too far away to subtract are rejected rather than wrapping.
+        let (_, signature) =
+            build_request_signature(&op, "default", "POST", PATH, body, i64::MIN, "nonce-0007")
+                .await?;
+        let headers = signed_headers(i64::MIN, "nonce-0007", &key_id, &signature);
+        let err = verify_request_signature(&op, "default", "POST", PATH, body, &headers)
+            .await
+            .expect_err("overflowing timestamp should be rejected");
+        assert!(err.to_string().contains("outside the allowed window"));
+
+        let mut headers = signed_headers(now, "nonce-0004", "other-key", &signature);
+        let err = verify_request_signature(&op, "default", "POST", PATH, body, &headers)
+            .await
+            .expect_err("unknown key id should be rejected");
+        assert!(err.to_string().contains("unknown key id"));
+        headers.remove("X-Ugoite-Nonce");
+        assert!(
+            verify_request_signature(&op, "default", "POST", PATH, body, &headers)
+                .await
+                .is_err()
+        );
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::integrity::{self, RealIntegrityProvider};
+use _ugoite_core::key_escrow;
+use _ugoite_core::space;
+use common::setup_operator;
+
+const PASSPHRASE: &str = "correct horse battery";
+
+#[tokio::test]
+/// REQ-INT-006
+async fn test_key_escrow_req_int_006_recovery_codes_restore_lost_meta() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/escrow-space";
+    space::create_space(&op, "escrow-space", "/tmp").await?;
+    let provider = RealIntegrityProvider::from_space(&op, "escrow-space").await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "e1",
+        "---\nform: Entry\n---\n# e1\n\n## Body\nSigned text.\n",
+        "author",
+        &provider,
+    )
+    .await?;
+    // Re-save the rendered markdown so the signed content round-trips through reads.
+    let rendered = entry::get_entry_content(&op, ws_path, "e1").await?;
+    entry::update_entry(
+        &op,
+        ws_path,
+        "e1",
+        &rendered.markdown,
+        Some(&rendered.revision_id),
+        "author",
+        None,
+        &provider,
+    )
+    .await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "e2",
+        "---\nform: Entry\n---\n# e2\n\n## Body\nHand-written.\n",
+        "author",
+        &provider,
+    )
+    .await?;
+    let (response_key_id, _) =
+        integrity::build_response_signature(&op, "escrow-space", b"body").await?;
+    let original_meta = space::get_space(&op, "escrow-space").await?;
+    let (key_id, secret) = integrity::load_hmac_material(&op, "escrow-space").await?;
+
+    assert!(
+        key_escrow::export_recovery_codes(&op, "escrow-space", "short")
+            .await
+            .is_err()
+    );
+    let kit = key_escrow::export_recovery_codes(&op, "escrow-space", PASSPHRASE).await?;
+    assert_eq!(kit.key_id, key_id);
+    assert!(kit.includes_response_key);
+    assert!(kit.codes.len() > 1);
+    assert!(kit.codes.iter().all(|code| code.starts_with("UGK1-")));
+
+    // A single mistyped character is caught by the line checksum.
+    let mut typo = kit.codes.clone();
+    let swapped = if typo[0].as_bytes()[8] == b'A' {
+        "B"
+    } else {
+        "A"
+    };
+    typo[0].replace_range(8..9, swapped);
+    let err = key_escrow::restore_from_recovery_codes(&op, "escrow-space", &typo, PASSPHRASE)
+        .await
+        .expect_err("typo should be rejected");
+    assert!(err.to_string().contains("line 1 has a typo"));
+    let err = key_escrow::restore_from_recovery_codes(
+        &op,
+        "escrow-space",
+        &kit.codes,
+        "wrong passphrase!",
+    )
+    .await
+    .expect_err("wrong passphrase should be rejected");
+    assert!(err.to_string().contains("incorrect"));
+
+    // Lose both key files, then restore from lower-cased codes given out of order.
+    op.write(&format!("{ws_path}/meta.json"), "{corrupt")
+        .await?;
+    op.delete(&format!("{ws_path}/hmac.json")).await?;
+    let mut codes: Vec<String> = kit.codes.iter().map(|code| code.to_lowercase()).collect();
+    codes.reverse();
+    let report =
+        key_escrow::restore_from_recovery_codes(&op, "escrow-space", &codes, PASSPHRASE).await?;
+    assert!(report.rebuilt_meta);
+    assert!(report.restored_response_key);
+    assert_eq!(report.entries_checked, 2);
+    assert_eq!(report.entries_verified, 1);
+    assert_eq!(report.entries_unverifiable, 1);
+    assert!(report.entries_mismatched.is_empty());
+
+    assert_eq!(space::get_space(&op, "escrow-space").await?, original_meta);
+    assert_eq!(
+        integrity::load_hmac_material(&op, "escrow-space").await?,
+        (key_id, secret)
+    );
+    let (restored_response_key_id, _) =
+        integrity::build_response_signature(&op, "escrow-space", b"body").await?;
+    assert_eq!(restored_response_key_id, response_key_id);
+
+    space::create_space(&op, "other-space", "/tmp").await?;
+    let err = key_escrow::restore_from_recovery_codes(&op, "other-space", &kit.codes, PASSPHRASE)
+        .await
+        .expect_err("codes are bound to their space");
+    assert!(err.to_string().contains("belong to space escrow-space"));
+
+    for space_id in ["../escrow-space", "..", "escrow-space/../other-space"] {
+        let err = key_escrow::export_recovery_codes(&op, space_id, PASSPHRASE)
+            .await
+            .expect_err("traversal is rejected on export");
+        assert!(err.to_string().contains("space_id"), "{space_id}: {err}");
+        let err = key_escrow::restore_from_recovery_codes(&op, space_id, &kit.codes, PASSPHRASE)
+            .await
+            .expect_err("traversal is rejected on restore");
+        assert!(err.to_string().contains("space_id"), "{space_id}: {err}");
+    }
+    Ok(())
+}
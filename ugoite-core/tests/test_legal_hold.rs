@@ -0,0 +1,154 @@
+mod common;
+use _ugoite_core::audit::{self, AuditListOptions};
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::legal_hold::{self, HoldTargetType, LEGAL_HOLD_SCOPE};
+use _ugoite_core::space;
+use common::setup_operator;
+
+async fn seed_hold_space(op: &opendal::Operator, space_id: &str) -> anyhow::Result<String> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{space_id}");
+    let integrity = FakeIntegrityProvider;
+    for entry_id in ["contract", "memo"] {
+        let markdown = format!("---\nform: Entry\n---\n# {entry_id}\n\n## Body\nText.\n");
+        entry::create_entry(op, &ws_path, entry_id, &markdown, "alice", &integrity).await?;
+    }
+    Ok(ws_path)
+}
+
+#[tokio::test]
+/// REQ-ENTRY-013
+async fn test_legal_hold_req_entry_013_entry_hold_blocks_deletion() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_hold_space(&op, "hold-entry").await?;
+    let scopes = [LEGAL_HOLD_SCOPE.to_string()];
+
+    let err = legal_hold::place_legal_hold(
+        &op,
+        &ws_path,
+        HoldTargetType::Entry,
+        "contract",
+        "Litigation 2026-17",
+        "mallory",
+        &[],
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains(LEGAL_HOLD_SCOPE));
+
+    let hold = legal_hold::place_legal_hold(
+        &op,
+        &ws_path,
+        HoldTargetType::Entry,
+        "contract",
+        "Litigation 2026-17",
+        "counsel",
+        &scopes,
+    )
+    .await?;
+    assert_eq!(hold.placed_by, "counsel");
+
+    for hard_delete in [false, true] {
+        let err = entry::delete_entry(&op, &ws_path, "contract", hard_delete)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("legal hold"), "{err}");
+    }
+    assert!(entry::get_entry(&op, &ws_path, "contract").await.is_ok());
+    entry::delete_entry(&op, &ws_path, "memo", false).await?;
+
+    let err = legal_hold::release_legal_hold(&op, &ws_path, &hold.hold_id, "mallory", &[])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(LEGAL_HOLD_SCOPE));
+    let released =
+        legal_hold::release_legal_hold(&op, &ws_path, &hold.hold_id, "counsel", &scopes).await?;
+    assert_eq!(released, hold);
+    assert!(legal_hold::list_legal_holds(&op, &ws_path)
+        .await?
+        .is_empty());
+    entry::delete_entry(&op, &ws_path, "contract", true).await?;
+
+    let events = audit::list_audit_events(&op, "hold-entry", AuditListOptions::default()).await?;
+    let mut actions: Vec<String> = events["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| {
+            format!(
+                "{}:{}",
+                event["action"].as_str().unwrap(),
+                event["target_id"].as_str().unwrap()
+            )
+        })
+        .collect();
+    actions.sort();
+    assert_eq!(
+        actions,
+        vec![
+            "legal_hold.place:contract".to_string(),
+            "legal_hold.release:contract".to_string(),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-013
+async fn test_legal_hold_req_entry_013_form_hold_covers_entries() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_hold_space(&op, "hold-form").await?;
+    let scopes = [LEGAL_HOLD_SCOPE.to_string()];
+
+    let err = legal_hold::place_legal_hold(
+        &op,
+        &ws_path,
+        HoldTargetType::Form,
+        "Missing",
+        "Audit",
+        "counsel",
+        &scopes,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Missing"));
+    let err = legal_hold::place_legal_hold(
+        &op,
+        &ws_path,
+        HoldTargetType::Entry,
+        "contract",
+        "  ",
+        "counsel",
+        &scopes,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("reason must not be empty"));
+
+    let hold = legal_hold::place_legal_hold(
+        &op,
+        &ws_path,
+        HoldTargetType::Form,
+        "Entry",
+        "Regulatory audit",
+        "counsel",
+        &scopes,
+    )
+    .await?;
+    let holds = legal_hold::holds_for_entry(&op, &ws_path, "Entry", "memo").await?;
+    assert_eq!(holds, vec![hold.clone()]);
+    assert!(entry::delete_entry(&op, &ws_path, "memo", false)
+        .await
+        .is_err());
+
+    legal_hold::release_legal_hold(&op, &ws_path, &hold.hold_id, "counsel", &scopes).await?;
+    entry::delete_entry(&op, &ws_path, "memo", false).await?;
+    let err = legal_hold::release_legal_hold(&op, &ws_path, &hold.hold_id, "counsel", &scopes)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Legal hold not found"));
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::entry_acl::{self, EntryAcl, EntryViewer, EntryVisibility};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::link::{self, EntryRef};
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::json;
+
+#[tokio::test]
+/// REQ-ENTRY-026
+async fn test_link_refs_req_entry_026_cross_space_references() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "home", "/tmp").await?;
+    space::create_space(&op, "team", "/tmp").await?;
+    for (ws_path, id, title) in [
+        ("spaces/home", "note", "Note"),
+        ("spaces/team", "spec", "Spec"),
+        ("spaces/team", "secret", "Secret"),
+    ] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: Entry\n---\n# {title}\n\n## Body\nText\n"),
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+
+    let refs = link::extract_entry_refs(
+        "See [spec](ugoite://entry/team:spec), [note](ugoite://entry/note) and [spec](ugoite://entry/team:spec).",
+    );
+    assert_eq!(
+        refs,
+        vec![EntryRef::parse("team:spec")?, EntryRef::parse("note")?]
+    );
+    assert!(EntryRef::parse("team:").is_err());
+    assert_eq!(EntryRef::parse("home:note")?.relative_to("home"), "note");
+
+    let resolved = link::resolve_entry_ref(&op, "home", "team:spec").await?;
+    assert_eq!(resolved["space_id"], json!("team"));
+    assert_eq!(resolved["cross_space"], json!(true));
+    assert_eq!(resolved["exists"], json!(true));
+    let missing = link::resolve_entry_ref(&op, "home", "nowhere:spec").await?;
+    assert_eq!(missing["exists"], json!(false));
+
+    let created = link::create_link(
+        &op,
+        "spaces/home",
+        "note",
+        "team:spec",
+        "reference",
+        "link-1",
+    )
+    .await?;
+    assert_eq!(created.target, "team:spec");
+    let team_links = link::list_links(&op, "spaces/team").await?;
+    assert_eq!(team_links.len(), 1);
+    assert_eq!(team_links[0].source, "spec");
+    assert_eq!(team_links[0].target, "home:note");
+    assert!(
+        link::create_link(&op, "spaces/home", "note", "team:missing", "reference", "x")
+            .await
+            .is_err()
+    );
+
+    entry_acl::set_entry_acl(
+        &op,
+        "spaces/team",
+        "secret",
+        &EntryAcl {
+            owner: "alice".to_string(),
+            shared_with: Vec::new(),
+            visibility: EntryVisibility::Private,
+        },
+    )
+    .await?;
+    let bob = EntryViewer::user("bob");
+    let entry = link::dereference_entry_ref(&op, "home", "team:spec", &bob).await?;
+    assert_eq!(entry["id"], json!("spec"));
+    assert_eq!(entry["space_id"], json!("team"));
+    assert!(
+        link::dereference_entry_ref(&op, "home", "team:secret", &bob)
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("not found")
+    );
+    let alice = EntryViewer::user("alice");
+    assert!(
+        link::dereference_entry_ref(&op, "home", "team:secret", &alice)
+            .await
+            .is_ok()
+    );
+
+    link::delete_link(&op, "spaces/home", "link-1").await?;
+    assert!(link::list_links(&op, "spaces/team").await?.is_empty());
+    Ok(())
+}
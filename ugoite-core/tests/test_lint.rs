@@ -0,0 +1,124 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::form;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::lint::{self, LintConfig, LintSeverity};
+use _ugoite_core::space;
+use common::setup_operator;
+use std::collections::HashSet;
+
+fn note(id: &str, body: &str) -> String {
+    format!("---\nform: Note\n---\n# {id}\n\n## Body\n{body}\n")
+}
+
+#[tokio::test]
+/// REQ-ENTRY-029
+async fn test_lint_req_entry_029_builtin_and_custom_rules() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/lint-space";
+    space::create_space(&op, "lint-space", "/tmp").await?;
+    form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({
+            "name": "Note",
+            "template": "# Note\n\n## Body\n",
+            "fields": {"Body": {"type": "markdown"}},
+        }),
+    )
+    .await?;
+    let body = [
+        "See [clean](ugoite://entry/clean) and [gone](ugoite://entry/missing).",
+        "Jump to [body](#body) or [nowhere](#nowhere).",
+        "![](ugoite://asset/clean) TODO: caption.",
+        "We utilize `TODO` in code.",
+        "```",
+        "FIXME inside a fence",
+        "```",
+    ]
+    .join("\n");
+    for (id, content) in [
+        ("clean", note("clean", "Nothing to report.")),
+        ("messy", note("messy", &body)),
+    ] {
+        entry::create_entry(&op, ws_path, id, &content, "author", &FakeIntegrityProvider).await?;
+    }
+
+    let findings = lint::lint_entry(&op, ws_path, "messy").await?;
+    let summary: Vec<(&str, usize, usize)> = findings
+        .iter()
+        .map(|f| (f.rule.as_str(), f.line, f.column))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("broken_link", 7, 39),
+            ("broken_link", 8, 26),
+            ("missing_alt_text", 9, 1),
+            ("todo_marker", 9, 27),
+        ]
+    );
+    assert_eq!(findings[0].message, "Link target missing does not exist");
+    assert_eq!(findings[0].severity, LintSeverity::Error);
+    assert_eq!((findings[0].end_line, findings[0].end_column), (7, 69));
+
+    space::patch_space(
+        &op,
+        "lint-space",
+        &serde_json::json!({"settings": {"lint": {
+            "disabled": ["todo_marker"],
+            "rules": [{
+                "id": "plain-words",
+                "pattern": r"\butilize\b",
+                "message": "Prefer \"use\"",
+                "case_insensitive": true,
+            }],
+        }}}),
+    )
+    .await?;
+    let reports = lint::lint_space(&op, ws_path).await?;
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].entry_id, "messy");
+    let rules: Vec<&str> = reports[0]
+        .findings
+        .iter()
+        .map(|f| f.rule.as_str())
+        .collect();
+    assert_eq!(
+        rules,
+        vec![
+            "broken_link",
+            "broken_link",
+            "missing_alt_text",
+            "plain-words",
+        ]
+    );
+    let custom = &reports[0].findings[3];
+    assert_eq!((custom.line, custom.column), (10, 4));
+    assert_eq!(custom.severity, LintSeverity::Warning);
+
+    space::patch_space(
+        &op,
+        "lint-space",
+        &serde_json::json!({"settings": {"lint": {
+            "rules": [{"id": "broken_link", "pattern": "x", "message": "clash"}],
+        }}}),
+    )
+    .await?;
+    assert!(lint::lint_entry(&op, ws_path, "messy").await.is_err());
+    Ok(())
+}
+
+#[test]
+/// REQ-ENTRY-029
+fn test_lint_req_entry_029_markdown_headings_and_code() -> anyhow::Result<()> {
+    let rules = lint::configured_rules(&LintConfig::default())?;
+    let markdown = "# Title\n\n#### Deep\n\n```\nTODO inside a fence\n```\n";
+    let findings = lint::lint_markdown(markdown, &rules, &HashSet::new());
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].rule, "heading_level_jump");
+    assert_eq!(findings[0].message, "Heading level jumps from h1 to h4");
+    assert_eq!((findings[0].line, findings[0].column), (3, 1));
+    Ok(())
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::mentions;
+use _ugoite_core::space;
+use common::setup_operator;
+
+#[test]
+/// REQ-ENTRY-019
+fn test_mentions_req_entry_019_parse_mentions_skips_emails() {
+    assert_eq!(
+        mentions::parse_mentions("Ping @alice, (@bob) and @alice. Mail ops@example.com"),
+        vec!["alice".to_string(), "bob".to_string()]
+    );
+}
+
+#[tokio::test]
+/// REQ-ENTRY-019
+async fn test_mentions_req_entry_019_feed_records_new_mentions() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "mention-space", "/tmp").await?;
+    let ws_path = "spaces/mention-space";
+    let integrity = FakeIntegrityProvider;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "standup",
+        "---\nform: Entry\n---\n# Standup\n\n## Body\n@alice please review. @carol self-note\n\n```\n@bob in code\n```\n",
+        "carol",
+        &integrity,
+    )
+    .await?;
+
+    let alice = mentions::list_mentions(&op, ws_path, "alice", None).await?;
+    assert_eq!(alice.len(), 1);
+    assert_eq!(alice[0].entry_id, "standup");
+    assert_eq!(alice[0].entry_title, "Standup");
+    assert_eq!(alice[0].author, "carol");
+    assert_eq!(alice[0].excerpt, "@alice please review. @carol self-note");
+    assert!(!alice[0].read);
+    assert!(mentions::list_mentions(&op, ws_path, "carol", None)
+        .await?
+        .is_empty());
+    assert!(mentions::list_mentions(&op, ws_path, "bob", None)
+        .await?
+        .is_empty());
+
+    entry::update_entry(
+        &op,
+        ws_path,
+        "standup",
+        "---\nform: Entry\n---\n# Standup\n\n## Body\n@alice please review, @bob too.\n",
+        None,
+        "carol",
+        None,
+        &integrity,
+    )
+    .await?;
+    assert_eq!(
+        mentions::list_mentions(&op, ws_path, "alice", None)
+            .await?
+            .len(),
+        1
+    );
+    let bob = mentions::list_mentions(&op, ws_path, "bob", None).await?;
+    assert_eq!(bob.len(), 1);
+    assert!(
+        mentions::list_mentions(&op, ws_path, "bob", Some(bob[0].created_at))
+            .await?
+            .is_empty()
+    );
+
+    assert_eq!(
+        mentions::mark_mentions_read(&op, ws_path, "alice", None).await?,
+        1
+    );
+    assert!(mentions::list_mentions(&op, ws_path, "alice", None).await?[0].read);
+    assert_eq!(
+        mentions::mark_mentions_read(&op, ws_path, "bob", Some(&["missing".to_string()])).await?,
+        0
+    );
+    assert_eq!(
+        mentions::mark_mentions_read(&op, ws_path, "bob", Some(&[bob[0].id.clone()])).await?,
+        1
+    );
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-019
+async fn test_mentions_req_entry_019_feed_failure_keeps_committed_write() -> anyhow::Result<()> {
+    use sha2::{Digest, Sha256};
+
+    let op = setup_operator()?;
+    space::create_space(&op, "mention-broken", "/tmp").await?;
+    let ws_path = "spaces/mention-broken";
+    let alice_feed = format!(
+        "{}/mentions/{}.json",
+        ws_path,
+        hex::encode(Sha256::digest(b"alice"))
+    );
+    op.write(&alice_feed, "not json").await?;
+    let integrity = FakeIntegrityProvider;
+
+    entry::create_entry(
+        &op,
+        ws_path,
+        "standup",
+        "---\nform: Entry\n---\n# Standup\n\n## Body\n@alice please review.\n",
+        "carol",
+        &integrity,
+    )
+    .await?;
+    entry::update_entry(
+        &op,
+        ws_path,
+        "standup",
+        "---\nform: Entry\n---\n# Standup\n\n## Body\n@alice please review again.\n",
+        None,
+        "carol",
+        None,
+        &integrity,
+    )
+    .await?;
+
+    let stored = entry::get_entry(&op, ws_path, "standup").await?;
+    assert!(stored["content"]
+        .as_str()
+        .unwrap_or_default()
+        .contains("review again"));
+    Ok(())
+}
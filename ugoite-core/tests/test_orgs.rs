@@ -0,0 +1,91 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::orgs;
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::json;
+
+#[tokio::test]
+/// REQ-SEC-020
+async fn test_orgs_req_sec_020_org_roles_and_stats() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    for (space_id, id, tags) in [
+        ("alpha", "a1", "[plan]"),
+        ("alpha", "a2", "[plan, risk]"),
+        ("beta", "b1", "[plan]"),
+    ] {
+        if !space::space_exists(&op, space_id).await? {
+            space::create_space(&op, space_id, "/tmp").await?;
+        }
+        entry::create_entry(
+            &op,
+            &format!("spaces/{}", space_id),
+            id,
+            &format!("---\nform: Entry\ntags: {tags}\n---\n# {id}\n\n## Body\nText\n"),
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    space::create_space(&op, "gamma", "/tmp").await?;
+
+    let org = orgs::create_org(&op, "Research", Some("R&D teams")).await?;
+    assert!(orgs::create_org(&op, "research", None)
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("already exists"));
+    orgs::assign_space(&op, &org.id, "alpha").await?;
+    orgs::assign_space(&op, &org.id, "beta").await?;
+    assert!(orgs::assign_space(&op, &org.id, "missing").await.is_err());
+    let other = orgs::create_org(&op, "Ops", None).await?;
+    assert!(orgs::assign_space(&op, &other.id, "alpha")
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("already belongs"));
+
+    orgs::set_org_member(&op, &org.id, "alice", Some("owner")).await?;
+    orgs::set_org_member(&op, &org.id, "bob", Some("viewer")).await?;
+    assert!(orgs::set_org_member(&op, &org.id, "carol", Some("root"))
+        .await
+        .is_err());
+    assert_eq!(
+        orgs::resolve_org_role(&op, "beta", "alice")
+            .await?
+            .as_deref(),
+        Some("admin")
+    );
+    assert_eq!(
+        orgs::resolve_org_role(&op, "alpha", "bob")
+            .await?
+            .as_deref(),
+        Some("viewer")
+    );
+    assert_eq!(orgs::resolve_org_role(&op, "gamma", "alice").await?, None);
+    assert_eq!(orgs::resolve_org_role(&op, "alpha", "carol").await?, None);
+    let listed = orgs::list_orgs(&op, Some("bob")).await?;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].spaces, vec!["alpha", "beta"]);
+
+    let stats = orgs::get_org_stats(&op, &org.id).await?;
+    assert_eq!(stats["space_count"], json!(2));
+    assert_eq!(stats["entry_count"], json!(3));
+    assert_eq!(stats["form_counts"]["Entry"], json!(3));
+    assert_eq!(stats["tag_counts"], json!({"plan": 3, "risk": 1}));
+    assert_eq!(stats["spaces"]["beta"]["entry_count"], json!(1));
+
+    assert!(orgs::delete_org(&op, &org.id)
+        .await
+        .unwrap_err()
+        .to_string()
+        .contains("still owns spaces"));
+    orgs::release_space(&op, &org.id, "alpha").await?;
+    orgs::release_space(&op, &org.id, "beta").await?;
+    assert_eq!(orgs::resolve_org_role(&op, "alpha", "alice").await?, None);
+    orgs::delete_org(&op, &org.id).await?;
+    assert!(orgs::get_org(&op, &org.id).await.is_err());
+    Ok(())
+}
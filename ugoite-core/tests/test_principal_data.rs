@@ -0,0 +1,237 @@
+mod common;
+use _ugoite_core::asset;
+use _ugoite_core::audit::{self, AuditListOptions};
+use _ugoite_core::entry;
+use _ugoite_core::entry_acl::{self, EntryAcl, EntryPrincipal, EntryVisibility, PrincipalKind};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::legal_hold::{self, HoldTargetType, LEGAL_HOLD_SCOPE};
+use _ugoite_core::principal_data::{self, PRINCIPAL_ERASE_SCOPE};
+use _ugoite_core::space;
+use common::setup_operator;
+
+fn note(title: &str, body: &str) -> String {
+    format!("---\nform: Entry\n---\n# {title}\n\n## Body\n{body}\n")
+}
+
+async fn seed_principal_space(op: &opendal::Operator, space_id: &str) -> anyhow::Result<String> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{space_id}");
+    let integrity = FakeIntegrityProvider;
+
+    entry::create_entry(
+        op,
+        &ws_path,
+        "roadmap",
+        &note("Roadmap", "Draft"),
+        "alice",
+        &integrity,
+    )
+    .await?;
+    let created = entry::get_entry_content(op, &ws_path, "roadmap").await?;
+    entry::update_entry(
+        op,
+        &ws_path,
+        "roadmap",
+        &note("Roadmap", "Reviewed"),
+        Some(&created.revision_id),
+        "bob",
+        None,
+        &integrity,
+    )
+    .await?;
+
+    entry::create_entry(
+        op,
+        &ws_path,
+        "diary",
+        &note("Diary", "Personal notes"),
+        "alice",
+        &integrity,
+    )
+    .await?;
+    let diary = entry::get_entry_content(op, &ws_path, "diary").await?;
+    let photo = asset::save_asset(op, &ws_path, "me.png", b"png-bytes").await?;
+    entry::update_entry(
+        op,
+        &ws_path,
+        "diary",
+        &note("Diary", "Personal notes with photo"),
+        Some(&diary.revision_id),
+        "alice",
+        Some(vec![serde_json::json!({
+            "id": photo.id,
+            "name": photo.name,
+            "path": photo.path,
+        })]),
+        &integrity,
+    )
+    .await?;
+    entry::create_entry(
+        op,
+        &ws_path,
+        "evidence",
+        &note("Evidence", "Kept for counsel"),
+        "alice",
+        &integrity,
+    )
+    .await?;
+
+    for entry_id in ["diary", "evidence"] {
+        entry_acl::set_entry_acl(
+            op,
+            &ws_path,
+            entry_id,
+            &EntryAcl {
+                owner: "alice".to_string(),
+                shared_with: Vec::new(),
+                visibility: EntryVisibility::Private,
+            },
+        )
+        .await?;
+    }
+    entry_acl::set_entry_acl(
+        op,
+        &ws_path,
+        "roadmap",
+        &EntryAcl {
+            owner: "bob".to_string(),
+            shared_with: vec![EntryPrincipal {
+                kind: PrincipalKind::User,
+                id: "alice".to_string(),
+            }],
+            visibility: EntryVisibility::Restricted,
+        },
+    )
+    .await?;
+
+    audit::append_audit_event(
+        op,
+        space_id,
+        &serde_json::json!({"action": "entry.create", "actor_user_id": "alice"}),
+        None,
+    )
+    .await?;
+    Ok(ws_path)
+}
+
+#[tokio::test]
+/// REQ-SEC-014
+async fn test_principal_data_req_sec_014_export_collects_user_data() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_principal_space(&op, "principal-export").await?;
+
+    let export = principal_data::export_principal_data(&op, &ws_path, "alice").await?;
+    assert_eq!(export["user_id"], "alice");
+    assert_eq!(export["space_id"], "principal-export");
+
+    let entries = export["entries"].as_array().unwrap();
+    let ids: Vec<&str> = entries.iter().map(|e| e["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, vec!["diary", "evidence", "roadmap"]);
+    let roadmap = &entries[2];
+    assert_eq!(roadmap["revisions"].as_array().unwrap().len(), 1);
+    assert!(roadmap["markdown"].as_str().unwrap().contains("Reviewed"));
+    assert_eq!(entries[0]["revisions"].as_array().unwrap().len(), 2);
+
+    let assets = export["assets"].as_array().unwrap();
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0]["name"], "me.png");
+    assert_eq!(export["entry_acls"].as_array().unwrap().len(), 3);
+    let events = export["audit_events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["action"], "entry.create");
+
+    let bob = principal_data::export_principal_data(&op, &ws_path, "bob").await?;
+    assert_eq!(bob["entries"].as_array().unwrap().len(), 1);
+    assert!(bob["assets"].as_array().unwrap().is_empty());
+    assert!(principal_data::export_principal_data(&op, &ws_path, " ")
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-SEC-014
+async fn test_principal_data_req_sec_014_erase_anonymizes_and_removes() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_principal_space(&op, "principal-erase").await?;
+    legal_hold::place_legal_hold(
+        &op,
+        &ws_path,
+        HoldTargetType::Entry,
+        "evidence",
+        "Pending case",
+        "counsel",
+        &[LEGAL_HOLD_SCOPE.to_string()],
+    )
+    .await?;
+
+    let err = principal_data::erase_principal_data(&op, &ws_path, "alice", "dpo", &[])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(PRINCIPAL_ERASE_SCOPE));
+
+    let report = principal_data::erase_principal_data(
+        &op,
+        &ws_path,
+        "alice",
+        "dpo",
+        &[PRINCIPAL_ERASE_SCOPE.to_string()],
+    )
+    .await?;
+    assert!(report.pseudonym.starts_with("erased-"));
+    assert_eq!(report.entries_removed, vec!["diary".to_string()]);
+    assert_eq!(report.retained_by_hold, vec!["evidence".to_string()]);
+    assert_eq!(
+        report.entries_anonymized,
+        vec!["evidence".to_string(), "roadmap".to_string()]
+    );
+    assert_eq!(report.assets_removed.len(), 1);
+    assert_eq!(report.audit_events_anonymized, 1);
+
+    assert!(entry::get_entry(&op, &ws_path, "diary").await.is_err());
+    assert!(asset::list_assets(&op, &ws_path).await?.is_empty());
+    let evidence = entry::get_entry_content(&op, &ws_path, "evidence").await?;
+    assert_eq!(evidence.author, report.pseudonym);
+    let roadmap = entry::get_entry_content(&op, &ws_path, "roadmap").await?;
+    assert_eq!(roadmap.author, "bob");
+    assert_eq!(
+        entry::get_entry_history(&op, &ws_path, "roadmap").await?["revisions"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+
+    let acl = entry_acl::get_entry_acl(&op, &ws_path, "roadmap")
+        .await?
+        .unwrap();
+    assert!(acl.shared_with.is_empty());
+    let held_acl = entry_acl::get_entry_acl(&op, &ws_path, "evidence")
+        .await?
+        .unwrap();
+    assert_eq!(held_acl.owner, report.pseudonym);
+    assert!(entry_acl::get_entry_acl(&op, &ws_path, "diary")
+        .await?
+        .is_none());
+
+    let export = principal_data::export_principal_data(&op, &ws_path, "alice").await?;
+    assert!(export["entries"].as_array().unwrap().is_empty());
+    assert!(export["audit_events"].as_array().unwrap().is_empty());
+
+    let erase_events = audit::list_audit_events(
+        &op,
+        "principal-erase",
+        AuditListOptions {
+            action: Some("principal.erase".to_string()),
+            ..AuditListOptions::default()
+        },
+    )
+    .await?;
+    let event = &erase_events["items"][0];
+    assert_eq!(event["actor_user_id"], "dpo");
+    assert_eq!(event["target_id"], report.pseudonym.as_str());
+    assert_eq!(event["metadata"]["entries_removed"], 1);
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use _ugoite_core::principals;
+use common::setup_operator;
+use serde_json::json;
+
+#[tokio::test]
+/// REQ-SEC-019
+async fn test_principals_req_sec_019_provisions_users_and_groups() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let alice = principals::create_user(
+        &op,
+        &json!({
+            "schemas": ["urn:ietf:params:scim:schemas:core:2.0:User"],
+            "userName": "alice@example.test",
+            "externalId": "00u1",
+            "displayName": "Alice",
+            "emails": [{"value": "alice@example.test", "primary": true}],
+        }),
+    )
+    .await?;
+    assert!(alice.active);
+    assert_eq!(alice.meta.resource_type, "User");
+    assert_eq!(alice.meta.version, "W/\"1\"");
+    let bob = principals::create_user(&op, &json!({"userName": "bob@example.test"})).await?;
+
+    let duplicate = principals::create_user(&op, &json!({"userName": "ALICE@example.test"})).await;
+    assert!(duplicate
+        .unwrap_err()
+        .to_string()
+        .contains("userName already exists"));
+
+    let found = principals::list_users(&op, Some("userName eq \"alice@example.test\"")).await?;
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, alice.id);
+    let found = principals::list_users(&op, Some("externalId eq \"00u1\"")).await?;
+    assert_eq!(found[0].id, alice.id);
+    assert!(principals::list_users(&op, Some("title co \"x\""))
+        .await
+        .is_err());
+
+    let editors = principals::create_group(
+        &op,
+        &json!({
+            "displayName": "Editors",
+            "members": [{"value": alice.id}, {"value": bob.id}],
+            "urn:ugoite:params:scim:schemas:extension:2.0:Group": {
+                "spaceRoles": {"docs": "editor"},
+            },
+        }),
+    )
+    .await?;
+    principals::create_group(
+        &op,
+        &json!({
+            "displayName": "Admins",
+            "members": [{"value": alice.id}],
+            "urn:ugoite:params:scim:schemas:extension:2.0:Group": {
+                "spaceRoles": {"docs": "admin", "ops": "viewer"},
+            },
+        }),
+    )
+    .await?;
+    let invalid = principals::create_group(
+        &op,
+        &json!({
+            "displayName": "Owners",
+            "urn:ugoite:params:scim:schemas:extension:2.0:Group": {
+                "spaceRoles": {"docs": "owner"},
+            },
+        }),
+    )
+    .await;
+    assert!(invalid.unwrap_err().to_string().contains("Invalid role"));
+
+    // The highest role across the user's groups wins.
+    let access = principals::resolve_provisioned_access(&op, "docs", "alice@example.test")
+        .await?
+        .expect("alice is provisioned");
+    assert!(access.active);
+    assert_eq!(access.role.as_deref(), Some("admin"));
+    assert_eq!(access.groups, vec!["Admins", "Editors"]);
+    let access = principals::resolve_provisioned_access(&op, "docs", "bob@example.test")
+        .await?
+        .expect("bob is provisioned");
+    assert_eq!(access.role.as_deref(), Some("editor"));
+    let access = principals::resolve_provisioned_access(&op, "other", "bob@example.test")
+        .await?
+        .expect("bob is provisioned");
+    assert_eq!(access.role, None);
+    assert!(
+        principals::resolve_provisioned_access(&op, "docs", "carol@example.test")
+            .await?
+            .is_none()
+    );
+
+    // Replacing the group removes bob's membership.
+    let replaced = principals::replace_group(
+        &op,
+        &editors.id,
+        &json!({
+            "displayName": "Editors",
+            "members": [{"value": alice.id}],
+            "urn:ugoite:params:scim:schemas:extension:2.0:Group": {
+                "spaceRoles": {"docs": "editor"},
+            },
+        }),
+    )
+    .await?;
+    assert_eq!(replaced.meta.version, "W/\"2\"");
+    assert_eq!(replaced.meta.created, editors.meta.created);
+    let access = principals::resolve_provisioned_access(&op, "docs", "bob@example.test")
+        .await?
+        .expect("bob is provisioned");
+    assert_eq!(access.role, None);
+    assert!(access.groups.is_empty());
+
+    let renamed = principals::replace_user(
+        &op,
+        &bob.id,
+        &json!({"userName": "robert@example.test", "displayName": "Robert"}),
+    )
+    .await?;
+    assert_eq!(renamed.user_name, "robert@example.test");
+    assert_eq!(
+        principals::get_user(&op, &bob.id)
+            .await?
+            .display_name
+            .as_deref(),
+        Some("Robert")
+    );
+
+    let deactivated = principals::deactivate_user(&op, &alice.id).await?;
+    assert!(!deactivated.active);
+    let access = principals::resolve_provisioned_access(&op, "docs", "alice@example.test")
+        .await?
+        .expect("alice is provisioned");
+    assert!(!access.active);
+
+    principals::delete_group(&op, &editors.id).await?;
+    let groups = principals::list_groups(&op, None).await?;
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].display_name, "Admins");
+    assert!(principals::get_group(&op, &editors.id).await.is_err());
+    Ok(())
+}
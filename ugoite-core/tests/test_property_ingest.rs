@@ -0,0 +1,135 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::form;
+use _ugoite_core::index;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::property_ingest::{self, DriftKind, PropertyIngestOptions, PropertyRow};
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::{json, Value};
+
+fn property_row(entry_id: &str, properties: Value) -> PropertyRow {
+    PropertyRow {
+        entry_id: entry_id.to_string(),
+        title: None,
+        tags: None,
+        properties: properties.as_object().cloned().unwrap_or_default(),
+    }
+}
+
+#[tokio::test]
+/// REQ-ENTRY-030
+async fn test_property_ingest_req_entry_030_batch_append_and_reconcile() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/ingest-space";
+    space::create_space(&op, "ingest-space", "/tmp").await?;
+    let metric = json!({
+        "name": "Metric",
+        "template": "# Metric\n\n## Region\n\n## Value\n",
+        "fields": {
+            "Region": {"type": "string"},
+            "Value": {"type": "number"},
+        },
+    });
+    form::upsert_form(&op, ws_path, &metric).await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "manual",
+        "---\nform: Metric\n---\n# manual\n\n## Region\nwest\n\n## Value\n7\n",
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+
+    let rows = vec![
+        property_row("m1", json!({"Region": "east", "Value": 1.5})),
+        property_row("m2", json!({"Region": "west", "Value": "2"})),
+        property_row("m3", json!({"Region": "north", "Value": "lots"})),
+        property_row("m1", json!({"Region": "south"})),
+        PropertyRow {
+            title: Some("Fourth metric".to_string()),
+            tags: Some(vec!["kpi".to_string()]),
+            ..property_row("m4", json!({"Region": "east", "Value": 4}))
+        },
+    ];
+    let report = property_ingest::ingest_entry_properties(
+        &op,
+        ws_path,
+        "Metric",
+        &rows,
+        &PropertyIngestOptions { batch_size: 2 },
+        "loader",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert_eq!(report.created, vec!["m1", "m2", "m4"]);
+    assert!(report.updated.is_empty());
+    assert_eq!(report.batches, 2);
+    let failed: Vec<&str> = report.failed.iter().map(|f| f.entry_id.as_str()).collect();
+    assert_eq!(failed, vec!["m3", "m1"]);
+    assert!(report.failed[1].error.contains("Duplicate entry_id"));
+
+    let m4 = entry::get_entry(&op, ws_path, "m4").await?;
+    assert_eq!(m4["title"], "Fourth metric");
+    assert_eq!(m4["tags"], json!(["kpi"]));
+    assert!(m4["content"].as_str().unwrap().contains("## Value\n4"));
+    let east = index::query_index(&op, ws_path, r#"{"Region": "east"}"#).await?;
+    let mut ids: Vec<&str> = east.iter().filter_map(|row| row["id"].as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["m1", "m4"]);
+
+    let update = property_ingest::ingest_entry_properties(
+        &op,
+        ws_path,
+        "Metric",
+        &[property_row("m2", json!({"Region": "west", "Value": 3}))],
+        &PropertyIngestOptions::default(),
+        "loader",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert_eq!(update.updated, vec!["m2"]);
+    let history = entry::get_entry_history(&op, ws_path, "m2").await?;
+    assert_eq!(history["revisions"].as_array().map(Vec::len), Some(2));
+
+    let clean = property_ingest::reconcile_entry_properties(&op, ws_path, None).await?;
+    assert_eq!(clean.entries_checked, 4);
+    assert!(clean.drift.is_empty(), "{:?}", clean.drift);
+
+    // A migration strategy stores its value verbatim, so whitespace the
+    // markdown cannot carry leaves the rows out of step with their markdown.
+    form::migrate_form(
+        &op,
+        ws_path,
+        &metric,
+        Some(json!({"Region": "  padded  "})),
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let drifted = property_ingest::reconcile_entry_properties(&op, ws_path, Some("Metric")).await?;
+    assert_eq!(drifted.entries_checked, 4);
+    let kinds: Vec<(&str, DriftKind, Vec<String>)> = drifted
+        .drift
+        .iter()
+        .map(|drift| {
+            (
+                drift.entry_id.as_str(),
+                drift.kind,
+                drift.properties.clone(),
+            )
+        })
+        .collect();
+    let region = vec!["Region".to_string()];
+    assert_eq!(
+        kinds,
+        vec![
+            ("m1", DriftKind::Markdown, region.clone()),
+            ("m2", DriftKind::Markdown, region.clone()),
+            ("m4", DriftKind::Markdown, region.clone()),
+            ("manual", DriftKind::Markdown, region),
+        ]
+    );
+    Ok(())
+}
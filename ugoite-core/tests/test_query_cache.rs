@@ -0,0 +1,152 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::query_cache::{self, QueryCacheConfig};
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::json;
+
+async fn seed_space(op: &opendal::Operator, space_id: &str) -> anyhow::Result<String> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{space_id}");
+    for (id, title) in [("entry-1", "Alpha"), ("entry-2", "Beta")] {
+        let content = format!("---\nform: Entry\n---\n# {title}\n\n## Body\ntext\n");
+        entry::create_entry(op, &ws_path, id, &content, "author", &FakeIntegrityProvider).await?;
+    }
+    Ok(ws_path)
+}
+
+#[tokio::test]
+/// REQ-IDX-011
+async fn test_query_cache_req_idx_011_hits_until_data_changes() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_space(&op, "cache-hits").await?;
+    let config = QueryCacheConfig::default();
+    let sql = "SELECT * FROM entries WHERE title = {{title}}";
+
+    let first =
+        query_cache::execute_sql_cached(&op, &ws_path, sql, &json!({"title": "Alpha"}), &config)
+            .await?;
+    assert!(!first.cached);
+    assert_eq!(first.rows.len(), 1);
+
+    let reformatted = "SELECT *\n  FROM   entries WHERE title = {{title}};";
+    let second = query_cache::execute_sql_cached(
+        &op,
+        &ws_path,
+        reformatted,
+        &json!({"title": "Alpha"}),
+        &config,
+    )
+    .await?;
+    assert!(second.cached);
+    assert_eq!(second.rows, first.rows);
+    assert_eq!(second.generation, first.generation);
+
+    let other_params =
+        query_cache::execute_sql_cached(&op, &ws_path, sql, &json!({"title": "Beta"}), &config)
+            .await?;
+    assert!(!other_params.cached);
+    assert_eq!(other_params.rows[0]["id"], json!("entry-2"));
+
+    entry::create_entry(
+        &op,
+        &ws_path,
+        "entry-3",
+        "---\nform: Entry\n---\n# Alpha\n\n## Body\nagain\n",
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let after_write =
+        query_cache::execute_sql_cached(&op, &ws_path, sql, &json!({"title": "Alpha"}), &config)
+            .await?;
+    assert!(!after_write.cached);
+    assert_ne!(after_write.generation, first.generation);
+    assert_eq!(after_write.rows.len(), 2);
+
+    let missing = query_cache::execute_sql_cached(&op, &ws_path, sql, &json!({}), &config)
+        .await
+        .unwrap_err();
+    assert!(missing.to_string().contains("UGOITE_SQL_VALIDATION"));
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-IDX-011
+async fn test_query_cache_req_idx_011_ttl_and_bounds() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_space(&op, "cache-bounds").await?;
+    let sql = "SELECT * FROM entries";
+
+    let expired = QueryCacheConfig {
+        ttl_ms: 0,
+        ..Default::default()
+    };
+    query_cache::execute_sql_cached(&op, &ws_path, sql, &json!(null), &expired).await?;
+    let again = query_cache::execute_sql_cached(&op, &ws_path, sql, &json!(null), &expired).await?;
+    assert!(!again.cached);
+
+    let tiny = QueryCacheConfig {
+        max_memory_bytes: 16,
+        ..Default::default()
+    };
+    let ordered = "SELECT * FROM entries ORDER BY title";
+    query_cache::execute_sql_cached(&op, &ws_path, ordered, &json!(null), &tiny).await?;
+    let too_big =
+        query_cache::execute_sql_cached(&op, &ws_path, ordered, &json!(null), &tiny).await?;
+    assert!(!too_big.cached);
+
+    let disk_only = QueryCacheConfig {
+        max_memory_bytes: 0,
+        max_disk_bytes: 1024 * 1024,
+        ..Default::default()
+    };
+    let limited = "SELECT * FROM entries LIMIT 1";
+    query_cache::execute_sql_cached(&op, &ws_path, limited, &json!(null), &disk_only).await?;
+    let from_disk =
+        query_cache::execute_sql_cached(&op, &ws_path, limited, &json!(null), &disk_only).await?;
+    assert!(from_disk.cached);
+    assert_eq!(from_disk.rows.len(), 1);
+
+    query_cache::clear_space_cache(&op, &ws_path).await?;
+    let cleared =
+        query_cache::execute_sql_cached(&op, &ws_path, limited, &json!(null), &disk_only).await?;
+    assert!(!cleared.cached);
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-IDX-011
+async fn test_query_cache_req_idx_011_scope_is_part_of_the_key() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_space(&op, "cache-scope").await?;
+    let config = QueryCacheConfig::default();
+    let sql = "SELECT * FROM entries";
+
+    let all = query_cache::execute_sql_cached_scoped(
+        &op,
+        &ws_path,
+        sql,
+        &json!(null),
+        &["Entry".to_string()],
+        false,
+        &config,
+    )
+    .await?;
+    assert_eq!(all.rows.len(), 2);
+    let none = query_cache::execute_sql_cached_scoped(
+        &op,
+        &ws_path,
+        sql,
+        &json!(null),
+        &[],
+        false,
+        &config,
+    )
+    .await?;
+    assert!(!none.cached);
+    assert!(none.rows.is_empty());
+    Ok(())
+}
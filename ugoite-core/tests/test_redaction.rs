@@ -0,0 +1,143 @@
+mod common;
+use _ugoite_core::audit;
+use _ugoite_core::entry;
+use _ugoite_core::form;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::redaction::{self, CustomPiiPattern, RedactionConfig, Redactor};
+use _ugoite_core::space;
+use _ugoite_core::tabular::TabularExportFormat;
+use common::setup_operator;
+
+#[tokio::test]
+/// REQ-SEC-013
+async fn test_redaction_req_sec_013_detects_and_redacts_text() -> anyhow::Result<()> {
+    let config = RedactionConfig {
+        custom: vec![CustomPiiPattern {
+            name: "employee_id".to_string(),
+            pattern: r"EMP-\d{6}".to_string(),
+        }],
+        ..RedactionConfig::default()
+    };
+    let redactor = Redactor::new(&config)?;
+    let text = "Mail jane.doe@example.co.jp or call +1 (555) 123-4567. \
+                Card 4111 1111 1111 1111, badge EMP-004211. \
+                Meeting on 2026-03-11 10:00, order 1234567890123 stays.";
+
+    let findings = redactor.scan_text("body", text);
+    let kinds: Vec<&str> = findings.iter().map(|f| f.kind.as_str()).collect();
+    assert_eq!(kinds, vec!["email", "phone", "credit_card", "employee_id"]);
+    assert_eq!(
+        &text[findings[0].start..findings[0].end],
+        "jane.doe@example.co.jp"
+    );
+    assert_eq!(findings[0].preview, "ja******************jp");
+    assert!(findings.iter().all(|f| f.path == "body"));
+
+    let redacted = redactor.redact_text(text);
+    assert_eq!(
+        redacted,
+        "Mail [REDACTED:email] or call [REDACTED:phone]. \
+         Card [REDACTED:credit_card], badge [REDACTED:employee_id]. \
+         Meeting on 2026-03-11 10:00, order 1234567890123 stays."
+    );
+
+    let value = serde_json::json!({"owner": "ops@example.com", "ids": ["EMP-123456", 42]});
+    let findings = redactor.scan_value("", &value);
+    let mut paths: Vec<&str> = findings.iter().map(|f| f.path.as_str()).collect();
+    paths.sort();
+    assert_eq!(paths, vec!["ids.0", "owner"]);
+    assert_eq!(
+        redactor.redact_value(&value),
+        serde_json::json!({"owner": "[REDACTED:email]", "ids": ["[REDACTED:employee_id]", 42]})
+    );
+
+    let only_custom = RedactionConfig {
+        emails: false,
+        phone_numbers: false,
+        credit_cards: false,
+        custom: vec![CustomPiiPattern {
+            name: "bad".to_string(),
+            pattern: "(".to_string(),
+        }],
+        replacement: "***".to_string(),
+    };
+    let err = Redactor::new(&only_custom).err().unwrap();
+    assert!(err.to_string().contains("Invalid PII pattern 'bad'"));
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-SEC-013
+async fn test_redaction_req_sec_013_entries_exports_and_audit_metadata() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "redaction-space", "/tmp").await?;
+    let ws_path = "spaces/redaction-space";
+    let integrity = FakeIntegrityProvider;
+    form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({
+            "name": "Contact",
+            "fields": {"Email": {"type": "string"}, "Notes": {"type": "markdown"}},
+        }),
+    )
+    .await?;
+    let markdown = "---\nform: Contact\n---\n# Call bob@example.org\n\n## Email\nbob@example.org\n\n## Notes\nMobile 090-1234-5678\n";
+    entry::create_entry(&op, ws_path, "bob", markdown, "alice", &integrity).await?;
+
+    let config = RedactionConfig::default();
+    let findings = redaction::scan_entry(&op, ws_path, "bob", &config).await?;
+    let mut located: Vec<(String, String)> =
+        findings.into_iter().map(|f| (f.path, f.kind)).collect();
+    located.sort();
+    assert_eq!(
+        located,
+        vec![
+            ("sections.Email".to_string(), "email".to_string()),
+            ("sections.Notes".to_string(), "phone".to_string()),
+            ("title".to_string(), "email".to_string()),
+        ]
+    );
+
+    let redacted = redaction::redact_entry(&op, ws_path, "bob", &config).await?;
+    assert_eq!(redacted["title"], "Call [REDACTED:email]");
+    assert_eq!(redacted["sections"]["Notes"], "Mobile [REDACTED:phone]");
+    assert!(!redacted["content"].as_str().unwrap().contains("bob@"));
+    // Stored content is untouched.
+    assert_eq!(
+        entry::get_entry(&op, ws_path, "bob").await?["sections"]["Email"],
+        "bob@example.org"
+    );
+
+    let mut csv = Vec::new();
+    form::export_tabular_redacted(
+        &op,
+        ws_path,
+        "Contact",
+        TabularExportFormat::Csv,
+        &mut csv,
+        &config,
+    )
+    .await?;
+    let csv = String::from_utf8(csv)?;
+    assert!(csv.contains("Call [REDACTED:email]"));
+    assert!(csv.contains("Mobile [REDACTED:phone]"));
+    assert!(!csv.contains("bob@example.org"));
+
+    let event = audit::append_audit_event(
+        &op,
+        "redaction-space",
+        &serde_json::json!({
+            "action": "member.invite",
+            "actor_user_id": "alice",
+            "metadata": {"invitee": "carol@example.net", "role": "viewer"},
+        }),
+        None,
+    )
+    .await?;
+    assert_eq!(event["metadata"]["invitee"], "[REDACTED:email]");
+    assert_eq!(event["metadata"]["role"], "viewer");
+
+    Ok(())
+}
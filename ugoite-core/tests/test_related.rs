@@ -0,0 +1,78 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::link;
+use _ugoite_core::related::{self, LinkProximity};
+use _ugoite_core::space;
+use common::setup_operator;
+use std::collections::HashMap;
+
+#[tokio::test]
+/// REQ-SRCH-007
+async fn test_related_req_srch_007_scores_tags_links_and_terms() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/related-space";
+    space::create_space(&op, "related-space", "/tmp").await?;
+    for (id, tags, body) in [
+        (
+            "solar",
+            "[energy, roadmap]",
+            "Solar panel rollout for the north campus",
+        ),
+        ("wind", "[energy]", "Wind turbine maintenance schedule"),
+        ("panels", "[]", "Supplier quote for solar panel rollout"),
+        ("hub", "[]", "Facilities overview"),
+        ("lunch", "[social]", "Team lunch menu"),
+    ] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: Entry\ntags: {tags}\n---\n# {id}\n\n## Body\n{body}\n"),
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    link::create_link(&op, ws_path, "solar", "hub", "reference", "l1").await?;
+    link::create_link(&op, ws_path, "wind", "hub", "reference", "l2").await?;
+
+    let related = related::get_related_entries(&op, ws_path, "solar", 10).await?;
+    let ids: Vec<&str> = related.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, vec!["hub", "wind", "panels"]);
+    assert_eq!(related[0].reasons.link, Some(LinkProximity::Direct));
+    assert_eq!(related[1].reasons.shared_tags, vec!["energy"]);
+    assert_eq!(related[1].reasons.link, Some(LinkProximity::TwoHop));
+    assert!(related[2]
+        .reasons
+        .shared_terms
+        .contains(&"rollout".to_string()));
+    assert!(related.iter().all(|r| r.score > 0.0));
+
+    let top = related::get_related_entries(&op, ws_path, "solar", 1).await?;
+    assert_eq!(top.len(), 1);
+
+    let embeddings = HashMap::from([
+        ("solar".to_string(), vec![1.0, 0.0]),
+        ("lunch".to_string(), vec![0.9, 0.1]),
+        ("wind".to_string(), vec![0.0, 1.0]),
+    ]);
+    let with_vectors =
+        related::get_related_entries_with_embeddings(&op, ws_path, "solar", 10, &embeddings)
+            .await?;
+    let lunch = with_vectors
+        .iter()
+        .find(|r| r.id == "lunch")
+        .expect("embedding similarity surfaces lunch");
+    assert!(lunch.reasons.embedding_similarity.unwrap() > 0.9);
+    assert!(related.iter().all(|r| r.id != "lunch"));
+
+    assert!(related::get_related_entries(&op, ws_path, "missing", 5)
+        .await
+        .is_err());
+    assert!(related::get_related_entries(&op, ws_path, "solar", 0)
+        .await
+        .is_err());
+    Ok(())
+}
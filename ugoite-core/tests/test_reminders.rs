@@ -0,0 +1,157 @@
+mod common;
+
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::reminders::{self, DueReminderOptions, ReminderStatus};
+use _ugoite_core::watchers::{self, WatchEventKind, WatchTarget};
+use _ugoite_core::{entry, form, space};
+use common::setup_operator;
+use serde_json::json;
+
+/// 2026-01-15T00:00:00Z.
+const NOW: f64 = 1_768_435_200.0;
+const DAY: f64 = 86_400.0;
+
+#[tokio::test]
+/// REQ-ENTRY-041
+async fn test_reminders_req_entry_041_due_reminders_and_snooze() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "planner", "/tmp").await?;
+    let ws_path = "spaces/planner";
+    let bad = form::upsert_form(
+        &op,
+        ws_path,
+        &json!({"name": "Broken", "fields": {"Due": {"type": "string", "reminder": true}}}),
+    )
+    .await
+    .expect_err("reminders need a date field");
+    assert!(bad.to_string().contains("must be a date or timestamp"));
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Task",
+            "template": "# Task\n\n## Due\n",
+            "fields": {"Due": {"type": "date", "reminder": {"lead_days": 1}}},
+        }),
+    )
+    .await?;
+    for (id, due) in [
+        ("rent", "2026-01-10"),
+        ("taxes", "2026-01-20"),
+        ("trip", "2026-03-01"),
+        ("someday", ""),
+    ] {
+        let content = format!("---\nform: Task\n---\n# {id}\n\n## Due\n{due}\n");
+        entry::create_entry(&op, ws_path, id, &content, "ann", &FakeIntegrityProvider).await?;
+    }
+
+    let at_now = |principal: Option<&str>, include_snoozed: bool| DueReminderOptions {
+        now: Some(NOW),
+        principal: principal.map(str::to_string),
+        include_snoozed,
+        ..DueReminderOptions::default()
+    };
+    let due = reminders::list_due_reminders(&op, ws_path, &at_now(None, false)).await?;
+    let listed: Vec<(&str, ReminderStatus)> = due
+        .iter()
+        .map(|reminder| (reminder.entry_id.as_str(), reminder.status))
+        .collect();
+    assert_eq!(
+        listed,
+        vec![
+            ("rent", ReminderStatus::Overdue),
+            ("taxes", ReminderStatus::Upcoming),
+        ]
+    );
+    assert_eq!(due[1].remind_at, due[1].due_at - DAY);
+
+    reminders::snooze_reminder(&op, ws_path, "bob", "taxes", "Due", NOW + DAY).await?;
+    assert!(
+        reminders::snooze_reminder(&op, ws_path, "bob", "taxes", "Title", NOW)
+            .await
+            .is_err()
+    );
+    let for_bob = reminders::list_due_reminders(&op, ws_path, &at_now(Some("bob"), false)).await?;
+    assert_eq!(for_bob.len(), 1);
+    let with_snoozed =
+        reminders::list_due_reminders(&op, ws_path, &at_now(Some("bob"), true)).await?;
+    assert_eq!(with_snoozed[1].snoozed_until, Some(NOW + DAY));
+    assert!(reminders::unsnooze_reminder(&op, ws_path, "bob", "taxes", "Due").await?);
+    assert!(!reminders::unsnooze_reminder(&op, ws_path, "bob", "taxes", "Due").await?);
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-041
+async fn test_reminders_req_entry_041_dispatch_to_watchers() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "desk", "/tmp").await?;
+    let ws_path = "spaces/desk";
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Task",
+            "template": "# Task\n\n## Due\n",
+            "fields": {"Due": {"type": "date", "reminder": {"lead_days": 1}}},
+        }),
+    )
+    .await?;
+    for (id, due) in [("rent", "2026-01-10"), ("taxes", "2026-01-16")] {
+        let content = format!("---\nform: Task\n---\n# {id}\n\n## Due\n{due}\n");
+        entry::create_entry(&op, ws_path, id, &content, "ann", &FakeIntegrityProvider).await?;
+    }
+    for principal in ["bob", "cat"] {
+        watchers::watch(&op, ws_path, principal, WatchTarget::Form("Task".into())).await?;
+    }
+    reminders::snooze_reminder(&op, ws_path, "cat", "rent", "Due", NOW + DAY / 2.0).await?;
+
+    // taxes is notified a day ahead, at 2026-01-15.
+    let first = reminders::dispatch_due_reminders(&op, ws_path, Some(NOW)).await?;
+    assert_eq!(
+        (first.dispatched, first.notifications, first.snoozed),
+        (2, 3, 1)
+    );
+    let reminders_for = |items: Vec<watchers::WatchNotification>| {
+        items
+            .into_iter()
+            .filter(|item| item.event == WatchEventKind::ReminderDue)
+            .map(|item| (item.entry_id, item.field.unwrap_or_default()))
+            .collect::<Vec<_>>()
+    };
+    let bob = reminders_for(watchers::list_notifications(&op, ws_path, "bob", None).await?);
+    assert_eq!(
+        bob,
+        vec![
+            ("rent".to_string(), "Due".to_string()),
+            ("taxes".to_string(), "Due".to_string()),
+        ]
+    );
+
+    let again = reminders::dispatch_due_reminders(&op, ws_path, Some(NOW + 60.0)).await?;
+    assert_eq!((again.dispatched, again.notifications), (0, 0));
+    // Once cat's snooze ends, cat gets the reminder too.
+    let after_snooze = reminders::dispatch_due_reminders(&op, ws_path, Some(NOW + DAY)).await?;
+    assert_eq!(
+        (after_snooze.dispatched, after_snooze.notifications),
+        (1, 1)
+    );
+    let cat = reminders_for(watchers::list_notifications(&op, ws_path, "cat", None).await?);
+    assert_eq!(cat.len(), 2);
+
+    // Moving the date arms the reminder again.
+    entry::update_entry(
+        &op,
+        ws_path,
+        "rent",
+        "---\nform: Task\n---\n# rent\n\n## Due\n2026-01-12\n",
+        None,
+        "ann",
+        None,
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let moved = reminders::dispatch_due_reminders(&op, ws_path, Some(NOW + DAY)).await?;
+    assert_eq!(moved.notifications, 2);
+    Ok(())
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::render::{self, RenderOptions};
+use _ugoite_core::space;
+use common::setup_operator;
+
+#[test]
+/// REQ-ENTRY-017
+fn test_render_req_entry_017_extensions_are_configurable() {
+    let markdown = "| a | b |\n|---|---|\n| 1 | 2 |\n\n- [x] done\n\nNote[^1] and $x^2$.\n\n[^1]: Footnote.\n\n```rust\nfn main() {}\n```\n";
+
+    let defaults = render::render_html(markdown, &RenderOptions::default());
+    assert!(defaults.contains("<table>"));
+    assert!(defaults.contains("type=\"checkbox\""));
+    assert!(defaults.contains("<code class=\"language-rust\">"));
+    assert!(!defaults.contains("footnote-definition"));
+    assert!(!defaults.contains("class=\"math"));
+
+    let options = RenderOptions {
+        tables: false,
+        footnotes: true,
+        math: true,
+        code_class_prefix: Some("hljs lang-".to_string()),
+        ..Default::default()
+    };
+    let configured = render::render_html(markdown, &options);
+    assert!(!configured.contains("<table>"));
+    assert!(configured.contains("footnote-definition"));
+    assert!(configured.contains("<span class=\"math math-inline\">x^2</span>"));
+    assert!(configured.contains("<code class=\"hljs lang-rust\">fn main() {}"));
+
+    let plain = render::render_html(
+        markdown,
+        &RenderOptions {
+            code_class_prefix: None,
+            ..Default::default()
+        },
+    );
+    assert!(plain.contains("<pre><code>fn main() {}"));
+}
+
+#[test]
+/// REQ-ENTRY-017
+fn test_render_req_entry_017_sanitizes_and_resolves_links() {
+    let markdown = "<script>alert(1)</script>\n\n[bad](javascript:alert(1)) [doc](<ugoite://entry/team notes#intro>) ![img](ugoite://asset/a1) [web](https://example.com)\n";
+    let options = RenderOptions {
+        entry_url: "/spaces/demo/entries/{id}".to_string(),
+        asset_url: "/spaces/demo/assets/{id}".to_string(),
+        ..Default::default()
+    };
+    let html = render::render_html(markdown, &options);
+    assert!(!html.contains("<script>"));
+    assert!(html.contains("&lt;script&gt;"));
+    assert!(!html.contains("javascript:"));
+    assert!(html.contains("href=\"/spaces/demo/entries/team%20notes#intro\""));
+    assert!(html.contains("src=\"/spaces/demo/assets/a1\""));
+    assert!(html.contains("href=\"https://example.com\""));
+}
+
+#[tokio::test]
+/// REQ-ENTRY-017
+async fn test_render_req_entry_017_render_entry_skips_frontmatter() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "render-space", "/tmp").await?;
+    let ws_path = "spaces/render-space";
+    entry::create_entry(
+        &op,
+        ws_path,
+        "note",
+        "---\nform: Entry\ntags: [draft]\n---\n# Plan\n\n## Body\nSee [other](ugoite://entry/other).\n",
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let html = render::render_entry_html(&op, ws_path, "note", &RenderOptions::default()).await?;
+    assert!(html.starts_with("<h1>Plan</h1>"));
+    assert!(!html.contains("form: Entry"));
+    assert!(html.contains("href=\"entries/other\""));
+    Ok(())
+}
+
+#[test]
+/// REQ-ENTRY-033
+fn test_render_req_entry_033_ast_follows_mdast() {
+    let markdown = "## Plan *now*\n\nSee [doc](ugoite://entry/d1) and `code`\nnext line.\n\n- [x] done\n- [ ] todo\n\n```rust main\nfn main() {}\n```\n\n| a | b |\n|:--|--:|\n| 1 | 2 |\n\n<div>raw</div>\n\n---\n";
+    let ast = render::render_ast(markdown, &RenderOptions::default());
+    assert_eq!(ast["type"], "root");
+    let children = ast["children"].as_array().expect("children");
+    let kinds: Vec<&str> = children
+        .iter()
+        .map(|node| node["type"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            "heading",
+            "paragraph",
+            "list",
+            "code",
+            "table",
+            "html",
+            "thematicBreak"
+        ]
+    );
+    assert_eq!(
+        children[0],
+        serde_json::json!({
+            "type": "heading",
+            "depth": 2,
+            "children": [
+                {"type": "text", "value": "Plan "},
+                {"type": "emphasis", "children": [{"type": "text", "value": "now"}]},
+            ],
+        })
+    );
+    let paragraph = &children[1]["children"];
+    assert_eq!(
+        paragraph[0],
+        serde_json::json!({"type": "text", "value": "See "})
+    );
+    assert_eq!(paragraph[1]["type"], "link");
+    assert_eq!(paragraph[1]["url"], "entries/d1");
+    assert_eq!(
+        paragraph[3],
+        serde_json::json!({"type": "inlineCode", "value": "code"})
+    );
+    assert_eq!(paragraph[4]["value"], "\nnext line.");
+    assert_eq!(children[2]["ordered"], false);
+    assert_eq!(children[2]["children"][0]["checked"], true);
+    assert_eq!(children[2]["children"][1]["checked"], false);
+    assert_eq!(
+        children[3],
+        serde_json::json!({"type": "code", "lang": "rust", "meta": "main", "value": "fn main() {}"})
+    );
+    assert_eq!(children[4]["align"], serde_json::json!(["left", "right"]));
+    assert_eq!(
+        children[4]["children"][1]["children"][1]["children"][0]["value"],
+        "2"
+    );
+    assert_eq!(children[5]["value"], "<div>raw</div>");
+}
+
+#[tokio::test]
+/// REQ-ENTRY-033
+async fn test_render_req_entry_033_get_entry_formats() -> anyhow::Result<()> {
+    use _ugoite_core::render::EntryFormat;
+
+    let op = setup_operator()?;
+    space::create_space(&op, "format-space", "/tmp").await?;
+    let ws_path = "spaces/format-space";
+    let markdown = "---\nform: Entry\n---\n# Plan\n\n## Body\n![chart](ugoite://asset/a1)\n\n<script>x</script>\n";
+    entry::create_entry(
+        &op,
+        ws_path,
+        "note",
+        markdown,
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let options = RenderOptions::default();
+
+    let record =
+        render::get_entry_formatted(&op, ws_path, "note", EntryFormat::Json, &options).await?;
+    assert_eq!(record, entry::get_entry(&op, ws_path, "note").await?);
+
+    let raw = render::get_entry_formatted(&op, ws_path, "note", "md".parse()?, &options).await?;
+    assert_eq!(raw["format"], "markdown");
+    assert_eq!(raw["title"], "Plan");
+    assert_eq!(raw["revision_id"], record["revision_id"]);
+    assert!(raw["content"]
+        .as_str()
+        .unwrap_or_default()
+        .starts_with("---\nform: Entry"));
+
+    let html =
+        render::get_entry_formatted(&op, ws_path, "note", EntryFormat::Html, &options).await?;
+    let html = html["content"].as_str().unwrap_or_default();
+    assert!(html.starts_with("<h1>Plan</h1>"));
+    assert!(html.contains("&lt;script&gt;"));
+    assert!(html.contains("src=\"assets/a1\""));
+
+    let ast = render::get_entry_formatted(&op, ws_path, "note", EntryFormat::Ast, &options).await?;
+    assert_eq!(ast["format"], "ast");
+    let body = &ast["content"]["children"];
+    assert_eq!(body[0]["type"], "heading");
+    assert_eq!(body[0]["depth"], 1);
+    let image = &body[2]["children"][0];
+    assert_eq!(
+        image,
+        &serde_json::json!({"type": "image", "url": "assets/a1", "title": null, "alt": "chart"})
+    );
+
+    assert!("pdf".parse::<EntryFormat>().is_err());
+    Ok(())
+}
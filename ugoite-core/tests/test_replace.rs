@@ -0,0 +1,256 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::entry_metadata;
+use _ugoite_core::field_crypto;
+use _ugoite_core::form;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::replace::{self, ReplaceOptions};
+use _ugoite_core::space;
+use common::setup_operator;
+
+fn options(find: &str, replace: &str) -> ReplaceOptions {
+    serde_json::from_value(serde_json::json!({"find": find, "replace": replace}))
+        .expect("valid replace options")
+}
+
+#[tokio::test]
+/// REQ-ENTRY-028
+async fn test_replace_req_entry_028_find_replace_and_undo() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/replace-space";
+    space::create_space(&op, "replace-space", "/tmp").await?;
+    for name in ["Note", "Meeting"] {
+        form::upsert_form(
+            &op,
+            ws_path,
+            &serde_json::json!({
+                "name": name,
+                "template": format!("# {name}\n\n## Body\n"),
+                "fields": {"Body": {"type": "markdown"}},
+            }),
+        )
+        .await?;
+    }
+    for (id, form_name, body) in [
+        ("n1", "Note", "Acme Corp signed.\nCall acme corp tomorrow."),
+        ("n2", "Note", "Nothing relevant here."),
+        (
+            "m1",
+            "Meeting",
+            "Acme Corp attended. Ticket ABC-12 and ABC-7.",
+        ),
+    ] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: {form_name}\n---\n# {id}\n\n## Body\n{body}\n"),
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+
+    let preview = replace::preview_replace(&op, ws_path, &options("Acme Corp", "Initech")).await?;
+    let ids: Vec<&str> = preview.iter().map(|p| p.entry_id.as_str()).collect();
+    assert_eq!(ids, vec!["m1", "n1"]);
+    let note = &preview[1];
+    assert_eq!(note.matches.len(), 1);
+    assert_eq!(note.matches[0].after, " signed.");
+    assert_eq!(note.matches[0].replacement, "Initech");
+    assert_eq!(
+        entry::get_entry_content(&op, ws_path, "n1")
+            .await?
+            .markdown
+            .matches("Acme Corp")
+            .count(),
+        1
+    );
+
+    let mut scoped = options("acme corp", "Initech");
+    scoped.case_sensitive = false;
+    scoped.forms = vec!["note".to_string()];
+    let report =
+        replace::apply_replace(&op, ws_path, &scoped, "editor", &FakeIntegrityProvider).await?;
+    assert_eq!(report.replaced.len(), 1);
+    assert_eq!(report.replaced[0].entry_id, "n1");
+    assert_eq!(report.replaced[0].replacements, 2);
+    let content = entry::get_entry_content(&op, ws_path, "n1").await?;
+    assert!(content
+        .markdown
+        .contains("Initech signed.\nCall Initech tomorrow."));
+    assert!(content.markdown.contains("form: Note"));
+    let record = entry_metadata::get_entry_metadata_namespace(
+        &op,
+        ws_path,
+        "n1",
+        replace::REPLACE_METADATA_NAMESPACE,
+    )
+    .await?;
+    assert_eq!(record["job_id"], report.job_id.as_str());
+    let meeting = entry::get_entry_content(&op, ws_path, "m1").await?;
+    assert!(meeting.markdown.contains("Acme Corp attended"));
+
+    let mut tickets = options(r"ABC-(\d+)", "TKT-$1");
+    tickets.regex = true;
+    let preview = replace::preview_replace(&op, ws_path, &tickets).await?;
+    assert_eq!(preview[0].matches[1].replacement, "TKT-7");
+    let ticket_job =
+        replace::apply_replace(&op, ws_path, &tickets, "editor", &FakeIntegrityProvider).await?;
+    let meeting = entry::get_entry_content(&op, ws_path, "m1").await?;
+    assert!(meeting.markdown.contains("Ticket TKT-12 and TKT-7."));
+
+    let undone = replace::undo_replace(
+        &op,
+        ws_path,
+        &report.job_id,
+        "editor",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert_eq!(undone.reverted, vec!["n1"]);
+    let restored = entry::get_entry_content(&op, ws_path, "n1").await?;
+    assert!(restored
+        .markdown
+        .contains("Acme Corp signed.\nCall acme corp tomorrow."));
+    assert!(replace::undo_replace(
+        &op,
+        ws_path,
+        &report.job_id,
+        "editor",
+        &FakeIntegrityProvider
+    )
+    .await
+    .is_err());
+
+    entry::update_entry(
+        &op,
+        ws_path,
+        "m1",
+        "---\nform: Meeting\n---\n# m1\n\n## Body\nRewritten by hand.\n",
+        None,
+        "author",
+        None,
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let skipped = replace::undo_replace(
+        &op,
+        ws_path,
+        &ticket_job.job_id,
+        "editor",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert_eq!(skipped.skipped, vec!["m1"]);
+    assert!(skipped.reverted.is_empty());
+
+    let mut empty = options("x*", "y");
+    empty.regex = true;
+    assert!(replace::preview_replace(&op, ws_path, &empty)
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-028
+async fn test_replace_req_entry_028_secret_fields_are_never_rewritten() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/replace-secrets";
+    space::create_space(&op, "replace-secrets", "/tmp").await?;
+    form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({
+            "name": "Credential",
+            "fields": {
+                "Service": {"type": "string"},
+                "ApiKey": {"type": "string", "secret": true},
+            },
+        }),
+    )
+    .await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "billing",
+        "---\nform: Credential\n---\n# Billing\n\n## Service\nsecret-service\n\n## ApiKey\nsk-live-123456\n",
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+
+    // The masked placeholder matches "secret" but is not offered for replacement.
+    let find = options("secret", "public");
+    let preview = replace::preview_replace(&op, ws_path, &find).await?;
+    assert_eq!(preview.len(), 1);
+    assert_eq!(preview[0].matches.len(), 1);
+    assert_eq!(preview[0].matches[0].after, "-service");
+
+    let report =
+        replace::apply_replace(&op, ws_path, &find, "editor", &FakeIntegrityProvider).await?;
+    assert!(report.failed.is_empty());
+    assert_eq!(report.replaced[0].replacements, 1);
+
+    // A pattern that only touches the secret section changes nothing.
+    let mut regex = options(r"\n\[secret\]", "\nleaked");
+    regex.regex = true;
+    let report =
+        replace::apply_replace(&op, ws_path, &regex, "editor", &FakeIntegrityProvider).await?;
+    assert!(report.replaced.is_empty());
+
+    let revealed = entry::get_entry_with_secrets(
+        &op,
+        ws_path,
+        "billing",
+        &[field_crypto::SECRET_READ_SCOPE.to_string()],
+    )
+    .await?;
+    assert_eq!(revealed["sections"]["Service"], "public-service");
+    assert_eq!(revealed["sections"]["ApiKey"], "sk-live-123456");
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-028
+async fn test_replace_req_entry_028_metadata_failure_is_reported_per_entry() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/replace-metadata";
+    space::create_space(&op, "replace-metadata", "/tmp").await?;
+    for id in ["a", "b"] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: Entry\n---\n# {id}\n\n## Body\nAcme Corp\n"),
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    op.write(&format!("{ws_path}/entry_metadata/a.json"), "not json")
+        .await?;
+
+    let report = replace::apply_replace(
+        &op,
+        ws_path,
+        &options("Acme Corp", "Initech"),
+        "editor",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].entry_id, "a");
+    assert!(report.failed[0]
+        .error
+        .contains("could not record the replace job"));
+    let replaced: Vec<&str> = report
+        .replaced
+        .iter()
+        .map(|r| r.entry_id.as_str())
+        .collect();
+    assert_eq!(replaced, vec!["b"]);
+    Ok(())
+}
@@ -0,0 +1,76 @@
+mod common;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::result_buffer::ResultBuffer;
+use _ugoite_core::{entry, space, sql_session};
+use common::setup_operator;
+use serde_json::{json, Value};
+
+fn row(idx: usize) -> Value {
+    json!({"id": format!("row-{idx}"), "body": "x".repeat(64)})
+}
+
+#[tokio::test]
+/// REQ-STO-015
+async fn test_result_buffer_req_sto_015_spills_over_memory_cap() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let mut buffer = ResultBuffer::new(&op, "scratch/spill", 256);
+    buffer.extend((0..20).map(row)).await?;
+    let results = buffer.finish();
+
+    assert_eq!(results.len(), 20);
+    assert!(results.spilled_batches() >= 5);
+    assert!(op.exists("scratch/spill/000000.jsonl").await?);
+
+    let page = results.read_range(3, 9).await?;
+    let ids: Vec<&str> = page.iter().map(|r| r["id"].as_str().unwrap()).collect();
+    let expected: Vec<String> = (3..12).map(|idx| format!("row-{idx}")).collect();
+    assert_eq!(ids, expected);
+    assert_eq!(results.read_range(18, 10).await?.len(), 2);
+    assert!(results.read_range(25, 10).await?.is_empty());
+
+    let mut streamed = Vec::new();
+    for index in 0..results.batch_count() {
+        streamed.extend(results.read_batch(index).await?);
+    }
+    assert_eq!(streamed, (0..20).map(row).collect::<Vec<_>>());
+    assert!(results.read_batch(results.batch_count()).await.is_err());
+
+    assert_eq!(results.into_rows().await?.len(), 20);
+    assert!(!op.exists("scratch/spill/000000.jsonl").await?);
+
+    // Under the cap nothing touches storage.
+    let mut small = ResultBuffer::new(&op, "scratch/small", 1 << 20);
+    small.extend((0..3).map(row)).await?;
+    let small = small.finish();
+    assert_eq!(small.spilled_batches(), 0);
+    assert_eq!(small.read_range(1, 5).await?.len(), 2);
+    small.cleanup().await?;
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-015
+async fn test_result_buffer_req_sto_015_sql_session_rows_stream_back() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "spill-sql", "/tmp").await?;
+    let ws_path = "spaces/spill-sql";
+    for idx in 0..3 {
+        entry::create_entry(
+            &op,
+            ws_path,
+            &format!("entry-{idx}"),
+            &format!("---\nform: Entry\n---\n# Entry {idx}\n\n## Body\ntext\n"),
+            "alice",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    let session = sql_session::create_sql_session(&op, ws_path, "SELECT * FROM entries").await?;
+    let session_id = session["id"].as_str().unwrap();
+
+    let results = sql_session::open_sql_session_rows(&op, ws_path, session_id).await?;
+    assert_eq!(results.len(), 3);
+    let all = sql_session::get_sql_session_rows_all(&op, ws_path, session_id).await?;
+    assert_eq!(results.into_rows().await?, all);
+    Ok(())
+}
@@ -0,0 +1,175 @@
+mod common;
+use _ugoite_core::audit::{self, AuditListOptions};
+use _ugoite_core::entry;
+use _ugoite_core::form;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::legal_hold::{self, HoldTargetType, LEGAL_HOLD_SCOPE};
+use _ugoite_core::retention::{self, RetentionAction, RetentionOptions, RetentionPolicy};
+use _ugoite_core::space;
+use common::setup_operator;
+
+const DAY: f64 = 86_400.0;
+
+async fn seed_log_form(
+    op: &opendal::Operator,
+    space_id: &str,
+    action: &str,
+) -> anyhow::Result<String> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{space_id}");
+    let integrity = FakeIntegrityProvider;
+    form::upsert_form(
+        op,
+        &ws_path,
+        &serde_json::json!({
+            "name": "Log",
+            "fields": {"Message": {"type": "string"}},
+            "retention": {"max_age_days": 30, "action": action},
+        }),
+    )
+    .await?;
+    for (entry_id, tags) in [("build-1", "[]"), ("build-2", "[]"), ("keep", "[pinned]")] {
+        let markdown =
+            format!("---\nform: Log\ntags: {tags}\n---\n# {entry_id}\n\n## Message\nok\n");
+        entry::create_entry(op, &ws_path, entry_id, &markdown, "ci", &integrity).await?;
+    }
+    Ok(ws_path)
+}
+
+#[tokio::test]
+/// REQ-FORM-013
+async fn test_retention_req_form_013_archive_policy_skips_pinned() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_log_form(&op, "retention-archive", "archive").await?;
+    let integrity = FakeIntegrityProvider;
+
+    let form_def = form::get_form(&op, &ws_path, "Log").await?;
+    assert_eq!(
+        retention::retention_policy(&form_def)?,
+        Some(RetentionPolicy {
+            max_age_days: 30,
+            action: RetentionAction::Archive,
+        })
+    );
+
+    // Nothing is old enough yet.
+    let report =
+        retention::enforce_retention(&op, &ws_path, &RetentionOptions::default(), &integrity)
+            .await?;
+    assert!(report.archived.is_empty());
+
+    let later = entry::get_entry(&op, &ws_path, "build-1").await?["created_at"]
+        .as_f64()
+        .unwrap()
+        + 31.0 * DAY;
+    let options = RetentionOptions {
+        dry_run: true,
+        as_of: Some(later),
+    };
+    let report = retention::enforce_retention(&op, &ws_path, &options, &integrity).await?;
+    assert_eq!(report.archived, vec!["build-1", "build-2"]);
+    assert_eq!(report.skipped_pinned, vec!["keep"]);
+    let tags = &entry::get_entry(&op, &ws_path, "build-1").await?["tags"];
+    assert!(!tags.to_string().contains(retention::ARCHIVED_TAG));
+
+    let options = RetentionOptions {
+        dry_run: false,
+        as_of: Some(later),
+    };
+    let report = retention::enforce_retention(&op, &ws_path, &options, &integrity).await?;
+    assert_eq!(report.archived, vec!["build-1", "build-2"]);
+    let archived = entry::get_entry(&op, &ws_path, "build-1").await?;
+    assert!(archived["tags"]
+        .as_array()
+        .unwrap()
+        .contains(&serde_json::json!(retention::ARCHIVED_TAG)));
+    let history = entry::get_entry_history(&op, &ws_path, "build-1").await?;
+    assert_eq!(history["revisions"].as_array().unwrap().len(), 2);
+
+    // A second sweep has nothing left to archive.
+    let report = retention::enforce_retention(&op, &ws_path, &options, &integrity).await?;
+    assert!(report.archived.is_empty());
+
+    let events = audit::list_audit_events(
+        &op,
+        "retention-archive",
+        AuditListOptions {
+            action: Some("retention.archive".to_string()),
+            ..AuditListOptions::default()
+        },
+    )
+    .await?;
+    assert_eq!(events["total"], 2);
+    let sweeps = audit::list_audit_events(
+        &op,
+        "retention-archive",
+        AuditListOptions {
+            action: Some("retention.enforce".to_string()),
+            ..AuditListOptions::default()
+        },
+    )
+    .await?;
+    assert_eq!(sweeps["total"], 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-013
+async fn test_retention_req_form_013_delete_policy_respects_legal_hold() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_log_form(&op, "retention-delete", "delete").await?;
+    let integrity = FakeIntegrityProvider;
+    legal_hold::place_legal_hold(
+        &op,
+        &ws_path,
+        HoldTargetType::Entry,
+        "build-2",
+        "Incident review",
+        "counsel",
+        &[LEGAL_HOLD_SCOPE.to_string()],
+    )
+    .await?;
+
+    let later = entry::get_entry(&op, &ws_path, "build-1").await?["created_at"]
+        .as_f64()
+        .unwrap()
+        + 31.0 * DAY;
+    let options = RetentionOptions {
+        dry_run: false,
+        as_of: Some(later),
+    };
+    let report = retention::enforce_retention(&op, &ws_path, &options, &integrity).await?;
+    assert_eq!(report.deleted, vec!["build-1"]);
+    assert_eq!(report.skipped_held, vec!["build-2"]);
+    assert_eq!(report.skipped_pinned, vec!["keep"]);
+    assert!(entry::get_entry(&op, &ws_path, "build-1").await.is_err());
+    assert!(entry::get_entry(&op, &ws_path, "build-2").await.is_ok());
+
+    let err = form::upsert_form(
+        &op,
+        &ws_path,
+        &serde_json::json!({
+            "name": "Log",
+            "fields": {"Message": {"type": "string"}},
+            "retention": {"max_age_days": 0, "action": "delete"},
+        }),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("max_age_days"));
+    let err = form::upsert_form(
+        &op,
+        &ws_path,
+        &serde_json::json!({
+            "name": "Log",
+            "fields": {"Message": {"type": "string"}},
+            "retention": {"max_age_days": 7, "action": "shred"},
+        }),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Invalid retention policy"));
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+mod common;
+use _ugoite_core::entry;
+use _ugoite_core::form;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::legal_hold::{self, HoldTargetType, LEGAL_HOLD_SCOPE};
+use _ugoite_core::revision_delta;
+use _ugoite_core::space;
+use common::setup_operator;
+use opendal::services::Fs;
+use opendal::Operator;
+use serde_json::json;
+use tempfile::tempdir;
+
+fn journal(form: &str, body: &str) -> String {
+    format!("---\nform: {form}\n---\n# Journal\n\n## Body\n{body}\n")
+}
+
+fn long_body(edit: usize) -> String {
+    let mut lines: Vec<String> = (0..400)
+        .map(|idx| format!("Line {idx} of a long running journal entry."))
+        .collect();
+    lines[edit * 7] = format!("Edited line {edit}.");
+    lines.join("\n")
+}
+
+/// Write `edits` revisions of one entry and return the body of each revision in order.
+async fn write_revisions(
+    op: &opendal::Operator,
+    ws_path: &str,
+    form_name: &str,
+    entry_id: &str,
+    edits: usize,
+) -> anyhow::Result<Vec<String>> {
+    let integrity = FakeIntegrityProvider;
+    let mut bodies = vec![long_body(0)];
+    entry::create_entry(
+        op,
+        ws_path,
+        entry_id,
+        &journal(form_name, &bodies[0]),
+        "alice",
+        &integrity,
+    )
+    .await?;
+    for edit in 1..edits {
+        let body = long_body(edit);
+        let current = entry::get_entry_content(op, ws_path, entry_id).await?;
+        entry::update_entry(
+            op,
+            ws_path,
+            entry_id,
+            &journal(form_name, &body),
+            Some(&current.revision_id),
+            "alice",
+            None,
+            &integrity,
+        )
+        .await?;
+        bodies.push(body);
+    }
+    Ok(bodies)
+}
+
+async fn revision_bodies(
+    op: &opendal::Operator,
+    ws_path: &str,
+    entry_id: &str,
+) -> anyhow::Result<Vec<String>> {
+    let history = entry::get_entry_history(op, ws_path, entry_id).await?;
+    let mut bodies = Vec::new();
+    for rev in history["revisions"].as_array().unwrap() {
+        let revision_id = rev["revision_id"].as_str().unwrap();
+        let revision = entry::get_entry_revision(op, ws_path, entry_id, revision_id).await?;
+        bodies.push(revision["fields"]["Body"].as_str().unwrap().to_string());
+    }
+    Ok(bodies)
+}
+
+fn revisions_bytes(root: &std::path::Path, ws_path: &str, form_name: &str) -> anyhow::Result<u64> {
+    let dir = root
+        .join(ws_path)
+        .join("forms")
+        .join(form_name)
+        .join("revisions/data");
+    let mut total = 0;
+    for item in std::fs::read_dir(dir)? {
+        total += item?.metadata()?.len();
+    }
+    Ok(total)
+}
+
+#[tokio::test]
+/// REQ-STO-014
+async fn test_revision_delta_req_sto_014_deltas_reconstruct_and_shrink() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let op = Operator::new(Fs::default().root(dir.path().to_string_lossy().as_ref()))?.finish();
+    space::create_space(&op, "delta-store", dir.path().to_string_lossy().as_ref()).await?;
+    let ws_path = "spaces/delta-store";
+    for (name, interval) in [("Journal", 4), ("FullJournal", 1)] {
+        form::upsert_form(
+            &op,
+            ws_path,
+            &json!({
+                "name": name,
+                "fields": {"Body": {"type": "markdown"}},
+                "revision_snapshot_interval": interval,
+            }),
+        )
+        .await?;
+    }
+    let journal_def = form::get_form(&op, ws_path, "Journal").await?;
+    assert_eq!(revision_delta::snapshot_interval(&journal_def), 4);
+
+    let written = write_revisions(&op, ws_path, "Journal", "daily", 6).await?;
+    assert_eq!(revision_bodies(&op, ws_path, "daily").await?, written);
+    let content = entry::get_entry_content(&op, ws_path, "daily").await?;
+    assert!(content.markdown.contains("Edited line 5."));
+
+    write_revisions(&op, ws_path, "FullJournal", "full", 6).await?;
+    let delta_bytes = revisions_bytes(dir.path(), ws_path, "Journal")?;
+    let full_bytes = revisions_bytes(dir.path(), ws_path, "FullJournal")?;
+    assert!(
+        delta_bytes < full_bytes,
+        "delta storage {delta_bytes} should be smaller than full storage {full_bytes}"
+    );
+
+    let err = form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Broken",
+            "fields": {"Body": {"type": "markdown"}},
+            "revision_snapshot_interval": 0,
+        }),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("revision_snapshot_interval"));
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-014
+async fn test_revision_delta_req_sto_014_migration_preserves_history() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "delta-migrate", "/tmp").await?;
+    let ws_path = "spaces/delta-migrate";
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({"name": "Evidence", "fields": {"Body": {"type": "markdown"}}}),
+    )
+    .await?;
+
+    let written = write_revisions(&op, ws_path, "Entry", "notes", 5).await?;
+    write_revisions(&op, ws_path, "Evidence", "exhibit", 2).await?;
+    legal_hold::place_legal_hold(
+        &op,
+        ws_path,
+        HoldTargetType::Entry,
+        "exhibit",
+        "Litigation",
+        "counsel",
+        &[LEGAL_HOLD_SCOPE.to_string()],
+    )
+    .await?;
+
+    let report = revision_delta::migrate_revision_storage(&op, ws_path).await?;
+    assert!(report.migrated_forms.contains(&"Entry".to_string()));
+    assert_eq!(report.held_forms, vec!["Evidence".to_string()]);
+    assert!(report.revisions >= 5);
+    assert!(report.delta_revisions >= 4);
+    assert_eq!(revision_bodies(&op, ws_path, "notes").await?, written);
+    assert_eq!(revision_bodies(&op, ws_path, "exhibit").await?.len(), 2);
+
+    // New revisions keep chaining onto the migrated history.
+    let current = entry::get_entry_content(&op, ws_path, "notes").await?;
+    entry::update_entry(
+        &op,
+        ws_path,
+        "notes",
+        &journal("Entry", "Short replacement"),
+        Some(&current.revision_id),
+        "bob",
+        None,
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    let bodies = revision_bodies(&op, ws_path, "notes").await?;
+    assert_eq!(bodies.len(), 6);
+    assert_eq!(bodies[5], "Short replacement");
+
+    Ok(())
+}
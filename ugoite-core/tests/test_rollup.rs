@@ -0,0 +1,149 @@
+mod common;
+
+use _ugoite_core::clock;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::{entry, form, index, space};
+use common::setup_operator;
+use serde_json::{json, Value};
+
+fn project_form(rollups: Value) -> Value {
+    let mut fields = json!({"Owner": {"type": "string"}});
+    if let (Some(map), Some(extra)) = (fields.as_object_mut(), rollups.as_object()) {
+        map.extend(extra.clone());
+    }
+    json!({"name": "Project", "template": "# Project\n\n## Owner\n", "fields": fields})
+}
+
+fn task(id: &str, project: &str, points: u32, status: &str) -> String {
+    format!(
+        "---\nform: Task\n---\n# {id}\n\n## Project\n{project}\n\n## Points\n{points}\n\n## Status\n{status}\n"
+    )
+}
+
+async fn computed(op: &opendal::Operator, ws_path: &str, id: &str) -> anyhow::Result<Value> {
+    Ok(entry::get_entry(op, ws_path, id).await?["computed"].clone())
+}
+
+#[tokio::test]
+/// REQ-FORM-017
+async fn test_rollup_req_form_017_aggregates_related_entries() -> anyhow::Result<()> {
+    clock::frozen(17, async {
+        let op = setup_operator()?;
+        space::create_space(&op, "roadmap", "/tmp").await?;
+        let ws_path = "spaces/roadmap";
+        let integrity = FakeIntegrityProvider;
+        form::upsert_form(&op, ws_path, &project_form(json!({}))).await?;
+        form::upsert_form(
+            &op,
+            ws_path,
+            &json!({
+                "name": "Task",
+                "template": "# Task\n\n## Project\n\n## Points\n\n## Status\n",
+                "fields": {
+                    "Project": {"type": "row_reference", "target_form": "Project"},
+                    "Points": {"type": "integer"},
+                    "Status": {"type": "string"},
+                },
+            }),
+        )
+        .await?;
+        for id in ["apollo", "gemini"] {
+            let content = format!("---\nform: Project\n---\n# {id}\n\n## Owner\nann\n");
+            entry::create_entry(&op, ws_path, id, &content, "ann", &integrity).await?;
+        }
+        entry::create_entry(&op, ws_path, "t1", &task("t1", "apollo", 3, "todo"), "ann", &integrity)
+            .await?;
+        entry::create_entry(&op, ws_path, "t2", &task("t2", "apollo", 5, "done"), "ann", &integrity)
+            .await?;
+
+        for (rollup, message) in [
+            (
+                json!({"Tasks": {"type": "rollup", "source_form": "Task", "relation_field": "Status", "aggregate": "count"}}),
+                "must be a row_reference",
+            ),
+            (
+                json!({"Points": {"type": "rollup", "source_form": "Task", "relation_field": "Project", "property": "Status", "aggregate": "sum"}}),
+                "only sum a numeric field",
+            ),
+            (
+                json!({"Points": {"type": "rollup", "source_form": "Task", "relation_field": "Project", "aggregate": "latest"}}),
+                "requires a property",
+            ),
+        ] {
+            let err = form::upsert_form(&op, ws_path, &project_form(rollup))
+                .await
+                .expect_err("invalid rollup");
+            assert!(err.to_string().contains(message), "{err}");
+        }
+
+        // Adding rollups backfills them from the existing tasks.
+        form::upsert_form(
+            &op,
+            ws_path,
+            &project_form(json!({
+                "Tasks": {"type": "rollup", "source_form": "Task", "relation_field": "Project", "aggregate": "count"},
+                "Points": {"type": "rollup", "source_form": "Task", "relation_field": "Project", "property": "Points", "aggregate": "sum"},
+                "Last status": {"type": "rollup", "source_form": "Task", "relation_field": "Project", "property": "Status", "aggregate": "latest"},
+            })),
+        )
+        .await?;
+        assert_eq!(
+            computed(&op, ws_path, "apollo").await?,
+            json!({"Tasks": 2, "Points": 8, "Last status": "done"})
+        );
+        assert_eq!(
+            computed(&op, ws_path, "gemini").await?,
+            json!({"Tasks": 0, "Points": 0, "Last status": null})
+        );
+
+        // Writes update the rollups of the projects a task leaves and joins.
+        entry::update_entry(
+            &op,
+            ws_path,
+            "t1",
+            &task("t1", "gemini", 3, "review"),
+            None,
+            "ann",
+            None,
+            &integrity,
+        )
+        .await?;
+        entry::create_entry(&op, ws_path, "t3", &task("t3", "gemini", 2, "todo"), "ann", &integrity)
+            .await?;
+        assert_eq!(
+            computed(&op, ws_path, "apollo").await?,
+            json!({"Tasks": 1, "Points": 5, "Last status": "done"})
+        );
+        assert_eq!(
+            computed(&op, ws_path, "gemini").await?,
+            json!({"Tasks": 2, "Points": 5, "Last status": "todo"})
+        );
+        entry::delete_entry(&op, ws_path, "t2", false).await?;
+        assert_eq!(
+            computed(&op, ws_path, "apollo").await?,
+            json!({"Tasks": 0, "Points": 0, "Last status": null})
+        );
+
+        let written = entry::update_entry(
+            &op,
+            ws_path,
+            "gemini",
+            "---\nform: Project\n---\n# gemini\n\n## Owner\nann\n\n## Tasks\n9\n",
+            None,
+            "ann",
+            None,
+            &integrity,
+        )
+        .await
+        .expect_err("rollups are computed");
+        assert!(written.to_string().contains("is computed"));
+
+        index::reindex_all(&op, ws_path).await?;
+        assert_eq!(
+            computed(&op, ws_path, "gemini").await?,
+            json!({"Tasks": 2, "Points": 5, "Last status": "todo"})
+        );
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,60 @@
+use _ugoite_core::runtime::{self, RuntimeConfig};
+use std::time::Duration;
+
+#[test]
+/// REQ-OPS-042
+fn test_runtime_req_ops_042_config_sizes_runtime() -> anyhow::Result<()> {
+    let config = RuntimeConfig {
+        worker_threads: Some(2),
+        max_blocking_threads: Some(4),
+    };
+    let rt = config.build()?;
+    assert_eq!(rt.metrics().num_workers(), 2);
+
+    let invalid = RuntimeConfig {
+        worker_threads: Some(0),
+        max_blocking_threads: None,
+    };
+    assert!(invalid.build().is_err());
+    assert!(runtime::configure(invalid).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-OPS-042
+async fn test_runtime_req_ops_042_metrics_and_graceful_drain() -> anyhow::Result<()> {
+    let ok = runtime::begin_task("test.succeeds")?;
+    ok.finish(true);
+    let failed = runtime::begin_task("test.fails")?;
+    failed.finish(false);
+    drop(runtime::begin_task("test.fails")?);
+
+    let metrics = runtime::metrics();
+    assert_eq!(metrics.tasks["test.succeeds"].succeeded, 1);
+    assert_eq!(metrics.tasks["test.fails"].started, 2);
+    assert_eq!(metrics.tasks["test.fails"].failed, 1);
+    assert_eq!(metrics.tasks["test.fails"].cancelled, 1);
+    assert_eq!(metrics.tasks["test.fails"].in_flight, 0);
+
+    // Once a task has started the runtime can no longer be resized.
+    assert!(runtime::configure(RuntimeConfig::default())
+        .unwrap_err()
+        .to_string()
+        .contains("already running"));
+
+    let slow = runtime::begin_task("test.slow")?;
+    let worker = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        slow.finish(true);
+    });
+    assert!(!runtime::shutdown(Duration::from_millis(1)).await);
+    assert!(runtime::metrics().shutting_down);
+    assert!(runtime::begin_task("test.rejected").is_err());
+    assert!(runtime::shutdown(Duration::from_secs(5)).await);
+    worker.await?;
+    assert_eq!(runtime::metrics().tasks["test.slow"].succeeded, 1);
+
+    runtime::resume();
+    runtime::begin_task("test.resumed")?.finish(true);
+    Ok(())
+}
@@ -1,10 +1,13 @@
 mod common;
 
-use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
 use _ugoite_core::sample_data::{
-    create_sample_space, create_sample_space_job, get_sample_space_job, list_sample_scenarios,
-    SampleDataJob, SampleDataOptions, SampleJobStatus,
+    anonymize_space, cancel_sample_space_job, create_sample_space, create_sample_space_job,
+    get_sample_space_job, list_sample_scenarios, AnonymizeRules, SampleDataJob, SampleDataOptions,
+    SampleJobStatus,
 };
+use _ugoite_core::{asset, entry, index, link, saved_sql};
+use _ugoite_core::{form, space};
 use common::setup_operator;
 use tempfile::TempDir;
 use tokio::time::{sleep, Duration};
@@ -23,6 +26,7 @@ async fn test_sample_data_req_api_009_create_sample_space() -> anyhow::Result<()
         entry_count: 120,
         seed: Some(7),
         owner_user_id: None,
+        fixture: false,
     };
 
     let summary = create_sample_space(&op, &root_uri, &options).await?;
@@ -61,6 +65,7 @@ async fn test_sample_data_req_api_009_respects_requested_small_entry_count() ->
         entry_count: 6,
         seed: Some(9),
         owner_user_id: None,
+        fixture: false,
     };
 
     let summary = create_sample_space(&op, &root_uri, &options).await?;
@@ -86,6 +91,7 @@ async fn test_sample_data_req_ops_016_bootstraps_trimmed_owner_membership() -> a
         entry_count: 6,
         seed: Some(11),
         owner_user_id: Some("  local-dev-user  ".to_string()),
+        fixture: false,
     };
 
     create_sample_space(&op, &root_uri, &options).await?;
@@ -125,6 +131,7 @@ async fn test_sample_data_req_api_010_job_lifecycle() -> anyhow::Result<()> {
         entry_count: 100,
         seed: Some(10),
         owner_user_id: None,
+        fixture: false,
     };
 
     let job = create_sample_space_job(&op, &root_uri, &options).await?;
@@ -176,6 +183,8 @@ async fn test_sample_data_req_api_010_job_status_retries_transient_eof() -> anyh
         status_message: Some("Queued".to_string()),
         processed_entries: 0,
         total_entries: 6,
+        percent_complete: 0.0,
+        cancel_requested: false,
         started_at: None,
         completed_at: None,
         error: None,
@@ -200,6 +209,414 @@ async fn test_sample_data_req_api_010_job_status_retries_transient_eof() -> anyh
     Ok(())
 }
 
+/// REQ-API-015
+#[tokio::test]
+async fn test_sample_data_req_api_015_project_tracker_relates_forms() -> anyhow::Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let root_uri = temp_root_uri(&tempdir);
+    let op = setup_operator()?;
+    let options = SampleDataOptions {
+        space_id: unique_space_id("sample-projects"),
+        scenario: "project-tracker".to_string(),
+        entry_count: 40,
+        seed: Some(3),
+        owner_user_id: None,
+        fixture: false,
+    };
+    let summary = create_sample_space(&op, &root_uri, &options).await?;
+    assert_eq!(
+        summary.forms,
+        vec!["Project", "Milestone", "Task", "StatusUpdate"]
+    );
+
+    let ws_path = format!("spaces/{}", options.space_id);
+    let entries = entry::list_entries(&op, &ws_path).await?;
+    assert_eq!(entries.len(), 40);
+    let project_ids: Vec<&str> = entries
+        .iter()
+        .filter(|entry| entry["form"] == "Project")
+        .filter_map(|entry| entry["id"].as_str())
+        .collect();
+    let tasks: Vec<&serde_json::Value> = entries
+        .iter()
+        .filter(|entry| entry["form"] == "Task")
+        .collect();
+    assert!(!tasks.is_empty());
+    for task in &tasks {
+        let project = task["properties"]["Project"].as_str().unwrap_or_default();
+        assert!(
+            project_ids.contains(&project),
+            "dangling Project: {project}"
+        );
+    }
+
+    let links = link::list_links(&op, &ws_path).await?;
+    assert!(links.iter().any(|link| link.kind == "blocks"));
+    Ok(())
+}
+
+/// REQ-API-015
+#[tokio::test]
+async fn test_sample_data_req_api_015_team_wiki_has_dense_backlinks() -> anyhow::Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let root_uri = temp_root_uri(&tempdir);
+    let op = setup_operator()?;
+    let options = SampleDataOptions {
+        space_id: unique_space_id("sample-wiki"),
+        scenario: "team-wiki".to_string(),
+        entry_count: 20,
+        seed: Some(4),
+        owner_user_id: None,
+        fixture: false,
+    };
+    create_sample_space(&op, &root_uri, &options).await?;
+
+    let ws_path = format!("spaces/{}", options.space_id);
+    let links = link::list_links(&op, &ws_path).await?;
+    let hub_backlinks = links
+        .iter()
+        .filter(|link| {
+            link.kind == "references" && (link.source == "page-0001" || link.target == "page-0001")
+        })
+        .count();
+    // 20 entries split 70/30 gives 14 pages, each after the hub linking back to it.
+    assert_eq!(hub_backlinks, 13);
+    assert!(links.len() > hub_backlinks);
+
+    let page = entry::get_entry(&op, &ws_path, "page-0002").await?;
+    assert!(page["content"]
+        .as_str()
+        .unwrap_or_default()
+        .contains("ugoite://entry/page-0001"));
+    Ok(())
+}
+
+/// REQ-API-015
+#[tokio::test]
+async fn test_sample_data_req_api_015_crm_saves_assets_and_dashboards() -> anyhow::Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let root_uri = temp_root_uri(&tempdir);
+    let op = setup_operator()?;
+    let options = SampleDataOptions {
+        space_id: unique_space_id("sample-crm"),
+        scenario: "crm".to_string(),
+        entry_count: 30,
+        seed: Some(5),
+        owner_user_id: None,
+        fixture: false,
+    };
+    create_sample_space(&op, &root_uri, &options).await?;
+
+    let ws_path = format!("spaces/{}", options.space_id);
+    let assets = asset::list_assets(&op, &ws_path).await?;
+    assert_eq!(assets.len(), 3);
+
+    let dashboards = saved_sql::list_sql(&op, &ws_path).await?;
+    assert_eq!(dashboards.len(), 3);
+    for dashboard in &dashboards {
+        let sql = dashboard["sql"].as_str().unwrap_or_default();
+        index::execute_sql_query(&op, &ws_path, sql).await?;
+    }
+    let deals = index::execute_sql_query(&op, &ws_path, "SELECT * FROM Deal").await?;
+    assert!(!deals.is_empty());
+    Ok(())
+}
+
+/// REQ-API-016
+#[tokio::test]
+async fn test_sample_data_req_api_016_cancel_removes_partial_space() -> anyhow::Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let root_uri = temp_root_uri(&tempdir);
+    let space_id = unique_space_id("sample-cancel");
+    let op = setup_operator()?;
+    let options = SampleDataOptions {
+        space_id: space_id.clone(),
+        scenario: "lab-qa".to_string(),
+        entry_count: 2_000,
+        seed: Some(12),
+        owner_user_id: None,
+        fixture: false,
+    };
+
+    let job = create_sample_space_job(&op, &root_uri, &options).await?;
+    let mut attempts = 0;
+    while get_sample_space_job(&op, &job.job_id)
+        .await?
+        .processed_entries
+        == 0
+    {
+        if attempts > 600 {
+            panic!("Sample job did not start in time");
+        }
+        attempts += 1;
+        sleep(Duration::from_millis(50)).await;
+    }
+    assert!(op.exists(&format!("spaces/{space_id}/")).await?);
+    let requested = cancel_sample_space_job(&op, &job.job_id).await?;
+    assert!(requested.cancel_requested);
+
+    let mut attempts = 0;
+    let cancelled = loop {
+        let latest = get_sample_space_job(&op, &job.job_id).await?;
+        assert!(latest.percent_complete < 100.0);
+        if latest.status == SampleJobStatus::Cancelled {
+            break latest;
+        }
+        if attempts > 600 {
+            panic!("Sample job was not cancelled in time");
+        }
+        attempts += 1;
+        sleep(Duration::from_millis(100)).await;
+    };
+    assert!(cancelled.cancel_requested);
+    assert!(cancelled.completed_at.is_some());
+    assert!(cancelled.summary.is_none());
+    assert!(!op.exists(&format!("spaces/{space_id}/")).await?);
+    assert!(
+        !op.exists(&format!("sample_jobs/{}.cancel", job.job_id))
+            .await?
+    );
+
+    // Cancelling again is a no-op.
+    let again = cancel_sample_space_job(&op, &job.job_id).await?;
+    assert_eq!(again.status, SampleJobStatus::Cancelled);
+    Ok(())
+}
+
+/// REQ-API-016
+#[tokio::test]
+async fn test_sample_data_req_api_016_completed_job_reports_percent() -> anyhow::Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let root_uri = temp_root_uri(&tempdir);
+    let op = setup_operator()?;
+    let options = SampleDataOptions {
+        space_id: unique_space_id("sample-percent"),
+        scenario: "lab-qa".to_string(),
+        entry_count: 6,
+        seed: Some(13),
+        owner_user_id: None,
+        fixture: false,
+    };
+
+    let job = create_sample_space_job(&op, &root_uri, &options).await?;
+    assert_eq!(job.percent_complete, 0.0);
+    let mut attempts = 0;
+    let mut last_percent = 0.0;
+    let completed = loop {
+        let latest = get_sample_space_job(&op, &job.job_id).await?;
+        assert!(latest.percent_complete >= last_percent);
+        last_percent = latest.percent_complete;
+        match latest.status {
+            SampleJobStatus::Completed => break latest,
+            SampleJobStatus::Failed | SampleJobStatus::Cancelled => {
+                panic!("Sample job did not complete: {:?}", latest.error);
+            }
+            _ => {}
+        }
+        if attempts > 600 {
+            panic!("Sample job did not finish in time");
+        }
+        attempts += 1;
+        sleep(Duration::from_millis(100)).await;
+    };
+    assert_eq!(completed.percent_complete, 100.0);
+    assert_eq!(completed.processed_entries, 6);
+    assert!(cancel_sample_space_job(&op, &job.job_id).await.is_err());
+    Ok(())
+}
+
+/// REQ-API-017
+#[tokio::test]
+async fn test_sample_data_req_api_017_fixture_mode_is_byte_stable() -> anyhow::Result<()> {
+    async fn fixture_entries(space_id: &str) -> anyhow::Result<Vec<serde_json::Value>> {
+        let tempdir = tempfile::tempdir()?;
+        let op = setup_operator()?;
+        let options = SampleDataOptions {
+            space_id: space_id.to_string(),
+            scenario: "retail-ops".to_string(),
+            entry_count: 12,
+            seed: Some(21),
+            owner_user_id: None,
+            fixture: true,
+        };
+        create_sample_space(&op, &temp_root_uri(&tempdir), &options).await?;
+        let ws_path = format!("spaces/{space_id}");
+        let mut entries = Vec::new();
+        for summary in entry::list_entries(&op, &ws_path).await? {
+            let id = summary["id"].as_str().unwrap_or_default();
+            let mut full = entry::get_entry(&op, &ws_path, id).await?;
+            // Signatures are keyed by the space's own HMAC key.
+            full["integrity"]["signature"] = serde_json::Value::Null;
+            entries.push(full);
+        }
+        entries.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+        Ok(entries)
+    }
+
+    let first = fixture_entries(&unique_space_id("fixture-a")).await?;
+    let second = fixture_entries(&unique_space_id("fixture-b")).await?;
+    assert_eq!(first, second);
+
+    // Golden values: downstream tests rely on these staying put across releases.
+    let alert = &first[0];
+    assert_eq!(alert["id"], "alert-00001");
+    assert_eq!(alert["created_at"], 1704067200.002);
+    assert_eq!(alert["revision_id"], "6ad1ab71-bd66-42ef-8023-599d49f32144");
+    assert_eq!(alert["sections"]["AlertDate"], "2025-11-19");
+    assert_eq!(alert["sections"]["Category"], "Outdoor");
+    assert_eq!(alert["sections"]["StoreId"], "store-001");
+    Ok(())
+}
+
+/// REQ-API-017
+#[tokio::test]
+async fn test_sample_data_req_api_017_fixture_mode_requires_seed() -> anyhow::Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let op = setup_operator()?;
+    let options = SampleDataOptions {
+        space_id: unique_space_id("fixture-unseeded"),
+        scenario: "retail-ops".to_string(),
+        entry_count: 12,
+        seed: None,
+        owner_user_id: None,
+        fixture: true,
+    };
+    let err = create_sample_space(&op, &temp_root_uri(&tempdir), &options)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("requires a seed"));
+    assert!(!op.exists(&format!("spaces/{}/", options.space_id)).await?);
+    Ok(())
+}
+
+/// REQ-API-018
+#[tokio::test]
+async fn test_sample_data_req_api_018_anonymize_keeps_structure_not_content() -> anyhow::Result<()>
+{
+    let tempdir = tempfile::tempdir()?;
+    let root_uri = temp_root_uri(&tempdir);
+    let op = setup_operator()?;
+    let source_id = unique_space_id("anon-source");
+    let ws_path = format!("spaces/{source_id}");
+    space::create_space(&op, &source_id, &root_uri).await?;
+    form::upsert_form(
+        &op,
+        &ws_path,
+        &serde_json::json!({
+            "name": "Client",
+            "fields": {
+                "Contact": {"type": "string", "required": true},
+                "Revenue": {"type": "number", "required": true},
+                "Since": {"type": "date", "required": true}
+            }
+        }),
+    )
+    .await?;
+    form::upsert_form(
+        &op,
+        &ws_path,
+        &serde_json::json!({
+            "name": "Ticket",
+            "fields": {
+                "Client": {"type": "row_reference", "target_form": "Client", "required": true},
+                "Status": {"type": "string", "required": true},
+                "Details": {"type": "markdown", "required": false}
+            }
+        }),
+    )
+    .await?;
+    for (id, contact, since) in [
+        ("acme-corp", "Jane Secret", "2023-05-01"),
+        ("globex", "John Private", "2022-01-15"),
+    ] {
+        let content = format!(
+            "---\nform: Client\ntags: [vip]\n---\n# {id}\n\n## Contact\n{contact}\n\n## Revenue\n120000\n\n## Since\n{since}\n"
+        );
+        entry::create_entry(&op, &ws_path, id, &content, "alice", &FakeIntegrityProvider).await?;
+    }
+    for (id, client) in [("ticket-outage", "acme-corp"), ("ticket-refund", "globex")] {
+        let content = format!(
+            "---\nform: Ticket\n---\n# {id}\n\n## Client\n{client}\n\n## Status\nOpen\n\n## Details\nCustomer reported confidential issue\n"
+        );
+        entry::create_entry(&op, &ws_path, id, &content, "alice", &FakeIntegrityProvider).await?;
+    }
+    link::create_link(
+        &op,
+        &ws_path,
+        "ticket-outage",
+        "ticket-refund",
+        "related",
+        "l1",
+    )
+    .await?;
+
+    let clone_id = unique_space_id("anon-clone");
+    let rules = AnonymizeRules {
+        seed: Some(1),
+        keep_fields: vec!["Ticket.Status".to_string()],
+        skip_forms: Vec::new(),
+    };
+    let summary = anonymize_space(&op, &root_uri, &source_id, &clone_id, &rules).await?;
+    assert_eq!(summary.forms, vec!["Client", "Entry", "Ticket"]);
+    assert_eq!(summary.entry_count, 4);
+    assert_eq!(summary.link_count, 1);
+
+    let clone_path = format!("spaces/{clone_id}");
+    let entries = entry::list_entries(&op, &clone_path).await?;
+    assert_eq!(entries.len(), 4);
+    let dump = serde_json::to_string(&entries)?;
+    for secret in [
+        "acme",
+        "globex",
+        "Jane",
+        "John",
+        "confidential",
+        "vip",
+        "outage",
+    ] {
+        assert!(!dump.contains(secret), "clone leaked {secret}");
+    }
+    let client_ids: Vec<&str> = entries
+        .iter()
+        .filter(|entry| entry["form"] == "Client")
+        .filter_map(|entry| entry["id"].as_str())
+        .collect();
+    for ticket in entries.iter().filter(|entry| entry["form"] == "Ticket") {
+        let client = ticket["properties"]["Client"].as_str().unwrap_or_default();
+        assert!(client_ids.contains(&client));
+        assert_eq!(ticket["properties"]["Status"], "Open");
+    }
+    for client in entries.iter().filter(|entry| entry["form"] == "Client") {
+        let revenue = client["properties"]["Revenue"].as_f64().unwrap_or_default();
+        assert!((60000.0..=180000.0).contains(&revenue));
+        assert_eq!(client["tags"], serde_json::json!(["tag-1"]));
+    }
+    let links = link::list_links(&op, &clone_path).await?;
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].kind, "related");
+    Ok(())
+}
+
+/// REQ-API-018
+#[tokio::test]
+async fn test_sample_data_req_api_018_anonymize_rejects_missing_source() -> anyhow::Result<()> {
+    let tempdir = tempfile::tempdir()?;
+    let op = setup_operator()?;
+    let clone_id = unique_space_id("anon-missing");
+    let err = anonymize_space(
+        &op,
+        &temp_root_uri(&tempdir),
+        "does-not-exist",
+        &clone_id,
+        &AnonymizeRules::default(),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Space not found"));
+    assert!(!space::space_exists(&op, &clone_id).await?);
+    Ok(())
+}
+
 fn temp_root_uri(tempdir: &TempDir) -> String {
     format!("file://{}/", tempdir.path().display())
 }
@@ -1,7 +1,8 @@
 mod common;
 
+use _ugoite_core::entry_acl::{EntryPrincipal, EntryViewer, EntryVisibility, PrincipalKind};
 use _ugoite_core::integrity::FakeIntegrityProvider;
-use _ugoite_core::saved_sql::{self, SqlPayload};
+use _ugoite_core::saved_sql::{self, SqlAction, SqlListFilter, SqlPayload};
 use _ugoite_core::space;
 use common::setup_operator;
 use serde_json::json;
@@ -24,6 +25,8 @@ async fn test_saved_sql_req_api_006_crud() -> anyhow::Result<()> {
                 "description": "Lower bound",
             }
         ]),
+        folder: None,
+        tags: None,
     };
 
     let entry =
@@ -50,6 +53,8 @@ async fn test_saved_sql_req_api_006_crud() -> anyhow::Result<()> {
         sql: "SELECT * FROM entries WHERE updated_at >= {{since}} ORDER BY updated_at DESC"
             .to_string(),
         variables: payload.variables.clone(),
+        folder: None,
+        tags: None,
     };
 
     let updated = saved_sql::update_sql(
@@ -93,6 +98,8 @@ async fn test_saved_sql_req_api_007_validation_errors() -> anyhow::Result<()> {
                 "description": "Lower bound",
             }
         ]),
+        folder: None,
+        tags: None,
     };
 
     let missing_err = saved_sql::create_sql(
@@ -111,6 +118,8 @@ async fn test_saved_sql_req_api_007_validation_errors() -> anyhow::Result<()> {
         name: "Undefined placeholder".to_string(),
         sql: "SELECT * FROM entries WHERE updated_at >= {{since}}".to_string(),
         variables: json!([]),
+        folder: None,
+        tags: None,
     };
 
     let undefined_err = saved_sql::create_sql(
@@ -129,6 +138,8 @@ async fn test_saved_sql_req_api_007_validation_errors() -> anyhow::Result<()> {
         name: "Invalid SQL".to_string(),
         sql: "FROM entries".to_string(),
         variables: json!([]),
+        folder: None,
+        tags: None,
     };
 
     let invalid_err = saved_sql::create_sql(
@@ -145,3 +156,288 @@ async fn test_saved_sql_req_api_007_validation_errors() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn organized_payload(name: &str, sql: &str, folder: &str, tags: &[&str]) -> SqlPayload {
+    SqlPayload {
+        name: name.to_string(),
+        sql: sql.to_string(),
+        variables: json!([]),
+        folder: Some(folder.to_string()),
+        tags: Some(tags.iter().map(|tag| tag.to_string()).collect()),
+    }
+}
+
+fn ids(entries: &[serde_json::Value]) -> Vec<&str> {
+    entries
+        .iter()
+        .filter_map(|item| item.get("id").and_then(|v| v.as_str()))
+        .collect()
+}
+
+#[tokio::test]
+/// REQ-API-019
+async fn test_saved_sql_req_api_019_folders_tags_and_search() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "sql-organize", "/tmp").await?;
+    let ws_path = "spaces/sql-organize";
+    let integrity = FakeIntegrityProvider;
+
+    for (id, payload) in [
+        (
+            "sql-revenue",
+            organized_payload(
+                "Revenue by month",
+                "SELECT * FROM entries WHERE form = 'Deal'",
+                " /finance/reports/ ",
+                &["finance", "monthly", "finance"],
+            ),
+        ),
+        (
+            "sql-churn",
+            organized_payload(
+                "Churn",
+                "SELECT * FROM entries WHERE title = 'churn'",
+                "finance",
+                &["monthly"],
+            ),
+        ),
+        (
+            "sql-standup",
+            organized_payload("Standup notes", "SELECT * FROM entries", "", &[]),
+        ),
+    ] {
+        saved_sql::create_sql(&op, ws_path, id, &payload, "author", &integrity).await?;
+    }
+
+    let revenue = saved_sql::get_sql(&op, ws_path, "sql-revenue").await?;
+    assert_eq!(revenue["folder"], json!("finance/reports"));
+    assert_eq!(revenue["tags"], json!(["finance", "monthly"]));
+
+    let everything = saved_sql::list_sql(&op, ws_path).await?;
+    assert_eq!(
+        ids(&everything),
+        vec!["sql-standup", "sql-churn", "sql-revenue"]
+    );
+
+    let folder_only = saved_sql::list_sql_filtered(
+        &op,
+        ws_path,
+        &SqlListFilter {
+            folder: Some("finance".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(ids(&folder_only), vec!["sql-churn"]);
+
+    let recursive = saved_sql::list_sql_filtered(
+        &op,
+        ws_path,
+        &SqlListFilter {
+            folder: Some("finance".to_string()),
+            recursive: true,
+            tags: vec!["monthly".to_string()],
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(ids(&recursive), vec!["sql-churn", "sql-revenue"]);
+
+    let searched = saved_sql::list_sql_filtered(
+        &op,
+        ws_path,
+        &SqlListFilter {
+            query: Some("CHURN".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(ids(&searched), vec!["sql-churn"]);
+
+    assert_eq!(
+        saved_sql::list_sql_folders(&op, ws_path).await?,
+        vec!["finance".to_string(), "finance/reports".to_string()]
+    );
+
+    let invalid = organized_payload("Bad", "SELECT * FROM entries", "a/../b", &[]);
+    let err = saved_sql::create_sql(&op, ws_path, "sql-bad", &invalid, "author", &integrity)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("UGOITE_SQL_VALIDATION"));
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-API-019
+async fn test_saved_sql_req_api_019_move_and_rename() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "sql-move", "/tmp").await?;
+    let ws_path = "spaces/sql-move";
+    let integrity = FakeIntegrityProvider;
+
+    let payload = organized_payload("Open deals", "SELECT * FROM entries", "sales", &["crm"]);
+    let created =
+        saved_sql::create_sql(&op, ws_path, "sql-deals", &payload, "author", &integrity).await?;
+
+    let moved = saved_sql::move_sql(
+        &op,
+        ws_path,
+        "sql-deals",
+        "sales/pipeline",
+        "mover",
+        &integrity,
+    )
+    .await?;
+    assert_eq!(moved["folder"], json!("sales/pipeline"));
+    assert_eq!(moved["tags"], json!(["crm"]));
+    assert_ne!(moved["revision_id"], created["revision_id"]);
+
+    let renamed = saved_sql::rename_sql(
+        &op,
+        ws_path,
+        "sql-deals",
+        "Pipeline deals",
+        "mover",
+        &integrity,
+    )
+    .await?;
+    assert_eq!(renamed["name"], json!("Pipeline deals"));
+    assert_eq!(renamed["folder"], json!("sales/pipeline"));
+    assert_eq!(renamed["sql"], json!("SELECT * FROM entries"));
+
+    let to_root = saved_sql::move_sql(&op, ws_path, "sql-deals", "", "mover", &integrity).await?;
+    assert_eq!(to_root["folder"], json!(""));
+    assert!(saved_sql::list_sql_folders(&op, ws_path).await?.is_empty());
+
+    assert!(
+        saved_sql::rename_sql(&op, ws_path, "sql-deals", "  ", "mover", &integrity)
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-API-020
+async fn test_saved_sql_req_api_020_share_settings_gate_access() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "sql-share", "/tmp").await?;
+    let ws_path = "spaces/sql-share";
+    let integrity = FakeIntegrityProvider;
+
+    let payload = organized_payload("Salaries", "SELECT * FROM entries", "hr", &[]);
+    saved_sql::create_sql(&op, ws_path, "sql-salaries", &payload, "alice", &integrity).await?;
+    let share = saved_sql::get_sql_share(&op, ws_path, "sql-salaries").await?;
+    assert_eq!(share.owner, "alice");
+    assert_eq!(share.visibility, EntryVisibility::Space);
+
+    let alice = EntryViewer::user("alice");
+    let bob = EntryViewer::user("bob");
+    let analyst = EntryViewer {
+        user_id: "carol".to_string(),
+        groups: vec!["analysts".to_string()],
+        is_space_admin: false,
+    };
+    let admin = EntryViewer {
+        is_space_admin: true,
+        ..EntryViewer::user("root")
+    };
+
+    assert!(saved_sql::set_sql_share(
+        &op,
+        ws_path,
+        "sql-salaries",
+        EntryVisibility::Private,
+        Vec::new(),
+        &bob,
+    )
+    .await
+    .is_err());
+
+    saved_sql::set_sql_share(
+        &op,
+        ws_path,
+        "sql-salaries",
+        EntryVisibility::Restricted,
+        vec![EntryPrincipal {
+            kind: PrincipalKind::UserGroup,
+            id: "analysts".to_string(),
+        }],
+        &alice,
+    )
+    .await?;
+
+    let visible = |viewer: EntryViewer| {
+        let op = op.clone();
+        async move {
+            let entries =
+                saved_sql::list_sql_for_viewer(&op, ws_path, &SqlListFilter::default(), &viewer)
+                    .await?;
+            anyhow::Ok(entries.iter().any(|e| e["id"] == json!("sql-salaries")))
+        }
+    };
+    assert!(visible(alice.clone()).await?);
+    assert!(visible(analyst.clone()).await?);
+    assert!(visible(admin.clone()).await?);
+    assert!(!visible(bob.clone()).await?);
+
+    let hidden = saved_sql::get_sql_for_viewer(&op, ws_path, "sql-salaries", &bob)
+        .await
+        .unwrap_err();
+    assert!(hidden.to_string().contains("not found"));
+    saved_sql::authorize_sql(&op, ws_path, "sql-salaries", &analyst, SqlAction::Run).await?;
+    assert!(
+        saved_sql::authorize_sql(&op, ws_path, "sql-salaries", &bob, SqlAction::Run)
+            .await
+            .is_err()
+    );
+    saved_sql::run_sql_for_viewer(&op, ws_path, "sql-salaries", &json!({}), &analyst).await?;
+    let hidden = saved_sql::run_sql_for_viewer(&op, ws_path, "sql-salaries", &json!({}), &bob)
+        .await
+        .unwrap_err();
+    assert!(hidden.to_string().contains("not found"));
+
+    let edit = organized_payload("Salaries 2026", "SELECT * FROM entries", "hr", &[]);
+    let read_only = saved_sql::update_sql_for_viewer(
+        &op,
+        ws_path,
+        "sql-salaries",
+        &edit,
+        None,
+        &analyst,
+        &integrity,
+    )
+    .await
+    .unwrap_err();
+    assert!(read_only.to_string().contains("read-only"));
+    let updated = saved_sql::update_sql_for_viewer(
+        &op,
+        ws_path,
+        "sql-salaries",
+        &edit,
+        None,
+        &alice,
+        &integrity,
+    )
+    .await?;
+    assert_eq!(updated["name"], json!("Salaries 2026"));
+
+    saved_sql::set_sql_share(
+        &op,
+        ws_path,
+        "sql-salaries",
+        EntryVisibility::Private,
+        Vec::new(),
+        &admin,
+    )
+    .await?;
+    assert!(!visible(analyst).await?);
+    assert_eq!(
+        saved_sql::get_sql_share(&op, ws_path, "sql-salaries")
+            .await?
+            .owner,
+        "alice"
+    );
+    Ok(())
+}
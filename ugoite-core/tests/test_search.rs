@@ -1,5 +1,5 @@
 mod common;
-use _ugoite_core::{entry, form, search, space};
+use _ugoite_core::{entry, form, index, search, space};
 use common::setup_operator;
 
 async fn create_test_entry(
@@ -92,3 +92,65 @@ async fn test_search_req_srch_002_fallback_scan() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+/// REQ-SRCH-006
+async fn test_search_req_srch_006_trash_aware_flags() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_id = "test-search-ws-trash";
+    space::create_space(&op, ws_id, "/tmp").await?;
+    let ws_path = format!("spaces/{}", ws_id);
+
+    create_test_entry(&op, &ws_path, "kept", "Quarterly project plan").await?;
+    create_test_entry(&op, &ws_path, "trashed", "Abandoned project draft").await?;
+    entry::delete_entry(&op, &ws_path, "trashed", false).await?;
+
+    let live = search::search_entries(&op, &ws_path, "project").await?;
+    assert_eq!(live.len(), 1);
+    assert_eq!(live[0].id, "kept");
+    assert_eq!(
+        serde_json::to_value(&live[0])?,
+        serde_json::json!({"id": "kept"})
+    );
+
+    let mut all = search::search_entries_with_deleted(
+        &op,
+        &ws_path,
+        "project",
+        entry::DeletedFilter::Include,
+    )
+    .await?;
+    all.sort_by(|a, b| a.id.cmp(&b.id));
+    let flags: Vec<(&str, bool)> = all.iter().map(|r| (r.id.as_str(), r.deleted)).collect();
+    assert_eq!(flags, vec![("kept", false), ("trashed", true)]);
+
+    let trash = entry::list_entries_with_deleted(&op, &ws_path, entry::DeletedFilter::Only).await?;
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0]["id"], "trashed");
+    assert_eq!(trash[0]["deleted"], true);
+    assert!(trash[0]["deleted_at"].is_number());
+    let live = entry::list_entries(&op, &ws_path).await?;
+    assert_eq!(live.len(), 1);
+    assert!(live[0].get("deleted").is_none());
+
+    let only = index::query_index_with_deleted(
+        &op,
+        &ws_path,
+        r#"{"form": "Entry"}"#,
+        entry::DeletedFilter::from_flags(true, true),
+    )
+    .await?;
+    assert_eq!(only.len(), 1);
+    assert_eq!(only[0]["id"], "trashed");
+    let sql = index::query_index_with_deleted(
+        &op,
+        &ws_path,
+        r#""SELECT * FROM entries WHERE deleted = true""#,
+        entry::DeletedFilter::Include,
+    )
+    .await?;
+    assert_eq!(sql.len(), 1);
+    assert_eq!(sql[0]["id"], "trashed");
+    assert_eq!(index::query_index(&op, &ws_path, "").await?.len(), 1);
+    Ok(())
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::form;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::sequence::{self, SequenceSpec};
+use _ugoite_core::space;
+use common::setup_operator;
+use serde_json::json;
+
+#[test]
+/// REQ-FORM-015
+fn test_sequence_req_form_015_format_templates() -> anyhow::Result<()> {
+    let spec = SequenceSpec::from_field_def(&json!({"format": "BUG-{n:04}", "start": 7}))?;
+    assert_eq!((spec.prefix.as_str(), spec.pad, spec.start), ("BUG-", 4, 7));
+    assert_eq!(spec.render(7), "BUG-0007");
+    assert_eq!(spec.render(12345), "BUG-12345");
+    assert_eq!(spec.parse("BUG-0042"), Some(42));
+    assert_eq!(spec.parse("BUG-12345"), Some(12345));
+    for bad in ["BUG-", "BUG-x1", "TASK-1", "BUG--1"] {
+        assert_eq!(spec.parse(bad), None, "{bad}");
+    }
+    assert_eq!(
+        SequenceSpec::from_field_def(&json!({}))?,
+        SequenceSpec::default()
+    );
+    assert_eq!(
+        SequenceSpec::from_field_def(&json!({"format": "#{n} (ops)"}))?.render(3),
+        "#3 (ops)"
+    );
+    for bad in [
+        json!({"format": "BUG"}),
+        json!({"format": "{n}-{n}"}),
+        json!({"format": "{n:4}"}),
+        json!({"format": "{n:021}"}),
+        json!({"format": "{n"}),
+        json!({"start": -1}),
+    ] {
+        assert!(SequenceSpec::from_field_def(&bad).is_err(), "{bad}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-015
+async fn test_sequence_req_form_015_assigned_on_create() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "tickets", "/tmp").await?;
+    let ws_path = "spaces/tickets";
+    assert!(form::upsert_form(
+        &op,
+        ws_path,
+        &json!({"name": "Broken", "fields": {"Key": {"type": "sequence", "format": "BUG"}}}),
+    )
+    .await
+    .is_err());
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Bug",
+            "template": "# Bug\n\n## Key\n\n## Summary\n",
+            "fields": {
+                "Key": {"type": "sequence", "format": "BUG-{n}", "start": 1024},
+                "Summary": {"type": "string"},
+            },
+        }),
+    )
+    .await?;
+    let integrity = FakeIntegrityProvider;
+    let bug = |id: &str, key: &str| {
+        format!("---\nform: Bug\n---\n# {id}\n\n## Key\n{key}\n\n## Summary\n{id} broke\n")
+    };
+    let key_of = |entry: &serde_json::Value| entry["sections"]["Key"].as_str().map(str::to_string);
+
+    for id in ["a", "b"] {
+        entry::create_entry(&op, ws_path, id, &bug(id, ""), "dev", &integrity).await?;
+    }
+    let a = entry::get_entry(&op, ws_path, "a").await?;
+    assert_eq!(key_of(&a).as_deref(), Some("BUG-1024"));
+    let b = entry::get_entry(&op, ws_path, "b").await?;
+    assert_eq!(key_of(&b).as_deref(), Some("BUG-1025"));
+
+    // Imported numbers are kept and never handed out again.
+    entry::create_entry(&op, ws_path, "c", &bug("c", "BUG-2000"), "dev", &integrity).await?;
+    entry::create_entry(&op, ws_path, "d", &bug("d", ""), "dev", &integrity).await?;
+    let d = entry::get_entry(&op, ws_path, "d").await?;
+    assert_eq!(key_of(&d).as_deref(), Some("BUG-2001"));
+    let counters = sequence::get_sequence_counters(&op, ws_path, "Bug").await?;
+    assert_eq!(counters.next.get("Key"), Some(&2002));
+    assert!(
+        entry::create_entry(&op, ws_path, "e", &bug("e", "TASK-1"), "dev", &integrity)
+            .await
+            .is_err()
+    );
+
+    // Updates keep the number, whether or not the section is resubmitted.
+    let without_key = "---\nform: Bug\n---\n# a\n\n## Summary\na still broke\n";
+    entry::update_entry(
+        &op,
+        ws_path,
+        "a",
+        without_key,
+        None,
+        "dev",
+        None,
+        &integrity,
+    )
+    .await?;
+    let a = entry::get_entry(&op, ws_path, "a").await?;
+    assert_eq!(key_of(&a).as_deref(), Some("BUG-1024"));
+    let renumbered = entry::update_entry(
+        &op,
+        ws_path,
+        "a",
+        &bug("a", "BUG-9"),
+        None,
+        "dev",
+        None,
+        &integrity,
+    )
+    .await
+    .expect_err("sequence values are immutable");
+    assert!(renumbered.to_string().contains("cannot be changed"));
+    Ok(())
+}
@@ -1,5 +1,6 @@
 mod common;
-use _ugoite_core::{form, space};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::{asset, entry, form, space, storage};
 use common::setup_operator;
 #[cfg(unix)]
 use opendal::services::Fs;
@@ -129,6 +130,59 @@ async fn test_space_req_sto_008_list_spaces_ignores_missing_meta() -> anyhow::Re
     Ok(())
 }
 
+#[tokio::test]
+/// REQ-STO-023
+async fn test_space_req_sto_023_list_spaces_detailed() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "sp-busy", "/tmp").await?;
+    space::create_space(&op, "sp-idle", "s3://bucket/prefix").await?;
+    op.create_dir("spaces/no-meta/").await?;
+    for id in ["one", "two", "three"] {
+        entry::create_entry(
+            &op,
+            "spaces/sp-busy",
+            id,
+            "---\nform: Entry\n---\n# Note\n\n## Body\ntext\n",
+            "tester",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    entry::delete_entry(&op, "spaces/sp-busy", "three", false).await?;
+    space::patch_space(
+        &op,
+        "sp-idle",
+        &serde_json::json!({"name": "Idle Space", "settings": {"archived": true}}),
+    )
+    .await?;
+
+    let listed = space::list_spaces_detailed(&op).await?;
+    let ids: Vec<&str> = listed.iter().map(|s| s.id.as_str()).collect();
+    assert_eq!(ids, vec!["sp-busy", "sp-idle"]);
+
+    let busy = &listed[0];
+    assert_eq!(busy.name, "sp-busy");
+    assert_eq!(busy.entry_count, 2);
+    assert_eq!(busy.storage_type, "local");
+    assert!(!busy.archived);
+    let newest = entry::list_entries(&op, "spaces/sp-busy")
+        .await?
+        .iter()
+        .filter_map(|e| e["updated_at"].as_f64())
+        .fold(0.0, f64::max);
+    assert!(busy.last_activity >= newest);
+
+    let idle = &listed[1];
+    assert_eq!(idle.name, "Idle Space");
+    assert_eq!(idle.entry_count, 0);
+    assert_eq!(idle.storage_type, "s3");
+    assert!(idle.archived);
+    let meta = space::get_space(&op, "sp-idle").await?;
+    assert_eq!(idle.last_activity, meta.created_at);
+
+    Ok(())
+}
+
 #[tokio::test]
 /// REQ-STO-002
 async fn test_space_req_sto_002_test_storage_connection_memory() -> anyhow::Result<()> {
@@ -168,3 +222,251 @@ async fn test_space_req_sto_002_test_storage_connection_unknown() -> anyhow::Res
     assert_eq!(result["mode"], "unknown");
     Ok(())
 }
+
+#[tokio::test]
+/// REQ-STO-013
+async fn test_space_req_sto_013_export_static_site_bundle() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "site-space", "/tmp").await?;
+    let ws_path = "spaces/site-space";
+    let integrity = FakeIntegrityProvider;
+
+    let image = asset::save_asset(&op, ws_path, "chart.png", b"png-bytes").await?;
+    let unused = asset::save_asset(&op, ws_path, "unused.txt", b"unused").await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "plan",
+        &format!(
+            "---\nform: Entry\ntags:\n  - roadmap\n---\n# Plan\n\n## Body\nSee [notes](ugoite://entry/notes) and ![chart](ugoite://asset/{}).\n\n<script>alert(1)</script>\n",
+            image.id
+        ),
+        "tester",
+        &integrity,
+    )
+    .await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "notes",
+        "---\nform: Entry\ntags:\n  - roadmap\n  - team\n---\n# Notes\n\n## Body\n| a | b |\n|---|---|\n| 1 | 2 |\n",
+        "tester",
+        &integrity,
+    )
+    .await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "gone",
+        "---\nform: Entry\n---\n# Gone\n\n## Body\nremoved\n",
+        "tester",
+        &integrity,
+    )
+    .await?;
+    entry::delete_entry(&op, ws_path, "gone", false).await?;
+
+    let target = setup_operator()?;
+    let export = space::export_static_site(&op, "site-space", &target, "site").await?;
+    assert_eq!(export.entry_count, 2);
+    assert_eq!(export.asset_count, 1);
+    assert_eq!(export.tag_count, 2);
+
+    let read = |path: &str| {
+        let target = target.clone();
+        let path = path.to_string();
+        async move {
+            anyhow::Ok(String::from_utf8(
+                target.read(&format!("site/{path}")).await?.to_vec(),
+            )?)
+        }
+    };
+    let plan = read("entries/plan.html").await?;
+    assert!(plan.contains("<a href=\"../entries/notes.html\">notes</a>"));
+    assert!(plan.contains(&format!("src=\"../assets/{}_chart.png\"", image.id)));
+    assert!(plan.contains("&lt;script&gt;"));
+    assert!(!plan.contains("<script>"));
+    assert!(plan.contains("<a href=\"../tags/roadmap.html\">roadmap</a>"));
+
+    let notes = read("entries/notes.html").await?;
+    assert!(notes.contains("<table>"));
+
+    let index = read("index.html").await?;
+    assert!(index.contains("entries/plan.html"));
+    assert!(!index.contains("entries/gone.html"));
+
+    let team = read("tags/team.html").await?;
+    assert!(team.contains("entries/notes.html"));
+    assert!(!team.contains("entries/plan.html"));
+    assert!(read("tags/index.html").await?.contains("roadmap.html"));
+
+    let copied = target
+        .read(&format!("site/assets/{}_chart.png", image.id))
+        .await?;
+    assert_eq!(copied.to_vec(), b"png-bytes");
+    assert!(
+        !target
+            .exists(&format!("site/assets/{}_unused.txt", unused.id))
+            .await?
+    );
+    assert!(!target.exists("site/entries/gone.html").await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-016
+async fn test_space_req_sto_016_parallel_listing_matches_serial() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    for idx in 0..12 {
+        space::create_space(&op, &format!("listed-{idx:02}"), "/tmp").await?;
+    }
+    // A directory without meta.json is not a space.
+    op.create_dir("spaces/half-created/").await?;
+    for form_name in ["Task", "Meeting"] {
+        form::upsert_form(
+            &op,
+            "spaces/listed-00",
+            &serde_json::json!({"name": form_name, "fields": {"Body": {"type": "markdown"}}}),
+        )
+        .await?;
+    }
+    for (idx, form_name) in ["Entry", "Task", "Meeting", "Task"].iter().enumerate() {
+        entry::create_entry(
+            &op,
+            "spaces/listed-00",
+            &format!("entry-{idx}"),
+            &format!("---\nform: {form_name}\n---\n# Entry {idx}\n\n## Body\ntext\n"),
+            "alice",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+
+    assert!(storage::list_parallelism() >= 1);
+    let parallel_spaces = space::list_spaces(&op).await?;
+    let parallel_entries = entry::list_entries(&op, "spaces/listed-00").await?;
+    storage::set_list_parallelism(1);
+    let serial_spaces = space::list_spaces(&op).await?;
+    let serial_entries = entry::list_entries(&op, "spaces/listed-00").await?;
+    storage::set_list_parallelism(0);
+
+    assert_eq!(parallel_spaces, serial_spaces);
+    assert!(!parallel_spaces.contains(&"half-created".to_string()));
+    assert_eq!(
+        parallel_spaces
+            .iter()
+            .filter(|id| id.starts_with("listed-"))
+            .count(),
+        12
+    );
+    let ids = |entries: &[Value]| {
+        let mut ids: Vec<String> = entries
+            .iter()
+            .map(|entry| entry["id"].as_str().unwrap().to_string())
+            .collect();
+        ids.sort();
+        ids
+    };
+    assert_eq!(ids(&parallel_entries), ids(&serial_entries));
+    assert_eq!(parallel_entries.len(), 4);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+/// REQ-STO-020
+async fn test_space_req_sto_020_vacuum_reclaims_expired_artifacts() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let root = dir.path().to_string_lossy().to_string();
+    let op = Operator::new(Fs::default().root(&root))?.finish();
+    space::create_space(&op, "vacuum-space", &root).await?;
+    let ws_path = "spaces/vacuum-space";
+    let integrity = FakeIntegrityProvider;
+    for id in ["a", "b"] {
+        let content = format!("---\nform: Entry\n---\n# {id}\n\n## Body\nv0");
+        entry::create_entry(&op, ws_path, id, &content, "author", &integrity).await?;
+    }
+    for version in 1..=2 {
+        let head = entry::get_entry_content(&op, ws_path, "a").await?;
+        let content = format!("---\nform: Entry\n---\n# a\n\n## Body\nv{version}");
+        entry::update_entry(
+            &op,
+            ws_path,
+            "a",
+            &content,
+            Some(&head.revision_id),
+            "author",
+            None,
+            &integrity,
+        )
+        .await?;
+    }
+
+    op.write(&format!("{ws_path}/.settings.json.abc.tmp"), vec![0u8; 64])
+        .await?;
+    op.write(
+        &format!("{ws_path}/sql_sessions/old/meta.json"),
+        serde_json::to_vec(&serde_json::json!({"expires_at": "2020-01-01T00:00:00Z"}))?,
+    )
+    .await?;
+    op.write(
+        &format!("{ws_path}/sql_sessions/old/spill/r/0.jsonl"),
+        "{}\n",
+    )
+    .await?;
+    for (job_id, status, completed_at) in [
+        ("done", "completed", Value::from("2020-01-01T00:00:00Z")),
+        ("busy", "running", Value::Null),
+    ] {
+        op.write(
+            &format!("{ws_path}/bulk_jobs/{job_id}.json"),
+            serde_json::to_vec(&serde_json::json!({
+                "status": status,
+                "completed_at": completed_at,
+            }))?,
+        )
+        .await?;
+    }
+
+    let options = space::VacuumOptions {
+        temp_file_age_secs: 0,
+        ..space::VacuumOptions::default()
+    };
+    let report = space::vacuum(&op, "vacuum-space", &options).await?;
+    assert_eq!(report.temp_files_removed, 1);
+    assert_eq!(report.sql_sessions_removed, 1);
+    assert_eq!(report.jobs_removed, 1);
+    assert_eq!(report.forms_compacted, vec!["Entry"]);
+    assert_eq!(report.superseded_rows, 2);
+    assert_eq!(report.data_files_after, 2);
+    assert!(report.data_files_before > report.data_files_after);
+    assert!(report.reclaimed_bytes > 0);
+    assert_eq!(
+        report.reclaimed_bytes,
+        report.bytes_before - report.bytes_after
+    );
+    assert!(!op.exists(&format!("{ws_path}/sql_sessions/old/")).await?);
+    assert!(op.exists(&format!("{ws_path}/bulk_jobs/busy.json")).await?);
+
+    let current = entry::get_entry_content(&op, ws_path, "a").await?;
+    assert!(current.markdown.ends_with("v2"));
+    let history = entry::get_entry_history(&op, ws_path, "a").await?;
+    assert_eq!(history["revisions"].as_array().map(Vec::len), Some(3));
+    assert_eq!(entry::list_entries(&op, ws_path).await?.len(), 2);
+
+    // Only the compacted data files remain.
+    for table in ["entries", "revisions"] {
+        let data_dir = dir
+            .path()
+            .join(format!("{ws_path}/forms/Entry/{table}/data"));
+        assert_eq!(std::fs::read_dir(data_dir)?.count(), 1, "{table}");
+    }
+    let again = space::vacuum(&op, "vacuum-space", &options).await?;
+    assert!(again.forms_compacted.is_empty());
+
+    // Writes land on the swapped-in tables.
+    let content = "---\nform: Entry\n---\n# c\n\n## Body\nv0";
+    entry::create_entry(&op, ws_path, "c", content, "author", &integrity).await?;
+    assert_eq!(entry::list_entries(&op, ws_path).await?.len(), 3);
+    Ok(())
+}
@@ -0,0 +1,109 @@
+mod common;
+use _ugoite_core::space_lock::{self, MIGRATION_LEASE};
+use _ugoite_core::{audit, revision_delta, space};
+use common::setup_operator;
+use opendal::services::Fs;
+use opendal::Operator;
+use serde_json::json;
+use std::time::Duration;
+use tempfile::tempdir;
+
+#[tokio::test]
+/// REQ-STO-017
+async fn test_space_lock_req_sto_017_fencing_tokens_increase() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let op = Operator::new(Fs::default().root(dir.path().to_string_lossy().as_ref()))?.finish();
+    let ws_path = "spaces/leases";
+
+    let first = space_lock::try_acquire_lease(&op, ws_path, "rebuild", "worker-a", 30).await?;
+    assert_eq!(first.fencing_token, 1);
+    let err = space_lock::try_acquire_lease(&op, ws_path, "rebuild", "worker-b", 30)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("held by worker-a"));
+    let current = space_lock::current_lease(&op, ws_path, "rebuild").await?;
+    assert_eq!(current.unwrap().holder, "worker-a");
+
+    space_lock::release_lease(&op, ws_path, &first).await?;
+    assert!(space_lock::current_lease(&op, ws_path, "rebuild")
+        .await?
+        .is_none());
+    let second = space_lock::try_acquire_lease(&op, ws_path, "rebuild", "worker-b", 30).await?;
+    assert_eq!(second.fencing_token, 2);
+    // The first holder was fenced off and can neither write nor release.
+    assert!(space_lock::ensure_lease_current(&op, ws_path, &first)
+        .await
+        .is_err());
+    space_lock::release_lease(&op, ws_path, &first).await?;
+    space_lock::ensure_lease_current(&op, ws_path, &second).await?;
+    space_lock::release_lease(&op, ws_path, &second).await?;
+
+    // Concurrent claims on a free lock produce exactly one holder.
+    let claims = futures::future::join_all((0..8).map(|idx| {
+        let op = op.clone();
+        async move {
+            space_lock::try_acquire_lease(&op, ws_path, "rebuild", &format!("racer-{idx}"), 30)
+                .await
+        }
+    }))
+    .await;
+    let winners: Vec<_> = claims.into_iter().filter_map(Result::ok).collect();
+    assert_eq!(winners.len(), 1);
+    assert_eq!(winners[0].fencing_token, 3);
+
+    assert!(
+        space_lock::try_acquire_lease(&op, ws_path, "../escape", "worker-a", 30)
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-017
+async fn test_space_lock_req_sto_017_expired_lease_is_taken_over() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = "spaces/lease-expiry";
+
+    let stalled = space_lock::try_acquire_lease(&op, ws_path, "audit", "stalled", 1).await?;
+    let renewed = space_lock::renew_lease(&op, ws_path, &stalled, 1).await?;
+    assert!(renewed.expires_at >= stalled.expires_at);
+    let taken =
+        space_lock::acquire_lease(&op, ws_path, "audit", "fresh", 30, Duration::from_secs(5))
+            .await?;
+    assert_eq!(taken.holder, "fresh");
+    assert!(taken.fencing_token > renewed.fencing_token);
+    assert!(space_lock::renew_lease(&op, ws_path, &renewed, 30)
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-017
+async fn test_space_lock_req_sto_017_writers_wait_for_lease() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "lease-writers", "/tmp").await?;
+    let ws_path = "spaces/lease-writers";
+
+    for action in ["first", "second"] {
+        audit::append_audit_event(
+            &op,
+            "lease-writers",
+            &json!({"action": action, "actor_user_id": "alice"}),
+            None,
+        )
+        .await?;
+    }
+    assert!(space_lock::current_lease(&op, ws_path, "audit")
+        .await?
+        .is_none());
+
+    // Another worker is mid-migration; this one waits for its lease to lapse.
+    space_lock::try_acquire_lease(&op, ws_path, MIGRATION_LEASE, "other-worker", 1).await?;
+    let report = revision_delta::migrate_revision_storage(&op, ws_path).await?;
+    assert!(report.migrated_forms.contains(&"Entry".to_string()));
+    let after = space_lock::try_acquire_lease(&op, ws_path, MIGRATION_LEASE, "next", 30).await?;
+    assert_eq!(after.fencing_token, 3);
+    Ok(())
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::sql::{self, SqlColumnType};
+use _ugoite_core::sql_functions::{self, SqlFunction};
+use _ugoite_core::{entry, form, index, space};
+use common::setup_operator;
+use serde_json::{json, Value};
+
+async fn sql_rows(op: &opendal::Operator, ws_path: &str, sql: &str) -> anyhow::Result<Vec<Value>> {
+    index::query_index(op, ws_path, &json!({ "$sql": sql }).to_string()).await
+}
+
+#[tokio::test]
+/// REQ-IDX-014
+async fn test_sql_functions_req_idx_014_builtin_functions() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "fn-space", "/tmp").await?;
+    let ws_path = "spaces/fn-space";
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Log",
+            "template": "# Log\n\n## Day\n\n## Body\n",
+            "fields": {
+                "Day": {"type": "date"},
+                "Body": {"type": "markdown"},
+            },
+        }),
+    )
+    .await?;
+    for (id, day, body) in [
+        (
+            "jan",
+            "2025-01-30",
+            "Shipped **the** [importer](ugoite://entry/x) today.",
+        ),
+        ("feb", "2025-02-03", "Quiet week."),
+        ("mar", "2025-03-31", "Planning for `Q2` started."),
+    ] {
+        let content = format!("---\nform: Log\n---\n# {id}\n\n## Day\n{day}\n\n## Body\n{body}\n");
+        entry::create_entry(&op, ws_path, id, &content, "author", &FakeIntegrityProvider).await?;
+    }
+
+    let rows = sql_rows(
+        &op,
+        ws_path,
+        "SELECT *, entry_link(id) AS link, markdown_excerpt(Body, 16) AS excerpt, \
+         date_trunc('month', Day) AS month FROM Log \
+         WHERE date_part('quarter', Day) = 1 AND date_trunc('week', Day) <> '2025-01-27' \
+         ORDER BY date_trunc('month', Day) DESC",
+    )
+    .await?;
+    let summary: Vec<(&str, &str, &str, &str)> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["id"].as_str().unwrap_or_default(),
+                row["link"].as_str().unwrap_or_default(),
+                row["excerpt"].as_str().unwrap_or_default(),
+                row["month"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                "mar",
+                "ugoite://entry/mar",
+                "Planning for Q2…",
+                "2025-03-01"
+            ),
+            ("feb", "ugoite://entry/feb", "Quiet week.", "2025-02-01"),
+        ]
+    );
+
+    let columns = index::sql_result_columns(
+        &op,
+        ws_path,
+        "SELECT *, date_part('year', updated_at) FROM Log",
+        None,
+    )
+    .await?;
+    let last = columns.last().expect("computed column");
+    assert_eq!(
+        (last.name.as_str(), last.column_type),
+        ("date_part", SqlColumnType::Int)
+    );
+
+    for (query, message) in [
+        (
+            "SELECT * FROM Log WHERE whisper(id) = 'x'",
+            "Unknown SQL function: whisper",
+        ),
+        (
+            "SELECT * FROM Log WHERE entry_link(id, 2) = 'x'",
+            "entry_link() takes 1 arguments, got 2",
+        ),
+        ("SELECT id FROM Log", "Only SELECT * and function calls"),
+        ("LOAD httpfs", "Queries cannot load SQL extensions"),
+    ] {
+        let err = sql::parse_sql(query).expect_err(query).to_string();
+        assert!(err.contains(message), "{query}: {err}");
+    }
+    Ok(())
+}
+
+#[test]
+/// REQ-IDX-014
+fn test_sql_functions_req_idx_014_extensions_require_configuration() -> anyhow::Result<()> {
+    sql_functions::register_extension(
+        "shouting",
+        vec![SqlFunction::new(
+            "shout",
+            1..=1,
+            SqlColumnType::String,
+            |args: &[Value]| Ok(json!(args[0].as_str().map(str::to_uppercase))),
+        )],
+    )?;
+    assert!(sql_functions::register_extension(
+        "other",
+        vec![SqlFunction::new(
+            "date_trunc",
+            0..=0,
+            SqlColumnType::String,
+            |_: &[Value]| Ok(Value::Null)
+        )],
+    )
+    .is_err());
+
+    sql_functions::set_enabled_extensions(&[]);
+    let query = "SELECT *, shout(title) AS loud FROM entries";
+    let err = sql::parse_sql(query)
+        .expect_err("extension disabled")
+        .to_string();
+    assert!(err.contains("requires extension shouting, which is not enabled"));
+
+    sql_functions::set_enabled_extensions(&["shouting"]);
+    let parsed = sql::parse_sql(query)?;
+    let tables = std::collections::HashMap::from([(
+        "entries".to_string(),
+        vec![json!({"id": "a", "title": "quiet"})],
+    )]);
+    let rows = sql::filter_entries_by_sql(&tables, &parsed)?;
+    assert_eq!(rows[0]["loud"], "QUIET");
+    Ok(())
+}
@@ -37,6 +37,8 @@ async fn test_sql_sessions_req_api_008_end_to_end() -> anyhow::Result<()> {
         name: "Alpha Query".to_string(),
         sql: "SELECT * FROM entries WHERE title = 'Alpha'".to_string(),
         variables: serde_json::json!([]),
+        folder: None,
+        tags: None,
     };
     saved_sql::create_sql(
         &op,
@@ -140,9 +142,15 @@ async fn test_sql_sessions_req_api_008_scopes_rows_before_limit() -> anyhow::Res
     let session_id = session["id"].as_str().unwrap();
     let readable_forms = vec!["PublicTask".to_string()];
 
-    let count =
-        sql_session::get_sql_session_count_scoped(&op, ws_path, session_id, &readable_forms, false)
-            .await?;
+    let count = sql_session::get_sql_session_count_scoped(
+        &op,
+        ws_path,
+        session_id,
+        &readable_forms,
+        false,
+        None,
+    )
+    .await?;
     assert_eq!(count, 2);
 
     let rows = sql_session::get_sql_session_rows_scoped(
@@ -153,6 +161,7 @@ async fn test_sql_sessions_req_api_008_scopes_rows_before_limit() -> anyhow::Res
         10,
         &readable_forms,
         false,
+        None,
     )
     .await?;
     assert_eq!(rows["total_count"], 2);
@@ -300,6 +309,7 @@ async fn test_sql_sessions_req_api_008_scopes_auxiliary_tables() -> anyhow::Resu
         assets_session["id"].as_str().unwrap(),
         &readable_forms,
         false,
+        None,
     )
     .await?;
     assert_eq!(assets_rows.len(), 1);
@@ -318,6 +328,7 @@ async fn test_sql_sessions_req_api_008_scopes_auxiliary_tables() -> anyhow::Resu
         links_session["id"].as_str().unwrap(),
         &readable_forms,
         false,
+        None,
     )
     .await?;
     assert_eq!(links_rows.len(), 1);
@@ -327,3 +338,205 @@ async fn test_sql_sessions_req_api_008_scopes_auxiliary_tables() -> anyhow::Resu
 
     Ok(())
 }
+
+#[tokio::test]
+/// REQ-API-022
+async fn test_sql_sessions_req_api_022_typed_columns() -> anyhow::Result<()> {
+    use _ugoite_core::integrity::FakeIntegrityProvider;
+    use _ugoite_core::sql::{SqlColumn, SqlColumnType};
+
+    let op = setup_operator()?;
+    space::create_space(&op, "test-sql-columns", "/tmp").await?;
+    let ws_path = "spaces/test-sql-columns";
+    form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({
+            "name": "Invoice",
+            "template": "# Invoice\n\n## Amount\n\n## Due\n\n## Paid\n\n## Code\n",
+            "fields": {
+                "Amount": {"type": "number"},
+                "Due": {"type": "date"},
+                "Paid": {"type": "boolean"},
+                "Code": {"type": "integer"},
+            },
+        }),
+    )
+    .await?;
+    form::upsert_form(
+        &op,
+        ws_path,
+        &serde_json::json!({
+            "name": "Ticket",
+            "template": "# Ticket\n\n## Code\n",
+            "fields": {"Code": {"type": "string"}},
+        }),
+    )
+    .await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "inv-1",
+        "---\nform: Invoice\n---\n# inv-1\n\n## Amount\n12.5\n\n## Due\n2024-02-01\n\n## Paid\ntrue\n\n## Code\n7\n",
+        "author",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+
+    let typed = |columns: &[SqlColumn], name: &str| {
+        columns
+            .iter()
+            .find(|column| column.name == name)
+            .map(|column| column.column_type)
+    };
+
+    let session = sql_session::create_sql_session(&op, ws_path, "SELECT * FROM invoice").await?;
+    let session_id = session["id"].as_str().unwrap();
+    let page = sql_session::get_sql_session_rows(&op, ws_path, session_id, 0, 10).await?;
+    let columns: Vec<SqlColumn> = serde_json::from_value(page["columns"].clone())?;
+    assert_eq!(
+        typed(&columns, "properties.Amount"),
+        Some(SqlColumnType::Float)
+    );
+    assert_eq!(typed(&columns, "properties.Due"), Some(SqlColumnType::Date));
+    assert_eq!(
+        typed(&columns, "properties.Paid"),
+        Some(SqlColumnType::Bool)
+    );
+    assert_eq!(typed(&columns, "properties.Code"), Some(SqlColumnType::Int));
+    assert_eq!(typed(&columns, "word_count"), Some(SqlColumnType::Int));
+    assert_eq!(
+        page["columns"][0],
+        serde_json::json!({"name": "id", "type": "string"})
+    );
+    let row = &page["rows"][0];
+    assert_eq!(row["properties"]["Amount"], 12.5);
+    assert_eq!(row["properties"]["Paid"], true);
+
+    // The entries table unions forms; conflicting field types fall back to string.
+    let session = sql_session::create_sql_session(&op, ws_path, "SELECT * FROM entries").await?;
+    let session_id = session["id"].as_str().unwrap();
+    let columns = sql_session::get_sql_session_columns(&op, ws_path, session_id).await?;
+    assert_eq!(
+        typed(&columns, "properties.Code"),
+        Some(SqlColumnType::String)
+    );
+    let scoped = sql_session::get_sql_session_columns_scoped(
+        &op,
+        ws_path,
+        session_id,
+        &["Invoice".to_string()],
+    )
+    .await?;
+    assert_eq!(typed(&scoped, "properties.Code"), Some(SqlColumnType::Int));
+    let scoped = sql_session::get_sql_session_columns_scoped(
+        &op,
+        ws_path,
+        session_id,
+        &["Ticket".to_string()],
+    )
+    .await?;
+    assert_eq!(typed(&scoped, "properties.Amount"), None);
+
+    let session = sql_session::create_sql_session(
+        &op,
+        ws_path,
+        "SELECT * FROM invoice i JOIN assets a ON a.entry_id = i.id",
+    )
+    .await?;
+    let session_id = session["id"].as_str().unwrap();
+    let columns = sql_session::get_sql_session_columns(&op, ws_path, session_id).await?;
+    assert_eq!(
+        typed(&columns, "i.properties.Due"),
+        Some(SqlColumnType::Date)
+    );
+    assert_eq!(typed(&columns, "a.path"), Some(SqlColumnType::String));
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-API-023
+async fn test_sql_sessions_req_api_023_limits_fail_session() -> anyhow::Result<()> {
+    use _ugoite_core::integrity::FakeIntegrityProvider;
+    use _ugoite_core::sql_session::SqlSessionLimits;
+
+    let op = setup_operator()?;
+    space::create_space(&op, "test-sql-limits", "/tmp").await?;
+    let ws_path = "spaces/test-sql-limits";
+    for id in ["a", "b", "c"] {
+        entry::create_entry(
+            &op,
+            ws_path,
+            id,
+            &format!("---\nform: Entry\n---\n# {id}\n\n## Body\nText for {id}.\n"),
+            "author",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+
+    let roomy = sql_session::create_sql_session(&op, ws_path, "SELECT * FROM entries").await?;
+    assert_eq!(roomy["limits"]["timeout_ms"], 30_000);
+    let roomy_id = roomy["id"].as_str().unwrap();
+    assert_eq!(
+        sql_session::get_sql_session_count(&op, ws_path, roomy_id).await?,
+        3
+    );
+
+    let tight = sql_session::create_sql_session_with_limits(
+        &op,
+        ws_path,
+        "SELECT * FROM entries e JOIN entries f ON e.form = f.form",
+        Some(SqlSessionLimits {
+            timeout_ms: u64::MAX,
+            max_bytes: 2048,
+        }),
+    )
+    .await?;
+    // Requested limits never exceed the server-wide caps.
+    assert_eq!(tight["limits"]["timeout_ms"], 30_000);
+    let tight_id = tight["id"].as_str().unwrap();
+    let err = sql_session::get_sql_session_rows(&op, ws_path, tight_id, 0, 10)
+        .await
+        .expect_err("join should exceed the memory cap");
+    assert!(err.to_string().contains("2048 byte memory cap"));
+
+    let status = sql_session::get_sql_session_status(&op, ws_path, tight_id).await?;
+    assert_eq!(status["status"], "failed");
+    assert_eq!(status["error"]["reason"], "limit_exceeded");
+    assert_eq!(status["error"]["limit"], "memory");
+    assert_eq!(status["error"]["value"], 2048);
+    let err = sql_session::get_sql_session_count(&op, ws_path, tight_id)
+        .await
+        .expect_err("failed sessions are not re-run");
+    assert!(err.to_string().starts_with("SQL session failed"));
+    Ok(())
+}
+
+#[test]
+/// REQ-API-023
+fn test_sql_sessions_req_api_023_timeout_interrupts_join() -> anyhow::Result<()> {
+    use _ugoite_core::sql::{self, SqlLimitExceeded, SqlLimitKind, SqlLimits};
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    let rows: Vec<serde_json::Value> = (0..40)
+        .map(|i| serde_json::json!({"id": format!("e{i}"), "form": "Entry"}))
+        .collect();
+    let tables = HashMap::from([("entries".to_string(), rows)]);
+    let query = sql::parse_sql("SELECT * FROM entries a JOIN entries b ON a.form = b.form")?;
+
+    let joined = sql::filter_entries_by_sql_with_limits(&tables, &query, SqlLimits::default())?;
+    assert_eq!(joined.len(), 1000);
+
+    let limits = SqlLimits {
+        timeout: Some(Duration::ZERO),
+        max_bytes: None,
+    };
+    let err = sql::filter_entries_by_sql_with_limits(&tables, &query, limits)
+        .expect_err("an elapsed deadline stops the join");
+    let exceeded = err.downcast_ref::<SqlLimitExceeded>().unwrap();
+    assert_eq!(exceeded.kind, SqlLimitKind::Timeout);
+    assert!(err.to_string().starts_with("UGOITE_SQL_ERROR"));
+    Ok(())
+}
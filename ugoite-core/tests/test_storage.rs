@@ -0,0 +1,317 @@
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::storage::fault::{self, FaultConfig, FaultStats};
+use _ugoite_core::storage::replica::{self, ReplicaConfig, ReplicaStats};
+use _ugoite_core::storage::versions::{self, ObjectVersion, VersionRestoreAction};
+use _ugoite_core::{entry, iceberg_store, space, storage};
+use opendal::services::Fs;
+use opendal::Operator;
+use tempfile::tempdir;
+
+fn fs_operator(dir: &tempfile::TempDir) -> anyhow::Result<Operator> {
+    Ok(Operator::new(Fs::default().root(dir.path().to_string_lossy().as_ref()))?.finish())
+}
+
+#[tokio::test]
+/// REQ-STO-018
+async fn test_storage_req_sto_018_publish_atomic_replaces_whole_file() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let op = fs_operator(&dir)?;
+    let old = vec![b'a'; 256 * 1024];
+    let new = vec![b'b'; 256 * 1024];
+    storage::publish_atomic(&op, "artifacts/report.json", old.clone()).await?;
+
+    let reader = {
+        let op = op.clone();
+        let (old, new) = (old.clone(), new.clone());
+        tokio::spawn(async move {
+            for _ in 0..50 {
+                let seen = op.read("artifacts/report.json").await?.to_vec();
+                assert!(seen == old || seen == new, "reader saw a partial file");
+            }
+            anyhow::Ok(())
+        })
+    };
+    for round in 0..20 {
+        let body = if round % 2 == 0 {
+            new.clone()
+        } else {
+            old.clone()
+        };
+        storage::publish_atomic(&op, "artifacts/report.json", body).await?;
+    }
+    reader.await??;
+
+    let names: Vec<String> = op
+        .list("artifacts/")
+        .await?
+        .into_iter()
+        .map(|item| item.name().to_string())
+        .filter(|name| !name.is_empty() && !name.ends_with('/'))
+        .collect();
+    assert_eq!(names, vec!["report.json".to_string()]);
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-018
+async fn test_storage_req_sto_018_readers_follow_metadata_pointer() -> anyhow::Result<()> {
+    let dir = tempdir()?;
+    let op = fs_operator(&dir)?;
+    space::create_space(&op, "snapshots", "/tmp").await?;
+    let ws_path = "spaces/snapshots";
+    entry::create_entry(
+        &op,
+        ws_path,
+        "entry-1",
+        "---\nform: Entry\n---\n# First\n\n## Body\ntext\n",
+        "alice",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+
+    let metadata_dir = format!("{ws_path}/forms/Entry/entries/metadata/");
+    let pointer = op
+        .read(&format!("{metadata_dir}current-metadata"))
+        .await?
+        .to_vec();
+    let current = String::from_utf8(pointer)?;
+    assert!(op.exists(&format!("{metadata_dir}{current}")).await?);
+
+    // A newer generation that is still being written must stay invisible.
+    op.write(
+        &format!("{metadata_dir}99999-torn.metadata.json"),
+        b"{\"format-version\": 2, \"sch".to_vec(),
+    )
+    .await?;
+    let fields = iceberg_store::load_form_schema_fields(&op, ws_path, "Entry").await?;
+    assert!(fields.is_some_and(|fields| fields.contains("Body")));
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-019
+async fn test_storage_req_sto_019_faults_are_seeded_and_reproducible() -> anyhow::Result<()> {
+    async fn outcomes(seed: u64) -> anyhow::Result<(Vec<bool>, FaultStats)> {
+        let (op, layer) = fault::fault_operator(FaultConfig {
+            seed,
+            error_rate: 0.4,
+            max_latency_ms: 2,
+            ..Default::default()
+        })?;
+        let mut results = Vec::new();
+        for idx in 0..30 {
+            results.push(
+                op.write(&format!("sim/{idx}.json"), b"{}".to_vec())
+                    .await
+                    .is_ok(),
+            );
+        }
+        Ok((results, layer.stats()))
+    }
+    let (first, stats) = outcomes(11).await?;
+    let (second, _) = outcomes(11).await?;
+    assert_eq!(first, second);
+    assert!(first.contains(&true) && first.contains(&false));
+    assert_eq!(
+        stats.errors as usize,
+        first.iter().filter(|ok| !**ok).count()
+    );
+    assert!(stats.delays > 0);
+
+    let err = fault::fault_operator(FaultConfig {
+        error_rate: 1.0,
+        ..Default::default()
+    })?
+    .0
+    .read("missing")
+    .await
+    .unwrap_err();
+    assert!(err.is_temporary());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-019
+async fn test_storage_req_sto_019_partial_writes_and_stale_reads() -> anyhow::Result<()> {
+    let (op, layer) = fault::fault_operator(FaultConfig {
+        partial_write_rate: 1.0,
+        ..Default::default()
+    })?;
+    assert!(op.write("torn.json", b"0123456789".to_vec()).await.is_err());
+    assert_eq!(op.read("torn.json").await?.to_vec(), b"01234".to_vec());
+    assert_eq!(layer.stats().partial_writes, 1);
+
+    let (op, layer) = fault::fault_operator(FaultConfig {
+        stale_reads: 2,
+        ..Default::default()
+    })?;
+    op.write("dir/new.json", b"{}".to_vec()).await?;
+    assert!(!op.exists("dir/new.json").await?);
+    assert!(op
+        .list("dir/")
+        .await?
+        .iter()
+        .all(|item| item.name() != "new.json"));
+    assert!(op.exists("dir/new.json").await?);
+    assert_eq!(op.read("dir/new.json").await?.to_vec(), b"{}".to_vec());
+    // Overwrites of visible objects are not delayed.
+    op.write("dir/new.json", b"[]".to_vec()).await?;
+    assert_eq!(op.read("dir/new.json").await?.to_vec(), b"[]".to_vec());
+    assert_eq!(layer.stats().stale_reads, 2);
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-019
+async fn test_storage_req_sto_019_test_uri_selects_fault_operator() -> anyhow::Result<()> {
+    let uri = "test://sim-uri?seed=5&stale_reads=1";
+    let config = FaultConfig::from_uri(uri)?;
+    assert_eq!(config.seed, 5);
+    assert_eq!(config.stale_reads, 1);
+    assert!(FaultConfig::from_uri("test://sim?error_rate=2").is_err());
+    assert!(FaultConfig::from_uri("test://sim?bogus=1").is_err());
+
+    let op = storage::operator_from_uri(uri)?;
+    op.write("shared.json", b"{}".to_vec()).await?;
+    let again = storage::operator_from_uri(uri)?;
+    assert!(!again.exists("shared.json").await?);
+    assert!(again.exists("shared.json").await?);
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-STO-021
+async fn test_storage_req_sto_021_reads_prefer_replica_and_fall_back() -> anyhow::Result<()> {
+    let primary = storage::operator_from_uri("memory://replica-primary")?;
+    let mirror = storage::operator_from_uri("memory://replica-mirror")?;
+    primary.write("doc.json", b"primary".to_vec()).await?;
+    mirror.write("doc.json", b"mirror".to_vec()).await?;
+    primary.write("only-primary.json", b"new".to_vec()).await?;
+
+    let (op, layer) = replica::replica_operator(
+        primary.clone(),
+        mirror.clone(),
+        ReplicaConfig {
+            max_staleness_ms: 60_000,
+        },
+    );
+    assert_eq!(op.read("doc.json").await?.to_vec(), b"mirror".to_vec());
+    assert_eq!(
+        op.read("only-primary.json").await?.to_vec(),
+        b"new".to_vec()
+    );
+
+    // Writes reach only the primary and are read back from it while the
+    // replica may still be catching up.
+    op.write("doc.json", b"updated".to_vec()).await?;
+    assert_eq!(mirror.read("doc.json").await?.to_vec(), b"mirror".to_vec());
+    assert_eq!(op.read("doc.json").await?.to_vec(), b"updated".to_vec());
+    op.write("dir/a.json", b"{}".to_vec()).await?;
+    let names: Vec<String> = op
+        .list("dir/")
+        .await?
+        .iter()
+        .map(|item| item.name().to_string())
+        .collect();
+    assert!(names.contains(&"a.json".to_string()));
+    op.delete("doc.json").await?;
+    assert!(!op.exists("doc.json").await?);
+    assert!(mirror.exists("doc.json").await?);
+    assert_eq!(
+        layer.stats(),
+        ReplicaStats {
+            replica_reads: 1,
+            primary_reads: 3,
+            fallbacks: 1,
+        }
+    );
+
+    // Without a staleness window every read tries the replica first.
+    let (lagging, _) = replica::replica_operator(
+        primary.clone(),
+        mirror.clone(),
+        ReplicaConfig {
+            max_staleness_ms: 0,
+        },
+    );
+    lagging
+        .write("only-primary.json", b"newer".to_vec())
+        .await?;
+    mirror.write("only-primary.json", b"new".to_vec()).await?;
+    assert_eq!(
+        lagging.read("only-primary.json").await?.to_vec(),
+        b"new".to_vec()
+    );
+    Ok(())
+}
+
+fn object_version(path: &str, id: &str, at: f64, latest: bool, deleted: bool) -> ObjectVersion {
+    ObjectVersion {
+        path: path.to_string(),
+        version_id: id.to_string(),
+        last_modified: Some(at),
+        size: if deleted { 0 } else { 10 },
+        is_latest: latest,
+        is_delete_marker: deleted,
+    }
+}
+
+#[tokio::test]
+/// REQ-STO-022
+async fn test_storage_req_sto_022_plans_point_in_time_restore() -> anyhow::Result<()> {
+    let versions = vec![
+        // Edited after the restore point: back to v1.
+        object_version("spaces/s/meta.json", "m1", 100.0, false, false),
+        object_version("spaces/s/meta.json", "m2", 300.0, true, false),
+        // Unchanged since before the restore point.
+        object_version("spaces/s/settings.json", "s1", 50.0, true, false),
+        // Created after the restore point.
+        object_version("spaces/s/new.json", "n1", 250.0, true, false),
+        // Deleted after the restore point: resurrected.
+        object_version("spaces/s/gone.json", "g1", 150.0, false, false),
+        object_version("spaces/s/gone.json", "g2", 280.0, true, true),
+        // Already deleted at the restore point and still deleted.
+        object_version("spaces/s/old.json", "o1", 10.0, false, false),
+        object_version("spaces/s/old.json", "o2", 20.0, true, true),
+        // Deleted at the restore point but recreated since.
+        object_version("spaces/s/back.json", "b1", 10.0, false, false),
+        object_version("spaces/s/back.json", "b2", 20.0, false, true),
+        object_version("spaces/s/back.json", "b3", 260.0, true, false),
+    ];
+    let actions = versions::plan_point_in_time_restore(&versions, 200.0);
+    assert_eq!(
+        actions,
+        vec![
+            VersionRestoreAction::Delete {
+                path: "spaces/s/back.json".into()
+            },
+            VersionRestoreAction::Restore {
+                path: "spaces/s/gone.json".into(),
+                version_id: "g1".into()
+            },
+            VersionRestoreAction::Restore {
+                path: "spaces/s/meta.json".into(),
+                version_id: "m1".into()
+            },
+            VersionRestoreAction::Delete {
+                path: "spaces/s/new.json".into()
+            },
+        ]
+    );
+
+    // Local and in-memory stores keep no versions.
+    let op = storage::operator_from_uri("memory://versions")?;
+    space::create_space(&op, "s", "/tmp").await?;
+    let err = versions::list_object_versions(&op, "spaces/s/")
+        .await
+        .expect_err("memory keeps no versions");
+    assert!(err.to_string().contains("enable bucket versioning"));
+    let err = space::restore_space_to_timestamp(&op, "s", 200.0, true)
+        .await
+        .expect_err("memory keeps no versions");
+    assert!(err.to_string().contains("does not expose object versions"));
+    assert!(space::restore_space_to_timestamp(&op, "s", 1e12, true)
+        .await
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,191 @@
+mod common;
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use _ugoite_core::sync::{self, SyncOperation, SyncOperationKind, SyncOutcome};
+use common::setup_operator;
+
+fn note(title: &str, body: &str) -> String {
+    format!("---\nform: Entry\n---\n# {title}\n\n## Body\n{body}\n")
+}
+
+fn upsert(entry_id: &str, markdown: String, base: Option<&str>) -> SyncOperation {
+    SyncOperation {
+        entry_id: entry_id.to_string(),
+        kind: SyncOperationKind::Upsert,
+        markdown: Some(markdown),
+        base_revision_id: base.map(str::to_string),
+    }
+}
+
+async fn seed_sync_space(op: &opendal::Operator, space_id: &str) -> anyhow::Result<String> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{space_id}");
+    for (entry_id, title) in [("alpha", "Alpha"), ("beta", "Beta")] {
+        entry::create_entry(
+            op,
+            &ws_path,
+            entry_id,
+            &note(title, "Initial"),
+            "alice",
+            &FakeIntegrityProvider,
+        )
+        .await?;
+    }
+    Ok(ws_path)
+}
+
+#[tokio::test]
+/// REQ-ENTRY-016
+async fn test_sync_req_entry_016_pull_pages_changes_by_cursor() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_sync_space(&op, "sync-pull").await?;
+
+    let first = sync::sync_pull(&op, &ws_path, None, 1, None).await?;
+    assert_eq!(first.changes.len(), 1);
+    assert!(first.has_more);
+    let second = sync::sync_pull(&op, &ws_path, Some(&first.cursor), 10, None).await?;
+    assert_eq!(second.changes.len(), 1);
+    assert!(!second.has_more);
+    let mut ids = vec![
+        first.changes[0].entry_id.clone(),
+        second.changes[0].entry_id.clone(),
+    ];
+    ids.sort();
+    assert_eq!(ids, vec!["alpha", "beta"]);
+    assert!(second.changes[0]
+        .markdown
+        .as_deref()
+        .unwrap()
+        .contains("Initial"));
+
+    let idle = sync::sync_pull(&op, &ws_path, Some(&second.cursor), 10, None).await?;
+    assert!(idle.changes.is_empty());
+    assert_eq!(idle.cursor, second.cursor);
+
+    let alpha = entry::get_entry_content(&op, &ws_path, "alpha").await?;
+    entry::update_entry(
+        &op,
+        &ws_path,
+        "alpha",
+        &note("Alpha", "Edited online"),
+        Some(&alpha.revision_id),
+        "bob",
+        None,
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    entry::delete_entry(&op, &ws_path, "beta", false).await?;
+
+    let delta = sync::sync_pull(&op, &ws_path, Some(&idle.cursor), 10, None).await?;
+    assert_eq!(delta.changes.len(), 2);
+    assert_eq!(delta.changes[0].entry_id, "alpha");
+    assert_eq!(delta.changes[0].parent_revision_id, Some(alpha.revision_id));
+    assert_eq!(delta.changes[0].author, "bob");
+    assert_eq!(delta.changes[1].entry_id, "beta");
+    assert!(delta.changes[1].deleted);
+    assert!(delta.changes[1].markdown.is_none());
+
+    assert!(sync::sync_pull(&op, &ws_path, Some("garbage"), 10, None)
+        .await
+        .is_err());
+    assert!(sync::sync_pull(&op, &ws_path, None, 0, None).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-016
+async fn test_sync_req_entry_016_push_resolves_conflicts() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = seed_sync_space(&op, "sync-push").await?;
+    let integrity = FakeIntegrityProvider;
+    let base = entry::get_entry_content(&op, &ws_path, "alpha").await?;
+    let beta = entry::get_entry_content(&op, &ws_path, "beta").await?;
+
+    // Another device edits alpha before this client reconnects.
+    entry::update_entry(
+        &op,
+        &ws_path,
+        "alpha",
+        &note("Alpha", "Server edit"),
+        Some(&base.revision_id),
+        "bob",
+        None,
+        &integrity,
+    )
+    .await?;
+    let server_head = entry::get_entry_content(&op, &ws_path, "alpha").await?;
+
+    let operations = vec![
+        upsert("gamma", note("Gamma", "Written offline"), None),
+        upsert(
+            "beta",
+            note("Beta", "Offline edit"),
+            Some(&beta.revision_id),
+        ),
+        upsert(
+            "alpha",
+            note("Alpha", "Offline edit"),
+            Some(&base.revision_id),
+        ),
+        upsert(
+            "alpha",
+            server_head.markdown.clone(),
+            Some(&base.revision_id),
+        ),
+        SyncOperation {
+            entry_id: "missing".to_string(),
+            kind: SyncOperationKind::Delete,
+            markdown: None,
+            base_revision_id: None,
+        },
+    ];
+    let results = sync::sync_push(&op, &ws_path, &operations, "alice", &integrity).await?;
+    let outcomes: Vec<SyncOutcome> = results.iter().map(|r| r.outcome).collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            SyncOutcome::Applied,
+            SyncOutcome::Applied,
+            SyncOutcome::Conflict,
+            SyncOutcome::Unchanged,
+            SyncOutcome::Rejected,
+        ]
+    );
+    assert!(results[4].error.as_deref().unwrap().contains("not found"));
+
+    // The server keeps its head; the client's version survives as its own entry.
+    let alpha = entry::get_entry_content(&op, &ws_path, "alpha").await?;
+    assert_eq!(alpha.revision_id, server_head.revision_id);
+    assert_eq!(
+        results[2].revision_id.as_deref(),
+        Some(server_head.revision_id.as_str())
+    );
+    let conflict_id = results[2].conflict_entry_id.clone().unwrap();
+    assert!(conflict_id.starts_with("alpha-conflict-"));
+    let conflict = entry::get_entry_content(&op, &ws_path, &conflict_id).await?;
+    assert!(conflict.markdown.contains("Offline edit"));
+
+    // Retrying the same push is idempotent for the conflict copy.
+    let retry = sync::sync_push(&op, &ws_path, &operations[2..3], "alice", &integrity).await?;
+    assert_eq!(
+        retry[0].conflict_entry_id.as_deref(),
+        Some(conflict_id.as_str())
+    );
+
+    // A delete based on a stale revision loses to the newer server edit.
+    let stale_delete = SyncOperation {
+        entry_id: "alpha".to_string(),
+        kind: SyncOperationKind::Delete,
+        markdown: None,
+        base_revision_id: Some(base.revision_id.clone()),
+    };
+    let results = sync::sync_push(&op, &ws_path, &[stale_delete], "alice", &integrity).await?;
+    assert_eq!(results[0].outcome, SyncOutcome::Conflict);
+    assert!(!entry::get_entry(&op, &ws_path, "alpha").await?["deleted"]
+        .as_bool()
+        .unwrap_or(false));
+
+    Ok(())
+}
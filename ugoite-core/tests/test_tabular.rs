@@ -0,0 +1,371 @@
+mod common;
+use _ugoite_core::entry;
+use _ugoite_core::form;
+use _ugoite_core::importers::ImportJobStatus;
+use _ugoite_core::tabular::{TabularExportFormat, TabularImportOptions};
+use _ugoite_core::{space, space_lock};
+use common::setup_operator;
+use std::collections::BTreeMap;
+
+async fn setup_meeting_form(op: &opendal::Operator, space_id: &str) -> anyhow::Result<String> {
+    space::create_space(op, space_id, "/tmp").await?;
+    let ws_path = format!("spaces/{}", space_id);
+    let form_def = serde_json::json!({
+        "name": "Meeting",
+        "fields": {
+            "Date": {"type": "date", "required": true},
+            "Attendees": {"type": "integer"},
+            "Notes": {"type": "markdown"}
+        }
+    });
+    form::upsert_form(op, &ws_path, &form_def).await?;
+    Ok(ws_path)
+}
+
+#[tokio::test]
+/// REQ-FORM-010
+async fn test_tabular_req_form_010_import_csv_with_row_report() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = setup_meeting_form(&op, "tabular-csv").await?;
+
+    let csv = "title,When,Headcount,Notes,labels\n\
+               Kickoff,2025-01-02,3,\"Agenda\nRetro\",planning;q1\n\
+               Broken,not-a-date,many,,\n\
+               ,,,,\n\
+               ,2025-02-03,5,,\n";
+    let mapping = BTreeMap::from([
+        ("When".to_string(), "Date".to_string()),
+        ("Headcount".to_string(), "Attendees".to_string()),
+        ("Notes".to_string(), "Notes".to_string()),
+    ]);
+    let options = TabularImportOptions {
+        tags_column: Some("labels".to_string()),
+        ..TabularImportOptions::default()
+    };
+    let report =
+        form::import_tabular(&op, &ws_path, "Meeting", csv.as_bytes(), &mapping, &options).await?;
+
+    assert_eq!(report.total_rows, 3);
+    assert_eq!(report.created, 2);
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.errors.len(), 1);
+    assert_eq!(report.errors[0].row, 3);
+    assert!(report.errors[0]
+        .errors
+        .iter()
+        .any(|e| e.contains("Field 'Date' has invalid type")));
+    assert!(report.errors[0]
+        .errors
+        .iter()
+        .any(|e| e.contains("Field 'Attendees' has invalid type")));
+
+    let kickoff = entry::get_entry(&op, &ws_path, &report.entry_ids[0]).await?;
+    assert_eq!(kickoff["title"], "Kickoff");
+    assert_eq!(kickoff["form"], "Meeting");
+    assert_eq!(kickoff["tags"], serde_json::json!(["planning", "q1"]));
+    assert_eq!(kickoff["frontmatter"]["form"], "Meeting");
+    let content = kickoff["content"].as_str().unwrap();
+    assert!(content.contains("## Date\n2025-01-02"));
+    assert!(content.contains("## Attendees\n3"));
+    assert!(content.contains("Agenda\nRetro"));
+
+    let untitled = entry::get_entry(&op, &ws_path, &report.entry_ids[1]).await?;
+    assert_eq!(untitled["title"], "Meeting 5");
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-010
+async fn test_tabular_req_form_010_rejects_unknown_mapping() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = setup_meeting_form(&op, "tabular-mapping").await?;
+    let options = TabularImportOptions::default();
+
+    let unknown_field = BTreeMap::from([("Date".to_string(), "Missing".to_string())]);
+    let err = form::import_tabular(
+        &op,
+        &ws_path,
+        "Meeting",
+        b"Date\n",
+        &unknown_field,
+        &options,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Unknown form field in mapping"));
+
+    let unknown_column = BTreeMap::from([("When".to_string(), "Date".to_string())]);
+    let err = form::import_tabular(
+        &op,
+        &ws_path,
+        "Meeting",
+        b"Date\n",
+        &unknown_column,
+        &options,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Column not found: When"));
+
+    let err = form::import_tabular(&op, &ws_path, "SQL", b"Date\n", &BTreeMap::new(), &options)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("metadata form"));
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-010
+async fn test_tabular_req_form_010_job_auto_maps_and_resumes() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = setup_meeting_form(&op, "tabular-job").await?;
+
+    let mut csv = String::from("Title;date;attendees\n");
+    for i in 1..=30 {
+        csv.push_str(&format!("Standup {i};2025-03-{:02};{i}\n", (i % 28) + 1));
+    }
+    let options = TabularImportOptions {
+        delimiter: ';',
+        ..TabularImportOptions::default()
+    };
+    let job = form::create_tabular_import_job(
+        &op,
+        &ws_path,
+        "Meeting",
+        csv.as_bytes(),
+        &BTreeMap::new(),
+        &options,
+    )
+    .await?;
+    assert_eq!(job.total_rows, 30);
+
+    let mut finished = None;
+    for _ in 0..400 {
+        let current = form::get_tabular_import_job(&op, &ws_path, &job.job_id).await?;
+        if matches!(
+            current.status,
+            ImportJobStatus::Completed | ImportJobStatus::Failed
+        ) {
+            finished = Some(current);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+    }
+    let finished = finished.expect("tabular import job did not finish");
+    assert_eq!(finished.status, ImportJobStatus::Completed);
+    assert_eq!(finished.processed_rows, 30);
+    assert_eq!(finished.report.created, 30);
+    assert_eq!(finished.report.failed, 0);
+
+    let first = entry::get_entry(&op, &ws_path, &finished.report.entry_ids[0]).await?;
+    assert_eq!(first["title"], "Standup 1");
+
+    let err = form::resume_tabular_import_job(&op, &ws_path, &job.job_id)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("already completed"));
+
+    // Simulate a crash after the checkpoint at row 20 with later rows already written.
+    let mut interrupted = finished.clone();
+    interrupted.status = ImportJobStatus::Running;
+    interrupted.processed_rows = 20;
+    interrupted.report.created = 20;
+    interrupted.report.entry_ids.truncate(20);
+    let job_dir = format!("{}/tabular_import_jobs/{}", ws_path, job.job_id);
+    op.write(
+        &format!("{}.json", job_dir),
+        serde_json::to_vec(&interrupted)?,
+    )
+    .await?;
+    op.write(&format!("{}.source", job_dir), csv.clone().into_bytes())
+        .await?;
+
+    // A runner that still holds the job's lease is not interrupted.
+    let live = space_lock::try_acquire_lease(
+        &op,
+        &ws_path,
+        &format!("tabular-import-{}", job.job_id),
+        "other-worker",
+        60,
+    )
+    .await?;
+    let err = form::resume_tabular_import_job(&op, &ws_path, &job.job_id)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("still running"), "{err}");
+    space_lock::release_lease(&op, &ws_path, &live).await?;
+    form::resume_tabular_import_job(&op, &ws_path, &job.job_id).await?;
+
+    let mut resumed = None;
+    for _ in 0..400 {
+        let current = form::get_tabular_import_job(&op, &ws_path, &job.job_id).await?;
+        if current.status == ImportJobStatus::Completed {
+            resumed = Some(current);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+    }
+    let resumed = resumed.expect("resumed job did not finish");
+    assert_eq!(resumed.report.created, 30);
+    assert_eq!(resumed.report.entry_ids, finished.report.entry_ids);
+    let meetings = entry::list_entries(&op, &ws_path).await?;
+    let count = meetings
+        .iter()
+        .filter(|e| e.get("form").and_then(|f| f.as_str()) == Some("Meeting"))
+        .count();
+    assert_eq!(count, 30);
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-011
+async fn test_tabular_req_form_011_export_csv_and_xlsx_round_trip() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    let ws_path = setup_meeting_form(&op, "tabular-export").await?;
+    let csv = "title,Date,Attendees,labels\n\
+               Kickoff,2025-01-02,3,planning\n\
+               \"Retro, part 2\",2025-01-09,,\n";
+    let options = TabularImportOptions {
+        tags_column: Some("labels".to_string()),
+        ..TabularImportOptions::default()
+    };
+    let imported = form::import_tabular(
+        &op,
+        &ws_path,
+        "Meeting",
+        csv.as_bytes(),
+        &BTreeMap::new(),
+        &options,
+    )
+    .await?;
+    assert_eq!(imported.created, 2);
+
+    let mut buffer = Vec::new();
+    let count = form::export_tabular(
+        &op,
+        &ws_path,
+        "Meeting",
+        TabularExportFormat::Csv,
+        &mut buffer,
+    )
+    .await?;
+    assert_eq!(count, 2);
+    let text = String::from_utf8(buffer)?;
+    let mut lines = text.lines();
+    assert_eq!(
+        lines.next(),
+        Some("entry_id,title,tags,author,created_at,updated_at,revision_id,Attendees,Date,Notes")
+    );
+    assert!(text.contains(",Kickoff,planning,importer,"));
+    assert!(text.contains(",3,2025-01-02,"));
+    assert!(text.contains("\"Retro, part 2\""));
+
+    let mut workbook = Vec::new();
+    form::export_tabular(
+        &op,
+        &ws_path,
+        "Meeting",
+        TabularExportFormat::Xlsx,
+        &mut workbook,
+    )
+    .await?;
+    assert!(workbook.starts_with(b"PK\x03\x04"));
+
+    let copy_path = setup_meeting_form(&op, "tabular-export-copy").await?;
+    let mapping = BTreeMap::from([
+        ("Date".to_string(), "Date".to_string()),
+        ("Attendees".to_string(), "Attendees".to_string()),
+    ]);
+    let copy_options = TabularImportOptions {
+        sheet: Some("Meeting".to_string()),
+        tags_column: Some("tags".to_string()),
+        ..TabularImportOptions::default()
+    };
+    let copied = form::import_tabular(
+        &op,
+        &copy_path,
+        "Meeting",
+        &workbook,
+        &mapping,
+        &copy_options,
+    )
+    .await?;
+    assert_eq!(copied.created, 2);
+    assert_eq!(copied.failed, 0);
+    let kickoff = entry::get_entry(&op, &copy_path, &copied.entry_ids[0]).await?;
+    assert_eq!(kickoff["title"], "Kickoff");
+    assert_eq!(kickoff["tags"], serde_json::json!(["planning"]));
+    assert!(kickoff["content"]
+        .as_str()
+        .unwrap()
+        .contains("## Attendees\n3"));
+
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-011
+async fn test_tabular_req_form_011_export_parquet_typed_columns() -> anyhow::Result<()> {
+    use arrow_array::{Array, Int32Array, StringArray};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let op = setup_operator()?;
+    let ws_path = setup_meeting_form(&op, "tabular-parquet").await?;
+    let csv = "title,Date,Attendees\nKickoff,2025-01-02,3\nRetro,2025-01-09,\n";
+    form::import_tabular(
+        &op,
+        &ws_path,
+        "Meeting",
+        csv.as_bytes(),
+        &BTreeMap::new(),
+        &TabularImportOptions::default(),
+    )
+    .await?;
+
+    let mut file = tempfile::tempfile()?;
+    let count = form::export_tabular(
+        &op,
+        &ws_path,
+        "Meeting",
+        TabularExportFormat::Parquet,
+        &mut file,
+    )
+    .await?;
+    assert_eq!(count, 2);
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)?.build()?;
+    let batches = reader.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(batches.len(), 1);
+    let batch = &batches[0];
+    assert_eq!(batch.num_rows(), 2);
+    let schema = batch.schema();
+    assert_eq!(schema.field(0).name(), "entry_id");
+    let titles = batch
+        .column_by_name("title")
+        .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+        .expect("title column");
+    assert_eq!(titles.value(0), "Kickoff");
+    let attendees = batch
+        .column_by_name("Attendees")
+        .and_then(|c| c.as_any().downcast_ref::<Int32Array>())
+        .expect("typed attendees column");
+    assert_eq!(attendees.value(0), 3);
+    assert!(attendees.is_null(1));
+
+    let err = form::export_tabular(
+        &op,
+        &ws_path,
+        "Missing",
+        TabularExportFormat::Csv,
+        Vec::new(),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Form Missing not found"));
+    assert!(TabularExportFormat::parse("ods").is_err());
+
+    Ok(())
+}
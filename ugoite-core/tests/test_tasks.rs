@@ -0,0 +1,123 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use _ugoite_core::tasks::{self, TaskFilter};
+use common::setup_operator;
+
+#[test]
+/// REQ-ENTRY-018
+fn test_tasks_req_entry_018_parse_tasks_extracts_annotations() {
+    let markdown = "# Plan\n\n- [ ] Draft report due:2025-03-01 @alice\n* [x] Book room 📅 2025-02-10 @bob. @alice\n1. [ ] Call vendor\n\n```\n- [ ] not a task\n```\n- plain item mailto:me@example.com\n";
+    let parsed = tasks::parse_tasks(markdown);
+    assert_eq!(parsed.len(), 3);
+
+    assert_eq!(parsed[0].index, 0);
+    assert!(!parsed[0].checked);
+    assert_eq!(parsed[0].text, "Draft report due:2025-03-01 @alice");
+    assert_eq!(parsed[0].due.as_deref(), Some("2025-03-01"));
+    assert_eq!(parsed[0].owners, vec!["alice".to_string()]);
+    assert_eq!(parsed[0].line, 2);
+
+    assert!(parsed[1].checked);
+    assert_eq!(parsed[1].due.as_deref(), Some("2025-02-10"));
+    assert_eq!(
+        parsed[1].owners,
+        vec!["bob".to_string(), "alice".to_string()]
+    );
+
+    assert_eq!(parsed[2].text, "Call vendor");
+    assert_eq!(parsed[2].due, None);
+    assert!(parsed[2].owners.is_empty());
+}
+
+#[tokio::test]
+/// REQ-ENTRY-018
+async fn test_tasks_req_entry_018_list_and_toggle_tasks() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "tasks-space", "/tmp").await?;
+    let ws_path = "spaces/tasks-space";
+    let integrity = FakeIntegrityProvider;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "plan",
+        "---\nform: Entry\n---\n# Plan\n\n## Body\n- [ ] Draft report due:2025-03-01 @alice\n- [x] Book room due:2025-02-10 @bob\n",
+        "author",
+        &integrity,
+    )
+    .await?;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "retro",
+        "---\nform: Entry\ntags: [team]\n---\n# Retro\n\n## Body\n- [ ] Share notes @alice\n",
+        "author",
+        &integrity,
+    )
+    .await?;
+
+    let all = tasks::list_tasks(&op, ws_path, &TaskFilter::default()).await?;
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].entry_id, "plan");
+    assert_eq!(all[0].entry_title, "Plan");
+
+    let open_for_alice = tasks::list_tasks(
+        &op,
+        ws_path,
+        &TaskFilter {
+            checked: Some(false),
+            owner: Some("@alice".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(open_for_alice.len(), 2);
+
+    let due_in_feb = tasks::list_tasks(
+        &op,
+        ws_path,
+        &TaskFilter {
+            due_after: Some("2025-02-01".to_string()),
+            due_before: Some("2025-02-28".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(due_in_feb.len(), 1);
+    assert_eq!(due_in_feb[0].task.text, "Book room due:2025-02-10 @bob");
+
+    let tagged = tasks::list_tasks(
+        &op,
+        ws_path,
+        &TaskFilter {
+            tag: Some("team".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(tagged.len(), 1);
+    assert_eq!(tagged[0].entry_id, "retro");
+
+    tasks::toggle_task(&op, ws_path, "plan", 0, "editor", &integrity).await?;
+    let plan_tasks = tasks::list_tasks(
+        &op,
+        ws_path,
+        &TaskFilter {
+            entry_id: Some("plan".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert!(plan_tasks.iter().all(|item| item.task.checked));
+    let content = entry::get_entry(&op, ws_path, "plan").await?["content"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    assert!(content.contains("- [x] Draft report due:2025-03-01 @alice"));
+
+    let missing = tasks::toggle_task(&op, ws_path, "plan", 5, "editor", &integrity).await;
+    assert!(missing.is_err());
+    Ok(())
+}
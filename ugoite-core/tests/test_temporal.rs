@@ -0,0 +1,155 @@
+mod common;
+
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::temporal;
+use _ugoite_core::{entry, form, index, space};
+use common::setup_operator;
+use serde_json::json;
+
+#[test]
+/// REQ-IDX-015
+fn test_temporal_req_idx_015_parse_values() {
+    let tokyo = temporal::parse_offset("+09:00").unwrap();
+    assert_eq!(tokyo.local_minus_utc(), 9 * 3600);
+    assert_eq!(
+        temporal::parse_offset("-0530").unwrap().local_minus_utc(),
+        -(5 * 3600 + 30 * 60)
+    );
+    assert_eq!(temporal::parse_offset("+9").unwrap(), tokyo);
+    assert_eq!(
+        temporal::parse_offset("UTC").unwrap(),
+        temporal::utc_offset()
+    );
+    assert!(temporal::parse_offset("Asia/Tokyo").is_err());
+    assert!(temporal::parse_offset("+15:00").is_err());
+
+    assert_eq!(
+        temporal::normalize_datetime("2024-03-01 09:00", tokyo).as_deref(),
+        Some("2024-03-01T09:00:00+09:00")
+    );
+    assert_eq!(
+        temporal::normalize_datetime("2024-03-01", tokyo).as_deref(),
+        Some("2024-03-01T00:00:00+09:00")
+    );
+    assert_eq!(
+        temporal::normalize_datetime("2024-03-01T09:00:00Z", tokyo).as_deref(),
+        Some("2024-03-01T09:00:00+00:00")
+    );
+    assert_eq!(temporal::normalize_datetime("next tuesday", tokyo), None);
+
+    // Same instant written in two offsets compares equal.
+    assert_eq!(
+        temporal::compare_instants(
+            &json!("2024-03-01T09:00:00+09:00"),
+            &json!("2024-03-01T00:00:00Z")
+        ),
+        Some(std::cmp::Ordering::Equal)
+    );
+    assert_eq!(
+        temporal::compare_instants(&json!("2024-03-01"), &json!("2024-03-02")),
+        None
+    );
+
+    for (input, secs) in [
+        (json!(90), Some(90)),
+        (json!("PT1H30M"), Some(5400)),
+        (json!("P1DT2H"), Some(93_600)),
+        (json!("P1W"), Some(604_800)),
+        (json!("1h30m"), Some(5400)),
+        (json!("2d"), Some(172_800)),
+        (json!("1:30:00"), Some(5400)),
+        (json!("90:00"), Some(5400)),
+        (json!("-45s"), Some(-45)),
+        (json!("P1M"), None),
+        (json!("soon"), None),
+    ] {
+        assert_eq!(temporal::parse_duration_secs(&input), secs, "{input}");
+    }
+}
+
+#[tokio::test]
+/// REQ-IDX-015
+async fn test_temporal_req_idx_015_space_timezone_and_sql() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "tz-space", "/tmp").await?;
+    let ws_path = "spaces/tz-space";
+    space::patch_space(
+        &op,
+        "tz-space",
+        &json!({"settings": {"default_timezone": "+09:00"}}),
+    )
+    .await?;
+    assert!(space::patch_space(
+        &op,
+        "tz-space",
+        &json!({"settings": {"default_timezone": "Mars/Olympus"}}),
+    )
+    .await
+    .is_err());
+
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Meeting",
+            "template": "# Meeting\n\n## Start\n\n## Length\n",
+            "fields": {
+                "Start": {"type": "datetime"},
+                "Length": {"type": "duration"},
+            },
+        }),
+    )
+    .await?;
+    for (id, start, length) in [
+        ("standup", "2024-03-01 09:00", "1h30m"),
+        ("retro", "2024-03-01T01:00:00Z", "PT45M"),
+    ] {
+        let content = format!(
+            "---\nform: Meeting\n---\n# {id}\n\n## Start\n{start}\n\n## Length\n{length}\n"
+        );
+        entry::create_entry(&op, ws_path, id, &content, "author", &FakeIntegrityProvider).await?;
+    }
+
+    let rows = index::query_index(
+        &op,
+        ws_path,
+        &json!({"$sql": "SELECT * FROM Meeting ORDER BY Start"}).to_string(),
+    )
+    .await?;
+    let stored: Vec<(&str, &str, i64)> = rows
+        .iter()
+        .map(|row| {
+            (
+                row["id"].as_str().unwrap_or_default(),
+                row["properties"]["Start"].as_str().unwrap_or_default(),
+                row["properties"]["Length"].as_i64().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        stored,
+        vec![
+            ("standup", "2024-03-01T09:00:00+09:00", 5400),
+            ("retro", "2024-03-01T01:00:00+00:00", 2700),
+        ]
+    );
+
+    // 09:00+09:00 is 00:00Z, before 00:30Z, even though the strings sort the
+    // other way.
+    let later = index::query_index(
+        &op,
+        ws_path,
+        &json!({"$sql": "SELECT * FROM Meeting WHERE Start < '2024-03-01T00:30:00Z'"}).to_string(),
+    )
+    .await?;
+    let ids: Vec<&str> = later.iter().filter_map(|row| row["id"].as_str()).collect();
+    assert_eq!(ids, vec!["standup"]);
+
+    let bad = "---\nform: Meeting\n---\n# bad\n\n## Start\nsoon\n\n## Length\n1h\n";
+    assert!(
+        entry::create_entry(&op, ws_path, "bad", bad, "author", &FakeIntegrityProvider)
+            .await
+            .is_err()
+    );
+    Ok(())
+}
@@ -0,0 +1,235 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::form;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use _ugoite_core::unique::{self, UniqueConflict};
+use common::setup_operator;
+use serde_json::json;
+
+fn person(id: &str, email: &str) -> String {
+    format!("---\nform: Person\n---\n# {id}\n\n## Email\n{email}\n")
+}
+
+fn conflict(result: anyhow::Result<impl std::fmt::Debug>) -> UniqueConflict {
+    result
+        .expect_err("unique conflict")
+        .downcast::<UniqueConflict>()
+        .expect("UniqueConflict error")
+}
+
+#[tokio::test]
+/// REQ-FORM-014
+async fn test_unique_req_form_014_enforced_on_write() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "people", "/tmp").await?;
+    let ws_path = "spaces/people";
+    for (field, message) in [
+        (
+            json!({"type": "string", "unique": "yes"}),
+            "must be a boolean",
+        ),
+        (
+            json!({"type": "list", "unique": true}),
+            "cannot be of type list",
+        ),
+        (
+            json!({"type": "string", "unique": true, "secret": true}),
+            "cannot be unique",
+        ),
+    ] {
+        let err = form::upsert_form(
+            &op,
+            ws_path,
+            &json!({"name": "Broken", "fields": {"Email": field}}),
+        )
+        .await
+        .expect_err("invalid unique field");
+        assert!(err.to_string().contains(message), "{err}");
+    }
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Person",
+            "template": "# Person\n\n## Email\n",
+            "fields": {"Email": {"type": "string", "unique": true}},
+        }),
+    )
+    .await?;
+    let integrity = FakeIntegrityProvider;
+    entry::create_entry(
+        &op,
+        ws_path,
+        "ann",
+        &person("ann", "ann@example.com"),
+        "a",
+        &integrity,
+    )
+    .await?;
+
+    let clash = conflict(
+        entry::create_entry(
+            &op,
+            ws_path,
+            "bob",
+            &person("bob", "ann@example.com"),
+            "a",
+            &integrity,
+        )
+        .await,
+    );
+    assert_eq!(clash.entry_id, "ann");
+    assert_eq!(clash.as_json()["code"], "unique_conflict");
+    assert_eq!(clash.as_json()["field"], "Email");
+    assert!(entry::get_entry(&op, ws_path, "bob").await.is_err());
+
+    entry::create_entry(
+        &op,
+        ws_path,
+        "bob",
+        &person("bob", "bob@example.com"),
+        "a",
+        &integrity,
+    )
+    .await?;
+    let clash = conflict(
+        entry::update_entry(
+            &op,
+            ws_path,
+            "bob",
+            &person("bob", "ann@example.com"),
+            None,
+            "a",
+            None,
+            &integrity,
+        )
+        .await,
+    );
+    assert_eq!(clash.entry_id, "ann");
+
+    // Moving ann off her address releases it.
+    entry::update_entry(
+        &op,
+        ws_path,
+        "ann",
+        &person("ann", "ann@work.example.com"),
+        None,
+        "a",
+        None,
+        &integrity,
+    )
+    .await?;
+    entry::update_entry(
+        &op,
+        ws_path,
+        "bob",
+        &person("bob", "ann@example.com"),
+        None,
+        "a",
+        None,
+        &integrity,
+    )
+    .await?;
+
+    // Empty values are not unique.
+    for id in ["cat", "dan"] {
+        let content = format!("---\nform: Person\n---\n# {id}\n");
+        entry::create_entry(&op, ws_path, id, &content, "a", &integrity).await?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-014
+async fn test_unique_req_form_014_verify_and_backfill() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "legacy", "/tmp").await?;
+    let ws_path = "spaces/legacy";
+    let person_form = |unique: bool| {
+        json!({
+            "name": "Person",
+            "template": "# Person\n\n## Email\n",
+            "fields": {"Email": {"type": "string", "unique": unique}},
+        })
+    };
+    form::upsert_form(&op, ws_path, &person_form(false)).await?;
+    let integrity = FakeIntegrityProvider;
+    for (id, email) in [
+        ("first", "shared@example.com"),
+        ("second", "shared@example.com"),
+        ("third", "own@example.com"),
+    ] {
+        entry::create_entry(&op, ws_path, id, &person(id, email), "a", &integrity).await?;
+    }
+    form::upsert_form(&op, ws_path, &person_form(true)).await?;
+
+    let report = unique::verify_unique_index(&op, ws_path, "Person", false).await?;
+    assert_eq!(report.fields, vec!["Email"]);
+    assert_eq!(report.entries, 3);
+    assert_eq!((report.missing, report.stale), (2, 0));
+    assert_eq!(report.duplicates.len(), 1);
+    assert_eq!(report.duplicates[0].value, "shared@example.com");
+    assert_eq!(report.duplicates[0].entry_ids, vec!["first", "second"]);
+    assert!(!report.repaired);
+
+    let repaired = unique::verify_unique_index(&op, ws_path, "Person", true).await?;
+    assert!(repaired.repaired);
+    let clean = unique::verify_unique_index(&op, ws_path, "Person", false).await?;
+    assert_eq!((clean.missing, clean.stale), (0, 0));
+
+    let clash = conflict(
+        entry::create_entry(
+            &op,
+            ws_path,
+            "fourth",
+            &person("fourth", "own@example.com"),
+            "a",
+            &integrity,
+        )
+        .await,
+    );
+    assert_eq!(clash.entry_id, "third");
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-014
+async fn test_unique_req_form_014_concurrent_creates_claim_once() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "people-race", "/tmp").await?;
+    let ws_path = "spaces/people-race";
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Person",
+            "template": "# Person\n\n## Email\n",
+            "fields": {"Email": {"type": "string", "unique": true}},
+        }),
+    )
+    .await?;
+    let integrity = FakeIntegrityProvider;
+
+    // A claim whose row is still being written must not look stale to a racer.
+    let ann = person("ann", "shared@example.com");
+    let bob = person("bob", "shared@example.com");
+    let (first, second) = tokio::join!(
+        entry::create_entry(&op, ws_path, "ann", &ann, "a", &integrity),
+        entry::create_entry(&op, ws_path, "bob", &bob, "a", &integrity),
+    );
+    let created: Vec<_> = [&first, &second]
+        .into_iter()
+        .filter(|result| result.is_ok())
+        .collect();
+    assert_eq!(created.len(), 1);
+    let loser = if first.is_ok() { second } else { first };
+    conflict(loser);
+
+    let report = unique::verify_unique_index(&op, ws_path, "Person", false).await?;
+    assert!(report.duplicates.is_empty());
+    assert_eq!(report.missing, 0);
+    assert_eq!(report.stale, 0);
+    Ok(())
+}
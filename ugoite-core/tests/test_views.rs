@@ -0,0 +1,159 @@
+mod common;
+
+use _ugoite_core::views::{self, SortDirection, ViewLayout, ViewPayload, ViewSort};
+use _ugoite_core::{form, index, space};
+use common::setup_operator;
+use serde_json::json;
+
+fn board(name: &str) -> ViewPayload {
+    ViewPayload {
+        name: name.to_string(),
+        layout: ViewLayout::Board,
+        columns: vec!["title".to_string(), "Owner".to_string()],
+        filters: json!({"tag": "active"})
+            .as_object()
+            .cloned()
+            .unwrap_or_default(),
+        sort: vec![ViewSort {
+            column: "updated_at".to_string(),
+            direction: SortDirection::Desc,
+        }],
+        group_by: Some("Owner".to_string()),
+        board_by: Some("Status".to_string()),
+        is_default: false,
+    }
+}
+
+#[tokio::test]
+/// REQ-FORM-018
+async fn test_views_req_form_018_form_view_definitions() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "views", "/tmp").await?;
+    let ws_path = "spaces/views";
+    form::upsert_form(
+        &op,
+        ws_path,
+        &json!({
+            "name": "Task",
+            "template": "# Task\n\n## Owner\n\n## Status\n",
+            "fields": {"Owner": {"type": "string"}, "Status": {"type": "string"}},
+        }),
+    )
+    .await?;
+    assert!(views::get_default_view(&op, ws_path, "Task")
+        .await?
+        .is_none());
+
+    // The first view becomes the form's default.
+    let table = views::create_view(
+        &op,
+        ws_path,
+        "Task",
+        &ViewPayload {
+            name: " All tasks ".to_string(),
+            ..ViewPayload::default()
+        },
+    )
+    .await?;
+    assert_eq!(table.name, "All tasks");
+    assert!(table.is_default);
+
+    let kanban = views::create_view(&op, ws_path, "Task", &board("Board")).await?;
+    assert!(!kanban.is_default);
+    assert_eq!(
+        kanban.query(),
+        json!({"tag": "active", "form": "Task"}),
+        "views select their form's entries"
+    );
+    index::query_index(&op, ws_path, &kanban.query().to_string()).await?;
+    assert_eq!(
+        views::get_view(&op, ws_path, "Task", &kanban.id).await?,
+        kanban
+    );
+
+    for (payload, message) in [
+        (
+            ViewPayload {
+                board_by: None,
+                ..board("Board")
+            },
+            "need a board_by",
+        ),
+        (
+            ViewPayload {
+                board_by: Some("title".to_string()),
+                ..board("Board")
+            },
+            "not a field of the form",
+        ),
+        (
+            ViewPayload {
+                columns: vec!["Missing".to_string()],
+                ..board("Board")
+            },
+            "not a form column",
+        ),
+        (
+            ViewPayload {
+                layout: ViewLayout::Table,
+                ..board("Board")
+            },
+            "Only board views",
+        ),
+    ] {
+        let err = views::create_view(&op, ws_path, "Task", &payload)
+            .await
+            .expect_err("invalid view");
+        assert!(err.to_string().contains(message), "{err}");
+    }
+    assert!(views::create_view(&op, ws_path, "Nope", &board("Board"))
+        .await
+        .is_err());
+
+    // Marking a view as default moves the default to it.
+    let kanban = views::update_view(
+        &op,
+        ws_path,
+        "Task",
+        &kanban.id,
+        &ViewPayload {
+            is_default: true,
+            ..board("Kanban")
+        },
+    )
+    .await?;
+    assert_eq!(kanban.name, "Kanban");
+    assert_eq!(
+        views::get_default_view(&op, ws_path, "Task")
+            .await?
+            .map(|view| view.id),
+        Some(kanban.id.clone())
+    );
+    let names: Vec<_> = views::list_views(&op, ws_path, "Task")
+        .await?
+        .into_iter()
+        .map(|view| (view.name, view.is_default))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("All tasks".to_string(), false),
+            ("Kanban".to_string(), true)
+        ]
+    );
+
+    // Deleting the default hands it to the oldest remaining view.
+    views::delete_view(&op, ws_path, "Task", &kanban.id).await?;
+    assert_eq!(
+        views::get_default_view(&op, ws_path, "Task")
+            .await?
+            .map(|view| view.id),
+        Some(table.id.clone())
+    );
+    assert!(views::get_view(&op, ws_path, "Task", &kanban.id)
+        .await
+        .is_err());
+    views::delete_view(&op, ws_path, "Task", &table.id).await?;
+    assert!(views::list_views(&op, ws_path, "Task").await?.is_empty());
+    Ok(())
+}
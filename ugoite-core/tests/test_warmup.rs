@@ -0,0 +1,50 @@
+mod common;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::{entry, form, space, warmup};
+use common::setup_operator;
+
+#[tokio::test]
+/// REQ-OPS-043
+async fn test_warmup_req_ops_043_preloads_requested_spaces() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "warm-a", "/tmp").await?;
+    space::create_space(&op, "warm-b", "/tmp").await?;
+    form::upsert_form(
+        &op,
+        "spaces/warm-a",
+        &serde_json::json!({"name": "Task", "fields": {"Body": {"type": "markdown"}}}),
+    )
+    .await?;
+    entry::create_entry(
+        &op,
+        "spaces/warm-a",
+        "entry-1",
+        "---\nform: Task\n---\n# Warm\n\n## Body\ntext\n",
+        "alice",
+        &FakeIntegrityProvider,
+    )
+    .await?;
+
+    let report = warmup::warmup(&op, &["warm-a".to_string(), "missing".to_string()]).await?;
+    assert!(!report.is_ok());
+    let warm = &report.spaces[0];
+    assert_eq!(warm.space_id, "warm-a");
+    assert!(warm.forms.contains(&"Task".to_string()));
+    assert_eq!(warm.tables_loaded, warm.forms.len() * 2);
+    assert!(warm.integrity_verified);
+    assert!(warm.error.is_none());
+    let missing = &report.spaces[1];
+    assert!(missing
+        .error
+        .as_deref()
+        .unwrap()
+        .contains("Space not found"));
+    assert!(!missing.integrity_verified);
+
+    // No ids warms every space.
+    let all = warmup::warmup(&op, &[]).await?;
+    assert!(all.is_ok());
+    let ids: Vec<&str> = all.spaces.iter().map(|s| s.space_id.as_str()).collect();
+    assert!(ids.contains(&"warm-a") && ids.contains(&"warm-b"));
+    Ok(())
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use _ugoite_core::entry;
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::space;
+use _ugoite_core::watchers::{self, WatchEventKind, WatchTarget};
+use common::setup_operator;
+
+#[tokio::test]
+/// REQ-ENTRY-024
+async fn test_watchers_req_entry_024_writes_notify_matching_watchers() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "watch-space", "/tmp").await?;
+    let ws_path = "spaces/watch-space";
+    let integrity = FakeIntegrityProvider;
+
+    assert!(watchers::watch(&op, ws_path, "alice", WatchTarget::Form("Entry".into())).await?);
+    assert!(!watchers::watch(&op, ws_path, "alice", WatchTarget::Form("Entry".into())).await?);
+    assert!(watchers::watch(&op, ws_path, "alice", WatchTarget::Tag("ops".into())).await?);
+    assert!(watchers::watch(&op, ws_path, "bob", WatchTarget::Entry("plan".into())).await?);
+    assert!(watchers::watch(&op, ws_path, "carol", WatchTarget::Tag("ops".into())).await?);
+    assert_eq!(
+        watchers::list_watches(&op, ws_path, Some("alice"))
+            .await?
+            .len(),
+        2
+    );
+
+    entry::create_entry(
+        &op,
+        ws_path,
+        "plan",
+        "---\nform: Entry\ntags: [ops]\n---\n# Plan\n\n## Body\nDraft\n",
+        "carol",
+        &integrity,
+    )
+    .await?;
+    let alice = watchers::list_notifications(&op, ws_path, "alice", None).await?;
+    assert_eq!(alice.len(), 1);
+    assert_eq!(alice[0].event, WatchEventKind::Created);
+    assert_eq!(alice[0].entry_title, "Plan");
+    assert_eq!(alice[0].author.as_deref(), Some("carol"));
+    assert_eq!(
+        alice[0].matched,
+        vec![
+            WatchTarget::Form("Entry".into()),
+            WatchTarget::Tag("ops".into())
+        ]
+    );
+    assert_eq!(
+        watchers::list_notifications(&op, ws_path, "bob", None)
+            .await?
+            .len(),
+        1
+    );
+    // Authors are not notified of their own writes.
+    assert!(watchers::list_notifications(&op, ws_path, "carol", None)
+        .await?
+        .is_empty());
+
+    entry::update_entry(
+        &op,
+        ws_path,
+        "plan",
+        "---\nform: Entry\ntags: []\n---\n# Plan\n\n## Body\nFinal\n",
+        None,
+        "alice",
+        None,
+        &integrity,
+    )
+    .await?;
+    let bob = watchers::list_notifications(&op, ws_path, "bob", None).await?;
+    assert_eq!(bob.len(), 2);
+    assert_eq!(bob[1].event, WatchEventKind::Updated);
+    assert!(watchers::list_notifications(&op, ws_path, "carol", None)
+        .await?
+        .is_empty());
+
+    entry::delete_entry(&op, ws_path, "plan", false).await?;
+    let bob = watchers::list_notifications(&op, ws_path, "bob", None).await?;
+    assert_eq!(bob[2].event, WatchEventKind::Deleted);
+
+    assert_eq!(
+        watchers::ack_notifications(&op, ws_path, "bob", Some(&[bob[0].id.clone()])).await?,
+        1
+    );
+    assert_eq!(
+        watchers::list_notifications(&op, ws_path, "bob", Some(1))
+            .await?
+            .len(),
+        1
+    );
+    assert_eq!(
+        watchers::ack_notifications(&op, ws_path, "bob", None).await?,
+        2
+    );
+    assert!(watchers::list_notifications(&op, ws_path, "bob", None)
+        .await?
+        .is_empty());
+
+    assert!(watchers::unwatch(&op, ws_path, "bob", &WatchTarget::Entry("plan".into())).await?);
+    assert!(watchers::list_watches(&op, ws_path, Some("bob"))
+        .await?
+        .is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-ENTRY-024
+async fn test_watchers_req_entry_024_notify_failure_keeps_committed_write() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "watch-broken", "/tmp").await?;
+    let ws_path = "spaces/watch-broken";
+    let integrity = FakeIntegrityProvider;
+    op.write(&format!("{ws_path}/watchers/watches.json"), "not json")
+        .await?;
+
+    // The row is durable before watchers are notified, so a broken watch list
+    // must not turn the write into an error a client would retry.
+    let markdown = "---\nform: Entry\n---\n# Plan\n\n## Body\nDraft\n";
+    entry::create_entry(&op, ws_path, "plan", markdown, "carol", &integrity).await?;
+    let updated = markdown.replace("Draft", "Final");
+    entry::update_entry(
+        &op, ws_path, "plan", &updated, None, "carol", None, &integrity,
+    )
+    .await?;
+    entry::delete_entry(&op, ws_path, "plan", false).await?;
+    Ok(())
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::webhooks::{
+    self, sign_webhook_payload, verify_webhook_signature, verify_webhook_signature_with_window,
+    WebhookProvider,
+};
+use _ugoite_core::{clock, entry, space};
+use common::setup_operator;
+use serde_json::json;
+use std::collections::HashMap;
+
+#[tokio::test]
+/// REQ-INT-007
+async fn test_webhooks_req_int_007_verifies_provider_signatures() -> anyhow::Result<()> {
+    // Example delivery from GitHub's webhook validation docs.
+    let secret = b"It's a Secret to Everybody";
+    let headers = HashMap::from([
+        (
+            "X-Hub-Signature-256".to_string(),
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17".to_string(),
+        ),
+        ("X-GitHub-Event".to_string(), "ping".to_string()),
+        ("X-GitHub-Delivery".to_string(), "delivery-1".to_string()),
+    ]);
+    let verified =
+        verify_webhook_signature(WebhookProvider::Github, secret, b"Hello, World!", &headers)?;
+    assert_eq!(verified.event.as_deref(), Some("ping"));
+    assert_eq!(verified.delivery_id.as_deref(), Some("delivery-1"));
+    let err = verify_webhook_signature(WebhookProvider::Github, secret, b"Hello, World?", &headers)
+        .expect_err("tampered body");
+    assert!(err.to_string().contains("does not match"));
+    let err = verify_webhook_signature(
+        WebhookProvider::Github,
+        b"other",
+        b"Hello, World!",
+        &headers,
+    )
+    .expect_err("wrong secret");
+    assert!(err.to_string().contains("does not match"));
+
+    clock::frozen(7, async {
+        let now = clock::FROZEN_EPOCH_MS / 1000;
+        let body = br#"{"type":"event_callback","event":{"text":"hi"}}"#;
+        let headers = sign_webhook_payload(WebhookProvider::Slack, b"slack-secret", body, now);
+        let verified =
+            verify_webhook_signature(WebhookProvider::Slack, b"slack-secret", body, &headers)?;
+        assert_eq!(verified.timestamp, Some(now));
+
+        // The timestamp is signed, so it cannot be moved back into the window.
+        let mut moved = headers.clone();
+        moved.insert(
+            "x-slack-request-timestamp".to_string(),
+            (now - 1).to_string(),
+        );
+        assert!(
+            verify_webhook_signature(WebhookProvider::Slack, b"slack-secret", body, &moved)
+                .is_err()
+        );
+        let stale = sign_webhook_payload(WebhookProvider::Slack, b"slack-secret", body, now - 301);
+        let err = verify_webhook_signature(WebhookProvider::Slack, b"slack-secret", body, &stale)
+            .expect_err("stale delivery");
+        assert!(err.to_string().contains("outside the allowed window"));
+        verify_webhook_signature_with_window(
+            WebhookProvider::Slack,
+            b"slack-secret",
+            body,
+            &stale,
+            600,
+        )?;
+        // Timestamps too far away to subtract are rejected rather than wrapping.
+        let overflowing =
+            sign_webhook_payload(WebhookProvider::Slack, b"slack-secret", body, i64::MIN);
+        let err =
+            verify_webhook_signature(WebhookProvider::Slack, b"slack-secret", body, &overflowing)
+                .expect_err("overflowing timestamp");
+        assert!(err.to_string().contains("outside the allowed window"));
+
+        let generic = sign_webhook_payload(WebhookProvider::Generic, b"generic", body, now);
+        assert!(generic["x-webhook-signature"].starts_with("sha256="));
+        verify_webhook_signature(WebhookProvider::Generic, b"generic", body, &generic)?;
+        let err = verify_webhook_signature(WebhookProvider::Github, b"generic", body, &generic)
+            .expect_err("header of another provider");
+        assert!(err.to_string().contains("Missing webhook signature"));
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::test]
+/// REQ-INT-007
+async fn test_webhooks_req_int_007_ingests_payload_through_template() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "hook-space", "/tmp").await?;
+    let ws_path = "spaces/hook-space";
+    space::patch_space(
+        &op,
+        "hook-space",
+        &json!({"settings": {"webhooks": {"github-issues": {
+            "id": "gh-issue-{{ issue.number }}",
+            "title": "#{{issue.number}} {{ issue.title }}",
+            "fields": {"Body": "{{ issue.body }}\n\nOpened by {{ issue.user.login }}"},
+            "tags": ["github", "{{ issue.labels.0.name }}", "{{ missing.label }}"],
+            "author": "github",
+        }}}}),
+    )
+    .await?;
+
+    let mut payload = json!({
+        "action": "opened",
+        "issue": {
+            "number": 42,
+            "title": "Crash on   start",
+            "body": "Steps to reproduce",
+            "user": {"login": "octocat"},
+            "labels": [{"name": "bug"}],
+        },
+    });
+    let result = webhooks::ingest_webhook_event(
+        &op,
+        ws_path,
+        "github-issues",
+        &payload,
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert_eq!(result.entry_id, "gh-issue-42");
+    assert_eq!(result.title, "#42 Crash on start");
+    assert!(result.created);
+
+    let stored = entry::get_entry(&op, ws_path, "gh-issue-42").await?;
+    assert_eq!(stored["title"], "#42 Crash on start");
+    assert_eq!(stored["tags"], json!(["github", "bug"]));
+    let content = entry::get_entry_content(&op, ws_path, "gh-issue-42").await?;
+    assert!(content
+        .markdown
+        .contains("## Body\nSteps to reproduce\n\nOpened by octocat"));
+
+    // A redelivery or follow-up event updates the same entry.
+    payload["issue"]["title"] = json!("Crash on start (fixed)");
+    let result = webhooks::ingest_webhook_event(
+        &op,
+        ws_path,
+        "github-issues",
+        &payload,
+        &FakeIntegrityProvider,
+    )
+    .await?;
+    assert!(!result.created);
+    let stored = entry::get_entry(&op, ws_path, "gh-issue-42").await?;
+    assert_eq!(stored["title"], "#42 Crash on start (fixed)");
+    assert_eq!(entry::list_entries(&op, ws_path).await?.len(), 1);
+
+    let err =
+        webhooks::ingest_webhook_event(&op, ws_path, "slack", &payload, &FakeIntegrityProvider)
+            .await
+            .expect_err("unknown source");
+    assert!(err.to_string().contains("Webhook source not configured"));
+    Ok(())
+}
@@ -0,0 +1,230 @@
+mod common;
+
+use _ugoite_core::audit::{self, AuditListOptions};
+use _ugoite_core::integrity::FakeIntegrityProvider;
+use _ugoite_core::watchers::{self, WatchEventKind, WatchTarget};
+use _ugoite_core::workflow;
+use _ugoite_core::{entry, form, space};
+use common::setup_operator;
+use serde_json::json;
+
+fn ticket_form(workflow: serde_json::Value) -> serde_json::Value {
+    json!({
+        "name": "Ticket",
+        "template": "# Ticket\n\n## Status\n\n## Summary\n",
+        "fields": {
+            "Status": {"type": "string"},
+            "Summary": {"type": "string"},
+            "Points": {"type": "number"},
+        },
+        "workflow": workflow,
+    })
+}
+
+fn ticket(id: &str, status: &str) -> String {
+    format!("---\nform: Ticket\n---\n# {id}\n\n## Status\n{status}\n\n## Summary\n{id} is broken\n")
+}
+
+#[tokio::test]
+/// REQ-FORM-016
+async fn test_workflow_req_form_016_definition_validated() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "tracker", "/tmp").await?;
+    let ws_path = "spaces/tracker";
+    for (workflow, message) in [
+        (
+            json!({"field": "Owner", "states": ["open"]}),
+            "not a form field",
+        ),
+        (
+            json!({"field": "Points", "states": ["open"]}),
+            "plain string field",
+        ),
+        (
+            json!({"field": "Status", "states": []}),
+            "at least one state",
+        ),
+        (
+            json!({"field": "Status", "states": ["open", "open"]}),
+            "listed twice",
+        ),
+        (
+            json!({"field": "Status", "states": ["open"], "initial": "done"}),
+            "initial state",
+        ),
+        (
+            json!({"field": "Status", "states": ["open"], "transitions": [{"from": "open", "to": "done"}]}),
+            "unknown state",
+        ),
+    ] {
+        let err = form::upsert_form(&op, ws_path, &ticket_form(workflow))
+            .await
+            .expect_err("invalid workflow");
+        assert!(err.to_string().contains(message), "{err}");
+    }
+    form::upsert_form(
+        &op,
+        ws_path,
+        &ticket_form(json!({"field": "Status", "states": ["open", "closed"]})),
+    )
+    .await?;
+    let stored = form::get_form(&op, ws_path, "Ticket").await?;
+    assert_eq!(stored["workflow"]["initial"], "open");
+    Ok(())
+}
+
+#[tokio::test]
+/// REQ-FORM-016
+async fn test_workflow_req_form_016_guarded_transitions() -> anyhow::Result<()> {
+    let op = setup_operator()?;
+    space::create_space(&op, "issues", "/tmp").await?;
+    let ws_path = "spaces/issues";
+    form::upsert_form(
+        &op,
+        ws_path,
+        &ticket_form(json!({
+            "field": "Status",
+            "states": ["open", "in_progress", "closed"],
+            "transitions": [
+                {"from": "open", "to": "in_progress"},
+                {"from": "in_progress", "to": "closed", "scopes": ["triage"]},
+                {"from": "closed", "to": "open", "scopes": ["triage"]},
+            ],
+        })),
+    )
+    .await?;
+    let integrity = FakeIntegrityProvider;
+    watchers::watch(&op, ws_path, "watcher", WatchTarget::Form("Ticket".into())).await?;
+
+    entry::create_entry(&op, ws_path, "t1", &ticket("t1", ""), "dev", &integrity).await?;
+    let created = entry::get_entry(&op, ws_path, "t1").await?;
+    assert_eq!(created["sections"]["Status"], "open");
+    let skipped = entry::create_entry(
+        &op,
+        ws_path,
+        "t2",
+        &ticket("t2", "closed"),
+        "dev",
+        &integrity,
+    )
+    .await
+    .expect_err("entries start in the initial state");
+    assert!(skipped.to_string().contains("start in workflow state"));
+
+    // Plain edits keep the state and cannot change it.
+    let edited = "---\nform: Ticket\n---\n# t1\n\n## Summary\nstill broken\n";
+    entry::update_entry(&op, ws_path, "t1", edited, None, "dev", None, &integrity).await?;
+    let moved = entry::update_entry(
+        &op,
+        ws_path,
+        "t1",
+        &ticket("t1", "closed"),
+        None,
+        "dev",
+        None,
+        &integrity,
+    )
+    .await
+    .expect_err("state edits go through transitions");
+    assert!(moved.to_string().contains("only through state transitions"));
+
+    let no_route = workflow::transition_entry_state(
+        &op,
+        ws_path,
+        "t1",
+        "closed",
+        None,
+        "dev",
+        &[],
+        &integrity,
+    )
+    .await
+    .expect_err("no transition from open to closed");
+    assert!(no_route.to_string().contains("no transition"));
+    let started = workflow::transition_entry_state(
+        &op,
+        ws_path,
+        "t1",
+        "in_progress",
+        None,
+        "dev",
+        &[],
+        &integrity,
+    )
+    .await?;
+    assert_eq!(started.from.as_deref(), Some("open"));
+    let unscoped = workflow::transition_entry_state(
+        &op,
+        ws_path,
+        "t1",
+        "closed",
+        None,
+        "dev",
+        &[],
+        &integrity,
+    )
+    .await
+    .expect_err("closing needs the triage scope");
+    assert!(unscoped.to_string().contains("'triage' scope"));
+    let closed = workflow::transition_entry_state(
+        &op,
+        ws_path,
+        "t1",
+        "closed",
+        Some(&started.revision_id),
+        "lead",
+        &["triage".to_string()],
+        &integrity,
+    )
+    .await?;
+    let stored = entry::get_entry(&op, ws_path, "t1").await?;
+    assert_eq!(stored["sections"]["Status"], "closed");
+    assert_eq!(stored["sections"]["Summary"], "still broken");
+    assert_eq!(stored["revision_id"], closed.revision_id);
+
+    let history = workflow::get_entry_state_history(&op, ws_path, "t1").await?;
+    let moves: Vec<(Option<&str>, &str, &str)> = history
+        .iter()
+        .map(|change| {
+            (
+                change.from.as_deref(),
+                change.to.as_str(),
+                change.author.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        moves,
+        vec![
+            (None, "open", "dev"),
+            (Some("open"), "in_progress", "dev"),
+            (Some("in_progress"), "closed", "lead"),
+        ]
+    );
+
+    let events = audit::list_audit_events(
+        &op,
+        "issues",
+        AuditListOptions {
+            action: Some("entry.transition".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(events["items"].as_array().map(Vec::len), Some(2));
+    let kinds: Vec<WatchEventKind> = watchers::list_notifications(&op, ws_path, "watcher", None)
+        .await?
+        .iter()
+        .map(|notification| notification.event)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            WatchEventKind::Created,
+            WatchEventKind::Updated,
+            WatchEventKind::Transitioned,
+            WatchEventKind::Transitioned,
+        ]
+    );
+    Ok(())
+}
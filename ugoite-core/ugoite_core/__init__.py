@@ -8,6 +8,7 @@ from .audit import (
     AuditEventInput,
     AuditListFilter,
     append_audit_event,
+    list_audit_actions,
     list_audit_events,
 )
 from .auth import (
@@ -19,14 +20,18 @@ from .auth import (
     authenticate_headers_for_space,
     clear_auth_manager_cache,
     export_authentication_overview,
+    introspect_token,
+    list_expiring_keys,
 )
 from .authz import (
     AccessContext,
     ActionName,
     AuthorizationError,
     RoleName,
+    authz_matrix_snapshot,
     filter_readable_entries,
     form_name_from_entry,
+    read_entry_ref,
     require_entry_read,
     require_entry_revision_write,
     require_entry_write,
@@ -41,6 +46,7 @@ from .entry_input_modes import (
     compose_entry_markdown_from_chat,
     compose_entry_markdown_from_fields,
 )
+from .json_payload import JsonPayload, loads_json
 from .membership import (
     AcceptInvitationInput,
     InvitationDeliveryProvider,
@@ -67,6 +73,7 @@ from .service_accounts import (
     RotateServiceAccountKeyInput,
     create_service_account,
     create_service_account_key,
+    list_expiring_service_account_keys,
     list_service_accounts,
     revoke_service_account_key,
     rotate_service_account_key,
@@ -80,6 +87,7 @@ from .sql_rules import (
 )
 from .sql_sessions import (
     SqlSessionPageInput,
+    get_sql_session_columns_for_identity,
     get_sql_session_count_for_identity,
     get_sql_session_rows_all_for_identity,
     get_sql_session_rows_for_identity,
@@ -90,53 +98,204 @@ with suppress(ImportError):
     __doc__ = _core.__doc__
 
 _core_any = cast("Any", _core)
+ack_watch_notifications = _core_any.ack_watch_notifications
+apply_collab_update = _core_any.apply_collab_update
+apply_replace = _core_any.apply_replace
+assign_org_space = _core_any.assign_org_space
+attach_revision_signature = _core_any.attach_revision_signature
+authenticate_headers_stored = _core_any.authenticate_headers_stored
+authenticate_oidc_id_token = _core_any.authenticate_oidc_id_token
+begin_response_signature = _core_any.begin_response_signature
+build_request_signature = _core_any.build_request_signature
 build_response_signature = _core_any.build_response_signature
+bulk_update_properties = _core_any.bulk_update_properties
+cancel_sample_space_job = _core_any.cancel_sample_space_job
+clear_entry_acl = _core_any.clear_entry_acl
+compact_collab_updates = _core_any.compact_collab_updates
+configure_runtime = _core_any.configure_runtime
+create_bulk_update_job = _core_any.create_bulk_update_job
+create_collection = _core_any.create_collection
+create_dashboard = _core_any.create_dashboard
+create_entries = _core_any.create_entries
 create_entry = _core_any.create_entry
+create_form_view = _core_any.create_form_view
+create_org = _core_any.create_org
 create_sample_space = _core_any.create_sample_space
 create_sample_space_job = _core_any.create_sample_space_job
+create_scim_group = _core_any.create_scim_group
+create_scim_user = _core_any.create_scim_user
 create_space = _core_any.create_space
 create_sql = _core_any.create_sql
 create_sql_session = _core_any.create_sql_session
+create_vacuum_job = _core_any.create_vacuum_job
+deactivate_scim_user = _core_any.deactivate_scim_user
+delete_form_view = _core_any.delete_form_view
+delete_org = _core_any.delete_org
+dereference_entry_ref = _core_any.dereference_entry_ref
 delete_asset = _core_any.delete_asset
+delete_collection = _core_any.delete_collection
+delete_dashboard = _core_any.delete_dashboard
+delete_entries = _core_any.delete_entries
 delete_entry = _core_any.delete_entry
+delete_scim_group = _core_any.delete_scim_group
 delete_sql = _core_any.delete_sql
+diff_entry_revisions = _core_any.diff_entry_revisions
+dispatch_due_reminders = _core_any.dispatch_due_reminders
+encode_collab_diff = _core_any.encode_collab_diff
+export_audit_events_otlp = _core_any.export_audit_events_otlp
+export_key_recovery_codes = _core_any.export_key_recovery_codes
+extract_entry_refs = _core_any.extract_entry_refs
 extract_properties = _core_any.extract_properties
+format_chat_notification = _core_any.format_chat_notification
+get_bulk_update_job = _core_any.get_bulk_update_job
+get_dashboard = _core_any.get_dashboard
+get_default_form_view = _core_any.get_default_form_view
 get_entry = _core_any.get_entry
+get_entry_acl = _core_any.get_entry_acl
+get_entry_head = _core_any.get_entry_head
 get_entry_history = _core_any.get_entry_history
+get_entry_metadata = _core_any.get_entry_metadata
+get_entry_outline = _core_any.get_entry_outline
 get_entry_revision = _core_any.get_entry_revision
 get_entry_revision_content = _core_any.get_entry_revision_content
+get_collab_state_vector = _core_any.get_collab_state_vector
+get_entry_state_history = _core_any.get_entry_state_history
 get_form = _core_any.get_form
+get_form_view = _core_any.get_form_view
+get_oidc_config = _core_any.get_oidc_config
+get_org = _core_any.get_org
+get_org_stats = _core_any.get_org_stats
+get_related_entries = _core_any.get_related_entries
+get_revision_signing_payload = _core_any.get_revision_signing_payload
 get_sample_space_job = _core_any.get_sample_space_job
+get_scim_group = _core_any.get_scim_group
+get_scim_user = _core_any.get_scim_user
 get_space = _core_any.get_space
+get_space_analytics = _core_any.get_space_analytics
+get_space_org = _core_any.get_space_org
 get_sql = _core_any.get_sql
+get_sql_share = _core_any.get_sql_share
+get_sql_session_columns = _core_any.get_sql_session_columns
 get_sql_session_count = _core_any.get_sql_session_count
 get_sql_session_rows = _core_any.get_sql_session_rows
 get_sql_session_rows_all = _core_any.get_sql_session_rows_all
+get_sql_session_rows_json = _core_any.get_sql_session_rows_json
 get_sql_session_status = _core_any.get_sql_session_status
 get_user_preferences = _core_any.get_user_preferences
+get_vacuum_job = _core_any.get_vacuum_job
+ingest_entry_properties = _core_any.ingest_entry_properties
+ingest_webhook_event = _core_any.ingest_webhook_event
+is_feature_enabled = _core_any.is_feature_enabled
+lint_entry = _core_any.lint_entry
+lint_space = _core_any.lint_space
 list_assets = _core_any.list_assets
+list_author_signing_keys = _core_any.list_author_signing_keys
+list_changes_since = _core_any.list_changes_since
+list_collection_entries = _core_any.list_collection_entries
+list_collections = _core_any.list_collections
 list_column_types = _core_any.list_column_types
+list_dashboards = _core_any.list_dashboards
+list_deleted_entries = _core_any.list_deleted_entries
+list_due_reminders = _core_any.list_due_reminders
 list_entries = _core_any.list_entries
+list_entries_json = _core_any.list_entries_json
+list_entries_ordered = _core_any.list_entries_ordered
 list_entry_summaries = _core_any.list_entry_summaries
+list_expiring_keys_stored = _core_any.list_expiring_keys_stored
+list_form_views = _core_any.list_form_views
 list_forms = _core_any.list_forms
+list_global_feature_flags = _core_any.list_global_feature_flags
+list_mentions = _core_any.list_mentions
+list_object_versions = _core_any.list_object_versions
+list_orgs = _core_any.list_orgs
 list_sample_scenarios = _core_any.list_sample_scenarios
+list_scim_groups = _core_any.list_scim_groups
+list_scim_users = _core_any.list_scim_users
+list_space_feature_flags = _core_any.list_space_feature_flags
 list_spaces = _core_any.list_spaces
+list_spaces_detailed = _core_any.list_spaces_detailed
 list_sql = _core_any.list_sql
+list_tasks = _core_any.list_tasks
+list_watch_notifications = _core_any.list_watch_notifications
+list_watches = _core_any.list_watches
+load_auth_config = _core_any.load_auth_config
 load_hmac_material = _core_any.load_hmac_material
 load_response_hmac_material = _core_any.load_response_hmac_material
+mark_mentions_read = _core_any.mark_mentions_read
 migrate_form = _core_any.migrate_form
+move_collection = _core_any.move_collection
+move_entry = _core_any.move_entry
+move_entry_to_collection = _core_any.move_entry_to_collection
+patch_entry_section = _core_any.patch_entry_section
 patch_space = _core_any.patch_space
 patch_user_preferences = _core_any.patch_user_preferences
+pin_entry = _core_any.pin_entry
+preview_replace = _core_any.preview_replace
+purge_deleted_older_than = _core_any.purge_deleted_older_than
+purge_entry = _core_any.purge_entry
 query_index = _core_any.query_index
+query_index_json = _core_any.query_index_json
+query_index_page = _core_any.query_index_page
+read_entry_section = _core_any.read_entry_section
+reconcile_entry_properties = _core_any.reconcile_entry_properties
+register_author_signing_key = _core_any.register_author_signing_key
 reindex_all = _core_any.reindex_all
+release_org_space = _core_any.release_org_space
+render_dashboard = _core_any.render_dashboard
+reorder_entries = _core_any.reorder_entries
+render_entry_html = _core_any.render_entry_html
+render_markdown = _core_any.render_markdown
+replace_scim_group = _core_any.replace_scim_group
+replace_scim_user = _core_any.replace_scim_user
+resolve_entry_ref = _core_any.resolve_entry_ref
+resolve_org_role = _core_any.resolve_org_role
+resolve_provisioned_access = _core_any.resolve_provisioned_access
+restore_entries = _core_any.restore_entries
 restore_entry = _core_any.restore_entry
+restore_key_recovery_codes = _core_any.restore_key_recovery_codes
+restore_object_version = _core_any.restore_object_version
+restore_space_to_timestamp = _core_any.restore_space_to_timestamp
+resume_runtime = _core_any.resume_runtime
+revoke_author_signing_key = _core_any.revoke_author_signing_key
+rewrite_asset_links = _core_any.rewrite_asset_links
+run_sql = _core_any.run_sql
+runtime_metrics = _core_any.runtime_metrics
 save_asset = _core_any.save_asset
+save_asset_from_url = _core_any.save_asset_from_url
+save_oidc_config = _core_any.save_oidc_config
 search_entries = _core_any.search_entries
+search_entries_json = _core_any.search_entries_json
+set_entry_acl = _core_any.set_entry_acl
+set_entry_metadata = _core_any.set_entry_metadata
+set_global_feature_flag = _core_any.set_global_feature_flag
+set_org_member = _core_any.set_org_member
+set_sql_share = _core_any.set_sql_share
+set_space_feature_flag = _core_any.set_space_feature_flag
+shutdown_runtime = _core_any.shutdown_runtime
+snooze_reminder = _core_any.snooze_reminder
+sync_pull = _core_any.sync_pull
+sync_push = _core_any.sync_push
+toggle_task = _core_any.toggle_task
+transition_entry_state = _core_any.transition_entry_state
+undo_replace = _core_any.undo_replace
+unsnooze_reminder = _core_any.unsnooze_reminder
+unwatch = _core_any.unwatch
+update_collection = _core_any.update_collection
+update_dashboard = _core_any.update_dashboard
 update_entry = _core_any.update_entry
 update_entry_index = _core_any.update_entry_index
+update_form_view = _core_any.update_form_view
+update_org = _core_any.update_org
 update_sql = _core_any.update_sql
 upsert_form = _core_any.upsert_form
+vacuum_space = _core_any.vacuum_space
 validate_properties = _core_any.validate_properties
+verify_request_signature = _core_any.verify_request_signature
+verify_revision_author = _core_any.verify_revision_author
+verify_unique_index = _core_any.verify_unique_index
+verify_webhook_signature = _core_any.verify_webhook_signature
+warmup = _core_any.warmup
+watch = _core_any.watch
 
 
 async def test_storage_connection(storage_config: dict[str, Any]) -> dict[str, object]:
@@ -161,6 +320,7 @@ __all__ = [
     "CreateServiceAccountKeyInput",
     "InvitationDeliveryProvider",
     "InviteMemberInput",
+    "JsonPayload",
     "MemberRole",
     "MemberState",
     "RequestIdentity",
@@ -173,71 +333,189 @@ __all__ = [
     "TokenOnlyInvitationProvider",
     "UpdateMemberRoleInput",
     "accept_invitation",
+    "ack_watch_notifications",
     "admin_space_id",
     "append_audit_event",
+    "apply_collab_update",
+    "apply_replace",
+    "assign_org_space",
+    "attach_revision_signature",
     "auth_headers_from_environment",
     "authenticate_headers",
     "authenticate_headers_for_space",
+    "authenticate_headers_stored",
+    "authenticate_oidc_id_token",
+    "authz_matrix_snapshot",
+    "begin_response_signature",
     "bootstrap_space_owner",
+    "build_request_signature",
     "build_response_signature",
     "build_sql_schema",
+    "bulk_update_properties",
+    "cancel_sample_space_job",
     "clear_auth_manager_cache",
+    "clear_entry_acl",
+    "compact_collab_updates",
     "compose_entry_markdown_from_chat",
     "compose_entry_markdown_from_fields",
+    "configure_runtime",
+    "create_bulk_update_job",
+    "create_collection",
+    "create_dashboard",
+    "create_entries",
     "create_entry",
+    "create_form_view",
     "create_invitation",
+    "create_org",
     "create_sample_space",
     "create_sample_space_job",
+    "create_scim_group",
+    "create_scim_user",
     "create_service_account",
     "create_service_account_key",
     "create_space",
     "create_sql",
     "create_sql_session",
+    "create_vacuum_job",
+    "deactivate_scim_user",
+    "delete_form_view",
+    "delete_org",
+    "dereference_entry_ref",
     "delete_asset",
+    "delete_collection",
+    "delete_dashboard",
+    "delete_entries",
     "delete_entry",
+    "delete_scim_group",
     "delete_sql",
+    "diff_entry_revisions",
+    "dispatch_due_reminders",
+    "encode_collab_diff",
     "ensure_admin_space",
+    "export_audit_events_otlp",
     "export_authentication_overview",
+    "export_key_recovery_codes",
+    "extract_entry_refs",
     "extract_properties",
     "filter_readable_entries",
     "form_name_from_entry",
+    "format_chat_notification",
+    "get_bulk_update_job",
+    "get_collab_state_vector",
+    "get_dashboard",
+    "get_default_form_view",
     "get_entry",
+    "get_entry_acl",
+    "get_entry_head",
     "get_entry_history",
+    "get_entry_metadata",
+    "get_entry_outline",
     "get_entry_revision",
     "get_entry_revision_content",
+    "get_entry_state_history",
     "get_form",
+    "get_form_view",
+    "get_oidc_config",
+    "get_org",
+    "get_org_stats",
+    "get_related_entries",
+    "get_revision_signing_payload",
     "get_sample_space_job",
+    "get_scim_group",
+    "get_scim_user",
     "get_space",
+    "get_space_analytics",
+    "get_space_org",
     "get_sql",
+    "get_sql_share",
+    "get_sql_session_columns",
+    "get_sql_session_columns_for_identity",
     "get_sql_session_count",
     "get_sql_session_count_for_identity",
     "get_sql_session_rows",
     "get_sql_session_rows_all",
     "get_sql_session_rows_all_for_identity",
     "get_sql_session_rows_for_identity",
+    "get_sql_session_rows_json",
     "get_sql_session_status",
     "get_user_preferences",
+    "get_vacuum_job",
+    "ingest_entry_properties",
+    "ingest_webhook_event",
+    "introspect_token",
     "is_active_member",
+    "is_feature_enabled",
+    "lint_entry",
+    "lint_space",
     "lint_sql",
     "list_assets",
+    "list_audit_actions",
     "list_audit_events",
+    "list_author_signing_keys",
+    "list_changes_since",
+    "list_collection_entries",
+    "list_collections",
     "list_column_types",
+    "list_dashboards",
+    "list_deleted_entries",
+    "list_due_reminders",
     "list_entries",
+    "list_entries_json",
+    "list_entries_ordered",
     "list_entry_summaries",
+    "list_expiring_keys",
+    "list_expiring_keys_stored",
+    "list_expiring_service_account_keys",
+    "list_form_views",
     "list_forms",
+    "list_global_feature_flags",
+    "list_mentions",
     "list_members",
+    "list_object_versions",
+    "list_orgs",
     "list_sample_scenarios",
+    "list_scim_groups",
+    "list_scim_users",
     "list_service_accounts",
+    "list_space_feature_flags",
     "list_spaces",
+    "list_spaces_detailed",
     "list_sql",
+    "list_tasks",
+    "list_watch_notifications",
+    "list_watches",
+    "load_auth_config",
     "load_hmac_material",
     "load_response_hmac_material",
     "load_sql_rules",
+    "loads_json",
+    "mark_mentions_read",
     "migrate_form",
+    "move_collection",
+    "move_entry",
+    "move_entry_to_collection",
+    "patch_entry_section",
     "patch_space",
     "patch_user_preferences",
+    "pin_entry",
+    "preview_replace",
+    "purge_deleted_older_than",
+    "purge_entry",
     "query_index",
+    "query_index_json",
+    "query_index_page",
+    "read_entry_ref",
+    "read_entry_section",
+    "reconcile_entry_properties",
+    "register_author_signing_key",
     "reindex_all",
+    "release_org_space",
+    "render_dashboard",
+    "reorder_entries",
+    "render_entry_html",
+    "render_markdown",
+    "replace_scim_group",
+    "replace_scim_user",
     "require_entry_read",
     "require_entry_revision_write",
     "require_entry_write",
@@ -247,18 +525,59 @@ __all__ = [
     "require_space_action",
     "require_space_creation_permission",
     "resolve_access_context",
+    "resolve_entry_ref",
+    "resolve_org_role",
+    "resolve_provisioned_access",
+    "restore_entries",
     "restore_entry",
+    "restore_key_recovery_codes",
+    "restore_object_version",
+    "restore_space_to_timestamp",
+    "resume_runtime",
+    "revoke_author_signing_key",
+    "rewrite_asset_links",
+    "run_sql",
     "revoke_member",
     "revoke_service_account_key",
     "rotate_service_account_key",
+    "runtime_metrics",
     "save_asset",
+    "save_asset_from_url",
+    "save_oidc_config",
     "search_entries",
+    "search_entries_json",
+    "set_entry_acl",
+    "set_entry_metadata",
+    "set_global_feature_flag",
+    "set_org_member",
+    "set_sql_share",
+    "set_space_feature_flag",
+    "shutdown_runtime",
+    "snooze_reminder",
     "sql_completions",
+    "sync_pull",
+    "sync_push",
     "test_storage_connection",
+    "toggle_task",
+    "transition_entry_state",
+    "undo_replace",
+    "unsnooze_reminder",
+    "unwatch",
+    "update_collection",
+    "update_dashboard",
     "update_entry",
     "update_entry_index",
+    "update_form_view",
     "update_member_role",
+    "update_org",
     "update_sql",
     "upsert_form",
+    "vacuum_space",
     "validate_properties",
+    "verify_request_signature",
+    "verify_revision_author",
+    "verify_unique_index",
+    "verify_webhook_signature",
+    "warmup",
+    "watch",
 ]
@@ -176,3 +176,179 @@ def auth_capabilities_snapshot_core(
     *args: object,
     **kwargs: object,
 ) -> dict[str, object]: ...
+def introspect_token_core(
+    *args: object,
+    **kwargs: object,
+) -> dict[str, object]: ...
+def list_expiring_keys_core(
+    *args: object,
+    **kwargs: object,
+) -> list[dict[str, object]]: ...
+def list_expiring_keys_stored(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[list[dict[str, object]]]: ...
+def export_audit_events_otlp(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def register_author_signing_key(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def list_author_signing_keys(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[list[dict[str, object]]]: ...
+def revoke_author_signing_key(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def get_revision_signing_payload(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def attach_revision_signature(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def verify_revision_author(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def list_spaces_detailed(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[list[dict[str, object]]]: ...
+def list_global_feature_flags(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, bool]]: ...
+def set_global_feature_flag(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, bool]]: ...
+def set_space_feature_flag(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, bool]]: ...
+def list_space_feature_flags(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[list[dict[str, object]]]: ...
+def is_feature_enabled(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[bool]: ...
+def create_entries(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[list[dict[str, object]]]: ...
+def delete_entries(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def restore_entries(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def diff_entry_revisions(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def verify_unique_index(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def transition_entry_state(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def get_entry_state_history(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def list_deleted_entries(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def purge_entry(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def purge_deleted_older_than(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def list_due_reminders(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def snooze_reminder(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def unsnooze_reminder(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def dispatch_due_reminders(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def create_form_view(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def update_form_view(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def get_form_view(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def list_form_views(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def get_default_form_view(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def delete_form_view(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def list_changes_since(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def get_entry_head(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def get_entry_acl(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object] | None]: ...
+def set_entry_acl(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def clear_entry_acl(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[None]: ...
+def run_sql(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[list[dict[str, object]]]: ...
+def get_sql_share(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
+def set_sql_share(
+    *args: object,
+    **kwargs: object,
+) -> Awaitable[dict[str, object]]: ...
@@ -62,8 +62,13 @@ async def append_audit_event(
     storage_config: dict[str, str],
     space_id: str,
     payload: AuditEventInput,
+    validation_mode: str | None = None,
 ) -> dict[str, Any]:
-    """Append a tamper-evident audit event to the space's JSONL audit log file."""
+    """Append a tamper-evident audit event to the space's JSONL audit log file.
+
+    ``validation_mode`` is ``"strict"`` or ``"permissive"``; when omitted the
+    ``UGOITE_AUDIT_VALIDATION`` environment variable decides.
+    """
     action = payload.action.strip()
     if not action:
         msg = "audit action must not be empty"
@@ -89,9 +94,15 @@ async def append_audit_event(
         space_id,
         json.dumps(event_payload, separators=(",", ":"), sort_keys=True),
         _retention_limit(),
+        validation_mode,
     )
 
 
+def list_audit_actions() -> list[dict[str, Any]]:
+    """Return the known audit actions with their target type and required metadata."""
+    return cast("list[dict[str, Any]]", _core_any.list_audit_actions_py())
+
+
 async def list_audit_events(
     storage_config: dict[str, str],
     space_id: str,
@@ -189,6 +189,20 @@ class AuthManager:
             ),
         )
 
+    def introspect_token(self, token: str) -> dict[str, object]:
+        """Report claims, key status and validity of a token without authenticating."""
+        raw = _core.introspect_token_core(
+            token,
+            bearer_tokens_json=os.environ.get("UGOITE_AUTH_BEARER_TOKENS_JSON"),
+            api_keys_json=os.environ.get("UGOITE_AUTH_API_KEYS_JSON"),
+            bearer_secrets=os.environ.get("UGOITE_AUTH_BEARER_SECRETS"),
+            active_kids=os.environ.get("UGOITE_AUTH_BEARER_ACTIVE_KIDS"),
+            revoked_key_ids=os.environ.get("UGOITE_AUTH_REVOKED_KEY_IDS"),
+            bootstrap_token=self.bootstrap_token,
+            bootstrap_user_id=self.bootstrap_user_id,
+        )
+        return _as_object_dict(raw) or {}
+
 
 def _token_fingerprint(token: str) -> str:
     return hashlib.sha256(token.encode("utf-8")).hexdigest()[:12]
@@ -389,6 +403,23 @@ def authenticate_headers(headers: dict[str, str] | object) -> RequestIdentity:
     return get_auth_manager().authenticate_headers(headers)
 
 
+def introspect_token(token: str) -> dict[str, object]:
+    """Inspect a bearer token or API key for admin debugging views."""
+    return get_auth_manager().introspect_token(token)
+
+
+def list_expiring_keys(window_seconds: float) -> list[dict[str, object]]:
+    """List configured credentials expiring within the window, soonest first."""
+    raw = _core.list_expiring_keys_core(
+        float(window_seconds),
+        bearer_tokens_json=os.environ.get("UGOITE_AUTH_BEARER_TOKENS_JSON"),
+        api_keys_json=os.environ.get("UGOITE_AUTH_API_KEYS_JSON"),
+        revoked_key_ids=os.environ.get("UGOITE_AUTH_REVOKED_KEY_IDS"),
+    )
+    if not isinstance(raw, list):
+        return []
+    return [item for item in raw if isinstance(item, dict)]
+
 async def authenticate_headers_for_space(
     storage_config: dict[str, str],
     space_id: str,
@@ -428,6 +459,8 @@ async def authenticate_headers_for_space(
             _raise_auth("missing_credentials", "Missing API key")
         if "revoked" in message:
             _raise_auth("revoked_key", "API key has been revoked")
+        if "expired" in message:
+            _raise_auth("expired_key", "API key has expired")
         _raise_auth("invalid_credentials", "Invalid API key")
 
     return RequestIdentity(
@@ -31,66 +31,22 @@ ActionName = Literal[
 ]
 
 _VALID_ROLES: set[str] = {"owner", "admin", "editor", "viewer", "service"}
+# Roles ordered by privilege, for cascading organization roles into spaces.
+_ROLE_RANKS: tuple[RoleName, ...] = ("viewer", "service", "editor", "admin", "owner")
 
-_ROLE_PERMISSIONS: dict[RoleName, set[ActionName]] = {
-    "owner": {
-        "space_list",
-        "space_read",
-        "space_admin",
-        "entry_read",
-        "entry_write",
-        "form_read",
-        "form_write",
-        "asset_read",
-        "asset_write",
-        "sql_read",
-        "sql_write",
-    },
-    "admin": {
-        "space_list",
-        "space_read",
-        "space_admin",
-        "entry_read",
-        "entry_write",
-        "form_read",
-        "form_write",
-        "asset_read",
-        "asset_write",
-        "sql_read",
-        "sql_write",
-    },
-    "editor": {
-        "space_list",
-        "space_read",
-        "entry_read",
-        "entry_write",
-        "form_read",
-        "form_write",
-        "asset_read",
-        "asset_write",
-        "sql_read",
-        "sql_write",
-    },
-    "viewer": {
-        "space_list",
-        "space_read",
-        "entry_read",
-        "form_read",
-        "asset_read",
-        "sql_read",
-    },
-    "service": {
-        "space_list",
-        "space_read",
-        "entry_read",
-        "entry_write",
-        "form_read",
-        "asset_read",
-        "asset_write",
-        "sql_read",
-        "sql_write",
-    },
-}
+
+def _role_permissions_from_core() -> dict[RoleName, set[ActionName]]:
+    snapshot = cast("dict[str, Any]", _core_any.authz_matrix_snapshot_core())
+    roles = cast("dict[str, list[str]]", snapshot.get("roles", {}))
+    return {
+        cast("RoleName", role): {cast("ActionName", action) for action in actions}
+        for role, actions in roles.items()
+        if role in _VALID_ROLES
+    }
+
+
+# Role rules live in core so enforcement and the permission matrix agree.
+_ROLE_PERMISSIONS: dict[RoleName, set[ActionName]] = _role_permissions_from_core()
 
 
 @dataclass(frozen=True)
@@ -120,6 +76,14 @@ def _normalized_role(raw: object, fallback: RoleName) -> RoleName:
     return fallback
 
 
+def _higher_role(current: RoleName | None, other: RoleName) -> RoleName:
+    if current is None:
+        return other
+    if _ROLE_RANKS.index(other) > _ROLE_RANKS.index(current):
+        return other
+    return current
+
+
 def _default_user_role() -> RoleName:
     return _normalized_role(os.environ.get("UGOITE_AUTHZ_DEFAULT_USER_ROLE"), "editor")
 
@@ -189,6 +153,7 @@ def _groups_from_space_meta(
 def _resolve_role(
     space_meta: dict[str, Any],
     identity: RequestIdentity,
+    group_role: RoleName | None = None,
 ) -> RoleName | None:
     settings = space_meta.get("settings")
     settings_map = settings if isinstance(settings, dict) else {}
@@ -243,6 +208,9 @@ def _resolve_role(
     if isinstance(admin_user_ids, list) and identity.user_id in admin_user_ids:
         return "admin"
 
+    if group_role is not None:
+        return group_role
+
     if membership_configured:
         return None
 
@@ -257,7 +225,35 @@ async def resolve_access_context(
     """Resolve role/group context for a principal in a space."""
     space_meta_obj = await _core_any.get_space(storage_config, space_id)
     space_meta = cast("dict[str, Any]", space_meta_obj)
-    role = _resolve_role(space_meta, identity)
+    provisioned: dict[str, Any] | None = None
+    if identity.principal_type != "service":
+        provisioned = cast(
+            "dict[str, Any] | None",
+            await _core_any.resolve_provisioned_access(
+                storage_config,
+                space_id,
+                identity.user_id,
+            ),
+        )
+    if provisioned is not None and provisioned.get("active") is False:
+        _deny(
+            "space_read",
+            f"Principal '{identity.user_id}' has been deactivated by the directory.",
+        )
+    group_role = provisioned.get("role") if provisioned is not None else None
+    role = _resolve_role(
+        space_meta,
+        identity,
+        _normalized_role(group_role, "viewer") if group_role is not None else None,
+    )
+    if identity.principal_type != "service":
+        org_role = await _core_any.resolve_org_role(
+            storage_config,
+            space_id,
+            identity.user_id,
+        )
+        if org_role is not None:
+            role = _higher_role(role, _normalized_role(org_role, "viewer"))
     if role is None:
         _deny(
             "space_read",
@@ -267,6 +263,10 @@ async def resolve_access_context(
             ),
         )
     groups = _groups_from_space_meta(space_id, identity.user_id, space_meta)
+    if provisioned is not None:
+        groups = groups | frozenset(
+            item for item in provisioned.get("groups", []) if isinstance(item, str)
+        )
     settings = space_meta.get("settings")
     settings_map = settings if isinstance(settings, dict) else {}
     form_acls_obj = settings_map.get("form_acls")
@@ -320,6 +320,32 @@ async def require_space_action(
     return access
 
 
+async def authz_matrix_snapshot(
+    storage_config: dict[str, str],
+    space_id: str,
+    identity: RequestIdentity,
+) -> dict[str, Any]:
+    """List every operation with its required role and scopes for ``identity``.
+
+    Each operation reports ``allowed`` for the principal's role and scopes in
+    the space, and ``form_acl_applies`` when a form ACL may still refuse it.
+    """
+    role: RoleName | None
+    try:
+        role = (await resolve_access_context(storage_config, space_id, identity)).role
+    except AuthorizationError:
+        role = None
+    identity_json = json.dumps(
+        {
+            "role": role,
+            "scopes": sorted(identity.scopes),
+            "scope_enforced": identity.scope_enforced,
+        },
+    )
+    snapshot = _core_any.authz_matrix_snapshot_core(identity_json)
+    return cast("dict[str, Any]", snapshot)
+
+
 async def require_space_creation_permission(
     storage_config: dict[str, str],
     identity: RequestIdentity,
@@ -572,6 +598,39 @@ async def require_entry_write(
     return await require_form_write(storage_config, space_id, identity, form_name)
 
 
+async def read_entry_ref(
+    storage_config: dict[str, str],
+    space_id: str,
+    identity: RequestIdentity,
+    reference: str,
+) -> dict[str, Any]:
+    """Dereference a possibly cross-space entry reference written in a space."""
+    resolved = cast(
+        "dict[str, Any]",
+        await _core_any.resolve_entry_ref(storage_config, space_id, reference),
+    )
+    target_space_id = str(resolved["space_id"])
+    access = await require_space_action(
+        storage_config,
+        target_space_id,
+        identity,
+        "entry_read",
+    )
+    entry = cast(
+        "dict[str, Any]",
+        await _core_any.dereference_entry_ref(
+            storage_config,
+            space_id,
+            reference,
+            identity.user_id,
+            sorted(access.groups),
+            access.role in {"owner", "admin"},
+        ),
+    )
+    await require_entry_read(storage_config, target_space_id, identity, entry)
+    return entry
+
+
 async def filter_readable_entries(
     storage_config: dict[str, str],
     space_id: str,
@@ -594,8 +653,10 @@ __all__ = [
     "ActionName",
     "AuthorizationError",
     "RoleName",
+    "authz_matrix_snapshot",
     "filter_readable_entries",
     "form_name_from_entry",
+    "read_entry_ref",
     "require_entry_read",
     "require_entry_revision_write",
     "require_entry_write",
@@ -0,0 +1,51 @@
+"""Lazily decoded JSON payloads returned by the ``*_json`` bindings."""
+
+from __future__ import annotations
+
+import json
+from importlib import import_module
+from typing import Any
+
+try:
+    _orjson: Any = import_module("orjson")
+except ImportError:  # pragma: no cover - orjson is optional
+    _orjson = None
+
+
+def loads_json(raw: bytes) -> Any:  # noqa: ANN401
+    """Decode JSON bytes, using orjson when it is installed."""
+    if _orjson is not None:
+        return _orjson.loads(raw)
+    return json.loads(raw)
+
+
+class JsonPayload:
+    """JSON bytes from the core that are only decoded on first access.
+
+    Callers that forward the result unchanged, such as HTTP handlers, can
+    send ``raw`` directly and never build Python objects for it.
+    """
+
+    __slots__ = ("_decoded", "_raw", "_value")
+
+    def __init__(self, raw: bytes) -> None:
+        """Wrap serialized JSON without decoding it."""
+        self._raw = raw
+        self._value: Any = None
+        self._decoded = False
+
+    @property
+    def raw(self) -> bytes:
+        """Serialized JSON exactly as produced by the core."""
+        return self._raw
+
+    def decode(self) -> Any:  # noqa: ANN401
+        """Decode the payload once and cache the result."""
+        if not self._decoded:
+            self._value = loads_json(self._raw)
+            self._decoded = True
+        return self._value
+
+    def __len__(self) -> int:
+        """Return the size of the serialized payload in bytes."""
+        return len(self._raw)
@@ -53,6 +53,7 @@ class CreateServiceAccountKeyInput:
     key_name: str
     created_by_user_id: str
     rotated_from: str | None = None
+    expires_at: str | None = None
 
 
 @dataclass(frozen=True)
@@ -63,6 +64,7 @@ class RotateServiceAccountKeyInput:
     key_id: str
     rotated_by_user_id: str
     key_name: str | None = None
+    expires_at: str | None = None
 
 
 @dataclass(frozen=True)
@@ -89,6 +91,35 @@ def _now_iso() -> str:
     return datetime.now(tz=UTC).isoformat().replace("+00:00", "Z")
 
 
+def _parse_timestamp(value: str) -> datetime | None:
+    try:
+        parsed = datetime.fromisoformat(value.strip().replace("Z", "+00:00"))
+    except ValueError:
+        return None
+    return parsed if parsed.tzinfo is not None else parsed.replace(tzinfo=UTC)
+
+
+def _normalize_expires_at(value: str | None) -> str | None:
+    if value is None or not value.strip():
+        return None
+    parsed = _parse_timestamp(value)
+    if parsed is None:
+        msg = "expires_at must be an ISO 8601 timestamp"
+        raise RuntimeError(msg)
+    if parsed <= datetime.now(tz=UTC):
+        msg = "expires_at must be in the future"
+        raise RuntimeError(msg)
+    return parsed.astimezone(UTC).isoformat().replace("+00:00", "Z")
+
+
+def _key_expired(key_obj: dict[str, Any]) -> bool:
+    expires_at = key_obj.get("expires_at")
+    if not isinstance(expires_at, str):
+        return False
+    parsed = _parse_timestamp(expires_at)
+    return parsed is not None and parsed < datetime.now(tz=UTC)
+
+
 async def _space_lock(space_id: str) -> asyncio.Lock:
     async with _space_locks_guard:
         existing = _space_locks.get(space_id)
@@ -166,6 +197,7 @@ def _key_public_view(key_obj: dict[str, Any]) -> dict[str, Any]:
         "created_at": key_obj.get("created_at"),
         "created_by_user_id": key_obj.get("created_by_user_id"),
         "revoked_at": key_obj.get("revoked_at"),
+        "expires_at": key_obj.get("expires_at"),
         "rotated_from": key_obj.get("rotated_from"),
         "last_used_at": key_obj.get("last_used_at"),
         "usage_count": key_obj.get("usage_count", 0),
@@ -295,6 +327,7 @@ async def create_service_account_key(
     if not created_by:
         msg = "created_by_user_id must not be empty"
         raise RuntimeError(msg)
+    expires_at = _normalize_expires_at(payload.expires_at)
 
     secret = _new_secret()
     secret_salt = secrets.token_urlsafe(16)
@@ -325,6 +358,7 @@ async def create_service_account_key(
             "created_at": _now_iso(),
             "created_by_user_id": created_by,
             "revoked_at": None,
+            "expires_at": expires_at,
             "rotated_from": payload.rotated_from,
             "last_used_at": None,
             "usage_count": 0,
@@ -349,7 +383,10 @@ async def create_service_account_key(
             outcome="success",
             target_type="service_account_key",
             target_id=key_id,
-            metadata={"service_account_id": service_account_id},
+            metadata={
+                "service_account_id": service_account_id,
+                "expires_at": expires_at or "",
+            },
         ),
     )
 
@@ -453,6 +490,7 @@ async def rotate_service_account_key(
             key_name=payload.key_name or f"rotated-{payload.key_id}",
             created_by_user_id=payload.rotated_by_user_id,
             rotated_from=payload.key_id,
+            expires_at=payload.expires_at,
         ),
     )
 
@@ -524,6 +562,9 @@ async def resolve_service_api_key(
                 if key_obj.get("revoked_at") is not None:
                     msg = "API key has been revoked"
                     raise RuntimeError(msg)
+                if _key_expired(key_obj):
+                    msg = "API key has expired"
+                    raise RuntimeError(msg)
 
                 key_obj["last_used_at"] = _now_iso()
                 usage_count_obj = key_obj.get("usage_count", 0)
@@ -591,6 +632,52 @@ async def resolve_service_api_key(
     return matched_result
 
 
+async def list_expiring_service_account_keys(
+    storage_config: dict[str, str],
+    space_id: str,
+    window_seconds: float,
+) -> list[dict[str, Any]]:
+    """List active keys of a space expiring within the window, soonest first."""
+    space_meta_obj = await _core_any.get_space(storage_config, space_id)
+    space_meta = cast("dict[str, Any]", space_meta_obj)
+    settings = _normalize_settings(space_meta)
+    accounts_obj = settings.get("service_accounts")
+    accounts = accounts_obj if isinstance(accounts_obj, dict) else {}
+
+    # Records are keyed by key id: core only needs owner and expiry, never
+    # the secret hash.
+    records: dict[str, dict[str, Any]] = {}
+    names: dict[str, Any] = {}
+    for service_account_id, account_obj in accounts.items():
+        if not isinstance(account_obj, dict):
+            continue
+        keys_obj = account_obj.get("keys")
+        keys = keys_obj if isinstance(keys_obj, dict) else {}
+        for key_id, key_obj in keys.items():
+            if not isinstance(key_obj, dict) or key_obj.get("revoked_at") is not None:
+                continue
+            records[key_id] = {
+                "user_id": account_obj.get("user_id")
+                or f"service:{space_id}:{service_account_id}",
+                "principal_type": "service",
+                "key_id": key_id,
+                "service_account_id": service_account_id,
+                "disabled": bool(account_obj.get("disabled", False)),
+                "expires_at": key_obj.get("expires_at"),
+            }
+            names[key_id] = key_obj.get("name")
+
+    expiring = _core_any.list_expiring_keys_core(
+        float(window_seconds),
+        api_keys_json=json.dumps(records, separators=(",", ":")),
+    )
+    return [
+        {**item, "name": names.get(item.get("key_id"))}
+        for item in expiring
+        if isinstance(item, dict)
+    ]
+
+
 __all__ = [
     "CreateServiceAccountInput",
     "CreateServiceAccountKeyInput",
@@ -599,6 +686,7 @@ __all__ = [
     "ServiceApiKeyAuthResult",
     "create_service_account",
     "create_service_account_key",
+    "list_expiring_service_account_keys",
     "list_service_accounts",
     "resolve_service_api_key",
     "revoke_service_account_key",
@@ -105,6 +105,7 @@ def build_sql_schema(
     return {
         "tables": tables,
         "keywords": list(rules.get("keywords", [])),
+        "functions": list(rules.get("functions", [])),
     }
 
 
@@ -198,6 +199,7 @@ def sql_completions(
     schema = build_sql_schema(forms, rules)
     tables = sorted(schema.get("tables", {}).keys())
     keywords = sorted(schema.get("keywords", []))
+    functions = sorted(schema.get("functions", []))
 
     table_match = re.search(r"\bfrom\b\s+([A-Za-z_][\w]*)", query, re.IGNORECASE)
     table_name = table_match.group(1) if table_match else None
@@ -212,6 +214,6 @@ def sql_completions(
         return tables
 
     if re.search(r"\b(where|and|or|order\s+by)\b\s*$", query, re.IGNORECASE):
-        return columns
+        return columns + functions
 
-    return sorted(set(tables + columns + keywords))
+    return sorted(set(tables + columns + keywords + functions))
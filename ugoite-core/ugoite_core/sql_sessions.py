@@ -9,7 +9,12 @@ from dataclasses import dataclass
 from typing import TYPE_CHECKING, Any, cast
 
 from . import _ugoite_core as _core
-from .authz import AuthorizationError, require_form_read, require_space_action
+from .authz import (
+    AuthorizationError,
+    require_form_read,
+    require_space_action,
+    resolve_access_context,
+)
 
 if TYPE_CHECKING:
     from .auth import RequestIdentity
@@ -53,18 +58,56 @@ async def _resolve_sql_read_scope(
     return readable_forms, include_untyped_entries
 
 
+async def _resolve_entry_viewer(
+    storage_config: dict[str, Any],
+    space_id: str,
+    identity: RequestIdentity,
+) -> dict[str, object]:
+    """Viewer arguments that make SQL rows honor per-entry ACLs."""
+    access = await resolve_access_context(storage_config, space_id, identity)
+    return {
+        "user_id": identity.user_id,
+        "groups": sorted(access.groups),
+        "is_space_admin": access.role in {"owner", "admin"},
+    }
+
+
+async def get_sql_session_columns_for_identity(
+    storage_config: dict[str, Any],
+    space_id: str,
+    identity: RequestIdentity,
+    session_id: str,
+) -> list[dict[str, object]]:
+    """REQ-API-008: typed SQL result columns within the caller's readable forms."""
+    readable_forms, _include_untyped_entries = await _resolve_sql_read_scope(
+        storage_config,
+        space_id,
+        identity,
+    )
+    return cast(
+        "list[dict[str, object]]",
+        await _core_any.get_sql_session_columns(
+            storage_config,
+            space_id,
+            session_id,
+            readable_forms,
+        ),
+    )
+
+
 async def get_sql_session_count_for_identity(
     storage_config: dict[str, Any],
     space_id: str,
     identity: RequestIdentity,
     session_id: str,
 ) -> int:
-    """REQ-API-008: count SQL rows using the caller's readable entry scope."""
+    """REQ-API-008/REQ-IDX-015: count SQL rows the caller may read."""
     readable_forms, include_untyped_entries = await _resolve_sql_read_scope(
         storage_config,
         space_id,
         identity,
     )
+    viewer = await _resolve_entry_viewer(storage_config, space_id, identity)
     return cast(
         "int",
         await _core_any.get_sql_session_count_scoped(
@@ -73,6 +116,7 @@ async def get_sql_session_count_for_identity(
             session_id,
             readable_forms,
             include_untyped_entries,
+            **viewer,
         ),
     )
 
@@ -84,12 +128,13 @@ async def get_sql_session_rows_for_identity(
     session_id: str,
     page: SqlSessionPageInput,
 ) -> dict[str, object]:
-    """REQ-API-008: page SQL rows using the caller's readable entry scope."""
+    """REQ-API-008/REQ-IDX-015: page SQL rows the caller may read."""
     readable_forms, include_untyped_entries = await _resolve_sql_read_scope(
         storage_config,
         space_id,
         identity,
     )
+    viewer = await _resolve_entry_viewer(storage_config, space_id, identity)
     return cast(
         "dict[str, object]",
         await _core_any.get_sql_session_rows_scoped(
@@ -100,6 +145,7 @@ async def get_sql_session_rows_for_identity(
             page.limit,
             readable_forms,
             include_untyped_entries,
+            **viewer,
         ),
     )
 
@@ -110,12 +156,13 @@ async def get_sql_session_rows_all_for_identity(
     identity: RequestIdentity,
     session_id: str,
 ) -> list[dict[str, object]]:
-    """REQ-API-008: read all SQL rows using the caller's readable entry scope."""
+    """REQ-API-008/REQ-IDX-015: read all SQL rows the caller may read."""
     readable_forms, include_untyped_entries = await _resolve_sql_read_scope(
         storage_config,
         space_id,
         identity,
     )
+    viewer = await _resolve_entry_viewer(storage_config, space_id, identity)
     return cast(
         "list[dict[str, object]]",
         await _core_any.get_sql_session_rows_all_scoped(
@@ -124,12 +171,14 @@ async def get_sql_session_rows_all_for_identity(
             session_id,
             readable_forms,
             include_untyped_entries,
+            **viewer,
         ),
     )
 
 
 __all__ = [
     "SqlSessionPageInput",
+    "get_sql_session_columns_for_identity",
     "get_sql_session_count_for_identity",
     "get_sql_session_rows_all_for_identity",
     "get_sql_session_rows_for_identity",